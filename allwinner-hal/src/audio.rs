@@ -0,0 +1,447 @@
+//! Audio clock and sample rate negotiation.
+//!
+//! Audio peripherals derive their sample rate by dividing a module clock, which comes from
+//! one of two audio PLLs: 24.576 MHz from audio PLL 1 serves rates of the 48-kHz family,
+//! and 22.5792 MHz from audio PLL 0 serves rates of the 44.1-kHz family. Each peripheral
+//! then divides its module clock by a fixed set of ratios.
+//!
+//! [`negotiate`] picks the clock family and divider giving the rate closest to a request,
+//! and reports the exact rate achieved, so that drivers apply PLL, module clock and
+//! divider settings from a single consistent choice. Peripheral drivers expose this as
+//! `negotiate_*` functions.
+//!
+//! FIFO interrupts of audio peripherals are reported as [`Event`]s: each driver records
+//! events raised in its `on_interrupt` entry point into [`Events`], which calls a
+//! registered [`Handler`], wakes a task waiting for the event, and counts underruns and
+//! overruns. Applications refill or drain buffers just in time and track xruns the same
+//! way on every audio peripheral.
+//!
+//! Streams of all audio peripherals implement [`Source`] or [`Sink`], handing out buffers
+//! of interleaved [`Sample`]s together with channel count and sample rate, so that
+//! application mixers target one interface whatever the physical input or output.
+//!
+//! Sample format is 16-bit signed PCM on all audio peripherals.
+
+use crate::ccu::{self, AudioCodecClock, AudioCodecClockSource, PeriFactorN};
+use atomic_waker::AtomicWaker;
+use core::{
+    future::poll_fn,
+    sync::atomic::{AtomicPtr, AtomicU32, Ordering},
+    task::Poll,
+};
+use embedded_time::rate::Hertz;
+
+/// Audio clock family, selecting audio PLL and module clock.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Family {
+    /// 24.576-MHz module clock from audio PLL 1, for 48 kHz and related rates.
+    Hz48000,
+    /// 22.5792-MHz module clock from audio PLL 0, for 44.1 kHz and related rates.
+    Hz44100,
+}
+
+impl Family {
+    /// Get module clock frequency of this family.
+    #[inline]
+    pub const fn module_clock(self) -> Hertz {
+        match self {
+            Family::Hz48000 => Hertz(24_576_000),
+            Family::Hz44100 => Hertz(22_579_200),
+        }
+    }
+    /// Get audio module clock register value producing module clock of this family.
+    #[inline]
+    pub fn clock(self) -> AudioCodecClock {
+        let clock = AudioCodecClock::default().set_factor_n(PeriFactorN::N1);
+        match self {
+            // 614.4 MHz / 25 = 24.576 MHz.
+            Family::Hz48000 => clock
+                .set_clock_source(AudioCodecClockSource::PllAudio1Div5)
+                .set_factor_m(24),
+            Family::Hz44100 => clock
+                .set_clock_source(AudioCodecClockSource::PllAudio0)
+                .set_factor_m(0),
+        }
+        .unmask_clock()
+    }
+    /// Enable audio PLL of this family, if it is not yet.
+    ///
+    /// # Safety
+    ///
+    /// No peripheral should be clocked from the PLL while it is disabled.
+    #[inline]
+    pub unsafe fn enable_pll(self, ccu: &ccu::RegisterBlock) {
+        unsafe {
+            match self {
+                Family::Hz48000 => ccu::enable_pll_audio1(ccu),
+                Family::Hz44100 => ccu::enable_pll_audio0(ccu),
+            }
+        }
+    }
+}
+
+/// Sample rate setting chosen by [`negotiate`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Negotiated<R> {
+    /// Clock family to set module clock from.
+    pub family: Family,
+    /// Peripheral rate setting.
+    pub rate: R,
+    /// Exact sample rate achieved, rounded down to whole hertz.
+    pub sample_rate: Hertz,
+}
+
+/// Choose clock family and one of `rates` giving sample rate closest to `requested`.
+///
+/// `divider` gives module clock cycles per sample of each rate setting. Exact matches are
+/// preferred, then the 48-kHz family on ties.
+///
+/// # Panics
+///
+/// Panics if `rates` is empty.
+#[inline]
+pub fn negotiate<R: Copy>(
+    requested: Hertz,
+    rates: &[R],
+    divider: impl Fn(R) -> u32,
+) -> Negotiated<R> {
+    let mut best: Option<(Negotiated<R>, u32)> = None;
+    for family in [Family::Hz48000, Family::Hz44100] {
+        let clock = family.module_clock().0;
+        for &rate in rates {
+            let divider = divider(rate);
+            // |clock / divider - requested|, without rounding clock / divider first
+            let error = (clock as u64).abs_diff(requested.0 as u64 * divider as u64);
+            let error = (error / divider as u64) as u32;
+            if best.is_none_or(|(_, best_error)| error < best_error) {
+                let sample_rate = Hertz(clock / divider);
+                best = Some((
+                    Negotiated {
+                        family,
+                        rate,
+                        sample_rate,
+                    },
+                    error,
+                ));
+            }
+        }
+    }
+    best.expect("at least one rate setting is needed").0
+}
+
+/// Standard sample rates recognized by [`identify_sample_rate`], in hertz.
+pub const STANDARD_RATES: [u32; 13] = [
+    8_000, 11_025, 12_000, 16_000, 22_050, 24_000, 32_000, 44_100, 48_000, 88_200, 96_000, 176_400,
+    192_000,
+];
+
+/// Identify standard sample rate of a frame clock measured from outside, such as LRCK
+/// driven by a clock master.
+///
+/// `periods` frame clock periods took `ticks` of a timer running at `tick_rate`. Returns
+/// the standard rate within 1% of measured rate, if any, to be checked against configured
+/// rate of a clock slave. Longer measurements are more precise; 1% needs at least 100
+/// timer ticks.
+#[inline]
+pub fn identify_sample_rate(periods: u32, ticks: u32, tick_rate: Hertz) -> Option<Hertz> {
+    if ticks == 0 {
+        return None;
+    }
+    let measured = periods as u64 * tick_rate.0 as u64 / ticks as u64;
+    STANDARD_RATES
+        .iter()
+        .find(|&&rate| measured.abs_diff(rate as u64) * 100 <= rate as u64)
+        .map(|&rate| Hertz(rate))
+}
+
+/// PCM sample of an audio channel.
+pub trait Sample: Copy + Default {
+    /// Number of significant bits.
+    const BITS: u32;
+
+    /// Convert to a 32-bit sample of the same level.
+    fn to_i32(self) -> i32;
+    /// Convert from a 32-bit sample of the same level, dropping low bits.
+    fn from_i32(val: i32) -> Self;
+}
+
+impl Sample for i16 {
+    const BITS: u32 = 16;
+
+    #[inline]
+    fn to_i32(self) -> i32 {
+        (self as i32) << 16
+    }
+    #[inline]
+    fn from_i32(val: i32) -> Self {
+        (val >> 16) as i16
+    }
+}
+
+impl Sample for i32 {
+    const BITS: u32 = 32;
+
+    #[inline]
+    fn to_i32(self) -> i32 {
+        self
+    }
+    #[inline]
+    fn from_i32(val: i32) -> Self {
+        val
+    }
+}
+
+/// Audio frame holding one sample of each channel.
+pub trait Frame: Copy {
+    /// Sample type of each channel.
+    type Sample: Sample;
+    /// Number of channels.
+    const CHANNELS: usize;
+
+    /// Get samples in channel order.
+    fn samples(&self) -> &[Self::Sample];
+    /// Get mutable samples in channel order.
+    fn samples_mut(&mut self) -> &mut [Self::Sample];
+}
+
+impl<S: Sample, const C: usize> Frame for [S; C] {
+    type Sample = S;
+    const CHANNELS: usize = C;
+
+    #[inline]
+    fn samples(&self) -> &[S] {
+        self
+    }
+    #[inline]
+    fn samples_mut(&mut self) -> &mut [S] {
+        self
+    }
+}
+
+/// Stream of audio frames between memory and a peripheral.
+///
+/// Buffers hold whole frames of interleaved samples, `channels` samples each.
+pub trait Stream {
+    /// Sample type of each channel.
+    type Sample: Sample;
+
+    /// Get number of channels in each frame.
+    fn channels(&self) -> usize;
+    /// Get exact sample rate of the peripheral when the stream started.
+    fn sample_rate(&self) -> Hertz;
+}
+
+/// Stream of frames captured by an audio input.
+pub trait Source: Stream {
+    /// Get next buffer of captured frames, if any.
+    fn read_buffer(&mut self) -> Option<&[Self::Sample]>;
+}
+
+/// Stream of frames played by an audio output.
+pub trait Sink: Stream {
+    /// Get next buffer to be filled with frames, if any.
+    fn write_buffer(&mut self) -> Option<&mut [Self::Sample]>;
+    /// Hand buffer last returned by [`Sink::write_buffer`] over to the output.
+    ///
+    /// Streams sending their buffers endlessly in turn need no hand-over, and do nothing.
+    fn submit_buffer(&mut self);
+}
+
+/// FIFO event raised by an audio peripheral interrupt.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Event {
+    /// Transmit FIFO fell to its trigger level, with room for more samples.
+    Empty = 0,
+    /// Transmit FIFO ran out of samples, sending silence or repeating samples.
+    Underrun = 1,
+    /// Receive FIFO reached its trigger level, holding samples to be read.
+    Available = 2,
+    /// Receive FIFO overflowed, losing samples.
+    Overrun = 3,
+}
+
+impl Event {
+    /// If this event is an underrun or overrun, losing or repeating samples.
+    #[inline]
+    pub const fn is_xrun(self) -> bool {
+        matches!(self, Event::Underrun | Event::Overrun)
+    }
+    /// If this event stays raised until FIFO is refilled or drained.
+    ///
+    /// Drivers disable interrupts of these events once raised; they are enabled again
+    /// when waiting for the next one.
+    #[inline]
+    pub const fn is_level(self) -> bool {
+        matches!(self, Event::Empty | Event::Available)
+    }
+}
+
+/// Audio interrupt handler, called from interrupt context with each raised event.
+pub type Handler = fn(event: Event);
+
+/// Interrupt events of an audio peripheral, shared by its interrupt entry point and tasks.
+pub struct Events {
+    handler: AtomicPtr<()>,
+    waker: AtomicWaker,
+    pending: AtomicU32,
+    counts: [AtomicU32; 4],
+}
+
+impl Events {
+    /// Create an event record with no handler and nothing raised.
+    #[inline]
+    pub const fn new() -> Self {
+        Self {
+            handler: AtomicPtr::new(core::ptr::null_mut()),
+            waker: AtomicWaker::new(),
+            pending: AtomicU32::new(0),
+            counts: [const { AtomicU32::new(0) }; 4],
+        }
+    }
+    /// Register handler called on each raised event, or remove it with `None`.
+    #[inline]
+    pub fn set_handler(&self, handler: Option<Handler>) {
+        let handler = handler.map_or(core::ptr::null_mut(), |h| h as *mut ());
+        self.handler.store(handler, Ordering::Release);
+    }
+    /// Record raised `event`, call registered handler and wake waiting task.
+    #[inline]
+    pub fn raise(&self, event: Event) {
+        self.pending.fetch_or(1 << event as u32, Ordering::AcqRel);
+        self.counts[event as usize].fetch_add(1, Ordering::Relaxed);
+        let handler = self.handler.load(Ordering::Acquire);
+        if !handler.is_null() {
+            // note(unsafe): only `Handler` function pointers are stored in `handler`
+            let handler: Handler = unsafe { core::mem::transmute(handler) };
+            handler(event);
+        }
+        self.waker.wake();
+    }
+    /// Take `event`, returning whether it was raised since last taken.
+    #[inline]
+    pub fn take(&self, event: Event) -> bool {
+        let bit = 1 << event as u32;
+        self.pending.fetch_and(!bit, Ordering::AcqRel) & bit != 0
+    }
+    /// Wait until `event` is raised, then take it.
+    #[inline]
+    pub async fn wait(&self, event: Event) {
+        poll_fn(|cx| {
+            self.waker.register(cx.waker());
+            if self.take(event) {
+                Poll::Ready(())
+            } else {
+                Poll::Pending
+            }
+        })
+        .await
+    }
+    /// Get number of times `event` has been raised.
+    #[inline]
+    pub fn count(&self, event: Event) -> u32 {
+        self.counts[event as usize].load(Ordering::Relaxed)
+    }
+    /// Get number of underruns and overruns raised.
+    #[inline]
+    pub fn xruns(&self) -> u32 {
+        self.count(Event::Underrun)
+            .wrapping_add(self.count(Event::Overrun))
+    }
+}
+
+impl Default for Events {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Event, Events, Family, Frame, Sample, identify_sample_rate, negotiate};
+    use embedded_time::rate::Hertz;
+
+    #[test]
+    fn function_negotiate() {
+        let rates = [1u32, 2, 4];
+        let divider = |rate: u32| 512 * rate;
+
+        let result = negotiate(Hertz(48_000), &rates, divider);
+        assert_eq!(result.family, Family::Hz48000);
+        assert_eq!(result.rate, 1);
+        assert_eq!(result.sample_rate, Hertz(48_000u32));
+
+        let result = negotiate(Hertz(22_050), &rates, divider);
+        assert_eq!(result.family, Family::Hz44100);
+        assert_eq!(result.rate, 2);
+        assert_eq!(result.sample_rate, Hertz(22_050u32));
+
+        let result = negotiate(Hertz(11_000), &rates, divider);
+        assert_eq!(result.family, Family::Hz44100);
+        assert_eq!(result.rate, 4);
+        assert_eq!(result.sample_rate, Hertz(11_025u32));
+
+        let result = negotiate(Hertz(100_000), &rates, divider);
+        assert_eq!(result.family, Family::Hz48000);
+        assert_eq!(result.rate, 1);
+    }
+
+    #[test]
+    fn struct_family_functions() {
+        let clock = Family::Hz48000.clock();
+        assert!(clock.is_clock_unmasked());
+        assert_eq!(clock.factor_m(), 24);
+        let clock = Family::Hz44100.clock();
+        assert_eq!(clock.factor_m(), 0);
+        assert_eq!(Family::Hz44100.module_clock(), Hertz(22_579_200u32));
+    }
+
+    #[test]
+    fn struct_events_functions() {
+        let events = Events::new();
+        assert!(!events.take(Event::Empty));
+
+        events.raise(Event::Empty);
+        events.raise(Event::Underrun);
+        events.raise(Event::Underrun);
+        assert!(events.take(Event::Empty));
+        assert!(!events.take(Event::Empty));
+        assert!(!events.take(Event::Overrun));
+        assert_eq!(events.count(Event::Empty), 1);
+        assert_eq!(events.count(Event::Underrun), 2);
+        assert_eq!(events.xruns(), 2);
+
+        assert!(Event::Overrun.is_xrun());
+        assert!(!Event::Available.is_xrun());
+        assert!(Event::Available.is_level());
+    }
+
+    #[test]
+    fn trait_sample_frame_functions() {
+        assert_eq!(0x1234i16.to_i32(), 0x1234_0000);
+        assert_eq!(i16::from_i32(-0x0001_8000), -2);
+        assert_eq!(i32::from_i32(-5).to_i32(), -5);
+
+        let mut frame = [1i16, 2];
+        assert_eq!(<[i16; 2] as Frame>::CHANNELS, 2);
+        frame.samples_mut()[1] = 3;
+        assert_eq!(frame.samples(), &[1, 3]);
+    }
+
+    #[test]
+    fn function_identify_sample_rate() {
+        let tick_rate = Hertz(24_000_000u32);
+        // 441 periods of 44.1 kHz take 10 ms.
+        assert_eq!(
+            identify_sample_rate(441, 240_000, tick_rate),
+            Some(Hertz(44_100u32))
+        );
+        assert_eq!(
+            identify_sample_rate(480, 240_500, tick_rate),
+            Some(Hertz(48_000u32))
+        );
+        assert_eq!(identify_sample_rate(460, 240_000, tick_rate), None);
+        assert_eq!(identify_sample_rate(0, 240_000, tick_rate), None);
+        assert_eq!(identify_sample_rate(480, 0, tick_rate), None);
+    }
+}
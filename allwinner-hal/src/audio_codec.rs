@@ -0,0 +1,591 @@
+//! Internal audio codec.
+//!
+//! The codec has a stereo DAC driving line and headphone outputs, and three ADC channels,
+//! each taking microphone, line or FM input through an analog front end with programmable
+//! gain, and a bias supply for electret microphones.
+//!
+//! Codec clocks are 24.576 MHz from audio PLL 1 by default, giving sample rates of the
+//! 48-kHz family; [`AudioCodec::negotiate_dac_rate`] and [`AudioCodec::negotiate_adc_rate`]
+//! switch to the 44.1-kHz family when a requested rate needs it. [`Playback`] streams PCM
+//! frames from a pair of buffers to the DAC over DMA, and [`Capture`] streams frames of
+//! ADC1 and ADC2 into a pair of buffers.
+//!
+//! [`AudioCodec::self_test`] checks both paths at once by looping DAC output back into the
+//! ADCs, and [`fill_sine`] generates a test tone for playback buffers.
+//!
+//! DAC FIFO raises [`Event::Empty`] and [`Event::Underrun`], and ADC FIFO raises
+//! [`Event::Available`] and [`Event::Overrun`], through [`on_interrupt`].
+
+#[cfg(feature = "d1")]
+mod capture;
+#[cfg(feature = "d1")]
+mod playback;
+mod register;
+mod self_test;
+#[cfg(feature = "d1")]
+pub use capture::*;
+#[cfg(feature = "d1")]
+pub use playback::*;
+pub use register::*;
+pub use self_test::*;
+
+use crate::audio::{self, Event, Events, Family, Handler, Negotiated};
+use crate::ccu::{self, ClockGate};
+use embedded_time::rate::Hertz;
+
+/// Stereo PCM frame of 16-bit left and right samples.
+pub type Frame = [i16; 2];
+
+static EVENTS: Events = Events::new();
+
+/// Audio codec interrupt entry point.
+///
+/// Call this function from audio codec interrupt handler. Each enabled FIFO event pending
+/// is cleared and raised to the registered handler and waiting task; FIFO empty and data
+/// available interrupts are disabled once raised.
+#[inline]
+pub fn on_interrupt(codec: &RegisterBlock) {
+    let control = codec.dac_fifo_control.read();
+    let status = codec.dac_fifo_status.read();
+    if control.is_empty_interrupt_enabled() && status.is_empty() {
+        unsafe {
+            codec
+                .dac_fifo_control
+                .modify(|val| val.disable_empty_interrupt());
+            codec
+                .dac_fifo_status
+                .write(DacFifoStatus::default().clear_empty());
+        }
+        EVENTS.raise(Event::Empty);
+    }
+    if control.is_underrun_interrupt_enabled() && status.has_underrun() {
+        unsafe {
+            codec
+                .dac_fifo_status
+                .write(DacFifoStatus::default().clear_underrun())
+        };
+        EVENTS.raise(Event::Underrun);
+    }
+    let control = codec.adc_fifo_control.read();
+    let status = codec.adc_fifo_status.read();
+    if control.is_data_interrupt_enabled() && status.is_data_available() {
+        unsafe {
+            codec
+                .adc_fifo_control
+                .modify(|val| val.disable_data_interrupt());
+            codec
+                .adc_fifo_status
+                .write(AdcFifoStatus::default().clear_data_available());
+        }
+        EVENTS.raise(Event::Available);
+    }
+    if control.is_overrun_interrupt_enabled() && status.has_overrun() {
+        unsafe {
+            codec
+                .adc_fifo_status
+                .write(AdcFifoStatus::default().clear_overrun())
+        };
+        EVENTS.raise(Event::Overrun);
+    }
+}
+
+/// Analog output of audio codec.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Output {
+    /// Differential or single-ended line output.
+    LineOut,
+    /// Headphone amplifier output.
+    Headphone,
+}
+
+/// ADC channel of audio codec.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum AdcChannel {
+    /// ADC1, left channel of line and FM inputs.
+    Adc1,
+    /// ADC2, right channel of line and FM inputs.
+    Adc2,
+    /// ADC3, microphone input only.
+    Adc3,
+}
+
+/// Managed audio codec structure with peripheral.
+pub struct AudioCodec<CODEC> {
+    codec: CODEC,
+    dac_family: Family,
+    adc_family: Family,
+}
+
+impl<CODEC: AsRef<RegisterBlock>> AudioCodec<CODEC> {
+    /// Create an audio codec instance with analog supply powered up.
+    ///
+    /// Audio PLL 1 is enabled if it is not yet, and DAC and ADC clocks are set to
+    /// 24.576 MHz from its divide-by-5 output, as in [`Family::Hz48000`].
+    #[inline]
+    pub fn new(codec: CODEC, ccu: &ccu::RegisterBlock) -> Self {
+        let clock = Family::Hz48000.clock();
+        // note(unsafe): async read and write using ccu registers
+        unsafe {
+            Family::Hz48000.enable_pll(ccu);
+            ccu.audio_codec_dac_clock.write(clock);
+            ccu.audio_codec_adc_clock.write(clock);
+            ccu::CODEC::reset(ccu);
+        }
+        unsafe { codec.as_ref().power.modify(|val| val.enable_analog_ldo()) };
+        Self {
+            codec,
+            dac_family: Family::Hz48000,
+            adc_family: Family::Hz48000,
+        }
+    }
+    /// Set DAC sample rate.
+    #[inline]
+    pub fn set_dac_sample_rate(&mut self, rate: SampleRate) {
+        unsafe {
+            self.codec
+                .as_ref()
+                .dac_fifo_control
+                .modify(|val| val.set_sample_rate(rate))
+        };
+    }
+    /// Get exact DAC sample rate, from its rate setting and codec clock family.
+    #[inline]
+    pub fn dac_sample_rate(&self) -> Hertz {
+        let rate = self.codec.as_ref().dac_fifo_control.read().sample_rate();
+        Hertz(self.dac_family.module_clock().0 / rate.divider())
+    }
+    /// Set DAC sample rate closest to `rate`, with DAC clock from the audio PLL of the
+    /// chosen clock family.
+    ///
+    /// Returns chosen setting and exact sample rate achieved. DAC should be stopped.
+    #[inline]
+    pub fn negotiate_dac_rate(
+        &mut self,
+        rate: Hertz,
+        ccu: &ccu::RegisterBlock,
+    ) -> Negotiated<SampleRate> {
+        let negotiated = audio::negotiate(rate, &SampleRate::ALL, SampleRate::divider);
+        // note(unsafe): async read and write using ccu registers
+        unsafe {
+            negotiated.family.enable_pll(ccu);
+            ccu.audio_codec_dac_clock.write(negotiated.family.clock());
+        }
+        self.set_dac_sample_rate(negotiated.rate);
+        self.dac_family = negotiated.family;
+        negotiated
+    }
+    /// Set digital DAC volume of left and right channels.
+    ///
+    /// Volume is 0 dB at [`DacVolume::ZERO_DB`], changing by 0.75 dB per step; zero mutes.
+    #[inline]
+    pub fn set_dac_volume(&mut self, left: u8, right: u8) {
+        unsafe {
+            self.codec.as_ref().dac_volume.write(
+                DacVolume::default()
+                    .set_left(left)
+                    .set_right(right)
+                    .enable(),
+            )
+        };
+    }
+    /// Enable analog DAC and route it to `output`.
+    #[inline]
+    pub fn enable_output(&mut self, output: Output) {
+        let regs = self.codec.as_ref();
+        unsafe {
+            regs.dac_analog.modify(|val| val.enable_dac().unmute());
+            match output {
+                Output::LineOut => regs.dac_analog.modify(|val| val.enable_line_out()),
+                Output::Headphone => {
+                    regs.power.modify(|val| val.enable_headphone_ldo());
+                    regs.headphone.modify(|val| val.enable());
+                }
+            }
+        }
+    }
+    /// Disable `output`, keeping analog DAC enabled for other outputs.
+    #[inline]
+    pub fn disable_output(&mut self, output: Output) {
+        let regs = self.codec.as_ref();
+        unsafe {
+            match output {
+                Output::LineOut => regs.dac_analog.modify(|val| val.disable_line_out()),
+                Output::Headphone => {
+                    regs.headphone.modify(|val| val.disable());
+                    regs.power.modify(|val| val.disable_headphone_ldo());
+                }
+            }
+        }
+    }
+    /// Set line output volume; 31 is 0 dB, changing by 1.5 dB per step.
+    #[inline]
+    pub fn set_line_out_volume(&mut self, volume: u8) {
+        unsafe {
+            self.codec
+                .as_ref()
+                .dac_analog
+                .modify(|val| val.set_line_out_volume(volume))
+        };
+    }
+    /// Set headphone attenuation; 0 is 0 dB, decreasing by 6 dB per step to 7.
+    #[inline]
+    pub fn set_headphone_attenuation(&mut self, attenuation: u8) {
+        unsafe {
+            self.codec
+                .as_ref()
+                .headphone
+                .modify(|val| val.set_attenuation(attenuation))
+        };
+    }
+    /// Start DAC, requesting DMA transfers of 16-bit samples into its FIFO.
+    ///
+    /// DMA channel should move 16-bit left and right samples alternately to
+    /// [`AudioCodec::dac_fifo_address`] with request port `AUDIO_CODEC_TX`; [`Playback`]
+    /// sets this up with a pair of buffers.
+    #[inline]
+    pub fn start_dac_dma(&mut self) {
+        self.start_dac(true);
+    }
+    /// Stop DAC and its DMA requests.
+    #[inline]
+    pub fn stop_dac_dma(&mut self) {
+        let regs = self.codec.as_ref();
+        unsafe {
+            regs.dac_digital.modify(|val| val.disable());
+            regs.dac_fifo_control.modify(|val| val.disable_drq());
+        }
+    }
+    /// Get address of DAC FIFO data register as DMA transfer destination.
+    #[inline]
+    pub fn dac_fifo_address(&self) -> usize {
+        &self.codec.as_ref().dac_tx_data as *const _ as usize
+    }
+    /// Check if DAC FIFO has underrun since last cleared, playing silence.
+    #[inline]
+    pub fn is_dac_underrun(&self) -> bool {
+        self.codec.as_ref().dac_fifo_status.read().has_underrun()
+    }
+    /// Clear DAC FIFO underrun flag.
+    #[inline]
+    pub fn clear_dac_underrun(&mut self) {
+        unsafe {
+            self.codec
+                .as_ref()
+                .dac_fifo_status
+                .write(DacFifoStatus::default().clear_underrun())
+        };
+    }
+    /// Enable microphone bias on main microphone pad with `voltage`.
+    #[inline]
+    pub fn enable_mic_bias(&mut self, voltage: MicBiasVoltage) {
+        unsafe {
+            self.codec
+                .as_ref()
+                .mic_bias
+                .modify(|val| val.set_main_voltage(voltage).enable_main())
+        };
+    }
+    /// Disable microphone bias on main microphone pad.
+    #[inline]
+    pub fn disable_mic_bias(&mut self) {
+        unsafe {
+            self.codec
+                .as_ref()
+                .mic_bias
+                .modify(|val| val.disable_main())
+        };
+    }
+    /// Enable analog front end of ADC `channel`.
+    #[inline]
+    pub fn enable_adc(&mut self, channel: AdcChannel) {
+        unsafe { self.adc(channel).modify(|val| val.enable()) };
+    }
+    /// Disable analog front end of ADC `channel`.
+    #[inline]
+    pub fn disable_adc(&mut self, channel: AdcChannel) {
+        unsafe { self.adc(channel).modify(|val| val.disable()) };
+    }
+    /// Route only `input` to ADC `channel`, disconnecting other inputs.
+    #[inline]
+    pub fn select_input(&mut self, channel: AdcChannel, input: Input) {
+        unsafe {
+            self.adc(channel).modify(|val| {
+                [Input::Mic, Input::LineIn, Input::FmIn]
+                    .iter()
+                    .fold(val, |val, &i| val.disable_input(i))
+                    .enable_input(input)
+            })
+        };
+    }
+    /// Set ADC sample rate.
+    #[inline]
+    pub fn set_adc_sample_rate(&mut self, rate: SampleRate) {
+        unsafe {
+            self.codec
+                .as_ref()
+                .adc_fifo_control
+                .modify(|val| val.set_sample_rate(rate))
+        };
+    }
+    /// Get exact ADC sample rate, from its rate setting and codec clock family.
+    #[inline]
+    pub fn adc_sample_rate(&self) -> Hertz {
+        let rate = self.codec.as_ref().adc_fifo_control.read().sample_rate();
+        Hertz(self.adc_family.module_clock().0 / rate.divider())
+    }
+    /// Set ADC sample rate closest to `rate`, with ADC clock from the audio PLL of the
+    /// chosen clock family.
+    ///
+    /// Returns chosen setting and exact sample rate achieved. ADC should be stopped.
+    #[inline]
+    pub fn negotiate_adc_rate(
+        &mut self,
+        rate: Hertz,
+        ccu: &ccu::RegisterBlock,
+    ) -> Negotiated<SampleRate> {
+        let negotiated = audio::negotiate(rate, &SampleRate::ALL, SampleRate::divider);
+        // note(unsafe): async read and write using ccu registers
+        unsafe {
+            negotiated.family.enable_pll(ccu);
+            ccu.audio_codec_adc_clock.write(negotiated.family.clock());
+        }
+        self.set_adc_sample_rate(negotiated.rate);
+        self.adc_family = negotiated.family;
+        negotiated
+    }
+    /// Set digital volume of ADC `channel`.
+    ///
+    /// Volume is 0 dB at [`AdcVolume::ZERO_DB`], changing by 0.75 dB per step; zero mutes.
+    #[inline]
+    pub fn set_adc_volume(&mut self, channel: AdcChannel, volume: u8) {
+        unsafe {
+            self.codec
+                .as_ref()
+                .adc_volume
+                .modify(|val| val.set_volume(channel as usize, volume))
+        };
+    }
+    /// Set microphone amplifier gain of ADC `channel` in decibels.
+    ///
+    /// Gain is 0 dB or from 6 to 33 dB; other values are rounded down, or clamped to 33 dB.
+    #[inline]
+    pub fn set_mic_gain(&mut self, channel: AdcChannel, decibels: u8) {
+        let selector = pga_gain_selector(decibels);
+        unsafe { self.adc(channel).modify(|val| val.set_pga_gain(selector)) };
+    }
+    /// Set whether line input to ADC `channel` is boosted by 6 dB.
+    #[inline]
+    pub fn set_line_in_boost(&mut self, channel: AdcChannel, boost: bool) {
+        unsafe { self.adc(channel).modify(|val| val.set_line_in_boost(boost)) };
+    }
+    /// Start ADC1 and ADC2, requesting DMA transfers of 16-bit samples from their FIFO.
+    ///
+    /// DMA channel should move 16-bit ADC1 and ADC2 samples alternately from
+    /// [`AudioCodec::adc_fifo_address`] with request port `AUDIO_CODEC_RX`; [`Capture`]
+    /// sets this up with a pair of buffers. Analog front ends are enabled beforehand.
+    #[inline]
+    pub fn start_adc_dma(&mut self) {
+        self.start_adc(true);
+    }
+    /// Stop ADC and its DMA requests.
+    #[inline]
+    pub fn stop_adc_dma(&mut self) {
+        unsafe {
+            self.codec
+                .as_ref()
+                .adc_fifo_control
+                .modify(|val| val.disable().disable_drq())
+        };
+    }
+    /// Get address of ADC FIFO data register as DMA transfer source.
+    #[inline]
+    pub fn adc_fifo_address(&self) -> usize {
+        &self.codec.as_ref().adc_rx_data as *const _ as usize
+    }
+    /// Check if ADC FIFO has overrun since last cleared, losing samples.
+    #[inline]
+    pub fn is_adc_overrun(&self) -> bool {
+        self.codec.as_ref().adc_fifo_status.read().has_overrun()
+    }
+    /// Clear ADC FIFO overrun flag.
+    #[inline]
+    pub fn clear_adc_overrun(&mut self) {
+        unsafe {
+            self.codec
+                .as_ref()
+                .adc_fifo_status
+                .write(AdcFifoStatus::default().clear_overrun())
+        };
+    }
+    /// Enable interrupt of FIFO `event`, raised through [`on_interrupt`].
+    #[inline]
+    pub fn enable_interrupt(&mut self, event: Event) {
+        self.set_interrupt(event, true);
+    }
+    /// Disable interrupt of FIFO `event`.
+    #[inline]
+    pub fn disable_interrupt(&mut self, event: Event) {
+        self.set_interrupt(event, false);
+    }
+    /// Register handler called from [`on_interrupt`] on each raised event, or remove it.
+    #[inline]
+    pub fn set_handler(&mut self, handler: Option<Handler>) {
+        EVENTS.set_handler(handler);
+    }
+    /// Wait for next FIFO `event`, enabling its interrupt.
+    ///
+    /// Events raised before this call are discarded.
+    #[inline]
+    pub async fn wait_event(&mut self, event: Event) {
+        EVENTS.take(event);
+        self.enable_interrupt(event);
+        EVENTS.wait(event).await;
+    }
+    /// Get number of times FIFO `event` has been raised.
+    #[inline]
+    pub fn event_count(&self, event: Event) -> u32 {
+        EVENTS.count(event)
+    }
+    /// Get number of DAC underruns and ADC overruns raised.
+    #[inline]
+    pub fn xruns(&self) -> u32 {
+        EVENTS.xruns()
+    }
+    /// Close audio codec and release peripheral.
+    #[inline]
+    pub fn free(self, ccu: &ccu::RegisterBlock) -> CODEC {
+        let regs = self.codec.as_ref();
+        unsafe {
+            regs.dac_digital.modify(|val| val.disable());
+            regs.dac_fifo_control.modify(|val| {
+                val.disable_drq()
+                    .disable_empty_interrupt()
+                    .disable_underrun_interrupt()
+            });
+            regs.adc_fifo_control.modify(|val| {
+                val.disable()
+                    .disable_drq()
+                    .disable_data_interrupt()
+                    .disable_overrun_interrupt()
+            });
+            regs.headphone.modify(|val| val.disable());
+            regs.dac_analog
+                .modify(|val| val.mute().disable_line_out().disable_dac());
+            for adc in &regs.adc {
+                adc.modify(|val| val.disable());
+            }
+            regs.mic_bias
+                .modify(|val| val.disable_main().disable_headset());
+            regs.power
+                .modify(|val| val.disable_headphone_ldo().disable_analog_ldo());
+            ccu::CODEC::free(ccu);
+            ccu.audio_codec_dac_clock.modify(|val| val.mask_clock());
+            ccu.audio_codec_adc_clock.modify(|val| val.mask_clock());
+        }
+        self.codec
+    }
+    /// Start DAC with 16-bit stereo FIFO samples, requesting DMA transfers if `drq`.
+    #[inline]
+    fn start_dac(&mut self, drq: bool) {
+        let regs = self.codec.as_ref();
+        let control = regs
+            .dac_fifo_control
+            .read()
+            .set_fifo_mode(FifoMode::Lsb16)
+            .set_20_bit(false)
+            .set_mono(false)
+            .disable_drq();
+        let control = if drq { control.enable_drq() } else { control };
+        unsafe {
+            regs.dac_fifo_control.write(control.flush_fifo());
+            regs.dac_fifo_status
+                .write(DacFifoStatus::default().clear_underrun().clear_overrun());
+            regs.dac_fifo_control.write(control);
+            regs.dac_digital.modify(|val| val.enable());
+        }
+    }
+    /// Start ADC1 and ADC2 with 16-bit FIFO samples, requesting DMA transfers if `drq`.
+    #[inline]
+    fn start_adc(&mut self, drq: bool) {
+        let regs = self.codec.as_ref();
+        let control = regs
+            .adc_fifo_control
+            .read()
+            .set_lsb_aligned(true)
+            .set_20_bit(false)
+            .disable_drq();
+        let control = if drq { control.enable_drq() } else { control };
+        unsafe {
+            regs.adc_digital.modify(|val| {
+                val.disable_channels()
+                    .enable_channel(AdcChannel::Adc1 as usize)
+                    .enable_channel(AdcChannel::Adc2 as usize)
+            });
+            regs.adc_fifo_control.write(control.flush_fifo());
+            regs.adc_fifo_status
+                .write(AdcFifoStatus::default().clear_overrun());
+            regs.adc_fifo_control.write(control.enable());
+        }
+    }
+    #[inline]
+    fn set_interrupt(&mut self, event: Event, enable: bool) {
+        let regs = self.codec.as_ref();
+        unsafe {
+            match (event, enable) {
+                (Event::Empty, true) => regs
+                    .dac_fifo_control
+                    .modify(|val| val.enable_empty_interrupt()),
+                (Event::Empty, false) => regs
+                    .dac_fifo_control
+                    .modify(|val| val.disable_empty_interrupt()),
+                (Event::Underrun, true) => regs
+                    .dac_fifo_control
+                    .modify(|val| val.enable_underrun_interrupt()),
+                (Event::Underrun, false) => regs
+                    .dac_fifo_control
+                    .modify(|val| val.disable_underrun_interrupt()),
+                (Event::Available, true) => regs
+                    .adc_fifo_control
+                    .modify(|val| val.enable_data_interrupt()),
+                (Event::Available, false) => regs
+                    .adc_fifo_control
+                    .modify(|val| val.disable_data_interrupt()),
+                (Event::Overrun, true) => regs
+                    .adc_fifo_control
+                    .modify(|val| val.enable_overrun_interrupt()),
+                (Event::Overrun, false) => regs
+                    .adc_fifo_control
+                    .modify(|val| val.disable_overrun_interrupt()),
+            }
+        }
+    }
+    #[inline]
+    fn adc(&self, channel: AdcChannel) -> &volatile_register::RW<AdcAnalog> {
+        &self.codec.as_ref().adc[channel as usize]
+    }
+}
+
+/// Convert microphone amplifier gain in decibels to register selector.
+#[inline]
+const fn pga_gain_selector(decibels: u8) -> u8 {
+    match decibels {
+        0..6 => 0,
+        6..33 => decibels - 2,
+        _ => 31,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::pga_gain_selector;
+
+    #[test]
+    fn function_pga_gain_selector() {
+        assert_eq!(pga_gain_selector(0), 0);
+        assert_eq!(pga_gain_selector(5), 0);
+        assert_eq!(pga_gain_selector(6), 4);
+        assert_eq!(pga_gain_selector(20), 18);
+        assert_eq!(pga_gain_selector(33), 31);
+        assert_eq!(pga_gain_selector(40), 31);
+    }
+}
@@ -0,0 +1,208 @@
+use super::{AudioCodec, Frame, RegisterBlock};
+use crate::audio::{Source, Stream};
+use crate::dmac::{
+    self, AddressMode, BurstLength, Channel, DataWidth, Descriptor, DmaConfig, Event, StreamParts,
+    drq,
+};
+use crate::time::TimeoutError;
+use atomic_waker::AtomicWaker;
+use core::{
+    future::poll_fn,
+    marker::PhantomData,
+    sync::atomic::{AtomicU32, Ordering},
+    task::Poll,
+};
+use embedded_time::rate::Hertz;
+
+static WAKER: AtomicWaker = AtomicWaker::new();
+static FILLED: AtomicU32 = AtomicU32::new(0);
+
+/// Count a filled capture buffer and wake the reading task.
+fn on_package_end(_channel: usize, _event: Event) {
+    FILLED.fetch_add(1, Ordering::Release);
+    WAKER.wake();
+}
+
+/// PCM capture streamed by a DMA channel into two buffers of `L` frames each.
+///
+/// Each frame holds one ADC1 sample on the left and one ADC2 sample on the right. DMA fills
+/// both buffers in turn, endlessly; [`Capture::ready`] and [`Capture::read_frames`] hand
+/// out the latest filled buffer. A buffer should be consumed before DMA comes back to it,
+/// that is within `L` frame periods; buffers overwritten before being read are counted as
+/// overruns.
+///
+/// Filled buffers are counted by DMA `PackageEnd` interrupts, so `dmac::on_interrupt`
+/// should be called from DMAC interrupt handler.
+pub struct Capture<'a, const N: usize, const L: usize> {
+    channel: Channel<'a, N>,
+    buffers: *const [[Frame; L]; 2],
+    descriptors: &'a mut [Descriptor; 2],
+    consumed: u32,
+    overruns: u32,
+    sample_rate: Hertz,
+    _buffers: PhantomData<&'a mut [[Frame; L]; 2]>,
+}
+
+impl<'a, const N: usize, const L: usize> Capture<'a, N, L> {
+    /// Start capturing ADC1 and ADC2 of `codec` into `buffers` on DMA `channel`, using
+    /// `descriptors` as a two-descriptor ring.
+    ///
+    /// Inputs, gains and sample rate are set on `codec` beforehand. Only one capture
+    /// stream should be running at a time.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `L` is zero.
+    #[inline]
+    pub fn start<CODEC: AsRef<RegisterBlock>>(
+        codec: &mut AudioCodec<CODEC>,
+        mut channel: Channel<'a, N>,
+        buffers: &'a mut [[Frame; L]; 2],
+        descriptors: &'a mut [Descriptor; 2],
+    ) -> Self {
+        assert!(L != 0, "capture buffer should hold at least one frame");
+        let config = DmaConfig {
+            source_drq: drq::AUDIO_CODEC_RX,
+            source_address_mode: AddressMode::Io,
+            source_burst: BurstLength::Single,
+            source_width: DataWidth::Bits16,
+            destination_drq: drq::DRAM,
+            destination_address_mode: AddressMode::Linear,
+            destination_burst: BurstLength::Single,
+            destination_width: DataWidth::Bits16,
+            ..DmaConfig::default()
+        };
+        let source = codec.adc_fifo_address() as u32;
+        for (descriptor, buffer) in descriptors.iter_mut().zip(buffers.iter()) {
+            *descriptor = Descriptor::new(
+                &config,
+                source,
+                buffer.as_ptr() as usize as u32,
+                (L * size_of::<Frame>()) as u32,
+            );
+        }
+        let (first, second) = descriptors.split_at_mut(1);
+        first[0].set_link(&second[0]);
+        second[0].set_link(&first[0]);
+        FILLED.store(0, Ordering::Release);
+        channel.set_handler(on_package_end);
+        channel.enable_interrupt(Event::PackageEnd);
+        codec.start_adc_dma();
+        // note(unsafe): buffers and descriptors are borrowed by `Capture` until stopped
+        unsafe { channel.start(&descriptors[0]) };
+        Self {
+            channel,
+            buffers,
+            descriptors,
+            consumed: 0,
+            overruns: 0,
+            sample_rate: codec.adc_sample_rate(),
+            _buffers: PhantomData,
+        }
+    }
+    /// Get latest buffer filled by DMA since last call, if any.
+    #[inline]
+    pub fn ready(&mut self) -> Option<&[Frame; L]> {
+        let index = self.take_ready()?;
+        // note(unsafe): DMA is writing the other buffer
+        Some(unsafe { &(*self.buffers)[index] })
+    }
+    /// Wait for next buffer filled by DMA and return it.
+    ///
+    /// Returns at once if a filled buffer has not been read yet.
+    #[inline]
+    pub async fn read_frames(&mut self) -> &[Frame; L] {
+        let index = poll_fn(|cx| {
+            WAKER.register(cx.waker());
+            match self.take_ready() {
+                Some(index) => Poll::Ready(index),
+                None => Poll::Pending,
+            }
+        })
+        .await;
+        // note(unsafe): DMA is writing the other buffer
+        unsafe { &(*self.buffers)[index] }
+    }
+    /// Get number of buffers overwritten by DMA before being read.
+    #[inline]
+    pub fn overruns(&self) -> u32 {
+        self.overruns
+    }
+    /// Get DMA channel, for example to read its transfer statistics.
+    #[inline]
+    pub fn channel(&mut self) -> &mut Channel<'a, N> {
+        &mut self.channel
+    }
+    /// Stop capture and release DMA channel, buffers and descriptors.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`TimeoutError`] if DMA channel doesn't stop before crate-wide blocking
+    /// timeout; buffers are then not released.
+    #[inline]
+    pub fn stop<CODEC: AsRef<RegisterBlock>>(
+        mut self,
+        codec: &mut AudioCodec<CODEC>,
+    ) -> Result<StreamParts<'a, N, [Frame; L]>, TimeoutError> {
+        self.channel.disable_interrupt(Event::PackageEnd);
+        dmac::clear_handler(N);
+        self.channel.abort()?;
+        codec.stop_adc_dma();
+        // note(unsafe): DMA has stopped, buffers are exclusively borrowed again
+        let buffers = unsafe { &mut *(self.buffers as *mut [[Frame; L]; 2]) };
+        Ok((self.channel, buffers, self.descriptors))
+    }
+    #[inline]
+    fn take_ready(&mut self) -> Option<usize> {
+        let (index, skipped) = next_ready(self.consumed, FILLED.load(Ordering::Acquire))?;
+        self.overruns += skipped;
+        self.consumed += skipped + 1;
+        Some(index)
+    }
+}
+
+impl<const N: usize, const L: usize> Stream for Capture<'_, N, L> {
+    type Sample = i16;
+
+    #[inline]
+    fn channels(&self) -> usize {
+        2
+    }
+    #[inline]
+    fn sample_rate(&self) -> Hertz {
+        self.sample_rate
+    }
+}
+
+impl<const N: usize, const L: usize> Source for Capture<'_, N, L> {
+    #[inline]
+    fn read_buffer(&mut self) -> Option<&[i16]> {
+        self.ready().map(|buffer| buffer.as_flattened())
+    }
+}
+
+/// Get index of latest filled buffer and number of skipped buffers, after `consumed` of
+/// `filled` buffers have been read.
+#[inline]
+const fn next_ready(consumed: u32, filled: u32) -> Option<(usize, u32)> {
+    let pending = filled.wrapping_sub(consumed);
+    if pending == 0 {
+        return None;
+    }
+    let latest = filled.wrapping_sub(1);
+    Some(((latest % 2) as usize, pending - 1))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::next_ready;
+
+    #[test]
+    fn function_next_ready() {
+        assert_eq!(next_ready(0, 0), None);
+        assert_eq!(next_ready(0, 1), Some((0, 0)));
+        assert_eq!(next_ready(1, 2), Some((1, 0)));
+        assert_eq!(next_ready(2, 5), Some((0, 2)));
+        assert_eq!(next_ready(u32::MAX, 0), Some((1, 0)));
+    }
+}
@@ -0,0 +1,228 @@
+use super::{AudioCodec, Frame, RegisterBlock};
+use crate::audio::{Sink, Stream};
+use crate::dmac::{
+    AddressMode, BurstLength, Channel, DataWidth, Descriptor, DmaConfig, StreamParts, drq,
+};
+use crate::time::TimeoutError;
+use core::marker::PhantomData;
+use core::sync::atomic::{Ordering, fence};
+use embedded_time::rate::Hertz;
+
+/// PCM playback streamed by a DMA channel from two buffers of `L` frames each.
+///
+/// DMA plays one buffer while the other is filled; [`Playback::writable`] hands out the
+/// idle buffer and [`Playback::submit`] queues it after the playing one. If the playing
+/// buffer ends before another one is queued, DMA stops and the DAC runs dry; this is
+/// counted as an underrun, and playback resumes on next submitted buffer.
+pub struct Playback<'a, const N: usize, const L: usize> {
+    channel: Channel<'a, N>,
+    buffers: *mut [[Frame; L]; 2],
+    descriptors: &'a mut [Descriptor; 2],
+    playing: usize,
+    queued: bool,
+    stalled: bool,
+    underruns: u32,
+    sample_rate: Hertz,
+    _buffers: PhantomData<&'a mut [[Frame; L]; 2]>,
+}
+
+impl<'a, const N: usize, const L: usize> Playback<'a, N, L> {
+    /// Start playing `buffers` in turn on DMA `channel`, using `descriptors` as a
+    /// two-descriptor chain.
+    ///
+    /// Both buffers should be filled before starting. Sample rate and outputs are set on
+    /// `codec` beforehand.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `L` is zero.
+    #[inline]
+    pub fn start<CODEC: AsRef<RegisterBlock>>(
+        codec: &mut AudioCodec<CODEC>,
+        mut channel: Channel<'a, N>,
+        buffers: &'a mut [[Frame; L]; 2],
+        descriptors: &'a mut [Descriptor; 2],
+    ) -> Self {
+        assert!(L != 0, "playback buffer should hold at least one frame");
+        let config = DmaConfig {
+            source_drq: drq::DRAM,
+            source_address_mode: AddressMode::Linear,
+            source_burst: BurstLength::Single,
+            source_width: DataWidth::Bits16,
+            destination_drq: drq::AUDIO_CODEC_TX,
+            destination_address_mode: AddressMode::Io,
+            destination_burst: BurstLength::Single,
+            destination_width: DataWidth::Bits16,
+            ..DmaConfig::default()
+        };
+        let destination = codec.dac_fifo_address() as u32;
+        for (descriptor, buffer) in descriptors.iter_mut().zip(buffers.iter()) {
+            *descriptor = Descriptor::new(
+                &config,
+                buffer.as_ptr() as usize as u32,
+                destination,
+                (L * size_of::<Frame>()) as u32,
+            );
+        }
+        let (first, second) = descriptors.split_at_mut(1);
+        first[0].set_link(&second[0]);
+        codec.start_dac_dma();
+        // note(unsafe): buffers and descriptors are borrowed by `Playback` until stopped
+        unsafe { channel.start(&descriptors[0]) };
+        Self {
+            channel,
+            buffers,
+            descriptors,
+            playing: 0,
+            queued: true,
+            stalled: false,
+            underruns: 0,
+            sample_rate: codec.dac_sample_rate(),
+            _buffers: PhantomData,
+        }
+    }
+    /// Get the buffer DMA is not playing, if it is free to be filled.
+    ///
+    /// Returns `None` while a submitted buffer is still waiting to be played.
+    #[inline]
+    pub fn writable(&mut self) -> Option<&mut [Frame; L]> {
+        // read busy state first, so that position below is final if DMA has stopped
+        let busy = self.channel.is_busy();
+        let current = playing_buffer(
+            self.buffers as usize as u32,
+            (L * size_of::<Frame>()) as u32,
+            self.channel.current_source(),
+            self.channel.bytes_left(),
+        );
+        if self.queued && current != self.playing {
+            self.playing = current;
+            self.queued = false;
+        }
+        if !busy {
+            if self.queued {
+                // DMA read end of chain just before queued buffer was linked.
+                self.underruns += 1;
+                self.playing ^= 1;
+                self.queued = false;
+                // note(unsafe): descriptor and buffer are borrowed by `Playback`
+                unsafe { self.channel.start(&self.descriptors[self.playing]) };
+            } else if !self.stalled {
+                self.underruns += 1;
+                self.stalled = true;
+            }
+        }
+        if self.queued {
+            return None;
+        }
+        // note(unsafe): DMA is playing the other buffer, or has stopped
+        Some(unsafe { &mut (*self.buffers)[self.playing ^ 1] })
+    }
+    /// Queue the buffer last returned by [`Playback::writable`] after the playing one.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a buffer is already queued.
+    #[inline]
+    pub fn submit(&mut self) {
+        assert!(!self.queued, "a playback buffer is already queued");
+        let next = self.playing ^ 1;
+        // note(unsafe): DMA reads link fields of descriptors by itself
+        unsafe {
+            core::ptr::write_volatile(&mut self.descriptors[next].link, Descriptor::LINK_END)
+        };
+        if self.stalled {
+            self.stalled = false;
+            self.playing = next;
+            fence(Ordering::SeqCst);
+            // note(unsafe): descriptor and buffer are borrowed by `Playback`
+            unsafe { self.channel.start(&self.descriptors[next]) };
+            return;
+        }
+        let link = &self.descriptors[next] as *const Descriptor as usize as u32;
+        unsafe { core::ptr::write_volatile(&mut self.descriptors[self.playing].link, link) };
+        fence(Ordering::SeqCst);
+        self.queued = true;
+    }
+    /// Get number of underruns since playback started.
+    #[inline]
+    pub fn underruns(&self) -> u32 {
+        self.underruns
+    }
+    /// Get DMA channel, for example to enable `PackageEnd` interrupt on each played buffer.
+    #[inline]
+    pub fn channel(&mut self) -> &mut Channel<'a, N> {
+        &mut self.channel
+    }
+    /// Stop playback and release DMA channel, buffers and descriptors.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`TimeoutError`] if DMA channel doesn't stop before crate-wide blocking
+    /// timeout; buffers are then not released.
+    #[inline]
+    pub fn stop<CODEC: AsRef<RegisterBlock>>(
+        mut self,
+        codec: &mut AudioCodec<CODEC>,
+    ) -> Result<StreamParts<'a, N, [Frame; L]>, TimeoutError> {
+        self.channel.abort()?;
+        codec.stop_dac_dma();
+        // note(unsafe): DMA has stopped, buffers are exclusively borrowed again
+        let buffers = unsafe { &mut *self.buffers };
+        Ok((self.channel, buffers, self.descriptors))
+    }
+}
+
+impl<const N: usize, const L: usize> Stream for Playback<'_, N, L> {
+    type Sample = i16;
+
+    #[inline]
+    fn channels(&self) -> usize {
+        2
+    }
+    #[inline]
+    fn sample_rate(&self) -> Hertz {
+        self.sample_rate
+    }
+}
+
+impl<const N: usize, const L: usize> Sink for Playback<'_, N, L> {
+    #[inline]
+    fn write_buffer(&mut self) -> Option<&mut [i16]> {
+        self.writable().map(|buffer| buffer.as_flattened_mut())
+    }
+    #[inline]
+    fn submit_buffer(&mut self) {
+        self.submit();
+    }
+}
+
+/// Get index of buffer DMA is playing or has last played from its `source` address and
+/// `bytes_left` in current descriptor, with buffers of `buffer_bytes` each placed back to
+/// back from `base`.
+///
+/// Source right at the end of first buffer is either the end of first buffer, with no
+/// bytes left, or the start of second buffer.
+#[inline]
+const fn playing_buffer(base: u32, buffer_bytes: u32, source: u32, bytes_left: u32) -> usize {
+    let end = base + buffer_bytes;
+    if source > end || (source == end && bytes_left != 0) {
+        1
+    } else {
+        0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::playing_buffer;
+
+    #[test]
+    fn function_playing_buffer() {
+        assert_eq!(playing_buffer(0x4000_0000, 0x100, 0x4000_0000, 0x100), 0);
+        assert_eq!(playing_buffer(0x4000_0000, 0x100, 0x4000_00FE, 0x2), 0);
+        assert_eq!(playing_buffer(0x4000_0000, 0x100, 0x4000_0100, 0), 0);
+        assert_eq!(playing_buffer(0x4000_0000, 0x100, 0x4000_0100, 0x100), 1);
+        assert_eq!(playing_buffer(0x4000_0000, 0x100, 0x4000_01FE, 0x2), 1);
+        assert_eq!(playing_buffer(0x4000_0000, 0x100, 0x4000_0200, 0), 1);
+    }
+}
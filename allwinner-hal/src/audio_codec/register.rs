@@ -0,0 +1,1394 @@
+use volatile_register::{RO, RW, WO};
+
+/// Audio codec registers.
+#[repr(C)]
+pub struct RegisterBlock {
+    /// 0x00 - DAC Digital Part Control Register.
+    pub dac_digital: RW<DacDigital>,
+    /// 0x04 - DAC Volume Control Register.
+    pub dac_volume: RW<DacVolume>,
+    _reserved0: [u32; 2],
+    /// 0x10 - DAC FIFO Control Register.
+    pub dac_fifo_control: RW<DacFifoControl>,
+    /// 0x14 - DAC FIFO Status Register.
+    pub dac_fifo_status: RW<DacFifoStatus>,
+    _reserved1: [u32; 2],
+    /// 0x20 - DAC TX Data Register.
+    pub dac_tx_data: WO<u32>,
+    /// 0x24 - DAC TX Counter Register.
+    pub dac_tx_count: RW<u32>,
+    /// 0x28 - DAC Debug Register.
+    pub dac_debug: RW<DacDebug>,
+    _reserved2: u32,
+    /// 0x30 - ADC FIFO Control Register.
+    pub adc_fifo_control: RW<AdcFifoControl>,
+    /// 0x34 - ADC Volume Control Register.
+    pub adc_volume: RW<AdcVolume>,
+    /// 0x38 - ADC FIFO Status Register.
+    pub adc_fifo_status: RW<AdcFifoStatus>,
+    _reserved3: u32,
+    /// 0x40 - ADC RX Data Register.
+    pub adc_rx_data: RO<u32>,
+    /// 0x44 - ADC RX Counter Register.
+    pub adc_rx_count: RW<u32>,
+    _reserved4: [u32; 2],
+    /// 0x50 - ADC Digital Control Register.
+    pub adc_digital: RW<AdcDigital>,
+    _reserved5: [u32; 171],
+    /// 0x300..=0x308 - ADC1, ADC2 and ADC3 Analog Control Registers.
+    pub adc: [RW<AdcAnalog>; 3],
+    _reserved6: u32,
+    /// 0x310 - DAC Analog Control Register.
+    pub dac_analog: RW<DacAnalog>,
+    /// 0x314 - Microphone Bias Control Register.
+    pub mic_bias: RW<MicBias>,
+    _reserved7: [u32; 10],
+    /// 0x340 - Headphone Control Register.
+    pub headphone: RW<Headphone>,
+    _reserved8: u32,
+    /// 0x348 - Analog Power Control Register.
+    pub power: RW<Power>,
+}
+
+/// Sample rate of DAC or ADC, from 24.576-MHz codec clock.
+///
+/// With 22.5792-MHz codec clock of the 44.1-kHz family, each rate scales by 147/160, for
+/// example `Hz48000` gives 44.1 kHz.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum SampleRate {
+    /// 48 kHz.
+    Hz48000 = 0,
+    /// 32 kHz.
+    Hz32000 = 1,
+    /// 24 kHz.
+    Hz24000 = 2,
+    /// 16 kHz.
+    Hz16000 = 3,
+    /// 12 kHz.
+    Hz12000 = 4,
+    /// 8 kHz.
+    Hz8000 = 5,
+    /// 192 kHz.
+    Hz192000 = 6,
+    /// 96 kHz.
+    Hz96000 = 7,
+}
+
+impl SampleRate {
+    /// All sample rates, from highest to lowest.
+    pub const ALL: [SampleRate; 8] = [
+        SampleRate::Hz192000,
+        SampleRate::Hz96000,
+        SampleRate::Hz48000,
+        SampleRate::Hz32000,
+        SampleRate::Hz24000,
+        SampleRate::Hz16000,
+        SampleRate::Hz12000,
+        SampleRate::Hz8000,
+    ];
+
+    /// Get number of codec clock cycles per sample.
+    #[inline]
+    pub const fn divider(self) -> u32 {
+        match self {
+            SampleRate::Hz48000 => 512,
+            SampleRate::Hz32000 => 768,
+            SampleRate::Hz24000 => 1024,
+            SampleRate::Hz16000 => 1536,
+            SampleRate::Hz12000 => 2048,
+            SampleRate::Hz8000 => 3072,
+            SampleRate::Hz192000 => 128,
+            SampleRate::Hz96000 => 256,
+        }
+    }
+    #[inline]
+    pub(crate) const fn from_bits(bits: u32) -> Self {
+        match bits {
+            0 => SampleRate::Hz48000,
+            1 => SampleRate::Hz32000,
+            2 => SampleRate::Hz24000,
+            3 => SampleRate::Hz16000,
+            4 => SampleRate::Hz12000,
+            5 => SampleRate::Hz8000,
+            6 => SampleRate::Hz192000,
+            _ => SampleRate::Hz96000,
+        }
+    }
+}
+
+/// DAC digital part control register.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct DacDigital(u32);
+
+impl DacDigital {
+    const EN_DA: u32 = 1 << 31;
+    const HPF_EN: u32 = 1 << 18;
+    const DVOL: u32 = 0x3F << 12;
+
+    /// If digital DAC is enabled.
+    #[inline]
+    pub const fn is_enabled(self) -> bool {
+        self.0 & Self::EN_DA != 0
+    }
+    /// Enable digital DAC.
+    #[inline]
+    pub const fn enable(self) -> Self {
+        Self(self.0 | Self::EN_DA)
+    }
+    /// Disable digital DAC.
+    #[inline]
+    pub const fn disable(self) -> Self {
+        Self(self.0 & !Self::EN_DA)
+    }
+    /// If high-pass filter is enabled.
+    #[inline]
+    pub const fn is_high_pass_enabled(self) -> bool {
+        self.0 & Self::HPF_EN != 0
+    }
+    /// Enable high-pass filter.
+    #[inline]
+    pub const fn enable_high_pass(self) -> Self {
+        Self(self.0 | Self::HPF_EN)
+    }
+    /// Disable high-pass filter.
+    #[inline]
+    pub const fn disable_high_pass(self) -> Self {
+        Self(self.0 & !Self::HPF_EN)
+    }
+    /// Get digital attenuation in 1.16-dB steps.
+    #[inline]
+    pub const fn attenuation(self) -> u8 {
+        ((self.0 & Self::DVOL) >> 12) as u8
+    }
+    /// Set digital attenuation in 1.16-dB steps, from 0 to 63.
+    #[inline]
+    pub const fn set_attenuation(self, val: u8) -> Self {
+        Self((self.0 & !Self::DVOL) | ((val as u32 & 0x3F) << 12))
+    }
+}
+
+impl Default for DacDigital {
+    #[inline]
+    fn default() -> Self {
+        Self(0x0000_0000)
+    }
+}
+
+/// DAC volume control register.
+///
+/// Volume is 0 dB at [`DacVolume::ZERO_DB`], changing by 0.75 dB per step; zero mutes.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct DacVolume(u32);
+
+impl DacVolume {
+    const DAC_VOL_SEL: u32 = 1 << 16;
+    const DAC_VOL_L: u32 = 0xFF << 8;
+    const DAC_VOL_R: u32 = 0xFF;
+
+    /// Volume value of 0 dB.
+    pub const ZERO_DB: u8 = 0xA0;
+
+    /// If volume control is enabled.
+    #[inline]
+    pub const fn is_enabled(self) -> bool {
+        self.0 & Self::DAC_VOL_SEL != 0
+    }
+    /// Enable volume control.
+    #[inline]
+    pub const fn enable(self) -> Self {
+        Self(self.0 | Self::DAC_VOL_SEL)
+    }
+    /// Disable volume control.
+    #[inline]
+    pub const fn disable(self) -> Self {
+        Self(self.0 & !Self::DAC_VOL_SEL)
+    }
+    /// Get left channel volume.
+    #[inline]
+    pub const fn left(self) -> u8 {
+        ((self.0 & Self::DAC_VOL_L) >> 8) as u8
+    }
+    /// Set left channel volume.
+    #[inline]
+    pub const fn set_left(self, val: u8) -> Self {
+        Self((self.0 & !Self::DAC_VOL_L) | ((val as u32) << 8))
+    }
+    /// Get right channel volume.
+    #[inline]
+    pub const fn right(self) -> u8 {
+        (self.0 & Self::DAC_VOL_R) as u8
+    }
+    /// Set right channel volume.
+    #[inline]
+    pub const fn set_right(self, val: u8) -> Self {
+        Self((self.0 & !Self::DAC_VOL_R) | val as u32)
+    }
+}
+
+impl Default for DacVolume {
+    #[inline]
+    fn default() -> Self {
+        Self(0x0000_A0A0)
+    }
+}
+
+/// FIFO sample packing of 16-bit and 20-bit samples.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum FifoMode {
+    /// Sample in high bits of each 32-bit word.
+    Msb = 0,
+    /// 20-bit sample in low bits of each 32-bit word.
+    Lsb20 = 1,
+    /// 16-bit sample in high half of each 32-bit word.
+    Msb16 = 2,
+    /// 16-bit sample in low half of each 32-bit word, or in each 16-bit write.
+    Lsb16 = 3,
+}
+
+/// Signal played by DAC in place of FIFO samples.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum DacPattern {
+    /// Samples from FIFO.
+    Fifo = 0,
+    /// Built-in sine wave at -6 dB.
+    SineMinus6Db = 1,
+    /// Built-in sine wave at -60 dB.
+    SineMinus60Db = 2,
+    /// Silence.
+    Silent = 3,
+}
+
+/// DAC debug register.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct DacDebug(u32);
+
+impl DacDebug {
+    const DAC_PATTERN_SELECT: u32 = 0x3 << 9;
+    const DA_SWP: u32 = 1 << 6;
+    const ADDA_LOOP_MODE: u32 = 0x3;
+
+    /// Get signal played by DAC.
+    #[inline]
+    pub const fn pattern(self) -> DacPattern {
+        match (self.0 & Self::DAC_PATTERN_SELECT) >> 9 {
+            0 => DacPattern::Fifo,
+            1 => DacPattern::SineMinus6Db,
+            2 => DacPattern::SineMinus60Db,
+            _ => DacPattern::Silent,
+        }
+    }
+    /// Set signal played by DAC.
+    #[inline]
+    pub const fn set_pattern(self, val: DacPattern) -> Self {
+        Self((self.0 & !Self::DAC_PATTERN_SELECT) | ((val as u32) << 9))
+    }
+    /// If left and right DAC channels are swapped.
+    #[inline]
+    pub const fn is_swapped(self) -> bool {
+        self.0 & Self::DA_SWP != 0
+    }
+    /// Set whether left and right DAC channels are swapped.
+    #[inline]
+    pub const fn set_swapped(self, val: bool) -> Self {
+        if val {
+            Self(self.0 | Self::DA_SWP)
+        } else {
+            Self(self.0 & !Self::DA_SWP)
+        }
+    }
+    /// If DAC output is looped back into ADC1 and ADC2 digital input.
+    #[inline]
+    pub const fn is_loopback_enabled(self) -> bool {
+        self.0 & Self::ADDA_LOOP_MODE == 1
+    }
+    /// Loop DAC output back into ADC1 and ADC2 digital input.
+    #[inline]
+    pub const fn enable_loopback(self) -> Self {
+        Self((self.0 & !Self::ADDA_LOOP_MODE) | 1)
+    }
+    /// Disable loopback, sampling ADC analog inputs.
+    #[inline]
+    pub const fn disable_loopback(self) -> Self {
+        Self(self.0 & !Self::ADDA_LOOP_MODE)
+    }
+}
+
+impl Default for DacDebug {
+    #[inline]
+    fn default() -> Self {
+        Self(0x0000_0000)
+    }
+}
+
+/// DAC FIFO control register.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct DacFifoControl(u32);
+
+impl DacFifoControl {
+    const DAC_FS: u32 = 0x7 << 29;
+    const FIFO_MODE: u32 = 0x3 << 24;
+    const TX_TRIG_LEVEL: u32 = 0x7F << 8;
+    const DAC_MONO_EN: u32 = 1 << 6;
+    const TX_SAMPLE_BITS: u32 = 1 << 5;
+    const DAC_DRQ_EN: u32 = 1 << 4;
+    const DAC_IRQ_EN: u32 = 1 << 3;
+    const FIFO_UNDERRUN_IRQ_EN: u32 = 1 << 2;
+    const FIFO_FLUSH: u32 = 1 << 0;
+
+    /// Get sample rate.
+    #[inline]
+    pub const fn sample_rate(self) -> SampleRate {
+        SampleRate::from_bits((self.0 & Self::DAC_FS) >> 29)
+    }
+    /// Set sample rate.
+    #[inline]
+    pub const fn set_sample_rate(self, val: SampleRate) -> Self {
+        Self((self.0 & !Self::DAC_FS) | ((val as u32) << 29))
+    }
+    /// Get FIFO sample packing.
+    #[inline]
+    pub const fn fifo_mode(self) -> FifoMode {
+        match (self.0 & Self::FIFO_MODE) >> 24 {
+            0 => FifoMode::Msb,
+            1 => FifoMode::Lsb20,
+            2 => FifoMode::Msb16,
+            _ => FifoMode::Lsb16,
+        }
+    }
+    /// Set FIFO sample packing.
+    #[inline]
+    pub const fn set_fifo_mode(self, val: FifoMode) -> Self {
+        Self((self.0 & !Self::FIFO_MODE) | ((val as u32) << 24))
+    }
+    /// Get number of free FIFO entries that triggers DMA request.
+    #[inline]
+    pub const fn trigger_level(self) -> u8 {
+        ((self.0 & Self::TX_TRIG_LEVEL) >> 8) as u8
+    }
+    /// Set number of free FIFO entries that triggers DMA request, from 0 to 127.
+    #[inline]
+    pub const fn set_trigger_level(self, val: u8) -> Self {
+        Self((self.0 & !Self::TX_TRIG_LEVEL) | ((val as u32 & 0x7F) << 8))
+    }
+    /// If mono mode is enabled, playing each sample on both channels.
+    #[inline]
+    pub const fn is_mono(self) -> bool {
+        self.0 & Self::DAC_MONO_EN != 0
+    }
+    /// Set mono mode.
+    #[inline]
+    pub const fn set_mono(self, val: bool) -> Self {
+        if val {
+            Self(self.0 | Self::DAC_MONO_EN)
+        } else {
+            Self(self.0 & !Self::DAC_MONO_EN)
+        }
+    }
+    /// If samples are 20 bits wide, or 16 bits otherwise.
+    #[inline]
+    pub const fn is_20_bit(self) -> bool {
+        self.0 & Self::TX_SAMPLE_BITS != 0
+    }
+    /// Set whether samples are 20 bits wide, or 16 bits otherwise.
+    #[inline]
+    pub const fn set_20_bit(self, val: bool) -> Self {
+        if val {
+            Self(self.0 | Self::TX_SAMPLE_BITS)
+        } else {
+            Self(self.0 & !Self::TX_SAMPLE_BITS)
+        }
+    }
+    /// If DMA request is enabled.
+    #[inline]
+    pub const fn is_drq_enabled(self) -> bool {
+        self.0 & Self::DAC_DRQ_EN != 0
+    }
+    /// Enable DMA request.
+    #[inline]
+    pub const fn enable_drq(self) -> Self {
+        Self(self.0 | Self::DAC_DRQ_EN)
+    }
+    /// Disable DMA request.
+    #[inline]
+    pub const fn disable_drq(self) -> Self {
+        Self(self.0 & !Self::DAC_DRQ_EN)
+    }
+    /// If FIFO empty interrupt is enabled, raised at trigger level.
+    #[inline]
+    pub const fn is_empty_interrupt_enabled(self) -> bool {
+        self.0 & Self::DAC_IRQ_EN != 0
+    }
+    /// Enable FIFO empty interrupt.
+    #[inline]
+    pub const fn enable_empty_interrupt(self) -> Self {
+        Self(self.0 | Self::DAC_IRQ_EN)
+    }
+    /// Disable FIFO empty interrupt.
+    #[inline]
+    pub const fn disable_empty_interrupt(self) -> Self {
+        Self(self.0 & !Self::DAC_IRQ_EN)
+    }
+    /// If FIFO underrun interrupt is enabled.
+    #[inline]
+    pub const fn is_underrun_interrupt_enabled(self) -> bool {
+        self.0 & Self::FIFO_UNDERRUN_IRQ_EN != 0
+    }
+    /// Enable FIFO underrun interrupt.
+    #[inline]
+    pub const fn enable_underrun_interrupt(self) -> Self {
+        Self(self.0 | Self::FIFO_UNDERRUN_IRQ_EN)
+    }
+    /// Disable FIFO underrun interrupt.
+    #[inline]
+    pub const fn disable_underrun_interrupt(self) -> Self {
+        Self(self.0 & !Self::FIFO_UNDERRUN_IRQ_EN)
+    }
+    /// Flush FIFO, cleared by hardware when done.
+    #[inline]
+    pub const fn flush_fifo(self) -> Self {
+        Self(self.0 | Self::FIFO_FLUSH)
+    }
+}
+
+impl Default for DacFifoControl {
+    #[inline]
+    fn default() -> Self {
+        Self(0x0000_4000)
+    }
+}
+
+/// DAC FIFO status register.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct DacFifoStatus(u32);
+
+impl DacFifoStatus {
+    const TX_EMPTY: u32 = 1 << 23;
+    const TXE_CNT: u32 = 0x7FFF << 8;
+    const TXE_INT: u32 = 1 << 3;
+    const TXU_INT: u32 = 1 << 2;
+    const TXO_INT: u32 = 1 << 1;
+
+    /// If FIFO has room for one more sample.
+    #[inline]
+    pub const fn has_room(self) -> bool {
+        self.0 & Self::TX_EMPTY != 0
+    }
+    /// Get number of free FIFO entries.
+    #[inline]
+    pub const fn free_count(self) -> u16 {
+        ((self.0 & Self::TXE_CNT) >> 8) as u16
+    }
+    /// If FIFO has fallen to trigger level.
+    #[inline]
+    pub const fn is_empty(self) -> bool {
+        self.0 & Self::TXE_INT != 0
+    }
+    /// Clear FIFO empty flag on write.
+    #[inline]
+    pub const fn clear_empty(self) -> Self {
+        Self(self.0 | Self::TXE_INT)
+    }
+    /// If FIFO has underrun.
+    #[inline]
+    pub const fn has_underrun(self) -> bool {
+        self.0 & Self::TXU_INT != 0
+    }
+    /// Clear FIFO underrun flag on write.
+    #[inline]
+    pub const fn clear_underrun(self) -> Self {
+        Self(self.0 | Self::TXU_INT)
+    }
+    /// If FIFO has overrun.
+    #[inline]
+    pub const fn has_overrun(self) -> bool {
+        self.0 & Self::TXO_INT != 0
+    }
+    /// Clear FIFO overrun flag on write.
+    #[inline]
+    pub const fn clear_overrun(self) -> Self {
+        Self(self.0 | Self::TXO_INT)
+    }
+}
+
+impl Default for DacFifoStatus {
+    #[inline]
+    fn default() -> Self {
+        Self(0x0000_0000)
+    }
+}
+
+/// Analog input of an ADC channel.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Input {
+    /// Microphone input through programmable gain amplifier.
+    Mic,
+    /// Line input; ADC1 takes left channel, ADC2 takes right channel.
+    LineIn,
+    /// FM radio input; ADC1 takes left channel, ADC2 takes right channel.
+    FmIn,
+}
+
+/// Microphone bias voltage.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum MicBiasVoltage {
+    /// 1.88 V.
+    V1_88,
+    /// 2.09 V.
+    V2_09,
+    /// 2.33 V.
+    V2_33,
+    /// 2.50 V.
+    V2_50,
+}
+
+/// ADC FIFO control register.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct AdcFifoControl(u32);
+
+impl AdcFifoControl {
+    const ADC_FS: u32 = 0x7 << 29;
+    const EN_AD: u32 = 1 << 28;
+    const RX_FIFO_MODE: u32 = 1 << 24;
+    const RX_SAMPLE_BITS: u32 = 1 << 16;
+    const RX_FIFO_TRG_LEVEL: u32 = 0x7F << 4;
+    const ADC_DRQ_EN: u32 = 1 << 3;
+    const ADC_IRQ_EN: u32 = 1 << 2;
+    const ADC_OVERRUN_IRQ_EN: u32 = 1 << 1;
+    const ADC_FIFO_FLUSH: u32 = 1 << 0;
+
+    /// Get sample rate.
+    #[inline]
+    pub const fn sample_rate(self) -> SampleRate {
+        SampleRate::from_bits((self.0 & Self::ADC_FS) >> 29)
+    }
+    /// Set sample rate.
+    #[inline]
+    pub const fn set_sample_rate(self, val: SampleRate) -> Self {
+        Self((self.0 & !Self::ADC_FS) | ((val as u32) << 29))
+    }
+    /// If digital ADC is enabled.
+    #[inline]
+    pub const fn is_enabled(self) -> bool {
+        self.0 & Self::EN_AD != 0
+    }
+    /// Enable digital ADC.
+    #[inline]
+    pub const fn enable(self) -> Self {
+        Self(self.0 | Self::EN_AD)
+    }
+    /// Disable digital ADC.
+    #[inline]
+    pub const fn disable(self) -> Self {
+        Self(self.0 & !Self::EN_AD)
+    }
+    /// If samples are sign-extended in low bits of data register, or in high bits otherwise.
+    #[inline]
+    pub const fn is_lsb_aligned(self) -> bool {
+        self.0 & Self::RX_FIFO_MODE != 0
+    }
+    /// Set whether samples are sign-extended in low bits of data register.
+    #[inline]
+    pub const fn set_lsb_aligned(self, val: bool) -> Self {
+        if val {
+            Self(self.0 | Self::RX_FIFO_MODE)
+        } else {
+            Self(self.0 & !Self::RX_FIFO_MODE)
+        }
+    }
+    /// If samples are 20 bits wide, or 16 bits otherwise.
+    #[inline]
+    pub const fn is_20_bit(self) -> bool {
+        self.0 & Self::RX_SAMPLE_BITS != 0
+    }
+    /// Set whether samples are 20 bits wide, or 16 bits otherwise.
+    #[inline]
+    pub const fn set_20_bit(self, val: bool) -> Self {
+        if val {
+            Self(self.0 | Self::RX_SAMPLE_BITS)
+        } else {
+            Self(self.0 & !Self::RX_SAMPLE_BITS)
+        }
+    }
+    /// Get number of FIFO samples minus one that triggers DMA request.
+    #[inline]
+    pub const fn trigger_level(self) -> u8 {
+        ((self.0 & Self::RX_FIFO_TRG_LEVEL) >> 4) as u8
+    }
+    /// Set number of FIFO samples minus one that triggers DMA request, from 0 to 127.
+    #[inline]
+    pub const fn set_trigger_level(self, val: u8) -> Self {
+        Self((self.0 & !Self::RX_FIFO_TRG_LEVEL) | ((val as u32 & 0x7F) << 4))
+    }
+    /// If DMA request is enabled.
+    #[inline]
+    pub const fn is_drq_enabled(self) -> bool {
+        self.0 & Self::ADC_DRQ_EN != 0
+    }
+    /// Enable DMA request.
+    #[inline]
+    pub const fn enable_drq(self) -> Self {
+        Self(self.0 | Self::ADC_DRQ_EN)
+    }
+    /// Disable DMA request.
+    #[inline]
+    pub const fn disable_drq(self) -> Self {
+        Self(self.0 & !Self::ADC_DRQ_EN)
+    }
+    /// If data available interrupt is enabled, raised at trigger level.
+    #[inline]
+    pub const fn is_data_interrupt_enabled(self) -> bool {
+        self.0 & Self::ADC_IRQ_EN != 0
+    }
+    /// Enable data available interrupt.
+    #[inline]
+    pub const fn enable_data_interrupt(self) -> Self {
+        Self(self.0 | Self::ADC_IRQ_EN)
+    }
+    /// Disable data available interrupt.
+    #[inline]
+    pub const fn disable_data_interrupt(self) -> Self {
+        Self(self.0 & !Self::ADC_IRQ_EN)
+    }
+    /// If FIFO overrun interrupt is enabled.
+    #[inline]
+    pub const fn is_overrun_interrupt_enabled(self) -> bool {
+        self.0 & Self::ADC_OVERRUN_IRQ_EN != 0
+    }
+    /// Enable FIFO overrun interrupt.
+    #[inline]
+    pub const fn enable_overrun_interrupt(self) -> Self {
+        Self(self.0 | Self::ADC_OVERRUN_IRQ_EN)
+    }
+    /// Disable FIFO overrun interrupt.
+    #[inline]
+    pub const fn disable_overrun_interrupt(self) -> Self {
+        Self(self.0 & !Self::ADC_OVERRUN_IRQ_EN)
+    }
+    /// Flush FIFO, cleared by hardware when done.
+    #[inline]
+    pub const fn flush_fifo(self) -> Self {
+        Self(self.0 | Self::ADC_FIFO_FLUSH)
+    }
+}
+
+impl Default for AdcFifoControl {
+    #[inline]
+    fn default() -> Self {
+        Self(0x0000_0400)
+    }
+}
+
+/// ADC volume control register.
+///
+/// Volume is 0 dB at [`AdcVolume::ZERO_DB`], changing by 0.75 dB per step; zero mutes.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct AdcVolume(u32);
+
+impl AdcVolume {
+    /// Volume value of 0 dB.
+    pub const ZERO_DB: u8 = 0xA0;
+
+    /// Get volume of ADC channel `index`, from 0 for ADC1 to 2 for ADC3.
+    #[inline]
+    pub const fn volume(self, index: usize) -> u8 {
+        (self.0 >> (index * 8)) as u8
+    }
+    /// Set volume of ADC channel `index`, from 0 for ADC1 to 2 for ADC3.
+    #[inline]
+    pub const fn set_volume(self, index: usize, val: u8) -> Self {
+        let shift = index * 8;
+        Self((self.0 & !(0xFF << shift)) | ((val as u32) << shift))
+    }
+}
+
+impl Default for AdcVolume {
+    #[inline]
+    fn default() -> Self {
+        Self(0x00A0_A0A0)
+    }
+}
+
+/// ADC FIFO status register.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct AdcFifoStatus(u32);
+
+impl AdcFifoStatus {
+    const RXA: u32 = 1 << 23;
+    const RXA_CNT: u32 = 0x7F << 8;
+    const RXA_INT: u32 = 1 << 3;
+    const RXO_INT: u32 = 1 << 1;
+
+    /// If FIFO holds at least one sample.
+    #[inline]
+    pub const fn is_available(self) -> bool {
+        self.0 & Self::RXA != 0
+    }
+    /// Get number of samples in FIFO.
+    #[inline]
+    pub const fn available_count(self) -> u8 {
+        ((self.0 & Self::RXA_CNT) >> 8) as u8
+    }
+    /// If FIFO has reached trigger level.
+    #[inline]
+    pub const fn is_data_available(self) -> bool {
+        self.0 & Self::RXA_INT != 0
+    }
+    /// Clear data available flag on write.
+    #[inline]
+    pub const fn clear_data_available(self) -> Self {
+        Self(self.0 | Self::RXA_INT)
+    }
+    /// If FIFO has overrun.
+    #[inline]
+    pub const fn has_overrun(self) -> bool {
+        self.0 & Self::RXO_INT != 0
+    }
+    /// Clear FIFO overrun flag on write.
+    #[inline]
+    pub const fn clear_overrun(self) -> Self {
+        Self(self.0 | Self::RXO_INT)
+    }
+}
+
+impl Default for AdcFifoStatus {
+    #[inline]
+    fn default() -> Self {
+        Self(0x0000_0000)
+    }
+}
+
+/// ADC digital control register.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct AdcDigital(u32);
+
+impl AdcDigital {
+    const ADC_CHANNEL_EN: u32 = 0x7;
+
+    /// If ADC channel `index` is enabled, from 0 for ADC1 to 2 for ADC3.
+    #[inline]
+    pub const fn is_channel_enabled(self, index: usize) -> bool {
+        self.0 & (1 << index) != 0
+    }
+    /// Enable ADC channel `index`, from 0 for ADC1 to 2 for ADC3.
+    #[inline]
+    pub const fn enable_channel(self, index: usize) -> Self {
+        Self(self.0 | (1 << index))
+    }
+    /// Disable all ADC channels.
+    #[inline]
+    pub const fn disable_channels(self) -> Self {
+        Self(self.0 & !Self::ADC_CHANNEL_EN)
+    }
+}
+
+/// ADC analog control register.
+///
+/// ADC3 has microphone input only; line and FM input bits are reserved on it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct AdcAnalog(u32);
+
+impl AdcAnalog {
+    const ADC_EN: u32 = 1 << 31;
+    const MIC_PGA_EN: u32 = 1 << 30;
+    const MIC_SIN_EN: u32 = 1 << 28;
+    const FMIN_EN: u32 = 1 << 27;
+    const FMIN_GAIN: u32 = 1 << 26;
+    const LINEIN_EN: u32 = 1 << 23;
+    const LINEIN_GAIN: u32 = 1 << 22;
+    const PGA_GAIN: u32 = 0x1F << 8;
+
+    #[inline]
+    const fn input_mask(input: Input) -> u32 {
+        match input {
+            Input::Mic => Self::MIC_SIN_EN,
+            Input::LineIn => Self::LINEIN_EN,
+            Input::FmIn => Self::FMIN_EN,
+        }
+    }
+    /// If ADC channel is enabled.
+    #[inline]
+    pub const fn is_enabled(self) -> bool {
+        self.0 & Self::ADC_EN != 0
+    }
+    /// Enable ADC channel and its microphone amplifier.
+    #[inline]
+    pub const fn enable(self) -> Self {
+        Self(self.0 | Self::ADC_EN | Self::MIC_PGA_EN)
+    }
+    /// Disable ADC channel and its microphone amplifier.
+    #[inline]
+    pub const fn disable(self) -> Self {
+        Self(self.0 & !(Self::ADC_EN | Self::MIC_PGA_EN))
+    }
+    /// If `input` is routed to ADC channel.
+    #[inline]
+    pub const fn is_input_enabled(self, input: Input) -> bool {
+        self.0 & Self::input_mask(input) != 0
+    }
+    /// Route `input` to ADC channel.
+    #[inline]
+    pub const fn enable_input(self, input: Input) -> Self {
+        Self(self.0 | Self::input_mask(input))
+    }
+    /// Disconnect `input` from ADC channel.
+    #[inline]
+    pub const fn disable_input(self, input: Input) -> Self {
+        Self(self.0 & !Self::input_mask(input))
+    }
+    /// If line input is boosted by 6 dB.
+    #[inline]
+    pub const fn is_line_in_boosted(self) -> bool {
+        self.0 & Self::LINEIN_GAIN != 0
+    }
+    /// Set whether line input is boosted by 6 dB.
+    #[inline]
+    pub const fn set_line_in_boost(self, val: bool) -> Self {
+        if val {
+            Self(self.0 | Self::LINEIN_GAIN)
+        } else {
+            Self(self.0 & !Self::LINEIN_GAIN)
+        }
+    }
+    /// If FM input is boosted by 6 dB.
+    #[inline]
+    pub const fn is_fm_in_boosted(self) -> bool {
+        self.0 & Self::FMIN_GAIN != 0
+    }
+    /// Set whether FM input is boosted by 6 dB.
+    #[inline]
+    pub const fn set_fm_in_boost(self, val: bool) -> Self {
+        if val {
+            Self(self.0 | Self::FMIN_GAIN)
+        } else {
+            Self(self.0 & !Self::FMIN_GAIN)
+        }
+    }
+    /// Get microphone amplifier gain selector.
+    ///
+    /// Selector 0 is 0 dB, 1 to 3 are 6 dB, and 4 to 31 are 6 to 33 dB in 1-dB steps.
+    #[inline]
+    pub const fn pga_gain(self) -> u8 {
+        ((self.0 & Self::PGA_GAIN) >> 8) as u8
+    }
+    /// Set microphone amplifier gain selector.
+    #[inline]
+    pub const fn set_pga_gain(self, val: u8) -> Self {
+        Self((self.0 & !Self::PGA_GAIN) | ((val as u32 & 0x1F) << 8))
+    }
+}
+
+/// DAC analog control register.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct DacAnalog(u32);
+
+impl DacAnalog {
+    const DACLEN: u32 = 1 << 15;
+    const DACREN: u32 = 1 << 14;
+    const LINEOUTLEN: u32 = 1 << 13;
+    const DMLEN: u32 = 1 << 12;
+    const LINEOUTREN: u32 = 1 << 11;
+    const DMREN: u32 = 1 << 10;
+    const LINEOUT_VOL: u32 = 0x1F;
+
+    /// If left and right analog DACs are enabled.
+    #[inline]
+    pub const fn is_dac_enabled(self) -> bool {
+        self.0 & (Self::DACLEN | Self::DACREN) == Self::DACLEN | Self::DACREN
+    }
+    /// Enable left and right analog DACs.
+    #[inline]
+    pub const fn enable_dac(self) -> Self {
+        Self(self.0 | Self::DACLEN | Self::DACREN)
+    }
+    /// Disable left and right analog DACs.
+    #[inline]
+    pub const fn disable_dac(self) -> Self {
+        Self(self.0 & !(Self::DACLEN | Self::DACREN))
+    }
+    /// If left and right DAC outputs are muted.
+    #[inline]
+    pub const fn is_muted(self) -> bool {
+        self.0 & (Self::DMLEN | Self::DMREN) == 0
+    }
+    /// Mute left and right DAC outputs.
+    #[inline]
+    pub const fn mute(self) -> Self {
+        Self(self.0 & !(Self::DMLEN | Self::DMREN))
+    }
+    /// Unmute left and right DAC outputs.
+    #[inline]
+    pub const fn unmute(self) -> Self {
+        Self(self.0 | Self::DMLEN | Self::DMREN)
+    }
+    /// If left and right line outputs are enabled.
+    #[inline]
+    pub const fn is_line_out_enabled(self) -> bool {
+        self.0 & (Self::LINEOUTLEN | Self::LINEOUTREN) == Self::LINEOUTLEN | Self::LINEOUTREN
+    }
+    /// Enable left and right line outputs.
+    #[inline]
+    pub const fn enable_line_out(self) -> Self {
+        Self(self.0 | Self::LINEOUTLEN | Self::LINEOUTREN)
+    }
+    /// Disable left and right line outputs.
+    #[inline]
+    pub const fn disable_line_out(self) -> Self {
+        Self(self.0 & !(Self::LINEOUTLEN | Self::LINEOUTREN))
+    }
+    /// Get line output volume; 31 is 0 dB, changing by 1.5 dB per step.
+    #[inline]
+    pub const fn line_out_volume(self) -> u8 {
+        (self.0 & Self::LINEOUT_VOL) as u8
+    }
+    /// Set line output volume; 31 is 0 dB, changing by 1.5 dB per step.
+    #[inline]
+    pub const fn set_line_out_volume(self, val: u8) -> Self {
+        Self((self.0 & !Self::LINEOUT_VOL) | (val as u32 & 0x1F))
+    }
+}
+
+/// Microphone bias control register.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct MicBias(u32);
+
+impl MicBias {
+    const HMICBIASEN: u32 = 1 << 15;
+    const HBIASSEL: u32 = 0x3 << 13;
+    const MMICBIASEN: u32 = 1 << 7;
+    const MBIASSEL: u32 = 0x3 << 5;
+
+    /// If main microphone bias is enabled.
+    #[inline]
+    pub const fn is_main_enabled(self) -> bool {
+        self.0 & Self::MMICBIASEN != 0
+    }
+    /// Enable main microphone bias.
+    #[inline]
+    pub const fn enable_main(self) -> Self {
+        Self(self.0 | Self::MMICBIASEN)
+    }
+    /// Disable main microphone bias.
+    #[inline]
+    pub const fn disable_main(self) -> Self {
+        Self(self.0 & !Self::MMICBIASEN)
+    }
+    /// Get main microphone bias voltage.
+    #[inline]
+    pub const fn main_voltage(self) -> MicBiasVoltage {
+        voltage_from_bits((self.0 & Self::MBIASSEL) >> 5)
+    }
+    /// Set main microphone bias voltage.
+    #[inline]
+    pub const fn set_main_voltage(self, val: MicBiasVoltage) -> Self {
+        Self((self.0 & !Self::MBIASSEL) | ((val as u32) << 5))
+    }
+    /// If headset microphone bias is enabled.
+    #[inline]
+    pub const fn is_headset_enabled(self) -> bool {
+        self.0 & Self::HMICBIASEN != 0
+    }
+    /// Enable headset microphone bias.
+    #[inline]
+    pub const fn enable_headset(self) -> Self {
+        Self(self.0 | Self::HMICBIASEN)
+    }
+    /// Disable headset microphone bias.
+    #[inline]
+    pub const fn disable_headset(self) -> Self {
+        Self(self.0 & !Self::HMICBIASEN)
+    }
+    /// Get headset microphone bias voltage.
+    #[inline]
+    pub const fn headset_voltage(self) -> MicBiasVoltage {
+        voltage_from_bits((self.0 & Self::HBIASSEL) >> 13)
+    }
+    /// Set headset microphone bias voltage.
+    #[inline]
+    pub const fn set_headset_voltage(self, val: MicBiasVoltage) -> Self {
+        Self((self.0 & !Self::HBIASSEL) | ((val as u32) << 13))
+    }
+}
+
+#[inline]
+const fn voltage_from_bits(bits: u32) -> MicBiasVoltage {
+    match bits {
+        0 => MicBiasVoltage::V1_88,
+        1 => MicBiasVoltage::V2_09,
+        2 => MicBiasVoltage::V2_33,
+        _ => MicBiasVoltage::V2_50,
+    }
+}
+
+/// Headphone control register.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct Headphone(u32);
+
+impl Headphone {
+    const HPFB_BUF_EN: u32 = 1 << 31;
+    const HEADPHONE_GAIN: u32 = 0x7 << 28;
+    const HP_DRVEN: u32 = 1 << 21;
+    const HP_DRVOUTEN: u32 = 1 << 20;
+
+    /// If headphone driver and its output are enabled.
+    #[inline]
+    pub const fn is_enabled(self) -> bool {
+        self.0 & (Self::HP_DRVEN | Self::HP_DRVOUTEN) == Self::HP_DRVEN | Self::HP_DRVOUTEN
+    }
+    /// Enable headphone feedback buffer, driver and its output.
+    #[inline]
+    pub const fn enable(self) -> Self {
+        Self(self.0 | Self::HPFB_BUF_EN | Self::HP_DRVEN | Self::HP_DRVOUTEN)
+    }
+    /// Disable headphone feedback buffer, driver and its output.
+    #[inline]
+    pub const fn disable(self) -> Self {
+        Self(self.0 & !(Self::HPFB_BUF_EN | Self::HP_DRVEN | Self::HP_DRVOUTEN))
+    }
+    /// Get headphone attenuation; 0 is 0 dB, decreasing by 6 dB per step.
+    #[inline]
+    pub const fn attenuation(self) -> u8 {
+        ((self.0 & Self::HEADPHONE_GAIN) >> 28) as u8
+    }
+    /// Set headphone attenuation; 0 is 0 dB, decreasing by 6 dB per step to 7.
+    #[inline]
+    pub const fn set_attenuation(self, val: u8) -> Self {
+        Self((self.0 & !Self::HEADPHONE_GAIN) | ((val as u32 & 0x7) << 28))
+    }
+}
+
+/// Analog power control register.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct Power(u32);
+
+impl Power {
+    const ALDO_EN: u32 = 1 << 31;
+    const HPLDO_EN: u32 = 1 << 30;
+
+    /// If analog LDO is enabled.
+    #[inline]
+    pub const fn is_analog_ldo_enabled(self) -> bool {
+        self.0 & Self::ALDO_EN != 0
+    }
+    /// Enable analog LDO.
+    #[inline]
+    pub const fn enable_analog_ldo(self) -> Self {
+        Self(self.0 | Self::ALDO_EN)
+    }
+    /// Disable analog LDO.
+    #[inline]
+    pub const fn disable_analog_ldo(self) -> Self {
+        Self(self.0 & !Self::ALDO_EN)
+    }
+    /// If headphone LDO is enabled.
+    #[inline]
+    pub const fn is_headphone_ldo_enabled(self) -> bool {
+        self.0 & Self::HPLDO_EN != 0
+    }
+    /// Enable headphone LDO.
+    #[inline]
+    pub const fn enable_headphone_ldo(self) -> Self {
+        Self(self.0 | Self::HPLDO_EN)
+    }
+    /// Disable headphone LDO.
+    #[inline]
+    pub const fn disable_headphone_ldo(self) -> Self {
+        Self(self.0 & !Self::HPLDO_EN)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        AdcAnalog, AdcDigital, AdcFifoControl, AdcFifoStatus, AdcVolume, DacAnalog, DacDebug,
+        DacDigital, DacFifoControl, DacFifoStatus, DacPattern, DacVolume, FifoMode, Headphone,
+        Input, MicBias, MicBiasVoltage, Power, RegisterBlock, SampleRate,
+    };
+    use core::mem::offset_of;
+
+    #[test]
+    fn offset_audio_codec() {
+        assert_eq!(offset_of!(RegisterBlock, dac_digital), 0x00);
+        assert_eq!(offset_of!(RegisterBlock, dac_volume), 0x04);
+        assert_eq!(offset_of!(RegisterBlock, dac_fifo_control), 0x10);
+        assert_eq!(offset_of!(RegisterBlock, dac_fifo_status), 0x14);
+        assert_eq!(offset_of!(RegisterBlock, dac_tx_data), 0x20);
+        assert_eq!(offset_of!(RegisterBlock, dac_tx_count), 0x24);
+        assert_eq!(offset_of!(RegisterBlock, dac_debug), 0x28);
+        assert_eq!(offset_of!(RegisterBlock, adc_fifo_control), 0x30);
+        assert_eq!(offset_of!(RegisterBlock, adc_volume), 0x34);
+        assert_eq!(offset_of!(RegisterBlock, adc_fifo_status), 0x38);
+        assert_eq!(offset_of!(RegisterBlock, adc_rx_data), 0x40);
+        assert_eq!(offset_of!(RegisterBlock, adc_rx_count), 0x44);
+        assert_eq!(offset_of!(RegisterBlock, adc_digital), 0x50);
+        assert_eq!(offset_of!(RegisterBlock, adc), 0x300);
+        assert_eq!(offset_of!(RegisterBlock, dac_analog), 0x310);
+        assert_eq!(offset_of!(RegisterBlock, mic_bias), 0x314);
+        assert_eq!(offset_of!(RegisterBlock, headphone), 0x340);
+        assert_eq!(offset_of!(RegisterBlock, power), 0x348);
+    }
+
+    #[test]
+    fn struct_dac_digital_functions() {
+        let val = DacDigital::default()
+            .enable()
+            .enable_high_pass()
+            .set_attenuation(63);
+        assert!(val.is_enabled());
+        assert!(val.is_high_pass_enabled());
+        assert_eq!(val.attenuation(), 63);
+        assert_eq!(val.0, 0x8007_F000);
+        assert_eq!(val.disable().disable_high_pass().0, 0x0003_F000);
+    }
+
+    #[test]
+    fn struct_dac_volume_functions() {
+        let val = DacVolume::default();
+        assert!(!val.is_enabled());
+        assert_eq!(val.left(), DacVolume::ZERO_DB);
+        assert_eq!(val.right(), DacVolume::ZERO_DB);
+
+        let val = val.enable().set_left(0x80).set_right(0xFF);
+        assert_eq!(val.0, 0x0001_80FF);
+        assert_eq!(val.disable().set_left(0).0, 0x0000_00FF);
+    }
+
+    #[test]
+    fn struct_dac_debug_functions() {
+        let val = DacDebug::default();
+        assert_eq!(val.pattern(), DacPattern::Fifo);
+        assert!(!val.is_loopback_enabled());
+
+        let val = val
+            .set_pattern(DacPattern::SineMinus6Db)
+            .set_swapped(true)
+            .enable_loopback();
+        assert_eq!(val.pattern(), DacPattern::SineMinus6Db);
+        assert!(val.is_swapped());
+        assert!(val.is_loopback_enabled());
+        assert_eq!(val.0, 0x0000_0241);
+
+        let val = val
+            .set_pattern(DacPattern::Silent)
+            .set_swapped(false)
+            .disable_loopback();
+        assert_eq!(val.pattern(), DacPattern::Silent);
+        assert_eq!(val.0, 0x0000_0600);
+    }
+
+    #[test]
+    fn struct_dac_fifo_control_functions() {
+        let val = DacFifoControl::default();
+        assert_eq!(val.sample_rate(), SampleRate::Hz48000);
+        assert_eq!(val.trigger_level(), 0x40);
+
+        let val = val
+            .set_sample_rate(SampleRate::Hz96000)
+            .set_fifo_mode(FifoMode::Lsb16)
+            .set_trigger_level(0x20)
+            .set_mono(true)
+            .set_20_bit(true)
+            .enable_drq()
+            .enable_empty_interrupt()
+            .enable_underrun_interrupt();
+        assert_eq!(val.sample_rate(), SampleRate::Hz96000);
+        assert_eq!(val.fifo_mode(), FifoMode::Lsb16);
+        assert!(val.is_mono());
+        assert!(val.is_20_bit());
+        assert!(val.is_drq_enabled());
+        assert!(val.is_empty_interrupt_enabled());
+        assert!(val.is_underrun_interrupt_enabled());
+        assert_eq!(val.0, 0xE300_207C);
+
+        let val = val
+            .set_mono(false)
+            .set_20_bit(false)
+            .disable_drq()
+            .disable_empty_interrupt()
+            .disable_underrun_interrupt()
+            .flush_fifo();
+        assert_eq!(val.0, 0xE300_2001);
+    }
+
+    #[test]
+    fn struct_dac_fifo_status_functions() {
+        let val = DacFifoStatus(0x0080_8004);
+        assert!(val.has_room());
+        assert_eq!(val.free_count(), 0x80);
+        assert!(!val.is_empty());
+        assert!(val.has_underrun());
+        assert!(!val.has_overrun());
+        assert_eq!(
+            DacFifoStatus::default()
+                .clear_empty()
+                .clear_underrun()
+                .clear_overrun()
+                .0,
+            0x0000_000E
+        );
+    }
+
+    #[test]
+    fn struct_dac_analog_functions() {
+        let val = DacAnalog(0x0)
+            .enable_dac()
+            .unmute()
+            .enable_line_out()
+            .set_line_out_volume(31);
+        assert!(val.is_dac_enabled());
+        assert!(!val.is_muted());
+        assert!(val.is_line_out_enabled());
+        assert_eq!(val.line_out_volume(), 31);
+        assert_eq!(val.0, 0x0000_FC1F);
+
+        let val = val.disable_dac().mute().disable_line_out();
+        assert!(val.is_muted());
+        assert_eq!(val.0, 0x0000_001F);
+    }
+
+    #[test]
+    fn struct_headphone_functions() {
+        let val = Headphone(0x0).enable().set_attenuation(7);
+        assert!(val.is_enabled());
+        assert_eq!(val.attenuation(), 7);
+        assert_eq!(val.0, 0xF030_0000);
+        assert_eq!(val.disable().set_attenuation(0).0, 0x0000_0000);
+    }
+
+    #[test]
+    fn struct_adc_fifo_control_functions() {
+        let val = AdcFifoControl::default();
+        assert_eq!(val.trigger_level(), 0x40);
+        assert_eq!(val.sample_rate(), SampleRate::Hz48000);
+
+        let val = val
+            .set_sample_rate(SampleRate::Hz16000)
+            .enable()
+            .set_lsb_aligned(true)
+            .set_20_bit(true)
+            .set_trigger_level(0x1F)
+            .enable_drq()
+            .enable_data_interrupt()
+            .enable_overrun_interrupt();
+        assert_eq!(val.sample_rate(), SampleRate::Hz16000);
+        assert!(val.is_enabled());
+        assert!(val.is_lsb_aligned());
+        assert!(val.is_20_bit());
+        assert!(val.is_drq_enabled());
+        assert!(val.is_data_interrupt_enabled());
+        assert!(val.is_overrun_interrupt_enabled());
+        assert_eq!(val.0, 0x7101_01FE);
+
+        let val = val
+            .disable()
+            .set_lsb_aligned(false)
+            .set_20_bit(false)
+            .disable_drq()
+            .disable_data_interrupt()
+            .disable_overrun_interrupt()
+            .flush_fifo();
+        assert_eq!(val.0, 0x6000_01F1);
+    }
+
+    #[test]
+    fn struct_adc_volume_functions() {
+        let val = AdcVolume::default();
+        assert_eq!(val.volume(0), AdcVolume::ZERO_DB);
+        assert_eq!(val.volume(2), AdcVolume::ZERO_DB);
+
+        let val = val.set_volume(1, 0xFF).set_volume(2, 0);
+        assert_eq!(val.volume(1), 0xFF);
+        assert_eq!(val.0, 0x0000_FFA0);
+    }
+
+    #[test]
+    fn struct_adc_fifo_status_functions() {
+        let val = AdcFifoStatus(0x0080_200A);
+        assert!(val.is_available());
+        assert_eq!(val.available_count(), 0x20);
+        assert!(val.is_data_available());
+        assert!(val.has_overrun());
+        assert_eq!(
+            AdcFifoStatus::default()
+                .clear_data_available()
+                .clear_overrun()
+                .0,
+            0x0000_000A
+        );
+    }
+
+    #[test]
+    fn struct_adc_digital_functions() {
+        let val = AdcDigital(0x0).enable_channel(0).enable_channel(1);
+        assert!(val.is_channel_enabled(1));
+        assert!(!val.is_channel_enabled(2));
+        assert_eq!(val.0, 0x0000_0003);
+        assert_eq!(val.disable_channels().0, 0x0000_0000);
+    }
+
+    #[test]
+    fn struct_adc_analog_functions() {
+        let val = AdcAnalog(0x0)
+            .enable()
+            .enable_input(Input::Mic)
+            .enable_input(Input::LineIn)
+            .set_line_in_boost(true)
+            .set_pga_gain(31);
+        assert!(val.is_enabled());
+        assert!(val.is_input_enabled(Input::LineIn));
+        assert!(!val.is_input_enabled(Input::FmIn));
+        assert!(val.is_line_in_boosted());
+        assert_eq!(val.pga_gain(), 31);
+        assert_eq!(val.0, 0xD0C0_1F00);
+
+        let val = val
+            .disable()
+            .disable_input(Input::Mic)
+            .enable_input(Input::FmIn)
+            .set_fm_in_boost(true)
+            .set_line_in_boost(false)
+            .set_pga_gain(0);
+        assert!(val.is_fm_in_boosted());
+        assert_eq!(val.0, 0x0C80_0000);
+    }
+
+    #[test]
+    fn struct_mic_bias_functions() {
+        let val = MicBias(0x0)
+            .enable_main()
+            .set_main_voltage(MicBiasVoltage::V2_50)
+            .enable_headset()
+            .set_headset_voltage(MicBiasVoltage::V2_09);
+        assert!(val.is_main_enabled());
+        assert!(val.is_headset_enabled());
+        assert_eq!(val.main_voltage(), MicBiasVoltage::V2_50);
+        assert_eq!(val.headset_voltage(), MicBiasVoltage::V2_09);
+        assert_eq!(val.0, 0x0000_A0E0);
+
+        let val = val.disable_main().disable_headset();
+        assert_eq!(val.0, 0x0000_2060);
+    }
+
+    #[test]
+    fn struct_power_functions() {
+        let val = Power(0x0).enable_analog_ldo().enable_headphone_ldo();
+        assert!(val.is_analog_ldo_enabled());
+        assert!(val.is_headphone_ldo_enabled());
+        assert_eq!(val.0, 0xC000_0000);
+        assert_eq!(val.disable_analog_ldo().0, 0x4000_0000);
+        assert_eq!(val.disable_headphone_ldo().0, 0x8000_0000);
+    }
+}
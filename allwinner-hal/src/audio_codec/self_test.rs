@@ -0,0 +1,142 @@
+use super::{AudioCodec, DacPattern, Frame, RegisterBlock};
+
+/// One period of a -6-dB sine wave, a 1-kHz tone at 48-kHz sample rate.
+pub const SINE_TABLE: [i16; 48] = [
+    0, 2139, 4240, 6270, 8192, 9974, 11585, 12998, 14189, 15137, 15826, 16244, 16384, 16244, 15826,
+    15137, 14189, 12998, 11585, 9974, 8192, 6270, 4240, 2139, 0, -2139, -4240, -6270, -8192, -9974,
+    -11585, -12998, -14189, -15137, -15826, -16244, -16384, -16244, -15826, -15137, -14189, -12998,
+    -11585, -9974, -8192, -6270, -4240, -2139,
+];
+
+/// Fill `frames` with sine tone of [`SINE_TABLE`] on both channels, starting at `phase`.
+///
+/// Returns phase of the frame after the last one, to continue the tone in next buffer;
+/// for example fill each `Playback` buffer in turn to play the tone over DMA.
+#[inline]
+pub fn fill_sine(frames: &mut [Frame], phase: usize) -> usize {
+    let mut phase = phase % SINE_TABLE.len();
+    for frame in frames {
+        *frame = [SINE_TABLE[phase]; 2];
+        phase = (phase + 1) % SINE_TABLE.len();
+    }
+    phase
+}
+
+/// Get peak absolute sample of left and right channels of `frames`.
+#[inline]
+pub fn peak(frames: &[Frame]) -> [u16; 2] {
+    frames.iter().fold([0, 0], |[left, right], frame| {
+        [
+            left.max(frame[0].unsigned_abs()),
+            right.max(frame[1].unsigned_abs()),
+        ]
+    })
+}
+
+/// Test tone played by [`AudioCodec::self_test`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ToneSource {
+    /// Built-in -6-dB sine generator of the DAC.
+    Generator,
+    /// [`SINE_TABLE`] written into DAC FIFO.
+    Table,
+}
+
+/// Result of audio codec loopback self-test.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct SelfTest {
+    /// Peak absolute sample captured by ADC1 and ADC2.
+    pub peak: [u16; 2],
+}
+
+impl SelfTest {
+    /// Lowest peak accepted by [`SelfTest::passed`], 6 dB below the test tone.
+    pub const MIN_PEAK: u16 = 8192;
+
+    /// Check if both channels captured the -6-dB test tone, at most 6 dB weaker.
+    #[inline]
+    pub const fn passed(self) -> bool {
+        self.peak[0] >= Self::MIN_PEAK && self.peak[1] >= Self::MIN_PEAK
+    }
+}
+
+impl<CODEC: AsRef<RegisterBlock>> AudioCodec<CODEC> {
+    /// Play a -6-dB test tone, loop DAC output back into ADC1 and ADC2, and capture it
+    /// into `frames` by polling FIFOs.
+    ///
+    /// Peak amplitude is taken over the second half of `frames`, after digital filters have
+    /// settled; a few hundred frames are enough. Sample rates and ADC volume are set
+    /// beforehand, and DMA streams should be stopped. DAC and ADC are stopped and loopback
+    /// is disabled on return.
+    #[inline]
+    pub fn self_test(&mut self, source: ToneSource, frames: &mut [Frame]) -> SelfTest {
+        let pattern = match source {
+            ToneSource::Generator => DacPattern::SineMinus6Db,
+            ToneSource::Table => DacPattern::Fifo,
+        };
+        unsafe {
+            self.codec
+                .as_ref()
+                .dac_debug
+                .modify(|val| val.set_pattern(pattern).enable_loopback())
+        };
+        self.start_dac(false);
+        self.start_adc(false);
+        let regs = self.codec.as_ref();
+        let samples = frames.as_flattened_mut();
+        let (mut written, mut read) = (0, 0);
+        while read < samples.len() {
+            if source == ToneSource::Table {
+                while regs.dac_fifo_status.read().has_room() {
+                    let sample = SINE_TABLE[(written / 2) % SINE_TABLE.len()];
+                    unsafe { regs.dac_tx_data.write(sample as u16 as u32) };
+                    written += 1;
+                }
+            }
+            if regs.adc_fifo_status.read().is_available() {
+                samples[read] = regs.adc_rx_data.read() as u16 as i16;
+                read += 1;
+            }
+        }
+        self.stop_adc_dma();
+        self.stop_dac_dma();
+        unsafe {
+            self.codec
+                .as_ref()
+                .dac_debug
+                .modify(|val| val.set_pattern(DacPattern::Fifo).disable_loopback())
+        };
+        SelfTest {
+            peak: peak(&frames[frames.len() / 2..]),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{SINE_TABLE, SelfTest, fill_sine, peak};
+
+    #[test]
+    fn function_fill_sine() {
+        let mut frames = [[0; 2]; 30];
+        assert_eq!(fill_sine(&mut frames, 0), 30);
+        assert_eq!(frames[12], [16384, 16384]);
+        assert_eq!(fill_sine(&mut frames, 30), 12);
+        assert_eq!(frames[6], [-16384, -16384]);
+        assert_eq!(fill_sine(&mut frames, 48 + 1), 31);
+        assert_eq!(frames[0], [SINE_TABLE[1]; 2]);
+    }
+
+    #[test]
+    fn function_peak() {
+        assert_eq!(peak(&[]), [0, 0]);
+        assert_eq!(peak(&[[100, -300], [-32768, 20]]), [32768, 300]);
+        assert!(
+            SelfTest {
+                peak: [16000, 9000]
+            }
+            .passed()
+        );
+        assert!(!SelfTest { peak: [16000, 100] }.passed());
+    }
+}
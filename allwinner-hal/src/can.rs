@@ -0,0 +1,704 @@
+//! Controller Area Network.
+//!
+//! CAN controller transmits and receives classic CAN 2.0B frames with standard or extended
+//! identifiers, at bitrates up to 1 Mbit/s. Received frames are queued in a hardware FIFO
+//! after passing the acceptance [`Filter`].
+//!
+//! [`Can`] implements `embedded_can::nb::Can` and `embedded_can::blocking::Can`, so that
+//! generic protocol stacks send and receive [`Frame`]s on it. Interrupts are raised on
+//! received frames, free transmit buffer and bus errors.
+//!
+//! [`Can::status`] reports error state and error counters as [`CanStatus`]. After too many
+//! transmit errors controller goes bus-off; depending on [`BusOffRecovery`] it rejoins the
+//! bus by itself on the next transmission, or after [`Can::recover`]. The controller handles
+//! classic CAN only; CAN FD frames are not supported by the silicon.
+//!
+//! [`Can::split`] gives [`CanTx`] and [`CanRx`] halves awaiting frames with interrupts routed
+//! to [`on_interrupt`]. A [`Dispatcher`] drives [`CanRx`] and hands each received frame to
+//! [`Subscriber`]s whose [`IdFilter`] matches it, so that several tasks wait for their own
+//! identifiers on one controller.
+//!
+//! On D1-like chips CAN is documented on T113 and R528, with CAN0 on `PB2`/`PB3` and CAN1 on
+//! `PB4`/`PB5`.
+//!
+//! This module is available with `embedded-can` feature.
+
+mod dispatch;
+mod frame;
+mod register;
+pub use dispatch::*;
+pub use frame::*;
+pub use register::*;
+
+use crate::ccu::{self, ClockGate, Clocks};
+use crate::time::{Deadline, TimeoutError};
+use crate::waker::WakerSet;
+use embedded_time::rate::Baud;
+
+/// Number of CAN controllers.
+const CAN_COUNT: usize = 2;
+
+static TX_WAKERS: WakerSet<CAN_COUNT> = WakerSet::new();
+static RX_WAKERS: WakerSet<CAN_COUNT> = WakerSet::new();
+
+const TX_INTERRUPTS: [Interrupt; 2] = [Interrupt::Transmit, Interrupt::ErrorWarning];
+const RX_INTERRUPTS: [Interrupt; 2] = [Interrupt::Receive, Interrupt::DataOverrun];
+
+/// CAN interrupt entry point of controller `I`.
+///
+/// Call this function from CAN interrupt handler when using [`CanTx`] or [`CanRx`].
+/// Interrupts of the half that raised them are disabled and its waiting task is woken;
+/// pending status is left for that task to handle.
+#[inline]
+pub fn on_interrupt<const I: usize>(can: &RegisterBlock) {
+    let enabled = can.int_enable.read();
+    let status = can.int_status.read();
+    for (interrupts, wakers) in [(TX_INTERRUPTS, &TX_WAKERS), (RX_INTERRUPTS, &RX_WAKERS)] {
+        if interrupts
+            .iter()
+            .any(|&i| enabled.has_interrupt(i) && status.has_interrupt(i))
+        {
+            unsafe {
+                can.int_enable.modify(|val| {
+                    interrupts
+                        .iter()
+                        .fold(val, |val, &i| val.disable_interrupt(i))
+                })
+            };
+            wakers.wake(I);
+        }
+    }
+}
+
+/// CAN configuration structure.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Config {
+    /// Bus bitrate, from 10 kbit/s to 1 Mbit/s.
+    pub bitrate: Baud,
+    /// Operating mode.
+    pub mode: OperatingMode,
+    /// Recovery policy after bus-off.
+    pub bus_off_recovery: BusOffRecovery,
+}
+
+impl Default for Config {
+    #[inline]
+    fn default() -> Self {
+        use embedded_time::rate::Extensions;
+        Self {
+            bitrate: 500_000.Bd(),
+            mode: OperatingMode::Normal,
+            bus_off_recovery: BusOffRecovery::Automatic,
+        }
+    }
+}
+
+/// Recovery policy after controller went bus-off.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum BusOffRecovery {
+    /// Start recovery on the next transmission, which waits until controller rejoins the bus.
+    Automatic,
+    /// Report [`Error::BusOff`] on transmission until [`Can::recover`] is called.
+    Manual,
+}
+
+/// Fault confinement state of controller.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ErrorState {
+    /// Both error counters are below error warning limit.
+    Active,
+    /// An error counter reached error warning limit.
+    Warning,
+    /// An error counter reached 128; controller sends passive error flags only.
+    Passive,
+    /// Transmit error counter exceeded 255; controller is off the bus.
+    BusOff,
+}
+
+/// Error state and counters of controller.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct CanStatus {
+    /// Fault confinement state.
+    pub state: ErrorState,
+    /// Transmit error counter.
+    pub transmit_errors: u8,
+    /// Receive error counter.
+    pub receive_errors: u8,
+    /// Kind of the last bus error, valid after [`Interrupt::BusError`].
+    pub last_error: ErrorCode,
+    /// If the last bus error occurred while receiving.
+    pub last_error_receiving: bool,
+}
+
+impl CanStatus {
+    /// Error state and counters from status and error counter register values.
+    #[inline]
+    pub const fn from_registers(status: Status, counter: ErrorCounter) -> Self {
+        let transmit_errors = counter.transmit_errors();
+        let receive_errors = counter.receive_errors();
+        let state = if status.is_bus_off() {
+            ErrorState::BusOff
+        } else if transmit_errors >= 128 || receive_errors >= 128 {
+            ErrorState::Passive
+        } else if status.is_error_warning() {
+            ErrorState::Warning
+        } else {
+            ErrorState::Active
+        };
+        Self {
+            state,
+            transmit_errors,
+            receive_errors,
+            last_error: status.error_code(),
+            last_error_receiving: status.is_receive_error(),
+        }
+    }
+}
+
+/// Bit timing in time quanta of CAN clock.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct BitTiming {
+    /// Clock prescaler, in 1..=1024.
+    pub prescaler: u16,
+    /// Time quanta before sample point, excluding synchronization segment, in 1..=16.
+    pub segment1: u8,
+    /// Time quanta after sample point, in 1..=8.
+    pub segment2: u8,
+    /// Synchronization jump width, in 1..=4.
+    pub sync_jump_width: u8,
+}
+
+impl BitTiming {
+    /// Find bit timing for `bitrate` from `clock`, with sample point near 87.5%.
+    ///
+    /// Returns `None` if `bitrate` can't be divided exactly from `clock`.
+    #[inline]
+    pub const fn from_bitrate(clock: u32, bitrate: u32) -> Option<Self> {
+        if bitrate == 0 {
+            return None;
+        }
+        // Prefer more time quanta per bit for finer sample point placement.
+        let mut quanta = 25;
+        while quanta >= 8 {
+            let Some(step) = bitrate.checked_mul(quanta) else {
+                return None;
+            };
+            if clock.is_multiple_of(step) && clock / step <= 1024 {
+                let segment2 = ((quanta + 4) / 8) as u8;
+                let segment1 = (quanta - 1) as u8 - segment2;
+                if segment1 <= 16 {
+                    return Some(Self {
+                        prescaler: (clock / step) as u16,
+                        segment1,
+                        segment2,
+                        sync_jump_width: if segment2 < 4 { segment2 } else { 4 },
+                    });
+                }
+            }
+            quanta -= 1;
+        }
+        None
+    }
+}
+
+/// CAN error.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Error {
+    /// Received frames were lost for receive FIFO being full.
+    Overrun,
+    /// Controller is bus-off after too many transmit errors, with manual recovery.
+    BusOff,
+    /// Transmit buffer did not become free, or controller did not recover from bus-off,
+    /// before crate-wide blocking timeout.
+    Timeout,
+    /// Bitrate can't be divided exactly from APB1 clock.
+    Bitrate,
+}
+
+impl embedded_can::Error for Error {
+    #[inline]
+    fn kind(&self) -> embedded_can::ErrorKind {
+        match self {
+            Error::Overrun => embedded_can::ErrorKind::Overrun,
+            Error::BusOff | Error::Timeout | Error::Bitrate => embedded_can::ErrorKind::Other,
+        }
+    }
+}
+
+/// Managed CAN structure with peripheral and pads.
+#[derive(Debug)]
+pub struct Can<CAN, const I: usize, PADS: Pads<I>> {
+    can: CAN,
+    pads: PADS,
+    mode: OperatingMode,
+    recovery: BusOffRecovery,
+}
+
+impl<CAN: AsRef<RegisterBlock>, const I: usize, PADS: Pads<I>> Can<CAN, I, PADS> {
+    /// Create a CAN instance accepting all frames, clocked from APB1 clock.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Bitrate`] if `config.bitrate` can't be divided exactly from APB1
+    /// clock; the controller is left untouched.
+    #[inline]
+    pub fn new(
+        can: CAN,
+        pads: PADS,
+        config: impl Into<Config>,
+        clocks: &Clocks,
+        ccu: &ccu::RegisterBlock,
+    ) -> Result<Self, Error> {
+        let Config {
+            bitrate,
+            mode,
+            bus_off_recovery,
+        } = config.into();
+        let timing = BitTiming::from_bitrate(clocks.apb1.0, bitrate.0).ok_or(Error::Bitrate)?;
+        // note(unsafe): async read and write using ccu registers
+        unsafe { PADS::Clock::reset(ccu) };
+        let regs = can.as_ref();
+        unsafe {
+            regs.mode.write(ModeSelect::default());
+            regs.int_enable.write(Interrupts::default());
+            regs.int_status.write(regs.int_status.read());
+            regs.bus_timing.write(
+                BusTiming::default()
+                    .set_prescaler(timing.prescaler)
+                    .set_segment1(timing.segment1)
+                    .set_segment2(timing.segment2)
+                    .set_sync_jump_width(timing.sync_jump_width),
+            );
+            let (code, mask) = Filter::accept_all().registers();
+            regs.acceptance_code.write(code);
+            regs.acceptance_mask.write(mask);
+            regs.mode.write(
+                ModeSelect::default()
+                    .set_single_filter()
+                    .set_operating_mode(mode)
+                    .leave_reset_mode(),
+            );
+        }
+        Ok(Self {
+            can,
+            pads,
+            mode,
+            recovery: bus_off_recovery,
+        })
+    }
+    /// Accept received frames passing `filter` only.
+    ///
+    /// Controller briefly leaves the bus while the filter is changed.
+    #[inline]
+    pub fn set_filter(&mut self, filter: Filter) -> Result<(), TimeoutError> {
+        let (code, mask) = filter.registers();
+        self.in_reset_mode(|regs| unsafe {
+            regs.acceptance_code.write(code);
+            regs.acceptance_mask.write(mask);
+        })
+    }
+    /// Get error state and counters of controller.
+    #[inline]
+    pub fn status(&self) -> CanStatus {
+        let regs = self.can.as_ref();
+        CanStatus::from_registers(regs.status.read(), regs.error_counter.read())
+    }
+    /// Start bus-off recovery if controller is bus-off.
+    ///
+    /// Controller rejoins the bus after observing 128 occurrences of 11 recessive bits, and
+    /// its error counters are cleared; until then [`Can::status`] reports
+    /// [`ErrorState::BusOff`].
+    #[inline]
+    pub fn recover(&mut self) {
+        start_recovery(self.can.as_ref());
+    }
+    /// Set bus-off recovery policy.
+    #[inline]
+    pub fn set_bus_off_recovery(&mut self, recovery: BusOffRecovery) {
+        self.recovery = recovery;
+    }
+    /// Set error warning limit of error counters, 96 by default.
+    ///
+    /// Controller briefly leaves the bus while the limit is changed.
+    #[inline]
+    pub fn set_error_warning_limit(&mut self, limit: u8) -> Result<(), TimeoutError> {
+        self.in_reset_mode(|regs| unsafe { regs.error_warning_limit.write(limit as u32) })
+    }
+    /// Get bit timing of controller.
+    #[inline]
+    pub fn bit_timing(&self) -> BitTiming {
+        let val = self.can.as_ref().bus_timing.read();
+        BitTiming {
+            prescaler: val.prescaler(),
+            segment1: val.segment1(),
+            segment2: val.segment2(),
+            sync_jump_width: val.sync_jump_width(),
+        }
+    }
+    /// Enable `interrupt`.
+    #[inline]
+    pub fn enable_interrupt(&mut self, interrupt: Interrupt) {
+        unsafe {
+            self.can
+                .as_ref()
+                .int_enable
+                .modify(|val| val.enable_interrupt(interrupt))
+        };
+    }
+    /// Disable `interrupt`.
+    #[inline]
+    pub fn disable_interrupt(&mut self, interrupt: Interrupt) {
+        unsafe {
+            self.can
+                .as_ref()
+                .int_enable
+                .modify(|val| val.disable_interrupt(interrupt))
+        };
+    }
+    /// Check if `interrupt` is pending.
+    #[inline]
+    pub fn has_interrupt(&self, interrupt: Interrupt) -> bool {
+        self.can.as_ref().int_status.read().has_interrupt(interrupt)
+    }
+    /// Clear pending `interrupt`.
+    #[inline]
+    pub fn clear_interrupt(&mut self, interrupt: Interrupt) {
+        unsafe {
+            self.can
+                .as_ref()
+                .int_status
+                .write(Interrupts::default().enable_interrupt(interrupt))
+        };
+    }
+    /// Split into transmit and receive halves usable from different tasks.
+    ///
+    /// Requires [`on_interrupt`] to be called from CAN interrupt handler.
+    #[inline]
+    pub fn split(&mut self) -> (CanTx<'_, I>, CanRx<'_, I>) {
+        let regs = self.can.as_ref();
+        (
+            CanTx {
+                can: regs,
+                mode: self.mode,
+                recovery: self.recovery,
+            },
+            CanRx { can: regs },
+        )
+    }
+    /// Get a temporary borrow on the underlying GPIO pads.
+    #[inline]
+    pub fn pads<F, T>(&mut self, f: F) -> T
+    where
+        F: FnOnce(&mut PADS) -> T,
+    {
+        f(&mut self.pads)
+    }
+    /// Close CAN and release peripheral.
+    #[inline]
+    pub fn free(self, ccu: &ccu::RegisterBlock) -> (CAN, PADS) {
+        unsafe {
+            let regs = self.can.as_ref();
+            regs.int_enable.write(Interrupts::default());
+            regs.mode.modify(|val| val.enter_reset_mode());
+        }
+        // note(unsafe): async read and write using ccu registers
+        unsafe { PADS::Clock::free(ccu) };
+        (self.can, self.pads)
+    }
+    /// Run `f` on registers with controller in reset mode, returning to bus afterwards.
+    #[inline]
+    fn in_reset_mode(&mut self, f: impl FnOnce(&RegisterBlock)) -> Result<(), TimeoutError> {
+        let regs = self.can.as_ref();
+        let deadline = Deadline::start();
+        unsafe { regs.mode.modify(|val| val.enter_reset_mode()) };
+        while !regs.mode.read().is_reset_mode() {
+            deadline.check()?;
+            core::hint::spin_loop();
+        }
+        f(regs);
+        unsafe { regs.mode.modify(|val| val.leave_reset_mode()) };
+        while regs.mode.read().is_reset_mode() {
+            deadline.check()?;
+            core::hint::spin_loop();
+        }
+        Ok(())
+    }
+}
+
+/// Transmit half of [`Can`], sending frames using interrupts.
+pub struct CanTx<'a, const I: usize> {
+    can: &'a RegisterBlock,
+    mode: OperatingMode,
+    recovery: BusOffRecovery,
+}
+
+impl<'a, const I: usize> CanTx<'a, I> {
+    /// Transmit `frame` if transmit buffer is free.
+    #[inline]
+    pub fn try_transmit(&mut self, frame: &Frame) -> nb::Result<(), Error> {
+        try_transmit(self.can, frame, self.mode, self.recovery)
+    }
+    /// Wait until transmit buffer is free, then transmit `frame`.
+    ///
+    /// With automatic bus-off recovery, this also waits until controller rejoins the bus.
+    #[inline]
+    pub async fn transmit(&mut self, frame: &Frame) -> Result<(), Error> {
+        loop {
+            match self.try_transmit(frame) {
+                Ok(()) => return Ok(()),
+                Err(nb::Error::Other(e)) => return Err(e),
+                Err(nb::Error::WouldBlock) => {}
+            }
+            enable_interrupts(self.can, &TX_INTERRUPTS);
+            TX_WAKERS
+                .wait_until(I, || {
+                    let status = self.can.status.read();
+                    // Bus-off needs handling unless recovery is already in progress.
+                    if status.is_bus_off() {
+                        self.recovery == BusOffRecovery::Manual
+                            || self.can.mode.read().is_reset_mode()
+                    } else {
+                        status.is_transmit_buffer_ready()
+                    }
+                })
+                .await;
+        }
+    }
+}
+
+/// Receive half of [`Can`], awaiting frames using interrupts.
+pub struct CanRx<'a, const I: usize> {
+    can: &'a RegisterBlock,
+}
+
+impl<'a, const I: usize> CanRx<'a, I> {
+    /// Receive the oldest frame in receive FIFO if any.
+    #[inline]
+    pub fn try_receive(&mut self) -> nb::Result<Frame, Error> {
+        try_receive(self.can)
+    }
+    /// Wait for the next received frame.
+    ///
+    /// Returns [`Error::Overrun`] once if frames were lost since last call.
+    #[inline]
+    pub async fn receive(&mut self) -> Result<Frame, Error> {
+        loop {
+            match self.try_receive() {
+                Ok(frame) => return Ok(frame),
+                Err(nb::Error::Other(e)) => return Err(e),
+                Err(nb::Error::WouldBlock) => {}
+            }
+            enable_interrupts(self.can, &RX_INTERRUPTS);
+            RX_WAKERS
+                .wait_until(I, || {
+                    let status = self.can.status.read();
+                    status.is_receive_buffer_ready() || status.is_data_overrun()
+                })
+                .await;
+        }
+    }
+}
+
+/// Clear pending `interrupts` and enable them.
+#[inline]
+fn enable_interrupts(regs: &RegisterBlock, interrupts: &[Interrupt]) {
+    let mask = interrupts
+        .iter()
+        .fold(Interrupts::default(), |val, &i| val.enable_interrupt(i));
+    unsafe {
+        regs.int_status.write(mask);
+        regs.int_enable.modify(|val| {
+            interrupts
+                .iter()
+                .fold(val, |val, &i| val.enable_interrupt(i))
+        });
+    }
+}
+
+/// Leave reset mode entered on bus-off, starting recovery sequence.
+#[inline]
+fn start_recovery(regs: &RegisterBlock) {
+    if regs.status.read().is_bus_off() && regs.mode.read().is_reset_mode() {
+        unsafe { regs.mode.modify(|val| val.leave_reset_mode()) };
+    }
+}
+
+/// Write `frame` into transmit buffer and request its transmission.
+#[inline]
+fn write_frame(regs: &RegisterBlock, frame: &Frame, mode: OperatingMode) {
+    let buffer = frame.encode();
+    for (reg, val) in regs.buffer.iter().zip(&buffer[..frame.buffer_len()]) {
+        unsafe { reg.write(*val) };
+    }
+    let command = match mode {
+        OperatingMode::Loopback => Command::default().request_self_receive(),
+        _ => Command::default().request_transmit(),
+    };
+    unsafe { regs.command.write(command) };
+}
+
+/// Read the oldest frame in receive FIFO and release it.
+#[inline]
+fn read_frame(regs: &RegisterBlock) -> Frame {
+    let mut buffer = [0; 13];
+    for (val, reg) in buffer.iter_mut().zip(&regs.buffer) {
+        *val = reg.read();
+    }
+    unsafe {
+        regs.command
+            .write(Command::default().release_receive_buffer())
+    };
+    Frame::decode(&buffer)
+}
+
+/// Transmit `frame` if transmit buffer is free, handling bus-off by `recovery` policy.
+#[inline]
+fn try_transmit(
+    regs: &RegisterBlock,
+    frame: &Frame,
+    mode: OperatingMode,
+    recovery: BusOffRecovery,
+) -> nb::Result<(), Error> {
+    let status = regs.status.read();
+    if status.is_bus_off() {
+        return match recovery {
+            BusOffRecovery::Automatic => {
+                start_recovery(regs);
+                Err(nb::Error::WouldBlock)
+            }
+            BusOffRecovery::Manual => Err(nb::Error::Other(Error::BusOff)),
+        };
+    }
+    if !status.is_transmit_buffer_ready() {
+        return Err(nb::Error::WouldBlock);
+    }
+    write_frame(regs, frame, mode);
+    Ok(())
+}
+
+/// Receive the oldest frame in receive FIFO if any.
+#[inline]
+fn try_receive(regs: &RegisterBlock) -> nb::Result<Frame, Error> {
+    check_overrun(regs)?;
+    if !regs.status.read().is_receive_buffer_ready() {
+        return Err(nb::Error::WouldBlock);
+    }
+    Ok(read_frame(regs))
+}
+
+/// Check for overrun, clearing it so that following frames are received.
+#[inline]
+fn check_overrun(regs: &RegisterBlock) -> Result<(), Error> {
+    if regs.status.read().is_data_overrun() {
+        unsafe { regs.command.write(Command::default().clear_overrun()) };
+        return Err(Error::Overrun);
+    }
+    Ok(())
+}
+
+impl<CAN: AsRef<RegisterBlock>, const I: usize, PADS: Pads<I>> embedded_can::nb::Can
+    for Can<CAN, I, PADS>
+{
+    type Frame = Frame;
+    type Error = Error;
+
+    #[inline]
+    fn transmit(&mut self, frame: &Frame) -> nb::Result<Option<Frame>, Error> {
+        try_transmit(self.can.as_ref(), frame, self.mode, self.recovery)?;
+        Ok(None)
+    }
+    #[inline]
+    fn receive(&mut self) -> nb::Result<Frame, Error> {
+        try_receive(self.can.as_ref())
+    }
+}
+
+impl<CAN: AsRef<RegisterBlock>, const I: usize, PADS: Pads<I>> embedded_can::blocking::Can
+    for Can<CAN, I, PADS>
+{
+    type Frame = Frame;
+    type Error = Error;
+
+    #[inline]
+    fn transmit(&mut self, frame: &Frame) -> Result<(), Error> {
+        let deadline = Deadline::start();
+        loop {
+            match embedded_can::nb::Can::transmit(self, frame) {
+                Ok(_) => return Ok(()),
+                Err(nb::Error::Other(e)) => return Err(e),
+                Err(nb::Error::WouldBlock) => {
+                    deadline.check().map_err(|_| Error::Timeout)?;
+                    core::hint::spin_loop();
+                }
+            }
+        }
+    }
+    #[inline]
+    fn receive(&mut self) -> Result<Frame, Error> {
+        loop {
+            match embedded_can::nb::Can::receive(self) {
+                Ok(frame) => return Ok(frame),
+                Err(nb::Error::Other(e)) => return Err(e),
+                Err(nb::Error::WouldBlock) => core::hint::spin_loop(),
+            }
+        }
+    }
+}
+
+/// Valid CAN pads.
+pub trait Pads<const I: usize> {
+    /// Clock of CAN peripheral.
+    type Clock: ccu::ClockGate + ccu::ClockReset;
+}
+
+/// Valid transmit pin for CAN peripheral.
+pub trait Transmit<const I: usize> {}
+
+/// Valid receive pin for CAN peripheral.
+pub trait Receive<const I: usize> {}
+
+impl<const I: usize, T, R> Pads<I> for (T, R)
+where
+    T: Transmit<I>,
+    R: Receive<I>,
+{
+    type Clock = ccu::CAN<I>;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::BitTiming;
+
+    #[test]
+    fn function_bit_timing_from_bitrate() {
+        assert_eq!(
+            BitTiming::from_bitrate(24_000_000, 500_000),
+            Some(BitTiming {
+                prescaler: 3,
+                segment1: 13,
+                segment2: 2,
+                sync_jump_width: 2,
+            })
+        );
+        assert_eq!(
+            BitTiming::from_bitrate(24_000_000, 1_000_000),
+            Some(BitTiming {
+                prescaler: 2,
+                segment1: 9,
+                segment2: 2,
+                sync_jump_width: 2,
+            })
+        );
+        assert_eq!(
+            BitTiming::from_bitrate(100_000_000, 125_000),
+            Some(BitTiming {
+                prescaler: 40,
+                segment1: 16,
+                segment2: 3,
+                sync_jump_width: 3,
+            })
+        );
+        assert_eq!(BitTiming::from_bitrate(24_000_000, 0), None);
+        assert_eq!(BitTiming::from_bitrate(24_000_000, 7_000_000), None);
+    }
+}
@@ -0,0 +1,303 @@
+use super::{CanRx, Frame};
+use atomic_waker::AtomicWaker;
+use core::{
+    cell::UnsafeCell,
+    future::poll_fn,
+    mem::MaybeUninit,
+    sync::atomic::{AtomicU8, AtomicU32, Ordering},
+    task::Poll,
+};
+use embedded_can::{ExtendedId, Frame as _, Id, StandardId};
+
+/// Identifier filter of a [`Dispatcher`] subscription.
+///
+/// Unlike hardware [`Filter`](super::Filter), software filters tell standard and extended
+/// frames apart. Set mask bits select identifier bits to be compared.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct IdFilter {
+    code: u32,
+    mask: u32,
+}
+
+impl IdFilter {
+    const EXTENDED: u32 = 1 << 31;
+
+    /// Filter matching all frames.
+    #[inline]
+    pub const fn any() -> Self {
+        Self { code: 0, mask: 0 }
+    }
+    /// Filter matching frames with identifier `id` only.
+    #[inline]
+    pub fn exact(id: impl Into<Id>) -> Self {
+        Self {
+            code: key(id.into()),
+            mask: 0xFFFF_FFFF,
+        }
+    }
+    /// Filter matching standard frames whose identifier matches `id` on set bits of `mask`.
+    #[inline]
+    pub fn standard(id: StandardId, mask: u16) -> Self {
+        Self {
+            code: id.as_raw() as u32,
+            mask: Self::EXTENDED | (mask as u32 & 0x7FF),
+        }
+    }
+    /// Filter matching extended frames whose identifier matches `id` on set bits of `mask`.
+    #[inline]
+    pub fn extended(id: ExtendedId, mask: u32) -> Self {
+        Self {
+            code: Self::EXTENDED | id.as_raw(),
+            mask: Self::EXTENDED | (mask & 0x1FFF_FFFF),
+        }
+    }
+    /// Check if frame identifier `id` matches this filter.
+    #[inline]
+    pub fn matches(&self, id: Id) -> bool {
+        (key(id) ^ self.code) & self.mask == 0
+    }
+}
+
+/// Identifier with frame format in bit 31.
+#[inline]
+fn key(id: Id) -> u32 {
+    match id {
+        Id::Standard(id) => id.as_raw() as u32,
+        Id::Extended(id) => IdFilter::EXTENDED | id.as_raw(),
+    }
+}
+
+const FREE: u8 = 0;
+const CLAIMED: u8 = 1;
+const ACTIVE: u8 = 2;
+
+const EMPTY: u8 = 0;
+const BUSY: u8 = 1;
+const FULL: u8 = 2;
+
+/// Subscription slot holding filter and a one-frame mailbox.
+struct Slot {
+    state: AtomicU8,
+    code: AtomicU32,
+    mask: AtomicU32,
+    mailbox: AtomicU8,
+    frame: UnsafeCell<MaybeUninit<Frame>>,
+    dropped: AtomicU32,
+    waker: AtomicWaker,
+}
+
+// note(unsafe): `frame` is only accessed by the side which moved `mailbox` to `BUSY`.
+unsafe impl Sync for Slot {}
+
+impl Slot {
+    const fn new() -> Self {
+        Self {
+            state: AtomicU8::new(FREE),
+            code: AtomicU32::new(0),
+            mask: AtomicU32::new(0),
+            mailbox: AtomicU8::new(EMPTY),
+            frame: UnsafeCell::new(MaybeUninit::uninit()),
+            dropped: AtomicU32::new(0),
+            waker: AtomicWaker::new(),
+        }
+    }
+    #[inline]
+    fn matches(&self, id: Id) -> bool {
+        self.state.load(Ordering::Acquire) == ACTIVE
+            && (key(id) ^ self.code.load(Ordering::Relaxed)) & self.mask.load(Ordering::Relaxed)
+                == 0
+    }
+    #[inline]
+    fn put(&self, frame: &Frame) {
+        if self
+            .mailbox
+            .compare_exchange(EMPTY, BUSY, Ordering::Acquire, Ordering::Relaxed)
+            .is_err()
+        {
+            self.dropped.fetch_add(1, Ordering::Relaxed);
+            return;
+        }
+        unsafe { (*self.frame.get()).write(*frame) };
+        self.mailbox.store(FULL, Ordering::Release);
+        self.waker.wake();
+    }
+    #[inline]
+    fn take(&self) -> Option<Frame> {
+        self.mailbox
+            .compare_exchange(FULL, BUSY, Ordering::Acquire, Ordering::Relaxed)
+            .ok()?;
+        let frame = unsafe { (*self.frame.get()).assume_init() };
+        self.mailbox.store(EMPTY, Ordering::Release);
+        Some(frame)
+    }
+}
+
+/// Software demultiplexer of received frames into `N` subscriptions.
+///
+/// One task runs [`Dispatcher::run`] on a [`CanRx`], or calls [`Dispatcher::dispatch`] with
+/// frames received otherwise. Each matching [`Subscriber`] gets a copy of the frame; a
+/// subscriber keeps one frame until it's taken, and frames arriving meanwhile are dropped.
+pub struct Dispatcher<const N: usize> {
+    slots: [Slot; N],
+}
+
+impl<const N: usize> Dispatcher<N> {
+    /// Create a dispatcher with no subscriptions.
+    #[inline]
+    pub const fn new() -> Self {
+        Self {
+            slots: [const { Slot::new() }; N],
+        }
+    }
+    /// Subscribe to frames matching `filter`.
+    ///
+    /// Returns `None` if all `N` subscriptions are in use.
+    #[inline]
+    pub fn subscribe(&self, filter: IdFilter) -> Option<Subscriber<'_>> {
+        let slot = self.slots.iter().find(|slot| {
+            slot.state
+                .compare_exchange(FREE, CLAIMED, Ordering::Acquire, Ordering::Relaxed)
+                .is_ok()
+        })?;
+        slot.code.store(filter.code, Ordering::Relaxed);
+        slot.mask.store(filter.mask, Ordering::Relaxed);
+        slot.take();
+        slot.dropped.store(0, Ordering::Relaxed);
+        slot.state.store(ACTIVE, Ordering::Release);
+        Some(Subscriber { slot })
+    }
+    /// Hand `frame` to all matching subscribers.
+    ///
+    /// Returns `false` if no subscriber matched `frame`.
+    #[inline]
+    pub fn dispatch(&self, frame: &Frame) -> bool {
+        let mut matched = false;
+        for slot in self.slots.iter().filter(|slot| slot.matches(frame.id())) {
+            slot.put(frame);
+            matched = true;
+        }
+        matched
+    }
+    /// Receive frames from `rx` and dispatch them forever.
+    ///
+    /// Receive FIFO overruns are ignored, as lost frames can't be told to their subscribers.
+    #[inline]
+    pub async fn run<const I: usize>(&self, rx: &mut CanRx<'_, I>) -> ! {
+        loop {
+            if let Ok(frame) = rx.receive().await {
+                self.dispatch(&frame);
+            }
+        }
+    }
+}
+
+impl<const N: usize> Default for Dispatcher<N> {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Subscription to frames of a [`Dispatcher`], released on drop.
+pub struct Subscriber<'a> {
+    slot: &'a Slot,
+}
+
+impl<'a> Subscriber<'a> {
+    /// Take the received frame if any.
+    #[inline]
+    pub fn try_receive(&mut self) -> Option<Frame> {
+        self.slot.take()
+    }
+    /// Wait for the next frame matching subscription filter.
+    #[inline]
+    pub async fn receive(&mut self) -> Frame {
+        poll_fn(|cx| {
+            self.slot.waker.register(cx.waker());
+            match self.slot.take() {
+                Some(frame) => Poll::Ready(frame),
+                None => Poll::Pending,
+            }
+        })
+        .await
+    }
+    /// Get and reset number of frames dropped because the previous frame was not taken.
+    #[inline]
+    pub fn take_dropped(&mut self) -> u32 {
+        self.slot.dropped.swap(0, Ordering::Relaxed)
+    }
+}
+
+impl Drop for Subscriber<'_> {
+    #[inline]
+    fn drop(&mut self) {
+        self.slot.state.store(FREE, Ordering::Release);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Dispatcher, IdFilter};
+    use crate::can::Frame;
+    use embedded_can::{ExtendedId, Frame as _, Id, StandardId};
+
+    fn standard(id: u16) -> Id {
+        Id::Standard(StandardId::new(id).unwrap())
+    }
+
+    fn extended(id: u32) -> Id {
+        Id::Extended(ExtendedId::new(id).unwrap())
+    }
+
+    #[test]
+    fn struct_id_filter_functions() {
+        assert!(IdFilter::any().matches(standard(0x123)));
+        assert!(IdFilter::any().matches(extended(0x123)));
+
+        let filter = IdFilter::exact(StandardId::new(0x123).unwrap());
+        assert!(filter.matches(standard(0x123)));
+        assert!(!filter.matches(standard(0x124)));
+        assert!(!filter.matches(extended(0x123)));
+
+        let filter = IdFilter::standard(StandardId::new(0x120).unwrap(), 0x7F0);
+        assert!(filter.matches(standard(0x12F)));
+        assert!(!filter.matches(standard(0x130)));
+        assert!(!filter.matches(extended(0x120)));
+
+        let filter = IdFilter::extended(ExtendedId::new(0x18FF_0000).unwrap(), 0x1FFF_0000);
+        assert!(filter.matches(extended(0x18FF_1234)));
+        assert!(!filter.matches(extended(0x18FE_1234)));
+        assert!(!filter.matches(standard(0x000)));
+    }
+
+    #[test]
+    fn struct_dispatcher_functions() {
+        let dispatcher = Dispatcher::<2>::new();
+        let mut low = dispatcher
+            .subscribe(IdFilter::standard(StandardId::new(0x100).unwrap(), 0x700))
+            .unwrap();
+        let mut exact = dispatcher
+            .subscribe(IdFilter::exact(StandardId::new(0x123).unwrap()))
+            .unwrap();
+        assert!(dispatcher.subscribe(IdFilter::any()).is_none());
+
+        let first = Frame::new(standard(0x123), &[1]).unwrap();
+        let second = Frame::new(standard(0x1AA), &[2]).unwrap();
+        assert!(dispatcher.dispatch(&first));
+        assert!(dispatcher.dispatch(&second));
+        assert!(!dispatcher.dispatch(&Frame::new(standard(0x200), &[]).unwrap()));
+
+        assert_eq!(exact.try_receive(), Some(first));
+        assert_eq!(exact.try_receive(), None);
+        assert_eq!(low.try_receive(), Some(first));
+        assert_eq!(low.take_dropped(), 1);
+        assert_eq!(low.try_receive(), None);
+
+        drop(exact);
+        let mut any = dispatcher.subscribe(IdFilter::any()).unwrap();
+        assert_eq!(any.try_receive(), None);
+        assert!(dispatcher.dispatch(&second));
+        assert_eq!(any.try_receive(), Some(second));
+        assert_eq!(low.try_receive(), Some(second));
+    }
+}
@@ -0,0 +1,248 @@
+use embedded_can::{ExtendedId, Id, StandardId};
+
+/// Classic CAN data or remote frame.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Frame {
+    id: Id,
+    remote: bool,
+    dlc: u8,
+    data: [u8; 8],
+}
+
+impl Frame {
+    const EFF_FLAG: u32 = 1 << 7;
+    const RTR_FLAG: u32 = 1 << 6;
+    const DLC: u32 = 0xF;
+
+    /// Number of buffer registers holding frame information, identifier and data.
+    pub(crate) const fn buffer_len(&self) -> usize {
+        let header = match self.id {
+            Id::Standard(_) => 3,
+            Id::Extended(_) => 5,
+        };
+        if self.remote {
+            header
+        } else {
+            header + self.dlc as usize
+        }
+    }
+    /// Encode frame into transmit buffer register values.
+    pub(crate) fn encode(&self) -> [u32; 13] {
+        let mut buffer = [0; 13];
+        let mut info = self.dlc as u32;
+        if self.remote {
+            info |= Self::RTR_FLAG;
+        }
+        let data_start = match self.id {
+            Id::Standard(id) => {
+                let id = (id.as_raw() as u32) << 21;
+                buffer[1] = id >> 24;
+                buffer[2] = (id >> 16) & 0xFF;
+                3
+            }
+            Id::Extended(id) => {
+                info |= Self::EFF_FLAG;
+                let id = id.as_raw() << 3;
+                buffer[1] = id >> 24;
+                buffer[2] = (id >> 16) & 0xFF;
+                buffer[3] = (id >> 8) & 0xFF;
+                buffer[4] = id & 0xFF;
+                5
+            }
+        };
+        buffer[0] = info;
+        if !self.remote {
+            for (reg, byte) in buffer[data_start..]
+                .iter_mut()
+                .zip(&self.data[..self.dlc as usize])
+            {
+                *reg = *byte as u32;
+            }
+        }
+        buffer
+    }
+    /// Decode frame from receive buffer register values.
+    pub(crate) fn decode(buffer: &[u32; 13]) -> Self {
+        let info = buffer[0];
+        let dlc = ((info & Self::DLC) as u8).min(8);
+        let remote = info & Self::RTR_FLAG != 0;
+        let (id, data_start) = if info & Self::EFF_FLAG != 0 {
+            let raw = ((buffer[1] & 0xFF) << 24)
+                | ((buffer[2] & 0xFF) << 16)
+                | ((buffer[3] & 0xFF) << 8)
+                | (buffer[4] & 0xFF);
+            // note(unwrap): identifier has 29 bits after shift.
+            let id = ExtendedId::new(raw >> 3).unwrap();
+            (Id::Extended(id), 5)
+        } else {
+            let raw = ((buffer[1] & 0xFF) << 3) | ((buffer[2] & 0xFF) >> 5);
+            // note(unwrap): identifier has 11 bits.
+            let id = StandardId::new(raw as u16).unwrap();
+            (Id::Standard(id), 3)
+        };
+        let mut data = [0; 8];
+        if !remote {
+            for (byte, reg) in data[..dlc as usize].iter_mut().zip(&buffer[data_start..]) {
+                *byte = *reg as u8;
+            }
+        }
+        Self {
+            id,
+            remote,
+            dlc,
+            data,
+        }
+    }
+}
+
+impl embedded_can::Frame for Frame {
+    #[inline]
+    fn new(id: impl Into<Id>, data: &[u8]) -> Option<Self> {
+        if data.len() > 8 {
+            return None;
+        }
+        let mut buf = [0; 8];
+        buf[..data.len()].copy_from_slice(data);
+        Some(Self {
+            id: id.into(),
+            remote: false,
+            dlc: data.len() as u8,
+            data: buf,
+        })
+    }
+    #[inline]
+    fn new_remote(id: impl Into<Id>, dlc: usize) -> Option<Self> {
+        if dlc > 8 {
+            return None;
+        }
+        Some(Self {
+            id: id.into(),
+            remote: true,
+            dlc: dlc as u8,
+            data: [0; 8],
+        })
+    }
+    #[inline]
+    fn is_extended(&self) -> bool {
+        matches!(self.id, Id::Extended(_))
+    }
+    #[inline]
+    fn is_remote_frame(&self) -> bool {
+        self.remote
+    }
+    #[inline]
+    fn id(&self) -> Id {
+        self.id
+    }
+    #[inline]
+    fn dlc(&self) -> usize {
+        self.dlc as usize
+    }
+    #[inline]
+    fn data(&self) -> &[u8] {
+        if self.remote {
+            &[]
+        } else {
+            &self.data[..self.dlc as usize]
+        }
+    }
+}
+
+/// Acceptance filter, matching identifier of received frames.
+///
+/// Hardware doesn't filter by frame format: a standard filter matches extended frames whose
+/// 11 leading identifier bits match it, and vice versa. Set mask bits select identifier bits
+/// to be compared.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Filter {
+    code: u32,
+    mask: u32,
+}
+
+impl Filter {
+    /// Filter accepting all frames.
+    #[inline]
+    pub const fn accept_all() -> Self {
+        Self {
+            code: 0,
+            mask: 0xFFFF_FFFF,
+        }
+    }
+    /// Filter accepting standard frames whose identifier matches `id` on set bits of `mask`.
+    #[inline]
+    pub fn standard(id: StandardId, mask: u16) -> Self {
+        Self {
+            code: (id.as_raw() as u32) << 21,
+            mask: !((mask as u32 & 0x7FF) << 21),
+        }
+    }
+    /// Filter accepting extended frames whose identifier matches `id` on set bits of `mask`.
+    #[inline]
+    pub fn extended(id: ExtendedId, mask: u32) -> Self {
+        Self {
+            code: id.as_raw() << 3,
+            mask: !((mask & 0x1FFF_FFFF) << 3),
+        }
+    }
+    /// Values of acceptance code and mask registers.
+    #[inline]
+    pub const fn registers(self) -> (u32, u32) {
+        (self.code, self.mask)
+    }
+}
+
+impl Default for Filter {
+    #[inline]
+    fn default() -> Self {
+        Self::accept_all()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Filter, Frame};
+    use embedded_can::{ExtendedId, Frame as _, StandardId};
+
+    #[test]
+    fn struct_frame_standard() {
+        let id = StandardId::new(0x123).unwrap();
+        let frame = Frame::new(id, &[0xDE, 0xAD, 0xBE]).unwrap();
+        assert_eq!(frame.buffer_len(), 6);
+        let buffer = frame.encode();
+        assert_eq!(&buffer[..6], &[0x03, 0x24, 0x60, 0xDE, 0xAD, 0xBE]);
+        assert_eq!(Frame::decode(&buffer), frame);
+
+        let remote = Frame::new_remote(id, 2).unwrap();
+        assert_eq!(remote.buffer_len(), 3);
+        let buffer = remote.encode();
+        assert_eq!(&buffer[..3], &[0x42, 0x24, 0x60]);
+        let decoded = Frame::decode(&buffer);
+        assert!(decoded.is_remote_frame());
+        assert_eq!(decoded.dlc(), 2);
+        assert_eq!(decoded.data(), &[]);
+        assert!(Frame::new(id, &[0; 9]).is_none());
+    }
+
+    #[test]
+    fn struct_frame_extended() {
+        let id = ExtendedId::new(0x1ABC_DEF0).unwrap();
+        let frame = Frame::new(id, &[1, 2, 3, 4, 5, 6, 7, 8]).unwrap();
+        assert!(frame.is_extended());
+        assert_eq!(frame.buffer_len(), 13);
+        let buffer = frame.encode();
+        assert_eq!(
+            buffer,
+            [0x88, 0xD5, 0xE6, 0xF7, 0x80, 1, 2, 3, 4, 5, 6, 7, 8]
+        );
+        assert_eq!(Frame::decode(&buffer), frame);
+    }
+
+    #[test]
+    fn struct_filter_functions() {
+        assert_eq!(Filter::accept_all().registers(), (0, 0xFFFF_FFFF));
+        let filter = Filter::standard(StandardId::new(0x120).unwrap(), 0x7F0);
+        assert_eq!(filter.registers(), (0x2400_0000, 0x01FF_FFFF));
+        let filter = Filter::extended(ExtendedId::new(0x1ABC_DEF0).unwrap(), 0x1FFF_FFFF);
+        assert_eq!(filter.registers(), (0xD5E6_F780, 0x0000_0007));
+    }
+}
@@ -0,0 +1,573 @@
+use volatile_register::{RO, RW};
+
+/// Controller Area Network registers.
+#[repr(C)]
+pub struct RegisterBlock {
+    /// 0x00 - CAN Mode Select Register.
+    pub mode: RW<ModeSelect>,
+    /// 0x04 - CAN Command Register.
+    pub command: RW<Command>,
+    /// 0x08 - CAN Status Register.
+    pub status: RO<Status>,
+    /// 0x0C - CAN Interrupt Status Register.
+    pub int_status: RW<Interrupts>,
+    /// 0x10 - CAN Interrupt Enable Register.
+    pub int_enable: RW<Interrupts>,
+    /// 0x14 - CAN Bus Timing Register.
+    pub bus_timing: RW<BusTiming>,
+    /// 0x18 - CAN Transmit Error Warning Limit Register.
+    pub error_warning_limit: RW<u32>,
+    /// 0x1C - CAN Error Counter Register.
+    pub error_counter: RW<ErrorCounter>,
+    /// 0x20 - CAN Receive Message Counter Register.
+    pub rx_message_count: RO<u32>,
+    /// 0x24 - CAN Receive Buffer Start Address Register.
+    pub rx_buffer_start: RO<u32>,
+    /// 0x28 - CAN Acceptance Code Register, writable in reset mode only.
+    pub acceptance_code: RW<u32>,
+    /// 0x2C - CAN Acceptance Mask Register, writable in reset mode only.
+    pub acceptance_mask: RW<u32>,
+    _reserved0: [u32; 4],
+    /// 0x40..=0x70 - CAN Transmit and Receive Buffer Registers 0 to 12.
+    ///
+    /// Writes go to transmit buffer, reads come from the oldest frame in receive FIFO.
+    pub buffer: [RW<u32>; 13],
+}
+
+/// Mode select register.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct ModeSelect(u32);
+
+impl ModeSelect {
+    const SLEEP_MODE: u32 = 1 << 4;
+    const SINGLE_FILTER: u32 = 1 << 3;
+    const LOOPBACK_MODE: u32 = 1 << 2;
+    const LISTEN_ONLY_MODE: u32 = 1 << 1;
+    const RESET_MODE: u32 = 1 << 0;
+
+    /// Check if controller is in reset mode.
+    #[inline]
+    pub const fn is_reset_mode(self) -> bool {
+        self.0 & Self::RESET_MODE != 0
+    }
+    /// Enter reset mode, where configuration registers are writable.
+    #[inline]
+    pub const fn enter_reset_mode(self) -> Self {
+        Self(self.0 | Self::RESET_MODE)
+    }
+    /// Leave reset mode and take part in bus traffic.
+    #[inline]
+    pub const fn leave_reset_mode(self) -> Self {
+        Self(self.0 & !Self::RESET_MODE)
+    }
+    /// Get operating mode.
+    #[inline]
+    pub const fn operating_mode(self) -> OperatingMode {
+        if self.0 & Self::LOOPBACK_MODE != 0 {
+            OperatingMode::Loopback
+        } else if self.0 & Self::LISTEN_ONLY_MODE != 0 {
+            OperatingMode::ListenOnly
+        } else {
+            OperatingMode::Normal
+        }
+    }
+    /// Set operating mode.
+    #[inline]
+    pub const fn set_operating_mode(self, mode: OperatingMode) -> Self {
+        let val = self.0 & !(Self::LOOPBACK_MODE | Self::LISTEN_ONLY_MODE);
+        Self(match mode {
+            OperatingMode::Normal => val,
+            OperatingMode::ListenOnly => val | Self::LISTEN_ONLY_MODE,
+            OperatingMode::Loopback => val | Self::LOOPBACK_MODE,
+        })
+    }
+    /// Check if acceptance filter is a single 32-bit filter.
+    #[inline]
+    pub const fn is_single_filter(self) -> bool {
+        self.0 & Self::SINGLE_FILTER != 0
+    }
+    /// Use acceptance code and mask as a single 32-bit filter.
+    #[inline]
+    pub const fn set_single_filter(self) -> Self {
+        Self(self.0 | Self::SINGLE_FILTER)
+    }
+    /// Use acceptance code and mask as two 16-bit filters.
+    #[inline]
+    pub const fn set_dual_filter(self) -> Self {
+        Self(self.0 & !Self::SINGLE_FILTER)
+    }
+    /// Check if controller is in sleep mode.
+    #[inline]
+    pub const fn is_sleep_mode(self) -> bool {
+        self.0 & Self::SLEEP_MODE != 0
+    }
+    /// Enter sleep mode, waking up on bus activity.
+    #[inline]
+    pub const fn enter_sleep_mode(self) -> Self {
+        Self(self.0 | Self::SLEEP_MODE)
+    }
+    /// Leave sleep mode.
+    #[inline]
+    pub const fn leave_sleep_mode(self) -> Self {
+        Self(self.0 & !Self::SLEEP_MODE)
+    }
+}
+
+impl Default for ModeSelect {
+    #[inline]
+    fn default() -> Self {
+        Self(0x0000_0001)
+    }
+}
+
+/// CAN controller operating mode.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum OperatingMode {
+    /// Transmit, receive and acknowledge frames on bus.
+    Normal,
+    /// Receive frames without acknowledging them or sending error frames.
+    ListenOnly,
+    /// Receive transmitted frames internally, without acknowledge from other nodes.
+    Loopback,
+}
+
+/// Command register.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct Command(u32);
+
+impl Command {
+    const BUS_OFF_REQ: u32 = 1 << 5;
+    const SELF_RCV_REQ: u32 = 1 << 4;
+    const CLEAR_OR_FLAG: u32 = 1 << 3;
+    const RELEASE_RBUF: u32 = 1 << 2;
+    const ABORT_REQ: u32 = 1 << 1;
+    const TRANS_REQ: u32 = 1 << 0;
+
+    /// Request transmission of frame in transmit buffer.
+    #[inline]
+    pub const fn request_transmit(self) -> Self {
+        Self(self.0 | Self::TRANS_REQ)
+    }
+    /// Request transmission of frame in transmit buffer, receiving it simultaneously.
+    #[inline]
+    pub const fn request_self_receive(self) -> Self {
+        Self(self.0 | Self::SELF_RCV_REQ)
+    }
+    /// Abort pending transmission.
+    #[inline]
+    pub const fn abort_transmit(self) -> Self {
+        Self(self.0 | Self::ABORT_REQ)
+    }
+    /// Release the oldest frame in receive FIFO.
+    #[inline]
+    pub const fn release_receive_buffer(self) -> Self {
+        Self(self.0 | Self::RELEASE_RBUF)
+    }
+    /// Clear data overrun status.
+    #[inline]
+    pub const fn clear_overrun(self) -> Self {
+        Self(self.0 | Self::CLEAR_OR_FLAG)
+    }
+    /// Request controller to go bus-off.
+    #[inline]
+    pub const fn request_bus_off(self) -> Self {
+        Self(self.0 | Self::BUS_OFF_REQ)
+    }
+}
+
+impl Default for Command {
+    #[inline]
+    fn default() -> Self {
+        Self(0)
+    }
+}
+
+/// Status register.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct Status(u32);
+
+impl Status {
+    const ERR_CODE: u32 = 0x3 << 22;
+    const ERR_DIR: u32 = 1 << 21;
+    const BUS_OFF: u32 = 1 << 7;
+    const ERR_STA: u32 = 1 << 6;
+    const TRANS_BUSY: u32 = 1 << 5;
+    const RCV_BUSY: u32 = 1 << 4;
+    const TRANS_OVER: u32 = 1 << 3;
+    const TBUF_RDY: u32 = 1 << 2;
+    const DATA_ORUN: u32 = 1 << 1;
+    const RBUF_RDY: u32 = 1 << 0;
+
+    /// Get kind of the last bus error.
+    #[inline]
+    pub const fn error_code(self) -> ErrorCode {
+        match (self.0 & Self::ERR_CODE) >> 22 {
+            0 => ErrorCode::Bit,
+            1 => ErrorCode::Form,
+            2 => ErrorCode::Stuff,
+            _ => ErrorCode::Other,
+        }
+    }
+    /// Check if the last bus error occurred while receiving.
+    #[inline]
+    pub const fn is_receive_error(self) -> bool {
+        self.0 & Self::ERR_DIR != 0
+    }
+    /// Check if controller is bus-off.
+    #[inline]
+    pub const fn is_bus_off(self) -> bool {
+        self.0 & Self::BUS_OFF != 0
+    }
+    /// Check if an error counter reached error warning limit.
+    #[inline]
+    pub const fn is_error_warning(self) -> bool {
+        self.0 & Self::ERR_STA != 0
+    }
+    /// Check if controller is transmitting a frame.
+    #[inline]
+    pub const fn is_transmitting(self) -> bool {
+        self.0 & Self::TRANS_BUSY != 0
+    }
+    /// Check if controller is receiving a frame.
+    #[inline]
+    pub const fn is_receiving(self) -> bool {
+        self.0 & Self::RCV_BUSY != 0
+    }
+    /// Check if the last requested transmission completed.
+    #[inline]
+    pub const fn is_transmit_complete(self) -> bool {
+        self.0 & Self::TRANS_OVER != 0
+    }
+    /// Check if transmit buffer can be written.
+    #[inline]
+    pub const fn is_transmit_buffer_ready(self) -> bool {
+        self.0 & Self::TBUF_RDY != 0
+    }
+    /// Check if a received frame was lost for receive FIFO being full.
+    #[inline]
+    pub const fn is_data_overrun(self) -> bool {
+        self.0 & Self::DATA_ORUN != 0
+    }
+    /// Check if receive FIFO holds a frame.
+    #[inline]
+    pub const fn is_receive_buffer_ready(self) -> bool {
+        self.0 & Self::RBUF_RDY != 0
+    }
+}
+
+/// Kind of bus error reported in status register.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ErrorCode {
+    /// Bit error.
+    Bit,
+    /// Form error.
+    Form,
+    /// Stuff error.
+    Stuff,
+    /// Other error, e.g. CRC or acknowledge error.
+    Other,
+}
+
+/// CAN interrupt.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Interrupt {
+    /// Frame received into receive FIFO.
+    Receive = 0,
+    /// Transmit buffer became available.
+    Transmit = 1,
+    /// Error warning or bus-off status changed.
+    ErrorWarning = 2,
+    /// Received frame lost for receive FIFO being full.
+    DataOverrun = 3,
+    /// Bus activity detected in sleep mode.
+    Wakeup = 4,
+    /// Controller entered or left error passive state.
+    ErrorPassive = 5,
+    /// Controller lost arbitration while transmitting.
+    ArbitrationLost = 6,
+    /// Bus error detected.
+    BusError = 7,
+}
+
+/// Interrupt status and enable register.
+///
+/// Status bits are cleared by writing 1.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct Interrupts(u32);
+
+impl Interrupts {
+    /// Check if `interrupt` is pending or enabled.
+    #[inline]
+    pub const fn has_interrupt(self, interrupt: Interrupt) -> bool {
+        self.0 & (1 << interrupt as u32) != 0
+    }
+    /// Enable `interrupt`, or select it to be cleared.
+    #[inline]
+    pub const fn enable_interrupt(self, interrupt: Interrupt) -> Self {
+        Self(self.0 | (1 << interrupt as u32))
+    }
+    /// Disable `interrupt`.
+    #[inline]
+    pub const fn disable_interrupt(self, interrupt: Interrupt) -> Self {
+        Self(self.0 & !(1 << interrupt as u32))
+    }
+}
+
+impl Default for Interrupts {
+    #[inline]
+    fn default() -> Self {
+        Self(0)
+    }
+}
+
+/// Bus timing register, writable in reset mode only.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct BusTiming(u32);
+
+impl BusTiming {
+    const SAM: u32 = 1 << 23;
+    const PHSEG2: u32 = 0x7 << 20;
+    const PHSEG1: u32 = 0xF << 16;
+    const SJW: u32 = 0x3 << 14;
+    const TQ_BRP: u32 = 0x3FF;
+
+    /// Get clock prescaler, in 1..=1024.
+    #[inline]
+    pub const fn prescaler(self) -> u16 {
+        (self.0 & Self::TQ_BRP) as u16 + 1
+    }
+    /// Set clock prescaler, in 1..=1024.
+    #[inline]
+    pub const fn set_prescaler(self, prescaler: u16) -> Self {
+        Self((self.0 & !Self::TQ_BRP) | ((prescaler as u32 - 1) & Self::TQ_BRP))
+    }
+    /// Get time quanta before sample point, excluding synchronization segment, in 1..=16.
+    #[inline]
+    pub const fn segment1(self) -> u8 {
+        ((self.0 & Self::PHSEG1) >> 16) as u8 + 1
+    }
+    /// Set time quanta before sample point, excluding synchronization segment, in 1..=16.
+    #[inline]
+    pub const fn set_segment1(self, tq: u8) -> Self {
+        Self((self.0 & !Self::PHSEG1) | (((tq as u32 - 1) << 16) & Self::PHSEG1))
+    }
+    /// Get time quanta after sample point, in 1..=8.
+    #[inline]
+    pub const fn segment2(self) -> u8 {
+        ((self.0 & Self::PHSEG2) >> 20) as u8 + 1
+    }
+    /// Set time quanta after sample point, in 1..=8.
+    #[inline]
+    pub const fn set_segment2(self, tq: u8) -> Self {
+        Self((self.0 & !Self::PHSEG2) | (((tq as u32 - 1) << 20) & Self::PHSEG2))
+    }
+    /// Get synchronization jump width, in 1..=4.
+    #[inline]
+    pub const fn sync_jump_width(self) -> u8 {
+        ((self.0 & Self::SJW) >> 14) as u8 + 1
+    }
+    /// Set synchronization jump width, in 1..=4.
+    #[inline]
+    pub const fn set_sync_jump_width(self, tq: u8) -> Self {
+        Self((self.0 & !Self::SJW) | (((tq as u32 - 1) << 14) & Self::SJW))
+    }
+    /// Check if bus is sampled three times at sample point.
+    #[inline]
+    pub const fn is_triple_sampling(self) -> bool {
+        self.0 & Self::SAM != 0
+    }
+    /// Sample bus three times at sample point, for low speed buses.
+    #[inline]
+    pub const fn enable_triple_sampling(self) -> Self {
+        Self(self.0 | Self::SAM)
+    }
+    /// Sample bus once at sample point.
+    #[inline]
+    pub const fn disable_triple_sampling(self) -> Self {
+        Self(self.0 & !Self::SAM)
+    }
+}
+
+impl Default for BusTiming {
+    #[inline]
+    fn default() -> Self {
+        Self(0)
+    }
+}
+
+/// Error counter register.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct ErrorCounter(u32);
+
+impl ErrorCounter {
+    const RX_ERR_CNT: u32 = 0xFF << 16;
+    const TX_ERR_CNT: u32 = 0xFF;
+
+    /// Get receive error counter.
+    #[inline]
+    pub const fn receive_errors(self) -> u8 {
+        ((self.0 & Self::RX_ERR_CNT) >> 16) as u8
+    }
+    /// Get transmit error counter.
+    #[inline]
+    pub const fn transmit_errors(self) -> u8 {
+        (self.0 & Self::TX_ERR_CNT) as u8
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        BusTiming, Command, ErrorCode, ErrorCounter, Interrupt, Interrupts, ModeSelect,
+        OperatingMode, RegisterBlock, Status,
+    };
+    use crate::can::{CanStatus, ErrorState};
+    use core::mem::offset_of;
+
+    #[test]
+    fn offset_can() {
+        assert_eq!(offset_of!(RegisterBlock, mode), 0x00);
+        assert_eq!(offset_of!(RegisterBlock, command), 0x04);
+        assert_eq!(offset_of!(RegisterBlock, status), 0x08);
+        assert_eq!(offset_of!(RegisterBlock, int_status), 0x0C);
+        assert_eq!(offset_of!(RegisterBlock, int_enable), 0x10);
+        assert_eq!(offset_of!(RegisterBlock, bus_timing), 0x14);
+        assert_eq!(offset_of!(RegisterBlock, error_warning_limit), 0x18);
+        assert_eq!(offset_of!(RegisterBlock, error_counter), 0x1C);
+        assert_eq!(offset_of!(RegisterBlock, rx_message_count), 0x20);
+        assert_eq!(offset_of!(RegisterBlock, acceptance_code), 0x28);
+        assert_eq!(offset_of!(RegisterBlock, acceptance_mask), 0x2C);
+        assert_eq!(offset_of!(RegisterBlock, buffer), 0x40);
+    }
+
+    #[test]
+    fn struct_mode_select_functions() {
+        let mut val = ModeSelect::default();
+        assert!(val.is_reset_mode());
+        assert_eq!(val.operating_mode(), OperatingMode::Normal);
+
+        val = val
+            .set_single_filter()
+            .set_operating_mode(OperatingMode::Loopback);
+        assert!(val.is_single_filter());
+        assert_eq!(val.operating_mode(), OperatingMode::Loopback);
+        assert_eq!(val.0, 0x0000000D);
+        val = val.set_operating_mode(OperatingMode::ListenOnly);
+        assert_eq!(val.operating_mode(), OperatingMode::ListenOnly);
+        assert_eq!(val.0, 0x0000000B);
+
+        val = val.leave_reset_mode().set_dual_filter().enter_sleep_mode();
+        assert!(!val.is_reset_mode());
+        assert!(!val.is_single_filter());
+        assert!(val.is_sleep_mode());
+        assert_eq!(val.0, 0x00000012);
+        val = val.leave_sleep_mode().enter_reset_mode();
+        assert_eq!(val.0, 0x00000003);
+    }
+
+    #[test]
+    fn struct_command_functions() {
+        let val = Command::default().request_transmit();
+        assert_eq!(val.0, 0x00000001);
+        let val = Command::default()
+            .request_self_receive()
+            .abort_transmit()
+            .release_receive_buffer()
+            .clear_overrun()
+            .request_bus_off();
+        assert_eq!(val.0, 0x0000003E);
+    }
+
+    #[test]
+    fn struct_status_functions() {
+        let val = Status(0x0060_00C5);
+        assert_eq!(val.error_code(), ErrorCode::Form);
+        assert!(val.is_receive_error());
+        assert!(val.is_bus_off());
+        assert!(val.is_error_warning());
+        assert!(!val.is_transmitting());
+        assert!(!val.is_receiving());
+        assert!(!val.is_transmit_complete());
+        assert!(val.is_transmit_buffer_ready());
+        assert!(!val.is_data_overrun());
+        assert!(val.is_receive_buffer_ready());
+
+        let val = Status(0x0080_003A);
+        assert_eq!(val.error_code(), ErrorCode::Stuff);
+        assert!(!val.is_receive_error());
+        assert!(val.is_transmitting());
+        assert!(val.is_receiving());
+        assert!(val.is_transmit_complete());
+        assert!(val.is_data_overrun());
+        assert!(!val.is_receive_buffer_ready());
+    }
+
+    #[test]
+    fn struct_interrupts_functions() {
+        let mut val = Interrupts::default();
+        val = val
+            .enable_interrupt(Interrupt::Receive)
+            .enable_interrupt(Interrupt::BusError);
+        assert!(val.has_interrupt(Interrupt::Receive));
+        assert!(val.has_interrupt(Interrupt::BusError));
+        assert!(!val.has_interrupt(Interrupt::Transmit));
+        assert_eq!(val.0, 0x00000081);
+        val = val.disable_interrupt(Interrupt::Receive);
+        assert_eq!(val.0, 0x00000080);
+    }
+
+    #[test]
+    fn struct_bus_timing_functions() {
+        let mut val = BusTiming::default()
+            .set_prescaler(3)
+            .set_segment1(13)
+            .set_segment2(2)
+            .set_sync_jump_width(1);
+        assert_eq!(val.prescaler(), 3);
+        assert_eq!(val.segment1(), 13);
+        assert_eq!(val.segment2(), 2);
+        assert_eq!(val.sync_jump_width(), 1);
+        assert_eq!(val.0, 0x001C0002);
+        val = val
+            .set_prescaler(1024)
+            .set_segment1(16)
+            .set_segment2(8)
+            .set_sync_jump_width(4)
+            .enable_triple_sampling();
+        assert!(val.is_triple_sampling());
+        assert_eq!(val.0, 0x00FFC3FF);
+        val = val.disable_triple_sampling();
+        assert!(!val.is_triple_sampling());
+    }
+
+    #[test]
+    fn struct_error_counter_functions() {
+        let val = ErrorCounter(0x0060_0080);
+        assert_eq!(val.receive_errors(), 0x60);
+        assert_eq!(val.transmit_errors(), 0x80);
+    }
+
+    #[test]
+    fn struct_can_status_functions() {
+        let val = CanStatus::from_registers(Status(0), ErrorCounter(0));
+        assert_eq!(val.state, ErrorState::Active);
+        let val = CanStatus::from_registers(Status(0x0000_0040), ErrorCounter(0x0000_0060));
+        assert_eq!(val.state, ErrorState::Warning);
+        assert_eq!(val.transmit_errors, 0x60);
+        let val = CanStatus::from_registers(Status(0x0060_0040), ErrorCounter(0x0080_0010));
+        assert_eq!(val.state, ErrorState::Passive);
+        assert_eq!(val.receive_errors, 0x80);
+        assert_eq!(val.last_error, ErrorCode::Form);
+        assert!(val.last_error_receiving);
+        let val = CanStatus::from_registers(Status(0x0000_00C0), ErrorCounter(0x0000_00FF));
+        assert_eq!(val.state, ErrorState::BusOff);
+        assert_eq!(val.last_error, ErrorCode::Bit);
+        assert!(!val.last_error_receiving);
+    }
+}
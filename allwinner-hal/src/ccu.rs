@@ -6,21 +6,264 @@ mod source;
 
 pub(crate) use factor::calculate_best_peripheral_factors_nm;
 pub use factor::{AxiFactorN, FactorP, PeriFactorN};
-pub use pll::{PllCpuControl, PllDdrControl, PllPeri0Control};
-pub use source::{CpuClockSource, DramClockSource, SmhcClockSource, SpiClockSource};
+pub use pll::{
+    PllAudio0Control, PllAudio0Pattern, PllAudio1Control, PllCpuControl, PllDdrControl,
+    PllPeri0Control, PllVideo0Control,
+};
+pub use source::{
+    AudioCodecClockSource, CeClockSource, CpuClockSource, DeClockSource, DramClockSource,
+    DspClockSource, G2dClockSource, LedcClockSource, RiscvClockSource, SmhcClockSource,
+    SpiClockSource, TconLcdClockSource,
+};
 
 use embedded_time::rate::Hertz;
 use volatile_register::RW;
 
 /// Clock configuration on current SoC.
+///
+/// More clocks may be added in later versions; create it by [`Clocks::new`].
 #[derive(Debug)]
+#[non_exhaustive]
 pub struct Clocks {
+    /// CPU core clock frequency.
+    pub cpu: Hertz,
     /// PSI clock frequency.
     pub psi: Hertz,
+    /// Advanced Peripheral Bus 0 clock frequency.
+    pub apb0: Hertz,
     /// Advanced Peripheral Bus 1 clock frequency.
     pub apb1: Hertz,
 }
 
+impl Clocks {
+    /// Create clock configuration from CPU, PSI, APB0 and APB1 clock frequencies.
+    #[inline]
+    pub const fn new(cpu: Hertz, psi: Hertz, apb0: Hertz, apb1: Hertz) -> Self {
+        Self {
+            cpu,
+            psi,
+            apb0,
+            apb1,
+        }
+    }
+}
+
+/// Calculate CPU core clock frequency from current CCU configuration.
+///
+/// On D1 this is the RISC-V core clock; on T113 it is the clock of its Cortex-A7 cores,
+/// configured by CPU AXI configuration register. Peripheral PLL sources are assumed to run
+/// at their nominal frequencies.
+#[cfg(feature = "d1")]
+#[inline]
+pub fn cpu_frequency(ccu: &RegisterBlock) -> Hertz {
+    let riscv = ccu.riscv_clock.read();
+    let source = match riscv.clock_source() {
+        RiscvClockSource::Hosc => 24_000_000,
+        RiscvClockSource::Clk32K => 32_768,
+        RiscvClockSource::Clk16MRC => 16_000_000,
+        RiscvClockSource::PllPeri800M => 800_000_000,
+        RiscvClockSource::PllPeri1x => 600_000_000,
+        RiscvClockSource::PllCpu => pll_cpu_frequency(ccu),
+        RiscvClockSource::PllAudio1Div2 => {
+            let pll = ccu.pll_audio1_control.read();
+            let frequency = 24_000_000 * (pll.pll_n() as u64 + 1)
+                / (pll.pll_m() as u64 + 1)
+                / (pll.pll_p0() as u64 + 1);
+            frequency as u32
+        }
+    };
+    Hertz(source / (riscv.factor_m() as u32 + 1))
+}
+
+/// Calculate CPU core clock frequency from current CCU configuration.
+///
+/// On D1 this is the RISC-V core clock; on T113 it is the clock of its Cortex-A7 cores,
+/// configured by CPU AXI configuration register. Peripheral PLL sources are assumed to run
+/// at their nominal frequencies.
+#[cfg(not(feature = "d1"))]
+#[inline]
+pub fn cpu_frequency(ccu: &RegisterBlock) -> Hertz {
+    let axi = ccu.cpu_axi_config.read();
+    let source = match axi.clock_source() {
+        CpuClockSource::Hosc => 24_000_000,
+        CpuClockSource::Clk32K => 32_768,
+        CpuClockSource::Clk16MRC => 16_000_000,
+        CpuClockSource::PllCpu => pll_cpu_frequency(ccu),
+        CpuClockSource::PllPeri1x => 600_000_000,
+        CpuClockSource::PllPeri2x => 1_200_000_000,
+        CpuClockSource::PllPeri800M => 800_000_000,
+    };
+    let divide_p = match axi.factor_p() {
+        FactorP::P1 => 1,
+        FactorP::P2 => 2,
+        FactorP::P4 => 4,
+    };
+    Hertz(source / (axi.factor_m() as u32 + 1) / divide_p)
+}
+
+/// Output frequency of CPU PLL from 24-MHz oscillator.
+#[inline]
+fn pll_cpu_frequency(ccu: &RegisterBlock) -> u32 {
+    let pll = ccu.pll_cpu_control.read();
+    24_000_000 * (pll.pll_n() as u32 + 1) / (pll.pll_m() as u32 + 1)
+}
+
+/// Run CPU from CPU PLL at `frequency`, rounded down to a multiple of 24 MHz and no lower
+/// than 288 MHz, and return actual PLL frequency.
+///
+/// CPU runs from 24-MHz oscillator while PLL locks to new frequency.
+///
+/// # Safety
+///
+/// CPU supply voltage must support the new frequency; [`crate::power::set_cpu_opp`] changes
+/// it together with frequency. Clock frequencies previously read into [`Clocks`] become stale.
+#[inline]
+pub unsafe fn set_cpu_pll_frequency(ccu: &RegisterBlock, frequency: Hertz) -> Hertz {
+    let n = cpu_pll_factor_n(frequency.0);
+    #[cfg(feature = "d1")]
+    unsafe {
+        ccu.riscv_clock
+            .modify(|val| val.set_clock_source(RiscvClockSource::Hosc));
+    }
+    #[cfg(not(feature = "d1"))]
+    unsafe {
+        ccu.cpu_axi_config
+            .modify(|val| val.set_clock_source(CpuClockSource::Hosc));
+    }
+    unsafe {
+        ccu.pll_cpu_control.modify(|val| {
+            val.enable_pll()
+                .enable_pll_ldo()
+                .disable_lock()
+                .set_pll_n(n)
+                .set_pll_m(0)
+        });
+        ccu.pll_cpu_control.modify(|val| val.enable_lock());
+    }
+    while !ccu.pll_cpu_control.read().is_locked() {
+        core::hint::spin_loop();
+    }
+    unsafe { ccu.pll_cpu_control.modify(|val| val.unmask_pll_output()) };
+    #[cfg(feature = "d1")]
+    unsafe {
+        ccu.riscv_clock
+            .modify(|val| val.set_clock_source(RiscvClockSource::PllCpu));
+    }
+    #[cfg(not(feature = "d1"))]
+    unsafe {
+        ccu.cpu_axi_config
+            .modify(|val| val.set_clock_source(CpuClockSource::PllCpu));
+    }
+    Hertz(24_000_000 * (n as u32 + 1))
+}
+
+/// Calculate CPU PLL factor N for `frequency` from 24-MHz oscillator, with factor M of 1.
+#[inline]
+const fn cpu_pll_factor_n(frequency: u32) -> u8 {
+    let multiple = frequency / 24_000_000;
+    let multiple = if multiple < 12 { 12 } else { multiple };
+    (multiple - 1) as u8
+}
+
+/// Enable audio PLL 0 at 90.3168 MHz on its 4X output, giving 22.5792 MHz on its 1X output
+/// for sample rates of the 44.1-kHz family.
+///
+/// N factor is 22.5792 through sigma-delta modulation, divided by P factor 6.
+/// Nothing is changed if audio PLL 0 is already enabled.
+///
+/// # Safety
+///
+/// No peripheral should be clocked from audio PLL 0 while it is disabled.
+#[inline]
+pub unsafe fn enable_pll_audio0(ccu: &RegisterBlock) {
+    if ccu.pll_audio0_control.read().is_pll_enabled() {
+        return;
+    }
+    unsafe {
+        // 0x1288D / 2^17 = 0.5792.
+        ccu.pll_audio0_pattern.write(
+            PllAudio0Pattern::default()
+                .enable_pattern()
+                .set_spread_mode(2)
+                .set_wave_bottom(0x1288D),
+        );
+        ccu.pll_audio0_control.modify(|val| {
+            val.enable_pll()
+                .enable_pll_ldo()
+                .disable_lock()
+                .enable_sdm()
+                .set_pll_n(21)
+                .set_pll_m1(0)
+                .set_pll_m0(0)
+                .set_pll_p(5)
+        });
+        ccu.pll_audio0_control.modify(|val| val.enable_lock());
+    }
+    while !ccu.pll_audio0_control.read().is_locked() {
+        core::hint::spin_loop();
+    }
+    unsafe { ccu.pll_audio0_control.modify(|val| val.unmask_pll_output()) };
+}
+
+/// Enable audio PLL 1 at 3072 MHz, giving 1536 MHz on its divide-by-2 output and
+/// 614.4 MHz on its divide-by-5 output.
+///
+/// Nothing is changed if audio PLL 1 is already enabled.
+///
+/// # Safety
+///
+/// No peripheral should be clocked from audio PLL 1 while it is disabled.
+#[inline]
+pub unsafe fn enable_pll_audio1(ccu: &RegisterBlock) {
+    if ccu.pll_audio1_control.read().is_pll_enabled() {
+        return;
+    }
+    unsafe {
+        ccu.pll_audio1_control.modify(|val| {
+            val.enable_pll()
+                .enable_pll_ldo()
+                .disable_lock()
+                .set_pll_n(127)
+                .set_pll_m(0)
+                .set_pll_p0(1)
+                .set_pll_p1(4)
+        });
+        ccu.pll_audio1_control.modify(|val| val.enable_lock());
+    }
+    while !ccu.pll_audio1_control.read().is_locked() {
+        core::hint::spin_loop();
+    }
+    unsafe { ccu.pll_audio1_control.modify(|val| val.unmask_pll_output()) };
+}
+
+/// Run video PLL 0 at `frequency` on its 4X output, rounded down to a multiple of 24 MHz
+/// and no lower than 288 MHz, and return actual 4X output frequency.
+///
+/// 1X output runs at a quarter of returned frequency.
+///
+/// # Safety
+///
+/// No peripheral should be clocked from video PLL 0 while it relocks.
+#[inline]
+pub unsafe fn set_video0_pll_frequency(ccu: &RegisterBlock, frequency: Hertz) -> Hertz {
+    let n = cpu_pll_factor_n(frequency.0);
+    unsafe {
+        ccu.pll_video0_control.modify(|val| {
+            val.enable_pll()
+                .enable_pll_ldo()
+                .disable_lock()
+                .set_pll_n(n)
+                .set_pll_m(0)
+        });
+        ccu.pll_video0_control.modify(|val| val.enable_lock());
+    }
+    while !ccu.pll_video0_control.read().is_locked() {
+        core::hint::spin_loop();
+    }
+    unsafe { ccu.pll_video0_control.modify(|val| val.unmask_pll_output()) };
+    Hertz(24_000_000 * (n as u32 + 1))
+}
+
 /// Clock Control Unit registers.
 #[repr(C)]
 pub struct RegisterBlock {
@@ -32,33 +275,152 @@ pub struct RegisterBlock {
     _reserved1: [u32; 3],
     /// 0x20 - Peripheral PLL 0 Control register.
     pub pll_peri0_control: RW<PllPeri0Control>,
-    _reserved2: [u32; 311],
+    _reserved2: [u32; 7],
+    /// 0x40 - Video PLL 0 Control register.
+    pub pll_video0_control: RW<PllVideo0Control>,
+    _reserved3: [u32; 13],
+    /// 0x78 - Audio PLL 0 Control register.
+    pub pll_audio0_control: RW<PllAudio0Control>,
+    _reserved4: u32,
+    /// 0x80 - Audio PLL 1 Control register.
+    pub pll_audio1_control: RW<PllAudio1Control>,
+    _reserved5: [u32; 61],
+    /// 0x178 - Audio PLL 0 Pattern 0 register.
+    pub pll_audio0_pattern: RW<PllAudio0Pattern>,
+    _reserved6: [u32; 225],
     /// 0x500 - CPU AXI Configuration register.
     pub cpu_axi_config: RW<CpuAxiConfig>,
-    _reserved3: [u32; 15],
+    _reserved7: [u32; 15],
     /// 0x540 - MBUS Clock register.
     pub mbus_clock: RW<MbusClock>,
-    _reserved4: [u32; 175],
+    _reserved8: [u32; 47],
+    /// 0x600 - Display Engine Clock register.
+    pub de_clock: RW<DeClock>,
+    _reserved9: [u32; 2],
+    /// 0x60c - Display Engine Bus Gating Reset register.
+    pub de_bgr: RW<DeBusGating>,
+    _reserved10: [u32; 8],
+    /// 0x630 - 2D Graphics Accelerator Clock register.
+    pub g2d_clock: RW<G2dClock>,
+    _reserved11: [u32; 2],
+    /// 0x63c - 2D Graphics Accelerator Bus Gating Reset register.
+    pub g2d_bgr: RW<G2dBusGating>,
+    _reserved12: [u32; 16],
+    /// 0x680 - Crypto Engine Clock register.
+    pub ce_clock: RW<CeClock>,
+    _reserved13: [u32; 2],
+    /// 0x68c - Crypto Engine Bus Gating Reset register.
+    pub ce_bgr: RW<CeBusGating>,
+    _reserved14: [u32; 31],
+    /// 0x70c - DMA Bus Gating Reset register.
+    pub dma_bgr: RW<DmaBusGating>,
+    _reserved15: [u32; 3],
+    /// 0x71c - Message Box Bus Gating Reset register.
+    pub msgbox_bgr: RW<MsgboxBusGating>,
+    _reserved16: [u32; 3],
+    /// 0x72c - Spinlock Bus Gating Reset register.
+    pub spinlock_bgr: RW<SpinlockBusGating>,
+    _reserved17: [u32; 3],
+    /// 0x73c - HSTIMER Bus Gating Reset register.
+    pub hstimer_bgr: RW<HstimerBusGating>,
+    _reserved18: [u32; 27],
+    /// 0x7ac - PWM Bus Gating Reset register.
+    pub pwm_bgr: RW<PwmBusGating>,
+    _reserved19: [u32; 20],
     /// 0x800 - DRAM Clock register.
     pub dram_clock: RW<DramClock>,
-    _reserved5: [u32; 2],
+    /// 0x804 - MBUS Master Clock Gating register.
+    pub mbus_gating: RW<MbusGating>,
+    _reserved20: u32,
     /// 0x80c - DRAM Bus Gating Reset register.
     pub dram_bgr: RW<DramBusGating>,
-    _reserved6: [u32; 8],
+    _reserved21: [u32; 8],
     /// 0x830..=0x838 - SMHC0 Clock register, SMHC1 Clock register and SMHC2 Clock register.
     pub smhc_clk: [RW<SmhcClock>; 3],
-    _reserved7: [u32; 4],
+    _reserved22: [u32; 4],
     /// 0x84c - SMHC Bus Gating Reset register.
     pub smhc_bgr: RW<SmhcBusGating>,
-    _reserved8: [u32; 47],
+    _reserved23: [u32; 47],
     /// 0x90c - UART Bus Gating Reset register.
     pub uart_bgr: RW<UartBusGating>,
-    _reserved9: [u32; 12],
+    _reserved24: [u32; 7],
+    /// 0x92c - CAN Bus Gating Reset register.
+    pub can_bgr: RW<CanBusGating>,
+    _reserved25: [u32; 4],
     /// 0x940..=0x944 - SPI0 Clock register and SPI1 Clock register.
     pub spi_clk: [RW<SpiClock>; 2],
-    _reserved10: [u32; 9],
+    _reserved26: [u32; 9],
     /// 0x96c - SPI Bus Gating Reset register.
     pub spi_bgr: RW<SpiBusGating>,
+    /// 0x970 - EMAC 25M Clock register.
+    pub emac_25m_clock: RW<Emac25MClock>,
+    _reserved27: [u32; 2],
+    /// 0x97c - EMAC Bus Gating Reset register.
+    pub emac_bgr: RW<EmacBusGating>,
+    _reserved28: [u32; 27],
+    /// 0x9ec - GPADC Bus Gating Reset register.
+    pub gpadc_bgr: RW<GpadcBusGating>,
+    _reserved29: [u32; 3],
+    /// 0x9fc - THS Bus Gating Reset register.
+    pub ths_bgr: RW<ThsBusGating>,
+    _reserved30: [u32; 9],
+    /// 0xa24 - OWA TX Clock register.
+    pub owa_tx_clock: RW<AudioCodecClock>,
+    _reserved31: u32,
+    /// 0xa2c - OWA Bus Gating Reset register.
+    pub owa_bgr: RW<OwaBusGating>,
+    _reserved32: [u32; 4],
+    /// 0xa40 - DMIC Clock register.
+    pub dmic_clock: RW<AudioCodecClock>,
+    _reserved33: [u32; 2],
+    /// 0xa4c - DMIC Bus Gating Reset register.
+    pub dmic_bgr: RW<DmicBusGating>,
+    /// 0xa50 - Audio Codec DAC Clock register.
+    pub audio_codec_dac_clock: RW<AudioCodecClock>,
+    /// 0xa54 - Audio Codec ADC Clock register.
+    pub audio_codec_adc_clock: RW<AudioCodecClock>,
+    _reserved34: u32,
+    /// 0xa5c - Audio Codec Bus Gating Reset register.
+    pub audio_codec_bgr: RW<AudioCodecBusGating>,
+    _reserved35: [u32; 4],
+    /// 0xa70..=0xa74 - USB0 and USB1 Clock registers.
+    pub usb_clock: [RW<UsbClock>; 2],
+    _reserved36: [u32; 5],
+    /// 0xa8c - USB Bus Gating Reset register.
+    pub usb_bgr: RW<UsbBusGating>,
+    _reserved37: [u32; 3],
+    /// 0xa9c - LRADC Bus Gating Reset register.
+    pub lradc_bgr: RW<LradcBusGating>,
+    _reserved38: [u32; 7],
+    /// 0xabc - Display Subsystem Top Bus Gating Reset register.
+    pub dpss_top_bgr: RW<DpssTopBusGating>,
+    _reserved39: [u32; 17],
+    /// 0xb04 - HDMI 24-MHz Clock register.
+    pub hdmi_24m_clock: RW<Hdmi24mClock>,
+    _reserved40: [u32; 5],
+    /// 0xb1c - HDMI Bus Gating Reset register.
+    pub hdmi_bgr: RW<HdmiBusGating>,
+    _reserved41: [u32; 16],
+    /// 0xb60 - TCON LCD Clock register.
+    pub tcon_lcd_clock: RW<TconLcdClock>,
+    _reserved42: [u32; 6],
+    /// 0xb7c - TCON LCD Bus Gating Reset register.
+    pub tcon_lcd_bgr: RW<TconLcdBusGating>,
+    _reserved43: [u32; 28],
+    /// 0xbf0 - LEDC Clock register.
+    pub ledc_clock: RW<LedcClock>,
+    _reserved44: [u32; 2],
+    /// 0xbfc - LEDC Bus Gating Reset register.
+    pub ledc_bgr: RW<LedcBusGating>,
+    _reserved45: [u32; 28],
+    /// 0xc70 - DSP Clock register.
+    pub dsp_clock: RW<DspClock>,
+    _reserved46: [u32; 2],
+    /// 0xc7c - DSP Bus Gating Reset register.
+    pub dsp_bgr: RW<DspBusGating>,
+    _reserved47: [u32; 32],
+    /// 0xd00 - RISC-V Clock register.
+    pub riscv_clock: RW<RiscvClock>,
 }
 
 /// CPU AXI Configuration register.
@@ -273,165 +635,265 @@ impl DramBusGating {
     }
 }
 
-/// UART Bus Gating Reset register.
+/// DMA Bus Gating Reset register.
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 #[repr(transparent)]
-pub struct UartBusGating(u32);
+pub struct DmaBusGating(u32);
 
-impl UartBusGating {
-    /// Disable clock gate for UART `I`.
+impl DmaBusGating {
+    const DMA_RST: u32 = 1 << 16;
+    const DMA_GATING: u32 = 1 << 0;
+
+    /// Assert DMA reset.
+    #[inline]
+    pub const fn assert_reset(self) -> Self {
+        Self(self.0 & !Self::DMA_RST)
+    }
+    /// De-assert DMA reset.
+    #[inline]
+    pub const fn deassert_reset(self) -> Self {
+        Self(self.0 | Self::DMA_RST)
+    }
+    /// Mask the DMA gating.
+    #[inline]
+    pub const fn gate_mask(self) -> Self {
+        Self(self.0 & !Self::DMA_GATING)
+    }
+    /// Unmask (pass) the DMA gating.
+    #[inline]
+    pub const fn gate_pass(self) -> Self {
+        Self(self.0 | Self::DMA_GATING)
+    }
+}
+
+/// Message Box Bus Gating Reset register.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct MsgboxBusGating(u32);
+
+impl MsgboxBusGating {
+    /// Disable clock gate for message box `I`.
     #[inline]
     pub const fn gate_mask<const I: usize>(self) -> Self {
         Self(self.0 & !(1 << I))
     }
-    /// Enable clock gate for UART `I`.
+    /// Enable clock gate for message box `I`.
     #[inline]
     pub const fn gate_pass<const I: usize>(self) -> Self {
         Self(self.0 | (1 << I))
     }
-    /// Assert reset signal for UART `I`.
+    /// Assert reset signal for message box `I`.
     #[inline]
     pub const fn assert_reset<const I: usize>(self) -> Self {
         Self(self.0 & !(1 << (I + 16)))
     }
-    /// Deassert reset signal for UART `I`.
+    /// Deassert reset signal for message box `I`.
     #[inline]
     pub const fn deassert_reset<const I: usize>(self) -> Self {
         Self(self.0 | (1 << (I + 16)))
     }
 }
 
-/// SPI Clock register.
+/// Spinlock Bus Gating Reset register.
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 #[repr(transparent)]
-pub struct SpiClock(u32);
+pub struct SpinlockBusGating(u32);
 
-impl SpiClock {
-    const CLK_SRC_SEL: u32 = 0x7 << 24;
-    const FACTOR_N: u32 = 0x3 << 8;
-    const FACTOR_M: u32 = 0xf << 0;
-    /// Get SPI clock source.
+impl SpinlockBusGating {
+    const SPINLOCK_RST: u32 = 1 << 16;
+    const SPINLOCK_GATING: u32 = 1 << 0;
+
+    /// Assert spinlock reset.
     #[inline]
-    pub const fn clock_source(self) -> SpiClockSource {
-        match (self.0 & Self::CLK_SRC_SEL) >> 24 {
-            0x0 => SpiClockSource::Hosc,
-            0x1 => SpiClockSource::PllPeri1x,
-            0x2 => SpiClockSource::PllPeri2x,
-            0x3 => SpiClockSource::PllAudio1Div2,
-            0x4 => SpiClockSource::PllAudio1Div5,
-            _ => panic!("impossible clock source"),
-        }
+    pub const fn assert_reset(self) -> Self {
+        Self(self.0 & !Self::SPINLOCK_RST)
     }
-    /// Set SPI clock source.
+    /// De-assert spinlock reset.
     #[inline]
-    pub const fn set_clock_source(self, val: SpiClockSource) -> Self {
-        let val = match val {
-            SpiClockSource::Hosc => 0x0,
-            SpiClockSource::PllPeri1x => 0x1,
-            SpiClockSource::PllPeri2x => 0x2,
-            SpiClockSource::PllAudio1Div2 => 0x3,
-            SpiClockSource::PllAudio1Div5 => 0x4,
-        };
-        Self((self.0 & !Self::CLK_SRC_SEL) | (val << 24))
+    pub const fn deassert_reset(self) -> Self {
+        Self(self.0 | Self::SPINLOCK_RST)
     }
-    /// Get SPI clock divide factor N.
+    /// Mask the spinlock gating.
     #[inline]
-    pub const fn factor_n(self) -> PeriFactorN {
-        match (self.0 & Self::FACTOR_N) >> 8 {
-            0 => PeriFactorN::N1,
-            1 => PeriFactorN::N2,
-            2 => PeriFactorN::N4,
-            3 => PeriFactorN::N8,
-            _ => unreachable!(),
-        }
+    pub const fn gate_mask(self) -> Self {
+        Self(self.0 & !Self::SPINLOCK_GATING)
     }
-    /// Set SPI clock divide factor N.
+    /// Unmask (pass) the spinlock gating.
     #[inline]
-    pub const fn set_factor_n(self, val: PeriFactorN) -> Self {
-        let val = match val {
-            PeriFactorN::N1 => 0,
-            PeriFactorN::N2 => 1,
-            PeriFactorN::N4 => 2,
-            PeriFactorN::N8 => 3,
-        };
-        Self((self.0 & !Self::FACTOR_N) | (val << 8))
+    pub const fn gate_pass(self) -> Self {
+        Self(self.0 | Self::SPINLOCK_GATING)
     }
-    /// Get SPI clock divide factor M.
+}
+
+/// HSTIMER Bus Gating Reset register.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct HstimerBusGating(u32);
+
+impl HstimerBusGating {
+    const HSTIMER_RST: u32 = 1 << 16;
+    const HSTIMER_GATING: u32 = 1 << 0;
+
+    /// Assert HSTIMER reset.
     #[inline]
-    pub const fn factor_m(self) -> u8 {
-        (self.0 & Self::FACTOR_M) as u8
+    pub const fn assert_reset(self) -> Self {
+        Self(self.0 & !Self::HSTIMER_RST)
     }
-    /// Set SPI clock divide factor M.
+    /// De-assert HSTIMER reset.
     #[inline]
-    pub const fn set_factor_m(self, val: u8) -> Self {
-        Self((self.0 & !Self::FACTOR_M) | val as u32)
+    pub const fn deassert_reset(self) -> Self {
+        Self(self.0 | Self::HSTIMER_RST)
+    }
+    /// Mask the HSTIMER gating.
+    #[inline]
+    pub const fn gate_mask(self) -> Self {
+        Self(self.0 & !Self::HSTIMER_GATING)
+    }
+    /// Unmask (pass) the HSTIMER gating.
+    #[inline]
+    pub const fn gate_pass(self) -> Self {
+        Self(self.0 | Self::HSTIMER_GATING)
     }
 }
 
-/// SPI Bus Gating Reset register.
+/// PWM Bus Gating Reset register.
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 #[repr(transparent)]
-pub struct SpiBusGating(u32);
+pub struct PwmBusGating(u32);
 
-impl SpiBusGating {
-    /// Disable clock gate for SPI `I`.
+impl PwmBusGating {
+    const PWM_RST: u32 = 1 << 16;
+    const PWM_GATING: u32 = 1 << 0;
+
+    /// Assert PWM reset.
     #[inline]
-    pub const fn gate_mask<const I: usize>(self) -> Self {
-        Self(self.0 & !(1 << I))
+    pub const fn assert_reset(self) -> Self {
+        Self(self.0 & !Self::PWM_RST)
     }
-    /// Enable clock gate for SPI `I`.
+    /// De-assert PWM reset.
     #[inline]
-    pub const fn gate_pass<const I: usize>(self) -> Self {
-        Self(self.0 | (1 << I))
+    pub const fn deassert_reset(self) -> Self {
+        Self(self.0 | Self::PWM_RST)
     }
-    /// Assert reset signal for SPI `I`.
+    /// Mask the PWM gating.
     #[inline]
-    pub const fn assert_reset<const I: usize>(self) -> Self {
-        Self(self.0 & !(1 << (I + 16)))
+    pub const fn gate_mask(self) -> Self {
+        Self(self.0 & !Self::PWM_GATING)
     }
-    /// Deassert reset signal for SPI `I`.
+    /// Unmask (pass) the PWM gating.
     #[inline]
-    pub const fn deassert_reset<const I: usize>(self) -> Self {
-        Self(self.0 | (1 << (I + 16)))
+    pub const fn gate_pass(self) -> Self {
+        Self(self.0 | Self::PWM_GATING)
     }
 }
 
-/// SMHC Clock register.
+/// GPADC Bus Gating Reset register.
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 #[repr(transparent)]
-pub struct SmhcClock(u32);
+pub struct GpadcBusGating(u32);
 
-impl SmhcClock {
+impl GpadcBusGating {
+    const GPADC_RST: u32 = 1 << 16;
+    const GPADC_GATING: u32 = 1 << 0;
+
+    /// Assert GPADC reset.
+    #[inline]
+    pub const fn assert_reset(self) -> Self {
+        Self(self.0 & !Self::GPADC_RST)
+    }
+    /// De-assert GPADC reset.
+    #[inline]
+    pub const fn deassert_reset(self) -> Self {
+        Self(self.0 | Self::GPADC_RST)
+    }
+    /// Mask the GPADC gating.
+    #[inline]
+    pub const fn gate_mask(self) -> Self {
+        Self(self.0 & !Self::GPADC_GATING)
+    }
+    /// Unmask (pass) the GPADC gating.
+    #[inline]
+    pub const fn gate_pass(self) -> Self {
+        Self(self.0 | Self::GPADC_GATING)
+    }
+}
+
+/// THS Bus Gating Reset register.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct ThsBusGating(u32);
+
+impl ThsBusGating {
+    const THS_RST: u32 = 1 << 16;
+    const THS_GATING: u32 = 1 << 0;
+
+    /// Assert THS reset.
+    #[inline]
+    pub const fn assert_reset(self) -> Self {
+        Self(self.0 & !Self::THS_RST)
+    }
+    /// De-assert THS reset.
+    #[inline]
+    pub const fn deassert_reset(self) -> Self {
+        Self(self.0 | Self::THS_RST)
+    }
+    /// Mask the THS gating.
+    #[inline]
+    pub const fn gate_mask(self) -> Self {
+        Self(self.0 & !Self::THS_GATING)
+    }
+    /// Unmask (pass) the THS gating.
+    #[inline]
+    pub const fn gate_pass(self) -> Self {
+        Self(self.0 | Self::THS_GATING)
+    }
+}
+
+/// Audio Codec Clock register.
+///
+/// Used for both DAC and ADC clocks of the audio codec, and for DMIC and OWA transmit
+/// clocks which have the same layout.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct AudioCodecClock(u32);
+
+impl AudioCodecClock {
+    const CLK_GATING: u32 = 0x1 << 31;
     const CLK_SRC_SEL: u32 = 0x7 << 24;
     const FACTOR_N: u32 = 0x3 << 8;
-    const FACTOR_M: u32 = 0xf << 0;
-    const CLK_GATING: u32 = 1 << 31;
+    const FACTOR_M: u32 = 0x1f;
 
-    /// Get SMHC clock source.
+    /// If clock is unmasked.
     #[inline]
-    pub const fn clock_source(self) -> SmhcClockSource {
+    pub const fn is_clock_unmasked(self) -> bool {
+        self.0 & Self::CLK_GATING != 0
+    }
+    /// Unmask (enable) clock.
+    #[inline]
+    pub const fn unmask_clock(self) -> Self {
+        Self(self.0 | Self::CLK_GATING)
+    }
+    /// Mask (disable) clock.
+    #[inline]
+    pub const fn mask_clock(self) -> Self {
+        Self(self.0 & !Self::CLK_GATING)
+    }
+    /// Get audio codec clock source.
+    #[inline]
+    pub const fn clock_source(self) -> AudioCodecClockSource {
         match (self.0 & Self::CLK_SRC_SEL) >> 24 {
-            0x0 => SmhcClockSource::Hosc,
-            0x1 => SmhcClockSource::PllPeri1x,
-            0x2 => SmhcClockSource::PllPeri2x,
-            0x3 => SmhcClockSource::PllPeri800M,
-            0x4 => SmhcClockSource::PllAudio1Div2,
+            0x0 => AudioCodecClockSource::PllAudio0,
+            0x1 => AudioCodecClockSource::PllAudio1Div2,
+            0x2 => AudioCodecClockSource::PllAudio1Div5,
             _ => panic!("impossible clock source"),
         }
     }
-    /// Set SMHC clock source.
+    /// Set audio codec clock source.
     #[inline]
-    pub const fn set_clock_source(self, val: SmhcClockSource) -> Self {
-        let val = match val {
-            SmhcClockSource::Hosc => 0x0,
-            SmhcClockSource::PllPeri1x => 0x1,
-            SmhcClockSource::PllPeri2x => 0x2,
-            SmhcClockSource::PllPeri800M => 0x3,
-            SmhcClockSource::PllAudio1Div2 => 0x4,
-        };
-        Self((self.0 & !Self::CLK_SRC_SEL) | (val << 24))
+    pub const fn set_clock_source(self, val: AudioCodecClockSource) -> Self {
+        Self((self.0 & !Self::CLK_SRC_SEL) | ((val as u32) << 24))
     }
-    /// Get SMHC clock divide factor N.
+    /// Get audio codec clock divide factor N.
     #[inline]
     pub const fn factor_n(self) -> PeriFactorN {
         match (self.0 & Self::FACTOR_N) >> 8 {
@@ -442,7 +904,7 @@ impl SmhcClock {
             _ => unreachable!(),
         }
     }
-    /// Set SMHC clock divide factor N.
+    /// Set audio codec clock divide factor N.
     #[inline]
     pub const fn set_factor_n(self, val: PeriFactorN) -> Self {
         let val = match val {
@@ -453,553 +915,3706 @@ impl SmhcClock {
         };
         Self((self.0 & !Self::FACTOR_N) | (val << 8))
     }
-    /// Get SMHC clock divide factor M.
+    /// Get audio codec clock divide factor M, from 0 to 31.
     #[inline]
     pub const fn factor_m(self) -> u8 {
         (self.0 & Self::FACTOR_M) as u8
     }
-    /// Set SMHC clock divide factor M.
+    /// Set audio codec clock divide factor M, from 0 to 31.
     #[inline]
     pub const fn set_factor_m(self, val: u8) -> Self {
-        Self((self.0 & !Self::FACTOR_M) | val as u32)
+        Self((self.0 & !Self::FACTOR_M) | (val as u32 & 0x1f))
     }
-    /// Enable clock gating.
+}
+
+impl Default for AudioCodecClock {
     #[inline]
-    pub const fn enable_clock_gating(self) -> Self {
-        Self(self.0 | Self::CLK_GATING)
+    fn default() -> Self {
+        Self(0x0000_0000)
     }
-    /// Disable clock gating.
-    #[inline]
-    pub const fn disable_clock_gating(self) -> Self {
-        Self(self.0 & !Self::CLK_GATING)
+}
+
+/// OWA Bus Gating Reset register.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct OwaBusGating(u32);
+
+impl OwaBusGating {
+    const OWA_RST: u32 = 1 << 16;
+    const OWA_GATING: u32 = 1 << 0;
+
+    /// Assert OWA reset.
+    #[inline]
+    pub const fn assert_reset(self) -> Self {
+        Self(self.0 & !Self::OWA_RST)
     }
-    /// Get if clock gating is enabled.
+    /// De-assert OWA reset.
     #[inline]
-    pub const fn is_clock_gating_enabled(self) -> bool {
-        self.0 & Self::CLK_GATING != 0
+    pub const fn deassert_reset(self) -> Self {
+        Self(self.0 | Self::OWA_RST)
+    }
+    /// Mask the OWA gating.
+    #[inline]
+    pub const fn gate_mask(self) -> Self {
+        Self(self.0 & !Self::OWA_GATING)
+    }
+    /// Unmask (pass) the OWA gating.
+    #[inline]
+    pub const fn gate_pass(self) -> Self {
+        Self(self.0 | Self::OWA_GATING)
     }
 }
 
-/// SMHC Clock Reset register.
+/// DMIC Bus Gating Reset register.
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 #[repr(transparent)]
-pub struct SmhcBusGating(u32);
+pub struct DmicBusGating(u32);
 
-impl SmhcBusGating {
-    /// Disable clock gate for SMHC `I`.
+impl DmicBusGating {
+    const DMIC_RST: u32 = 1 << 16;
+    const DMIC_GATING: u32 = 1 << 0;
+
+    /// Assert DMIC reset.
     #[inline]
-    pub const fn gate_mask<const I: usize>(self) -> Self {
-        Self(self.0 & !(1 << I))
+    pub const fn assert_reset(self) -> Self {
+        Self(self.0 & !Self::DMIC_RST)
     }
-    /// Enable clock gate for SMHC `I`.
+    /// De-assert DMIC reset.
     #[inline]
-    pub const fn gate_pass<const I: usize>(self) -> Self {
-        Self(self.0 | (1 << I))
+    pub const fn deassert_reset(self) -> Self {
+        Self(self.0 | Self::DMIC_RST)
     }
-    /// Assert reset signal for SMHC `I`.
+    /// Mask the DMIC gating.
     #[inline]
-    pub const fn assert_reset<const I: usize>(self) -> Self {
-        Self(self.0 & !(1 << (I + 16)))
+    pub const fn gate_mask(self) -> Self {
+        Self(self.0 & !Self::DMIC_GATING)
     }
-    /// Deassert reset signal for SMHC `I`.
+    /// Unmask (pass) the DMIC gating.
     #[inline]
-    pub const fn deassert_reset<const I: usize>(self) -> Self {
-        Self(self.0 | (1 << (I + 16)))
+    pub const fn gate_pass(self) -> Self {
+        Self(self.0 | Self::DMIC_GATING)
     }
 }
 
-/// Peripheral that have clock reset feature in CCU.
-pub trait ClockReset {
-    /// Assert reset signal.
-    unsafe fn assert_reset_only(ccu: &RegisterBlock);
-    /// Deassert reset signal.
-    unsafe fn deassert_reset_only(ccu: &RegisterBlock);
-}
+/// Audio Codec Bus Gating Reset register.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct AudioCodecBusGating(u32);
 
-/// Peripheral that can be clock gated by CCU.
-pub trait ClockGate: ClockReset {
-    /// Unmask clock gate.
-    unsafe fn unmask_gate_only(ccu: &RegisterBlock);
-    /// Mask clock gate.
-    unsafe fn mask_gate_only(ccu: &RegisterBlock);
-    /// Assert reset signal and mask the clock gate.
-    unsafe fn disable_in(ccu: &RegisterBlock);
-    /// Deassert reset signal and unmask the clock gate.
-    unsafe fn enable_in(ccu: &RegisterBlock);
-    /// Reset this peripheral without reconfiguring clocks (if applicable).
+impl AudioCodecBusGating {
+    const AUDIO_CODEC_RST: u32 = 1 << 16;
+    const AUDIO_CODEC_GATING: u32 = 1 << 0;
+
+    /// Assert Audio Codec reset.
     #[inline]
-    unsafe fn reset(ccu: &RegisterBlock) {
-        unsafe {
-            // assert reset and then deassert reset.
-            Self::disable_in(ccu);
-            Self::enable_in(ccu);
-        }
+    pub const fn assert_reset(self) -> Self {
+        Self(self.0 & !Self::AUDIO_CODEC_RST)
     }
-    /// Free this peripheral by provided `ccu`.
+    /// De-assert Audio Codec reset.
     #[inline]
-    unsafe fn free(ccu: &RegisterBlock) {
-        unsafe {
-            // by default, asserting reset signal and mask clock gate.
-            Self::disable_in(ccu);
-        }
+    pub const fn deassert_reset(self) -> Self {
+        Self(self.0 | Self::AUDIO_CODEC_RST)
     }
-}
-
-/// Peripheral whose clock can be configurated by CCU.
-pub trait ClockConfig {
-    /// Type of clock source.
-    type Source;
-    /// Configure peripheral clock.
-    ///
-    /// Value `factor_m` should be in 0 ..= 15.
-    unsafe fn configure(
-        ccu: &RegisterBlock,
-        source: Self::Source,
-        factor_m: u8,
-        factor_n: PeriFactorN,
-    );
-    /// Reconfigure peripheral clock by applying clock parameters while asserting reset.
+    /// Mask the Audio Codec gating.
     #[inline]
-    unsafe fn reconfigure(
-        ccu: &RegisterBlock,
-        source: Self::Source,
-        factor_m: u8,
-        factor_n: PeriFactorN,
-    ) where
-        Self: ClockGate,
-    {
-        unsafe {
-            Self::disable_in(ccu);
-            Self::configure(ccu, source, factor_m, factor_n);
-            Self::enable_in(ccu);
-        }
+    pub const fn gate_mask(self) -> Self {
+        Self(self.0 & !Self::AUDIO_CODEC_GATING)
     }
-    /// Reconfigure this clock with dependency to a resettable clock type `T`.
+    /// Unmask (pass) the Audio Codec gating.
     #[inline]
-    unsafe fn reconfigure_with<T: ClockReset, F, G>(
-        ccu: &RegisterBlock,
-        dependency: T,
-        before_configure: F,
-        after_configure: G,
-    ) where
-        Self: ClockGate,
-        F: FnOnce(&RegisterBlock) -> (Self::Source, u8, PeriFactorN),
-        G: FnOnce(&RegisterBlock),
-    {
-        unsafe {
-            let _ = dependency; // does not use value, the type T is used instead
-            T::assert_reset_only(ccu);
-            Self::disable_in(ccu);
-            let (source, factor_m, factor_n) = before_configure(ccu);
-            Self::configure(ccu, source, factor_m, factor_n);
-            after_configure(ccu);
-            Self::deassert_reset_only(ccu);
-            T::deassert_reset_only(ccu);
-            Self::unmask_gate_only(ccu);
-        }
+    pub const fn gate_pass(self) -> Self {
+        Self(self.0 | Self::AUDIO_CODEC_GATING)
     }
 }
 
-// TODO: a more proper abstraction considering the PLL source behind peripheral clock
+/// USB Clock register.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct UsbClock(u32);
 
-/// Dynamic Random-Access Memory (DRAM) clock type.
-pub struct DRAM;
+impl UsbClock {
+    const USB_CLKEN: u32 = 1 << 31;
+    const USBPHY_RSTN: u32 = 1 << 30;
 
-impl ClockReset for DRAM {
+    /// If OHCI 12-MHz clock is unmasked.
     #[inline]
-    unsafe fn deassert_reset_only(ccu: &RegisterBlock) {
-        unsafe {
-            ccu.dram_bgr.modify(|v| v.deassert_reset());
-        }
+    pub const fn is_clock_unmasked(self) -> bool {
+        self.0 & Self::USB_CLKEN != 0
     }
+    /// Unmask (enable) OHCI 12-MHz clock.
     #[inline]
-    unsafe fn assert_reset_only(ccu: &RegisterBlock) {
-        unsafe {
-            ccu.dram_bgr.modify(|v| v.assert_reset());
-        }
+    pub const fn unmask_clock(self) -> Self {
+        Self(self.0 | Self::USB_CLKEN)
     }
-}
-
-impl ClockGate for DRAM {
+    /// Mask (disable) OHCI 12-MHz clock.
     #[inline]
-    unsafe fn unmask_gate_only(ccu: &RegisterBlock) {
-        unsafe {
-            ccu.dram_bgr.modify(|v| v.gate_pass());
-        }
+    pub const fn mask_clock(self) -> Self {
+        Self(self.0 & !Self::USB_CLKEN)
     }
+    /// If USB PHY reset is de-asserted.
     #[inline]
-    unsafe fn mask_gate_only(ccu: &RegisterBlock) {
-        unsafe {
-            ccu.dram_bgr.modify(|v| v.gate_mask());
-        }
+    pub const fn is_phy_reset_deasserted(self) -> bool {
+        self.0 & Self::USBPHY_RSTN != 0
     }
+    /// Assert USB PHY reset.
     #[inline]
-    unsafe fn disable_in(ccu: &RegisterBlock) {
-        unsafe {
-            ccu.dram_bgr.modify(|v| v.gate_mask().assert_reset());
-        }
+    pub const fn assert_phy_reset(self) -> Self {
+        Self(self.0 & !Self::USBPHY_RSTN)
     }
+    /// De-assert USB PHY reset.
     #[inline]
-    unsafe fn enable_in(ccu: &RegisterBlock) {
-        unsafe {
-            ccu.dram_bgr.modify(|v| v.gate_pass().deassert_reset());
-        }
+    pub const fn deassert_phy_reset(self) -> Self {
+        Self(self.0 | Self::USBPHY_RSTN)
     }
 }
 
-impl ClockConfig for DRAM {
-    type Source = DramClockSource;
+/// USB Bus Gating Reset register.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct UsbBusGating(u32);
 
+impl UsbBusGating {
+    const USBOTG_RST: u32 = 1 << 24;
+    const USBOTG_GATING: u32 = 1 << 8;
+
+    /// Assert USB OTG reset.
     #[inline]
-    unsafe fn configure(
-        ccu: &RegisterBlock,
-        source: Self::Source,
-        factor_m: u8,
-        factor_n: PeriFactorN,
-    ) {
-        unsafe {
-            let dram_clk = ccu.dram_clock.read();
-            ccu.dram_clock.write(
-                dram_clk
-                    .set_clock_source(source)
-                    .set_factor_m(factor_m)
-                    .set_factor_n(factor_n),
-            )
-        }
+    pub const fn assert_otg_reset(self) -> Self {
+        Self(self.0 & !Self::USBOTG_RST)
     }
-}
-
-/// MCTL Bus (MBUS) clock type.
-pub struct MBUS;
-
-impl ClockReset for MBUS {
+    /// De-assert USB OTG reset.
     #[inline]
-    unsafe fn assert_reset_only(ccu: &RegisterBlock) {
-        unsafe {
-            ccu.mbus_clock.modify(|v| v.assert_reset());
-        }
+    pub const fn deassert_otg_reset(self) -> Self {
+        Self(self.0 | Self::USBOTG_RST)
     }
+    /// Mask the USB OTG gating.
     #[inline]
-    unsafe fn deassert_reset_only(ccu: &RegisterBlock) {
-        unsafe {
-            ccu.mbus_clock.modify(|v| v.deassert_reset());
-        }
+    pub const fn gate_mask_otg(self) -> Self {
+        Self(self.0 & !Self::USBOTG_GATING)
     }
-}
-
-/// Universal Asynchronous Receiver-Transmitter clock type.
-///
-/// UART peripheral should be indexed by type parameter `IDX`.
-#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
-pub struct UART<const IDX: usize>;
-
-impl<const I: usize> ClockReset for UART<I> {
+    /// Unmask (pass) the USB OTG gating.
     #[inline]
-    unsafe fn assert_reset_only(ccu: &RegisterBlock) {
-        unsafe {
-            ccu.uart_bgr.modify(|v| v.assert_reset::<I>());
-        }
+    pub const fn gate_pass_otg(self) -> Self {
+        Self(self.0 | Self::USBOTG_GATING)
     }
+    /// Assert reset signal for USB EHCI `I`.
     #[inline]
-    unsafe fn deassert_reset_only(ccu: &RegisterBlock) {
-        unsafe {
-            ccu.uart_bgr.modify(|v| v.deassert_reset::<I>());
-        }
+    pub const fn assert_ehci_reset<const I: usize>(self) -> Self {
+        Self(self.0 & !(1 << (I + 20)))
     }
-}
-
-impl<const I: usize> ClockGate for UART<I> {
+    /// Deassert reset signal for USB EHCI `I`.
     #[inline]
-    unsafe fn unmask_gate_only(ccu: &RegisterBlock) {
-        unsafe {
-            ccu.uart_bgr.modify(|v| v.gate_pass::<I>());
-        }
+    pub const fn deassert_ehci_reset<const I: usize>(self) -> Self {
+        Self(self.0 | (1 << (I + 20)))
     }
+    /// Disable clock gate for USB EHCI `I`.
     #[inline]
-    unsafe fn mask_gate_only(ccu: &RegisterBlock) {
-        unsafe {
-            ccu.uart_bgr.modify(|v| v.gate_mask::<I>());
-        }
+    pub const fn gate_mask_ehci<const I: usize>(self) -> Self {
+        Self(self.0 & !(1 << (I + 4)))
     }
+    /// Enable clock gate for USB EHCI `I`.
     #[inline]
-    unsafe fn disable_in(ccu: &RegisterBlock) {
-        unsafe {
-            ccu.uart_bgr
-                .modify(|v| v.gate_mask::<I>().assert_reset::<I>());
-        }
+    pub const fn gate_pass_ehci<const I: usize>(self) -> Self {
+        Self(self.0 | (1 << (I + 4)))
     }
+    /// Assert reset signal for USB OHCI `I`.
     #[inline]
-    unsafe fn enable_in(ccu: &RegisterBlock) {
-        unsafe {
-            ccu.uart_bgr
-                .modify(|v| v.gate_pass::<I>().deassert_reset::<I>());
-        }
+    pub const fn assert_ohci_reset<const I: usize>(self) -> Self {
+        Self(self.0 & !(1 << (I + 16)))
+    }
+    /// Deassert reset signal for USB OHCI `I`.
+    #[inline]
+    pub const fn deassert_ohci_reset<const I: usize>(self) -> Self {
+        Self(self.0 | (1 << (I + 16)))
+    }
+    /// Disable clock gate for USB OHCI `I`.
+    #[inline]
+    pub const fn gate_mask_ohci<const I: usize>(self) -> Self {
+        Self(self.0 & !(1 << I))
+    }
+    /// Enable clock gate for USB OHCI `I`.
+    #[inline]
+    pub const fn gate_pass_ohci<const I: usize>(self) -> Self {
+        Self(self.0 | (1 << I))
     }
 }
 
-/// Serial Peripheral Interface clock type.
+/// LRADC Bus Gating Reset register.
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
-pub struct SPI<const IDX: usize>;
+#[repr(transparent)]
+pub struct LradcBusGating(u32);
 
-impl<const I: usize> ClockReset for SPI<I> {
+impl LradcBusGating {
+    const LRADC_RST: u32 = 1 << 16;
+    const LRADC_GATING: u32 = 1 << 0;
+
+    /// Assert LRADC reset.
     #[inline]
-    unsafe fn assert_reset_only(ccu: &RegisterBlock) {
-        unsafe {
-            ccu.spi_bgr.modify(|v| v.assert_reset::<I>());
-        }
+    pub const fn assert_reset(self) -> Self {
+        Self(self.0 & !Self::LRADC_RST)
     }
+    /// De-assert LRADC reset.
     #[inline]
-    unsafe fn deassert_reset_only(ccu: &RegisterBlock) {
-        unsafe {
-            ccu.spi_bgr.modify(|v| v.deassert_reset::<I>());
-        }
+    pub const fn deassert_reset(self) -> Self {
+        Self(self.0 | Self::LRADC_RST)
+    }
+    /// Mask the LRADC gating.
+    #[inline]
+    pub const fn gate_mask(self) -> Self {
+        Self(self.0 & !Self::LRADC_GATING)
+    }
+    /// Unmask (pass) the LRADC gating.
+    #[inline]
+    pub const fn gate_pass(self) -> Self {
+        Self(self.0 | Self::LRADC_GATING)
     }
 }
 
-impl<const I: usize> ClockGate for SPI<I> {
+/// UART Bus Gating Reset register.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct UartBusGating(u32);
+
+impl UartBusGating {
+    /// Disable clock gate for UART `I`.
     #[inline]
-    unsafe fn unmask_gate_only(ccu: &RegisterBlock) {
-        unsafe {
-            ccu.spi_bgr.modify(|v| v.gate_pass::<I>());
+    pub const fn gate_mask<const I: usize>(self) -> Self {
+        Self(self.0 & !(1 << I))
+    }
+    /// Enable clock gate for UART `I`.
+    #[inline]
+    pub const fn gate_pass<const I: usize>(self) -> Self {
+        Self(self.0 | (1 << I))
+    }
+    /// Assert reset signal for UART `I`.
+    #[inline]
+    pub const fn assert_reset<const I: usize>(self) -> Self {
+        Self(self.0 & !(1 << (I + 16)))
+    }
+    /// Deassert reset signal for UART `I`.
+    #[inline]
+    pub const fn deassert_reset<const I: usize>(self) -> Self {
+        Self(self.0 | (1 << (I + 16)))
+    }
+}
+
+/// CAN Bus Gating Reset register.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct CanBusGating(u32);
+
+impl CanBusGating {
+    /// Disable clock gate for CAN `I`.
+    #[inline]
+    pub const fn gate_mask<const I: usize>(self) -> Self {
+        Self(self.0 & !(1 << I))
+    }
+    /// Enable clock gate for CAN `I`.
+    #[inline]
+    pub const fn gate_pass<const I: usize>(self) -> Self {
+        Self(self.0 | (1 << I))
+    }
+    /// Assert reset signal for CAN `I`.
+    #[inline]
+    pub const fn assert_reset<const I: usize>(self) -> Self {
+        Self(self.0 & !(1 << (I + 16)))
+    }
+    /// Deassert reset signal for CAN `I`.
+    #[inline]
+    pub const fn deassert_reset<const I: usize>(self) -> Self {
+        Self(self.0 | (1 << (I + 16)))
+    }
+}
+
+/// SPI Clock register.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct SpiClock(u32);
+
+impl SpiClock {
+    const CLK_SRC_SEL: u32 = 0x7 << 24;
+    const FACTOR_N: u32 = 0x3 << 8;
+    const FACTOR_M: u32 = 0xf << 0;
+    /// Get SPI clock source.
+    #[inline]
+    pub const fn clock_source(self) -> SpiClockSource {
+        match (self.0 & Self::CLK_SRC_SEL) >> 24 {
+            0x0 => SpiClockSource::Hosc,
+            0x1 => SpiClockSource::PllPeri1x,
+            0x2 => SpiClockSource::PllPeri2x,
+            0x3 => SpiClockSource::PllAudio1Div2,
+            0x4 => SpiClockSource::PllAudio1Div5,
+            _ => panic!("impossible clock source"),
         }
     }
+    /// Set SPI clock source.
     #[inline]
-    unsafe fn mask_gate_only(ccu: &RegisterBlock) {
-        unsafe {
-            ccu.spi_bgr.modify(|v| v.gate_mask::<I>());
+    pub const fn set_clock_source(self, val: SpiClockSource) -> Self {
+        let val = match val {
+            SpiClockSource::Hosc => 0x0,
+            SpiClockSource::PllPeri1x => 0x1,
+            SpiClockSource::PllPeri2x => 0x2,
+            SpiClockSource::PllAudio1Div2 => 0x3,
+            SpiClockSource::PllAudio1Div5 => 0x4,
+        };
+        Self((self.0 & !Self::CLK_SRC_SEL) | (val << 24))
+    }
+    /// Get SPI clock divide factor N.
+    #[inline]
+    pub const fn factor_n(self) -> PeriFactorN {
+        match (self.0 & Self::FACTOR_N) >> 8 {
+            0 => PeriFactorN::N1,
+            1 => PeriFactorN::N2,
+            2 => PeriFactorN::N4,
+            3 => PeriFactorN::N8,
+            _ => unreachable!(),
         }
     }
+    /// Set SPI clock divide factor N.
     #[inline]
-    unsafe fn disable_in(ccu: &RegisterBlock) {
-        unsafe {
-            ccu.spi_bgr
-                .modify(|v| v.gate_mask::<I>().assert_reset::<I>());
+    pub const fn set_factor_n(self, val: PeriFactorN) -> Self {
+        let val = match val {
+            PeriFactorN::N1 => 0,
+            PeriFactorN::N2 => 1,
+            PeriFactorN::N4 => 2,
+            PeriFactorN::N8 => 3,
+        };
+        Self((self.0 & !Self::FACTOR_N) | (val << 8))
+    }
+    /// Get SPI clock divide factor M.
+    #[inline]
+    pub const fn factor_m(self) -> u8 {
+        (self.0 & Self::FACTOR_M) as u8
+    }
+    /// Set SPI clock divide factor M.
+    #[inline]
+    pub const fn set_factor_m(self, val: u8) -> Self {
+        Self((self.0 & !Self::FACTOR_M) | val as u32)
+    }
+}
+
+/// LEDC Clock register.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct LedcClock(u32);
+
+impl LedcClock {
+    const LEDC_CLK_GATING: u32 = 0x1 << 31;
+    const CLK_SRC_SEL: u32 = 0x1 << 24;
+    const FACTOR_N: u32 = 0x3 << 8;
+    const FACTOR_M: u32 = 0xf;
+
+    /// If clock is unmasked.
+    #[inline]
+    pub const fn is_clock_unmasked(self) -> bool {
+        self.0 & Self::LEDC_CLK_GATING != 0
+    }
+    /// Unmask (enable) clock.
+    #[inline]
+    pub const fn unmask_clock(self) -> Self {
+        Self(self.0 | Self::LEDC_CLK_GATING)
+    }
+    /// Mask (disable) clock.
+    #[inline]
+    pub const fn mask_clock(self) -> Self {
+        Self(self.0 & !Self::LEDC_CLK_GATING)
+    }
+    /// Get LEDC clock source.
+    #[inline]
+    pub const fn clock_source(self) -> LedcClockSource {
+        match (self.0 & Self::CLK_SRC_SEL) >> 24 {
+            0x0 => LedcClockSource::Hosc,
+            0x1 => LedcClockSource::PllPeri1x,
+            _ => unreachable!(),
         }
     }
+    /// Set LEDC clock source.
     #[inline]
-    unsafe fn enable_in(ccu: &RegisterBlock) {
-        unsafe {
-            ccu.spi_bgr
-                .modify(|v| v.gate_pass::<I>().deassert_reset::<I>());
+    pub const fn set_clock_source(self, val: LedcClockSource) -> Self {
+        Self((self.0 & !Self::CLK_SRC_SEL) | ((val as u32) << 24))
+    }
+    /// Get LEDC clock divide factor N.
+    #[inline]
+    pub const fn factor_n(self) -> PeriFactorN {
+        match (self.0 & Self::FACTOR_N) >> 8 {
+            0 => PeriFactorN::N1,
+            1 => PeriFactorN::N2,
+            2 => PeriFactorN::N4,
+            3 => PeriFactorN::N8,
+            _ => unreachable!(),
+        }
+    }
+    /// Set LEDC clock divide factor N.
+    #[inline]
+    pub const fn set_factor_n(self, val: PeriFactorN) -> Self {
+        let val = match val {
+            PeriFactorN::N1 => 0,
+            PeriFactorN::N2 => 1,
+            PeriFactorN::N4 => 2,
+            PeriFactorN::N8 => 3,
+        };
+        Self((self.0 & !Self::FACTOR_N) | (val << 8))
+    }
+    /// Get LEDC clock divide factor M.
+    #[inline]
+    pub const fn factor_m(self) -> u8 {
+        (self.0 & Self::FACTOR_M) as u8
+    }
+    /// Set LEDC clock divide factor M.
+    #[inline]
+    pub const fn set_factor_m(self, val: u8) -> Self {
+        Self((self.0 & !Self::FACTOR_M) | val as u32)
+    }
+}
+
+/// LEDC Bus Gating Reset register.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct LedcBusGating(u32);
+
+impl LedcBusGating {
+    const LEDC_RST: u32 = 1 << 16;
+    const LEDC_GATING: u32 = 1 << 0;
+
+    /// Assert LEDC reset.
+    #[inline]
+    pub const fn assert_reset(self) -> Self {
+        Self(self.0 & !Self::LEDC_RST)
+    }
+    /// De-assert LEDC reset.
+    #[inline]
+    pub const fn deassert_reset(self) -> Self {
+        Self(self.0 | Self::LEDC_RST)
+    }
+    /// Mask the LEDC gating.
+    #[inline]
+    pub const fn gate_mask(self) -> Self {
+        Self(self.0 & !Self::LEDC_GATING)
+    }
+    /// Unmask (pass) the LEDC gating.
+    #[inline]
+    pub const fn gate_pass(self) -> Self {
+        Self(self.0 | Self::LEDC_GATING)
+    }
+}
+
+/// DSP Clock register.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct DspClock(u32);
+
+impl DspClock {
+    const CLK_GATING: u32 = 0x1 << 31;
+    const CLK_SRC_SEL: u32 = 0x7 << 24;
+    const FACTOR_M: u32 = 0x1f;
+
+    /// If clock is unmasked.
+    #[inline]
+    pub const fn is_clock_unmasked(self) -> bool {
+        self.0 & Self::CLK_GATING != 0
+    }
+    /// Unmask (enable) clock.
+    #[inline]
+    pub const fn unmask_clock(self) -> Self {
+        Self(self.0 | Self::CLK_GATING)
+    }
+    /// Mask (disable) clock.
+    #[inline]
+    pub const fn mask_clock(self) -> Self {
+        Self(self.0 & !Self::CLK_GATING)
+    }
+    /// Get DSP clock source.
+    #[inline]
+    pub const fn clock_source(self) -> DspClockSource {
+        match (self.0 & Self::CLK_SRC_SEL) >> 24 {
+            0x0 => DspClockSource::Hosc,
+            0x1 => DspClockSource::Clk32K,
+            0x2 => DspClockSource::Clk16MRC,
+            0x3 => DspClockSource::PllPeri2x,
+            0x4 => DspClockSource::PllAudio1Div2,
+            _ => panic!("impossible clock source"),
         }
     }
+    /// Set DSP clock source.
+    #[inline]
+    pub const fn set_clock_source(self, val: DspClockSource) -> Self {
+        Self((self.0 & !Self::CLK_SRC_SEL) | ((val as u32) << 24))
+    }
+    /// Get DSP clock divide factor M.
+    #[inline]
+    pub const fn factor_m(self) -> u8 {
+        (self.0 & Self::FACTOR_M) as u8
+    }
+    /// Set DSP clock divide factor M.
+    #[inline]
+    pub const fn set_factor_m(self, val: u8) -> Self {
+        Self((self.0 & !Self::FACTOR_M) | (val as u32 & Self::FACTOR_M))
+    }
 }
 
-impl<const I: usize> ClockConfig for SPI<I> {
-    type Source = SpiClockSource;
+/// RISC-V Clock register.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct RiscvClock(u32);
+
+impl RiscvClock {
+    const CLK_SRC_SEL: u32 = 0x7 << 24;
+    const AXI_DIV_CFG: u32 = 0x3 << 8;
+    const DIV_CFG: u32 = 0x1f;
+
+    /// Get RISC-V core clock source.
+    #[inline]
+    pub const fn clock_source(self) -> RiscvClockSource {
+        match (self.0 & Self::CLK_SRC_SEL) >> 24 {
+            0x0 => RiscvClockSource::Hosc,
+            0x1 => RiscvClockSource::Clk32K,
+            0x2 => RiscvClockSource::Clk16MRC,
+            0x3 => RiscvClockSource::PllPeri800M,
+            0x4 => RiscvClockSource::PllPeri1x,
+            0x5 => RiscvClockSource::PllCpu,
+            0x6 => RiscvClockSource::PllAudio1Div2,
+            _ => panic!("impossible clock source"),
+        }
+    }
+    /// Set RISC-V core clock source.
+    #[inline]
+    pub const fn set_clock_source(self, val: RiscvClockSource) -> Self {
+        Self((self.0 & !Self::CLK_SRC_SEL) | ((val as u32) << 24))
+    }
+    /// Get RISC-V AXI clock divide factor N, dividing core clock by N + 1.
+    #[inline]
+    pub const fn axi_factor_n(self) -> u8 {
+        ((self.0 & Self::AXI_DIV_CFG) >> 8) as u8
+    }
+    /// Set RISC-V AXI clock divide factor N.
+    #[inline]
+    pub const fn set_axi_factor_n(self, val: u8) -> Self {
+        Self((self.0 & !Self::AXI_DIV_CFG) | ((val as u32) << 8 & Self::AXI_DIV_CFG))
+    }
+    /// Get RISC-V core clock divide factor M, dividing clock source by M + 1.
+    #[inline]
+    pub const fn factor_m(self) -> u8 {
+        (self.0 & Self::DIV_CFG) as u8
+    }
+    /// Set RISC-V core clock divide factor M.
+    #[inline]
+    pub const fn set_factor_m(self, val: u8) -> Self {
+        Self((self.0 & !Self::DIV_CFG) | (val as u32 & Self::DIV_CFG))
+    }
+}
+
+/// DSP Bus Gating Reset register.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct DspBusGating(u32);
+
+impl DspBusGating {
+    const DSP_DBG_RST: u32 = 1 << 18;
+    const DSP_CFG_RST: u32 = 1 << 17;
+    const DSP_RST: u32 = 1 << 16;
+    const DSP_CFG_GATING: u32 = 1 << 1;
+
+    /// Assert DSP configuration and debug reset.
+    #[inline]
+    pub const fn assert_reset(self) -> Self {
+        Self(self.0 & !(Self::DSP_CFG_RST | Self::DSP_DBG_RST))
+    }
+    /// De-assert DSP configuration and debug reset.
+    #[inline]
+    pub const fn deassert_reset(self) -> Self {
+        Self(self.0 | Self::DSP_CFG_RST | Self::DSP_DBG_RST)
+    }
+    /// Check if DSP core is held in reset.
+    #[inline]
+    pub const fn is_core_reset_asserted(self) -> bool {
+        self.0 & Self::DSP_RST == 0
+    }
+    /// Assert DSP core reset.
+    #[inline]
+    pub const fn assert_core_reset(self) -> Self {
+        Self(self.0 & !Self::DSP_RST)
+    }
+    /// De-assert DSP core reset.
+    #[inline]
+    pub const fn deassert_core_reset(self) -> Self {
+        Self(self.0 | Self::DSP_RST)
+    }
+    /// Mask the DSP configuration gating.
+    #[inline]
+    pub const fn gate_mask(self) -> Self {
+        Self(self.0 & !Self::DSP_CFG_GATING)
+    }
+    /// Unmask (pass) the DSP configuration gating.
+    #[inline]
+    pub const fn gate_pass(self) -> Self {
+        Self(self.0 | Self::DSP_CFG_GATING)
+    }
+}
+
+/// Display Engine Clock register.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct DeClock(u32);
+
+impl DeClock {
+    const CLK_GATING: u32 = 0x1 << 31;
+    const CLK_SRC_SEL: u32 = 0x7 << 24;
+    const FACTOR_M: u32 = 0x1f;
+
+    /// If clock is unmasked.
+    #[inline]
+    pub const fn is_clock_unmasked(self) -> bool {
+        self.0 & Self::CLK_GATING != 0
+    }
+    /// Unmask (enable) clock.
+    #[inline]
+    pub const fn unmask_clock(self) -> Self {
+        Self(self.0 | Self::CLK_GATING)
+    }
+    /// Mask (disable) clock.
+    #[inline]
+    pub const fn mask_clock(self) -> Self {
+        Self(self.0 & !Self::CLK_GATING)
+    }
+    /// Get display engine clock source.
+    #[inline]
+    pub const fn clock_source(self) -> DeClockSource {
+        match (self.0 & Self::CLK_SRC_SEL) >> 24 {
+            0x0 => DeClockSource::PllPeri2x,
+            0x1 => DeClockSource::PllVideo0x4,
+            0x2 => DeClockSource::PllVideo1x4,
+            0x3 => DeClockSource::PllAudio1Div2,
+            _ => panic!("impossible clock source"),
+        }
+    }
+    /// Set display engine clock source.
+    #[inline]
+    pub const fn set_clock_source(self, val: DeClockSource) -> Self {
+        Self((self.0 & !Self::CLK_SRC_SEL) | ((val as u32) << 24))
+    }
+    /// Get display engine clock divide factor M.
+    #[inline]
+    pub const fn factor_m(self) -> u8 {
+        (self.0 & Self::FACTOR_M) as u8
+    }
+    /// Set display engine clock divide factor M.
+    #[inline]
+    pub const fn set_factor_m(self, val: u8) -> Self {
+        Self((self.0 & !Self::FACTOR_M) | (val as u32 & Self::FACTOR_M))
+    }
+}
+
+/// Display Engine Bus Gating Reset register.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct DeBusGating(u32);
+
+impl DeBusGating {
+    const DE_RST: u32 = 1 << 16;
+    const DE_GATING: u32 = 1 << 0;
+
+    /// Assert display engine reset.
+    #[inline]
+    pub const fn assert_reset(self) -> Self {
+        Self(self.0 & !Self::DE_RST)
+    }
+    /// De-assert display engine reset.
+    #[inline]
+    pub const fn deassert_reset(self) -> Self {
+        Self(self.0 | Self::DE_RST)
+    }
+    /// Mask the display engine gating.
+    #[inline]
+    pub const fn gate_mask(self) -> Self {
+        Self(self.0 & !Self::DE_GATING)
+    }
+    /// Unmask (pass) the display engine gating.
+    #[inline]
+    pub const fn gate_pass(self) -> Self {
+        Self(self.0 | Self::DE_GATING)
+    }
+}
+
+/// Memory bus master of MBUS clock gating.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum MbusMaster {
+    /// DMA controller.
+    Dma = 0,
+    /// Video engine.
+    Ve = 1,
+    /// Crypto engine.
+    Ce = 2,
+}
+
+/// MBUS Master Clock Gating register.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct MbusGating(u32);
+
+impl MbusGating {
+    /// If DRAM access clock of `master` is unmasked.
+    #[inline]
+    pub const fn is_gate_passed(self, master: MbusMaster) -> bool {
+        self.0 & (1 << master as u32) != 0
+    }
+    /// Mask DRAM access clock of `master`.
+    #[inline]
+    pub const fn gate_mask(self, master: MbusMaster) -> Self {
+        Self(self.0 & !(1 << master as u32))
+    }
+    /// Unmask (pass) DRAM access clock of `master`.
+    #[inline]
+    pub const fn gate_pass(self, master: MbusMaster) -> Self {
+        Self(self.0 | (1 << master as u32))
+    }
+}
+
+/// Crypto Engine Clock register.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct CeClock(u32);
+
+impl CeClock {
+    const CLK_GATING: u32 = 0x1 << 31;
+    const CLK_SRC_SEL: u32 = 0x7 << 24;
+    const FACTOR_N: u32 = 0x3 << 8;
+    const FACTOR_M: u32 = 0xf;
+
+    /// If clock is unmasked.
+    #[inline]
+    pub const fn is_clock_unmasked(self) -> bool {
+        self.0 & Self::CLK_GATING != 0
+    }
+    /// Unmask (enable) clock.
+    #[inline]
+    pub const fn unmask_clock(self) -> Self {
+        Self(self.0 | Self::CLK_GATING)
+    }
+    /// Mask (disable) clock.
+    #[inline]
+    pub const fn mask_clock(self) -> Self {
+        Self(self.0 & !Self::CLK_GATING)
+    }
+    /// Get crypto engine clock source.
+    #[inline]
+    pub const fn clock_source(self) -> CeClockSource {
+        match (self.0 & Self::CLK_SRC_SEL) >> 24 {
+            0x0 => CeClockSource::Hosc,
+            0x1 => CeClockSource::PllPeri2x,
+            0x2 => CeClockSource::PllPeri1x,
+            _ => panic!("impossible clock source"),
+        }
+    }
+    /// Set crypto engine clock source.
+    #[inline]
+    pub const fn set_clock_source(self, val: CeClockSource) -> Self {
+        Self((self.0 & !Self::CLK_SRC_SEL) | ((val as u32) << 24))
+    }
+    /// Get crypto engine clock divide factor N.
+    #[inline]
+    pub const fn factor_n(self) -> PeriFactorN {
+        match (self.0 & Self::FACTOR_N) >> 8 {
+            0 => PeriFactorN::N1,
+            1 => PeriFactorN::N2,
+            2 => PeriFactorN::N4,
+            3 => PeriFactorN::N8,
+            _ => unreachable!(),
+        }
+    }
+    /// Set crypto engine clock divide factor N.
+    #[inline]
+    pub const fn set_factor_n(self, val: PeriFactorN) -> Self {
+        let val = match val {
+            PeriFactorN::N1 => 0,
+            PeriFactorN::N2 => 1,
+            PeriFactorN::N4 => 2,
+            PeriFactorN::N8 => 3,
+        };
+        Self((self.0 & !Self::FACTOR_N) | (val << 8))
+    }
+    /// Get crypto engine clock divide factor M.
+    #[inline]
+    pub const fn factor_m(self) -> u8 {
+        (self.0 & Self::FACTOR_M) as u8
+    }
+    /// Set crypto engine clock divide factor M.
+    #[inline]
+    pub const fn set_factor_m(self, val: u8) -> Self {
+        Self((self.0 & !Self::FACTOR_M) | (val as u32 & Self::FACTOR_M))
+    }
+}
+
+/// Crypto Engine Bus Gating Reset register.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct CeBusGating(u32);
+
+impl CeBusGating {
+    const CE_RST: u32 = 1 << 16;
+    const CE_GATING: u32 = 1 << 0;
+
+    /// Assert crypto engine reset.
+    #[inline]
+    pub const fn assert_reset(self) -> Self {
+        Self(self.0 & !Self::CE_RST)
+    }
+    /// De-assert crypto engine reset.
+    #[inline]
+    pub const fn deassert_reset(self) -> Self {
+        Self(self.0 | Self::CE_RST)
+    }
+    /// Mask the crypto engine gating.
+    #[inline]
+    pub const fn gate_mask(self) -> Self {
+        Self(self.0 & !Self::CE_GATING)
+    }
+    /// Unmask (pass) the crypto engine gating.
+    #[inline]
+    pub const fn gate_pass(self) -> Self {
+        Self(self.0 | Self::CE_GATING)
+    }
+}
+
+/// 2D Graphics Accelerator Clock register.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct G2dClock(u32);
+
+impl G2dClock {
+    const CLK_GATING: u32 = 0x1 << 31;
+    const CLK_SRC_SEL: u32 = 0x7 << 24;
+    const FACTOR_M: u32 = 0x1f;
+
+    /// If clock is unmasked.
+    #[inline]
+    pub const fn is_clock_unmasked(self) -> bool {
+        self.0 & Self::CLK_GATING != 0
+    }
+    /// Unmask (enable) clock.
+    #[inline]
+    pub const fn unmask_clock(self) -> Self {
+        Self(self.0 | Self::CLK_GATING)
+    }
+    /// Mask (disable) clock.
+    #[inline]
+    pub const fn mask_clock(self) -> Self {
+        Self(self.0 & !Self::CLK_GATING)
+    }
+    /// Get 2D graphics accelerator clock source.
+    #[inline]
+    pub const fn clock_source(self) -> G2dClockSource {
+        match (self.0 & Self::CLK_SRC_SEL) >> 24 {
+            0x0 => G2dClockSource::PllPeri2x,
+            0x1 => G2dClockSource::PllVideo0x4,
+            0x2 => G2dClockSource::PllVideo1x4,
+            0x3 => G2dClockSource::PllAudio1Div2,
+            _ => panic!("impossible clock source"),
+        }
+    }
+    /// Set 2D graphics accelerator clock source.
+    #[inline]
+    pub const fn set_clock_source(self, val: G2dClockSource) -> Self {
+        Self((self.0 & !Self::CLK_SRC_SEL) | ((val as u32) << 24))
+    }
+    /// Get 2D graphics accelerator clock divide factor M.
+    #[inline]
+    pub const fn factor_m(self) -> u8 {
+        (self.0 & Self::FACTOR_M) as u8
+    }
+    /// Set 2D graphics accelerator clock divide factor M.
+    #[inline]
+    pub const fn set_factor_m(self, val: u8) -> Self {
+        Self((self.0 & !Self::FACTOR_M) | (val as u32 & Self::FACTOR_M))
+    }
+}
+
+/// 2D Graphics Accelerator Bus Gating Reset register.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct G2dBusGating(u32);
+
+impl G2dBusGating {
+    const G2D_RST: u32 = 1 << 16;
+    const G2D_GATING: u32 = 1 << 0;
+
+    /// Assert 2D graphics accelerator reset.
+    #[inline]
+    pub const fn assert_reset(self) -> Self {
+        Self(self.0 & !Self::G2D_RST)
+    }
+    /// De-assert 2D graphics accelerator reset.
+    #[inline]
+    pub const fn deassert_reset(self) -> Self {
+        Self(self.0 | Self::G2D_RST)
+    }
+    /// Mask the 2D graphics accelerator gating.
+    #[inline]
+    pub const fn gate_mask(self) -> Self {
+        Self(self.0 & !Self::G2D_GATING)
+    }
+    /// Unmask (pass) the 2D graphics accelerator gating.
+    #[inline]
+    pub const fn gate_pass(self) -> Self {
+        Self(self.0 | Self::G2D_GATING)
+    }
+}
+
+/// Display Subsystem Top Bus Gating Reset register.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct DpssTopBusGating(u32);
+
+impl DpssTopBusGating {
+    const DPSS_TOP_RST: u32 = 1 << 16;
+    const DPSS_TOP_GATING: u32 = 1 << 0;
+
+    /// Assert display subsystem top reset.
+    #[inline]
+    pub const fn assert_reset(self) -> Self {
+        Self(self.0 & !Self::DPSS_TOP_RST)
+    }
+    /// De-assert display subsystem top reset.
+    #[inline]
+    pub const fn deassert_reset(self) -> Self {
+        Self(self.0 | Self::DPSS_TOP_RST)
+    }
+    /// Mask the display subsystem top gating.
+    #[inline]
+    pub const fn gate_mask(self) -> Self {
+        Self(self.0 & !Self::DPSS_TOP_GATING)
+    }
+    /// Unmask (pass) the display subsystem top gating.
+    #[inline]
+    pub const fn gate_pass(self) -> Self {
+        Self(self.0 | Self::DPSS_TOP_GATING)
+    }
+}
+
+/// HDMI 24-MHz Clock register.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct Hdmi24mClock(u32);
+
+impl Hdmi24mClock {
+    const CLK_GATING: u32 = 1 << 31;
+
+    /// If HDMI 24-MHz clock is unmasked.
+    #[inline]
+    pub const fn is_clock_unmasked(self) -> bool {
+        self.0 & Self::CLK_GATING != 0
+    }
+    /// Unmask (enable) HDMI 24-MHz clock.
+    #[inline]
+    pub const fn unmask_clock(self) -> Self {
+        Self(self.0 | Self::CLK_GATING)
+    }
+    /// Mask (disable) HDMI 24-MHz clock.
+    #[inline]
+    pub const fn mask_clock(self) -> Self {
+        Self(self.0 & !Self::CLK_GATING)
+    }
+}
+
+/// HDMI Bus Gating Reset register.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct HdmiBusGating(u32);
+
+impl HdmiBusGating {
+    const HDMI_SUB_RST: u32 = 1 << 17;
+    const HDMI_MAIN_RST: u32 = 1 << 16;
+    const HDMI_GATING: u32 = 1 << 0;
+
+    /// Assert HDMI main and sub resets.
+    #[inline]
+    pub const fn assert_reset(self) -> Self {
+        Self(self.0 & !(Self::HDMI_MAIN_RST | Self::HDMI_SUB_RST))
+    }
+    /// De-assert HDMI main and sub resets.
+    #[inline]
+    pub const fn deassert_reset(self) -> Self {
+        Self(self.0 | Self::HDMI_MAIN_RST | Self::HDMI_SUB_RST)
+    }
+    /// Mask the HDMI gating.
+    #[inline]
+    pub const fn gate_mask(self) -> Self {
+        Self(self.0 & !Self::HDMI_GATING)
+    }
+    /// Unmask (pass) the HDMI gating.
+    #[inline]
+    pub const fn gate_pass(self) -> Self {
+        Self(self.0 | Self::HDMI_GATING)
+    }
+}
+
+/// TCON LCD Clock register.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct TconLcdClock(u32);
+
+impl TconLcdClock {
+    const CLK_GATING: u32 = 0x1 << 31;
+    const CLK_SRC_SEL: u32 = 0x7 << 24;
+    const FACTOR_N: u32 = 0x3 << 8;
+    const FACTOR_M: u32 = 0xf;
+
+    /// If clock is unmasked.
+    #[inline]
+    pub const fn is_clock_unmasked(self) -> bool {
+        self.0 & Self::CLK_GATING != 0
+    }
+    /// Unmask (enable) clock.
+    #[inline]
+    pub const fn unmask_clock(self) -> Self {
+        Self(self.0 | Self::CLK_GATING)
+    }
+    /// Mask (disable) clock.
+    #[inline]
+    pub const fn mask_clock(self) -> Self {
+        Self(self.0 & !Self::CLK_GATING)
+    }
+    /// Get TCON LCD clock source.
+    #[inline]
+    pub const fn clock_source(self) -> TconLcdClockSource {
+        match (self.0 & Self::CLK_SRC_SEL) >> 24 {
+            0x0 => TconLcdClockSource::PllVideo0,
+            0x1 => TconLcdClockSource::PllVideo0x4,
+            0x2 => TconLcdClockSource::PllVideo1,
+            0x3 => TconLcdClockSource::PllVideo1x4,
+            0x4 => TconLcdClockSource::PllPeri2x,
+            0x5 => TconLcdClockSource::PllAudio1Div2,
+            _ => panic!("impossible clock source"),
+        }
+    }
+    /// Set TCON LCD clock source.
+    #[inline]
+    pub const fn set_clock_source(self, val: TconLcdClockSource) -> Self {
+        Self((self.0 & !Self::CLK_SRC_SEL) | ((val as u32) << 24))
+    }
+    /// Get TCON LCD clock divide factor N.
+    #[inline]
+    pub const fn factor_n(self) -> PeriFactorN {
+        match (self.0 & Self::FACTOR_N) >> 8 {
+            0 => PeriFactorN::N1,
+            1 => PeriFactorN::N2,
+            2 => PeriFactorN::N4,
+            3 => PeriFactorN::N8,
+            _ => unreachable!(),
+        }
+    }
+    /// Set TCON LCD clock divide factor N.
+    #[inline]
+    pub const fn set_factor_n(self, val: PeriFactorN) -> Self {
+        let val = match val {
+            PeriFactorN::N1 => 0,
+            PeriFactorN::N2 => 1,
+            PeriFactorN::N4 => 2,
+            PeriFactorN::N8 => 3,
+        };
+        Self((self.0 & !Self::FACTOR_N) | (val << 8))
+    }
+    /// Get TCON LCD clock divide factor M.
+    #[inline]
+    pub const fn factor_m(self) -> u8 {
+        (self.0 & Self::FACTOR_M) as u8
+    }
+    /// Set TCON LCD clock divide factor M.
+    #[inline]
+    pub const fn set_factor_m(self, val: u8) -> Self {
+        Self((self.0 & !Self::FACTOR_M) | val as u32)
+    }
+}
+
+/// TCON LCD Bus Gating Reset register.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct TconLcdBusGating(u32);
+
+impl TconLcdBusGating {
+    const TCON_LCD_RST: u32 = 1 << 16;
+    const TCON_LCD_GATING: u32 = 1 << 0;
+
+    /// Assert TCON LCD reset.
+    #[inline]
+    pub const fn assert_reset(self) -> Self {
+        Self(self.0 & !Self::TCON_LCD_RST)
+    }
+    /// De-assert TCON LCD reset.
+    #[inline]
+    pub const fn deassert_reset(self) -> Self {
+        Self(self.0 | Self::TCON_LCD_RST)
+    }
+    /// Mask the TCON LCD gating.
+    #[inline]
+    pub const fn gate_mask(self) -> Self {
+        Self(self.0 & !Self::TCON_LCD_GATING)
+    }
+    /// Unmask (pass) the TCON LCD gating.
+    #[inline]
+    pub const fn gate_pass(self) -> Self {
+        Self(self.0 | Self::TCON_LCD_GATING)
+    }
+}
+
+/// SPI Bus Gating Reset register.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct SpiBusGating(u32);
+
+impl SpiBusGating {
+    /// Disable clock gate for SPI `I`.
+    #[inline]
+    pub const fn gate_mask<const I: usize>(self) -> Self {
+        Self(self.0 & !(1 << I))
+    }
+    /// Enable clock gate for SPI `I`.
+    #[inline]
+    pub const fn gate_pass<const I: usize>(self) -> Self {
+        Self(self.0 | (1 << I))
+    }
+    /// Assert reset signal for SPI `I`.
+    #[inline]
+    pub const fn assert_reset<const I: usize>(self) -> Self {
+        Self(self.0 & !(1 << (I + 16)))
+    }
+    /// Deassert reset signal for SPI `I`.
+    #[inline]
+    pub const fn deassert_reset<const I: usize>(self) -> Self {
+        Self(self.0 | (1 << (I + 16)))
+    }
+}
+
+/// EMAC 25M Clock register.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct Emac25MClock(u32);
+
+impl Emac25MClock {
+    const CLK_GATING: u32 = 1 << 31;
+    const SRC_GATING: u32 = 1 << 30;
+
+    /// Check if 25-MHz clock output for Ethernet PHY is enabled.
+    #[inline]
+    pub const fn is_clock_enabled(self) -> bool {
+        self.0 & Self::CLK_GATING != 0 && self.0 & Self::SRC_GATING != 0
+    }
+    /// Enable 25-MHz clock output for Ethernet PHY.
+    #[inline]
+    pub const fn enable_clock(self) -> Self {
+        Self(self.0 | Self::CLK_GATING | Self::SRC_GATING)
+    }
+    /// Disable 25-MHz clock output for Ethernet PHY.
+    #[inline]
+    pub const fn disable_clock(self) -> Self {
+        Self(self.0 & !(Self::CLK_GATING | Self::SRC_GATING))
+    }
+}
+
+/// EMAC Bus Gating Reset register.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct EmacBusGating(u32);
+
+impl EmacBusGating {
+    const EMAC_RST: u32 = 1 << 16;
+    const EMAC_GATING: u32 = 1 << 0;
+
+    /// Assert EMAC reset.
+    #[inline]
+    pub const fn assert_reset(self) -> Self {
+        Self(self.0 & !Self::EMAC_RST)
+    }
+    /// De-assert EMAC reset.
+    #[inline]
+    pub const fn deassert_reset(self) -> Self {
+        Self(self.0 | Self::EMAC_RST)
+    }
+    /// Mask the EMAC gating.
+    #[inline]
+    pub const fn gate_mask(self) -> Self {
+        Self(self.0 & !Self::EMAC_GATING)
+    }
+    /// Unmask (pass) the EMAC gating.
+    #[inline]
+    pub const fn gate_pass(self) -> Self {
+        Self(self.0 | Self::EMAC_GATING)
+    }
+}
+
+/// SMHC Clock register.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct SmhcClock(u32);
+
+impl SmhcClock {
+    const CLK_SRC_SEL: u32 = 0x7 << 24;
+    const FACTOR_N: u32 = 0x3 << 8;
+    const FACTOR_M: u32 = 0xf << 0;
+    const CLK_GATING: u32 = 1 << 31;
+
+    /// Get SMHC clock source.
+    #[inline]
+    pub const fn clock_source(self) -> SmhcClockSource {
+        match (self.0 & Self::CLK_SRC_SEL) >> 24 {
+            0x0 => SmhcClockSource::Hosc,
+            0x1 => SmhcClockSource::PllPeri1x,
+            0x2 => SmhcClockSource::PllPeri2x,
+            0x3 => SmhcClockSource::PllPeri800M,
+            0x4 => SmhcClockSource::PllAudio1Div2,
+            _ => panic!("impossible clock source"),
+        }
+    }
+    /// Set SMHC clock source.
+    #[inline]
+    pub const fn set_clock_source(self, val: SmhcClockSource) -> Self {
+        let val = match val {
+            SmhcClockSource::Hosc => 0x0,
+            SmhcClockSource::PllPeri1x => 0x1,
+            SmhcClockSource::PllPeri2x => 0x2,
+            SmhcClockSource::PllPeri800M => 0x3,
+            SmhcClockSource::PllAudio1Div2 => 0x4,
+        };
+        Self((self.0 & !Self::CLK_SRC_SEL) | (val << 24))
+    }
+    /// Get clock source of SMHC `I`.
+    ///
+    /// SMHC0 and SMHC1 have no PLL_PERI(800M) source, and select PLL_AUDIO1(DIV2) by value 3.
+    #[inline]
+    pub const fn instance_clock_source<const I: usize>(self) -> SmhcClockSource {
+        match ((self.0 & Self::CLK_SRC_SEL) >> 24, I) {
+            (0x3, 0 | 1) => SmhcClockSource::PllAudio1Div2,
+            _ => self.clock_source(),
+        }
+    }
+    /// Set clock source of SMHC `I`.
+    ///
+    /// Returns `None` if PLL_PERI(800M) is selected on SMHC0 or SMHC1, which lack it.
+    #[inline]
+    pub const fn set_instance_clock_source<const I: usize>(
+        self,
+        val: SmhcClockSource,
+    ) -> Option<Self> {
+        match (val, I) {
+            (SmhcClockSource::PllPeri800M, 0 | 1) => None,
+            (SmhcClockSource::PllAudio1Div2, 0 | 1) => {
+                Some(Self((self.0 & !Self::CLK_SRC_SEL) | (0x3 << 24)))
+            }
+            _ => Some(self.set_clock_source(val)),
+        }
+    }
+    /// Get SMHC clock divide factor N.
+    #[inline]
+    pub const fn factor_n(self) -> PeriFactorN {
+        match (self.0 & Self::FACTOR_N) >> 8 {
+            0 => PeriFactorN::N1,
+            1 => PeriFactorN::N2,
+            2 => PeriFactorN::N4,
+            3 => PeriFactorN::N8,
+            _ => unreachable!(),
+        }
+    }
+    /// Set SMHC clock divide factor N.
+    #[inline]
+    pub const fn set_factor_n(self, val: PeriFactorN) -> Self {
+        let val = match val {
+            PeriFactorN::N1 => 0,
+            PeriFactorN::N2 => 1,
+            PeriFactorN::N4 => 2,
+            PeriFactorN::N8 => 3,
+        };
+        Self((self.0 & !Self::FACTOR_N) | (val << 8))
+    }
+    /// Get SMHC clock divide factor M.
+    #[inline]
+    pub const fn factor_m(self) -> u8 {
+        (self.0 & Self::FACTOR_M) as u8
+    }
+    /// Set SMHC clock divide factor M.
+    #[inline]
+    pub const fn set_factor_m(self, val: u8) -> Self {
+        Self((self.0 & !Self::FACTOR_M) | val as u32)
+    }
+    /// Enable clock gating.
+    #[inline]
+    pub const fn enable_clock_gating(self) -> Self {
+        Self(self.0 | Self::CLK_GATING)
+    }
+    /// Disable clock gating.
+    #[inline]
+    pub const fn disable_clock_gating(self) -> Self {
+        Self(self.0 & !Self::CLK_GATING)
+    }
+    /// Get if clock gating is enabled.
+    #[inline]
+    pub const fn is_clock_gating_enabled(self) -> bool {
+        self.0 & Self::CLK_GATING != 0
+    }
+}
+
+/// SMHC Clock Reset register.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct SmhcBusGating(u32);
+
+impl SmhcBusGating {
+    /// Disable clock gate for SMHC `I`.
+    #[inline]
+    pub const fn gate_mask<const I: usize>(self) -> Self {
+        Self(self.0 & !(1 << I))
+    }
+    /// Enable clock gate for SMHC `I`.
+    #[inline]
+    pub const fn gate_pass<const I: usize>(self) -> Self {
+        Self(self.0 | (1 << I))
+    }
+    /// Assert reset signal for SMHC `I`.
+    #[inline]
+    pub const fn assert_reset<const I: usize>(self) -> Self {
+        Self(self.0 & !(1 << (I + 16)))
+    }
+    /// Deassert reset signal for SMHC `I`.
+    #[inline]
+    pub const fn deassert_reset<const I: usize>(self) -> Self {
+        Self(self.0 | (1 << (I + 16)))
+    }
+}
+
+/// Peripheral that have clock reset feature in CCU.
+pub trait ClockReset {
+    /// Assert reset signal.
+    unsafe fn assert_reset_only(ccu: &RegisterBlock);
+    /// Deassert reset signal.
+    unsafe fn deassert_reset_only(ccu: &RegisterBlock);
+}
+
+/// Peripheral that can be clock gated by CCU.
+pub trait ClockGate: ClockReset {
+    /// Unmask clock gate.
+    unsafe fn unmask_gate_only(ccu: &RegisterBlock);
+    /// Mask clock gate.
+    unsafe fn mask_gate_only(ccu: &RegisterBlock);
+    /// Assert reset signal and mask the clock gate.
+    unsafe fn disable_in(ccu: &RegisterBlock);
+    /// Deassert reset signal and unmask the clock gate.
+    unsafe fn enable_in(ccu: &RegisterBlock);
+    /// Reset this peripheral without reconfiguring clocks (if applicable).
+    #[inline]
+    unsafe fn reset(ccu: &RegisterBlock) {
+        unsafe {
+            // assert reset and then deassert reset.
+            Self::disable_in(ccu);
+            Self::enable_in(ccu);
+        }
+    }
+    /// Free this peripheral by provided `ccu`.
+    #[inline]
+    unsafe fn free(ccu: &RegisterBlock) {
+        unsafe {
+            // by default, asserting reset signal and mask clock gate.
+            Self::disable_in(ccu);
+        }
+    }
+}
+
+/// Peripheral whose clock can be configurated by CCU.
+pub trait ClockConfig {
+    /// Type of clock source.
+    type Source;
+    /// Configure peripheral clock.
+    ///
+    /// Value `factor_m` should be in 0 ..= 15.
+    unsafe fn configure(
+        ccu: &RegisterBlock,
+        source: Self::Source,
+        factor_m: u8,
+        factor_n: PeriFactorN,
+    );
+    /// Reconfigure peripheral clock by applying clock parameters while asserting reset.
+    #[inline]
+    unsafe fn reconfigure(
+        ccu: &RegisterBlock,
+        source: Self::Source,
+        factor_m: u8,
+        factor_n: PeriFactorN,
+    ) where
+        Self: ClockGate,
+    {
+        unsafe {
+            Self::disable_in(ccu);
+            Self::configure(ccu, source, factor_m, factor_n);
+            Self::enable_in(ccu);
+        }
+    }
+    /// Reconfigure this clock with dependency to a resettable clock type `T`.
+    #[inline]
+    unsafe fn reconfigure_with<T: ClockReset, F, G>(
+        ccu: &RegisterBlock,
+        dependency: T,
+        before_configure: F,
+        after_configure: G,
+    ) where
+        Self: ClockGate,
+        F: FnOnce(&RegisterBlock) -> (Self::Source, u8, PeriFactorN),
+        G: FnOnce(&RegisterBlock),
+    {
+        unsafe {
+            let _ = dependency; // does not use value, the type T is used instead
+            T::assert_reset_only(ccu);
+            Self::disable_in(ccu);
+            let (source, factor_m, factor_n) = before_configure(ccu);
+            Self::configure(ccu, source, factor_m, factor_n);
+            after_configure(ccu);
+            Self::deassert_reset_only(ccu);
+            T::deassert_reset_only(ccu);
+            Self::unmask_gate_only(ccu);
+        }
+    }
+}
+
+// TODO: a more proper abstraction considering the PLL source behind peripheral clock
+
+/// Dynamic Random-Access Memory (DRAM) clock type.
+pub struct DRAM;
+
+impl ClockReset for DRAM {
+    #[inline]
+    unsafe fn deassert_reset_only(ccu: &RegisterBlock) {
+        unsafe {
+            ccu.dram_bgr.modify(|v| v.deassert_reset());
+        }
+    }
+    #[inline]
+    unsafe fn assert_reset_only(ccu: &RegisterBlock) {
+        unsafe {
+            ccu.dram_bgr.modify(|v| v.assert_reset());
+        }
+    }
+}
+
+impl ClockGate for DRAM {
+    #[inline]
+    unsafe fn unmask_gate_only(ccu: &RegisterBlock) {
+        unsafe {
+            ccu.dram_bgr.modify(|v| v.gate_pass());
+        }
+    }
+    #[inline]
+    unsafe fn mask_gate_only(ccu: &RegisterBlock) {
+        unsafe {
+            ccu.dram_bgr.modify(|v| v.gate_mask());
+        }
+    }
+    #[inline]
+    unsafe fn disable_in(ccu: &RegisterBlock) {
+        unsafe {
+            ccu.dram_bgr.modify(|v| v.gate_mask().assert_reset());
+        }
+    }
+    #[inline]
+    unsafe fn enable_in(ccu: &RegisterBlock) {
+        unsafe {
+            ccu.dram_bgr.modify(|v| v.gate_pass().deassert_reset());
+        }
+    }
+}
+
+impl ClockConfig for DRAM {
+    type Source = DramClockSource;
+
+    #[inline]
+    unsafe fn configure(
+        ccu: &RegisterBlock,
+        source: Self::Source,
+        factor_m: u8,
+        factor_n: PeriFactorN,
+    ) {
+        unsafe {
+            let dram_clk = ccu.dram_clock.read();
+            ccu.dram_clock.write(
+                dram_clk
+                    .set_clock_source(source)
+                    .set_factor_m(factor_m)
+                    .set_factor_n(factor_n),
+            )
+        }
+    }
+}
+
+/// MCTL Bus (MBUS) clock type.
+pub struct MBUS;
+
+impl ClockReset for MBUS {
+    #[inline]
+    unsafe fn assert_reset_only(ccu: &RegisterBlock) {
+        unsafe {
+            ccu.mbus_clock.modify(|v| v.assert_reset());
+        }
+    }
+    #[inline]
+    unsafe fn deassert_reset_only(ccu: &RegisterBlock) {
+        unsafe {
+            ccu.mbus_clock.modify(|v| v.deassert_reset());
+        }
+    }
+}
+
+/// Direct Memory Access Controller (DMAC) clock type.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct DMA;
+
+impl ClockReset for DMA {
+    #[inline]
+    unsafe fn assert_reset_only(ccu: &RegisterBlock) {
+        unsafe {
+            ccu.dma_bgr.modify(|v| v.assert_reset());
+        }
+    }
+    #[inline]
+    unsafe fn deassert_reset_only(ccu: &RegisterBlock) {
+        unsafe {
+            ccu.dma_bgr.modify(|v| v.deassert_reset());
+        }
+    }
+}
+
+impl ClockGate for DMA {
+    #[inline]
+    unsafe fn unmask_gate_only(ccu: &RegisterBlock) {
+        unsafe {
+            ccu.dma_bgr.modify(|v| v.gate_pass());
+        }
+    }
+    #[inline]
+    unsafe fn mask_gate_only(ccu: &RegisterBlock) {
+        unsafe {
+            ccu.dma_bgr.modify(|v| v.gate_mask());
+        }
+    }
+    #[inline]
+    unsafe fn disable_in(ccu: &RegisterBlock) {
+        unsafe {
+            ccu.dma_bgr.modify(|v| v.gate_mask().assert_reset());
+        }
+    }
+    #[inline]
+    unsafe fn enable_in(ccu: &RegisterBlock) {
+        unsafe {
+            ccu.dma_bgr.modify(|v| v.gate_pass().deassert_reset());
+        }
+    }
+}
+
+/// Message Box (MSGBOX) clock type.
+///
+/// Message box 0 is the DSP one, and message box 1 is the RISC-V CPU one; it should be
+/// indexed by type parameter `IDX`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct MSGBOX<const IDX: usize>;
+
+impl<const I: usize> ClockReset for MSGBOX<I> {
+    #[inline]
+    unsafe fn assert_reset_only(ccu: &RegisterBlock) {
+        unsafe {
+            ccu.msgbox_bgr.modify(|v| v.assert_reset::<I>());
+        }
+    }
+    #[inline]
+    unsafe fn deassert_reset_only(ccu: &RegisterBlock) {
+        unsafe {
+            ccu.msgbox_bgr.modify(|v| v.deassert_reset::<I>());
+        }
+    }
+}
+
+impl<const I: usize> ClockGate for MSGBOX<I> {
+    #[inline]
+    unsafe fn unmask_gate_only(ccu: &RegisterBlock) {
+        unsafe {
+            ccu.msgbox_bgr.modify(|v| v.gate_pass::<I>());
+        }
+    }
+    #[inline]
+    unsafe fn mask_gate_only(ccu: &RegisterBlock) {
+        unsafe {
+            ccu.msgbox_bgr.modify(|v| v.gate_mask::<I>());
+        }
+    }
+    #[inline]
+    unsafe fn disable_in(ccu: &RegisterBlock) {
+        unsafe {
+            ccu.msgbox_bgr
+                .modify(|v| v.gate_mask::<I>().assert_reset::<I>());
+        }
+    }
+    #[inline]
+    unsafe fn enable_in(ccu: &RegisterBlock) {
+        unsafe {
+            ccu.msgbox_bgr
+                .modify(|v| v.gate_pass::<I>().deassert_reset::<I>());
+        }
+    }
+}
+
+/// Hardware spinlock (SPINLOCK) clock type.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct SPINLOCK;
+
+impl ClockReset for SPINLOCK {
+    #[inline]
+    unsafe fn assert_reset_only(ccu: &RegisterBlock) {
+        unsafe {
+            ccu.spinlock_bgr.modify(|v| v.assert_reset());
+        }
+    }
+    #[inline]
+    unsafe fn deassert_reset_only(ccu: &RegisterBlock) {
+        unsafe {
+            ccu.spinlock_bgr.modify(|v| v.deassert_reset());
+        }
+    }
+}
+
+impl ClockGate for SPINLOCK {
+    #[inline]
+    unsafe fn unmask_gate_only(ccu: &RegisterBlock) {
+        unsafe {
+            ccu.spinlock_bgr.modify(|v| v.gate_pass());
+        }
+    }
+    #[inline]
+    unsafe fn mask_gate_only(ccu: &RegisterBlock) {
+        unsafe {
+            ccu.spinlock_bgr.modify(|v| v.gate_mask());
+        }
+    }
+    #[inline]
+    unsafe fn disable_in(ccu: &RegisterBlock) {
+        unsafe {
+            ccu.spinlock_bgr.modify(|v| v.gate_mask().assert_reset());
+        }
+    }
+    #[inline]
+    unsafe fn enable_in(ccu: &RegisterBlock) {
+        unsafe {
+            ccu.spinlock_bgr.modify(|v| v.gate_pass().deassert_reset());
+        }
+    }
+}
+
+/// High Speed Timer (HSTIMER) clock type.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct HSTIMER;
+
+impl ClockReset for HSTIMER {
+    #[inline]
+    unsafe fn assert_reset_only(ccu: &RegisterBlock) {
+        unsafe {
+            ccu.hstimer_bgr.modify(|v| v.assert_reset());
+        }
+    }
+    #[inline]
+    unsafe fn deassert_reset_only(ccu: &RegisterBlock) {
+        unsafe {
+            ccu.hstimer_bgr.modify(|v| v.deassert_reset());
+        }
+    }
+}
+
+impl ClockGate for HSTIMER {
+    #[inline]
+    unsafe fn unmask_gate_only(ccu: &RegisterBlock) {
+        unsafe {
+            ccu.hstimer_bgr.modify(|v| v.gate_pass());
+        }
+    }
+    #[inline]
+    unsafe fn mask_gate_only(ccu: &RegisterBlock) {
+        unsafe {
+            ccu.hstimer_bgr.modify(|v| v.gate_mask());
+        }
+    }
+    #[inline]
+    unsafe fn disable_in(ccu: &RegisterBlock) {
+        unsafe {
+            ccu.hstimer_bgr.modify(|v| v.gate_mask().assert_reset());
+        }
+    }
+    #[inline]
+    unsafe fn enable_in(ccu: &RegisterBlock) {
+        unsafe {
+            ccu.hstimer_bgr.modify(|v| v.gate_pass().deassert_reset());
+        }
+    }
+}
+
+/// Pulse Width Modulation (PWM) clock type.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct PWM;
+
+impl ClockReset for PWM {
+    #[inline]
+    unsafe fn assert_reset_only(ccu: &RegisterBlock) {
+        unsafe {
+            ccu.pwm_bgr.modify(|v| v.assert_reset());
+        }
+    }
+    #[inline]
+    unsafe fn deassert_reset_only(ccu: &RegisterBlock) {
+        unsafe {
+            ccu.pwm_bgr.modify(|v| v.deassert_reset());
+        }
+    }
+}
+
+impl ClockGate for PWM {
+    #[inline]
+    unsafe fn unmask_gate_only(ccu: &RegisterBlock) {
+        unsafe {
+            ccu.pwm_bgr.modify(|v| v.gate_pass());
+        }
+    }
+    #[inline]
+    unsafe fn mask_gate_only(ccu: &RegisterBlock) {
+        unsafe {
+            ccu.pwm_bgr.modify(|v| v.gate_mask());
+        }
+    }
+    #[inline]
+    unsafe fn disable_in(ccu: &RegisterBlock) {
+        unsafe {
+            ccu.pwm_bgr.modify(|v| v.gate_mask().assert_reset());
+        }
+    }
+    #[inline]
+    unsafe fn enable_in(ccu: &RegisterBlock) {
+        unsafe {
+            ccu.pwm_bgr.modify(|v| v.gate_pass().deassert_reset());
+        }
+    }
+}
+
+/// General Purpose Analog-to-Digital Converter (GPADC) clock type.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct GPADC;
+
+impl ClockReset for GPADC {
+    #[inline]
+    unsafe fn assert_reset_only(ccu: &RegisterBlock) {
+        unsafe {
+            ccu.gpadc_bgr.modify(|v| v.assert_reset());
+        }
+    }
+    #[inline]
+    unsafe fn deassert_reset_only(ccu: &RegisterBlock) {
+        unsafe {
+            ccu.gpadc_bgr.modify(|v| v.deassert_reset());
+        }
+    }
+}
+
+impl ClockGate for GPADC {
+    #[inline]
+    unsafe fn unmask_gate_only(ccu: &RegisterBlock) {
+        unsafe {
+            ccu.gpadc_bgr.modify(|v| v.gate_pass());
+        }
+    }
+    #[inline]
+    unsafe fn mask_gate_only(ccu: &RegisterBlock) {
+        unsafe {
+            ccu.gpadc_bgr.modify(|v| v.gate_mask());
+        }
+    }
+    #[inline]
+    unsafe fn disable_in(ccu: &RegisterBlock) {
+        unsafe {
+            ccu.gpadc_bgr.modify(|v| v.gate_mask().assert_reset());
+        }
+    }
+    #[inline]
+    unsafe fn enable_in(ccu: &RegisterBlock) {
+        unsafe {
+            ccu.gpadc_bgr.modify(|v| v.gate_pass().deassert_reset());
+        }
+    }
+}
+
+/// Thermal Sensor (THS) clock type.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct THS;
+
+impl ClockReset for THS {
+    #[inline]
+    unsafe fn assert_reset_only(ccu: &RegisterBlock) {
+        unsafe {
+            ccu.ths_bgr.modify(|v| v.assert_reset());
+        }
+    }
+    #[inline]
+    unsafe fn deassert_reset_only(ccu: &RegisterBlock) {
+        unsafe {
+            ccu.ths_bgr.modify(|v| v.deassert_reset());
+        }
+    }
+}
+
+impl ClockGate for THS {
+    #[inline]
+    unsafe fn unmask_gate_only(ccu: &RegisterBlock) {
+        unsafe {
+            ccu.ths_bgr.modify(|v| v.gate_pass());
+        }
+    }
+    #[inline]
+    unsafe fn mask_gate_only(ccu: &RegisterBlock) {
+        unsafe {
+            ccu.ths_bgr.modify(|v| v.gate_mask());
+        }
+    }
+    #[inline]
+    unsafe fn disable_in(ccu: &RegisterBlock) {
+        unsafe {
+            ccu.ths_bgr.modify(|v| v.gate_mask().assert_reset());
+        }
+    }
+    #[inline]
+    unsafe fn enable_in(ccu: &RegisterBlock) {
+        unsafe {
+            ccu.ths_bgr.modify(|v| v.gate_pass().deassert_reset());
+        }
+    }
+}
+
+/// One Wire Audio (OWA) clock type.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct OWA;
+
+impl ClockReset for OWA {
+    #[inline]
+    unsafe fn assert_reset_only(ccu: &RegisterBlock) {
+        unsafe {
+            ccu.owa_bgr.modify(|v| v.assert_reset());
+        }
+    }
+    #[inline]
+    unsafe fn deassert_reset_only(ccu: &RegisterBlock) {
+        unsafe {
+            ccu.owa_bgr.modify(|v| v.deassert_reset());
+        }
+    }
+}
+
+impl ClockGate for OWA {
+    #[inline]
+    unsafe fn unmask_gate_only(ccu: &RegisterBlock) {
+        unsafe {
+            ccu.owa_bgr.modify(|v| v.gate_pass());
+        }
+    }
+    #[inline]
+    unsafe fn mask_gate_only(ccu: &RegisterBlock) {
+        unsafe {
+            ccu.owa_bgr.modify(|v| v.gate_mask());
+        }
+    }
+    #[inline]
+    unsafe fn disable_in(ccu: &RegisterBlock) {
+        unsafe {
+            ccu.owa_bgr.modify(|v| v.gate_mask().assert_reset());
+        }
+    }
+    #[inline]
+    unsafe fn enable_in(ccu: &RegisterBlock) {
+        unsafe {
+            ccu.owa_bgr.modify(|v| v.gate_pass().deassert_reset());
+        }
+    }
+}
+
+/// Digital Microphone (DMIC) clock type.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct DMIC;
+
+impl ClockReset for DMIC {
+    #[inline]
+    unsafe fn assert_reset_only(ccu: &RegisterBlock) {
+        unsafe {
+            ccu.dmic_bgr.modify(|v| v.assert_reset());
+        }
+    }
+    #[inline]
+    unsafe fn deassert_reset_only(ccu: &RegisterBlock) {
+        unsafe {
+            ccu.dmic_bgr.modify(|v| v.deassert_reset());
+        }
+    }
+}
+
+impl ClockGate for DMIC {
+    #[inline]
+    unsafe fn unmask_gate_only(ccu: &RegisterBlock) {
+        unsafe {
+            ccu.dmic_bgr.modify(|v| v.gate_pass());
+        }
+    }
+    #[inline]
+    unsafe fn mask_gate_only(ccu: &RegisterBlock) {
+        unsafe {
+            ccu.dmic_bgr.modify(|v| v.gate_mask());
+        }
+    }
+    #[inline]
+    unsafe fn disable_in(ccu: &RegisterBlock) {
+        unsafe {
+            ccu.dmic_bgr.modify(|v| v.gate_mask().assert_reset());
+        }
+    }
+    #[inline]
+    unsafe fn enable_in(ccu: &RegisterBlock) {
+        unsafe {
+            ccu.dmic_bgr.modify(|v| v.gate_pass().deassert_reset());
+        }
+    }
+}
+
+/// Audio Codec clock type.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct CODEC;
+
+impl ClockReset for CODEC {
+    #[inline]
+    unsafe fn assert_reset_only(ccu: &RegisterBlock) {
+        unsafe {
+            ccu.audio_codec_bgr.modify(|v| v.assert_reset());
+        }
+    }
+    #[inline]
+    unsafe fn deassert_reset_only(ccu: &RegisterBlock) {
+        unsafe {
+            ccu.audio_codec_bgr.modify(|v| v.deassert_reset());
+        }
+    }
+}
+
+impl ClockGate for CODEC {
+    #[inline]
+    unsafe fn unmask_gate_only(ccu: &RegisterBlock) {
+        unsafe {
+            ccu.audio_codec_bgr.modify(|v| v.gate_pass());
+        }
+    }
+    #[inline]
+    unsafe fn mask_gate_only(ccu: &RegisterBlock) {
+        unsafe {
+            ccu.audio_codec_bgr.modify(|v| v.gate_mask());
+        }
+    }
+    #[inline]
+    unsafe fn disable_in(ccu: &RegisterBlock) {
+        unsafe {
+            ccu.audio_codec_bgr.modify(|v| v.gate_mask().assert_reset());
+        }
+    }
+    #[inline]
+    unsafe fn enable_in(ccu: &RegisterBlock) {
+        unsafe {
+            ccu.audio_codec_bgr
+                .modify(|v| v.gate_pass().deassert_reset());
+        }
+    }
+}
+
+/// USB On-The-Go controller (USB OTG) clock type.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct USBOTG;
+
+impl ClockReset for USBOTG {
+    #[inline]
+    unsafe fn assert_reset_only(ccu: &RegisterBlock) {
+        unsafe {
+            ccu.usb_bgr.modify(|v| v.assert_otg_reset());
+        }
+    }
+    #[inline]
+    unsafe fn deassert_reset_only(ccu: &RegisterBlock) {
+        unsafe {
+            ccu.usb_bgr.modify(|v| v.deassert_otg_reset());
+        }
+    }
+}
+
+impl ClockGate for USBOTG {
+    #[inline]
+    unsafe fn unmask_gate_only(ccu: &RegisterBlock) {
+        unsafe {
+            ccu.usb_bgr.modify(|v| v.gate_pass_otg());
+        }
+    }
+    #[inline]
+    unsafe fn mask_gate_only(ccu: &RegisterBlock) {
+        unsafe {
+            ccu.usb_bgr.modify(|v| v.gate_mask_otg());
+        }
+    }
+    #[inline]
+    unsafe fn disable_in(ccu: &RegisterBlock) {
+        unsafe {
+            ccu.usb_bgr.modify(|v| v.gate_mask_otg().assert_otg_reset());
+        }
+    }
+    #[inline]
+    unsafe fn enable_in(ccu: &RegisterBlock) {
+        unsafe {
+            ccu.usb_bgr
+                .modify(|v| v.gate_pass_otg().deassert_otg_reset());
+        }
+    }
+}
+
+/// USB Enhanced Host Controller Interface (EHCI) clock type.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct USBEHCI<const IDX: usize>;
+
+impl<const I: usize> ClockReset for USBEHCI<I> {
+    #[inline]
+    unsafe fn assert_reset_only(ccu: &RegisterBlock) {
+        unsafe {
+            ccu.usb_bgr.modify(|v| v.assert_ehci_reset::<I>());
+        }
+    }
+    #[inline]
+    unsafe fn deassert_reset_only(ccu: &RegisterBlock) {
+        unsafe {
+            ccu.usb_bgr.modify(|v| v.deassert_ehci_reset::<I>());
+        }
+    }
+}
+
+impl<const I: usize> ClockGate for USBEHCI<I> {
+    #[inline]
+    unsafe fn unmask_gate_only(ccu: &RegisterBlock) {
+        unsafe {
+            ccu.usb_bgr.modify(|v| v.gate_pass_ehci::<I>());
+        }
+    }
+    #[inline]
+    unsafe fn mask_gate_only(ccu: &RegisterBlock) {
+        unsafe {
+            ccu.usb_bgr.modify(|v| v.gate_mask_ehci::<I>());
+        }
+    }
+    #[inline]
+    unsafe fn disable_in(ccu: &RegisterBlock) {
+        unsafe {
+            ccu.usb_bgr
+                .modify(|v| v.gate_mask_ehci::<I>().assert_ehci_reset::<I>());
+        }
+    }
+    #[inline]
+    unsafe fn enable_in(ccu: &RegisterBlock) {
+        unsafe {
+            ccu.usb_bgr
+                .modify(|v| v.gate_pass_ehci::<I>().deassert_ehci_reset::<I>());
+        }
+    }
+}
+
+/// USB Open Host Controller Interface (OHCI) clock type.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct USBOHCI<const IDX: usize>;
+
+impl<const I: usize> ClockReset for USBOHCI<I> {
+    #[inline]
+    unsafe fn assert_reset_only(ccu: &RegisterBlock) {
+        unsafe {
+            ccu.usb_bgr.modify(|v| v.assert_ohci_reset::<I>());
+        }
+    }
+    #[inline]
+    unsafe fn deassert_reset_only(ccu: &RegisterBlock) {
+        unsafe {
+            ccu.usb_bgr.modify(|v| v.deassert_ohci_reset::<I>());
+        }
+    }
+}
+
+impl<const I: usize> ClockGate for USBOHCI<I> {
+    #[inline]
+    unsafe fn unmask_gate_only(ccu: &RegisterBlock) {
+        unsafe {
+            ccu.usb_bgr.modify(|v| v.gate_pass_ohci::<I>());
+        }
+    }
+    #[inline]
+    unsafe fn mask_gate_only(ccu: &RegisterBlock) {
+        unsafe {
+            ccu.usb_bgr.modify(|v| v.gate_mask_ohci::<I>());
+        }
+    }
+    #[inline]
+    unsafe fn disable_in(ccu: &RegisterBlock) {
+        unsafe {
+            ccu.usb_bgr
+                .modify(|v| v.gate_mask_ohci::<I>().assert_ohci_reset::<I>());
+        }
+    }
+    #[inline]
+    unsafe fn enable_in(ccu: &RegisterBlock) {
+        unsafe {
+            ccu.usb_bgr
+                .modify(|v| v.gate_pass_ohci::<I>().deassert_ohci_reset::<I>());
+        }
+    }
+}
+
+/// Low Rate Analog-to-Digital Converter (LRADC) clock type.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct LRADC;
+
+impl ClockReset for LRADC {
+    #[inline]
+    unsafe fn assert_reset_only(ccu: &RegisterBlock) {
+        unsafe {
+            ccu.lradc_bgr.modify(|v| v.assert_reset());
+        }
+    }
+    #[inline]
+    unsafe fn deassert_reset_only(ccu: &RegisterBlock) {
+        unsafe {
+            ccu.lradc_bgr.modify(|v| v.deassert_reset());
+        }
+    }
+}
+
+impl ClockGate for LRADC {
+    #[inline]
+    unsafe fn unmask_gate_only(ccu: &RegisterBlock) {
+        unsafe {
+            ccu.lradc_bgr.modify(|v| v.gate_pass());
+        }
+    }
+    #[inline]
+    unsafe fn mask_gate_only(ccu: &RegisterBlock) {
+        unsafe {
+            ccu.lradc_bgr.modify(|v| v.gate_mask());
+        }
+    }
+    #[inline]
+    unsafe fn disable_in(ccu: &RegisterBlock) {
+        unsafe {
+            ccu.lradc_bgr.modify(|v| v.gate_mask().assert_reset());
+        }
+    }
+    #[inline]
+    unsafe fn enable_in(ccu: &RegisterBlock) {
+        unsafe {
+            ccu.lradc_bgr.modify(|v| v.gate_pass().deassert_reset());
+        }
+    }
+}
+
+/// Universal Asynchronous Receiver-Transmitter clock type.
+///
+/// UART peripheral should be indexed by type parameter `IDX`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct UART<const IDX: usize>;
+
+impl<const I: usize> ClockReset for UART<I> {
+    #[inline]
+    unsafe fn assert_reset_only(ccu: &RegisterBlock) {
+        unsafe {
+            ccu.uart_bgr.modify(|v| v.assert_reset::<I>());
+        }
+    }
+    #[inline]
+    unsafe fn deassert_reset_only(ccu: &RegisterBlock) {
+        unsafe {
+            ccu.uart_bgr.modify(|v| v.deassert_reset::<I>());
+        }
+    }
+}
+
+impl<const I: usize> ClockGate for UART<I> {
+    #[inline]
+    unsafe fn unmask_gate_only(ccu: &RegisterBlock) {
+        unsafe {
+            ccu.uart_bgr.modify(|v| v.gate_pass::<I>());
+        }
+    }
+    #[inline]
+    unsafe fn mask_gate_only(ccu: &RegisterBlock) {
+        unsafe {
+            ccu.uart_bgr.modify(|v| v.gate_mask::<I>());
+        }
+    }
+    #[inline]
+    unsafe fn disable_in(ccu: &RegisterBlock) {
+        unsafe {
+            ccu.uart_bgr
+                .modify(|v| v.gate_mask::<I>().assert_reset::<I>());
+        }
+    }
+    #[inline]
+    unsafe fn enable_in(ccu: &RegisterBlock) {
+        unsafe {
+            ccu.uart_bgr
+                .modify(|v| v.gate_pass::<I>().deassert_reset::<I>());
+        }
+    }
+}
+
+/// Controller Area Network clock type.
+///
+/// CAN peripheral should be indexed by type parameter `IDX`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct CAN<const IDX: usize>;
+
+impl<const I: usize> ClockReset for CAN<I> {
+    #[inline]
+    unsafe fn assert_reset_only(ccu: &RegisterBlock) {
+        unsafe {
+            ccu.can_bgr.modify(|v| v.assert_reset::<I>());
+        }
+    }
+    #[inline]
+    unsafe fn deassert_reset_only(ccu: &RegisterBlock) {
+        unsafe {
+            ccu.can_bgr.modify(|v| v.deassert_reset::<I>());
+        }
+    }
+}
+
+impl<const I: usize> ClockGate for CAN<I> {
+    #[inline]
+    unsafe fn unmask_gate_only(ccu: &RegisterBlock) {
+        unsafe {
+            ccu.can_bgr.modify(|v| v.gate_pass::<I>());
+        }
+    }
+    #[inline]
+    unsafe fn mask_gate_only(ccu: &RegisterBlock) {
+        unsafe {
+            ccu.can_bgr.modify(|v| v.gate_mask::<I>());
+        }
+    }
+    #[inline]
+    unsafe fn disable_in(ccu: &RegisterBlock) {
+        unsafe {
+            ccu.can_bgr
+                .modify(|v| v.gate_mask::<I>().assert_reset::<I>());
+        }
+    }
+    #[inline]
+    unsafe fn enable_in(ccu: &RegisterBlock) {
+        unsafe {
+            ccu.can_bgr
+                .modify(|v| v.gate_pass::<I>().deassert_reset::<I>());
+        }
+    }
+}
+
+/// Serial Peripheral Interface clock type.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct SPI<const IDX: usize>;
+
+impl<const I: usize> ClockReset for SPI<I> {
+    #[inline]
+    unsafe fn assert_reset_only(ccu: &RegisterBlock) {
+        unsafe {
+            ccu.spi_bgr.modify(|v| v.assert_reset::<I>());
+        }
+    }
+    #[inline]
+    unsafe fn deassert_reset_only(ccu: &RegisterBlock) {
+        unsafe {
+            ccu.spi_bgr.modify(|v| v.deassert_reset::<I>());
+        }
+    }
+}
+
+impl<const I: usize> ClockGate for SPI<I> {
+    #[inline]
+    unsafe fn unmask_gate_only(ccu: &RegisterBlock) {
+        unsafe {
+            ccu.spi_bgr.modify(|v| v.gate_pass::<I>());
+        }
+    }
+    #[inline]
+    unsafe fn mask_gate_only(ccu: &RegisterBlock) {
+        unsafe {
+            ccu.spi_bgr.modify(|v| v.gate_mask::<I>());
+        }
+    }
+    #[inline]
+    unsafe fn disable_in(ccu: &RegisterBlock) {
+        unsafe {
+            ccu.spi_bgr
+                .modify(|v| v.gate_mask::<I>().assert_reset::<I>());
+        }
+    }
+    #[inline]
+    unsafe fn enable_in(ccu: &RegisterBlock) {
+        unsafe {
+            ccu.spi_bgr
+                .modify(|v| v.gate_pass::<I>().deassert_reset::<I>());
+        }
+    }
+}
+
+impl<const I: usize> ClockConfig for SPI<I> {
+    type Source = SpiClockSource;
+
+    unsafe fn configure(
+        ccu: &RegisterBlock,
+        source: Self::Source,
+        factor_m: u8,
+        factor_n: PeriFactorN,
+    ) {
+        unsafe {
+            let spi_clk = ccu.spi_clk[I].read();
+            ccu.spi_clk[I].write(
+                spi_clk
+                    .set_clock_source(source)
+                    .set_factor_m(factor_m)
+                    .set_factor_n(factor_n),
+            )
+        }
+    }
+}
+
+/// Ethernet Media Access Controller (EMAC) clock type.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct EMAC;
+
+impl ClockReset for EMAC {
+    #[inline]
+    unsafe fn assert_reset_only(ccu: &RegisterBlock) {
+        unsafe {
+            ccu.emac_bgr.modify(|v| v.assert_reset());
+        }
+    }
+    #[inline]
+    unsafe fn deassert_reset_only(ccu: &RegisterBlock) {
+        unsafe {
+            ccu.emac_bgr.modify(|v| v.deassert_reset());
+        }
+    }
+}
+
+impl ClockGate for EMAC {
+    #[inline]
+    unsafe fn unmask_gate_only(ccu: &RegisterBlock) {
+        unsafe {
+            ccu.emac_bgr.modify(|v| v.gate_pass());
+        }
+    }
+    #[inline]
+    unsafe fn mask_gate_only(ccu: &RegisterBlock) {
+        unsafe {
+            ccu.emac_bgr.modify(|v| v.gate_mask());
+        }
+    }
+    #[inline]
+    unsafe fn disable_in(ccu: &RegisterBlock) {
+        unsafe {
+            ccu.emac_bgr.modify(|v| v.gate_mask().assert_reset());
+        }
+    }
+    #[inline]
+    unsafe fn enable_in(ccu: &RegisterBlock) {
+        unsafe {
+            ccu.emac_bgr.modify(|v| v.gate_pass().deassert_reset());
+        }
+    }
+}
+
+/// LED Controller (LEDC) clock type.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct LEDC;
+
+impl ClockReset for LEDC {
+    #[inline]
+    unsafe fn assert_reset_only(ccu: &RegisterBlock) {
+        unsafe {
+            ccu.ledc_bgr.modify(|v| v.assert_reset());
+        }
+    }
+    #[inline]
+    unsafe fn deassert_reset_only(ccu: &RegisterBlock) {
+        unsafe {
+            ccu.ledc_bgr.modify(|v| v.deassert_reset());
+        }
+    }
+}
+
+impl ClockGate for LEDC {
+    #[inline]
+    unsafe fn unmask_gate_only(ccu: &RegisterBlock) {
+        unsafe {
+            ccu.ledc_bgr.modify(|v| v.gate_pass());
+        }
+    }
+    #[inline]
+    unsafe fn mask_gate_only(ccu: &RegisterBlock) {
+        unsafe {
+            ccu.ledc_bgr.modify(|v| v.gate_mask());
+        }
+    }
+    #[inline]
+    unsafe fn disable_in(ccu: &RegisterBlock) {
+        unsafe {
+            ccu.ledc_bgr.modify(|v| v.gate_mask().assert_reset());
+        }
+    }
+    #[inline]
+    unsafe fn enable_in(ccu: &RegisterBlock) {
+        unsafe {
+            ccu.ledc_bgr.modify(|v| v.gate_pass().deassert_reset());
+        }
+    }
+}
+
+impl ClockConfig for LEDC {
+    type Source = LedcClockSource;
+
+    unsafe fn configure(
+        ccu: &RegisterBlock,
+        source: Self::Source,
+        factor_m: u8,
+        factor_n: PeriFactorN,
+    ) {
+        unsafe {
+            let ledc_clk = ccu.ledc_clock.read();
+            ccu.ledc_clock.write(
+                ledc_clk
+                    .set_clock_source(source)
+                    .set_factor_m(factor_m)
+                    .set_factor_n(factor_n)
+                    .unmask_clock(),
+            )
+        }
+    }
+}
+
+/// Display Engine (DE) clock type.
+///
+/// Display engine clock has no factor N; `factor_n` of [`ClockConfig`] is ignored.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct DE;
+
+impl ClockReset for DE {
+    #[inline]
+    unsafe fn assert_reset_only(ccu: &RegisterBlock) {
+        unsafe {
+            ccu.de_bgr.modify(|v| v.assert_reset());
+        }
+    }
+    #[inline]
+    unsafe fn deassert_reset_only(ccu: &RegisterBlock) {
+        unsafe {
+            ccu.de_bgr.modify(|v| v.deassert_reset());
+        }
+    }
+}
+
+impl ClockGate for DE {
+    #[inline]
+    unsafe fn unmask_gate_only(ccu: &RegisterBlock) {
+        unsafe {
+            ccu.de_bgr.modify(|v| v.gate_pass());
+        }
+    }
+    #[inline]
+    unsafe fn mask_gate_only(ccu: &RegisterBlock) {
+        unsafe {
+            ccu.de_bgr.modify(|v| v.gate_mask());
+        }
+    }
+    #[inline]
+    unsafe fn disable_in(ccu: &RegisterBlock) {
+        unsafe {
+            ccu.de_bgr.modify(|v| v.gate_mask().assert_reset());
+        }
+    }
+    #[inline]
+    unsafe fn enable_in(ccu: &RegisterBlock) {
+        unsafe {
+            ccu.de_bgr.modify(|v| v.gate_pass().deassert_reset());
+        }
+    }
+}
+
+impl ClockConfig for DE {
+    type Source = DeClockSource;
+
+    unsafe fn configure(
+        ccu: &RegisterBlock,
+        source: Self::Source,
+        factor_m: u8,
+        _factor_n: PeriFactorN,
+    ) {
+        unsafe {
+            let de_clk = ccu.de_clock.read();
+            ccu.de_clock.write(
+                de_clk
+                    .set_clock_source(source)
+                    .set_factor_m(factor_m)
+                    .unmask_clock(),
+            )
+        }
+    }
+}
+
+/// Crypto Engine (CE) clock type.
+///
+/// Gating also passes DRAM access clock of crypto engine on memory bus.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct CE;
+
+impl ClockReset for CE {
+    #[inline]
+    unsafe fn assert_reset_only(ccu: &RegisterBlock) {
+        unsafe {
+            ccu.ce_bgr.modify(|v| v.assert_reset());
+        }
+    }
+    #[inline]
+    unsafe fn deassert_reset_only(ccu: &RegisterBlock) {
+        unsafe {
+            ccu.ce_bgr.modify(|v| v.deassert_reset());
+        }
+    }
+}
+
+impl ClockGate for CE {
+    #[inline]
+    unsafe fn unmask_gate_only(ccu: &RegisterBlock) {
+        unsafe {
+            ccu.ce_bgr.modify(|v| v.gate_pass());
+            ccu.mbus_gating.modify(|v| v.gate_pass(MbusMaster::Ce));
+        }
+    }
+    #[inline]
+    unsafe fn mask_gate_only(ccu: &RegisterBlock) {
+        unsafe {
+            ccu.mbus_gating.modify(|v| v.gate_mask(MbusMaster::Ce));
+            ccu.ce_bgr.modify(|v| v.gate_mask());
+        }
+    }
+    #[inline]
+    unsafe fn disable_in(ccu: &RegisterBlock) {
+        unsafe {
+            ccu.mbus_gating.modify(|v| v.gate_mask(MbusMaster::Ce));
+            ccu.ce_bgr.modify(|v| v.gate_mask().assert_reset());
+        }
+    }
+    #[inline]
+    unsafe fn enable_in(ccu: &RegisterBlock) {
+        unsafe {
+            ccu.ce_bgr.modify(|v| v.gate_pass().deassert_reset());
+            ccu.mbus_gating.modify(|v| v.gate_pass(MbusMaster::Ce));
+        }
+    }
+}
+
+impl ClockConfig for CE {
+    type Source = CeClockSource;
+
+    unsafe fn configure(
+        ccu: &RegisterBlock,
+        source: Self::Source,
+        factor_m: u8,
+        factor_n: PeriFactorN,
+    ) {
+        unsafe {
+            let ce_clk = ccu.ce_clock.read();
+            ccu.ce_clock.write(
+                ce_clk
+                    .set_clock_source(source)
+                    .set_factor_m(factor_m)
+                    .set_factor_n(factor_n)
+                    .unmask_clock(),
+            )
+        }
+    }
+}
+
+/// 2D Graphics Accelerator (G2D) clock type.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct G2D;
+
+impl ClockReset for G2D {
+    #[inline]
+    unsafe fn assert_reset_only(ccu: &RegisterBlock) {
+        unsafe {
+            ccu.g2d_bgr.modify(|v| v.assert_reset());
+        }
+    }
+    #[inline]
+    unsafe fn deassert_reset_only(ccu: &RegisterBlock) {
+        unsafe {
+            ccu.g2d_bgr.modify(|v| v.deassert_reset());
+        }
+    }
+}
+
+impl ClockGate for G2D {
+    #[inline]
+    unsafe fn unmask_gate_only(ccu: &RegisterBlock) {
+        unsafe {
+            ccu.g2d_bgr.modify(|v| v.gate_pass());
+        }
+    }
+    #[inline]
+    unsafe fn mask_gate_only(ccu: &RegisterBlock) {
+        unsafe {
+            ccu.g2d_bgr.modify(|v| v.gate_mask());
+        }
+    }
+    #[inline]
+    unsafe fn disable_in(ccu: &RegisterBlock) {
+        unsafe {
+            ccu.g2d_bgr.modify(|v| v.gate_mask().assert_reset());
+        }
+    }
+    #[inline]
+    unsafe fn enable_in(ccu: &RegisterBlock) {
+        unsafe {
+            ccu.g2d_bgr.modify(|v| v.gate_pass().deassert_reset());
+        }
+    }
+}
+
+impl ClockConfig for G2D {
+    type Source = G2dClockSource;
+
+    unsafe fn configure(
+        ccu: &RegisterBlock,
+        source: Self::Source,
+        factor_m: u8,
+        _factor_n: PeriFactorN,
+    ) {
+        unsafe {
+            let g2d_clk = ccu.g2d_clock.read();
+            ccu.g2d_clock.write(
+                g2d_clk
+                    .set_clock_source(source)
+                    .set_factor_m(factor_m)
+                    .unmask_clock(),
+            )
+        }
+    }
+}
+
+/// Display Subsystem Top (DPSS TOP) clock type.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct DPSSTOP;
+
+impl ClockReset for DPSSTOP {
+    #[inline]
+    unsafe fn assert_reset_only(ccu: &RegisterBlock) {
+        unsafe {
+            ccu.dpss_top_bgr.modify(|v| v.assert_reset());
+        }
+    }
+    #[inline]
+    unsafe fn deassert_reset_only(ccu: &RegisterBlock) {
+        unsafe {
+            ccu.dpss_top_bgr.modify(|v| v.deassert_reset());
+        }
+    }
+}
+
+impl ClockGate for DPSSTOP {
+    #[inline]
+    unsafe fn unmask_gate_only(ccu: &RegisterBlock) {
+        unsafe {
+            ccu.dpss_top_bgr.modify(|v| v.gate_pass());
+        }
+    }
+    #[inline]
+    unsafe fn mask_gate_only(ccu: &RegisterBlock) {
+        unsafe {
+            ccu.dpss_top_bgr.modify(|v| v.gate_mask());
+        }
+    }
+    #[inline]
+    unsafe fn disable_in(ccu: &RegisterBlock) {
+        unsafe {
+            ccu.dpss_top_bgr.modify(|v| v.gate_mask().assert_reset());
+        }
+    }
+    #[inline]
+    unsafe fn enable_in(ccu: &RegisterBlock) {
+        unsafe {
+            ccu.dpss_top_bgr.modify(|v| v.gate_pass().deassert_reset());
+        }
+    }
+}
+
+/// HDMI transmitter clock type.
+///
+/// HDMI 24-MHz clock is not covered by this type, and is handled by HDMI driver.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct HDMI;
+
+impl ClockReset for HDMI {
+    #[inline]
+    unsafe fn assert_reset_only(ccu: &RegisterBlock) {
+        unsafe {
+            ccu.hdmi_bgr.modify(|v| v.assert_reset());
+        }
+    }
+    #[inline]
+    unsafe fn deassert_reset_only(ccu: &RegisterBlock) {
+        unsafe {
+            ccu.hdmi_bgr.modify(|v| v.deassert_reset());
+        }
+    }
+}
+
+impl ClockGate for HDMI {
+    #[inline]
+    unsafe fn unmask_gate_only(ccu: &RegisterBlock) {
+        unsafe {
+            ccu.hdmi_bgr.modify(|v| v.gate_pass());
+        }
+    }
+    #[inline]
+    unsafe fn mask_gate_only(ccu: &RegisterBlock) {
+        unsafe {
+            ccu.hdmi_bgr.modify(|v| v.gate_mask());
+        }
+    }
+    #[inline]
+    unsafe fn disable_in(ccu: &RegisterBlock) {
+        unsafe {
+            ccu.hdmi_bgr.modify(|v| v.gate_mask().assert_reset());
+        }
+    }
+    #[inline]
+    unsafe fn enable_in(ccu: &RegisterBlock) {
+        unsafe {
+            ccu.hdmi_bgr.modify(|v| v.gate_pass().deassert_reset());
+        }
+    }
+}
+
+/// Timing Controller (TCON) LCD clock type.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct TCONLCD;
+
+impl ClockReset for TCONLCD {
+    #[inline]
+    unsafe fn assert_reset_only(ccu: &RegisterBlock) {
+        unsafe {
+            ccu.tcon_lcd_bgr.modify(|v| v.assert_reset());
+        }
+    }
+    #[inline]
+    unsafe fn deassert_reset_only(ccu: &RegisterBlock) {
+        unsafe {
+            ccu.tcon_lcd_bgr.modify(|v| v.deassert_reset());
+        }
+    }
+}
+
+impl ClockGate for TCONLCD {
+    #[inline]
+    unsafe fn unmask_gate_only(ccu: &RegisterBlock) {
+        unsafe {
+            ccu.tcon_lcd_bgr.modify(|v| v.gate_pass());
+        }
+    }
+    #[inline]
+    unsafe fn mask_gate_only(ccu: &RegisterBlock) {
+        unsafe {
+            ccu.tcon_lcd_bgr.modify(|v| v.gate_mask());
+        }
+    }
+    #[inline]
+    unsafe fn disable_in(ccu: &RegisterBlock) {
+        unsafe {
+            ccu.tcon_lcd_bgr.modify(|v| v.gate_mask().assert_reset());
+        }
+    }
+    #[inline]
+    unsafe fn enable_in(ccu: &RegisterBlock) {
+        unsafe {
+            ccu.tcon_lcd_bgr.modify(|v| v.gate_pass().deassert_reset());
+        }
+    }
+}
+
+impl ClockConfig for TCONLCD {
+    type Source = TconLcdClockSource;
+
+    unsafe fn configure(
+        ccu: &RegisterBlock,
+        source: Self::Source,
+        factor_m: u8,
+        factor_n: PeriFactorN,
+    ) {
+        unsafe {
+            let tcon_lcd_clk = ccu.tcon_lcd_clock.read();
+            ccu.tcon_lcd_clock.write(
+                tcon_lcd_clk
+                    .set_clock_source(source)
+                    .set_factor_m(factor_m)
+                    .set_factor_n(factor_n)
+                    .unmask_clock(),
+            )
+        }
+    }
+}
+
+/// SD/MMC Host Controller clock type.
+///
+/// SMHC peripheral should be indexed by type parameter `IDX`. SMHC0 and SMHC1 have no
+/// PLL_PERI(800M) source; configuring them with it keeps their clock register unchanged.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct SMHC<const IDX: usize>;
+
+impl<const I: usize> ClockReset for SMHC<I> {
+    #[inline]
+    unsafe fn assert_reset_only(ccu: &RegisterBlock) {
+        unsafe {
+            ccu.smhc_bgr.modify(|v| v.assert_reset::<I>());
+        }
+    }
+    #[inline]
+    unsafe fn deassert_reset_only(ccu: &RegisterBlock) {
+        unsafe {
+            ccu.smhc_bgr.modify(|v| v.deassert_reset::<I>());
+        }
+    }
+}
+
+impl<const I: usize> ClockGate for SMHC<I> {
+    #[inline]
+    unsafe fn unmask_gate_only(ccu: &RegisterBlock) {
+        unsafe {
+            ccu.smhc_bgr.modify(|v| v.gate_pass::<I>());
+        }
+    }
+    #[inline]
+    unsafe fn mask_gate_only(ccu: &RegisterBlock) {
+        unsafe {
+            ccu.smhc_bgr.modify(|v| v.gate_mask::<I>());
+        }
+    }
+    #[inline]
+    unsafe fn disable_in(ccu: &RegisterBlock) {
+        unsafe {
+            ccu.smhc_bgr
+                .modify(|v| v.gate_mask::<I>().assert_reset::<I>());
+        }
+    }
+    #[inline]
+    unsafe fn enable_in(ccu: &RegisterBlock) {
+        unsafe {
+            ccu.smhc_bgr
+                .modify(|v| v.gate_pass::<I>().deassert_reset::<I>());
+        }
+    }
+}
+
+impl<const I: usize> ClockConfig for SMHC<I> {
+    type Source = SmhcClockSource;
+
+    unsafe fn configure(
+        ccu: &RegisterBlock,
+        source: Self::Source,
+        factor_m: u8,
+        factor_n: PeriFactorN,
+    ) {
+        let Some(smhc_clk) = ccu.smhc_clk[I]
+            .read()
+            .set_instance_clock_source::<I>(source)
+        else {
+            return;
+        };
+        unsafe {
+            ccu.smhc_clk[I].write(
+                smhc_clk
+                    .set_factor_m(factor_m)
+                    .set_factor_n(factor_n)
+                    .enable_clock_gating(),
+            )
+        }
+    }
+}
+
+/// HiFi4 Digital Signal Processor (DSP) clock type.
+///
+/// Resets and gating of this type cover DSP configuration and debug interfaces; DSP core
+/// reset is controlled by [`crate::dsp::Dsp`]. DSP clock has no factor N; `factor_n` of
+/// [`ClockConfig`] is ignored.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct DSP;
+
+impl ClockReset for DSP {
+    #[inline]
+    unsafe fn assert_reset_only(ccu: &RegisterBlock) {
+        unsafe {
+            ccu.dsp_bgr.modify(|v| v.assert_reset());
+        }
+    }
+    #[inline]
+    unsafe fn deassert_reset_only(ccu: &RegisterBlock) {
+        unsafe {
+            ccu.dsp_bgr.modify(|v| v.deassert_reset());
+        }
+    }
+}
+
+impl ClockGate for DSP {
+    #[inline]
+    unsafe fn unmask_gate_only(ccu: &RegisterBlock) {
+        unsafe {
+            ccu.dsp_bgr.modify(|v| v.gate_pass());
+        }
+    }
+    #[inline]
+    unsafe fn mask_gate_only(ccu: &RegisterBlock) {
+        unsafe {
+            ccu.dsp_bgr.modify(|v| v.gate_mask());
+        }
+    }
+    #[inline]
+    unsafe fn disable_in(ccu: &RegisterBlock) {
+        unsafe {
+            ccu.dsp_bgr.modify(|v| v.gate_mask().assert_reset());
+        }
+    }
+    #[inline]
+    unsafe fn enable_in(ccu: &RegisterBlock) {
+        unsafe {
+            ccu.dsp_bgr.modify(|v| v.gate_pass().deassert_reset());
+        }
+    }
+}
+
+impl ClockConfig for DSP {
+    type Source = DspClockSource;
+
+    unsafe fn configure(
+        ccu: &RegisterBlock,
+        source: Self::Source,
+        factor_m: u8,
+        _factor_n: PeriFactorN,
+    ) {
+        unsafe {
+            let dsp_clk = ccu.dsp_clock.read();
+            ccu.dsp_clock.write(
+                dsp_clk
+                    .set_clock_source(source)
+                    .set_factor_m(factor_m)
+                    .unmask_clock(),
+            )
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        AudioCodecClockSource, AxiFactorN, CpuAxiConfig, CpuClockSource, DramBusGating, DramClock,
+        DramClockSource, FactorP, MbusClock, PeriFactorN, RegisterBlock, SmhcClock,
+        SmhcClockSource,
+    };
+    use core::mem::offset_of;
+    #[test]
+    fn offset_ccu() {
+        assert_eq!(offset_of!(RegisterBlock, pll_cpu_control), 0x0);
+        assert_eq!(offset_of!(RegisterBlock, pll_ddr_control), 0x10);
+        assert_eq!(offset_of!(RegisterBlock, pll_peri0_control), 0x20);
+        assert_eq!(offset_of!(RegisterBlock, pll_video0_control), 0x40);
+        assert_eq!(offset_of!(RegisterBlock, pll_audio0_control), 0x78);
+        assert_eq!(offset_of!(RegisterBlock, pll_audio1_control), 0x80);
+        assert_eq!(offset_of!(RegisterBlock, pll_audio0_pattern), 0x178);
+        assert_eq!(offset_of!(RegisterBlock, cpu_axi_config), 0x500);
+        assert_eq!(offset_of!(RegisterBlock, mbus_clock), 0x540);
+        assert_eq!(offset_of!(RegisterBlock, de_clock), 0x600);
+        assert_eq!(offset_of!(RegisterBlock, de_bgr), 0x60c);
+        assert_eq!(offset_of!(RegisterBlock, g2d_clock), 0x630);
+        assert_eq!(offset_of!(RegisterBlock, g2d_bgr), 0x63c);
+        assert_eq!(offset_of!(RegisterBlock, ce_clock), 0x680);
+        assert_eq!(offset_of!(RegisterBlock, ce_bgr), 0x68c);
+        assert_eq!(offset_of!(RegisterBlock, dma_bgr), 0x70c);
+        assert_eq!(offset_of!(RegisterBlock, msgbox_bgr), 0x71c);
+        assert_eq!(offset_of!(RegisterBlock, spinlock_bgr), 0x72c);
+        assert_eq!(offset_of!(RegisterBlock, hstimer_bgr), 0x73c);
+        assert_eq!(offset_of!(RegisterBlock, pwm_bgr), 0x7ac);
+        assert_eq!(offset_of!(RegisterBlock, dram_clock), 0x800);
+        assert_eq!(offset_of!(RegisterBlock, mbus_gating), 0x804);
+        assert_eq!(offset_of!(RegisterBlock, dram_bgr), 0x80c);
+        assert_eq!(offset_of!(RegisterBlock, smhc_clk), 0x830);
+        assert_eq!(offset_of!(RegisterBlock, smhc_bgr), 0x84c);
+        assert_eq!(offset_of!(RegisterBlock, uart_bgr), 0x90c);
+        assert_eq!(offset_of!(RegisterBlock, can_bgr), 0x92c);
+        assert_eq!(offset_of!(RegisterBlock, spi_clk), 0x940);
+        assert_eq!(offset_of!(RegisterBlock, spi_bgr), 0x96c);
+        assert_eq!(offset_of!(RegisterBlock, emac_25m_clock), 0x970);
+        assert_eq!(offset_of!(RegisterBlock, emac_bgr), 0x97c);
+        assert_eq!(offset_of!(RegisterBlock, gpadc_bgr), 0x9ec);
+        assert_eq!(offset_of!(RegisterBlock, ths_bgr), 0x9fc);
+        assert_eq!(offset_of!(RegisterBlock, owa_tx_clock), 0xa24);
+        assert_eq!(offset_of!(RegisterBlock, owa_bgr), 0xa2c);
+        assert_eq!(offset_of!(RegisterBlock, dmic_clock), 0xa40);
+        assert_eq!(offset_of!(RegisterBlock, dmic_bgr), 0xa4c);
+        assert_eq!(offset_of!(RegisterBlock, audio_codec_dac_clock), 0xa50);
+        assert_eq!(offset_of!(RegisterBlock, audio_codec_adc_clock), 0xa54);
+        assert_eq!(offset_of!(RegisterBlock, audio_codec_bgr), 0xa5c);
+        assert_eq!(offset_of!(RegisterBlock, usb_clock), 0xa70);
+        assert_eq!(offset_of!(RegisterBlock, usb_bgr), 0xa8c);
+        assert_eq!(offset_of!(RegisterBlock, lradc_bgr), 0xa9c);
+        assert_eq!(offset_of!(RegisterBlock, dpss_top_bgr), 0xabc);
+        assert_eq!(offset_of!(RegisterBlock, hdmi_24m_clock), 0xb04);
+        assert_eq!(offset_of!(RegisterBlock, hdmi_bgr), 0xb1c);
+        assert_eq!(offset_of!(RegisterBlock, tcon_lcd_clock), 0xb60);
+        assert_eq!(offset_of!(RegisterBlock, tcon_lcd_bgr), 0xb7c);
+        assert_eq!(offset_of!(RegisterBlock, ledc_clock), 0xbf0);
+        assert_eq!(offset_of!(RegisterBlock, ledc_bgr), 0xbfc);
+        assert_eq!(offset_of!(RegisterBlock, dsp_clock), 0xc70);
+        assert_eq!(offset_of!(RegisterBlock, dsp_bgr), 0xc7c);
+        assert_eq!(offset_of!(RegisterBlock, riscv_clock), 0xd00);
+    }
+
+    #[test]
+    fn function_cpu_pll_factor_n() {
+        assert_eq!(super::cpu_pll_factor_n(1_008_000_000), 41);
+        assert_eq!(super::cpu_pll_factor_n(1_000_000_000), 40);
+        assert_eq!(super::cpu_pll_factor_n(24_000_000), 11);
+        assert_eq!(super::cpu_pll_factor_n(u32::MAX), 177);
+    }
+
+    #[test]
+    fn struct_cpu_axi_config_functions() {
+        let mut val = CpuAxiConfig(0x0);
+
+        for i in 0..7 as u8 {
+            let tmp = match i {
+                0 => CpuClockSource::Hosc,
+                1 => CpuClockSource::Clk32K,
+                2 => CpuClockSource::Clk16MRC,
+                3 => CpuClockSource::PllCpu,
+                4 => CpuClockSource::PllPeri1x,
+                5 => CpuClockSource::PllPeri2x,
+                6 => CpuClockSource::PllPeri800M,
+                _ => unreachable!(),
+            };
+
+            val = val.set_clock_source(tmp);
+
+            match i {
+                0 => assert_eq!(val.0, 0x00000000),
+                1 => assert_eq!(val.0, 0x01000000),
+                2 => assert_eq!(val.0, 0x02000000),
+                3 => assert_eq!(val.0, 0x03000000),
+                4 => assert_eq!(val.0, 0x04000000),
+                5 => assert_eq!(val.0, 0x05000000),
+                6 => assert_eq!(val.0, 0x06000000),
+                _ => unreachable!(),
+            }
+
+            assert_eq!(val.clock_source(), tmp);
+        }
+
+        val = val.set_clock_source(CpuClockSource::Hosc);
+        assert_eq!(val.0, 0x00000000);
+        assert_eq!(val.clock_source(), CpuClockSource::Hosc);
+
+        for i in 0..3 as u8 {
+            let tmp = match i {
+                0 => FactorP::P1,
+                1 => FactorP::P2,
+                2 => FactorP::P4,
+                _ => unreachable!(),
+            };
+
+            val = val.set_factor_p(tmp);
+
+            match i {
+                0 => assert_eq!(val.0, 0x00000000),
+                1 => assert_eq!(val.0, 0x00010000),
+                2 => assert_eq!(val.0, 0x00020000),
+                _ => unreachable!(),
+            }
+
+            assert_eq!(val.factor_p(), tmp);
+        }
+
+        val = val.set_factor_p(FactorP::P1);
+        assert_eq!(val.0, 0x00000000);
+        assert_eq!(val.factor_p(), FactorP::P1);
+
+        val = val.set_factor_n(AxiFactorN::N4);
+        assert_eq!(val.0, 0x00000300);
+        assert_eq!(val.factor_n(), AxiFactorN::N4);
+
+        val = val.set_factor_n(AxiFactorN::N2);
+        assert_eq!(val.0, 0x00000100);
+        assert_eq!(val.factor_n(), AxiFactorN::N2);
+
+        val = val.set_factor_m(0x03);
+        assert_eq!(val.0, 0x00000103);
+        assert_eq!(val.factor_m(), 0x03);
+
+        val = val.set_factor_m(0x0);
+        assert_eq!(val.0, 0x00000100);
+        assert_eq!(val.factor_m(), 0x0);
+    }
+
+    #[test]
+    fn struct_mbus_clock_functions() {
+        let mut val = MbusClock(0x0);
+
+        val = val.deassert_reset();
+        assert!(!val.is_reset_asserted());
+        assert_eq!(val.0, 0x40000000);
+
+        val = val.assert_reset();
+        assert!(val.is_reset_asserted());
+        assert_eq!(val.0, 0x00000000);
+    }
+
+    #[test]
+    fn struct_dram_clock_functions() {
+        let mut val = DramClock(0x0);
+
+        val = val.unmask_clock();
+        assert!(val.is_clock_unmasked());
+        assert_eq!(val.0, 0x80000000);
+
+        val = val.mask_clock();
+        assert!(!val.is_clock_unmasked());
+        assert_eq!(val.0, 0x00000000);
+
+        for i in 0..4 as u8 {
+            let cs_tmp = match i {
+                0x0 => DramClockSource::PllDdr,
+                0x1 => DramClockSource::PllAudio1Div2,
+                0x2 => DramClockSource::PllPeri2x,
+                0x3 => DramClockSource::PllPeri800M,
+                _ => unreachable!(),
+            };
+
+            let val_tmp = match i {
+                0x0 => 0x00000000,
+                0x1 => 0x01000000,
+                0x2 => 0x02000000,
+                0x3 => 0x03000000,
+                _ => unreachable!(),
+            };
+
+            val = val.set_clock_source(cs_tmp);
+            assert_eq!(val.clock_source(), cs_tmp);
+            assert_eq!(val.0, val_tmp);
+        }
+
+        val = DramClock(0x0);
+
+        for i in 0..4 as u8 {
+            let fn_tmp = match i {
+                0x0 => PeriFactorN::N1,
+                0x1 => PeriFactorN::N2,
+                0x2 => PeriFactorN::N4,
+                0x3 => PeriFactorN::N8,
+                _ => unreachable!(),
+            };
+
+            let val_tmp = match i {
+                0x0 => 0x00000000,
+                0x1 => 0x00000100,
+                0x2 => 0x00000200,
+                0x3 => 0x00000300,
+                _ => unreachable!(),
+            };
+
+            val = val.set_factor_n(fn_tmp);
+            assert_eq!(val.factor_n(), fn_tmp);
+            assert_eq!(val.0, val_tmp);
+        }
+
+        val = DramClock(0x0);
+        val = val.set_factor_m(0x03);
+        assert_eq!(val.factor_m(), 0x03);
+        assert_eq!(val.0, 0x00000003);
+    }
+
+    #[test]
+    fn struct_dram_bgr_functions() {
+        let mut val = DramBusGating(0x0);
+
+        val = val.deassert_reset();
+        assert_eq!(val.0, 0x00010000);
+
+        val = val.assert_reset();
+        assert_eq!(val.0, 0x00000000);
+
+        val = val.gate_pass();
+        assert_eq!(val.0, 0x00000001);
+
+        val = val.gate_mask();
+        assert_eq!(val.0, 0x00000000);
+    }
+
+    #[test]
+    fn struct_dma_bgr_functions() {
+        let mut val = super::DmaBusGating(0x0);
+
+        val = val.deassert_reset();
+        assert_eq!(val.0, 0x00010000);
+
+        val = val.assert_reset();
+        assert_eq!(val.0, 0x00000000);
+
+        val = val.gate_pass();
+        assert_eq!(val.0, 0x00000001);
+
+        val = val.gate_mask();
+        assert_eq!(val.0, 0x00000000);
+    }
+
+    #[test]
+    fn struct_msgbox_bgr_functions() {
+        let mut val = super::MsgboxBusGating(0x0);
+
+        val = val.gate_pass::<1>().deassert_reset::<1>();
+        assert_eq!(val.0, 0x00020002);
+
+        val = val.gate_pass::<0>();
+        assert_eq!(val.0, 0x00020003);
+
+        val = val.gate_mask::<1>().assert_reset::<1>();
+        assert_eq!(val.0, 0x00000001);
+    }
+
+    #[test]
+    fn struct_emac_bgr_functions() {
+        let mut val = super::EmacBusGating(0x0);
+
+        val = val.deassert_reset();
+        assert_eq!(val.0, 0x00010000);
+
+        val = val.assert_reset();
+        assert_eq!(val.0, 0x00000000);
+
+        val = val.gate_pass();
+        assert_eq!(val.0, 0x00000001);
+
+        val = val.gate_mask();
+        assert_eq!(val.0, 0x00000000);
+
+        let mut val = super::Emac25MClock(0x0);
+        val = val.enable_clock();
+        assert!(val.is_clock_enabled());
+        assert_eq!(val.0, 0xC0000000);
+        val = val.disable_clock();
+        assert!(!val.is_clock_enabled());
+        assert_eq!(val.0, 0x00000000);
+    }
+
+    #[test]
+    fn struct_spinlock_bgr_functions() {
+        let mut val = super::SpinlockBusGating(0x0);
+
+        val = val.deassert_reset().gate_pass();
+        assert_eq!(val.0, 0x00010001);
+
+        val = val.assert_reset().gate_mask();
+        assert_eq!(val.0, 0x00000000);
+    }
+
+    #[test]
+    fn struct_hstimer_bgr_functions() {
+        let mut val = super::HstimerBusGating(0x0);
+
+        val = val.deassert_reset();
+        assert_eq!(val.0, 0x00010000);
+
+        val = val.assert_reset();
+        assert_eq!(val.0, 0x00000000);
+
+        val = val.gate_pass();
+        assert_eq!(val.0, 0x00000001);
+
+        val = val.gate_mask();
+        assert_eq!(val.0, 0x00000000);
+    }
+
+    #[test]
+    fn struct_gpadc_bgr_functions() {
+        let mut val = super::GpadcBusGating(0x0);
+
+        val = val.deassert_reset();
+        assert_eq!(val.0, 0x00010000);
+
+        val = val.assert_reset();
+        assert_eq!(val.0, 0x00000000);
+
+        val = val.gate_pass();
+        assert_eq!(val.0, 0x00000001);
+
+        val = val.gate_mask();
+        assert_eq!(val.0, 0x00000000);
+    }
+
+    #[test]
+    fn struct_ths_bgr_functions() {
+        let mut val = super::ThsBusGating(0x0);
+
+        val = val.deassert_reset();
+        assert_eq!(val.0, 0x00010000);
+
+        val = val.assert_reset();
+        assert_eq!(val.0, 0x00000000);
+
+        val = val.gate_pass();
+        assert_eq!(val.0, 0x00000001);
+
+        val = val.gate_mask();
+        assert_eq!(val.0, 0x00000000);
+    }
+
+    #[test]
+    fn struct_audio_codec_clock_functions() {
+        let val = super::AudioCodecClock(0x0)
+            .set_clock_source(AudioCodecClockSource::PllAudio1Div5)
+            .set_factor_n(PeriFactorN::N2)
+            .set_factor_m(24)
+            .unmask_clock();
+        assert_eq!(val.0, 0x8200_0118);
+        assert!(val.is_clock_unmasked());
+        assert_eq!(val.clock_source(), AudioCodecClockSource::PllAudio1Div5);
+        assert_eq!(val.factor_n(), PeriFactorN::N2);
+        assert_eq!(val.factor_m(), 24);
+        assert_eq!(val.mask_clock().0, 0x0200_0118);
+    }
+
+    #[test]
+    fn struct_audio_codec_bgr_functions() {
+        let mut val = super::AudioCodecBusGating(0x0);
+
+        val = val.deassert_reset();
+        assert_eq!(val.0, 0x00010000);
 
-    unsafe fn configure(
-        ccu: &RegisterBlock,
-        source: Self::Source,
-        factor_m: u8,
-        factor_n: PeriFactorN,
-    ) {
-        unsafe {
-            let spi_clk = ccu.spi_clk[I].read();
-            ccu.spi_clk[I].write(
-                spi_clk
-                    .set_clock_source(source)
-                    .set_factor_m(factor_m)
-                    .set_factor_n(factor_n),
-            )
-        }
+        val = val.assert_reset();
+        assert_eq!(val.0, 0x00000000);
+
+        val = val.gate_pass();
+        assert_eq!(val.0, 0x00000001);
+
+        val = val.gate_mask();
+        assert_eq!(val.0, 0x00000000);
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::{
-        AxiFactorN, CpuAxiConfig, CpuClockSource, DramBusGating, DramClock, DramClockSource,
-        FactorP, MbusClock, PeriFactorN, RegisterBlock,
-    };
-    use core::mem::offset_of;
     #[test]
-    fn offset_ccu() {
-        assert_eq!(offset_of!(RegisterBlock, pll_cpu_control), 0x0);
-        assert_eq!(offset_of!(RegisterBlock, pll_ddr_control), 0x10);
-        assert_eq!(offset_of!(RegisterBlock, pll_peri0_control), 0x20);
-        assert_eq!(offset_of!(RegisterBlock, cpu_axi_config), 0x500);
-        assert_eq!(offset_of!(RegisterBlock, mbus_clock), 0x540);
-        assert_eq!(offset_of!(RegisterBlock, dram_clock), 0x800);
-        assert_eq!(offset_of!(RegisterBlock, dram_bgr), 0x80c);
-        assert_eq!(offset_of!(RegisterBlock, smhc_clk), 0x830);
-        assert_eq!(offset_of!(RegisterBlock, smhc_bgr), 0x84c);
-        assert_eq!(offset_of!(RegisterBlock, uart_bgr), 0x90c);
-        assert_eq!(offset_of!(RegisterBlock, spi_clk), 0x940);
-        assert_eq!(offset_of!(RegisterBlock, spi_bgr), 0x96c);
+    fn struct_owa_bgr_functions() {
+        let mut val = super::OwaBusGating(0x0);
+
+        val = val.deassert_reset();
+        assert_eq!(val.0, 0x00010000);
+
+        val = val.assert_reset();
+        assert_eq!(val.0, 0x00000000);
+
+        val = val.gate_pass();
+        assert_eq!(val.0, 0x00000001);
+
+        val = val.gate_mask();
+        assert_eq!(val.0, 0x00000000);
     }
 
     #[test]
-    fn struct_cpu_axi_config_functions() {
-        let mut val = CpuAxiConfig(0x0);
+    fn struct_dmic_bgr_functions() {
+        let mut val = super::DmicBusGating(0x0);
 
-        for i in 0..7 as u8 {
-            let tmp = match i {
-                0 => CpuClockSource::Hosc,
-                1 => CpuClockSource::Clk32K,
-                2 => CpuClockSource::Clk16MRC,
-                3 => CpuClockSource::PllCpu,
-                4 => CpuClockSource::PllPeri1x,
-                5 => CpuClockSource::PllPeri2x,
-                6 => CpuClockSource::PllPeri800M,
-                _ => unreachable!(),
-            };
+        val = val.deassert_reset();
+        assert_eq!(val.0, 0x00010000);
 
-            val = val.set_clock_source(tmp);
+        val = val.assert_reset();
+        assert_eq!(val.0, 0x00000000);
 
-            match i {
-                0 => assert_eq!(val.0, 0x00000000),
-                1 => assert_eq!(val.0, 0x01000000),
-                2 => assert_eq!(val.0, 0x02000000),
-                3 => assert_eq!(val.0, 0x03000000),
-                4 => assert_eq!(val.0, 0x04000000),
-                5 => assert_eq!(val.0, 0x05000000),
-                6 => assert_eq!(val.0, 0x06000000),
-                _ => unreachable!(),
-            }
+        val = val.gate_pass();
+        assert_eq!(val.0, 0x00000001);
 
-            assert_eq!(val.clock_source(), tmp);
-        }
+        val = val.gate_mask();
+        assert_eq!(val.0, 0x00000000);
+    }
 
-        val = val.set_clock_source(CpuClockSource::Hosc);
+    #[test]
+    fn struct_usb_clock_functions() {
+        let mut val = super::UsbClock(0x0);
+
+        val = val.unmask_clock().deassert_phy_reset();
+        assert!(val.is_clock_unmasked());
+        assert!(val.is_phy_reset_deasserted());
+        assert_eq!(val.0, 0xC0000000);
+
+        val = val.mask_clock().assert_phy_reset();
         assert_eq!(val.0, 0x00000000);
-        assert_eq!(val.clock_source(), CpuClockSource::Hosc);
+    }
 
-        for i in 0..3 as u8 {
-            let tmp = match i {
-                0 => FactorP::P1,
-                1 => FactorP::P2,
-                2 => FactorP::P4,
-                _ => unreachable!(),
-            };
+    #[test]
+    fn struct_usb_bgr_functions() {
+        let mut val = super::UsbBusGating(0x0);
 
-            val = val.set_factor_p(tmp);
+        val = val.deassert_otg_reset();
+        assert_eq!(val.0, 0x01000000);
 
-            match i {
-                0 => assert_eq!(val.0, 0x00000000),
-                1 => assert_eq!(val.0, 0x00010000),
-                2 => assert_eq!(val.0, 0x00020000),
-                _ => unreachable!(),
-            }
+        val = val.gate_pass_otg();
+        assert_eq!(val.0, 0x01000100);
 
-            assert_eq!(val.factor_p(), tmp);
-        }
+        val = val.assert_otg_reset().gate_mask_otg();
+        assert_eq!(val.0, 0x00000000);
 
-        val = val.set_factor_p(FactorP::P1);
+        val = val.deassert_ehci_reset::<1>().gate_pass_ehci::<1>();
+        assert_eq!(val.0, 0x00200020);
+
+        val = val.deassert_ohci_reset::<1>().gate_pass_ohci::<1>();
+        assert_eq!(val.0, 0x00220022);
+
+        val = val.deassert_ehci_reset::<0>().gate_pass_ohci::<0>();
+        assert_eq!(val.0, 0x00320023);
+
+        val = val
+            .assert_ehci_reset::<0>()
+            .gate_mask_ohci::<0>()
+            .assert_ehci_reset::<1>()
+            .gate_mask_ehci::<1>()
+            .assert_ohci_reset::<1>()
+            .gate_mask_ohci::<1>();
         assert_eq!(val.0, 0x00000000);
-        assert_eq!(val.factor_p(), FactorP::P1);
+    }
 
-        val = val.set_factor_n(AxiFactorN::N4);
-        assert_eq!(val.0, 0x00000300);
-        assert_eq!(val.factor_n(), AxiFactorN::N4);
+    #[test]
+    fn struct_lradc_bgr_functions() {
+        let mut val = super::LradcBusGating(0x0);
 
-        val = val.set_factor_n(AxiFactorN::N2);
-        assert_eq!(val.0, 0x00000100);
-        assert_eq!(val.factor_n(), AxiFactorN::N2);
+        val = val.deassert_reset();
+        assert_eq!(val.0, 0x00010000);
 
-        val = val.set_factor_m(0x03);
-        assert_eq!(val.0, 0x00000103);
-        assert_eq!(val.factor_m(), 0x03);
+        val = val.assert_reset();
+        assert_eq!(val.0, 0x00000000);
 
-        val = val.set_factor_m(0x0);
-        assert_eq!(val.0, 0x00000100);
-        assert_eq!(val.factor_m(), 0x0);
+        val = val.gate_pass();
+        assert_eq!(val.0, 0x00000001);
+
+        val = val.gate_mask();
+        assert_eq!(val.0, 0x00000000);
     }
 
     #[test]
-    fn struct_mbus_clock_functions() {
-        let mut val = MbusClock(0x0);
+    fn struct_pwm_bgr_functions() {
+        let mut val = super::PwmBusGating(0x0);
 
         val = val.deassert_reset();
-        assert!(!val.is_reset_asserted());
-        assert_eq!(val.0, 0x40000000);
+        assert_eq!(val.0, 0x00010000);
 
         val = val.assert_reset();
-        assert!(val.is_reset_asserted());
+        assert_eq!(val.0, 0x00000000);
+
+        val = val.gate_pass();
+        assert_eq!(val.0, 0x00000001);
+
+        val = val.gate_mask();
         assert_eq!(val.0, 0x00000000);
     }
 
     #[test]
-    fn struct_dram_clock_functions() {
-        let mut val = DramClock(0x0);
+    fn struct_ledc_clock_functions() {
+        let mut val = super::LedcClock(0x0);
 
         val = val.unmask_clock();
         assert!(val.is_clock_unmasked());
         assert_eq!(val.0, 0x80000000);
-
         val = val.mask_clock();
-        assert!(!val.is_clock_unmasked());
         assert_eq!(val.0, 0x00000000);
 
-        for i in 0..4 as u8 {
-            let cs_tmp = match i {
-                0x0 => DramClockSource::PllDdr,
-                0x1 => DramClockSource::PllAudio1Div2,
-                0x2 => DramClockSource::PllPeri2x,
-                0x3 => DramClockSource::PllPeri800M,
-                _ => unreachable!(),
-            };
+        val = val.set_clock_source(super::LedcClockSource::PllPeri1x);
+        assert_eq!(val.clock_source(), super::LedcClockSource::PllPeri1x);
+        assert_eq!(val.0, 0x01000000);
 
-            let val_tmp = match i {
-                0x0 => 0x00000000,
-                0x1 => 0x01000000,
-                0x2 => 0x02000000,
-                0x3 => 0x03000000,
-                _ => unreachable!(),
-            };
+        val = super::LedcClock(0x0)
+            .set_factor_n(super::PeriFactorN::N8)
+            .set_factor_m(0xf);
+        assert_eq!(val.factor_n(), super::PeriFactorN::N8);
+        assert_eq!(val.factor_m(), 0xf);
+        assert_eq!(val.0, 0x0000030f);
 
-            val = val.set_clock_source(cs_tmp);
-            assert_eq!(val.clock_source(), cs_tmp);
-            assert_eq!(val.0, val_tmp);
-        }
+        let mut val = super::LedcBusGating(0x0);
+        val = val.deassert_reset().gate_pass();
+        assert_eq!(val.0, 0x00010001);
+        val = val.assert_reset().gate_mask();
+        assert_eq!(val.0, 0x00000000);
+    }
 
-        val = DramClock(0x0);
+    #[test]
+    fn struct_dsp_clock_functions() {
+        let val = super::DspClock(0x0)
+            .set_clock_source(super::DspClockSource::PllPeri2x)
+            .set_factor_m(0x2)
+            .unmask_clock();
+        assert_eq!(val.0, 0x83000002);
+        assert!(val.is_clock_unmasked());
+        assert_eq!(val.clock_source(), super::DspClockSource::PllPeri2x);
+        assert_eq!(val.factor_m(), 0x2);
+        let val = val.set_clock_source(super::DspClockSource::PllAudio1Div2);
+        assert_eq!(val.0, 0x84000002);
+        assert_eq!(val.mask_clock().0, 0x04000002);
 
-        for i in 0..4 as u8 {
-            let fn_tmp = match i {
-                0x0 => PeriFactorN::N1,
-                0x1 => PeriFactorN::N2,
-                0x2 => PeriFactorN::N4,
-                0x3 => PeriFactorN::N8,
-                _ => unreachable!(),
-            };
+        let mut val = super::DspBusGating(0x0);
+        assert!(val.is_core_reset_asserted());
+        val = val.deassert_reset().gate_pass();
+        assert_eq!(val.0, 0x00060002);
+        val = val.deassert_core_reset();
+        assert!(!val.is_core_reset_asserted());
+        assert_eq!(val.0, 0x00070002);
+        val = val.assert_core_reset().assert_reset().gate_mask();
+        assert_eq!(val.0, 0x00000000);
+    }
 
-            let val_tmp = match i {
-                0x0 => 0x00000000,
-                0x1 => 0x00000100,
-                0x2 => 0x00000200,
-                0x3 => 0x00000300,
-                _ => unreachable!(),
-            };
+    #[test]
+    fn struct_riscv_clock_functions() {
+        let val = super::RiscvClock(0x0)
+            .set_clock_source(super::RiscvClockSource::PllCpu)
+            .set_axi_factor_n(0x1)
+            .set_factor_m(0x2);
+        assert_eq!(val.0, 0x05000102);
+        assert_eq!(val.clock_source(), super::RiscvClockSource::PllCpu);
+        assert_eq!(val.axi_factor_n(), 0x1);
+        assert_eq!(val.factor_m(), 0x2);
+        let val = val
+            .set_clock_source(super::RiscvClockSource::Hosc)
+            .set_factor_m(0x3f);
+        assert_eq!(val.0, 0x0000011f);
+        assert_eq!(val.clock_source(), super::RiscvClockSource::Hosc);
+    }
 
-            val = val.set_factor_n(fn_tmp);
-            assert_eq!(val.factor_n(), fn_tmp);
-            assert_eq!(val.0, val_tmp);
-        }
+    #[test]
+    fn struct_de_clock_functions() {
+        let val = super::DeClock(0x0)
+            .set_clock_source(super::DeClockSource::PllPeri2x)
+            .set_factor_m(0x3)
+            .unmask_clock();
+        assert_eq!(val.0, 0x80000003);
+        assert!(val.is_clock_unmasked());
+        assert_eq!(val.clock_source(), super::DeClockSource::PllPeri2x);
+        assert_eq!(val.factor_m(), 0x3);
+        let val = val.set_clock_source(super::DeClockSource::PllAudio1Div2);
+        assert_eq!(val.0, 0x83000003);
+        assert_eq!(val.mask_clock().0, 0x03000003);
 
-        val = DramClock(0x0);
-        val = val.set_factor_m(0x03);
-        assert_eq!(val.factor_m(), 0x03);
-        assert_eq!(val.0, 0x00000003);
+        let mut val = super::DeBusGating(0x0);
+        val = val.deassert_reset().gate_pass();
+        assert_eq!(val.0, 0x00010001);
+        val = val.assert_reset().gate_mask();
+        assert_eq!(val.0, 0x00000000);
+
+        let mut val = super::DpssTopBusGating(0x0);
+        val = val.deassert_reset().gate_pass();
+        assert_eq!(val.0, 0x00010001);
+        val = val.assert_reset().gate_mask();
+        assert_eq!(val.0, 0x00000000);
     }
 
     #[test]
-    fn struct_dram_bgr_functions() {
-        let mut val = DramBusGating(0x0);
+    fn struct_g2d_clock_functions() {
+        let val = super::G2dClock(0x0)
+            .set_clock_source(super::G2dClockSource::PllVideo0x4)
+            .set_factor_m(0x1)
+            .unmask_clock();
+        assert_eq!(val.0, 0x81000001);
+        assert!(val.is_clock_unmasked());
+        assert_eq!(val.clock_source(), super::G2dClockSource::PllVideo0x4);
+        assert_eq!(val.factor_m(), 0x1);
+        assert_eq!(val.mask_clock().0, 0x01000001);
 
-        val = val.deassert_reset();
-        assert_eq!(val.0, 0x00010000);
+        let mut val = super::G2dBusGating(0x0);
+        val = val.deassert_reset().gate_pass();
+        assert_eq!(val.0, 0x00010001);
+        val = val.assert_reset().gate_mask();
+        assert_eq!(val.0, 0x00000000);
+    }
 
-        val = val.assert_reset();
+    #[test]
+    fn struct_ce_clock_functions() {
+        let val = super::CeClock(0x0)
+            .set_clock_source(super::CeClockSource::PllPeri2x)
+            .set_factor_n(super::PeriFactorN::N2)
+            .set_factor_m(0x3)
+            .unmask_clock();
+        assert_eq!(val.0, 0x81000103);
+        assert!(val.is_clock_unmasked());
+        assert_eq!(val.clock_source(), super::CeClockSource::PllPeri2x);
+        assert_eq!(val.factor_n(), super::PeriFactorN::N2);
+        assert_eq!(val.factor_m(), 0x3);
+        assert_eq!(val.mask_clock().0, 0x01000103);
+
+        let mut val = super::CeBusGating(0x0);
+        val = val.deassert_reset().gate_pass();
+        assert_eq!(val.0, 0x00010001);
+        val = val.assert_reset().gate_mask();
         assert_eq!(val.0, 0x00000000);
 
-        val = val.gate_pass();
-        assert_eq!(val.0, 0x00000001);
+        let val = super::MbusGating(0x1).gate_pass(super::MbusMaster::Ce);
+        assert_eq!(val.0, 0x00000005);
+        assert!(val.is_gate_passed(super::MbusMaster::Dma));
+        assert!(val.is_gate_passed(super::MbusMaster::Ce));
+        assert_eq!(val.gate_mask(super::MbusMaster::Ce).0, 0x00000001);
+    }
 
-        val = val.gate_mask();
+    #[test]
+    fn struct_hdmi_clock_functions() {
+        let val = super::Hdmi24mClock(0x0).unmask_clock();
+        assert_eq!(val.0, 0x80000000);
+        assert!(val.is_clock_unmasked());
+        assert_eq!(val.mask_clock().0, 0x00000000);
+
+        let mut val = super::HdmiBusGating(0x0);
+        val = val.deassert_reset().gate_pass();
+        assert_eq!(val.0, 0x00030001);
+        val = val.assert_reset().gate_mask();
+        assert_eq!(val.0, 0x00000000);
+    }
+
+    #[test]
+    fn struct_tcon_lcd_clock_functions() {
+        let val = super::TconLcdClock(0x0)
+            .set_clock_source(super::TconLcdClockSource::PllVideo0x4)
+            .set_factor_n(super::PeriFactorN::N2)
+            .set_factor_m(0x3)
+            .unmask_clock();
+        assert_eq!(val.0, 0x81000103);
+        assert!(val.is_clock_unmasked());
+        assert_eq!(val.clock_source(), super::TconLcdClockSource::PllVideo0x4);
+        assert_eq!(val.factor_n(), super::PeriFactorN::N2);
+        assert_eq!(val.factor_m(), 0x3);
+        assert_eq!(val.mask_clock().0, 0x01000103);
+
+        let mut val = super::TconLcdBusGating(0x0);
+        val = val.deassert_reset().gate_pass();
+        assert_eq!(val.0, 0x00010001);
+        val = val.assert_reset().gate_mask();
         assert_eq!(val.0, 0x00000000);
     }
 
+    #[test]
+    fn struct_can_bgr_functions() {
+        let mut val = super::CanBusGating(0x0);
+
+        val = val.gate_pass::<1>().deassert_reset::<1>();
+        assert_eq!(val.0, 0x00020002);
+
+        val = val.gate_pass::<0>();
+        assert_eq!(val.0, 0x00020003);
+
+        val = val.gate_mask::<1>().assert_reset::<1>();
+        assert_eq!(val.0, 0x00000001);
+    }
+
     #[test]
     fn struct_uart_bgr_functions() {
         let mut val = super::UartBusGating(0x0);
@@ -1115,4 +4730,38 @@ mod tests {
         val = val.assert_reset::<1>();
         assert_eq!(val.0, 0x00000000);
     }
+
+    #[test]
+    fn struct_smhc_clock_instance_source() {
+        let val = SmhcClock(0x0)
+            .set_instance_clock_source::<0>(SmhcClockSource::PllAudio1Div2)
+            .unwrap();
+        assert_eq!(val.0, 0x0300_0000);
+        assert_eq!(
+            val.instance_clock_source::<0>(),
+            SmhcClockSource::PllAudio1Div2
+        );
+        assert_eq!(
+            val.instance_clock_source::<2>(),
+            SmhcClockSource::PllPeri800M
+        );
+
+        let val = SmhcClock(0x0)
+            .set_instance_clock_source::<2>(SmhcClockSource::PllAudio1Div2)
+            .unwrap();
+        assert_eq!(val.0, 0x0400_0000);
+        let val = SmhcClock(0x0)
+            .set_instance_clock_source::<1>(SmhcClockSource::PllPeri2x)
+            .unwrap();
+        assert_eq!(val.0, 0x0200_0000);
+        assert_eq!(val.instance_clock_source::<1>(), SmhcClockSource::PllPeri2x);
+
+        let source = SmhcClockSource::PllPeri800M;
+        assert_eq!(SmhcClock(0x0).set_instance_clock_source::<0>(source), None);
+        assert_eq!(SmhcClock(0x0).set_instance_clock_source::<1>(source), None);
+        let val = SmhcClock(0x0)
+            .set_instance_clock_source::<2>(SmhcClockSource::PllPeri800M)
+            .unwrap();
+        assert_eq!(val.0, 0x0300_0000);
+    }
 }
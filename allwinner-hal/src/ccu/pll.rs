@@ -354,9 +354,456 @@ impl Default for PllPeri0Control {
     }
 }
 
+/// Audio PLL 0 Control register.
+///
+/// 4X output runs at 24 MHz × N / M0 / M1 / P, with fractional N when sigma-delta modulation
+/// is enabled; 1X output is a quarter of it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct PllAudio0Control(u32);
+
+impl PllAudio0Control {
+    const PLL_ENABLE: u32 = 1 << 31;
+    const PLL_LDO_ENABLE: u32 = 1 << 30;
+    const LOCK_ENABLE: u32 = 1 << 29;
+    const LOCK: u32 = 1 << 28;
+    const PLL_OUTPUT_GATE: u32 = 1 << 27;
+    const PLL_SDM_ENABLE: u32 = 1 << 24;
+    const PLL_P: u32 = 0x3f << 16;
+    const PLL_N: u32 = 0xff << 8;
+    const PLL_M1: u32 = 0x1 << 1;
+    const PLL_M0: u32 = 0x1 << 0;
+
+    /// Get if PLL is enabled.
+    #[inline]
+    pub const fn is_pll_enabled(self) -> bool {
+        self.0 & Self::PLL_ENABLE != 0
+    }
+    /// Enable PLL.
+    #[inline]
+    pub const fn enable_pll(self) -> Self {
+        Self(self.0 | Self::PLL_ENABLE)
+    }
+    /// Disable PLL.
+    #[inline]
+    pub const fn disable_pll(self) -> Self {
+        Self(self.0 & !Self::PLL_ENABLE)
+    }
+    /// Get if PLL LDO is enabled.
+    #[inline]
+    pub const fn is_pll_ldo_enabled(self) -> bool {
+        self.0 & Self::PLL_LDO_ENABLE != 0
+    }
+    /// Enable PLL LDO.
+    #[inline]
+    pub const fn enable_pll_ldo(self) -> Self {
+        Self(self.0 | Self::PLL_LDO_ENABLE)
+    }
+    /// Disable PLL LDO.
+    #[inline]
+    pub const fn disable_pll_ldo(self) -> Self {
+        Self(self.0 & !Self::PLL_LDO_ENABLE)
+    }
+    /// Get if PLL lock is enabled.
+    #[inline]
+    pub const fn is_lock_enabled(self) -> bool {
+        self.0 & Self::LOCK_ENABLE != 0
+    }
+    /// Enable PLL lock.
+    #[inline]
+    pub const fn enable_lock(self) -> Self {
+        Self(self.0 | Self::LOCK_ENABLE)
+    }
+    /// Disable PLL lock.
+    #[inline]
+    pub const fn disable_lock(self) -> Self {
+        Self(self.0 & !Self::LOCK_ENABLE)
+    }
+    /// Get if the PLL locked state is set by hardware.
+    #[inline]
+    pub const fn is_locked(self) -> bool {
+        self.0 & Self::LOCK != 0
+    }
+    /// Unmask (enable) PLL output.
+    #[inline]
+    pub const fn unmask_pll_output(self) -> Self {
+        Self(self.0 | Self::PLL_OUTPUT_GATE)
+    }
+    /// Mask (disable) PLL output.
+    #[inline]
+    pub const fn mask_pll_output(self) -> Self {
+        Self(self.0 & !Self::PLL_OUTPUT_GATE)
+    }
+    /// Get if PLL output is unmasked.
+    #[inline]
+    pub const fn is_pll_output_unmasked(self) -> bool {
+        self.0 & Self::PLL_OUTPUT_GATE != 0
+    }
+    /// Get if sigma-delta modulation is enabled.
+    #[inline]
+    pub const fn is_sdm_enabled(self) -> bool {
+        self.0 & Self::PLL_SDM_ENABLE != 0
+    }
+    /// Enable sigma-delta modulation, adding fraction of audio PLL 0 pattern register to N.
+    #[inline]
+    pub const fn enable_sdm(self) -> Self {
+        Self(self.0 | Self::PLL_SDM_ENABLE)
+    }
+    /// Disable sigma-delta modulation.
+    #[inline]
+    pub const fn disable_sdm(self) -> Self {
+        Self(self.0 & !Self::PLL_SDM_ENABLE)
+    }
+    /// Get PLL P factor, output divider minus one.
+    #[inline]
+    pub const fn pll_p(self) -> u8 {
+        ((self.0 & Self::PLL_P) >> 16) as u8
+    }
+    /// Set PLL P factor, output divider minus one.
+    #[inline]
+    pub const fn set_pll_p(self, val: u8) -> Self {
+        Self((self.0 & !Self::PLL_P) | ((val as u32 & 0x3f) << 16))
+    }
+    /// Get PLL N factor.
+    #[inline]
+    pub const fn pll_n(self) -> u8 {
+        ((self.0 & Self::PLL_N) >> 8) as u8
+    }
+    /// Set PLL N factor.
+    #[inline]
+    pub const fn set_pll_n(self, val: u8) -> Self {
+        Self((self.0 & !Self::PLL_N) | ((val as u32) << 8))
+    }
+    /// Get PLL M1 factor.
+    #[inline]
+    pub const fn pll_m1(self) -> u8 {
+        ((self.0 & Self::PLL_M1) >> 1) as u8
+    }
+    /// Set PLL M1 factor.
+    #[inline]
+    pub const fn set_pll_m1(self, val: u8) -> Self {
+        Self((self.0 & !Self::PLL_M1) | ((val as u32 & 0x1) << 1))
+    }
+    /// Get PLL M0 factor.
+    #[inline]
+    pub const fn pll_m0(self) -> u8 {
+        (self.0 & Self::PLL_M0) as u8
+    }
+    /// Set PLL M0 factor.
+    #[inline]
+    pub const fn set_pll_m0(self, val: u8) -> Self {
+        Self((self.0 & !Self::PLL_M0) | (val as u32 & 0x1))
+    }
+}
+
+impl Default for PllAudio0Control {
+    #[inline]
+    fn default() -> Self {
+        Self(0x4814_5500)
+    }
+}
+
+/// Audio PLL 0 Pattern 0 register.
+///
+/// Fraction added to factor N is `wave_bottom / 2^17` with static pattern mode.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct PllAudio0Pattern(u32);
+
+impl PllAudio0Pattern {
+    const SIG_DELT_PAT_EN: u32 = 1 << 31;
+    const SPR_FREQ_MODE: u32 = 0x3 << 29;
+    const WAVE_BOT: u32 = 0x1ffff;
+
+    /// Get if sigma-delta pattern is enabled.
+    #[inline]
+    pub const fn is_pattern_enabled(self) -> bool {
+        self.0 & Self::SIG_DELT_PAT_EN != 0
+    }
+    /// Enable sigma-delta pattern.
+    #[inline]
+    pub const fn enable_pattern(self) -> Self {
+        Self(self.0 | Self::SIG_DELT_PAT_EN)
+    }
+    /// Disable sigma-delta pattern.
+    #[inline]
+    pub const fn disable_pattern(self) -> Self {
+        Self(self.0 & !Self::SIG_DELT_PAT_EN)
+    }
+    /// Get spread frequency mode, 2 for static fractional N.
+    #[inline]
+    pub const fn spread_mode(self) -> u8 {
+        ((self.0 & Self::SPR_FREQ_MODE) >> 29) as u8
+    }
+    /// Set spread frequency mode, 2 for static fractional N.
+    #[inline]
+    pub const fn set_spread_mode(self, val: u8) -> Self {
+        Self((self.0 & !Self::SPR_FREQ_MODE) | ((val as u32 & 0x3) << 29))
+    }
+    /// Get wave bottom, fraction of N in units of 2^-17.
+    #[inline]
+    pub const fn wave_bottom(self) -> u32 {
+        self.0 & Self::WAVE_BOT
+    }
+    /// Set wave bottom, fraction of N in units of 2^-17.
+    #[inline]
+    pub const fn set_wave_bottom(self, val: u32) -> Self {
+        Self((self.0 & !Self::WAVE_BOT) | (val & Self::WAVE_BOT))
+    }
+}
+
+impl Default for PllAudio0Pattern {
+    #[inline]
+    fn default() -> Self {
+        Self(0x0000_0000)
+    }
+}
+
+/// Audio PLL 1 Control register.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct PllAudio1Control(u32);
+
+impl PllAudio1Control {
+    const PLL_ENABLE: u32 = 1 << 31;
+    const PLL_LDO_ENABLE: u32 = 1 << 30;
+    const LOCK_ENABLE: u32 = 1 << 29;
+    const LOCK: u32 = 1 << 28;
+    const PLL_OUTPUT_GATE: u32 = 1 << 27;
+    const PLL_P1: u32 = 0x07 << 20;
+    const PLL_P0: u32 = 0x07 << 16;
+    const PLL_N: u32 = 0xff << 8;
+    const PLL_M: u32 = 0x1 << 1;
+
+    /// Get if PLL is enabled.
+    #[inline]
+    pub const fn is_pll_enabled(self) -> bool {
+        self.0 & Self::PLL_ENABLE != 0
+    }
+    /// Enable PLL.
+    #[inline]
+    pub const fn enable_pll(self) -> Self {
+        Self(self.0 | Self::PLL_ENABLE)
+    }
+    /// Disable PLL.
+    #[inline]
+    pub const fn disable_pll(self) -> Self {
+        Self(self.0 & !Self::PLL_ENABLE)
+    }
+    /// Get if PLL LDO is enabled.
+    #[inline]
+    pub const fn is_pll_ldo_enabled(self) -> bool {
+        self.0 & Self::PLL_LDO_ENABLE != 0
+    }
+    /// Enable PLL LDO.
+    #[inline]
+    pub const fn enable_pll_ldo(self) -> Self {
+        Self(self.0 | Self::PLL_LDO_ENABLE)
+    }
+    /// Disable PLL LDO.
+    #[inline]
+    pub const fn disable_pll_ldo(self) -> Self {
+        Self(self.0 & !Self::PLL_LDO_ENABLE)
+    }
+    /// Get if PLL lock is enabled.
+    #[inline]
+    pub const fn is_lock_enabled(self) -> bool {
+        self.0 & Self::LOCK_ENABLE != 0
+    }
+    /// Enable PLL lock.
+    #[inline]
+    pub const fn enable_lock(self) -> Self {
+        Self(self.0 | Self::LOCK_ENABLE)
+    }
+    /// Disable PLL lock.
+    #[inline]
+    pub const fn disable_lock(self) -> Self {
+        Self(self.0 & !Self::LOCK_ENABLE)
+    }
+    /// Get if the PLL locked state is set by hardware.
+    #[inline]
+    pub const fn is_locked(self) -> bool {
+        self.0 & Self::LOCK != 0
+    }
+    /// Unmask (enable) PLL output.
+    pub const fn unmask_pll_output(self) -> Self {
+        Self(self.0 | Self::PLL_OUTPUT_GATE)
+    }
+    /// Mask (disable) PLL output.
+    #[inline]
+    pub const fn mask_pll_output(self) -> Self {
+        Self(self.0 & !Self::PLL_OUTPUT_GATE)
+    }
+    /// Get if PLL output is unmasked.
+    #[inline]
+    pub const fn is_pll_output_unmasked(self) -> bool {
+        self.0 & Self::PLL_OUTPUT_GATE != 0
+    }
+    /// Get PLL P1 factor, divider of the divide-by-5 output minus one.
+    #[inline]
+    pub const fn pll_p1(self) -> u8 {
+        ((self.0 & Self::PLL_P1) >> 20) as u8
+    }
+    /// Set PLL P1 factor, divider of the divide-by-5 output minus one.
+    #[inline]
+    pub const fn set_pll_p1(self, val: u8) -> Self {
+        Self((self.0 & !Self::PLL_P1) | ((val as u32) << 20))
+    }
+    /// Get PLL P0 factor, divider of the divide-by-2 output minus one.
+    #[inline]
+    pub const fn pll_p0(self) -> u8 {
+        ((self.0 & Self::PLL_P0) >> 16) as u8
+    }
+    /// Set PLL P0 factor, divider of the divide-by-2 output minus one.
+    #[inline]
+    pub const fn set_pll_p0(self, val: u8) -> Self {
+        Self((self.0 & !Self::PLL_P0) | ((val as u32) << 16))
+    }
+    /// Get PLL N factor.
+    #[inline]
+    pub const fn pll_n(self) -> u8 {
+        ((self.0 & Self::PLL_N) >> 8) as u8
+    }
+    /// Set PLL N factor.
+    #[inline]
+    pub const fn set_pll_n(self, val: u8) -> Self {
+        Self((self.0 & !Self::PLL_N) | ((val as u32) << 8))
+    }
+    /// Get PLL M factor.
+    #[inline]
+    pub const fn pll_m(self) -> u8 {
+        ((self.0 & Self::PLL_M) >> 1) as u8
+    }
+    /// Set PLL M factor.
+    #[inline]
+    pub const fn set_pll_m(self, val: u8) -> Self {
+        Self((self.0 & !Self::PLL_M) | ((val as u32) << 1))
+    }
+}
+
+impl Default for PllAudio1Control {
+    #[inline]
+    fn default() -> Self {
+        Self(0x0814_2A00)
+    }
+}
+
+/// Video PLL 0 Control register.
+///
+/// 4X output runs at 24 MHz × N / M; 2X and 1X outputs are a half and a quarter of it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct PllVideo0Control(u32);
+
+impl PllVideo0Control {
+    const PLL_ENABLE: u32 = 1 << 31;
+    const PLL_LDO_ENABLE: u32 = 1 << 30;
+    const LOCK_ENABLE: u32 = 1 << 29;
+    const LOCK: u32 = 1 << 28;
+    const PLL_OUTPUT_GATE: u32 = 1 << 27;
+    const PLL_N: u32 = 0xff << 8;
+    const PLL_M: u32 = 0x1 << 1;
+
+    /// Get if PLL is enabled.
+    #[inline]
+    pub const fn is_pll_enabled(self) -> bool {
+        self.0 & Self::PLL_ENABLE != 0
+    }
+    /// Enable PLL.
+    #[inline]
+    pub const fn enable_pll(self) -> Self {
+        Self(self.0 | Self::PLL_ENABLE)
+    }
+    /// Disable PLL.
+    #[inline]
+    pub const fn disable_pll(self) -> Self {
+        Self(self.0 & !Self::PLL_ENABLE)
+    }
+    /// Get if PLL LDO is enabled.
+    #[inline]
+    pub const fn is_pll_ldo_enabled(self) -> bool {
+        self.0 & Self::PLL_LDO_ENABLE != 0
+    }
+    /// Enable PLL LDO.
+    #[inline]
+    pub const fn enable_pll_ldo(self) -> Self {
+        Self(self.0 | Self::PLL_LDO_ENABLE)
+    }
+    /// Disable PLL LDO.
+    #[inline]
+    pub const fn disable_pll_ldo(self) -> Self {
+        Self(self.0 & !Self::PLL_LDO_ENABLE)
+    }
+    /// Get if PLL lock is enabled.
+    #[inline]
+    pub const fn is_lock_enabled(self) -> bool {
+        self.0 & Self::LOCK_ENABLE != 0
+    }
+    /// Enable PLL lock.
+    #[inline]
+    pub const fn enable_lock(self) -> Self {
+        Self(self.0 | Self::LOCK_ENABLE)
+    }
+    /// Disable PLL lock.
+    #[inline]
+    pub const fn disable_lock(self) -> Self {
+        Self(self.0 & !Self::LOCK_ENABLE)
+    }
+    /// Get if the PLL locked state is set by hardware.
+    #[inline]
+    pub const fn is_locked(self) -> bool {
+        self.0 & Self::LOCK != 0
+    }
+    /// Unmask (enable) PLL output.
+    #[inline]
+    pub const fn unmask_pll_output(self) -> Self {
+        Self(self.0 | Self::PLL_OUTPUT_GATE)
+    }
+    /// Mask (disable) PLL output.
+    #[inline]
+    pub const fn mask_pll_output(self) -> Self {
+        Self(self.0 & !Self::PLL_OUTPUT_GATE)
+    }
+    /// Get if PLL output is unmasked.
+    #[inline]
+    pub const fn is_pll_output_unmasked(self) -> bool {
+        self.0 & Self::PLL_OUTPUT_GATE != 0
+    }
+    /// Get PLL N factor.
+    #[inline]
+    pub const fn pll_n(self) -> u8 {
+        ((self.0 & Self::PLL_N) >> 8) as u8
+    }
+    /// Set PLL N factor.
+    #[inline]
+    pub const fn set_pll_n(self, val: u8) -> Self {
+        Self((self.0 & !Self::PLL_N) | ((val as u32) << 8))
+    }
+    /// Get PLL M factor.
+    #[inline]
+    pub const fn pll_m(self) -> u8 {
+        ((self.0 & Self::PLL_M) >> 1) as u8
+    }
+    /// Set PLL M factor.
+    #[inline]
+    pub const fn set_pll_m(self, val: u8) -> Self {
+        Self((self.0 & !Self::PLL_M) | ((val as u32) << 1))
+    }
+}
+
+impl Default for PllVideo0Control {
+    #[inline]
+    fn default() -> Self {
+        Self(0x4800_2301)
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use super::{PllCpuControl, PllDdrControl, PllPeri0Control};
+    use super::{
+        PllAudio0Control, PllAudio0Pattern, PllAudio1Control, PllCpuControl, PllDdrControl,
+        PllPeri0Control, PllVideo0Control,
+    };
 
     #[test]
     fn struct_pll_cpu_control_functions() {
@@ -591,4 +1038,88 @@ mod tests {
         assert_eq!(default.pll_n(), 0x63);
         assert_eq!(default.pll_m(), 0x0);
     }
+
+    #[test]
+    fn struct_pll_audio1_control_functions() {
+        let val = PllAudio1Control::default();
+        assert!(!val.is_pll_enabled());
+        assert_eq!(val.pll_n(), 0x2A);
+
+        let val = PllAudio1Control(0x0)
+            .enable_pll()
+            .enable_pll_ldo()
+            .enable_lock()
+            .unmask_pll_output()
+            .set_pll_p1(4)
+            .set_pll_p0(1)
+            .set_pll_n(127)
+            .set_pll_m(0);
+        assert_eq!(val.0, 0xE841_7F00);
+        assert_eq!(val.pll_p1(), 4);
+        assert_eq!(val.pll_p0(), 1);
+        assert!(!val.is_locked());
+        assert!(PllAudio1Control(0x1000_0000).is_locked());
+    }
+
+    #[test]
+    fn struct_pll_audio0_control_functions() {
+        let val = PllAudio0Control::default();
+        assert!(!val.is_pll_enabled());
+        assert_eq!(val.pll_n(), 0x55);
+        assert_eq!(val.pll_p(), 0x14);
+
+        let val = PllAudio0Control(0x0)
+            .enable_pll()
+            .enable_pll_ldo()
+            .enable_lock()
+            .unmask_pll_output()
+            .enable_sdm()
+            .set_pll_p(5)
+            .set_pll_n(21)
+            .set_pll_m1(1)
+            .set_pll_m0(1);
+        assert_eq!(val.0, 0xE905_1503);
+        assert!(val.is_sdm_enabled());
+        assert_eq!(val.pll_p(), 5);
+        assert_eq!(val.pll_n(), 21);
+        assert_eq!(val.pll_m1(), 1);
+        assert_eq!(val.pll_m0(), 1);
+        let val = val.disable_sdm().set_pll_m1(0).set_pll_m0(0);
+        assert_eq!(val.0, 0xE805_1500);
+        assert!(PllAudio0Control(0x1000_0000).is_locked());
+    }
+
+    #[test]
+    fn struct_pll_audio0_pattern_functions() {
+        let val = PllAudio0Pattern::default()
+            .enable_pattern()
+            .set_spread_mode(2)
+            .set_wave_bottom(0x1288D);
+        assert!(val.is_pattern_enabled());
+        assert_eq!(val.spread_mode(), 2);
+        assert_eq!(val.wave_bottom(), 0x1288D);
+        assert_eq!(val.0, 0xC001_288D);
+        assert_eq!(val.disable_pattern().0, 0x4001_288D);
+    }
+
+    #[test]
+    fn struct_pll_video0_control_functions() {
+        let val = PllVideo0Control::default();
+        assert!(!val.is_pll_enabled());
+        assert!(val.is_pll_ldo_enabled());
+        assert_eq!(val.pll_n(), 0x23);
+
+        let val = PllVideo0Control(0x0)
+            .enable_pll()
+            .enable_pll_ldo()
+            .enable_lock()
+            .unmask_pll_output()
+            .set_pll_n(98)
+            .set_pll_m(1);
+        assert_eq!(val.0, 0xE800_6202);
+        assert_eq!(val.pll_n(), 98);
+        assert_eq!(val.pll_m(), 1);
+        assert!(!val.is_locked());
+        assert!(PllVideo0Control(0x1000_0000).is_locked());
+    }
 }
@@ -59,3 +59,111 @@ pub enum SmhcClockSource {
     /// Audio PLL 1 (divided by 2).
     PllAudio1Div2 = 4,
 }
+
+/// LEDC clock source.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum LedcClockSource {
+    /// 24-MHz 'HOSC' external oscillator.
+    Hosc = 0,
+    /// Peripheral PLL (1x frequency).
+    PllPeri1x = 1,
+}
+
+/// Audio codec clock source.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum AudioCodecClockSource {
+    /// Audio PLL 0 (1x frequency).
+    PllAudio0 = 0,
+    /// Audio PLL 1 (divided by 2).
+    PllAudio1Div2 = 1,
+    /// Audio PLL 1 (divided by 5).
+    PllAudio1Div5 = 2,
+}
+
+/// Display engine clock source.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum DeClockSource {
+    /// Peripheral PLL (2x frequency).
+    PllPeri2x = 0,
+    /// Video PLL 0 (4x frequency).
+    PllVideo0x4 = 1,
+    /// Video PLL 1 (4x frequency).
+    PllVideo1x4 = 2,
+    /// Audio PLL 1 (divided by 2).
+    PllAudio1Div2 = 3,
+}
+
+/// DSP clock source.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum DspClockSource {
+    /// 24-MHz 'HOSC' external oscillator.
+    Hosc = 0,
+    /// 32-KHz clock.
+    Clk32K = 1,
+    /// 16-MHz RC oscillator.
+    Clk16MRC = 2,
+    /// Peripheral PLL (2x frequency).
+    PllPeri2x = 3,
+    /// Audio PLL 1 (divided by 2).
+    PllAudio1Div2 = 4,
+}
+
+/// RISC-V core clock source.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum RiscvClockSource {
+    /// 24-MHz 'HOSC' external oscillator.
+    Hosc = 0,
+    /// 32-KHz clock.
+    Clk32K = 1,
+    /// 16-MHz RC oscillator.
+    Clk16MRC = 2,
+    /// Peripheral PLL (800-MHz).
+    PllPeri800M = 3,
+    /// Peripheral PLL (1x frequency).
+    PllPeri1x = 4,
+    /// CPU PLL.
+    PllCpu = 5,
+    /// Audio PLL 1 (divided by 2).
+    PllAudio1Div2 = 6,
+}
+
+/// 2D graphics accelerator clock source.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum G2dClockSource {
+    /// Peripheral PLL (2x frequency).
+    PllPeri2x = 0,
+    /// Video PLL 0 (4x frequency).
+    PllVideo0x4 = 1,
+    /// Video PLL 1 (4x frequency).
+    PllVideo1x4 = 2,
+    /// Audio PLL 1 (divided by 2).
+    PllAudio1Div2 = 3,
+}
+
+/// Crypto engine clock source.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum CeClockSource {
+    /// 24-MHz 'HOSC' external oscillator.
+    Hosc = 0,
+    /// Peripheral PLL (2x frequency).
+    PllPeri2x = 1,
+    /// Peripheral PLL (1x frequency).
+    PllPeri1x = 2,
+}
+
+/// TCON LCD clock source.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum TconLcdClockSource {
+    /// Video PLL 0 (1x frequency).
+    PllVideo0 = 0,
+    /// Video PLL 0 (4x frequency).
+    PllVideo0x4 = 1,
+    /// Video PLL 1 (1x frequency).
+    PllVideo1 = 2,
+    /// Video PLL 1 (4x frequency).
+    PllVideo1x4 = 3,
+    /// Peripheral PLL (2x frequency).
+    PllPeri2x = 4,
+    /// Audio PLL 1 (divided by 2).
+    PllAudio1Div2 = 5,
+}
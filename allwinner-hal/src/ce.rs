@@ -0,0 +1,496 @@
+//! Crypto Engine.
+//!
+//! Crypto engine runs cryptographic tasks described in memory, reading input and writing output
+//! buffers by DMA. [`Ce`] encrypts and decrypts with AES-128, AES-192 and AES-256 in ECB, CBC
+//! and CTR modes:
+//!
+//! ```ignore
+//! let mut ce = Ce::new(p.ce, &p.ccu);
+//! let key = AesKey::Aes128([0x2B; 16]);
+//! let mut mode = AesMode::Cbc { iv: [0; 16] };
+//! ce.encrypt(&key, &mut mode, &plaintext, &mut ciphertext)?;
+//! // `mode` now holds the IV chaining to the next call
+//! ```
+//!
+//! Data length must be a multiple of the 16-byte AES block. Chaining state in [`AesMode`] is
+//! updated after each call, so that a long message can be processed in pieces.
+//!
+//! [`AesKey::Hardware`] references a key held inside the crypto engine, such as the secure
+//! storage key burned in eFuse, so that no key bytes pass through DRAM. Keys stored at rest
+//! can be wrapped by encrypting them with such a key:
+//!
+//! ```ignore
+//! let ssk = AesKey::Hardware { slot: KeySlot::Ssk, size: AesKeySize::Bits128 };
+//! ce.encrypt(&ssk, &mut AesMode::Ecb, &data_key, &mut wrapped_key)?;
+//! ```
+//!
+//! [`Ce::hash_update`] and [`Ce::hash_finalize`] compute MD5, SHA-1, SHA-224, SHA-256, SHA-384
+//! and SHA-512 digests of messages fed in pieces, keeping partial blocks in a [`HashState`]:
+//!
+//! ```ignore
+//! let mut state = HashState::new(HashAlgorithm::Sha256);
+//! ce.hash_update(&mut state, header)?;
+//! ce.hash_update(&mut state, payload)?;
+//! let mut digest = [0; 32];
+//! ce.hash_finalize(&mut state, &mut digest)?;
+//! ```
+//!
+//! [`Trng`] reads the true random number generator, checking its output with continuous
+//! health tests, and fills seeds of software random number generators.
+//!
+//! RSA modular exponentiation of 2048 and 4096-bit keys and NIST P-256 point operations run
+//! on the crypto engine as well, verifying signed images with [`Ce::verify_rsa_pkcs1`] and
+//! [`Ce::verify_ecdsa_p256`]:
+//!
+//! ```ignore
+//! let mut digest = [0; 32];
+//! ce.hash(HashAlgorithm::Sha256, image, &mut digest)?;
+//! ce.verify_ecdsa_p256(&public_key, &digest, &EcdsaSignature::from_bytes(&signature))?;
+//! ```
+//!
+//! With `cipher` feature, `block_cipher::AesCipher` implements RustCrypto block cipher traits
+//! over ECB tasks, so that `cbc`, `ctr` and other mode crates run on the crypto engine. With
+//! `digest` feature, `message_digest` implements RustCrypto hash traits over hash tasks; with
+//! `rand_core` feature, [`Trng`] implements `rand_core::RngCore` and `rand_core::CryptoRng`.
+//!
+//! Buffers are accessed by DMA, and should reside in DRAM.
+
+#[cfg(feature = "cipher")]
+pub mod block_cipher;
+mod descriptor;
+mod hash;
+#[cfg(feature = "digest")]
+pub mod message_digest;
+mod public_key;
+mod register;
+mod trng;
+pub use descriptor::*;
+pub use hash::*;
+pub use public_key::*;
+pub use register::*;
+pub use trng::*;
+
+use crate::{
+    ccu::{self, CeClockSource, ClockConfig, ClockGate, PeriFactorN},
+    time::Deadline,
+};
+use core::sync::atomic::{Ordering, fence};
+
+/// AES block size in bytes.
+pub const AES_BLOCK_SIZE: usize = 16;
+
+/// AES key.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum AesKey {
+    /// 128-bit key.
+    Aes128([u8; 16]),
+    /// 192-bit key.
+    Aes192([u8; 24]),
+    /// 256-bit key.
+    Aes256([u8; 32]),
+    /// Key of `size` held in crypto engine `slot`, never exposed to memory.
+    Hardware {
+        /// Key slot.
+        slot: KeySlot,
+        /// Key length.
+        size: AesKeySize,
+    },
+}
+
+impl AesKey {
+    /// Get key length.
+    #[inline]
+    pub const fn size(&self) -> AesKeySize {
+        match self {
+            AesKey::Aes128(_) => AesKeySize::Bits128,
+            AesKey::Aes192(_) => AesKeySize::Bits192,
+            AesKey::Aes256(_) => AesKeySize::Bits256,
+            AesKey::Hardware { size, .. } => *size,
+        }
+    }
+    /// Get key bytes, which are empty for a hardware key.
+    #[inline]
+    pub const fn as_bytes(&self) -> &[u8] {
+        match self {
+            AesKey::Aes128(key) => key,
+            AesKey::Aes192(key) => key,
+            AesKey::Aes256(key) => key,
+            AesKey::Hardware { .. } => &[],
+        }
+    }
+}
+
+/// AES mode of operation with its chaining state.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum AesMode {
+    /// Electronic codebook, each block ciphered alone.
+    Ecb,
+    /// Cipher block chaining.
+    Cbc {
+        /// Initialization vector, replaced by the last ciphertext block after each call.
+        iv: [u8; 16],
+    },
+    /// Counter mode.
+    Ctr {
+        /// Big-endian 128-bit counter block, advanced by the number of blocks after each call.
+        counter: [u8; 16],
+    },
+}
+
+impl AesMode {
+    /// Get block cipher mode.
+    #[inline]
+    pub const fn cipher_mode(&self) -> CipherMode {
+        match self {
+            AesMode::Ecb => CipherMode::Ecb,
+            AesMode::Cbc { .. } => CipherMode::Cbc,
+            AesMode::Ctr { .. } => CipherMode::Ctr,
+        }
+    }
+    /// Vector read by the task, or zeros for ECB mode.
+    #[inline]
+    const fn vector(&self) -> [u8; 16] {
+        match *self {
+            AesMode::Ecb => [0; 16],
+            AesMode::Cbc { iv } => iv,
+            AesMode::Ctr { counter } => counter,
+        }
+    }
+    /// Chain to the next call after `blocks` blocks, ending with `last_ciphertext`.
+    #[inline]
+    fn advance(&mut self, blocks: usize, last_ciphertext: [u8; 16]) {
+        match self {
+            AesMode::Ecb => {}
+            AesMode::Cbc { iv } => *iv = last_ciphertext,
+            AesMode::Ctr { counter } => {
+                let val = u128::from_be_bytes(*counter).wrapping_add(blocks as u128);
+                *counter = val.to_be_bytes();
+            }
+        }
+    }
+}
+
+/// Crypto engine error.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Error {
+    /// Crypto engine does not support task algorithm.
+    UnsupportedAlgorithm,
+    /// Data length is not a multiple of block size, or input and output lengths differ.
+    InvalidLength,
+    /// Crypto engine failed to load the key.
+    KeyError,
+    /// Task did not finish before crate-wide blocking timeout.
+    Timeout,
+    /// Random number generator failed its entropy health test.
+    HealthTest(HealthTestFailure),
+    /// Signature does not match the message and public key.
+    InvalidSignature,
+}
+
+/// Task descriptor with the key and vectors it refers to.
+#[repr(C, align(32))]
+struct AesTask {
+    descriptor: TaskDescriptor,
+    key: [u8; 32],
+    iv: [u8; 16],
+    counter: [u8; 16],
+}
+
+/// Managed crypto engine structure with peripheral.
+///
+/// Clock control unit is kept to reset the engine if a task doesn't finish in time.
+pub struct Ce<'a, CE> {
+    ce: CE,
+    ccu: &'a ccu::RegisterBlock,
+}
+
+impl<'a, CE: AsRef<RegisterBlock>> Ce<'a, CE> {
+    /// Create an idle crypto engine clocked at 300 MHz.
+    #[inline]
+    pub fn new(ce: CE, ccu: &'a ccu::RegisterBlock) -> Self {
+        // note(unsafe): async read and write using ccu registers
+        unsafe { ccu::CE::reconfigure(ccu, CeClockSource::PllPeri2x, 3, PeriFactorN::N1) };
+        let regs = ce.as_ref();
+        unsafe {
+            regs.interrupt_enable
+                .write(InterruptEnable::default().enable_interrupt::<0>());
+            regs.interrupt_status
+                .write(InterruptStatus::default().clear_finished::<0>());
+        }
+        Self { ce, ccu }
+    }
+    /// Encrypt `input` into `output` with `key` in `mode`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidLength`] if data length is not a multiple of
+    /// [`AES_BLOCK_SIZE`], or if `input` and `output` differ in length.
+    #[inline]
+    pub fn encrypt(
+        &mut self,
+        key: &AesKey,
+        mode: &mut AesMode,
+        input: &[u8],
+        output: &mut [u8],
+    ) -> Result<(), Error> {
+        if input.len() != output.len() {
+            return Err(Error::InvalidLength);
+        }
+        // note(unsafe): input and output are valid and distinct buffers of the same length
+        unsafe {
+            self.aes(
+                key,
+                mode,
+                Direction::Encrypt,
+                input.as_ptr(),
+                output.as_mut_ptr(),
+                input.len(),
+            )
+        }
+    }
+    /// Decrypt `input` into `output` with `key` in `mode`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidLength`] if data length is not a multiple of
+    /// [`AES_BLOCK_SIZE`], or if `input` and `output` differ in length.
+    #[inline]
+    pub fn decrypt(
+        &mut self,
+        key: &AesKey,
+        mode: &mut AesMode,
+        input: &[u8],
+        output: &mut [u8],
+    ) -> Result<(), Error> {
+        if input.len() != output.len() {
+            return Err(Error::InvalidLength);
+        }
+        // note(unsafe): input and output are valid and distinct buffers of the same length
+        unsafe {
+            self.aes(
+                key,
+                mode,
+                Direction::Decrypt,
+                input.as_ptr(),
+                output.as_mut_ptr(),
+                input.len(),
+            )
+        }
+    }
+    /// Encrypt `data` in place with `key` in `mode`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidLength`] if data length is not a multiple of
+    /// [`AES_BLOCK_SIZE`].
+    #[inline]
+    pub fn encrypt_in_place(
+        &mut self,
+        key: &AesKey,
+        mode: &mut AesMode,
+        data: &mut [u8],
+    ) -> Result<(), Error> {
+        let len = data.len();
+        let ptr = data.as_mut_ptr();
+        // note(unsafe): crypto engine reads each block before writing it back
+        unsafe { self.aes(key, mode, Direction::Encrypt, ptr, ptr, len) }
+    }
+    /// Decrypt `data` in place with `key` in `mode`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidLength`] if data length is not a multiple of
+    /// [`AES_BLOCK_SIZE`].
+    #[inline]
+    pub fn decrypt_in_place(
+        &mut self,
+        key: &AesKey,
+        mode: &mut AesMode,
+        data: &mut [u8],
+    ) -> Result<(), Error> {
+        let len = data.len();
+        let ptr = data.as_mut_ptr();
+        // note(unsafe): crypto engine reads each block before writing it back
+        unsafe { self.aes(key, mode, Direction::Decrypt, ptr, ptr, len) }
+    }
+    /// Release the crypto engine.
+    #[inline]
+    pub fn free(self) -> CE {
+        let regs = self.ce.as_ref();
+        unsafe {
+            regs.interrupt_enable.write(InterruptEnable::default());
+            regs.interrupt_status
+                .write(InterruptStatus::default().clear_finished::<0>());
+        }
+        // note(unsafe): async read and write using ccu registers
+        unsafe { ccu::CE::free(self.ccu) };
+        self.ce
+    }
+    /// Run an AES task on `len` bytes from `input` into `output`, and chain `mode`.
+    ///
+    /// # Safety
+    ///
+    /// `input` must be valid for reads and `output` valid for writes of `len` bytes. They
+    /// either are the same buffer or don't overlap.
+    #[inline]
+    unsafe fn aes(
+        &mut self,
+        key: &AesKey,
+        mode: &mut AesMode,
+        direction: Direction,
+        input: *const u8,
+        output: *mut u8,
+        len: usize,
+    ) -> Result<(), Error> {
+        if !len.is_multiple_of(AES_BLOCK_SIZE) || len > u32::MAX as usize {
+            return Err(Error::InvalidLength);
+        }
+        if len == 0 {
+            return Ok(());
+        }
+        let last = len - AES_BLOCK_SIZE;
+        let mut last_ciphertext = [0; AES_BLOCK_SIZE];
+        // ciphertext is overwritten when decrypting in place, keep its last block beforehand
+        if direction == Direction::Decrypt {
+            unsafe { core::ptr::copy(input.add(last), last_ciphertext.as_mut_ptr(), 16) };
+        }
+        let common = CommonControl::default()
+            .set_algorithm(Algorithm::Aes)
+            .set_direction(direction)
+            .enable_interrupt();
+        let mut symmetric = SymmetricControl::default()
+            .set_key_size(key.size())
+            .set_counter_width(CounterWidth::Bits128)
+            .set_mode(mode.cipher_mode());
+        if let AesKey::Hardware { slot, .. } = *key {
+            if matches!(slot, KeySlot::Internal(8..)) {
+                return Err(Error::KeyError);
+            }
+            symmetric = symmetric.set_key_slot(slot);
+        }
+        let mut task = AesTask {
+            descriptor: TaskDescriptor::new(common, symmetric),
+            key: [0; 32],
+            iv: mode.vector(),
+            counter: [0; 16],
+        };
+        let key = key.as_bytes();
+        task.key[..key.len()].copy_from_slice(key);
+        task.descriptor.key_address = task.key.as_ptr() as usize as u32;
+        task.descriptor.iv_address = task.iv.as_ptr() as usize as u32;
+        task.descriptor.counter_address = task.counter.as_mut_ptr() as usize as u32;
+        task.descriptor
+            .set_buffers(input as usize as u32, output as usize as u32, len as u32);
+        let result = self.run(&task.descriptor);
+        // key must not remain in memory
+        task.key.fill(0);
+        result?;
+        if direction == Direction::Encrypt {
+            unsafe { core::ptr::copy(output.add(last), last_ciphertext.as_mut_ptr(), 16) };
+        }
+        mode.advance(len / AES_BLOCK_SIZE, last_ciphertext);
+        Ok(())
+    }
+    /// Run `task` on flow 0 and wait until it finishes.
+    #[inline]
+    fn run(&mut self, task: &TaskDescriptor) -> Result<(), Error> {
+        let regs = self.ce.as_ref();
+        unsafe {
+            regs.interrupt_status
+                .write(InterruptStatus::default().clear_finished::<0>());
+            regs.task_queue.write(task as *const _ as usize as u32);
+        }
+        fence(Ordering::SeqCst);
+        unsafe { regs.task_load.write(TaskLoad::default().load()) };
+        let deadline = Deadline::start();
+        while !regs.interrupt_status.read().is_finished::<0>() {
+            if deadline.check().is_err() {
+                self.abort();
+                return Err(Error::Timeout);
+            }
+            core::hint::spin_loop();
+        }
+        // finished flag is cleared by writing one, so status register is written afresh
+        unsafe {
+            regs.interrupt_status
+                .write(InterruptStatus::default().clear_finished::<0>())
+        };
+        fence(Ordering::SeqCst);
+        let error = regs.error_status.read();
+        if error.is_algorithm_unsupported::<0>() {
+            Err(Error::UnsupportedAlgorithm)
+        } else if error.is_data_length_invalid::<0>() {
+            Err(Error::InvalidLength)
+        } else if error.is_key_sram_error::<0>() {
+            Err(Error::KeyError)
+        } else {
+            Ok(())
+        }
+    }
+    /// Stop the running task by resetting the crypto engine.
+    ///
+    /// Task descriptor and buffers may live on caller's stack; the engine no longer accesses
+    /// them once reset, and it's left idle as after [`Ce::new`].
+    #[inline]
+    fn abort(&mut self) {
+        // note(unsafe): reset masks memory bus access of the engine before asserting reset
+        unsafe { ccu::CE::reset(self.ccu) };
+        fence(Ordering::SeqCst);
+        let regs = self.ce.as_ref();
+        unsafe {
+            regs.interrupt_enable
+                .write(InterruptEnable::default().enable_interrupt::<0>());
+            regs.interrupt_status
+                .write(InterruptStatus::default().clear_finished::<0>());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{AesKey, AesKeySize, AesMode, CipherMode, KeySlot};
+
+    #[test]
+    fn struct_aes_key_functions() {
+        let key = AesKey::Aes192([0x5A; 24]);
+        assert_eq!(key.size(), AesKeySize::Bits192);
+        assert_eq!(key.as_bytes(), &[0x5A; 24]);
+        assert_eq!(AesKey::Aes128([0; 16]).size(), AesKeySize::Bits128);
+        assert_eq!(AesKey::Aes256([0; 32]).as_bytes().len(), 32);
+        let key = AesKey::Hardware {
+            slot: KeySlot::Ssk,
+            size: AesKeySize::Bits128,
+        };
+        assert_eq!(key.size(), AesKeySize::Bits128);
+        assert_eq!(key.as_bytes(), &[]);
+    }
+
+    #[test]
+    fn struct_aes_mode_functions() {
+        let mut mode = AesMode::Ecb;
+        mode.advance(4, [0xFF; 16]);
+        assert_eq!(mode, AesMode::Ecb);
+        assert_eq!(mode.vector(), [0; 16]);
+
+        let mut mode = AesMode::Cbc { iv: [0; 16] };
+        assert_eq!(mode.cipher_mode(), CipherMode::Cbc);
+        mode.advance(2, [0x11; 16]);
+        assert_eq!(mode, AesMode::Cbc { iv: [0x11; 16] });
+
+        let mut counter = [0; 16];
+        counter[15] = 0xFE;
+        let mut mode = AesMode::Ctr { counter };
+        assert_eq!(mode.vector(), counter);
+        mode.advance(3, [0; 16]);
+        let mut expected = [0; 16];
+        expected[14] = 0x01;
+        expected[15] = 0x01;
+        assert_eq!(mode, AesMode::Ctr { counter: expected });
+
+        let mut mode = AesMode::Ctr {
+            counter: [0xFF; 16],
+        };
+        mode.advance(1, [0; 16]);
+        assert_eq!(mode, AesMode::Ctr { counter: [0; 16] });
+    }
+}
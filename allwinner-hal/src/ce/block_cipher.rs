@@ -0,0 +1,132 @@
+//! RustCrypto block cipher traits on crypto engine AES.
+//!
+//! [`AesCipher`] borrows a [`Ce`] with an [`AesKey`] and implements [`BlockEncrypt`] and
+//! [`BlockDecrypt`], running each batch of blocks as one ECB task:
+//!
+//! ```ignore
+//! let aes = AesCipher::new(&mut ce, AesKey::Aes256(key));
+//! let mut block = Block::<AesCipher<_>>::from(plaintext);
+//! aes.encrypt_block(&mut block);
+//! ```
+//!
+//! Trait methods cannot return errors; they panic if the crypto engine fails.
+
+use super::{AES_BLOCK_SIZE, AesKey, AesMode, Ce, Direction, RegisterBlock};
+use cipher::{
+    Block, BlockBackend, BlockCipher, BlockClosure, BlockDecrypt, BlockEncrypt, BlockSizeUser,
+    ParBlocks, ParBlocksSizeUser,
+    consts::U16,
+    inout::{InOut, InOutBuf},
+};
+use core::cell::RefCell;
+
+/// AES block cipher running on crypto engine.
+pub struct AesCipher<'a, 'b, CE> {
+    ce: RefCell<&'a mut Ce<'b, CE>>,
+    key: AesKey,
+}
+
+impl<'a, 'b, CE: AsRef<RegisterBlock>> AesCipher<'a, 'b, CE> {
+    /// Create a block cipher with `key` on crypto engine.
+    #[inline]
+    pub fn new(ce: &'a mut Ce<'b, CE>, key: AesKey) -> Self {
+        Self {
+            ce: RefCell::new(ce),
+            key,
+        }
+    }
+    /// Release the crypto engine.
+    #[inline]
+    pub fn free(self) -> &'a mut Ce<'b, CE> {
+        self.ce.into_inner()
+    }
+}
+
+impl<CE> BlockSizeUser for AesCipher<'_, '_, CE> {
+    type BlockSize = U16;
+}
+
+impl<CE> BlockCipher for AesCipher<'_, '_, CE> {}
+
+impl<CE: AsRef<RegisterBlock>> BlockEncrypt for AesCipher<'_, '_, CE> {
+    #[inline]
+    fn encrypt_with_backend(&self, f: impl BlockClosure<BlockSize = Self::BlockSize>) {
+        let mut ce = self.ce.borrow_mut();
+        f.call(&mut Backend {
+            ce: &mut **ce,
+            key: &self.key,
+            direction: Direction::Encrypt,
+        });
+    }
+}
+
+impl<CE: AsRef<RegisterBlock>> BlockDecrypt for AesCipher<'_, '_, CE> {
+    #[inline]
+    fn decrypt_with_backend(&self, f: impl BlockClosure<BlockSize = Self::BlockSize>) {
+        let mut ce = self.ce.borrow_mut();
+        f.call(&mut Backend {
+            ce: &mut **ce,
+            key: &self.key,
+            direction: Direction::Decrypt,
+        });
+    }
+}
+
+/// Backend running blocks of one direction.
+struct Backend<'a, 'b, CE> {
+    ce: &'a mut Ce<'b, CE>,
+    key: &'a AesKey,
+    direction: Direction,
+}
+
+impl<CE> BlockSizeUser for Backend<'_, '_, CE> {
+    type BlockSize = U16;
+}
+
+impl<CE> ParBlocksSizeUser for Backend<'_, '_, CE> {
+    type ParBlocksSize = U16;
+}
+
+impl<CE: AsRef<RegisterBlock>> Backend<'_, '_, CE> {
+    /// Run an ECB task on `len` bytes from `input` into `output`.
+    ///
+    /// # Safety
+    ///
+    /// Same as [`Ce::aes`].
+    #[inline]
+    unsafe fn process(&mut self, input: *const u8, output: *mut u8, len: usize) {
+        let result = unsafe {
+            self.ce.aes(
+                self.key,
+                &mut AesMode::Ecb,
+                self.direction,
+                input,
+                output,
+                len,
+            )
+        };
+        if let Err(e) = result {
+            panic!("crypto engine AES task failed: {:?}", e);
+        }
+    }
+}
+
+impl<CE: AsRef<RegisterBlock>> BlockBackend for Backend<'_, '_, CE> {
+    #[inline]
+    fn proc_block(&mut self, block: InOut<'_, '_, Block<Self>>) {
+        let (input, output) = block.into_raw();
+        // note(unsafe): `InOut` blocks are the same buffer or don't overlap
+        unsafe { self.process(input.cast(), output.cast(), AES_BLOCK_SIZE) };
+    }
+    #[inline]
+    fn proc_par_blocks(&mut self, blocks: InOut<'_, '_, ParBlocks<Self>>) {
+        self.proc_tail_blocks(blocks.into_buf());
+    }
+    #[inline]
+    fn proc_tail_blocks(&mut self, blocks: InOutBuf<'_, '_, Block<Self>>) {
+        let len = blocks.len() * AES_BLOCK_SIZE;
+        let (input, output) = blocks.into_raw();
+        // note(unsafe): `InOutBuf` buffers are the same or don't overlap
+        unsafe { self.process(input.cast(), output.cast(), len) };
+    }
+}
@@ -0,0 +1,134 @@
+use super::register::{AsymmetricControl, CommonControl, SymmetricControl};
+
+/// Number of scatter-gather buffers of each direction in a task.
+pub const SG_COUNT: usize = 8;
+
+/// Contiguous buffer of task input or output.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[repr(C)]
+pub struct ScatterGather {
+    /// Buffer address.
+    pub address: u32,
+    /// Buffer length in 32-bit words.
+    pub length: u32,
+}
+
+/// Crypto engine task descriptor.
+///
+/// Crypto engine reads descriptors, keys and vectors from memory, they should stay valid until
+/// the task finishes.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(C, align(4))]
+pub struct TaskDescriptor {
+    /// Flow number running this task.
+    pub id: u32,
+    /// Task common control.
+    pub common_control: CommonControl,
+    /// Task symmetric control.
+    pub symmetric_control: SymmetricControl,
+    /// Task asymmetric control.
+    pub asymmetric_control: AsymmetricControl,
+    /// Key address.
+    pub key_address: u32,
+    /// Initialization vector address.
+    pub iv_address: u32,
+    /// Address where counter of CTR mode is stored after the task.
+    pub counter_address: u32,
+    /// Total data length in 32-bit words.
+    pub data_length: u32,
+    /// Input buffers, ending at the first empty one.
+    pub source: [ScatterGather; SG_COUNT],
+    /// Output buffers, ending at the first empty one.
+    pub destination: [ScatterGather; SG_COUNT],
+    /// Address of the next descriptor, or zero.
+    pub next: u32,
+    _reserved0: [u32; 3],
+}
+
+impl TaskDescriptor {
+    /// Create a task of flow 0 without buffers.
+    #[inline]
+    pub fn new(common_control: CommonControl, symmetric_control: SymmetricControl) -> Self {
+        Self {
+            id: 0,
+            common_control,
+            symmetric_control,
+            asymmetric_control: AsymmetricControl::default(),
+            key_address: 0,
+            iv_address: 0,
+            counter_address: 0,
+            data_length: 0,
+            source: [ScatterGather {
+                address: 0,
+                length: 0,
+            }; SG_COUNT],
+            destination: [ScatterGather {
+                address: 0,
+                length: 0,
+            }; SG_COUNT],
+            next: 0,
+            _reserved0: [0; 3],
+        }
+    }
+    /// Process `byte_count` bytes from `source` into `destination`, as one buffer each.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `byte_count` is not a multiple of four.
+    #[inline]
+    pub fn set_buffers(&mut self, source: u32, destination: u32, byte_count: u32) {
+        assert!(
+            byte_count.is_multiple_of(4),
+            "task data not made of whole words"
+        );
+        let length = byte_count / 4;
+        self.source[0] = ScatterGather {
+            address: source,
+            length,
+        };
+        self.destination[0] = ScatterGather {
+            address: destination,
+            length,
+        };
+        self.data_length = length;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ScatterGather, TaskDescriptor};
+    use crate::ce::{CommonControl, SymmetricControl};
+    use core::mem::{offset_of, size_of};
+
+    #[test]
+    fn offset_task_descriptor() {
+        assert_eq!(offset_of!(TaskDescriptor, id), 0x00);
+        assert_eq!(offset_of!(TaskDescriptor, common_control), 0x04);
+        assert_eq!(offset_of!(TaskDescriptor, symmetric_control), 0x08);
+        assert_eq!(offset_of!(TaskDescriptor, asymmetric_control), 0x0C);
+        assert_eq!(offset_of!(TaskDescriptor, key_address), 0x10);
+        assert_eq!(offset_of!(TaskDescriptor, iv_address), 0x14);
+        assert_eq!(offset_of!(TaskDescriptor, counter_address), 0x18);
+        assert_eq!(offset_of!(TaskDescriptor, data_length), 0x1C);
+        assert_eq!(offset_of!(TaskDescriptor, source), 0x20);
+        assert_eq!(offset_of!(TaskDescriptor, destination), 0x60);
+        assert_eq!(offset_of!(TaskDescriptor, next), 0xA0);
+        assert_eq!(size_of::<TaskDescriptor>(), 0xB0);
+    }
+
+    #[test]
+    fn struct_task_descriptor_functions() {
+        let mut task = TaskDescriptor::new(CommonControl::default(), SymmetricControl::default());
+        task.set_buffers(0x4000_0000, 0x4100_0000, 64);
+        assert_eq!(task.data_length, 16);
+        assert_eq!(
+            task.source[0],
+            ScatterGather {
+                address: 0x4000_0000,
+                length: 16
+            }
+        );
+        assert_eq!(task.destination[0].address, 0x4100_0000);
+        assert_eq!(task.source[1], ScatterGather::default());
+    }
+}
@@ -0,0 +1,426 @@
+use super::{Algorithm, Ce, CommonControl, Error, RegisterBlock, SymmetricControl, TaskDescriptor};
+
+/// Largest hash block size in bytes.
+const MAX_BLOCK_SIZE: usize = 128;
+
+/// Largest hash state size in bytes.
+const MAX_STATE_SIZE: usize = 64;
+
+/// Hash algorithm run by crypto engine.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum HashAlgorithm {
+    /// MD5 message digest, 16-byte output.
+    Md5,
+    /// SHA-1 secure hash, 20-byte output.
+    Sha1,
+    /// SHA-224 secure hash, 28-byte output.
+    Sha224,
+    /// SHA-256 secure hash, 32-byte output.
+    Sha256,
+    /// SHA-384 secure hash, 48-byte output.
+    Sha384,
+    /// SHA-512 secure hash, 64-byte output.
+    Sha512,
+}
+
+impl HashAlgorithm {
+    /// Get digest size in bytes.
+    #[inline]
+    pub const fn output_size(self) -> usize {
+        match self {
+            HashAlgorithm::Md5 => 16,
+            HashAlgorithm::Sha1 => 20,
+            HashAlgorithm::Sha224 => 28,
+            HashAlgorithm::Sha256 => 32,
+            HashAlgorithm::Sha384 => 48,
+            HashAlgorithm::Sha512 => 64,
+        }
+    }
+    /// Get block size in bytes.
+    #[inline]
+    pub const fn block_size(self) -> usize {
+        match self {
+            HashAlgorithm::Sha384 | HashAlgorithm::Sha512 => 128,
+            _ => 64,
+        }
+    }
+    /// Task algorithm of this hash.
+    #[inline]
+    const fn algorithm(self) -> Algorithm {
+        match self {
+            HashAlgorithm::Md5 => Algorithm::Md5,
+            HashAlgorithm::Sha1 => Algorithm::Sha1,
+            HashAlgorithm::Sha224 => Algorithm::Sha224,
+            HashAlgorithm::Sha256 => Algorithm::Sha256,
+            HashAlgorithm::Sha384 => Algorithm::Sha384,
+            HashAlgorithm::Sha512 => Algorithm::Sha512,
+        }
+    }
+    /// Size of intermediate state in bytes.
+    #[inline]
+    const fn state_size(self) -> usize {
+        match self {
+            HashAlgorithm::Md5 => 16,
+            HashAlgorithm::Sha1 => 20,
+            HashAlgorithm::Sha224 | HashAlgorithm::Sha256 => 32,
+            HashAlgorithm::Sha384 | HashAlgorithm::Sha512 => 64,
+        }
+    }
+    /// Size of message length field in padding.
+    #[inline]
+    const fn length_size(self) -> usize {
+        match self {
+            HashAlgorithm::Sha384 | HashAlgorithm::Sha512 => 16,
+            _ => 8,
+        }
+    }
+    /// Standard initial state, in the byte order of digest output.
+    const fn initial_state(self) -> [u8; MAX_STATE_SIZE] {
+        const MD5: [u32; 4] = [0x6745_2301, 0xEFCD_AB89, 0x98BA_DCFE, 0x1032_5476];
+        const SHA1: [u32; 5] = [
+            0x6745_2301,
+            0xEFCD_AB89,
+            0x98BA_DCFE,
+            0x1032_5476,
+            0xC3D2_E1F0,
+        ];
+        const SHA224: [u32; 8] = [
+            0xC105_9ED8,
+            0x367C_D507,
+            0x3070_DD17,
+            0xF70E_5939,
+            0xFFC0_0B31,
+            0x6858_1511,
+            0x64F9_8FA7,
+            0xBEFA_4FA4,
+        ];
+        const SHA256: [u32; 8] = [
+            0x6A09_E667,
+            0xBB67_AE85,
+            0x3C6E_F372,
+            0xA54F_F53A,
+            0x510E_527F,
+            0x9B05_688C,
+            0x1F83_D9AB,
+            0x5BE0_CD19,
+        ];
+        const SHA384: [u64; 8] = [
+            0xCBBB_9D5D_C105_9ED8,
+            0x629A_292A_367C_D507,
+            0x9159_015A_3070_DD17,
+            0x152F_ECD8_F70E_5939,
+            0x6733_2667_FFC0_0B31,
+            0x8EB4_4A87_6858_1511,
+            0xDB0C_2E0D_64F9_8FA7,
+            0x47B5_481D_BEFA_4FA4,
+        ];
+        const SHA512: [u64; 8] = [
+            0x6A09_E667_F3BC_C908,
+            0xBB67_AE85_84CA_A73B,
+            0x3C6E_F372_FE94_F82B,
+            0xA54F_F53A_5F1D_36F1,
+            0x510E_527F_ADE6_82D1,
+            0x9B05_688C_2B3E_6C1F,
+            0x1F83_D9AB_FB41_BD6B,
+            0x5BE0_CD19_137E_2179,
+        ];
+        let mut ans = [0; MAX_STATE_SIZE];
+        let mut i = 0;
+        match self {
+            HashAlgorithm::Md5 => {
+                while i < MD5.len() {
+                    let bytes = MD5[i].to_le_bytes();
+                    let mut j = 0;
+                    while j < 4 {
+                        ans[i * 4 + j] = bytes[j];
+                        j += 1;
+                    }
+                    i += 1;
+                }
+            }
+            HashAlgorithm::Sha1 | HashAlgorithm::Sha224 | HashAlgorithm::Sha256 => {
+                let words: &[u32] = match self {
+                    HashAlgorithm::Sha1 => &SHA1,
+                    HashAlgorithm::Sha224 => &SHA224,
+                    _ => &SHA256,
+                };
+                while i < words.len() {
+                    let bytes = words[i].to_be_bytes();
+                    let mut j = 0;
+                    while j < 4 {
+                        ans[i * 4 + j] = bytes[j];
+                        j += 1;
+                    }
+                    i += 1;
+                }
+            }
+            HashAlgorithm::Sha384 | HashAlgorithm::Sha512 => {
+                let words = match self {
+                    HashAlgorithm::Sha384 => &SHA384,
+                    _ => &SHA512,
+                };
+                while i < words.len() {
+                    let bytes = words[i].to_be_bytes();
+                    let mut j = 0;
+                    while j < 8 {
+                        ans[i * 8 + j] = bytes[j];
+                        j += 1;
+                    }
+                    i += 1;
+                }
+            }
+        }
+        ans
+    }
+}
+
+/// Streaming hash state of crypto engine.
+///
+/// Message is fed in pieces of any length with [`Ce::hash_update`], and the digest is read by
+/// [`Ce::hash_finalize`]. Crypto engine processes whole blocks; partial blocks are kept in
+/// this state until more data arrives, and padding is appended by the processor.
+#[derive(Clone, Debug)]
+pub struct HashState {
+    algorithm: HashAlgorithm,
+    state: [u8; MAX_STATE_SIZE],
+    buffer: [u8; MAX_BLOCK_SIZE],
+    buffered: usize,
+    length: u128,
+}
+
+impl HashState {
+    /// Create a hash state of `algorithm` for an empty message.
+    #[inline]
+    pub const fn new(algorithm: HashAlgorithm) -> Self {
+        Self {
+            algorithm,
+            state: algorithm.initial_state(),
+            buffer: [0; MAX_BLOCK_SIZE],
+            buffered: 0,
+            length: 0,
+        }
+    }
+    /// Get hash algorithm.
+    #[inline]
+    pub const fn algorithm(&self) -> HashAlgorithm {
+        self.algorithm
+    }
+    /// Get length of message hashed so far in bytes.
+    #[inline]
+    pub const fn message_length(&self) -> u128 {
+        self.length
+    }
+    /// Restart hashing an empty message.
+    #[inline]
+    pub fn reset(&mut self) {
+        *self = Self::new(self.algorithm);
+    }
+    /// Padded final blocks of message, and their length in bytes.
+    #[inline]
+    fn padding(&self) -> ([u8; 2 * MAX_BLOCK_SIZE], usize) {
+        let block_size = self.algorithm.block_size();
+        let length_size = self.algorithm.length_size();
+        let mut blocks = [0; 2 * MAX_BLOCK_SIZE];
+        blocks[..self.buffered].copy_from_slice(&self.buffer[..self.buffered]);
+        blocks[self.buffered] = 0x80;
+        let len = if self.buffered + 1 + length_size <= block_size {
+            block_size
+        } else {
+            2 * block_size
+        };
+        let bits = self.length.wrapping_mul(8);
+        let field = &mut blocks[len - length_size..len];
+        match self.algorithm {
+            HashAlgorithm::Md5 => field.copy_from_slice(&(bits as u64).to_le_bytes()),
+            HashAlgorithm::Sha384 | HashAlgorithm::Sha512 => {
+                field.copy_from_slice(&bits.to_be_bytes())
+            }
+            _ => field.copy_from_slice(&(bits as u64).to_be_bytes()),
+        }
+        (blocks, len)
+    }
+}
+
+/// Hash task descriptor with the states it refers to.
+#[repr(C, align(32))]
+struct HashTask {
+    descriptor: TaskDescriptor,
+    input_state: [u8; MAX_STATE_SIZE],
+    output_state: [u8; MAX_STATE_SIZE],
+}
+
+impl<CE: AsRef<RegisterBlock>> Ce<'_, CE> {
+    /// Hash `data` as the next piece of message in `state`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if crypto engine fails; `state` is left unchanged.
+    #[inline]
+    pub fn hash_update(&mut self, state: &mut HashState, data: &[u8]) -> Result<(), Error> {
+        let block_size = state.algorithm.block_size();
+        let mut new_state = state.state;
+        let total = data.len();
+        let mut data = data;
+        let mut buffer = state.buffer;
+        let mut buffered = state.buffered;
+        if buffered > 0 {
+            let len = data.len().min(block_size - buffered);
+            buffer[buffered..buffered + len].copy_from_slice(&data[..len]);
+            buffered += len;
+            data = &data[len..];
+            if buffered == block_size {
+                self.hash_blocks(state.algorithm, &mut new_state, &buffer[..block_size])?;
+                buffered = 0;
+            }
+        }
+        let whole = data.len() - data.len() % block_size;
+        if whole > 0 {
+            self.hash_blocks(state.algorithm, &mut new_state, &data[..whole])?;
+            data = &data[whole..];
+        }
+        buffer[buffered..buffered + data.len()].copy_from_slice(data);
+        state.buffered = buffered + data.len();
+        state.buffer = buffer;
+        state.state = new_state;
+        state.length = state.length.wrapping_add(total as u128);
+        Ok(())
+    }
+    /// Finish message in `state` and write its digest to `output`, returning the digest size.
+    ///
+    /// `state` restarts with an empty message afterwards.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if crypto engine fails.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `output` is shorter than digest size of the algorithm.
+    #[inline]
+    pub fn hash_finalize(
+        &mut self,
+        state: &mut HashState,
+        output: &mut [u8],
+    ) -> Result<usize, Error> {
+        let size = state.algorithm.output_size();
+        assert!(output.len() >= size, "hash output buffer too short");
+        let (blocks, len) = state.padding();
+        let mut new_state = state.state;
+        self.hash_blocks(state.algorithm, &mut new_state, &blocks[..len])?;
+        output[..size].copy_from_slice(&new_state[..size]);
+        state.reset();
+        Ok(size)
+    }
+    /// Hash `data` as a whole message and write its digest to `output`, returning the digest
+    /// size.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if crypto engine fails.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `output` is shorter than digest size of the algorithm.
+    #[inline]
+    pub fn hash(
+        &mut self,
+        algorithm: HashAlgorithm,
+        data: &[u8],
+        output: &mut [u8],
+    ) -> Result<usize, Error> {
+        let mut state = HashState::new(algorithm);
+        self.hash_update(&mut state, data)?;
+        self.hash_finalize(&mut state, output)
+    }
+    /// Run a hash task on whole `blocks`, continuing from `state`.
+    #[inline]
+    fn hash_blocks(
+        &mut self,
+        algorithm: HashAlgorithm,
+        state: &mut [u8; MAX_STATE_SIZE],
+        blocks: &[u8],
+    ) -> Result<(), Error> {
+        if blocks.len() > u32::MAX as usize {
+            return Err(Error::InvalidLength);
+        }
+        let common = CommonControl::default()
+            .set_algorithm(algorithm.algorithm())
+            .enable_hash_iv_input()
+            .enable_interrupt();
+        let mut task = HashTask {
+            descriptor: TaskDescriptor::new(common, SymmetricControl::default()),
+            input_state: *state,
+            output_state: [0; MAX_STATE_SIZE],
+        };
+        task.descriptor.iv_address = task.input_state.as_ptr() as usize as u32;
+        task.descriptor.set_buffers(
+            blocks.as_ptr() as usize as u32,
+            task.output_state.as_mut_ptr() as usize as u32,
+            blocks.len() as u32,
+        );
+        // digest output is as long as the state, not the input
+        task.descriptor.destination[0].length = algorithm.state_size() as u32 / 4;
+        self.run(&task.descriptor)?;
+        *state = task.output_state;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{HashAlgorithm, HashState};
+
+    #[test]
+    fn struct_hash_algorithm_functions() {
+        assert_eq!(HashAlgorithm::Md5.output_size(), 16);
+        assert_eq!(HashAlgorithm::Sha224.output_size(), 28);
+        assert_eq!(HashAlgorithm::Sha384.output_size(), 48);
+        assert_eq!(HashAlgorithm::Sha256.block_size(), 64);
+        assert_eq!(HashAlgorithm::Sha512.block_size(), 128);
+
+        let state = HashAlgorithm::Md5.initial_state();
+        assert_eq!(state[..4], [0x01, 0x23, 0x45, 0x67]);
+        let state = HashAlgorithm::Sha1.initial_state();
+        assert_eq!(state[16..20], [0xC3, 0xD2, 0xE1, 0xF0]);
+        let state = HashAlgorithm::Sha256.initial_state();
+        assert_eq!(state[..4], [0x6A, 0x09, 0xE6, 0x67]);
+        assert_eq!(state[28..32], [0x5B, 0xE0, 0xCD, 0x19]);
+        let state = HashAlgorithm::Sha512.initial_state();
+        assert_eq!(
+            state[56..64],
+            [0x5B, 0xE0, 0xCD, 0x19, 0x13, 0x7E, 0x21, 0x79]
+        );
+    }
+
+    #[test]
+    fn struct_hash_state_padding() {
+        let mut state = HashState::new(HashAlgorithm::Sha256);
+        state.buffer[..3].copy_from_slice(b"abc");
+        state.buffered = 3;
+        state.length = 3;
+        let (blocks, len) = state.padding();
+        assert_eq!(len, 64);
+        assert_eq!(blocks[..4], [b'a', b'b', b'c', 0x80]);
+        assert!(blocks[4..63].iter().all(|&b| b == 0));
+        assert_eq!(blocks[63], 24);
+
+        let mut state = HashState::new(HashAlgorithm::Md5);
+        state.buffered = 56;
+        state.length = 0x100 + 56;
+        let (blocks, len) = state.padding();
+        assert_eq!(len, 128);
+        assert_eq!(blocks[56], 0x80);
+        assert_eq!(blocks[120..128], [0xC0, 0x09, 0, 0, 0, 0, 0, 0]);
+
+        let mut state = HashState::new(HashAlgorithm::Sha512);
+        state.buffered = 111;
+        state.length = 111;
+        let (blocks, len) = state.padding();
+        assert_eq!(len, 128);
+        assert_eq!(blocks[111], 0x80);
+        assert_eq!(blocks[126..128], [0x03, 0x78]);
+        state.buffered = 112;
+        let (_, len) = state.padding();
+        assert_eq!(len, 256);
+    }
+}
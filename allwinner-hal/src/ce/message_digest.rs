@@ -0,0 +1,141 @@
+//! RustCrypto hash traits on crypto engine.
+//!
+//! [`Hasher`] borrows a [`Ce`] and implements [`Update`], [`FixedOutput`],
+//! [`FixedOutputReset`] and [`Reset`] for the algorithm given by its type parameter:
+//!
+//! ```ignore
+//! let mut hasher = Hasher::<_, Sha256>::new(&mut ce);
+//! hasher.update(image);
+//! let digest = hasher.finalize_fixed();
+//! ```
+//!
+//! A hasher cannot be created by `Default`, as it needs the crypto engine; functions generic
+//! over `Update + FixedOutput` accept it in place of a software [`digest::Digest`].
+//!
+//! Trait methods cannot return errors; they panic if the crypto engine fails.
+
+use super::{Ce, HashAlgorithm, HashState, RegisterBlock};
+use core::marker::PhantomData;
+use digest::{
+    FixedOutput, FixedOutputReset, HashMarker, Output, OutputSizeUser, Reset, Update,
+    consts::{U16, U20, U28, U32, U48, U64},
+    generic_array::ArrayLength,
+};
+
+/// Hash algorithm with its digest size as a type.
+pub trait DigestAlgorithm {
+    /// Hash algorithm run by crypto engine.
+    const ALGORITHM: HashAlgorithm;
+    /// Digest size in bytes.
+    type OutputSize: ArrayLength<u8> + 'static;
+}
+
+/// MD5 message digest.
+pub struct Md5;
+
+/// SHA-1 secure hash.
+pub struct Sha1;
+
+/// SHA-224 secure hash.
+pub struct Sha224;
+
+/// SHA-256 secure hash.
+pub struct Sha256;
+
+/// SHA-384 secure hash.
+pub struct Sha384;
+
+/// SHA-512 secure hash.
+pub struct Sha512;
+
+impl DigestAlgorithm for Md5 {
+    const ALGORITHM: HashAlgorithm = HashAlgorithm::Md5;
+    type OutputSize = U16;
+}
+
+impl DigestAlgorithm for Sha1 {
+    const ALGORITHM: HashAlgorithm = HashAlgorithm::Sha1;
+    type OutputSize = U20;
+}
+
+impl DigestAlgorithm for Sha224 {
+    const ALGORITHM: HashAlgorithm = HashAlgorithm::Sha224;
+    type OutputSize = U28;
+}
+
+impl DigestAlgorithm for Sha256 {
+    const ALGORITHM: HashAlgorithm = HashAlgorithm::Sha256;
+    type OutputSize = U32;
+}
+
+impl DigestAlgorithm for Sha384 {
+    const ALGORITHM: HashAlgorithm = HashAlgorithm::Sha384;
+    type OutputSize = U48;
+}
+
+impl DigestAlgorithm for Sha512 {
+    const ALGORITHM: HashAlgorithm = HashAlgorithm::Sha512;
+    type OutputSize = U64;
+}
+
+/// Hasher of algorithm `A` running on crypto engine.
+pub struct Hasher<'a, 'b, CE, A> {
+    ce: &'a mut Ce<'b, CE>,
+    state: HashState,
+    _algorithm: PhantomData<A>,
+}
+
+impl<'a, 'b, CE: AsRef<RegisterBlock>, A: DigestAlgorithm> Hasher<'a, 'b, CE, A> {
+    /// Create a hasher of an empty message on crypto engine.
+    #[inline]
+    pub fn new(ce: &'a mut Ce<'b, CE>) -> Self {
+        Self {
+            ce,
+            state: HashState::new(A::ALGORITHM),
+            _algorithm: PhantomData,
+        }
+    }
+    /// Release the crypto engine.
+    #[inline]
+    pub fn free(self) -> &'a mut Ce<'b, CE> {
+        self.ce
+    }
+}
+
+impl<CE, A: DigestAlgorithm> OutputSizeUser for Hasher<'_, '_, CE, A> {
+    type OutputSize = A::OutputSize;
+}
+
+impl<CE, A: DigestAlgorithm> HashMarker for Hasher<'_, '_, CE, A> {}
+
+impl<CE: AsRef<RegisterBlock>, A: DigestAlgorithm> Update for Hasher<'_, '_, CE, A> {
+    #[inline]
+    fn update(&mut self, data: &[u8]) {
+        if let Err(e) = self.ce.hash_update(&mut self.state, data) {
+            panic!("crypto engine hash task failed: {:?}", e);
+        }
+    }
+}
+
+impl<CE: AsRef<RegisterBlock>, A: DigestAlgorithm> FixedOutput for Hasher<'_, '_, CE, A> {
+    #[inline]
+    fn finalize_into(mut self, out: &mut Output<Self>) {
+        self.finalize_into_reset(out);
+    }
+}
+
+impl<CE, A: DigestAlgorithm> Reset for Hasher<'_, '_, CE, A> {
+    #[inline]
+    fn reset(&mut self) {
+        self.state.reset();
+    }
+}
+
+impl<CE: AsRef<RegisterBlock>, A: DigestAlgorithm> FixedOutputReset for Hasher<'_, '_, CE, A> {
+    #[inline]
+    fn finalize_into_reset(&mut self, out: &mut Output<Self>) {
+        if let Err(e) = self.ce.hash_finalize(&mut self.state, out) {
+            panic!("crypto engine hash task failed: {:?}", e);
+        }
+    }
+}
@@ -0,0 +1,638 @@
+use super::{
+    Algorithm, AsymmetricControl, Ce, CommonControl, EccOperation, Error, HashAlgorithm,
+    RegisterBlock, SymmetricControl, TaskDescriptor,
+};
+
+/// Largest RSA modulus size in bytes.
+const MAX_RSA_SIZE: usize = 512;
+
+/// P-256 operand size in bytes.
+const P256_SIZE: usize = 32;
+
+/// 256-bit unsigned integer in little-endian 32-bit words.
+type U256 = [u32; 8];
+
+/// P-256 field prime.
+const P256_P: U256 = [
+    0xFFFF_FFFF,
+    0xFFFF_FFFF,
+    0xFFFF_FFFF,
+    0x0000_0000,
+    0x0000_0000,
+    0x0000_0000,
+    0x0000_0001,
+    0xFFFF_FFFF,
+];
+
+/// P-256 curve coefficient `a`, equal to `p - 3`.
+const P256_A: U256 = [
+    0xFFFF_FFFC,
+    0xFFFF_FFFF,
+    0xFFFF_FFFF,
+    0x0000_0000,
+    0x0000_0000,
+    0x0000_0000,
+    0x0000_0001,
+    0xFFFF_FFFF,
+];
+
+/// P-256 curve coefficient `b`.
+const P256_B: U256 = [
+    0x27D2_604B,
+    0x3BCE_3C3E,
+    0xCC53_B0F6,
+    0x651D_06B0,
+    0x7698_86BC,
+    0xB3EB_BD55,
+    0xAA3A_93E7,
+    0x5AC6_35D8,
+];
+
+/// P-256 group order.
+const P256_N: U256 = [
+    0xFC63_2551,
+    0xF3B9_CAC2,
+    0xA717_9E84,
+    0xBCE6_FAAD,
+    0xFFFF_FFFF,
+    0xFFFF_FFFF,
+    0x0000_0000,
+    0xFFFF_FFFF,
+];
+
+/// P-256 base point.
+const P256_G: EccPoint = EccPoint {
+    x: [
+        0x6B, 0x17, 0xD1, 0xF2, 0xE1, 0x2C, 0x42, 0x47, 0xF8, 0xBC, 0xE6, 0xE5, 0x63, 0xA4, 0x40,
+        0xF2, 0x77, 0x03, 0x7D, 0x81, 0x2D, 0xEB, 0x33, 0xA0, 0xF4, 0xA1, 0x39, 0x45, 0xD8, 0x98,
+        0xC2, 0x96,
+    ],
+    y: [
+        0x4F, 0xE3, 0x42, 0xE2, 0xFE, 0x1A, 0x7F, 0x9B, 0x8E, 0xE7, 0xEB, 0x4A, 0x7C, 0x0F, 0x9E,
+        0x16, 0x2B, 0xCE, 0x33, 0x57, 0x6B, 0x31, 0x5E, 0xCE, 0xCB, 0xB6, 0x40, 0x68, 0x37, 0xBF,
+        0x51, 0xF5,
+    ],
+};
+
+/// Point on NIST P-256 curve, in big-endian affine coordinates.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct EccPoint {
+    /// X coordinate.
+    pub x: [u8; 32],
+    /// Y coordinate.
+    pub y: [u8; 32],
+}
+
+impl EccPoint {
+    /// Point from uncompressed SEC1 encoding `04 || x || y`, or `None` if not uncompressed.
+    #[inline]
+    pub fn from_sec1(bytes: &[u8]) -> Option<Self> {
+        if bytes.len() != 65 || bytes[0] != 0x04 {
+            return None;
+        }
+        let mut point = Self {
+            x: [0; 32],
+            y: [0; 32],
+        };
+        point.x.copy_from_slice(&bytes[1..33]);
+        point.y.copy_from_slice(&bytes[33..65]);
+        Some(point)
+    }
+    /// Check if point satisfies P-256 curve equation.
+    #[inline]
+    pub fn is_on_curve(&self) -> bool {
+        let x = from_be_bytes(&self.x);
+        let y = from_be_bytes(&self.y);
+        if !less_than(&x, &P256_P) || !less_than(&y, &P256_P) {
+            return false;
+        }
+        // y^2 = x^3 + ax + b (mod p)
+        let left = mul_mod(&y, &y, &P256_P);
+        let x3 = mul_mod(&mul_mod(&x, &x, &P256_P), &x, &P256_P);
+        let ax = mul_mod(&P256_A, &x, &P256_P);
+        let right = add_mod(&add_mod(&x3, &ax, &P256_P), &P256_B, &P256_P);
+        left == right
+    }
+}
+
+/// ECDSA signature over P-256, in big-endian integers.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct EcdsaSignature {
+    /// Integer `r`.
+    pub r: [u8; 32],
+    /// Integer `s`.
+    pub s: [u8; 32],
+}
+
+impl EcdsaSignature {
+    /// Signature from fixed-size encoding `r || s`.
+    #[inline]
+    pub fn from_bytes(bytes: &[u8; 64]) -> Self {
+        let mut signature = Self {
+            r: [0; 32],
+            s: [0; 32],
+        };
+        signature.r.copy_from_slice(&bytes[..32]);
+        signature.s.copy_from_slice(&bytes[32..]);
+        signature
+    }
+}
+
+/// RSA task descriptor with the operands it refers to.
+#[repr(C, align(32))]
+struct RsaTask {
+    descriptor: TaskDescriptor,
+    modulus: [u8; MAX_RSA_SIZE],
+    exponent: [u8; MAX_RSA_SIZE],
+    input: [u8; MAX_RSA_SIZE],
+    output: [u8; MAX_RSA_SIZE],
+}
+
+/// Elliptic curve task descriptor with the operands it refers to.
+#[repr(C, align(32))]
+struct EccTask {
+    descriptor: TaskDescriptor,
+    scalar: [u8; P256_SIZE],
+    input: [u8; 6 * P256_SIZE],
+    output: [u8; 2 * P256_SIZE],
+}
+
+impl<CE: AsRef<RegisterBlock>> Ce<'_, CE> {
+    /// Compute `base` to the power of `exponent` modulo `modulus` into `output`.
+    ///
+    /// Integers are big-endian; `modulus` is 2048 or 4096 bits long, and `base` and `output`
+    /// are as long as `modulus`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidLength`] if operand lengths are not as above, or if `exponent`
+    /// is longer than `modulus`.
+    #[inline]
+    pub fn rsa_mod_exp(
+        &mut self,
+        modulus: &[u8],
+        exponent: &[u8],
+        base: &[u8],
+        output: &mut [u8],
+    ) -> Result<(), Error> {
+        let size = modulus.len();
+        if !matches!(size, 256 | 512)
+            || exponent.len() > size
+            || base.len() != size
+            || output.len() != size
+        {
+            return Err(Error::InvalidLength);
+        }
+        let common = CommonControl::default()
+            .set_algorithm(Algorithm::Rsa)
+            .enable_interrupt();
+        let mut task = RsaTask {
+            descriptor: TaskDescriptor::new(common, SymmetricControl::default()),
+            modulus: [0; MAX_RSA_SIZE],
+            exponent: [0; MAX_RSA_SIZE],
+            input: [0; MAX_RSA_SIZE],
+            output: [0; MAX_RSA_SIZE],
+        };
+        // crypto engine takes little-endian operands
+        reverse_into(&mut task.modulus, modulus);
+        reverse_into(&mut task.exponent, exponent);
+        reverse_into(&mut task.input, base);
+        task.descriptor.asymmetric_control =
+            AsymmetricControl::default().set_width((size / 4) as u16);
+        task.descriptor.key_address = task.modulus.as_ptr() as usize as u32;
+        task.descriptor.iv_address = task.exponent.as_ptr() as usize as u32;
+        task.descriptor.set_buffers(
+            task.input.as_ptr() as usize as u32,
+            task.output.as_mut_ptr() as usize as u32,
+            size as u32,
+        );
+        self.run(&task.descriptor)?;
+        reverse_into(output, &task.output[..size]);
+        Ok(())
+    }
+    /// Multiply P-256 `point` by big-endian `scalar`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if crypto engine fails.
+    #[inline]
+    pub fn ecc_point_multiply(
+        &mut self,
+        scalar: &[u8; 32],
+        point: &EccPoint,
+    ) -> Result<EccPoint, Error> {
+        self.ecc(EccOperation::PointMultiply, scalar, point, point)
+    }
+    /// Add P-256 points `a` and `b`.
+    ///
+    /// Points must differ and must not be inverse of each other; use [`Ce::ecc_point_double`]
+    /// for a point added to itself.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if crypto engine fails.
+    #[inline]
+    pub fn ecc_point_add(&mut self, a: &EccPoint, b: &EccPoint) -> Result<EccPoint, Error> {
+        self.ecc(EccOperation::PointAdd, &[0; 32], a, b)
+    }
+    /// Double P-256 `point`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if crypto engine fails.
+    #[inline]
+    pub fn ecc_point_double(&mut self, point: &EccPoint) -> Result<EccPoint, Error> {
+        self.ecc(EccOperation::PointDouble, &[0; 32], point, point)
+    }
+    /// Verify RSASSA-PKCS1-v1_5 `signature` of message `digest` hashed by `algorithm`.
+    ///
+    /// Public key is big-endian `modulus` of 2048 or 4096 bits and `exponent`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidSignature`] if signature does not match, or
+    /// [`Error::InvalidLength`] if public key lengths are not supported.
+    #[inline]
+    pub fn verify_rsa_pkcs1(
+        &mut self,
+        modulus: &[u8],
+        exponent: &[u8],
+        algorithm: HashAlgorithm,
+        digest: &[u8],
+        signature: &[u8],
+    ) -> Result<(), Error> {
+        if !matches!(modulus.len(), 256 | 512) || exponent.len() > modulus.len() {
+            return Err(Error::InvalidLength);
+        }
+        // signature representative must be less than modulus
+        if signature.len() != modulus.len() || signature >= modulus {
+            return Err(Error::InvalidSignature);
+        }
+        let mut message = [0; MAX_RSA_SIZE];
+        let message = &mut message[..modulus.len()];
+        self.rsa_mod_exp(modulus, exponent, signature, message)?;
+        match is_pkcs1_encoding(message, algorithm, digest) {
+            true => Ok(()),
+            false => Err(Error::InvalidSignature),
+        }
+    }
+    /// Verify ECDSA P-256 `signature` of message `digest` by `public_key`.
+    ///
+    /// Digest longer than 32 bytes is truncated to its leftmost 32 bytes.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidSignature`] if signature does not match or public key is not on
+    /// the curve.
+    #[inline]
+    pub fn verify_ecdsa_p256(
+        &mut self,
+        public_key: &EccPoint,
+        digest: &[u8],
+        signature: &EcdsaSignature,
+    ) -> Result<(), Error> {
+        let r = from_be_bytes(&signature.r);
+        let s = from_be_bytes(&signature.s);
+        if is_zero(&r) || is_zero(&s) || !less_than(&r, &P256_N) || !less_than(&s, &P256_N) {
+            return Err(Error::InvalidSignature);
+        }
+        if !public_key.is_on_curve() {
+            return Err(Error::InvalidSignature);
+        }
+        let mut e = [0; 32];
+        let len = digest.len().min(32);
+        e[32 - len..].copy_from_slice(&digest[..len]);
+        let e = reduce(&from_be_bytes(&e), &P256_N);
+        let w = inv_mod(&s, &P256_N);
+        let u1 = mul_mod(&e, &w, &P256_N);
+        let u2 = mul_mod(&r, &w, &P256_N);
+        // u2 is never zero as r and w are not
+        let point = self.ecc_point_multiply(&to_be_bytes(&u2), public_key)?;
+        let point = if is_zero(&u1) {
+            point
+        } else {
+            let base = self.ecc_point_multiply(&to_be_bytes(&u1), &P256_G)?;
+            if base == point {
+                self.ecc_point_double(&point)?
+            } else if base.x == point.x {
+                // sum is the point at infinity
+                return Err(Error::InvalidSignature);
+            } else {
+                self.ecc_point_add(&base, &point)?
+            }
+        };
+        let v = reduce(&from_be_bytes(&point.x), &P256_N);
+        match v == r {
+            true => Ok(()),
+            false => Err(Error::InvalidSignature),
+        }
+    }
+    /// Run a P-256 point operation.
+    #[inline]
+    fn ecc(
+        &mut self,
+        operation: EccOperation,
+        scalar: &[u8; 32],
+        a: &EccPoint,
+        b: &EccPoint,
+    ) -> Result<EccPoint, Error> {
+        let common = CommonControl::default()
+            .set_algorithm(Algorithm::Ecc)
+            .enable_interrupt();
+        let mut task = EccTask {
+            descriptor: TaskDescriptor::new(common, SymmetricControl::default()),
+            scalar: [0; P256_SIZE],
+            input: [0; 6 * P256_SIZE],
+            output: [0; 2 * P256_SIZE],
+        };
+        // input is p, a, x1, y1, x2 and y2 in little-endian
+        let operands = [
+            to_be_bytes(&P256_P),
+            to_be_bytes(&P256_A),
+            a.x,
+            a.y,
+            b.x,
+            b.y,
+        ];
+        for (chunk, operand) in task.input.chunks_mut(P256_SIZE).zip(&operands) {
+            reverse_into(chunk, operand);
+        }
+        reverse_into(&mut task.scalar, scalar);
+        task.descriptor.asymmetric_control = AsymmetricControl::default()
+            .set_width((P256_SIZE / 4) as u16)
+            .set_ecc_operation(operation);
+        task.descriptor.key_address = task.scalar.as_ptr() as usize as u32;
+        task.descriptor.set_buffers(
+            task.input.as_ptr() as usize as u32,
+            task.output.as_mut_ptr() as usize as u32,
+            task.input.len() as u32,
+        );
+        task.descriptor.destination[0].length = (task.output.len() / 4) as u32;
+        let result = self.run(&task.descriptor);
+        // scalar may be a private key
+        task.scalar.fill(0);
+        result?;
+        let mut point = EccPoint {
+            x: [0; 32],
+            y: [0; 32],
+        };
+        reverse_into(&mut point.x, &task.output[..P256_SIZE]);
+        reverse_into(&mut point.y, &task.output[P256_SIZE..]);
+        Ok(point)
+    }
+}
+
+/// Check if `message` is RSASSA-PKCS1-v1_5 encoding of `digest` hashed by `algorithm`.
+#[inline]
+fn is_pkcs1_encoding(message: &[u8], algorithm: HashAlgorithm, digest: &[u8]) -> bool {
+    let prefix: &[u8] = match algorithm {
+        HashAlgorithm::Md5 => &[
+            0x30, 0x20, 0x30, 0x0C, 0x06, 0x08, 0x2A, 0x86, 0x48, 0x86, 0xF7, 0x0D, 0x02, 0x05,
+            0x05, 0x00, 0x04, 0x10,
+        ],
+        HashAlgorithm::Sha1 => &[
+            0x30, 0x21, 0x30, 0x09, 0x06, 0x05, 0x2B, 0x0E, 0x03, 0x02, 0x1A, 0x05, 0x00, 0x04,
+            0x14,
+        ],
+        HashAlgorithm::Sha224 => &[
+            0x30, 0x2D, 0x30, 0x0D, 0x06, 0x09, 0x60, 0x86, 0x48, 0x01, 0x65, 0x03, 0x04, 0x02,
+            0x04, 0x05, 0x00, 0x04, 0x1C,
+        ],
+        HashAlgorithm::Sha256 => &[
+            0x30, 0x31, 0x30, 0x0D, 0x06, 0x09, 0x60, 0x86, 0x48, 0x01, 0x65, 0x03, 0x04, 0x02,
+            0x01, 0x05, 0x00, 0x04, 0x20,
+        ],
+        HashAlgorithm::Sha384 => &[
+            0x30, 0x41, 0x30, 0x0D, 0x06, 0x09, 0x60, 0x86, 0x48, 0x01, 0x65, 0x03, 0x04, 0x02,
+            0x02, 0x05, 0x00, 0x04, 0x30,
+        ],
+        HashAlgorithm::Sha512 => &[
+            0x30, 0x51, 0x30, 0x0D, 0x06, 0x09, 0x60, 0x86, 0x48, 0x01, 0x65, 0x03, 0x04, 0x02,
+            0x03, 0x05, 0x00, 0x04, 0x40,
+        ],
+    };
+    if digest.len() != algorithm.output_size() {
+        return false;
+    }
+    // 0x00 || 0x01 || at least eight 0xFF || 0x00 || prefix || digest
+    let tail = prefix.len() + digest.len();
+    if message.len() < tail + 11 {
+        return false;
+    }
+    let padding = message.len() - tail - 3;
+    let (head, rest) = message.split_at(2);
+    let (ones, rest) = rest.split_at(padding);
+    let (zero, rest) = rest.split_at(1);
+    let (info, hash) = rest.split_at(prefix.len());
+    head == [0x00, 0x01]
+        && ones.iter().all(|&b| b == 0xFF)
+        && zero == [0x00]
+        && info == prefix
+        && hash == digest
+}
+
+/// Copy `src` into `dst` in reverse byte order, zero filling the rest of `dst`.
+#[inline]
+fn reverse_into(dst: &mut [u8], src: &[u8]) {
+    for (d, s) in dst.iter_mut().zip(src.iter().rev()) {
+        *d = *s;
+    }
+    dst[src.len()..].fill(0);
+}
+
+/// Integer from big-endian bytes.
+#[inline]
+fn from_be_bytes(bytes: &[u8; 32]) -> U256 {
+    let mut ans = [0; 8];
+    for (i, word) in ans.iter_mut().enumerate() {
+        let start = 28 - 4 * i;
+        *word = u32::from_be_bytes([
+            bytes[start],
+            bytes[start + 1],
+            bytes[start + 2],
+            bytes[start + 3],
+        ]);
+    }
+    ans
+}
+
+/// Integer into big-endian bytes.
+#[inline]
+fn to_be_bytes(a: &U256) -> [u8; 32] {
+    let mut ans = [0; 32];
+    for (i, word) in a.iter().enumerate() {
+        let start = 28 - 4 * i;
+        ans[start..start + 4].copy_from_slice(&word.to_be_bytes());
+    }
+    ans
+}
+
+#[inline]
+fn is_zero(a: &U256) -> bool {
+    a.iter().all(|&w| w == 0)
+}
+
+#[inline]
+fn less_than(a: &U256, b: &U256) -> bool {
+    for i in (0..8).rev() {
+        if a[i] != b[i] {
+            return a[i] < b[i];
+        }
+    }
+    false
+}
+
+#[inline]
+fn add(a: &U256, b: &U256) -> (U256, bool) {
+    let mut ans = [0; 8];
+    let mut carry = false;
+    for i in 0..8 {
+        let (sum, c1) = a[i].overflowing_add(b[i]);
+        let (sum, c2) = sum.overflowing_add(carry as u32);
+        ans[i] = sum;
+        carry = c1 || c2;
+    }
+    (ans, carry)
+}
+
+#[inline]
+fn sub(a: &U256, b: &U256) -> (U256, bool) {
+    let mut ans = [0; 8];
+    let mut borrow = false;
+    for i in 0..8 {
+        let (diff, b1) = a[i].overflowing_sub(b[i]);
+        let (diff, b2) = diff.overflowing_sub(borrow as u32);
+        ans[i] = diff;
+        borrow = b1 || b2;
+    }
+    (ans, borrow)
+}
+
+/// Reduce `a` modulo `m`, where `m` is larger than 2^255.
+#[inline]
+fn reduce(a: &U256, m: &U256) -> U256 {
+    match less_than(a, m) {
+        true => *a,
+        false => sub(a, m).0,
+    }
+}
+
+/// `a + b` modulo `m`, where `a` and `b` are less than `m`.
+#[inline]
+fn add_mod(a: &U256, b: &U256, m: &U256) -> U256 {
+    let (sum, carry) = add(a, b);
+    match carry || !less_than(&sum, m) {
+        true => sub(&sum, m).0,
+        false => sum,
+    }
+}
+
+/// `a * b` modulo `m`, where `b` is less than `m`.
+#[inline]
+fn mul_mod(a: &U256, b: &U256, m: &U256) -> U256 {
+    let mut ans = [0; 8];
+    for i in (0..256).rev() {
+        ans = add_mod(&ans, &ans, m);
+        if a[i / 32] & (1 << (i % 32)) != 0 {
+            ans = add_mod(&ans, b, m);
+        }
+    }
+    ans
+}
+
+/// Inverse of `a` modulo prime `m`, by Fermat's little theorem.
+#[inline]
+fn inv_mod(a: &U256, m: &U256) -> U256 {
+    let exponent = sub(m, &[2, 0, 0, 0, 0, 0, 0, 0]).0;
+    let mut ans = [1, 0, 0, 0, 0, 0, 0, 0];
+    for i in (0..256).rev() {
+        ans = mul_mod(&ans, &ans, m);
+        if exponent[i / 32] & (1 << (i % 32)) != 0 {
+            ans = mul_mod(&ans, a, m);
+        }
+    }
+    ans
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        EccPoint, EcdsaSignature, HashAlgorithm, P256_G, P256_N, P256_P, from_be_bytes, inv_mod,
+        is_pkcs1_encoding, mul_mod, reverse_into, to_be_bytes,
+    };
+
+    #[test]
+    fn function_modular_arithmetic() {
+        let a = from_be_bytes(&P256_G.x);
+        assert_eq!(to_be_bytes(&a), P256_G.x);
+        assert_eq!(a[7], 0x6B17_D1F2);
+        let one = [1, 0, 0, 0, 0, 0, 0, 0];
+        for m in [P256_N, P256_P] {
+            let inv = inv_mod(&a, &m);
+            assert_eq!(mul_mod(&a, &inv, &m), one);
+        }
+        // (n - 1)^2 = 1 (mod n)
+        let mut minus_one = P256_N;
+        minus_one[0] -= 1;
+        assert_eq!(mul_mod(&minus_one, &minus_one, &P256_N), one);
+    }
+
+    #[test]
+    fn struct_ecc_point_functions() {
+        assert!(P256_G.is_on_curve());
+        let mut point = P256_G;
+        point.y[31] ^= 1;
+        assert!(!point.is_on_curve());
+
+        let mut sec1 = [0; 65];
+        sec1[0] = 0x04;
+        sec1[1..33].copy_from_slice(&P256_G.x);
+        sec1[33..].copy_from_slice(&P256_G.y);
+        assert_eq!(EccPoint::from_sec1(&sec1), Some(P256_G));
+        sec1[0] = 0x02;
+        assert_eq!(EccPoint::from_sec1(&sec1), None);
+
+        let mut bytes = [0; 64];
+        bytes[0] = 0x12;
+        bytes[63] = 0x34;
+        let signature = EcdsaSignature::from_bytes(&bytes);
+        assert_eq!(signature.r[0], 0x12);
+        assert_eq!(signature.s[31], 0x34);
+    }
+
+    #[test]
+    fn function_is_pkcs1_encoding() {
+        let digest = [0xAB; 32];
+        let mut message = [0xFF; 256];
+        message[0] = 0x00;
+        message[1] = 0x01;
+        let info = [
+            0x30, 0x31, 0x30, 0x0D, 0x06, 0x09, 0x60, 0x86, 0x48, 0x01, 0x65, 0x03, 0x04, 0x02,
+            0x01, 0x05, 0x00, 0x04, 0x20,
+        ];
+        let tail = 256 - info.len() - digest.len();
+        message[tail - 1] = 0x00;
+        message[tail..tail + info.len()].copy_from_slice(&info);
+        message[256 - 32..].copy_from_slice(&digest);
+        assert!(is_pkcs1_encoding(&message, HashAlgorithm::Sha256, &digest));
+        assert!(!is_pkcs1_encoding(
+            &message,
+            HashAlgorithm::Sha1,
+            &digest[..20]
+        ));
+        assert!(!is_pkcs1_encoding(
+            &message,
+            HashAlgorithm::Sha256,
+            &[0xAC; 32]
+        ));
+        message[10] = 0xFE;
+        assert!(!is_pkcs1_encoding(&message, HashAlgorithm::Sha256, &digest));
+    }
+
+    #[test]
+    fn function_reverse_into() {
+        let mut buf = [0xEE; 6];
+        reverse_into(&mut buf, &[1, 2, 3, 4]);
+        assert_eq!(buf, [4, 3, 2, 1, 0, 0]);
+    }
+}
@@ -0,0 +1,578 @@
+use volatile_register::{RO, RW};
+
+/// Crypto engine registers.
+#[repr(C)]
+pub struct RegisterBlock {
+    /// 0x00 - Task descriptor queue address register.
+    pub task_queue: RW<u32>,
+    /// 0x04 - Control register.
+    pub control: RW<u32>,
+    /// 0x08 - Interrupt enable register.
+    pub interrupt_enable: RW<InterruptEnable>,
+    /// 0x0c - Interrupt status register.
+    pub interrupt_status: RW<InterruptStatus>,
+    /// 0x10 - Task load register.
+    pub task_load: RW<TaskLoad>,
+    /// 0x14 - Task status register.
+    pub task_status: RO<u32>,
+    /// 0x18 - Error status register.
+    pub error_status: RO<ErrorStatus>,
+    _reserved0: [u32; 2],
+    /// 0x24 - Current source address register.
+    pub current_source: RO<u32>,
+    /// 0x28 - Current destination address register.
+    pub current_destination: RO<u32>,
+    /// 0x2c - Throughput register.
+    pub throughput: RO<u32>,
+}
+
+/// Number of task flows.
+pub const FLOW_COUNT: usize = 4;
+
+/// Interrupt enable register.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct InterruptEnable(u32);
+
+impl InterruptEnable {
+    /// Check if task finished interrupt of flow `F` is enabled.
+    #[inline]
+    pub const fn is_interrupt_enabled<const F: usize>(self) -> bool {
+        self.0 & (1 << F) != 0
+    }
+    /// Enable task finished interrupt of flow `F`.
+    #[inline]
+    pub const fn enable_interrupt<const F: usize>(self) -> Self {
+        Self(self.0 | (1 << F))
+    }
+    /// Disable task finished interrupt of flow `F`.
+    #[inline]
+    pub const fn disable_interrupt<const F: usize>(self) -> Self {
+        Self(self.0 & !(1 << F))
+    }
+}
+
+impl Default for InterruptEnable {
+    #[inline]
+    fn default() -> Self {
+        Self(0x0000_0000)
+    }
+}
+
+/// Interrupt status register.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct InterruptStatus(u32);
+
+impl InterruptStatus {
+    /// Check if task of flow `F` has finished.
+    #[inline]
+    pub const fn is_finished<const F: usize>(self) -> bool {
+        self.0 & (1 << F) != 0
+    }
+    /// Clear task finished flag of flow `F`.
+    #[inline]
+    pub const fn clear_finished<const F: usize>(self) -> Self {
+        Self(self.0 | (1 << F))
+    }
+}
+
+impl Default for InterruptStatus {
+    #[inline]
+    fn default() -> Self {
+        Self(0x0000_0000)
+    }
+}
+
+/// Task load register.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct TaskLoad(u32);
+
+impl TaskLoad {
+    const LOAD: u32 = 1 << 0;
+
+    /// Check if task is being loaded.
+    #[inline]
+    pub const fn is_loading(self) -> bool {
+        self.0 & Self::LOAD != 0
+    }
+    /// Load task from descriptor queue address.
+    #[inline]
+    pub const fn load(self) -> Self {
+        Self(self.0 | Self::LOAD)
+    }
+}
+
+impl Default for TaskLoad {
+    #[inline]
+    fn default() -> Self {
+        Self(0x0000_0000)
+    }
+}
+
+/// Error status register, with four error bits per flow.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct ErrorStatus(u32);
+
+impl ErrorStatus {
+    const ALGORITHM_UNSUPPORTED: u32 = 1 << 0;
+    const DATA_LENGTH: u32 = 1 << 1;
+    const KEY_SRAM: u32 = 1 << 2;
+
+    /// Check if task of flow `F` requested an unsupported algorithm.
+    #[inline]
+    pub const fn is_algorithm_unsupported<const F: usize>(self) -> bool {
+        self.0 & (Self::ALGORITHM_UNSUPPORTED << (4 * F)) != 0
+    }
+    /// Check if task of flow `F` had an invalid data length.
+    #[inline]
+    pub const fn is_data_length_invalid<const F: usize>(self) -> bool {
+        self.0 & (Self::DATA_LENGTH << (4 * F)) != 0
+    }
+    /// Check if task of flow `F` failed to access key memory.
+    #[inline]
+    pub const fn is_key_sram_error<const F: usize>(self) -> bool {
+        self.0 & (Self::KEY_SRAM << (4 * F)) != 0
+    }
+}
+
+/// Algorithm of a crypto engine task.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Algorithm {
+    /// Advanced Encryption Standard block cipher.
+    Aes = 0,
+    /// MD5 message digest.
+    Md5 = 16,
+    /// SHA-1 secure hash.
+    Sha1 = 17,
+    /// SHA-224 secure hash.
+    Sha224 = 18,
+    /// SHA-256 secure hash.
+    Sha256 = 19,
+    /// SHA-384 secure hash.
+    Sha384 = 20,
+    /// SHA-512 secure hash.
+    Sha512 = 21,
+    /// True random number generator.
+    Trng = 28,
+    /// RSA modular exponentiation.
+    Rsa = 32,
+    /// Elliptic curve point operation.
+    Ecc = 33,
+}
+
+/// Direction of a symmetric cipher task.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Direction {
+    /// Encrypt plaintext into ciphertext.
+    Encrypt,
+    /// Decrypt ciphertext into plaintext.
+    Decrypt,
+}
+
+/// Task common control word of a task descriptor.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct CommonControl(u32);
+
+impl CommonControl {
+    const ALGORITHM: u32 = 0x7F;
+    const DECRYPT: u32 = 1 << 8;
+    const HASH_IV_INPUT: u32 = 1 << 16;
+    const INTERRUPT: u32 = 1 << 31;
+
+    /// Get task algorithm, or `None` if it's not an [`Algorithm`].
+    #[inline]
+    pub const fn algorithm(self) -> Option<Algorithm> {
+        match self.0 & Self::ALGORITHM {
+            0 => Some(Algorithm::Aes),
+            16 => Some(Algorithm::Md5),
+            17 => Some(Algorithm::Sha1),
+            18 => Some(Algorithm::Sha224),
+            19 => Some(Algorithm::Sha256),
+            20 => Some(Algorithm::Sha384),
+            21 => Some(Algorithm::Sha512),
+            28 => Some(Algorithm::Trng),
+            32 => Some(Algorithm::Rsa),
+            33 => Some(Algorithm::Ecc),
+            _ => None,
+        }
+    }
+    /// Set task algorithm.
+    #[inline]
+    pub const fn set_algorithm(self, val: Algorithm) -> Self {
+        Self((self.0 & !Self::ALGORITHM) | val as u32)
+    }
+    /// Get symmetric cipher direction.
+    #[inline]
+    pub const fn direction(self) -> Direction {
+        match self.0 & Self::DECRYPT {
+            0 => Direction::Encrypt,
+            _ => Direction::Decrypt,
+        }
+    }
+    /// Set symmetric cipher direction.
+    #[inline]
+    pub const fn set_direction(self, val: Direction) -> Self {
+        match val {
+            Direction::Encrypt => Self(self.0 & !Self::DECRYPT),
+            Direction::Decrypt => Self(self.0 | Self::DECRYPT),
+        }
+    }
+    /// Check if hash task starts from state at IV address instead of standard initial value.
+    #[inline]
+    pub const fn is_hash_iv_input(self) -> bool {
+        self.0 & Self::HASH_IV_INPUT != 0
+    }
+    /// Start hash task from state at IV address.
+    #[inline]
+    pub const fn enable_hash_iv_input(self) -> Self {
+        Self(self.0 | Self::HASH_IV_INPUT)
+    }
+    /// Start hash task from standard initial value.
+    #[inline]
+    pub const fn disable_hash_iv_input(self) -> Self {
+        Self(self.0 & !Self::HASH_IV_INPUT)
+    }
+    /// Check if task raises its flow's finished flag.
+    #[inline]
+    pub const fn is_interrupt_enabled(self) -> bool {
+        self.0 & Self::INTERRUPT != 0
+    }
+    /// Raise finished flag of flow when task finishes.
+    #[inline]
+    pub const fn enable_interrupt(self) -> Self {
+        Self(self.0 | Self::INTERRUPT)
+    }
+    /// Finish task without raising finished flag.
+    #[inline]
+    pub const fn disable_interrupt(self) -> Self {
+        Self(self.0 & !Self::INTERRUPT)
+    }
+}
+
+impl Default for CommonControl {
+    #[inline]
+    fn default() -> Self {
+        Self(0x0000_0000)
+    }
+}
+
+/// AES key length.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum AesKeySize {
+    /// 128-bit key.
+    Bits128 = 0,
+    /// 192-bit key.
+    Bits192 = 1,
+    /// 256-bit key.
+    Bits256 = 2,
+}
+
+/// Key held inside crypto engine, which tasks reference instead of key bytes in memory.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum KeySlot {
+    /// Secure storage key, burned in eFuse.
+    Ssk,
+    /// Hardware unique key of the chip.
+    Huk,
+    /// Root secure storage key.
+    Rssk,
+    /// Internal key 0 to 7, loaded by secure firmware.
+    Internal(u8),
+}
+
+/// Width of counter incremented in CTR mode, from its least significant bit.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum CounterWidth {
+    /// 16-bit counter.
+    Bits16 = 0,
+    /// 32-bit counter.
+    Bits32 = 1,
+    /// 64-bit counter.
+    Bits64 = 2,
+    /// 128-bit counter.
+    Bits128 = 3,
+}
+
+/// Block cipher mode of operation.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum CipherMode {
+    /// Electronic codebook, each block ciphered alone.
+    Ecb = 0,
+    /// Cipher block chaining.
+    Cbc = 1,
+    /// Counter mode.
+    Ctr = 2,
+}
+
+/// Task symmetric control word of a task descriptor.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct SymmetricControl(u32);
+
+impl SymmetricControl {
+    const KEY_SIZE: u32 = 0x3;
+    const COUNTER_WIDTH: u32 = 0x3 << 2;
+    const MODE: u32 = 0xF << 8;
+    const KEY_SELECT: u32 = 0xF << 20;
+
+    /// Get AES key length.
+    #[inline]
+    pub const fn key_size(self) -> AesKeySize {
+        match self.0 & Self::KEY_SIZE {
+            0 => AesKeySize::Bits128,
+            1 => AesKeySize::Bits192,
+            _ => AesKeySize::Bits256,
+        }
+    }
+    /// Set AES key length.
+    #[inline]
+    pub const fn set_key_size(self, val: AesKeySize) -> Self {
+        Self((self.0 & !Self::KEY_SIZE) | val as u32)
+    }
+    /// Get counter width of CTR mode.
+    #[inline]
+    pub const fn counter_width(self) -> CounterWidth {
+        match (self.0 & Self::COUNTER_WIDTH) >> 2 {
+            0 => CounterWidth::Bits16,
+            1 => CounterWidth::Bits32,
+            2 => CounterWidth::Bits64,
+            _ => CounterWidth::Bits128,
+        }
+    }
+    /// Set counter width of CTR mode.
+    #[inline]
+    pub const fn set_counter_width(self, val: CounterWidth) -> Self {
+        Self((self.0 & !Self::COUNTER_WIDTH) | ((val as u32) << 2))
+    }
+    /// Get block cipher mode, or `None` if it's not a [`CipherMode`].
+    #[inline]
+    pub const fn mode(self) -> Option<CipherMode> {
+        match (self.0 & Self::MODE) >> 8 {
+            0 => Some(CipherMode::Ecb),
+            1 => Some(CipherMode::Cbc),
+            2 => Some(CipherMode::Ctr),
+            _ => None,
+        }
+    }
+    /// Set block cipher mode.
+    #[inline]
+    pub const fn set_mode(self, val: CipherMode) -> Self {
+        Self((self.0 & !Self::MODE) | ((val as u32) << 8))
+    }
+    /// Get key slot used instead of key in memory, or `None` if key is read from memory.
+    #[inline]
+    pub const fn key_slot(self) -> Option<KeySlot> {
+        match (self.0 & Self::KEY_SELECT) >> 20 {
+            1 => Some(KeySlot::Ssk),
+            2 => Some(KeySlot::Huk),
+            3 => Some(KeySlot::Rssk),
+            n @ 8..=15 => Some(KeySlot::Internal(n as u8 - 8)),
+            _ => None,
+        }
+    }
+    /// Use key slot instead of key in memory; internal key index is taken modulo 8.
+    #[inline]
+    pub const fn set_key_slot(self, val: KeySlot) -> Self {
+        let val = match val {
+            KeySlot::Ssk => 1,
+            KeySlot::Huk => 2,
+            KeySlot::Rssk => 3,
+            KeySlot::Internal(n) => 8 + (n as u32 & 0x7),
+        };
+        Self((self.0 & !Self::KEY_SELECT) | (val << 20))
+    }
+    /// Read key from memory at task key address.
+    #[inline]
+    pub const fn select_input_key(self) -> Self {
+        Self(self.0 & !Self::KEY_SELECT)
+    }
+}
+
+impl Default for SymmetricControl {
+    #[inline]
+    fn default() -> Self {
+        Self(0x0000_0000)
+    }
+}
+
+/// Elliptic curve point operation.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum EccOperation {
+    /// Add two points.
+    PointAdd = 0,
+    /// Double a point.
+    PointDouble = 1,
+    /// Multiply a point by a scalar.
+    PointMultiply = 2,
+}
+
+/// Task asymmetric control word of a task descriptor.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct AsymmetricControl(u32);
+
+impl AsymmetricControl {
+    const WIDTH: u32 = 0xFFFF;
+    const ECC_OPERATION: u32 = 0xF << 28;
+
+    /// Get operand width in 32-bit words.
+    #[inline]
+    pub const fn width(self) -> u16 {
+        (self.0 & Self::WIDTH) as u16
+    }
+    /// Set operand width in 32-bit words.
+    #[inline]
+    pub const fn set_width(self, val: u16) -> Self {
+        Self((self.0 & !Self::WIDTH) | val as u32)
+    }
+    /// Get elliptic curve point operation, or `None` if it's not an [`EccOperation`].
+    #[inline]
+    pub const fn ecc_operation(self) -> Option<EccOperation> {
+        match (self.0 & Self::ECC_OPERATION) >> 28 {
+            0 => Some(EccOperation::PointAdd),
+            1 => Some(EccOperation::PointDouble),
+            2 => Some(EccOperation::PointMultiply),
+            _ => None,
+        }
+    }
+    /// Set elliptic curve point operation.
+    #[inline]
+    pub const fn set_ecc_operation(self, val: EccOperation) -> Self {
+        Self((self.0 & !Self::ECC_OPERATION) | ((val as u32) << 28))
+    }
+}
+
+impl Default for AsymmetricControl {
+    #[inline]
+    fn default() -> Self {
+        Self(0x0000_0000)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        AesKeySize, Algorithm, AsymmetricControl, CipherMode, CommonControl, CounterWidth,
+        Direction, EccOperation, ErrorStatus, InterruptEnable, InterruptStatus, KeySlot,
+        RegisterBlock, SymmetricControl, TaskLoad,
+    };
+    use core::mem::offset_of;
+
+    #[test]
+    fn offset_ce() {
+        assert_eq!(offset_of!(RegisterBlock, task_queue), 0x00);
+        assert_eq!(offset_of!(RegisterBlock, control), 0x04);
+        assert_eq!(offset_of!(RegisterBlock, interrupt_enable), 0x08);
+        assert_eq!(offset_of!(RegisterBlock, interrupt_status), 0x0c);
+        assert_eq!(offset_of!(RegisterBlock, task_load), 0x10);
+        assert_eq!(offset_of!(RegisterBlock, task_status), 0x14);
+        assert_eq!(offset_of!(RegisterBlock, error_status), 0x18);
+        assert_eq!(offset_of!(RegisterBlock, current_source), 0x24);
+        assert_eq!(offset_of!(RegisterBlock, current_destination), 0x28);
+        assert_eq!(offset_of!(RegisterBlock, throughput), 0x2c);
+    }
+
+    #[test]
+    fn struct_interrupt_functions() {
+        let val = InterruptEnable::default()
+            .enable_interrupt::<0>()
+            .enable_interrupt::<3>();
+        assert_eq!(val.0, 0x0000_0009);
+        assert!(val.is_interrupt_enabled::<3>() && !val.is_interrupt_enabled::<1>());
+        assert_eq!(val.disable_interrupt::<0>().0, 0x0000_0008);
+
+        let val = InterruptStatus::default();
+        assert!(!val.is_finished::<0>());
+        let val = val.clear_finished::<2>();
+        assert_eq!(val.0, 0x0000_0004);
+        assert!(val.is_finished::<2>());
+    }
+
+    #[test]
+    fn struct_task_functions() {
+        let val = TaskLoad::default();
+        assert!(!val.is_loading());
+        assert_eq!(val.load().0, 0x0000_0001);
+
+        let val = ErrorStatus(0x0000_0021);
+        assert!(val.is_algorithm_unsupported::<0>());
+        assert!(!val.is_data_length_invalid::<0>());
+        assert!(val.is_data_length_invalid::<1>());
+        assert!(!val.is_key_sram_error::<1>());
+        assert!(ErrorStatus(0x0000_0400).is_key_sram_error::<2>());
+    }
+
+    #[test]
+    fn struct_common_control_functions() {
+        let val = CommonControl::default()
+            .set_algorithm(Algorithm::Aes)
+            .set_direction(Direction::Decrypt)
+            .enable_interrupt();
+        assert_eq!(val.0, 0x8000_0100);
+        assert_eq!(val.algorithm(), Some(Algorithm::Aes));
+        assert_eq!(val.direction(), Direction::Decrypt);
+        assert!(val.is_interrupt_enabled());
+        let val = val.set_direction(Direction::Encrypt).disable_interrupt();
+        assert_eq!(val.0, 0x0000_0000);
+        assert_eq!(CommonControl(0x0000_007F).algorithm(), None);
+
+        let val = CommonControl::default()
+            .set_algorithm(Algorithm::Sha256)
+            .enable_hash_iv_input();
+        assert_eq!(val.0, 0x0001_0013);
+        assert_eq!(val.algorithm(), Some(Algorithm::Sha256));
+        assert!(val.is_hash_iv_input());
+        assert_eq!(val.disable_hash_iv_input().0, 0x0000_0013);
+        assert_eq!(CommonControl(0x0000_0010).algorithm(), Some(Algorithm::Md5));
+        assert_eq!(
+            CommonControl(0x8000_001C).algorithm(),
+            Some(Algorithm::Trng)
+        );
+    }
+
+    #[test]
+    fn struct_symmetric_control_functions() {
+        let val = SymmetricControl::default()
+            .set_key_size(AesKeySize::Bits256)
+            .set_counter_width(CounterWidth::Bits128)
+            .set_mode(CipherMode::Ctr);
+        assert_eq!(val.0, 0x0000_020E);
+        assert_eq!(val.key_size(), AesKeySize::Bits256);
+        assert_eq!(val.counter_width(), CounterWidth::Bits128);
+        assert_eq!(val.mode(), Some(CipherMode::Ctr));
+        let val = val
+            .set_key_size(AesKeySize::Bits192)
+            .set_counter_width(CounterWidth::Bits16)
+            .set_mode(CipherMode::Cbc);
+        assert_eq!(val.0, 0x0000_0101);
+        assert_eq!(SymmetricControl(0x0000_0F00).mode(), None);
+
+        assert_eq!(val.key_slot(), None);
+        let val = val.set_key_slot(KeySlot::Huk);
+        assert_eq!(val.0, 0x0020_0101);
+        assert_eq!(val.key_slot(), Some(KeySlot::Huk));
+        let val = val.set_key_slot(KeySlot::Internal(5));
+        assert_eq!(val.0, 0x00D0_0101);
+        assert_eq!(val.key_slot(), Some(KeySlot::Internal(5)));
+        assert_eq!(val.select_input_key().0, 0x0000_0101);
+        assert_eq!(SymmetricControl(0x0050_0000).key_slot(), None);
+    }
+
+    #[test]
+    fn struct_asymmetric_control_functions() {
+        let val = AsymmetricControl::default()
+            .set_width(64)
+            .set_ecc_operation(EccOperation::PointMultiply);
+        assert_eq!(val.0, 0x2000_0040);
+        assert_eq!(val.width(), 64);
+        assert_eq!(val.ecc_operation(), Some(EccOperation::PointMultiply));
+        let val = val.set_width(128).set_ecc_operation(EccOperation::PointAdd);
+        assert_eq!(val.0, 0x0000_0080);
+        assert_eq!(AsymmetricControl(0xF000_0000).ecc_operation(), None);
+        assert_eq!(CommonControl(0x0000_0020).algorithm(), Some(Algorithm::Rsa));
+    }
+}
@@ -0,0 +1,304 @@
+use super::{Algorithm, Ce, CommonControl, Error, RegisterBlock, SymmetricControl, TaskDescriptor};
+
+/// Bytes generated by one random number task.
+const CHUNK_SIZE: usize = 32;
+
+/// Samples tested before a generator is used.
+const STARTUP_SAMPLES: usize = 1024;
+
+/// Repetition count test cutoff, for 2 bits of entropy per byte and false alarm rate 2^-20.
+const REPETITION_CUTOFF: u16 = 11;
+
+/// Adaptive proportion test window size.
+const PROPORTION_WINDOW: u16 = 512;
+
+/// Adaptive proportion test cutoff, for 2 bits of entropy per byte and false alarm rate 2^-20.
+const PROPORTION_CUTOFF: u16 = 177;
+
+/// Entropy health test that failed.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum HealthTestFailure {
+    /// A byte repeated too many times in a row.
+    RepetitionCount,
+    /// A byte occurred too often in a window of samples.
+    AdaptiveProportion,
+}
+
+/// Continuous health tests of an entropy source.
+///
+/// Implements the repetition count and adaptive proportion tests of NIST SP 800-90B on byte
+/// samples, assuming at least 2 bits of min-entropy per byte. A failure is latched until
+/// [`HealthTest::reset`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct HealthTest {
+    repeated: Option<u8>,
+    repetitions: u16,
+    window_sample: Option<u8>,
+    window_count: u16,
+    window_position: u16,
+    failure: Option<HealthTestFailure>,
+}
+
+impl HealthTest {
+    /// Create health tests with no samples.
+    #[inline]
+    pub const fn new() -> Self {
+        Self {
+            repeated: None,
+            repetitions: 0,
+            window_sample: None,
+            window_count: 0,
+            window_position: 0,
+            failure: None,
+        }
+    }
+    /// Test next `sample`.
+    ///
+    /// # Errors
+    ///
+    /// Returns the failed test, or the earlier failure if tests have failed before.
+    #[inline]
+    pub fn check(&mut self, sample: u8) -> Result<(), HealthTestFailure> {
+        if let Some(failure) = self.failure {
+            return Err(failure);
+        }
+        if self.repeated == Some(sample) {
+            self.repetitions += 1;
+            if self.repetitions >= REPETITION_CUTOFF {
+                self.failure = Some(HealthTestFailure::RepetitionCount);
+            }
+        } else {
+            self.repeated = Some(sample);
+            self.repetitions = 1;
+        }
+        match self.window_sample {
+            None => {
+                self.window_sample = Some(sample);
+                self.window_count = 1;
+                self.window_position = 1;
+            }
+            Some(first) => {
+                if first == sample {
+                    self.window_count += 1;
+                    if self.window_count >= PROPORTION_CUTOFF {
+                        self.failure = Some(HealthTestFailure::AdaptiveProportion);
+                    }
+                }
+                self.window_position += 1;
+                if self.window_position == PROPORTION_WINDOW {
+                    self.window_sample = None;
+                }
+            }
+        }
+        match self.failure {
+            Some(failure) => Err(failure),
+            None => Ok(()),
+        }
+    }
+    /// Get the latched failure, if any.
+    #[inline]
+    pub const fn failure(&self) -> Option<HealthTestFailure> {
+        self.failure
+    }
+    /// Clear samples and latched failure.
+    #[inline]
+    pub fn reset(&mut self) {
+        *self = Self::new();
+    }
+}
+
+impl Default for HealthTest {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Random number task descriptor with its output.
+#[repr(C, align(32))]
+struct TrngTask {
+    descriptor: TaskDescriptor,
+    output: [u8; CHUNK_SIZE],
+}
+
+/// True random number generator of crypto engine.
+pub struct Trng<'a, 'b, CE> {
+    ce: &'a mut Ce<'b, CE>,
+    health: HealthTest,
+}
+
+impl<'a, 'b, CE: AsRef<RegisterBlock>> Trng<'a, 'b, CE> {
+    /// Start the random number generator, testing its first 1024 bytes.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::HealthTest`] if the startup samples fail health tests.
+    #[inline]
+    pub fn new(ce: &'a mut Ce<'b, CE>) -> Result<Self, Error> {
+        let mut trng = Self {
+            ce,
+            health: HealthTest::new(),
+        };
+        for _ in 0..STARTUP_SAMPLES / CHUNK_SIZE {
+            trng.generate()?;
+        }
+        Ok(trng)
+    }
+    /// Fill `dest` with random bytes.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::HealthTest`] if generated bytes fail health tests; the generator then
+    /// keeps failing until it's created again.
+    #[inline]
+    pub fn fill(&mut self, dest: &mut [u8]) -> Result<(), Error> {
+        for chunk in dest.chunks_mut(CHUNK_SIZE) {
+            let output = self.generate()?;
+            chunk.copy_from_slice(&output[..chunk.len()]);
+        }
+        Ok(())
+    }
+    /// Release the crypto engine.
+    #[inline]
+    pub fn free(self) -> &'a mut Ce<'b, CE> {
+        self.ce
+    }
+    /// Run a random number task and test its output.
+    #[inline]
+    fn generate(&mut self) -> Result<[u8; CHUNK_SIZE], Error> {
+        if let Some(failure) = self.health.failure() {
+            return Err(Error::HealthTest(failure));
+        }
+        let common = CommonControl::default()
+            .set_algorithm(Algorithm::Trng)
+            .enable_interrupt();
+        let mut task = TrngTask {
+            descriptor: TaskDescriptor::new(common, SymmetricControl::default()),
+            output: [0; CHUNK_SIZE],
+        };
+        let length = CHUNK_SIZE as u32 / 4;
+        task.descriptor.destination[0].address = task.output.as_mut_ptr() as usize as u32;
+        task.descriptor.destination[0].length = length;
+        task.descriptor.data_length = length;
+        self.ce.run(&task.descriptor)?;
+        for &sample in &task.output {
+            self.health.check(sample).map_err(Error::HealthTest)?;
+        }
+        Ok(task.output)
+    }
+}
+
+#[cfg(feature = "rand_core")]
+impl<CE: AsRef<RegisterBlock>> Trng<'_, '_, CE> {
+    /// Create software random number generator `R` seeded from this generator.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::HealthTest`] if seed bytes fail health tests.
+    #[inline]
+    pub fn seed<R: rand_core::SeedableRng>(&mut self) -> Result<R, Error> {
+        let mut seed = R::Seed::default();
+        self.fill(seed.as_mut())?;
+        Ok(R::from_seed(seed))
+    }
+}
+
+#[cfg(feature = "rand_core")]
+impl<CE: AsRef<RegisterBlock>> rand_core::RngCore for Trng<'_, '_, CE> {
+    #[inline]
+    fn next_u32(&mut self) -> u32 {
+        rand_core::impls::next_u32_via_fill(self)
+    }
+    #[inline]
+    fn next_u64(&mut self) -> u64 {
+        rand_core::impls::next_u64_via_fill(self)
+    }
+    /// Fill `dest` with random bytes.
+    ///
+    /// # Panics
+    ///
+    /// Panics if generator fails; use `try_fill_bytes` to handle the error.
+    #[inline]
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        if let Err(e) = self.fill(dest) {
+            panic!("crypto engine random number task failed: {:?}", e);
+        }
+    }
+    #[inline]
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), rand_core::Error> {
+        self.fill(dest).map_err(|e| {
+            let code = rand_core::Error::CUSTOM_START
+                + match e {
+                    Error::UnsupportedAlgorithm => 0,
+                    Error::InvalidLength => 1,
+                    Error::KeyError => 2,
+                    Error::Timeout => 3,
+                    Error::HealthTest(HealthTestFailure::RepetitionCount) => 4,
+                    Error::HealthTest(HealthTestFailure::AdaptiveProportion) => 5,
+                    Error::InvalidSignature => 6,
+                };
+            core::num::NonZeroU32::new(code).unwrap().into()
+        })
+    }
+}
+
+#[cfg(feature = "rand_core")]
+impl<CE: AsRef<RegisterBlock>> rand_core::CryptoRng for Trng<'_, '_, CE> {}
+
+#[cfg(test)]
+mod tests {
+    use super::{HealthTest, HealthTestFailure};
+
+    #[test]
+    fn struct_health_test_repetition() {
+        let mut health = HealthTest::new();
+        for i in 0..=255u8 {
+            assert_eq!(health.check(i), Ok(()));
+        }
+        for _ in 0..10 {
+            assert_eq!(health.check(0x55), Ok(()));
+        }
+        assert_eq!(health.check(0x55), Err(HealthTestFailure::RepetitionCount));
+        // failure is latched
+        assert_eq!(health.check(0xAA), Err(HealthTestFailure::RepetitionCount));
+        health.reset();
+        assert_eq!(health.failure(), None);
+        assert_eq!(health.check(0x55), Ok(()));
+    }
+
+    #[test]
+    fn struct_health_test_proportion() {
+        // first byte of window occurs at every other sample, never repeating in a row
+        let mut health = HealthTest::new();
+        let mut count = 0;
+        let mut i = 0u32;
+        loop {
+            let sample = match i % 2 {
+                0 => 0x00,
+                _ => (i % 251) as u8 + 1,
+            };
+            if sample == 0x00 {
+                count += 1;
+            }
+            let result = health.check(sample);
+            if count < 177 {
+                assert_eq!(result, Ok(()));
+            } else {
+                assert_eq!(result, Err(HealthTestFailure::AdaptiveProportion));
+                break;
+            }
+            i += 1;
+        }
+        assert!(i < 512);
+
+        // windows restart, so a byte slightly below cutoff in each window passes
+        let mut health = HealthTest::new();
+        for i in 0..4096u32 {
+            let sample = match i % 4 {
+                0 => 0x00,
+                _ => (i % 200) as u8 + 1,
+            };
+            assert_eq!(health.check(sample), Ok(()));
+        }
+    }
+}
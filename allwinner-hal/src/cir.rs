@@ -0,0 +1,298 @@
+//! Consumer infrared (CIR) receiver.
+//!
+//! IR-RX block samples demodulated output of an infrared receiver module and pushes
+//! run-length encoded levels into a FIFO. [`Cir`] merges those samples into pulses and
+//! feeds them to protocol decoders, producing NEC and RC-5 scancodes.
+//!
+//! [`IrTransmitter`] sends NEC and Pronto codes by modulating a carrier on a PWM channel.
+
+mod decode;
+mod register;
+mod transmit;
+pub use decode::*;
+pub use register::*;
+pub use transmit::*;
+
+use crate::r_ccu::{self, IrRxClock, IrRxClockSource};
+use atomic_waker::AtomicWaker;
+use core::{future::poll_fn, task::Poll};
+
+/// Length of one sample in microseconds, at 8-MHz module clock divided by 64.
+pub const SAMPLE_MICROS: u32 = 8;
+
+/// Receiver interrupt handler, called with each decoded scancode.
+pub type Handler = fn(scancode: Scancode);
+
+static WAKER: AtomicWaker = AtomicWaker::new();
+
+const INTERRUPTS: [Interrupt; 3] = [
+    Interrupt::Overrun,
+    Interrupt::PacketEnd,
+    Interrupt::FifoAvailable,
+];
+
+/// CIR receiver interrupt entry point.
+///
+/// Call this function from IR-RX interrupt handler when receiving with [`Cir::receive`].
+/// Receiver interrupts are disabled and the waiting task is woken; pending status is left
+/// for that task to handle.
+#[inline]
+pub fn on_interrupt(cir: &RegisterBlock) {
+    let enabled = cir.rx_int.read();
+    let status = cir.rx_status.read();
+    if INTERRUPTS
+        .iter()
+        .any(|&i| enabled.is_interrupt_enabled(i) && status.has_interrupt(i))
+    {
+        unsafe {
+            cir.rx_int.modify(|val| {
+                INTERRUPTS
+                    .iter()
+                    .fold(val, |val, &i| val.disable_interrupt(i))
+            })
+        };
+        WAKER.wake();
+    }
+}
+
+/// Valid CIR receiver input pad.
+pub trait Receive {}
+
+/// CIR receiver configuration.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Config {
+    /// Pulses shorter than this number of samples are discarded as noise, at most 63.
+    pub noise_threshold: u8,
+    /// Packet ends when input stays idle for this many microseconds.
+    pub idle_micros: u32,
+}
+
+impl Default for Config {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            noise_threshold: 1,
+            idle_micros: 10_000,
+        }
+    }
+}
+
+/// Managed CIR receiver structure with peripheral and pad.
+pub struct Cir<CIR, PAD: Receive> {
+    cir: CIR,
+    pad: PAD,
+    decoder: Decoder,
+    pulse: Option<Pulse>,
+    handler: Option<Handler>,
+}
+
+impl<CIR: AsRef<RegisterBlock>, PAD: Receive> Cir<CIR, PAD> {
+    /// Create a CIR receiver sampling every 8 microseconds.
+    #[inline]
+    pub fn new(cir: CIR, pad: PAD, config: Config, r_ccu: &r_ccu::RegisterBlock) -> Self {
+        unsafe {
+            r_ccu.ir_rx_bgr.modify(|val| val.assert_reset().gate_mask());
+            // 24-MHz 'HOSC' divided by 3 gives 8-MHz module clock.
+            r_ccu.ir_rx_clock.write(
+                IrRxClock::default()
+                    .set_clock_source(IrRxClockSource::Hosc)
+                    .set_factor_n(0)
+                    .set_factor_m(2)
+                    .unmask_clock(),
+            );
+            r_ccu
+                .ir_rx_bgr
+                .modify(|val| val.deassert_reset().gate_pass());
+        }
+        let regs = cir.as_ref();
+        unsafe {
+            regs.control.write(Control::default());
+            regs.cir_config.write(
+                CirConfig::default()
+                    .set_sample_clock(SampleClock::Div64)
+                    .set_noise_threshold(config.noise_threshold)
+                    .set_idle_threshold(idle_threshold(config.idle_micros)),
+            );
+            regs.rx_config.write(RxConfig::default().invert_polarity());
+            regs.rx_int
+                .write(RxInt::default().set_trigger_level((FIFO_DEPTH / 2 - 1) as u8));
+            regs.rx_status.write(
+                INTERRUPTS
+                    .iter()
+                    .fold(RxStatus::default(), |val, &i| val.clear_interrupt(i)),
+            );
+            regs.control.write(
+                Control::default()
+                    .set_cir_mode()
+                    .enable_receiver()
+                    .enable_global(),
+            );
+        }
+        Self {
+            cir,
+            pad,
+            decoder: Decoder::new(),
+            pulse: None,
+            handler: None,
+        }
+    }
+    /// Decode received samples, returning the next scancode if any.
+    ///
+    /// Samples after the returned scancode are left in FIFO for next call.
+    #[inline]
+    pub fn poll(&mut self) -> Option<Scancode> {
+        loop {
+            let regs = self.cir.as_ref();
+            let status = regs.rx_status.read();
+            if status.has_interrupt(Interrupt::Overrun) {
+                unsafe {
+                    regs.rx_status
+                        .write(RxStatus::default().clear_interrupt(Interrupt::Overrun))
+                };
+                self.decoder.reset();
+                self.pulse = None;
+            }
+            if status.fifo_count() > 0 {
+                let sample = regs.rx_fifo.read();
+                if let Some(code) = self.push_sample(sample as u8) {
+                    return Some(code);
+                }
+                continue;
+            }
+            unsafe {
+                regs.rx_status
+                    .write(RxStatus::default().clear_interrupt(Interrupt::FifoAvailable))
+            };
+            if !status.has_interrupt(Interrupt::PacketEnd) {
+                return None;
+            }
+            unsafe {
+                regs.rx_status
+                    .write(RxStatus::default().clear_interrupt(Interrupt::PacketEnd))
+            };
+            let pulse = self.pulse.take();
+            let code = pulse.and_then(|pulse| self.decoder.feed(pulse));
+            if let Some(code) = code.or_else(|| self.decoder.end()) {
+                return Some(code);
+            }
+        }
+    }
+    /// Wait for the next scancode using receiver interrupts.
+    ///
+    /// Requires [`on_interrupt`] to be called from IR-RX interrupt handler.
+    #[inline]
+    pub async fn receive(&mut self) -> Scancode {
+        loop {
+            if let Some(code) = self.poll() {
+                return code;
+            }
+            self.enable_interrupts();
+            poll_fn(|cx| {
+                WAKER.register(cx.waker());
+                if self.is_pending() {
+                    Poll::Ready(())
+                } else {
+                    Poll::Pending
+                }
+            })
+            .await;
+        }
+    }
+    /// Set handler called by [`Cir::handle_interrupt`] for each decoded scancode.
+    #[inline]
+    pub fn set_handler(&mut self, handler: Option<Handler>) {
+        self.handler = handler;
+    }
+    /// Decode all received samples and call registered handler for each scancode.
+    ///
+    /// Call this function from IR-RX interrupt handler after [`Cir::enable_interrupts`].
+    #[inline]
+    pub fn handle_interrupt(&mut self) {
+        while let Some(code) = self.poll() {
+            if let Some(handler) = self.handler {
+                handler(code);
+            }
+        }
+    }
+    /// Enable FIFO available, packet end and overrun interrupts.
+    #[inline]
+    pub fn enable_interrupts(&mut self) {
+        unsafe {
+            self.cir.as_ref().rx_int.modify(|val| {
+                INTERRUPTS
+                    .iter()
+                    .fold(val, |val, &i| val.enable_interrupt(i))
+            })
+        };
+    }
+    /// Disable all receiver interrupts.
+    #[inline]
+    pub fn disable_interrupts(&mut self) {
+        unsafe {
+            self.cir.as_ref().rx_int.modify(|val| {
+                INTERRUPTS
+                    .iter()
+                    .fold(val, |val, &i| val.disable_interrupt(i))
+            })
+        };
+    }
+    /// Check if any receiver interrupt is pending.
+    #[inline]
+    pub fn is_pending(&self) -> bool {
+        let status = self.cir.as_ref().rx_status.read();
+        INTERRUPTS.iter().any(|&i| status.has_interrupt(i))
+    }
+    /// Close receiver and release peripheral and pad.
+    #[inline]
+    pub fn free(self, r_ccu: &r_ccu::RegisterBlock) -> (CIR, PAD) {
+        unsafe {
+            self.cir.as_ref().rx_int.write(RxInt::default());
+            self.cir.as_ref().control.write(Control::default());
+            r_ccu.ir_rx_bgr.modify(|val| val.assert_reset().gate_mask());
+            r_ccu.ir_rx_clock.modify(|val| val.mask_clock());
+        }
+        (self.cir, self.pad)
+    }
+    /// Merge FIFO sample into current pulse, feeding finished pulse to decoder.
+    #[inline]
+    fn push_sample(&mut self, sample: u8) -> Option<Scancode> {
+        let mark = sample & 0x80 != 0;
+        let micros = ((sample & 0x7F) as u32 + 1) * SAMPLE_MICROS;
+        match &mut self.pulse {
+            Some(pulse) if pulse.mark == mark => {
+                pulse.micros += micros;
+                None
+            }
+            current => {
+                let finished = current.replace(Pulse { mark, micros });
+                finished.and_then(|pulse| self.decoder.feed(pulse))
+            }
+        }
+    }
+}
+
+/// Convert idle time in microseconds into idle threshold register value.
+#[inline]
+const fn idle_threshold(micros: u32) -> u8 {
+    let units = micros.div_ceil(128 * SAMPLE_MICROS);
+    if units == 0 {
+        0
+    } else if units > 256 {
+        255
+    } else {
+        (units - 1) as u8
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::idle_threshold;
+
+    #[test]
+    fn function_idle_threshold() {
+        assert_eq!(idle_threshold(0), 0);
+        assert_eq!(idle_threshold(1024), 0);
+        assert_eq!(idle_threshold(10_000), 9);
+        assert_eq!(idle_threshold(1_000_000), 255);
+    }
+}
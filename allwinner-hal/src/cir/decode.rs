@@ -0,0 +1,418 @@
+//! Infrared remote protocol decoders.
+
+/// One level interval of demodulated infrared signal.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Pulse {
+    /// True for carrier present (mark), false for carrier absent (space).
+    pub mark: bool,
+    /// Length of this interval in microseconds.
+    pub micros: u32,
+}
+
+impl Pulse {
+    /// Create a mark of `micros` microseconds.
+    #[inline]
+    pub const fn mark(micros: u32) -> Self {
+        Self { mark: true, micros }
+    }
+    /// Create a space of `micros` microseconds.
+    #[inline]
+    pub const fn space(micros: u32) -> Self {
+        Self {
+            mark: false,
+            micros,
+        }
+    }
+}
+
+/// Decoded remote key press.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Scancode {
+    /// NEC protocol frame.
+    Nec {
+        /// 8-bit address, or 16-bit address of extended NEC.
+        address: u16,
+        /// Command byte.
+        command: u8,
+        /// True for repeat code sent while key is held.
+        repeat: bool,
+    },
+    /// Philips RC-5 protocol frame.
+    Rc5 {
+        /// 5-bit address.
+        address: u8,
+        /// 7-bit command, including field bit.
+        command: u8,
+        /// Toggle bit, flipped on each new key press.
+        toggle: bool,
+    },
+}
+
+const NEC_LEADER_MARK: u32 = 9000;
+const NEC_LEADER_SPACE: u32 = 4500;
+const NEC_REPEAT_SPACE: u32 = 2250;
+const NEC_BIT_MARK: u32 = 562;
+const NEC_ZERO_SPACE: u32 = 562;
+const NEC_ONE_SPACE: u32 = 1687;
+const RC5_HALF_BIT: u32 = 889;
+const RC5_HALF_BITS: u8 = 28;
+
+/// Check if `micros` is within 30 percent of `nominal`.
+#[inline]
+const fn matches(micros: u32, nominal: u32) -> bool {
+    micros * 10 >= nominal * 7 && micros * 10 <= nominal * 13
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum NecState {
+    Idle,
+    LeaderSpace,
+    RepeatMark,
+    BitMark,
+    BitSpace,
+}
+
+/// NEC protocol decoder.
+#[derive(Clone, Debug)]
+pub struct NecDecoder {
+    state: NecState,
+    bits: u32,
+    count: u8,
+    last: Option<(u16, u8)>,
+}
+
+impl NecDecoder {
+    /// Create an idle NEC decoder.
+    #[inline]
+    pub const fn new() -> Self {
+        Self {
+            state: NecState::Idle,
+            bits: 0,
+            count: 0,
+            last: None,
+        }
+    }
+    /// Feed one pulse, returning a scancode when a frame or repeat code completes.
+    #[inline]
+    pub fn feed(&mut self, pulse: Pulse) -> Option<Scancode> {
+        let Pulse { mark, micros } = pulse;
+        match (self.state, mark) {
+            (NecState::LeaderSpace, false) if matches(micros, NEC_LEADER_SPACE) => {
+                self.bits = 0;
+                self.count = 0;
+                self.state = NecState::BitMark;
+            }
+            (NecState::LeaderSpace, false) if matches(micros, NEC_REPEAT_SPACE) => {
+                self.state = NecState::RepeatMark;
+            }
+            (NecState::RepeatMark, true) if matches(micros, NEC_BIT_MARK) => {
+                self.state = NecState::Idle;
+                let (address, command) = self.last?;
+                return Some(Scancode::Nec {
+                    address,
+                    command,
+                    repeat: true,
+                });
+            }
+            (NecState::BitMark, true) if matches(micros, NEC_BIT_MARK) => {
+                if self.count == 32 {
+                    self.state = NecState::Idle;
+                    return self.frame();
+                }
+                self.state = NecState::BitSpace;
+            }
+            (NecState::BitSpace, false) if matches(micros, NEC_ZERO_SPACE) => {
+                self.count += 1;
+                self.state = NecState::BitMark;
+            }
+            (NecState::BitSpace, false) if matches(micros, NEC_ONE_SPACE) => {
+                self.bits |= 1 << self.count;
+                self.count += 1;
+                self.state = NecState::BitMark;
+            }
+            (_, true) if matches(micros, NEC_LEADER_MARK) => {
+                self.state = NecState::LeaderSpace;
+            }
+            _ => self.state = NecState::Idle,
+        }
+        None
+    }
+    /// Abort current frame and forget last received code.
+    #[inline]
+    pub fn reset(&mut self) {
+        *self = Self::new();
+    }
+    #[inline]
+    fn frame(&mut self) -> Option<Scancode> {
+        let [address, address_inv, command, command_inv] = self.bits.to_le_bytes();
+        if command != !command_inv {
+            return None;
+        }
+        let address = if address == !address_inv {
+            address as u16
+        } else {
+            self.bits as u16
+        };
+        self.last = Some((address, command));
+        Some(Scancode::Nec {
+            address,
+            command,
+            repeat: false,
+        })
+    }
+}
+
+impl Default for NecDecoder {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Philips RC-5 protocol decoder.
+///
+/// RC-5 frames are Manchester encoded; the decoder collects half-bit levels and reads
+/// each bit from level of its second half, where a mark means 1.
+#[derive(Clone, Debug)]
+pub struct Rc5Decoder {
+    halves: u32,
+    count: u8,
+}
+
+impl Rc5Decoder {
+    /// Create an idle RC-5 decoder.
+    #[inline]
+    pub const fn new() -> Self {
+        Self {
+            halves: 0,
+            count: 0,
+        }
+    }
+    /// Feed one pulse, returning a scancode when a frame completes.
+    #[inline]
+    pub fn feed(&mut self, pulse: Pulse) -> Option<Scancode> {
+        let Pulse { mark, micros } = pulse;
+        let n = if matches(micros, RC5_HALF_BIT) {
+            1
+        } else if matches(micros, 2 * RC5_HALF_BIT) {
+            2
+        } else {
+            // a long space may contain trailing space half of last bit
+            return if mark { self.abort() } else { self.end() };
+        };
+        if self.count == 0 {
+            if !mark {
+                return None;
+            }
+            // leading space half of start bit is not visible on idle line
+            self.count = 1;
+        }
+        for _ in 0..n {
+            if self.count == RC5_HALF_BITS {
+                return self.abort();
+            }
+            self.push(mark);
+        }
+        if self.count == RC5_HALF_BITS {
+            return self.frame();
+        }
+        None
+    }
+    /// Finish current frame on packet end, returning a scancode if it was complete.
+    #[inline]
+    pub fn end(&mut self) -> Option<Scancode> {
+        if self.count == RC5_HALF_BITS - 1 && self.halves & (1 << (RC5_HALF_BITS - 2)) != 0 {
+            self.push(false);
+            return self.frame();
+        }
+        self.abort()
+    }
+    /// Abort current frame.
+    #[inline]
+    pub fn reset(&mut self) {
+        *self = Self::new();
+    }
+    #[inline]
+    fn push(&mut self, mark: bool) {
+        if mark {
+            self.halves |= 1 << self.count;
+        }
+        self.count += 1;
+    }
+    #[inline]
+    fn abort(&mut self) -> Option<Scancode> {
+        self.reset();
+        None
+    }
+    #[inline]
+    fn frame(&mut self) -> Option<Scancode> {
+        let mut bits = 0u16;
+        for i in 0..RC5_HALF_BITS / 2 {
+            let first = self.halves & (1 << (2 * i)) != 0;
+            let second = self.halves & (1 << (2 * i + 1)) != 0;
+            if first == second {
+                return self.abort();
+            }
+            bits = (bits << 1) | second as u16;
+        }
+        self.reset();
+        // S1 is always 1; inverted S2 extends command to 7 bits
+        if bits & (1 << 13) == 0 {
+            return None;
+        }
+        let field = (!bits >> 12) & 1;
+        Some(Scancode::Rc5 {
+            address: ((bits >> 6) & 0x1F) as u8,
+            command: ((field << 6) | (bits & 0x3F)) as u8,
+            toggle: bits & (1 << 11) != 0,
+        })
+    }
+}
+
+impl Default for Rc5Decoder {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Decoder trying NEC and RC-5 protocols on the same pulse stream.
+#[derive(Clone, Debug, Default)]
+pub struct Decoder {
+    nec: NecDecoder,
+    rc5: Rc5Decoder,
+}
+
+impl Decoder {
+    /// Create an idle decoder.
+    #[inline]
+    pub const fn new() -> Self {
+        Self {
+            nec: NecDecoder::new(),
+            rc5: Rc5Decoder::new(),
+        }
+    }
+    /// Feed one pulse, returning a scancode when any protocol completes a frame.
+    #[inline]
+    pub fn feed(&mut self, pulse: Pulse) -> Option<Scancode> {
+        let nec = self.nec.feed(pulse);
+        let rc5 = self.rc5.feed(pulse);
+        nec.or(rc5)
+    }
+    /// Finish current frame on packet end.
+    #[inline]
+    pub fn end(&mut self) -> Option<Scancode> {
+        self.rc5.end()
+    }
+    /// Abort current frames of all protocols.
+    #[inline]
+    pub fn reset(&mut self) {
+        self.nec.reset();
+        self.rc5.reset();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Decoder, NecDecoder, Pulse, Rc5Decoder, Scancode};
+
+    fn nec_frame(bits: u32, out: &mut [Pulse; 67]) {
+        out[0] = Pulse::mark(9000);
+        out[1] = Pulse::space(4500);
+        for i in 0..32 {
+            out[2 + 2 * i] = Pulse::mark(560);
+            let space = if bits & (1 << i) != 0 { 1690 } else { 560 };
+            out[3 + 2 * i] = Pulse::space(space);
+        }
+        out[66] = Pulse::mark(560);
+    }
+
+    fn feed_all(decoder: &mut Decoder, pulses: &[Pulse]) -> Option<Scancode> {
+        let mut ans = None;
+        for &pulse in pulses {
+            if let Some(code) = decoder.feed(pulse) {
+                assert!(ans.is_none());
+                ans = Some(code);
+            }
+        }
+        ans.or_else(|| decoder.end())
+    }
+
+    #[test]
+    fn struct_nec_decoder() {
+        let mut pulses = [Pulse::mark(0); 67];
+        let mut decoder = NecDecoder::new();
+        // address 0x04, command 0x08
+        nec_frame(0xF708_FB04, &mut pulses);
+        let codes: Option<Scancode> = pulses.iter().filter_map(|&p| decoder.feed(p)).next();
+        assert_eq!(
+            codes,
+            Some(Scancode::Nec {
+                address: 0x04,
+                command: 0x08,
+                repeat: false
+            })
+        );
+        // repeat code
+        assert_eq!(decoder.feed(Pulse::space(40000)), None);
+        assert_eq!(decoder.feed(Pulse::mark(9000)), None);
+        assert_eq!(decoder.feed(Pulse::space(2250)), None);
+        assert_eq!(
+            decoder.feed(Pulse::mark(560)),
+            Some(Scancode::Nec {
+                address: 0x04,
+                command: 0x08,
+                repeat: true
+            })
+        );
+        // extended address
+        nec_frame(0xBF40_1234, &mut pulses);
+        let codes = pulses.iter().filter_map(|&p| decoder.feed(p)).next();
+        assert_eq!(
+            codes,
+            Some(Scancode::Nec {
+                address: 0x1234,
+                command: 0x40,
+                repeat: false
+            })
+        );
+        // corrupted command
+        nec_frame(0xF709_FB04, &mut pulses);
+        assert_eq!(pulses.iter().filter_map(|&p| decoder.feed(p)).next(), None);
+    }
+
+    #[test]
+    fn struct_rc5_decoder() {
+        // S1=1, S2=1, toggle=1, address 0x05, command 0x34
+        let bits: u16 = (0b11 << 12) | (1 << 11) | (0x05 << 6) | 0x34;
+        let mut pulses = [Pulse::mark(0); 28];
+        let mut len = 0;
+        let mut level = None;
+        for i in (0..14).rev() {
+            let one = bits & (1 << i) != 0;
+            for mark in [!one, one] {
+                if level == Some(mark) {
+                    pulses[len - 1].micros += 889;
+                } else {
+                    pulses[len] = Pulse { mark, micros: 889 };
+                    len += 1;
+                    level = Some(mark);
+                }
+            }
+        }
+        // leading space is invisible, trailing space merges into idle line
+        let pulses = &pulses[1..len - 1];
+        let mut decoder = Decoder::new();
+        assert_eq!(
+            feed_all(&mut decoder, pulses),
+            Some(Scancode::Rc5 {
+                address: 0x05,
+                command: 0x34,
+                toggle: true
+            })
+        );
+        let mut decoder = Rc5Decoder::new();
+        assert_eq!(decoder.feed(Pulse::mark(889)), None);
+        assert_eq!(decoder.feed(Pulse::space(5000)), None);
+    }
+}
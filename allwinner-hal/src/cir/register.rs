@@ -0,0 +1,346 @@
+use volatile_register::{RO, RW};
+
+/// Depth of CIR receiver FIFO in samples.
+pub const FIFO_DEPTH: usize = 64;
+
+/// Consumer infrared receiver registers.
+#[repr(C)]
+pub struct RegisterBlock {
+    /// 0x00 - CIR Receiver Control Register.
+    pub control: RW<Control>,
+    _reserved0: [u32; 3],
+    /// 0x10 - CIR Receiver Pulse Configure Register.
+    pub rx_config: RW<RxConfig>,
+    _reserved1: [u32; 3],
+    /// 0x20 - CIR Receiver FIFO Register.
+    pub rx_fifo: RO<u32>,
+    _reserved2: [u32; 2],
+    /// 0x2C - CIR Receiver Interrupt Control Register.
+    pub rx_int: RW<RxInt>,
+    /// 0x30 - CIR Receiver Status Register.
+    pub rx_status: RW<RxStatus>,
+    /// 0x34 - CIR Receiver Configure Register.
+    pub cir_config: RW<CirConfig>,
+}
+
+/// CIR receiver control register.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct Control(u32);
+
+impl Control {
+    const MD: u32 = 0x3 << 4;
+    const RXEN: u32 = 1 << 1;
+    const GEN: u32 = 1 << 0;
+
+    /// If receiver works in CIR mode.
+    #[inline]
+    pub const fn is_cir_mode(self) -> bool {
+        self.0 & Self::MD == Self::MD
+    }
+    /// Set receiver to CIR mode.
+    #[inline]
+    pub const fn set_cir_mode(self) -> Self {
+        Self(self.0 | Self::MD)
+    }
+    /// If receiver block is enabled.
+    #[inline]
+    pub const fn is_receiver_enabled(self) -> bool {
+        self.0 & Self::RXEN != 0
+    }
+    /// Enable receiver block.
+    #[inline]
+    pub const fn enable_receiver(self) -> Self {
+        Self(self.0 | Self::RXEN)
+    }
+    /// Disable receiver block.
+    #[inline]
+    pub const fn disable_receiver(self) -> Self {
+        Self(self.0 & !Self::RXEN)
+    }
+    /// If global CIR function is enabled.
+    #[inline]
+    pub const fn is_global_enabled(self) -> bool {
+        self.0 & Self::GEN != 0
+    }
+    /// Enable global CIR function.
+    #[inline]
+    pub const fn enable_global(self) -> Self {
+        Self(self.0 | Self::GEN)
+    }
+    /// Disable global CIR function.
+    #[inline]
+    pub const fn disable_global(self) -> Self {
+        Self(self.0 & !Self::GEN)
+    }
+}
+
+impl Default for Control {
+    #[inline]
+    fn default() -> Self {
+        Self(0x0000_0000)
+    }
+}
+
+/// CIR receiver pulse configure register.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct RxConfig(u32);
+
+impl RxConfig {
+    const RPPI: u32 = 1 << 2;
+
+    /// If input signal polarity is inverted.
+    #[inline]
+    pub const fn is_polarity_inverted(self) -> bool {
+        self.0 & Self::RPPI != 0
+    }
+    /// Invert input signal polarity, for active-low IR receiver modules.
+    #[inline]
+    pub const fn invert_polarity(self) -> Self {
+        Self(self.0 | Self::RPPI)
+    }
+    /// Keep input signal polarity.
+    #[inline]
+    pub const fn keep_polarity(self) -> Self {
+        Self(self.0 & !Self::RPPI)
+    }
+}
+
+impl Default for RxConfig {
+    #[inline]
+    fn default() -> Self {
+        Self(0x0000_0004)
+    }
+}
+
+/// CIR receiver interrupt.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Interrupt {
+    /// Receiver FIFO overrun.
+    Overrun = 0,
+    /// Receiver packet end, raised when input stays idle beyond idle threshold.
+    PacketEnd = 1,
+    /// Receiver FIFO holds more samples than trigger level.
+    FifoAvailable = 4,
+}
+
+/// CIR receiver interrupt control register.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct RxInt(u32);
+
+impl RxInt {
+    const RAL: u32 = 0x3F << 8;
+
+    /// Check if `interrupt` is enabled.
+    #[inline]
+    pub const fn is_interrupt_enabled(self, interrupt: Interrupt) -> bool {
+        self.0 & (1 << interrupt as u32) != 0
+    }
+    /// Enable `interrupt`.
+    #[inline]
+    pub const fn enable_interrupt(self, interrupt: Interrupt) -> Self {
+        Self(self.0 | (1 << interrupt as u32))
+    }
+    /// Disable `interrupt`.
+    #[inline]
+    pub const fn disable_interrupt(self, interrupt: Interrupt) -> Self {
+        Self(self.0 & !(1 << interrupt as u32))
+    }
+    /// Get FIFO trigger level; FIFO available is raised above this number of samples.
+    #[inline]
+    pub const fn trigger_level(self) -> u8 {
+        ((self.0 & Self::RAL) >> 8) as u8
+    }
+    /// Set FIFO trigger level; FIFO available is raised above this number of samples.
+    #[inline]
+    pub const fn set_trigger_level(self, val: u8) -> Self {
+        Self((self.0 & !Self::RAL) | ((val as u32 & 0x3F) << 8))
+    }
+}
+
+impl Default for RxInt {
+    #[inline]
+    fn default() -> Self {
+        Self(0x0000_0000)
+    }
+}
+
+/// CIR receiver status register.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct RxStatus(u32);
+
+impl RxStatus {
+    const RAC: u32 = 0x7F << 8;
+    const STAT: u32 = 1 << 7;
+
+    /// Check if `interrupt` is pending.
+    #[inline]
+    pub const fn has_interrupt(self, interrupt: Interrupt) -> bool {
+        self.0 & (1 << interrupt as u32) != 0
+    }
+    /// Clear pending `interrupt` on write.
+    #[inline]
+    pub const fn clear_interrupt(self, interrupt: Interrupt) -> Self {
+        Self(self.0 | (1 << interrupt as u32))
+    }
+    /// Check if receiver is busy receiving a packet.
+    #[inline]
+    pub const fn is_busy(self) -> bool {
+        self.0 & Self::STAT != 0
+    }
+    /// Get number of samples in receiver FIFO.
+    #[inline]
+    pub const fn fifo_count(self) -> u8 {
+        ((self.0 & Self::RAC) >> 8) as u8
+    }
+}
+
+impl Default for RxStatus {
+    #[inline]
+    fn default() -> Self {
+        Self(0x0000_0000)
+    }
+}
+
+/// Sample clock divider of CIR receiver.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum SampleClock {
+    /// Sample at module clock divided by 64.
+    Div64 = 0,
+    /// Sample at module clock divided by 128.
+    Div128 = 1,
+    /// Sample at module clock divided by 256.
+    Div256 = 2,
+    /// Sample at module clock divided by 512.
+    Div512 = 3,
+}
+
+/// CIR receiver configure register.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct CirConfig(u32);
+
+impl CirConfig {
+    const ITHR: u32 = 0xFF << 8;
+    const NTHR: u32 = 0x3F << 2;
+    const SCS: u32 = 0x3;
+
+    /// Get idle threshold, in units of 128 samples.
+    ///
+    /// Packet ends when input stays idle for `128 * (threshold + 1)` samples.
+    #[inline]
+    pub const fn idle_threshold(self) -> u8 {
+        ((self.0 & Self::ITHR) >> 8) as u8
+    }
+    /// Set idle threshold, in units of 128 samples.
+    #[inline]
+    pub const fn set_idle_threshold(self, val: u8) -> Self {
+        Self((self.0 & !Self::ITHR) | ((val as u32) << 8))
+    }
+    /// Get noise threshold in samples; shorter pulses are discarded.
+    #[inline]
+    pub const fn noise_threshold(self) -> u8 {
+        ((self.0 & Self::NTHR) >> 2) as u8
+    }
+    /// Set noise threshold in samples; shorter pulses are discarded.
+    #[inline]
+    pub const fn set_noise_threshold(self, val: u8) -> Self {
+        Self((self.0 & !Self::NTHR) | ((val as u32 & 0x3F) << 2))
+    }
+    /// Get sample clock divider.
+    #[inline]
+    pub const fn sample_clock(self) -> SampleClock {
+        match self.0 & Self::SCS {
+            0 => SampleClock::Div64,
+            1 => SampleClock::Div128,
+            2 => SampleClock::Div256,
+            _ => SampleClock::Div512,
+        }
+    }
+    /// Set sample clock divider.
+    #[inline]
+    pub const fn set_sample_clock(self, val: SampleClock) -> Self {
+        Self((self.0 & !Self::SCS) | val as u32)
+    }
+}
+
+impl Default for CirConfig {
+    #[inline]
+    fn default() -> Self {
+        Self(0x0000_1828)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        CirConfig, Control, Interrupt, RegisterBlock, RxConfig, RxInt, RxStatus, SampleClock,
+    };
+    use core::mem::offset_of;
+
+    #[test]
+    fn offset_cir() {
+        assert_eq!(offset_of!(RegisterBlock, control), 0x00);
+        assert_eq!(offset_of!(RegisterBlock, rx_config), 0x10);
+        assert_eq!(offset_of!(RegisterBlock, rx_fifo), 0x20);
+        assert_eq!(offset_of!(RegisterBlock, rx_int), 0x2C);
+        assert_eq!(offset_of!(RegisterBlock, rx_status), 0x30);
+        assert_eq!(offset_of!(RegisterBlock, cir_config), 0x34);
+    }
+
+    #[test]
+    fn struct_control_functions() {
+        let mut val = Control::default();
+        val = val.set_cir_mode().enable_receiver().enable_global();
+        assert!(val.is_cir_mode());
+        assert!(val.is_receiver_enabled());
+        assert!(val.is_global_enabled());
+        assert_eq!(val.0, 0x00000033);
+        val = val.disable_receiver().disable_global();
+        assert_eq!(val.0, 0x00000030);
+
+        let val = RxConfig::default();
+        assert!(val.is_polarity_inverted());
+        assert_eq!(val.keep_polarity().0, 0x00000000);
+    }
+
+    #[test]
+    fn struct_rx_int_status_functions() {
+        let mut val = RxInt::default();
+        val = val
+            .enable_interrupt(Interrupt::Overrun)
+            .enable_interrupt(Interrupt::PacketEnd)
+            .enable_interrupt(Interrupt::FifoAvailable)
+            .set_trigger_level(31);
+        assert!(val.is_interrupt_enabled(Interrupt::PacketEnd));
+        assert_eq!(val.trigger_level(), 31);
+        assert_eq!(val.0, 0x00001F13);
+        val = val.disable_interrupt(Interrupt::FifoAvailable);
+        assert_eq!(val.0, 0x00001F03);
+
+        let val = RxStatus(0x00002092);
+        assert!(val.has_interrupt(Interrupt::PacketEnd));
+        assert!(val.has_interrupt(Interrupt::FifoAvailable));
+        assert!(!val.has_interrupt(Interrupt::Overrun));
+        assert!(val.is_busy());
+        assert_eq!(val.fifo_count(), 0x20);
+        let val = RxStatus::default().clear_interrupt(Interrupt::Overrun);
+        assert_eq!(val.0, 0x00000001);
+    }
+
+    #[test]
+    fn struct_cir_config_functions() {
+        let mut val = CirConfig::default();
+        assert_eq!(val.idle_threshold(), 0x18);
+        assert_eq!(val.noise_threshold(), 0xA);
+        assert_eq!(val.sample_clock(), SampleClock::Div64);
+        val = val
+            .set_idle_threshold(9)
+            .set_noise_threshold(1)
+            .set_sample_clock(SampleClock::Div128);
+        assert_eq!(val.0, 0x00000905);
+    }
+}
@@ -0,0 +1,245 @@
+//! Infrared transmitter using PWM carrier generation.
+
+use super::decode::Pulse;
+use crate::pwm::{FrequencyError, Output, PwmChannel};
+use crate::time::{Duration, Instant};
+use embedded_hal::pwm::SetDutyCycle;
+use embedded_time::rate::Hertz;
+
+/// Carrier frequency of NEC and most consumer remotes.
+pub const CARRIER_38KHZ: Hertz = Hertz(38_000);
+
+/// Pulses of NEC repeat code, sent every 108 ms while key is held.
+pub const NEC_REPEAT_PULSES: [Pulse; 3] = [Pulse::mark(9000), Pulse::space(2250), Pulse::mark(562)];
+
+/// Encode an NEC frame; addresses above 0xFF are sent as extended NEC.
+#[inline]
+pub const fn nec_pulses(address: u16, command: u8) -> [Pulse; 67] {
+    let address = if address > 0xFF {
+        address
+    } else {
+        ((!address << 8) & 0xFF00) | address
+    };
+    let bits = ((!command as u32) << 24) | ((command as u32) << 16) | address as u32;
+    let mut ans = [Pulse::mark(562); 67];
+    ans[0] = Pulse::mark(9000);
+    ans[1] = Pulse::space(4500);
+    let mut i = 0;
+    while i < 32 {
+        let space = if bits & (1 << i) != 0 { 1687 } else { 562 };
+        ans[3 + 2 * i] = Pulse::space(space);
+        i += 1;
+    }
+    ans
+}
+
+/// Error parsing a Pronto hex code.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ProntoError {
+    /// Only learned modulated codes, with format word 0000, are supported.
+    UnsupportedFormat,
+    /// Number of words does not match sequence lengths in header.
+    InvalidLength,
+    /// Carrier frequency word is zero.
+    InvalidFrequency,
+}
+
+/// Learned modulated Pronto hex code.
+///
+/// Pronto codes start with a four-word header: format, carrier frequency, and numbers of
+/// burst pairs in once and repeat sequences. Each burst pair counts carrier periods of one
+/// mark and the following space.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Pronto<'a> {
+    words: &'a [u16],
+}
+
+impl<'a> Pronto<'a> {
+    /// Parse Pronto code from its words, as in `[0x0000, 0x006D, 0x0022, 0x0002, ...]`.
+    #[inline]
+    pub fn new(words: &'a [u16]) -> Result<Self, ProntoError> {
+        let [format, frequency, once, repeat, ..] = *words else {
+            return Err(ProntoError::InvalidLength);
+        };
+        if format != 0x0000 {
+            return Err(ProntoError::UnsupportedFormat);
+        }
+        if frequency == 0 {
+            return Err(ProntoError::InvalidFrequency);
+        }
+        if words.len() != 4 + 2 * (once as usize + repeat as usize) {
+            return Err(ProntoError::InvalidLength);
+        }
+        Ok(Self { words })
+    }
+    /// Get carrier frequency.
+    #[inline]
+    pub fn carrier(&self) -> Hertz {
+        // One frequency unit is 0.241246 microseconds of carrier period.
+        Hertz((1_000_000_000_000 / (self.words[1] as u64 * 241_246)) as u32)
+    }
+    /// Pulses sent once on key press.
+    #[inline]
+    pub fn once(&self) -> impl Iterator<Item = Pulse> + 'a {
+        let once = self.words[2] as usize;
+        self.pulses(4, once)
+    }
+    /// Pulses repeated while key is held.
+    #[inline]
+    pub fn repeat(&self) -> impl Iterator<Item = Pulse> + 'a {
+        let once = self.words[2] as usize;
+        let repeat = self.words[3] as usize;
+        self.pulses(4 + 2 * once, repeat)
+    }
+    #[inline]
+    fn pulses(&self, start: usize, pairs: usize) -> impl Iterator<Item = Pulse> + 'a {
+        let unit = self.words[1] as u64 * 241_246;
+        let micros = move |count: u16| (count as u64 * unit / 1_000_000) as u32;
+        self.words[start..start + 2 * pairs]
+            .chunks_exact(2)
+            .flat_map(move |pair| [Pulse::mark(micros(pair[0])), Pulse::space(micros(pair[1]))])
+    }
+}
+
+/// Infrared transmitter modulating a carrier on a PWM channel.
+///
+/// Marks output carrier at 1/3 duty cycle and spaces keep output inactive. Frames are timed
+/// by busy waiting on system counter, so the CPU is occupied during transmission.
+pub struct IrTransmitter<'a, const I: usize, PAD: Output<I>> {
+    channel: PwmChannel<'a, I, PAD>,
+}
+
+impl<'a, const I: usize, PAD: Output<I>> IrTransmitter<'a, I, PAD> {
+    /// Create an infrared transmitter with 38-kHz carrier on PWM `channel`.
+    ///
+    /// The pad should drive an IR LED, active high. Returns [`FrequencyError::OutOfRange`]
+    /// if pair clock of `channel` cannot generate the carrier.
+    #[inline]
+    pub fn new(mut channel: PwmChannel<'a, I, PAD>) -> Result<Self, FrequencyError> {
+        channel.set_frequency(CARRIER_38KHZ)?;
+        channel.set_duty_cycle_fully_off()?;
+        channel.enable();
+        Ok(Self { channel })
+    }
+    /// Set carrier frequency.
+    ///
+    /// Returns [`FrequencyError::OutOfRange`] if `frequency` cannot be generated, keeping
+    /// current carrier.
+    #[inline]
+    pub fn set_carrier(&mut self, frequency: Hertz) -> Result<(), FrequencyError> {
+        self.channel.set_frequency(frequency)
+    }
+    /// Get actual carrier frequency.
+    #[inline]
+    pub fn carrier(&self) -> Hertz {
+        self.channel.frequency()
+    }
+    /// Send `pulses`, leaving output inactive afterwards.
+    #[inline]
+    pub fn send_pulses(&mut self, pulses: impl IntoIterator<Item = Pulse>) {
+        let mut at = Instant::now();
+        for Pulse { mark, micros } in pulses {
+            let _ = if mark {
+                self.channel.set_duty_cycle_fraction(1, 3)
+            } else {
+                self.channel.set_duty_cycle_fully_off()
+            };
+            at += Duration::from_micros(micros as u64);
+            while Instant::now() < at {
+                core::hint::spin_loop();
+            }
+        }
+        let _ = self.channel.set_duty_cycle_fully_off();
+    }
+    /// Send an NEC frame; addresses above 0xFF are sent as extended NEC.
+    #[inline]
+    pub fn send_nec(&mut self, address: u16, command: u8) {
+        self.send_pulses(nec_pulses(address, command));
+    }
+    /// Send an NEC repeat code.
+    #[inline]
+    pub fn send_nec_repeat(&mut self) {
+        self.send_pulses(NEC_REPEAT_PULSES);
+    }
+    /// Send a Pronto code at its carrier frequency, with its repeat sequence sent `repeats` times.
+    ///
+    /// Returns [`FrequencyError::OutOfRange`] without sending if the carrier cannot be
+    /// generated.
+    #[inline]
+    pub fn send_pronto(&mut self, pronto: &Pronto, repeats: usize) -> Result<(), FrequencyError> {
+        self.set_carrier(pronto.carrier())?;
+        self.send_pulses(pronto.once());
+        for _ in 0..repeats {
+            self.send_pulses(pronto.repeat());
+        }
+        Ok(())
+    }
+    /// Stop carrier output and release PWM channel.
+    #[inline]
+    pub fn free(mut self) -> PwmChannel<'a, I, PAD> {
+        let _ = self.channel.set_duty_cycle_fully_off();
+        self.channel
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{NEC_REPEAT_PULSES, Pronto, ProntoError, nec_pulses};
+    use crate::cir::{NecDecoder, Pulse, Scancode};
+
+    #[test]
+    fn function_nec_pulses() {
+        let mut decoder = NecDecoder::new();
+        let decode = |decoder: &mut NecDecoder, pulses: &[Pulse]| {
+            pulses.iter().filter_map(|&p| decoder.feed(p)).next()
+        };
+        assert_eq!(
+            decode(&mut decoder, &nec_pulses(0x04, 0x08)),
+            Some(Scancode::Nec {
+                address: 0x04,
+                command: 0x08,
+                repeat: false
+            })
+        );
+        assert_eq!(
+            decode(&mut decoder, &NEC_REPEAT_PULSES),
+            Some(Scancode::Nec {
+                address: 0x04,
+                command: 0x08,
+                repeat: true
+            })
+        );
+        assert_eq!(
+            decode(&mut decoder, &nec_pulses(0x1234, 0x40)),
+            Some(Scancode::Nec {
+                address: 0x1234,
+                command: 0x40,
+                repeat: false
+            })
+        );
+    }
+
+    #[test]
+    fn struct_pronto() {
+        let words = [
+            0x0000, 0x006D, 0x0001, 0x0001, 0x0157, 0x00AC, 0x0157, 0x0056,
+        ];
+        let pronto = Pronto::new(&words).unwrap();
+        assert_eq!(pronto.carrier().0, 38028);
+        let mut once = pronto.once();
+        assert_eq!(once.next(), Some(Pulse::mark(9019)));
+        assert_eq!(once.next(), Some(Pulse::space(4522)));
+        assert_eq!(once.next(), None);
+        assert_eq!(pronto.repeat().nth(1), Some(Pulse::space(2261)));
+
+        assert_eq!(Pronto::new(&words[..7]), Err(ProntoError::InvalidLength));
+        assert_eq!(
+            Pronto::new(&[0x0100, 0x006D, 0, 0]),
+            Err(ProntoError::UnsupportedFormat)
+        );
+        assert_eq!(
+            Pronto::new(&[0x0000, 0x0000, 0, 0]),
+            Err(ProntoError::InvalidFrequency)
+        );
+    }
+}
@@ -0,0 +1,110 @@
+//! CRC-32 checksum.
+//!
+//! Computes IEEE 802.3 CRC-32, as used by zlib, gzip and boot image formats, over a whole
+//! buffer with [`crc32`] or over data received in pieces with [`Crc32`]:
+//!
+//! ```
+//! use allwinner_hal::crc::{Crc32, crc32};
+//!
+//! let mut crc = Crc32::new();
+//! crc.update(b"12345");
+//! crc.update(b"6789");
+//! assert_eq!(crc.finalize(), crc32(b"123456789"));
+//! ```
+//!
+//! Neither crypto engine nor memory system interface of D1 computes CRC, so the checksum is
+//! computed by the processor bit by bit. It needs no lookup table, keeping flash and cache
+//! footprint small in early boot stages.
+
+/// Reversed CRC-32 polynomial.
+const POLYNOMIAL: u32 = 0xEDB8_8320;
+
+/// Streaming CRC-32 computation.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Crc32 {
+    state: u32,
+}
+
+impl Crc32 {
+    /// Create CRC-32 computation of empty data.
+    #[inline]
+    pub const fn new() -> Self {
+        Self { state: !0 }
+    }
+    /// Feed `data` into the computation.
+    #[inline]
+    pub const fn update(&mut self, data: &[u8]) {
+        self.state = update(self.state, data);
+    }
+    /// Get CRC-32 of data fed so far.
+    #[inline]
+    pub const fn finalize(&self) -> u32 {
+        !self.state
+    }
+    /// Restart computation of empty data.
+    #[inline]
+    pub const fn reset(&mut self) {
+        *self = Self::new();
+    }
+}
+
+impl Default for Crc32 {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Compute CRC-32 of `data`.
+#[inline]
+pub const fn crc32(data: &[u8]) -> u32 {
+    !update(!0, data)
+}
+
+/// Feed `data` into CRC register `state`.
+#[inline]
+const fn update(mut state: u32, data: &[u8]) -> u32 {
+    let mut i = 0;
+    while i < data.len() {
+        state ^= data[i] as u32;
+        let mut bit = 0;
+        while bit < 8 {
+            state = (state >> 1) ^ (POLYNOMIAL & 0u32.wrapping_sub(state & 1));
+            bit += 1;
+        }
+        i += 1;
+    }
+    state
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Crc32, crc32};
+
+    #[test]
+    fn function_crc32() {
+        assert_eq!(crc32(b""), 0x0000_0000);
+        assert_eq!(crc32(b"a"), 0xE8B7_BE43);
+        assert_eq!(crc32(b"123456789"), 0xCBF4_3926);
+        assert_eq!(
+            crc32(b"The quick brown fox jumps over the lazy dog"),
+            0x414F_A339
+        );
+        const CRC: u32 = crc32(b"123456789");
+        assert_eq!(CRC, 0xCBF4_3926);
+    }
+
+    #[test]
+    fn struct_crc32_functions() {
+        let mut crc = Crc32::new();
+        assert_eq!(crc.finalize(), 0x0000_0000);
+        for chunk in b"The quick brown fox jumps over the lazy dog".chunks(5) {
+            crc.update(chunk);
+        }
+        assert_eq!(crc.finalize(), 0x414F_A339);
+        crc.reset();
+        crc.update(b"123456789");
+        assert_eq!(crc.finalize(), 0xCBF4_3926);
+        assert_eq!(Crc32::default(), Crc32::new());
+    }
+}
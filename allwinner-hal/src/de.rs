@@ -0,0 +1,336 @@
+//! Display Engine 2.0 mixer.
+//!
+//! The mixer blends its channels over a background color into frames scanned out by a timing
+//! controller. [`Mixer`] drives mixer 0 with one UI layer showing a linear framebuffer in
+//! memory over opaque black, and routes its output to TCON LCD 0:
+//!
+//! ```ignore
+//! static mut FRAMEBUFFER: [u32; 800 * 480] = [0; 800 * 480];
+//! let mut mixer = Mixer::new(p.de, p.de_mixer0, 800, 480, &p.dpss_top, &p.ccu);
+//! let layer = Layer::new(PixelFormat::Xrgb8888, 800, 480);
+//! mixer.set_layer(&layer, unsafe { addr_of!(FRAMEBUFFER) } as usize);
+//! let mut tcon = Tcon::new(p.tcon_lcd0, pads, timing, Config::default(), &p.ccu)?;
+//! tcon.enable();
+//! ```
+//!
+//! Mixer registers are double buffered; changes made by [`Mixer`] take effect from the next
+//! frame of the timing controller. Framebuffers are read by DMA, and should reside in DRAM.
+//!
+//! With `embedded-graphics-core` feature, [`Framebuffer`] wraps such a buffer as an
+//! `embedded-graphics` draw target, so user interface crates render directly into the buffer
+//! being scanned out:
+//!
+//! ```ignore
+//! let mut fb = Framebuffer::<Rgb888>::new(unsafe { &mut *addr_of_mut!(FRAMEBUFFER) }, 800, 480);
+//! mixer.set_layer(&fb.layer(), fb.address());
+//! fb.clear(Rgb888::WHITE)?;
+//! ```
+//!
+//! Drawing into the scanned out buffer may show half drawn frames. With two framebuffers,
+//! [`Mixer::present`] shows the back buffer from the next frame and waits for vertical blank
+//! of the timing controller, after which the former front buffer is free to draw:
+//!
+//! ```ignore
+//! loop {
+//!     draw(&mut back)?;
+//!     mixer.present(&back, &mut tcon).await;
+//!     core::mem::swap(&mut front, &mut back);
+//! }
+//! ```
+
+#[cfg(feature = "embedded-graphics-core")]
+mod framebuffer;
+mod register;
+#[cfg(feature = "embedded-graphics-core")]
+pub use framebuffer::*;
+pub use register::*;
+
+use crate::{
+    ccu::{self, ClockConfig, ClockGate, DeClockSource, PeriFactorN},
+    tcon::{DeOutput, TopRegisterBlock},
+};
+
+/// Channel index of the UI channel in mixer 0, after its VI channel.
+const UI_CHANNEL: u8 = 1;
+
+/// Opaque black in ARGB8888.
+const BLACK: u32 = 0xFF00_0000;
+
+/// Framebuffer layer of a [`Mixer`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Layer {
+    /// Pixel format of framebuffer.
+    pub format: PixelFormat,
+    /// Layer width in pixels.
+    pub width: u16,
+    /// Layer height in lines.
+    pub height: u16,
+    /// Distance in bytes between the starts of adjacent lines in framebuffer.
+    pub stride: u32,
+    /// Horizontal position of the top left pixel on screen.
+    pub x: u16,
+    /// Vertical position of the top left pixel on screen.
+    pub y: u16,
+    /// Global alpha multiplied with pixel alpha, 255 being opaque.
+    pub alpha: u8,
+}
+
+impl Layer {
+    /// Opaque layer of `width` by `height` pixels at top left of screen, with packed lines.
+    #[inline]
+    pub const fn new(format: PixelFormat, width: u16, height: u16) -> Self {
+        Self {
+            format,
+            width,
+            height,
+            stride: width as u32 * format.bytes_per_pixel() as u32,
+            x: 0,
+            y: 0,
+            alpha: 0xFF,
+        }
+    }
+}
+
+/// Managed display engine mixer 0 structure with peripherals.
+pub struct Mixer<DE, MIXER> {
+    de: DE,
+    mixer: MIXER,
+    width: u16,
+    height: u16,
+}
+
+impl<DE: AsRef<RegisterBlock>, MIXER: AsRef<MixerRegisterBlock>> Mixer<DE, MIXER> {
+    /// Create a mixer with `width` by `height` output to TCON LCD 0, showing background only.
+    ///
+    /// # Panics
+    ///
+    /// Panics if output size is zero or exceeds 4096 in any dimension.
+    #[inline]
+    pub fn new(
+        de: DE,
+        mixer: MIXER,
+        width: u16,
+        height: u16,
+        tcon_top: &TopRegisterBlock,
+        ccu: &ccu::RegisterBlock,
+    ) -> Self {
+        assert!(
+            (1..=4096).contains(&width) && (1..=4096).contains(&height),
+            "output size not supported by mixer"
+        );
+        // note(unsafe): async read and write using ccu registers
+        unsafe {
+            ccu::DE::reconfigure(ccu, DeClockSource::PllPeri2x, 3, PeriFactorN::N1);
+            ccu::DPSSTOP::reset(ccu);
+        }
+        let top = de.as_ref();
+        unsafe {
+            top.divider.modify(|val| val.set_factor_m::<0>(0));
+            top.clock_gating.modify(|val| val.enable::<0>());
+            top.bus_gating.modify(|val| val.enable::<0>());
+            top.reset.modify(|val| val.deassert_reset::<0>());
+            tcon_top
+                .port_select
+                .modify(|val| val.set_de_output::<0>(DeOutput::Lcd0));
+        }
+        let regs = mixer.as_ref();
+        let size = Size::default().set_width(width).set_height(height);
+        unsafe {
+            regs.global.control.write(GlobalControl::default().enable());
+            regs.global.size.write(size);
+            for layer in &regs.ui.layers {
+                layer.attribute.write(LayerAttribute::default());
+            }
+            regs.blender.pipe_control.write(PipeControl::default());
+            regs.blender
+                .route
+                .write(Route::default().set_channel::<0>(UI_CHANNEL));
+            regs.blender.premultiply.write(0);
+            regs.blender.background.write(BLACK);
+            regs.blender.output_size.write(size);
+            regs.blender.output_control.write(0);
+            regs.global
+                .double_buffer
+                .write(DoubleBuffer::default().commit());
+        }
+        Self {
+            de,
+            mixer,
+            width,
+            height,
+        }
+    }
+    /// Show `layer` with framebuffer at `address`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `layer` doesn't fit on screen, or its stride is shorter than a line of pixels.
+    #[inline]
+    pub fn set_layer(&mut self, layer: &Layer, address: usize) {
+        assert!(
+            layer.width >= 1
+                && layer.height >= 1
+                && layer.x as u32 + layer.width as u32 <= self.width as u32
+                && layer.y as u32 + layer.height as u32 <= self.height as u32,
+            "layer exceeds mixer output"
+        );
+        assert!(
+            layer.stride >= layer.width as u32 * layer.format.bytes_per_pixel() as u32,
+            "layer stride shorter than a line"
+        );
+        let regs = self.mixer.as_ref();
+        let ui = &regs.ui.layers[0];
+        let pipe = &regs.blender.pipes[0];
+        let size = Size::default()
+            .set_width(layer.width)
+            .set_height(layer.height);
+        unsafe {
+            ui.size.write(size);
+            ui.coordinate.write(Coordinate::default());
+            ui.pitch.write(layer.stride);
+            ui.top_address.write(address as u32);
+            regs.ui
+                .top_high_address
+                .write(((address as u64 >> 32) & 0xFF) as u32);
+            regs.ui.overlay_size.write(size);
+            ui.attribute.write(
+                LayerAttribute::default()
+                    .set_format(layer.format)
+                    .set_alpha_mode(AlphaMode::Combined)
+                    .set_alpha(layer.alpha)
+                    .enable(),
+            );
+            pipe.input_size.write(size);
+            pipe.coordinate
+                .write(Coordinate::default().set_x(layer.x).set_y(layer.y));
+            regs.blender
+                .pipe_control
+                .modify(|val| val.enable_pipe::<0>());
+        }
+        self.commit();
+    }
+    /// Show framebuffer at `address` on current layer.
+    #[inline]
+    pub fn set_framebuffer(&mut self, address: usize) {
+        let regs = self.mixer.as_ref();
+        unsafe {
+            regs.ui.layers[0].top_address.write(address as u32);
+            regs.ui
+                .top_high_address
+                .write(((address as u64 >> 32) & 0xFF) as u32);
+        }
+        self.commit();
+    }
+    /// Show `framebuffer` on current layer from the next frame, waiting until it is shown.
+    ///
+    /// Layer address is swapped in vertical blank of `tcon`, so the previous framebuffer is
+    /// no longer scanned out when this function returns. `framebuffer` should match the
+    /// layer passed to [`Mixer::set_layer`].
+    ///
+    /// Requires [`tcon::on_interrupt`](crate::tcon::on_interrupt) to be called from TCON LCD
+    /// interrupt handler.
+    #[cfg(feature = "embedded-graphics-core")]
+    #[inline]
+    pub async fn present<C, TCON, PADS>(
+        &mut self,
+        framebuffer: &Framebuffer<'_, C>,
+        tcon: &mut crate::tcon::Tcon<TCON, PADS>,
+    ) where
+        C: FramebufferColor,
+        TCON: AsRef<crate::tcon::RegisterBlock>,
+        PADS: crate::tcon::Pads,
+    {
+        self.set_framebuffer(framebuffer.address());
+        while self.is_commit_pending() {
+            tcon.wait_for_vblank().await;
+        }
+    }
+    /// Move current layer to (`x`, `y`) on screen.
+    ///
+    /// Layer should stay within mixer output.
+    #[inline]
+    pub fn set_position(&mut self, x: u16, y: u16) {
+        let regs = self.mixer.as_ref();
+        unsafe {
+            regs.blender.pipes[0]
+                .coordinate
+                .write(Coordinate::default().set_x(x).set_y(y))
+        };
+        self.commit();
+    }
+    /// Set global alpha of current layer, 255 being opaque.
+    #[inline]
+    pub fn set_alpha(&mut self, alpha: u8) {
+        let regs = self.mixer.as_ref();
+        unsafe {
+            regs.ui.layers[0]
+                .attribute
+                .modify(|val| val.set_alpha(alpha))
+        };
+        self.commit();
+    }
+    /// Hide current layer, showing background only.
+    #[inline]
+    pub fn disable_layer(&mut self) {
+        let regs = self.mixer.as_ref();
+        unsafe {
+            regs.ui.layers[0].attribute.modify(|val| val.disable());
+            regs.blender
+                .pipe_control
+                .modify(|val| val.disable_pipe::<0>());
+        }
+        self.commit();
+    }
+    /// Check if changes are waiting for the next frame.
+    #[inline]
+    pub fn is_commit_pending(&self) -> bool {
+        self.mixer.as_ref().global.double_buffer.read().is_pending()
+    }
+    /// Get mixer output size as `(width, height)`.
+    #[inline]
+    pub fn size(&self) -> (u16, u16) {
+        (self.width, self.height)
+    }
+    /// Close mixer and release peripherals.
+    #[inline]
+    pub fn free(self, ccu: &ccu::RegisterBlock) -> (DE, MIXER) {
+        let top = self.de.as_ref();
+        unsafe {
+            self.mixer
+                .as_ref()
+                .global
+                .control
+                .write(GlobalControl::default());
+            top.reset.modify(|val| val.assert_reset::<0>());
+            top.bus_gating.modify(|val| val.disable::<0>());
+            top.clock_gating.modify(|val| val.disable::<0>());
+        }
+        // note(unsafe): async read and write using ccu registers
+        unsafe { ccu::DE::free(ccu) };
+        (self.de, self.mixer)
+    }
+    #[inline]
+    fn commit(&self) {
+        unsafe {
+            self.mixer
+                .as_ref()
+                .global
+                .double_buffer
+                .write(DoubleBuffer::default().commit())
+        };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Layer, PixelFormat};
+
+    #[test]
+    fn struct_layer_functions() {
+        let layer = Layer::new(PixelFormat::Rgb565, 800, 480);
+        assert_eq!(layer.stride, 1600);
+        assert_eq!((layer.x, layer.y, layer.alpha), (0, 0, 0xFF));
+        let layer = Layer::new(PixelFormat::Argb8888, 480, 272);
+        assert_eq!(layer.stride, 1920);
+    }
+}
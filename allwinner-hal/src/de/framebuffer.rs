@@ -0,0 +1,398 @@
+use super::{Layer, PixelFormat};
+use crate::{
+    dmac::{AddressMode, Channel, DataWidth, Descriptor, DmaConfig},
+    time::{Deadline, TimeoutError},
+};
+use core::{
+    convert::Infallible,
+    mem::size_of,
+    sync::atomic::{Ordering, fence},
+};
+use embedded_graphics_core::{
+    Pixel,
+    draw_target::DrawTarget,
+    geometry::{Dimensions, OriginDimensions, Point, Size},
+    pixelcolor::{IntoStorage, PixelColor, Rgb565, Rgb888},
+    primitives::Rectangle,
+};
+
+/// Pixel color of a [`Framebuffer`].
+pub trait FramebufferColor: PixelColor {
+    /// Memory representation of one pixel.
+    type Storage: Copy;
+    /// Layer pixel format of the memory representation.
+    const FORMAT: PixelFormat;
+    /// Convert color into its memory representation.
+    fn to_storage(self) -> Self::Storage;
+}
+
+impl FramebufferColor for Rgb565 {
+    type Storage = u16;
+    const FORMAT: PixelFormat = PixelFormat::Rgb565;
+    #[inline]
+    fn to_storage(self) -> u16 {
+        self.into_storage()
+    }
+}
+
+impl FramebufferColor for Rgb888 {
+    type Storage = u32;
+    const FORMAT: PixelFormat = PixelFormat::Xrgb8888;
+    #[inline]
+    fn to_storage(self) -> u32 {
+        self.into_storage()
+    }
+}
+
+/// Linear framebuffer drawn by `embedded-graphics`.
+///
+/// Pixels are stored line by line without padding, as described by [`Framebuffer::layer`].
+/// With dirty tracking enabled, the framebuffer records the bounding box of pixels changed
+/// since the last [`Framebuffer::take_dirty`], e.g. to update only part of a display.
+///
+/// Solid fills and image copies of large areas could be offloaded to a DMA channel by
+/// [`Framebuffer::fill_dma`] and [`Framebuffer::blit_dma`].
+pub struct Framebuffer<'a, C: FramebufferColor> {
+    buffer: &'a mut [C::Storage],
+    width: u16,
+    height: u16,
+    tracking: bool,
+    dirty: Option<(Point, Point)>,
+}
+
+impl<'a, C: FramebufferColor> Framebuffer<'a, C> {
+    /// Create a `width` by `height` framebuffer over `buffer`, without dirty tracking.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `buffer` is shorter than `width` times `height` pixels.
+    #[inline]
+    pub fn new(buffer: &'a mut [C::Storage], width: u16, height: u16) -> Self {
+        assert!(
+            buffer.len() >= width as usize * height as usize,
+            "buffer shorter than framebuffer"
+        );
+        Self {
+            buffer,
+            width,
+            height,
+            tracking: false,
+            dirty: None,
+        }
+    }
+    /// Record bounding box of changed pixels.
+    #[inline]
+    pub fn enable_dirty_tracking(&mut self) {
+        self.tracking = true;
+    }
+    /// Stop recording changed pixels and forget recorded area.
+    #[inline]
+    pub fn disable_dirty_tracking(&mut self) {
+        self.tracking = false;
+        self.dirty = None;
+    }
+    /// Take bounding box of pixels changed since last call, or `None` if nothing changed.
+    #[inline]
+    pub fn take_dirty(&mut self) -> Option<Rectangle> {
+        self.dirty
+            .take()
+            .map(|(top_left, bottom_right)| Rectangle::with_corners(top_left, bottom_right))
+    }
+    /// Mark pixels in `area` as changed, e.g. after writing [`Framebuffer::pixels_mut`].
+    #[inline]
+    pub fn mark_dirty(&mut self, area: &Rectangle) {
+        if let Some(area) = self.clip(area) {
+            self.expand_dirty(&area);
+        }
+    }
+    /// Get layer showing this framebuffer at top left of screen.
+    #[inline]
+    pub fn layer(&self) -> Layer {
+        Layer::new(C::FORMAT, self.width, self.height)
+    }
+    /// Get address of the first pixel.
+    #[inline]
+    pub fn address(&self) -> usize {
+        self.buffer.as_ptr() as usize
+    }
+    /// Get pixels in memory representation.
+    #[inline]
+    pub fn pixels(&self) -> &[C::Storage] {
+        &self.buffer[..self.width as usize * self.height as usize]
+    }
+    /// Get mutable pixels in memory representation.
+    ///
+    /// Writes through this slice are not tracked; mark them by [`Framebuffer::mark_dirty`].
+    #[inline]
+    pub fn pixels_mut(&mut self) -> &mut [C::Storage] {
+        &mut self.buffer[..self.width as usize * self.height as usize]
+    }
+    /// Fill `area` with `color` by DMA `channel`, returning once the fill finishes.
+    #[inline]
+    pub fn fill_dma<const N: usize>(
+        &mut self,
+        channel: &mut Channel<'_, N>,
+        area: &Rectangle,
+        color: C,
+    ) -> Result<(), TimeoutError> {
+        let Some(area) = self.clip(area) else {
+            return Ok(());
+        };
+        let pattern = color.to_storage();
+        let config = DmaConfig {
+            source_address_mode: AddressMode::Io,
+            ..dma_config::<C::Storage>()
+        };
+        let source = &pattern as *const C::Storage as usize;
+        self.dma_rows(channel, &config, &area, |_| source)?;
+        self.expand_dirty(&area);
+        Ok(())
+    }
+    /// Copy packed pixels of `source` into `area` by DMA `channel`, returning once the copy
+    /// finishes.
+    ///
+    /// Parts of `area` outside the framebuffer are skipped.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `source` is shorter than pixels of `area`.
+    #[inline]
+    pub fn blit_dma<const N: usize>(
+        &mut self,
+        channel: &mut Channel<'_, N>,
+        area: &Rectangle,
+        source: &[C::Storage],
+    ) -> Result<(), TimeoutError> {
+        let source_width = area.size.width as usize;
+        assert!(
+            source.len() >= source_width * area.size.height as usize,
+            "source shorter than area"
+        );
+        let Some(clipped) = self.clip(area) else {
+            return Ok(());
+        };
+        let skip_x = (clipped.top_left.x - area.top_left.x) as usize;
+        let skip_y = (clipped.top_left.y - area.top_left.y) as usize;
+        let base = source.as_ptr() as usize;
+        let config = dma_config::<C::Storage>();
+        if clipped.size.width == self.width as u32 && source_width == self.width as usize {
+            let whole = Rectangle::new(
+                clipped.top_left,
+                Size::new(clipped.size.width * clipped.size.height, 1),
+            );
+            let offset = skip_y * source_width * size_of::<C::Storage>();
+            self.dma_rows(channel, &config, &whole, |_| base + offset)?;
+        } else {
+            self.dma_rows(channel, &config, &clipped, |row| {
+                base + ((skip_y + row) * source_width + skip_x) * size_of::<C::Storage>()
+            })?;
+        }
+        self.expand_dirty(&clipped);
+        Ok(())
+    }
+    /// Run one DMA transfer per row of `area` into framebuffer, reading row `i` from
+    /// `source(i)`.
+    ///
+    /// A single row wider than the framebuffer continues on following lines.
+    #[inline]
+    fn dma_rows<const N: usize>(
+        &mut self,
+        channel: &mut Channel<'_, N>,
+        config: &DmaConfig,
+        area: &Rectangle,
+        source: impl Fn(usize) -> usize,
+    ) -> Result<(), TimeoutError> {
+        let len = area.size.width as usize * size_of::<C::Storage>();
+        let base = self.buffer.as_mut_ptr() as usize;
+        for row in 0..area.size.height as usize {
+            let index =
+                (area.top_left.y as usize + row) * self.width as usize + area.top_left.x as usize;
+            let destination = base + index * size_of::<C::Storage>();
+            let descriptor =
+                Descriptor::new(config, source(row) as u32, destination as u32, len as u32);
+            // make descriptor and pixel data visible to DMAC before it starts
+            fence(Ordering::SeqCst);
+            // note(unsafe): descriptor and buffers outlive the transfer, which is waited below
+            unsafe { channel.start(&descriptor) };
+            let deadline = Deadline::start();
+            while channel.is_busy() {
+                if let Err(error) = deadline.check() {
+                    channel.abort()?;
+                    return Err(error);
+                }
+            }
+            channel.stop();
+            // make pixels written by DMAC visible to following reads
+            fence(Ordering::SeqCst);
+        }
+        Ok(())
+    }
+    /// Intersect `area` with framebuffer, or `None` if nothing is left.
+    #[inline]
+    fn clip(&self, area: &Rectangle) -> Option<Rectangle> {
+        let area = area.intersection(&self.bounding_box());
+        (!area.is_zero_sized()).then_some(area)
+    }
+    /// Add clipped `area` to dirty bounding box if tracking.
+    #[inline]
+    fn expand_dirty(&mut self, area: &Rectangle) {
+        if !self.tracking {
+            return;
+        }
+        let Some(bottom_right) = area.bottom_right() else {
+            return;
+        };
+        let top_left = area.top_left;
+        self.dirty = Some(match self.dirty {
+            Some((a, b)) => (
+                Point::new(a.x.min(top_left.x), a.y.min(top_left.y)),
+                Point::new(b.x.max(bottom_right.x), b.y.max(bottom_right.y)),
+            ),
+            None => (top_left, bottom_right),
+        });
+    }
+}
+
+/// Memory to memory DMA configuration moving pixels of `T`.
+#[inline]
+fn dma_config<T>() -> DmaConfig {
+    let width = match size_of::<T>() {
+        2 => DataWidth::Bits16,
+        _ => DataWidth::Bits32,
+    };
+    DmaConfig {
+        source_width: width,
+        destination_width: width,
+        ..DmaConfig::default()
+    }
+}
+
+impl<C: FramebufferColor> OriginDimensions for Framebuffer<'_, C> {
+    #[inline]
+    fn size(&self) -> Size {
+        Size::new(self.width as u32, self.height as u32)
+    }
+}
+
+impl<C: FramebufferColor> DrawTarget for Framebuffer<'_, C> {
+    type Color = C;
+    type Error = Infallible;
+
+    #[inline]
+    fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Pixel<Self::Color>>,
+    {
+        for Pixel(point, color) in pixels {
+            if point.x < 0
+                || point.y < 0
+                || point.x >= self.width as i32
+                || point.y >= self.height as i32
+            {
+                continue;
+            }
+            let index = point.y as usize * self.width as usize + point.x as usize;
+            self.buffer[index] = color.to_storage();
+            self.expand_dirty(&Rectangle::new(point, Size::new(1, 1)));
+        }
+        Ok(())
+    }
+    #[inline]
+    fn fill_solid(&mut self, area: &Rectangle, color: Self::Color) -> Result<(), Self::Error> {
+        let Some(area) = self.clip(area) else {
+            return Ok(());
+        };
+        let value = color.to_storage();
+        let x = area.top_left.x as usize;
+        let width = area.size.width as usize;
+        for y in area.rows() {
+            let start = y as usize * self.width as usize + x;
+            self.buffer[start..start + width].fill(value);
+        }
+        self.expand_dirty(&area);
+        Ok(())
+    }
+    #[inline]
+    fn clear(&mut self, color: Self::Color) -> Result<(), Self::Error> {
+        self.fill_solid(&self.bounding_box(), color)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Framebuffer, FramebufferColor, dma_config};
+    use crate::{
+        de::PixelFormat,
+        dmac::{AddressMode, DataWidth},
+    };
+    use embedded_graphics_core::{
+        Pixel,
+        draw_target::DrawTarget,
+        geometry::{Point, Size},
+        pixelcolor::{Rgb565, Rgb888, RgbColor},
+        primitives::Rectangle,
+    };
+
+    #[test]
+    fn trait_framebuffer_color() {
+        assert_eq!(Rgb565::RED.to_storage(), 0xF800);
+        assert_eq!(Rgb888::new(0x12, 0x34, 0x56).to_storage(), 0x0012_3456);
+        assert_eq!(<Rgb565 as FramebufferColor>::FORMAT, PixelFormat::Rgb565);
+        assert_eq!(<Rgb888 as FramebufferColor>::FORMAT, PixelFormat::Xrgb8888);
+
+        let config = dma_config::<u16>();
+        assert_eq!(config.source_width, DataWidth::Bits16);
+        assert_eq!(config.destination_width, DataWidth::Bits16);
+        assert_eq!(config.source_address_mode, AddressMode::Linear);
+        assert_eq!(dma_config::<u32>().source_width, DataWidth::Bits32);
+    }
+
+    #[test]
+    fn struct_framebuffer_draw() {
+        let mut buffer = [0u16; 4 * 3];
+        let mut fb = Framebuffer::<Rgb565>::new(&mut buffer, 4, 3);
+        assert_eq!(fb.layer().stride, 8);
+        assert_eq!(fb.layer().format, PixelFormat::Rgb565);
+
+        fb.draw_iter([
+            Pixel(Point::new(1, 1), Rgb565::WHITE),
+            Pixel(Point::new(-1, 0), Rgb565::WHITE),
+            Pixel(Point::new(4, 0), Rgb565::WHITE),
+        ])
+        .unwrap();
+        assert_eq!(fb.pixels()[5], 0xFFFF);
+        assert_eq!(fb.take_dirty(), None);
+
+        fb.enable_dirty_tracking();
+        fb.fill_solid(
+            &Rectangle::new(Point::new(2, 1), Size::new(5, 5)),
+            Rgb565::BLUE,
+        )
+        .unwrap();
+        fb.draw_iter([Pixel(Point::new(0, 0), Rgb565::RED)])
+            .unwrap();
+        assert_eq!(
+            fb.pixels(),
+            &[
+                0xF800, 0, 0, 0, //
+                0, 0xFFFF, 0x001F, 0x001F, //
+                0, 0, 0x001F, 0x001F,
+            ]
+        );
+        assert_eq!(
+            fb.take_dirty(),
+            Some(Rectangle::new(Point::zero(), Size::new(4, 3)))
+        );
+        assert_eq!(fb.take_dirty(), None);
+
+        fb.mark_dirty(&Rectangle::new(Point::new(3, 2), Size::new(2, 2)));
+        assert_eq!(
+            fb.take_dirty(),
+            Some(Rectangle::new(Point::new(3, 2), Size::new(1, 1)))
+        );
+        fb.clear(Rgb565::BLACK).unwrap();
+        fb.disable_dirty_tracking();
+        assert_eq!(fb.take_dirty(), None);
+        assert!(fb.pixels().iter().all(|&pixel| pixel == 0));
+    }
+}
@@ -0,0 +1,674 @@
+use volatile_register::{RO, RW};
+
+/// Display engine top registers, clocking and resetting mixers.
+#[repr(C)]
+pub struct RegisterBlock {
+    /// 0x00 - Mixer module clock gating register.
+    pub clock_gating: RW<Gating>,
+    /// 0x04 - Mixer bus clock gating register.
+    pub bus_gating: RW<Gating>,
+    /// 0x08 - Mixer reset register.
+    pub reset: RW<Reset>,
+    /// 0x0c - Mixer clock divider register.
+    pub divider: RW<Divider>,
+}
+
+/// Display engine mixer registers.
+#[repr(C)]
+pub struct MixerRegisterBlock {
+    /// 0x0000..=0x000c - Global registers.
+    pub global: GlobalRegisters,
+    _reserved0: [u32; 1020],
+    /// 0x1000..=0x10fc - Blender registers.
+    pub blender: BlenderRegisters,
+    _reserved1: [u32; 1984],
+    /// 0x3000..=0x3088 - UI channel registers.
+    pub ui: UiChannelRegisters,
+}
+
+/// Mixer global registers.
+#[repr(C)]
+pub struct GlobalRegisters {
+    /// 0x00 - Global control register.
+    pub control: RW<GlobalControl>,
+    /// 0x04 - Global status register.
+    pub status: RO<u32>,
+    /// 0x08 - Double buffer control register.
+    pub double_buffer: RW<DoubleBuffer>,
+    /// 0x0c - Output size register.
+    pub size: RW<Size>,
+}
+
+/// Number of blender pipes.
+pub const PIPE_COUNT: usize = 4;
+
+/// Mixer blender registers.
+#[repr(C)]
+pub struct BlenderRegisters {
+    /// 0x00 - Pipe control register.
+    pub pipe_control: RW<PipeControl>,
+    /// 0x04..=0x40 - Pipe register groups.
+    pub pipes: [PipeRegisters; PIPE_COUNT],
+    _reserved0: [u32; 15],
+    /// 0x80 - Pipe routing register.
+    pub route: RW<Route>,
+    /// 0x84 - Pre-multiplied alpha register.
+    pub premultiply: RW<u32>,
+    /// 0x88 - Background color register.
+    pub background: RW<u32>,
+    /// 0x8c - Output size register.
+    pub output_size: RW<Size>,
+    /// 0x90..=0x9c - Blending mode registers.
+    pub mode: [RW<u32>; PIPE_COUNT],
+    _reserved1: [u32; 23],
+    /// 0xfc - Output control register.
+    pub output_control: RW<u32>,
+}
+
+/// Blender pipe register group.
+#[repr(C)]
+pub struct PipeRegisters {
+    /// 0x00 - Fill color register.
+    pub fill_color: RW<u32>,
+    /// 0x04 - Input size register.
+    pub input_size: RW<Size>,
+    /// 0x08 - Input coordinate register.
+    pub coordinate: RW<Coordinate>,
+    _reserved0: u32,
+}
+
+/// Number of layers in a UI channel.
+pub const LAYER_COUNT: usize = 4;
+
+/// UI channel registers.
+#[repr(C)]
+pub struct UiChannelRegisters {
+    /// 0x00..=0x7c - Layer register groups.
+    pub layers: [UiLayerRegisters; LAYER_COUNT],
+    /// 0x80 - Top field high address register, 8 bits per layer.
+    pub top_high_address: RW<u32>,
+    /// 0x84 - Bottom field high address register, 8 bits per layer.
+    pub bottom_high_address: RW<u32>,
+    /// 0x88 - Overlay size register.
+    pub overlay_size: RW<Size>,
+}
+
+/// UI layer register group.
+#[repr(C)]
+pub struct UiLayerRegisters {
+    /// 0x00 - Layer attribute control register.
+    pub attribute: RW<LayerAttribute>,
+    /// 0x04 - Layer size register.
+    pub size: RW<Size>,
+    /// 0x08 - Layer coordinate register.
+    pub coordinate: RW<Coordinate>,
+    /// 0x0c - Layer pitch register, in bytes.
+    pub pitch: RW<u32>,
+    /// 0x10 - Layer top field low address register.
+    pub top_address: RW<u32>,
+    /// 0x14 - Layer bottom field low address register.
+    pub bottom_address: RW<u32>,
+    /// 0x18 - Layer fill color register.
+    pub fill_color: RW<u32>,
+    _reserved0: u32,
+}
+
+/// Mixer clock gating register.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct Gating(u32);
+
+impl Gating {
+    /// Check if clock of mixer `I` is enabled.
+    #[inline]
+    pub const fn is_enabled<const I: usize>(self) -> bool {
+        self.0 & (1 << I) != 0
+    }
+    /// Enable clock of mixer `I`.
+    #[inline]
+    pub const fn enable<const I: usize>(self) -> Self {
+        Self(self.0 | (1 << I))
+    }
+    /// Disable clock of mixer `I`.
+    #[inline]
+    pub const fn disable<const I: usize>(self) -> Self {
+        Self(self.0 & !(1 << I))
+    }
+}
+
+impl Default for Gating {
+    #[inline]
+    fn default() -> Self {
+        Self(0x0000_0000)
+    }
+}
+
+/// Mixer reset register.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct Reset(u32);
+
+impl Reset {
+    /// Check if reset of mixer `I` is de-asserted.
+    #[inline]
+    pub const fn is_reset_deasserted<const I: usize>(self) -> bool {
+        self.0 & (1 << I) != 0
+    }
+    /// Assert reset of mixer `I`.
+    #[inline]
+    pub const fn assert_reset<const I: usize>(self) -> Self {
+        Self(self.0 & !(1 << I))
+    }
+    /// De-assert reset of mixer `I`.
+    #[inline]
+    pub const fn deassert_reset<const I: usize>(self) -> Self {
+        Self(self.0 | (1 << I))
+    }
+}
+
+impl Default for Reset {
+    #[inline]
+    fn default() -> Self {
+        Self(0x0000_0000)
+    }
+}
+
+/// Mixer clock divider register.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct Divider(u32);
+
+impl Divider {
+    const FACTOR_M: u32 = 0xF;
+
+    /// Get clock divide factor M of mixer `I`.
+    #[inline]
+    pub const fn factor_m<const I: usize>(self) -> u8 {
+        ((self.0 >> (4 * I)) & Self::FACTOR_M) as u8
+    }
+    /// Set clock divide factor M of mixer `I`, dividing display engine clock by M + 1.
+    #[inline]
+    pub const fn set_factor_m<const I: usize>(self, val: u8) -> Self {
+        Self((self.0 & !(Self::FACTOR_M << (4 * I))) | ((val as u32 & Self::FACTOR_M) << (4 * I)))
+    }
+}
+
+impl Default for Divider {
+    #[inline]
+    fn default() -> Self {
+        Self(0x0000_0000)
+    }
+}
+
+/// Mixer global control register.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct GlobalControl(u32);
+
+impl GlobalControl {
+    const RT_EN: u32 = 1 << 0;
+
+    /// Check if mixer is enabled.
+    #[inline]
+    pub const fn is_enabled(self) -> bool {
+        self.0 & Self::RT_EN != 0
+    }
+    /// Enable mixer.
+    #[inline]
+    pub const fn enable(self) -> Self {
+        Self(self.0 | Self::RT_EN)
+    }
+    /// Disable mixer.
+    #[inline]
+    pub const fn disable(self) -> Self {
+        Self(self.0 & !Self::RT_EN)
+    }
+}
+
+impl Default for GlobalControl {
+    #[inline]
+    fn default() -> Self {
+        Self(0x0000_0000)
+    }
+}
+
+/// Double buffer control register.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct DoubleBuffer(u32);
+
+impl DoubleBuffer {
+    const DBUFFER_RDY: u32 = 1 << 0;
+
+    /// Check if register updates are waiting for the next frame.
+    #[inline]
+    pub const fn is_pending(self) -> bool {
+        self.0 & Self::DBUFFER_RDY != 0
+    }
+    /// Apply register updates at the next frame.
+    #[inline]
+    pub const fn commit(self) -> Self {
+        Self(self.0 | Self::DBUFFER_RDY)
+    }
+}
+
+impl Default for DoubleBuffer {
+    #[inline]
+    fn default() -> Self {
+        Self(0x0000_0000)
+    }
+}
+
+/// Size register of mixer, blender and layers.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct Size(u32);
+
+impl Size {
+    const WIDTH: u32 = 0x1FFF;
+    const HEIGHT: u32 = 0x1FFF << 16;
+
+    /// Get width in pixels.
+    #[inline]
+    pub const fn width(self) -> u16 {
+        ((self.0 & Self::WIDTH) + 1) as u16
+    }
+    /// Set width in pixels, in 1..=8192.
+    #[inline]
+    pub const fn set_width(self, val: u16) -> Self {
+        Self((self.0 & !Self::WIDTH) | (val as u32 - 1))
+    }
+    /// Get height in lines.
+    #[inline]
+    pub const fn height(self) -> u16 {
+        (((self.0 & Self::HEIGHT) >> 16) + 1) as u16
+    }
+    /// Set height in lines, in 1..=8192.
+    #[inline]
+    pub const fn set_height(self, val: u16) -> Self {
+        Self((self.0 & !Self::HEIGHT) | ((val as u32 - 1) << 16))
+    }
+}
+
+impl Default for Size {
+    #[inline]
+    fn default() -> Self {
+        Self(0x0000_0000)
+    }
+}
+
+/// Coordinate register of blender pipes and layers.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct Coordinate(u32);
+
+impl Coordinate {
+    const X: u32 = 0xFFFF;
+    const Y: u32 = 0xFFFF << 16;
+
+    /// Get horizontal position in pixels.
+    #[inline]
+    pub const fn x(self) -> u16 {
+        (self.0 & Self::X) as u16
+    }
+    /// Set horizontal position in pixels.
+    #[inline]
+    pub const fn set_x(self, val: u16) -> Self {
+        Self((self.0 & !Self::X) | val as u32)
+    }
+    /// Get vertical position in lines.
+    #[inline]
+    pub const fn y(self) -> u16 {
+        ((self.0 & Self::Y) >> 16) as u16
+    }
+    /// Set vertical position in lines.
+    #[inline]
+    pub const fn set_y(self, val: u16) -> Self {
+        Self((self.0 & !Self::Y) | ((val as u32) << 16))
+    }
+}
+
+impl Default for Coordinate {
+    #[inline]
+    fn default() -> Self {
+        Self(0x0000_0000)
+    }
+}
+
+/// Blender pipe control register.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct PipeControl(u32);
+
+impl PipeControl {
+    /// Check if pipe `P` is enabled.
+    #[inline]
+    pub const fn is_pipe_enabled<const P: usize>(self) -> bool {
+        self.0 & (1 << (8 + P)) != 0
+    }
+    /// Enable pipe `P`.
+    #[inline]
+    pub const fn enable_pipe<const P: usize>(self) -> Self {
+        Self(self.0 | (1 << (8 + P)))
+    }
+    /// Disable pipe `P`.
+    #[inline]
+    pub const fn disable_pipe<const P: usize>(self) -> Self {
+        Self(self.0 & !(1 << (8 + P)))
+    }
+    /// Check if pipe `P` is filled with its fill color.
+    #[inline]
+    pub const fn is_fill_color_enabled<const P: usize>(self) -> bool {
+        self.0 & (1 << P) != 0
+    }
+    /// Fill pipe `P` with its fill color.
+    #[inline]
+    pub const fn enable_fill_color<const P: usize>(self) -> Self {
+        Self(self.0 | (1 << P))
+    }
+    /// Stop filling pipe `P` with its fill color.
+    #[inline]
+    pub const fn disable_fill_color<const P: usize>(self) -> Self {
+        Self(self.0 & !(1 << P))
+    }
+}
+
+impl Default for PipeControl {
+    #[inline]
+    fn default() -> Self {
+        Self(0x0000_0000)
+    }
+}
+
+/// Blender pipe routing register.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct Route(u32);
+
+impl Route {
+    const CHANNEL: u32 = 0xF;
+
+    /// Get channel feeding pipe `P`.
+    #[inline]
+    pub const fn channel<const P: usize>(self) -> u8 {
+        ((self.0 >> (4 * P)) & Self::CHANNEL) as u8
+    }
+    /// Feed pipe `P` from channel `val`.
+    #[inline]
+    pub const fn set_channel<const P: usize>(self, val: u8) -> Self {
+        Self((self.0 & !(Self::CHANNEL << (4 * P))) | ((val as u32 & Self::CHANNEL) << (4 * P)))
+    }
+}
+
+impl Default for Route {
+    #[inline]
+    fn default() -> Self {
+        Self(0x0000_0000)
+    }
+}
+
+/// Pixel format of a UI layer.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum PixelFormat {
+    /// 32-bit pixels with alpha in the most significant byte, then red, green and blue.
+    Argb8888 = 0x00,
+    /// 32-bit pixels with alpha in the most significant byte, then blue, green and red.
+    Abgr8888 = 0x01,
+    /// 32-bit pixels with unused most significant byte, then red, green and blue.
+    Xrgb8888 = 0x04,
+    /// 24-bit pixels with red in the most significant byte, then green and blue.
+    Rgb888 = 0x08,
+    /// 16-bit pixels with 5-bit red, 6-bit green and 5-bit blue from the most significant bit.
+    Rgb565 = 0x0A,
+    /// 16-bit pixels with 4 bits each of alpha, red, green and blue from the most significant bit.
+    Argb4444 = 0x0C,
+    /// 16-bit pixels with 1-bit alpha and 5 bits each of red, green and blue from the most
+    /// significant bit.
+    Argb1555 = 0x10,
+}
+
+impl PixelFormat {
+    /// Size of one pixel in bytes.
+    #[inline]
+    pub const fn bytes_per_pixel(self) -> u8 {
+        match self {
+            PixelFormat::Argb8888 | PixelFormat::Abgr8888 | PixelFormat::Xrgb8888 => 4,
+            PixelFormat::Rgb888 => 3,
+            PixelFormat::Rgb565 | PixelFormat::Argb4444 | PixelFormat::Argb1555 => 2,
+        }
+    }
+}
+
+/// Alpha source of a UI layer.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum AlphaMode {
+    /// Alpha of each pixel.
+    Pixel = 0,
+    /// Global alpha of layer.
+    Global = 1,
+    /// Alpha of each pixel multiplied by global alpha of layer.
+    Combined = 2,
+}
+
+/// UI layer attribute control register.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct LayerAttribute(u32);
+
+impl LayerAttribute {
+    const LAY_EN: u32 = 1 << 0;
+    const ALPHA_MODE: u32 = 0x3 << 1;
+    const FILL_COLOR_EN: u32 = 1 << 4;
+    const FORMAT: u32 = 0x1F << 8;
+    const ALPHA: u32 = 0xFF << 24;
+
+    /// Check if layer is enabled.
+    #[inline]
+    pub const fn is_enabled(self) -> bool {
+        self.0 & Self::LAY_EN != 0
+    }
+    /// Enable layer.
+    #[inline]
+    pub const fn enable(self) -> Self {
+        Self(self.0 | Self::LAY_EN)
+    }
+    /// Disable layer.
+    #[inline]
+    pub const fn disable(self) -> Self {
+        Self(self.0 & !Self::LAY_EN)
+    }
+    /// Get alpha source of layer.
+    #[inline]
+    pub const fn alpha_mode(self) -> AlphaMode {
+        match (self.0 & Self::ALPHA_MODE) >> 1 {
+            0 => AlphaMode::Pixel,
+            1 => AlphaMode::Global,
+            _ => AlphaMode::Combined,
+        }
+    }
+    /// Set alpha source of layer.
+    #[inline]
+    pub const fn set_alpha_mode(self, val: AlphaMode) -> Self {
+        Self((self.0 & !Self::ALPHA_MODE) | ((val as u32) << 1))
+    }
+    /// Check if layer is filled with its fill color instead of framebuffer.
+    #[inline]
+    pub const fn is_fill_color_enabled(self) -> bool {
+        self.0 & Self::FILL_COLOR_EN != 0
+    }
+    /// Fill layer with its fill color instead of framebuffer.
+    #[inline]
+    pub const fn enable_fill_color(self) -> Self {
+        Self(self.0 | Self::FILL_COLOR_EN)
+    }
+    /// Show framebuffer on layer.
+    #[inline]
+    pub const fn disable_fill_color(self) -> Self {
+        Self(self.0 & !Self::FILL_COLOR_EN)
+    }
+    /// Get pixel format of layer, or `None` if it's not a [`PixelFormat`].
+    #[inline]
+    pub const fn format(self) -> Option<PixelFormat> {
+        match (self.0 & Self::FORMAT) >> 8 {
+            0x00 => Some(PixelFormat::Argb8888),
+            0x01 => Some(PixelFormat::Abgr8888),
+            0x04 => Some(PixelFormat::Xrgb8888),
+            0x08 => Some(PixelFormat::Rgb888),
+            0x0A => Some(PixelFormat::Rgb565),
+            0x0C => Some(PixelFormat::Argb4444),
+            0x10 => Some(PixelFormat::Argb1555),
+            _ => None,
+        }
+    }
+    /// Set pixel format of layer.
+    #[inline]
+    pub const fn set_format(self, val: PixelFormat) -> Self {
+        Self((self.0 & !Self::FORMAT) | ((val as u32) << 8))
+    }
+    /// Get global alpha of layer.
+    #[inline]
+    pub const fn alpha(self) -> u8 {
+        ((self.0 & Self::ALPHA) >> 24) as u8
+    }
+    /// Set global alpha of layer.
+    #[inline]
+    pub const fn set_alpha(self, val: u8) -> Self {
+        Self((self.0 & !Self::ALPHA) | ((val as u32) << 24))
+    }
+}
+
+impl Default for LayerAttribute {
+    #[inline]
+    fn default() -> Self {
+        Self(0x0000_0000)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        AlphaMode, BlenderRegisters, Coordinate, Divider, DoubleBuffer, Gating, GlobalControl,
+        LayerAttribute, MixerRegisterBlock, PipeControl, PixelFormat, RegisterBlock, Reset, Route,
+        Size, UiChannelRegisters, UiLayerRegisters,
+    };
+    use core::mem::{offset_of, size_of};
+
+    #[test]
+    fn offset_de() {
+        assert_eq!(offset_of!(RegisterBlock, clock_gating), 0x00);
+        assert_eq!(offset_of!(RegisterBlock, bus_gating), 0x04);
+        assert_eq!(offset_of!(RegisterBlock, reset), 0x08);
+        assert_eq!(offset_of!(RegisterBlock, divider), 0x0c);
+
+        assert_eq!(offset_of!(MixerRegisterBlock, global), 0x0000);
+        assert_eq!(offset_of!(MixerRegisterBlock, blender), 0x1000);
+        assert_eq!(offset_of!(MixerRegisterBlock, ui), 0x3000);
+
+        assert_eq!(offset_of!(BlenderRegisters, pipe_control), 0x00);
+        assert_eq!(offset_of!(BlenderRegisters, pipes), 0x04);
+        assert_eq!(offset_of!(BlenderRegisters, route), 0x80);
+        assert_eq!(offset_of!(BlenderRegisters, premultiply), 0x84);
+        assert_eq!(offset_of!(BlenderRegisters, background), 0x88);
+        assert_eq!(offset_of!(BlenderRegisters, output_size), 0x8c);
+        assert_eq!(offset_of!(BlenderRegisters, mode), 0x90);
+        assert_eq!(offset_of!(BlenderRegisters, output_control), 0xfc);
+        assert_eq!(size_of::<BlenderRegisters>(), 0x100);
+
+        assert_eq!(offset_of!(UiLayerRegisters, attribute), 0x00);
+        assert_eq!(offset_of!(UiLayerRegisters, size), 0x04);
+        assert_eq!(offset_of!(UiLayerRegisters, coordinate), 0x08);
+        assert_eq!(offset_of!(UiLayerRegisters, pitch), 0x0c);
+        assert_eq!(offset_of!(UiLayerRegisters, top_address), 0x10);
+        assert_eq!(offset_of!(UiLayerRegisters, bottom_address), 0x14);
+        assert_eq!(offset_of!(UiLayerRegisters, fill_color), 0x18);
+        assert_eq!(size_of::<UiLayerRegisters>(), 0x20);
+        assert_eq!(offset_of!(UiChannelRegisters, top_high_address), 0x80);
+        assert_eq!(offset_of!(UiChannelRegisters, bottom_high_address), 0x84);
+        assert_eq!(offset_of!(UiChannelRegisters, overlay_size), 0x88);
+    }
+
+    #[test]
+    fn struct_top_functions() {
+        let val = Gating::default().enable::<0>().enable::<1>();
+        assert_eq!(val.0, 0x0000_0003);
+        assert!(val.is_enabled::<0>());
+        assert_eq!(val.disable::<0>().0, 0x0000_0002);
+
+        let val = Reset::default().deassert_reset::<0>();
+        assert_eq!(val.0, 0x0000_0001);
+        assert!(val.is_reset_deasserted::<0>() && !val.is_reset_deasserted::<1>());
+        assert_eq!(val.assert_reset::<0>().0, 0x0000_0000);
+
+        let val = Divider::default()
+            .set_factor_m::<0>(0x3)
+            .set_factor_m::<1>(0x5);
+        assert_eq!(val.0, 0x0000_0053);
+        assert_eq!(val.factor_m::<0>(), 0x3);
+        assert_eq!(val.factor_m::<1>(), 0x5);
+    }
+
+    #[test]
+    fn struct_global_functions() {
+        let val = GlobalControl::default().enable();
+        assert_eq!(val.0, 0x0000_0001);
+        assert!(val.is_enabled());
+        assert_eq!(val.disable().0, 0x0000_0000);
+
+        let val = DoubleBuffer::default();
+        assert!(!val.is_pending());
+        assert!(val.commit().is_pending());
+
+        let val = Size::default().set_width(800).set_height(480);
+        assert_eq!(val.0, 0x01DF_031F);
+        assert_eq!(val.width(), 800);
+        assert_eq!(val.height(), 480);
+
+        let val = Coordinate::default().set_x(16).set_y(32);
+        assert_eq!(val.0, 0x0020_0010);
+        assert_eq!(val.x(), 16);
+        assert_eq!(val.y(), 32);
+    }
+
+    #[test]
+    fn struct_blender_functions() {
+        let val = PipeControl::default()
+            .enable_pipe::<0>()
+            .enable_fill_color::<1>();
+        assert_eq!(val.0, 0x0000_0102);
+        assert!(val.is_pipe_enabled::<0>() && !val.is_pipe_enabled::<1>());
+        assert!(val.is_fill_color_enabled::<1>());
+        let val = val.disable_pipe::<0>().disable_fill_color::<1>();
+        assert_eq!(val.0, 0x0000_0000);
+
+        let val = Route::default().set_channel::<0>(1).set_channel::<1>(0);
+        assert_eq!(val.0, 0x0000_0001);
+        assert_eq!(val.channel::<0>(), 1);
+        let val = val.set_channel::<2>(2);
+        assert_eq!(val.0, 0x0000_0201);
+        assert_eq!(val.channel::<2>(), 2);
+    }
+
+    #[test]
+    fn struct_layer_attribute_functions() {
+        let val = LayerAttribute::default()
+            .set_format(PixelFormat::Rgb565)
+            .set_alpha_mode(AlphaMode::Combined)
+            .set_alpha(0x80)
+            .enable();
+        assert_eq!(val.0, 0x8000_0A05);
+        assert!(val.is_enabled());
+        assert_eq!(val.format(), Some(PixelFormat::Rgb565));
+        assert_eq!(val.alpha_mode(), AlphaMode::Combined);
+        assert_eq!(val.alpha(), 0x80);
+        let val = val.enable_fill_color();
+        assert!(val.is_fill_color_enabled());
+        assert_eq!(val.0, 0x8000_0A15);
+        let val = val.disable_fill_color().disable();
+        assert_eq!(val.0, 0x8000_0A04);
+
+        assert_eq!(LayerAttribute(0x0000_1400).format(), None);
+        assert_eq!(PixelFormat::Argb8888.bytes_per_pixel(), 4);
+        assert_eq!(PixelFormat::Rgb888.bytes_per_pixel(), 3);
+        assert_eq!(PixelFormat::Rgb565.bytes_per_pixel(), 2);
+    }
+}
@@ -0,0 +1,54 @@
+//! Peripheral independent delays.
+
+use crate::{ccu::Clocks, perf::Cycles};
+use embedded_time::rate::Hertz;
+
+/// Busy-wait delay counting CPU clock cycles.
+///
+/// Requires no peripheral, thus usable in early boot code before timers are set up.
+/// Delay is accurate as long as CPU clock stays at frequency given on creation.
+#[derive(Clone, Copy, Debug)]
+pub struct McycleDelay {
+    frequency: Hertz,
+}
+
+impl McycleDelay {
+    /// Create a delay using CPU clock frequency of `clocks`.
+    #[inline]
+    pub const fn new(clocks: &Clocks) -> Self {
+        Self {
+            frequency: clocks.cpu,
+        }
+    }
+    /// Create a delay with CPU clock `frequency`.
+    #[inline]
+    pub const fn with_frequency(frequency: Hertz) -> Self {
+        Self { frequency }
+    }
+    /// Spin for `cycles` CPU clock cycles.
+    #[inline]
+    pub fn delay_cycles(&self, cycles: u64) {
+        let start = Cycles::now();
+        while (Cycles::now() - start).0 < cycles {
+            core::hint::spin_loop();
+        }
+    }
+}
+
+impl embedded_hal::delay::DelayNs for McycleDelay {
+    #[inline]
+    fn delay_ns(&mut self, ns: u32) {
+        let cycles = (ns as u64 * self.frequency.0 as u64).div_ceil(1_000_000_000);
+        self.delay_cycles(cycles)
+    }
+    #[inline]
+    fn delay_us(&mut self, us: u32) {
+        let cycles = (us as u64 * self.frequency.0 as u64).div_ceil(1_000_000);
+        self.delay_cycles(cycles)
+    }
+    #[inline]
+    fn delay_ms(&mut self, ms: u32) {
+        let cycles = (ms as u64 * self.frequency.0 as u64).div_ceil(1_000);
+        self.delay_cycles(cycles)
+    }
+}
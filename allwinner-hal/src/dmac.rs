@@ -0,0 +1,295 @@
+//! Direct Memory Access Controller.
+
+mod descriptor;
+mod interrupt;
+mod register;
+pub use descriptor::*;
+pub use interrupt::*;
+pub use register::*;
+
+/// DMA request port numbers.
+///
+/// Memory ports are common to all SoCs; peripheral ports are selected by SoC feature.
+pub mod drq {
+    use super::Drq;
+
+    /// On-chip SRAM.
+    pub const SRAM: Drq = Drq::new(0);
+    /// DRAM.
+    pub const DRAM: Drq = Drq::new(1);
+
+    #[cfg(feature = "d1")]
+    pub use crate::wafer::d1::drq::*;
+}
+
+use crate::ccu::{self, ClockGate};
+use crate::time::{Deadline, TimeoutError};
+
+/// DMAC clock gating mode.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum GatingMode {
+    /// Keep all DMAC circuits clocked for lowest transfer latency.
+    LowLatency,
+    /// Gate idle DMAC circuits automatically for lowest idle power.
+    LowPower,
+}
+
+/// If DMA master clock interface auto gating must stay disabled on current SoC.
+///
+/// On D1-like chips, DMAC may stall on memory accesses when master clock interface is
+/// auto gated, vendor SDK keeps it always clocked. Driver applies this on any gating mode.
+const MCLK_AUTO_GATING_ERRATA: bool = cfg!(feature = "d1");
+
+/// Managed DMAC structure with peripheral.
+pub struct Dmac<DMAC> {
+    dmac: DMAC,
+}
+
+impl<DMAC: AsRef<RegisterBlock>> Dmac<DMAC> {
+    /// Create a DMAC instance.
+    #[inline]
+    pub fn new(dmac: DMAC, ccu: &ccu::RegisterBlock) -> Self {
+        // note(unsafe): async read and write using ccu registers
+        unsafe { ccu::DMA::reset(ccu) };
+        // disable and clear interrupts of all channels
+        let regs = dmac.as_ref();
+        for i in 0..2 {
+            unsafe {
+                regs.irq_enable[i].write(0);
+                regs.irq_pending[i].write(0xFFFF_FFFF);
+            }
+        }
+        let mut ans = Self { dmac };
+        ans.set_gating_mode(GatingMode::LowPower);
+        ans
+    }
+    /// Set clock gating mode of DMAC.
+    #[inline]
+    pub fn set_gating_mode(&mut self, mode: GatingMode) {
+        let auto_gating = &self.dmac.as_ref().auto_gating;
+        unsafe {
+            auto_gating.modify(|val| {
+                let val = match mode {
+                    GatingMode::LowLatency => val
+                        .disable_mclk_auto_gating()
+                        .disable_common_auto_gating()
+                        .disable_channel_auto_gating(),
+                    GatingMode::LowPower => val
+                        .enable_mclk_auto_gating()
+                        .enable_common_auto_gating()
+                        .enable_channel_auto_gating(),
+                };
+                if MCLK_AUTO_GATING_ERRATA {
+                    val.disable_mclk_auto_gating()
+                } else {
+                    val
+                }
+            })
+        };
+    }
+    /// Get clock gating mode of DMAC.
+    #[inline]
+    pub fn gating_mode(&self) -> GatingMode {
+        let val = self.dmac.as_ref().auto_gating.read();
+        if val.is_common_auto_gating_enabled() || val.is_channel_auto_gating_enabled() {
+            GatingMode::LowPower
+        } else {
+            GatingMode::LowLatency
+        }
+    }
+    /// Split DMAC into separately owned channels.
+    #[inline]
+    pub fn split(&mut self) -> Channels<'_> {
+        let dmac = self.dmac.as_ref();
+        Channels {
+            ch0: Channel { dmac },
+            ch1: Channel { dmac },
+            ch2: Channel { dmac },
+            ch3: Channel { dmac },
+            ch4: Channel { dmac },
+            ch5: Channel { dmac },
+            ch6: Channel { dmac },
+            ch7: Channel { dmac },
+            ch8: Channel { dmac },
+            ch9: Channel { dmac },
+            ch10: Channel { dmac },
+            ch11: Channel { dmac },
+            ch12: Channel { dmac },
+            ch13: Channel { dmac },
+            ch14: Channel { dmac },
+            ch15: Channel { dmac },
+        }
+    }
+    /// Close DMAC and release peripheral.
+    #[inline]
+    pub fn free(self, ccu: &ccu::RegisterBlock) -> DMAC {
+        unsafe { ccu::DMA::free(ccu) };
+        self.dmac
+    }
+}
+
+/// DMA channels of current SoC.
+pub struct Channels<'a> {
+    pub ch0: Channel<'a, 0>,
+    pub ch1: Channel<'a, 1>,
+    pub ch2: Channel<'a, 2>,
+    pub ch3: Channel<'a, 3>,
+    pub ch4: Channel<'a, 4>,
+    pub ch5: Channel<'a, 5>,
+    pub ch6: Channel<'a, 6>,
+    pub ch7: Channel<'a, 7>,
+    pub ch8: Channel<'a, 8>,
+    pub ch9: Channel<'a, 9>,
+    pub ch10: Channel<'a, 10>,
+    pub ch11: Channel<'a, 11>,
+    pub ch12: Channel<'a, 12>,
+    pub ch13: Channel<'a, 13>,
+    pub ch14: Channel<'a, 14>,
+    pub ch15: Channel<'a, 15>,
+}
+
+/// DMA channel `N`.
+pub struct Channel<'a, const N: usize> {
+    dmac: &'a RegisterBlock,
+}
+
+/// DMA channel `N`, two buffers `B` and their descriptors, released by stopping a
+/// double-buffered stream.
+pub type StreamParts<'a, const N: usize, B> =
+    (Channel<'a, N>, &'a mut [B; 2], &'a mut [Descriptor; 2]);
+
+impl<'a, const N: usize> Channel<'a, N> {
+    /// Start transfer of descriptor chain beginning with `descriptor`.
+    ///
+    /// # Safety
+    ///
+    /// The descriptor chain, and source and destination buffers it refers to, must
+    /// stay valid until the transfer finishes or is stopped.
+    #[inline]
+    pub unsafe fn start(&mut self, descriptor: *const Descriptor) {
+        let channel = &self.dmac.channels[N];
+        unsafe {
+            channel.descriptor_address.write(descriptor as usize as u32);
+            channel.enable.modify(|val| val.enable());
+        }
+    }
+    /// Stop current transfer by disabling this channel.
+    #[inline]
+    pub fn stop(&mut self) {
+        unsafe { self.dmac.channels[N].enable.modify(|val| val.disable()) };
+    }
+    /// Pause current transfer.
+    ///
+    /// The channel finishes its ongoing burst and keeps descriptor state, so the
+    /// transfer can be continued later using [`Channel::resume`].
+    #[inline]
+    pub fn pause(&mut self) {
+        unsafe { self.dmac.channels[N].pause.modify(|val| val.pause()) };
+    }
+    /// Resume a paused transfer.
+    #[inline]
+    pub fn resume(&mut self) {
+        unsafe { self.dmac.channels[N].pause.modify(|val| val.resume()) };
+    }
+    /// Check if current transfer is paused.
+    #[inline]
+    pub fn is_paused(&self) -> bool {
+        self.dmac.channels[N].pause.read().is_paused()
+    }
+    /// Abort current transfer, returning number of bytes left in current descriptor.
+    ///
+    /// The channel is paused first so that the ongoing burst completes, then it is
+    /// disabled and waited until idle. Pause state and pending interrupts of this
+    /// channel are cleared afterwards, leaving it ready for next [`Channel::start`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`TimeoutError`] if the channel is still busy after crate-wide blocking
+    /// timeout, leaving it disabled and paused.
+    #[inline]
+    pub fn abort(&mut self) -> Result<u32, TimeoutError> {
+        let channel = &self.dmac.channels[N];
+        unsafe { channel.pause.modify(|val| val.pause()) };
+        let bytes_left = channel.byte_counter_left.read();
+        unsafe { channel.enable.modify(|val| val.disable()) };
+        let deadline = Deadline::start();
+        while self.is_busy() {
+            deadline.check()?;
+            core::hint::spin_loop();
+        }
+        unsafe {
+            channel.pause.modify(|val| val.resume());
+            self.dmac.irq_pending[N / 8].write(0xF << ((N % 8) * 4));
+        }
+        interrupt::record_error(N);
+        Ok(bytes_left)
+    }
+    /// Enable interrupt of `event` on this channel.
+    #[inline]
+    pub fn enable_interrupt(&mut self, event: Event) {
+        let bit = event.bit() << ((N % 8) * 4);
+        unsafe { self.dmac.irq_enable[N / 8].modify(|val| val | bit) };
+    }
+    /// Disable interrupt of `event` on this channel.
+    #[inline]
+    pub fn disable_interrupt(&mut self, event: Event) {
+        let bit = event.bit() << ((N % 8) * 4);
+        unsafe { self.dmac.irq_enable[N / 8].modify(|val| val & !bit) };
+    }
+    /// Register interrupt handler of this channel, called from [`on_interrupt`].
+    #[inline]
+    pub fn set_handler(&mut self, handler: Handler) {
+        interrupt::set_handler(N, handler);
+    }
+    /// Wait until current transfer finishes, using queue end interrupt.
+    ///
+    /// This channel must have no handler registered, so that [`on_interrupt`] wakes the
+    /// waiting task.
+    #[inline]
+    pub async fn wait(&mut self) {
+        self.enable_interrupt(Event::QueueEnd);
+        interrupt::WAKERS.wait_until(N, || !self.is_busy()).await;
+        self.disable_interrupt(Event::QueueEnd);
+    }
+    /// Get transfer statistics of this channel.
+    #[inline]
+    pub fn statistics(&self) -> Statistics {
+        interrupt::statistics(N)
+    }
+    /// Check if this channel is transferring data.
+    #[inline]
+    pub fn is_busy(&self) -> bool {
+        self.dmac.status.read() & (1 << N) != 0
+    }
+    /// Get configuration of current descriptor.
+    #[inline]
+    pub fn configuration(&self) -> Configuration {
+        self.dmac.channels[N].configuration.read()
+    }
+    /// Get bus priority of current descriptor.
+    #[inline]
+    pub fn priority(&self) -> Priority {
+        let wait_cycles = self.dmac.channels[N].parameter.read().wait_cycles();
+        match wait_cycles {
+            0 => Priority::High,
+            16 => Priority::Medium,
+            64 => Priority::Low,
+            cycles => Priority::WaitCycles(cycles),
+        }
+    }
+    /// Get number of bytes left in current descriptor.
+    #[inline]
+    pub fn bytes_left(&self) -> u32 {
+        self.dmac.channels[N].byte_counter_left.read()
+    }
+    /// Get source address the channel is about to read.
+    #[inline]
+    pub fn current_source(&self) -> u32 {
+        self.dmac.channels[N].current_source.read()
+    }
+    /// Get destination address the channel is about to write.
+    #[inline]
+    pub fn current_destination(&self) -> u32 {
+        self.dmac.channels[N].current_destination.read()
+    }
+}
@@ -0,0 +1,226 @@
+use super::drq;
+use super::register::{AddressMode, BurstLength, Configuration, DataWidth, Parameter};
+
+/// DMA request (DRQ) port number of a transfer source or destination.
+///
+/// Port numbers of current SoC are listed in module [`drq`](super::drq).
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct Drq(u8);
+
+impl Drq {
+    /// Create a DRQ port from raw port number.
+    #[inline]
+    pub const fn new(number: u8) -> Self {
+        Self(number)
+    }
+    /// Get raw port number.
+    #[inline]
+    pub const fn number(self) -> u8 {
+        self.0
+    }
+}
+
+/// Bus priority of a DMA channel.
+///
+/// DMAC arbitrates active channels without a dedicated priority field. Instead, each
+/// channel waits for a number of clock cycles between two data requests; channels of
+/// lower priority wait longer and leave more bus bandwidth to higher priority ones.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Priority {
+    /// No wait cycles, takes as much bandwidth as the channel could.
+    High,
+    /// Wait 16 cycles between data requests.
+    Medium,
+    /// Wait 64 cycles between data requests.
+    Low,
+    /// Wait for a custom number of cycles between data requests.
+    WaitCycles(u8),
+}
+
+impl Priority {
+    /// Wait clock cycles of this priority.
+    #[inline]
+    pub const fn wait_cycles(self) -> u8 {
+        match self {
+            Priority::High => 0,
+            Priority::Medium => 16,
+            Priority::Low => 64,
+            Priority::WaitCycles(cycles) => cycles,
+        }
+    }
+}
+
+/// DMA channel transfer configuration.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct DmaConfig {
+    /// DRQ port of transfer source.
+    pub source_drq: Drq,
+    /// Address mode of transfer source.
+    pub source_address_mode: AddressMode,
+    /// Burst length of transfer source.
+    pub source_burst: BurstLength,
+    /// Data width of transfer source.
+    pub source_width: DataWidth,
+    /// DRQ port of transfer destination.
+    pub destination_drq: Drq,
+    /// Address mode of transfer destination.
+    pub destination_address_mode: AddressMode,
+    /// Burst length of transfer destination.
+    pub destination_burst: BurstLength,
+    /// Data width of transfer destination.
+    pub destination_width: DataWidth,
+    /// Bus priority of the channel.
+    pub priority: Priority,
+}
+
+impl Default for DmaConfig {
+    /// Memory to memory transfer in 32-bit words, 4 words per burst.
+    #[inline]
+    fn default() -> Self {
+        Self {
+            source_drq: drq::DRAM,
+            source_address_mode: AddressMode::Linear,
+            source_burst: BurstLength::Four,
+            source_width: DataWidth::Bits32,
+            destination_drq: drq::DRAM,
+            destination_address_mode: AddressMode::Linear,
+            destination_burst: BurstLength::Four,
+            destination_width: DataWidth::Bits32,
+            priority: Priority::High,
+        }
+    }
+}
+
+impl DmaConfig {
+    /// Get register value of channel configuration.
+    #[inline]
+    pub fn configuration(&self) -> Configuration {
+        Configuration::default()
+            .set_source_drq(self.source_drq.number())
+            .set_source_address_mode(self.source_address_mode)
+            .set_source_burst(self.source_burst)
+            .set_source_width(self.source_width)
+            .set_destination_drq(self.destination_drq.number())
+            .set_destination_address_mode(self.destination_address_mode)
+            .set_destination_burst(self.destination_burst)
+            .set_destination_width(self.destination_width)
+    }
+    /// Get register value of channel parameter.
+    #[inline]
+    pub fn parameter(&self) -> Parameter {
+        Parameter::default().set_wait_cycles(self.priority.wait_cycles())
+    }
+}
+
+/// DMA transfer descriptor.
+///
+/// DMAC reads descriptors from memory, it should stay valid until the transfer finishes.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[repr(C, align(4))]
+pub struct Descriptor {
+    /// Transfer configuration.
+    pub configuration: Configuration,
+    /// Source address.
+    pub source_address: u32,
+    /// Destination address.
+    pub destination_address: u32,
+    /// Byte count of this transfer.
+    pub byte_count: u32,
+    /// Transfer parameter.
+    pub parameter: Parameter,
+    /// Address of the next descriptor, or [`Descriptor::LINK_END`].
+    pub link: u32,
+}
+
+impl Descriptor {
+    /// Link value marking the last descriptor of a chain.
+    pub const LINK_END: u32 = 0xFFFF_F800;
+
+    /// Create a single descriptor that ends the chain.
+    #[inline]
+    pub fn new(
+        config: &DmaConfig,
+        source_address: u32,
+        destination_address: u32,
+        byte_count: u32,
+    ) -> Self {
+        Self {
+            configuration: config.configuration(),
+            source_address,
+            destination_address,
+            byte_count,
+            parameter: config.parameter(),
+            link: Self::LINK_END,
+        }
+    }
+    /// Link `next` descriptor after this one.
+    #[inline]
+    pub fn set_link(&mut self, next: &Descriptor) {
+        self.link = next as *const Descriptor as usize as u32;
+    }
+    /// If this descriptor ends the chain.
+    #[inline]
+    pub const fn is_last(&self) -> bool {
+        self.link == Self::LINK_END
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Descriptor, DmaConfig, Drq, Priority};
+    use crate::dmac::{AddressMode, BurstLength, DataWidth};
+    use core::mem::{offset_of, size_of};
+
+    #[test]
+    fn offset_descriptor() {
+        assert_eq!(offset_of!(Descriptor, configuration), 0x00);
+        assert_eq!(offset_of!(Descriptor, source_address), 0x04);
+        assert_eq!(offset_of!(Descriptor, destination_address), 0x08);
+        assert_eq!(offset_of!(Descriptor, byte_count), 0x0C);
+        assert_eq!(offset_of!(Descriptor, parameter), 0x10);
+        assert_eq!(offset_of!(Descriptor, link), 0x14);
+        assert_eq!(size_of::<Descriptor>(), 0x18);
+    }
+
+    #[test]
+    fn struct_dma_config_functions() {
+        let config = DmaConfig::default();
+        assert_eq!(config.configuration().bits(), 0x04410441);
+        assert_eq!(config.parameter().bits(), 0x00000000);
+
+        let config = DmaConfig {
+            source_drq: Drq::new(1),
+            source_address_mode: AddressMode::Linear,
+            source_burst: BurstLength::Sixteen,
+            source_width: DataWidth::Bits16,
+            destination_drq: Drq::new(7),
+            destination_address_mode: AddressMode::Io,
+            destination_burst: BurstLength::Single,
+            destination_width: DataWidth::Bits16,
+            priority: Priority::Low,
+        };
+        assert_eq!(config.configuration().bits(), 0x030702c1);
+        assert_eq!(config.parameter().wait_cycles(), 64);
+
+        let config = DmaConfig {
+            priority: Priority::WaitCycles(200),
+            ..DmaConfig::default()
+        };
+        assert_eq!(config.parameter().bits(), 200);
+    }
+
+    #[test]
+    fn struct_descriptor_functions() {
+        let config = DmaConfig::default();
+        let mut first = Descriptor::new(&config, 0x4000_0000, 0x4100_0000, 512);
+        assert!(first.is_last());
+        assert_eq!(first.configuration, config.configuration());
+        assert_eq!(first.byte_count, 512);
+
+        let second = Descriptor::new(&config, 0x4000_0200, 0x4100_0200, 512);
+        first.set_link(&second);
+        assert!(!first.is_last());
+        assert_eq!(first.link, &second as *const Descriptor as usize as u32);
+    }
+}
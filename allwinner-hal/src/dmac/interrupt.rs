@@ -0,0 +1,156 @@
+use super::register::{CHANNEL_COUNT, RegisterBlock};
+use crate::waker::WakerSet;
+use core::sync::atomic::{AtomicPtr, AtomicU32, Ordering};
+
+/// DMA channel interrupt event.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Event {
+    /// Half of current descriptor package is transferred.
+    HalfPackage,
+    /// Current descriptor package is transferred.
+    PackageEnd,
+    /// Whole descriptor chain is transferred.
+    QueueEnd,
+}
+
+impl Event {
+    /// Interrupt bit of this event in a channel's 4-bit enable or pending field.
+    #[inline]
+    pub(crate) const fn bit(self) -> u32 {
+        match self {
+            Event::HalfPackage => 1 << 0,
+            Event::PackageEnd => 1 << 1,
+            Event::QueueEnd => 1 << 2,
+        }
+    }
+}
+
+/// Per-channel interrupt handler, called with channel index and event.
+pub type Handler = fn(channel: usize, event: Event);
+
+/// Transfer statistics of one DMA channel.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Statistics {
+    /// Number of finished descriptor chains.
+    pub transfers: u32,
+    /// Number of aborted transfers.
+    pub errors: u32,
+    /// Number of interrupts without a registered handler, after which channel interrupts
+    /// are disabled and waiting tasks woken.
+    pub unhandled: u32,
+}
+
+static HANDLERS: [AtomicPtr<()>; CHANNEL_COUNT] =
+    [const { AtomicPtr::new(core::ptr::null_mut()) }; CHANNEL_COUNT];
+static TRANSFERS: [AtomicU32; CHANNEL_COUNT] = [const { AtomicU32::new(0) }; CHANNEL_COUNT];
+static ERRORS: [AtomicU32; CHANNEL_COUNT] = [const { AtomicU32::new(0) }; CHANNEL_COUNT];
+static UNHANDLED: [AtomicU32; CHANNEL_COUNT] = [const { AtomicU32::new(0) }; CHANNEL_COUNT];
+pub(crate) static WAKERS: WakerSet<CHANNEL_COUNT> = WakerSet::new();
+
+/// Register interrupt handler of DMA channel `channel`, replacing the previous one.
+#[inline]
+pub fn set_handler(channel: usize, handler: Handler) {
+    HANDLERS[channel].store(handler as *mut (), Ordering::Release);
+}
+
+/// Remove interrupt handler of DMA channel `channel`.
+#[inline]
+pub fn clear_handler(channel: usize) {
+    HANDLERS[channel].store(core::ptr::null_mut(), Ordering::Release);
+}
+
+/// Get transfer statistics of DMA channel `channel`.
+#[inline]
+pub fn statistics(channel: usize) -> Statistics {
+    Statistics {
+        transfers: TRANSFERS[channel].load(Ordering::Relaxed),
+        errors: ERRORS[channel].load(Ordering::Relaxed),
+        unhandled: UNHANDLED[channel].load(Ordering::Relaxed),
+    }
+}
+
+/// Reset transfer statistics of DMA channel `channel`.
+#[inline]
+pub fn reset_statistics(channel: usize) {
+    TRANSFERS[channel].store(0, Ordering::Relaxed);
+    ERRORS[channel].store(0, Ordering::Relaxed);
+    UNHANDLED[channel].store(0, Ordering::Relaxed);
+}
+
+#[inline]
+pub(crate) fn record_error(channel: usize) {
+    ERRORS[channel].fetch_add(1, Ordering::Relaxed);
+}
+
+/// DMAC interrupt entry point.
+///
+/// Call this function from the DMAC interrupt handler. It clears all enabled pending
+/// events, updates channel statistics and calls registered channel handlers. Channels
+/// without a handler get interrupts disabled and tasks waiting on them woken, counted in
+/// [`Statistics::unhandled`] apart from errors.
+pub fn on_interrupt(dmac: &RegisterBlock) {
+    for i in 0..2 {
+        let pending = dmac.irq_pending[i].read() & dmac.irq_enable[i].read();
+        if pending == 0 {
+            continue;
+        }
+        unsafe { dmac.irq_pending[i].write(pending) };
+        for j in 0..8 {
+            let channel = i * 8 + j;
+            let bits = (pending >> (j * 4)) & 0xF;
+            if bits == 0 {
+                continue;
+            }
+            if bits & Event::QueueEnd.bit() != 0 {
+                TRANSFERS[channel].fetch_add(1, Ordering::Relaxed);
+            }
+            let handler = HANDLERS[channel].load(Ordering::Acquire);
+            if handler.is_null() {
+                UNHANDLED[channel].fetch_add(1, Ordering::Relaxed);
+                unsafe { dmac.irq_enable[i].modify(|val| val & !(0xF << (j * 4))) };
+                WAKERS.wake(channel);
+                continue;
+            }
+            // note(unsafe): only `Handler` function pointers are stored in `HANDLERS`
+            let handler: Handler = unsafe { core::mem::transmute(handler) };
+            for event in [Event::HalfPackage, Event::PackageEnd, Event::QueueEnd] {
+                if bits & event.bit() != 0 {
+                    handler(channel, event);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Event, Statistics, UNHANDLED, record_error, reset_statistics, statistics};
+    use core::sync::atomic::Ordering;
+
+    #[test]
+    fn struct_event_functions() {
+        assert_eq!(Event::HalfPackage.bit(), 0x1);
+        assert_eq!(Event::PackageEnd.bit(), 0x2);
+        assert_eq!(Event::QueueEnd.bit(), 0x4);
+    }
+
+    #[test]
+    fn channel_statistics() {
+        reset_statistics(15);
+        record_error(15);
+        record_error(15);
+        assert_eq!(
+            statistics(15),
+            Statistics {
+                transfers: 0,
+                errors: 2,
+                unhandled: 0,
+            }
+        );
+        UNHANDLED[15].fetch_add(1, Ordering::Relaxed);
+        assert_eq!(statistics(15).unhandled, 1);
+        assert_eq!(statistics(15).errors, 2);
+        reset_statistics(15);
+        assert_eq!(statistics(15), Statistics::default());
+    }
+}
@@ -0,0 +1,540 @@
+use volatile_register::{RO, RW};
+
+/// Number of DMA channels on current SoC.
+pub const CHANNEL_COUNT: usize = 16;
+
+/// Direct Memory Access Controller registers.
+#[repr(C)]
+pub struct RegisterBlock {
+    /// 0x00 ..= 0x04 - DMAC IRQ Enable Registers 0 and 1.
+    pub irq_enable: [RW<u32>; 2],
+    _reserved0: [u32; 2],
+    /// 0x10 ..= 0x14 - DMAC IRQ Pending Registers 0 and 1.
+    pub irq_pending: [RW<u32>; 2],
+    _reserved1: [u32; 4],
+    /// 0x28 - DMAC Auto Gating Register.
+    pub auto_gating: RW<AutoGating>,
+    _reserved2: [u32; 1],
+    /// 0x30 - DMAC Status Register.
+    pub status: RO<u32>,
+    _reserved3: [u32; 51],
+    /// 0x100 ..= 0x4ff - DMAC channel register groups.
+    pub channels: [ChannelRegisters; CHANNEL_COUNT],
+}
+
+/// DMA channel register group.
+#[repr(C)]
+pub struct ChannelRegisters {
+    /// 0x00 - DMAC Channel Enable Register.
+    pub enable: RW<ChannelEnable>,
+    /// 0x04 - DMAC Channel Pause Register.
+    pub pause: RW<ChannelPause>,
+    /// 0x08 - DMAC Channel Start Address Register.
+    pub descriptor_address: RW<u32>,
+    /// 0x0C - DMAC Channel Configuration Register.
+    pub configuration: RO<Configuration>,
+    /// 0x10 - DMAC Channel Current Source Register.
+    pub current_source: RO<u32>,
+    /// 0x14 - DMAC Channel Current Destination Register.
+    pub current_destination: RO<u32>,
+    /// 0x18 - DMAC Channel Byte Counter Left Register.
+    pub byte_counter_left: RO<u32>,
+    /// 0x1C - DMAC Channel Parameter Register.
+    pub parameter: RO<Parameter>,
+    _reserved0: [u32; 2],
+    /// 0x28 - DMAC Mode Register.
+    pub mode: RW<u32>,
+    /// 0x2C - DMAC Former Descriptor Address Register.
+    pub former_descriptor_address: RO<u32>,
+    /// 0x30 - DMAC Package Number Register.
+    pub package_number: RO<u32>,
+    _reserved1: [u32; 3],
+}
+
+/// Auto gating register.
+///
+/// A set bit disables auto gating of the related circuit, keeping its clock running.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct AutoGating(u32);
+
+impl AutoGating {
+    const MCLK_CIRCUIT: u32 = 1 << 2;
+    const COMMON_CIRCUIT: u32 = 1 << 1;
+    const CHAN_CIRCUIT: u32 = 1 << 0;
+
+    /// If auto gating of DMA master clock interface is enabled.
+    #[inline]
+    pub const fn is_mclk_auto_gating_enabled(self) -> bool {
+        self.0 & Self::MCLK_CIRCUIT == 0
+    }
+    /// Enable auto gating of DMA master clock interface.
+    #[inline]
+    pub const fn enable_mclk_auto_gating(self) -> Self {
+        Self(self.0 & !Self::MCLK_CIRCUIT)
+    }
+    /// Disable auto gating of DMA master clock interface.
+    #[inline]
+    pub const fn disable_mclk_auto_gating(self) -> Self {
+        Self(self.0 | Self::MCLK_CIRCUIT)
+    }
+    /// If auto gating of DMA common circuit is enabled.
+    #[inline]
+    pub const fn is_common_auto_gating_enabled(self) -> bool {
+        self.0 & Self::COMMON_CIRCUIT == 0
+    }
+    /// Enable auto gating of DMA common circuit.
+    #[inline]
+    pub const fn enable_common_auto_gating(self) -> Self {
+        Self(self.0 & !Self::COMMON_CIRCUIT)
+    }
+    /// Disable auto gating of DMA common circuit.
+    #[inline]
+    pub const fn disable_common_auto_gating(self) -> Self {
+        Self(self.0 | Self::COMMON_CIRCUIT)
+    }
+    /// If auto gating of DMA channel circuit is enabled.
+    #[inline]
+    pub const fn is_channel_auto_gating_enabled(self) -> bool {
+        self.0 & Self::CHAN_CIRCUIT == 0
+    }
+    /// Enable auto gating of DMA channel circuit.
+    #[inline]
+    pub const fn enable_channel_auto_gating(self) -> Self {
+        Self(self.0 & !Self::CHAN_CIRCUIT)
+    }
+    /// Disable auto gating of DMA channel circuit.
+    #[inline]
+    pub const fn disable_channel_auto_gating(self) -> Self {
+        Self(self.0 | Self::CHAN_CIRCUIT)
+    }
+}
+
+/// Channel enable register.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct ChannelEnable(u32);
+
+impl ChannelEnable {
+    const DMA_EN: u32 = 1 << 0;
+
+    /// If the channel is enabled.
+    #[inline]
+    pub const fn is_enabled(self) -> bool {
+        self.0 & Self::DMA_EN != 0
+    }
+    /// Enable the channel.
+    #[inline]
+    pub const fn enable(self) -> Self {
+        Self(self.0 | Self::DMA_EN)
+    }
+    /// Disable the channel.
+    #[inline]
+    pub const fn disable(self) -> Self {
+        Self(self.0 & !Self::DMA_EN)
+    }
+}
+
+/// Channel pause register.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct ChannelPause(u32);
+
+impl ChannelPause {
+    const DMA_PAUSE: u32 = 1 << 0;
+
+    /// If transfer of the channel is paused.
+    #[inline]
+    pub const fn is_paused(self) -> bool {
+        self.0 & Self::DMA_PAUSE != 0
+    }
+    /// Pause transfer of the channel.
+    #[inline]
+    pub const fn pause(self) -> Self {
+        Self(self.0 | Self::DMA_PAUSE)
+    }
+    /// Resume transfer of the channel.
+    #[inline]
+    pub const fn resume(self) -> Self {
+        Self(self.0 & !Self::DMA_PAUSE)
+    }
+}
+
+/// Channel configuration, in descriptor and channel configuration register.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Default)]
+#[repr(transparent)]
+pub struct Configuration(u32);
+
+/// Number of data units transferred in one burst.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum BurstLength {
+    /// 1 unit per burst.
+    Single,
+    /// 4 units per burst.
+    Four,
+    /// 8 units per burst.
+    Eight,
+    /// 16 units per burst.
+    Sixteen,
+}
+
+/// Address mode of source or destination.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum AddressMode {
+    /// Address increments after each unit, used for memory.
+    Linear,
+    /// Address stays fixed, used for peripheral FIFO registers.
+    Io,
+}
+
+/// Width of one data unit.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum DataWidth {
+    /// 8-bit.
+    Bits8,
+    /// 16-bit.
+    Bits16,
+    /// 32-bit.
+    Bits32,
+    /// 64-bit.
+    Bits64,
+}
+
+impl Configuration {
+    const BMODE_SEL: u32 = 1 << 30;
+    const DEST_DATA_WIDTH: u32 = 0x3 << 25;
+    const DEST_ADDR_MODE: u32 = 1 << 24;
+    const DEST_BLOCK_SIZE: u32 = 0x3 << 22;
+    const DEST_DRQ_TYPE: u32 = 0x3f << 16;
+    const SRC_DATA_WIDTH: u32 = 0x3 << 9;
+    const SRC_ADDR_MODE: u32 = 1 << 8;
+    const SRC_BLOCK_SIZE: u32 = 0x3 << 6;
+    const SRC_DRQ_TYPE: u32 = 0x3f;
+
+    /// Get raw bits of this configuration.
+    #[inline]
+    pub const fn bits(self) -> u32 {
+        self.0
+    }
+    /// If BMODE is selected.
+    #[inline]
+    pub const fn is_bmode_enabled(self) -> bool {
+        self.0 & Self::BMODE_SEL != 0
+    }
+    /// Enable BMODE.
+    #[inline]
+    pub const fn enable_bmode(self) -> Self {
+        Self(self.0 | Self::BMODE_SEL)
+    }
+    /// Disable BMODE.
+    #[inline]
+    pub const fn disable_bmode(self) -> Self {
+        Self(self.0 & !Self::BMODE_SEL)
+    }
+    /// Get destination data width.
+    #[inline]
+    pub const fn destination_width(self) -> DataWidth {
+        width_from_bits((self.0 & Self::DEST_DATA_WIDTH) >> 25)
+    }
+    /// Set destination data width.
+    #[inline]
+    pub const fn set_destination_width(self, val: DataWidth) -> Self {
+        Self((self.0 & !Self::DEST_DATA_WIDTH) | ((val as u32) << 25))
+    }
+    /// Get destination address mode.
+    #[inline]
+    pub const fn destination_address_mode(self) -> AddressMode {
+        match (self.0 & Self::DEST_ADDR_MODE) >> 24 {
+            0 => AddressMode::Linear,
+            1 => AddressMode::Io,
+            _ => unreachable!(),
+        }
+    }
+    /// Set destination address mode.
+    #[inline]
+    pub const fn set_destination_address_mode(self, val: AddressMode) -> Self {
+        Self((self.0 & !Self::DEST_ADDR_MODE) | ((val as u32) << 24))
+    }
+    /// Get destination burst length.
+    #[inline]
+    pub const fn destination_burst(self) -> BurstLength {
+        burst_from_bits((self.0 & Self::DEST_BLOCK_SIZE) >> 22)
+    }
+    /// Set destination burst length.
+    #[inline]
+    pub const fn set_destination_burst(self, val: BurstLength) -> Self {
+        Self((self.0 & !Self::DEST_BLOCK_SIZE) | ((val as u32) << 22))
+    }
+    /// Get destination DRQ port.
+    #[inline]
+    pub const fn destination_drq(self) -> u8 {
+        ((self.0 & Self::DEST_DRQ_TYPE) >> 16) as u8
+    }
+    /// Set destination DRQ port.
+    ///
+    /// Value `val` should be in 0 ..= 63.
+    #[inline]
+    pub const fn set_destination_drq(self, val: u8) -> Self {
+        Self((self.0 & !Self::DEST_DRQ_TYPE) | (((val as u32) << 16) & Self::DEST_DRQ_TYPE))
+    }
+    /// Get source data width.
+    #[inline]
+    pub const fn source_width(self) -> DataWidth {
+        width_from_bits((self.0 & Self::SRC_DATA_WIDTH) >> 9)
+    }
+    /// Set source data width.
+    #[inline]
+    pub const fn set_source_width(self, val: DataWidth) -> Self {
+        Self((self.0 & !Self::SRC_DATA_WIDTH) | ((val as u32) << 9))
+    }
+    /// Get source address mode.
+    #[inline]
+    pub const fn source_address_mode(self) -> AddressMode {
+        match (self.0 & Self::SRC_ADDR_MODE) >> 8 {
+            0 => AddressMode::Linear,
+            1 => AddressMode::Io,
+            _ => unreachable!(),
+        }
+    }
+    /// Set source address mode.
+    #[inline]
+    pub const fn set_source_address_mode(self, val: AddressMode) -> Self {
+        Self((self.0 & !Self::SRC_ADDR_MODE) | ((val as u32) << 8))
+    }
+    /// Get source burst length.
+    #[inline]
+    pub const fn source_burst(self) -> BurstLength {
+        burst_from_bits((self.0 & Self::SRC_BLOCK_SIZE) >> 6)
+    }
+    /// Set source burst length.
+    #[inline]
+    pub const fn set_source_burst(self, val: BurstLength) -> Self {
+        Self((self.0 & !Self::SRC_BLOCK_SIZE) | ((val as u32) << 6))
+    }
+    /// Get source DRQ port.
+    #[inline]
+    pub const fn source_drq(self) -> u8 {
+        (self.0 & Self::SRC_DRQ_TYPE) as u8
+    }
+    /// Set source DRQ port.
+    ///
+    /// Value `val` should be in 0 ..= 63.
+    #[inline]
+    pub const fn set_source_drq(self, val: u8) -> Self {
+        Self((self.0 & !Self::SRC_DRQ_TYPE) | (val as u32 & Self::SRC_DRQ_TYPE))
+    }
+}
+
+#[inline]
+const fn width_from_bits(bits: u32) -> DataWidth {
+    match bits {
+        0 => DataWidth::Bits8,
+        1 => DataWidth::Bits16,
+        2 => DataWidth::Bits32,
+        3 => DataWidth::Bits64,
+        _ => unreachable!(),
+    }
+}
+
+#[inline]
+const fn burst_from_bits(bits: u32) -> BurstLength {
+    match bits {
+        0 => BurstLength::Single,
+        1 => BurstLength::Four,
+        2 => BurstLength::Eight,
+        3 => BurstLength::Sixteen,
+        _ => unreachable!(),
+    }
+}
+
+/// Channel parameter, in descriptor and channel parameter register.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Default)]
+#[repr(transparent)]
+pub struct Parameter(u32);
+
+impl Parameter {
+    const WAIT_CYC: u32 = 0xff;
+
+    /// Get raw bits of this parameter.
+    #[inline]
+    pub const fn bits(self) -> u32 {
+        self.0
+    }
+    /// Get wait clock cycles between two data requests in normal mode.
+    #[inline]
+    pub const fn wait_cycles(self) -> u8 {
+        (self.0 & Self::WAIT_CYC) as u8
+    }
+    /// Set wait clock cycles between two data requests in normal mode.
+    #[inline]
+    pub const fn set_wait_cycles(self, val: u8) -> Self {
+        Self((self.0 & !Self::WAIT_CYC) | val as u32)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        AddressMode, AutoGating, BurstLength, ChannelEnable, ChannelPause, ChannelRegisters,
+        Configuration, DataWidth, Parameter, RegisterBlock,
+    };
+    use core::mem::{offset_of, size_of};
+
+    #[test]
+    fn offset_dmac() {
+        assert_eq!(offset_of!(RegisterBlock, irq_enable), 0x00);
+        assert_eq!(offset_of!(RegisterBlock, irq_pending), 0x10);
+        assert_eq!(offset_of!(RegisterBlock, auto_gating), 0x28);
+        assert_eq!(offset_of!(RegisterBlock, status), 0x30);
+        assert_eq!(offset_of!(RegisterBlock, channels), 0x100);
+        assert_eq!(size_of::<RegisterBlock>(), 0x500);
+    }
+
+    #[test]
+    fn offset_channel() {
+        assert_eq!(offset_of!(ChannelRegisters, enable), 0x00);
+        assert_eq!(offset_of!(ChannelRegisters, pause), 0x04);
+        assert_eq!(offset_of!(ChannelRegisters, descriptor_address), 0x08);
+        assert_eq!(offset_of!(ChannelRegisters, configuration), 0x0C);
+        assert_eq!(offset_of!(ChannelRegisters, current_source), 0x10);
+        assert_eq!(offset_of!(ChannelRegisters, current_destination), 0x14);
+        assert_eq!(offset_of!(ChannelRegisters, byte_counter_left), 0x18);
+        assert_eq!(offset_of!(ChannelRegisters, parameter), 0x1C);
+        assert_eq!(offset_of!(ChannelRegisters, mode), 0x28);
+        assert_eq!(
+            offset_of!(ChannelRegisters, former_descriptor_address),
+            0x2C
+        );
+        assert_eq!(offset_of!(ChannelRegisters, package_number), 0x30);
+        assert_eq!(size_of::<ChannelRegisters>(), 0x40);
+    }
+
+    #[test]
+    fn struct_auto_gating_functions() {
+        let mut val = AutoGating(0x0);
+        assert!(val.is_mclk_auto_gating_enabled());
+        assert!(val.is_common_auto_gating_enabled());
+        assert!(val.is_channel_auto_gating_enabled());
+
+        val = val.disable_mclk_auto_gating();
+        assert!(!val.is_mclk_auto_gating_enabled());
+        assert_eq!(val.0, 0x00000004);
+
+        val = val.disable_common_auto_gating();
+        assert!(!val.is_common_auto_gating_enabled());
+        assert_eq!(val.0, 0x00000006);
+
+        val = val.disable_channel_auto_gating();
+        assert!(!val.is_channel_auto_gating_enabled());
+        assert_eq!(val.0, 0x00000007);
+
+        val = val.enable_mclk_auto_gating();
+        assert_eq!(val.0, 0x00000003);
+        val = val.enable_common_auto_gating();
+        assert_eq!(val.0, 0x00000001);
+        val = val.enable_channel_auto_gating();
+        assert_eq!(val.0, 0x00000000);
+    }
+
+    #[test]
+    fn struct_channel_enable_functions() {
+        let mut val = ChannelEnable(0x0);
+
+        val = val.enable();
+        assert!(val.is_enabled());
+        assert_eq!(val.0, 0x00000001);
+
+        val = val.disable();
+        assert!(!val.is_enabled());
+        assert_eq!(val.0, 0x00000000);
+    }
+
+    #[test]
+    fn struct_channel_pause_functions() {
+        let mut val = ChannelPause(0x0);
+
+        val = val.pause();
+        assert!(val.is_paused());
+        assert_eq!(val.0, 0x00000001);
+
+        val = val.resume();
+        assert!(!val.is_paused());
+        assert_eq!(val.0, 0x00000000);
+    }
+
+    #[test]
+    fn struct_configuration_functions() {
+        let mut val = Configuration(0x0);
+
+        val = val.enable_bmode();
+        assert!(val.is_bmode_enabled());
+        assert_eq!(val.0, 0x40000000);
+
+        val = val.disable_bmode();
+        assert!(!val.is_bmode_enabled());
+        assert_eq!(val.0, 0x00000000);
+
+        for i in 0..4u8 {
+            let (width, burst) = match i {
+                0x0 => (DataWidth::Bits8, BurstLength::Single),
+                0x1 => (DataWidth::Bits16, BurstLength::Four),
+                0x2 => (DataWidth::Bits32, BurstLength::Eight),
+                0x3 => (DataWidth::Bits64, BurstLength::Sixteen),
+                _ => unreachable!(),
+            };
+
+            val = Configuration(0x0).set_destination_width(width);
+            assert_eq!(val.destination_width(), width);
+            assert_eq!(val.0, (i as u32) << 25);
+
+            val = Configuration(0x0).set_destination_burst(burst);
+            assert_eq!(val.destination_burst(), burst);
+            assert_eq!(val.0, (i as u32) << 22);
+
+            val = Configuration(0x0).set_source_width(width);
+            assert_eq!(val.source_width(), width);
+            assert_eq!(val.0, (i as u32) << 9);
+
+            val = Configuration(0x0).set_source_burst(burst);
+            assert_eq!(val.source_burst(), burst);
+            assert_eq!(val.0, (i as u32) << 6);
+        }
+
+        val = Configuration(0x0).set_destination_address_mode(AddressMode::Io);
+        assert_eq!(val.destination_address_mode(), AddressMode::Io);
+        assert_eq!(val.0, 0x01000000);
+
+        val = val.set_destination_address_mode(AddressMode::Linear);
+        assert_eq!(val.destination_address_mode(), AddressMode::Linear);
+        assert_eq!(val.0, 0x00000000);
+
+        val = val.set_source_address_mode(AddressMode::Io);
+        assert_eq!(val.source_address_mode(), AddressMode::Io);
+        assert_eq!(val.0, 0x00000100);
+
+        val = val.set_source_address_mode(AddressMode::Linear);
+        assert_eq!(val.source_address_mode(), AddressMode::Linear);
+        assert_eq!(val.0, 0x00000000);
+
+        val = val.set_destination_drq(0x3f);
+        assert_eq!(val.destination_drq(), 0x3f);
+        assert_eq!(val.0, 0x003f0000);
+
+        val = Configuration(0x0).set_source_drq(0x3f);
+        assert_eq!(val.source_drq(), 0x3f);
+        assert_eq!(val.0, 0x0000003f);
+    }
+
+    #[test]
+    fn struct_parameter_functions() {
+        let mut val = Parameter(0x0);
+
+        val = val.set_wait_cycles(0xff);
+        assert_eq!(val.wait_cycles(), 0xff);
+        assert_eq!(val.0, 0x000000ff);
+
+        val = val.set_wait_cycles(0x0);
+        assert_eq!(val.wait_cycles(), 0x0);
+        assert_eq!(val.0, 0x00000000);
+    }
+}
@@ -0,0 +1,353 @@
+//! Digital microphone interface.
+//!
+//! DMIC clocks up to four PDM data lines, each carrying a left and a right microphone, and
+//! decimates them into 16-bit PCM samples. Enabled channels are packed into frames of
+//! interleaved samples in channel order, so that microphone arrays for far-field voice
+//! are sampled in lockstep.
+//!
+//! Module clock is 24.576 MHz from audio PLL 1 by default, giving sample rates of the
+//! 48-kHz family; [`Dmic::negotiate_sample_rate`] switches to the 44.1-kHz family when
+//! a requested rate needs it. [`Stream`] drains the FIFO through a DMA channel into a pair of buffers, and
+//! [`deinterleave`] splits a buffer of frames into one buffer per channel.
+//!
+//! FIFO raises [`Event::Available`] and [`Event::Overrun`] through [`on_interrupt`].
+
+mod register;
+#[cfg(feature = "d1")]
+mod stream;
+pub use register::*;
+#[cfg(feature = "d1")]
+pub use stream::*;
+
+use crate::audio::{self, Event, Events, Family, Handler, Negotiated};
+use crate::ccu::{self, ClockGate};
+use embedded_time::rate::Hertz;
+
+static EVENTS: Events = Events::new();
+
+/// DMIC interrupt entry point.
+///
+/// Call this function from DMIC interrupt handler. Each enabled FIFO event pending is
+/// cleared and raised to the registered handler and waiting task; data available interrupt
+/// is disabled once raised.
+#[inline]
+pub fn on_interrupt(dmic: &RegisterBlock) {
+    let control = dmic.int_control.read();
+    let status = dmic.int_status.read();
+    if control.is_data_interrupt_enabled() && status.is_data_available() {
+        unsafe {
+            dmic.int_control.modify(|val| val.disable_data_interrupt());
+            dmic.int_status
+                .write(InterruptStatus::default().clear_data_available());
+        }
+        EVENTS.raise(Event::Available);
+    }
+    if control.is_overrun_interrupt_enabled() && status.has_overrun() {
+        unsafe {
+            dmic.int_status
+                .write(InterruptStatus::default().clear_overrun())
+        };
+        EVENTS.raise(Event::Overrun);
+    }
+}
+
+/// Managed digital microphone interface structure with peripheral.
+pub struct Dmic<DMIC> {
+    dmic: DMIC,
+    channels: u8,
+    family: Family,
+}
+
+impl<DMIC: AsRef<RegisterBlock>> Dmic<DMIC> {
+    /// Create a DMIC instance with channels 0 and 1, left and right of data line 0.
+    ///
+    /// Audio PLL 1 is enabled if it is not yet, and DMIC clock is set to 24.576 MHz from
+    /// its divide-by-5 output, as in [`Family::Hz48000`].
+    #[inline]
+    pub fn new(dmic: DMIC, ccu: &ccu::RegisterBlock) -> Self {
+        let clock = Family::Hz48000.clock();
+        // note(unsafe): async read and write using ccu registers
+        unsafe {
+            Family::Hz48000.enable_pll(ccu);
+            ccu.dmic_clock.write(clock);
+            ccu::DMIC::reset(ccu);
+        }
+        let mut dmic = Self {
+            dmic,
+            channels: 0,
+            family: Family::Hz48000,
+        };
+        dmic.set_channels(0b11);
+        dmic
+    }
+    /// Set enabled channels, bit `n` for channel `n`.
+    ///
+    /// Channel `n` is the left microphone of data line `n / 2` if `n` is even, or the right
+    /// one otherwise. Samples of enabled channels are interleaved in channel order.
+    ///
+    /// # Panics
+    ///
+    /// Panics if no channel is enabled.
+    #[inline]
+    pub fn set_channels(&mut self, channels: u8) {
+        assert!(channels != 0, "at least one DMIC channel should be enabled");
+        let (count, map) = channel_map(channels);
+        let regs = self.dmic.as_ref();
+        unsafe {
+            regs.channel_number.write(count as u32 - 1);
+            regs.channel_map.write(map);
+            regs.enable.modify(|val| val.set_channels(channels));
+        }
+        self.channels = count;
+    }
+    /// Get number of enabled channels, that is samples in each frame.
+    #[inline]
+    pub fn channel_count(&self) -> usize {
+        self.channels as usize
+    }
+    /// Set sample rate, and oversample rate that keeps microphone clock near 3 MHz.
+    #[inline]
+    pub fn set_sample_rate(&mut self, rate: SampleRate) {
+        let oversample = match rate {
+            SampleRate::Hz48000 | SampleRate::Hz32000 => Oversample::X64,
+            _ => Oversample::X128,
+        };
+        let regs = self.dmic.as_ref();
+        unsafe {
+            regs.sample_rate.modify(|val| val.set_sample_rate(rate));
+            regs.control.modify(|val| val.set_oversample(oversample));
+        }
+    }
+    /// Get exact sample rate, from rate setting and module clock family.
+    #[inline]
+    pub fn sample_rate(&self) -> Hertz {
+        let rate = self.dmic.as_ref().sample_rate.read().sample_rate();
+        Hertz(self.family.module_clock().0 / rate.divider())
+    }
+    /// Set sample rate closest to `rate`, with DMIC clock from the audio PLL of the chosen
+    /// clock family.
+    ///
+    /// Returns chosen setting and exact sample rate achieved. DMIC should be stopped.
+    #[inline]
+    pub fn negotiate_sample_rate(
+        &mut self,
+        rate: Hertz,
+        ccu: &ccu::RegisterBlock,
+    ) -> Negotiated<SampleRate> {
+        let negotiated = audio::negotiate(rate, &SampleRate::ALL, SampleRate::divider);
+        // note(unsafe): async read and write using ccu registers
+        unsafe {
+            negotiated.family.enable_pll(ccu);
+            ccu.dmic_clock.write(negotiated.family.clock());
+        }
+        self.set_sample_rate(negotiated.rate);
+        self.family = negotiated.family;
+        negotiated
+    }
+    /// Set digital volume of `channel`.
+    ///
+    /// Volume is 0 dB at [`Volume::ZERO_DB`], changing by 0.75 dB per step; zero mutes.
+    #[inline]
+    pub fn set_volume(&mut self, channel: usize, volume: u8) {
+        let register = &self.dmic.as_ref().volume[channel / 4];
+        unsafe { register.modify(|val| val.set_volume(channel % 4, volume)) };
+    }
+    /// Set whether left and right channels of data line `index` are swapped.
+    ///
+    /// Microphones of a pair are wired to sample on opposite clock edges; swapping fixes
+    /// a pair wired the other way around.
+    #[inline]
+    pub fn set_swapped(&mut self, index: usize, swapped: bool) {
+        unsafe {
+            self.dmic
+                .as_ref()
+                .control
+                .modify(|val| val.set_swapped(index, swapped))
+        };
+    }
+    /// Start DMIC, requesting DMA transfers of 16-bit samples from its FIFO.
+    ///
+    /// DMA channel should move 16-bit samples from [`Dmic::fifo_address`] with request port
+    /// `DMIC_RX`; [`Stream`] sets this up with a pair of buffers.
+    #[inline]
+    pub fn start_dma(&mut self) {
+        let regs = self.dmic.as_ref();
+        unsafe {
+            regs.fifo_control
+                .modify(|val| val.set_lsb_aligned(true).set_24_bit(false).flush_fifo());
+            regs.int_status
+                .write(InterruptStatus::default().clear_overrun());
+            regs.int_control.modify(|val| val.enable_drq());
+            regs.enable.modify(|val| val.enable());
+        }
+    }
+    /// Stop DMIC and its DMA requests.
+    #[inline]
+    pub fn stop_dma(&mut self) {
+        let regs = self.dmic.as_ref();
+        unsafe {
+            regs.enable.modify(|val| val.disable());
+            regs.int_control.modify(|val| val.disable_drq());
+        }
+    }
+    /// Get address of FIFO data register as DMA transfer source.
+    #[inline]
+    pub fn fifo_address(&self) -> usize {
+        &self.dmic.as_ref().data as *const _ as usize
+    }
+    /// Check if FIFO has overrun since last cleared, losing samples.
+    #[inline]
+    pub fn is_overrun(&self) -> bool {
+        self.dmic.as_ref().int_status.read().has_overrun()
+    }
+    /// Clear FIFO overrun flag.
+    #[inline]
+    pub fn clear_overrun(&mut self) {
+        unsafe {
+            self.dmic
+                .as_ref()
+                .int_status
+                .write(InterruptStatus::default().clear_overrun())
+        };
+    }
+    /// Enable interrupt of FIFO `event`, raised through [`on_interrupt`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `event` is a transmit FIFO event.
+    #[inline]
+    pub fn enable_interrupt(&mut self, event: Event) {
+        unsafe {
+            self.dmic.as_ref().int_control.modify(|val| match event {
+                Event::Available => val.enable_data_interrupt(),
+                Event::Overrun => val.enable_overrun_interrupt(),
+                _ => panic!("DMIC has no transmit FIFO"),
+            })
+        };
+    }
+    /// Disable interrupt of FIFO `event`.
+    #[inline]
+    pub fn disable_interrupt(&mut self, event: Event) {
+        unsafe {
+            self.dmic.as_ref().int_control.modify(|val| match event {
+                Event::Available => val.disable_data_interrupt(),
+                Event::Overrun => val.disable_overrun_interrupt(),
+                _ => val,
+            })
+        };
+    }
+    /// Register handler called from [`on_interrupt`] on each raised event, or remove it.
+    #[inline]
+    pub fn set_handler(&mut self, handler: Option<Handler>) {
+        EVENTS.set_handler(handler);
+    }
+    /// Wait for next FIFO `event`, enabling its interrupt.
+    ///
+    /// Events raised before this call are discarded.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `event` is a transmit FIFO event.
+    #[inline]
+    pub async fn wait_event(&mut self, event: Event) {
+        EVENTS.take(event);
+        self.enable_interrupt(event);
+        EVENTS.wait(event).await;
+    }
+    /// Get number of times FIFO `event` has been raised.
+    #[inline]
+    pub fn event_count(&self, event: Event) -> u32 {
+        EVENTS.count(event)
+    }
+    /// Get number of FIFO overruns raised.
+    #[inline]
+    pub fn xruns(&self) -> u32 {
+        EVENTS.xruns()
+    }
+    /// Close DMIC and release peripheral.
+    #[inline]
+    pub fn free(self, ccu: &ccu::RegisterBlock) -> DMIC {
+        let regs = self.dmic.as_ref();
+        unsafe {
+            regs.enable.write(Enable::default());
+            regs.int_control.modify(|val| {
+                val.disable_drq()
+                    .disable_data_interrupt()
+                    .disable_overrun_interrupt()
+            });
+            ccu::DMIC::free(ccu);
+            ccu.dmic_clock.modify(|val| val.mask_clock());
+        }
+        self.dmic
+    }
+}
+
+/// Split interleaved `frames` into one buffer per channel.
+///
+/// Sample `n` of each frame goes to `channels[n]`; frames are copied until `frames` or any
+/// channel buffer runs out. Returns number of frames copied.
+///
+/// # Panics
+///
+/// Panics if `channels` is empty.
+#[inline]
+pub fn deinterleave(frames: &[i16], channels: &mut [&mut [i16]]) -> usize {
+    assert!(
+        !channels.is_empty(),
+        "at least one channel buffer is needed"
+    );
+    let count = channels
+        .iter()
+        .map(|channel| channel.len())
+        .fold(frames.len() / channels.len(), usize::min);
+    for (index, frame) in frames.chunks_exact(channels.len()).take(count).enumerate() {
+        for (channel, &sample) in channels.iter_mut().zip(frame) {
+            channel[index] = sample;
+        }
+    }
+    count
+}
+
+/// Get number of enabled `channels` and channel mapping register value packing them in
+/// channel order.
+#[inline]
+const fn channel_map(channels: u8) -> (u8, u32) {
+    let mut count = 0;
+    let mut map = 0;
+    let mut channel = 0;
+    while channel < CHANNEL_COUNT {
+        if channels & (1 << channel) != 0 {
+            map |= (channel as u32) << (count * 4);
+            count += 1;
+        }
+        channel += 1;
+    }
+    (count, map)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{channel_map, deinterleave};
+
+    #[test]
+    fn function_channel_map() {
+        assert_eq!(channel_map(0b0000_0011), (2, 0x10));
+        assert_eq!(channel_map(0b1010_0100), (3, 0x752));
+        assert_eq!(channel_map(0xFF), (8, 0x7654_3210));
+    }
+
+    #[test]
+    fn function_deinterleave() {
+        let frames = [1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12];
+        let mut a = [0; 4];
+        let mut b = [0; 4];
+        let mut c = [0; 4];
+        assert_eq!(deinterleave(&frames, &mut [&mut a, &mut b, &mut c]), 4);
+        assert_eq!((a, b, c), ([1, 4, 7, 10], [2, 5, 8, 11], [3, 6, 9, 12]));
+
+        let mut a = [0; 2];
+        let mut b = [0; 8];
+        assert_eq!(deinterleave(&frames, &mut [&mut a, &mut b]), 2);
+        assert_eq!((a, &b[..3]), ([1, 3], &[2, 4, 0][..]));
+    }
+}
@@ -0,0 +1,536 @@
+use volatile_register::{RO, RW};
+
+/// Number of DMIC channels, left and right on each of four data lines.
+pub const CHANNEL_COUNT: usize = 8;
+
+/// Digital microphone interface registers.
+#[repr(C)]
+pub struct RegisterBlock {
+    /// 0x00 - DMIC Enable Control Register.
+    pub enable: RW<Enable>,
+    /// 0x04 - DMIC Sample Rate Register.
+    pub sample_rate: RW<SampleRateControl>,
+    /// 0x08 - DMIC Control Register.
+    pub control: RW<Control>,
+    _reserved0: u32,
+    /// 0x10 - DMIC Data Register.
+    pub data: RO<u32>,
+    /// 0x14 - DMIC Interrupt Control Register.
+    pub int_control: RW<InterruptControl>,
+    /// 0x18 - DMIC Interrupt Status Register.
+    pub int_status: RW<InterruptStatus>,
+    /// 0x1c - DMIC RX FIFO Control Register.
+    pub fifo_control: RW<FifoControl>,
+    /// 0x20 - DMIC RX FIFO Status Register.
+    pub fifo_status: RO<u32>,
+    /// 0x24 - DMIC Channel Number Register.
+    pub channel_number: RW<u32>,
+    /// 0x28 - DMIC Channel Mapping Register.
+    pub channel_map: RW<u32>,
+    /// 0x2c - DMIC Counter Register.
+    pub count: RW<u32>,
+    /// 0x30..=0x34 - Data0 and Data1, Data2 and Data3 Volume Control Registers.
+    pub volume: [RW<Volume>; 2],
+    /// 0x38 - High Pass Filter Enable Control Register.
+    pub hpf_enable: RW<u32>,
+}
+
+/// Sample rate of DMIC, from 24.576-MHz module clock.
+///
+/// With 22.5792-MHz module clock of the 44.1-kHz family, each rate scales by 147/160.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum SampleRate {
+    /// 48 kHz.
+    Hz48000 = 0,
+    /// 32 kHz.
+    Hz32000 = 1,
+    /// 24 kHz.
+    Hz24000 = 2,
+    /// 16 kHz.
+    Hz16000 = 3,
+    /// 12 kHz.
+    Hz12000 = 4,
+    /// 8 kHz.
+    Hz8000 = 5,
+}
+
+impl SampleRate {
+    /// All sample rates, from highest to lowest.
+    pub const ALL: [SampleRate; 6] = [
+        SampleRate::Hz48000,
+        SampleRate::Hz32000,
+        SampleRate::Hz24000,
+        SampleRate::Hz16000,
+        SampleRate::Hz12000,
+        SampleRate::Hz8000,
+    ];
+
+    /// Get number of module clock cycles per sample.
+    #[inline]
+    pub const fn divider(self) -> u32 {
+        match self {
+            SampleRate::Hz48000 => 512,
+            SampleRate::Hz32000 => 768,
+            SampleRate::Hz24000 => 1024,
+            SampleRate::Hz16000 => 1536,
+            SampleRate::Hz12000 => 2048,
+            SampleRate::Hz8000 => 3072,
+        }
+    }
+}
+
+/// Ratio of microphone clock to sample rate.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Oversample {
+    /// 128 times, for sample rates up to 24 kHz.
+    X128 = 0,
+    /// 64 times, for sample rates from 16 kHz.
+    X64 = 1,
+}
+
+/// DMIC enable control register.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct Enable(u32);
+
+impl Enable {
+    const GLOBE_EN: u32 = 1 << 8;
+    const DATA_CH_EN: u32 = 0xFF;
+
+    /// If DMIC is enabled.
+    #[inline]
+    pub const fn is_enabled(self) -> bool {
+        self.0 & Self::GLOBE_EN != 0
+    }
+    /// Enable DMIC.
+    #[inline]
+    pub const fn enable(self) -> Self {
+        Self(self.0 | Self::GLOBE_EN)
+    }
+    /// Disable DMIC.
+    #[inline]
+    pub const fn disable(self) -> Self {
+        Self(self.0 & !Self::GLOBE_EN)
+    }
+    /// Get enabled channels, bit `n` for channel `n`.
+    #[inline]
+    pub const fn channels(self) -> u8 {
+        (self.0 & Self::DATA_CH_EN) as u8
+    }
+    /// Set enabled channels, bit `n` for channel `n`.
+    ///
+    /// Channel `n` is the left microphone of data line `n / 2` if `n` is even, or the
+    /// right one otherwise.
+    #[inline]
+    pub const fn set_channels(self, val: u8) -> Self {
+        Self((self.0 & !Self::DATA_CH_EN) | val as u32)
+    }
+}
+
+impl Default for Enable {
+    #[inline]
+    fn default() -> Self {
+        Self(0x0000_0000)
+    }
+}
+
+/// DMIC sample rate register.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct SampleRateControl(u32);
+
+impl SampleRateControl {
+    const DMIC_SR: u32 = 0x7;
+
+    /// Get sample rate.
+    #[inline]
+    pub const fn sample_rate(self) -> SampleRate {
+        match self.0 & Self::DMIC_SR {
+            0 => SampleRate::Hz48000,
+            1 => SampleRate::Hz32000,
+            2 => SampleRate::Hz24000,
+            3 => SampleRate::Hz16000,
+            4 => SampleRate::Hz12000,
+            5 => SampleRate::Hz8000,
+            _ => panic!("reserved sample rate"),
+        }
+    }
+    /// Set sample rate.
+    #[inline]
+    pub const fn set_sample_rate(self, val: SampleRate) -> Self {
+        Self((self.0 & !Self::DMIC_SR) | val as u32)
+    }
+}
+
+impl Default for SampleRateControl {
+    #[inline]
+    fn default() -> Self {
+        Self(0x0000_0000)
+    }
+}
+
+/// DMIC control register.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct Control(u32);
+
+impl Control {
+    const DATA_LR_SWAP_EN: u32 = 0xF << 4;
+    const DMIC_OVERSAMPLE_RATE: u32 = 1 << 0;
+
+    /// If left and right channels of data line `index` are swapped.
+    #[inline]
+    pub const fn is_swapped(self, index: usize) -> bool {
+        self.0 & (1 << (4 + index)) != 0
+    }
+    /// Set whether left and right channels of data line `index` are swapped.
+    #[inline]
+    pub const fn set_swapped(self, index: usize, val: bool) -> Self {
+        let bit = (1 << (4 + index)) & Self::DATA_LR_SWAP_EN;
+        if val {
+            Self(self.0 | bit)
+        } else {
+            Self(self.0 & !bit)
+        }
+    }
+    /// Get oversample rate.
+    #[inline]
+    pub const fn oversample(self) -> Oversample {
+        if self.0 & Self::DMIC_OVERSAMPLE_RATE != 0 {
+            Oversample::X64
+        } else {
+            Oversample::X128
+        }
+    }
+    /// Set oversample rate.
+    #[inline]
+    pub const fn set_oversample(self, val: Oversample) -> Self {
+        Self((self.0 & !Self::DMIC_OVERSAMPLE_RATE) | val as u32)
+    }
+}
+
+impl Default for Control {
+    #[inline]
+    fn default() -> Self {
+        Self(0x0000_0000)
+    }
+}
+
+/// DMIC interrupt control register.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct InterruptControl(u32);
+
+impl InterruptControl {
+    const FIFO_DRQ_EN: u32 = 1 << 2;
+    const FIFO_OVERRUN_IRQ_EN: u32 = 1 << 1;
+    const DATA_IRQ_EN: u32 = 1 << 0;
+
+    /// If DMA request is enabled.
+    #[inline]
+    pub const fn is_drq_enabled(self) -> bool {
+        self.0 & Self::FIFO_DRQ_EN != 0
+    }
+    /// Enable DMA request.
+    #[inline]
+    pub const fn enable_drq(self) -> Self {
+        Self(self.0 | Self::FIFO_DRQ_EN)
+    }
+    /// Disable DMA request.
+    #[inline]
+    pub const fn disable_drq(self) -> Self {
+        Self(self.0 & !Self::FIFO_DRQ_EN)
+    }
+    /// If FIFO overrun interrupt is enabled.
+    #[inline]
+    pub const fn is_overrun_interrupt_enabled(self) -> bool {
+        self.0 & Self::FIFO_OVERRUN_IRQ_EN != 0
+    }
+    /// Enable FIFO overrun interrupt.
+    #[inline]
+    pub const fn enable_overrun_interrupt(self) -> Self {
+        Self(self.0 | Self::FIFO_OVERRUN_IRQ_EN)
+    }
+    /// Disable FIFO overrun interrupt.
+    #[inline]
+    pub const fn disable_overrun_interrupt(self) -> Self {
+        Self(self.0 & !Self::FIFO_OVERRUN_IRQ_EN)
+    }
+    /// If FIFO data available interrupt is enabled.
+    #[inline]
+    pub const fn is_data_interrupt_enabled(self) -> bool {
+        self.0 & Self::DATA_IRQ_EN != 0
+    }
+    /// Enable FIFO data available interrupt.
+    #[inline]
+    pub const fn enable_data_interrupt(self) -> Self {
+        Self(self.0 | Self::DATA_IRQ_EN)
+    }
+    /// Disable FIFO data available interrupt.
+    #[inline]
+    pub const fn disable_data_interrupt(self) -> Self {
+        Self(self.0 & !Self::DATA_IRQ_EN)
+    }
+}
+
+impl Default for InterruptControl {
+    #[inline]
+    fn default() -> Self {
+        Self(0x0000_0000)
+    }
+}
+
+/// DMIC interrupt status register.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct InterruptStatus(u32);
+
+impl InterruptStatus {
+    const FIFO_OVERRUN_IRQ_PENDING: u32 = 1 << 1;
+    const FIFO_DATA_IRQ_PENDING: u32 = 1 << 0;
+
+    /// If FIFO has overrun.
+    #[inline]
+    pub const fn has_overrun(self) -> bool {
+        self.0 & Self::FIFO_OVERRUN_IRQ_PENDING != 0
+    }
+    /// Clear FIFO overrun flag on write.
+    #[inline]
+    pub const fn clear_overrun(self) -> Self {
+        Self(self.0 | Self::FIFO_OVERRUN_IRQ_PENDING)
+    }
+    /// If FIFO data has reached trigger level.
+    #[inline]
+    pub const fn is_data_available(self) -> bool {
+        self.0 & Self::FIFO_DATA_IRQ_PENDING != 0
+    }
+    /// Clear FIFO data available flag on write.
+    #[inline]
+    pub const fn clear_data_available(self) -> Self {
+        Self(self.0 | Self::FIFO_DATA_IRQ_PENDING)
+    }
+}
+
+impl Default for InterruptStatus {
+    #[inline]
+    fn default() -> Self {
+        Self(0x0000_0000)
+    }
+}
+
+/// DMIC RX FIFO control register.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct FifoControl(u32);
+
+impl FifoControl {
+    const DMIC_RXFIFO_FLUSH: u32 = 1 << 31;
+    const RXFIFO_MODE: u32 = 1 << 9;
+    const DMIC_SAMPLE_RESOLUTION: u32 = 1 << 8;
+    const FIFO_TRG_LEVEL: u32 = 0xFF;
+
+    /// Flush FIFO, cleared by hardware when done.
+    #[inline]
+    pub const fn flush_fifo(self) -> Self {
+        Self(self.0 | Self::DMIC_RXFIFO_FLUSH)
+    }
+    /// If samples are sign-extended in low bits of data register, or in high bits otherwise.
+    #[inline]
+    pub const fn is_lsb_aligned(self) -> bool {
+        self.0 & Self::RXFIFO_MODE != 0
+    }
+    /// Set whether samples are sign-extended in low bits of data register.
+    #[inline]
+    pub const fn set_lsb_aligned(self, val: bool) -> Self {
+        if val {
+            Self(self.0 | Self::RXFIFO_MODE)
+        } else {
+            Self(self.0 & !Self::RXFIFO_MODE)
+        }
+    }
+    /// If samples are 24 bits wide, or 16 bits otherwise.
+    #[inline]
+    pub const fn is_24_bit(self) -> bool {
+        self.0 & Self::DMIC_SAMPLE_RESOLUTION != 0
+    }
+    /// Set whether samples are 24 bits wide, or 16 bits otherwise.
+    #[inline]
+    pub const fn set_24_bit(self, val: bool) -> Self {
+        if val {
+            Self(self.0 | Self::DMIC_SAMPLE_RESOLUTION)
+        } else {
+            Self(self.0 & !Self::DMIC_SAMPLE_RESOLUTION)
+        }
+    }
+    /// Get number of FIFO samples that triggers DMA request or data interrupt.
+    #[inline]
+    pub const fn trigger_level(self) -> u8 {
+        (self.0 & Self::FIFO_TRG_LEVEL) as u8
+    }
+    /// Set number of FIFO samples that triggers DMA request or data interrupt.
+    #[inline]
+    pub const fn set_trigger_level(self, val: u8) -> Self {
+        Self((self.0 & !Self::FIFO_TRG_LEVEL) | val as u32)
+    }
+}
+
+impl Default for FifoControl {
+    #[inline]
+    fn default() -> Self {
+        Self(0x0000_0040)
+    }
+}
+
+/// DMIC volume control register of two data lines.
+///
+/// Volume is 0 dB at [`Volume::ZERO_DB`], changing by 0.75 dB per step; zero mutes.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct Volume(u32);
+
+impl Volume {
+    /// Volume value of 0 dB.
+    pub const ZERO_DB: u8 = 0xA0;
+
+    /// Get volume of channel `index` in this register, from 0 to 3.
+    ///
+    /// Even indices are left channels and odd ones right channels, of first data line for
+    /// 0 and 1, and of second data line for 2 and 3.
+    #[inline]
+    pub const fn volume(self, index: usize) -> u8 {
+        (self.0 >> volume_shift(index)) as u8
+    }
+    /// Set volume of channel `index` in this register, from 0 to 3.
+    #[inline]
+    pub const fn set_volume(self, index: usize, val: u8) -> Self {
+        let shift = volume_shift(index);
+        Self((self.0 & !(0xFF << shift)) | ((val as u32) << shift))
+    }
+}
+
+impl Default for Volume {
+    #[inline]
+    fn default() -> Self {
+        Self(0xA0A0_A0A0)
+    }
+}
+
+/// Bit offset of channel `index` in a volume register; left channel of each data line
+/// occupies the higher byte.
+#[inline]
+const fn volume_shift(index: usize) -> usize {
+    (index / 2) * 16 + if index.is_multiple_of(2) { 8 } else { 0 }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        Control, Enable, FifoControl, InterruptControl, InterruptStatus, Oversample, RegisterBlock,
+        SampleRate, SampleRateControl, Volume,
+    };
+    use core::mem::offset_of;
+
+    #[test]
+    fn offset_dmic() {
+        assert_eq!(offset_of!(RegisterBlock, enable), 0x00);
+        assert_eq!(offset_of!(RegisterBlock, sample_rate), 0x04);
+        assert_eq!(offset_of!(RegisterBlock, control), 0x08);
+        assert_eq!(offset_of!(RegisterBlock, data), 0x10);
+        assert_eq!(offset_of!(RegisterBlock, int_control), 0x14);
+        assert_eq!(offset_of!(RegisterBlock, int_status), 0x18);
+        assert_eq!(offset_of!(RegisterBlock, fifo_control), 0x1c);
+        assert_eq!(offset_of!(RegisterBlock, fifo_status), 0x20);
+        assert_eq!(offset_of!(RegisterBlock, channel_number), 0x24);
+        assert_eq!(offset_of!(RegisterBlock, channel_map), 0x28);
+        assert_eq!(offset_of!(RegisterBlock, count), 0x2c);
+        assert_eq!(offset_of!(RegisterBlock, volume), 0x30);
+        assert_eq!(offset_of!(RegisterBlock, hpf_enable), 0x38);
+    }
+
+    #[test]
+    fn struct_enable_functions() {
+        let val = Enable::default().enable().set_channels(0x0F);
+        assert!(val.is_enabled());
+        assert_eq!(val.channels(), 0x0F);
+        assert_eq!(val.0, 0x0000_010F);
+        assert_eq!(val.disable().set_channels(0x80).0, 0x0000_0080);
+    }
+
+    #[test]
+    fn struct_sample_rate_control_functions() {
+        let val = SampleRateControl::default();
+        assert_eq!(val.sample_rate(), SampleRate::Hz48000);
+        let val = val.set_sample_rate(SampleRate::Hz8000);
+        assert_eq!(val.sample_rate(), SampleRate::Hz8000);
+        assert_eq!(val.0, 0x0000_0005);
+    }
+
+    #[test]
+    fn struct_control_functions() {
+        let val = Control::default();
+        assert_eq!(val.oversample(), Oversample::X128);
+        let val = val.set_oversample(Oversample::X64).set_swapped(2, true);
+        assert_eq!(val.oversample(), Oversample::X64);
+        assert!(val.is_swapped(2));
+        assert!(!val.is_swapped(0));
+        assert_eq!(val.0, 0x0000_0041);
+        assert_eq!(val.set_swapped(2, false).0, 0x0000_0001);
+    }
+
+    #[test]
+    fn struct_interrupt_control_functions() {
+        let val = InterruptControl::default()
+            .enable_drq()
+            .enable_overrun_interrupt()
+            .enable_data_interrupt();
+        assert!(val.is_drq_enabled());
+        assert!(val.is_overrun_interrupt_enabled());
+        assert!(val.is_data_interrupt_enabled());
+        assert_eq!(val.0, 0x0000_0007);
+        let val = val
+            .disable_drq()
+            .disable_overrun_interrupt()
+            .disable_data_interrupt();
+        assert_eq!(val.0, 0x0000_0000);
+    }
+
+    #[test]
+    fn struct_interrupt_status_functions() {
+        let val = InterruptStatus(0x0000_0002);
+        assert!(val.has_overrun());
+        assert!(!val.is_data_available());
+        let val = InterruptStatus::default()
+            .clear_overrun()
+            .clear_data_available();
+        assert_eq!(val.0, 0x0000_0003);
+    }
+
+    #[test]
+    fn struct_fifo_control_functions() {
+        let val = FifoControl::default();
+        assert_eq!(val.trigger_level(), 0x40);
+        let val = val
+            .set_lsb_aligned(true)
+            .set_24_bit(true)
+            .set_trigger_level(0x20);
+        assert!(val.is_lsb_aligned());
+        assert!(val.is_24_bit());
+        assert_eq!(val.trigger_level(), 0x20);
+        assert_eq!(val.0, 0x0000_0320);
+        let val = val.set_lsb_aligned(false).set_24_bit(false).flush_fifo();
+        assert_eq!(val.0, 0x8000_0020);
+    }
+
+    #[test]
+    fn struct_volume_functions() {
+        let val = Volume::default();
+        assert_eq!(val.volume(0), Volume::ZERO_DB);
+        assert_eq!(val.volume(3), Volume::ZERO_DB);
+        let val = val.set_volume(0, 0x11).set_volume(1, 0x22);
+        assert_eq!(val.0, 0xA0A0_1122);
+        let val = val.set_volume(2, 0x33).set_volume(3, 0x44);
+        assert_eq!(val.0, 0x3344_1122);
+        assert_eq!(val.volume(2), 0x33);
+        assert_eq!(val.volume(1), 0x22);
+    }
+}
@@ -0,0 +1,135 @@
+use super::{Dmic, RegisterBlock};
+use crate::audio::{self, Source};
+use crate::dmac::{
+    AddressMode, BurstLength, Channel, DataWidth, Descriptor, DmaConfig, StreamParts, drq,
+};
+use crate::time::TimeoutError;
+use core::marker::PhantomData;
+use embedded_time::rate::Hertz;
+
+/// DMIC samples streamed by a DMA channel into two buffers of `L` samples each.
+///
+/// Each buffer holds whole frames of interleaved samples of enabled channels, to be split
+/// with [`deinterleave`](super::deinterleave). DMA fills both buffers in turn, endlessly;
+/// [`Stream::ready`] hands out a buffer once DMA has moved on to the other one. A buffer
+/// should be consumed before DMA comes back to it, otherwise its samples are overwritten.
+pub struct Stream<'a, const N: usize, const L: usize> {
+    channel: Channel<'a, N>,
+    buffers: *const [[i16; L]; 2],
+    descriptors: &'a mut [Descriptor; 2],
+    next: usize,
+    channels: usize,
+    sample_rate: Hertz,
+    _buffers: PhantomData<&'a mut [[i16; L]; 2]>,
+}
+
+impl<'a, const N: usize, const L: usize> Stream<'a, N, L> {
+    /// Start streaming samples of enabled `dmic` channels into `buffers` on DMA `channel`,
+    /// using `descriptors` as a two-descriptor ring.
+    ///
+    /// Channels and sample rate are set on `dmic` beforehand.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `L` is zero or not a multiple of enabled channel count.
+    #[inline]
+    pub fn start<DMIC: AsRef<RegisterBlock>>(
+        dmic: &mut Dmic<DMIC>,
+        mut channel: Channel<'a, N>,
+        buffers: &'a mut [[i16; L]; 2],
+        descriptors: &'a mut [Descriptor; 2],
+    ) -> Self {
+        assert!(
+            L != 0 && L.is_multiple_of(dmic.channel_count()),
+            "stream buffer should hold whole frames"
+        );
+        let config = DmaConfig {
+            source_drq: drq::DMIC_RX,
+            source_address_mode: AddressMode::Io,
+            source_burst: BurstLength::Single,
+            source_width: DataWidth::Bits16,
+            destination_drq: drq::DRAM,
+            destination_address_mode: AddressMode::Linear,
+            destination_burst: BurstLength::Single,
+            destination_width: DataWidth::Bits16,
+            ..DmaConfig::default()
+        };
+        let source = dmic.fifo_address() as u32;
+        let byte_count = (L * size_of::<i16>()) as u32;
+        for (descriptor, buffer) in descriptors.iter_mut().zip(buffers.iter()) {
+            *descriptor =
+                Descriptor::new(&config, source, buffer.as_ptr() as usize as u32, byte_count);
+        }
+        let (first, second) = descriptors.split_at_mut(1);
+        first[0].set_link(&second[0]);
+        second[0].set_link(&first[0]);
+        dmic.start_dma();
+        // note(unsafe): buffers and descriptors are borrowed by `Stream` until stopped
+        unsafe { channel.start(&descriptors[0]) };
+        Self {
+            channel,
+            buffers,
+            descriptors,
+            next: 0,
+            channels: dmic.channel_count(),
+            sample_rate: dmic.sample_rate(),
+            _buffers: PhantomData,
+        }
+    }
+    /// Get next buffer filled by DMA, if any.
+    #[inline]
+    pub fn ready(&mut self) -> Option<&[i16; L]> {
+        let end = self.buffers as usize as u32 + (L * size_of::<i16>()) as u32;
+        // destination right at the end of first buffer counts as second buffer
+        let writing = (self.channel.current_destination() >= end) as usize;
+        if writing == self.next {
+            return None;
+        }
+        let index = self.next;
+        self.next ^= 1;
+        // note(unsafe): DMA is writing the other buffer
+        Some(unsafe { &(*self.buffers)[index] })
+    }
+    /// Get DMA channel, for example to enable `PackageEnd` interrupt on each full buffer.
+    #[inline]
+    pub fn channel(&mut self) -> &mut Channel<'a, N> {
+        &mut self.channel
+    }
+    /// Stop streaming and release DMA channel, buffers and descriptors.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`TimeoutError`] if DMA channel doesn't stop before crate-wide blocking
+    /// timeout; buffers are then not released.
+    #[inline]
+    pub fn stop<DMIC: AsRef<RegisterBlock>>(
+        mut self,
+        dmic: &mut Dmic<DMIC>,
+    ) -> Result<StreamParts<'a, N, [i16; L]>, TimeoutError> {
+        self.channel.abort()?;
+        dmic.stop_dma();
+        // note(unsafe): DMA has stopped, buffers are exclusively borrowed again
+        let buffers = unsafe { &mut *(self.buffers as *mut [[i16; L]; 2]) };
+        Ok((self.channel, buffers, self.descriptors))
+    }
+}
+
+impl<const N: usize, const L: usize> audio::Stream for Stream<'_, N, L> {
+    type Sample = i16;
+
+    #[inline]
+    fn channels(&self) -> usize {
+        self.channels
+    }
+    #[inline]
+    fn sample_rate(&self) -> Hertz {
+        self.sample_rate
+    }
+}
+
+impl<const N: usize, const L: usize> Source for Stream<'_, N, L> {
+    #[inline]
+    fn read_buffer(&mut self) -> Option<&[i16]> {
+        self.ready().map(|buffer| buffer.as_slice())
+    }
+}
@@ -0,0 +1,143 @@
+//! HiFi4 Digital Signal Processor.
+//!
+//! D1 has no CPUS management core; always-on tasks such as audio processing or sensor
+//! polling are offloaded to its HiFi4 DSP instead. Firmware is copied into DSP local SRAM
+//! while it is mapped into system address space, then the SRAM is handed to the DSP and the
+//! core released at its reset vector:
+//!
+//! ```ignore
+//! let mut dsp = Dsp::new(dsp_peripheral, Config::default(), &ccu);
+//! let sram = unsafe { core::slice::from_raw_parts_mut(DSP_SRAM as *mut u8, DSP_SRAM_SIZE) };
+//! dsp.load(&ccu, &sysctl, sram, DSP_FIRMWARE)?;
+//! unsafe { dsp.release(&ccu, &sysctl, DSP_RESET_VECTOR) };
+//! ```
+//!
+//! D1s and other chips without DSP have no such peripheral.
+
+mod register;
+pub use register::*;
+
+use crate::{
+    ccu::{self, ClockConfig, ClockGate, DspClockSource, PeriFactorN},
+    sysctl,
+};
+
+/// DSP error.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Error {
+    /// Firmware image is larger than DSP local SRAM.
+    ImageTooLarge,
+}
+
+/// DSP clock configuration.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Config {
+    /// DSP clock source.
+    pub source: DspClockSource,
+    /// DSP clock divide factor M, dividing source by `factor_m + 1`.
+    pub factor_m: u8,
+}
+
+impl Default for Config {
+    /// 400 MHz from peripheral PLL (2x frequency).
+    #[inline]
+    fn default() -> Self {
+        Self {
+            source: DspClockSource::PllPeri2x,
+            factor_m: 2,
+        }
+    }
+}
+
+/// Managed DSP structure with peripheral.
+pub struct Dsp<DSP> {
+    dsp: DSP,
+}
+
+impl<DSP: AsRef<RegisterBlock>> Dsp<DSP> {
+    /// Clock DSP with `config`, holding its core in reset.
+    #[inline]
+    pub fn new(dsp: DSP, config: Config, ccu: &ccu::RegisterBlock) -> Self {
+        unsafe {
+            ccu.dsp_bgr.modify(|val| val.assert_core_reset());
+            ccu::DSP::reconfigure(ccu, config.source, config.factor_m, PeriFactorN::N1);
+            dsp.as_ref().control.write(Control::default().stall());
+        }
+        Self { dsp }
+    }
+    /// Halt DSP, then copy `image` to the start of DSP local SRAM `sram` in system address
+    /// space.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::ImageTooLarge`] if `image` doesn't fit in `sram`.
+    #[inline]
+    pub fn load(
+        &mut self,
+        ccu: &ccu::RegisterBlock,
+        sysctl: &sysctl::RegisterBlock,
+        sram: &mut [u8],
+        image: &[u8],
+    ) -> Result<(), Error> {
+        let destination = sram.get_mut(..image.len()).ok_or(Error::ImageTooLarge)?;
+        self.halt(ccu);
+        unsafe { sysctl.dsp_boot_ram_map.modify(|val| val.map_to_system()) };
+        destination.copy_from_slice(image);
+        Ok(())
+    }
+    /// Hand DSP local SRAM to the DSP, and run DSP from `reset_vector`.
+    ///
+    /// # Safety
+    ///
+    /// `reset_vector` must point to valid DSP firmware in DSP address space. CPU must not
+    /// access DSP local SRAM through system address space until [`Dsp::load`] maps it back.
+    #[inline]
+    pub unsafe fn release(
+        &mut self,
+        ccu: &ccu::RegisterBlock,
+        sysctl: &sysctl::RegisterBlock,
+        reset_vector: u32,
+    ) {
+        self.halt(ccu);
+        let regs = self.dsp.as_ref();
+        unsafe {
+            sysctl.dsp_boot_ram_map.modify(|val| val.map_to_dsp());
+            regs.alternate_reset_vector.write(reset_vector);
+            regs.control
+                .modify(|val| val.select_alternate_vector().enable_clock());
+            ccu.dsp_bgr.modify(|val| val.deassert_core_reset());
+            regs.control.modify(|val| val.run());
+        }
+    }
+    /// Stall DSP and hold its core in reset.
+    #[inline]
+    pub fn halt(&mut self, ccu: &ccu::RegisterBlock) {
+        let regs = self.dsp.as_ref();
+        unsafe {
+            regs.control.modify(|val| val.stall());
+            ccu.dsp_bgr.modify(|val| val.assert_core_reset());
+            regs.control.modify(|val| val.disable_clock());
+        }
+    }
+    /// Check if DSP core is running.
+    #[inline]
+    pub fn is_running(&self, ccu: &ccu::RegisterBlock) -> bool {
+        !ccu.dsp_bgr.read().is_core_reset_asserted()
+            && !self.dsp.as_ref().control.read().is_stalled()
+    }
+    /// Get DSP processor ID.
+    #[inline]
+    pub fn processor_id(&self) -> u32 {
+        self.dsp.as_ref().processor_id.read()
+    }
+    /// Halt DSP, close its clock and release the peripheral.
+    #[inline]
+    pub fn free(mut self, ccu: &ccu::RegisterBlock) -> DSP {
+        self.halt(ccu);
+        unsafe {
+            ccu::DSP::free(ccu);
+            ccu.dsp_clock.modify(|val| val.mask_clock());
+        }
+        self.dsp
+    }
+}
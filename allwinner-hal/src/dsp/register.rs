@@ -0,0 +1,110 @@
+use volatile_register::{RO, RW};
+
+/// DSP configuration registers.
+#[repr(C)]
+pub struct RegisterBlock {
+    /// 0x00 - DSP Alternate Reset Vector register.
+    pub alternate_reset_vector: RW<u32>,
+    /// 0x04 - DSP Control register 0.
+    pub control: RW<Control>,
+    _reserved0: u32,
+    /// 0x0C - DSP Processor ID register.
+    pub processor_id: RO<u32>,
+    /// 0x10 - DSP Status register.
+    pub status: RO<u32>,
+    _reserved1: [u32; 12],
+    /// 0x44 - DSP Version register.
+    pub version: RO<u32>,
+}
+
+/// DSP control register 0.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct Control(u32);
+
+impl Control {
+    const DSP_CLKEN: u32 = 1 << 2;
+    const START_VEC_SEL: u32 = 1 << 1;
+    const RUN_STALL: u32 = 1 << 0;
+
+    /// Check if DSP core clock is enabled.
+    #[inline]
+    pub const fn is_clock_enabled(self) -> bool {
+        self.0 & Self::DSP_CLKEN != 0
+    }
+    /// Enable DSP core clock.
+    #[inline]
+    pub const fn enable_clock(self) -> Self {
+        Self(self.0 | Self::DSP_CLKEN)
+    }
+    /// Disable DSP core clock.
+    #[inline]
+    pub const fn disable_clock(self) -> Self {
+        Self(self.0 & !Self::DSP_CLKEN)
+    }
+    /// Check if DSP starts at alternate reset vector instead of default one.
+    #[inline]
+    pub const fn is_alternate_vector_selected(self) -> bool {
+        self.0 & Self::START_VEC_SEL != 0
+    }
+    /// Start DSP at alternate reset vector.
+    #[inline]
+    pub const fn select_alternate_vector(self) -> Self {
+        Self(self.0 | Self::START_VEC_SEL)
+    }
+    /// Start DSP at default reset vector.
+    #[inline]
+    pub const fn select_default_vector(self) -> Self {
+        Self(self.0 & !Self::START_VEC_SEL)
+    }
+    /// Check if DSP pipeline is stalled.
+    #[inline]
+    pub const fn is_stalled(self) -> bool {
+        self.0 & Self::RUN_STALL != 0
+    }
+    /// Stall DSP pipeline.
+    #[inline]
+    pub const fn stall(self) -> Self {
+        Self(self.0 | Self::RUN_STALL)
+    }
+    /// Let DSP pipeline run.
+    #[inline]
+    pub const fn run(self) -> Self {
+        Self(self.0 & !Self::RUN_STALL)
+    }
+}
+
+impl Default for Control {
+    #[inline]
+    fn default() -> Self {
+        Self(0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Control, RegisterBlock};
+    use core::mem::offset_of;
+
+    #[test]
+    fn offset_dsp() {
+        assert_eq!(offset_of!(RegisterBlock, alternate_reset_vector), 0x00);
+        assert_eq!(offset_of!(RegisterBlock, control), 0x04);
+        assert_eq!(offset_of!(RegisterBlock, processor_id), 0x0C);
+        assert_eq!(offset_of!(RegisterBlock, status), 0x10);
+        assert_eq!(offset_of!(RegisterBlock, version), 0x44);
+    }
+
+    #[test]
+    fn struct_control_functions() {
+        let mut val = Control::default();
+        assert!(!val.is_stalled());
+        val = val.stall().select_alternate_vector().enable_clock();
+        assert!(val.is_stalled());
+        assert!(val.is_alternate_vector_selected());
+        assert!(val.is_clock_enabled());
+        assert_eq!(val.0, 0x00000007);
+        val = val.run().select_default_vector().disable_clock();
+        assert_eq!(val.0, 0x00000000);
+    }
+}
@@ -0,0 +1,409 @@
+//! Ethernet Media Access Controller.
+//!
+//! EMAC drives the management bus of external Ethernet PHYs as an MDIO master. [`Emac`]
+//! implements [`Mdio`], so that [`Phy`] or vendor PHY drivers configure the PHY, and link
+//! parameters resolved by auto-negotiation are applied to MAC with [`Emac::update_link`].
+//!
+//! Link changes are either polled periodically, or watched through the in-band RGMII
+//! [`Interrupt::RgmiiLink`] or a GPIO interrupt wired to the PHY.
+//!
+//! [`EmacConfig`] sets station address, checksum offload and receive filtering. Frames to
+//! extra unicast or multicast addresses pass through perfect filters or a [`HashFilter`],
+//! e.g. to receive mDNS without promiscuous mode. Checksums are inserted per frame with
+//! [`TxDescriptor::prepare`], and results of receive checksum checks are reported in
+//! [`RxDescriptor`].
+//!
+//! [`Emac::suspend`] keeps EMAC clocked as a wakeup source while CPU sleeps, receiving
+//! frames selected by [`WakeOnLan`], e.g. magic packets, until [`Emac::resume`].
+//!
+//! EMAC of D1-like chips has no IEEE 1588 timestamping unit: descriptors carry no timestamps
+//! and there is no adjustable PTP clock. The EMAC register list in the Interfaces chapter of
+//! D1-H and T113-S3 user manuals ends at RGMII status register 0xD0, without timestamp
+//! control, sub-second increment, addend or system time registers. PTP implementations
+//! timestamp frames in software with [`Instant::now`](crate::time::Instant::now) in transmit
+//! and receive interrupts, or use a PHY with hardware timestamping.
+
+mod descriptor;
+mod filter;
+mod mdio;
+mod phy;
+mod register;
+mod wake;
+pub use descriptor::*;
+pub use filter::*;
+pub use mdio::*;
+pub use phy::*;
+pub use register::*;
+pub use wake::*;
+
+use crate::ccu::{self, ClockGate, Clocks};
+use crate::time::{Deadline, TimeoutError};
+
+/// Number of perfect filter addresses besides station address.
+pub const ADDRESS_FILTER_COUNT: usize = 7;
+
+/// Station address, checksum offload and receive filtering of [`Emac`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct EmacConfig {
+    /// Station MAC address, matched by destination address filter.
+    pub mac_address: [u8; 6],
+    /// Check IP header and payload checksums of received frames.
+    pub rx_checksum: bool,
+    /// Allow checksum insertion on transmit, at the cost of transmitting in store-and-forward
+    /// mode.
+    pub tx_checksum: bool,
+    /// Receive all frames regardless of destination address.
+    pub promiscuous: bool,
+    /// Receive all multicast frames.
+    pub all_multicast: bool,
+    /// Receive broadcast frames.
+    pub broadcast: bool,
+    /// Multicast addresses received besides perfect filters.
+    pub multicast_hash: HashFilter,
+}
+
+impl Default for EmacConfig {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            mac_address: [0; 6],
+            rx_checksum: true,
+            tx_checksum: true,
+            promiscuous: false,
+            all_multicast: false,
+            broadcast: true,
+            multicast_hash: HashFilter::new(),
+        }
+    }
+}
+
+/// Managed Ethernet Media Access Controller.
+pub struct Emac<EMAC> {
+    emac: EMAC,
+    divider: MdcDivider,
+    suspended: Option<(FrameFilter, Interrupts)>,
+}
+
+impl<EMAC: AsRef<RegisterBlock>> Emac<EMAC> {
+    /// Create an EMAC instance, with MDC clock derived from AHB clock.
+    #[inline]
+    pub fn new(emac: EMAC, clocks: &Clocks, ccu: &ccu::RegisterBlock) -> Self {
+        // note(unsafe): async read and write using ccu registers
+        unsafe { ccu::EMAC::reset(ccu) };
+        let regs = emac.as_ref();
+        unsafe {
+            regs.int_enable.write(Interrupts::default());
+            regs.int_status.write(regs.int_status.read());
+        }
+        Self {
+            emac,
+            divider: MdcDivider::from_ahb_clock(clocks.psi.0),
+            suspended: None,
+        }
+    }
+    /// Set link speed and duplex mode of MAC.
+    #[inline]
+    pub fn set_link(&mut self, link: Link) {
+        unsafe {
+            self.emac
+                .as_ref()
+                .basic_control0
+                .modify(|val| val.set_speed(link.speed).set_duplex(link.duplex))
+        };
+    }
+    /// Get link speed and duplex mode of MAC.
+    #[inline]
+    pub fn link(&self) -> Link {
+        let val = self.emac.as_ref().basic_control0.read();
+        Link {
+            speed: val.speed(),
+            duplex: val.duplex(),
+        }
+    }
+    /// Get link status reported in-band by RGMII PHY.
+    #[inline]
+    pub fn rgmii_link(&self) -> Option<Link> {
+        let val = self.emac.as_ref().rgmii_status.read();
+        val.is_link_up().then(|| Link {
+            speed: val.speed(),
+            duplex: val.duplex(),
+        })
+    }
+    /// Poll link status of `phy`, applying new link parameters to MAC when link comes up.
+    #[inline]
+    pub fn update_link(&mut self, phy: &mut Phy) -> Result<Option<LinkEvent>, TimeoutError> {
+        let event = phy.poll(self)?;
+        if let Some(LinkEvent::Up(link)) = event {
+            self.set_link(link);
+        }
+        Ok(event)
+    }
+    /// Apply station address, checksum offload and receive filtering in `config`.
+    #[inline]
+    pub fn configure(&mut self, config: &EmacConfig) {
+        self.set_mac_address(config.mac_address);
+        self.set_multicast_hash(&config.multicast_hash);
+        let regs = self.emac.as_ref();
+        unsafe {
+            regs.rx_control0.modify(|val| match config.rx_checksum {
+                true => val.enable_checksum_offload(),
+                false => val.disable_checksum_offload(),
+            });
+            regs.tx_control1.modify(|val| match config.tx_checksum {
+                true => val.enable_store_and_forward(),
+                false => val.disable_store_and_forward(),
+            });
+            regs.rx_frame_filter.modify(|val| {
+                let val = match config.all_multicast {
+                    true => val.enable_all_multicast(),
+                    false => val.disable_all_multicast(),
+                };
+                let val = match config.broadcast {
+                    true => val.enable_broadcast(),
+                    false => val.disable_broadcast(),
+                };
+                match config.promiscuous {
+                    true => val.enable_promiscuous(),
+                    false => val.disable_promiscuous(),
+                }
+            });
+        }
+    }
+    /// Receive all frames regardless of destination address, or only frames passing filters.
+    #[inline]
+    pub fn set_promiscuous(&mut self, enabled: bool) {
+        unsafe {
+            self.emac
+                .as_ref()
+                .rx_frame_filter
+                .modify(|val| match enabled {
+                    true => val.enable_promiscuous(),
+                    false => val.disable_promiscuous(),
+                })
+        };
+    }
+    /// Set station MAC address.
+    #[inline]
+    pub fn set_mac_address(&mut self, address: [u8; 6]) {
+        let (high, low) = address_registers(address);
+        let regs = &self.emac.as_ref().mac_address[0];
+        unsafe {
+            regs.high.write(high);
+            regs.low.write(low);
+        }
+    }
+    /// Get station MAC address.
+    #[inline]
+    pub fn mac_address(&self) -> [u8; 6] {
+        let regs = &self.emac.as_ref().mac_address[0];
+        let [a0, a1, a2, a3] = regs.low.read().to_le_bytes();
+        let [a4, a5, _, _] = regs.high.read().to_le_bytes();
+        [a0, a1, a2, a3, a4, a5]
+    }
+    /// Set perfect filter `index` to receive frames to `address`, or disable it with `None`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is not less than [`ADDRESS_FILTER_COUNT`].
+    #[inline]
+    pub fn set_address_filter(&mut self, index: usize, address: Option<[u8; 6]>) {
+        assert!(
+            index < ADDRESS_FILTER_COUNT,
+            "EMAC address filter out of range"
+        );
+        let regs = &self.emac.as_ref().mac_address[index + 1];
+        let (high, low) = match address {
+            Some(address) => {
+                let (high, low) = address_registers(address);
+                (high | ADDRESS_ENABLE, low)
+            }
+            None => (0, 0),
+        };
+        unsafe {
+            regs.low.write(low);
+            regs.high.write(high);
+        }
+    }
+    /// Receive multicast frames passing `filter`, in addition to perfect filters.
+    ///
+    /// An empty filter turns hash table matching off.
+    #[inline]
+    pub fn set_multicast_hash(&mut self, filter: &HashFilter) {
+        let regs = self.emac.as_ref();
+        let [hash0, hash1] = filter.registers();
+        unsafe {
+            regs.rx_hash[0].write(hash0);
+            regs.rx_hash[1].write(hash1);
+            regs.rx_frame_filter.modify(|val| match filter.is_empty() {
+                true => val.disable_multicast_hash(),
+                false => val.enable_multicast_hash(),
+            });
+        }
+    }
+    /// Keep EMAC as a wakeup source while CPU sleeps, raising interrupts on frames and
+    /// events selected by `wake` only.
+    ///
+    /// Receive filters are narrowed to station address, broadcast if magic packets wake the
+    /// system, and multicast filters if selected. Receive DMA should be left running with
+    /// free descriptors, and AHB clock kept running in standby. Does nothing if EMAC is
+    /// already suspended.
+    #[inline]
+    pub fn suspend(&mut self, wake: &WakeOnLan) {
+        if self.suspended.is_some() {
+            return;
+        }
+        let regs = self.emac.as_ref();
+        let filter = regs.rx_frame_filter.read();
+        let enable = regs.int_enable.read();
+        self.suspended = Some((filter, enable));
+        let mut wake_filter = filter.disable_promiscuous().disable_all_multicast();
+        wake_filter = match wake.magic_packet {
+            true => wake_filter.enable_broadcast(),
+            false => wake_filter.disable_broadcast(),
+        };
+        if !wake.multicast {
+            wake_filter = wake_filter.disable_multicast_hash();
+        }
+        let mut wake_enable = Interrupts::default();
+        if wake.magic_packet || wake.unicast || wake.multicast {
+            wake_enable = wake_enable.enable_interrupt(Interrupt::Receive);
+        }
+        if wake.link_change {
+            wake_enable = wake_enable.enable_interrupt(Interrupt::RgmiiLink);
+        }
+        unsafe {
+            regs.rx_frame_filter.write(wake_filter);
+            regs.int_enable.write(wake_enable);
+        }
+    }
+    /// Restore receive filters and interrupts saved on [`Emac::suspend`].
+    #[inline]
+    pub fn resume(&mut self) {
+        if let Some((filter, enable)) = self.suspended.take() {
+            let regs = self.emac.as_ref();
+            unsafe {
+                regs.rx_frame_filter.write(filter);
+                regs.int_enable.write(enable);
+            }
+        }
+    }
+    /// Check if EMAC is suspended as a wakeup source.
+    #[inline]
+    pub const fn is_suspended(&self) -> bool {
+        self.suspended.is_some()
+    }
+    /// Enable `interrupt`.
+    #[inline]
+    pub fn enable_interrupt(&mut self, interrupt: Interrupt) {
+        unsafe {
+            self.emac
+                .as_ref()
+                .int_enable
+                .modify(|val| val.enable_interrupt(interrupt))
+        };
+    }
+    /// Disable `interrupt`.
+    #[inline]
+    pub fn disable_interrupt(&mut self, interrupt: Interrupt) {
+        unsafe {
+            self.emac
+                .as_ref()
+                .int_enable
+                .modify(|val| val.disable_interrupt(interrupt))
+        };
+    }
+    /// Check if `interrupt` is pending.
+    #[inline]
+    pub fn has_interrupt(&self, interrupt: Interrupt) -> bool {
+        self.emac
+            .as_ref()
+            .int_status
+            .read()
+            .has_interrupt(interrupt)
+    }
+    /// Clear pending `interrupt`.
+    #[inline]
+    pub fn clear_interrupt(&mut self, interrupt: Interrupt) {
+        unsafe {
+            self.emac
+                .as_ref()
+                .int_status
+                .write(Interrupts::default().enable_interrupt(interrupt))
+        };
+    }
+    /// Close EMAC and release peripheral.
+    #[inline]
+    pub fn free(self, ccu: &ccu::RegisterBlock) -> EMAC {
+        unsafe { self.emac.as_ref().int_enable.write(Interrupts::default()) };
+        // note(unsafe): async read and write using ccu registers
+        unsafe { ccu::EMAC::free(ccu) };
+        self.emac
+    }
+    /// Run a management frame, returning data read from PHY.
+    #[inline]
+    fn mii_frame(&mut self, command: MiiCommand, data: u16) -> Result<u16, TimeoutError> {
+        let regs = self.emac.as_ref();
+        let deadline = Deadline::start();
+        while regs.mii_command.read().is_busy() {
+            deadline.check()?;
+            core::hint::spin_loop();
+        }
+        unsafe {
+            regs.mii_data.write(data as u32);
+            regs.mii_command
+                .write(command.set_mdc_divider(self.divider).start());
+        }
+        while regs.mii_command.read().is_busy() {
+            deadline.check()?;
+            core::hint::spin_loop();
+        }
+        Ok(regs.mii_data.read() as u16)
+    }
+}
+
+/// Enable bit in address high register of perfect filters.
+const ADDRESS_ENABLE: u32 = 1 << 31;
+
+/// Values of address high and low registers for `address`.
+#[inline]
+const fn address_registers(address: [u8; 6]) -> (u32, u32) {
+    let [a0, a1, a2, a3, a4, a5] = address;
+    (
+        u32::from_le_bytes([a4, a5, 0, 0]),
+        u32::from_le_bytes([a0, a1, a2, a3]),
+    )
+}
+
+impl<EMAC: AsRef<RegisterBlock>> Mdio for Emac<EMAC> {
+    type Error = TimeoutError;
+
+    #[inline]
+    fn read(&mut self, phy: u8, register: u8) -> Result<u16, TimeoutError> {
+        let command = MiiCommand::default()
+            .set_phy_address(phy)
+            .set_phy_register(register)
+            .set_read();
+        self.mii_frame(command, 0)
+    }
+    #[inline]
+    fn write(&mut self, phy: u8, register: u8, value: u16) -> Result<(), TimeoutError> {
+        let command = MiiCommand::default()
+            .set_phy_address(phy)
+            .set_phy_register(register)
+            .set_write();
+        self.mii_frame(command, value).map(|_| ())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::address_registers;
+
+    #[test]
+    fn function_address_registers() {
+        assert_eq!(
+            address_registers([0x02, 0x11, 0x22, 0x33, 0x44, 0x55]),
+            (0x00005544, 0x33221102)
+        );
+    }
+}
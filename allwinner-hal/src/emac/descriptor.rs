@@ -0,0 +1,295 @@
+/// Checksums inserted into a transmitted frame by EMAC.
+///
+/// Insertion requires store-and-forward transmit mode, enabled by
+/// [`EmacConfig::tx_checksum`](super::EmacConfig::tx_checksum).
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ChecksumInsertion {
+    /// Frame is transmitted as is.
+    Disabled = 0,
+    /// Insert IPv4 header checksum.
+    IpHeader = 1,
+    /// Insert IPv4 header and TCP/UDP/ICMP payload checksums, with pseudo-header checksum
+    /// already in payload checksum field.
+    IpHeaderAndPayload = 2,
+    /// Insert IPv4 header and TCP/UDP/ICMP payload checksums, computing pseudo-header
+    /// checksum in hardware.
+    Full = 3,
+}
+
+/// EMAC transmit DMA descriptor.
+///
+/// Transmit DMA reads descriptors from memory, they should stay valid until DMA hands them back.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[repr(C, align(4))]
+pub struct TxDescriptor {
+    status: u32,
+    control: u32,
+    buffer_address: u32,
+    next_descriptor: u32,
+}
+
+impl TxDescriptor {
+    const OWN: u32 = 1 << 31;
+    const HEADER_ERR: u32 = 1 << 16;
+    const PAYLOAD_ERR: u32 = 1 << 12;
+
+    const INT_CTL: u32 = 1 << 31;
+    const LAST_DESC: u32 = 1 << 30;
+    const FIRST_DESC: u32 = 1 << 29;
+    const CHECKSUM_CTL: u32 = 0x3 << 27;
+    const CHAIN: u32 = 1 << 24;
+    const BUFFER_SIZE: u32 = 0x7FF;
+
+    /// Maximum number of bytes transferred by one descriptor.
+    pub const MAX_BUFFER_SIZE: usize = 0x7FF;
+
+    /// Create an empty descriptor owned by CPU.
+    #[inline]
+    pub const fn new() -> Self {
+        Self {
+            status: 0,
+            control: 0,
+            buffer_address: 0,
+            next_descriptor: 0,
+        }
+    }
+    /// Hand descriptor to DMA, transmitting a whole frame of `len` bytes at `address`
+    /// with `checksum` inserted, and chaining to descriptor at `next`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `len` exceeds [`TxDescriptor::MAX_BUFFER_SIZE`].
+    #[inline]
+    pub fn prepare(
+        &mut self,
+        address: usize,
+        len: usize,
+        next: usize,
+        checksum: ChecksumInsertion,
+    ) {
+        assert!(
+            len <= Self::MAX_BUFFER_SIZE,
+            "frame too long for descriptor"
+        );
+        self.buffer_address = address as u32;
+        self.next_descriptor = next as u32;
+        self.control = Self::INT_CTL
+            | Self::LAST_DESC
+            | Self::FIRST_DESC
+            | ((checksum as u32) << 27)
+            | Self::CHAIN
+            | len as u32;
+        self.status = Self::OWN;
+    }
+    /// If descriptor is owned by DMA.
+    #[inline]
+    pub const fn is_owned_by_dma(&self) -> bool {
+        self.status & Self::OWN != 0
+    }
+    /// Get checksums inserted into the frame.
+    #[inline]
+    pub const fn checksum_insertion(&self) -> ChecksumInsertion {
+        match (self.control & Self::CHECKSUM_CTL) >> 27 {
+            0 => ChecksumInsertion::Disabled,
+            1 => ChecksumInsertion::IpHeader,
+            2 => ChecksumInsertion::IpHeaderAndPayload,
+            _ => ChecksumInsertion::Full,
+        }
+    }
+    /// If IP header checksum could not be inserted, e.g. frame is not IPv4.
+    #[inline]
+    pub const fn has_header_checksum_error(&self) -> bool {
+        self.status & Self::HEADER_ERR != 0
+    }
+    /// If payload checksum could not be inserted, e.g. frame is truncated.
+    #[inline]
+    pub const fn has_payload_checksum_error(&self) -> bool {
+        self.status & Self::PAYLOAD_ERR != 0
+    }
+    /// Get buffer size in bytes.
+    #[inline]
+    pub const fn buffer_size(&self) -> usize {
+        (self.control & Self::BUFFER_SIZE) as usize
+    }
+    /// Get buffer address.
+    #[inline]
+    pub const fn buffer_address(&self) -> usize {
+        self.buffer_address as usize
+    }
+    /// Get address of next descriptor.
+    #[inline]
+    pub const fn next_descriptor(&self) -> usize {
+        self.next_descriptor as usize
+    }
+}
+
+impl Default for TxDescriptor {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// EMAC receive DMA descriptor.
+///
+/// Receive DMA reads descriptors from memory, they should stay valid until DMA hands them back.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[repr(C, align(4))]
+pub struct RxDescriptor {
+    status: u32,
+    control: u32,
+    buffer_address: u32,
+    next_descriptor: u32,
+}
+
+impl RxDescriptor {
+    const OWN: u32 = 1 << 31;
+    const DST_FILTER_FAIL: u32 = 1 << 30;
+    const FRAME_LEN: u32 = 0x3FFF << 16;
+    const FIRST_DESC: u32 = 1 << 9;
+    const LAST_DESC: u32 = 1 << 8;
+    const HEADER_ERR: u32 = 1 << 7;
+    const CRC_ERR: u32 = 1 << 1;
+    const PAYLOAD_ERR: u32 = 1 << 0;
+
+    const CHAIN: u32 = 1 << 24;
+    const BUFFER_SIZE: u32 = 0x7FF;
+
+    /// Maximum number of bytes received by one descriptor.
+    pub const MAX_BUFFER_SIZE: usize = 0x7FF;
+
+    /// Create an empty descriptor owned by CPU.
+    #[inline]
+    pub const fn new() -> Self {
+        Self {
+            status: 0,
+            control: 0,
+            buffer_address: 0,
+            next_descriptor: 0,
+        }
+    }
+    /// Hand descriptor to DMA, receiving into `len` bytes at `address` and chaining to
+    /// descriptor at `next`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `len` exceeds [`RxDescriptor::MAX_BUFFER_SIZE`].
+    #[inline]
+    pub fn prepare(&mut self, address: usize, len: usize, next: usize) {
+        assert!(
+            len <= Self::MAX_BUFFER_SIZE,
+            "buffer too long for descriptor"
+        );
+        self.buffer_address = address as u32;
+        self.next_descriptor = next as u32;
+        self.control = Self::CHAIN | len as u32;
+        self.status = Self::OWN;
+    }
+    /// If descriptor is owned by DMA.
+    #[inline]
+    pub const fn is_owned_by_dma(&self) -> bool {
+        self.status & Self::OWN != 0
+    }
+    /// If this is the first descriptor of a frame.
+    #[inline]
+    pub const fn is_first(&self) -> bool {
+        self.status & Self::FIRST_DESC != 0
+    }
+    /// If this is the last descriptor of a frame.
+    #[inline]
+    pub const fn is_last(&self) -> bool {
+        self.status & Self::LAST_DESC != 0
+    }
+    /// Get length of received frame in bytes, valid on last descriptor.
+    #[inline]
+    pub const fn frame_length(&self) -> usize {
+        ((self.status & Self::FRAME_LEN) >> 16) as usize
+    }
+    /// If frame failed destination address filters, only reported in promiscuous mode.
+    #[inline]
+    pub const fn is_filter_failed(&self) -> bool {
+        self.status & Self::DST_FILTER_FAIL != 0
+    }
+    /// If frame check sequence of frame mismatched.
+    #[inline]
+    pub const fn has_crc_error(&self) -> bool {
+        self.status & Self::CRC_ERR != 0
+    }
+    /// If IPv4 header checksum mismatched, with receive checksum offload enabled.
+    #[inline]
+    pub const fn has_header_checksum_error(&self) -> bool {
+        self.status & Self::HEADER_ERR != 0
+    }
+    /// If TCP/UDP/ICMP payload checksum mismatched, with receive checksum offload enabled.
+    #[inline]
+    pub const fn has_payload_checksum_error(&self) -> bool {
+        self.status & Self::PAYLOAD_ERR != 0
+    }
+    /// Get buffer size in bytes.
+    #[inline]
+    pub const fn buffer_size(&self) -> usize {
+        (self.control & Self::BUFFER_SIZE) as usize
+    }
+    /// Get buffer address.
+    #[inline]
+    pub const fn buffer_address(&self) -> usize {
+        self.buffer_address as usize
+    }
+    /// Get address of next descriptor.
+    #[inline]
+    pub const fn next_descriptor(&self) -> usize {
+        self.next_descriptor as usize
+    }
+}
+
+impl Default for RxDescriptor {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ChecksumInsertion, RxDescriptor, TxDescriptor};
+    use core::mem::size_of;
+
+    #[test]
+    fn struct_tx_descriptor_functions() {
+        assert_eq!(size_of::<TxDescriptor>(), 16);
+        let mut desc = TxDescriptor::new();
+        assert!(!desc.is_owned_by_dma());
+        desc.prepare(0x4000_0000, 1514, 0x4000_1000, ChecksumInsertion::Full);
+        assert!(desc.is_owned_by_dma());
+        assert_eq!(desc.control, 0xF90005EA);
+        assert_eq!(desc.checksum_insertion(), ChecksumInsertion::Full);
+        assert_eq!(desc.buffer_size(), 1514);
+        assert_eq!(desc.buffer_address(), 0x4000_0000);
+        assert_eq!(desc.next_descriptor(), 0x4000_1000);
+        desc.status = 0x00011000;
+        assert!(desc.has_header_checksum_error());
+        assert!(desc.has_payload_checksum_error());
+        desc.prepare(0x4000_0000, 60, 0x4000_1000, ChecksumInsertion::IpHeader);
+        assert_eq!(desc.checksum_insertion(), ChecksumInsertion::IpHeader);
+        assert!(!desc.has_header_checksum_error());
+    }
+
+    #[test]
+    fn struct_rx_descriptor_functions() {
+        assert_eq!(size_of::<RxDescriptor>(), 16);
+        let mut desc = RxDescriptor::new();
+        desc.prepare(0x4000_2000, 1536, 0x4000_3000);
+        assert!(desc.is_owned_by_dma());
+        assert_eq!(desc.control, 0x01000600);
+        assert_eq!(desc.buffer_size(), 1536);
+        desc.status = 0x05EA0381;
+        assert!(!desc.is_owned_by_dma());
+        assert!(desc.is_first());
+        assert!(desc.is_last());
+        assert_eq!(desc.frame_length(), 1514);
+        assert!(desc.has_header_checksum_error());
+        assert!(desc.has_payload_checksum_error());
+        assert!(!desc.has_crc_error());
+        assert!(!desc.is_filter_failed());
+    }
+}
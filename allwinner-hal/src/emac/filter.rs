@@ -0,0 +1,83 @@
+/// 64-bin hash table filter of destination MAC addresses.
+///
+/// A frame passes the filter if the bin selected by its destination address is set. Bins
+/// are shared by many addresses, so the filter only narrows down frames to be checked by
+/// software.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct HashFilter {
+    bins: u64,
+}
+
+impl HashFilter {
+    /// Create an empty hash filter, rejecting all frames.
+    #[inline]
+    pub const fn new() -> Self {
+        Self { bins: 0 }
+    }
+    /// Bin index of `address`, the upper 6 bits of bit-reversed Ethernet CRC-32.
+    #[inline]
+    pub const fn bin(address: [u8; 6]) -> u8 {
+        let mut crc = 0xFFFF_FFFFu32;
+        let mut i = 0;
+        while i < address.len() {
+            crc ^= address[i] as u32;
+            let mut bit = 0;
+            while bit < 8 {
+                crc = if crc & 1 != 0 {
+                    (crc >> 1) ^ 0xEDB8_8320
+                } else {
+                    crc >> 1
+                };
+                bit += 1;
+            }
+            i += 1;
+        }
+        ((!crc).reverse_bits() >> 26) as u8
+    }
+    /// Let frames to `address` pass the filter.
+    #[inline]
+    pub const fn insert(self, address: [u8; 6]) -> Self {
+        Self {
+            bins: self.bins | (1 << Self::bin(address)),
+        }
+    }
+    /// Check if frames to `address` pass the filter.
+    #[inline]
+    pub const fn contains(self, address: [u8; 6]) -> bool {
+        self.bins & (1 << Self::bin(address)) != 0
+    }
+    /// Check if no frame passes the filter.
+    #[inline]
+    pub const fn is_empty(self) -> bool {
+        self.bins == 0
+    }
+    /// Values of hash table registers 0 and 1, holding bins 32 to 63 and 0 to 31.
+    #[inline]
+    pub const fn registers(self) -> [u32; 2] {
+        [(self.bins >> 32) as u32, self.bins as u32]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::HashFilter;
+
+    #[test]
+    fn struct_hash_filter_functions() {
+        // mDNS IPv4 and IPv6 multicast addresses.
+        let mdns4 = [0x01, 0x00, 0x5E, 0x00, 0x00, 0xFB];
+        let mdns6 = [0x33, 0x33, 0x00, 0x00, 0x00, 0xFB];
+        assert_eq!(HashFilter::bin([0xFF; 6]), 0x00);
+        assert_eq!(HashFilter::bin(mdns4), 0x30);
+        assert_eq!(HashFilter::bin(mdns6), 0x11);
+        let filter = HashFilter::new();
+        assert!(filter.is_empty());
+        assert!(!filter.contains(mdns4));
+        let filter = filter.insert(mdns4).insert(mdns6);
+        assert!(!filter.is_empty());
+        assert!(filter.contains(mdns4));
+        assert!(filter.contains(mdns6));
+        assert!(!filter.contains([0xFF; 6]));
+        assert_eq!(filter.registers(), [0x00010000, 0x00020000]);
+    }
+}
@@ -0,0 +1,38 @@
+/// MDIO management bus master, accessing clause 22 registers of Ethernet PHYs.
+///
+/// Implemented by [`Emac`](super::Emac); PHY drivers written against this trait also run on
+/// other MDIO masters, e.g. a bit-banged GPIO bus.
+pub trait Mdio {
+    /// Error type of management frames.
+    type Error: core::fmt::Debug;
+
+    /// Read register `register` of PHY at address `phy`.
+    fn read(&mut self, phy: u8, register: u8) -> Result<u16, Self::Error>;
+    /// Write `value` to register `register` of PHY at address `phy`.
+    fn write(&mut self, phy: u8, register: u8, value: u16) -> Result<(), Self::Error>;
+
+    /// Read, modify and write back register `register` of PHY at address `phy`.
+    #[inline]
+    fn modify(
+        &mut self,
+        phy: u8,
+        register: u8,
+        f: impl FnOnce(u16) -> u16,
+    ) -> Result<(), Self::Error> {
+        let value = self.read(phy, register)?;
+        self.write(phy, register, f(value))
+    }
+}
+
+impl<T: Mdio + ?Sized> Mdio for &mut T {
+    type Error = T::Error;
+
+    #[inline]
+    fn read(&mut self, phy: u8, register: u8) -> Result<u16, Self::Error> {
+        (**self).read(phy, register)
+    }
+    #[inline]
+    fn write(&mut self, phy: u8, register: u8, value: u16) -> Result<(), Self::Error> {
+        (**self).write(phy, register, value)
+    }
+}
@@ -0,0 +1,431 @@
+use super::{Duplex, Mdio, Speed};
+use crate::time::Deadline;
+
+// Clause 22 PHY registers.
+const BMCR: u8 = 0;
+const BMSR: u8 = 1;
+const PHYID1: u8 = 2;
+const PHYID2: u8 = 3;
+const ANAR: u8 = 4;
+const ANLPAR: u8 = 5;
+const GBCR: u8 = 9;
+const GBSR: u8 = 10;
+
+// Basic mode control register.
+const BMCR_RESET: u16 = 1 << 15;
+const BMCR_SPEED100: u16 = 1 << 13;
+const BMCR_AN_ENABLE: u16 = 1 << 12;
+const BMCR_AN_RESTART: u16 = 1 << 9;
+const BMCR_FULL_DUPLEX: u16 = 1 << 8;
+const BMCR_SPEED1000: u16 = 1 << 6;
+
+// Basic mode status register.
+const BMSR_EXTENDED_STATUS: u16 = 1 << 8;
+const BMSR_AN_COMPLETE: u16 = 1 << 5;
+const BMSR_LINK_STATUS: u16 = 1 << 2;
+
+// Auto-negotiation advertisement and link partner ability registers.
+const AN_SELECTOR_802_3: u16 = 0x0001;
+const AN_10HALF: u16 = 1 << 5;
+const AN_10FULL: u16 = 1 << 6;
+const AN_100HALF: u16 = 1 << 7;
+const AN_100FULL: u16 = 1 << 8;
+const AN_PAUSE: u16 = 1 << 10;
+const AN_ASYM_PAUSE: u16 = 1 << 11;
+
+// 1000BASE-T control and status registers.
+const GBCR_1000HALF: u16 = 1 << 8;
+const GBCR_1000FULL: u16 = 1 << 9;
+const GBSR_1000HALF: u16 = 1 << 10;
+const GBSR_1000FULL: u16 = 1 << 11;
+
+/// Link abilities advertised in auto-negotiation.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Advertise {
+    /// 10BASE-T half duplex.
+    pub mbps10_half: bool,
+    /// 10BASE-T full duplex.
+    pub mbps10_full: bool,
+    /// 100BASE-TX half duplex.
+    pub mbps100_half: bool,
+    /// 100BASE-TX full duplex.
+    pub mbps100_full: bool,
+    /// 1000BASE-T half duplex, ignored if PHY has no gigabit support.
+    pub mbps1000_half: bool,
+    /// 1000BASE-T full duplex, ignored if PHY has no gigabit support.
+    pub mbps1000_full: bool,
+    /// Symmetric and asymmetric pause frames.
+    pub pause: bool,
+}
+
+impl Default for Advertise {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            mbps10_half: true,
+            mbps10_full: true,
+            mbps100_half: true,
+            mbps100_full: true,
+            mbps1000_half: false,
+            mbps1000_full: true,
+            pause: true,
+        }
+    }
+}
+
+/// Link parameters resolved by PHY.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Link {
+    /// Link speed.
+    pub speed: Speed,
+    /// Duplex mode.
+    pub duplex: Duplex,
+}
+
+/// Link status change reported by [`Phy::poll`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum LinkEvent {
+    /// Link came up, or changed its parameters.
+    Up(Link),
+    /// Link went down.
+    Down,
+}
+
+/// Error of PHY management.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum PhyError<E> {
+    /// Management frame failed.
+    Mdio(E),
+    /// No PHY answered on the bus.
+    NotFound,
+    /// PHY didn't finish reset in time.
+    Timeout,
+}
+
+impl<E> From<E> for PhyError<E> {
+    #[inline]
+    fn from(error: E) -> Self {
+        PhyError::Mdio(error)
+    }
+}
+
+/// Clause 22 Ethernet PHY, handling auto-negotiation and link status.
+///
+/// Vendor specific registers, e.g. RGMII delays or interrupt masks, are reached through
+/// [`Mdio`] at [`Phy::address`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Phy {
+    address: u8,
+    link: Option<Link>,
+}
+
+impl Phy {
+    /// PHY at `address` on the management bus, with link assumed down.
+    #[inline]
+    pub const fn new(address: u8) -> Self {
+        Self {
+            address,
+            link: None,
+        }
+    }
+    /// Find the first PHY answering on the management bus.
+    #[inline]
+    pub fn probe<M: Mdio>(mdio: &mut M) -> Result<Self, PhyError<M::Error>> {
+        for address in 0..32 {
+            let phy = Self::new(address);
+            if !matches!(phy.id(mdio)?, 0 | 0xFFFF_FFFF) {
+                return Ok(phy);
+            }
+        }
+        Err(PhyError::NotFound)
+    }
+    /// Get management bus address of PHY.
+    #[inline]
+    pub const fn address(&self) -> u8 {
+        self.address
+    }
+    /// Read 32-bit PHY identifier, OUI and model in upper bits and revision in lowest 4 bits.
+    #[inline]
+    pub fn id<M: Mdio>(&self, mdio: &mut M) -> Result<u32, M::Error> {
+        let high = mdio.read(self.address, PHYID1)?;
+        let low = mdio.read(self.address, PHYID2)?;
+        Ok(((high as u32) << 16) | low as u32)
+    }
+    /// Reset PHY to its default configuration, waiting until it finishes.
+    #[inline]
+    pub fn reset<M: Mdio>(&mut self, mdio: &mut M) -> Result<(), PhyError<M::Error>> {
+        mdio.write(self.address, BMCR, BMCR_RESET)?;
+        self.link = None;
+        let deadline = Deadline::start();
+        while mdio.read(self.address, BMCR)? & BMCR_RESET != 0 {
+            deadline.check().map_err(|_| PhyError::Timeout)?;
+        }
+        Ok(())
+    }
+    /// Advertise `advertise` abilities and restart auto-negotiation.
+    ///
+    /// Link comes up later; watch it with [`Phy::poll`].
+    #[inline]
+    pub fn autonegotiate<M: Mdio>(
+        &mut self,
+        mdio: &mut M,
+        advertise: &Advertise,
+    ) -> Result<(), M::Error> {
+        let mut anar = AN_SELECTOR_802_3;
+        for (enabled, bit) in [
+            (advertise.mbps10_half, AN_10HALF),
+            (advertise.mbps10_full, AN_10FULL),
+            (advertise.mbps100_half, AN_100HALF),
+            (advertise.mbps100_full, AN_100FULL),
+            (advertise.pause, AN_PAUSE | AN_ASYM_PAUSE),
+        ] {
+            if enabled {
+                anar |= bit;
+            }
+        }
+        mdio.write(self.address, ANAR, anar)?;
+        if mdio.read(self.address, BMSR)? & BMSR_EXTENDED_STATUS != 0 {
+            mdio.modify(self.address, GBCR, |val| {
+                let mut val = val & !(GBCR_1000HALF | GBCR_1000FULL);
+                if advertise.mbps1000_half {
+                    val |= GBCR_1000HALF;
+                }
+                if advertise.mbps1000_full {
+                    val |= GBCR_1000FULL;
+                }
+                val
+            })?;
+        }
+        mdio.modify(self.address, BMCR, |val| {
+            val | BMCR_AN_ENABLE | BMCR_AN_RESTART
+        })?;
+        self.link = None;
+        Ok(())
+    }
+    /// Disable auto-negotiation and force link parameters to `link`.
+    #[inline]
+    pub fn force<M: Mdio>(&mut self, mdio: &mut M, link: Link) -> Result<(), M::Error> {
+        mdio.modify(self.address, BMCR, |val| {
+            let val = val
+                & !(BMCR_AN_ENABLE
+                    | BMCR_SPEED100
+                    | BMCR_SPEED1000
+                    | BMCR_FULL_DUPLEX
+                    | BMCR_RESET);
+            let val = match link.speed {
+                Speed::Mbps10 => val,
+                Speed::Mbps100 => val | BMCR_SPEED100,
+                Speed::Mbps1000 => val | BMCR_SPEED1000,
+            };
+            match link.duplex {
+                Duplex::Full => val | BMCR_FULL_DUPLEX,
+                Duplex::Half => val,
+            }
+        })?;
+        self.link = None;
+        Ok(())
+    }
+    /// Read current link from PHY, or `None` if link is down or still negotiating.
+    #[inline]
+    pub fn read_link<M: Mdio>(&self, mdio: &mut M) -> Result<Option<Link>, M::Error> {
+        // Link status is latched low until read, thus the second read reports current state.
+        mdio.read(self.address, BMSR)?;
+        let bmsr = mdio.read(self.address, BMSR)?;
+        if bmsr & BMSR_LINK_STATUS == 0 {
+            return Ok(None);
+        }
+        let bmcr = mdio.read(self.address, BMCR)?;
+        if bmcr & BMCR_AN_ENABLE == 0 {
+            return Ok(Some(forced_link(bmcr)));
+        }
+        if bmsr & BMSR_AN_COMPLETE == 0 {
+            return Ok(None);
+        }
+        let (gbcr, gbsr) = if bmsr & BMSR_EXTENDED_STATUS != 0 {
+            (
+                mdio.read(self.address, GBCR)?,
+                mdio.read(self.address, GBSR)?,
+            )
+        } else {
+            (0, 0)
+        };
+        let anar = mdio.read(self.address, ANAR)?;
+        let anlpar = mdio.read(self.address, ANLPAR)?;
+        Ok(resolve(anar, anlpar, gbcr, gbsr))
+    }
+    /// Get link reported by the last poll.
+    #[inline]
+    pub const fn link(&self) -> Option<Link> {
+        self.link
+    }
+    /// Poll link status, reporting a change since the last poll.
+    ///
+    /// Call it periodically, or from PHY interrupt handler, e.g. on
+    /// [`Interrupt::RgmiiLink`](super::Interrupt::RgmiiLink) or a GPIO pin wired to interrupt
+    /// output of PHY after clearing its vendor specific status.
+    #[inline]
+    pub fn poll<M: Mdio>(&mut self, mdio: &mut M) -> Result<Option<LinkEvent>, M::Error> {
+        let link = self.read_link(mdio)?;
+        if link == self.link {
+            return Ok(None);
+        }
+        self.link = link;
+        Ok(Some(match link {
+            Some(link) => LinkEvent::Up(link),
+            None => LinkEvent::Down,
+        }))
+    }
+}
+
+/// Link parameters forced in basic mode control register `bmcr`.
+#[inline]
+fn forced_link(bmcr: u16) -> Link {
+    let speed = if bmcr & BMCR_SPEED1000 != 0 {
+        Speed::Mbps1000
+    } else if bmcr & BMCR_SPEED100 != 0 {
+        Speed::Mbps100
+    } else {
+        Speed::Mbps10
+    };
+    let duplex = if bmcr & BMCR_FULL_DUPLEX != 0 {
+        Duplex::Full
+    } else {
+        Duplex::Half
+    };
+    Link { speed, duplex }
+}
+
+/// Resolve highest common link from local and partner abilities.
+#[inline]
+fn resolve(anar: u16, anlpar: u16, gbcr: u16, gbsr: u16) -> Option<Link> {
+    let common = anar & anlpar;
+    let (speed, duplex) = if gbcr & GBCR_1000FULL != 0 && gbsr & GBSR_1000FULL != 0 {
+        (Speed::Mbps1000, Duplex::Full)
+    } else if gbcr & GBCR_1000HALF != 0 && gbsr & GBSR_1000HALF != 0 {
+        (Speed::Mbps1000, Duplex::Half)
+    } else if common & AN_100FULL != 0 {
+        (Speed::Mbps100, Duplex::Full)
+    } else if common & AN_100HALF != 0 {
+        (Speed::Mbps100, Duplex::Half)
+    } else if common & AN_10FULL != 0 {
+        (Speed::Mbps10, Duplex::Full)
+    } else if common & AN_10HALF != 0 {
+        (Speed::Mbps10, Duplex::Half)
+    } else {
+        return None;
+    };
+    Some(Link { speed, duplex })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        Advertise, BMCR, BMSR, Duplex, GBSR, Link, LinkEvent, Mdio, Phy, PhyError, Speed, resolve,
+    };
+
+    struct FakePhy {
+        address: u8,
+        regs: [u16; 32],
+    }
+
+    impl Mdio for FakePhy {
+        type Error = ();
+
+        fn read(&mut self, phy: u8, register: u8) -> Result<u16, ()> {
+            if phy != self.address {
+                return Ok(0xFFFF);
+            }
+            Ok(self.regs[register as usize])
+        }
+        fn write(&mut self, phy: u8, register: u8, value: u16) -> Result<(), ()> {
+            if phy == self.address {
+                // Reset and restart bits clear by themselves.
+                let value = if register == BMCR {
+                    value & !0x8200
+                } else {
+                    value
+                };
+                self.regs[register as usize] = value;
+            }
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn function_resolve() {
+        // Local advertises everything, partner 100BASE-TX full and 1000BASE-T full.
+        assert_eq!(
+            resolve(0x0DE1, 0x0101, 0x0300, 0x0800),
+            Some(Link {
+                speed: Speed::Mbps1000,
+                duplex: Duplex::Full
+            })
+        );
+        assert_eq!(
+            resolve(0x0DE1, 0x0181, 0x0000, 0x0C00),
+            Some(Link {
+                speed: Speed::Mbps100,
+                duplex: Duplex::Full
+            })
+        );
+        assert_eq!(
+            resolve(0x0061, 0x0021, 0, 0),
+            Some(Link {
+                speed: Speed::Mbps10,
+                duplex: Duplex::Half
+            })
+        );
+        assert_eq!(resolve(0x0101, 0x0081, 0, 0), None);
+    }
+
+    #[test]
+    fn struct_phy_functions() {
+        let mut mdio = FakePhy {
+            address: 1,
+            regs: [0; 32],
+        };
+        mdio.regs[2] = 0x001C;
+        mdio.regs[3] = 0xC916;
+        mdio.regs[BMSR as usize] = 0x7969;
+        let mut phy = Phy::probe(&mut mdio).unwrap();
+        assert_eq!(phy.address(), 1);
+        assert_eq!(phy.id(&mut mdio), Ok(0x001CC916));
+        assert_eq!(phy.reset(&mut mdio), Ok(()));
+
+        phy.autonegotiate(&mut mdio, &Advertise::default()).unwrap();
+        assert_eq!(mdio.regs[4], 0x0DE1);
+        assert_eq!(mdio.regs[9], 0x0200);
+        assert_eq!(mdio.regs[BMCR as usize], 0x1000);
+
+        // Link down: nothing changed since last poll.
+        mdio.regs[BMSR as usize] = 0x7949;
+        assert_eq!(phy.poll(&mut mdio), Ok(None));
+        // Link up with auto-negotiation complete at 1000BASE-T full duplex.
+        mdio.regs[BMSR as usize] = 0x796D;
+        mdio.regs[5] = 0x45E1;
+        mdio.regs[GBSR as usize] = 0x3C00;
+        let link = Link {
+            speed: Speed::Mbps1000,
+            duplex: Duplex::Full,
+        };
+        assert_eq!(phy.poll(&mut mdio), Ok(Some(LinkEvent::Up(link))));
+        assert_eq!(phy.poll(&mut mdio), Ok(None));
+        assert_eq!(phy.link(), Some(link));
+
+        let link = Link {
+            speed: Speed::Mbps100,
+            duplex: Duplex::Half,
+        };
+        phy.force(&mut mdio, link).unwrap();
+        assert_eq!(mdio.regs[BMCR as usize], 0x2000);
+        assert_eq!(phy.poll(&mut mdio), Ok(Some(LinkEvent::Up(link))));
+        mdio.regs[BMSR as usize] = 0x7949;
+        assert_eq!(phy.poll(&mut mdio), Ok(Some(LinkEvent::Down)));
+
+        let mut empty = FakePhy {
+            address: 32,
+            regs: [0; 32],
+        };
+        assert_eq!(Phy::probe(&mut empty), Err(PhyError::NotFound));
+    }
+}
@@ -0,0 +1,739 @@
+use volatile_register::{RO, RW};
+
+/// Ethernet Media Access Controller registers.
+#[repr(C)]
+pub struct RegisterBlock {
+    /// 0x00 - EMAC Basic Control Register 0.
+    pub basic_control0: RW<BasicControl0>,
+    /// 0x04 - EMAC Basic Control Register 1.
+    pub basic_control1: RW<u32>,
+    /// 0x08 - EMAC Interrupt Status Register.
+    pub int_status: RW<Interrupts>,
+    /// 0x0C - EMAC Interrupt Enable Register.
+    pub int_enable: RW<Interrupts>,
+    /// 0x10 - EMAC Transmit Control Register 0.
+    pub tx_control0: RW<u32>,
+    /// 0x14 - EMAC Transmit Control Register 1.
+    pub tx_control1: RW<TxControl1>,
+    _reserved0: u32,
+    /// 0x1C - EMAC Transmit Flow Control Register.
+    pub tx_flow_control: RW<u32>,
+    /// 0x20 - EMAC Transmit Descriptor List Address Register.
+    pub tx_desc_list: RW<u32>,
+    /// 0x24 - EMAC Receive Control Register 0.
+    pub rx_control0: RW<RxControl0>,
+    /// 0x28 - EMAC Receive Control Register 1.
+    pub rx_control1: RW<u32>,
+    _reserved1: [u32; 2],
+    /// 0x34 - EMAC Receive Descriptor List Address Register.
+    pub rx_desc_list: RW<u32>,
+    /// 0x38 - EMAC Receive Frame Filter Register.
+    pub rx_frame_filter: RW<FrameFilter>,
+    _reserved2: u32,
+    /// 0x40..=0x44 - EMAC Hash Table Registers 0 and 1.
+    pub rx_hash: [RW<u32>; 2],
+    /// 0x48 - EMAC Management Interface Command Register.
+    pub mii_command: RW<MiiCommand>,
+    /// 0x4C - EMAC Management Interface Data Register.
+    pub mii_data: RW<u32>,
+    /// 0x50..=0x8C - EMAC MAC Address High and Low Registers 0 to 7.
+    pub mac_address: [MacAddress; 8],
+    _reserved3: [u32; 8],
+    /// 0xB0 - EMAC Transmit DMA Status Register.
+    pub tx_dma_status: RO<u32>,
+    /// 0xB4 - EMAC Transmit DMA Current Descriptor Register.
+    pub tx_current_desc: RO<u32>,
+    /// 0xB8 - EMAC Transmit DMA Current Buffer Address Register.
+    pub tx_current_buffer: RO<u32>,
+    _reserved4: u32,
+    /// 0xC0 - EMAC Receive DMA Status Register.
+    pub rx_dma_status: RO<u32>,
+    /// 0xC4 - EMAC Receive DMA Current Descriptor Register.
+    pub rx_current_desc: RO<u32>,
+    /// 0xC8 - EMAC Receive DMA Current Buffer Address Register.
+    pub rx_current_buffer: RO<u32>,
+    _reserved5: u32,
+    /// 0xD0 - EMAC RGMII Status Register.
+    pub rgmii_status: RO<RgmiiStatus>,
+}
+
+/// MAC address high and low register pair.
+#[repr(C)]
+pub struct MacAddress {
+    /// Address bytes 4 and 5, and enable bit of filter addresses.
+    pub high: RW<u32>,
+    /// Address bytes 0 to 3.
+    pub low: RW<u32>,
+}
+
+/// Link speed of MAC and PHY.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Speed {
+    /// 10 Mbit/s.
+    Mbps10,
+    /// 100 Mbit/s.
+    Mbps100,
+    /// 1000 Mbit/s, over RGMII only.
+    Mbps1000,
+}
+
+/// Duplex mode of MAC and PHY.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Duplex {
+    /// Half duplex, sharing the medium with collision detection.
+    Half,
+    /// Full duplex.
+    Full,
+}
+
+/// Basic control register 0.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct BasicControl0(u32);
+
+impl BasicControl0 {
+    const SPEED: u32 = 0x3 << 2;
+    const LOOPBACK: u32 = 1 << 1;
+    const DUPLEX: u32 = 1 << 0;
+
+    /// Get MAC link speed.
+    #[inline]
+    pub const fn speed(self) -> Speed {
+        match (self.0 & Self::SPEED) >> 2 {
+            0 => Speed::Mbps1000,
+            2 => Speed::Mbps10,
+            _ => Speed::Mbps100,
+        }
+    }
+    /// Set MAC link speed.
+    #[inline]
+    pub const fn set_speed(self, speed: Speed) -> Self {
+        let val = match speed {
+            Speed::Mbps1000 => 0,
+            Speed::Mbps10 => 2,
+            Speed::Mbps100 => 3,
+        };
+        Self((self.0 & !Self::SPEED) | (val << 2))
+    }
+    /// Check if MAC loopback is enabled.
+    #[inline]
+    pub const fn is_loopback_enabled(self) -> bool {
+        self.0 & Self::LOOPBACK != 0
+    }
+    /// Loop transmitted frames back to receiver inside MAC.
+    #[inline]
+    pub const fn enable_loopback(self) -> Self {
+        Self(self.0 | Self::LOOPBACK)
+    }
+    /// Disable MAC loopback.
+    #[inline]
+    pub const fn disable_loopback(self) -> Self {
+        Self(self.0 & !Self::LOOPBACK)
+    }
+    /// Get MAC duplex mode.
+    #[inline]
+    pub const fn duplex(self) -> Duplex {
+        if self.0 & Self::DUPLEX != 0 {
+            Duplex::Full
+        } else {
+            Duplex::Half
+        }
+    }
+    /// Set MAC duplex mode.
+    #[inline]
+    pub const fn set_duplex(self, duplex: Duplex) -> Self {
+        match duplex {
+            Duplex::Full => Self(self.0 | Self::DUPLEX),
+            Duplex::Half => Self(self.0 & !Self::DUPLEX),
+        }
+    }
+}
+
+impl Default for BasicControl0 {
+    #[inline]
+    fn default() -> Self {
+        Self(0)
+    }
+}
+
+/// EMAC interrupt.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Interrupt {
+    /// Frame transmitted.
+    Transmit = 0,
+    /// Transmit DMA stopped.
+    TransmitDmaStopped = 1,
+    /// Next transmit descriptor is owned by CPU.
+    TransmitBufferUnavailable = 2,
+    /// Transmit jabber timeout.
+    TransmitTimeout = 3,
+    /// Transmit FIFO underflow.
+    TransmitUnderflow = 4,
+    /// Frame fully moved into transmit FIFO.
+    TransmitEarly = 5,
+    /// Frame received.
+    Receive = 8,
+    /// Next receive descriptor is owned by CPU.
+    ReceiveBufferUnavailable = 9,
+    /// Receive DMA stopped.
+    ReceiveDmaStopped = 10,
+    /// Received frame longer than 2048 bytes.
+    ReceiveTimeout = 11,
+    /// Receive FIFO overflow.
+    ReceiveOverflow = 12,
+    /// First buffer of a frame filled by receive DMA.
+    ReceiveEarly = 13,
+    /// Link status reported in-band by RGMII PHY changed.
+    RgmiiLink = 16,
+}
+
+/// Interrupt status and enable register.
+///
+/// Status bits are cleared by writing 1.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct Interrupts(u32);
+
+impl Interrupts {
+    /// Check if `interrupt` is pending or enabled.
+    #[inline]
+    pub const fn has_interrupt(self, interrupt: Interrupt) -> bool {
+        self.0 & (1 << interrupt as u32) != 0
+    }
+    /// Enable `interrupt`, or select it to be cleared.
+    #[inline]
+    pub const fn enable_interrupt(self, interrupt: Interrupt) -> Self {
+        Self(self.0 | (1 << interrupt as u32))
+    }
+    /// Disable `interrupt`.
+    #[inline]
+    pub const fn disable_interrupt(self, interrupt: Interrupt) -> Self {
+        Self(self.0 & !(1 << interrupt as u32))
+    }
+}
+
+impl Default for Interrupts {
+    #[inline]
+    fn default() -> Self {
+        Self(0)
+    }
+}
+
+/// Transmit control register 1.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct TxControl1(u32);
+
+impl TxControl1 {
+    const TX_MD: u32 = 1 << 1;
+
+    /// Check if transmission starts only after a full frame is in transmit FIFO.
+    #[inline]
+    pub const fn is_store_and_forward_enabled(self) -> bool {
+        self.0 & Self::TX_MD != 0
+    }
+    /// Start transmission after a full frame is in transmit FIFO.
+    ///
+    /// Required by transmit checksum insertion.
+    #[inline]
+    pub const fn enable_store_and_forward(self) -> Self {
+        Self(self.0 | Self::TX_MD)
+    }
+    /// Start transmission once transmit FIFO reaches its threshold.
+    #[inline]
+    pub const fn disable_store_and_forward(self) -> Self {
+        Self(self.0 & !Self::TX_MD)
+    }
+}
+
+impl Default for TxControl1 {
+    #[inline]
+    fn default() -> Self {
+        Self(0)
+    }
+}
+
+/// Receive control register 0.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct RxControl0(u32);
+
+impl RxControl0 {
+    const RX_EN: u32 = 1 << 31;
+    const STRIP_FCS: u32 = 1 << 28;
+    const CHECK_CRC: u32 = 1 << 27;
+
+    /// Check if receiver is enabled.
+    #[inline]
+    pub const fn is_receiver_enabled(self) -> bool {
+        self.0 & Self::RX_EN != 0
+    }
+    /// Enable receiver.
+    #[inline]
+    pub const fn enable_receiver(self) -> Self {
+        Self(self.0 | Self::RX_EN)
+    }
+    /// Disable receiver.
+    #[inline]
+    pub const fn disable_receiver(self) -> Self {
+        Self(self.0 & !Self::RX_EN)
+    }
+    /// Check if frame check sequence is stripped from received frames.
+    #[inline]
+    pub const fn is_strip_fcs_enabled(self) -> bool {
+        self.0 & Self::STRIP_FCS != 0
+    }
+    /// Strip frame check sequence from received frames.
+    #[inline]
+    pub const fn enable_strip_fcs(self) -> Self {
+        Self(self.0 | Self::STRIP_FCS)
+    }
+    /// Keep frame check sequence in received frames.
+    #[inline]
+    pub const fn disable_strip_fcs(self) -> Self {
+        Self(self.0 & !Self::STRIP_FCS)
+    }
+    /// Check if IP header and TCP/UDP/ICMP payload checksums of received frames are checked.
+    #[inline]
+    pub const fn is_checksum_offload_enabled(self) -> bool {
+        self.0 & Self::CHECK_CRC != 0
+    }
+    /// Check IP header and payload checksums of received frames, reporting results in
+    /// receive descriptors.
+    #[inline]
+    pub const fn enable_checksum_offload(self) -> Self {
+        Self(self.0 | Self::CHECK_CRC)
+    }
+    /// Leave checksums of received frames unchecked.
+    #[inline]
+    pub const fn disable_checksum_offload(self) -> Self {
+        Self(self.0 & !Self::CHECK_CRC)
+    }
+}
+
+impl Default for RxControl0 {
+    #[inline]
+    fn default() -> Self {
+        Self(0)
+    }
+}
+
+/// Receive frame filter register.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct FrameFilter(u32);
+
+impl FrameFilter {
+    const DIS_ADDR_FILTER: u32 = 1 << 31;
+    const DIS_BROADCAST: u32 = 1 << 17;
+    const RX_ALL_MULTICAST: u32 = 1 << 16;
+    const HASH_MULTICAST: u32 = 1 << 9;
+    const HASH_UNICAST: u32 = 1 << 8;
+    const RX_ALL: u32 = 1 << 0;
+
+    /// Check if all frames are received regardless of destination address.
+    #[inline]
+    pub const fn is_promiscuous(self) -> bool {
+        self.0 & Self::RX_ALL != 0
+    }
+    /// Receive all frames regardless of destination address.
+    #[inline]
+    pub const fn enable_promiscuous(self) -> Self {
+        Self(self.0 | Self::RX_ALL)
+    }
+    /// Receive only frames passing address filters.
+    #[inline]
+    pub const fn disable_promiscuous(self) -> Self {
+        Self(self.0 & !Self::RX_ALL)
+    }
+    /// Check if all multicast frames are received.
+    #[inline]
+    pub const fn is_all_multicast(self) -> bool {
+        self.0 & Self::RX_ALL_MULTICAST != 0
+    }
+    /// Receive all multicast frames.
+    #[inline]
+    pub const fn enable_all_multicast(self) -> Self {
+        Self(self.0 | Self::RX_ALL_MULTICAST)
+    }
+    /// Receive only multicast frames passing address filters.
+    #[inline]
+    pub const fn disable_all_multicast(self) -> Self {
+        Self(self.0 & !Self::RX_ALL_MULTICAST)
+    }
+    /// Check if broadcast frames are received.
+    #[inline]
+    pub const fn is_broadcast_enabled(self) -> bool {
+        self.0 & Self::DIS_BROADCAST == 0
+    }
+    /// Receive broadcast frames.
+    #[inline]
+    pub const fn enable_broadcast(self) -> Self {
+        Self(self.0 & !Self::DIS_BROADCAST)
+    }
+    /// Drop broadcast frames.
+    #[inline]
+    pub const fn disable_broadcast(self) -> Self {
+        Self(self.0 | Self::DIS_BROADCAST)
+    }
+    /// Check if multicast frames are matched against hash table.
+    #[inline]
+    pub const fn is_multicast_hash_enabled(self) -> bool {
+        self.0 & Self::HASH_MULTICAST != 0
+    }
+    /// Match multicast frames against hash table instead of perfect filters.
+    #[inline]
+    pub const fn enable_multicast_hash(self) -> Self {
+        Self(self.0 | Self::HASH_MULTICAST)
+    }
+    /// Match multicast frames against perfect filters.
+    #[inline]
+    pub const fn disable_multicast_hash(self) -> Self {
+        Self(self.0 & !Self::HASH_MULTICAST)
+    }
+    /// Check if unicast frames are matched against hash table.
+    #[inline]
+    pub const fn is_unicast_hash_enabled(self) -> bool {
+        self.0 & Self::HASH_UNICAST != 0
+    }
+    /// Match unicast frames against hash table instead of perfect filters.
+    #[inline]
+    pub const fn enable_unicast_hash(self) -> Self {
+        Self(self.0 | Self::HASH_UNICAST)
+    }
+    /// Match unicast frames against perfect filters.
+    #[inline]
+    pub const fn disable_unicast_hash(self) -> Self {
+        Self(self.0 & !Self::HASH_UNICAST)
+    }
+    /// Check if address filters are bypassed, receiving all frames without filter results.
+    #[inline]
+    pub const fn is_address_filter_disabled(self) -> bool {
+        self.0 & Self::DIS_ADDR_FILTER != 0
+    }
+    /// Apply address filters.
+    #[inline]
+    pub const fn enable_address_filter(self) -> Self {
+        Self(self.0 & !Self::DIS_ADDR_FILTER)
+    }
+    /// Bypass address filters.
+    #[inline]
+    pub const fn disable_address_filter(self) -> Self {
+        Self(self.0 | Self::DIS_ADDR_FILTER)
+    }
+}
+
+impl Default for FrameFilter {
+    #[inline]
+    fn default() -> Self {
+        Self(0)
+    }
+}
+
+/// MDC clock divider from AHB clock.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum MdcDivider {
+    /// Divide by 16.
+    Div16,
+    /// Divide by 32.
+    Div32,
+    /// Divide by 64.
+    Div64,
+    /// Divide by 128.
+    Div128,
+}
+
+impl MdcDivider {
+    /// Smallest divider keeping MDC clock from `ahb_hz` within 2.5 MHz.
+    #[inline]
+    pub const fn from_ahb_clock(ahb_hz: u32) -> Self {
+        const MDC_MAX_HZ: u32 = 2_500_000;
+        if ahb_hz <= MDC_MAX_HZ * 16 {
+            Self::Div16
+        } else if ahb_hz <= MDC_MAX_HZ * 32 {
+            Self::Div32
+        } else if ahb_hz <= MDC_MAX_HZ * 64 {
+            Self::Div64
+        } else {
+            Self::Div128
+        }
+    }
+}
+
+/// Management interface command register.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct MiiCommand(u32);
+
+impl MiiCommand {
+    const MDC_DIV_RATIO: u32 = 0x7 << 20;
+    const PHY_ADDR: u32 = 0x1F << 12;
+    const PHY_REG_ADDR: u32 = 0x1F << 4;
+    const MII_WR: u32 = 1 << 1;
+    const MII_BUSY: u32 = 1 << 0;
+
+    /// Get MDC clock divider.
+    #[inline]
+    pub const fn mdc_divider(self) -> MdcDivider {
+        match (self.0 & Self::MDC_DIV_RATIO) >> 20 {
+            0 => MdcDivider::Div16,
+            1 => MdcDivider::Div32,
+            2 => MdcDivider::Div64,
+            _ => MdcDivider::Div128,
+        }
+    }
+    /// Set MDC clock divider.
+    #[inline]
+    pub const fn set_mdc_divider(self, divider: MdcDivider) -> Self {
+        Self((self.0 & !Self::MDC_DIV_RATIO) | ((divider as u32) << 20))
+    }
+    /// Get PHY address of management frame.
+    #[inline]
+    pub const fn phy_address(self) -> u8 {
+        ((self.0 & Self::PHY_ADDR) >> 12) as u8
+    }
+    /// Set PHY address of management frame, 0 to 31.
+    #[inline]
+    pub const fn set_phy_address(self, address: u8) -> Self {
+        Self((self.0 & !Self::PHY_ADDR) | (((address as u32) << 12) & Self::PHY_ADDR))
+    }
+    /// Get PHY register of management frame.
+    #[inline]
+    pub const fn phy_register(self) -> u8 {
+        ((self.0 & Self::PHY_REG_ADDR) >> 4) as u8
+    }
+    /// Set PHY register of management frame, 0 to 31.
+    #[inline]
+    pub const fn set_phy_register(self, register: u8) -> Self {
+        Self((self.0 & !Self::PHY_REG_ADDR) | (((register as u32) << 4) & Self::PHY_REG_ADDR))
+    }
+    /// Check if management frame writes PHY register.
+    #[inline]
+    pub const fn is_write(self) -> bool {
+        self.0 & Self::MII_WR != 0
+    }
+    /// Write PHY register from data register.
+    #[inline]
+    pub const fn set_write(self) -> Self {
+        Self(self.0 | Self::MII_WR)
+    }
+    /// Read PHY register into data register.
+    #[inline]
+    pub const fn set_read(self) -> Self {
+        Self(self.0 & !Self::MII_WR)
+    }
+    /// Check if management frame is in progress.
+    #[inline]
+    pub const fn is_busy(self) -> bool {
+        self.0 & Self::MII_BUSY != 0
+    }
+    /// Start management frame.
+    #[inline]
+    pub const fn start(self) -> Self {
+        Self(self.0 | Self::MII_BUSY)
+    }
+}
+
+impl Default for MiiCommand {
+    #[inline]
+    fn default() -> Self {
+        Self(0)
+    }
+}
+
+/// RGMII status register, reporting in-band link status of RGMII PHY.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct RgmiiStatus(u32);
+
+impl RgmiiStatus {
+    const LINK_MODE: u32 = 1 << 3;
+    const LINK_SPEED: u32 = 0x3 << 1;
+    const LINK: u32 = 1 << 0;
+
+    /// Get duplex mode of link.
+    #[inline]
+    pub const fn duplex(self) -> Duplex {
+        if self.0 & Self::LINK_MODE != 0 {
+            Duplex::Full
+        } else {
+            Duplex::Half
+        }
+    }
+    /// Get speed of link.
+    #[inline]
+    pub const fn speed(self) -> Speed {
+        match (self.0 & Self::LINK_SPEED) >> 1 {
+            0 => Speed::Mbps10,
+            1 => Speed::Mbps100,
+            _ => Speed::Mbps1000,
+        }
+    }
+    /// Check if link is up.
+    #[inline]
+    pub const fn is_link_up(self) -> bool {
+        self.0 & Self::LINK != 0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        BasicControl0, Duplex, FrameFilter, Interrupt, Interrupts, MdcDivider, MiiCommand,
+        RegisterBlock, RgmiiStatus, RxControl0, Speed, TxControl1,
+    };
+    use core::mem::offset_of;
+
+    #[test]
+    fn offset_emac() {
+        assert_eq!(offset_of!(RegisterBlock, basic_control0), 0x00);
+        assert_eq!(offset_of!(RegisterBlock, int_status), 0x08);
+        assert_eq!(offset_of!(RegisterBlock, tx_control0), 0x10);
+        assert_eq!(offset_of!(RegisterBlock, tx_flow_control), 0x1C);
+        assert_eq!(offset_of!(RegisterBlock, tx_desc_list), 0x20);
+        assert_eq!(offset_of!(RegisterBlock, rx_control0), 0x24);
+        assert_eq!(offset_of!(RegisterBlock, rx_desc_list), 0x34);
+        assert_eq!(offset_of!(RegisterBlock, rx_frame_filter), 0x38);
+        assert_eq!(offset_of!(RegisterBlock, rx_hash), 0x40);
+        assert_eq!(offset_of!(RegisterBlock, mii_command), 0x48);
+        assert_eq!(offset_of!(RegisterBlock, mii_data), 0x4C);
+        assert_eq!(offset_of!(RegisterBlock, mac_address), 0x50);
+        assert_eq!(offset_of!(RegisterBlock, tx_dma_status), 0xB0);
+        assert_eq!(offset_of!(RegisterBlock, rx_dma_status), 0xC0);
+        assert_eq!(offset_of!(RegisterBlock, rgmii_status), 0xD0);
+    }
+
+    #[test]
+    fn struct_basic_control0_functions() {
+        let mut val = BasicControl0::default();
+        assert_eq!(val.speed(), Speed::Mbps1000);
+        assert_eq!(val.duplex(), Duplex::Half);
+
+        val = val.set_speed(Speed::Mbps100).set_duplex(Duplex::Full);
+        assert_eq!(val.speed(), Speed::Mbps100);
+        assert_eq!(val.duplex(), Duplex::Full);
+        assert_eq!(val.0, 0x0000000D);
+        val = val.set_speed(Speed::Mbps10);
+        assert_eq!(val.speed(), Speed::Mbps10);
+        assert_eq!(val.0, 0x00000009);
+        val = val.set_speed(Speed::Mbps1000).set_duplex(Duplex::Half);
+        assert_eq!(val.0, 0x00000000);
+
+        val = val.enable_loopback();
+        assert!(val.is_loopback_enabled());
+        assert_eq!(val.0, 0x00000002);
+        val = val.disable_loopback();
+        assert!(!val.is_loopback_enabled());
+    }
+
+    #[test]
+    fn struct_interrupts_functions() {
+        let mut val = Interrupts::default();
+        val = val
+            .enable_interrupt(Interrupt::Receive)
+            .enable_interrupt(Interrupt::RgmiiLink);
+        assert!(val.has_interrupt(Interrupt::Receive));
+        assert!(val.has_interrupt(Interrupt::RgmiiLink));
+        assert!(!val.has_interrupt(Interrupt::Transmit));
+        assert_eq!(val.0, 0x00010100);
+        val = val.disable_interrupt(Interrupt::Receive);
+        assert_eq!(val.0, 0x00010000);
+    }
+
+    #[test]
+    fn struct_tx_control1_functions() {
+        let mut val = TxControl1::default();
+        val = val.enable_store_and_forward();
+        assert!(val.is_store_and_forward_enabled());
+        assert_eq!(val.0, 0x00000002);
+        val = val.disable_store_and_forward();
+        assert!(!val.is_store_and_forward_enabled());
+        assert_eq!(val.0, 0x00000000);
+    }
+
+    #[test]
+    fn struct_rx_control0_functions() {
+        let mut val = RxControl0::default();
+        val = val
+            .enable_receiver()
+            .enable_strip_fcs()
+            .enable_checksum_offload();
+        assert!(val.is_receiver_enabled());
+        assert!(val.is_strip_fcs_enabled());
+        assert!(val.is_checksum_offload_enabled());
+        assert_eq!(val.0, 0x98000000);
+        val = val.disable_checksum_offload();
+        assert!(!val.is_checksum_offload_enabled());
+        assert_eq!(val.0, 0x90000000);
+        val = val.disable_receiver().disable_strip_fcs();
+        assert_eq!(val.0, 0x00000000);
+    }
+
+    #[test]
+    fn struct_frame_filter_functions() {
+        let mut val = FrameFilter::default();
+        assert!(val.is_broadcast_enabled());
+        val = val
+            .enable_promiscuous()
+            .enable_all_multicast()
+            .disable_broadcast()
+            .enable_multicast_hash()
+            .enable_unicast_hash()
+            .disable_address_filter();
+        assert!(val.is_promiscuous());
+        assert!(val.is_all_multicast());
+        assert!(!val.is_broadcast_enabled());
+        assert!(val.is_multicast_hash_enabled());
+        assert!(val.is_unicast_hash_enabled());
+        assert!(val.is_address_filter_disabled());
+        assert_eq!(val.0, 0x80030301);
+        val = val
+            .disable_promiscuous()
+            .disable_all_multicast()
+            .enable_broadcast()
+            .disable_multicast_hash()
+            .disable_unicast_hash()
+            .enable_address_filter();
+        assert_eq!(val.0, 0x00000000);
+    }
+
+    #[test]
+    fn struct_mii_command_functions() {
+        let mut val = MiiCommand::default();
+        val = val
+            .set_mdc_divider(MdcDivider::Div128)
+            .set_phy_address(1)
+            .set_phy_register(0x1F)
+            .set_write()
+            .start();
+        assert_eq!(val.mdc_divider(), MdcDivider::Div128);
+        assert_eq!(val.phy_address(), 1);
+        assert_eq!(val.phy_register(), 0x1F);
+        assert!(val.is_write());
+        assert!(val.is_busy());
+        assert_eq!(val.0, 0x003011F3);
+        val = val.set_read().set_mdc_divider(MdcDivider::Div32);
+        assert!(!val.is_write());
+        assert_eq!(val.0, 0x001011F1);
+    }
+
+    #[test]
+    fn function_mdc_divider_from_ahb_clock() {
+        assert_eq!(MdcDivider::from_ahb_clock(24_000_000), MdcDivider::Div16);
+        assert_eq!(MdcDivider::from_ahb_clock(80_000_000), MdcDivider::Div32);
+        assert_eq!(MdcDivider::from_ahb_clock(150_000_000), MdcDivider::Div64);
+        assert_eq!(MdcDivider::from_ahb_clock(200_000_000), MdcDivider::Div128);
+    }
+
+    #[test]
+    fn struct_rgmii_status_functions() {
+        let val = RgmiiStatus(0x0000000D);
+        assert!(val.is_link_up());
+        assert_eq!(val.speed(), Speed::Mbps1000);
+        assert_eq!(val.duplex(), Duplex::Full);
+        let val = RgmiiStatus(0x00000002);
+        assert!(!val.is_link_up());
+        assert_eq!(val.speed(), Speed::Mbps100);
+        assert_eq!(val.duplex(), Duplex::Half);
+    }
+}
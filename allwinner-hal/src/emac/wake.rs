@@ -0,0 +1,113 @@
+/// Frames and events waking the system while [`Emac`](super::Emac) is suspended.
+///
+/// EMAC has no hardware pattern matcher; a suspended EMAC keeps receiving frames allowed by
+/// this configuration and raises receive interrupt on each of them. Wake handler checks the
+/// received frame with [`WakeOnLan::matches`] and puts system back to sleep if it doesn't.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct WakeOnLan {
+    /// Magic packets carrying station address, sent to any destination.
+    pub magic_packet: bool,
+    /// Unicast frames to station address or perfect filter addresses.
+    pub unicast: bool,
+    /// Multicast frames passing perfect filters or hash table.
+    pub multicast: bool,
+    /// Link status change reported in-band by RGMII PHY.
+    pub link_change: bool,
+}
+
+impl Default for WakeOnLan {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            magic_packet: true,
+            unicast: false,
+            multicast: false,
+            link_change: false,
+        }
+    }
+}
+
+impl WakeOnLan {
+    /// Check if received `frame` should wake the system with station `address`.
+    #[inline]
+    pub fn matches(&self, frame: &[u8], address: [u8; 6]) -> bool {
+        let Some(destination) = frame.get(..6) else {
+            return false;
+        };
+        if self.magic_packet && is_magic_packet(frame, address) {
+            return true;
+        }
+        let multicast = destination[0] & 1 != 0;
+        let broadcast = destination == [0xFF; 6];
+        (self.unicast && !multicast) || (self.multicast && multicast && !broadcast)
+    }
+}
+
+/// Check if `frame` is a magic packet to station `address`.
+///
+/// A magic packet contains 6 bytes of `0xFF` followed by 16 repetitions of station address
+/// anywhere after Ethernet header, in UDP datagrams or raw frames of any EtherType.
+#[inline]
+pub fn is_magic_packet(frame: &[u8], address: [u8; 6]) -> bool {
+    const HEADER_LEN: usize = 14;
+    const MAGIC_LEN: usize = 6 + 16 * 6;
+    let Some(payload) = frame.get(HEADER_LEN..) else {
+        return false;
+    };
+    payload.windows(MAGIC_LEN).any(|window| {
+        let (sync, repeats) = window.split_at(6);
+        sync == [0xFF; 6] && repeats.chunks_exact(6).all(|chunk| chunk == address)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{WakeOnLan, is_magic_packet};
+
+    const STATION: [u8; 6] = [0x02, 0x11, 0x22, 0x33, 0x44, 0x55];
+
+    fn magic_frame(address: [u8; 6]) -> [u8; 14 + 8 + 102] {
+        let mut frame = [0u8; 14 + 8 + 102];
+        frame[..6].copy_from_slice(&[0xFF; 6]);
+        frame[12..14].copy_from_slice(&[0x08, 0x42]);
+        frame[22..28].copy_from_slice(&[0xFF; 6]);
+        for chunk in frame[28..].chunks_exact_mut(6) {
+            chunk.copy_from_slice(&address);
+        }
+        frame
+    }
+
+    #[test]
+    fn function_is_magic_packet() {
+        assert!(is_magic_packet(&magic_frame(STATION), STATION));
+        assert!(!is_magic_packet(&magic_frame([0x02; 6]), STATION));
+        assert!(!is_magic_packet(&magic_frame(STATION)[..100], STATION));
+        assert!(!is_magic_packet(&[0xFF; 10], STATION));
+    }
+
+    #[test]
+    fn struct_wake_on_lan_functions() {
+        let mut unicast = [0u8; 60];
+        unicast[..6].copy_from_slice(&STATION);
+        let mut multicast = [0u8; 60];
+        multicast[..6].copy_from_slice(&[0x01, 0x00, 0x5E, 0x00, 0x00, 0xFB]);
+        let broadcast = [0xFFu8; 60];
+
+        let wake = WakeOnLan::default();
+        assert!(wake.matches(&magic_frame(STATION), STATION));
+        assert!(!wake.matches(&unicast, STATION));
+        assert!(!wake.matches(&broadcast, STATION));
+
+        let wake = WakeOnLan {
+            magic_packet: false,
+            unicast: true,
+            multicast: true,
+            link_change: false,
+        };
+        assert!(!wake.matches(&magic_frame(STATION), STATION));
+        assert!(wake.matches(&unicast, STATION));
+        assert!(wake.matches(&multicast, STATION));
+        assert!(!wake.matches(&broadcast, STATION));
+        assert!(!wake.matches(&[], STATION));
+    }
+}
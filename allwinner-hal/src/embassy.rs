@@ -0,0 +1,172 @@
+//! Embassy support.
+//!
+//! With feature `embassy`, an embassy application gets from this crate:
+//!
+//! - the time driver of [`time_driver`], on system counter and timer 1;
+//! - the executor pender for `embassy-executor` built without an architecture feature,
+//!   waking a thread mode executor from `wfi`, and running interrupt mode executors from
+//!   machine software interrupt of the C906 core;
+//! - [`bind_interrupts!`](crate::bind_interrupts), routing PLIC interrupts to `on_interrupt`
+//!   functions of drivers.
+//!
+//! A `critical-section` implementation is still required, such as the one of feature
+//! `critical-section-single-hart`.
+//!
+//! ```ignore
+//! static TIMERS: StaticCell<Timers<TIMER>> = StaticCell::new();
+//! let (timer0, timer1, watchdog) = TIMERS.init(Timers::new(p.timer)).split();
+//! embassy::time_driver::init(timer1);
+//!
+//! bind_interrupts!(static IRQS: Interrupt = {
+//!     TIMER1 => allwinner_hal::embassy::time_driver::on_interrupt();
+//!     CAN0 => allwinner_hal::can::on_interrupt::<0>(&CAN0);
+//! });
+//!
+//! let mut executor = raw::Executor::new(embassy::THREAD_MODE);
+//! loop {
+//!     unsafe { executor.poll() };
+//!     embassy::wait_for_work();
+//! }
+//!
+//! // in machine external interrupt handler:
+//! IRQS.dispatch(&plic, Machine);
+//! ```
+
+pub use crate::time::embassy as time_driver;
+
+use crate::power;
+use core::sync::atomic::{AtomicBool, Ordering};
+
+/// Pender context of thread mode executor.
+pub const THREAD_MODE: *mut () = core::ptr::null_mut();
+
+/// Pender context of interrupt mode executor, polled from machine software interrupt.
+pub const SOFTWARE_INTERRUPT: *mut () = core::ptr::without_provenance_mut(1);
+
+/// CLINT machine software interrupt pending register of hart 0.
+const MSIP: *mut u32 = 0x1400_0000 as *mut u32;
+
+/// Set when thread mode executor has work.
+static THREAD_PENDING: AtomicBool = AtomicBool::new(false);
+
+#[unsafe(export_name = "__pender")]
+fn pender(context: *mut ()) {
+    if context == SOFTWARE_INTERRUPT {
+        unsafe { MSIP.write_volatile(1) };
+    } else {
+        THREAD_PENDING.store(true, Ordering::Release);
+    }
+}
+
+/// Sleep until thread mode executor has work.
+///
+/// Call after each poll of thread mode executor.
+#[inline]
+pub fn wait_for_work() {
+    // interrupts are masked between check and `wfi`, so that a wakeup is not missed;
+    // `wfi` still resumes on interrupts enabled in `mie`.
+    let was_enabled = disable_interrupts();
+    while !THREAD_PENDING.swap(false, Ordering::AcqRel) {
+        power::idle();
+        unsafe { enable_interrupts() };
+        disable_interrupts();
+    }
+    if was_enabled {
+        unsafe { enable_interrupts() };
+    }
+}
+
+/// Enable machine software interrupt of current hart, so interrupt mode executors run.
+///
+/// # Safety
+///
+/// Machine software interrupt handler must call [`on_software_interrupt`] and poll
+/// interrupt mode executors.
+#[inline]
+pub unsafe fn enable_software_interrupt() {
+    unsafe { set_msie() };
+}
+
+/// Machine software interrupt entry point.
+///
+/// Call this function from machine software interrupt handler before polling interrupt
+/// mode executors. It clears the software interrupt raised by the pender.
+#[inline]
+pub fn on_software_interrupt() {
+    unsafe { MSIP.write_volatile(0) };
+}
+
+/// Route PLIC interrupts to driver interrupt entry points.
+///
+/// Defines a static [`Dispatcher`](crate::interrupt::Dispatcher) of interrupt numbers `$I`,
+/// calling each expression when its interrupt is claimed:
+///
+/// ```ignore
+/// bind_interrupts!(static IRQS: Interrupt = {
+///     TIMER1 => allwinner_hal::embassy::time_driver::on_interrupt();
+/// });
+/// ```
+#[macro_export]
+macro_rules! bind_interrupts {
+    ($vis:vis static $name:ident: $I:ty = { $($irq:ident => $handler:expr;)* }) => {
+        $vis static $name: $crate::interrupt::Dispatcher<$I> =
+            $crate::interrupt::Dispatcher::new(&[
+                $((<$I>::$irq, {
+                    fn handler() {
+                        $handler;
+                    }
+                    handler
+                }),)*
+            ]);
+    };
+}
+
+#[cfg(target_arch = "riscv64")]
+#[inline]
+fn disable_interrupts() -> bool {
+    let mstatus: usize;
+    unsafe { core::arch::asm!("csrrci {}, mstatus, 8", out(reg) mstatus) };
+    mstatus & (1 << 3) != 0
+}
+
+#[cfg(target_arch = "riscv64")]
+#[inline]
+unsafe fn enable_interrupts() {
+    unsafe { core::arch::asm!("csrsi mstatus, 8") };
+}
+
+#[cfg(target_arch = "riscv64")]
+#[inline]
+unsafe fn set_msie() {
+    unsafe { core::arch::asm!("csrsi mie, 8") };
+}
+
+#[cfg(not(target_arch = "riscv64"))]
+#[inline]
+fn disable_interrupts() -> bool {
+    false
+}
+
+#[cfg(not(target_arch = "riscv64"))]
+#[inline]
+unsafe fn enable_interrupts() {}
+
+#[cfg(not(target_arch = "riscv64"))]
+#[inline]
+unsafe fn set_msie() {}
+
+#[cfg(test)]
+mod tests {
+    use crate::wafer::d1::Interrupt;
+
+    fn on_uart0() {}
+
+    bind_interrupts!(static IRQS: Interrupt = {
+        UART0 => on_uart0();
+    });
+
+    #[test]
+    fn macro_bind_interrupts() {
+        assert_eq!(IRQS.spurious_count(), 0);
+    }
+}
@@ -0,0 +1,549 @@
+//! 2D graphics accelerator.
+//!
+//! The accelerator fills rectangles, copies images converting between pixel formats, and
+//! rotates images in memory without using the processor. [`G2d::submit`] starts an
+//! [`Operation`] and returns a [`Fence`], which is signaled once the operation has finished:
+//!
+//! ```ignore
+//! let mut g2d = G2d::new(p.g2d, &p.ccu);
+//! let screen = Surface::new(fb.address(), PixelFormat::Xrgb8888, 800, 480);
+//! let fill = Operation::Fill {
+//!     dst: screen,
+//!     area: Rect::new(0, 0, 800, 480),
+//!     color: 0xFF00_0000,
+//! };
+//! let fence = unsafe { g2d.submit(&fill) }?;
+//! // ... prepare next frame on processor while accelerator fills the screen
+//! g2d.wait(fence)?;
+//! ```
+//!
+//! One operation runs at a time; submitting an operation waits for the previous one to finish.
+//! [`G2d::wait_async`] waits for a fence without blocking, when [`on_interrupt`] is called from
+//! G2D interrupt handler.
+//!
+//! Images are accessed by DMA, and should reside in DRAM.
+
+mod register;
+pub use register::*;
+
+use crate::{
+    ccu::{self, ClockConfig, ClockGate, G2dClockSource, PeriFactorN},
+    de::{AlphaMode, Coordinate, LayerAttribute, PipeControl, PixelFormat, Size},
+    time::Deadline,
+};
+use atomic_waker::AtomicWaker;
+use core::{
+    future::poll_fn,
+    sync::atomic::{Ordering, fence},
+    task::Poll,
+};
+
+static WAKER: AtomicWaker = AtomicWaker::new();
+
+/// Raster operation passing blender pipe 0 through unchanged.
+const ROP_PASS_PIPE0: u32 = 0xF0;
+
+/// Largest image width or height processed by the accelerator.
+const MAX_SIZE: u16 = 8192;
+
+/// G2D interrupt entry point.
+///
+/// Call this function from G2D interrupt handler when waiting with [`G2d::wait_async`].
+/// Finished interrupt of the unit is disabled and the waiting task is woken; finished flag is
+/// left for that task to handle.
+#[inline]
+pub fn on_interrupt(g2d: &RegisterBlock) {
+    // finished flags are cleared by writing one, so interrupt registers are written afresh
+    let mixer = g2d.mixer.interrupt.read();
+    if mixer.is_interrupt_enabled() && mixer.is_finished() {
+        unsafe { g2d.mixer.interrupt.write(MixerInterrupt::default()) };
+        WAKER.wake();
+    }
+    let rotate = g2d.rotate.interrupt.read();
+    if rotate.is_interrupt_enabled() && rotate.is_finished() {
+        unsafe { g2d.rotate.interrupt.write(RotateInterrupt::default()) };
+        WAKER.wake();
+    }
+}
+
+/// Image in memory processed by the accelerator.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Surface {
+    /// Address of the top left pixel.
+    pub address: usize,
+    /// Pixel format of image.
+    pub format: PixelFormat,
+    /// Image width in pixels.
+    pub width: u16,
+    /// Image height in lines.
+    pub height: u16,
+    /// Distance in bytes between the starts of adjacent lines.
+    pub stride: u32,
+}
+
+impl Surface {
+    /// Image of `width` by `height` pixels at `address`, with packed lines.
+    #[inline]
+    pub const fn new(address: usize, format: PixelFormat, width: u16, height: u16) -> Self {
+        Self {
+            address,
+            format,
+            width,
+            height,
+            stride: width as u32 * format.bytes_per_pixel() as u32,
+        }
+    }
+    /// Address of pixel at (`x`, `y`).
+    #[inline]
+    const fn address_of(&self, x: u16, y: u16) -> u64 {
+        self.address as u64
+            + y as u64 * self.stride as u64
+            + x as u64 * self.format.bytes_per_pixel() as u64
+    }
+    /// Panic if `width` by `height` pixels at (`x`, `y`) don't fit in image.
+    #[inline]
+    fn check_area(&self, x: u16, y: u16, width: u16, height: u16) {
+        assert!(
+            self.stride >= self.width as u32 * self.format.bytes_per_pixel() as u32,
+            "surface stride shorter than a line"
+        );
+        assert!(
+            (1..=MAX_SIZE).contains(&width)
+                && (1..=MAX_SIZE).contains(&height)
+                && x as u32 + width as u32 <= self.width as u32
+                && y as u32 + height as u32 <= self.height as u32,
+            "area exceeds surface"
+        );
+    }
+}
+
+/// Rectangular area of an image.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Rect {
+    /// Horizontal position of the top left pixel.
+    pub x: u16,
+    /// Vertical position of the top left pixel.
+    pub y: u16,
+    /// Area width in pixels.
+    pub width: u16,
+    /// Area height in lines.
+    pub height: u16,
+}
+
+impl Rect {
+    /// Area of `width` by `height` pixels with top left pixel at (`x`, `y`).
+    #[inline]
+    pub const fn new(x: u16, y: u16, width: u16, height: u16) -> Self {
+        Self {
+            x,
+            y,
+            width,
+            height,
+        }
+    }
+}
+
+/// Operation run by the accelerator.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Operation {
+    /// Fill `area` of `dst` with ARGB8888 `color`, converted to pixel format of `dst`.
+    Fill {
+        /// Destination image.
+        dst: Surface,
+        /// Filled area of destination.
+        area: Rect,
+        /// Fill color in ARGB8888.
+        color: u32,
+    },
+    /// Copy `area` of `src` to `dst` with top left pixel at (`x`, `y`), converting pixel format.
+    Blit {
+        /// Source image.
+        src: Surface,
+        /// Copied area of source.
+        area: Rect,
+        /// Destination image.
+        dst: Surface,
+        /// Horizontal position in destination.
+        x: u16,
+        /// Vertical position in destination.
+        y: u16,
+    },
+    /// Rotate `area` of `src` clockwise into `dst` with top left pixel at (`x`, `y`).
+    ///
+    /// Source and destination must have the same pixel format.
+    Rotate {
+        /// Source image.
+        src: Surface,
+        /// Rotated area of source.
+        area: Rect,
+        /// Destination image.
+        dst: Surface,
+        /// Horizontal position in destination.
+        x: u16,
+        /// Vertical position in destination.
+        y: u16,
+        /// Clockwise rotation angle.
+        rotation: Rotation,
+        /// Mirror area horizontally before rotation.
+        mirror: bool,
+    },
+}
+
+/// Completion marker of a submitted [`Operation`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Fence(u32);
+
+/// 2D graphics accelerator error.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Error {
+    /// Operation did not finish before crate-wide blocking timeout, and has been aborted.
+    Timeout,
+}
+
+/// Managed 2D graphics accelerator structure with peripheral.
+pub struct G2d<G2D> {
+    g2d: G2D,
+    submitted: u32,
+    completed: u32,
+    running: Option<Unit>,
+}
+
+impl<G2D: AsRef<RegisterBlock>> G2d<G2D> {
+    /// Create an idle 2D graphics accelerator clocked at 300 MHz.
+    #[inline]
+    pub fn new(g2d: G2D, ccu: &ccu::RegisterBlock) -> Self {
+        // note(unsafe): async read and write using ccu registers
+        unsafe { ccu::G2D::reconfigure(ccu, G2dClockSource::PllPeri2x, 3, PeriFactorN::N1) };
+        let regs = g2d.as_ref();
+        unsafe {
+            regs.divider.write(0);
+            for unit in [Unit::Mixer, Unit::Rotate] {
+                regs.clock_gating.modify(|val| val.enable(unit));
+                regs.bus_gating.modify(|val| val.enable(unit));
+                regs.reset.modify(|val| val.deassert_reset(unit));
+            }
+        }
+        Self {
+            g2d,
+            submitted: 0,
+            completed: 0,
+            running: None,
+        }
+    }
+    /// Start `operation` after the previous one has finished, returning its fence.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Timeout`] if the previous operation did not finish.
+    ///
+    /// # Panics
+    ///
+    /// Panics if an area exceeds its image or is larger than 8192 pixels in any dimension, or
+    /// if rotated images differ in pixel format.
+    ///
+    /// # Safety
+    ///
+    /// Images must stay valid, and destination must not be otherwise accessed, until the
+    /// returned fence is signaled.
+    #[inline]
+    pub unsafe fn submit(&mut self, operation: &Operation) -> Result<Fence, Error> {
+        self.wait(Fence(self.submitted))?;
+        let unit = match *operation {
+            Operation::Fill { dst, area, color } => {
+                dst.check_area(area.x, area.y, area.width, area.height);
+                self.set_layer(&dst, &area);
+                let layer = &self.g2d.as_ref().layer;
+                unsafe {
+                    layer.attribute.modify(|val| val.enable_fill_color());
+                    layer.fill_color.write(color);
+                }
+                self.start_mixer(&dst, &area, area.x, area.y);
+                Unit::Mixer
+            }
+            Operation::Blit {
+                src,
+                area,
+                dst,
+                x,
+                y,
+            } => {
+                src.check_area(area.x, area.y, area.width, area.height);
+                dst.check_area(x, y, area.width, area.height);
+                self.set_layer(&src, &area);
+                self.start_mixer(&dst, &area, x, y);
+                Unit::Mixer
+            }
+            Operation::Rotate {
+                src,
+                area,
+                dst,
+                x,
+                y,
+                rotation,
+                mirror,
+            } => {
+                assert!(src.format == dst.format, "rotated images differ in format");
+                let (width, height) = match rotation.is_transposed() {
+                    true => (area.height, area.width),
+                    false => (area.width, area.height),
+                };
+                src.check_area(area.x, area.y, area.width, area.height);
+                dst.check_area(x, y, width, height);
+                self.start_rotate(&src, &area, &dst, x, y, rotation, mirror);
+                Unit::Rotate
+            }
+        };
+        self.submitted = self.submitted.wrapping_add(1);
+        self.running = Some(unit);
+        Ok(Fence(self.submitted))
+    }
+    /// Check if operation of `fence` has finished.
+    #[inline]
+    pub fn is_signaled(&mut self, fence: Fence) -> bool {
+        self.poll();
+        self.completed.wrapping_sub(fence.0) as i32 >= 0
+    }
+    /// Wait until operation of `fence` has finished.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Timeout`] if the operation did not finish; it is aborted, and its fence
+    /// is signaled.
+    #[inline]
+    pub fn wait(&mut self, fence: Fence) -> Result<(), Error> {
+        let deadline = Deadline::start();
+        while !self.is_signaled(fence) {
+            if deadline.check().is_err() {
+                self.abort();
+                return Err(Error::Timeout);
+            }
+            core::hint::spin_loop();
+        }
+        Ok(())
+    }
+    /// Wait until operation of `fence` has finished.
+    ///
+    /// Requires [`on_interrupt`] to be called from G2D interrupt handler; otherwise this
+    /// function never returns while the operation is running.
+    #[inline]
+    pub async fn wait_async(&mut self, fence: Fence) {
+        poll_fn(|cx| {
+            WAKER.register(cx.waker());
+            if self.is_signaled(fence) {
+                return Poll::Ready(());
+            }
+            self.enable_interrupt();
+            if self.is_signaled(fence) {
+                Poll::Ready(())
+            } else {
+                Poll::Pending
+            }
+        })
+        .await;
+    }
+    /// Release the accelerator, aborting any running operation.
+    #[inline]
+    pub fn free(mut self, ccu: &ccu::RegisterBlock) -> G2D {
+        self.abort();
+        let regs = self.g2d.as_ref();
+        unsafe {
+            for unit in [Unit::Mixer, Unit::Rotate] {
+                regs.reset.modify(|val| val.assert_reset(unit));
+                regs.bus_gating.modify(|val| val.disable(unit));
+                regs.clock_gating.modify(|val| val.disable(unit));
+            }
+        }
+        // note(unsafe): async read and write using ccu registers
+        unsafe { ccu::G2D::free(ccu) };
+        self.g2d
+    }
+    /// Retire running operation if its unit has finished.
+    #[inline]
+    fn poll(&mut self) {
+        let regs = self.g2d.as_ref();
+        let finished = match self.running {
+            None => return,
+            Some(Unit::Mixer) => {
+                let val = regs.mixer.interrupt.read();
+                if val.is_finished() {
+                    unsafe { regs.mixer.interrupt.write(val.clear_finished()) };
+                }
+                val.is_finished()
+            }
+            Some(Unit::Rotate) => {
+                let val = regs.rotate.interrupt.read();
+                if val.is_finished() {
+                    unsafe {
+                        regs.rotate.interrupt.write(val.clear_finished());
+                        regs.rotate.control.modify(|val| val.disable());
+                    }
+                }
+                val.is_finished()
+            }
+        };
+        if finished {
+            fence(Ordering::SeqCst);
+            self.completed = self.submitted;
+            self.running = None;
+        }
+    }
+    /// Stop running operation by resetting its unit, and signal its fence.
+    #[inline]
+    fn abort(&mut self) {
+        if let Some(unit) = self.running.take() {
+            let regs = self.g2d.as_ref();
+            unsafe {
+                regs.reset.modify(|val| val.assert_reset(unit));
+                regs.reset.modify(|val| val.deassert_reset(unit));
+            }
+            self.completed = self.submitted;
+        }
+    }
+    /// Enable finished interrupt of running unit, keeping its finished flag.
+    #[inline]
+    fn enable_interrupt(&self) {
+        let regs = self.g2d.as_ref();
+        unsafe {
+            match self.running {
+                Some(Unit::Mixer) => regs
+                    .mixer
+                    .interrupt
+                    .write(MixerInterrupt::default().enable_interrupt()),
+                Some(Unit::Rotate) => regs
+                    .rotate
+                    .interrupt
+                    .write(RotateInterrupt::default().enable_interrupt()),
+                None => {}
+            }
+        }
+    }
+    /// Read `area` of `src` on video layer.
+    #[inline]
+    fn set_layer(&self, src: &Surface, area: &Rect) {
+        let layer = &self.g2d.as_ref().layer;
+        let size = Size::default()
+            .set_width(area.width)
+            .set_height(area.height);
+        let address = src.address_of(area.x, area.y);
+        unsafe {
+            layer.attribute.write(
+                LayerAttribute::default()
+                    .set_format(src.format)
+                    .set_alpha_mode(AlphaMode::Pixel)
+                    .set_alpha(0xFF)
+                    .enable(),
+            );
+            layer.size.write(size);
+            layer.coordinate.write(Coordinate::default());
+            layer.pitch[0].write(src.stride);
+            layer.address[0].write(address as u32);
+            layer.high_address.write(((address >> 32) & 0xFF) as u32);
+            layer.overlay_size.write(size);
+        }
+    }
+    /// Blend video layer alone and write `area` sized output to `dst` at (`x`, `y`).
+    #[inline]
+    fn start_mixer(&self, dst: &Surface, area: &Rect, x: u16, y: u16) {
+        let regs = self.g2d.as_ref();
+        let size = Size::default()
+            .set_width(area.width)
+            .set_height(area.height);
+        let address = dst.address_of(x, y);
+        unsafe {
+            regs.blender
+                .pipe_control
+                .write(PipeControl::default().enable_pipe::<0>());
+            regs.blender.input_size[0].write(size);
+            regs.blender.input_offset[0].write(Coordinate::default());
+            regs.blender.premultiply.write(0);
+            regs.blender.output_size.write(size);
+            regs.blender.output_color.write(0);
+            regs.blender.rop_control.write(ROP_PASS_PIPE0);
+            regs.write_back.format.write(dst.format as u32);
+            regs.write_back.size.write(size);
+            regs.write_back.pitch[0].write(dst.stride);
+            regs.write_back.address[0].low.write(address as u32);
+            regs.write_back.address[0]
+                .high
+                .write(((address >> 32) & 0xFF) as u32);
+            regs.mixer.interrupt.write(
+                MixerInterrupt::default()
+                    .enable_interrupt()
+                    .clear_finished(),
+            );
+        }
+        fence(Ordering::SeqCst);
+        unsafe { regs.mixer.control.modify(|val| val.start()) };
+    }
+    /// Rotate `area` of `src` into `dst` at (`x`, `y`).
+    #[allow(clippy::too_many_arguments)]
+    #[inline]
+    fn start_rotate(
+        &self,
+        src: &Surface,
+        area: &Rect,
+        dst: &Surface,
+        x: u16,
+        y: u16,
+        rotation: Rotation,
+        mirror: bool,
+    ) {
+        let rotate = &self.g2d.as_ref().rotate;
+        let input_size = Size::default()
+            .set_width(area.width)
+            .set_height(area.height);
+        let output_size = match rotation.is_transposed() {
+            true => Size::default()
+                .set_width(area.height)
+                .set_height(area.width),
+            false => input_size,
+        };
+        let input = src.address_of(area.x, area.y);
+        let output = dst.address_of(x, y);
+        let control = RotateControl::default().set_rotation(rotation).enable();
+        let control = match mirror {
+            true => control.enable_mirror(),
+            false => control,
+        };
+        unsafe {
+            rotate.control.write(control);
+            rotate.input_format.write(src.format as u32);
+            rotate.input_size.write(input_size);
+            rotate.input_pitch[0].write(src.stride);
+            rotate.input_address[0].low.write(input as u32);
+            rotate.input_address[0]
+                .high
+                .write(((input >> 32) & 0xFF) as u32);
+            rotate.output_size.write(output_size);
+            rotate.output_pitch[0].write(dst.stride);
+            rotate.output_address[0].low.write(output as u32);
+            rotate.output_address[0]
+                .high
+                .write(((output >> 32) & 0xFF) as u32);
+            rotate.interrupt.write(
+                RotateInterrupt::default()
+                    .enable_interrupt()
+                    .clear_finished(),
+            );
+        }
+        fence(Ordering::SeqCst);
+        unsafe { rotate.control.write(control.start()) };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Rect, Surface};
+    use crate::de::PixelFormat;
+
+    #[test]
+    fn struct_surface_functions() {
+        let surface = Surface::new(0x4000_0000, PixelFormat::Rgb565, 800, 480);
+        assert_eq!(surface.stride, 1600);
+        assert_eq!(surface.address_of(0, 0), 0x4000_0000);
+        assert_eq!(surface.address_of(10, 2), 0x4000_0000 + 2 * 1600 + 20);
+        surface.check_area(0, 0, 800, 480);
+        let area = Rect::new(700, 400, 100, 80);
+        surface.check_area(area.x, area.y, area.width, area.height);
+    }
+}
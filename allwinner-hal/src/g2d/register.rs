@@ -0,0 +1,546 @@
+use crate::de::{Coordinate, LayerAttribute, PipeControl, Size};
+use volatile_register::RW;
+
+/// 2D graphics accelerator registers.
+#[repr(C)]
+pub struct RegisterBlock {
+    /// 0x00000 - Module clock gating register.
+    pub clock_gating: RW<Gating>,
+    /// 0x00004 - Bus clock gating register.
+    pub bus_gating: RW<Gating>,
+    /// 0x00008 - Reset register.
+    pub reset: RW<Reset>,
+    /// 0x0000c - Module clock divider register.
+    pub divider: RW<u32>,
+    _reserved0: [u32; 60],
+    /// 0x00100..=0x00108 - Mixer registers.
+    pub mixer: MixerRegisters,
+    _reserved1: [u32; 189],
+    /// 0x00400..=0x00480 - Blender registers.
+    pub blender: BlenderRegisters,
+    _reserved2: [u32; 223],
+    /// 0x00800..=0x0082c - Video layer registers.
+    pub layer: VideoLayerRegisters,
+    _reserved3: [u32; 2548],
+    /// 0x03000..=0x03028 - Write back registers.
+    pub write_back: WriteBackRegisters,
+    _reserved4: [u32; 37877],
+    /// 0x28000..=0x280b4 - Rotation registers.
+    pub rotate: RotateRegisters,
+}
+
+/// Mixer control registers.
+#[repr(C)]
+pub struct MixerRegisters {
+    /// 0x00 - Mixer control register.
+    pub control: RW<MixerControl>,
+    /// 0x04 - Mixer interrupt register.
+    pub interrupt: RW<MixerInterrupt>,
+    /// 0x08 - Mixer clock register.
+    pub clock: RW<u32>,
+}
+
+/// Number of blender pipes.
+pub const PIPE_COUNT: usize = 2;
+
+/// Blender registers.
+#[repr(C)]
+pub struct BlenderRegisters {
+    /// 0x00 - Pipe control register.
+    pub pipe_control: RW<PipeControl>,
+    _reserved0: [u32; 3],
+    /// 0x10..=0x14 - Pipe fill color registers.
+    pub fill_color: [RW<u32>; PIPE_COUNT],
+    _reserved1: [u32; 2],
+    /// 0x20..=0x24 - Pipe input size registers.
+    pub input_size: [RW<Size>; PIPE_COUNT],
+    _reserved2: [u32; 2],
+    /// 0x30..=0x34 - Pipe input offset registers.
+    pub input_offset: [RW<Coordinate>; PIPE_COUNT],
+    _reserved3: [u32; 2],
+    /// 0x40 - Pre-multiplied alpha register.
+    pub premultiply: RW<u32>,
+    /// 0x44 - Background color register.
+    pub background: RW<u32>,
+    /// 0x48 - Output size register.
+    pub output_size: RW<Size>,
+    /// 0x4c - Blending mode register.
+    pub mode: RW<u32>,
+    /// 0x50 - Color key control register.
+    pub key_control: RW<u32>,
+    /// 0x54 - Color key configuration register.
+    pub key_config: RW<u32>,
+    /// 0x58 - Color key maximum register.
+    pub key_max: RW<u32>,
+    /// 0x5c - Color key minimum register.
+    pub key_min: RW<u32>,
+    /// 0x60 - Output color control register.
+    pub output_color: RW<u32>,
+    _reserved4: [u32; 7],
+    /// 0x80 - Raster operation control register.
+    pub rop_control: RW<u32>,
+}
+
+/// Number of planes in an image.
+pub const PLANE_COUNT: usize = 3;
+
+/// Video layer registers.
+#[repr(C)]
+pub struct VideoLayerRegisters {
+    /// 0x00 - Layer attribute control register.
+    pub attribute: RW<LayerAttribute>,
+    /// 0x04 - Layer size register.
+    pub size: RW<Size>,
+    /// 0x08 - Layer coordinate register.
+    pub coordinate: RW<Coordinate>,
+    /// 0x0c..=0x14 - Plane pitch registers, in bytes.
+    pub pitch: [RW<u32>; PLANE_COUNT],
+    /// 0x18..=0x20 - Plane low address registers.
+    pub address: [RW<u32>; PLANE_COUNT],
+    /// 0x24 - Layer fill color register.
+    pub fill_color: RW<u32>,
+    /// 0x28 - Plane high address register, 8 bits per plane.
+    pub high_address: RW<u32>,
+    /// 0x2c - Overlay size register.
+    pub overlay_size: RW<Size>,
+}
+
+/// Low and high address registers of an image plane.
+#[repr(C)]
+pub struct PlaneAddress {
+    /// 0x00 - Low address register.
+    pub low: RW<u32>,
+    /// 0x04 - High address register.
+    pub high: RW<u32>,
+}
+
+/// Write back registers, storing mixer output to memory.
+#[repr(C)]
+pub struct WriteBackRegisters {
+    /// 0x00 - Output pixel format register, holding a [`PixelFormat`](crate::de::PixelFormat).
+    pub format: RW<u32>,
+    /// 0x04 - Output size register.
+    pub size: RW<Size>,
+    /// 0x08..=0x10 - Plane pitch registers, in bytes.
+    pub pitch: [RW<u32>; PLANE_COUNT],
+    /// 0x14..=0x28 - Plane address registers.
+    pub address: [PlaneAddress; PLANE_COUNT],
+}
+
+/// Rotation registers.
+#[repr(C)]
+pub struct RotateRegisters {
+    /// 0x00 - Rotation control register.
+    pub control: RW<RotateControl>,
+    /// 0x04 - Rotation interrupt register.
+    pub interrupt: RW<RotateInterrupt>,
+    /// 0x08 - Rotation timeout register.
+    pub timeout: RW<u32>,
+    _reserved0: [u32; 5],
+    /// 0x20 - Input pixel format register, holding a [`PixelFormat`](crate::de::PixelFormat).
+    pub input_format: RW<u32>,
+    /// 0x24 - Input size register.
+    pub input_size: RW<Size>,
+    _reserved1: [u32; 2],
+    /// 0x30..=0x38 - Input plane pitch registers, in bytes.
+    pub input_pitch: [RW<u32>; PLANE_COUNT],
+    _reserved2: u32,
+    /// 0x40..=0x54 - Input plane address registers.
+    pub input_address: [PlaneAddress; PLANE_COUNT],
+    _reserved3: [u32; 11],
+    /// 0x84 - Output size register.
+    pub output_size: RW<Size>,
+    _reserved4: [u32; 2],
+    /// 0x90..=0x98 - Output plane pitch registers, in bytes.
+    pub output_pitch: [RW<u32>; PLANE_COUNT],
+    _reserved5: u32,
+    /// 0xa0..=0xb4 - Output plane address registers.
+    pub output_address: [PlaneAddress; PLANE_COUNT],
+}
+
+/// Processing unit of the 2D graphics accelerator.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Unit {
+    /// Mixer, filling, copying and converting images.
+    Mixer = 0,
+    /// Rotation unit.
+    Rotate = 1,
+}
+
+/// Unit clock gating register.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct Gating(u32);
+
+impl Gating {
+    /// Check if clock of `unit` is enabled.
+    #[inline]
+    pub const fn is_enabled(self, unit: Unit) -> bool {
+        self.0 & (1 << unit as u32) != 0
+    }
+    /// Enable clock of `unit`.
+    #[inline]
+    pub const fn enable(self, unit: Unit) -> Self {
+        Self(self.0 | (1 << unit as u32))
+    }
+    /// Disable clock of `unit`.
+    #[inline]
+    pub const fn disable(self, unit: Unit) -> Self {
+        Self(self.0 & !(1 << unit as u32))
+    }
+}
+
+impl Default for Gating {
+    #[inline]
+    fn default() -> Self {
+        Self(0x0000_0000)
+    }
+}
+
+/// Unit reset register.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct Reset(u32);
+
+impl Reset {
+    /// Check if reset of `unit` is de-asserted.
+    #[inline]
+    pub const fn is_reset_deasserted(self, unit: Unit) -> bool {
+        self.0 & (1 << unit as u32) != 0
+    }
+    /// Assert reset of `unit`.
+    #[inline]
+    pub const fn assert_reset(self, unit: Unit) -> Self {
+        Self(self.0 & !(1 << unit as u32))
+    }
+    /// De-assert reset of `unit`.
+    #[inline]
+    pub const fn deassert_reset(self, unit: Unit) -> Self {
+        Self(self.0 | (1 << unit as u32))
+    }
+}
+
+impl Default for Reset {
+    #[inline]
+    fn default() -> Self {
+        Self(0x0000_0000)
+    }
+}
+
+/// Mixer control register.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct MixerControl(u32);
+
+impl MixerControl {
+    const START: u32 = 1 << 31;
+
+    /// Check if mixer is processing an operation.
+    #[inline]
+    pub const fn is_started(self) -> bool {
+        self.0 & Self::START != 0
+    }
+    /// Start mixer operation, cleared by hardware when finished.
+    #[inline]
+    pub const fn start(self) -> Self {
+        Self(self.0 | Self::START)
+    }
+}
+
+impl Default for MixerControl {
+    #[inline]
+    fn default() -> Self {
+        Self(0x0000_0000)
+    }
+}
+
+/// Mixer interrupt register.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct MixerInterrupt(u32);
+
+impl MixerInterrupt {
+    const FINISH_IRQ_EN: u32 = 1 << 4;
+    const FINISH: u32 = 1 << 0;
+
+    /// Check if operation finished interrupt is enabled.
+    #[inline]
+    pub const fn is_interrupt_enabled(self) -> bool {
+        self.0 & Self::FINISH_IRQ_EN != 0
+    }
+    /// Enable operation finished interrupt.
+    #[inline]
+    pub const fn enable_interrupt(self) -> Self {
+        Self(self.0 | Self::FINISH_IRQ_EN)
+    }
+    /// Disable operation finished interrupt.
+    #[inline]
+    pub const fn disable_interrupt(self) -> Self {
+        Self(self.0 & !Self::FINISH_IRQ_EN)
+    }
+    /// Check if an operation has finished.
+    #[inline]
+    pub const fn is_finished(self) -> bool {
+        self.0 & Self::FINISH != 0
+    }
+    /// Clear operation finished flag.
+    #[inline]
+    pub const fn clear_finished(self) -> Self {
+        Self(self.0 | Self::FINISH)
+    }
+}
+
+impl Default for MixerInterrupt {
+    #[inline]
+    fn default() -> Self {
+        Self(0x0000_0000)
+    }
+}
+
+/// Clockwise rotation angle.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Rotation {
+    /// No rotation.
+    None = 0,
+    /// Rotate by 90 degrees.
+    Rotate90 = 1,
+    /// Rotate by 180 degrees.
+    Rotate180 = 2,
+    /// Rotate by 270 degrees.
+    Rotate270 = 3,
+}
+
+impl Rotation {
+    /// Check if rotation swaps width and height of image.
+    #[inline]
+    pub const fn is_transposed(self) -> bool {
+        matches!(self, Rotation::Rotate90 | Rotation::Rotate270)
+    }
+}
+
+/// Rotation control register.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct RotateControl(u32);
+
+impl RotateControl {
+    const ENABLE: u32 = 1 << 0;
+    const ANGLE: u32 = 0x3 << 4;
+    const MIRROR: u32 = 1 << 7;
+    const START: u32 = 1 << 31;
+
+    /// Check if rotation unit is enabled.
+    #[inline]
+    pub const fn is_enabled(self) -> bool {
+        self.0 & Self::ENABLE != 0
+    }
+    /// Enable rotation unit.
+    #[inline]
+    pub const fn enable(self) -> Self {
+        Self(self.0 | Self::ENABLE)
+    }
+    /// Disable rotation unit.
+    #[inline]
+    pub const fn disable(self) -> Self {
+        Self(self.0 & !Self::ENABLE)
+    }
+    /// Get clockwise rotation angle.
+    #[inline]
+    pub const fn rotation(self) -> Rotation {
+        match (self.0 & Self::ANGLE) >> 4 {
+            0 => Rotation::None,
+            1 => Rotation::Rotate90,
+            2 => Rotation::Rotate180,
+            _ => Rotation::Rotate270,
+        }
+    }
+    /// Set clockwise rotation angle.
+    #[inline]
+    pub const fn set_rotation(self, val: Rotation) -> Self {
+        Self((self.0 & !Self::ANGLE) | ((val as u32) << 4))
+    }
+    /// Check if image is mirrored horizontally before rotation.
+    #[inline]
+    pub const fn is_mirrored(self) -> bool {
+        self.0 & Self::MIRROR != 0
+    }
+    /// Mirror image horizontally before rotation.
+    #[inline]
+    pub const fn enable_mirror(self) -> Self {
+        Self(self.0 | Self::MIRROR)
+    }
+    /// Keep image orientation before rotation.
+    #[inline]
+    pub const fn disable_mirror(self) -> Self {
+        Self(self.0 & !Self::MIRROR)
+    }
+    /// Check if rotation unit is processing an operation.
+    #[inline]
+    pub const fn is_started(self) -> bool {
+        self.0 & Self::START != 0
+    }
+    /// Start rotation, cleared by hardware when finished.
+    #[inline]
+    pub const fn start(self) -> Self {
+        Self(self.0 | Self::START)
+    }
+}
+
+impl Default for RotateControl {
+    #[inline]
+    fn default() -> Self {
+        Self(0x0000_0000)
+    }
+}
+
+/// Rotation interrupt register.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct RotateInterrupt(u32);
+
+impl RotateInterrupt {
+    const FINISH_IRQ_EN: u32 = 1 << 16;
+    const FINISH: u32 = 1 << 0;
+
+    /// Check if operation finished interrupt is enabled.
+    #[inline]
+    pub const fn is_interrupt_enabled(self) -> bool {
+        self.0 & Self::FINISH_IRQ_EN != 0
+    }
+    /// Enable operation finished interrupt.
+    #[inline]
+    pub const fn enable_interrupt(self) -> Self {
+        Self(self.0 | Self::FINISH_IRQ_EN)
+    }
+    /// Disable operation finished interrupt.
+    #[inline]
+    pub const fn disable_interrupt(self) -> Self {
+        Self(self.0 & !Self::FINISH_IRQ_EN)
+    }
+    /// Check if an operation has finished.
+    #[inline]
+    pub const fn is_finished(self) -> bool {
+        self.0 & Self::FINISH != 0
+    }
+    /// Clear operation finished flag.
+    #[inline]
+    pub const fn clear_finished(self) -> Self {
+        Self(self.0 | Self::FINISH)
+    }
+}
+
+impl Default for RotateInterrupt {
+    #[inline]
+    fn default() -> Self {
+        Self(0x0000_0000)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        BlenderRegisters, Gating, MixerControl, MixerInterrupt, RegisterBlock, Reset,
+        RotateControl, RotateInterrupt, RotateRegisters, Rotation, Unit, VideoLayerRegisters,
+        WriteBackRegisters,
+    };
+    use core::mem::{offset_of, size_of};
+
+    #[test]
+    fn offset_g2d() {
+        assert_eq!(offset_of!(RegisterBlock, clock_gating), 0x00000);
+        assert_eq!(offset_of!(RegisterBlock, bus_gating), 0x00004);
+        assert_eq!(offset_of!(RegisterBlock, reset), 0x00008);
+        assert_eq!(offset_of!(RegisterBlock, divider), 0x0000c);
+        assert_eq!(offset_of!(RegisterBlock, mixer), 0x00100);
+        assert_eq!(offset_of!(RegisterBlock, blender), 0x00400);
+        assert_eq!(offset_of!(RegisterBlock, layer), 0x00800);
+        assert_eq!(offset_of!(RegisterBlock, write_back), 0x03000);
+        assert_eq!(offset_of!(RegisterBlock, rotate), 0x28000);
+
+        assert_eq!(offset_of!(BlenderRegisters, fill_color), 0x10);
+        assert_eq!(offset_of!(BlenderRegisters, input_size), 0x20);
+        assert_eq!(offset_of!(BlenderRegisters, input_offset), 0x30);
+        assert_eq!(offset_of!(BlenderRegisters, premultiply), 0x40);
+        assert_eq!(offset_of!(BlenderRegisters, output_size), 0x48);
+        assert_eq!(offset_of!(BlenderRegisters, output_color), 0x60);
+        assert_eq!(offset_of!(BlenderRegisters, rop_control), 0x80);
+
+        assert_eq!(offset_of!(VideoLayerRegisters, pitch), 0x0c);
+        assert_eq!(offset_of!(VideoLayerRegisters, address), 0x18);
+        assert_eq!(offset_of!(VideoLayerRegisters, fill_color), 0x24);
+        assert_eq!(offset_of!(VideoLayerRegisters, high_address), 0x28);
+        assert_eq!(offset_of!(VideoLayerRegisters, overlay_size), 0x2c);
+
+        assert_eq!(offset_of!(WriteBackRegisters, size), 0x04);
+        assert_eq!(offset_of!(WriteBackRegisters, pitch), 0x08);
+        assert_eq!(offset_of!(WriteBackRegisters, address), 0x14);
+        assert_eq!(size_of::<WriteBackRegisters>(), 0x2c);
+
+        assert_eq!(offset_of!(RotateRegisters, interrupt), 0x04);
+        assert_eq!(offset_of!(RotateRegisters, input_format), 0x20);
+        assert_eq!(offset_of!(RotateRegisters, input_size), 0x24);
+        assert_eq!(offset_of!(RotateRegisters, input_pitch), 0x30);
+        assert_eq!(offset_of!(RotateRegisters, input_address), 0x40);
+        assert_eq!(offset_of!(RotateRegisters, output_size), 0x84);
+        assert_eq!(offset_of!(RotateRegisters, output_pitch), 0x90);
+        assert_eq!(offset_of!(RotateRegisters, output_address), 0xa0);
+    }
+
+    #[test]
+    fn struct_top_functions() {
+        let val = Gating::default().enable(Unit::Mixer).enable(Unit::Rotate);
+        assert_eq!(val.0, 0x0000_0003);
+        assert!(val.is_enabled(Unit::Rotate));
+        assert_eq!(val.disable(Unit::Mixer).0, 0x0000_0002);
+
+        let val = Reset::default().deassert_reset(Unit::Rotate);
+        assert_eq!(val.0, 0x0000_0002);
+        assert!(val.is_reset_deasserted(Unit::Rotate));
+        assert!(!val.is_reset_deasserted(Unit::Mixer));
+        assert_eq!(val.assert_reset(Unit::Rotate).0, 0x0000_0000);
+    }
+
+    #[test]
+    fn struct_mixer_functions() {
+        let val = MixerControl::default();
+        assert!(!val.is_started());
+        assert_eq!(val.start().0, 0x8000_0000);
+
+        let val = MixerInterrupt::default().enable_interrupt();
+        assert_eq!(val.0, 0x0000_0010);
+        assert!(val.is_interrupt_enabled() && !val.is_finished());
+        let val = val.clear_finished();
+        assert_eq!(val.0, 0x0000_0011);
+        assert!(val.is_finished());
+        assert_eq!(val.disable_interrupt().0, 0x0000_0001);
+    }
+
+    #[test]
+    fn struct_rotate_functions() {
+        let val = RotateControl::default()
+            .set_rotation(Rotation::Rotate270)
+            .enable_mirror()
+            .enable();
+        assert_eq!(val.0, 0x0000_00B1);
+        assert!(val.is_enabled() && val.is_mirrored());
+        assert_eq!(val.rotation(), Rotation::Rotate270);
+        let val = val
+            .set_rotation(Rotation::Rotate90)
+            .disable_mirror()
+            .start();
+        assert_eq!(val.0, 0x8000_0011);
+        assert!(val.is_started());
+        assert_eq!(val.disable().0, 0x8000_0010);
+
+        assert!(Rotation::Rotate90.is_transposed());
+        assert!(!Rotation::Rotate180.is_transposed());
+
+        let val = RotateInterrupt::default().enable_interrupt();
+        assert_eq!(val.0, 0x0001_0000);
+        assert!(val.is_interrupt_enabled() && !val.is_finished());
+        let val = val.clear_finished();
+        assert_eq!(val.0, 0x0001_0001);
+        assert!(val.is_finished());
+        assert_eq!(val.disable_interrupt().0, 0x0000_0001);
+    }
+}
@@ -0,0 +1,369 @@
+//! General Purpose Analog-to-Digital Converter.
+//!
+//! GPADC converts enabled channels continuously into 12-bit values. Each channel can compare
+//! its data with a low and a high threshold and raise an interrupt when data crosses either
+//! of them, so that slowly changing voltages like battery level or resistor ladder buttons
+//! are monitored without polling.
+//!
+//! For audio-rate acquisition, [`Stream`] drains the FIFO through a DMA channel into a
+//! pair of buffers, so that samples are handled a buffer at a time.
+//!
+//! With `embedded-hal-02` feature, analog input pads of current SoC implement
+//! `embedded_hal_02::adc::Channel`, so that [`Gpadc`] serves generic drivers through the
+//! one-shot ADC trait `OneShot`.
+//!
+//! D1-like chips bond out channel 0 only, on pad `GPADC0`.
+
+mod register;
+#[cfg(feature = "d1")]
+mod stream;
+pub use register::*;
+#[cfg(feature = "d1")]
+pub use stream::*;
+
+use crate::ccu::{self, ClockGate};
+use crate::time::Deadline;
+use crate::timer::HOSC_FREQUENCY;
+use core::sync::atomic::{AtomicPtr, Ordering};
+use embedded_time::rate::Hertz;
+use volatile_register::RW;
+
+/// Largest value of a conversion result.
+pub const MAX_VALUE: u16 = 0xFFF;
+
+/// Threshold crossed by channel data.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Threshold {
+    /// Data fell below low threshold.
+    Low,
+    /// Data rose above high threshold.
+    High,
+}
+
+/// Threshold interrupt handler, called with channel index and crossed threshold.
+///
+/// Data beyond a threshold raises the interrupt again on every conversion; handlers usually
+/// move thresholds to add hysteresis, or disable the interrupt.
+pub type Handler = fn(channel: usize, threshold: Threshold);
+
+static HANDLERS: [AtomicPtr<()>; CHANNEL_COUNT] =
+    [const { AtomicPtr::new(core::ptr::null_mut()) }; CHANNEL_COUNT];
+
+/// GPADC interrupt entry point.
+///
+/// Call this function from GPADC interrupt handler. Crossed thresholds are cleared and the
+/// registered handler of their channel is called; thresholds of channels without a handler
+/// get their interrupt disabled.
+#[inline]
+pub fn on_interrupt(gpadc: &RegisterBlock) {
+    for (threshold, enable, status) in [
+        (Threshold::Low, &gpadc.low_int_enable, &gpadc.low_int_status),
+        (
+            Threshold::High,
+            &gpadc.high_int_enable,
+            &gpadc.high_int_status,
+        ),
+    ] {
+        let enabled = enable.read();
+        let pending = status.read();
+        for (channel, handler) in HANDLERS.iter().enumerate() {
+            if !enabled.is_enabled(channel) || !pending.is_pending(channel) {
+                continue;
+            }
+            unsafe { status.write(ChannelStatus::default().clear_pending(channel)) };
+            let handler = handler.load(Ordering::Acquire);
+            if handler.is_null() {
+                unsafe { enable.modify(|val| val.disable(channel)) };
+            } else {
+                // note(unsafe): only `Handler` function pointers are stored in `HANDLERS`
+                let handler: Handler = unsafe { core::mem::transmute(handler) };
+                handler(channel, threshold);
+            }
+        }
+    }
+}
+
+/// GPADC error.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Error {
+    /// Conversion did not finish before crate-wide blocking timeout.
+    Timeout,
+}
+
+/// Managed GPADC structure with peripheral.
+pub struct Gpadc<GPADC> {
+    gpadc: GPADC,
+}
+
+impl<GPADC: AsRef<RegisterBlock>> Gpadc<GPADC> {
+    /// Create a GPADC instance converting continuously at about 50 kHz, with no channel
+    /// enabled.
+    #[inline]
+    pub fn new(gpadc: GPADC, ccu: &ccu::RegisterBlock) -> Self {
+        // note(unsafe): async read and write using ccu registers
+        unsafe { ccu::GPADC::reset(ccu) };
+        let regs = gpadc.as_ref();
+        unsafe {
+            regs.sample_rate.write(SampleRate::default());
+            regs.channel_enable.write(ChannelEnable::default());
+            regs.low_int_enable.write(ChannelEnable::default());
+            regs.high_int_enable.write(ChannelEnable::default());
+            regs.data_int_enable.write(ChannelEnable::default());
+            regs.control.write(
+                Control::default()
+                    .enable_auto_calibration()
+                    .set_work_mode(WorkMode::Continuous)
+                    .enable(),
+            );
+        }
+        Self { gpadc }
+    }
+    /// Set conversion rate, from about 366 Hz to 1 MHz.
+    #[inline]
+    pub fn set_sample_rate(&mut self, rate: Hertz) {
+        let divider = sample_rate_divider(rate.0);
+        unsafe {
+            self.gpadc
+                .as_ref()
+                .sample_rate
+                .modify(|val| val.set_divider(divider))
+        };
+    }
+    /// Get actual conversion rate.
+    #[inline]
+    pub fn sample_rate(&self) -> Hertz {
+        let divider = self.gpadc.as_ref().sample_rate.read().divider() as u32;
+        Hertz(HOSC_FREQUENCY.0 / (divider + 1))
+    }
+    /// Include `channel` in continuous conversion.
+    #[inline]
+    pub fn enable_channel(&mut self, channel: usize) {
+        assert!(channel < CHANNEL_COUNT, "GPADC channel out of range");
+        unsafe {
+            self.gpadc
+                .as_ref()
+                .channel_enable
+                .modify(|val| val.enable(channel))
+        };
+    }
+    /// Exclude `channel` from continuous conversion.
+    #[inline]
+    pub fn disable_channel(&mut self, channel: usize) {
+        assert!(channel < CHANNEL_COUNT, "GPADC channel out of range");
+        unsafe {
+            self.gpadc
+                .as_ref()
+                .channel_enable
+                .modify(|val| val.disable(channel))
+        };
+    }
+    /// Wait for next conversion of enabled `channel` and return its 12-bit value.
+    #[inline]
+    pub fn read(&mut self, channel: usize) -> Result<u16, Error> {
+        assert!(channel < CHANNEL_COUNT, "GPADC channel out of range");
+        let regs = self.gpadc.as_ref();
+        unsafe {
+            regs.data_int_status
+                .write(ChannelStatus::default().clear_pending(channel))
+        };
+        let deadline = Deadline::start();
+        while !regs.data_int_status.read().is_pending(channel) {
+            deadline.check().map_err(|_| Error::Timeout)?;
+            core::hint::spin_loop();
+        }
+        Ok((regs.data[channel].read() & MAX_VALUE as u32) as u16)
+    }
+    /// Set `low` and `high` thresholds of `channel`.
+    #[inline]
+    pub fn set_thresholds(&mut self, channel: usize, low: u16, high: u16) {
+        assert!(channel < CHANNEL_COUNT, "GPADC channel out of range");
+        unsafe {
+            self.gpadc.as_ref().compare[channel].write(
+                CompareData::default()
+                    .set_low_threshold(low)
+                    .set_high_threshold(high),
+            )
+        };
+    }
+    /// Get low and high thresholds of `channel`.
+    #[inline]
+    pub fn thresholds(&self, channel: usize) -> (u16, u16) {
+        let val = self.gpadc.as_ref().compare[channel].read();
+        (val.low_threshold(), val.high_threshold())
+    }
+    /// Enable interrupt of `channel` crossing `threshold`.
+    #[inline]
+    pub fn enable_threshold_interrupt(&mut self, channel: usize, threshold: Threshold) {
+        assert!(channel < CHANNEL_COUNT, "GPADC channel out of range");
+        let (enable, status) = self.threshold_registers(threshold);
+        unsafe {
+            status.write(ChannelStatus::default().clear_pending(channel));
+            enable.modify(|val| val.enable(channel));
+        }
+    }
+    /// Disable interrupt of `channel` crossing `threshold`.
+    #[inline]
+    pub fn disable_threshold_interrupt(&mut self, channel: usize, threshold: Threshold) {
+        assert!(channel < CHANNEL_COUNT, "GPADC channel out of range");
+        let (enable, _) = self.threshold_registers(threshold);
+        unsafe { enable.modify(|val| val.disable(channel)) };
+    }
+    /// Check if `channel` has crossed `threshold` since last cleared.
+    #[inline]
+    pub fn is_threshold_pending(&self, channel: usize, threshold: Threshold) -> bool {
+        let (_, status) = self.threshold_registers(threshold);
+        status.read().is_pending(channel)
+    }
+    /// Clear pending `threshold` crossing of `channel`.
+    #[inline]
+    pub fn clear_threshold_pending(&mut self, channel: usize, threshold: Threshold) {
+        let (_, status) = self.threshold_registers(threshold);
+        unsafe { status.write(ChannelStatus::default().clear_pending(channel)) };
+    }
+    /// Register threshold interrupt handler of `channel`, called from [`on_interrupt`].
+    #[inline]
+    pub fn set_handler(&mut self, channel: usize, handler: Handler) {
+        HANDLERS[channel].store(handler as *mut (), Ordering::Release);
+    }
+    /// Disable threshold interrupts of `channel` and remove its registered handler.
+    #[inline]
+    pub fn clear_handler(&mut self, channel: usize) {
+        self.disable_threshold_interrupt(channel, Threshold::Low);
+        self.disable_threshold_interrupt(channel, Threshold::High);
+        HANDLERS[channel].store(core::ptr::null_mut(), Ordering::Release);
+    }
+    /// Start feeding conversion results of enabled channels into FIFO for DMA.
+    ///
+    /// Results of several enabled channels are interleaved in channel order. DMA channel
+    /// should move 32-bit words from [`Gpadc::fifo_address`] with request port `GPADC_RX`;
+    /// [`Stream`] sets this up with a pair of buffers.
+    #[inline]
+    pub fn start_dma(&mut self) {
+        let regs = self.gpadc.as_ref();
+        let control = FifoIntControl::default().set_trigger_level(0);
+        unsafe {
+            regs.fifo_int_control.write(control.flush_fifo());
+            regs.fifo_int_status.write(
+                FifoIntStatus::default()
+                    .clear_overrun()
+                    .clear_data_pending(),
+            );
+            regs.fifo_int_control.write(control.enable_dma_request());
+        }
+    }
+    /// Stop requesting DMA transfers from FIFO.
+    #[inline]
+    pub fn stop_dma(&mut self) {
+        unsafe {
+            self.gpadc
+                .as_ref()
+                .fifo_int_control
+                .modify(|val| val.disable_dma_request())
+        };
+    }
+    /// Get address of FIFO data register as DMA transfer source.
+    #[inline]
+    pub fn fifo_address(&self) -> usize {
+        &self.gpadc.as_ref().fifo_data as *const _ as usize
+    }
+    /// Check if FIFO has overrun since last cleared, losing samples.
+    #[inline]
+    pub fn is_fifo_overrun(&self) -> bool {
+        self.gpadc.as_ref().fifo_int_status.read().is_overrun()
+    }
+    /// Clear FIFO overrun flag.
+    #[inline]
+    pub fn clear_fifo_overrun(&mut self) {
+        unsafe {
+            self.gpadc
+                .as_ref()
+                .fifo_int_status
+                .write(FifoIntStatus::default().clear_overrun())
+        };
+    }
+    /// Close GPADC, remove all handlers and release peripheral.
+    #[inline]
+    pub fn free(self, ccu: &ccu::RegisterBlock) -> GPADC {
+        let regs = self.gpadc.as_ref();
+        unsafe {
+            regs.low_int_enable.write(ChannelEnable::default());
+            regs.high_int_enable.write(ChannelEnable::default());
+            regs.fifo_int_control.write(FifoIntControl::default());
+            regs.control.modify(|val| val.disable());
+        }
+        for handler in &HANDLERS {
+            handler.store(core::ptr::null_mut(), Ordering::Release);
+        }
+        unsafe { ccu::GPADC::free(ccu) };
+        self.gpadc
+    }
+    #[inline]
+    fn threshold_registers(
+        &self,
+        threshold: Threshold,
+    ) -> (&RW<ChannelEnable>, &RW<ChannelStatus>) {
+        let regs = self.gpadc.as_ref();
+        match threshold {
+            Threshold::Low => (&regs.low_int_enable, &regs.low_int_status),
+            Threshold::High => (&regs.high_int_enable, &regs.high_int_status),
+        }
+    }
+}
+
+#[cfg(feature = "embedded-hal-02")]
+impl<GPADC: AsRef<RegisterBlock>, WORD: From<u16>, PIN>
+    embedded_hal_02::adc::OneShot<Gpadc<GPADC>, WORD, PIN> for Gpadc<GPADC>
+where
+    PIN: embedded_hal_02::adc::Channel<Gpadc<GPADC>, ID = u8>,
+{
+    type Error = Error;
+
+    /// Enable channel of `PIN` if needed and return its next conversion result.
+    #[inline]
+    fn read(&mut self, _pin: &mut PIN) -> nb::Result<WORD, Error> {
+        let channel = PIN::channel() as usize;
+        if !self
+            .gpadc
+            .as_ref()
+            .channel_enable
+            .read()
+            .is_enabled(channel)
+        {
+            self.enable_channel(channel);
+        }
+        Gpadc::read(self, channel)
+            .map(WORD::from)
+            .map_err(nb::Error::Other)
+    }
+}
+
+/// Calculate sample rate divider for `rate`, clamped to valid range.
+#[inline]
+const fn sample_rate_divider(rate: u32) -> u16 {
+    let rate = if rate > 1_000_000 {
+        1_000_000
+    } else if rate == 0 {
+        1
+    } else {
+        rate
+    };
+    let divide = HOSC_FREQUENCY.0.div_ceil(rate);
+    if divide > 0x10000 {
+        0xFFFF
+    } else {
+        (divide - 1) as u16
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::sample_rate_divider;
+
+    #[test]
+    fn function_sample_rate_divider() {
+        assert_eq!(sample_rate_divider(50_000), 479);
+        assert_eq!(sample_rate_divider(1_000), 23_999);
+        assert_eq!(sample_rate_divider(10_000_000), 23);
+        assert_eq!(sample_rate_divider(1), 0xFFFF);
+    }
+}
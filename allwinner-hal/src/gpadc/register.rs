@@ -0,0 +1,477 @@
+use volatile_register::{RO, RW};
+
+/// Number of channels in GPADC register space.
+pub const CHANNEL_COUNT: usize = 16;
+/// Number of samples GPADC FIFO holds.
+pub const FIFO_DEPTH: usize = 64;
+
+/// General purpose ADC registers.
+#[repr(C)]
+pub struct RegisterBlock {
+    /// 0x00 - GPADC Sample Rate Configure Register.
+    pub sample_rate: RW<SampleRate>,
+    /// 0x04 - GPADC Control Register.
+    pub control: RW<Control>,
+    /// 0x08 - GPADC Compare and Select Enable Register.
+    pub channel_enable: RW<ChannelEnable>,
+    /// 0x0C - GPADC FIFO Interrupt Control Register.
+    pub fifo_int_control: RW<FifoIntControl>,
+    /// 0x10 - GPADC FIFO Interrupt Status Register.
+    pub fifo_int_status: RW<FifoIntStatus>,
+    /// 0x14 - GPADC FIFO Data Register.
+    pub fifo_data: RO<u32>,
+    /// 0x18 - GPADC Calibration Data Register.
+    pub calibration_data: RW<u32>,
+    _reserved0: u32,
+    /// 0x20 - GPADC Data Low Interrupt Configure Register.
+    pub low_int_enable: RW<ChannelEnable>,
+    /// 0x24 - GPADC Data High Interrupt Configure Register.
+    pub high_int_enable: RW<ChannelEnable>,
+    /// 0x28 - GPADC Data Interrupt Configure Register.
+    pub data_int_enable: RW<ChannelEnable>,
+    _reserved1: u32,
+    /// 0x30 - GPADC Data Low Interrupt Status Register.
+    pub low_int_status: RW<ChannelStatus>,
+    /// 0x34 - GPADC Data High Interrupt Status Register.
+    pub high_int_status: RW<ChannelStatus>,
+    /// 0x38 - GPADC Data Interrupt Status Register.
+    pub data_int_status: RW<ChannelStatus>,
+    _reserved2: u32,
+    /// 0x40 ..= 0x7C - GPADC Channel Compare Data Registers.
+    pub compare: [RW<CompareData>; CHANNEL_COUNT],
+    /// 0x80 ..= 0xBC - GPADC Channel Data Registers.
+    pub data: [RO<u32>; CHANNEL_COUNT],
+}
+
+/// GPADC sample rate configure register.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct SampleRate(u32);
+
+impl SampleRate {
+    const FS_DIV: u32 = 0xFFFF << 16;
+    const TACQ: u32 = 0xFFFF;
+
+    /// Get sample rate divider; sample rate is 24 MHz divided by `divider + 1`.
+    #[inline]
+    pub const fn divider(self) -> u16 {
+        ((self.0 & Self::FS_DIV) >> 16) as u16
+    }
+    /// Set sample rate divider; sample rate is 24 MHz divided by `divider + 1`.
+    #[inline]
+    pub const fn set_divider(self, val: u16) -> Self {
+        Self((self.0 & !Self::FS_DIV) | ((val as u32) << 16))
+    }
+    /// Get acquire time in 24-MHz clock cycles, minus one.
+    #[inline]
+    pub const fn acquire_time(self) -> u16 {
+        (self.0 & Self::TACQ) as u16
+    }
+    /// Set acquire time in 24-MHz clock cycles, minus one.
+    #[inline]
+    pub const fn set_acquire_time(self, val: u16) -> Self {
+        Self((self.0 & !Self::TACQ) | val as u32)
+    }
+}
+
+impl Default for SampleRate {
+    #[inline]
+    fn default() -> Self {
+        Self(0x01DF_002F)
+    }
+}
+
+/// GPADC conversion mode.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum WorkMode {
+    /// Convert each enabled channel once.
+    Single,
+    /// Convert enabled channels in one cycle.
+    SingleCycle,
+    /// Convert enabled channels continuously.
+    Continuous,
+    /// Convert enabled channels in bursts.
+    Burst,
+}
+
+/// GPADC control register.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct Control(u32);
+
+impl Control {
+    const ADC_AUTOCALI_EN: u32 = 1 << 23;
+    const WORK_MODE: u32 = 0x3 << 18;
+    const ADC_CALI_EN: u32 = 1 << 17;
+    const ADC_EN: u32 = 1 << 16;
+
+    /// If calibration runs automatically on power up.
+    #[inline]
+    pub const fn is_auto_calibration_enabled(self) -> bool {
+        self.0 & Self::ADC_AUTOCALI_EN != 0
+    }
+    /// Enable automatic calibration on power up.
+    #[inline]
+    pub const fn enable_auto_calibration(self) -> Self {
+        Self(self.0 | Self::ADC_AUTOCALI_EN)
+    }
+    /// Disable automatic calibration on power up.
+    #[inline]
+    pub const fn disable_auto_calibration(self) -> Self {
+        Self(self.0 & !Self::ADC_AUTOCALI_EN)
+    }
+    /// Get conversion mode.
+    #[inline]
+    pub const fn work_mode(self) -> WorkMode {
+        match (self.0 & Self::WORK_MODE) >> 18 {
+            0 => WorkMode::Single,
+            1 => WorkMode::SingleCycle,
+            2 => WorkMode::Continuous,
+            _ => WorkMode::Burst,
+        }
+    }
+    /// Set conversion mode.
+    #[inline]
+    pub const fn set_work_mode(self, val: WorkMode) -> Self {
+        Self((self.0 & !Self::WORK_MODE) | ((val as u32) << 18))
+    }
+    /// Start calibration on write.
+    #[inline]
+    pub const fn start_calibration(self) -> Self {
+        Self(self.0 | Self::ADC_CALI_EN)
+    }
+    /// Check if calibration has finished.
+    #[inline]
+    pub const fn is_calibration_finished(self) -> bool {
+        self.0 & Self::ADC_CALI_EN == 0
+    }
+    /// If ADC is enabled.
+    #[inline]
+    pub const fn is_enabled(self) -> bool {
+        self.0 & Self::ADC_EN != 0
+    }
+    /// Enable ADC to start conversion.
+    #[inline]
+    pub const fn enable(self) -> Self {
+        Self(self.0 | Self::ADC_EN)
+    }
+    /// Disable ADC.
+    #[inline]
+    pub const fn disable(self) -> Self {
+        Self(self.0 & !Self::ADC_EN)
+    }
+}
+
+impl Default for Control {
+    #[inline]
+    fn default() -> Self {
+        Self(0x0000_0000)
+    }
+}
+
+/// GPADC FIFO interrupt control register.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct FifoIntControl(u32);
+
+impl FifoIntControl {
+    const FIFO_DATA_DRQ_EN: u32 = 1 << 18;
+    const FIFO_OVER_RUN_IRQ_EN: u32 = 1 << 17;
+    const FIFO_DATA_IRQ_EN: u32 = 1 << 16;
+    const FIFO_TRIG_LEVEL: u32 = 0x3F << 8;
+    const FIFO_FLUSH: u32 = 1 << 4;
+
+    /// If FIFO data requests DMA transfer.
+    #[inline]
+    pub const fn is_dma_request_enabled(self) -> bool {
+        self.0 & Self::FIFO_DATA_DRQ_EN != 0
+    }
+    /// Enable DMA request on FIFO data.
+    #[inline]
+    pub const fn enable_dma_request(self) -> Self {
+        Self(self.0 | Self::FIFO_DATA_DRQ_EN)
+    }
+    /// Disable DMA request on FIFO data.
+    #[inline]
+    pub const fn disable_dma_request(self) -> Self {
+        Self(self.0 & !Self::FIFO_DATA_DRQ_EN)
+    }
+    /// If FIFO overrun interrupt is enabled.
+    #[inline]
+    pub const fn is_overrun_interrupt_enabled(self) -> bool {
+        self.0 & Self::FIFO_OVER_RUN_IRQ_EN != 0
+    }
+    /// Enable FIFO overrun interrupt.
+    #[inline]
+    pub const fn enable_overrun_interrupt(self) -> Self {
+        Self(self.0 | Self::FIFO_OVER_RUN_IRQ_EN)
+    }
+    /// Disable FIFO overrun interrupt.
+    #[inline]
+    pub const fn disable_overrun_interrupt(self) -> Self {
+        Self(self.0 & !Self::FIFO_OVER_RUN_IRQ_EN)
+    }
+    /// If FIFO data interrupt is enabled.
+    #[inline]
+    pub const fn is_data_interrupt_enabled(self) -> bool {
+        self.0 & Self::FIFO_DATA_IRQ_EN != 0
+    }
+    /// Enable FIFO data interrupt.
+    #[inline]
+    pub const fn enable_data_interrupt(self) -> Self {
+        Self(self.0 | Self::FIFO_DATA_IRQ_EN)
+    }
+    /// Disable FIFO data interrupt.
+    #[inline]
+    pub const fn disable_data_interrupt(self) -> Self {
+        Self(self.0 & !Self::FIFO_DATA_IRQ_EN)
+    }
+    /// Get FIFO trigger level; data request raises above this number of samples.
+    #[inline]
+    pub const fn trigger_level(self) -> u8 {
+        ((self.0 & Self::FIFO_TRIG_LEVEL) >> 8) as u8
+    }
+    /// Set FIFO trigger level; data request raises above this number of samples.
+    #[inline]
+    pub const fn set_trigger_level(self, val: u8) -> Self {
+        Self((self.0 & !Self::FIFO_TRIG_LEVEL) | ((val as u32 & 0x3F) << 8))
+    }
+    /// Flush FIFO on write.
+    #[inline]
+    pub const fn flush_fifo(self) -> Self {
+        Self(self.0 | Self::FIFO_FLUSH)
+    }
+}
+
+impl Default for FifoIntControl {
+    #[inline]
+    fn default() -> Self {
+        Self(0x0000_1F00)
+    }
+}
+
+/// GPADC FIFO interrupt status register.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct FifoIntStatus(u32);
+
+impl FifoIntStatus {
+    const FIFO_OVER_PEND: u32 = 1 << 17;
+    const FIFO_DATA_PEND: u32 = 1 << 16;
+    const RXA_CNT: u32 = 0x3F << 8;
+
+    /// Check if FIFO has overrun since last cleared.
+    #[inline]
+    pub const fn is_overrun(self) -> bool {
+        self.0 & Self::FIFO_OVER_PEND != 0
+    }
+    /// Clear FIFO overrun flag on write.
+    #[inline]
+    pub const fn clear_overrun(self) -> Self {
+        Self(self.0 | Self::FIFO_OVER_PEND)
+    }
+    /// Check if FIFO data interrupt is pending.
+    #[inline]
+    pub const fn is_data_pending(self) -> bool {
+        self.0 & Self::FIFO_DATA_PEND != 0
+    }
+    /// Clear pending FIFO data interrupt on write.
+    #[inline]
+    pub const fn clear_data_pending(self) -> Self {
+        Self(self.0 | Self::FIFO_DATA_PEND)
+    }
+    /// Get number of samples in FIFO.
+    #[inline]
+    pub const fn fifo_count(self) -> u8 {
+        ((self.0 & Self::RXA_CNT) >> 8) as u8
+    }
+}
+
+impl Default for FifoIntStatus {
+    #[inline]
+    fn default() -> Self {
+        Self(0x0000_0000)
+    }
+}
+
+/// GPADC per-channel enable register.
+///
+/// Used for channel selection and for each kind of channel interrupt.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct ChannelEnable(u32);
+
+impl ChannelEnable {
+    /// If `channel` is enabled.
+    #[inline]
+    pub const fn is_enabled(self, channel: usize) -> bool {
+        self.0 & (1 << channel) != 0
+    }
+    /// Enable `channel`.
+    #[inline]
+    pub const fn enable(self, channel: usize) -> Self {
+        Self(self.0 | (1 << channel))
+    }
+    /// Disable `channel`.
+    #[inline]
+    pub const fn disable(self, channel: usize) -> Self {
+        Self(self.0 & !(1 << channel))
+    }
+}
+
+impl Default for ChannelEnable {
+    #[inline]
+    fn default() -> Self {
+        Self(0x0000_0000)
+    }
+}
+
+/// GPADC per-channel interrupt status register.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct ChannelStatus(u32);
+
+impl ChannelStatus {
+    /// Check if interrupt of `channel` is pending.
+    #[inline]
+    pub const fn is_pending(self, channel: usize) -> bool {
+        self.0 & (1 << channel) != 0
+    }
+    /// Clear pending interrupt of `channel` on write.
+    #[inline]
+    pub const fn clear_pending(self, channel: usize) -> Self {
+        Self(self.0 | (1 << channel))
+    }
+}
+
+impl Default for ChannelStatus {
+    #[inline]
+    fn default() -> Self {
+        Self(0x0000_0000)
+    }
+}
+
+/// GPADC channel compare data register.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct CompareData(u32);
+
+impl CompareData {
+    const CMP_HIG_DATA: u32 = 0xFFF << 16;
+    const CMP_LOW_DATA: u32 = 0xFFF;
+
+    /// Get high threshold; data above it raises high interrupt.
+    #[inline]
+    pub const fn high_threshold(self) -> u16 {
+        ((self.0 & Self::CMP_HIG_DATA) >> 16) as u16
+    }
+    /// Set high threshold; data above it raises high interrupt.
+    #[inline]
+    pub const fn set_high_threshold(self, val: u16) -> Self {
+        Self((self.0 & !Self::CMP_HIG_DATA) | ((val as u32 & 0xFFF) << 16))
+    }
+    /// Get low threshold; data below it raises low interrupt.
+    #[inline]
+    pub const fn low_threshold(self) -> u16 {
+        (self.0 & Self::CMP_LOW_DATA) as u16
+    }
+    /// Set low threshold; data below it raises low interrupt.
+    #[inline]
+    pub const fn set_low_threshold(self, val: u16) -> Self {
+        Self((self.0 & !Self::CMP_LOW_DATA) | (val as u32 & 0xFFF))
+    }
+}
+
+impl Default for CompareData {
+    #[inline]
+    fn default() -> Self {
+        Self(0x0BFF_0400)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        ChannelEnable, ChannelStatus, CompareData, Control, FifoIntControl, FifoIntStatus,
+        RegisterBlock, SampleRate, WorkMode,
+    };
+    use core::mem::offset_of;
+
+    #[test]
+    fn offset_gpadc() {
+        assert_eq!(offset_of!(RegisterBlock, sample_rate), 0x00);
+        assert_eq!(offset_of!(RegisterBlock, control), 0x04);
+        assert_eq!(offset_of!(RegisterBlock, channel_enable), 0x08);
+        assert_eq!(offset_of!(RegisterBlock, fifo_int_control), 0x0C);
+        assert_eq!(offset_of!(RegisterBlock, fifo_data), 0x14);
+        assert_eq!(offset_of!(RegisterBlock, calibration_data), 0x18);
+        assert_eq!(offset_of!(RegisterBlock, low_int_enable), 0x20);
+        assert_eq!(offset_of!(RegisterBlock, data_int_enable), 0x28);
+        assert_eq!(offset_of!(RegisterBlock, low_int_status), 0x30);
+        assert_eq!(offset_of!(RegisterBlock, data_int_status), 0x38);
+        assert_eq!(offset_of!(RegisterBlock, compare), 0x40);
+        assert_eq!(offset_of!(RegisterBlock, data), 0x80);
+    }
+
+    #[test]
+    fn struct_sample_rate_control_functions() {
+        let val = SampleRate::default();
+        assert_eq!(val.divider(), 0x1DF);
+        assert_eq!(val.acquire_time(), 0x2F);
+        let val = val.set_divider(23_999).set_acquire_time(0x10);
+        assert_eq!(val.0, 0x5DBF_0010);
+
+        let mut val = Control::default();
+        val = val
+            .enable_auto_calibration()
+            .set_work_mode(WorkMode::Continuous)
+            .enable();
+        assert_eq!(val.work_mode(), WorkMode::Continuous);
+        assert!(val.is_enabled());
+        assert_eq!(val.0, 0x0089_0000);
+        val = val.start_calibration();
+        assert!(!val.is_calibration_finished());
+        assert_eq!(val.0, 0x008B_0000);
+    }
+
+    #[test]
+    fn struct_fifo_functions() {
+        let val = FifoIntControl::default();
+        assert_eq!(val.trigger_level(), 0x1F);
+        let val = val
+            .set_trigger_level(0)
+            .enable_dma_request()
+            .enable_overrun_interrupt()
+            .flush_fifo();
+        assert!(val.is_dma_request_enabled());
+        assert!(val.is_overrun_interrupt_enabled());
+        assert!(!val.is_data_interrupt_enabled());
+        assert_eq!(val.0, 0x0006_0010);
+        assert_eq!(val.disable_dma_request().0, 0x0002_0010);
+
+        let val = FifoIntStatus(0x0002_2A00);
+        assert!(val.is_overrun());
+        assert!(!val.is_data_pending());
+        assert_eq!(val.fifo_count(), 0x2A);
+        assert_eq!(FifoIntStatus::default().clear_overrun().0, 0x0002_0000);
+        assert_eq!(FifoIntStatus::default().clear_data_pending().0, 0x0001_0000);
+    }
+
+    #[test]
+    fn struct_channel_functions() {
+        let val = ChannelEnable::default().enable(0).enable(3);
+        assert!(val.is_enabled(3));
+        assert_eq!(val.0, 0x0000_0009);
+        assert_eq!(val.disable(0).0, 0x0000_0008);
+
+        let val = ChannelStatus::default().clear_pending(1);
+        assert!(val.is_pending(1));
+        assert_eq!(val.0, 0x0000_0002);
+
+        let val = CompareData::default();
+        assert_eq!(val.high_threshold(), 0xBFF);
+        assert_eq!(val.low_threshold(), 0x400);
+        let val = val.set_high_threshold(0xFFF).set_low_threshold(0x123);
+        assert_eq!(val.0, 0x0FFF_0123);
+    }
+}
@@ -0,0 +1,136 @@
+use super::{Gpadc, RegisterBlock};
+use crate::dmac::{
+    AddressMode, BurstLength, Channel, DataWidth, Descriptor, DmaConfig, StreamParts, drq,
+};
+use crate::time::TimeoutError;
+use core::marker::PhantomData;
+
+/// GPADC samples streamed by a DMA channel into two buffers of `L` samples each.
+///
+/// DMA fills both buffers in turn, endlessly; [`Stream::ready`] hands out a buffer once
+/// DMA has moved on to the other one. Each sample is a FIFO word whose low 12 bits hold
+/// the conversion result. A buffer should be consumed before DMA comes back to it, that is
+/// within `L` sample periods, otherwise its samples are overwritten.
+pub struct Stream<'a, const N: usize, const L: usize> {
+    channel: Channel<'a, N>,
+    buffers: *const [[u32; L]; 2],
+    descriptors: &'a mut [Descriptor; 2],
+    next: usize,
+    _buffers: PhantomData<&'a mut [[u32; L]; 2]>,
+}
+
+impl<'a, const N: usize, const L: usize> Stream<'a, N, L> {
+    /// Start streaming conversion results of enabled `gpadc` channels into `buffers`
+    /// on DMA `channel`, using `descriptors` as a two-descriptor ring.
+    ///
+    /// Sample rate is set on `gpadc` beforehand and may be changed while streaming.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `L` is zero.
+    #[inline]
+    pub fn start<GPADC: AsRef<RegisterBlock>>(
+        gpadc: &mut Gpadc<GPADC>,
+        mut channel: Channel<'a, N>,
+        buffers: &'a mut [[u32; L]; 2],
+        descriptors: &'a mut [Descriptor; 2],
+    ) -> Self {
+        assert!(L != 0, "stream buffer should hold at least one sample");
+        let config = DmaConfig {
+            source_drq: drq::GPADC_RX,
+            source_address_mode: AddressMode::Io,
+            source_burst: BurstLength::Single,
+            source_width: DataWidth::Bits32,
+            destination_drq: drq::DRAM,
+            destination_address_mode: AddressMode::Linear,
+            destination_burst: BurstLength::Single,
+            destination_width: DataWidth::Bits32,
+            ..DmaConfig::default()
+        };
+        let source = gpadc.fifo_address() as u32;
+        let byte_count = (L * size_of::<u32>()) as u32;
+        for (descriptor, buffer) in descriptors.iter_mut().zip(buffers.iter()) {
+            *descriptor =
+                Descriptor::new(&config, source, buffer.as_ptr() as usize as u32, byte_count);
+        }
+        let (first, second) = descriptors.split_at_mut(1);
+        first[0].set_link(&second[0]);
+        second[0].set_link(&first[0]);
+        gpadc.start_dma();
+        // note(unsafe): buffers and descriptors are borrowed by `Stream` until stopped
+        unsafe { channel.start(&descriptors[0]) };
+        Self {
+            channel,
+            buffers,
+            descriptors,
+            next: 0,
+            _buffers: PhantomData,
+        }
+    }
+    /// Get next buffer filled by DMA, if any.
+    #[inline]
+    pub fn ready(&mut self) -> Option<&[u32; L]> {
+        let base = self.buffers as usize as u32;
+        let writing = writing_buffer(
+            base,
+            (L * size_of::<u32>()) as u32,
+            self.channel.current_destination(),
+        );
+        if writing == self.next {
+            return None;
+        }
+        let index = self.next;
+        self.next ^= 1;
+        // note(unsafe): DMA is writing the other buffer
+        Some(unsafe { &(*self.buffers)[index] })
+    }
+    /// Get DMA channel, for example to enable `PackageEnd` interrupt on each full buffer.
+    #[inline]
+    pub fn channel(&mut self) -> &mut Channel<'a, N> {
+        &mut self.channel
+    }
+    /// Stop streaming and release DMA channel, buffers and descriptors.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`TimeoutError`] if DMA channel doesn't stop before crate-wide blocking
+    /// timeout; buffers are then not released.
+    #[inline]
+    pub fn stop<GPADC: AsRef<RegisterBlock>>(
+        mut self,
+        gpadc: &mut Gpadc<GPADC>,
+    ) -> Result<StreamParts<'a, N, [u32; L]>, TimeoutError> {
+        self.channel.abort()?;
+        gpadc.stop_dma();
+        // note(unsafe): DMA has stopped, buffers are exclusively borrowed again
+        let buffers = unsafe { &mut *(self.buffers as *mut [[u32; L]; 2]) };
+        Ok((self.channel, buffers, self.descriptors))
+    }
+}
+
+/// Get index of buffer DMA is writing from its `destination` address, with buffers of
+/// `buffer_bytes` each placed back to back from `base`.
+///
+/// Destination right at the end of first buffer counts as second buffer, as first buffer
+/// is full by then.
+#[inline]
+const fn writing_buffer(base: u32, buffer_bytes: u32, destination: u32) -> usize {
+    if destination >= base + buffer_bytes {
+        1
+    } else {
+        0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::writing_buffer;
+
+    #[test]
+    fn function_writing_buffer() {
+        assert_eq!(writing_buffer(0x4000_0000, 0x100, 0x4000_0000), 0);
+        assert_eq!(writing_buffer(0x4000_0000, 0x100, 0x4000_00FC), 0);
+        assert_eq!(writing_buffer(0x4000_0000, 0x100, 0x4000_0100), 1);
+        assert_eq!(writing_buffer(0x4000_0000, 0x100, 0x4000_0200), 1);
+    }
+}
@@ -0,0 +1,455 @@
+//! HDMI transmitter.
+//!
+//! The transmitter encodes pixels of TCON TV 0 into TMDS signals for an HDMI or DVI sink.
+//! [`Hdmi::read_edid`] reads capabilities of sink over DDC, from which [`choose_best_mode`]
+//! picks a video [`Mode`]; [`Hdmi::set_mode`] then programs frame composer, PHY and, for
+//! HDMI sinks, the AVI infoframe:
+//!
+//! ```ignore
+//! let mut hdmi = Hdmi::new(p.hdmi, p.hdmi_phy, &p.dpss_top, &p.ccu);
+//! let edid = hdmi.read_edid()?;
+//! let mode = hdmi::choose_best_mode(&edid);
+//! let interface = if edid.is_hdmi() { Interface::Hdmi } else { Interface::Dvi };
+//! hdmi.set_mode(&mode, interface)?;
+//! ```
+//!
+//! Timing controller feeding the transmitter should be programmed with `mode.timing` and
+//! sync polarities of the mode. Pixels are sent as 8-bit RGB.
+
+mod edid;
+mod mode;
+mod register;
+pub use edid::*;
+pub use mode::*;
+pub use register::*;
+
+use crate::{
+    ccu::{self, ClockGate},
+    tcon::{HdmiSource, Polarity, TopRegisterBlock},
+    time::Deadline,
+};
+use embedded_time::rate::Hertz;
+use volatile_register::RW;
+
+/// I2C address of PHY on PHY I2C master.
+const PHY_I2C_ADDRESS: u8 = 0x69;
+/// I2C address of EDID on DDC.
+const DDC_EDID_ADDRESS: u8 = 0x50;
+
+/// I2C master read operation.
+const I2C_READ: u8 = 0x01;
+/// I2C master write operation.
+const I2C_WRITE: u8 = 0x10;
+/// I2C master raises done flag.
+const I2C_DONE_POLARITY: u8 = 0x08;
+/// I2C master raises error flag on missing acknowledge and arbitration loss.
+const I2C_ERROR_POLARITY: u8 = 0x88;
+/// SCL high and low counts of about 100 kHz from 24-MHz clock.
+const DDC_SCL_COUNT: u8 = 120;
+
+/// Mute both global and wakeup interrupt lines.
+const IH_MUTE_ALL: u8 = 0x03;
+/// Disable clocks of HDCP, CEC, color space converter, audio and pixel repetition, keeping
+/// pixel and TMDS clocks running.
+const MC_CLOCK_DISABLE: u8 = 0x7C;
+/// TMDS software reset request, active low.
+const MC_TMDS_RESET: u8 = 1 << 1;
+
+/// PHY settings of TMDS clocks up to the first value, from vendor BSP.
+///
+/// Values are written to PHY registers in [`PHY_REGISTERS`].
+const PHY_SETTINGS: [(u32, [u16; 6]); 4] = [
+    (27_000_000, [0x01E0, 0x0000, 0x08DA, 0x0007, 0x0318, 0x8009]),
+    (74_250_000, [0x0540, 0x0005, 0x0000, 0x0007, 0x02B5, 0x8009]),
+    (
+        148_500_000,
+        [0x04A0, 0x000A, 0x0000, 0x0002, 0x0021, 0x8029],
+    ),
+    (u32::MAX, [0x0000, 0x000F, 0x0000, 0x0002, 0x0000, 0x801A]),
+];
+/// PHY PLL, current, clock symbol, voltage level, termination and control registers.
+const PHY_REGISTERS: [u8; 6] = [0x06, 0x15, 0x10, 0x19, 0x0E, 0x09];
+/// PHY registers cleared after settings.
+const PHY_CLEARED_REGISTERS: [u8; 3] = [0x1E, 0x13, 0x17];
+
+/// HDMI transmitter error.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Error {
+    /// Sink or PHY did not acknowledge an I2C transfer, e.g. for sink being unplugged.
+    Nack,
+    /// EDID header or checksum is invalid.
+    InvalidEdid,
+    /// Mode exceeds TMDS clock or timing limits of transmitter.
+    UnsupportedMode,
+    /// I2C transfer did not finish, or PHY did not lock, before crate-wide blocking timeout.
+    Timeout,
+}
+
+/// Signalling of sink.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Interface {
+    /// HDMI sink, receiving infoframes between video data.
+    Hdmi,
+    /// DVI sink, receiving video data only.
+    Dvi,
+}
+
+/// RGB quantization range signalled in AVI infoframe.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Quantization {
+    /// Range implied by video mode, limited for CEA-861 modes other than 640x480.
+    Default = 0,
+    /// Limited range, 16 to 235.
+    Limited = 1,
+    /// Full range, 0 to 255.
+    Full = 2,
+}
+
+/// Auxiliary video information (AVI) infoframe.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct AviInfoFrame {
+    /// CEA-861 video identification code, or 0 if mode is not defined by CEA-861.
+    pub vic: u8,
+    /// Picture aspect ratio.
+    pub aspect: AspectRatio,
+    /// RGB quantization range.
+    pub quantization: Quantization,
+    /// Content is from a computer rather than a video source, asking sink not to process it.
+    pub it_content: bool,
+}
+
+impl AviInfoFrame {
+    /// Infoframe describing `mode` with default quantization range.
+    #[inline]
+    pub const fn from_mode(mode: &Mode) -> Self {
+        Self {
+            vic: mode.vic,
+            aspect: mode.aspect,
+            quantization: Quantization::Default,
+            it_content: false,
+        }
+    }
+    /// Frame composer values of AVI data bytes 1 to 3 and 5.
+    ///
+    /// Data byte 1 signals RGB without scan information, with active format equal to
+    /// picture aspect ratio.
+    #[inline]
+    const fn registers(&self) -> [u8; 4] {
+        const ACTIVE_FORMAT_PRESENT: u8 = 1 << 6;
+        const ACTIVE_FORMAT_SAME_AS_PICTURE: u8 = 0x8;
+        const IT_CONTENT: u8 = 1 << 7;
+        [
+            ACTIVE_FORMAT_PRESENT,
+            ((self.aspect as u8) << 4) | ACTIVE_FORMAT_SAME_AS_PICTURE,
+            ((self.quantization as u8) << 2) | if self.it_content { IT_CONTENT } else { 0 },
+            0,
+        ]
+    }
+}
+
+/// Managed HDMI transmitter structure with peripherals.
+pub struct Hdmi<HDMI, PHY> {
+    hdmi: HDMI,
+    phy: PHY,
+    mode: Option<Mode>,
+}
+
+impl<HDMI: AsRef<RegisterBlock>, PHY: AsRef<PhyRegisterBlock>> Hdmi<HDMI, PHY> {
+    /// Create an HDMI transmitter fed by TCON TV 0, with PHY powered down.
+    #[inline]
+    pub fn new(
+        hdmi: HDMI,
+        phy: PHY,
+        tcon_top: &TopRegisterBlock,
+        ccu: &ccu::RegisterBlock,
+    ) -> Self {
+        // note(unsafe): async read and write using ccu registers
+        unsafe {
+            ccu.hdmi_24m_clock.modify(|val| val.unmask_clock());
+            ccu::HDMI::reset(ccu);
+            tcon_top
+                .gate_source
+                .modify(|val| val.set_hdmi_source(HdmiSource::Tv0).enable_tv_clock::<0>());
+            phy.as_ref()
+                .debug_control
+                .modify(|val| val.enable_pixel_lock().set_i2c_address(PHY_I2C_ADDRESS));
+        }
+        let regs = hdmi.as_ref();
+        unsafe {
+            regs.ih_mute.write(IH_MUTE_ALL);
+            regs.phy_config
+                .modify(|val| val.disable_transmitter().power_down().enable_hpd_sense());
+            regs.ddc.soft_reset.write(0);
+            regs.ddc.divider.write(0);
+            regs.ddc.ss_scl_high[1].write(DDC_SCL_COUNT);
+            regs.ddc.ss_scl_low[1].write(DDC_SCL_COUNT);
+            regs.ddc.interrupt.write(I2C_DONE_POLARITY);
+            regs.ddc.control_interrupt.write(I2C_ERROR_POLARITY);
+            regs.phy_i2c.interrupt.write(I2C_DONE_POLARITY);
+            regs.phy_i2c.control_interrupt.write(I2C_ERROR_POLARITY);
+            regs.ih_ddc_status.write(I2cStatus::default().clear());
+            regs.ih_phy_i2c_status.write(I2cStatus::default().clear());
+        }
+        Self {
+            hdmi,
+            phy,
+            mode: None,
+        }
+    }
+    /// Check if a sink is plugged in, by its hot plug detect line.
+    #[inline]
+    pub fn is_connected(&self) -> bool {
+        self.hdmi.as_ref().phy_status.read().is_plugged()
+    }
+    /// Read EDID of sink over DDC, including its first extension block if any.
+    #[inline]
+    pub fn read_edid(&mut self) -> Result<Edid, Error> {
+        let mut base = [0; EDID_BLOCK_LEN];
+        self.read_ddc(0, &mut base)?;
+        let extension = if base[126] != 0 {
+            let mut block = [0; EDID_BLOCK_LEN];
+            self.read_ddc(EDID_BLOCK_LEN as u8, &mut block)?;
+            Some(block)
+        } else {
+            None
+        };
+        Edid::new(base, extension)
+    }
+    /// Send `mode` to a sink of `interface`, returning once PHY locks.
+    ///
+    /// For HDMI sinks an AVI infoframe describing `mode` is sent; it can be changed
+    /// afterwards by [`Hdmi::set_avi_infoframe`].
+    #[inline]
+    pub fn set_mode(&mut self, mode: &Mode, interface: Interface) -> Result<(), Error> {
+        if !mode.is_supported() {
+            return Err(Error::UnsupportedMode);
+        }
+        let t = &mode.timing;
+        let mut video_config = VideoConfig::default()
+            .set_vsync_polarity(mode.vsync)
+            .set_hsync_polarity(mode.hsync)
+            .set_data_enable_polarity(Polarity::ActiveHigh);
+        video_config = match interface {
+            Interface::Hdmi => video_config.enable_hdmi_mode(),
+            Interface::Dvi => video_config.enable_dvi_mode(),
+        };
+        let regs = self.hdmi.as_ref();
+        unsafe {
+            let fc = &regs.fc;
+            fc.video_config.write(video_config);
+            write_u16(&fc.h_active, t.hactive);
+            write_u16(&fc.h_blank, t.htotal() - t.hactive);
+            write_u16(&fc.v_active, t.vactive);
+            fc.v_blank.write((t.vtotal() - t.vactive) as u8);
+            write_u16(&fc.hsync_delay, t.hfront_porch);
+            write_u16(&fc.hsync_width, t.hsync_len);
+            fc.vsync_delay.write(t.vfront_porch as u8);
+            fc.vsync_width.write(t.vsync_len as u8);
+        }
+        self.configure_phy(t.pixel_clock)?;
+        unsafe {
+            let fc = &regs.fc;
+            fc.control_duration.write(12);
+            fc.ex_control_duration.write(32);
+            fc.ex_control_space.write(1);
+            for (preamble, val) in fc.preamble.iter().zip([0x0B, 0x16, 0x21]) {
+                preamble.write(val);
+            }
+            regs.mc.clock_disable.write(MC_CLOCK_DISABLE);
+            // bypass color space converter
+            regs.mc.flow_control.write(0);
+        }
+        if interface == Interface::Hdmi {
+            self.set_avi_infoframe(&AviInfoFrame::from_mode(mode));
+        }
+        let regs = self.hdmi.as_ref();
+        unsafe {
+            // video packetizer passes 8-bit pixels through
+            regs.vp_color_depth.write(0x40);
+            regs.vp_stuffing.write(0x27);
+            regs.vp_remap.write(0);
+            regs.vp_config.write(0x46);
+            // video sampler takes 8-bit RGB 4:4:4 with zero stuffing
+            regs.tx_input.write(0x01);
+            regs.tx_stuffing.write(0x07);
+            for data in &regs.tx_stuffing_data {
+                data.write(0);
+            }
+            regs.fc.pixel_repetition.write(0x10);
+            // reset TMDS encoder to clear FIFO overflow, then latch configuration again
+            regs.mc.soft_reset.write(!MC_TMDS_RESET);
+            regs.fc.video_config.write(video_config);
+        }
+        self.mode = Some(*mode);
+        Ok(())
+    }
+    /// Set AVI infoframe sent once per frame to HDMI sinks.
+    #[inline]
+    pub fn set_avi_infoframe(&mut self, frame: &AviInfoFrame) {
+        let fc = &self.hdmi.as_ref().fc;
+        let [byte1, byte2, byte3, byte5] = frame.registers();
+        unsafe {
+            fc.avi_config[0].write(byte1);
+            fc.avi_config[1].write(byte2);
+            fc.avi_config[2].write(byte3);
+            fc.avi_config3.write(byte5);
+            fc.avi_vic.write(frame.vic & 0x7F);
+            for bar in &fc.avi_bar {
+                bar.write(0);
+            }
+        }
+    }
+    /// Get mode being sent, or `None` if transmitter is disabled.
+    #[inline]
+    pub fn mode(&self) -> Option<Mode> {
+        self.mode
+    }
+    /// Stop sending and power down PHY.
+    #[inline]
+    pub fn disable(&mut self) {
+        unsafe {
+            self.hdmi
+                .as_ref()
+                .phy_config
+                .modify(|val| val.disable_transmitter().power_down())
+        };
+        self.mode = None;
+    }
+    /// Close transmitter and release peripherals.
+    #[inline]
+    pub fn free(mut self, tcon_top: &TopRegisterBlock, ccu: &ccu::RegisterBlock) -> (HDMI, PHY) {
+        self.disable();
+        unsafe {
+            tcon_top.gate_source.modify(|val| {
+                val.set_hdmi_source(HdmiSource::None)
+                    .disable_tv_clock::<0>()
+            });
+        }
+        // note(unsafe): async read and write using ccu registers
+        unsafe {
+            ccu::HDMI::free(ccu);
+            ccu.hdmi_24m_clock.modify(|val| val.mask_clock());
+        }
+        (self.hdmi, self.phy)
+    }
+    /// Program PHY for TMDS clock of `pixel_clock` and wait for it to lock.
+    #[inline]
+    fn configure_phy(&self, pixel_clock: Hertz) -> Result<(), Error> {
+        let regs = self.hdmi.as_ref();
+        unsafe {
+            self.phy
+                .as_ref()
+                .rext_control
+                .modify(|val| val.enable_external_resistor());
+            regs.phy_config
+                .modify(|val| val.disable_transmitter().power_down());
+            regs.mc.phy_reset.write(1);
+            regs.mc.phy_reset.write(0);
+            regs.phy_config.modify(|val| val.power_up());
+            regs.phy_i2c.slave.write(PHY_I2C_ADDRESS);
+        }
+        let (_, settings) = PHY_SETTINGS
+            .into_iter()
+            .find(|&(max, _)| pixel_clock.0 <= max)
+            .unwrap_or(PHY_SETTINGS[PHY_SETTINGS.len() - 1]);
+        for (address, val) in PHY_REGISTERS.into_iter().zip(settings) {
+            self.write_phy(address, val)?;
+        }
+        for address in PHY_CLEARED_REGISTERS {
+            self.write_phy(address, 0)?;
+        }
+        unsafe { regs.phy_config.modify(|val| val.enable_transmitter()) };
+        let deadline = Deadline::start();
+        while !regs.phy_status.read().is_locked() {
+            deadline.check().map_err(|_| Error::Timeout)?;
+        }
+        Ok(())
+    }
+    /// Write `val` to PHY register at `address`.
+    #[inline]
+    fn write_phy(&self, address: u8, val: u16) -> Result<(), Error> {
+        let regs = self.hdmi.as_ref();
+        let [high, low] = val.to_be_bytes();
+        unsafe {
+            regs.ih_phy_i2c_status.write(I2cStatus::default().clear());
+            regs.phy_i2c.address.write(address);
+            regs.phy_i2c.data_out[0].write(high);
+            regs.phy_i2c.data_out[1].write(low);
+            regs.phy_i2c.operation.write(I2C_WRITE);
+        }
+        wait_i2c(&regs.ih_phy_i2c_status)
+    }
+    /// Read EDID bytes from `offset` into `buf`.
+    #[inline]
+    fn read_ddc(&self, offset: u8, buf: &mut [u8]) -> Result<(), Error> {
+        let regs = self.hdmi.as_ref();
+        for (i, byte) in buf.iter_mut().enumerate() {
+            unsafe {
+                regs.ih_ddc_status.write(I2cStatus::default().clear());
+                regs.ddc.slave.write(DDC_EDID_ADDRESS);
+                regs.ddc.address.write(offset.wrapping_add(i as u8));
+                regs.ddc.operation.write(I2C_READ);
+            }
+            wait_i2c(&regs.ih_ddc_status)?;
+            *byte = regs.ddc.data_in.read();
+        }
+        Ok(())
+    }
+}
+
+/// Write `val` into a pair of registers, low byte first.
+#[inline]
+fn write_u16(regs: &[RW<u8>; 2], val: u16) {
+    let [low, high] = val.to_le_bytes();
+    unsafe {
+        regs[0].write(low);
+        regs[1].write(high);
+    }
+}
+
+/// Wait for I2C master operation of `status` to finish.
+#[inline]
+fn wait_i2c(status: &RW<I2cStatus>) -> Result<(), Error> {
+    let deadline = Deadline::start();
+    loop {
+        let val = status.read();
+        if val.is_error() {
+            return Err(Error::Nack);
+        }
+        if val.is_done() {
+            return Ok(());
+        }
+        deadline.check().map_err(|_| Error::Timeout)?;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{AspectRatio, AviInfoFrame, Mode, PHY_SETTINGS, Quantization};
+
+    #[test]
+    fn struct_avi_infoframe_functions() {
+        let frame = AviInfoFrame::from_mode(&Mode::FHD_1080P60);
+        assert_eq!(frame.vic, 16);
+        assert_eq!(frame.aspect, AspectRatio::Aspect16x9);
+        assert_eq!(frame.registers(), [0x40, 0x28, 0x00, 0x00]);
+        let frame = AviInfoFrame {
+            quantization: Quantization::Full,
+            it_content: true,
+            ..AviInfoFrame::from_mode(&Mode::VGA_480P60)
+        };
+        assert_eq!(frame.registers(), [0x40, 0x18, 0x88, 0x00]);
+    }
+
+    #[test]
+    fn function_phy_settings() {
+        for mode in Mode::STANDARD {
+            let clock = mode.timing.pixel_clock.0;
+            let (max, _) = PHY_SETTINGS
+                .into_iter()
+                .find(|&(max, _)| clock <= max)
+                .unwrap();
+            assert!(max < u32::MAX);
+        }
+    }
+}
@@ -0,0 +1,322 @@
+use super::{Error, Mode, mode::AspectRatio};
+use crate::tcon::{Polarity, Timing};
+use core::iter;
+use embedded_time::rate::Hertz;
+
+/// Length of an EDID block in bytes.
+pub const EDID_BLOCK_LEN: usize = 128;
+
+const HEADER: [u8; 8] = [0x00, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0x00];
+const DESCRIPTOR_LEN: usize = 18;
+const CEA_EXTENSION: u8 = 0x02;
+const VIDEO_BLOCK: u8 = 2;
+const VENDOR_BLOCK: u8 = 3;
+const HDMI_OUI: [u8; 3] = [0x03, 0x0C, 0x00];
+const MONITOR_NAME: u8 = 0xFC;
+
+/// Extended display identification data (EDID) of a sink.
+///
+/// Holds the base block, and the first extension block if it is a CEA-861 extension, which
+/// lists video identification codes of sink and tells HDMI sinks from DVI ones.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Edid {
+    base: [u8; EDID_BLOCK_LEN],
+    cea: Option<[u8; EDID_BLOCK_LEN]>,
+}
+
+impl Edid {
+    /// Parse EDID from `base` block and optional first `extension` block.
+    ///
+    /// Extension blocks other than CEA-861 are ignored.
+    #[inline]
+    pub fn new(
+        base: [u8; EDID_BLOCK_LEN],
+        extension: Option<[u8; EDID_BLOCK_LEN]>,
+    ) -> Result<Self, Error> {
+        if base[..8] != HEADER || !is_checksum_valid(&base) {
+            return Err(Error::InvalidEdid);
+        }
+        let cea = match extension {
+            Some(block) if block[0] == CEA_EXTENSION => {
+                if !is_checksum_valid(&block) {
+                    return Err(Error::InvalidEdid);
+                }
+                Some(block)
+            }
+            _ => None,
+        };
+        Ok(Self { base, cea })
+    }
+    /// Get three-letter manufacturer identifier.
+    #[inline]
+    pub fn manufacturer(&self) -> [u8; 3] {
+        let id = u16::from_be_bytes([self.base[8], self.base[9]]);
+        [10, 5, 0].map(|shift| b'@' + ((id >> shift) & 0x1F) as u8)
+    }
+    /// Get manufacturer product code.
+    #[inline]
+    pub fn product_code(&self) -> u16 {
+        u16::from_le_bytes([self.base[10], self.base[11]])
+    }
+    /// Get number of extension blocks following the base block.
+    #[inline]
+    pub fn extension_count(&self) -> u8 {
+        self.base[126]
+    }
+    /// Get monitor name, if sink provides one.
+    #[inline]
+    pub fn monitor_name(&self) -> Option<&str> {
+        self.base[54..126]
+            .chunks_exact(DESCRIPTOR_LEN)
+            .find(|d| d[..3] == [0, 0, 0] && d[3] == MONITOR_NAME)
+            .and_then(|d| {
+                let text = &d[5..];
+                let len = text.iter().position(|&c| c == b'\n').unwrap_or(text.len());
+                core::str::from_utf8(&text[..len]).ok()
+            })
+            .map(str::trim_end)
+    }
+    /// Get preferred mode of sink, i.e. its first detailed timing.
+    #[inline]
+    pub fn preferred_mode(&self) -> Option<Mode> {
+        parse_detailed_timing(&self.base[54..54 + DESCRIPTOR_LEN])
+    }
+    /// Iterate detailed timings of base block and CEA-861 extension.
+    #[inline]
+    pub fn detailed_modes(&self) -> impl Iterator<Item = Mode> + '_ {
+        let extension = self.cea.iter().flat_map(|block| {
+            let descriptors = match dtd_offset(block) {
+                Some(start) => &block[start..127],
+                None => &[],
+            };
+            descriptors.chunks_exact(DESCRIPTOR_LEN)
+        });
+        self.base[54..126]
+            .chunks_exact(DESCRIPTOR_LEN)
+            .chain(extension)
+            .filter_map(parse_detailed_timing)
+    }
+    /// Check if sink lists CEA-861 video identification code `vic`.
+    #[inline]
+    pub fn supports_vic(&self, vic: u8) -> bool {
+        self.data_blocks()
+            .filter(|&(tag, _)| tag == VIDEO_BLOCK)
+            .flat_map(|(_, payload)| payload.iter())
+            .any(|&svd| {
+                // codes 1 to 64 carry native flag in bit 7
+                let code = if (1..=64).contains(&(svd & 0x7F)) {
+                    svd & 0x7F
+                } else {
+                    svd
+                };
+                vic != 0 && code == vic
+            })
+    }
+    /// Check if sink accepts HDMI signalling, such as infoframes; otherwise it is a DVI sink.
+    #[inline]
+    pub fn is_hdmi(&self) -> bool {
+        self.data_blocks()
+            .any(|(tag, payload)| tag == VENDOR_BLOCK && payload.starts_with(&HDMI_OUI))
+    }
+    /// Iterate tag and payload of data blocks in CEA-861 extension.
+    #[inline]
+    fn data_blocks(&self) -> impl Iterator<Item = (u8, &[u8])> + '_ {
+        let mut blocks: &[u8] = match self
+            .cea
+            .as_ref()
+            .and_then(|block| dtd_offset(block).map(|end| &block[4..end]))
+        {
+            Some(blocks) => blocks,
+            None => &[],
+        };
+        iter::from_fn(move || {
+            let (&header, rest) = blocks.split_first()?;
+            let len = (header & 0x1F) as usize;
+            let payload = rest.get(..len)?;
+            blocks = &rest[len..];
+            Some((header >> 5, payload))
+        })
+    }
+}
+
+/// Get offset of detailed timings in CEA-861 extension `block`, which also ends its data
+/// blocks, or `None` if the sink gives an offset outside the block.
+#[inline]
+fn dtd_offset(block: &[u8; EDID_BLOCK_LEN]) -> Option<usize> {
+    let offset = block[2] as usize;
+    (4..=127).contains(&offset).then_some(offset)
+}
+
+/// Check if bytes of `block` sum to zero.
+#[inline]
+fn is_checksum_valid(block: &[u8; EDID_BLOCK_LEN]) -> bool {
+    block.iter().fold(0u8, |sum, &b| sum.wrapping_add(b)) == 0
+}
+
+/// Parse an 18-byte detailed timing descriptor, or `None` if it is a display descriptor.
+#[inline]
+fn parse_detailed_timing(d: &[u8]) -> Option<Mode> {
+    let clock = u16::from_le_bytes([d[0], d[1]]) as u32;
+    if clock == 0 {
+        return None;
+    }
+    let high = |byte: u8, shift: u8| ((byte >> shift) as u16 & 0xF) << 8;
+    let hactive = d[2] as u16 | high(d[4], 4);
+    let hblank = d[3] as u16 | high(d[4], 0);
+    let vactive = d[5] as u16 | high(d[7], 4);
+    let vblank = d[6] as u16 | high(d[7], 0);
+    let hfront_porch = d[8] as u16 | ((d[11] as u16 >> 6) & 0x3) << 8;
+    let hsync_len = d[9] as u16 | ((d[11] as u16 >> 4) & 0x3) << 8;
+    let vfront_porch = (d[10] >> 4) as u16 | ((d[11] as u16 >> 2) & 0x3) << 4;
+    let vsync_len = (d[10] & 0xF) as u16 | (d[11] as u16 & 0x3) << 4;
+    let flags = d[17];
+    if flags & 0x80 != 0 || hfront_porch + hsync_len > hblank || vfront_porch + vsync_len > vblank {
+        // interlaced modes are not sent
+        return None;
+    }
+    let polarity = |bit: u8| {
+        // digital separate sync carries polarities, other sync types default to active low
+        if flags & 0x18 == 0x18 && flags & bit != 0 {
+            Polarity::ActiveHigh
+        } else {
+            Polarity::ActiveLow
+        }
+    };
+    Some(Mode {
+        timing: Timing {
+            pixel_clock: Hertz(clock * 10_000),
+            hactive,
+            hfront_porch,
+            hsync_len,
+            hback_porch: hblank - hfront_porch - hsync_len,
+            vactive,
+            vfront_porch,
+            vsync_len,
+            vback_porch: vblank - vfront_porch - vsync_len,
+        },
+        hsync: polarity(0x02),
+        vsync: polarity(0x04),
+        vic: 0,
+        aspect: AspectRatio::NoData,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{EDID_BLOCK_LEN, Edid};
+    use crate::hdmi::{Error, Mode, choose_best_mode};
+
+    const DTD_1080P60: [u8; 18] = [
+        0x02, 0x3A, 0x80, 0x18, 0x71, 0x38, 0x2D, 0x40, 0x58, 0x2C, 0x45, 0x00, 0, 0, 0, 0, 0, 0x1E,
+    ];
+    const DTD_720P60: [u8; 18] = [
+        0x01, 0x1D, 0x00, 0x72, 0x51, 0xD0, 0x1E, 0x20, 0x6E, 0x28, 0x55, 0x00, 0, 0, 0, 0, 0, 0x1E,
+    ];
+
+    fn fix_checksum(block: &mut [u8; EDID_BLOCK_LEN]) {
+        let sum = block[..127].iter().fold(0u8, |sum, &b| sum.wrapping_add(b));
+        block[127] = sum.wrapping_neg();
+    }
+
+    fn base_block(preferred: [u8; 18]) -> [u8; EDID_BLOCK_LEN] {
+        let mut block = [0; EDID_BLOCK_LEN];
+        block[..8].copy_from_slice(&[0x00, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0x00]);
+        block[8..12].copy_from_slice(&[0x10, 0xAC, 0x34, 0x12]);
+        block[54..72].copy_from_slice(&preferred);
+        block[72..90].copy_from_slice(b"\0\0\0\xFC\0TEST\n        ");
+        block[126] = 1;
+        fix_checksum(&mut block);
+        block
+    }
+
+    fn cea_block(svds: &[u8], hdmi: bool) -> [u8; EDID_BLOCK_LEN] {
+        let mut block = [0; EDID_BLOCK_LEN];
+        block[..2].copy_from_slice(&[0x02, 0x03]);
+        let mut i = 4;
+        block[i] = (2 << 5) | svds.len() as u8;
+        block[i + 1..i + 1 + svds.len()].copy_from_slice(svds);
+        i += 1 + svds.len();
+        if hdmi {
+            block[i..i + 6].copy_from_slice(&[(3 << 5) | 5, 0x03, 0x0C, 0x00, 0x10, 0x00]);
+            i += 6;
+        }
+        block[2] = i as u8;
+        block[i..i + 18].copy_from_slice(&DTD_720P60);
+        fix_checksum(&mut block);
+        block
+    }
+
+    #[test]
+    fn struct_edid_functions() {
+        let edid = Edid::new(
+            base_block(DTD_1080P60),
+            Some(cea_block(&[0x90, 0x04, 0x01], true)),
+        )
+        .unwrap();
+        assert_eq!(&edid.manufacturer(), b"DEL");
+        assert_eq!(edid.product_code(), 0x1234);
+        assert_eq!(edid.extension_count(), 1);
+        assert_eq!(edid.monitor_name(), Some("TEST"));
+        assert!(edid.is_hdmi());
+        assert!(edid.supports_vic(16));
+        assert!(edid.supports_vic(4));
+        assert!(edid.supports_vic(1));
+        assert!(!edid.supports_vic(31));
+        assert!(!edid.supports_vic(0));
+
+        let preferred = edid.preferred_mode().unwrap();
+        assert_eq!(preferred.timing, Mode::FHD_1080P60.timing);
+        assert_eq!(
+            (preferred.hsync, preferred.vsync),
+            (Mode::FHD_1080P60.hsync, Mode::FHD_1080P60.vsync)
+        );
+        assert_eq!(preferred.vic, 0);
+        let mut modes = edid.detailed_modes();
+        assert_eq!(modes.next(), Some(preferred));
+        assert_eq!(modes.next().unwrap().timing, Mode::HD_720P60.timing);
+        assert_eq!(modes.next(), None);
+        assert_eq!(choose_best_mode(&edid), Mode::FHD_1080P60);
+
+        let edid = Edid::new(base_block(DTD_720P60), None).unwrap();
+        assert!(!edid.is_hdmi());
+        assert!(!edid.supports_vic(4));
+        assert_eq!(choose_best_mode(&edid), Mode::HD_720P60);
+
+        let mut block = base_block(DTD_1080P60);
+        block[20] ^= 1;
+        assert_eq!(Edid::new(block, None), Err(Error::InvalidEdid));
+        let mut block = cea_block(&[], false);
+        block[20] ^= 1;
+        assert_eq!(
+            Edid::new(base_block(DTD_1080P60), Some(block)),
+            Err(Error::InvalidEdid)
+        );
+        // detailed timing offset beyond the block is ignored rather than trusted
+        let mut block = cea_block(&[0x90], true);
+        block[2] = 200;
+        fix_checksum(&mut block);
+        let edid = Edid::new(base_block(DTD_1080P60), Some(block)).unwrap();
+        assert!(!edid.is_hdmi());
+        assert!(!edid.supports_vic(16));
+        assert_eq!(edid.detailed_modes().count(), 1);
+    }
+
+    #[test]
+    fn function_choose_best_mode() {
+        // 3840x2160 at 30 Hz exceeds TMDS clock of transmitter
+        let mut dtd_4k = DTD_1080P60;
+        dtd_4k[..2].copy_from_slice(&29700u16.to_le_bytes());
+        let edid = Edid::new(
+            base_block(dtd_4k),
+            Some(cea_block(&[0x13, 0x04, 0x02], true)),
+        )
+        .unwrap();
+        assert_eq!(choose_best_mode(&edid), Mode::HD_720P60);
+
+        let edid = Edid::new(base_block(dtd_4k), Some(cea_block(&[], true))).unwrap();
+        assert_eq!(choose_best_mode(&edid), Mode::HD_720P60);
+
+        let edid = Edid::new(base_block(dtd_4k), None).unwrap();
+        assert_eq!(choose_best_mode(&edid), Mode::VGA_480P60);
+    }
+}
@@ -0,0 +1,266 @@
+use super::Edid;
+use crate::tcon::{Polarity, Timing};
+use embedded_time::rate::Hertz;
+
+/// Highest TMDS clock the transmitter sends, reaching 1080p at 60 Hz.
+pub const MAX_PIXEL_CLOCK: Hertz = Hertz(148_500_000);
+
+/// Picture aspect ratio signalled in AVI infoframe.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum AspectRatio {
+    /// Aspect ratio is not signalled.
+    NoData = 0,
+    /// 4:3 picture.
+    Aspect4x3 = 1,
+    /// 16:9 picture.
+    Aspect16x9 = 2,
+}
+
+/// Video mode sent to a sink.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Mode {
+    /// Display timing, with pixel clock being TMDS clock.
+    pub timing: Timing,
+    /// Horizontal sync polarity.
+    pub hsync: Polarity,
+    /// Vertical sync polarity.
+    pub vsync: Polarity,
+    /// CEA-861 video identification code, or 0 if mode is not defined by CEA-861.
+    pub vic: u8,
+    /// Picture aspect ratio.
+    pub aspect: AspectRatio,
+}
+
+/// Build a progressive CEA-861 mode from horizontal and vertical active, front porch, sync
+/// and back porch lengths.
+const fn cea(
+    vic: u8,
+    pixel_clock: u32,
+    h: [u16; 4],
+    v: [u16; 4],
+    sync: Polarity,
+    aspect: AspectRatio,
+) -> Mode {
+    Mode {
+        timing: Timing {
+            pixel_clock: Hertz(pixel_clock),
+            hactive: h[0],
+            hfront_porch: h[1],
+            hsync_len: h[2],
+            hback_porch: h[3],
+            vactive: v[0],
+            vfront_porch: v[1],
+            vsync_len: v[2],
+            vback_porch: v[3],
+        },
+        hsync: sync,
+        vsync: sync,
+        vic,
+        aspect,
+    }
+}
+
+impl Mode {
+    /// 640x480 at 60 Hz, supported by every sink.
+    pub const VGA_480P60: Mode = cea(
+        1,
+        25_175_000,
+        [640, 16, 96, 48],
+        [480, 10, 2, 33],
+        Polarity::ActiveLow,
+        AspectRatio::Aspect4x3,
+    );
+    /// 720x480 at 60 Hz.
+    pub const SD_480P60: Mode = cea(
+        2,
+        27_000_000,
+        [720, 16, 62, 60],
+        [480, 9, 6, 30],
+        Polarity::ActiveLow,
+        AspectRatio::Aspect4x3,
+    );
+    /// 720x576 at 50 Hz.
+    pub const SD_576P50: Mode = cea(
+        17,
+        27_000_000,
+        [720, 12, 64, 68],
+        [576, 5, 5, 39],
+        Polarity::ActiveLow,
+        AspectRatio::Aspect4x3,
+    );
+    /// 1280x720 at 50 Hz.
+    pub const HD_720P50: Mode = cea(
+        19,
+        74_250_000,
+        [1280, 440, 40, 220],
+        [720, 5, 5, 20],
+        Polarity::ActiveHigh,
+        AspectRatio::Aspect16x9,
+    );
+    /// 1280x720 at 60 Hz.
+    pub const HD_720P60: Mode = cea(
+        4,
+        74_250_000,
+        [1280, 110, 40, 220],
+        [720, 5, 5, 20],
+        Polarity::ActiveHigh,
+        AspectRatio::Aspect16x9,
+    );
+    /// 1920x1080 at 30 Hz.
+    pub const FHD_1080P30: Mode = cea(
+        34,
+        74_250_000,
+        [1920, 88, 44, 148],
+        [1080, 4, 5, 36],
+        Polarity::ActiveHigh,
+        AspectRatio::Aspect16x9,
+    );
+    /// 1920x1080 at 50 Hz.
+    pub const FHD_1080P50: Mode = cea(
+        31,
+        148_500_000,
+        [1920, 528, 44, 148],
+        [1080, 4, 5, 36],
+        Polarity::ActiveHigh,
+        AspectRatio::Aspect16x9,
+    );
+    /// 1920x1080 at 60 Hz.
+    pub const FHD_1080P60: Mode = cea(
+        16,
+        148_500_000,
+        [1920, 88, 44, 148],
+        [1080, 4, 5, 36],
+        Polarity::ActiveHigh,
+        AspectRatio::Aspect16x9,
+    );
+    /// Standard modes the transmitter sends, from the most to the least preferred.
+    pub const STANDARD: [Mode; 8] = [
+        Mode::FHD_1080P60,
+        Mode::FHD_1080P50,
+        Mode::HD_720P60,
+        Mode::HD_720P50,
+        Mode::FHD_1080P30,
+        Mode::SD_576P50,
+        Mode::SD_480P60,
+        Mode::VGA_480P60,
+    ];
+
+    /// Get standard mode of CEA-861 video identification code `vic`.
+    ///
+    /// 16:9 variants of 480p and 576p are returned with their own codes.
+    #[inline]
+    pub fn from_vic(vic: u8) -> Option<Mode> {
+        let (base, aspect) = match vic {
+            3 => (Mode::SD_480P60, AspectRatio::Aspect16x9),
+            18 => (Mode::SD_576P50, AspectRatio::Aspect16x9),
+            _ => {
+                return Mode::STANDARD.iter().find(|mode| mode.vic == vic).copied();
+            }
+        };
+        Some(Mode {
+            vic,
+            aspect,
+            ..base
+        })
+    }
+    /// Check if transmitter can send this mode.
+    #[inline]
+    pub const fn is_supported(&self) -> bool {
+        let t = &self.timing;
+        let hblank = t.htotal() - t.hactive;
+        let vblank = t.vtotal() - t.vactive;
+        t.pixel_clock.0 <= MAX_PIXEL_CLOCK.0
+            && t.hactive >= 1
+            && t.hactive <= 8191
+            && hblank <= 8191
+            && t.vactive >= 1
+            && t.vactive <= 8191
+            && vblank <= 255
+            && t.hfront_porch <= 8191
+            && t.hsync_len >= 1
+            && t.hsync_len <= 1023
+            && t.vfront_porch <= 255
+            && t.vsync_len >= 1
+            && t.vsync_len <= 63
+    }
+    /// Check if `other` has the same timing, allowing pixel clocks to differ by 0.5%.
+    #[inline]
+    fn matches(&self, other: &Mode) -> bool {
+        let (a, b) = (&self.timing, &other.timing);
+        let clock_diff = a.pixel_clock.0.abs_diff(b.pixel_clock.0);
+        clock_diff <= a.pixel_clock.0 / 200
+            && Timing {
+                pixel_clock: a.pixel_clock,
+                ..*b
+            } == *a
+            && self.hsync == other.hsync
+            && self.vsync == other.vsync
+    }
+}
+
+/// Choose the best mode to send to sink described by `edid`.
+///
+/// The preferred mode of sink is used if supported, identified as a standard mode when it is
+/// one so that AVI infoframe carries its video identification code. Otherwise the most
+/// preferred standard mode listed by sink is used, then any other supported detailed timing
+/// of sink, falling back to 640x480 at 60 Hz.
+#[inline]
+pub fn choose_best_mode(edid: &Edid) -> Mode {
+    let identify = |mode: Mode| {
+        Mode::STANDARD
+            .iter()
+            .find(|standard| standard.matches(&mode))
+            .copied()
+            .unwrap_or(mode)
+    };
+    if let Some(mode) = edid.preferred_mode().filter(Mode::is_supported) {
+        return identify(mode);
+    }
+    Mode::STANDARD
+        .iter()
+        .find(|mode| edid.supports_vic(mode.vic))
+        .copied()
+        .or_else(|| edid.detailed_modes().find(Mode::is_supported).map(identify))
+        .unwrap_or(Mode::VGA_480P60)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{AspectRatio, MAX_PIXEL_CLOCK, Mode};
+    use crate::tcon::Polarity;
+    use embedded_time::rate::Hertz;
+
+    #[test]
+    fn struct_mode_functions() {
+        for mode in Mode::STANDARD {
+            assert!(mode.is_supported());
+            assert_eq!(Mode::from_vic(mode.vic), Some(mode));
+        }
+        assert_eq!(Mode::FHD_1080P60.timing.htotal(), 2200);
+        assert_eq!(Mode::FHD_1080P60.timing.vtotal(), 1125);
+        assert_eq!(Mode::HD_720P50.timing.htotal(), 1980);
+        assert_eq!(Mode::SD_576P50.timing.htotal(), 864);
+        assert_eq!(Mode::VGA_480P60.timing.vtotal(), 525);
+
+        let wide = Mode::from_vic(3).unwrap();
+        assert_eq!(wide.vic, 3);
+        assert_eq!(wide.aspect, AspectRatio::Aspect16x9);
+        assert_eq!(wide.timing, Mode::SD_480P60.timing);
+        assert_eq!(Mode::from_vic(95), None);
+
+        let mut mode = Mode::FHD_1080P60;
+        mode.timing.pixel_clock = Hertz(MAX_PIXEL_CLOCK.0 + 1);
+        assert!(!mode.is_supported());
+        let mut mode = Mode::FHD_1080P60;
+        mode.timing.vsync_len = 64;
+        assert!(!mode.is_supported());
+
+        let mut mode = Mode::VGA_480P60;
+        mode.vic = 0;
+        mode.timing.pixel_clock = Hertz(25_170_000);
+        assert!(Mode::VGA_480P60.matches(&mode));
+        mode.hsync = Polarity::ActiveHigh;
+        assert!(!Mode::VGA_480P60.matches(&mode));
+        assert!(!Mode::FHD_1080P60.matches(&Mode::FHD_1080P50));
+    }
+}
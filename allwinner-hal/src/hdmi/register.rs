@@ -0,0 +1,544 @@
+use crate::tcon::Polarity;
+use volatile_register::{RO, RW};
+
+/// HDMI transmitter controller registers.
+///
+/// Registers are 8 bits wide; values wider than 8 bits span several registers.
+#[repr(C)]
+pub struct RegisterBlock {
+    /// 0x0000 - Design identification register.
+    pub design_id: RO<u8>,
+    /// 0x0001 - Revision identification register.
+    pub revision_id: RO<u8>,
+    _reserved0: [u8; 0x103],
+    /// 0x0105 - DDC master interrupt status register.
+    pub ih_ddc_status: RW<I2cStatus>,
+    _reserved1: [u8; 2],
+    /// 0x0108 - PHY I2C master interrupt status register.
+    pub ih_phy_i2c_status: RW<I2cStatus>,
+    _reserved2: [u8; 0xf6],
+    /// 0x01ff - Global interrupt mute register.
+    pub ih_mute: RW<u8>,
+    /// 0x0200 - Input video mapping register.
+    pub tx_input: RW<u8>,
+    /// 0x0201 - Input video stuffing enable register.
+    pub tx_stuffing: RW<u8>,
+    /// 0x0202..=0x0207 - Stuffing data registers of the three components, low byte first.
+    pub tx_stuffing_data: [RW<u8>; 6],
+    _reserved3: [u8; 0x5f8],
+    /// 0x0800 - Video packetizer status register.
+    pub vp_status: RO<u8>,
+    /// 0x0801 - Video packetizer color depth register.
+    pub vp_color_depth: RW<u8>,
+    /// 0x0802 - Video packetizer stuffing register.
+    pub vp_stuffing: RW<u8>,
+    /// 0x0803 - Video packetizer YCC422 remapping register.
+    pub vp_remap: RW<u8>,
+    /// 0x0804 - Video packetizer output configuration register.
+    pub vp_config: RW<u8>,
+    _reserved4: [u8; 0x7fb],
+    /// 0x1000..=0x10e0 - Frame composer registers.
+    pub fc: FrameComposerRegisters,
+    _reserved5: [u8; 0x1f1f],
+    /// 0x3000 - PHY configuration register.
+    pub phy_config: RW<PhyConfig>,
+    /// 0x3001..=0x3003 - PHY test interface registers.
+    pub phy_test: [RW<u8>; 3],
+    /// 0x3004 - PHY status register.
+    pub phy_status: RO<PhyStatus>,
+    _reserved6: [u8; 0x1b],
+    /// 0x3020..=0x302a - PHY I2C master registers.
+    pub phy_i2c: PhyI2cRegisters,
+    _reserved7: [u8; 0xfd5],
+    /// 0x4000..=0x4007 - Main controller registers.
+    pub mc: MainControllerRegisters,
+    _reserved8: [u8; 0x3df8],
+    /// 0x7e00..=0x7e12 - DDC master registers.
+    pub ddc: DdcRegisters,
+}
+
+/// Frame composer registers.
+#[repr(C)]
+pub struct FrameComposerRegisters {
+    /// 0x00 - Input video configuration register.
+    pub video_config: RW<VideoConfig>,
+    /// 0x01..=0x02 - Active pixels per line, low byte first.
+    pub h_active: [RW<u8>; 2],
+    /// 0x03..=0x04 - Blanking pixels per line, low byte first.
+    pub h_blank: [RW<u8>; 2],
+    /// 0x05..=0x06 - Active lines per frame, low byte first.
+    pub v_active: [RW<u8>; 2],
+    /// 0x07 - Blanking lines per frame.
+    pub v_blank: RW<u8>,
+    /// 0x08..=0x09 - Horizontal front porch in pixels, low byte first.
+    pub hsync_delay: [RW<u8>; 2],
+    /// 0x0a..=0x0b - Horizontal sync width in pixels, low byte first.
+    pub hsync_width: [RW<u8>; 2],
+    /// 0x0c - Vertical front porch in lines.
+    pub vsync_delay: RW<u8>,
+    /// 0x0d - Vertical sync width in lines.
+    pub vsync_width: RW<u8>,
+    /// 0x0e..=0x10 - Input frequency registers.
+    pub input_frequency: [RW<u8>; 3],
+    /// 0x11 - Control period duration register.
+    pub control_duration: RW<u8>,
+    /// 0x12 - Extended control period duration register.
+    pub ex_control_duration: RW<u8>,
+    /// 0x13 - Extended control period maximum spacing register.
+    pub ex_control_space: RW<u8>,
+    /// 0x14..=0x16 - Channel 0 to 2 preamble registers.
+    pub preamble: [RW<u8>; 3],
+    /// 0x17 - AVI infoframe data byte 5 register.
+    pub avi_config3: RW<u8>,
+    /// 0x18 - General control packet register.
+    pub gcp: RW<u8>,
+    /// 0x19..=0x1b - AVI infoframe data byte 1 to 3 registers.
+    pub avi_config: [RW<u8>; 3],
+    /// 0x1c - AVI infoframe video identification code register.
+    pub avi_vic: RW<u8>,
+    /// 0x1d..=0x24 - AVI infoframe bar information registers.
+    pub avi_bar: [RW<u8>; 8],
+    _reserved0: [u8; 0xbb],
+    /// 0xe0 - Pixel repetition configuration register.
+    pub pixel_repetition: RW<u8>,
+}
+
+/// PHY I2C master registers.
+#[repr(C)]
+pub struct PhyI2cRegisters {
+    /// 0x0 - Slave address register.
+    pub slave: RW<u8>,
+    /// 0x1 - Register address register.
+    pub address: RW<u8>,
+    /// 0x2..=0x3 - Data to write, high byte first.
+    pub data_out: [RW<u8>; 2],
+    /// 0x4..=0x5 - Data read, high byte first.
+    pub data_in: [RO<u8>; 2],
+    /// 0x6 - Operation register.
+    pub operation: RW<u8>,
+    /// 0x7 - Done interrupt control register.
+    pub interrupt: RW<u8>,
+    /// 0x8 - Error interrupt control register.
+    pub control_interrupt: RW<u8>,
+    /// 0x9 - Speed control register.
+    pub divider: RW<u8>,
+    /// 0xa - Software reset register, active low.
+    pub soft_reset: RW<u8>,
+}
+
+/// Main controller registers.
+#[repr(C)]
+pub struct MainControllerRegisters {
+    /// 0x0 - Frame composer input clock divider register.
+    pub sfr_divider: RW<u8>,
+    /// 0x1 - Clock disable register.
+    pub clock_disable: RW<u8>,
+    /// 0x2 - Software reset register, active low.
+    pub soft_reset: RW<u8>,
+    /// 0x3 - HDCP operation control register.
+    pub operation_control: RW<u8>,
+    /// 0x4 - Data path flow control register.
+    pub flow_control: RW<u8>,
+    /// 0x5 - PHY reset register.
+    pub phy_reset: RW<u8>,
+    /// 0x6 - Clock lock status register.
+    pub lock_on_clock: RW<u8>,
+    /// 0x7 - HEAC PHY reset register.
+    pub heac_phy_reset: RW<u8>,
+}
+
+/// DDC (display data channel) I2C master registers.
+#[repr(C)]
+pub struct DdcRegisters {
+    /// 0x00 - Slave address register.
+    pub slave: RW<u8>,
+    /// 0x01 - Register address register.
+    pub address: RW<u8>,
+    /// 0x02 - Data to write.
+    pub data_out: RW<u8>,
+    /// 0x03 - Data read.
+    pub data_in: RO<u8>,
+    /// 0x04 - Operation register.
+    pub operation: RW<u8>,
+    /// 0x05 - Done interrupt control register.
+    pub interrupt: RW<u8>,
+    /// 0x06 - Error interrupt control register.
+    pub control_interrupt: RW<u8>,
+    /// 0x07 - Speed control register.
+    pub divider: RW<u8>,
+    /// 0x08 - E-DDC segment address register.
+    pub segment_address: RW<u8>,
+    /// 0x09 - Software reset register, active low.
+    pub soft_reset: RW<u8>,
+    /// 0x0a - E-DDC segment pointer register.
+    pub segment_pointer: RW<u8>,
+    /// 0x0b..=0x0c - Standard speed SCL high count, high byte first.
+    pub ss_scl_high: [RW<u8>; 2],
+    /// 0x0d..=0x0e - Standard speed SCL low count, high byte first.
+    pub ss_scl_low: [RW<u8>; 2],
+    /// 0x0f..=0x10 - Fast speed SCL high count, high byte first.
+    pub fs_scl_high: [RW<u8>; 2],
+    /// 0x11..=0x12 - Fast speed SCL low count, high byte first.
+    pub fs_scl_low: [RW<u8>; 2],
+}
+
+/// HDMI PHY glue registers.
+#[repr(C)]
+pub struct PhyRegisterBlock {
+    /// 0x00 - PHY debug control register.
+    pub debug_control: RW<PhyDebugControl>,
+    /// 0x04 - External resistor control register.
+    pub rext_control: RW<RextControl>,
+}
+
+/// I2C master interrupt status register.
+///
+/// Status flags are cleared by writing one.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct I2cStatus(u8);
+
+impl I2cStatus {
+    const ERROR: u8 = 1 << 0;
+    const DONE: u8 = 1 << 1;
+
+    /// Check if last operation finished.
+    #[inline]
+    pub const fn is_done(self) -> bool {
+        self.0 & Self::DONE != 0
+    }
+    /// Check if last operation failed, e.g. for a missing acknowledge.
+    #[inline]
+    pub const fn is_error(self) -> bool {
+        self.0 & Self::ERROR != 0
+    }
+    /// Clear both done and error flags.
+    #[inline]
+    pub const fn clear(self) -> Self {
+        Self(self.0 | Self::DONE | Self::ERROR)
+    }
+}
+
+impl Default for I2cStatus {
+    #[inline]
+    fn default() -> Self {
+        Self(0x00)
+    }
+}
+
+/// Input video configuration register.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct VideoConfig(u8);
+
+impl VideoConfig {
+    const VSYNC_POL: u8 = 1 << 6;
+    const HSYNC_POL: u8 = 1 << 5;
+    const DE_POL: u8 = 1 << 4;
+    const HDMI_MODE: u8 = 1 << 3;
+
+    /// Get vertical sync polarity.
+    #[inline]
+    pub const fn vsync_polarity(self) -> Polarity {
+        match self.0 & Self::VSYNC_POL {
+            0 => Polarity::ActiveLow,
+            _ => Polarity::ActiveHigh,
+        }
+    }
+    /// Set vertical sync polarity.
+    #[inline]
+    pub const fn set_vsync_polarity(self, val: Polarity) -> Self {
+        match val {
+            Polarity::ActiveLow => Self(self.0 & !Self::VSYNC_POL),
+            Polarity::ActiveHigh => Self(self.0 | Self::VSYNC_POL),
+        }
+    }
+    /// Get horizontal sync polarity.
+    #[inline]
+    pub const fn hsync_polarity(self) -> Polarity {
+        match self.0 & Self::HSYNC_POL {
+            0 => Polarity::ActiveLow,
+            _ => Polarity::ActiveHigh,
+        }
+    }
+    /// Set horizontal sync polarity.
+    #[inline]
+    pub const fn set_hsync_polarity(self, val: Polarity) -> Self {
+        match val {
+            Polarity::ActiveLow => Self(self.0 & !Self::HSYNC_POL),
+            Polarity::ActiveHigh => Self(self.0 | Self::HSYNC_POL),
+        }
+    }
+    /// Get data enable polarity.
+    #[inline]
+    pub const fn data_enable_polarity(self) -> Polarity {
+        match self.0 & Self::DE_POL {
+            0 => Polarity::ActiveLow,
+            _ => Polarity::ActiveHigh,
+        }
+    }
+    /// Set data enable polarity.
+    #[inline]
+    pub const fn set_data_enable_polarity(self, val: Polarity) -> Self {
+        match val {
+            Polarity::ActiveLow => Self(self.0 & !Self::DE_POL),
+            Polarity::ActiveHigh => Self(self.0 | Self::DE_POL),
+        }
+    }
+    /// Check if HDMI mode is selected, i.e. data islands such as infoframes are sent.
+    #[inline]
+    pub const fn is_hdmi_mode(self) -> bool {
+        self.0 & Self::HDMI_MODE != 0
+    }
+    /// Select HDMI mode, sending data islands such as infoframes.
+    #[inline]
+    pub const fn enable_hdmi_mode(self) -> Self {
+        Self(self.0 | Self::HDMI_MODE)
+    }
+    /// Select DVI mode, sending video only.
+    #[inline]
+    pub const fn enable_dvi_mode(self) -> Self {
+        Self(self.0 & !Self::HDMI_MODE)
+    }
+}
+
+impl Default for VideoConfig {
+    #[inline]
+    fn default() -> Self {
+        Self(0x00)
+    }
+}
+
+/// PHY configuration register.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct PhyConfig(u8);
+
+impl PhyConfig {
+    const PDDQ: u8 = 1 << 4;
+    const TXPWRON: u8 = 1 << 3;
+    const ENHPDRXSENSE: u8 = 1 << 2;
+
+    /// Check if PHY is in power down mode.
+    #[inline]
+    pub const fn is_powered_down(self) -> bool {
+        self.0 & Self::PDDQ != 0
+    }
+    /// Put PHY into power down mode.
+    #[inline]
+    pub const fn power_down(self) -> Self {
+        Self(self.0 | Self::PDDQ)
+    }
+    /// Wake PHY from power down mode.
+    #[inline]
+    pub const fn power_up(self) -> Self {
+        Self(self.0 & !Self::PDDQ)
+    }
+    /// Check if TMDS transmitter is powered on.
+    #[inline]
+    pub const fn is_transmitter_enabled(self) -> bool {
+        self.0 & Self::TXPWRON != 0
+    }
+    /// Power on TMDS transmitter.
+    #[inline]
+    pub const fn enable_transmitter(self) -> Self {
+        Self(self.0 | Self::TXPWRON)
+    }
+    /// Power off TMDS transmitter.
+    #[inline]
+    pub const fn disable_transmitter(self) -> Self {
+        Self(self.0 & !Self::TXPWRON)
+    }
+    /// Check if hot plug and receiver sense detectors are enabled.
+    #[inline]
+    pub const fn is_hpd_sense_enabled(self) -> bool {
+        self.0 & Self::ENHPDRXSENSE != 0
+    }
+    /// Enable hot plug and receiver sense detectors.
+    #[inline]
+    pub const fn enable_hpd_sense(self) -> Self {
+        Self(self.0 | Self::ENHPDRXSENSE)
+    }
+    /// Disable hot plug and receiver sense detectors.
+    #[inline]
+    pub const fn disable_hpd_sense(self) -> Self {
+        Self(self.0 & !Self::ENHPDRXSENSE)
+    }
+}
+
+/// PHY status register.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct PhyStatus(u8);
+
+impl PhyStatus {
+    const TX_PHY_LOCK: u8 = 1 << 0;
+    const HPD: u8 = 1 << 1;
+
+    /// Check if PHY PLL is locked to TMDS clock.
+    #[inline]
+    pub const fn is_locked(self) -> bool {
+        self.0 & Self::TX_PHY_LOCK != 0
+    }
+    /// Check if hot plug detect line is asserted by a sink.
+    #[inline]
+    pub const fn is_plugged(self) -> bool {
+        self.0 & Self::HPD != 0
+    }
+}
+
+/// PHY debug control register.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct PhyDebugControl(u32);
+
+impl PhyDebugControl {
+    const ADDR: u32 = 0xff << 16;
+    const PX_LOCK: u32 = 1 << 0;
+
+    /// Get I2C address PHY responds to.
+    #[inline]
+    pub const fn i2c_address(self) -> u8 {
+        ((self.0 & Self::ADDR) >> 16) as u8
+    }
+    /// Set I2C address PHY responds to.
+    #[inline]
+    pub const fn set_i2c_address(self, val: u8) -> Self {
+        Self((self.0 & !Self::ADDR) | ((val as u32) << 16))
+    }
+    /// Check if PHY is locked to pixel clock.
+    #[inline]
+    pub const fn is_pixel_lock_enabled(self) -> bool {
+        self.0 & Self::PX_LOCK != 0
+    }
+    /// Lock PHY to pixel clock.
+    #[inline]
+    pub const fn enable_pixel_lock(self) -> Self {
+        Self(self.0 | Self::PX_LOCK)
+    }
+}
+
+/// External resistor control register.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct RextControl(u32);
+
+impl RextControl {
+    const REXT_EN: u32 = 1 << 31;
+
+    /// Check if external calibration resistor is used.
+    #[inline]
+    pub const fn is_external_resistor_enabled(self) -> bool {
+        self.0 & Self::REXT_EN != 0
+    }
+    /// Use external calibration resistor.
+    #[inline]
+    pub const fn enable_external_resistor(self) -> Self {
+        Self(self.0 | Self::REXT_EN)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        DdcRegisters, FrameComposerRegisters, I2cStatus, MainControllerRegisters, PhyConfig,
+        PhyDebugControl, PhyI2cRegisters, PhyRegisterBlock, PhyStatus, RegisterBlock, RextControl,
+        VideoConfig,
+    };
+    use crate::tcon::Polarity;
+    use core::mem::offset_of;
+
+    #[test]
+    fn offset_hdmi() {
+        assert_eq!(offset_of!(RegisterBlock, revision_id), 0x0001);
+        assert_eq!(offset_of!(RegisterBlock, ih_ddc_status), 0x0105);
+        assert_eq!(offset_of!(RegisterBlock, ih_phy_i2c_status), 0x0108);
+        assert_eq!(offset_of!(RegisterBlock, ih_mute), 0x01ff);
+        assert_eq!(offset_of!(RegisterBlock, tx_input), 0x0200);
+        assert_eq!(offset_of!(RegisterBlock, tx_stuffing_data), 0x0202);
+        assert_eq!(offset_of!(RegisterBlock, vp_status), 0x0800);
+        assert_eq!(offset_of!(RegisterBlock, vp_config), 0x0804);
+        assert_eq!(offset_of!(RegisterBlock, fc), 0x1000);
+        assert_eq!(offset_of!(RegisterBlock, phy_config), 0x3000);
+        assert_eq!(offset_of!(RegisterBlock, phy_status), 0x3004);
+        assert_eq!(offset_of!(RegisterBlock, phy_i2c), 0x3020);
+        assert_eq!(offset_of!(RegisterBlock, mc), 0x4000);
+        assert_eq!(offset_of!(RegisterBlock, ddc), 0x7e00);
+
+        assert_eq!(offset_of!(FrameComposerRegisters, v_blank), 0x07);
+        assert_eq!(offset_of!(FrameComposerRegisters, vsync_width), 0x0d);
+        assert_eq!(offset_of!(FrameComposerRegisters, control_duration), 0x11);
+        assert_eq!(offset_of!(FrameComposerRegisters, preamble), 0x14);
+        assert_eq!(offset_of!(FrameComposerRegisters, avi_config3), 0x17);
+        assert_eq!(offset_of!(FrameComposerRegisters, avi_config), 0x19);
+        assert_eq!(offset_of!(FrameComposerRegisters, avi_vic), 0x1c);
+        assert_eq!(offset_of!(FrameComposerRegisters, pixel_repetition), 0xe0);
+
+        assert_eq!(offset_of!(PhyI2cRegisters, operation), 0x6);
+        assert_eq!(offset_of!(PhyI2cRegisters, soft_reset), 0xa);
+        assert_eq!(offset_of!(MainControllerRegisters, flow_control), 0x4);
+        assert_eq!(offset_of!(MainControllerRegisters, phy_reset), 0x5);
+        assert_eq!(offset_of!(DdcRegisters, operation), 0x04);
+        assert_eq!(offset_of!(DdcRegisters, soft_reset), 0x09);
+        assert_eq!(offset_of!(DdcRegisters, fs_scl_low), 0x11);
+
+        assert_eq!(offset_of!(PhyRegisterBlock, debug_control), 0x00);
+        assert_eq!(offset_of!(PhyRegisterBlock, rext_control), 0x04);
+    }
+
+    #[test]
+    fn struct_i2c_status_functions() {
+        let val = I2cStatus(0x02);
+        assert!(val.is_done());
+        assert!(!val.is_error());
+        assert!(I2cStatus(0x01).is_error());
+        assert_eq!(I2cStatus::default().clear().0, 0x03);
+    }
+
+    #[test]
+    fn struct_video_config_functions() {
+        let val = VideoConfig::default()
+            .set_vsync_polarity(Polarity::ActiveHigh)
+            .set_hsync_polarity(Polarity::ActiveHigh)
+            .set_data_enable_polarity(Polarity::ActiveHigh)
+            .enable_hdmi_mode();
+        assert_eq!(val.0, 0x78);
+        assert_eq!(val.vsync_polarity(), Polarity::ActiveHigh);
+        assert_eq!(val.hsync_polarity(), Polarity::ActiveHigh);
+        assert_eq!(val.data_enable_polarity(), Polarity::ActiveHigh);
+        assert!(val.is_hdmi_mode());
+        let val = val
+            .set_hsync_polarity(Polarity::ActiveLow)
+            .enable_dvi_mode();
+        assert_eq!(val.0, 0x50);
+        assert_eq!(val.hsync_polarity(), Polarity::ActiveLow);
+        assert!(!val.is_hdmi_mode());
+    }
+
+    #[test]
+    fn struct_phy_functions() {
+        let val = PhyConfig(0x00).power_down().enable_hpd_sense();
+        assert_eq!(val.0, 0x14);
+        assert!(val.is_powered_down());
+        assert!(val.is_hpd_sense_enabled());
+        let val = val.power_up().enable_transmitter();
+        assert_eq!(val.0, 0x0c);
+        assert!(val.is_transmitter_enabled());
+        assert_eq!(val.disable_transmitter().disable_hpd_sense().0, 0x00);
+
+        assert!(PhyStatus(0x01).is_locked());
+        assert!(PhyStatus(0x02).is_plugged());
+        assert!(!PhyStatus(0x02).is_locked());
+
+        let val = PhyDebugControl(0x0)
+            .set_i2c_address(0x69)
+            .enable_pixel_lock();
+        assert_eq!(val.0, 0x0069_0001);
+        assert_eq!(val.i2c_address(), 0x69);
+        assert!(val.is_pixel_lock_enabled());
+
+        let val = RextControl(0x0).enable_external_resistor();
+        assert_eq!(val.0, 0x8000_0000);
+        assert!(val.is_external_resistor_enabled());
+    }
+}
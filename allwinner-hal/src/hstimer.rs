@@ -0,0 +1,141 @@
+//! High speed timers.
+//!
+//! HSTimers are 56-bit down counters clocked by AHB bus clock, which is PSI clock on
+//! D1-like chips, giving finer granularity than 24-MHz general purpose timers.
+
+mod register;
+pub use register::*;
+
+use crate::ccu::{self, ClockGate, Clocks};
+use embedded_time::rate::Hertz;
+
+/// Largest interval value of 56-bit HSTimer counter.
+pub const MAX_INTERVAL: u64 = (1 << 56) - 1;
+
+/// Managed high speed timer structure with peripheral.
+pub struct HsTimers<HSTIMER> {
+    hstimer: HSTIMER,
+    source: Hertz,
+}
+
+impl<HSTIMER: AsRef<RegisterBlock>> HsTimers<HSTIMER> {
+    /// Create an HSTimer instance, stopping all timers and clearing their interrupts.
+    #[inline]
+    pub fn new(hstimer: HSTIMER, clocks: &Clocks, ccu: &ccu::RegisterBlock) -> Self {
+        // note(unsafe): async read and write using ccu registers
+        unsafe { ccu::HSTIMER::reset(ccu) };
+        let regs = hstimer.as_ref();
+        unsafe {
+            for timer in &regs.timers {
+                timer.control.write(Control::default());
+            }
+            regs.irq_enable.write(IrqEnable::default());
+            regs.irq_status.write(
+                IrqStatus::default()
+                    .clear_pending::<0>()
+                    .clear_pending::<1>(),
+            );
+        }
+        Self {
+            hstimer,
+            source: clocks.psi,
+        }
+    }
+    /// Split HSTimer peripheral into separately owned timers.
+    #[inline]
+    pub fn split(&mut self) -> (HsTimer<'_, 0>, HsTimer<'_, 1>) {
+        let hstimer = self.hstimer.as_ref();
+        let source = self.source;
+        (HsTimer { hstimer, source }, HsTimer { hstimer, source })
+    }
+    /// Close HSTimer and release peripheral.
+    #[inline]
+    pub fn free(self, ccu: &ccu::RegisterBlock) -> HSTIMER {
+        unsafe { ccu::HSTIMER::free(ccu) };
+        self.hstimer
+    }
+}
+
+/// High speed timer `I`.
+pub struct HsTimer<'a, const I: usize> {
+    hstimer: &'a RegisterBlock,
+    source: Hertz,
+}
+
+impl<'a, const I: usize> HsTimer<'a, I> {
+    /// Start counting down from `ticks` in `mode`, one tick per `prescale` source cycles.
+    ///
+    /// `ticks` is truncated to 56 bits.
+    #[inline]
+    pub fn start(&mut self, ticks: u64, mode: TimerMode, prescale: Prescale) {
+        let timer = &self.hstimer.timers[I];
+        let ticks = ticks & MAX_INTERVAL;
+        unsafe {
+            timer.control.write(Control::default());
+            self.hstimer
+                .irq_status
+                .write(IrqStatus::default().clear_pending::<I>());
+            timer.interval_lo.write(ticks as u32);
+            timer.interval_hi.write((ticks >> 32) as u32);
+            let control = Control::default().set_mode(mode).set_prescale(prescale);
+            timer.control.write(control.set_reload());
+            while !timer.control.read().is_reload_finished() {
+                core::hint::spin_loop();
+            }
+            timer.control.write(control.enable());
+        }
+    }
+    /// Stop counting.
+    #[inline]
+    pub fn stop(&mut self) {
+        unsafe { self.hstimer.timers[I].control.modify(|val| val.disable()) };
+    }
+    /// Check if timer is counting.
+    #[inline]
+    pub fn is_running(&self) -> bool {
+        self.hstimer.timers[I].control.read().is_enabled()
+    }
+    /// Get number of ticks left until timer reaches zero.
+    #[inline]
+    pub fn current_value(&self) -> u64 {
+        let timer = &self.hstimer.timers[I];
+        // Read again if low half wraps between reads of high half.
+        loop {
+            let hi = timer.current_value_hi.read();
+            let lo = timer.current_value_lo.read();
+            if timer.current_value_hi.read() == hi {
+                return ((hi as u64 & 0xFF_FFFF) << 32) | lo as u64;
+            }
+        }
+    }
+    /// Get counting frequency of this timer.
+    #[inline]
+    pub fn frequency(&self) -> Hertz {
+        let prescale = self.hstimer.timers[I].control.read().prescale();
+        Hertz(self.source.0 / prescale.divisor())
+    }
+    /// Enable interrupt on timer reaching zero.
+    #[inline]
+    pub fn enable_interrupt(&mut self) {
+        unsafe { self.hstimer.irq_enable.modify(|val| val.enable::<I>()) };
+    }
+    /// Disable interrupt on timer reaching zero.
+    #[inline]
+    pub fn disable_interrupt(&mut self) {
+        unsafe { self.hstimer.irq_enable.modify(|val| val.disable::<I>()) };
+    }
+    /// Check if timer has reached zero since interrupt was last cleared.
+    #[inline]
+    pub fn is_pending(&self) -> bool {
+        self.hstimer.irq_status.read().is_pending::<I>()
+    }
+    /// Clear pending interrupt of this timer.
+    #[inline]
+    pub fn clear_interrupt(&mut self) {
+        unsafe {
+            self.hstimer
+                .irq_status
+                .write(IrqStatus::default().clear_pending::<I>())
+        };
+    }
+}
@@ -0,0 +1,268 @@
+use volatile_register::{RO, RW};
+
+pub use crate::timer::TimerMode;
+
+/// Number of high speed timers on current SoC.
+pub const HSTIMER_COUNT: usize = 2;
+
+/// High speed timer registers.
+#[repr(C)]
+pub struct RegisterBlock {
+    /// 0x00 - HSTimer IRQ Enable Register.
+    pub irq_enable: RW<IrqEnable>,
+    /// 0x04 - HSTimer Status Register.
+    pub irq_status: RW<IrqStatus>,
+    _reserved0: [u32; 6],
+    /// 0x20 ..= 0x5C - HSTimer 0 and HSTimer 1 register groups.
+    pub timers: [HsTimerRegisters; HSTIMER_COUNT],
+}
+
+/// High speed timer register group.
+#[repr(C)]
+pub struct HsTimerRegisters {
+    /// 0x00 - HSTimer Control Register.
+    pub control: RW<Control>,
+    /// 0x04 - HSTimer Interval Value Low Register.
+    pub interval_lo: RW<u32>,
+    /// 0x08 - HSTimer Interval Value High Register, bits 23:0.
+    pub interval_hi: RW<u32>,
+    /// 0x0C - HSTimer Current Value Low Register.
+    pub current_value_lo: RO<u32>,
+    /// 0x10 - HSTimer Current Value High Register, bits 23:0.
+    pub current_value_hi: RO<u32>,
+    _reserved0: [u32; 3],
+}
+
+/// HSTimer IRQ enable register.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct IrqEnable(u32);
+
+impl IrqEnable {
+    /// If interrupt of HSTimer `I` is enabled.
+    #[inline]
+    pub const fn is_enabled<const I: usize>(self) -> bool {
+        self.0 & (1 << I) != 0
+    }
+    /// Enable interrupt of HSTimer `I`.
+    #[inline]
+    pub const fn enable<const I: usize>(self) -> Self {
+        Self(self.0 | (1 << I))
+    }
+    /// Disable interrupt of HSTimer `I`.
+    #[inline]
+    pub const fn disable<const I: usize>(self) -> Self {
+        Self(self.0 & !(1 << I))
+    }
+}
+
+impl Default for IrqEnable {
+    #[inline]
+    fn default() -> Self {
+        Self(0x0000_0000)
+    }
+}
+
+/// HSTimer IRQ status register.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct IrqStatus(u32);
+
+impl IrqStatus {
+    /// If interrupt of HSTimer `I` is pending.
+    #[inline]
+    pub const fn is_pending<const I: usize>(self) -> bool {
+        self.0 & (1 << I) != 0
+    }
+    /// Clear pending interrupt of HSTimer `I` on write.
+    #[inline]
+    pub const fn clear_pending<const I: usize>(self) -> Self {
+        Self(self.0 | (1 << I))
+    }
+}
+
+impl Default for IrqStatus {
+    #[inline]
+    fn default() -> Self {
+        Self(0x0000_0000)
+    }
+}
+
+/// HSTimer control register.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct Control(u32);
+
+/// HSTimer clock pre-scale.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Prescale {
+    /// Divide clock by 1.
+    P1,
+    /// Divide clock by 2.
+    P2,
+    /// Divide clock by 4.
+    P4,
+    /// Divide clock by 8.
+    P8,
+    /// Divide clock by 16.
+    P16,
+}
+
+impl Prescale {
+    /// Get clock divide factor.
+    #[inline]
+    pub const fn divisor(self) -> u32 {
+        1 << self as u32
+    }
+}
+
+impl Control {
+    const TEST_MODE: u32 = 1 << 31;
+    const MODE: u32 = 1 << 7;
+    const CLK_PRES: u32 = 0x7 << 4;
+    const RELOAD: u32 = 1 << 1;
+    const EN: u32 = 1 << 0;
+
+    /// If test mode is enabled, where only low 32 bits of interval are used.
+    #[inline]
+    pub const fn is_test_mode(self) -> bool {
+        self.0 & Self::TEST_MODE != 0
+    }
+    /// Get timer counting mode.
+    #[inline]
+    pub const fn mode(self) -> TimerMode {
+        match (self.0 & Self::MODE) >> 7 {
+            0 => TimerMode::Periodic,
+            1 => TimerMode::Single,
+            _ => unreachable!(),
+        }
+    }
+    /// Set timer counting mode.
+    #[inline]
+    pub const fn set_mode(self, val: TimerMode) -> Self {
+        Self((self.0 & !Self::MODE) | ((val as u32) << 7))
+    }
+    /// Get timer clock pre-scale.
+    #[inline]
+    pub const fn prescale(self) -> Prescale {
+        match (self.0 & Self::CLK_PRES) >> 4 {
+            0 => Prescale::P1,
+            1 => Prescale::P2,
+            2 => Prescale::P4,
+            3 => Prescale::P8,
+            4 => Prescale::P16,
+            _ => panic!("impossible pre-scale"),
+        }
+    }
+    /// Set timer clock pre-scale.
+    #[inline]
+    pub const fn set_prescale(self, val: Prescale) -> Self {
+        Self((self.0 & !Self::CLK_PRES) | ((val as u32) << 4))
+    }
+    /// Reload interval value into current value.
+    #[inline]
+    pub const fn set_reload(self) -> Self {
+        Self(self.0 | Self::RELOAD)
+    }
+    /// Is reload finished by hardware?
+    #[inline]
+    pub const fn is_reload_finished(self) -> bool {
+        self.0 & Self::RELOAD == 0
+    }
+    /// If timer is enabled.
+    #[inline]
+    pub const fn is_enabled(self) -> bool {
+        self.0 & Self::EN != 0
+    }
+    /// Enable timer.
+    #[inline]
+    pub const fn enable(self) -> Self {
+        Self(self.0 | Self::EN)
+    }
+    /// Disable timer.
+    #[inline]
+    pub const fn disable(self) -> Self {
+        Self(self.0 & !Self::EN)
+    }
+}
+
+impl Default for Control {
+    #[inline]
+    fn default() -> Self {
+        Self(0x0000_0000)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        Control, HsTimerRegisters, IrqEnable, IrqStatus, Prescale, RegisterBlock, TimerMode,
+    };
+    use core::mem::{offset_of, size_of};
+
+    #[test]
+    fn offset_hstimer() {
+        assert_eq!(offset_of!(RegisterBlock, irq_enable), 0x00);
+        assert_eq!(offset_of!(RegisterBlock, irq_status), 0x04);
+        assert_eq!(offset_of!(RegisterBlock, timers), 0x20);
+        assert_eq!(offset_of!(HsTimerRegisters, control), 0x00);
+        assert_eq!(offset_of!(HsTimerRegisters, interval_lo), 0x04);
+        assert_eq!(offset_of!(HsTimerRegisters, interval_hi), 0x08);
+        assert_eq!(offset_of!(HsTimerRegisters, current_value_lo), 0x0C);
+        assert_eq!(offset_of!(HsTimerRegisters, current_value_hi), 0x10);
+        assert_eq!(size_of::<HsTimerRegisters>(), 0x20);
+    }
+
+    #[test]
+    fn struct_irq_functions() {
+        let mut val = IrqEnable::default();
+        val = val.enable::<1>();
+        assert!(val.is_enabled::<1>());
+        assert!(!val.is_enabled::<0>());
+        assert_eq!(val.0, 0x00000002);
+        val = val.disable::<1>();
+        assert_eq!(val.0, 0x00000000);
+
+        let val = IrqStatus(0x1);
+        assert!(val.is_pending::<0>());
+        assert!(!val.is_pending::<1>());
+        let val = IrqStatus::default()
+            .clear_pending::<0>()
+            .clear_pending::<1>();
+        assert_eq!(val.0, 0x00000003);
+    }
+
+    #[test]
+    fn struct_control_functions() {
+        let mut val = Control(0x0);
+
+        val = val.set_mode(TimerMode::Single);
+        assert_eq!(val.mode(), TimerMode::Single);
+        assert_eq!(val.0, 0x00000080);
+        val = val.set_mode(TimerMode::Periodic);
+        assert_eq!(val.mode(), TimerMode::Periodic);
+        assert_eq!(val.0, 0x00000000);
+
+        val = val.set_prescale(Prescale::P16);
+        assert_eq!(val.prescale(), Prescale::P16);
+        assert_eq!(val.0, 0x00000040);
+        val = val.set_prescale(Prescale::P2);
+        assert_eq!(val.prescale(), Prescale::P2);
+        assert_eq!(val.0, 0x00000010);
+        assert_eq!(Prescale::P8.divisor(), 8);
+
+        val = Control(0x0).set_reload();
+        assert!(!val.is_reload_finished());
+        assert_eq!(val.0, 0x00000002);
+
+        val = Control(0x0).enable();
+        assert!(val.is_enabled());
+        assert_eq!(val.0, 0x00000001);
+        val = val.disable();
+        assert!(!val.is_enabled());
+        assert_eq!(val.0, 0x00000000);
+
+        assert!(Control(0x8000_0000).is_test_mode());
+        assert!(!Control::default().is_test_mode());
+    }
+}
@@ -0,0 +1,142 @@
+//! Hardware spinlock.
+//!
+//! Spinlocks coordinate access to peripherals shared between the RISC-V core and the DSP,
+//! such as a common UART or GPIO bank. A lock is taken by [`HwSpinlock::try_lock`] or
+//! [`HwSpinlock::lock`], and released when the returned guard is dropped:
+//!
+//! ```ignore
+//! let spinlock = HwSpinlock::new(spinlock_peripheral, &ccu);
+//! {
+//!     let _guard = spinlock.lock(UART0_LOCK)?;
+//!     // access UART0 registers shared with DSP firmware.
+//! }
+//! ```
+//!
+//! Locks only exclude processors that agree on lock numbers; they don't protect registers
+//! from code that doesn't take them.
+
+mod register;
+pub use register::*;
+
+use crate::{
+    ccu::{self, ClockGate},
+    time::Deadline,
+};
+use volatile_register::RW;
+
+/// Hardware spinlock error.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Error {
+    /// Lock number is not less than [`LOCKS`].
+    InvalidLock,
+    /// Lock is held by this or another processor.
+    Locked,
+    /// Lock wasn't released in time.
+    Timeout,
+}
+
+/// Managed hardware spinlock structure with peripheral.
+pub struct HwSpinlock<SPINLOCK> {
+    spinlock: SPINLOCK,
+}
+
+impl<SPINLOCK: AsRef<RegisterBlock>> HwSpinlock<SPINLOCK> {
+    /// Create hardware spinlock peripheral.
+    ///
+    /// Spinlock clock is enabled without reset, as remote processor may be holding locks.
+    #[inline]
+    pub fn new(spinlock: SPINLOCK, ccu: &ccu::RegisterBlock) -> Self {
+        unsafe { ccu::SPINLOCK::enable_in(ccu) };
+        Self { spinlock }
+    }
+    /// Take lock `id` if it's free.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidLock`] if `id` is out of range, or [`Error::Locked`] if lock
+    /// is already held, including by this processor.
+    #[inline]
+    pub fn try_lock(&self, id: usize) -> Result<SpinlockGuard<'_>, Error> {
+        let lock = self
+            .spinlock
+            .as_ref()
+            .lock
+            .get(id)
+            .ok_or(Error::InvalidLock)?;
+        if lock.read() == 0 {
+            Ok(SpinlockGuard { lock, id })
+        } else {
+            Err(Error::Locked)
+        }
+    }
+    /// Wait until lock `id` is free, then take it.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidLock`] if `id` is out of range, or [`Error::Timeout`] if lock
+    /// isn't released within crate-wide blocking timeout.
+    #[inline]
+    pub fn lock(&self, id: usize) -> Result<SpinlockGuard<'_>, Error> {
+        let deadline = Deadline::start();
+        loop {
+            match self.try_lock(id) {
+                Err(Error::Locked) => {
+                    deadline.check().map_err(|_| Error::Timeout)?;
+                    core::hint::spin_loop();
+                }
+                result => return result,
+            }
+        }
+    }
+    /// Check if lock `id` is held by any processor.
+    #[inline]
+    pub fn is_locked(&self, id: usize) -> bool {
+        id < LOCKS && self.spinlock.as_ref().status.read() & (1 << id) != 0
+    }
+    /// Release lock `id` without its guard.
+    ///
+    /// # Safety
+    ///
+    /// Processor holding the lock must no longer access the resource it protects, e.g. after
+    /// the DSP was halted while holding it.
+    #[inline]
+    pub unsafe fn force_unlock(&self, id: usize) {
+        if let Some(lock) = self.spinlock.as_ref().lock.get(id) {
+            unsafe { lock.write(0) };
+        }
+    }
+    /// Get spinlock registers, e.g. for critical sections taking a lock.
+    #[cfg(feature = "critical-section-multi-core")]
+    #[inline]
+    pub(crate) fn register_block(&self) -> &RegisterBlock {
+        self.spinlock.as_ref()
+    }
+    /// Release the peripheral.
+    ///
+    /// Spinlock clock is left enabled for remote processor.
+    #[inline]
+    pub fn free(self) -> SPINLOCK {
+        self.spinlock
+    }
+}
+
+/// Held hardware spinlock, released on drop.
+pub struct SpinlockGuard<'a> {
+    lock: &'a RW<u32>,
+    id: usize,
+}
+
+impl SpinlockGuard<'_> {
+    /// Get number of held lock.
+    #[inline]
+    pub fn id(&self) -> usize {
+        self.id
+    }
+}
+
+impl Drop for SpinlockGuard<'_> {
+    #[inline]
+    fn drop(&mut self) {
+        unsafe { self.lock.write(0) };
+    }
+}
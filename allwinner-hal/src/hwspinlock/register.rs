@@ -0,0 +1,58 @@
+use volatile_register::{RO, RW};
+
+/// Number of hardware spinlocks.
+pub const LOCKS: usize = 32;
+
+/// Hardware spinlock registers.
+#[repr(C)]
+pub struct RegisterBlock {
+    /// 0x000 - Spinlock System Status register.
+    pub system_status: RO<SystemStatus>,
+    _reserved0: [u32; 3],
+    /// 0x010 - Spinlock Status register, one bit per lock.
+    pub status: RO<u32>,
+    _reserved1: [u32; 59],
+    /// 0x100 - Spinlock registers, taken on read returning zero and released on writing zero.
+    pub lock: [RW<u32>; LOCKS],
+}
+
+/// Spinlock System Status register.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct SystemStatus(u32);
+
+impl SystemStatus {
+    const LOCKS_NUM: u32 = 0x3 << 28;
+
+    /// Get number of implemented spinlocks, or `None` if field value is reserved.
+    #[inline]
+    pub const fn lock_count(self) -> Option<usize> {
+        match (self.0 & Self::LOCKS_NUM) >> 28 {
+            1 => Some(32),
+            2 => Some(64),
+            3 => Some(128),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{RegisterBlock, SystemStatus};
+    use core::mem::offset_of;
+
+    #[test]
+    fn offset_hwspinlock() {
+        assert_eq!(offset_of!(RegisterBlock, system_status), 0x000);
+        assert_eq!(offset_of!(RegisterBlock, status), 0x010);
+        assert_eq!(offset_of!(RegisterBlock, lock), 0x100);
+    }
+
+    #[test]
+    fn struct_system_status_functions() {
+        assert_eq!(SystemStatus(0x1000_0000).lock_count(), Some(32));
+        assert_eq!(SystemStatus(0x2000_0000).lock_count(), Some(64));
+        assert_eq!(SystemStatus(0x3000_0000).lock_count(), Some(128));
+        assert_eq!(SystemStatus(0x0000_0000).lock_count(), None);
+    }
+}
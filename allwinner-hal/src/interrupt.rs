@@ -0,0 +1,180 @@
+//! Platform-Level Interrupt Controller with typed interrupt numbers.
+//!
+//! [`InterruptController`] drives the PLIC with interrupt numbers of a SoC, such as
+//! [`crate::wafer::d1::Interrupt`], in hart contexts such as [`crate::wafer::d1::Machine`]:
+//!
+//! ```ignore
+//! use allwinner_hal::wafer::d1::{Interrupt, Machine};
+//! let plic = InterruptController::<_, Interrupt>::new(plic);
+//! plic.set_priority(Interrupt::UART0, 1);
+//! plic.set_threshold(Machine, 0);
+//! plic.enable(Interrupt::UART0, Machine);
+//! // in machine external interrupt handler:
+//! while let Some(claim) = plic.claim(Machine) {
+//!     match claim.interrupt() {
+//!         Some(Interrupt::UART0) => serial_handler(),
+//!         _ => {}
+//!     }
+//!     plic.complete(Machine, claim);
+//! }
+//! ```
+//!
+//! [`Dispatcher`] replaces such match with a handler table, and counts spurious interrupts:
+//!
+//! ```ignore
+//! static DISPATCHER: Dispatcher<Interrupt> = Dispatcher::new(&[
+//!     (Interrupt::UART0, serial_handler),
+//!     (Interrupt::TIMER0, timer_handler),
+//! ]);
+//! // in machine external interrupt handler:
+//! DISPATCHER.dispatch(&plic, Machine);
+//! ```
+
+mod dispatch;
+pub use dispatch::*;
+
+use core::{marker::PhantomData, num::NonZeroU32};
+use plic::{HartContext, InterruptSource, Plic};
+
+/// Highest interrupt priority and threshold of T-Head C906 PLIC.
+pub const MAX_PRIORITY: u8 = 31;
+
+/// Interrupt numbers of a SoC.
+pub trait Source: InterruptSource + Copy {
+    /// Convert PLIC interrupt identifier into interrupt number, if known.
+    fn from_id(id: NonZeroU32) -> Option<Self>;
+}
+
+/// Interrupt claimed from PLIC, to be completed after handling.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Claim<I> {
+    id: NonZeroU32,
+    _interrupt: PhantomData<I>,
+}
+
+impl<I: Source> Claim<I> {
+    /// Get claimed interrupt number, or `None` if unknown to this SoC.
+    #[inline]
+    pub fn interrupt(&self) -> Option<I> {
+        I::from_id(self.id)
+    }
+    /// Get raw PLIC interrupt identifier.
+    #[inline]
+    pub const fn id(&self) -> NonZeroU32 {
+        self.id
+    }
+}
+
+impl<I> InterruptSource for Claim<I> {
+    #[inline]
+    fn id(self) -> NonZeroU32 {
+        self.id
+    }
+}
+
+/// Managed PLIC structure with interrupt numbers `I`.
+pub struct InterruptController<PLIC, I> {
+    plic: PLIC,
+    _interrupt: PhantomData<I>,
+}
+
+impl<PLIC: AsRef<Plic>, I: Source> InterruptController<PLIC, I> {
+    /// Create interrupt controller, leaving priorities and enables unchanged.
+    #[inline]
+    pub const fn new(plic: PLIC) -> Self {
+        Self {
+            plic,
+            _interrupt: PhantomData,
+        }
+    }
+    /// Set priority of `interrupt`; priority 0 never interrupts.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `priority` is greater than [`MAX_PRIORITY`].
+    #[inline]
+    pub fn set_priority(&self, interrupt: I, priority: u8) {
+        assert!(priority <= MAX_PRIORITY);
+        self.plic.as_ref().set_priority(interrupt, priority as u32);
+    }
+    /// Get priority of `interrupt`.
+    #[inline]
+    pub fn priority(&self, interrupt: I) -> u8 {
+        self.plic.as_ref().get_priority(interrupt) as u8
+    }
+    /// Set priority threshold of `context`; only interrupts of higher priority are signaled.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `threshold` is greater than [`MAX_PRIORITY`].
+    #[inline]
+    pub fn set_threshold<C: HartContext>(&self, context: C, threshold: u8) {
+        assert!(threshold <= MAX_PRIORITY);
+        self.plic.as_ref().set_threshold(context, threshold as u32);
+    }
+    /// Get priority threshold of `context`.
+    #[inline]
+    pub fn threshold<C: HartContext>(&self, context: C) -> u8 {
+        self.plic.as_ref().get_threshold(context) as u8
+    }
+    /// Enable `interrupt` in `context`.
+    #[inline]
+    pub fn enable<C: HartContext>(&self, interrupt: I, context: C) {
+        self.plic.as_ref().enable(interrupt, context);
+    }
+    /// Disable `interrupt` in `context`.
+    #[inline]
+    pub fn disable<C: HartContext>(&self, interrupt: I, context: C) {
+        self.plic.as_ref().disable(interrupt, context);
+    }
+    /// Check if `interrupt` is enabled in `context`.
+    #[inline]
+    pub fn is_enabled<C: HartContext>(&self, interrupt: I, context: C) -> bool {
+        self.plic.as_ref().is_enabled(interrupt, context)
+    }
+    /// Check if `interrupt` is pending.
+    #[inline]
+    pub fn is_pending(&self, interrupt: I) -> bool {
+        self.plic.as_ref().is_pending(interrupt)
+    }
+    /// Claim highest priority pending interrupt of `context`, if any.
+    #[inline]
+    pub fn claim<C: HartContext>(&self, context: C) -> Option<Claim<I>> {
+        self.plic.as_ref().claim(context).map(|id| Claim {
+            id,
+            _interrupt: PhantomData,
+        })
+    }
+    /// Complete `claim` in `context`, allowing its interrupt to be claimed again.
+    #[inline]
+    pub fn complete<C: HartContext>(&self, context: C, claim: Claim<I>) {
+        self.plic.as_ref().complete(context, claim);
+    }
+    /// Release the PLIC.
+    #[inline]
+    pub fn free(self) -> PLIC {
+        self.plic
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Claim;
+    use crate::wafer::d1::Interrupt;
+    use core::{marker::PhantomData, num::NonZeroU32};
+
+    #[test]
+    fn struct_claim_functions() {
+        let claim = Claim::<Interrupt> {
+            id: NonZeroU32::new(18).unwrap(),
+            _interrupt: PhantomData,
+        };
+        assert_eq!(claim.interrupt(), Some(Interrupt::UART0));
+        assert_eq!(claim.id().get(), 18);
+        let claim = Claim::<Interrupt> {
+            id: NonZeroU32::new(1).unwrap(),
+            _interrupt: PhantomData,
+        };
+        assert_eq!(claim.interrupt(), None);
+    }
+}
@@ -0,0 +1,102 @@
+use super::{Claim, InterruptController, Source};
+use core::sync::atomic::{AtomicU32, Ordering};
+use plic::{HartContext, Plic};
+
+/// Handler for claims without a registered handler.
+pub type DefaultHandler<I> = fn(Claim<I>);
+
+/// Routes claimed interrupts to handlers registered by interrupt number.
+///
+/// Claims without a handler, including unknown interrupt numbers, are counted as spurious
+/// and passed to the default handler; without one, their interrupt is disabled in the
+/// claiming context so that it doesn't fire again.
+pub struct Dispatcher<I: 'static> {
+    handlers: &'static [(I, fn())],
+    default: Option<DefaultHandler<I>>,
+    spurious: AtomicU32,
+}
+
+impl<I: Source + PartialEq> Dispatcher<I> {
+    /// Create dispatcher routing each interrupt number in `handlers` to its function.
+    #[inline]
+    pub const fn new(handlers: &'static [(I, fn())]) -> Self {
+        Self {
+            handlers,
+            default: None,
+            spurious: AtomicU32::new(0),
+        }
+    }
+    /// Use `handler` for claims without a registered handler.
+    #[inline]
+    pub const fn with_default(mut self, handler: DefaultHandler<I>) -> Self {
+        self.default = Some(handler);
+        self
+    }
+    /// Claim, handle and complete interrupts of `context` until none is pending.
+    ///
+    /// Call from external interrupt handler of `context`.
+    #[inline]
+    pub fn dispatch<PLIC: AsRef<Plic>, C: HartContext + Copy>(
+        &self,
+        plic: &InterruptController<PLIC, I>,
+        context: C,
+    ) {
+        while let Some(claim) = plic.claim(context) {
+            match self.handler(claim) {
+                Some(handler) => handler(),
+                None => {
+                    self.spurious.fetch_add(1, Ordering::Relaxed);
+                    match (self.default, claim.interrupt()) {
+                        (Some(default), _) => default(claim),
+                        (None, Some(interrupt)) => plic.disable(interrupt, context),
+                        (None, None) => {}
+                    }
+                }
+            }
+            plic.complete(context, claim);
+        }
+    }
+    /// Get number of claims without a registered handler.
+    #[inline]
+    pub fn spurious_count(&self) -> u32 {
+        self.spurious.load(Ordering::Relaxed)
+    }
+    /// Find registered handler of `claim`.
+    #[inline]
+    fn handler(&self, claim: Claim<I>) -> Option<fn()> {
+        let interrupt = claim.interrupt()?;
+        self.handlers
+            .iter()
+            .find(|(registered, _)| *registered == interrupt)
+            .map(|(_, handler)| *handler)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Dispatcher;
+    use crate::{interrupt::Claim, wafer::d1::Interrupt};
+    use core::{marker::PhantomData, num::NonZeroU32};
+
+    fn uart0() {}
+
+    static DISPATCHER: Dispatcher<Interrupt> = Dispatcher::new(&[(Interrupt::UART0, uart0)]);
+
+    fn claim(id: u32) -> Claim<Interrupt> {
+        Claim {
+            id: NonZeroU32::new(id).unwrap(),
+            _interrupt: PhantomData,
+        }
+    }
+
+    #[test]
+    fn struct_dispatcher_functions() {
+        assert_eq!(
+            DISPATCHER.handler(claim(18)).map(|f| f as usize),
+            Some(uart0 as fn() as usize)
+        );
+        assert!(DISPATCHER.handler(claim(19)).is_none());
+        assert!(DISPATCHER.handler(claim(1)).is_none());
+        assert_eq!(DISPATCHER.spurious_count(), 0);
+    }
+}
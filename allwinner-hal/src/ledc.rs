@@ -0,0 +1,333 @@
+//! LED controller for WS2812-like addressable LEDs.
+//!
+//! LEDC shifts 24-bit pixels out of a 32-entry FIFO on a single data line, encoding each bit
+//! as a high pulse followed by a low pulse. The FIFO is fed by CPU or by DMA.
+
+mod register;
+pub use register::*;
+
+use crate::ccu::{self, ClockConfig, ClockGate, LedcClockSource, PeriFactorN};
+use crate::time::Deadline;
+
+/// Largest number of pixels in one transfer.
+pub const MAX_PIXELS: usize = 1024;
+
+/// Gamma 2.0 correction table, mapping linear brightness to LED duty.
+pub const GAMMA2: [u8; 256] = gamma2_table();
+
+/// Valid LEDC data output pad.
+pub trait Output {}
+
+/// Color of one pixel.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Rgb {
+    /// Red component.
+    pub r: u8,
+    /// Green component.
+    pub g: u8,
+    /// Blue component.
+    pub b: u8,
+}
+
+impl Rgb {
+    /// Create a pixel color from its components.
+    #[inline]
+    pub const fn new(r: u8, g: u8, b: u8) -> Self {
+        Self { r, g, b }
+    }
+}
+
+/// Bit timings of LED data line, in nanoseconds.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Timing {
+    /// High time of a 0 bit.
+    pub t0h: u16,
+    /// Low time of a 0 bit.
+    pub t0l: u16,
+    /// High time of a 1 bit.
+    pub t1h: u16,
+    /// Low time of a 1 bit.
+    pub t1l: u16,
+    /// Low time of reset code latching the data.
+    pub reset: u32,
+}
+
+impl Timing {
+    /// Timings of WS2812B LEDs.
+    pub const WS2812: Timing = Timing {
+        t0h: 400,
+        t0l: 850,
+        t1h: 800,
+        t1l: 450,
+        reset: 300_000,
+    };
+}
+
+impl Default for Timing {
+    #[inline]
+    fn default() -> Self {
+        Self::WS2812
+    }
+}
+
+/// LEDC configuration.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Config {
+    /// Bit timings of data line.
+    pub timing: Timing,
+    /// Color component order expected by the LEDs.
+    pub order: ColorOrder,
+    /// Optional table applied to each color component before sending.
+    pub gamma: Option<&'static [u8; 256]>,
+}
+
+impl Default for Config {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            timing: Timing::WS2812,
+            order: ColorOrder::Grb,
+            gamma: None,
+        }
+    }
+}
+
+/// LEDC error.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Error {
+    /// Number of pixels exceeds [`MAX_PIXELS`].
+    TooManyPixels,
+    /// Transfer did not finish before crate-wide blocking timeout.
+    Timeout,
+}
+
+/// Managed LEDC structure with peripheral and pad.
+pub struct Ledc<LEDC, PAD: Output> {
+    ledc: LEDC,
+    pad: PAD,
+    gamma: Option<&'static [u8; 256]>,
+}
+
+impl<LEDC: AsRef<RegisterBlock>, PAD: Output> Ledc<LEDC, PAD> {
+    /// Create an LEDC instance clocked by 24-MHz 'HOSC' oscillator.
+    #[inline]
+    pub fn new(ledc: LEDC, pad: PAD, config: Config, ccu: &ccu::RegisterBlock) -> Self {
+        // note(unsafe): async read and write using ccu registers
+        unsafe { ccu::LEDC::reconfigure(ccu, LedcClockSource::Hosc, 0, PeriFactorN::N1) };
+        let regs = ledc.as_ref();
+        let timing = config.timing;
+        unsafe {
+            regs.control.write(Control::default().set_soft_reset());
+            while !regs.control.read().is_soft_reset_finished() {
+                core::hint::spin_loop();
+            }
+            regs.t01_timing.write(
+                T01Timing::default()
+                    .set_t1h(ns_to_cycles(timing.t1h as u32) as u8)
+                    .set_t1l(ns_to_cycles(timing.t1l as u32) as u8)
+                    .set_t0h(ns_to_cycles(timing.t0h as u32) as u8)
+                    .set_t0l(ns_to_cycles(timing.t0l as u32) as u8),
+            );
+            regs.reset_timing
+                .write(ResetTiming::default().set_reset_time(ns_to_cycles(timing.reset) as u16));
+            regs.wait_time0.write(0);
+            regs.wait_time1.write(0);
+            regs.dma_control.write(DmaControl::default().disable_dma());
+            regs.int_control.write(IntControl::default());
+            regs.control
+                .write(Control::default().set_color_order(config.order));
+        }
+        Self {
+            ledc,
+            pad,
+            gamma: config.gamma,
+        }
+    }
+    /// Send `pixels` to the LEDs, feeding FIFO by CPU, and wait until they are sent.
+    #[inline]
+    pub fn write_pixels(&mut self, pixels: &[Rgb]) -> Result<(), Error> {
+        if pixels.is_empty() {
+            return Ok(());
+        }
+        self.prepare(pixels.len())?;
+        let regs = self.ledc.as_ref();
+        let (head, tail) = pixels.split_at(pixels.len().min(FIFO_DEPTH));
+        for &pixel in head {
+            unsafe { regs.data.write(self.encode(pixel)) };
+        }
+        self.start(pixels.len());
+        for &pixel in tail {
+            let deadline = Deadline::start();
+            while regs.int_status.read().is_fifo_full() {
+                deadline.check().map_err(|_| Error::Timeout)?;
+                core::hint::spin_loop();
+            }
+            unsafe { regs.data.write(self.encode(pixel)) };
+        }
+        self.wait_for_transfer()
+    }
+    /// Start transfer of `count` pixels fed by DMA.
+    ///
+    /// DMA channel should move words encoded by [`Ledc::encode`] to [`Ledc::data_address`]
+    /// with request port `LEDC_TX`. Call [`Ledc::wait_for_transfer`] to wait for completion.
+    #[inline]
+    pub fn start_dma(&mut self, count: usize) -> Result<(), Error> {
+        self.prepare(count)?;
+        unsafe {
+            self.ledc
+                .as_ref()
+                .dma_control
+                .modify(|val| val.enable_dma())
+        };
+        self.start(count);
+        Ok(())
+    }
+    /// Wait until current transfer finishes.
+    #[inline]
+    pub fn wait_for_transfer(&self) -> Result<(), Error> {
+        let regs = self.ledc.as_ref();
+        let deadline = Deadline::start();
+        while !regs
+            .int_status
+            .read()
+            .has_interrupt(Interrupt::TransferFinish)
+        {
+            deadline.check().map_err(|_| Error::Timeout)?;
+            core::hint::spin_loop();
+        }
+        unsafe {
+            regs.int_status
+                .write(IntStatus::default().clear_interrupt(Interrupt::TransferFinish));
+            regs.dma_control.modify(|val| val.disable_dma());
+        }
+        Ok(())
+    }
+    /// Encode `pixel` into data register word, applying gamma correction.
+    ///
+    /// Color components are reordered by hardware as configured.
+    #[inline]
+    pub fn encode(&self, pixel: Rgb) -> u32 {
+        let Rgb { r, g, b } = match self.gamma {
+            Some(table) => Rgb::new(
+                table[pixel.r as usize],
+                table[pixel.g as usize],
+                table[pixel.b as usize],
+            ),
+            None => pixel,
+        };
+        (r as u32) << 16 | (g as u32) << 8 | b as u32
+    }
+    /// Get bus address of data register, as DMA transfer destination.
+    #[inline]
+    pub fn data_address(&self) -> usize {
+        &self.ledc.as_ref().data as *const _ as usize
+    }
+    /// Enable LEDC `interrupt`.
+    #[inline]
+    pub fn enable_interrupt(&mut self, interrupt: Interrupt) {
+        unsafe {
+            self.ledc
+                .as_ref()
+                .int_control
+                .modify(|val| val.enable_interrupt(interrupt).enable_global_interrupt())
+        };
+    }
+    /// Disable LEDC `interrupt`.
+    #[inline]
+    pub fn disable_interrupt(&mut self, interrupt: Interrupt) {
+        unsafe {
+            self.ledc
+                .as_ref()
+                .int_control
+                .modify(|val| val.disable_interrupt(interrupt))
+        };
+    }
+    /// Check if `interrupt` is pending.
+    #[inline]
+    pub fn has_interrupt(&self, interrupt: Interrupt) -> bool {
+        self.ledc
+            .as_ref()
+            .int_status
+            .read()
+            .has_interrupt(interrupt)
+    }
+    /// Clear pending `interrupt`.
+    #[inline]
+    pub fn clear_interrupt(&mut self, interrupt: Interrupt) {
+        unsafe {
+            self.ledc
+                .as_ref()
+                .int_status
+                .write(IntStatus::default().clear_interrupt(interrupt))
+        };
+    }
+    /// Close LEDC and release peripheral and pad.
+    #[inline]
+    pub fn free(self, ccu: &ccu::RegisterBlock) -> (LEDC, PAD) {
+        unsafe { ccu::LEDC::free(ccu) };
+        (self.ledc, self.pad)
+    }
+    #[inline]
+    fn prepare(&self, count: usize) -> Result<(), Error> {
+        if count > MAX_PIXELS {
+            return Err(Error::TooManyPixels);
+        }
+        let regs = self.ledc.as_ref();
+        unsafe {
+            regs.int_status.write(
+                IntStatus::default()
+                    .clear_interrupt(Interrupt::TransferFinish)
+                    .clear_interrupt(Interrupt::FifoCpuRequest)
+                    .clear_interrupt(Interrupt::WaitDataTimeout)
+                    .clear_interrupt(Interrupt::FifoOverflow),
+            );
+            regs.reset_timing
+                .modify(|val| val.set_led_number(count.saturating_sub(1) as u16));
+        }
+        Ok(())
+    }
+    #[inline]
+    fn start(&self, count: usize) {
+        unsafe {
+            self.ledc
+                .as_ref()
+                .control
+                .modify(|val| val.set_data_length(count as u16).enable())
+        };
+    }
+}
+
+/// Convert nanoseconds to 24-MHz clock cycles, rounding up.
+#[inline]
+const fn ns_to_cycles(ns: u32) -> u32 {
+    (ns as u64 * 24).div_ceil(1000) as u32
+}
+
+const fn gamma2_table() -> [u8; 256] {
+    let mut table = [0u8; 256];
+    let mut i = 0;
+    while i < 256 {
+        table[i] = ((i * i + 127) / 255) as u8;
+        i += 1;
+    }
+    table
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{GAMMA2, ns_to_cycles};
+
+    #[test]
+    fn function_ns_to_cycles() {
+        assert_eq!(ns_to_cycles(400), 10);
+        assert_eq!(ns_to_cycles(850), 21);
+        assert_eq!(ns_to_cycles(300_000), 7200);
+    }
+
+    #[test]
+    fn const_gamma2() {
+        assert_eq!(GAMMA2[0], 0);
+        assert_eq!(GAMMA2[128], 64);
+        assert_eq!(GAMMA2[255], 255);
+    }
+}
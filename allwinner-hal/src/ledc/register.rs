@@ -0,0 +1,480 @@
+use volatile_register::{RO, RW, WO};
+
+/// Depth of LEDC data FIFO in pixels.
+pub const FIFO_DEPTH: usize = 32;
+
+/// LED controller registers.
+#[repr(C)]
+pub struct RegisterBlock {
+    /// 0x00 - LEDC Control Register.
+    pub control: RW<Control>,
+    /// 0x04 - LEDC T0 and T1 Timing Control Register.
+    pub t01_timing: RW<T01Timing>,
+    /// 0x08 - LEDC Data Finish Wait Time 0 Control Register.
+    pub wait_time0: RW<u32>,
+    /// 0x0C - LEDC Reset Timing Control Register.
+    pub reset_timing: RW<ResetTiming>,
+    /// 0x10 - LEDC Data Packet Wait Time 1 Control Register.
+    pub wait_time1: RW<u32>,
+    /// 0x14 - LEDC Data Register.
+    pub data: WO<u32>,
+    /// 0x18 - LEDC DMA Control Register.
+    pub dma_control: RW<DmaControl>,
+    /// 0x1C - LEDC Interrupt Control Register.
+    pub int_control: RW<IntControl>,
+    /// 0x20 - LEDC Interrupt Status Register.
+    pub int_status: RW<IntStatus>,
+    _reserved0: [u32; 3],
+    /// 0x30 ..= 0xAC - LEDC FIFO Data Registers, for debugging.
+    pub fifo_data: [RO<u32>; FIFO_DEPTH],
+}
+
+/// Color component order sent to the LEDs.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ColorOrder {
+    /// Green, red, blue; used by WS2812.
+    Grb,
+    /// Green, blue, red.
+    Gbr,
+    /// Red, green, blue.
+    Rgb,
+    /// Red, blue, green.
+    Rbg,
+    /// Blue, green, red.
+    Bgr,
+    /// Blue, red, green.
+    Brg,
+}
+
+/// LEDC control register.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct Control(u32);
+
+impl Control {
+    const TOTAL_DATA_LENGTH: u32 = 0x1FFF << 16;
+    const RESET_LED_EN: u32 = 1 << 10;
+    const LED_RGB_MODE: u32 = 0x7 << 6;
+    const SOFT_RESET: u32 = 1 << 1;
+    const LEDC_EN: u32 = 1 << 0;
+
+    /// Get number of pixels in current transfer.
+    #[inline]
+    pub const fn data_length(self) -> u16 {
+        ((self.0 & Self::TOTAL_DATA_LENGTH) >> 16) as u16
+    }
+    /// Set number of pixels in current transfer.
+    #[inline]
+    pub const fn set_data_length(self, val: u16) -> Self {
+        Self((self.0 & !Self::TOTAL_DATA_LENGTH) | ((val as u32 & 0x1FFF) << 16))
+    }
+    /// Send a reset code to the LEDs on write.
+    #[inline]
+    pub const fn set_reset_led(self) -> Self {
+        Self(self.0 | Self::RESET_LED_EN)
+    }
+    /// Get color component order.
+    #[inline]
+    pub const fn color_order(self) -> ColorOrder {
+        match (self.0 & Self::LED_RGB_MODE) >> 6 {
+            0 => ColorOrder::Grb,
+            1 => ColorOrder::Gbr,
+            2 => ColorOrder::Rgb,
+            3 => ColorOrder::Rbg,
+            4 => ColorOrder::Bgr,
+            5 => ColorOrder::Brg,
+            _ => panic!("impossible color order"),
+        }
+    }
+    /// Set color component order.
+    #[inline]
+    pub const fn set_color_order(self, val: ColorOrder) -> Self {
+        Self((self.0 & !Self::LED_RGB_MODE) | ((val as u32) << 6))
+    }
+    /// Reset LEDC state machine and FIFO on write.
+    #[inline]
+    pub const fn set_soft_reset(self) -> Self {
+        Self(self.0 | Self::SOFT_RESET)
+    }
+    /// Check if software reset has finished.
+    #[inline]
+    pub const fn is_soft_reset_finished(self) -> bool {
+        self.0 & Self::SOFT_RESET == 0
+    }
+    /// If LEDC is enabled.
+    #[inline]
+    pub const fn is_enabled(self) -> bool {
+        self.0 & Self::LEDC_EN != 0
+    }
+    /// Enable LEDC to start transfer.
+    #[inline]
+    pub const fn enable(self) -> Self {
+        Self(self.0 | Self::LEDC_EN)
+    }
+    /// Disable LEDC.
+    #[inline]
+    pub const fn disable(self) -> Self {
+        Self(self.0 & !Self::LEDC_EN)
+    }
+}
+
+impl Default for Control {
+    #[inline]
+    fn default() -> Self {
+        Self(0x0000_003C)
+    }
+}
+
+/// LEDC T0 and T1 timing control register.
+///
+/// Times are counted in 24-MHz clock cycles, about 42 nanoseconds each.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct T01Timing(u32);
+
+impl T01Timing {
+    const T1H: u32 = 0x3F << 21;
+    const T1L: u32 = 0x1F << 16;
+    const T0H: u32 = 0x1F << 6;
+    const T0L: u32 = 0x3F;
+
+    /// Get high time of a 1 bit.
+    #[inline]
+    pub const fn t1h(self) -> u8 {
+        ((self.0 & Self::T1H) >> 21) as u8
+    }
+    /// Set high time of a 1 bit.
+    #[inline]
+    pub const fn set_t1h(self, val: u8) -> Self {
+        Self((self.0 & !Self::T1H) | ((val as u32 & 0x3F) << 21))
+    }
+    /// Get low time of a 1 bit.
+    #[inline]
+    pub const fn t1l(self) -> u8 {
+        ((self.0 & Self::T1L) >> 16) as u8
+    }
+    /// Set low time of a 1 bit.
+    #[inline]
+    pub const fn set_t1l(self, val: u8) -> Self {
+        Self((self.0 & !Self::T1L) | ((val as u32 & 0x1F) << 16))
+    }
+    /// Get high time of a 0 bit.
+    #[inline]
+    pub const fn t0h(self) -> u8 {
+        ((self.0 & Self::T0H) >> 6) as u8
+    }
+    /// Set high time of a 0 bit.
+    #[inline]
+    pub const fn set_t0h(self, val: u8) -> Self {
+        Self((self.0 & !Self::T0H) | ((val as u32 & 0x1F) << 6))
+    }
+    /// Get low time of a 0 bit.
+    #[inline]
+    pub const fn t0l(self) -> u8 {
+        (self.0 & Self::T0L) as u8
+    }
+    /// Set low time of a 0 bit.
+    #[inline]
+    pub const fn set_t0l(self, val: u8) -> Self {
+        Self((self.0 & !Self::T0L) | (val as u32 & 0x3F))
+    }
+}
+
+impl Default for T01Timing {
+    #[inline]
+    fn default() -> Self {
+        Self(0x0286_01D3)
+    }
+}
+
+/// LEDC reset timing control register.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct ResetTiming(u32);
+
+impl ResetTiming {
+    const TR: u32 = 0x1FFF << 16;
+    const LED_NUM: u32 = 0x3FF;
+
+    /// Get reset code time in 24-MHz clock cycles.
+    #[inline]
+    pub const fn reset_time(self) -> u16 {
+        ((self.0 & Self::TR) >> 16) as u16
+    }
+    /// Set reset code time in 24-MHz clock cycles.
+    #[inline]
+    pub const fn set_reset_time(self, val: u16) -> Self {
+        Self((self.0 & !Self::TR) | ((val as u32 & 0x1FFF) << 16))
+    }
+    /// Get number of LEDs, minus one.
+    #[inline]
+    pub const fn led_number(self) -> u16 {
+        (self.0 & Self::LED_NUM) as u16
+    }
+    /// Set number of LEDs, minus one.
+    #[inline]
+    pub const fn set_led_number(self, val: u16) -> Self {
+        Self((self.0 & !Self::LED_NUM) | (val as u32 & 0x3FF))
+    }
+}
+
+impl Default for ResetTiming {
+    #[inline]
+    fn default() -> Self {
+        Self(0x1D4C_0000)
+    }
+}
+
+/// LEDC DMA control register.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct DmaControl(u32);
+
+impl DmaControl {
+    const DMA_EN: u32 = 1 << 5;
+    const FIFO_TRIG_LEVEL: u32 = 0x1F;
+
+    /// If DMA requests are enabled.
+    #[inline]
+    pub const fn is_dma_enabled(self) -> bool {
+        self.0 & Self::DMA_EN != 0
+    }
+    /// Enable DMA requests.
+    #[inline]
+    pub const fn enable_dma(self) -> Self {
+        Self(self.0 | Self::DMA_EN)
+    }
+    /// Disable DMA requests.
+    #[inline]
+    pub const fn disable_dma(self) -> Self {
+        Self(self.0 & !Self::DMA_EN)
+    }
+    /// Get FIFO level at or below which data is requested.
+    #[inline]
+    pub const fn fifo_trigger_level(self) -> u8 {
+        (self.0 & Self::FIFO_TRIG_LEVEL) as u8
+    }
+    /// Set FIFO level at or below which data is requested.
+    #[inline]
+    pub const fn set_fifo_trigger_level(self, val: u8) -> Self {
+        Self((self.0 & !Self::FIFO_TRIG_LEVEL) | (val as u32 & 0x1F))
+    }
+}
+
+impl Default for DmaControl {
+    #[inline]
+    fn default() -> Self {
+        Self(0x0000_002F)
+    }
+}
+
+/// LEDC interrupt source.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Interrupt {
+    /// All data of current transfer has been sent.
+    TransferFinish = 0,
+    /// FIFO level dropped to trigger level and requests data from CPU.
+    FifoCpuRequest = 1,
+    /// Data wait time 0 or wait time 1 has expired.
+    WaitDataTimeout = 3,
+    /// FIFO overflowed.
+    FifoOverflow = 4,
+}
+
+/// LEDC interrupt control register.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct IntControl(u32);
+
+impl IntControl {
+    const GLOBAL_INT_EN: u32 = 1 << 5;
+
+    /// If `interrupt` is enabled.
+    #[inline]
+    pub const fn is_interrupt_enabled(self, interrupt: Interrupt) -> bool {
+        self.0 & (1 << interrupt as u32) != 0
+    }
+    /// Enable `interrupt`.
+    #[inline]
+    pub const fn enable_interrupt(self, interrupt: Interrupt) -> Self {
+        Self(self.0 | (1 << interrupt as u32))
+    }
+    /// Disable `interrupt`.
+    #[inline]
+    pub const fn disable_interrupt(self, interrupt: Interrupt) -> Self {
+        Self(self.0 & !(1 << interrupt as u32))
+    }
+    /// If LEDC interrupt line is enabled.
+    #[inline]
+    pub const fn is_global_interrupt_enabled(self) -> bool {
+        self.0 & Self::GLOBAL_INT_EN != 0
+    }
+    /// Enable LEDC interrupt line.
+    #[inline]
+    pub const fn enable_global_interrupt(self) -> Self {
+        Self(self.0 | Self::GLOBAL_INT_EN)
+    }
+    /// Disable LEDC interrupt line.
+    #[inline]
+    pub const fn disable_global_interrupt(self) -> Self {
+        Self(self.0 & !Self::GLOBAL_INT_EN)
+    }
+}
+
+impl Default for IntControl {
+    #[inline]
+    fn default() -> Self {
+        Self(0x0000_0000)
+    }
+}
+
+/// LEDC interrupt status register.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct IntStatus(u32);
+
+impl IntStatus {
+    const FIFO_EMPTY: u32 = 1 << 17;
+    const FIFO_FULL: u32 = 1 << 16;
+    const FIFO_WLW: u32 = 0x3F << 10;
+
+    /// If `interrupt` is pending.
+    #[inline]
+    pub const fn has_interrupt(self, interrupt: Interrupt) -> bool {
+        self.0 & (1 << interrupt as u32) != 0
+    }
+    /// Clear pending `interrupt` on write.
+    #[inline]
+    pub const fn clear_interrupt(self, interrupt: Interrupt) -> Self {
+        Self(self.0 | (1 << interrupt as u32))
+    }
+    /// If data FIFO is empty.
+    #[inline]
+    pub const fn is_fifo_empty(self) -> bool {
+        self.0 & Self::FIFO_EMPTY != 0
+    }
+    /// If data FIFO is full.
+    #[inline]
+    pub const fn is_fifo_full(self) -> bool {
+        self.0 & Self::FIFO_FULL != 0
+    }
+    /// Get number of pixels in data FIFO.
+    #[inline]
+    pub const fn fifo_level(self) -> u8 {
+        ((self.0 & Self::FIFO_WLW) >> 10) as u8
+    }
+}
+
+impl Default for IntStatus {
+    #[inline]
+    fn default() -> Self {
+        Self(0x0000_0000)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        ColorOrder, Control, DmaControl, IntControl, IntStatus, Interrupt, RegisterBlock,
+        ResetTiming, T01Timing,
+    };
+    use core::mem::offset_of;
+
+    #[test]
+    fn offset_ledc() {
+        assert_eq!(offset_of!(RegisterBlock, control), 0x00);
+        assert_eq!(offset_of!(RegisterBlock, t01_timing), 0x04);
+        assert_eq!(offset_of!(RegisterBlock, wait_time0), 0x08);
+        assert_eq!(offset_of!(RegisterBlock, reset_timing), 0x0C);
+        assert_eq!(offset_of!(RegisterBlock, wait_time1), 0x10);
+        assert_eq!(offset_of!(RegisterBlock, data), 0x14);
+        assert_eq!(offset_of!(RegisterBlock, dma_control), 0x18);
+        assert_eq!(offset_of!(RegisterBlock, int_control), 0x1C);
+        assert_eq!(offset_of!(RegisterBlock, int_status), 0x20);
+        assert_eq!(offset_of!(RegisterBlock, fifo_data), 0x30);
+    }
+
+    #[test]
+    fn struct_control_functions() {
+        let mut val = Control(0x0);
+        val = val.set_data_length(0x1FFF);
+        assert_eq!(val.data_length(), 0x1FFF);
+        assert_eq!(val.0, 0x1FFF0000);
+
+        val = Control(0x0).set_color_order(ColorOrder::Brg);
+        assert_eq!(val.color_order(), ColorOrder::Brg);
+        assert_eq!(val.0, 0x00000140);
+        assert_eq!(Control::default().color_order(), ColorOrder::Grb);
+
+        val = Control(0x0).set_reset_led();
+        assert_eq!(val.0, 0x00000400);
+
+        val = Control(0x0).set_soft_reset();
+        assert!(!val.is_soft_reset_finished());
+        assert_eq!(val.0, 0x00000002);
+
+        val = Control(0x0).enable();
+        assert!(val.is_enabled());
+        assert_eq!(val.0, 0x00000001);
+        val = val.disable();
+        assert!(!val.is_enabled());
+        assert_eq!(val.0, 0x00000000);
+    }
+
+    #[test]
+    fn struct_timing_functions() {
+        let val = T01Timing::default();
+        assert_eq!(val.t1h(), 0x14);
+        assert_eq!(val.t1l(), 0x06);
+        assert_eq!(val.t0h(), 0x07);
+        assert_eq!(val.t0l(), 0x13);
+        let val = T01Timing(0x0)
+            .set_t1h(0x3F)
+            .set_t1l(0x1F)
+            .set_t0h(0x1F)
+            .set_t0l(0x3F);
+        assert_eq!(val.0, 0x07FF07FF);
+
+        let mut val = ResetTiming(0x0);
+        val = val.set_reset_time(0x1FFF);
+        assert_eq!(val.reset_time(), 0x1FFF);
+        assert_eq!(val.0, 0x1FFF0000);
+        val = val.set_led_number(0x3FF);
+        assert_eq!(val.led_number(), 0x3FF);
+        assert_eq!(val.0, 0x1FFF03FF);
+    }
+
+    #[test]
+    fn struct_dma_interrupt_functions() {
+        let mut val = DmaControl(0x0);
+        val = val.enable_dma();
+        assert!(val.is_dma_enabled());
+        assert_eq!(val.0, 0x00000020);
+        val = val.set_fifo_trigger_level(0xF);
+        assert_eq!(val.fifo_trigger_level(), 0xF);
+        assert_eq!(val.0, 0x0000002F);
+        val = val.disable_dma();
+        assert_eq!(val.0, 0x0000000F);
+
+        let mut val = IntControl::default();
+        val = val.enable_interrupt(Interrupt::TransferFinish);
+        assert!(val.is_interrupt_enabled(Interrupt::TransferFinish));
+        val = val.enable_global_interrupt();
+        assert!(val.is_global_interrupt_enabled());
+        assert_eq!(val.0, 0x00000021);
+        val = val
+            .disable_interrupt(Interrupt::TransferFinish)
+            .disable_global_interrupt();
+        assert_eq!(val.0, 0x00000000);
+
+        let val = IntStatus(0x0003_0811);
+        assert!(val.is_fifo_empty());
+        assert!(val.is_fifo_full());
+        assert_eq!(val.fifo_level(), 2);
+        assert!(val.has_interrupt(Interrupt::TransferFinish));
+        assert!(val.has_interrupt(Interrupt::FifoOverflow));
+        assert!(!val.has_interrupt(Interrupt::FifoCpuRequest));
+        let val = IntStatus::default().clear_interrupt(Interrupt::WaitDataTimeout);
+        assert_eq!(val.0, 0x00000008);
+    }
+}
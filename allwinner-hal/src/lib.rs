@@ -8,16 +8,64 @@
 //! this package with `embedded-hal` ecosystem drivers to provide abundant amount of features.
 #![no_std]
 #[deny(missing_docs)]
+pub mod audio;
+pub mod audio_codec;
+#[cfg(feature = "embedded-can")]
+pub mod can;
 pub mod ccu;
+pub mod ce;
+pub mod cir;
 pub mod com;
+pub mod crc;
+pub mod de;
+pub mod delay;
+pub mod dmac;
+pub mod dmic;
+pub mod dsp;
+pub mod emac;
+#[cfg(feature = "embassy")]
+pub mod embassy;
+pub mod g2d;
 #[macro_use]
 pub mod gpio;
+pub mod gpadc;
+pub mod hdmi;
+pub mod hstimer;
+pub mod hwspinlock;
+pub mod interrupt;
+pub mod ledc;
+pub mod lradc;
+pub mod msgbox;
+pub mod owa;
+pub mod panel;
+pub mod perf;
 pub mod phy;
+pub mod pmic;
+pub mod power;
+pub mod pwm;
+pub mod r_ccu;
+pub mod rtc;
+pub mod secureboot;
+pub mod sid;
+pub mod smc;
 pub mod smhc;
+pub mod softpwm;
 pub mod spi;
+#[cfg(any(
+    feature = "critical-section-single-hart",
+    feature = "critical-section-multi-core"
+))]
+pub mod sync;
 #[doc(hidden)]
 pub mod sysctl;
+pub mod tcon;
+pub mod thermal;
+pub mod ths;
+pub mod time;
+pub mod timer;
 pub mod uart;
+pub mod usb;
+mod waker;
 
 #[doc(hidden)]
 pub mod prelude {
@@ -0,0 +1,160 @@
+//! Low Rate Analog-to-Digital Converter.
+//!
+//! LRADC samples a resistor ladder of keys on its dedicated input into 6-bit levels, and
+//! raises interrupts on key down, key hold and key up, so that several buttons share one
+//! analog pad. [`KeyMap`] maps level bands to logical buttons and turns those interrupts
+//! into press and release events.
+
+mod keymap;
+mod register;
+pub use keymap::*;
+pub use register::*;
+
+use crate::ccu::{self, ClockGate};
+
+/// Largest level of a sample, at reference voltage.
+pub const MAX_LEVEL: u8 = 0x3F;
+
+const KEY_INTERRUPTS: [Interrupt; 4] = [
+    Interrupt::KeyDown,
+    Interrupt::Hold,
+    Interrupt::AlreadyHold,
+    Interrupt::KeyUp,
+];
+
+/// LRADC configuration.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Config {
+    /// Key sampling rate.
+    pub sample_rate: SampleRate,
+    /// Number of samples skipped after key down, letting level settle.
+    pub first_convert_delay: u8,
+    /// Raise hold interrupts while a key stays pressed.
+    pub hold: bool,
+}
+
+impl Default for Config {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            sample_rate: SampleRate::Hz250,
+            first_convert_delay: 2,
+            hold: true,
+        }
+    }
+}
+
+/// Managed LRADC structure with peripheral.
+pub struct Lradc<LRADC> {
+    lradc: LRADC,
+}
+
+impl<LRADC: AsRef<RegisterBlock>> Lradc<LRADC> {
+    /// Create an LRADC instance detecting keys, with all interrupts disabled.
+    #[inline]
+    pub fn new(lradc: LRADC, config: Config, ccu: &ccu::RegisterBlock) -> Self {
+        // note(unsafe): async read and write using ccu registers
+        unsafe { ccu::LRADC::reset(ccu) };
+        let regs = lradc.as_ref();
+        let control = Control::default()
+            .set_first_convert_delay(config.first_convert_delay)
+            .set_key_mode(KeyMode::Normal)
+            .set_level_ab_count(1)
+            .set_sample_rate(config.sample_rate);
+        let control = if config.hold {
+            control.enable_hold()
+        } else {
+            control.disable_hold()
+        };
+        unsafe {
+            regs.int_control.write(IntControl::default());
+            regs.int_status.write(
+                KEY_INTERRUPTS
+                    .iter()
+                    .fold(IntStatus::default(), |val, &i| val.clear_interrupt(i))
+                    .clear_interrupt(Interrupt::Data),
+            );
+            regs.control.write(control.enable());
+        }
+        Self { lradc }
+    }
+    /// Get latest sampled level, from 0 to [`MAX_LEVEL`].
+    #[inline]
+    pub fn level(&self) -> u8 {
+        (self.lradc.as_ref().data.read() & MAX_LEVEL as u32) as u8
+    }
+    /// Enable `interrupt`.
+    #[inline]
+    pub fn enable_interrupt(&mut self, interrupt: Interrupt) {
+        unsafe {
+            self.lradc
+                .as_ref()
+                .int_control
+                .modify(|val| val.enable_interrupt(interrupt))
+        };
+    }
+    /// Disable `interrupt`.
+    #[inline]
+    pub fn disable_interrupt(&mut self, interrupt: Interrupt) {
+        unsafe {
+            self.lradc
+                .as_ref()
+                .int_control
+                .modify(|val| val.disable_interrupt(interrupt))
+        };
+    }
+    /// Check if `interrupt` is pending.
+    #[inline]
+    pub fn has_interrupt(&self, interrupt: Interrupt) -> bool {
+        self.lradc
+            .as_ref()
+            .int_status
+            .read()
+            .has_interrupt(interrupt)
+    }
+    /// Clear pending `interrupt`.
+    #[inline]
+    pub fn clear_interrupt(&mut self, interrupt: Interrupt) {
+        unsafe {
+            self.lradc
+                .as_ref()
+                .int_status
+                .write(IntStatus::default().clear_interrupt(interrupt))
+        };
+    }
+    /// Consume pending key interrupts into `keys` and return the next key event, if any.
+    ///
+    /// Call this function from LRADC interrupt handler, or periodically, until it returns
+    /// `None`.
+    #[inline]
+    pub fn poll<K: Copy + PartialEq>(&mut self, keys: &mut KeyMap<'_, K>) -> Option<KeyEvent<K>> {
+        let regs = self.lradc.as_ref();
+        let status = regs.int_status.read();
+        if KEY_INTERRUPTS[..3].iter().any(|&i| status.has_interrupt(i)) {
+            keys.press(self.level());
+        }
+        if status.has_interrupt(Interrupt::KeyUp) {
+            keys.release();
+        }
+        unsafe {
+            regs.int_status.write(
+                KEY_INTERRUPTS
+                    .iter()
+                    .filter(|&&i| status.has_interrupt(i))
+                    .fold(IntStatus::default(), |val, &i| val.clear_interrupt(i)),
+            )
+        };
+        keys.next_event()
+    }
+    /// Close LRADC and release peripheral.
+    #[inline]
+    pub fn free(self, ccu: &ccu::RegisterBlock) -> LRADC {
+        let regs = self.lradc.as_ref();
+        unsafe {
+            regs.int_control.write(IntControl::default());
+            regs.control.modify(|val| val.disable());
+            ccu::LRADC::free(ccu);
+        }
+        self.lradc
+    }
+}
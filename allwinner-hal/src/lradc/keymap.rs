@@ -0,0 +1,142 @@
+/// Level band of one logical key on a resistor ladder.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct KeyBand<K> {
+    /// Logical key reported for levels in this band.
+    pub key: K,
+    /// Lowest level of this band, inclusive.
+    pub low: u8,
+    /// Highest level of this band, inclusive.
+    pub high: u8,
+}
+
+impl<K> KeyBand<K> {
+    /// Create a band reporting `key` for levels from `low` to `high`, inclusive.
+    #[inline]
+    pub const fn new(key: K, low: u8, high: u8) -> Self {
+        Self { key, low, high }
+    }
+}
+
+/// Key press or release event.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum KeyEvent<K> {
+    /// Key is pressed.
+    Press(K),
+    /// Key is released.
+    Release(K),
+}
+
+/// Maps LRADC levels to logical keys and tracks pressed key.
+///
+/// Key down and key up are fed by [`press`](Self::press) and [`release`](Self::release),
+/// then drained as events from [`next_event`](Self::next_event). Moving from one key to
+/// another without releasing reports a release of the former before a press of the latter.
+#[derive(Clone, Debug)]
+pub struct KeyMap<'a, K> {
+    bands: &'a [KeyBand<K>],
+    current: Option<K>,
+    reported: Option<K>,
+    release_pending: bool,
+}
+
+impl<'a, K: Copy + PartialEq> KeyMap<'a, K> {
+    /// Create a key map from level `bands`; the first band containing a level wins.
+    #[inline]
+    pub const fn new(bands: &'a [KeyBand<K>]) -> Self {
+        Self {
+            bands,
+            current: None,
+            reported: None,
+            release_pending: false,
+        }
+    }
+    /// Get logical key of `level`, if any band contains it.
+    #[inline]
+    pub fn lookup(&self, level: u8) -> Option<K> {
+        self.bands
+            .iter()
+            .find(|band| band.low <= level && level <= band.high)
+            .map(|band| band.key)
+    }
+    /// Record key down or hold at `level`.
+    ///
+    /// Levels outside every band are treated as no key pressed.
+    #[inline]
+    pub fn press(&mut self, level: u8) {
+        self.current = self.lookup(level);
+        self.release_pending = false;
+    }
+    /// Record key up, after any key down recorded before it.
+    #[inline]
+    pub fn release(&mut self) {
+        self.release_pending = true;
+    }
+    /// Get key pressed as of last reported event.
+    #[inline]
+    pub fn pressed(&self) -> Option<K> {
+        self.reported
+    }
+    /// Take next press or release event, if any.
+    #[inline]
+    pub fn next_event(&mut self) -> Option<KeyEvent<K>> {
+        if self.reported != self.current {
+            if let Some(key) = self.reported.take() {
+                return Some(KeyEvent::Release(key));
+            }
+            self.reported = self.current;
+            return self.current.map(KeyEvent::Press);
+        }
+        if self.release_pending {
+            self.release_pending = false;
+            self.current = None;
+            return self.reported.take().map(KeyEvent::Release);
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{KeyBand, KeyEvent, KeyMap};
+
+    #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+    enum Button {
+        Up,
+        Down,
+    }
+
+    const BANDS: [KeyBand<Button>; 2] = [
+        KeyBand::new(Button::Up, 5, 12),
+        KeyBand::new(Button::Down, 20, 28),
+    ];
+
+    #[test]
+    fn struct_key_map() {
+        let mut map = KeyMap::new(&BANDS);
+        assert_eq!(map.lookup(12), Some(Button::Up));
+        assert_eq!(map.lookup(15), None);
+        assert_eq!(map.next_event(), None);
+
+        map.press(8);
+        assert_eq!(map.next_event(), Some(KeyEvent::Press(Button::Up)));
+        assert_eq!(map.next_event(), None);
+        map.press(9);
+        assert_eq!(map.next_event(), None);
+        assert_eq!(map.pressed(), Some(Button::Up));
+
+        map.press(24);
+        assert_eq!(map.next_event(), Some(KeyEvent::Release(Button::Up)));
+        assert_eq!(map.next_event(), Some(KeyEvent::Press(Button::Down)));
+        map.release();
+        assert_eq!(map.next_event(), Some(KeyEvent::Release(Button::Down)));
+        assert_eq!(map.next_event(), None);
+
+        // short tap within one poll
+        map.press(6);
+        map.release();
+        assert_eq!(map.next_event(), Some(KeyEvent::Press(Button::Up)));
+        assert_eq!(map.next_event(), Some(KeyEvent::Release(Button::Up)));
+        assert_eq!(map.next_event(), None);
+        assert_eq!(map.pressed(), None);
+    }
+}
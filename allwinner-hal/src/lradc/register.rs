@@ -0,0 +1,259 @@
+use volatile_register::{RO, RW};
+
+/// Low rate ADC registers.
+#[repr(C)]
+pub struct RegisterBlock {
+    /// 0x00 - LRADC Control Register.
+    pub control: RW<Control>,
+    /// 0x04 - LRADC Interrupt Control Register.
+    pub int_control: RW<IntControl>,
+    /// 0x08 - LRADC Interrupt Status Register.
+    pub int_status: RW<IntStatus>,
+    /// 0x0C - LRADC Data Register.
+    pub data: RO<u32>,
+}
+
+/// LRADC key detection mode.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum KeyMode {
+    /// Report key down, hold and key up.
+    Normal,
+    /// Report one sample per key press.
+    Single,
+    /// Report samples continuously while key is held.
+    Continuous,
+}
+
+/// LRADC sample rate.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum SampleRate {
+    /// 250 samples per second.
+    Hz250,
+    /// 125 samples per second.
+    Hz125,
+    /// 62.5 samples per second.
+    Hz62_5,
+    /// 32.25 samples per second.
+    Hz32_25,
+}
+
+/// LRADC control register.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct Control(u32);
+
+impl Control {
+    const FIRST_CONVERT_DLY: u32 = 0xFF << 24;
+    const KEY_MODE_SELECT: u32 = 0x3 << 12;
+    const LEVELA_B_CNT: u32 = 0xF << 8;
+    const LRADC_HOLD_EN: u32 = 1 << 6;
+    const LRADC_SAMPLE_RATE: u32 = 0x3 << 2;
+    const LRADC_EN: u32 = 1 << 0;
+
+    /// Get number of samples skipped after key down.
+    #[inline]
+    pub const fn first_convert_delay(self) -> u8 {
+        ((self.0 & Self::FIRST_CONVERT_DLY) >> 24) as u8
+    }
+    /// Set number of samples skipped after key down.
+    #[inline]
+    pub const fn set_first_convert_delay(self, val: u8) -> Self {
+        Self((self.0 & !Self::FIRST_CONVERT_DLY) | ((val as u32) << 24))
+    }
+    /// Get key detection mode.
+    #[inline]
+    pub const fn key_mode(self) -> KeyMode {
+        match (self.0 & Self::KEY_MODE_SELECT) >> 12 {
+            0 => KeyMode::Normal,
+            1 => KeyMode::Single,
+            2 => KeyMode::Continuous,
+            _ => panic!("impossible key mode"),
+        }
+    }
+    /// Set key detection mode.
+    #[inline]
+    pub const fn set_key_mode(self, val: KeyMode) -> Self {
+        Self((self.0 & !Self::KEY_MODE_SELECT) | ((val as u32) << 12))
+    }
+    /// Get number of stable samples, minus one, from level A to level B.
+    #[inline]
+    pub const fn level_ab_count(self) -> u8 {
+        ((self.0 & Self::LEVELA_B_CNT) >> 8) as u8
+    }
+    /// Set number of stable samples, minus one, from level A to level B.
+    #[inline]
+    pub const fn set_level_ab_count(self, val: u8) -> Self {
+        Self((self.0 & !Self::LEVELA_B_CNT) | ((val as u32 & 0xF) << 8))
+    }
+    /// If hold detection is enabled.
+    #[inline]
+    pub const fn is_hold_enabled(self) -> bool {
+        self.0 & Self::LRADC_HOLD_EN != 0
+    }
+    /// Enable hold detection.
+    #[inline]
+    pub const fn enable_hold(self) -> Self {
+        Self(self.0 | Self::LRADC_HOLD_EN)
+    }
+    /// Disable hold detection.
+    #[inline]
+    pub const fn disable_hold(self) -> Self {
+        Self(self.0 & !Self::LRADC_HOLD_EN)
+    }
+    /// Get sample rate.
+    #[inline]
+    pub const fn sample_rate(self) -> SampleRate {
+        match (self.0 & Self::LRADC_SAMPLE_RATE) >> 2 {
+            0 => SampleRate::Hz250,
+            1 => SampleRate::Hz125,
+            2 => SampleRate::Hz62_5,
+            _ => SampleRate::Hz32_25,
+        }
+    }
+    /// Set sample rate.
+    #[inline]
+    pub const fn set_sample_rate(self, val: SampleRate) -> Self {
+        Self((self.0 & !Self::LRADC_SAMPLE_RATE) | ((val as u32) << 2))
+    }
+    /// If LRADC is enabled.
+    #[inline]
+    pub const fn is_enabled(self) -> bool {
+        self.0 & Self::LRADC_EN != 0
+    }
+    /// Enable LRADC.
+    #[inline]
+    pub const fn enable(self) -> Self {
+        Self(self.0 | Self::LRADC_EN)
+    }
+    /// Disable LRADC.
+    #[inline]
+    pub const fn disable(self) -> Self {
+        Self(self.0 & !Self::LRADC_EN)
+    }
+}
+
+impl Default for Control {
+    #[inline]
+    fn default() -> Self {
+        Self(0x0100_0168)
+    }
+}
+
+/// LRADC interrupt.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Interrupt {
+    /// New sample is available.
+    Data = 0,
+    /// Key is pressed.
+    KeyDown = 1,
+    /// Key is held.
+    Hold = 2,
+    /// Key was already held when hold detection started.
+    AlreadyHold = 3,
+    /// Key is released.
+    KeyUp = 4,
+}
+
+/// LRADC interrupt control register.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct IntControl(u32);
+
+impl IntControl {
+    /// Check if `interrupt` is enabled.
+    #[inline]
+    pub const fn is_interrupt_enabled(self, interrupt: Interrupt) -> bool {
+        self.0 & (1 << interrupt as u32) != 0
+    }
+    /// Enable `interrupt`.
+    #[inline]
+    pub const fn enable_interrupt(self, interrupt: Interrupt) -> Self {
+        Self(self.0 | (1 << interrupt as u32))
+    }
+    /// Disable `interrupt`.
+    #[inline]
+    pub const fn disable_interrupt(self, interrupt: Interrupt) -> Self {
+        Self(self.0 & !(1 << interrupt as u32))
+    }
+}
+
+impl Default for IntControl {
+    #[inline]
+    fn default() -> Self {
+        Self(0x0000_0000)
+    }
+}
+
+/// LRADC interrupt status register.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct IntStatus(u32);
+
+impl IntStatus {
+    /// Check if `interrupt` is pending.
+    #[inline]
+    pub const fn has_interrupt(self, interrupt: Interrupt) -> bool {
+        self.0 & (1 << interrupt as u32) != 0
+    }
+    /// Clear pending `interrupt` on write.
+    #[inline]
+    pub const fn clear_interrupt(self, interrupt: Interrupt) -> Self {
+        Self(self.0 | (1 << interrupt as u32))
+    }
+}
+
+impl Default for IntStatus {
+    #[inline]
+    fn default() -> Self {
+        Self(0x0000_0000)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Control, IntControl, IntStatus, Interrupt, KeyMode, RegisterBlock, SampleRate};
+    use core::mem::offset_of;
+
+    #[test]
+    fn offset_lradc() {
+        assert_eq!(offset_of!(RegisterBlock, control), 0x00);
+        assert_eq!(offset_of!(RegisterBlock, int_control), 0x04);
+        assert_eq!(offset_of!(RegisterBlock, int_status), 0x08);
+        assert_eq!(offset_of!(RegisterBlock, data), 0x0C);
+    }
+
+    #[test]
+    fn struct_control_functions() {
+        let val = Control::default();
+        assert_eq!(val.first_convert_delay(), 1);
+        assert_eq!(val.level_ab_count(), 1);
+        assert!(val.is_hold_enabled());
+        assert_eq!(val.sample_rate(), SampleRate::Hz62_5);
+        assert_eq!(val.key_mode(), KeyMode::Normal);
+
+        let val = Control(0x0)
+            .set_first_convert_delay(2)
+            .set_key_mode(KeyMode::Continuous)
+            .set_level_ab_count(1)
+            .enable_hold()
+            .set_sample_rate(SampleRate::Hz250)
+            .enable();
+        assert!(val.is_enabled());
+        assert_eq!(val.0, 0x0200_2141);
+        assert_eq!(val.disable_hold().disable().0, 0x0200_2100);
+    }
+
+    #[test]
+    fn struct_interrupt_functions() {
+        let val = IntControl::default()
+            .enable_interrupt(Interrupt::KeyDown)
+            .enable_interrupt(Interrupt::KeyUp);
+        assert!(val.is_interrupt_enabled(Interrupt::KeyUp));
+        assert_eq!(val.0, 0x0000_0012);
+        assert_eq!(val.disable_interrupt(Interrupt::KeyDown).0, 0x0000_0010);
+
+        let val = IntStatus::default().clear_interrupt(Interrupt::Hold);
+        assert!(val.has_interrupt(Interrupt::Hold));
+        assert_eq!(val.0, 0x0000_0004);
+    }
+}
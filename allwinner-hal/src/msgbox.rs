@@ -0,0 +1,240 @@
+//! Message Box inter-processor mailbox.
+//!
+//! Each processor owns a message box holding 32-bit message FIFOs from every remote user.
+//! A processor receives from its own message box, and transmits into the message box of the
+//! remote processor; [`Msgbox`] takes both to run [`CHANNELS`] channels in each direction:
+//!
+//! ```ignore
+//! let config = Config { local_user: 1, remote_user: 0 };
+//! let mut msgbox = Msgbox::new(riscv_msgbox, dsp_msgbox, config, &ccu);
+//! msgbox.send(0, 0x1234_5678)?;
+//! let reply = msgbox.receive(1)?;
+//! ```
+//!
+//! Larger messages are carried in rpmsg-lite style frames of [`FrameHeader`] and payload
+//! by [`Msgbox::send_frame`] and [`Msgbox::receive_frame`].
+
+mod register;
+mod rpmsg;
+pub use register::*;
+pub use rpmsg::*;
+
+use crate::{
+    ccu::{self, ClockGate},
+    time::Deadline,
+};
+
+/// Message box error.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Error {
+    /// Channel index is not less than [`CHANNELS`].
+    InvalidChannel,
+    /// Message FIFO didn't become ready in time.
+    Timeout,
+    /// Frame payload exceeds [`MAX_PAYLOAD`] or receive buffer.
+    FrameTooLarge,
+}
+
+/// Message box configuration.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Config {
+    /// User index of this processor in remote message box.
+    pub local_user: usize,
+    /// User index of remote processor in local message box.
+    pub remote_user: usize,
+}
+
+/// Managed message box channels between this processor and a remote one.
+pub struct Msgbox<LOCAL, REMOTE> {
+    local: LOCAL,
+    remote: REMOTE,
+    config: Config,
+}
+
+impl<LOCAL: AsRef<RegisterBlock>, REMOTE: AsRef<RegisterBlock>> Msgbox<LOCAL, REMOTE> {
+    /// Create message box channels with all interrupts disabled.
+    ///
+    /// Message box clocks are enabled without reset, as remote processor may be using them.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a user index of `config` is not less than [`USERS`].
+    #[inline]
+    pub fn new(local: LOCAL, remote: REMOTE, config: Config, ccu: &ccu::RegisterBlock) -> Self {
+        assert!(config.local_user < USERS && config.remote_user < USERS);
+        unsafe {
+            ccu::MSGBOX::<0>::enable_in(ccu);
+            ccu::MSGBOX::<1>::enable_in(ccu);
+            let user = &local.as_ref().users[config.remote_user];
+            user.rx_interrupt_enable.write(Interrupts::default());
+            user.tx_interrupt_enable.write(Interrupts::default());
+        }
+        Self {
+            local,
+            remote,
+            config,
+        }
+    }
+    /// Push `message` to remote processor on `channel` if its FIFO is not full.
+    #[inline]
+    pub fn try_send(&mut self, channel: usize, message: u32) -> nb::Result<(), Error> {
+        check_channel(channel)?;
+        let user = self.tx_user();
+        if user.fifo_status[channel].read().is_full() {
+            return Err(nb::Error::WouldBlock);
+        }
+        unsafe { user.message[channel].write(message) };
+        Ok(())
+    }
+    /// Pop message from remote processor on `channel` if any.
+    #[inline]
+    pub fn try_receive(&mut self, channel: usize) -> nb::Result<u32, Error> {
+        check_channel(channel)?;
+        let user = self.rx_user();
+        if user.message_status[channel].read().message_count() == 0 {
+            return Err(nb::Error::WouldBlock);
+        }
+        Ok(user.message[channel].read())
+    }
+    /// Wait until FIFO of `channel` has room, then push `message`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Timeout`] if remote processor doesn't drain FIFO within crate-wide
+    /// blocking timeout.
+    #[inline]
+    pub fn send(&mut self, channel: usize, message: u32) -> Result<(), Error> {
+        let deadline = Deadline::start();
+        loop {
+            match self.try_send(channel, message) {
+                Ok(()) => return Ok(()),
+                Err(nb::Error::Other(e)) => return Err(e),
+                Err(nb::Error::WouldBlock) => {
+                    deadline.check().map_err(|_| Error::Timeout)?;
+                    core::hint::spin_loop();
+                }
+            }
+        }
+    }
+    /// Wait for a message on `channel`, then pop it.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Timeout`] if no message arrives within crate-wide blocking timeout.
+    #[inline]
+    pub fn receive(&mut self, channel: usize) -> Result<u32, Error> {
+        let deadline = Deadline::start();
+        loop {
+            match self.try_receive(channel) {
+                Ok(message) => return Ok(message),
+                Err(nb::Error::Other(e)) => return Err(e),
+                Err(nb::Error::WouldBlock) => {
+                    deadline.check().map_err(|_| Error::Timeout)?;
+                    core::hint::spin_loop();
+                }
+            }
+        }
+    }
+    /// Enable interrupt on messages received on `channel`.
+    #[inline]
+    pub fn enable_receive_interrupt(&mut self, channel: usize) -> Result<(), Error> {
+        check_channel(channel)?;
+        let enable = &self.rx_user().rx_interrupt_enable;
+        unsafe { enable.modify(|val| val.enable_receive(channel)) };
+        Ok(())
+    }
+    /// Disable interrupt on messages received on `channel`.
+    #[inline]
+    pub fn disable_receive_interrupt(&mut self, channel: usize) -> Result<(), Error> {
+        check_channel(channel)?;
+        let enable = &self.rx_user().rx_interrupt_enable;
+        unsafe { enable.modify(|val| val.disable_receive(channel)) };
+        Ok(())
+    }
+    /// Check if receive interrupt of `channel` is pending.
+    #[inline]
+    pub fn is_receive_pending(&self, channel: usize) -> bool {
+        channel < CHANNELS
+            && self
+                .rx_user()
+                .rx_interrupt_status
+                .read()
+                .has_receive(channel)
+    }
+    /// Clear pending receive interrupt of `channel`.
+    ///
+    /// Interrupt is raised again while FIFO still holds messages.
+    #[inline]
+    pub fn clear_receive_pending(&mut self, channel: usize) -> Result<(), Error> {
+        check_channel(channel)?;
+        let status = &self.rx_user().rx_interrupt_status;
+        unsafe { status.write(Interrupts::default().enable_receive(channel)) };
+        Ok(())
+    }
+    /// Enable interrupt on FIFO of `channel` towards remote processor draining.
+    #[inline]
+    pub fn enable_transmit_interrupt(&mut self, channel: usize) -> Result<(), Error> {
+        check_channel(channel)?;
+        let enable = &self.rx_user().tx_interrupt_enable;
+        unsafe { enable.modify(|val| val.enable_transmit(channel)) };
+        Ok(())
+    }
+    /// Disable interrupt on FIFO of `channel` towards remote processor draining.
+    #[inline]
+    pub fn disable_transmit_interrupt(&mut self, channel: usize) -> Result<(), Error> {
+        check_channel(channel)?;
+        let enable = &self.rx_user().tx_interrupt_enable;
+        unsafe { enable.modify(|val| val.disable_transmit(channel)) };
+        Ok(())
+    }
+    /// Check if transmit interrupt of `channel` is pending.
+    #[inline]
+    pub fn is_transmit_pending(&self, channel: usize) -> bool {
+        channel < CHANNELS
+            && self
+                .rx_user()
+                .tx_interrupt_status
+                .read()
+                .has_transmit(channel)
+    }
+    /// Clear pending transmit interrupt of `channel`.
+    #[inline]
+    pub fn clear_transmit_pending(&mut self, channel: usize) -> Result<(), Error> {
+        check_channel(channel)?;
+        let status = &self.rx_user().tx_interrupt_status;
+        unsafe { status.write(Interrupts::default().enable_transmit(channel)) };
+        Ok(())
+    }
+    /// Disable interrupts and release message boxes.
+    ///
+    /// Message box clocks are left enabled for remote processor.
+    #[inline]
+    pub fn free(self) -> (LOCAL, REMOTE) {
+        let user = self.rx_user();
+        unsafe {
+            user.rx_interrupt_enable.write(Interrupts::default());
+            user.tx_interrupt_enable.write(Interrupts::default());
+        }
+        (self.local, self.remote)
+    }
+    /// Registers of channels from remote processor in local message box.
+    #[inline]
+    fn rx_user(&self) -> &UserBlock {
+        &self.local.as_ref().users[self.config.remote_user]
+    }
+    /// Registers of channels from this processor in remote message box.
+    #[inline]
+    fn tx_user(&self) -> &UserBlock {
+        &self.remote.as_ref().users[self.config.local_user]
+    }
+}
+
+/// Return an error if `channel` is out of range.
+#[inline]
+fn check_channel(channel: usize) -> Result<(), Error> {
+    if channel < CHANNELS {
+        Ok(())
+    } else {
+        Err(Error::InvalidChannel)
+    }
+}
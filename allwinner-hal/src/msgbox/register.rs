@@ -0,0 +1,159 @@
+use volatile_register::{RO, RW};
+
+/// Number of channels between two users.
+pub const CHANNELS: usize = 4;
+
+/// Number of remote users of a message box.
+pub const USERS: usize = 2;
+
+/// Message box registers.
+#[repr(C)]
+pub struct RegisterBlock {
+    /// Registers of channels with each remote user.
+    pub users: [UserBlock; USERS],
+}
+
+/// Message box registers of channels with one remote user.
+#[repr(C)]
+pub struct UserBlock {
+    _reserved0: [u32; 8],
+    /// 0x20 - Receive Interrupt Enable register.
+    pub rx_interrupt_enable: RW<Interrupts>,
+    /// 0x24 - Receive Interrupt Status register.
+    pub rx_interrupt_status: RW<Interrupts>,
+    _reserved1: [u32; 2],
+    /// 0x30 - Transmit Interrupt Enable register.
+    pub tx_interrupt_enable: RW<Interrupts>,
+    /// 0x34 - Transmit Interrupt Status register.
+    pub tx_interrupt_status: RW<Interrupts>,
+    _reserved2: [u32; 2],
+    /// 0x40 - Debug register.
+    pub debug: RW<u32>,
+    _reserved3: [u32; 3],
+    /// 0x50 - FIFO Status registers.
+    pub fifo_status: [RO<FifoStatus>; CHANNELS],
+    /// 0x60 - Message Status registers.
+    pub message_status: [RO<MessageStatus>; CHANNELS],
+    /// 0x70 - Message FIFO registers, pushed on write and popped on read.
+    pub message: [RW<u32>; CHANNELS],
+    /// 0x80 - Transmit Interrupt Threshold registers.
+    pub tx_threshold: [RW<u32>; CHANNELS],
+    _reserved4: [u32; 28],
+}
+
+/// Message box interrupt enable and status register.
+///
+/// Receive interrupts of channel `c` are at bit `2c`, transmit interrupts at bit `2c + 1`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct Interrupts(u32);
+
+impl Interrupts {
+    /// Check if receive interrupt of `channel` is enabled or pending.
+    #[inline]
+    pub const fn has_receive(self, channel: usize) -> bool {
+        self.0 & (1 << (2 * channel)) != 0
+    }
+    /// Enable receive interrupt of `channel`, or clear it when written to status.
+    #[inline]
+    pub const fn enable_receive(self, channel: usize) -> Self {
+        Self(self.0 | (1 << (2 * channel)))
+    }
+    /// Disable receive interrupt of `channel`.
+    #[inline]
+    pub const fn disable_receive(self, channel: usize) -> Self {
+        Self(self.0 & !(1 << (2 * channel)))
+    }
+    /// Check if transmit interrupt of `channel` is enabled or pending.
+    #[inline]
+    pub const fn has_transmit(self, channel: usize) -> bool {
+        self.0 & (1 << (2 * channel + 1)) != 0
+    }
+    /// Enable transmit interrupt of `channel`, or clear it when written to status.
+    #[inline]
+    pub const fn enable_transmit(self, channel: usize) -> Self {
+        Self(self.0 | (1 << (2 * channel + 1)))
+    }
+    /// Disable transmit interrupt of `channel`.
+    #[inline]
+    pub const fn disable_transmit(self, channel: usize) -> Self {
+        Self(self.0 & !(1 << (2 * channel + 1)))
+    }
+}
+
+impl Default for Interrupts {
+    #[inline]
+    fn default() -> Self {
+        Self(0)
+    }
+}
+
+/// Message box FIFO status register.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct FifoStatus(u32);
+
+impl FifoStatus {
+    const FIFO_FULL: u32 = 1 << 0;
+
+    /// Check if message FIFO is full.
+    #[inline]
+    pub const fn is_full(self) -> bool {
+        self.0 & Self::FIFO_FULL != 0
+    }
+}
+
+/// Message box message status register.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct MessageStatus(u32);
+
+impl MessageStatus {
+    const MSG_NUM: u32 = 0xF;
+
+    /// Get number of messages in FIFO.
+    #[inline]
+    pub const fn message_count(self) -> u8 {
+        (self.0 & Self::MSG_NUM) as u8
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{FifoStatus, Interrupts, MessageStatus, RegisterBlock, UserBlock};
+    use core::mem::{offset_of, size_of};
+
+    #[test]
+    fn offset_msgbox() {
+        assert_eq!(offset_of!(UserBlock, rx_interrupt_enable), 0x20);
+        assert_eq!(offset_of!(UserBlock, rx_interrupt_status), 0x24);
+        assert_eq!(offset_of!(UserBlock, tx_interrupt_enable), 0x30);
+        assert_eq!(offset_of!(UserBlock, tx_interrupt_status), 0x34);
+        assert_eq!(offset_of!(UserBlock, debug), 0x40);
+        assert_eq!(offset_of!(UserBlock, fifo_status), 0x50);
+        assert_eq!(offset_of!(UserBlock, message_status), 0x60);
+        assert_eq!(offset_of!(UserBlock, message), 0x70);
+        assert_eq!(offset_of!(UserBlock, tx_threshold), 0x80);
+        assert_eq!(size_of::<UserBlock>(), 0x100);
+        assert_eq!(size_of::<RegisterBlock>(), 0x200);
+    }
+
+    #[test]
+    fn struct_interrupts_functions() {
+        let mut val = Interrupts::default();
+        val = val.enable_receive(0).enable_transmit(3);
+        assert!(val.has_receive(0));
+        assert!(!val.has_transmit(0));
+        assert!(val.has_transmit(3));
+        assert_eq!(val.0, 0x00000081);
+        val = val.disable_receive(0).disable_transmit(3);
+        assert_eq!(val.0, 0x00000000);
+    }
+
+    #[test]
+    fn struct_status_functions() {
+        assert!(FifoStatus(0x1).is_full());
+        assert!(!FifoStatus(0x0).is_full());
+        assert_eq!(MessageStatus(0x8).message_count(), 8);
+    }
+}
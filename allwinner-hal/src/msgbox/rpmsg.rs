@@ -0,0 +1,138 @@
+use super::{Error, Msgbox, RegisterBlock};
+
+/// Largest frame payload in bytes, as rpmsg-lite 512-byte buffers less header.
+pub const MAX_PAYLOAD: usize = 496;
+
+/// Header of rpmsg-lite style frame.
+///
+/// Frame is sent on one channel as source address, destination address, flags in upper and
+/// payload length in lower half of third word, then payload in little-endian words padded
+/// with zeros.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct FrameHeader {
+    /// Endpoint address of sender.
+    pub source: u32,
+    /// Endpoint address of receiver.
+    pub destination: u32,
+    /// Payload length in bytes.
+    pub len: u16,
+    /// Application defined flags.
+    pub flags: u16,
+}
+
+impl FrameHeader {
+    /// Encode header into message words.
+    #[inline]
+    pub const fn to_words(self) -> [u32; 3] {
+        [
+            self.source,
+            self.destination,
+            (self.flags as u32) << 16 | self.len as u32,
+        ]
+    }
+    /// Decode header from message words.
+    #[inline]
+    pub const fn from_words(words: [u32; 3]) -> Self {
+        Self {
+            source: words[0],
+            destination: words[1],
+            len: words[2] as u16,
+            flags: (words[2] >> 16) as u16,
+        }
+    }
+}
+
+impl<LOCAL: AsRef<RegisterBlock>, REMOTE: AsRef<RegisterBlock>> Msgbox<LOCAL, REMOTE> {
+    /// Send `payload` in a frame from endpoint `source` to `destination` on `channel`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::FrameTooLarge`] if `payload` exceeds [`MAX_PAYLOAD`], or
+    /// [`Error::Timeout`] if remote processor stops draining FIFO.
+    #[inline]
+    pub fn send_frame(
+        &mut self,
+        channel: usize,
+        source: u32,
+        destination: u32,
+        flags: u16,
+        payload: &[u8],
+    ) -> Result<(), Error> {
+        if payload.len() > MAX_PAYLOAD {
+            return Err(Error::FrameTooLarge);
+        }
+        let header = FrameHeader {
+            source,
+            destination,
+            len: payload.len() as u16,
+            flags,
+        };
+        for word in header.to_words() {
+            self.send(channel, word)?;
+        }
+        for chunk in payload.chunks(4) {
+            self.send(channel, pack_word(chunk))?;
+        }
+        Ok(())
+    }
+    /// Receive a frame on `channel`, copying its payload to the start of `buf`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::FrameTooLarge`] if payload exceeds [`MAX_PAYLOAD`] or `buf`, in
+    /// which case payload is drained and discarded, or [`Error::Timeout`] if frame doesn't
+    /// arrive in time.
+    #[inline]
+    pub fn receive_frame(&mut self, channel: usize, buf: &mut [u8]) -> Result<FrameHeader, Error> {
+        let mut words = [0; 3];
+        for word in words.iter_mut() {
+            *word = self.receive(channel)?;
+        }
+        let header = FrameHeader::from_words(words);
+        let len = header.len as usize;
+        if len > MAX_PAYLOAD || len > buf.len() {
+            for _ in 0..len.div_ceil(4) {
+                self.receive(channel)?;
+            }
+            return Err(Error::FrameTooLarge);
+        }
+        for chunk in buf[..len].chunks_mut(4) {
+            let bytes = self.receive(channel)?.to_le_bytes();
+            chunk.copy_from_slice(&bytes[..chunk.len()]);
+        }
+        Ok(header)
+    }
+}
+
+/// Pack up to 4 bytes into a little-endian message word, padding with zeros.
+#[inline]
+fn pack_word(chunk: &[u8]) -> u32 {
+    let mut bytes = [0; 4];
+    bytes[..chunk.len()].copy_from_slice(chunk);
+    u32::from_le_bytes(bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{FrameHeader, pack_word};
+
+    #[test]
+    fn struct_frame_header_functions() {
+        let header = FrameHeader {
+            source: 0x400,
+            destination: 0x35,
+            len: 0x1F0,
+            flags: 0xA5,
+        };
+        let words = header.to_words();
+        assert_eq!(words, [0x400, 0x35, 0x00A5_01F0]);
+        assert_eq!(FrameHeader::from_words(words), header);
+    }
+
+    #[test]
+    fn function_pack_word() {
+        assert_eq!(pack_word(&[0x11, 0x22, 0x33, 0x44]), 0x4433_2211);
+        assert_eq!(pack_word(&[0x11, 0x22]), 0x0000_2211);
+        assert_eq!(pack_word(&[]), 0);
+    }
+}
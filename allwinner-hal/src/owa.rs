@@ -0,0 +1,282 @@
+//! One Wire Audio, S/PDIF transmitter.
+//!
+//! OWA sends stereo samples as an IEC 60958 biphase-mark stream on a single wire, for
+//! optical or coaxial links to amplifiers and receivers. Channel status bits announce the
+//! sample rate and word length in consumer format, and mark compressed IEC 61937 bursts as
+//! non-audio data so that receivers decode them instead of playing them as PCM.
+//!
+//! Module clock is 24.576 MHz from audio PLL 1 by default, giving sample rates of the
+//! 48-kHz family; [`Owa::negotiate_sample_rate`] switches to the 44.1-kHz family when
+//! a requested rate needs it. [`Stream`] feeds the FIFO from a pair of buffers over DMA.
+//!
+//! TX FIFO raises [`Event::Empty`] and [`Event::Underrun`] through [`on_interrupt`].
+
+mod register;
+#[cfg(feature = "d1")]
+mod stream;
+pub use register::*;
+#[cfg(feature = "d1")]
+pub use stream::*;
+
+use crate::audio::{self, Event, Events, Family, Handler, Negotiated};
+use crate::ccu::{self, ClockGate};
+use embedded_time::rate::Hertz;
+
+static EVENTS: Events = Events::new();
+
+/// OWA interrupt entry point.
+///
+/// Call this function from OWA interrupt handler. Each enabled TX FIFO event pending is
+/// cleared and raised to the registered handler and waiting task; TX FIFO empty interrupt
+/// is disabled once raised.
+#[inline]
+pub fn on_interrupt(owa: &RegisterBlock) {
+    let control = owa.int_control.read();
+    let status = owa.int_status.read();
+    if control.is_empty_interrupt_enabled() && status.is_tx_empty() {
+        unsafe {
+            owa.int_control.modify(|val| val.disable_empty_interrupt());
+            owa.int_status
+                .write(InterruptStatus::default().clear_tx_empty());
+        }
+        EVENTS.raise(Event::Empty);
+    }
+    if control.is_underrun_interrupt_enabled() && status.has_underrun() {
+        unsafe {
+            owa.int_status
+                .write(InterruptStatus::default().clear_underrun())
+        };
+        EVENTS.raise(Event::Underrun);
+    }
+}
+
+/// Format of transmitted frames.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Format {
+    /// Two channels of linear PCM samples.
+    LinearPcm,
+    /// Compressed IEC 61937 bursts packed into 16-bit stereo words, passed through to
+    /// a decoding receiver.
+    Iec61937,
+}
+
+/// Managed One Wire Audio structure with peripheral.
+pub struct Owa<OWA> {
+    owa: OWA,
+    family: Family,
+}
+
+impl<OWA: AsRef<RegisterBlock>> Owa<OWA> {
+    /// Create an OWA instance sending 48-kHz linear PCM samples of 16 bits.
+    ///
+    /// Audio PLL 1 is enabled if it is not yet, and OWA transmit clock is set to 24.576 MHz
+    /// from its divide-by-5 output, as in [`Family::Hz48000`].
+    #[inline]
+    pub fn new(owa: OWA, ccu: &ccu::RegisterBlock) -> Self {
+        let clock = Family::Hz48000.clock();
+        // note(unsafe): async read and write using ccu registers
+        unsafe {
+            Family::Hz48000.enable_pll(ccu);
+            ccu.owa_tx_clock.write(clock);
+            ccu::OWA::reset(ccu);
+        }
+        let regs = owa.as_ref();
+        unsafe {
+            regs.control.write(GeneralControl::default().reset());
+            regs.tx_config.write(
+                TxConfig::default()
+                    .set_hold_last_sample(true)
+                    .set_sample_bits(SampleBits::Bits16)
+                    .enable_channel_status(),
+            );
+            regs.tx_status1
+                .write(ChannelStatus1::default().set_word_length(SampleBits::Bits16));
+        }
+        let mut owa = Self {
+            owa,
+            family: Family::Hz48000,
+        };
+        owa.set_sample_rate(SampleRate::Hz48000);
+        owa
+    }
+    /// Set sample rate, and sampling frequency in channel status.
+    #[inline]
+    pub fn set_sample_rate(&mut self, rate: SampleRate) {
+        let regs = self.owa.as_ref();
+        unsafe {
+            regs.tx_config.modify(|val| val.set_ratio(rate.tx_ratio()));
+            regs.tx_status0
+                .modify(|val| val.set_frequency(rate.status_code()));
+            regs.tx_status1
+                .modify(|val| val.set_original_frequency(rate.original_code()));
+        }
+    }
+    /// Get exact sample rate, from transmit clock ratio and module clock family.
+    #[inline]
+    pub fn sample_rate(&self) -> Hertz {
+        let ratio = self.owa.as_ref().tx_config.read().ratio() as u32;
+        Hertz(self.family.module_clock().0 / ((ratio + 1) * 128))
+    }
+    /// Set sample rate closest to `rate`, with transmit clock from the audio PLL of the
+    /// chosen clock family, and sampling frequency of exact rate achieved in channel status.
+    ///
+    /// Returns chosen setting and exact sample rate achieved. Transmitter should be stopped.
+    #[inline]
+    pub fn negotiate_sample_rate(
+        &mut self,
+        rate: Hertz,
+        ccu: &ccu::RegisterBlock,
+    ) -> Negotiated<SampleRate> {
+        let negotiated = audio::negotiate(rate, &SampleRate::ALL, SampleRate::divider);
+        // note(unsafe): async read and write using ccu registers
+        unsafe {
+            negotiated.family.enable_pll(ccu);
+            ccu.owa_tx_clock.write(negotiated.family.clock());
+        }
+        let (code, original_code) = status_codes(negotiated.sample_rate.0);
+        let regs = self.owa.as_ref();
+        unsafe {
+            regs.tx_config
+                .modify(|val| val.set_ratio(negotiated.rate.tx_ratio()));
+            regs.tx_status0.modify(|val| val.set_frequency(code));
+            regs.tx_status1
+                .modify(|val| val.set_original_frequency(original_code));
+        }
+        self.family = negotiated.family;
+        negotiated
+    }
+    /// Set format of transmitted frames, marking IEC 61937 bursts as non-audio data.
+    #[inline]
+    pub fn set_format(&mut self, format: Format) {
+        let non_audio = format == Format::Iec61937;
+        let regs = self.owa.as_ref();
+        unsafe {
+            regs.tx_config.modify(|val| val.set_non_audio(non_audio));
+            regs.tx_status0.modify(|val| val.set_non_audio(non_audio));
+        }
+    }
+    /// Set consumer category code and whether copying is permitted in channel status.
+    #[inline]
+    pub fn set_category(&mut self, category: u8, copy_permitted: bool) {
+        unsafe {
+            self.owa.as_ref().tx_status0.modify(|val| {
+                val.set_category(category)
+                    .set_copy_permitted(copy_permitted)
+            })
+        };
+    }
+    /// Start transmitter, requesting DMA transfers of 16-bit samples into its FIFO.
+    ///
+    /// DMA channel should move 16-bit left and right samples alternately to
+    /// [`Owa::fifo_address`] with request port `OWA_TX`; [`Stream`] sets this up with a
+    /// pair of buffers.
+    #[inline]
+    pub fn start_dma(&mut self) {
+        let regs = self.owa.as_ref();
+        unsafe {
+            regs.fifo_control
+                .modify(|val| val.set_tx_lsb_aligned(true).flush_tx_fifo());
+            regs.int_status
+                .write(InterruptStatus::default().clear_underrun().clear_overrun());
+            regs.int_control.modify(|val| val.enable_tx_drq());
+            regs.tx_config.modify(|val| val.enable());
+            regs.control.modify(|val| val.enable());
+        }
+    }
+    /// Stop transmitter and its DMA requests.
+    #[inline]
+    pub fn stop_dma(&mut self) {
+        let regs = self.owa.as_ref();
+        unsafe {
+            regs.control.modify(|val| val.disable());
+            regs.tx_config.modify(|val| val.disable());
+            regs.int_control.modify(|val| val.disable_tx_drq());
+        }
+    }
+    /// Get address of TX FIFO register as DMA transfer destination.
+    #[inline]
+    pub fn fifo_address(&self) -> usize {
+        &self.owa.as_ref().tx_fifo as *const _ as usize
+    }
+    /// Check if TX FIFO has underrun since last cleared, repeating last sample.
+    #[inline]
+    pub fn is_underrun(&self) -> bool {
+        self.owa.as_ref().int_status.read().has_underrun()
+    }
+    /// Clear TX FIFO underrun flag.
+    #[inline]
+    pub fn clear_underrun(&mut self) {
+        unsafe {
+            self.owa
+                .as_ref()
+                .int_status
+                .write(InterruptStatus::default().clear_underrun())
+        };
+    }
+    /// Enable interrupt of TX FIFO `event`, raised through [`on_interrupt`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `event` is a receive FIFO event.
+    #[inline]
+    pub fn enable_interrupt(&mut self, event: Event) {
+        unsafe {
+            self.owa.as_ref().int_control.modify(|val| match event {
+                Event::Empty => val.enable_empty_interrupt(),
+                Event::Underrun => val.enable_underrun_interrupt(),
+                _ => panic!("OWA transmitter has no receive FIFO"),
+            })
+        };
+    }
+    /// Disable interrupt of TX FIFO `event`.
+    #[inline]
+    pub fn disable_interrupt(&mut self, event: Event) {
+        unsafe {
+            self.owa.as_ref().int_control.modify(|val| match event {
+                Event::Empty => val.disable_empty_interrupt(),
+                Event::Underrun => val.disable_underrun_interrupt(),
+                _ => val,
+            })
+        };
+    }
+    /// Register handler called from [`on_interrupt`] on each raised event, or remove it.
+    #[inline]
+    pub fn set_handler(&mut self, handler: Option<Handler>) {
+        EVENTS.set_handler(handler);
+    }
+    /// Wait for next TX FIFO `event`, enabling its interrupt.
+    ///
+    /// Events raised before this call are discarded.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `event` is a receive FIFO event.
+    #[inline]
+    pub async fn wait_event(&mut self, event: Event) {
+        EVENTS.take(event);
+        self.enable_interrupt(event);
+        EVENTS.wait(event).await;
+    }
+    /// Get number of times TX FIFO `event` has been raised.
+    #[inline]
+    pub fn event_count(&self, event: Event) -> u32 {
+        EVENTS.count(event)
+    }
+    /// Get number of TX FIFO underruns raised.
+    #[inline]
+    pub fn xruns(&self) -> u32 {
+        EVENTS.xruns()
+    }
+    /// Close OWA and release peripheral.
+    #[inline]
+    pub fn free(self, ccu: &ccu::RegisterBlock) -> OWA {
+        let regs = self.owa.as_ref();
+        unsafe {
+            regs.control.write(GeneralControl::default());
+            regs.int_control.write(InterruptControl::default());
+            ccu::OWA::free(ccu);
+            ccu.owa_tx_clock.modify(|val| val.mask_clock());
+        }
+        self.owa
+    }
+}
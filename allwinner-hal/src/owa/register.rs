@@ -0,0 +1,750 @@
+use volatile_register::{RO, RW, WO};
+
+/// One Wire Audio registers.
+#[repr(C)]
+pub struct RegisterBlock {
+    /// 0x00 - OWA General Control Register.
+    pub control: RW<GeneralControl>,
+    /// 0x04 - OWA TX Configuration Register.
+    pub tx_config: RW<TxConfig>,
+    /// 0x08 - OWA RX Configuration Register.
+    pub rx_config: RW<u32>,
+    /// 0x0c - OWA Interrupt Status Register.
+    pub int_status: RW<InterruptStatus>,
+    /// 0x10 - OWA RX FIFO Register.
+    pub rx_fifo: RO<u32>,
+    /// 0x14 - OWA FIFO Control Register.
+    pub fifo_control: RW<FifoControl>,
+    /// 0x18 - OWA FIFO Status Register.
+    pub fifo_status: RO<FifoStatus>,
+    /// 0x1c - OWA Interrupt Control Register.
+    pub int_control: RW<InterruptControl>,
+    /// 0x20 - OWA TX FIFO Register.
+    pub tx_fifo: WO<u32>,
+    /// 0x24 - OWA TX Counter Register.
+    pub tx_count: RW<u32>,
+    /// 0x28 - OWA RX Counter Register.
+    pub rx_count: RW<u32>,
+    /// 0x2c - OWA TX Channel Status Register 0.
+    pub tx_status0: RW<ChannelStatus0>,
+    /// 0x30 - OWA TX Channel Status Register 1.
+    pub tx_status1: RW<ChannelStatus1>,
+}
+
+/// Sample rate of OWA output, from 24.576-MHz module clock.
+///
+/// With 22.5792-MHz module clock of the 44.1-kHz family, each rate scales by 147/160, for
+/// example `Hz48000` gives 44.1 kHz.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum SampleRate {
+    /// 32 kHz.
+    Hz32000,
+    /// 48 kHz.
+    Hz48000,
+    /// 96 kHz.
+    Hz96000,
+    /// 192 kHz.
+    Hz192000,
+}
+
+impl SampleRate {
+    /// All sample rates, from highest to lowest.
+    pub const ALL: [SampleRate; 4] = [
+        SampleRate::Hz192000,
+        SampleRate::Hz96000,
+        SampleRate::Hz48000,
+        SampleRate::Hz32000,
+    ];
+
+    /// Get number of module clock cycles per sample.
+    #[inline]
+    pub const fn divider(self) -> u32 {
+        (self.tx_ratio() as u32 + 1) * 128
+    }
+    /// Get transmit clock divide ratio minus one, for 128 biphase cells per frame.
+    #[inline]
+    pub const fn tx_ratio(self) -> u8 {
+        match self {
+            SampleRate::Hz32000 => 5,
+            SampleRate::Hz48000 => 3,
+            SampleRate::Hz96000 => 1,
+            SampleRate::Hz192000 => 0,
+        }
+    }
+    /// Get sampling frequency code of channel status bits 24 to 27.
+    #[inline]
+    pub const fn status_code(self) -> u8 {
+        match self {
+            SampleRate::Hz32000 => 0x3,
+            SampleRate::Hz48000 => 0x2,
+            SampleRate::Hz96000 => 0xA,
+            SampleRate::Hz192000 => 0xE,
+        }
+    }
+    /// Get original sampling frequency code of channel status bits 36 to 39.
+    #[inline]
+    pub const fn original_code(self) -> u8 {
+        match self {
+            SampleRate::Hz32000 => 0xC,
+            SampleRate::Hz48000 => 0xD,
+            SampleRate::Hz96000 => 0x5,
+            SampleRate::Hz192000 => 0x1,
+        }
+    }
+}
+
+/// Get sampling frequency and original sampling frequency codes of channel status for
+/// exact sample rate `hz`.
+///
+/// Rates without a code of their own are marked as not indicated.
+#[inline]
+pub const fn status_codes(hz: u32) -> (u8, u8) {
+    match hz {
+        44_100 => (0x0, 0xF),
+        48_000 => (0x2, 0xD),
+        32_000 => (0x3, 0xC),
+        88_200 => (0x8, 0x7),
+        96_000 => (0xA, 0x5),
+        176_400 => (0xC, 0x3),
+        192_000 => (0xE, 0x1),
+        _ => (0x1, 0x0),
+    }
+}
+
+/// Width of transmitted samples.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum SampleBits {
+    /// 16 bits.
+    Bits16 = 0,
+    /// 20 bits.
+    Bits20 = 1,
+    /// 24 bits.
+    Bits24 = 2,
+}
+
+/// OWA general control register.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct GeneralControl(u32);
+
+impl GeneralControl {
+    const GEN: u32 = 1 << 1;
+    const RST: u32 = 1 << 0;
+
+    /// If OWA is globally enabled.
+    #[inline]
+    pub const fn is_enabled(self) -> bool {
+        self.0 & Self::GEN != 0
+    }
+    /// Enable OWA globally.
+    #[inline]
+    pub const fn enable(self) -> Self {
+        Self(self.0 | Self::GEN)
+    }
+    /// Disable OWA globally.
+    #[inline]
+    pub const fn disable(self) -> Self {
+        Self(self.0 & !Self::GEN)
+    }
+    /// Reset OWA logic, cleared by hardware when done.
+    #[inline]
+    pub const fn reset(self) -> Self {
+        Self(self.0 | Self::RST)
+    }
+}
+
+impl Default for GeneralControl {
+    #[inline]
+    fn default() -> Self {
+        Self(0x0000_0000)
+    }
+}
+
+/// OWA TX configuration register.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct TxConfig(u32);
+
+impl TxConfig {
+    const TX_SINGLE_MODE: u32 = 1 << 31;
+    const ASS: u32 = 1 << 17;
+    const TX_AUDIO: u32 = 1 << 16;
+    const TX_RATIO: u32 = 0x1F << 4;
+    const TX_SAMPLE_BIT: u32 = 0x3 << 2;
+    const TX_CHSTS_MODE: u32 = 1 << 1;
+    const TXEN: u32 = 1 << 0;
+
+    /// If one channel of samples is sent as both left and right channels.
+    #[inline]
+    pub const fn is_single_mode(self) -> bool {
+        self.0 & Self::TX_SINGLE_MODE != 0
+    }
+    /// Set whether one channel of samples is sent as both left and right channels.
+    #[inline]
+    pub const fn set_single_mode(self, val: bool) -> Self {
+        if val {
+            Self(self.0 | Self::TX_SINGLE_MODE)
+        } else {
+            Self(self.0 & !Self::TX_SINGLE_MODE)
+        }
+    }
+    /// If last sample is repeated on FIFO underrun, or zero is sent otherwise.
+    #[inline]
+    pub const fn is_hold_last_sample(self) -> bool {
+        self.0 & Self::ASS != 0
+    }
+    /// Set whether last sample is repeated on FIFO underrun, or zero is sent otherwise.
+    #[inline]
+    pub const fn set_hold_last_sample(self, val: bool) -> Self {
+        if val {
+            Self(self.0 | Self::ASS)
+        } else {
+            Self(self.0 & !Self::ASS)
+        }
+    }
+    /// If samples are marked as non-audio data, like compressed IEC 61937 bursts.
+    #[inline]
+    pub const fn is_non_audio(self) -> bool {
+        self.0 & Self::TX_AUDIO != 0
+    }
+    /// Set whether samples are marked as non-audio data, like compressed IEC 61937 bursts.
+    #[inline]
+    pub const fn set_non_audio(self, val: bool) -> Self {
+        if val {
+            Self(self.0 | Self::TX_AUDIO)
+        } else {
+            Self(self.0 & !Self::TX_AUDIO)
+        }
+    }
+    /// Get transmit clock divide ratio minus one.
+    #[inline]
+    pub const fn ratio(self) -> u8 {
+        ((self.0 & Self::TX_RATIO) >> 4) as u8
+    }
+    /// Set transmit clock divide ratio minus one, from 0 to 31.
+    #[inline]
+    pub const fn set_ratio(self, val: u8) -> Self {
+        Self((self.0 & !Self::TX_RATIO) | ((val as u32 & 0x1F) << 4))
+    }
+    /// Get width of transmitted samples.
+    #[inline]
+    pub const fn sample_bits(self) -> SampleBits {
+        match (self.0 & Self::TX_SAMPLE_BIT) >> 2 {
+            0 => SampleBits::Bits16,
+            1 => SampleBits::Bits20,
+            2 => SampleBits::Bits24,
+            _ => panic!("reserved sample width"),
+        }
+    }
+    /// Set width of transmitted samples.
+    #[inline]
+    pub const fn set_sample_bits(self, val: SampleBits) -> Self {
+        Self((self.0 & !Self::TX_SAMPLE_BIT) | ((val as u32) << 2))
+    }
+    /// If channel status bits are sent from channel status registers.
+    #[inline]
+    pub const fn is_channel_status_enabled(self) -> bool {
+        self.0 & Self::TX_CHSTS_MODE != 0
+    }
+    /// Send channel status bits from channel status registers.
+    #[inline]
+    pub const fn enable_channel_status(self) -> Self {
+        Self(self.0 | Self::TX_CHSTS_MODE)
+    }
+    /// Stop sending channel status bits from channel status registers.
+    #[inline]
+    pub const fn disable_channel_status(self) -> Self {
+        Self(self.0 & !Self::TX_CHSTS_MODE)
+    }
+    /// If transmitter is enabled.
+    #[inline]
+    pub const fn is_enabled(self) -> bool {
+        self.0 & Self::TXEN != 0
+    }
+    /// Enable transmitter.
+    #[inline]
+    pub const fn enable(self) -> Self {
+        Self(self.0 | Self::TXEN)
+    }
+    /// Disable transmitter.
+    #[inline]
+    pub const fn disable(self) -> Self {
+        Self(self.0 & !Self::TXEN)
+    }
+}
+
+impl Default for TxConfig {
+    #[inline]
+    fn default() -> Self {
+        Self(0x0000_00F0)
+    }
+}
+
+/// OWA interrupt status register.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct InterruptStatus(u32);
+
+impl InterruptStatus {
+    const TXU_INT: u32 = 1 << 6;
+    const TXO_INT: u32 = 1 << 5;
+    const TXE_INT: u32 = 1 << 4;
+
+    /// If TX FIFO has underrun.
+    #[inline]
+    pub const fn has_underrun(self) -> bool {
+        self.0 & Self::TXU_INT != 0
+    }
+    /// Clear TX FIFO underrun flag on write.
+    #[inline]
+    pub const fn clear_underrun(self) -> Self {
+        Self(self.0 | Self::TXU_INT)
+    }
+    /// If TX FIFO has overrun.
+    #[inline]
+    pub const fn has_overrun(self) -> bool {
+        self.0 & Self::TXO_INT != 0
+    }
+    /// Clear TX FIFO overrun flag on write.
+    #[inline]
+    pub const fn clear_overrun(self) -> Self {
+        Self(self.0 | Self::TXO_INT)
+    }
+    /// If TX FIFO space has reached trigger level.
+    #[inline]
+    pub const fn is_tx_empty(self) -> bool {
+        self.0 & Self::TXE_INT != 0
+    }
+    /// Clear TX FIFO empty flag on write.
+    #[inline]
+    pub const fn clear_tx_empty(self) -> Self {
+        Self(self.0 | Self::TXE_INT)
+    }
+}
+
+impl Default for InterruptStatus {
+    #[inline]
+    fn default() -> Self {
+        Self(0x0000_0000)
+    }
+}
+
+/// OWA FIFO control register.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct FifoControl(u32);
+
+impl FifoControl {
+    const FTX: u32 = 1 << 30;
+    const TXTL: u32 = 0xFF << 12;
+    const TXIM: u32 = 1 << 2;
+
+    /// Flush TX FIFO, cleared by hardware when done.
+    #[inline]
+    pub const fn flush_tx_fifo(self) -> Self {
+        Self(self.0 | Self::FTX)
+    }
+    /// Get number of free TX FIFO words that triggers DMA request or empty interrupt.
+    #[inline]
+    pub const fn tx_trigger_level(self) -> u8 {
+        ((self.0 & Self::TXTL) >> 12) as u8
+    }
+    /// Set number of free TX FIFO words that triggers DMA request or empty interrupt.
+    #[inline]
+    pub const fn set_tx_trigger_level(self, val: u8) -> Self {
+        Self((self.0 & !Self::TXTL) | ((val as u32) << 12))
+    }
+    /// If samples are taken from low bits of TX FIFO words, or from high bits otherwise.
+    #[inline]
+    pub const fn is_tx_lsb_aligned(self) -> bool {
+        self.0 & Self::TXIM != 0
+    }
+    /// Set whether samples are taken from low bits of TX FIFO words.
+    #[inline]
+    pub const fn set_tx_lsb_aligned(self, val: bool) -> Self {
+        if val {
+            Self(self.0 | Self::TXIM)
+        } else {
+            Self(self.0 & !Self::TXIM)
+        }
+    }
+}
+
+impl Default for FifoControl {
+    #[inline]
+    fn default() -> Self {
+        Self(0x0004_0200)
+    }
+}
+
+/// OWA FIFO status register.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct FifoStatus(u32);
+
+impl FifoStatus {
+    const TXE: u32 = 1 << 31;
+    const TXE_CNT: u32 = 0x7FFF << 16;
+
+    /// If TX FIFO has room for at least one word.
+    #[inline]
+    pub const fn is_tx_empty(self) -> bool {
+        self.0 & Self::TXE != 0
+    }
+    /// Get number of free TX FIFO words.
+    #[inline]
+    pub const fn tx_empty_count(self) -> u16 {
+        ((self.0 & Self::TXE_CNT) >> 16) as u16
+    }
+}
+
+/// OWA interrupt control register.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct InterruptControl(u32);
+
+impl InterruptControl {
+    const TX_DRQ: u32 = 1 << 7;
+    const TXUI_EN: u32 = 1 << 6;
+    const TXOI_EN: u32 = 1 << 5;
+    const TXEI_EN: u32 = 1 << 4;
+
+    /// If TX DMA request is enabled.
+    #[inline]
+    pub const fn is_tx_drq_enabled(self) -> bool {
+        self.0 & Self::TX_DRQ != 0
+    }
+    /// Enable TX DMA request.
+    #[inline]
+    pub const fn enable_tx_drq(self) -> Self {
+        Self(self.0 | Self::TX_DRQ)
+    }
+    /// Disable TX DMA request.
+    #[inline]
+    pub const fn disable_tx_drq(self) -> Self {
+        Self(self.0 & !Self::TX_DRQ)
+    }
+    /// If TX FIFO underrun interrupt is enabled.
+    #[inline]
+    pub const fn is_underrun_interrupt_enabled(self) -> bool {
+        self.0 & Self::TXUI_EN != 0
+    }
+    /// Enable TX FIFO underrun interrupt.
+    #[inline]
+    pub const fn enable_underrun_interrupt(self) -> Self {
+        Self(self.0 | Self::TXUI_EN)
+    }
+    /// Disable TX FIFO underrun interrupt.
+    #[inline]
+    pub const fn disable_underrun_interrupt(self) -> Self {
+        Self(self.0 & !Self::TXUI_EN)
+    }
+    /// If TX FIFO overrun interrupt is enabled.
+    #[inline]
+    pub const fn is_overrun_interrupt_enabled(self) -> bool {
+        self.0 & Self::TXOI_EN != 0
+    }
+    /// Enable TX FIFO overrun interrupt.
+    #[inline]
+    pub const fn enable_overrun_interrupt(self) -> Self {
+        Self(self.0 | Self::TXOI_EN)
+    }
+    /// Disable TX FIFO overrun interrupt.
+    #[inline]
+    pub const fn disable_overrun_interrupt(self) -> Self {
+        Self(self.0 & !Self::TXOI_EN)
+    }
+    /// If TX FIFO empty interrupt is enabled.
+    #[inline]
+    pub const fn is_empty_interrupt_enabled(self) -> bool {
+        self.0 & Self::TXEI_EN != 0
+    }
+    /// Enable TX FIFO empty interrupt.
+    #[inline]
+    pub const fn enable_empty_interrupt(self) -> Self {
+        Self(self.0 | Self::TXEI_EN)
+    }
+    /// Disable TX FIFO empty interrupt.
+    #[inline]
+    pub const fn disable_empty_interrupt(self) -> Self {
+        Self(self.0 & !Self::TXEI_EN)
+    }
+}
+
+impl Default for InterruptControl {
+    #[inline]
+    fn default() -> Self {
+        Self(0x0000_0000)
+    }
+}
+
+/// OWA channel status register 0, holding channel status bits 0 to 31.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct ChannelStatus0(u32);
+
+impl ChannelStatus0 {
+    const FREQ: u32 = 0xF << 24;
+    const CATEGORY: u32 = 0xFF << 8;
+    const CP: u32 = 1 << 2;
+    const AUDIO: u32 = 1 << 1;
+    const PRO: u32 = 1 << 0;
+
+    /// Get sampling frequency code.
+    #[inline]
+    pub const fn frequency(self) -> u8 {
+        ((self.0 & Self::FREQ) >> 24) as u8
+    }
+    /// Set sampling frequency code, see [`SampleRate::status_code`].
+    #[inline]
+    pub const fn set_frequency(self, val: u8) -> Self {
+        Self((self.0 & !Self::FREQ) | ((val as u32 & 0xF) << 24))
+    }
+    /// Get category code.
+    #[inline]
+    pub const fn category(self) -> u8 {
+        ((self.0 & Self::CATEGORY) >> 8) as u8
+    }
+    /// Set category code; zero for general equipment.
+    #[inline]
+    pub const fn set_category(self, val: u8) -> Self {
+        Self((self.0 & !Self::CATEGORY) | ((val as u32) << 8))
+    }
+    /// If copying is permitted.
+    #[inline]
+    pub const fn is_copy_permitted(self) -> bool {
+        self.0 & Self::CP != 0
+    }
+    /// Set whether copying is permitted.
+    #[inline]
+    pub const fn set_copy_permitted(self, val: bool) -> Self {
+        if val {
+            Self(self.0 | Self::CP)
+        } else {
+            Self(self.0 & !Self::CP)
+        }
+    }
+    /// If samples are non-audio data, or linear PCM otherwise.
+    #[inline]
+    pub const fn is_non_audio(self) -> bool {
+        self.0 & Self::AUDIO != 0
+    }
+    /// Set whether samples are non-audio data, or linear PCM otherwise.
+    #[inline]
+    pub const fn set_non_audio(self, val: bool) -> Self {
+        if val {
+            Self(self.0 | Self::AUDIO)
+        } else {
+            Self(self.0 & !Self::AUDIO)
+        }
+    }
+    /// If channel status is in professional format, or consumer format otherwise.
+    #[inline]
+    pub const fn is_professional(self) -> bool {
+        self.0 & Self::PRO != 0
+    }
+}
+
+impl Default for ChannelStatus0 {
+    #[inline]
+    fn default() -> Self {
+        Self(0x0000_0000)
+    }
+}
+
+/// OWA channel status register 1, holding channel status bits 32 to 39.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct ChannelStatus1(u32);
+
+impl ChannelStatus1 {
+    const ORIGINAL_FREQ: u32 = 0xF << 4;
+    const WORD_LENGTH: u32 = 0xF;
+
+    /// Get original sampling frequency code.
+    #[inline]
+    pub const fn original_frequency(self) -> u8 {
+        ((self.0 & Self::ORIGINAL_FREQ) >> 4) as u8
+    }
+    /// Set original sampling frequency code, see [`SampleRate::original_code`].
+    #[inline]
+    pub const fn set_original_frequency(self, val: u8) -> Self {
+        Self((self.0 & !Self::ORIGINAL_FREQ) | ((val as u32 & 0xF) << 4))
+    }
+    /// Get sample word length code, including maximum word length bit.
+    #[inline]
+    pub const fn word_length(self) -> u8 {
+        (self.0 & Self::WORD_LENGTH) as u8
+    }
+    /// Set sample word length code, including maximum word length bit.
+    #[inline]
+    pub const fn set_word_length(self, val: SampleBits) -> Self {
+        let code = match val {
+            SampleBits::Bits16 => 0x2,
+            SampleBits::Bits20 => 0xA,
+            SampleBits::Bits24 => 0xB,
+        };
+        Self((self.0 & !Self::WORD_LENGTH) | code)
+    }
+}
+
+impl Default for ChannelStatus1 {
+    #[inline]
+    fn default() -> Self {
+        Self(0x0000_0000)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        ChannelStatus0, ChannelStatus1, FifoControl, FifoStatus, GeneralControl, InterruptControl,
+        InterruptStatus, RegisterBlock, SampleBits, SampleRate, TxConfig, status_codes,
+    };
+    use core::mem::offset_of;
+
+    #[test]
+    fn offset_owa() {
+        assert_eq!(offset_of!(RegisterBlock, control), 0x00);
+        assert_eq!(offset_of!(RegisterBlock, tx_config), 0x04);
+        assert_eq!(offset_of!(RegisterBlock, rx_config), 0x08);
+        assert_eq!(offset_of!(RegisterBlock, int_status), 0x0c);
+        assert_eq!(offset_of!(RegisterBlock, rx_fifo), 0x10);
+        assert_eq!(offset_of!(RegisterBlock, fifo_control), 0x14);
+        assert_eq!(offset_of!(RegisterBlock, fifo_status), 0x18);
+        assert_eq!(offset_of!(RegisterBlock, int_control), 0x1c);
+        assert_eq!(offset_of!(RegisterBlock, tx_fifo), 0x20);
+        assert_eq!(offset_of!(RegisterBlock, tx_count), 0x24);
+        assert_eq!(offset_of!(RegisterBlock, rx_count), 0x28);
+        assert_eq!(offset_of!(RegisterBlock, tx_status0), 0x2c);
+        assert_eq!(offset_of!(RegisterBlock, tx_status1), 0x30);
+    }
+
+    #[test]
+    fn struct_general_control_functions() {
+        let val = GeneralControl::default().enable();
+        assert!(val.is_enabled());
+        assert_eq!(val.0, 0x0000_0002);
+        assert_eq!(val.disable().reset().0, 0x0000_0001);
+    }
+
+    #[test]
+    fn struct_tx_config_functions() {
+        let val = TxConfig::default();
+        assert_eq!(val.ratio(), 0xF);
+        assert_eq!(val.sample_bits(), SampleBits::Bits16);
+
+        let val = val
+            .set_single_mode(true)
+            .set_hold_last_sample(true)
+            .set_non_audio(true)
+            .set_ratio(SampleRate::Hz48000.tx_ratio())
+            .set_sample_bits(SampleBits::Bits24)
+            .enable_channel_status()
+            .enable();
+        assert!(val.is_single_mode());
+        assert!(val.is_hold_last_sample());
+        assert!(val.is_non_audio());
+        assert_eq!(val.ratio(), 3);
+        assert_eq!(val.sample_bits(), SampleBits::Bits24);
+        assert!(val.is_channel_status_enabled());
+        assert!(val.is_enabled());
+        assert_eq!(val.0, 0x8003_003B);
+
+        let val = val
+            .set_single_mode(false)
+            .set_hold_last_sample(false)
+            .set_non_audio(false)
+            .disable_channel_status()
+            .disable();
+        assert_eq!(val.0, 0x0000_0038);
+    }
+
+    #[test]
+    fn struct_interrupt_status_functions() {
+        let val = InterruptStatus(0x0000_0050);
+        assert!(val.has_underrun());
+        assert!(!val.has_overrun());
+        assert!(val.is_tx_empty());
+        let val = InterruptStatus::default()
+            .clear_underrun()
+            .clear_overrun()
+            .clear_tx_empty();
+        assert_eq!(val.0, 0x0000_0070);
+    }
+
+    #[test]
+    fn struct_fifo_control_functions() {
+        let val = FifoControl::default();
+        assert_eq!(val.tx_trigger_level(), 0x40);
+        assert!(!val.is_tx_lsb_aligned());
+        let val = val.set_tx_trigger_level(0x20).set_tx_lsb_aligned(true);
+        assert!(val.is_tx_lsb_aligned());
+        assert_eq!(val.0, 0x0002_0204);
+        assert_eq!(val.set_tx_lsb_aligned(false).flush_tx_fifo().0, 0x4002_0200);
+    }
+
+    #[test]
+    fn struct_fifo_status_functions() {
+        let val = FifoStatus(0x8080_0000);
+        assert!(val.is_tx_empty());
+        assert_eq!(val.tx_empty_count(), 0x80);
+    }
+
+    #[test]
+    fn struct_interrupt_control_functions() {
+        let val = InterruptControl::default()
+            .enable_tx_drq()
+            .enable_underrun_interrupt()
+            .enable_overrun_interrupt()
+            .enable_empty_interrupt();
+        assert!(val.is_tx_drq_enabled());
+        assert!(val.is_underrun_interrupt_enabled());
+        assert!(val.is_overrun_interrupt_enabled());
+        assert!(val.is_empty_interrupt_enabled());
+        assert_eq!(val.0, 0x0000_00F0);
+        let val = val
+            .disable_tx_drq()
+            .disable_underrun_interrupt()
+            .disable_overrun_interrupt()
+            .disable_empty_interrupt();
+        assert_eq!(val.0, 0x0000_0000);
+    }
+
+    #[test]
+    fn struct_channel_status_functions() {
+        let val = ChannelStatus0::default()
+            .set_frequency(SampleRate::Hz48000.status_code())
+            .set_category(0x01)
+            .set_copy_permitted(true)
+            .set_non_audio(true);
+        assert_eq!(val.frequency(), 0x2);
+        assert_eq!(val.category(), 0x01);
+        assert!(val.is_copy_permitted());
+        assert!(val.is_non_audio());
+        assert!(!val.is_professional());
+        assert_eq!(val.0, 0x0200_0106);
+        assert_eq!(
+            val.set_copy_permitted(false).set_non_audio(false).0,
+            0x0200_0100
+        );
+
+        let val = ChannelStatus1::default()
+            .set_original_frequency(SampleRate::Hz48000.original_code())
+            .set_word_length(SampleBits::Bits16);
+        assert_eq!(val.original_frequency(), 0xD);
+        assert_eq!(val.word_length(), 0x2);
+        assert_eq!(val.0, 0x0000_00D2);
+        assert_eq!(val.set_word_length(SampleBits::Bits24).0, 0x0000_00DB);
+    }
+
+    #[test]
+    fn function_status_codes() {
+        assert_eq!(status_codes(48_000), (0x2, 0xD));
+        assert_eq!(status_codes(44_100), (0x0, 0xF));
+        assert_eq!(status_codes(176_400), (0xC, 0x3));
+        assert_eq!(status_codes(29_400), (0x1, 0x0));
+        assert_eq!(SampleRate::Hz32000.divider(), 768);
+        assert_eq!(SampleRate::Hz192000.divider(), 128);
+    }
+}
@@ -0,0 +1,138 @@
+use super::{Owa, RegisterBlock};
+use crate::audio::{self, Sink};
+use crate::audio_codec::Frame;
+use crate::dmac::{
+    AddressMode, BurstLength, Channel, DataWidth, Descriptor, DmaConfig, StreamParts, drq,
+};
+use crate::time::TimeoutError;
+use core::marker::PhantomData;
+use embedded_time::rate::Hertz;
+
+/// OWA frames fed by a DMA channel from two buffers of `L` frames each.
+///
+/// DMA sends both buffers in turn, endlessly; [`Stream::writable`] hands out a buffer once
+/// DMA has moved on to the other one, to be refilled. A buffer should be refilled before
+/// DMA comes back to it, that is within `L` frame periods, otherwise its frames are sent
+/// again.
+pub struct Stream<'a, const N: usize, const L: usize> {
+    channel: Channel<'a, N>,
+    buffers: *mut [[Frame; L]; 2],
+    descriptors: &'a mut [Descriptor; 2],
+    next: usize,
+    sample_rate: Hertz,
+    _buffers: PhantomData<&'a mut [[Frame; L]; 2]>,
+}
+
+impl<'a, const N: usize, const L: usize> Stream<'a, N, L> {
+    /// Start sending `buffers` in turn on DMA `channel`, using `descriptors` as
+    /// a two-descriptor ring.
+    ///
+    /// Both buffers should be filled before starting. Sample rate and format are set on
+    /// `owa` beforehand.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `L` is zero.
+    #[inline]
+    pub fn start<OWA: AsRef<RegisterBlock>>(
+        owa: &mut Owa<OWA>,
+        mut channel: Channel<'a, N>,
+        buffers: &'a mut [[Frame; L]; 2],
+        descriptors: &'a mut [Descriptor; 2],
+    ) -> Self {
+        assert!(L != 0, "stream buffer should hold at least one frame");
+        let config = DmaConfig {
+            source_drq: drq::DRAM,
+            source_address_mode: AddressMode::Linear,
+            source_burst: BurstLength::Single,
+            source_width: DataWidth::Bits16,
+            destination_drq: drq::OWA_TX,
+            destination_address_mode: AddressMode::Io,
+            destination_burst: BurstLength::Single,
+            destination_width: DataWidth::Bits16,
+            ..DmaConfig::default()
+        };
+        let destination = owa.fifo_address() as u32;
+        let byte_count = (L * size_of::<Frame>()) as u32;
+        for (descriptor, buffer) in descriptors.iter_mut().zip(buffers.iter()) {
+            *descriptor = Descriptor::new(
+                &config,
+                buffer.as_ptr() as usize as u32,
+                destination,
+                byte_count,
+            );
+        }
+        let (first, second) = descriptors.split_at_mut(1);
+        first[0].set_link(&second[0]);
+        second[0].set_link(&first[0]);
+        owa.start_dma();
+        // note(unsafe): buffers and descriptors are borrowed by `Stream` until stopped
+        unsafe { channel.start(&descriptors[0]) };
+        Self {
+            channel,
+            buffers,
+            descriptors,
+            next: 0,
+            sample_rate: owa.sample_rate(),
+            _buffers: PhantomData,
+        }
+    }
+    /// Get next buffer sent by DMA to be refilled, if any.
+    #[inline]
+    pub fn writable(&mut self) -> Option<&mut [Frame; L]> {
+        let end = self.buffers as usize as u32 + (L * size_of::<Frame>()) as u32;
+        // source right at the end of first buffer counts as second buffer
+        let reading = (self.channel.current_source() >= end) as usize;
+        if reading == self.next {
+            return None;
+        }
+        let index = self.next;
+        self.next ^= 1;
+        // note(unsafe): DMA is reading the other buffer
+        Some(unsafe { &mut (*self.buffers)[index] })
+    }
+    /// Get DMA channel, for example to enable `PackageEnd` interrupt on each sent buffer.
+    #[inline]
+    pub fn channel(&mut self) -> &mut Channel<'a, N> {
+        &mut self.channel
+    }
+    /// Stop sending and release DMA channel, buffers and descriptors.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`TimeoutError`] if DMA channel doesn't stop before crate-wide blocking
+    /// timeout; buffers are then not released.
+    #[inline]
+    pub fn stop<OWA: AsRef<RegisterBlock>>(
+        mut self,
+        owa: &mut Owa<OWA>,
+    ) -> Result<StreamParts<'a, N, [Frame; L]>, TimeoutError> {
+        self.channel.abort()?;
+        owa.stop_dma();
+        // note(unsafe): DMA has stopped, buffers are exclusively borrowed again
+        let buffers = unsafe { &mut *self.buffers };
+        Ok((self.channel, buffers, self.descriptors))
+    }
+}
+
+impl<const N: usize, const L: usize> audio::Stream for Stream<'_, N, L> {
+    type Sample = i16;
+
+    #[inline]
+    fn channels(&self) -> usize {
+        2
+    }
+    #[inline]
+    fn sample_rate(&self) -> Hertz {
+        self.sample_rate
+    }
+}
+
+impl<const N: usize, const L: usize> Sink for Stream<'_, N, L> {
+    #[inline]
+    fn write_buffer(&mut self) -> Option<&mut [i16]> {
+        self.writable().map(|buffer| buffer.as_flattened_mut())
+    }
+    #[inline]
+    fn submit_buffer(&mut self) {}
+}
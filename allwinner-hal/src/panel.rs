@@ -0,0 +1,387 @@
+//! Display panel power sequencing.
+//!
+//! Panels expect supplies, control signals, video and backlight to be switched in a fixed
+//! order with settling delays in between; driving video or backlight into an unpowered panel
+//! may latch up its driver. [`PowerSequence`] takes this order as board configuration and
+//! enforces it, following the prepare and enable steps found in panel datasheets:
+//!
+//! ```ignore
+//! let mut vdd = p.gpio.pd18.into_output();
+//! let mut reset = p.gpio.pd19.into_output();
+//! let backlight = pwm.split().pwm7.into_pwm(p.gpio.pd22.into_function::<5>(), Hertz(20_000))?;
+//! let timing = SequenceTiming {
+//!     prepare: Duration::from_millis(20),
+//!     enable: Duration::from_millis(200),
+//!     disable: Duration::from_millis(20),
+//!     unprepare: Duration::from_millis(500),
+//! };
+//! let mut panel = PowerSequence::new([&mut vdd, &mut reset], backlight, delay, timing);
+//! panel.prepare();
+//! tcon.enable();
+//! panel.enable()?;
+//! ```
+//!
+//! Enable pads are driven high in the order given, and low in reverse order; active-low
+//! signals such as panel reset are thus released when the panel is prepared.
+
+use crate::time::{Duration, duration_to_micros};
+use core::convert::Infallible;
+use embedded_hal::{digital::OutputPin, pwm::SetDutyCycle};
+
+/// Delays between the steps of a panel power sequence.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct SequenceTiming {
+    /// Delay after each enable pad is driven high, letting supply or reset settle.
+    pub prepare: Duration,
+    /// Delay between start of video signal and backlight turning on.
+    pub enable: Duration,
+    /// Delay between backlight turning off and stop of video signal.
+    pub disable: Duration,
+    /// Delay after enable pads are driven low, before the panel may be prepared again.
+    pub unprepare: Duration,
+}
+
+/// Power state of a panel.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum PanelState {
+    /// Enable pads are low and backlight is off.
+    Off,
+    /// Enable pads are high; video signal may be started or stopped.
+    Prepared,
+    /// Backlight is on.
+    Enabled,
+}
+
+/// Panel power sequence error.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Error<E> {
+    /// Panel is not prepared, so backlight can't be turned on.
+    NotPrepared,
+    /// Backlight PWM channel failed to set duty cycle.
+    Backlight(E),
+}
+
+/// Power sequence of a panel with `N` enable pads and a PWM backlight.
+pub struct PowerSequence<'a, const N: usize, BL, D> {
+    pads: [&'a mut dyn OutputPin<Error = Infallible>; N],
+    backlight: BL,
+    delay: D,
+    timing: SequenceTiming,
+    brightness: u8,
+    state: PanelState,
+}
+
+impl<'a, const N: usize, BL: SetDutyCycle, D> PowerSequence<'a, N, BL, D> {
+    /// Create power sequence of a panel at full brightness, turning backlight off and driving
+    /// enable `pads` low.
+    #[inline]
+    pub fn new(
+        mut pads: [&'a mut dyn OutputPin<Error = Infallible>; N],
+        mut backlight: BL,
+        delay: D,
+        timing: SequenceTiming,
+    ) -> Self {
+        let _ = backlight.set_duty_cycle_fully_off();
+        for pad in pads.iter_mut().rev() {
+            let _ = pad.set_low();
+        }
+        Self {
+            pads,
+            backlight,
+            delay,
+            timing,
+            brightness: 100,
+            state: PanelState::Off,
+        }
+    }
+    /// Get power state of panel.
+    #[inline]
+    pub fn state(&self) -> PanelState {
+        self.state
+    }
+    /// Get backlight brightness in percent.
+    #[inline]
+    pub fn brightness(&self) -> u8 {
+        self.brightness
+    }
+    /// Set backlight brightness in percent, saturating at 100; applied at once if panel is
+    /// enabled.
+    #[inline]
+    pub fn set_brightness(&mut self, percent: u8) -> Result<(), Error<BL::Error>> {
+        self.brightness = percent.min(100);
+        if self.state == PanelState::Enabled {
+            self.backlight
+                .set_duty_cycle_percent(self.brightness)
+                .map_err(Error::Backlight)?;
+        }
+        Ok(())
+    }
+    /// Release enable pads, backlight PWM channel and delay provider, leaving their state.
+    #[inline]
+    pub fn free(self) -> ([&'a mut dyn OutputPin<Error = Infallible>; N], BL, D) {
+        (self.pads, self.backlight, self.delay)
+    }
+}
+
+impl<'a, const N: usize, BL: SetDutyCycle, D: embedded_hal::delay::DelayNs>
+    PowerSequence<'a, N, BL, D>
+{
+    /// Drive enable pads high in order, waiting after each one.
+    ///
+    /// Does nothing unless panel is off.
+    #[inline]
+    pub fn prepare(&mut self) {
+        if self.state != PanelState::Off {
+            return;
+        }
+        for pad in self.pads.iter_mut() {
+            let _ = pad.set_high();
+            self.delay.delay_us(duration_to_micros(self.timing.prepare));
+        }
+        self.state = PanelState::Prepared;
+    }
+    /// Turn backlight on after video signal has been started.
+    ///
+    /// Does nothing if panel is already enabled.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::NotPrepared`] if panel is off.
+    #[inline]
+    pub fn enable(&mut self) -> Result<(), Error<BL::Error>> {
+        match self.state {
+            PanelState::Off => return Err(Error::NotPrepared),
+            PanelState::Enabled => return Ok(()),
+            PanelState::Prepared => {}
+        }
+        self.delay.delay_us(duration_to_micros(self.timing.enable));
+        self.backlight
+            .set_duty_cycle_percent(self.brightness)
+            .map_err(Error::Backlight)?;
+        self.state = PanelState::Enabled;
+        Ok(())
+    }
+    /// Turn backlight off, after which video signal may be stopped.
+    ///
+    /// Does nothing unless panel is enabled.
+    #[inline]
+    pub fn disable(&mut self) -> Result<(), Error<BL::Error>> {
+        if self.state != PanelState::Enabled {
+            return Ok(());
+        }
+        self.backlight
+            .set_duty_cycle_fully_off()
+            .map_err(Error::Backlight)?;
+        self.state = PanelState::Prepared;
+        self.delay.delay_us(duration_to_micros(self.timing.disable));
+        Ok(())
+    }
+    /// Drive enable pads low in reverse order after video signal has stopped, disabling panel
+    /// first if it's enabled.
+    #[inline]
+    pub fn unprepare(&mut self) -> Result<(), Error<BL::Error>> {
+        self.disable()?;
+        if self.state != PanelState::Prepared {
+            return Ok(());
+        }
+        for pad in self.pads.iter_mut().rev() {
+            let _ = pad.set_low();
+        }
+        self.state = PanelState::Off;
+        self.delay
+            .delay_us(duration_to_micros(self.timing.unprepare));
+        Ok(())
+    }
+}
+
+impl<'a, const N: usize, BL: SetDutyCycle, D: embedded_hal_async::delay::DelayNs>
+    PowerSequence<'a, N, BL, D>
+{
+    /// Drive enable pads high in order, waiting asynchronously after each one.
+    ///
+    /// Does nothing unless panel is off.
+    #[inline]
+    pub async fn prepare_async(&mut self) {
+        if self.state != PanelState::Off {
+            return;
+        }
+        for pad in self.pads.iter_mut() {
+            let _ = pad.set_high();
+            self.delay
+                .delay_us(duration_to_micros(self.timing.prepare))
+                .await;
+        }
+        self.state = PanelState::Prepared;
+    }
+    /// Turn backlight on after video signal has been started, waiting asynchronously.
+    ///
+    /// Does nothing if panel is already enabled.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::NotPrepared`] if panel is off.
+    #[inline]
+    pub async fn enable_async(&mut self) -> Result<(), Error<BL::Error>> {
+        match self.state {
+            PanelState::Off => return Err(Error::NotPrepared),
+            PanelState::Enabled => return Ok(()),
+            PanelState::Prepared => {}
+        }
+        self.delay
+            .delay_us(duration_to_micros(self.timing.enable))
+            .await;
+        self.backlight
+            .set_duty_cycle_percent(self.brightness)
+            .map_err(Error::Backlight)?;
+        self.state = PanelState::Enabled;
+        Ok(())
+    }
+    /// Turn backlight off, waiting asynchronously before video signal may be stopped.
+    ///
+    /// Does nothing unless panel is enabled.
+    #[inline]
+    pub async fn disable_async(&mut self) -> Result<(), Error<BL::Error>> {
+        if self.state != PanelState::Enabled {
+            return Ok(());
+        }
+        self.backlight
+            .set_duty_cycle_fully_off()
+            .map_err(Error::Backlight)?;
+        self.state = PanelState::Prepared;
+        self.delay
+            .delay_us(duration_to_micros(self.timing.disable))
+            .await;
+        Ok(())
+    }
+    /// Drive enable pads low in reverse order after video signal has stopped, disabling panel
+    /// first if it's enabled, and wait asynchronously.
+    #[inline]
+    pub async fn unprepare_async(&mut self) -> Result<(), Error<BL::Error>> {
+        self.disable_async().await?;
+        if self.state != PanelState::Prepared {
+            return Ok(());
+        }
+        for pad in self.pads.iter_mut().rev() {
+            let _ = pad.set_low();
+        }
+        self.state = PanelState::Off;
+        self.delay
+            .delay_us(duration_to_micros(self.timing.unprepare))
+            .await;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Error, PanelState, PowerSequence, SequenceTiming};
+    use crate::time::Duration;
+    use core::{cell::Cell, convert::Infallible};
+    use embedded_hal::{delay::DelayNs, digital::OutputPin, pwm::SetDutyCycle};
+
+    /// Pad levels, backlight duty cycle and elapsed microseconds shared by mocks.
+    #[derive(Default)]
+    struct Board {
+        vdd: Cell<bool>,
+        reset: Cell<bool>,
+        duty: Cell<u16>,
+        elapsed: Cell<u32>,
+    }
+
+    struct MockPin<'a>(&'a Cell<bool>);
+
+    impl embedded_hal::digital::ErrorType for MockPin<'_> {
+        type Error = Infallible;
+    }
+
+    impl OutputPin for MockPin<'_> {
+        fn set_low(&mut self) -> Result<(), Self::Error> {
+            self.0.set(false);
+            Ok(())
+        }
+        fn set_high(&mut self) -> Result<(), Self::Error> {
+            self.0.set(true);
+            Ok(())
+        }
+    }
+
+    struct MockBacklight<'a>(&'a Board);
+
+    impl embedded_hal::pwm::ErrorType for MockBacklight<'_> {
+        type Error = Infallible;
+    }
+
+    impl SetDutyCycle for MockBacklight<'_> {
+        fn max_duty_cycle(&self) -> u16 {
+            1000
+        }
+        fn set_duty_cycle(&mut self, duty: u16) -> Result<(), Self::Error> {
+            // backlight must never light an unpowered panel
+            assert!(duty == 0 || (self.0.vdd.get() && self.0.reset.get()));
+            self.0.duty.set(duty);
+            Ok(())
+        }
+    }
+
+    struct MockDelay<'a>(&'a Board);
+
+    impl DelayNs for MockDelay<'_> {
+        fn delay_ns(&mut self, ns: u32) {
+            self.0.elapsed.set(self.0.elapsed.get() + ns / 1000);
+        }
+        fn delay_us(&mut self, us: u32) {
+            self.0.elapsed.set(self.0.elapsed.get() + us);
+        }
+    }
+
+    #[test]
+    fn struct_power_sequence() {
+        let board = Board::default();
+        board.duty.set(500);
+        board.vdd.set(true);
+        let (mut vdd, mut reset) = (MockPin(&board.vdd), MockPin(&board.reset));
+        let timing = SequenceTiming {
+            prepare: Duration::from_millis(20),
+            enable: Duration::from_millis(200),
+            disable: Duration::from_millis(10),
+            unprepare: Duration::from_millis(500),
+        };
+        let mut panel = PowerSequence::new(
+            [&mut vdd, &mut reset],
+            MockBacklight(&board),
+            MockDelay(&board),
+            timing,
+        );
+        assert_eq!(panel.state(), PanelState::Off);
+        assert!(!board.vdd.get() && board.duty.get() == 0);
+        assert_eq!(panel.enable(), Err(Error::NotPrepared));
+
+        panel.prepare();
+        assert!(board.vdd.get() && board.reset.get());
+        assert_eq!(board.elapsed.get(), 40_000);
+        panel.prepare();
+        assert_eq!(board.elapsed.get(), 40_000);
+
+        panel.set_brightness(150).unwrap();
+        assert_eq!(panel.brightness(), 100);
+        assert_eq!(board.duty.get(), 0);
+        panel.enable().unwrap();
+        assert_eq!(panel.state(), PanelState::Enabled);
+        assert_eq!(board.duty.get(), 1000);
+        assert_eq!(board.elapsed.get(), 240_000);
+        panel.set_brightness(40).unwrap();
+        assert_eq!(board.duty.get(), 400);
+
+        panel.unprepare().unwrap();
+        assert_eq!(panel.state(), PanelState::Off);
+        assert!(!board.vdd.get() && !board.reset.get());
+        assert_eq!(board.duty.get(), 0);
+        assert_eq!(board.elapsed.get(), 750_000);
+        panel.disable().unwrap();
+        assert_eq!(board.elapsed.get(), 750_000);
+
+        panel.prepare();
+        panel.enable().unwrap();
+        assert_eq!(board.duty.get(), 400);
+    }
+}
@@ -0,0 +1,228 @@
+//! CPU performance counters for micro-benchmarks.
+//!
+//! Counters are read from RISC-V `cycle` and `instret` CSRs. On other targets, such as
+//! host builds running tests, they always read zero.
+
+use core::ops::{Add, Sub};
+use embedded_time::rate::Hertz;
+
+/// Number of CPU clock cycles.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Cycles(pub u64);
+
+impl Cycles {
+    /// Read current CPU cycle counter.
+    #[inline]
+    pub fn now() -> Self {
+        Cycles(cycle_counter())
+    }
+    /// Convert to nanoseconds at CPU clock `frequency`.
+    #[inline]
+    pub const fn as_nanos(self, frequency: Hertz) -> u64 {
+        (self.0 as u128 * 1_000_000_000 / frequency.0 as u128) as u64
+    }
+}
+
+impl Add for Cycles {
+    type Output = Cycles;
+
+    #[inline]
+    fn add(self, rhs: Cycles) -> Cycles {
+        Cycles(self.0.wrapping_add(rhs.0))
+    }
+}
+
+impl Sub for Cycles {
+    type Output = Cycles;
+
+    #[inline]
+    fn sub(self, rhs: Cycles) -> Cycles {
+        Cycles(self.0.wrapping_sub(rhs.0))
+    }
+}
+
+/// Performance counter values at one point of execution.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Sample {
+    /// CPU clock cycles.
+    pub cycles: Cycles,
+    /// Retired instructions.
+    pub instructions: u64,
+}
+
+impl Sample {
+    /// Read current counter values.
+    #[inline]
+    pub fn now() -> Self {
+        let (cycles, instructions) = counters();
+        Self {
+            cycles: Cycles(cycles),
+            instructions,
+        }
+    }
+    /// Get counter differences since `earlier` sample.
+    #[inline]
+    pub const fn since(self, earlier: Sample) -> Sample {
+        Sample {
+            cycles: Cycles(self.cycles.0.wrapping_sub(earlier.cycles.0)),
+            instructions: self.instructions.wrapping_sub(earlier.instructions),
+        }
+    }
+}
+
+/// Measure CPU clock cycles spent in `f`.
+#[inline]
+pub fn measure(f: impl FnOnce()) -> Cycles {
+    measure_sample(f).cycles
+}
+
+/// Measure CPU clock cycles and retired instructions spent in `f`.
+#[inline]
+pub fn measure_sample(f: impl FnOnce()) -> Sample {
+    let start = Sample::now();
+    f();
+    Sample::now().since(start)
+}
+
+/// Accumulated statistics of a measured code section.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Section {
+    /// Number of recorded runs.
+    pub runs: u32,
+    /// Total cycles of all runs.
+    pub total: Cycles,
+    /// Fewest cycles of one run.
+    pub min: Cycles,
+    /// Most cycles of one run.
+    pub max: Cycles,
+}
+
+impl Section {
+    /// Create an empty section counter.
+    #[inline]
+    pub const fn new() -> Self {
+        Self {
+            runs: 0,
+            total: Cycles(0),
+            min: Cycles(u64::MAX),
+            max: Cycles(0),
+        }
+    }
+    /// Run `f` and record its cycles, returning its output.
+    #[inline]
+    pub fn measure<R>(&mut self, f: impl FnOnce() -> R) -> R {
+        let start = Sample::now();
+        let ans = f();
+        self.record(Sample::now().since(start).cycles);
+        ans
+    }
+    /// Record cycles of one run.
+    #[inline]
+    pub fn record(&mut self, cycles: Cycles) {
+        self.runs = self.runs.saturating_add(1);
+        self.total = self.total + cycles;
+        self.min = self.min.min(cycles);
+        self.max = self.max.max(cycles);
+    }
+    /// Get average cycles per run, or `None` if nothing is recorded.
+    #[inline]
+    pub const fn average(&self) -> Option<Cycles> {
+        if self.runs == 0 {
+            return None;
+        }
+        Some(Cycles(self.total.0 / self.runs as u64))
+    }
+    /// Clear recorded runs.
+    #[inline]
+    pub fn reset(&mut self) {
+        *self = Self::new();
+    }
+}
+
+impl Default for Section {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(target_arch = "riscv64")]
+#[inline]
+fn counters() -> (u64, u64) {
+    let (cycles, instructions): (u64, u64);
+    unsafe {
+        core::arch::asm!(
+            "rdcycle {}",
+            "rdinstret {}",
+            out(reg) cycles,
+            out(reg) instructions,
+        )
+    };
+    (cycles, instructions)
+}
+
+#[cfg(not(target_arch = "riscv64"))]
+#[inline]
+fn counters() -> (u64, u64) {
+    (0, 0)
+}
+
+#[cfg(target_arch = "riscv64")]
+#[inline]
+fn cycle_counter() -> u64 {
+    let cycles: u64;
+    unsafe { core::arch::asm!("rdcycle {}", out(reg) cycles) };
+    cycles
+}
+
+#[cfg(not(target_arch = "riscv64"))]
+#[inline]
+fn cycle_counter() -> u64 {
+    0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Cycles, Sample, Section};
+    use embedded_time::rate::Hertz;
+
+    #[test]
+    fn struct_cycles_functions() {
+        assert_eq!(Cycles(1_008).as_nanos(Hertz(1_008_000_000)), 1_000);
+        assert_eq!(Cycles(10) - Cycles(4), Cycles(6));
+        assert_eq!(Cycles(10) + Cycles(4), Cycles(14));
+        let later = Sample {
+            cycles: Cycles(5),
+            instructions: 2,
+        };
+        let earlier = Sample {
+            cycles: Cycles(u64::MAX),
+            instructions: 1,
+        };
+        assert_eq!(
+            later.since(earlier),
+            Sample {
+                cycles: Cycles(6),
+                instructions: 1
+            }
+        );
+    }
+
+    #[test]
+    fn struct_section_functions() {
+        let mut section = Section::new();
+        assert_eq!(section.average(), None);
+        section.record(Cycles(100));
+        section.record(Cycles(300));
+        section.record(Cycles(200));
+        assert_eq!(section.runs, 3);
+        assert_eq!(section.total, Cycles(600));
+        assert_eq!(section.min, Cycles(100));
+        assert_eq!(section.max, Cycles(300));
+        assert_eq!(section.average(), Some(Cycles(200)));
+        section.reset();
+        assert_eq!(section, Section::default());
+        assert_eq!(section.measure(|| 42), 42);
+        assert_eq!(section.min, Cycles(0));
+    }
+}
@@ -0,0 +1,211 @@
+//! X-Powers AXP power management ICs.
+//!
+//! AXP228 and AXP717 are commonly paired with these SoCs to supply CPU, DRAM and I/O rails,
+//! charge a single-cell battery and report power key presses. Drivers talk to them over any
+//! bus implementing `embedded_hal::i2c::I2c`.
+
+pub mod axp228;
+pub mod axp717;
+
+use embedded_hal::i2c::I2c;
+
+/// Default 7-bit I2C address of AXP power management ICs.
+pub const ADDRESS: u8 = 0x34;
+
+/// PMIC error.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Error<E> {
+    /// Bus transfer failed.
+    I2c(E),
+    /// Requested voltage is out of range of the rail.
+    InvalidVoltage,
+    /// Requested charger setting is not supported.
+    InvalidChargerConfig,
+}
+
+/// Power key events since last read.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct PowerKeyEvents {
+    /// Power key was pressed shortly.
+    pub short_press: bool,
+    /// Power key was held longer than long press time.
+    pub long_press: bool,
+}
+
+/// Charger configuration.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct ChargerConfig {
+    /// Enable battery charging.
+    pub enable: bool,
+    /// Battery target voltage in millivolts.
+    pub target_millivolts: u16,
+    /// Constant charge current in milliamps, rounded down to a supported step.
+    pub current_milliamps: u16,
+}
+
+/// Power supply status.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct PowerStatus {
+    /// VBUS input is present and usable.
+    pub vbus_present: bool,
+    /// Battery is connected.
+    pub battery_present: bool,
+    /// Battery is being charged.
+    pub charging: bool,
+}
+
+/// Linear voltage range of a rail, in millivolts.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub(crate) struct VoltageRange {
+    /// Voltage of first selector in this range.
+    pub min: u16,
+    /// Voltage step between two selectors.
+    pub step: u16,
+    /// First selector of this range.
+    pub first: u8,
+    /// Last selector of this range, inclusive.
+    pub last: u8,
+}
+
+impl VoltageRange {
+    #[inline]
+    pub(crate) const fn new(min: u16, step: u16, first: u8, last: u8) -> Self {
+        Self {
+            min,
+            step,
+            first,
+            last,
+        }
+    }
+}
+
+/// Get selector of exactly `millivolts` in `ranges`, if any.
+#[inline]
+pub(crate) fn voltage_to_selector(ranges: &[VoltageRange], millivolts: u16) -> Option<u8> {
+    ranges.iter().find_map(|range| {
+        let max = range.min + range.step * (range.last - range.first) as u16;
+        if millivolts < range.min
+            || millivolts > max
+            || !(millivolts - range.min).is_multiple_of(range.step)
+        {
+            return None;
+        }
+        Some(range.first + ((millivolts - range.min) / range.step) as u8)
+    })
+}
+
+/// Get voltage of `selector` in `ranges`, if any range contains it.
+#[inline]
+pub(crate) fn selector_to_voltage(ranges: &[VoltageRange], selector: u8) -> Option<u16> {
+    ranges
+        .iter()
+        .find(|range| range.first <= selector && selector <= range.last)
+        .map(|range| range.min + range.step * (selector - range.first) as u16)
+}
+
+/// Registers of one power rail.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub(crate) struct RailDescriptor {
+    /// Voltage register and selector mask, if voltage is adjustable.
+    pub voltage: Option<(u8, u8)>,
+    /// Voltage ranges of selectors.
+    pub ranges: &'static [VoltageRange],
+    /// Enable register and bit mask.
+    pub enable: (u8, u8),
+}
+
+/// Register access shared by AXP drivers.
+pub(crate) struct Registers<I2C> {
+    pub i2c: I2C,
+    pub address: u8,
+}
+
+impl<I2C: I2c> Registers<I2C> {
+    #[inline]
+    pub(crate) fn read(&mut self, register: u8) -> Result<u8, Error<I2C::Error>> {
+        let mut buf = [0];
+        self.i2c
+            .write_read(self.address, &[register], &mut buf)
+            .map_err(Error::I2c)?;
+        Ok(buf[0])
+    }
+    #[inline]
+    pub(crate) fn write(&mut self, register: u8, value: u8) -> Result<(), Error<I2C::Error>> {
+        self.i2c
+            .write(self.address, &[register, value])
+            .map_err(Error::I2c)
+    }
+    #[inline]
+    pub(crate) fn modify(
+        &mut self,
+        register: u8,
+        mask: u8,
+        value: u8,
+    ) -> Result<(), Error<I2C::Error>> {
+        let old = self.read(register)?;
+        self.write(register, (old & !mask) | (value & mask))
+    }
+    #[inline]
+    pub(crate) fn set_rail_enabled(
+        &mut self,
+        rail: &RailDescriptor,
+        enabled: bool,
+    ) -> Result<(), Error<I2C::Error>> {
+        let (register, mask) = rail.enable;
+        self.modify(register, mask, if enabled { mask } else { 0 })
+    }
+    #[inline]
+    pub(crate) fn is_rail_enabled(
+        &mut self,
+        rail: &RailDescriptor,
+    ) -> Result<bool, Error<I2C::Error>> {
+        let (register, mask) = rail.enable;
+        Ok(self.read(register)? & mask != 0)
+    }
+    #[inline]
+    pub(crate) fn set_rail_voltage(
+        &mut self,
+        rail: &RailDescriptor,
+        millivolts: u16,
+    ) -> Result<(), Error<I2C::Error>> {
+        let (register, mask) = rail.voltage.ok_or(Error::InvalidVoltage)?;
+        let selector = voltage_to_selector(rail.ranges, millivolts).ok_or(Error::InvalidVoltage)?;
+        self.modify(register, mask, selector)
+    }
+    #[inline]
+    pub(crate) fn rail_voltage(
+        &mut self,
+        rail: &RailDescriptor,
+    ) -> Result<Option<u16>, Error<I2C::Error>> {
+        let Some((register, mask)) = rail.voltage else {
+            return Ok(None);
+        };
+        let selector = self.read(register)? & mask;
+        Ok(selector_to_voltage(rail.ranges, selector))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{VoltageRange, selector_to_voltage, voltage_to_selector};
+
+    const RANGES: [VoltageRange; 2] = [
+        VoltageRange::new(500, 10, 0, 70),
+        VoltageRange::new(1220, 20, 71, 87),
+    ];
+
+    #[test]
+    fn function_voltage_selector() {
+        assert_eq!(voltage_to_selector(&RANGES, 500), Some(0));
+        assert_eq!(voltage_to_selector(&RANGES, 1200), Some(70));
+        assert_eq!(voltage_to_selector(&RANGES, 1220), Some(71));
+        assert_eq!(voltage_to_selector(&RANGES, 1540), Some(87));
+        assert_eq!(voltage_to_selector(&RANGES, 1210), None);
+        assert_eq!(voltage_to_selector(&RANGES, 1560), None);
+        assert_eq!(voltage_to_selector(&RANGES, 400), None);
+
+        assert_eq!(selector_to_voltage(&RANGES, 70), Some(1200));
+        assert_eq!(selector_to_voltage(&RANGES, 80), Some(1400));
+        assert_eq!(selector_to_voltage(&RANGES, 88), None);
+    }
+}
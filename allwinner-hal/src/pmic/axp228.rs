@@ -0,0 +1,325 @@
+//! AXP228 (AXP221s and AXP223 compatible) power management IC.
+
+use super::{
+    ADDRESS, ChargerConfig, Error, PowerKeyEvents, PowerStatus, RailDescriptor, Registers,
+    VoltageRange,
+};
+use crate::usb::{VbusDrive, VbusSense};
+use embedded_hal::i2c::I2c;
+
+const POWER_STATUS: u8 = 0x00;
+const CHARGE_STATUS: u8 = 0x01;
+const OUTPUT_CONTROL_1: u8 = 0x10;
+const OUTPUT_CONTROL_2: u8 = 0x12;
+const OUTPUT_CONTROL_3: u8 = 0x13;
+const VBUS_IPSOUT: u8 = 0x30;
+const CHARGE_CONTROL_1: u8 = 0x33;
+const IRQ_ENABLE_3: u8 = 0x42;
+const IRQ_STATUS_3: u8 = 0x4A;
+const BATTERY_VOLTAGE_HIGH: u8 = 0x78;
+const BATTERY_VOLTAGE_LOW: u8 = 0x79;
+const ADC_ENABLE: u8 = 0x82;
+const MISC_CONTROL: u8 = 0x8F;
+const FUEL_GAUGE: u8 = 0xB9;
+
+const VBUS_PRESENT: u8 = 1 << 5;
+const BATTERY_PRESENT: u8 = 1 << 5;
+const CHARGING: u8 = 1 << 6;
+const CHARGER_ENABLE: u8 = 1 << 7;
+const CHARGE_TARGET_VOLTAGE: u8 = 0x3 << 5;
+const CHARGE_CURRENT: u8 = 0xF;
+const POWER_KEY_SHORT: u8 = 1 << 1;
+const POWER_KEY_LONG: u8 = 1 << 0;
+const BATTERY_VOLTAGE_ADC: u8 = 1 << 7;
+const DRIVE_VBUS: u8 = 1 << 2;
+const DRIVE_VBUS_PIN_ENABLE: u8 = 1 << 4;
+const FUEL_GAUGE_VALID: u8 = 1 << 7;
+const FUEL_GAUGE_PERCENT: u8 = 0x7F;
+
+const DCDC1_RANGES: [VoltageRange; 1] = [VoltageRange::new(1600, 100, 0, 18)];
+const DCDC2_RANGES: [VoltageRange; 1] = [VoltageRange::new(600, 20, 0, 47)];
+const DCDC3_RANGES: [VoltageRange; 1] = [VoltageRange::new(600, 20, 0, 63)];
+const DCDC5_RANGES: [VoltageRange; 1] = [VoltageRange::new(1000, 50, 0, 31)];
+const DC5LDO_RANGES: [VoltageRange; 1] = [VoltageRange::new(700, 100, 0, 7)];
+const LDO_RANGES: [VoltageRange; 1] = [VoltageRange::new(700, 100, 0, 26)];
+
+/// AXP228 power rail.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Rail {
+    /// DC-DC converter 1, 1.6 to 3.4 V in 100-mV steps.
+    Dcdc1,
+    /// DC-DC converter 2, 0.6 to 1.54 V in 20-mV steps.
+    Dcdc2,
+    /// DC-DC converter 3, 0.6 to 1.86 V in 20-mV steps.
+    Dcdc3,
+    /// DC-DC converter 4, 0.6 to 1.54 V in 20-mV steps.
+    Dcdc4,
+    /// DC-DC converter 5, 1.0 to 2.55 V in 50-mV steps.
+    Dcdc5,
+    /// LDO fed by DC-DC converter 5, 0.7 to 1.4 V in 100-mV steps.
+    Dc5ldo,
+    /// Switch output of DC-DC converter 1.
+    Dc1sw,
+    /// Analog LDO 1, 0.7 to 3.3 V in 100-mV steps.
+    Aldo1,
+    /// Analog LDO 2, 0.7 to 3.3 V in 100-mV steps.
+    Aldo2,
+    /// Analog LDO 3, 0.7 to 3.3 V in 100-mV steps.
+    Aldo3,
+    /// Digital LDO 1, 0.7 to 3.3 V in 100-mV steps.
+    Dldo1,
+    /// Digital LDO 2, 0.7 to 3.3 V in 100-mV steps.
+    Dldo2,
+    /// Digital LDO 3, 0.7 to 3.3 V in 100-mV steps.
+    Dldo3,
+    /// Digital LDO 4, 0.7 to 3.3 V in 100-mV steps.
+    Dldo4,
+    /// Extended LDO 1, 0.7 to 3.3 V in 100-mV steps.
+    Eldo1,
+    /// Extended LDO 2, 0.7 to 3.3 V in 100-mV steps.
+    Eldo2,
+    /// Extended LDO 3, 0.7 to 3.3 V in 100-mV steps.
+    Eldo3,
+}
+
+impl Rail {
+    #[inline]
+    const fn descriptor(self) -> RailDescriptor {
+        const fn rail(
+            voltage: u8,
+            mask: u8,
+            ranges: &'static [VoltageRange],
+            enable: (u8, u8),
+        ) -> RailDescriptor {
+            RailDescriptor {
+                voltage: Some((voltage, mask)),
+                ranges,
+                enable,
+            }
+        }
+        match self {
+            Rail::Dcdc1 => rail(0x21, 0x1F, &DCDC1_RANGES, (OUTPUT_CONTROL_1, 1 << 1)),
+            Rail::Dcdc2 => rail(0x22, 0x3F, &DCDC2_RANGES, (OUTPUT_CONTROL_1, 1 << 2)),
+            Rail::Dcdc3 => rail(0x23, 0x3F, &DCDC3_RANGES, (OUTPUT_CONTROL_1, 1 << 3)),
+            Rail::Dcdc4 => rail(0x24, 0x3F, &DCDC2_RANGES, (OUTPUT_CONTROL_1, 1 << 4)),
+            Rail::Dcdc5 => rail(0x25, 0x1F, &DCDC5_RANGES, (OUTPUT_CONTROL_1, 1 << 5)),
+            Rail::Dc5ldo => rail(0x1C, 0x07, &DC5LDO_RANGES, (OUTPUT_CONTROL_1, 1 << 0)),
+            Rail::Dc1sw => RailDescriptor {
+                voltage: None,
+                ranges: &[],
+                enable: (OUTPUT_CONTROL_2, 1 << 7),
+            },
+            Rail::Aldo1 => rail(0x28, 0x1F, &LDO_RANGES, (OUTPUT_CONTROL_3, 1 << 6)),
+            Rail::Aldo2 => rail(0x29, 0x1F, &LDO_RANGES, (OUTPUT_CONTROL_3, 1 << 7)),
+            Rail::Aldo3 => rail(0x2A, 0x1F, &LDO_RANGES, (OUTPUT_CONTROL_1, 1 << 7)),
+            Rail::Dldo1 => rail(0x15, 0x1F, &LDO_RANGES, (OUTPUT_CONTROL_2, 1 << 3)),
+            Rail::Dldo2 => rail(0x16, 0x1F, &LDO_RANGES, (OUTPUT_CONTROL_2, 1 << 4)),
+            Rail::Dldo3 => rail(0x17, 0x1F, &LDO_RANGES, (OUTPUT_CONTROL_2, 1 << 5)),
+            Rail::Dldo4 => rail(0x18, 0x1F, &LDO_RANGES, (OUTPUT_CONTROL_2, 1 << 6)),
+            Rail::Eldo1 => rail(0x19, 0x1F, &LDO_RANGES, (OUTPUT_CONTROL_2, 1 << 0)),
+            Rail::Eldo2 => rail(0x1A, 0x1F, &LDO_RANGES, (OUTPUT_CONTROL_2, 1 << 1)),
+            Rail::Eldo3 => rail(0x1B, 0x1F, &LDO_RANGES, (OUTPUT_CONTROL_2, 1 << 2)),
+        }
+    }
+}
+
+/// AXP228 driver over I2C bus.
+pub struct Axp228<I2C> {
+    regs: Registers<I2C>,
+}
+
+impl<I2C: I2c> Axp228<I2C> {
+    /// Create an AXP228 driver at default address.
+    #[inline]
+    pub fn new(i2c: I2C) -> Self {
+        Self {
+            regs: Registers {
+                i2c,
+                address: ADDRESS,
+            },
+        }
+    }
+    /// Enable output of `rail`.
+    #[inline]
+    pub fn enable_rail(&mut self, rail: Rail) -> Result<(), Error<I2C::Error>> {
+        self.regs.set_rail_enabled(&rail.descriptor(), true)
+    }
+    /// Disable output of `rail`.
+    #[inline]
+    pub fn disable_rail(&mut self, rail: Rail) -> Result<(), Error<I2C::Error>> {
+        self.regs.set_rail_enabled(&rail.descriptor(), false)
+    }
+    /// Check if output of `rail` is enabled.
+    #[inline]
+    pub fn is_rail_enabled(&mut self, rail: Rail) -> Result<bool, Error<I2C::Error>> {
+        self.regs.is_rail_enabled(&rail.descriptor())
+    }
+    /// Set output voltage of `rail` in millivolts, which should be exactly on a step.
+    #[inline]
+    pub fn set_voltage(&mut self, rail: Rail, millivolts: u16) -> Result<(), Error<I2C::Error>> {
+        self.regs.set_rail_voltage(&rail.descriptor(), millivolts)
+    }
+    /// Get output voltage of `rail` in millivolts, or `None` for switch outputs.
+    #[inline]
+    pub fn voltage(&mut self, rail: Rail) -> Result<Option<u16>, Error<I2C::Error>> {
+        self.regs.rail_voltage(&rail.descriptor())
+    }
+    /// Configure battery charger.
+    ///
+    /// Target voltage is one of 4100, 4200, 4220 or 4240 millivolts; charge current is
+    /// from 300 to 2550 milliamps in 150-mA steps.
+    #[inline]
+    pub fn configure_charger(&mut self, config: ChargerConfig) -> Result<(), Error<I2C::Error>> {
+        let voltage = match config.target_millivolts {
+            4100 => 0,
+            4220 => 1,
+            4200 => 2,
+            4240 => 3,
+            _ => return Err(Error::InvalidChargerConfig),
+        };
+        if !(300..=2550).contains(&config.current_milliamps) {
+            return Err(Error::InvalidChargerConfig);
+        }
+        let current = ((config.current_milliamps - 300) / 150) as u8;
+        let enable = if config.enable { CHARGER_ENABLE } else { 0 };
+        self.regs.modify(
+            CHARGE_CONTROL_1,
+            CHARGER_ENABLE | CHARGE_TARGET_VOLTAGE | CHARGE_CURRENT,
+            enable | (voltage << 5) | current,
+        )
+    }
+    /// Get power supply status.
+    #[inline]
+    pub fn power_status(&mut self) -> Result<PowerStatus, Error<I2C::Error>> {
+        let power = self.regs.read(POWER_STATUS)?;
+        let charge = self.regs.read(CHARGE_STATUS)?;
+        Ok(PowerStatus {
+            vbus_present: power & VBUS_PRESENT != 0,
+            battery_present: charge & BATTERY_PRESENT != 0,
+            charging: charge & CHARGING != 0,
+        })
+    }
+    /// Get battery capacity in percent from fuel gauge, or `None` if not yet valid.
+    #[inline]
+    pub fn battery_percent(&mut self) -> Result<Option<u8>, Error<I2C::Error>> {
+        let val = self.regs.read(FUEL_GAUGE)?;
+        Ok((val & FUEL_GAUGE_VALID != 0).then_some(val & FUEL_GAUGE_PERCENT))
+    }
+    /// Get battery voltage in millivolts, enabling its ADC if needed.
+    #[inline]
+    pub fn battery_millivolts(&mut self) -> Result<u16, Error<I2C::Error>> {
+        self.regs
+            .modify(ADC_ENABLE, BATTERY_VOLTAGE_ADC, BATTERY_VOLTAGE_ADC)?;
+        let high = self.regs.read(BATTERY_VOLTAGE_HIGH)? as u32;
+        let low = self.regs.read(BATTERY_VOLTAGE_LOW)? as u32 & 0xF;
+        // 1.1 mV per step.
+        Ok((((high << 4) | low) * 11 / 10) as u16)
+    }
+    /// Enable power key press events.
+    #[inline]
+    pub fn enable_power_key_events(&mut self) -> Result<(), Error<I2C::Error>> {
+        let mask = POWER_KEY_SHORT | POWER_KEY_LONG;
+        self.regs.modify(IRQ_ENABLE_3, mask, mask)
+    }
+    /// Read and clear power key events since last call.
+    #[inline]
+    pub fn power_key_events(&mut self) -> Result<PowerKeyEvents, Error<I2C::Error>> {
+        let status = self.regs.read(IRQ_STATUS_3)? & (POWER_KEY_SHORT | POWER_KEY_LONG);
+        if status != 0 {
+            self.regs.write(IRQ_STATUS_3, status)?;
+        }
+        Ok(PowerKeyEvents {
+            short_press: status & POWER_KEY_SHORT != 0,
+            long_press: status & POWER_KEY_LONG != 0,
+        })
+    }
+    /// Release I2C bus.
+    #[inline]
+    pub fn free(self) -> I2C {
+        self.regs.i2c
+    }
+}
+
+impl<I2C: I2c> VbusDrive for Axp228<I2C> {
+    type Error = Error<I2C::Error>;
+    /// Switch external VBUS supply through N_VBUSEN pin.
+    #[inline]
+    fn set_vbus(&mut self, enabled: bool) -> Result<(), Self::Error> {
+        self.regs
+            .modify(MISC_CONTROL, DRIVE_VBUS_PIN_ENABLE, DRIVE_VBUS_PIN_ENABLE)?;
+        let drive = if enabled { DRIVE_VBUS } else { 0 };
+        self.regs.modify(VBUS_IPSOUT, DRIVE_VBUS, drive)
+    }
+}
+
+impl<I2C: I2c> VbusSense for Axp228<I2C> {
+    type Error = Error<I2C::Error>;
+    #[inline]
+    fn is_vbus_present(&mut self) -> Result<bool, Self::Error> {
+        Ok(self.power_status()?.vbus_present)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Axp228, Rail};
+    use crate::{
+        pmic::{ADDRESS, ChargerConfig, Error},
+        usb::{VbusDrive, VbusSense},
+    };
+    use embedded_hal::i2c::{ErrorType, I2c, Operation};
+
+    struct MockBus([u8; 256]);
+
+    impl ErrorType for MockBus {
+        type Error = core::convert::Infallible;
+    }
+
+    impl I2c for MockBus {
+        fn transaction(
+            &mut self,
+            address: u8,
+            operations: &mut [Operation<'_>],
+        ) -> Result<(), Self::Error> {
+            assert_eq!(address, ADDRESS);
+            let mut register = 0;
+            for operation in operations {
+                match operation {
+                    Operation::Write([reg]) => register = *reg,
+                    Operation::Write([reg, value]) => self.0[*reg as usize] = *value,
+                    Operation::Read([value]) => *value = self.0[register as usize],
+                    _ => unreachable!(),
+                }
+            }
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn struct_axp228() {
+        let mut pmic = Axp228::new(MockBus([0; 256]));
+        pmic.set_voltage(Rail::Dcdc3, 1100).unwrap();
+        pmic.enable_rail(Rail::Dcdc3).unwrap();
+        pmic.enable_rail(Rail::Aldo3).unwrap();
+        assert_eq!(pmic.voltage(Rail::Dcdc3).unwrap(), Some(1100));
+        assert_eq!(pmic.voltage(Rail::Dc1sw).unwrap(), None);
+        assert_eq!(
+            pmic.set_voltage(Rail::Dcdc1, 3350),
+            Err(Error::InvalidVoltage)
+        );
+        pmic.configure_charger(ChargerConfig {
+            enable: true,
+            target_millivolts: 4200,
+            current_milliamps: 1200,
+        })
+        .unwrap();
+        pmic.set_vbus(true).unwrap();
+        assert!(!pmic.is_vbus_present().unwrap());
+        let bus = pmic.free();
+        assert_eq!(bus.0[0x23], 25);
+        assert_eq!(bus.0[0x10], 0x88);
+        assert_eq!(bus.0[0x33], 0xC6);
+        assert_eq!(bus.0[0x30], 0x04);
+        assert_eq!(bus.0[0x8F], 0x10);
+    }
+}
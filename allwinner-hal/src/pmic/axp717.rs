@@ -0,0 +1,270 @@
+//! AXP717 power management IC.
+
+use super::{
+    ADDRESS, ChargerConfig, Error, PowerKeyEvents, PowerStatus, RailDescriptor, Registers,
+    VoltageRange,
+};
+use crate::usb::{VbusDrive, VbusSense};
+use embedded_hal::i2c::I2c;
+
+const PMU_STATUS_1: u8 = 0x00;
+const PMU_STATUS_2: u8 = 0x01;
+const MODULE_ENABLE_1: u8 = 0x0B;
+const MODULE_ENABLE_2: u8 = 0x19;
+const BATTERY_VOLTAGE_HIGH: u8 = 0x34;
+const BATTERY_VOLTAGE_LOW: u8 = 0x35;
+const IRQ_ENABLE_1: u8 = 0x41;
+const IRQ_STATUS_1: u8 = 0x49;
+const CHARGE_CURRENT: u8 = 0x62;
+const CHARGE_VOLTAGE: u8 = 0x64;
+const DCDC_OUTPUT_CONTROL: u8 = 0x80;
+const LDO_OUTPUT_CONTROL_0: u8 = 0x90;
+const LDO_OUTPUT_CONTROL_1: u8 = 0x91;
+const BATTERY_PERCENT: u8 = 0xA4;
+const ADC_ENABLE: u8 = 0xC0;
+
+const VBUS_GOOD: u8 = 1 << 5;
+const BATTERY_PRESENT: u8 = 1 << 3;
+const CHARGE_STATUS: u8 = 0x7;
+const BOOST_ENABLE: u8 = 1 << 4;
+const CHARGER_ENABLE: u8 = 1 << 1;
+const CHARGE_CURRENT_MASK: u8 = 0x3F;
+const CHARGE_VOLTAGE_MASK: u8 = 0x7;
+const POWER_KEY_SHORT: u8 = 1 << 3;
+const POWER_KEY_LONG: u8 = 1 << 2;
+const BATTERY_VOLTAGE_ADC: u8 = 1 << 0;
+const BATTERY_PERCENT_MASK: u8 = 0x7F;
+
+const DCDC1_RANGES: [VoltageRange; 2] = [
+    VoltageRange::new(500, 10, 0, 70),
+    VoltageRange::new(1220, 20, 71, 87),
+];
+const DCDC2_RANGES: [VoltageRange; 3] = [
+    VoltageRange::new(500, 10, 0, 70),
+    VoltageRange::new(1220, 20, 71, 87),
+    VoltageRange::new(1600, 100, 88, 107),
+];
+const DCDC3_RANGES: [VoltageRange; 2] = [
+    VoltageRange::new(500, 10, 0, 70),
+    VoltageRange::new(1220, 20, 71, 102),
+];
+const DCDC4_RANGES: [VoltageRange; 1] = [VoltageRange::new(1000, 100, 0, 27)];
+const LDO_RANGES: [VoltageRange; 1] = [VoltageRange::new(500, 100, 0, 30)];
+const CPUSLDO_RANGES: [VoltageRange; 1] = [VoltageRange::new(500, 50, 0, 18)];
+
+/// AXP717 power rail.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Rail {
+    /// DC-DC converter 1, 0.5 to 1.2 V in 10-mV steps and up to 1.54 V in 20-mV steps.
+    Dcdc1,
+    /// DC-DC converter 2, as converter 1 and from 1.6 to 3.5 V in 100-mV steps.
+    Dcdc2,
+    /// DC-DC converter 3, 0.5 to 1.2 V in 10-mV steps and up to 1.84 V in 20-mV steps.
+    Dcdc3,
+    /// DC-DC converter 4, 1.0 to 3.7 V in 100-mV steps.
+    Dcdc4,
+    /// LDO A1, 0.5 to 3.5 V in 100-mV steps.
+    Aldo1,
+    /// LDO A2, 0.5 to 3.5 V in 100-mV steps.
+    Aldo2,
+    /// LDO A3, 0.5 to 3.5 V in 100-mV steps.
+    Aldo3,
+    /// LDO A4, 0.5 to 3.5 V in 100-mV steps.
+    Aldo4,
+    /// LDO B1, 0.5 to 3.5 V in 100-mV steps.
+    Bldo1,
+    /// LDO B2, 0.5 to 3.5 V in 100-mV steps.
+    Bldo2,
+    /// LDO B3, 0.5 to 3.5 V in 100-mV steps.
+    Bldo3,
+    /// LDO B4, 0.5 to 3.5 V in 100-mV steps.
+    Bldo4,
+    /// LDO C1, 0.5 to 3.5 V in 100-mV steps.
+    Cldo1,
+    /// LDO C2, 0.5 to 3.5 V in 100-mV steps.
+    Cldo2,
+    /// LDO C3, 0.5 to 3.5 V in 100-mV steps.
+    Cldo3,
+    /// LDO C4, 0.5 to 3.5 V in 100-mV steps.
+    Cldo4,
+    /// CPUS domain LDO, 0.5 to 1.4 V in 50-mV steps.
+    Cpusldo,
+}
+
+impl Rail {
+    #[inline]
+    const fn descriptor(self) -> RailDescriptor {
+        const fn rail(
+            voltage: u8,
+            mask: u8,
+            ranges: &'static [VoltageRange],
+            enable: (u8, u8),
+        ) -> RailDescriptor {
+            RailDescriptor {
+                voltage: Some((voltage, mask)),
+                ranges,
+                enable,
+            }
+        }
+        match self {
+            Rail::Dcdc1 => rail(0x83, 0x7F, &DCDC1_RANGES, (DCDC_OUTPUT_CONTROL, 1 << 0)),
+            Rail::Dcdc2 => rail(0x84, 0x7F, &DCDC2_RANGES, (DCDC_OUTPUT_CONTROL, 1 << 1)),
+            Rail::Dcdc3 => rail(0x85, 0x7F, &DCDC3_RANGES, (DCDC_OUTPUT_CONTROL, 1 << 2)),
+            Rail::Dcdc4 => rail(0x86, 0x7F, &DCDC4_RANGES, (DCDC_OUTPUT_CONTROL, 1 << 3)),
+            Rail::Aldo1 => rail(0x93, 0x1F, &LDO_RANGES, (LDO_OUTPUT_CONTROL_0, 1 << 0)),
+            Rail::Aldo2 => rail(0x94, 0x1F, &LDO_RANGES, (LDO_OUTPUT_CONTROL_0, 1 << 1)),
+            Rail::Aldo3 => rail(0x95, 0x1F, &LDO_RANGES, (LDO_OUTPUT_CONTROL_0, 1 << 2)),
+            Rail::Aldo4 => rail(0x96, 0x1F, &LDO_RANGES, (LDO_OUTPUT_CONTROL_0, 1 << 3)),
+            Rail::Bldo1 => rail(0x97, 0x1F, &LDO_RANGES, (LDO_OUTPUT_CONTROL_0, 1 << 4)),
+            Rail::Bldo2 => rail(0x98, 0x1F, &LDO_RANGES, (LDO_OUTPUT_CONTROL_0, 1 << 5)),
+            Rail::Bldo3 => rail(0x99, 0x1F, &LDO_RANGES, (LDO_OUTPUT_CONTROL_0, 1 << 6)),
+            Rail::Bldo4 => rail(0x9A, 0x1F, &LDO_RANGES, (LDO_OUTPUT_CONTROL_0, 1 << 7)),
+            Rail::Cldo1 => rail(0x9B, 0x1F, &LDO_RANGES, (LDO_OUTPUT_CONTROL_1, 1 << 0)),
+            Rail::Cldo2 => rail(0x9C, 0x1F, &LDO_RANGES, (LDO_OUTPUT_CONTROL_1, 1 << 1)),
+            Rail::Cldo3 => rail(0x9D, 0x1F, &LDO_RANGES, (LDO_OUTPUT_CONTROL_1, 1 << 2)),
+            Rail::Cldo4 => rail(0x9E, 0x1F, &LDO_RANGES, (LDO_OUTPUT_CONTROL_1, 1 << 3)),
+            Rail::Cpusldo => rail(0x9F, 0x1F, &CPUSLDO_RANGES, (LDO_OUTPUT_CONTROL_1, 1 << 4)),
+        }
+    }
+}
+
+/// AXP717 driver over I2C bus.
+pub struct Axp717<I2C> {
+    regs: Registers<I2C>,
+}
+
+impl<I2C: I2c> Axp717<I2C> {
+    /// Create an AXP717 driver at default address.
+    #[inline]
+    pub fn new(i2c: I2C) -> Self {
+        Self {
+            regs: Registers {
+                i2c,
+                address: ADDRESS,
+            },
+        }
+    }
+    /// Enable output of `rail`.
+    #[inline]
+    pub fn enable_rail(&mut self, rail: Rail) -> Result<(), Error<I2C::Error>> {
+        self.regs.set_rail_enabled(&rail.descriptor(), true)
+    }
+    /// Disable output of `rail`.
+    #[inline]
+    pub fn disable_rail(&mut self, rail: Rail) -> Result<(), Error<I2C::Error>> {
+        self.regs.set_rail_enabled(&rail.descriptor(), false)
+    }
+    /// Check if output of `rail` is enabled.
+    #[inline]
+    pub fn is_rail_enabled(&mut self, rail: Rail) -> Result<bool, Error<I2C::Error>> {
+        self.regs.is_rail_enabled(&rail.descriptor())
+    }
+    /// Set output voltage of `rail` in millivolts, which should be exactly on a step.
+    #[inline]
+    pub fn set_voltage(&mut self, rail: Rail, millivolts: u16) -> Result<(), Error<I2C::Error>> {
+        self.regs.set_rail_voltage(&rail.descriptor(), millivolts)
+    }
+    /// Get output voltage of `rail` in millivolts.
+    #[inline]
+    pub fn voltage(&mut self, rail: Rail) -> Result<Option<u16>, Error<I2C::Error>> {
+        self.regs.rail_voltage(&rail.descriptor())
+    }
+    /// Configure battery charger.
+    ///
+    /// Target voltage is one of 4000, 4100, 4200, 4350 or 4400 millivolts; charge current
+    /// is up to 3008 milliamps in 64-mA steps.
+    #[inline]
+    pub fn configure_charger(&mut self, config: ChargerConfig) -> Result<(), Error<I2C::Error>> {
+        let voltage = match config.target_millivolts {
+            4000 => 0,
+            4100 => 1,
+            4200 => 2,
+            4350 => 3,
+            4400 => 4,
+            _ => return Err(Error::InvalidChargerConfig),
+        };
+        if config.current_milliamps > 3008 {
+            return Err(Error::InvalidChargerConfig);
+        }
+        let current = (config.current_milliamps / 64) as u8;
+        self.regs
+            .modify(CHARGE_VOLTAGE, CHARGE_VOLTAGE_MASK, voltage)?;
+        self.regs
+            .modify(CHARGE_CURRENT, CHARGE_CURRENT_MASK, current)?;
+        let enable = if config.enable { CHARGER_ENABLE } else { 0 };
+        self.regs.modify(MODULE_ENABLE_2, CHARGER_ENABLE, enable)
+    }
+    /// Get power supply status.
+    #[inline]
+    pub fn power_status(&mut self) -> Result<PowerStatus, Error<I2C::Error>> {
+        let status_1 = self.regs.read(PMU_STATUS_1)?;
+        let status_2 = self.regs.read(PMU_STATUS_2)?;
+        let battery_present = status_1 & BATTERY_PRESENT != 0;
+        Ok(PowerStatus {
+            vbus_present: status_1 & VBUS_GOOD != 0,
+            battery_present,
+            // trickle, pre-charge, constant current and constant voltage stages
+            charging: battery_present && status_2 & CHARGE_STATUS <= 3,
+        })
+    }
+    /// Get battery capacity in percent from fuel gauge, or `None` if no battery.
+    #[inline]
+    pub fn battery_percent(&mut self) -> Result<Option<u8>, Error<I2C::Error>> {
+        if self.regs.read(PMU_STATUS_1)? & BATTERY_PRESENT == 0 {
+            return Ok(None);
+        }
+        Ok(Some(
+            self.regs.read(BATTERY_PERCENT)? & BATTERY_PERCENT_MASK,
+        ))
+    }
+    /// Get battery voltage in millivolts, enabling its ADC if needed.
+    #[inline]
+    pub fn battery_millivolts(&mut self) -> Result<u16, Error<I2C::Error>> {
+        self.regs
+            .modify(ADC_ENABLE, BATTERY_VOLTAGE_ADC, BATTERY_VOLTAGE_ADC)?;
+        let high = self.regs.read(BATTERY_VOLTAGE_HIGH)? as u16 & 0x3F;
+        let low = self.regs.read(BATTERY_VOLTAGE_LOW)? as u16;
+        Ok((high << 8) | low)
+    }
+    /// Enable power key press events.
+    #[inline]
+    pub fn enable_power_key_events(&mut self) -> Result<(), Error<I2C::Error>> {
+        let mask = POWER_KEY_SHORT | POWER_KEY_LONG;
+        self.regs.modify(IRQ_ENABLE_1, mask, mask)
+    }
+    /// Read and clear power key events since last call.
+    #[inline]
+    pub fn power_key_events(&mut self) -> Result<PowerKeyEvents, Error<I2C::Error>> {
+        let status = self.regs.read(IRQ_STATUS_1)? & (POWER_KEY_SHORT | POWER_KEY_LONG);
+        if status != 0 {
+            self.regs.write(IRQ_STATUS_1, status)?;
+        }
+        Ok(PowerKeyEvents {
+            short_press: status & POWER_KEY_SHORT != 0,
+            long_press: status & POWER_KEY_LONG != 0,
+        })
+    }
+    /// Release I2C bus.
+    #[inline]
+    pub fn free(self) -> I2C {
+        self.regs.i2c
+    }
+}
+
+impl<I2C: I2c> VbusDrive for Axp717<I2C> {
+    type Error = Error<I2C::Error>;
+    /// Switch VBUS supply from battery by the boost converter.
+    #[inline]
+    fn set_vbus(&mut self, enabled: bool) -> Result<(), Self::Error> {
+        let boost = if enabled { BOOST_ENABLE } else { 0 };
+        self.regs.modify(MODULE_ENABLE_1, BOOST_ENABLE, boost)
+    }
+}
+
+impl<I2C: I2c> VbusSense for Axp717<I2C> {
+    type Error = Error<I2C::Error>;
+    #[inline]
+    fn is_vbus_present(&mut self) -> Result<bool, Self::Error> {
+        Ok(self.power_status()?.vbus_present)
+    }
+}
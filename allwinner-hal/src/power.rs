@@ -0,0 +1,643 @@
+//! System reset, reset cause and low-power sleep.
+//!
+//! D1-like chips have no reset source register, thus reset cause is tracked in the RTC
+//! general purpose data register of [`RebootReason`], which survives warm resets.
+//!
+//! [`idle`] suspends the CPU until an interrupt arrives, with clocks and DRAM running. For
+//! lower power, [`standby`] puts DRAM into self-refresh and runs the CPU from the 24-MHz
+//! oscillator until one of [`WakeupSources`], such as a GPIO external interrupt, RTC alarm,
+//! IR receiver, USB resume or LRADC key, requests wakeup:
+//!
+//! ```ignore
+//! let mut sources = WakeupSources::<2>::new()
+//!     .eint(&mut power_key, Event::NegativeEdge)?
+//!     .rtc_alarm(&mut rtc)?;
+//! let woken_by = unsafe { power::standby(&ccu, &com, &phy, &mut sources)? };
+//! ```
+//!
+//! D1 has no CPUS management core to sequence standby, so the CPU drives it itself and must
+//! execute from SRAM while DRAM is in self-refresh.
+//!
+//! [`suspend`] wraps standby with bus clocks and GPIO pads saved and restored, and calls
+//! [`Suspend`] and [`Resume`] callbacks of drivers registered for the cycle:
+//!
+//! ```ignore
+//! let woken_by = unsafe {
+//!     power::suspend(&ccu, &com, &phy, &gpio, &mut sources, |ctx: &mut SuspendContext<2>| {
+//!         ctx.register(&mut serial)?;
+//!         ctx.register(&mut spi)
+//!     })?
+//! };
+//! ```
+//!
+//! [`PowerDomain`] counts drivers using each clock gate and PLL, so that [`minimize`] turns
+//! off those left unused after drivers are dropped.
+//!
+//! [`set_cpu_opp`] changes CPU frequency together with its supply voltage from a board
+//! operating point table, through a PMIC rail or other [`CpuSupply`]:
+//!
+//! ```ignore
+//! let mut supply = PmicRail::new(&mut pmic, axp228::Rail::Dcdc2);
+//! unsafe { power::set_cpu_opp(&ccu, &mut supply, &BOARD_OPPS, Hertz(1_008_000_000))? };
+//! ```
+
+mod domain;
+mod dvfs;
+mod suspend;
+pub use domain::*;
+pub use dvfs::*;
+pub use suspend::*;
+
+use crate::{
+    ccu,
+    cir::{self, Cir},
+    com,
+    gpio::{EintPad, Event},
+    lradc::{self, Interrupt, Lradc},
+    phy,
+    rtc::{self, DateTime, REBOOT_REASON_INDEX, RebootReason, Rtc},
+    timer,
+};
+
+/// Self-refresh request bit of DRAM power control register.
+const SELF_REFRESH_REQUEST: u32 = 1 << 0;
+
+/// Operating mode field of DRAM status register.
+const OPERATING_MODE: u32 = 0x7;
+
+/// Normal operating mode of DRAM.
+const MODE_NORMAL: u32 = 0x1;
+
+/// Self-refresh operating mode of DRAM.
+const MODE_SELF_REFRESH: u32 = 0x3;
+
+/// Status polls before DRAM mode change times out.
+///
+/// Crate-wide blocking timeout lives in DRAM, so it can't be read during self-refresh.
+const MODE_CHANGE_SPINS: u32 = 1_000_000;
+
+/// Low-power state error.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Error {
+    /// DRAM didn't change operating mode in time.
+    Timeout,
+    /// Power domain has no room for another clock gate.
+    DomainFull,
+    /// Clock gate or PLL already has `u16::MAX` users.
+    TooManyUsers,
+    /// Wakeup source list has no room for another source.
+    SourcesFull,
+    /// Suspend context has no room for another driver.
+    DriversFull,
+}
+
+/// Cause of last system reset.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ResetCause {
+    /// Power on, or RTC domain lost power.
+    PowerOn,
+    /// Software reset with recorded reason.
+    Software(RebootReason),
+    /// Watchdog timeout, or another reset without recorded reason while firmware was running.
+    ///
+    /// On boards with RTC backup battery, a power loss while running is also reported here.
+    Watchdog,
+}
+
+impl ResetCause {
+    /// Decode reset cause from reboot reason register value.
+    #[inline]
+    pub const fn from_bits(bits: u32) -> Self {
+        if bits == RebootReason::RUNNING {
+            return ResetCause::Watchdog;
+        }
+        match RebootReason::from_bits(bits) {
+            Some(RebootReason::Watchdog) => ResetCause::Watchdog,
+            Some(reason) => ResetCause::Software(reason),
+            None => ResetCause::PowerOn,
+        }
+    }
+}
+
+/// Get cause of last reset, and mark firmware as running for next boot.
+///
+/// Call this function once on boot, before any reboot reason is recorded.
+#[inline]
+pub fn reset_cause(rtc: &rtc::RegisterBlock) -> ResetCause {
+    let register = &rtc.gp_data[REBOOT_REASON_INDEX];
+    let cause = ResetCause::from_bits(register.read());
+    unsafe { register.write(RebootReason::RUNNING) };
+    cause
+}
+
+/// Reset the system using watchdog software reset.
+///
+/// Records [`RebootReason::Normal`] if no other reason is recorded.
+#[inline]
+pub fn system_reset(timer: &timer::RegisterBlock, rtc: &rtc::RegisterBlock) -> ! {
+    /// Software reset enable with key field.
+    const SOFT_RESET: u32 = 0x16AA_0001;
+
+    let register = &rtc.gp_data[REBOOT_REASON_INDEX];
+    if RebootReason::from_bits(register.read()).is_none() {
+        unsafe { register.write(RebootReason::Normal.to_bits()) };
+    }
+    unsafe { timer.wdog_soft_reset.write(SOFT_RESET) };
+    loop {
+        core::hint::spin_loop();
+    }
+}
+
+/// Suspend the CPU until RTC alarm fires at `wakeup`.
+///
+/// Alarm 0 is used with its interrupt and system wakeup enabled; RTC interrupt should be
+/// enabled in PLIC and external interrupts enabled in `mie` for the CPU to resume. Returns
+/// immediately if `wakeup` is not later than current time, including when it passes while
+/// the alarm is being armed. Alarm 0 is disarmed and cleared before returning.
+#[inline]
+pub fn sleep_until<RTC: AsRef<rtc::RegisterBlock>>(
+    rtc: &mut Rtc<RTC>,
+    wakeup: DateTime,
+) -> Result<(), rtc::Error> {
+    if wakeup <= rtc.date_time() {
+        return Ok(());
+    }
+    rtc.set_alarm(wakeup)?;
+    rtc.enable_alarm_interrupt();
+    rtc.enable_alarm_wakeup();
+    // an alarm armed after `wakeup` has passed never fires
+    if wakeup > rtc.date_time() {
+        while !rtc.is_alarm_pending() {
+            wait_for_interrupt();
+        }
+    }
+    rtc.disable_alarm_wakeup();
+    rtc.disable_alarm_interrupt();
+    rtc.disable_alarm();
+    rtc.clear_alarm();
+    Ok(())
+}
+
+/// Source that wakes the CPU from [`standby`].
+pub trait WakeupSource {
+    /// Enable the wakeup interrupt.
+    fn arm(&mut self);
+    /// Check if wakeup is requested.
+    fn is_pending(&mut self) -> bool;
+    /// Disable the wakeup interrupt, leaving pending state to its driver.
+    fn disarm(&mut self);
+}
+
+impl<const P: char, const N: u8> WakeupSource for EintPad<'_, P, N> {
+    #[inline]
+    fn arm(&mut self) {
+        self.enable_interrupt();
+    }
+    #[inline]
+    fn is_pending(&mut self) -> bool {
+        self.check_interrupt()
+    }
+    #[inline]
+    fn disarm(&mut self) {
+        self.disable_interrupt();
+    }
+}
+
+impl<RTC: AsRef<rtc::RegisterBlock>> WakeupSource for Rtc<RTC> {
+    #[inline]
+    fn arm(&mut self) {
+        self.enable_alarm_interrupt();
+        self.enable_alarm_wakeup();
+    }
+    #[inline]
+    fn is_pending(&mut self) -> bool {
+        self.is_alarm_pending()
+    }
+    #[inline]
+    fn disarm(&mut self) {
+        self.disable_alarm_wakeup();
+        self.disable_alarm_interrupt();
+    }
+}
+
+/// Wakes on key down.
+impl<LRADC: AsRef<lradc::RegisterBlock>> WakeupSource for Lradc<LRADC> {
+    #[inline]
+    fn arm(&mut self) {
+        self.enable_interrupt(Interrupt::KeyDown);
+    }
+    #[inline]
+    fn is_pending(&mut self) -> bool {
+        self.has_interrupt(Interrupt::KeyDown)
+    }
+    #[inline]
+    fn disarm(&mut self) {
+        self.disable_interrupt(Interrupt::KeyDown);
+    }
+}
+
+/// Wakes on any received IR sample.
+impl<CIR: AsRef<cir::RegisterBlock>, PAD: cir::Receive> WakeupSource for Cir<CIR, PAD> {
+    #[inline]
+    fn arm(&mut self) {
+        self.enable_interrupts();
+    }
+    #[inline]
+    fn is_pending(&mut self) -> bool {
+        Cir::is_pending(self)
+    }
+    #[inline]
+    fn disarm(&mut self) {
+        self.disable_interrupts();
+    }
+}
+
+/// Wakes on resume or reset signaling from host.
+///
+/// Resume and reset interrupts stay enabled while `usb-device` runs the bus, so arming and
+/// disarming leave them unchanged; interrupt status is left for `usb-device` to read.
+#[cfg(feature = "usb-device")]
+impl<USB: AsRef<crate::usb::RegisterBlock>, DMA: crate::usb::EndpointDma> WakeupSource
+    for crate::usb::UsbBus<USB, DMA>
+{
+    #[inline]
+    fn arm(&mut self) {}
+    #[inline]
+    fn is_pending(&mut self) -> bool {
+        !self.is_suspended()
+    }
+    #[inline]
+    fn disarm(&mut self) {}
+}
+
+/// Wakeup sources of [`standby`], with room for `N` sources.
+///
+/// Sources are configured when added, and armed or disarmed together so that no wakeup
+/// enable is left behind after sleep.
+pub struct WakeupSources<'a, const N: usize> {
+    sources: [Option<&'a mut dyn WakeupSource>; N],
+    len: usize,
+}
+
+impl<'a, const N: usize> WakeupSources<'a, N> {
+    /// Create an empty wakeup source list.
+    #[inline]
+    pub const fn new() -> Self {
+        Self {
+            sources: [const { None }; N],
+            len: 0,
+        }
+    }
+    /// Wake on `event` of GPIO external interrupt `pad`, clearing its stale pending state.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::SourcesFull`] if `N` sources are added.
+    #[inline]
+    pub fn eint<const P: char, const M: u8>(
+        self,
+        pad: &'a mut EintPad<'_, P, M>,
+        event: Event,
+    ) -> Result<Self, Error> {
+        pad.listen(event);
+        pad.clear_interrupt_pending_bit();
+        self.source(pad)
+    }
+    /// Wake when RTC alarm 0 fires.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::SourcesFull`] if `N` sources are added.
+    #[inline]
+    pub fn rtc_alarm<RTC: AsRef<rtc::RegisterBlock>>(
+        self,
+        rtc: &'a mut Rtc<RTC>,
+    ) -> Result<Self, Error> {
+        self.source(rtc)
+    }
+    /// Wake when IR receiver samples a signal.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::SourcesFull`] if `N` sources are added.
+    #[inline]
+    pub fn ir<CIR: AsRef<cir::RegisterBlock>, PAD: cir::Receive>(
+        self,
+        cir: &'a mut Cir<CIR, PAD>,
+    ) -> Result<Self, Error> {
+        self.source(cir)
+    }
+    /// Wake when USB host resumes or resets the suspended bus.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::SourcesFull`] if `N` sources are added.
+    #[cfg(feature = "usb-device")]
+    #[inline]
+    pub fn usb_resume<USB: AsRef<crate::usb::RegisterBlock>, DMA: crate::usb::EndpointDma>(
+        self,
+        usb: &'a mut crate::usb::UsbBus<USB, DMA>,
+    ) -> Result<Self, Error> {
+        self.source(usb)
+    }
+    /// Wake when an LRADC key is pressed.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::SourcesFull`] if `N` sources are added.
+    #[inline]
+    pub fn lradc<LRADC: AsRef<lradc::RegisterBlock>>(
+        self,
+        lradc: &'a mut Lradc<LRADC>,
+    ) -> Result<Self, Error> {
+        self.source(lradc)
+    }
+    /// Wake on any other `source`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::SourcesFull`] if `N` sources are added.
+    #[inline]
+    pub fn source(mut self, source: &'a mut dyn WakeupSource) -> Result<Self, Error> {
+        let slot = self.sources.get_mut(self.len).ok_or(Error::SourcesFull)?;
+        *slot = Some(source);
+        self.len += 1;
+        Ok(self)
+    }
+    /// Get number of added sources.
+    #[inline]
+    pub const fn len(&self) -> usize {
+        self.len
+    }
+    /// Check if no source is added.
+    #[inline]
+    pub const fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+    /// Enable wakeup interrupts of all sources.
+    #[inline]
+    pub fn arm(&mut self) {
+        for source in self.sources.iter_mut().flatten() {
+            source.arm();
+        }
+    }
+    /// Get index of first source requesting wakeup, in order of addition.
+    #[inline]
+    pub fn pending(&mut self) -> Option<usize> {
+        self.sources
+            .iter_mut()
+            .flatten()
+            .position(|source| source.is_pending())
+    }
+    /// Disable wakeup interrupts of all sources, leaving pending states to their drivers.
+    #[inline]
+    pub fn disarm(&mut self) {
+        for source in self.sources.iter_mut().flatten() {
+            source.disarm();
+        }
+    }
+}
+
+impl<const N: usize> Default for WakeupSources<'_, N> {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Suspend the CPU until an interrupt is pending.
+///
+/// Interrupt should be enabled in PLIC and `mie` for the CPU to resume; clocks and DRAM keep
+/// running.
+#[inline]
+pub fn idle() {
+    wait_for_interrupt();
+}
+
+/// Put DRAM into self-refresh, keeping its contents at lowest power.
+///
+/// # Errors
+///
+/// Returns [`Error::Timeout`] if DRAM doesn't enter self-refresh.
+///
+/// # Safety
+///
+/// DRAM must not be accessed until [`exit_self_refresh`]; caller code, stack and data must
+/// reside in SRAM, and DRAM masters such as DMA must be idle.
+#[inline]
+pub unsafe fn enter_self_refresh(phy: &phy::RegisterBlock) -> Result<(), Error> {
+    unsafe { phy.pwrctl.modify(|val| val | SELF_REFRESH_REQUEST) };
+    wait_dram_mode(phy, MODE_SELF_REFRESH)
+}
+
+/// Bring DRAM out of self-refresh into normal operation.
+///
+/// # Errors
+///
+/// Returns [`Error::Timeout`] if DRAM doesn't return to normal operation.
+///
+/// # Safety
+///
+/// DRAM must have been put into self-refresh by [`enter_self_refresh`].
+#[inline]
+pub unsafe fn exit_self_refresh(phy: &phy::RegisterBlock) -> Result<(), Error> {
+    unsafe { phy.pwrctl.modify(|val| val & !SELF_REFRESH_REQUEST) };
+    wait_dram_mode(phy, MODE_NORMAL)
+}
+
+/// Enter standby until one of `sources` requests wakeup, and return its index in `sources`.
+///
+/// DRAM masters are disabled and DRAM is put into self-refresh, then the CPU runs from the
+/// 24-MHz oscillator and waits for interrupts. Wakeup interrupts should be enabled in PLIC and
+/// `mie`. Previous CPU clock, DRAM and masters are restored before returning, and sources are
+/// disarmed with their pending state kept.
+///
+/// # Errors
+///
+/// Returns [`Error::Timeout`] if DRAM fails to enter self-refresh, in which case standby is
+/// abandoned, or if DRAM is late to report normal operation after wakeup.
+///
+/// # Safety
+///
+/// This function, wakeup source functions, stack and `sources` must reside in SRAM, e.g.
+/// when called from a boot runtime running in SRAM. No DMA transfer may be in progress.
+#[inline]
+pub unsafe fn standby<const N: usize>(
+    ccu: &ccu::RegisterBlock,
+    com: &com::RegisterBlock,
+    phy: &phy::RegisterBlock,
+    sources: &mut WakeupSources<'_, N>,
+) -> Result<usize, Error> {
+    sources.arm();
+    let masters = [com.maer0.read(), com.maer1.read(), com.maer2.read()];
+    unsafe {
+        com.maer0.write(0);
+        com.maer1.write(0);
+        com.maer2.write(0);
+    }
+    if let Err(e) = unsafe { enter_self_refresh(phy) } {
+        unsafe {
+            let _ = exit_self_refresh(phy);
+            com.maer0.write(masters[0]);
+            com.maer1.write(masters[1]);
+            com.maer2.write(masters[2]);
+        }
+        sources.disarm();
+        return Err(e);
+    }
+    let cpu_clock = unsafe { run_cpu_from_hosc(ccu) };
+    let woken_by = loop {
+        wait_for_interrupt();
+        if let Some(index) = sources.pending() {
+            break index;
+        }
+    };
+    unsafe { restore_cpu_clock(ccu, cpu_clock) };
+    let exited = unsafe { exit_self_refresh(phy) };
+    unsafe {
+        com.maer0.write(masters[0]);
+        com.maer1.write(masters[1]);
+        com.maer2.write(masters[2]);
+    }
+    sources.disarm();
+    exited.map(|()| woken_by)
+}
+
+/// Run RISC-V core from the undivided 24-MHz oscillator, returning previous clock
+/// configuration for [`restore_cpu_clock`].
+#[cfg(feature = "d1")]
+#[inline]
+unsafe fn run_cpu_from_hosc(ccu: &ccu::RegisterBlock) -> ccu::RiscvClock {
+    let cpu_clock = ccu.riscv_clock.read();
+    unsafe {
+        ccu.riscv_clock.write(
+            cpu_clock
+                .set_clock_source(ccu::RiscvClockSource::Hosc)
+                .set_factor_m(0),
+        )
+    };
+    cpu_clock
+}
+
+/// Run Cortex-A7 cores from the undivided 24-MHz oscillator, returning previous clock
+/// configuration for [`restore_cpu_clock`].
+#[cfg(not(feature = "d1"))]
+#[inline]
+unsafe fn run_cpu_from_hosc(ccu: &ccu::RegisterBlock) -> ccu::CpuAxiConfig {
+    let cpu_clock = ccu.cpu_axi_config.read();
+    unsafe {
+        ccu.cpu_axi_config.write(
+            cpu_clock
+                .set_clock_source(ccu::CpuClockSource::Hosc)
+                .set_factor_m(0)
+                .set_factor_p(ccu::FactorP::P1),
+        )
+    };
+    cpu_clock
+}
+
+/// Restore CPU clock configuration saved by [`run_cpu_from_hosc`].
+#[cfg(feature = "d1")]
+#[inline]
+unsafe fn restore_cpu_clock(ccu: &ccu::RegisterBlock, cpu_clock: ccu::RiscvClock) {
+    unsafe { ccu.riscv_clock.write(cpu_clock) };
+}
+
+/// Restore CPU clock configuration saved by [`run_cpu_from_hosc`].
+#[cfg(not(feature = "d1"))]
+#[inline]
+unsafe fn restore_cpu_clock(ccu: &ccu::RegisterBlock, cpu_clock: ccu::CpuAxiConfig) {
+    unsafe { ccu.cpu_axi_config.write(cpu_clock) };
+}
+
+/// Wait until DRAM operating mode becomes `mode`.
+#[inline]
+fn wait_dram_mode(phy: &phy::RegisterBlock, mode: u32) -> Result<(), Error> {
+    for _ in 0..MODE_CHANGE_SPINS {
+        if phy.statr.read() & OPERATING_MODE == mode {
+            return Ok(());
+        }
+        core::hint::spin_loop();
+    }
+    Err(Error::Timeout)
+}
+
+#[cfg(target_arch = "riscv64")]
+#[inline]
+fn wait_for_interrupt() {
+    unsafe { core::arch::asm!("wfi") };
+}
+
+#[cfg(not(target_arch = "riscv64"))]
+#[inline]
+fn wait_for_interrupt() {
+    core::hint::spin_loop();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Error, RebootReason, ResetCause, WakeupSource, WakeupSources};
+
+    #[test]
+    fn struct_reset_cause_functions() {
+        assert_eq!(ResetCause::from_bits(0), ResetCause::PowerOn);
+        assert_eq!(ResetCause::from_bits(0xDEAD_BEEF), ResetCause::PowerOn);
+        assert_eq!(
+            ResetCause::from_bits(RebootReason::RUNNING),
+            ResetCause::Watchdog
+        );
+        assert_eq!(
+            ResetCause::from_bits(RebootReason::Watchdog.to_bits()),
+            ResetCause::Watchdog
+        );
+        assert_eq!(
+            ResetCause::from_bits(RebootReason::Bootloader.to_bits()),
+            ResetCause::Software(RebootReason::Bootloader)
+        );
+        assert_eq!(RebootReason::from_bits(RebootReason::RUNNING), None);
+    }
+
+    struct MockSource {
+        armed: bool,
+        pending: bool,
+    }
+
+    impl WakeupSource for MockSource {
+        fn arm(&mut self) {
+            self.armed = true;
+        }
+        fn is_pending(&mut self) -> bool {
+            self.pending
+        }
+        fn disarm(&mut self) {
+            self.armed = false;
+        }
+    }
+
+    #[test]
+    fn struct_wakeup_sources_functions() {
+        let mut a = MockSource {
+            armed: false,
+            pending: false,
+        };
+        let mut b = MockSource {
+            armed: false,
+            pending: true,
+        };
+        let sources = WakeupSources::<2>::new();
+        assert!(sources.is_empty());
+        let mut sources = sources.source(&mut a).unwrap().source(&mut b).unwrap();
+        assert_eq!(sources.len(), 2);
+        sources.arm();
+        assert_eq!(sources.pending(), Some(1));
+        sources.disarm();
+        assert!(!a.armed && !b.armed);
+
+        let mut c = MockSource {
+            armed: false,
+            pending: false,
+        };
+        let sources = WakeupSources::<0>::new();
+        assert!(matches!(sources.source(&mut c), Err(Error::SourcesFull)));
+    }
+}
@@ -0,0 +1,214 @@
+use super::Error;
+use crate::ccu::{self, ClockGate};
+use core::any::TypeId;
+
+/// PLL whose users are tracked by [`PowerDomain`].
+///
+/// Peripheral PLL 0, CPU and DDR PLLs clock buses, CPU and memory, and are never turned off.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Pll {
+    /// Audio PLL 0.
+    Audio0,
+    /// Audio PLL 1.
+    Audio1,
+    /// Video PLL 0.
+    Video0,
+}
+
+impl Pll {
+    /// Index of user count.
+    #[inline]
+    const fn index(self) -> usize {
+        match self {
+            Pll::Audio0 => 0,
+            Pll::Audio1 => 1,
+            Pll::Video0 => 2,
+        }
+    }
+}
+
+/// Users of a peripheral clock gate.
+#[derive(Clone, Copy, Debug)]
+struct Gate {
+    id: TypeId,
+    free: unsafe fn(&ccu::RegisterBlock),
+    users: u16,
+}
+
+/// Users of clock gates and PLLs, with room for `N` clock gates.
+///
+/// Drivers acquire what they need when created and release it when dropped;
+/// [`minimize`] then turns off what is no longer used:
+///
+/// ```ignore
+/// let mut domain = PowerDomain::<8>::new();
+/// domain.acquire_gate::<ccu::CODEC>()?;
+/// domain.acquire_pll(Pll::Audio1)?;
+/// // ... audio playback finishes
+/// domain.release_gate::<ccu::CODEC>();
+/// domain.release_pll(Pll::Audio1);
+/// unsafe { power::minimize(&ccu, &mut domain) };
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct PowerDomain<const N: usize> {
+    plls: [u16; 3],
+    gates: [Option<Gate>; N],
+}
+
+impl<const N: usize> PowerDomain<N> {
+    /// Create a power domain with no users.
+    #[inline]
+    pub const fn new() -> Self {
+        Self {
+            plls: [0; 3],
+            gates: [None; N],
+        }
+    }
+    /// Add a user of clock gate `T`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::DomainFull`] if `N` other clock gates are tracked, or
+    /// [`Error::TooManyUsers`] if `T` already has `u16::MAX` users.
+    #[inline]
+    pub fn acquire_gate<T: ClockGate + 'static>(&mut self) -> Result<(), Error> {
+        let id = TypeId::of::<T>();
+        if let Some(gate) = self.gates.iter_mut().flatten().find(|g| g.id == id) {
+            gate.users = gate.users.checked_add(1).ok_or(Error::TooManyUsers)?;
+            return Ok(());
+        }
+        let slot = self
+            .gates
+            .iter_mut()
+            .find(|g| g.is_none())
+            .ok_or(Error::DomainFull)?;
+        *slot = Some(Gate {
+            id,
+            free: T::free,
+            users: 1,
+        });
+        Ok(())
+    }
+    /// Remove a user of clock gate `T`.
+    #[inline]
+    pub fn release_gate<T: ClockGate + 'static>(&mut self) {
+        let id = TypeId::of::<T>();
+        if let Some(gate) = self.gates.iter_mut().flatten().find(|g| g.id == id) {
+            gate.users = gate.users.saturating_sub(1);
+        }
+    }
+    /// Get number of users of clock gate `T`.
+    #[inline]
+    pub fn gate_users<T: ClockGate + 'static>(&self) -> u16 {
+        let id = TypeId::of::<T>();
+        self.gates
+            .iter()
+            .flatten()
+            .find(|g| g.id == id)
+            .map_or(0, |g| g.users)
+    }
+    /// Add a user of `pll`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::TooManyUsers`] if `pll` already has `u16::MAX` users.
+    #[inline]
+    pub fn acquire_pll(&mut self, pll: Pll) -> Result<(), Error> {
+        let users = &mut self.plls[pll.index()];
+        *users = users.checked_add(1).ok_or(Error::TooManyUsers)?;
+        Ok(())
+    }
+    /// Remove a user of `pll`.
+    #[inline]
+    pub fn release_pll(&mut self, pll: Pll) {
+        let users = &mut self.plls[pll.index()];
+        *users = users.saturating_sub(1);
+    }
+    /// Get number of users of `pll`.
+    #[inline]
+    pub const fn pll_users(&self, pll: Pll) -> u16 {
+        self.plls[pll.index()]
+    }
+}
+
+impl<const N: usize> Default for PowerDomain<N> {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Turn off clock gates and PLLs of `domain` that have no users.
+///
+/// Unused clock gates are masked with their peripherals held in reset, and stop being
+/// tracked; unused PLLs are disabled with their LDOs. Clock gates never acquired in `domain`
+/// are left unchanged.
+///
+/// # Safety
+///
+/// Every driver using a clock gate or PLL of `domain` must have acquired it.
+#[inline]
+pub unsafe fn minimize<const N: usize>(ccu: &ccu::RegisterBlock, domain: &mut PowerDomain<N>) {
+    for slot in domain.gates.iter_mut() {
+        if let Some(gate) = slot
+            && gate.users == 0
+        {
+            unsafe { (gate.free)(ccu) };
+            *slot = None;
+        }
+    }
+    unsafe {
+        if domain.pll_users(Pll::Audio0) == 0 {
+            ccu.pll_audio0_control
+                .modify(|val| val.mask_pll_output().disable_pll().disable_pll_ldo());
+        }
+        if domain.pll_users(Pll::Audio1) == 0 {
+            ccu.pll_audio1_control
+                .modify(|val| val.mask_pll_output().disable_pll().disable_pll_ldo());
+        }
+        if domain.pll_users(Pll::Video0) == 0 {
+            ccu.pll_video0_control
+                .modify(|val| val.mask_pll_output().disable_pll().disable_pll_ldo());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Error, Pll, PowerDomain};
+    use crate::ccu;
+
+    #[test]
+    fn struct_power_domain_functions() {
+        let mut domain = PowerDomain::<2>::new();
+        assert_eq!(domain.acquire_gate::<ccu::CODEC>(), Ok(()));
+        assert_eq!(domain.acquire_gate::<ccu::CODEC>(), Ok(()));
+        assert_eq!(domain.acquire_gate::<ccu::DMA>(), Ok(()));
+        assert_eq!(domain.acquire_gate::<ccu::LEDC>(), Err(Error::DomainFull));
+        assert_eq!(domain.gate_users::<ccu::CODEC>(), 2);
+        assert_eq!(domain.gate_users::<ccu::DMA>(), 1);
+        assert_eq!(domain.gate_users::<ccu::LEDC>(), 0);
+        domain.release_gate::<ccu::CODEC>();
+        domain.release_gate::<ccu::LEDC>();
+        assert_eq!(domain.gate_users::<ccu::CODEC>(), 1);
+
+        assert_eq!(domain.acquire_pll(Pll::Audio1), Ok(()));
+        assert_eq!(domain.pll_users(Pll::Audio1), 1);
+        assert_eq!(domain.pll_users(Pll::Audio0), 0);
+        domain.release_pll(Pll::Audio1);
+        domain.release_pll(Pll::Audio1);
+        assert_eq!(domain.pll_users(Pll::Audio1), 0);
+
+        domain.plls[Pll::Video0.index()] = u16::MAX;
+        assert_eq!(domain.acquire_pll(Pll::Video0), Err(Error::TooManyUsers));
+        assert_eq!(domain.pll_users(Pll::Video0), u16::MAX);
+        for _ in 1..u16::MAX {
+            domain.acquire_gate::<ccu::CODEC>().unwrap();
+        }
+        assert_eq!(domain.gate_users::<ccu::CODEC>(), u16::MAX);
+        assert_eq!(
+            domain.acquire_gate::<ccu::CODEC>(),
+            Err(Error::TooManyUsers)
+        );
+    }
+}
@@ -0,0 +1,139 @@
+use crate::{
+    ccu,
+    pmic::{self, axp228::Axp228, axp717::Axp717},
+};
+use embedded_hal::i2c::I2c;
+use embedded_time::rate::Hertz;
+
+/// CPU operating performance point.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Opp {
+    /// CPU clock frequency.
+    pub frequency: Hertz,
+    /// Lowest CPU supply voltage at this frequency, in millivolts.
+    pub millivolts: u16,
+}
+
+/// Dynamic voltage and frequency scaling error.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum DvfsError<E> {
+    /// No operating point in table is at or below requested frequency.
+    InvalidFrequency,
+    /// CPU supply failed to change voltage.
+    Supply(E),
+}
+
+/// Adjustable voltage supply of CPU cores.
+pub trait CpuSupply {
+    /// Supply error type.
+    type Error;
+    /// Set supply voltage in millivolts, returning once output has settled.
+    fn set_millivolts(&mut self, millivolts: u16) -> Result<(), Self::Error>;
+}
+
+/// PMIC rail supplying CPU cores, as wired on the board.
+pub struct PmicRail<'a, PMIC, RAIL> {
+    pmic: &'a mut PMIC,
+    rail: RAIL,
+}
+
+impl<'a, PMIC, RAIL> PmicRail<'a, PMIC, RAIL> {
+    /// Use `rail` of `pmic` as CPU supply.
+    #[inline]
+    pub fn new(pmic: &'a mut PMIC, rail: RAIL) -> Self {
+        Self { pmic, rail }
+    }
+}
+
+impl<I2C: I2c> CpuSupply for PmicRail<'_, Axp228<I2C>, pmic::axp228::Rail> {
+    type Error = pmic::Error<I2C::Error>;
+    #[inline]
+    fn set_millivolts(&mut self, millivolts: u16) -> Result<(), Self::Error> {
+        self.pmic.set_voltage(self.rail, millivolts)
+    }
+}
+
+impl<I2C: I2c> CpuSupply for PmicRail<'_, Axp717<I2C>, pmic::axp717::Rail> {
+    type Error = pmic::Error<I2C::Error>;
+    #[inline]
+    fn set_millivolts(&mut self, millivolts: u16) -> Result<(), Self::Error> {
+        self.pmic.set_voltage(self.rail, millivolts)
+    }
+}
+
+/// Run CPU at the fastest operating point of `table` not above `frequency`, and return it.
+///
+/// Supply voltage is raised before CPU frequency increases, and lowered only after it
+/// decreases, so that CPU never runs faster than its voltage allows. Table entries may be
+/// in any order; CPU PLL rounds their frequencies down to multiples of 24 MHz.
+///
+/// # Errors
+///
+/// Returns [`DvfsError::InvalidFrequency`] if no entry is at or below `frequency`, or
+/// [`DvfsError::Supply`] if voltage can't be changed, in which case CPU keeps its previous
+/// frequency if it was going to increase.
+///
+/// # Safety
+///
+/// `table` voltages must be sufficient for their frequencies on this board. Clock
+/// frequencies previously read into [`ccu::Clocks`] become stale.
+#[inline]
+pub unsafe fn set_cpu_opp<S: CpuSupply>(
+    ccu: &ccu::RegisterBlock,
+    supply: &mut S,
+    table: &[Opp],
+    frequency: Hertz,
+) -> Result<Opp, DvfsError<S::Error>> {
+    let opp = select_opp(table, frequency).ok_or(DvfsError::InvalidFrequency)?;
+    if opp.frequency.0 > ccu::cpu_frequency(ccu).0 {
+        supply
+            .set_millivolts(opp.millivolts)
+            .map_err(DvfsError::Supply)?;
+        unsafe { ccu::set_cpu_pll_frequency(ccu, opp.frequency) };
+    } else {
+        unsafe { ccu::set_cpu_pll_frequency(ccu, opp.frequency) };
+        supply
+            .set_millivolts(opp.millivolts)
+            .map_err(DvfsError::Supply)?;
+    }
+    Ok(opp)
+}
+
+/// Get the fastest operating point of `table` not above `frequency`.
+#[inline]
+fn select_opp(table: &[Opp], frequency: Hertz) -> Option<Opp> {
+    table
+        .iter()
+        .filter(|opp| opp.frequency.0 <= frequency.0)
+        .max_by_key(|opp| opp.frequency.0)
+        .copied()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Opp, select_opp};
+    use embedded_time::rate::Hertz;
+
+    #[test]
+    fn function_select_opp() {
+        let table = [
+            Opp {
+                frequency: Hertz(1_008_000_000),
+                millivolts: 1100,
+            },
+            Opp {
+                frequency: Hertz(408_000_000),
+                millivolts: 900,
+            },
+            Opp {
+                frequency: Hertz(720_000_000),
+                millivolts: 960,
+            },
+        ];
+        assert_eq!(select_opp(&table, Hertz(1_008_000_000)), Some(table[0]));
+        assert_eq!(select_opp(&table, Hertz(800_000_000)), Some(table[2]));
+        assert_eq!(select_opp(&table, Hertz(408_000_000)), Some(table[1]));
+        assert_eq!(select_opp(&table, Hertz(300_000_000)), None);
+        assert_eq!(select_opp(&[], Hertz(1_008_000_000)), None);
+    }
+}
@@ -0,0 +1,287 @@
+use super::{Error, WakeupSources, standby};
+use crate::{ccu, com, gpio, phy};
+
+/// Driver restoring its state after standby.
+pub trait Resume {
+    /// Restore state saved by [`Suspend::suspend`], after clocks and pads are restored.
+    fn resume(&mut self);
+}
+
+/// Driver saving its state before standby.
+pub trait Suspend: Resume {
+    /// Finish pending work and save state, before clocks and pads are saved.
+    fn suspend(&mut self);
+}
+
+/// Drivers registered for a [`suspend`] cycle, with room for `N` drivers.
+///
+/// Drivers are suspended in reverse order of registration and resumed in order, so a driver
+/// should be registered after those it depends on.
+pub struct SuspendContext<'a, const N: usize> {
+    drivers: [Option<&'a mut dyn Suspend>; N],
+    len: usize,
+}
+
+impl<'a, const N: usize> SuspendContext<'a, N> {
+    /// Create an empty driver list.
+    #[inline]
+    const fn new() -> Self {
+        Self {
+            drivers: [const { None }; N],
+            len: 0,
+        }
+    }
+    /// Register `driver` for suspend and resume callbacks.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::DriversFull`] if `N` drivers are registered.
+    #[inline]
+    pub fn register(&mut self, driver: &'a mut dyn Suspend) -> Result<(), Error> {
+        let slot = self.drivers.get_mut(self.len).ok_or(Error::DriversFull)?;
+        *slot = Some(driver);
+        self.len += 1;
+        Ok(())
+    }
+    /// Get number of registered drivers.
+    #[inline]
+    pub const fn len(&self) -> usize {
+        self.len
+    }
+    /// Check if no driver is registered.
+    #[inline]
+    pub const fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+    /// Suspend drivers in reverse order of registration.
+    #[inline]
+    fn suspend_all(&mut self) {
+        for driver in self.drivers.iter_mut().rev().flatten() {
+            driver.suspend();
+        }
+    }
+    /// Resume drivers in order of registration.
+    #[inline]
+    fn resume_all(&mut self) {
+        for driver in self.drivers.iter_mut().flatten() {
+            driver.resume();
+        }
+    }
+}
+
+/// Configuration of one GPIO port.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+struct PortSnapshot {
+    cfg: [u32; 4],
+    dat: u32,
+    drv: [u32; 4],
+    pull: [u32; 2],
+}
+
+/// GPIO port modes, output levels, drive strengths and pulls saved across standby.
+///
+/// External interrupt configuration is left to wakeup sources.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct GpioSnapshot {
+    ports: [PortSnapshot; 6],
+}
+
+impl GpioSnapshot {
+    /// Save configuration of all GPIO ports.
+    #[inline]
+    pub fn save(gpio: &gpio::RegisterBlock) -> Self {
+        Self {
+            ports: core::array::from_fn(|i| {
+                let port = &gpio.port[i];
+                PortSnapshot {
+                    cfg: core::array::from_fn(|j| port.cfg[j].read()),
+                    dat: port.dat.read(),
+                    drv: core::array::from_fn(|j| port.drv[j].read()),
+                    pull: core::array::from_fn(|j| port.pull[j].read()),
+                }
+            }),
+        }
+    }
+    /// Restore saved configuration, setting output levels and pulls before pad modes.
+    ///
+    /// # Safety
+    ///
+    /// Pads changed since saving must no longer be owned by any driver.
+    #[inline]
+    pub unsafe fn restore(&self, gpio: &gpio::RegisterBlock) {
+        for (port, saved) in gpio.port.iter().zip(self.ports.iter()) {
+            unsafe {
+                port.dat.write(saved.dat);
+                for (reg, val) in port.pull.iter().zip(saved.pull) {
+                    reg.write(val);
+                }
+                for (reg, val) in port.drv.iter().zip(saved.drv) {
+                    reg.write(val);
+                }
+                for (reg, val) in port.cfg.iter().zip(saved.cfg) {
+                    reg.write(val);
+                }
+            }
+        }
+    }
+}
+
+macro_rules! impl_clock_snapshot {
+    ($($field:ident: $ty:ident,)*) => {
+        /// Peripheral bus clock gates and resets saved across standby.
+        ///
+        /// Memory bus, DRAM and clocks shared with the DSP are left untouched.
+        #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+        pub struct ClockSnapshot {
+            $($field: ccu::$ty,)*
+        }
+
+        impl ClockSnapshot {
+            /// Save peripheral bus clock gates and resets.
+            #[inline]
+            pub fn save(ccu: &ccu::RegisterBlock) -> Self {
+                Self {
+                    $($field: ccu.$field.read(),)*
+                }
+            }
+            /// Restore saved peripheral bus clock gates and resets.
+            ///
+            /// # Safety
+            ///
+            /// Peripherals whose clock changed since saving must no longer be owned by any
+            /// driver.
+            #[inline]
+            pub unsafe fn restore(&self, ccu: &ccu::RegisterBlock) {
+                unsafe {
+                    $(ccu.$field.write(self.$field);)*
+                }
+            }
+        }
+    };
+}
+
+impl_clock_snapshot! {
+    de_bgr: DeBusGating,
+    g2d_bgr: G2dBusGating,
+    ce_bgr: CeBusGating,
+    dma_bgr: DmaBusGating,
+    hstimer_bgr: HstimerBusGating,
+    pwm_bgr: PwmBusGating,
+    smhc_bgr: SmhcBusGating,
+    uart_bgr: UartBusGating,
+    can_bgr: CanBusGating,
+    spi_bgr: SpiBusGating,
+    emac_bgr: EmacBusGating,
+    gpadc_bgr: GpadcBusGating,
+    ths_bgr: ThsBusGating,
+    owa_bgr: OwaBusGating,
+    dmic_bgr: DmicBusGating,
+    audio_codec_bgr: AudioCodecBusGating,
+    usb_bgr: UsbBusGating,
+    lradc_bgr: LradcBusGating,
+    dpss_top_bgr: DpssTopBusGating,
+    hdmi_bgr: HdmiBusGating,
+    tcon_lcd_bgr: TconLcdBusGating,
+    ledc_bgr: LedcBusGating,
+}
+
+/// Enter [`standby`] with state of drivers registered by `register` saved and restored.
+///
+/// Bus clock gates and GPIO pads are saved, then registered drivers are suspended. After
+/// wakeup, or if standby fails, clocks and pads are restored before drivers are resumed.
+/// Returns index of the wakeup source in `sources`.
+///
+/// # Errors
+///
+/// Returns an error from `register`, in which case nothing is suspended, or from
+/// [`standby`], in which case drivers are still resumed.
+///
+/// # Safety
+///
+/// As for [`standby`]; driver callbacks must also reside in SRAM.
+#[inline]
+pub unsafe fn suspend<'a, const N: usize, const M: usize>(
+    ccu: &ccu::RegisterBlock,
+    com: &com::RegisterBlock,
+    phy: &phy::RegisterBlock,
+    gpio: &gpio::RegisterBlock,
+    sources: &mut WakeupSources<'_, M>,
+    register: impl FnOnce(&mut SuspendContext<'a, N>) -> Result<(), Error>,
+) -> Result<usize, Error> {
+    let mut context = SuspendContext::new();
+    register(&mut context)?;
+    let clocks = ClockSnapshot::save(ccu);
+    let pads = GpioSnapshot::save(gpio);
+    context.suspend_all();
+    let woken_by = unsafe { standby(ccu, com, phy, sources) };
+    unsafe {
+        clocks.restore(ccu);
+        pads.restore(gpio);
+    }
+    context.resume_all();
+    woken_by
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Error, Resume, Suspend, SuspendContext};
+
+    struct MockDriver<'a> {
+        id: u8,
+        log: &'a core::cell::Cell<[u8; 4]>,
+        len: &'a core::cell::Cell<usize>,
+    }
+
+    impl MockDriver<'_> {
+        fn record(&self, event: u8) {
+            let mut log = self.log.get();
+            log[self.len.get()] = event;
+            self.log.set(log);
+            self.len.set(self.len.get() + 1);
+        }
+    }
+
+    impl Resume for MockDriver<'_> {
+        fn resume(&mut self) {
+            self.record(self.id + 10);
+        }
+    }
+
+    impl Suspend for MockDriver<'_> {
+        fn suspend(&mut self) {
+            self.record(self.id);
+        }
+    }
+
+    #[test]
+    fn struct_suspend_context_functions() {
+        let log = core::cell::Cell::new([0; 4]);
+        let len = core::cell::Cell::new(0);
+        let mut a = MockDriver {
+            id: 1,
+            log: &log,
+            len: &len,
+        };
+        let mut b = MockDriver {
+            id: 2,
+            log: &log,
+            len: &len,
+        };
+        let mut context = SuspendContext::<2>::new();
+        assert!(context.is_empty());
+        context.register(&mut a).unwrap();
+        context.register(&mut b).unwrap();
+        assert_eq!(context.len(), 2);
+        context.suspend_all();
+        context.resume_all();
+        assert_eq!(log.get(), [2, 1, 11, 12]);
+
+        let mut c = MockDriver {
+            id: 3,
+            log: &log,
+            len: &len,
+        };
+        let mut context = SuspendContext::<0>::new();
+        assert_eq!(context.register(&mut c), Err(Error::DriversFull));
+    }
+}
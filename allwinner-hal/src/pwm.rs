@@ -0,0 +1,613 @@
+//! Pulse Width Modulation.
+//!
+//! PWM channels count 24-MHz 'HOSC' or APB0 clock, divided by a per-pair divider and a
+//! per-channel pre-scaler, and output a waveform of up to 65535 clock cycles per period.
+
+mod register;
+mod tone;
+pub use register::*;
+pub use tone::*;
+
+use crate::ccu::{self, ClockGate, Clocks};
+use crate::time::{Deadline, TimeoutError};
+use crate::timer::HOSC_FREQUENCY;
+use core::num::NonZeroU16;
+use embedded_time::rate::Hertz;
+
+/// Largest number of clock cycles in one PWM period.
+///
+/// Period register counts up to 65536 cycles; one cycle less is used so that active
+/// cycles can cover the whole period, giving a duty cycle of 100%.
+pub const MAX_PERIOD_CYCLES: u16 = 0xFFFF;
+
+/// Achievable output of a frequency setting.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Resolution {
+    /// Actual output waveform frequency.
+    pub frequency: Hertz,
+    /// Number of duty cycle steps, i.e. clock cycles in one period.
+    pub steps: u16,
+}
+
+/// Error setting PWM frequency.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum FrequencyError {
+    /// Frequency cannot be generated from any available clock.
+    OutOfRange,
+    /// Period register was not ready for a new setting before crate-wide blocking timeout.
+    Timeout,
+}
+
+impl From<TimeoutError> for FrequencyError {
+    #[inline]
+    fn from(_: TimeoutError) -> Self {
+        FrequencyError::Timeout
+    }
+}
+
+impl embedded_hal::pwm::Error for TimeoutError {
+    #[inline]
+    fn kind(&self) -> embedded_hal::pwm::ErrorKind {
+        embedded_hal::pwm::ErrorKind::Other
+    }
+}
+
+/// Valid PWM output pad for channel `I`.
+pub trait Output<const I: usize> {}
+
+/// Managed PWM structure with peripheral.
+pub struct Pwm<PWM> {
+    pwm: PWM,
+    apb0: Hertz,
+}
+
+impl<PWM: AsRef<RegisterBlock>> Pwm<PWM> {
+    /// Create a PWM instance, disabling all channels.
+    ///
+    /// All channel pairs are clocked by 'HOSC' oscillator without pair divider. APB0 clock
+    /// from `clocks` is used when [`PwmChannel::try_set_frequency`] selects it for a pair.
+    #[inline]
+    pub fn new(pwm: PWM, clocks: &Clocks, ccu: &ccu::RegisterBlock) -> Self {
+        // note(unsafe): async read and write using ccu registers
+        unsafe { ccu::PWM::reset(ccu) };
+        let regs = pwm.as_ref();
+        unsafe {
+            regs.enable.write(Enable::default());
+            regs.irq_enable.write(IrqEnable::default());
+            regs.clock_gating.write(ClockGating::default());
+            for config in &regs.clock_config {
+                config.write(
+                    ClockConfig::default()
+                        .set_clock_source(ClockSource::Hosc)
+                        .set_factor_m(0),
+                );
+            }
+        }
+        Self {
+            pwm,
+            apb0: clocks.apb0,
+        }
+    }
+    /// Split PWM peripheral into separately owned channels.
+    #[inline]
+    pub fn split(&mut self) -> Channels<'_> {
+        let pwm = self.pwm.as_ref();
+        let apb0 = self.apb0;
+        Channels {
+            ch0: Channel { pwm, apb0 },
+            ch1: Channel { pwm, apb0 },
+            ch2: Channel { pwm, apb0 },
+            ch3: Channel { pwm, apb0 },
+            ch4: Channel { pwm, apb0 },
+            ch5: Channel { pwm, apb0 },
+            ch6: Channel { pwm, apb0 },
+            ch7: Channel { pwm, apb0 },
+        }
+    }
+    /// Close PWM and release peripheral.
+    #[inline]
+    pub fn free(self, ccu: &ccu::RegisterBlock) -> PWM {
+        unsafe { ccu::PWM::free(ccu) };
+        self.pwm
+    }
+}
+
+/// Channels of PWM peripheral.
+pub struct Channels<'a> {
+    /// PWM channel 0.
+    pub ch0: Channel<'a, 0>,
+    /// PWM channel 1.
+    pub ch1: Channel<'a, 1>,
+    /// PWM channel 2.
+    pub ch2: Channel<'a, 2>,
+    /// PWM channel 3.
+    pub ch3: Channel<'a, 3>,
+    /// PWM channel 4.
+    pub ch4: Channel<'a, 4>,
+    /// PWM channel 5.
+    pub ch5: Channel<'a, 5>,
+    /// PWM channel 6.
+    pub ch6: Channel<'a, 6>,
+    /// PWM channel 7.
+    pub ch7: Channel<'a, 7>,
+}
+
+/// Unconfigured PWM channel `I`.
+pub struct Channel<'a, const I: usize> {
+    pwm: &'a RegisterBlock,
+    apb0: Hertz,
+}
+
+impl<'a, const I: usize> Channel<'a, I> {
+    /// Configure channel to output waveform of `frequency` on `pad`, starting at 0% duty cycle.
+    ///
+    /// Output is active high; frequency should range from about 1.5 Hz to 12 MHz.
+    ///
+    /// Returns [`FrequencyError::OutOfRange`] if `frequency` cannot be generated from the
+    /// pair clock; the channel is left disabled.
+    #[inline]
+    pub fn into_pwm<PAD: Output<I>>(
+        self,
+        pad: PAD,
+        frequency: Hertz,
+    ) -> Result<PwmChannel<'a, I, PAD>, FrequencyError> {
+        unsafe {
+            self.pwm
+                .clock_gating
+                .modify(|val| val.gate_pass::<I>().disable_bypass::<I>());
+            self.pwm.channels[I].control.write(
+                Control::default()
+                    .set_mode(PwmMode::Cycle)
+                    .set_polarity(Polarity::ActiveHigh),
+            );
+        }
+        let mut channel = PwmChannel {
+            pwm: self.pwm,
+            apb0: self.apb0,
+            pad,
+            period: 1,
+            active: 0,
+        };
+        if let Err(e) = channel.set_frequency(frequency) {
+            unsafe { self.pwm.clock_gating.modify(|val| val.gate_mask::<I>()) };
+            return Err(e);
+        }
+        unsafe { self.pwm.enable.modify(|val| val.enable::<I>()) };
+        Ok(channel)
+    }
+}
+
+/// PWM channel `I` with output pad.
+pub struct PwmChannel<'a, const I: usize, PAD: Output<I>> {
+    pwm: &'a RegisterBlock,
+    apb0: Hertz,
+    pad: PAD,
+    period: u16,
+    active: u16,
+}
+
+impl<'a, const I: usize, PAD: Output<I>> PwmChannel<'a, I, PAD> {
+    /// Set output waveform frequency, keeping current duty cycle ratio and pair clock.
+    ///
+    /// With default 'HOSC' pair clock, frequency should range from about 1.5 Hz to 12 MHz;
+    /// returns [`FrequencyError::OutOfRange`] otherwise, keeping current frequency.
+    #[inline]
+    pub fn set_frequency(&mut self, frequency: Hertz) -> Result<(), FrequencyError> {
+        let (prescale, period) =
+            fit_frequency(self.pair_clock(), frequency.0).ok_or(FrequencyError::OutOfRange)?;
+        self.write_prescale_and_period(prescale, period)
+    }
+    /// Set output waveform frequency, selecting pair clock source and dividers that match
+    /// `frequency` best, and keeping current duty cycle ratio.
+    ///
+    /// Pair clock is shared with the other channel of this pair; it is only changed when
+    /// that channel is unused, otherwise only pre-scaler and period are selected. Among
+    /// equally accurate settings, the one with most duty cycle steps is chosen.
+    #[inline]
+    pub fn try_set_frequency(&mut self, frequency: Hertz) -> Result<Resolution, FrequencyError> {
+        let config = self.pwm.clock_config[I / 2].read();
+        let shared = self.pwm.clock_gating.read().is_sibling_gate_pass::<I>();
+        let mut best: Option<(ClockConfig, u8, u16, u64)> = None;
+        for source in [ClockSource::Hosc, ClockSource::Apb0] {
+            for factor_m in 0..=8 {
+                let candidate = config.set_clock_source(source).set_factor_m(factor_m);
+                if shared && candidate != config {
+                    continue;
+                }
+                let clock = self.source_frequency(source) >> factor_m;
+                let Some((prescale, period)) = fit_frequency(clock, frequency.0) else {
+                    continue;
+                };
+                // error in millihertz between actual and requested frequency
+                let cycles = (prescale as u64 + 1) * period as u64;
+                let error = (clock as u64 * 1000 / cycles).abs_diff(frequency.0 as u64 * 1000);
+                let better = match best {
+                    None => true,
+                    Some((_, _, best_period, best_error)) => {
+                        error < best_error || (error == best_error && period > best_period)
+                    }
+                };
+                if better {
+                    best = Some((candidate, prescale, period, error));
+                }
+            }
+        }
+        let (candidate, prescale, period, _) = best.ok_or(FrequencyError::OutOfRange)?;
+        if candidate != config {
+            unsafe { self.pwm.clock_config[I / 2].write(candidate) };
+        }
+        self.write_prescale_and_period(prescale, period)?;
+        Ok(Resolution {
+            frequency: self.frequency(),
+            steps: period,
+        })
+    }
+    /// Get actual output waveform frequency.
+    #[inline]
+    pub fn frequency(&self) -> Hertz {
+        let prescale = self.pwm.channels[I].control.read().prescale() as u32 + 1;
+        Hertz(self.pair_clock() / prescale / self.period as u32)
+    }
+    /// Set active state polarity.
+    #[inline]
+    pub fn set_polarity(&mut self, polarity: Polarity) {
+        unsafe {
+            self.pwm.channels[I]
+                .control
+                .modify(|val| val.set_polarity(polarity))
+        };
+    }
+    /// Start waveform output.
+    #[inline]
+    pub fn enable(&mut self) {
+        unsafe { self.pwm.enable.modify(|val| val.enable::<I>()) };
+    }
+    /// Stop waveform output.
+    #[inline]
+    pub fn disable(&mut self) {
+        unsafe { self.pwm.enable.modify(|val| val.disable::<I>()) };
+    }
+    /// Check if waveform output is enabled.
+    #[inline]
+    pub fn is_enabled(&self) -> bool {
+        self.pwm.enable.read().is_enabled::<I>()
+    }
+    /// Output exactly `count` pulses and then stop, without CPU involvement.
+    ///
+    /// Pulses use current frequency and duty cycle. Completion can be polled with
+    /// [`is_pulse_train_finished`](Self::is_pulse_train_finished) or signalled through
+    /// channel interrupt.
+    #[inline]
+    pub fn start_pulse_train(&mut self, count: NonZeroU16) {
+        unsafe {
+            self.pwm
+                .irq_status
+                .write(IrqStatus::default().clear_pending::<I>());
+            self.pwm.channels[I].control.modify(|val| {
+                val.set_mode(PwmMode::Pulse)
+                    .set_pulse_number(count.get() - 1)
+                    .set_pulse_start()
+            });
+            self.pwm.enable.modify(|val| val.enable::<I>());
+        }
+    }
+    /// Check if last pulse train has finished.
+    #[inline]
+    pub fn is_pulse_train_finished(&self) -> bool {
+        self.pwm.channels[I].control.read().is_pulse_finished()
+    }
+    /// Return to continuous waveform output after pulse trains.
+    #[inline]
+    pub fn set_continuous(&mut self) {
+        unsafe {
+            self.pwm.channels[I]
+                .control
+                .modify(|val| val.set_mode(PwmMode::Cycle))
+        };
+    }
+    /// Enable channel interrupt, raised at end of each period in continuous output, or after
+    /// the last pulse of a pulse train.
+    #[inline]
+    pub fn enable_interrupt(&mut self) {
+        unsafe { self.pwm.irq_enable.modify(|val| val.enable::<I>()) };
+    }
+    /// Disable channel interrupt.
+    #[inline]
+    pub fn disable_interrupt(&mut self) {
+        unsafe { self.pwm.irq_enable.modify(|val| val.disable::<I>()) };
+    }
+    /// Check if channel interrupt is pending.
+    #[inline]
+    pub fn is_pending(&self) -> bool {
+        self.pwm.irq_status.read().is_pending::<I>()
+    }
+    /// Clear pending channel interrupt.
+    #[inline]
+    pub fn clear_interrupt(&mut self) {
+        unsafe {
+            self.pwm
+                .irq_status
+                .write(IrqStatus::default().clear_pending::<I>())
+        };
+    }
+    /// Stop output and release channel and pad.
+    #[inline]
+    pub fn free(self) -> (Channel<'a, I>, PAD) {
+        unsafe {
+            self.pwm.irq_enable.modify(|val| val.disable::<I>());
+            self.pwm.enable.modify(|val| val.disable::<I>());
+            self.pwm.clock_gating.modify(|val| val.gate_mask::<I>());
+        }
+        (
+            Channel {
+                pwm: self.pwm,
+                apb0: self.apb0,
+            },
+            self.pad,
+        )
+    }
+    #[inline]
+    fn source_frequency(&self, source: ClockSource) -> u32 {
+        match source {
+            ClockSource::Hosc => HOSC_FREQUENCY.0,
+            ClockSource::Apb0 => self.apb0.0,
+        }
+    }
+    /// Get frequency of pair clock after pair divider.
+    #[inline]
+    fn pair_clock(&self) -> u32 {
+        let config = self.pwm.clock_config[I / 2].read();
+        self.source_frequency(config.clock_source()) >> config.factor_m()
+    }
+    #[inline]
+    fn write_prescale_and_period(
+        &mut self,
+        prescale: u8,
+        period: u16,
+    ) -> Result<(), FrequencyError> {
+        let active = (self.active as u32 * period as u32 / self.period as u32) as u16;
+        let channel = &self.pwm.channels[I];
+        unsafe { channel.control.modify(|val| val.set_prescale(prescale)) };
+        self.period = period;
+        Ok(self.write_period(active)?)
+    }
+    #[inline]
+    fn write_period(&mut self, active: u16) -> Result<(), TimeoutError> {
+        let channel = &self.pwm.channels[I];
+        let deadline = Deadline::start();
+        while !channel.control.read().is_period_ready() {
+            deadline.check()?;
+            core::hint::spin_loop();
+        }
+        // An active cycle count of `period` keeps output active for the whole period.
+        unsafe {
+            channel.period.write(
+                Period::default()
+                    .set_entire_cycle(self.period - 1)
+                    .set_active_cycle(active),
+            )
+        };
+        self.active = active;
+        Ok(())
+    }
+}
+
+/// Complementary PWM pair of even channel `I` and odd channel `J`, with dead time.
+///
+/// Channel `J` outputs complement of channel `I`; both outputs stay inactive for dead time
+/// around each transition, so that switches driven by the pair are never on together.
+/// Duty cycle refers to output of channel `I`.
+pub struct ComplementaryPwm<'a, const I: usize, const J: usize, P: Output<I>, N: Output<J>> {
+    primary: PwmChannel<'a, I, P>,
+    pad_n: N,
+}
+
+macro_rules! impl_complementary {
+    ($($i: expr, $j: expr;)+) => {
+        $(
+impl<'a> Channel<'a, $i> {
+    /// Configure this channel and the next odd channel as complementary outputs of
+    /// `frequency` on `pad` and `pad_n`, starting at 0% duty cycle.
+    ///
+    /// Dead time is counted in 24-MHz 'HOSC' clock cycles. Returns
+    /// [`FrequencyError::OutOfRange`] if `frequency` cannot be generated from the pair clock.
+    #[inline]
+    pub fn into_complementary<P: Output<$i>, N: Output<$j>>(
+        self,
+        _other: Channel<'a, $j>,
+        pad: P,
+        pad_n: N,
+        frequency: Hertz,
+        dead_time: u8,
+    ) -> Result<ComplementaryPwm<'a, $i, $j, P, N>, FrequencyError> {
+        unsafe {
+            self.pwm
+                .clock_gating
+                .modify(|val| val.gate_pass::<$j>().disable_bypass::<$j>());
+            self.pwm.dead_zone[$i / 2]
+                .write(DeadZone::default().set_interval(dead_time).enable());
+        }
+        let pwm = self.pwm;
+        let primary = match self.into_pwm(pad, frequency) {
+            Ok(primary) => primary,
+            Err(e) => {
+                unsafe {
+                    pwm.clock_gating.modify(|val| val.gate_mask::<$j>());
+                    pwm.dead_zone[$i / 2].write(DeadZone::default());
+                }
+                return Err(e);
+            }
+        };
+        unsafe { primary.pwm.enable.modify(|val| val.enable::<$j>()) };
+        Ok(ComplementaryPwm { primary, pad_n })
+    }
+}
+        )+
+    };
+}
+
+impl_complementary! {
+    0, 1;
+    2, 3;
+    4, 5;
+    6, 7;
+}
+
+impl<'a, const I: usize, const J: usize, P: Output<I>, N: Output<J>>
+    ComplementaryPwm<'a, I, J, P, N>
+{
+    /// Set output waveform frequency, keeping current duty cycle ratio.
+    ///
+    /// Returns [`FrequencyError::OutOfRange`] if `frequency` cannot be generated from the
+    /// pair clock.
+    #[inline]
+    pub fn set_frequency(&mut self, frequency: Hertz) -> Result<(), FrequencyError> {
+        self.primary.set_frequency(frequency)
+    }
+    /// Get actual output waveform frequency.
+    #[inline]
+    pub fn frequency(&self) -> Hertz {
+        self.primary.frequency()
+    }
+    /// Set dead time in 24-MHz 'HOSC' clock cycles.
+    #[inline]
+    pub fn set_dead_time(&mut self, cycles: u8) {
+        unsafe { self.primary.pwm.dead_zone[I / 2].modify(|val| val.set_interval(cycles)) };
+    }
+    /// Get dead time in 24-MHz 'HOSC' clock cycles.
+    #[inline]
+    pub fn dead_time(&self) -> u8 {
+        self.primary.pwm.dead_zone[I / 2].read().interval()
+    }
+    /// Set active state polarity of both outputs.
+    #[inline]
+    pub fn set_polarity(&mut self, polarity: Polarity) {
+        self.primary.set_polarity(polarity);
+    }
+    /// Start waveform output on both channels.
+    #[inline]
+    pub fn enable(&mut self) {
+        unsafe {
+            self.primary
+                .pwm
+                .enable
+                .modify(|val| val.enable::<I>().enable::<J>())
+        };
+    }
+    /// Stop waveform output on both channels.
+    #[inline]
+    pub fn disable(&mut self) {
+        unsafe {
+            self.primary
+                .pwm
+                .enable
+                .modify(|val| val.disable::<I>().disable::<J>())
+        };
+    }
+    /// Check if waveform output is enabled.
+    #[inline]
+    pub fn is_enabled(&self) -> bool {
+        self.primary.is_enabled()
+    }
+    /// Stop output, disable dead zone and release channels and pads.
+    #[inline]
+    pub fn free(self) -> (Channel<'a, I>, Channel<'a, J>, P, N) {
+        let (pwm, apb0) = (self.primary.pwm, self.primary.apb0);
+        unsafe {
+            pwm.enable.modify(|val| val.disable::<J>());
+            pwm.clock_gating.modify(|val| val.gate_mask::<J>());
+            pwm.dead_zone[I / 2].write(DeadZone::default());
+        }
+        let (channel, pad) = self.primary.free();
+        (channel, Channel { pwm, apb0 }, pad, self.pad_n)
+    }
+}
+
+impl<'a, const I: usize, const J: usize, P: Output<I>, N: Output<J>> embedded_hal::pwm::ErrorType
+    for ComplementaryPwm<'a, I, J, P, N>
+{
+    type Error = TimeoutError;
+}
+
+impl<'a, const I: usize, const J: usize, P: Output<I>, N: Output<J>> embedded_hal::pwm::SetDutyCycle
+    for ComplementaryPwm<'a, I, J, P, N>
+{
+    #[inline]
+    fn max_duty_cycle(&self) -> u16 {
+        self.primary.max_duty_cycle()
+    }
+    #[inline]
+    fn set_duty_cycle(&mut self, duty: u16) -> Result<(), Self::Error> {
+        self.primary.set_duty_cycle(duty)
+    }
+}
+
+impl<'a, const I: usize, PAD: Output<I>> embedded_hal::pwm::ErrorType for PwmChannel<'a, I, PAD> {
+    type Error = TimeoutError;
+}
+
+impl<'a, const I: usize, PAD: Output<I>> embedded_hal::pwm::SetDutyCycle
+    for PwmChannel<'a, I, PAD>
+{
+    #[inline]
+    fn max_duty_cycle(&self) -> u16 {
+        self.period
+    }
+    #[inline]
+    fn set_duty_cycle(&mut self, duty: u16) -> Result<(), Self::Error> {
+        self.write_period(duty.min(self.period))
+    }
+}
+
+/// Calculate pre-scale factor `K` and clock cycles in one period for `frequency`, if
+/// `frequency` can be generated from `source` at all.
+#[inline]
+const fn fit_frequency(source: u32, frequency: u32) -> Option<(u8, u16)> {
+    if frequency == 0
+        || frequency > source / 2
+        || source / frequency > 256 * MAX_PERIOD_CYCLES as u32
+    {
+        return None;
+    }
+    Some(prescale_and_period(source, frequency))
+}
+
+/// Calculate pre-scale factor `K` and clock cycles in one period for `frequency`.
+///
+/// Range of `frequency` is checked by [`fit_frequency`].
+#[inline]
+const fn prescale_and_period(source: u32, frequency: u32) -> (u8, u16) {
+    debug_assert!(frequency != 0 && frequency <= source / 2);
+    let cycles = source / frequency;
+    let divide = cycles.div_ceil(MAX_PERIOD_CYCLES as u32);
+    let divide = if divide > 256 { 256 } else { divide };
+    let period = cycles / divide;
+    let period = if period > MAX_PERIOD_CYCLES as u32 {
+        MAX_PERIOD_CYCLES
+    } else {
+        period as u16
+    };
+    ((divide - 1) as u8, period)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{MAX_PERIOD_CYCLES, fit_frequency, prescale_and_period};
+
+    #[test]
+    fn function_prescale_and_period() {
+        assert_eq!(prescale_and_period(24_000_000, 1_000), (0, 24_000));
+        assert_eq!(prescale_and_period(24_000_000, 50), (7, 60_000));
+        assert_eq!(prescale_and_period(24_000_000, 12_000_000), (0, 2));
+        assert_eq!(prescale_and_period(24_000_000, 1), (255, MAX_PERIOD_CYCLES));
+    }
+
+    #[test]
+    fn function_fit_frequency() {
+        assert_eq!(fit_frequency(24_000_000, 1_000), Some((0, 24_000)));
+        assert_eq!(fit_frequency(24_000_000, 0), None);
+        assert_eq!(fit_frequency(24_000_000, 12_000_001), None);
+        assert_eq!(fit_frequency(24_000_000, 1), None);
+        assert_eq!(fit_frequency(24_000_000 >> 8, 1), Some((1, 46_875)));
+    }
+}
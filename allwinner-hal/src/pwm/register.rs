@@ -0,0 +1,558 @@
+use volatile_register::{RO, RW};
+
+/// Number of PWM channels on current SoC.
+pub const CHANNEL_COUNT: usize = 8;
+
+/// Pulse Width Modulation registers.
+#[repr(C)]
+pub struct RegisterBlock {
+    /// 0x00 - PWM IRQ Enable Register.
+    pub irq_enable: RW<IrqEnable>,
+    /// 0x04 - PWM IRQ Status Register.
+    pub irq_status: RW<IrqStatus>,
+    _reserved0: [u32; 6],
+    /// 0x20 ..= 0x2C - PWM01, PWM23, PWM45 and PWM67 Clock Configuration Registers.
+    pub clock_config: [RW<ClockConfig>; CHANNEL_COUNT / 2],
+    _reserved1: [u32; 4],
+    /// 0x40 - PWM Clock Gating Register.
+    pub clock_gating: RW<ClockGating>,
+    _reserved2: [u32; 7],
+    /// 0x60 ..= 0x6C - PWM01, PWM23, PWM45 and PWM67 Dead Zone Control Registers.
+    pub dead_zone: [RW<DeadZone>; CHANNEL_COUNT / 2],
+    _reserved3: [u32; 4],
+    /// 0x80 - PWM Enable Register.
+    pub enable: RW<Enable>,
+    _reserved4: [u32; 31],
+    /// 0x100 ..= 0x1FC - PWM channel register groups.
+    pub channels: [ChannelRegisters; CHANNEL_COUNT],
+}
+
+/// PWM channel register group.
+#[repr(C)]
+pub struct ChannelRegisters {
+    /// 0x00 - PWM Control Register.
+    pub control: RW<Control>,
+    /// 0x04 - PWM Period Register.
+    pub period: RW<Period>,
+    /// 0x08 - PWM Count Register, bits 15:0.
+    pub counter: RO<u32>,
+    /// 0x0C - PWM Pulse Counter Register, bits 15:0.
+    pub pulse_counter: RO<u32>,
+    _reserved0: [u32; 4],
+}
+
+/// PWM IRQ enable register.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct IrqEnable(u32);
+
+impl IrqEnable {
+    /// If interrupt of channel `I` is enabled.
+    #[inline]
+    pub const fn is_enabled<const I: usize>(self) -> bool {
+        self.0 & (1 << I) != 0
+    }
+    /// Enable interrupt of channel `I`.
+    #[inline]
+    pub const fn enable<const I: usize>(self) -> Self {
+        Self(self.0 | (1 << I))
+    }
+    /// Disable interrupt of channel `I`.
+    #[inline]
+    pub const fn disable<const I: usize>(self) -> Self {
+        Self(self.0 & !(1 << I))
+    }
+}
+
+impl Default for IrqEnable {
+    #[inline]
+    fn default() -> Self {
+        Self(0x0000_0000)
+    }
+}
+
+/// PWM IRQ status register.
+///
+/// Channel interrupt is raised at end of each period in cycle mode, and after the last
+/// pulse in pulse mode.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct IrqStatus(u32);
+
+impl IrqStatus {
+    /// If interrupt of channel `I` is pending.
+    #[inline]
+    pub const fn is_pending<const I: usize>(self) -> bool {
+        self.0 & (1 << I) != 0
+    }
+    /// Clear pending interrupt of channel `I` on write.
+    #[inline]
+    pub const fn clear_pending<const I: usize>(self) -> Self {
+        Self(self.0 | (1 << I))
+    }
+}
+
+impl Default for IrqStatus {
+    #[inline]
+    fn default() -> Self {
+        Self(0x0000_0000)
+    }
+}
+
+/// PWM clock source.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ClockSource {
+    /// 24-MHz 'HOSC' oscillator.
+    Hosc,
+    /// APB0 bus clock.
+    Apb0,
+}
+
+/// PWM pair clock configuration register.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct ClockConfig(u32);
+
+impl ClockConfig {
+    const CLK_SRC_SEL: u32 = 0x3 << 7;
+    const CLK_DIV_M: u32 = 0xF;
+
+    /// Get clock source of this channel pair.
+    #[inline]
+    pub const fn clock_source(self) -> ClockSource {
+        match (self.0 & Self::CLK_SRC_SEL) >> 7 {
+            0 => ClockSource::Hosc,
+            1 => ClockSource::Apb0,
+            _ => panic!("impossible clock source"),
+        }
+    }
+    /// Set clock source of this channel pair.
+    #[inline]
+    pub const fn set_clock_source(self, val: ClockSource) -> Self {
+        Self((self.0 & !Self::CLK_SRC_SEL) | ((val as u32) << 7))
+    }
+    /// Get clock divide factor `M`, where clock is divided by `2^M`.
+    #[inline]
+    pub const fn factor_m(self) -> u8 {
+        (self.0 & Self::CLK_DIV_M) as u8
+    }
+    /// Set clock divide factor `M`, where clock is divided by `2^M`.
+    ///
+    /// Factor should not exceed 8.
+    #[inline]
+    pub const fn set_factor_m(self, val: u8) -> Self {
+        Self((self.0 & !Self::CLK_DIV_M) | (val as u32 & 0xF))
+    }
+}
+
+impl Default for ClockConfig {
+    #[inline]
+    fn default() -> Self {
+        Self(0x0000_0000)
+    }
+}
+
+/// PWM clock gating register.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct ClockGating(u32);
+
+impl ClockGating {
+    /// If clock of channel `I` is passed through.
+    #[inline]
+    pub const fn is_gate_pass<const I: usize>(self) -> bool {
+        self.0 & (1 << I) != 0
+    }
+    /// Unmask (pass) clock gating of channel `I`.
+    #[inline]
+    pub const fn gate_pass<const I: usize>(self) -> Self {
+        Self(self.0 | (1 << I))
+    }
+    /// If clock of the other channel in pair of channel `I` is passed through.
+    #[inline]
+    pub const fn is_sibling_gate_pass<const I: usize>(self) -> bool {
+        self.0 & (1 << (I ^ 1)) != 0
+    }
+    /// Mask clock gating of channel `I`.
+    #[inline]
+    pub const fn gate_mask<const I: usize>(self) -> Self {
+        Self(self.0 & !(1 << I))
+    }
+    /// If channel `I` outputs its source clock directly.
+    #[inline]
+    pub const fn is_bypass_enabled<const I: usize>(self) -> bool {
+        self.0 & (1 << (16 + I)) != 0
+    }
+    /// Output source clock directly on channel `I`.
+    #[inline]
+    pub const fn enable_bypass<const I: usize>(self) -> Self {
+        Self(self.0 | (1 << (16 + I)))
+    }
+    /// Output modulated waveform on channel `I`.
+    #[inline]
+    pub const fn disable_bypass<const I: usize>(self) -> Self {
+        Self(self.0 & !(1 << (16 + I)))
+    }
+}
+
+impl Default for ClockGating {
+    #[inline]
+    fn default() -> Self {
+        Self(0x0000_0000)
+    }
+}
+
+/// PWM pair dead zone control register.
+///
+/// With dead zone enabled, odd channel of the pair outputs complement of even channel,
+/// and both outputs stay inactive for dead zone interval around each transition.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct DeadZone(u32);
+
+impl DeadZone {
+    const DZ_INTV: u32 = 0xFF << 8;
+    const DZ_EN: u32 = 1 << 0;
+
+    /// Get dead zone interval in pair clock cycles.
+    #[inline]
+    pub const fn interval(self) -> u8 {
+        ((self.0 & Self::DZ_INTV) >> 8) as u8
+    }
+    /// Set dead zone interval in pair clock cycles.
+    #[inline]
+    pub const fn set_interval(self, val: u8) -> Self {
+        Self((self.0 & !Self::DZ_INTV) | ((val as u32) << 8))
+    }
+    /// If dead zone and complementary output are enabled.
+    #[inline]
+    pub const fn is_enabled(self) -> bool {
+        self.0 & Self::DZ_EN != 0
+    }
+    /// Enable dead zone and complementary output.
+    #[inline]
+    pub const fn enable(self) -> Self {
+        Self(self.0 | Self::DZ_EN)
+    }
+    /// Disable dead zone and complementary output.
+    #[inline]
+    pub const fn disable(self) -> Self {
+        Self(self.0 & !Self::DZ_EN)
+    }
+}
+
+impl Default for DeadZone {
+    #[inline]
+    fn default() -> Self {
+        Self(0x0000_0000)
+    }
+}
+
+/// PWM enable register.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct Enable(u32);
+
+impl Enable {
+    /// If channel `I` is enabled.
+    #[inline]
+    pub const fn is_enabled<const I: usize>(self) -> bool {
+        self.0 & (1 << I) != 0
+    }
+    /// Enable channel `I`.
+    #[inline]
+    pub const fn enable<const I: usize>(self) -> Self {
+        Self(self.0 | (1 << I))
+    }
+    /// Disable channel `I`.
+    #[inline]
+    pub const fn disable<const I: usize>(self) -> Self {
+        Self(self.0 & !(1 << I))
+    }
+}
+
+impl Default for Enable {
+    #[inline]
+    fn default() -> Self {
+        Self(0x0000_0000)
+    }
+}
+
+/// PWM output mode.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum PwmMode {
+    /// Output waveform continuously.
+    Cycle,
+    /// Output a number of pulses on each pulse start.
+    Pulse,
+}
+
+/// PWM active state polarity.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Polarity {
+    /// Output is low during active cycles.
+    ActiveLow,
+    /// Output is high during active cycles.
+    ActiveHigh,
+}
+
+/// PWM channel control register.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct Control(u32);
+
+impl Control {
+    const PUL_NUM: u32 = 0xFFFF << 16;
+    const PERIOD_RDY: u32 = 1 << 11;
+    const PUL_START: u32 = 1 << 10;
+    const MODE: u32 = 1 << 9;
+    const ACT_STA: u32 = 1 << 8;
+    const PRESCAL_K: u32 = 0xFF;
+
+    /// Get number of pulses output in pulse mode, minus one.
+    #[inline]
+    pub const fn pulse_number(self) -> u16 {
+        ((self.0 & Self::PUL_NUM) >> 16) as u16
+    }
+    /// Set number of pulses output in pulse mode, minus one.
+    #[inline]
+    pub const fn set_pulse_number(self, val: u16) -> Self {
+        Self((self.0 & !Self::PUL_NUM) | ((val as u32) << 16))
+    }
+    /// Check if period register is ready for new values.
+    #[inline]
+    pub const fn is_period_ready(self) -> bool {
+        self.0 & Self::PERIOD_RDY == 0
+    }
+    /// Start output pulses in pulse mode.
+    #[inline]
+    pub const fn set_pulse_start(self) -> Self {
+        Self(self.0 | Self::PUL_START)
+    }
+    /// Check if pulse output has finished, cleared by hardware after the last pulse.
+    #[inline]
+    pub const fn is_pulse_finished(self) -> bool {
+        self.0 & Self::PUL_START == 0
+    }
+    /// Get output mode.
+    #[inline]
+    pub const fn mode(self) -> PwmMode {
+        match (self.0 & Self::MODE) >> 9 {
+            0 => PwmMode::Cycle,
+            1 => PwmMode::Pulse,
+            _ => unreachable!(),
+        }
+    }
+    /// Set output mode.
+    #[inline]
+    pub const fn set_mode(self, val: PwmMode) -> Self {
+        Self((self.0 & !Self::MODE) | ((val as u32) << 9))
+    }
+    /// Get active state polarity.
+    #[inline]
+    pub const fn polarity(self) -> Polarity {
+        match (self.0 & Self::ACT_STA) >> 8 {
+            0 => Polarity::ActiveLow,
+            1 => Polarity::ActiveHigh,
+            _ => unreachable!(),
+        }
+    }
+    /// Set active state polarity.
+    #[inline]
+    pub const fn set_polarity(self, val: Polarity) -> Self {
+        Self((self.0 & !Self::ACT_STA) | ((val as u32) << 8))
+    }
+    /// Get pre-scale factor `K`, where clock is divided by `K + 1`.
+    #[inline]
+    pub const fn prescale(self) -> u8 {
+        (self.0 & Self::PRESCAL_K) as u8
+    }
+    /// Set pre-scale factor `K`, where clock is divided by `K + 1`.
+    #[inline]
+    pub const fn set_prescale(self, val: u8) -> Self {
+        Self((self.0 & !Self::PRESCAL_K) | val as u32)
+    }
+}
+
+impl Default for Control {
+    #[inline]
+    fn default() -> Self {
+        Self(0x0000_0000)
+    }
+}
+
+/// PWM channel period register.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct Period(u32);
+
+impl Period {
+    const ENTIRE_CYCLE: u32 = 0xFFFF << 16;
+    const ACT_CYCLE: u32 = 0xFFFF;
+
+    /// Get number of clock cycles in one period, minus one.
+    #[inline]
+    pub const fn entire_cycle(self) -> u16 {
+        ((self.0 & Self::ENTIRE_CYCLE) >> 16) as u16
+    }
+    /// Set number of clock cycles in one period, minus one.
+    #[inline]
+    pub const fn set_entire_cycle(self, val: u16) -> Self {
+        Self((self.0 & !Self::ENTIRE_CYCLE) | ((val as u32) << 16))
+    }
+    /// Get number of active clock cycles in one period.
+    #[inline]
+    pub const fn active_cycle(self) -> u16 {
+        (self.0 & Self::ACT_CYCLE) as u16
+    }
+    /// Set number of active clock cycles in one period.
+    #[inline]
+    pub const fn set_active_cycle(self, val: u16) -> Self {
+        Self((self.0 & !Self::ACT_CYCLE) | val as u32)
+    }
+}
+
+impl Default for Period {
+    #[inline]
+    fn default() -> Self {
+        Self(0x0000_0000)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        ChannelRegisters, ClockConfig, ClockGating, ClockSource, Control, DeadZone, Enable,
+        IrqEnable, IrqStatus, Period, Polarity, PwmMode, RegisterBlock,
+    };
+    use core::mem::{offset_of, size_of};
+
+    #[test]
+    fn offset_pwm() {
+        assert_eq!(offset_of!(RegisterBlock, irq_enable), 0x00);
+        assert_eq!(offset_of!(RegisterBlock, irq_status), 0x04);
+        assert_eq!(offset_of!(RegisterBlock, clock_config), 0x20);
+        assert_eq!(offset_of!(RegisterBlock, clock_gating), 0x40);
+        assert_eq!(offset_of!(RegisterBlock, dead_zone), 0x60);
+        assert_eq!(offset_of!(RegisterBlock, enable), 0x80);
+        assert_eq!(offset_of!(RegisterBlock, channels), 0x100);
+        assert_eq!(offset_of!(ChannelRegisters, control), 0x00);
+        assert_eq!(offset_of!(ChannelRegisters, period), 0x04);
+        assert_eq!(offset_of!(ChannelRegisters, counter), 0x08);
+        assert_eq!(offset_of!(ChannelRegisters, pulse_counter), 0x0C);
+        assert_eq!(size_of::<ChannelRegisters>(), 0x20);
+    }
+
+    #[test]
+    fn struct_irq_functions() {
+        let mut val = IrqEnable::default();
+        val = val.enable::<5>();
+        assert!(val.is_enabled::<5>());
+        assert!(!val.is_enabled::<0>());
+        assert_eq!(val.0, 0x00000020);
+        val = val.disable::<5>();
+        assert_eq!(val.0, 0x00000000);
+
+        let val = IrqStatus(0x4);
+        assert!(val.is_pending::<2>());
+        assert!(!val.is_pending::<1>());
+        let val = IrqStatus::default()
+            .clear_pending::<0>()
+            .clear_pending::<7>();
+        assert_eq!(val.0, 0x00000081);
+    }
+
+    #[test]
+    fn struct_clock_config_functions() {
+        let mut val = ClockConfig::default();
+        val = val.set_clock_source(ClockSource::Apb0);
+        assert_eq!(val.clock_source(), ClockSource::Apb0);
+        assert_eq!(val.0, 0x00000080);
+        val = val.set_clock_source(ClockSource::Hosc);
+        assert_eq!(val.clock_source(), ClockSource::Hosc);
+        assert_eq!(val.0, 0x00000000);
+
+        val = val.set_factor_m(8);
+        assert_eq!(val.factor_m(), 8);
+        assert_eq!(val.0, 0x00000008);
+    }
+
+    #[test]
+    fn struct_clock_gating_enable_functions() {
+        let mut val = ClockGating::default();
+        val = val.gate_pass::<3>();
+        assert!(val.is_gate_pass::<3>());
+        assert!(!val.is_gate_pass::<0>());
+        assert_eq!(val.0, 0x00000008);
+        val = val.enable_bypass::<3>();
+        assert!(val.is_bypass_enabled::<3>());
+        assert_eq!(val.0, 0x00080008);
+        val = val.disable_bypass::<3>().gate_mask::<3>();
+        assert_eq!(val.0, 0x00000000);
+
+        let mut val = Enable::default();
+        val = val.enable::<7>();
+        assert!(val.is_enabled::<7>());
+        assert_eq!(val.0, 0x00000080);
+        val = val.disable::<7>();
+        assert_eq!(val.0, 0x00000000);
+    }
+
+    #[test]
+    fn struct_dead_zone_functions() {
+        let mut val = DeadZone::default();
+        val = val.set_interval(0xAB);
+        assert_eq!(val.interval(), 0xAB);
+        assert_eq!(val.0, 0x0000AB00);
+        val = val.enable();
+        assert!(val.is_enabled());
+        assert_eq!(val.0, 0x0000AB01);
+        val = val.disable();
+        assert!(!val.is_enabled());
+        assert_eq!(val.0, 0x0000AB00);
+    }
+
+    #[test]
+    fn struct_control_functions() {
+        let mut val = Control::default();
+        assert!(val.is_period_ready());
+        assert!(!Control(0x0000_0800).is_period_ready());
+
+        val = val.set_pulse_start();
+        assert!(!val.is_pulse_finished());
+        assert_eq!(val.0, 0x00000400);
+
+        val = Control::default().set_pulse_number(0x1234);
+        assert_eq!(val.pulse_number(), 0x1234);
+        assert_eq!(val.0, 0x12340000);
+
+        val = Control::default().set_mode(PwmMode::Pulse);
+        assert_eq!(val.mode(), PwmMode::Pulse);
+        assert_eq!(val.0, 0x00000200);
+        val = val.set_mode(PwmMode::Cycle);
+        assert_eq!(val.mode(), PwmMode::Cycle);
+        assert_eq!(val.0, 0x00000000);
+
+        val = val.set_polarity(Polarity::ActiveHigh);
+        assert_eq!(val.polarity(), Polarity::ActiveHigh);
+        assert_eq!(val.0, 0x00000100);
+        val = val.set_polarity(Polarity::ActiveLow);
+        assert_eq!(val.polarity(), Polarity::ActiveLow);
+        assert_eq!(val.0, 0x00000000);
+
+        val = val.set_prescale(0xFF);
+        assert_eq!(val.prescale(), 0xFF);
+        assert_eq!(val.0, 0x000000FF);
+    }
+
+    #[test]
+    fn struct_period_functions() {
+        let mut val = Period::default();
+        val = val.set_entire_cycle(0x1234);
+        assert_eq!(val.entire_cycle(), 0x1234);
+        assert_eq!(val.0, 0x12340000);
+        val = val.set_active_cycle(0x5678);
+        assert_eq!(val.active_cycle(), 0x5678);
+        assert_eq!(val.0, 0x12345678);
+    }
+}
@@ -0,0 +1,83 @@
+use super::{FrequencyError, Output, PwmChannel};
+use crate::time::{Duration, duration_to_micros};
+use embedded_hal::pwm::SetDutyCycle;
+use embedded_time::rate::Hertz;
+
+/// Buzzer driven by a PWM channel, timed by a delay provider.
+///
+/// Tones are square waves of 50% duty cycle; a frequency of zero plays silence, which
+/// is useful for rests between notes.
+pub struct Tone<'a, const I: usize, PAD: Output<I>, D> {
+    channel: PwmChannel<'a, I, PAD>,
+    delay: D,
+}
+
+impl<'a, const I: usize, PAD: Output<I>, D> Tone<'a, I, PAD, D> {
+    /// Create a silent buzzer on PWM `channel`, timing tones with `delay`.
+    #[inline]
+    pub fn new(mut channel: PwmChannel<'a, I, PAD>, delay: D) -> Self {
+        let _ = channel.set_duty_cycle_fully_off();
+        channel.enable();
+        Self { channel, delay }
+    }
+    /// Start sounding `frequency` until [`Tone::stop`] is called.
+    ///
+    /// Returns [`FrequencyError::OutOfRange`] if PWM channel cannot generate `frequency`;
+    /// the buzzer is silenced then.
+    #[inline]
+    pub fn start(&mut self, frequency: Hertz) -> Result<(), FrequencyError> {
+        if frequency.0 == 0 {
+            self.stop();
+            return Ok(());
+        }
+        if let Err(e) = self.channel.set_frequency(frequency) {
+            self.stop();
+            return Err(e);
+        }
+        self.channel.set_duty_cycle_percent(50)?;
+        Ok(())
+    }
+    /// Silence the buzzer.
+    #[inline]
+    pub fn stop(&mut self) {
+        let _ = self.channel.set_duty_cycle_fully_off();
+    }
+    /// Silence the buzzer and release PWM channel and delay provider.
+    #[inline]
+    pub fn free(mut self) -> (PwmChannel<'a, I, PAD>, D) {
+        self.stop();
+        (self.channel, self.delay)
+    }
+}
+
+impl<'a, const I: usize, PAD: Output<I>, D: embedded_hal::delay::DelayNs> Tone<'a, I, PAD, D> {
+    /// Sound `frequency` for `duration`, blocking until it ends.
+    ///
+    /// Returns error from [`Tone::start`] without waiting if `frequency` can't be sounded.
+    #[inline]
+    pub fn play(&mut self, frequency: Hertz, duration: Duration) -> Result<(), FrequencyError> {
+        self.start(frequency)?;
+        self.delay.delay_us(duration_to_micros(duration));
+        self.stop();
+        Ok(())
+    }
+}
+
+impl<'a, const I: usize, PAD: Output<I>, D: embedded_hal_async::delay::DelayNs>
+    Tone<'a, I, PAD, D>
+{
+    /// Sound `frequency` for `duration`, waiting asynchronously until it ends.
+    ///
+    /// Returns error from [`Tone::start`] without waiting if `frequency` can't be sounded.
+    #[inline]
+    pub async fn play_async(
+        &mut self,
+        frequency: Hertz,
+        duration: Duration,
+    ) -> Result<(), FrequencyError> {
+        self.start(frequency)?;
+        self.delay.delay_us(duration_to_micros(duration)).await;
+        self.stop();
+        Ok(())
+    }
+}
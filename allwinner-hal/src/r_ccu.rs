@@ -0,0 +1,162 @@
+//! Clock control unit of CPUS power domain (R_CCU).
+//!
+//! R_CCU controls clocks of peripherals in always-on power domain, such as IR receiver.
+
+use volatile_register::RW;
+
+/// R_CCU registers.
+#[repr(C)]
+pub struct RegisterBlock {
+    _reserved0: [u32; 112],
+    /// 0x1c0 - IR RX Clock register.
+    pub ir_rx_clock: RW<IrRxClock>,
+    _reserved1: [u32; 2],
+    /// 0x1cc - IR RX Bus Gating Reset register.
+    pub ir_rx_bgr: RW<IrRxBusGating>,
+}
+
+/// IR RX clock source.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum IrRxClockSource {
+    /// 32-KHz 'LOSC' oscillator.
+    Losc = 0,
+    /// 24-MHz 'HOSC' external oscillator.
+    Hosc = 1,
+}
+
+/// IR RX Clock register.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct IrRxClock(u32);
+
+impl IrRxClock {
+    const IR_RX_CLK_GATING: u32 = 0x1 << 31;
+    const CLK_SRC_SEL: u32 = 0x3 << 24;
+    const FACTOR_N: u32 = 0x3 << 8;
+    const FACTOR_M: u32 = 0x1f;
+
+    /// If clock is unmasked.
+    #[inline]
+    pub const fn is_clock_unmasked(self) -> bool {
+        self.0 & Self::IR_RX_CLK_GATING != 0
+    }
+    /// Unmask (enable) clock.
+    #[inline]
+    pub const fn unmask_clock(self) -> Self {
+        Self(self.0 | Self::IR_RX_CLK_GATING)
+    }
+    /// Mask (disable) clock.
+    #[inline]
+    pub const fn mask_clock(self) -> Self {
+        Self(self.0 & !Self::IR_RX_CLK_GATING)
+    }
+    /// Get IR RX clock source.
+    #[inline]
+    pub const fn clock_source(self) -> IrRxClockSource {
+        match (self.0 & Self::CLK_SRC_SEL) >> 24 {
+            0x0 => IrRxClockSource::Losc,
+            0x1 => IrRxClockSource::Hosc,
+            _ => panic!("impossible clock source"),
+        }
+    }
+    /// Set IR RX clock source.
+    #[inline]
+    pub const fn set_clock_source(self, val: IrRxClockSource) -> Self {
+        Self((self.0 & !Self::CLK_SRC_SEL) | ((val as u32) << 24))
+    }
+    /// Get IR RX clock divide factor N, where clock is divided by `2^N`.
+    #[inline]
+    pub const fn factor_n(self) -> u8 {
+        ((self.0 & Self::FACTOR_N) >> 8) as u8
+    }
+    /// Set IR RX clock divide factor N, where clock is divided by `2^N`.
+    #[inline]
+    pub const fn set_factor_n(self, val: u8) -> Self {
+        Self((self.0 & !Self::FACTOR_N) | ((val as u32 & 0x3) << 8))
+    }
+    /// Get IR RX clock divide factor M, where clock is divided by `M + 1`.
+    #[inline]
+    pub const fn factor_m(self) -> u8 {
+        (self.0 & Self::FACTOR_M) as u8
+    }
+    /// Set IR RX clock divide factor M, where clock is divided by `M + 1`.
+    #[inline]
+    pub const fn set_factor_m(self, val: u8) -> Self {
+        Self((self.0 & !Self::FACTOR_M) | (val as u32 & 0x1f))
+    }
+}
+
+impl Default for IrRxClock {
+    #[inline]
+    fn default() -> Self {
+        Self(0x0000_0000)
+    }
+}
+
+/// IR RX Bus Gating Reset register.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct IrRxBusGating(u32);
+
+impl IrRxBusGating {
+    const IR_RX_RST: u32 = 1 << 16;
+    const IR_RX_GATING: u32 = 1 << 0;
+
+    /// Assert IR RX reset.
+    #[inline]
+    pub const fn assert_reset(self) -> Self {
+        Self(self.0 & !Self::IR_RX_RST)
+    }
+    /// De-assert IR RX reset.
+    #[inline]
+    pub const fn deassert_reset(self) -> Self {
+        Self(self.0 | Self::IR_RX_RST)
+    }
+    /// Mask the IR RX gating.
+    #[inline]
+    pub const fn gate_mask(self) -> Self {
+        Self(self.0 & !Self::IR_RX_GATING)
+    }
+    /// Unmask (pass) the IR RX gating.
+    #[inline]
+    pub const fn gate_pass(self) -> Self {
+        Self(self.0 | Self::IR_RX_GATING)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{IrRxBusGating, IrRxClock, IrRxClockSource, RegisterBlock};
+    use core::mem::offset_of;
+
+    #[test]
+    fn offset_r_ccu() {
+        assert_eq!(offset_of!(RegisterBlock, ir_rx_clock), 0x1c0);
+        assert_eq!(offset_of!(RegisterBlock, ir_rx_bgr), 0x1cc);
+    }
+
+    #[test]
+    fn struct_ir_rx_clock_functions() {
+        let mut val = IrRxClock::default();
+        val = val.unmask_clock();
+        assert!(val.is_clock_unmasked());
+        assert_eq!(val.0, 0x80000000);
+        val = val.mask_clock();
+        assert_eq!(val.0, 0x00000000);
+
+        val = val.set_clock_source(IrRxClockSource::Hosc);
+        assert_eq!(val.clock_source(), IrRxClockSource::Hosc);
+        assert_eq!(val.0, 0x01000000);
+
+        val = IrRxClock::default().set_factor_n(3).set_factor_m(0x1f);
+        assert_eq!(val.factor_n(), 3);
+        assert_eq!(val.factor_m(), 0x1f);
+        assert_eq!(val.0, 0x0000031f);
+
+        let mut val = IrRxBusGating(0x0);
+        val = val.deassert_reset().gate_pass();
+        assert_eq!(val.0, 0x00010001);
+        val = val.assert_reset().gate_mask();
+        assert_eq!(val.0, 0x00000000);
+    }
+}
@@ -0,0 +1,476 @@
+//! Real-time clock.
+//!
+//! RTC on D1-like chips counts days since 1970-01-01 in a 16-bit register and time of day
+//! in an hour-minute-second register; calendar dates are converted in software, supporting
+//! years 1970 to 2149.
+
+mod register;
+pub use register::*;
+
+/// Alarm 0 bit in enable, IRQ enable and IRQ status registers.
+const ALARM0: u32 = 1 << 0;
+
+/// First year representable by RTC day counter.
+pub const EPOCH_YEAR: u16 = 1970;
+
+/// Calendar date and time of day.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct DateTime {
+    /// Year, 1970 to 2149.
+    pub year: u16,
+    /// Month, 1 to 12.
+    pub month: u8,
+    /// Day of month, 1 to 31.
+    pub day: u8,
+    /// Hour, 0 to 23.
+    pub hour: u8,
+    /// Minute, 0 to 59.
+    pub minute: u8,
+    /// Second, 0 to 59.
+    pub second: u8,
+}
+
+/// RTC error.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Error {
+    /// Date or time is out of range of the calendar or RTC day counter.
+    InvalidDateTime,
+}
+
+impl DateTime {
+    /// Create date time from days since 1970-01-01 and time of day.
+    #[inline]
+    pub const fn from_days(days: u16, hh_mm_ss: HhMmSs) -> Self {
+        let (year, month, day) = civil_from_days(days as u32);
+        Self {
+            year,
+            month,
+            day,
+            hour: hh_mm_ss.hour(),
+            minute: hh_mm_ss.minute(),
+            second: hh_mm_ss.second(),
+        }
+    }
+    /// Get days since 1970-01-01 of this date.
+    #[inline]
+    pub const fn days(&self) -> Result<u16, Error> {
+        if !self.is_valid() {
+            return Err(Error::InvalidDateTime);
+        }
+        let days = days_from_civil(self.year, self.month, self.day);
+        if days < 0 || days > u16::MAX as i32 {
+            return Err(Error::InvalidDateTime);
+        }
+        Ok(days as u16)
+    }
+    /// Get time of day in RTC register format.
+    #[inline]
+    pub fn hh_mm_ss(&self) -> HhMmSs {
+        HhMmSs::default()
+            .set_hour(self.hour)
+            .set_minute(self.minute)
+            .set_second(self.second)
+    }
+    /// Get day of week, 0 for Sunday to 6 for Saturday.
+    ///
+    /// Any year of proleptic Gregorian calendar is accepted, including years before 1970.
+    #[inline]
+    pub const fn weekday(&self) -> u8 {
+        // 1970-01-01 is Thursday.
+        (days_from_civil(self.year, self.month, self.day) + 4).rem_euclid(7) as u8
+    }
+    /// Check if all fields are in range.
+    #[inline]
+    pub const fn is_valid(&self) -> bool {
+        self.year >= EPOCH_YEAR
+            && self.month >= 1
+            && self.month <= 12
+            && self.day >= 1
+            && self.day <= days_in_month(self.year, self.month)
+            && self.hour < 24
+            && self.minute < 60
+            && self.second < 60
+    }
+}
+
+/// Check if `year` is a leap year in Gregorian calendar.
+#[inline]
+pub const fn is_leap_year(year: u16) -> bool {
+    (year.is_multiple_of(4) && !year.is_multiple_of(100)) || year.is_multiple_of(400)
+}
+
+/// Get number of days in `month` of `year`.
+#[inline]
+pub const fn days_in_month(year: u16, month: u8) -> u8 {
+    match month {
+        1 | 3 | 5 | 7 | 8 | 10 | 12 => 31,
+        4 | 6 | 9 | 11 => 30,
+        2 if is_leap_year(year) => 29,
+        2 => 28,
+        _ => 0,
+    }
+}
+
+/// Days since 1970-01-01 of a date, negative for dates before 1970.
+#[inline]
+const fn days_from_civil(year: u16, month: u8, day: u8) -> i32 {
+    // Count years from March so that leap day falls at end of year.
+    let year = year as i32 - if month <= 2 { 1 } else { 0 };
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let month = month as i32;
+    let day_of_year =
+        (153 * (if month > 2 { month - 3 } else { month + 9 }) + 2) / 5 + day as i32 - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    // 719468 days from 0000-03-01 to 1970-01-01.
+    era * 146097 + day_of_era - 719468
+}
+
+/// Date of days since 1970-01-01.
+#[inline]
+const fn civil_from_days(days: u32) -> (u16, u8, u8) {
+    let days = days + 719468;
+    let era = days / 146097;
+    let day_of_era = days - era * 146097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let mp = (5 * day_of_year + 2) / 153;
+    let day = (day_of_year - (153 * mp + 2) / 5 + 1) as u8;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 } as u8;
+    let year = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
+    (year as u16, month, day)
+}
+
+/// Index of general purpose data register holding reboot reason.
+///
+/// The last register is used, as vendor bootloaders may use lower ones for boot flags.
+pub const REBOOT_REASON_INDEX: usize = GP_DATA_COUNT - 1;
+
+/// Reason of last reboot, passed across warm reset in a general purpose data register.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum RebootReason {
+    /// Requested reboot.
+    Normal,
+    /// Reboot into bootloader, e.g. for firmware update.
+    Bootloader,
+    /// Reboot into recovery firmware.
+    Recovery,
+    /// Reboot after panic.
+    Panic,
+    /// Reboot after watchdog timeout.
+    Watchdog,
+    /// Application defined reason.
+    Other(u8),
+}
+
+impl RebootReason {
+    /// Marks register value as a reboot reason, 'RB' in ASCII.
+    const MAGIC: u32 = 0x5242_0000;
+    const MAGIC_MASK: u32 = 0xFFFF_0000;
+    const KIND: u32 = 0xFF << 8;
+    const CODE: u32 = 0xFF;
+    /// Marks that firmware is running and no reboot reason is recorded yet.
+    pub(crate) const RUNNING: u32 = Self::MAGIC | (0xFE << 8);
+
+    /// Encode reboot reason into register value.
+    #[inline]
+    pub const fn to_bits(self) -> u32 {
+        let (kind, code) = match self {
+            RebootReason::Normal => (0, 0),
+            RebootReason::Bootloader => (1, 0),
+            RebootReason::Recovery => (2, 0),
+            RebootReason::Panic => (3, 0),
+            RebootReason::Watchdog => (4, 0),
+            RebootReason::Other(code) => (0xFF, code as u32),
+        };
+        Self::MAGIC | (kind << 8) | code
+    }
+    /// Decode reboot reason from register value, or `None` if no reason was recorded.
+    #[inline]
+    pub const fn from_bits(bits: u32) -> Option<Self> {
+        if bits & Self::MAGIC_MASK != Self::MAGIC {
+            return None;
+        }
+        match (bits & Self::KIND) >> 8 {
+            0 => Some(RebootReason::Normal),
+            1 => Some(RebootReason::Bootloader),
+            2 => Some(RebootReason::Recovery),
+            3 => Some(RebootReason::Panic),
+            4 => Some(RebootReason::Watchdog),
+            0xFF => Some(RebootReason::Other((bits & Self::CODE) as u8)),
+            _ => None,
+        }
+    }
+}
+
+/// Managed real-time clock structure with peripheral.
+pub struct Rtc<RTC> {
+    rtc: RTC,
+}
+
+impl<RTC: AsRef<RegisterBlock>> Rtc<RTC> {
+    /// Create an RTC instance.
+    ///
+    /// RTC keeps counting across resets, thus current time and alarm are left unchanged.
+    #[inline]
+    pub fn new(rtc: RTC) -> Self {
+        Self { rtc }
+    }
+    /// Get current date and time.
+    #[inline]
+    pub fn date_time(&self) -> DateTime {
+        let rtc = self.rtc.as_ref();
+        // Read again if day changes between reads, e.g. at midnight.
+        loop {
+            let days = rtc.day.read();
+            let hh_mm_ss = rtc.hh_mm_ss.read();
+            if rtc.day.read() == days {
+                return DateTime::from_days(days as u16, hh_mm_ss);
+            }
+        }
+    }
+    /// Set current date and time.
+    #[inline]
+    pub fn set_date_time(&mut self, date_time: DateTime) -> Result<(), Error> {
+        let days = date_time.days()?;
+        let rtc = self.rtc.as_ref();
+        while rtc.losc_control.read().is_day_busy() {
+            core::hint::spin_loop();
+        }
+        unsafe { rtc.day.write(days as u32) };
+        while rtc.losc_control.read().is_day_busy() {
+            core::hint::spin_loop();
+        }
+        while rtc.losc_control.read().is_hh_mm_ss_busy() {
+            core::hint::spin_loop();
+        }
+        unsafe { rtc.hh_mm_ss.write(date_time.hh_mm_ss()) };
+        while rtc.losc_control.read().is_hh_mm_ss_busy() {
+            core::hint::spin_loop();
+        }
+        Ok(())
+    }
+    /// Set alarm 0 to fire at `date_time` and enable it.
+    #[inline]
+    pub fn set_alarm(&mut self, date_time: DateTime) -> Result<(), Error> {
+        let days = date_time.days()?;
+        let rtc = self.rtc.as_ref();
+        unsafe { rtc.alarm0_enable.write(0) };
+        while rtc.losc_control.read().is_alarm_busy() {
+            core::hint::spin_loop();
+        }
+        unsafe {
+            rtc.alarm0_day.write(days as u32);
+            rtc.alarm0_hh_mm_ss.write(date_time.hh_mm_ss());
+        }
+        while rtc.losc_control.read().is_alarm_busy() {
+            core::hint::spin_loop();
+        }
+        unsafe {
+            rtc.alarm0_irq_status.write(ALARM0);
+            rtc.alarm0_enable.write(ALARM0);
+        }
+        Ok(())
+    }
+    /// Get date and time alarm 0 is set to.
+    #[inline]
+    pub fn alarm(&self) -> DateTime {
+        let rtc = self.rtc.as_ref();
+        DateTime::from_days(rtc.alarm0_day.read() as u16, rtc.alarm0_hh_mm_ss.read())
+    }
+    /// Disable alarm 0.
+    #[inline]
+    pub fn disable_alarm(&mut self) {
+        unsafe { self.rtc.as_ref().alarm0_enable.write(0) };
+    }
+    /// Check if alarm 0 is enabled.
+    #[inline]
+    pub fn is_alarm_enabled(&self) -> bool {
+        self.rtc.as_ref().alarm0_enable.read() & ALARM0 != 0
+    }
+    /// Enable interrupt on alarm 0.
+    #[inline]
+    pub fn enable_alarm_interrupt(&mut self) {
+        unsafe { self.rtc.as_ref().alarm0_irq_enable.write(ALARM0) };
+    }
+    /// Disable interrupt on alarm 0.
+    #[inline]
+    pub fn disable_alarm_interrupt(&mut self) {
+        unsafe { self.rtc.as_ref().alarm0_irq_enable.write(0) };
+    }
+    /// Check if alarm 0 has fired since it was last cleared.
+    #[inline]
+    pub fn is_alarm_pending(&self) -> bool {
+        self.rtc.as_ref().alarm0_irq_status.read() & ALARM0 != 0
+    }
+    /// Clear pending alarm 0.
+    #[inline]
+    pub fn clear_alarm(&mut self) {
+        unsafe { self.rtc.as_ref().alarm0_irq_status.write(ALARM0) };
+    }
+    /// Enable system wakeup on alarm.
+    #[inline]
+    pub fn enable_alarm_wakeup(&mut self) {
+        unsafe {
+            self.rtc
+                .as_ref()
+                .alarm_config
+                .modify(|val| val.enable_wakeup())
+        };
+    }
+    /// Disable system wakeup on alarm.
+    #[inline]
+    pub fn disable_alarm_wakeup(&mut self) {
+        unsafe {
+            self.rtc
+                .as_ref()
+                .alarm_config
+                .modify(|val| val.disable_wakeup())
+        };
+    }
+    /// Read general purpose data register `index`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is not less than [`GP_DATA_COUNT`].
+    #[inline]
+    pub fn gp_data(&self, index: usize) -> u32 {
+        self.rtc.as_ref().gp_data[index].read()
+    }
+    /// Write general purpose data register `index`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is not less than [`GP_DATA_COUNT`].
+    #[inline]
+    pub fn set_gp_data(&mut self, index: usize, val: u32) {
+        unsafe { self.rtc.as_ref().gp_data[index].write(val) };
+    }
+    /// Get reboot reason recorded before last warm reset.
+    #[inline]
+    pub fn reboot_reason(&self) -> Option<RebootReason> {
+        RebootReason::from_bits(self.gp_data(REBOOT_REASON_INDEX))
+    }
+    /// Record reboot reason for firmware after next warm reset.
+    #[inline]
+    pub fn set_reboot_reason(&mut self, reason: RebootReason) {
+        self.set_gp_data(REBOOT_REASON_INDEX, reason.to_bits());
+    }
+    /// Clear recorded reboot reason, usually after it's handled on boot.
+    #[inline]
+    pub fn clear_reboot_reason(&mut self) {
+        self.set_gp_data(REBOOT_REASON_INDEX, 0);
+    }
+    /// Release RTC peripheral.
+    #[inline]
+    pub fn free(self) -> RTC {
+        self.rtc
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        DateTime, Error, HhMmSs, RebootReason, civil_from_days, days_from_civil, days_in_month,
+        is_leap_year,
+    };
+
+    #[test]
+    fn function_leap_year() {
+        assert!(is_leap_year(2024));
+        assert!(!is_leap_year(2023));
+        assert!(!is_leap_year(2100));
+        assert!(is_leap_year(2000));
+        assert_eq!(days_in_month(2024, 2), 29);
+        assert_eq!(days_in_month(2100, 2), 28);
+        assert_eq!(days_in_month(2023, 4), 30);
+        assert_eq!(days_in_month(2023, 13), 0);
+    }
+
+    #[test]
+    fn function_civil_days() {
+        assert_eq!(days_from_civil(1970, 1, 1), 0);
+        assert_eq!(days_from_civil(2000, 3, 1), 11017);
+        assert_eq!(days_from_civil(2024, 2, 29), 19782);
+        assert_eq!(civil_from_days(0), (1970, 1, 1));
+        assert_eq!(civil_from_days(19782), (2024, 2, 29));
+        assert_eq!(civil_from_days(65535), (2149, 6, 6));
+        for days in (0..=65535).step_by(97) {
+            let (year, month, day) = civil_from_days(days);
+            assert_eq!(days_from_civil(year, month, day), days as i32);
+        }
+        assert_eq!(days_from_civil(1969, 12, 31), -1);
+        assert_eq!(days_from_civil(0, 1, 1), -719528);
+    }
+
+    #[test]
+    fn struct_date_time_functions() {
+        let date_time = DateTime {
+            year: 2024,
+            month: 2,
+            day: 29,
+            hour: 23,
+            minute: 59,
+            second: 58,
+        };
+        assert!(date_time.is_valid());
+        assert_eq!(date_time.days(), Ok(19782));
+        assert_eq!(date_time.weekday(), 4);
+        assert_eq!(
+            date_time.hh_mm_ss(),
+            HhMmSs::default().set_hour(23).set_minute(59).set_second(58)
+        );
+        assert_eq!(DateTime::from_days(19782, date_time.hh_mm_ss()), date_time);
+
+        let invalid = DateTime {
+            day: 30,
+            ..date_time
+        };
+        assert_eq!(invalid.days(), Err(Error::InvalidDateTime));
+        let invalid = DateTime {
+            year: 2150,
+            ..date_time
+        };
+        assert_eq!(invalid.days(), Err(Error::InvalidDateTime));
+        let before_epoch = DateTime {
+            year: 1969,
+            month: 12,
+            day: 31,
+            ..date_time
+        };
+        assert_eq!(before_epoch.days(), Err(Error::InvalidDateTime));
+        assert_eq!(before_epoch.weekday(), 3);
+        let year_zero = DateTime {
+            year: 0,
+            month: 1,
+            day: 1,
+            ..date_time
+        };
+        assert_eq!(year_zero.days(), Err(Error::InvalidDateTime));
+        assert_eq!(year_zero.weekday(), 6);
+        let invalid = DateTime {
+            hour: 24,
+            ..date_time
+        };
+        assert!(!invalid.is_valid());
+    }
+
+    #[test]
+    fn struct_reboot_reason_functions() {
+        for reason in [
+            RebootReason::Normal,
+            RebootReason::Bootloader,
+            RebootReason::Recovery,
+            RebootReason::Panic,
+            RebootReason::Watchdog,
+            RebootReason::Other(0x5A),
+        ] {
+            assert_eq!(RebootReason::from_bits(reason.to_bits()), Some(reason));
+        }
+        assert_eq!(RebootReason::Panic.to_bits(), 0x5242_0300);
+        assert_eq!(RebootReason::Other(0x5A).to_bits(), 0x5242_FF5A);
+        assert_eq!(RebootReason::from_bits(0x0000_0000), None);
+        assert_eq!(RebootReason::from_bits(0x5242_0500), None);
+    }
+}
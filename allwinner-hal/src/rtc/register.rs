@@ -0,0 +1,208 @@
+use volatile_register::RW;
+
+/// Number of general purpose data registers.
+pub const GP_DATA_COUNT: usize = 8;
+
+/// Real-time clock registers.
+#[repr(C)]
+pub struct RegisterBlock {
+    /// 0x00 - Low Oscillator Control Register.
+    pub losc_control: RW<LoscControl>,
+    /// 0x04 - Low Oscillator Auto Switch Status Register.
+    pub losc_auto_switch_status: RW<u32>,
+    /// 0x08 - Internal OSC Clock Pre-scaler Register.
+    pub intosc_prescale: RW<u32>,
+    _reserved0: [u32; 1],
+    /// 0x10 - RTC Day Register.
+    ///
+    /// Number of days since 1970-01-01, in bits 15:0.
+    pub day: RW<u32>,
+    /// 0x14 - RTC Hour-Minute-Second Register.
+    pub hh_mm_ss: RW<HhMmSs>,
+    _reserved1: [u32; 2],
+    /// 0x20 - Alarm 0 Day Setting Register.
+    pub alarm0_day: RW<u32>,
+    /// 0x24 - Alarm 0 Hour-Minute-Second Setting Register.
+    pub alarm0_hh_mm_ss: RW<HhMmSs>,
+    /// 0x28 - Alarm 0 Enable Register.
+    pub alarm0_enable: RW<u32>,
+    /// 0x2C - Alarm 0 IRQ Enable Register.
+    pub alarm0_irq_enable: RW<u32>,
+    /// 0x30 - Alarm 0 IRQ Status Register.
+    pub alarm0_irq_status: RW<u32>,
+    _reserved2: [u32; 7],
+    /// 0x50 - Alarm Configuration Register.
+    pub alarm_config: RW<AlarmConfig>,
+    _reserved3: [u32; 3],
+    /// 0x60 - 32K Fanout Control Gating Register.
+    pub fanout_gating: RW<u32>,
+    _reserved4: [u32; 39],
+    /// 0x100 ..= 0x11C - General Purpose Data Registers.
+    ///
+    /// Powered by RTC domain, thus kept across warm resets.
+    pub gp_data: [RW<u32>; GP_DATA_COUNT],
+}
+
+/// Low oscillator control register.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct LoscControl(u32);
+
+impl LoscControl {
+    const ALARM_ACCESS: u32 = 1 << 9;
+    const HH_MM_SS_ACCESS: u32 = 1 << 8;
+    const DAY_ACCESS: u32 = 1 << 7;
+
+    /// If a write to alarm 0 day or hour-minute-second registers is in progress.
+    #[inline]
+    pub const fn is_alarm_busy(self) -> bool {
+        self.0 & Self::ALARM_ACCESS != 0
+    }
+    /// If a write to hour-minute-second register is in progress.
+    #[inline]
+    pub const fn is_hh_mm_ss_busy(self) -> bool {
+        self.0 & Self::HH_MM_SS_ACCESS != 0
+    }
+    /// If a write to day register is in progress.
+    #[inline]
+    pub const fn is_day_busy(self) -> bool {
+        self.0 & Self::DAY_ACCESS != 0
+    }
+}
+
+/// Hour-minute-second register of time and alarm.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct HhMmSs(u32);
+
+impl HhMmSs {
+    const HOUR: u32 = 0x1F << 16;
+    const MINUTE: u32 = 0x3F << 8;
+    const SECOND: u32 = 0x3F;
+
+    /// Get hour, 0 to 23.
+    #[inline]
+    pub const fn hour(self) -> u8 {
+        ((self.0 & Self::HOUR) >> 16) as u8
+    }
+    /// Set hour.
+    #[inline]
+    pub const fn set_hour(self, val: u8) -> Self {
+        Self((self.0 & !Self::HOUR) | (((val as u32) << 16) & Self::HOUR))
+    }
+    /// Get minute, 0 to 59.
+    #[inline]
+    pub const fn minute(self) -> u8 {
+        ((self.0 & Self::MINUTE) >> 8) as u8
+    }
+    /// Set minute.
+    #[inline]
+    pub const fn set_minute(self, val: u8) -> Self {
+        Self((self.0 & !Self::MINUTE) | (((val as u32) << 8) & Self::MINUTE))
+    }
+    /// Get second, 0 to 59.
+    #[inline]
+    pub const fn second(self) -> u8 {
+        (self.0 & Self::SECOND) as u8
+    }
+    /// Set second.
+    #[inline]
+    pub const fn set_second(self, val: u8) -> Self {
+        Self((self.0 & !Self::SECOND) | (val as u32 & Self::SECOND))
+    }
+}
+
+impl Default for HhMmSs {
+    #[inline]
+    fn default() -> Self {
+        Self(0x0000_0000)
+    }
+}
+
+/// Alarm configuration register.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct AlarmConfig(u32);
+
+impl AlarmConfig {
+    const ALARM_WAKEUP: u32 = 1 << 0;
+
+    /// If alarm output wakes up the system by driving NMI/power key signal.
+    #[inline]
+    pub const fn is_wakeup_enabled(self) -> bool {
+        self.0 & Self::ALARM_WAKEUP != 0
+    }
+    /// Enable system wakeup on alarm.
+    #[inline]
+    pub const fn enable_wakeup(self) -> Self {
+        Self(self.0 | Self::ALARM_WAKEUP)
+    }
+    /// Disable system wakeup on alarm.
+    #[inline]
+    pub const fn disable_wakeup(self) -> Self {
+        Self(self.0 & !Self::ALARM_WAKEUP)
+    }
+}
+
+impl Default for AlarmConfig {
+    #[inline]
+    fn default() -> Self {
+        Self(0x0000_0000)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{AlarmConfig, HhMmSs, LoscControl, RegisterBlock};
+    use core::mem::offset_of;
+
+    #[test]
+    fn offset_rtc() {
+        assert_eq!(offset_of!(RegisterBlock, losc_control), 0x00);
+        assert_eq!(offset_of!(RegisterBlock, intosc_prescale), 0x08);
+        assert_eq!(offset_of!(RegisterBlock, day), 0x10);
+        assert_eq!(offset_of!(RegisterBlock, hh_mm_ss), 0x14);
+        assert_eq!(offset_of!(RegisterBlock, alarm0_day), 0x20);
+        assert_eq!(offset_of!(RegisterBlock, alarm0_hh_mm_ss), 0x24);
+        assert_eq!(offset_of!(RegisterBlock, alarm0_enable), 0x28);
+        assert_eq!(offset_of!(RegisterBlock, alarm0_irq_enable), 0x2C);
+        assert_eq!(offset_of!(RegisterBlock, alarm0_irq_status), 0x30);
+        assert_eq!(offset_of!(RegisterBlock, alarm_config), 0x50);
+        assert_eq!(offset_of!(RegisterBlock, fanout_gating), 0x60);
+        assert_eq!(offset_of!(RegisterBlock, gp_data), 0x100);
+    }
+
+    #[test]
+    fn struct_losc_control_functions() {
+        let val = LoscControl(0x0000_0380);
+        assert!(val.is_alarm_busy());
+        assert!(val.is_hh_mm_ss_busy());
+        assert!(val.is_day_busy());
+        let val = LoscControl(0x0000_4010);
+        assert!(!val.is_alarm_busy());
+        assert!(!val.is_hh_mm_ss_busy());
+        assert!(!val.is_day_busy());
+    }
+
+    #[test]
+    fn struct_hh_mm_ss_functions() {
+        let val = HhMmSs::default().set_hour(23).set_minute(59).set_second(58);
+        assert_eq!(val.0, 0x0017_3B3A);
+        assert_eq!(val.hour(), 23);
+        assert_eq!(val.minute(), 59);
+        assert_eq!(val.second(), 58);
+        let val = val.set_hour(7);
+        assert_eq!(val.0, 0x0007_3B3A);
+    }
+
+    #[test]
+    fn struct_alarm_config_functions() {
+        let mut val = AlarmConfig::default();
+        val = val.enable_wakeup();
+        assert!(val.is_wakeup_enabled());
+        assert_eq!(val.0, 0x00000001);
+        val = val.disable_wakeup();
+        assert!(!val.is_wakeup_enabled());
+        assert_eq!(val.0, 0x00000000);
+    }
+}
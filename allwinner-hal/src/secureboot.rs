@@ -0,0 +1,138 @@
+//! Secure boot image verification.
+//!
+//! Boot ROM of a chip in secure mode loads a TOC0 image, which holds the first stage firmware
+//! with an X.509 certificate. The certificate carries the RSA public key trusted by the hash
+//! in eFuse, and the SHA-256 hash of the firmware. [`Toc0::verify`] repeats these checks with
+//! crypto engine and SID, so that a Rust bootloader can verify images the same way:
+//!
+//! ```ignore
+//! let toc0 = Toc0::parse(image)?;
+//! let verified = toc0.verify(&mut ce, &sid)?;
+//! copy_and_jump(verified.load_address, verified.data);
+//! ```
+//!
+//! Later stages are packed in a TOC1 image, where each binary item has a certificate item of
+//! the same name. [`Toc1::verify`] checks an item against a key verified earlier, such as the
+//! key of the TOC0 certificate, to chain-load verified payloads:
+//!
+//! ```ignore
+//! let toc1 = Toc1::parse(package)?;
+//! let opensbi = toc1.verify(&mut ce, &verified.key, b"opensbi")?;
+//! ```
+//!
+//! Certificates are signed with SHA-256 and RSA PKCS #1 v1.5. Images are hashed by crypto
+//! engine DMA, and should reside in DRAM.
+
+mod certificate;
+mod der;
+mod toc0;
+mod toc1;
+pub use certificate::*;
+pub use toc0::*;
+pub use toc1::*;
+
+use crate::ce::{self, Ce, HashAlgorithm};
+use crate::sid;
+
+/// Magic number of TOC0 and TOC1 headers.
+const TOC_MAGIC: u32 = 0x8911_9800;
+
+/// Value in checksum field while the checksum is computed.
+const CHECKSUM_STAMP: u32 = 0x5F0A_6C39;
+
+/// Secure boot verification error.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Error {
+    /// Image header or item table is malformed.
+    InvalidHeader,
+    /// Image checksum doesn't match its contents.
+    Checksum,
+    /// Certificate is not valid DER encoded X.509.
+    InvalidCertificate,
+    /// Image lacks an item needed for verification.
+    MissingItem,
+    /// Image uses an algorithm or feature not supported, such as encrypted items.
+    Unsupported,
+    /// Public key doesn't match root of trust public key hash in eFuse.
+    UntrustedKey,
+    /// Payload hash differs from the hash in its certificate.
+    HashMismatch,
+    /// Crypto engine failed, or signature doesn't match.
+    Crypto(ce::Error),
+    /// Reading eFuse failed.
+    Efuse(sid::Error),
+}
+
+/// Payload that passed verification.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Verified<'a> {
+    /// Payload contents.
+    pub data: &'a [u8],
+    /// Address the payload is to be loaded at and run from.
+    pub load_address: u32,
+    /// Public key of payload certificate, which verifies later stages.
+    pub key: RsaPublicKey<'a>,
+}
+
+/// Read little-endian word at byte `offset` of `image`.
+#[inline]
+fn read_u32(image: &[u8], offset: usize) -> u32 {
+    let bytes = &image[offset..offset + 4];
+    u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]])
+}
+
+/// Sum of little-endian words of `image`, with the word at byte `field` taken as stamp value.
+#[inline]
+fn checksum(image: &[u8], field: usize) -> u32 {
+    image
+        .chunks_exact(4)
+        .enumerate()
+        .fold(0u32, |sum, (i, word)| {
+            let word = match i * 4 == field {
+                true => CHECKSUM_STAMP,
+                false => u32::from_le_bytes([word[0], word[1], word[2], word[3]]),
+            };
+            sum.wrapping_add(word)
+        })
+}
+
+/// Check that `data` hashes to payload hash in `certificate`.
+#[inline]
+fn check_payload<CE: AsRef<ce::RegisterBlock>>(
+    ce: &mut Ce<'_, CE>,
+    certificate: &Certificate,
+    data: &[u8],
+) -> Result<(), Error> {
+    let expected = certificate
+        .payload_hash()
+        .ok_or(Error::InvalidCertificate)?;
+    let mut digest = [0; 32];
+    ce.hash(HashAlgorithm::Sha256, data, &mut digest)
+        .map_err(Error::Crypto)?;
+    match &digest == expected {
+        true => Ok(()),
+        false => Err(Error::HashMismatch),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{CHECKSUM_STAMP, checksum, read_u32};
+
+    #[test]
+    fn function_checksum() {
+        let mut image = [0u8; 16];
+        image[0..4].copy_from_slice(&1u32.to_le_bytes());
+        image[4..8].copy_from_slice(&0xFFFF_FFFFu32.to_le_bytes());
+        image[12..16].copy_from_slice(&0x1234u32.to_le_bytes());
+        assert_eq!(read_u32(&image, 12), 0x1234);
+        // field contents are replaced by stamp value
+        let sum = 1u32
+            .wrapping_add(0xFFFF_FFFF)
+            .wrapping_add(CHECKSUM_STAMP)
+            .wrapping_add(0x1234);
+        assert_eq!(checksum(&image, 8), sum);
+        image[8..12].copy_from_slice(&sum.to_le_bytes());
+        assert_eq!(checksum(&image, 8), sum);
+    }
+}
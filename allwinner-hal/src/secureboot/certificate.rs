@@ -0,0 +1,321 @@
+use super::{
+    Error,
+    der::{BIT_STRING, BOOLEAN, INTEGER, OBJECT_IDENTIFIER, OCTET_STRING, Reader, SEQUENCE},
+};
+use crate::ce::{self, Ce, HashAlgorithm, HashState};
+
+/// Object identifier of `rsaEncryption`, 1.2.840.113549.1.1.1.
+const RSA_ENCRYPTION: &[u8] = &[0x2A, 0x86, 0x48, 0x86, 0xF7, 0x0D, 0x01, 0x01, 0x01];
+
+/// Object identifier of `sha256WithRSAEncryption`, 1.2.840.113549.1.1.11.
+const SHA256_WITH_RSA_ENCRYPTION: &[u8] = &[0x2A, 0x86, 0x48, 0x86, 0xF7, 0x0D, 0x01, 0x01, 0x0B];
+
+/// Object identifier of extension holding payload SHA-256 hash, 1.3.6.1.4.1.2011.2.310.1.1.
+pub const PAYLOAD_HASH_OID: &[u8] = &[
+    0x2B, 0x06, 0x01, 0x04, 0x01, 0x8F, 0x5B, 0x02, 0x82, 0x36, 0x01, 0x01,
+];
+
+/// Version field tag of TBS certificate.
+const VERSION: u8 = 0xA0;
+
+/// Extensions field tag of TBS certificate.
+const EXTENSIONS: u8 = 0xA3;
+
+/// RSA public key with big-endian integers.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct RsaPublicKey<'a> {
+    /// Modulus without leading zeros.
+    pub modulus: &'a [u8],
+    /// Public exponent without leading zeros.
+    pub exponent: &'a [u8],
+}
+
+impl RsaPublicKey<'_> {
+    /// Compute root of trust public key hash of this key, as programmed into eFuse.
+    ///
+    /// The hash is SHA-256 of the modulus followed by the exponent, zero-extended to the
+    /// modulus length.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidCertificate`] if exponent is longer than modulus, or
+    /// [`Error::Crypto`] if crypto engine fails.
+    #[inline]
+    pub fn rotpk_hash<CE: AsRef<ce::RegisterBlock>>(
+        &self,
+        ce: &mut Ce<'_, CE>,
+    ) -> Result<[u8; 32], Error> {
+        let mut padding = self
+            .modulus
+            .len()
+            .checked_sub(self.exponent.len())
+            .ok_or(Error::InvalidCertificate)?;
+        let mut state = HashState::new(HashAlgorithm::Sha256);
+        ce.hash_update(&mut state, self.modulus)
+            .map_err(Error::Crypto)?;
+        let zeros = [0; 64];
+        while padding > 0 {
+            let len = padding.min(zeros.len());
+            ce.hash_update(&mut state, &zeros[..len])
+                .map_err(Error::Crypto)?;
+            padding -= len;
+        }
+        ce.hash_update(&mut state, self.exponent)
+            .map_err(Error::Crypto)?;
+        let mut ans = [0; 32];
+        ce.hash_finalize(&mut state, &mut ans)
+            .map_err(Error::Crypto)?;
+        Ok(ans)
+    }
+}
+
+/// X.509 certificate of a boot image.
+///
+/// Only fields used in verification are decoded: the RSA subject public key, extensions and
+/// the SHA-256 with RSA signature.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Certificate<'a> {
+    tbs: &'a [u8],
+    public_key: RsaPublicKey<'a>,
+    extensions: &'a [u8],
+    signature: &'a [u8],
+}
+
+impl<'a> Certificate<'a> {
+    /// Decode certificate from DER encoded `der`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidCertificate`] if `der` is malformed, or [`Error::Unsupported`]
+    /// if certificate key or signature is not RSA with SHA-256.
+    #[inline]
+    pub fn parse(der: &'a [u8]) -> Result<Self, Error> {
+        let mut certificate = Reader::new(Reader::new(der).expect(SEQUENCE)?);
+        let tbs = certificate.read()?;
+        if tbs.tag != SEQUENCE {
+            return Err(Error::InvalidCertificate);
+        }
+        let mut algorithm = Reader::new(certificate.expect(SEQUENCE)?);
+        if algorithm.expect(OBJECT_IDENTIFIER)? != SHA256_WITH_RSA_ENCRYPTION {
+            return Err(Error::Unsupported);
+        }
+        let signature = bit_string(certificate.expect(BIT_STRING)?)?;
+
+        let mut fields = Reader::new(tbs.contents);
+        if fields.peek_tag() == Some(VERSION) {
+            fields.read()?;
+        }
+        fields.expect(INTEGER)?; // serial number
+        fields.expect(SEQUENCE)?; // signature algorithm
+        fields.expect(SEQUENCE)?; // issuer
+        fields.expect(SEQUENCE)?; // validity
+        fields.expect(SEQUENCE)?; // subject
+        let mut key_info = Reader::new(fields.expect(SEQUENCE)?);
+        let mut key_algorithm = Reader::new(key_info.expect(SEQUENCE)?);
+        if key_algorithm.expect(OBJECT_IDENTIFIER)? != RSA_ENCRYPTION {
+            return Err(Error::Unsupported);
+        }
+        let key = Reader::new(bit_string(key_info.expect(BIT_STRING)?)?).expect(SEQUENCE)?;
+        let mut key = Reader::new(key);
+        let public_key = RsaPublicKey {
+            modulus: unsigned(key.expect(INTEGER)?),
+            exponent: unsigned(key.expect(INTEGER)?),
+        };
+        let mut extensions: &[u8] = &[];
+        while !fields.is_empty() {
+            let field = fields.read()?;
+            if field.tag == EXTENSIONS {
+                extensions = Reader::new(field.contents).expect(SEQUENCE)?;
+            }
+        }
+        Ok(Self {
+            tbs: tbs.raw,
+            public_key,
+            extensions,
+            signature,
+        })
+    }
+    /// Get subject public key.
+    #[inline]
+    pub const fn public_key(&self) -> RsaPublicKey<'a> {
+        self.public_key
+    }
+    /// Get value of extension identified by DER encoded object identifier `oid`.
+    #[inline]
+    pub fn extension(&self, oid: &[u8]) -> Option<&'a [u8]> {
+        let mut extensions = Reader::new(self.extensions);
+        while !extensions.is_empty() {
+            let mut extension = Reader::new(extensions.expect(SEQUENCE).ok()?);
+            if extension.expect(OBJECT_IDENTIFIER).ok()? != oid {
+                continue;
+            }
+            if extension.peek_tag() == Some(BOOLEAN) {
+                extension.read().ok()?;
+            }
+            return extension.expect(OCTET_STRING).ok();
+        }
+        None
+    }
+    /// Get SHA-256 hash of payload signed by this certificate.
+    ///
+    /// Extension value is either the hash or a DER encoded octet string of the hash.
+    #[inline]
+    pub fn payload_hash(&self) -> Option<&'a [u8; 32]> {
+        let value = self.extension(PAYLOAD_HASH_OID)?;
+        let hash = match value.len() {
+            32 => value,
+            _ => Reader::new(value).expect(OCTET_STRING).ok()?,
+        };
+        hash.try_into().ok()
+    }
+    /// Verify certificate signature by `issuer` key.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Crypto`] holding [`ce::Error::InvalidSignature`] if signature does not
+    /// match.
+    #[inline]
+    pub fn verify<CE: AsRef<ce::RegisterBlock>>(
+        &self,
+        ce: &mut Ce<'_, CE>,
+        issuer: &RsaPublicKey,
+    ) -> Result<(), Error> {
+        let mut digest = [0; 32];
+        ce.hash(HashAlgorithm::Sha256, self.tbs, &mut digest)
+            .map_err(Error::Crypto)?;
+        ce.verify_rsa_pkcs1(
+            issuer.modulus,
+            issuer.exponent,
+            HashAlgorithm::Sha256,
+            &digest,
+            self.signature,
+        )
+        .map_err(Error::Crypto)
+    }
+}
+
+/// Get contents of a bit string with no unused bits.
+#[inline]
+fn bit_string(contents: &[u8]) -> Result<&[u8], Error> {
+    match contents.split_first() {
+        Some((0, bits)) => Ok(bits),
+        _ => Err(Error::InvalidCertificate),
+    }
+}
+
+/// Strip leading zeros of a non-negative integer.
+#[inline]
+fn unsigned(contents: &[u8]) -> &[u8] {
+    let zeros = contents.iter().take_while(|&&b| b == 0).count();
+    &contents[zeros..]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Certificate, Error, PAYLOAD_HASH_OID, RSA_ENCRYPTION, SHA256_WITH_RSA_ENCRYPTION};
+
+    struct Writer {
+        buf: [u8; 1024],
+        len: usize,
+    }
+
+    impl Writer {
+        fn new() -> Self {
+            Self {
+                buf: [0; 1024],
+                len: 0,
+            }
+        }
+        fn put(&mut self, tag: u8, contents: &[u8]) -> &mut Self {
+            let len = contents.len();
+            let header: &[u8] = match len {
+                0..0x80 => &[tag, len as u8],
+                0x80..0x100 => &[tag, 0x81, len as u8],
+                _ => &[tag, 0x82, (len >> 8) as u8, len as u8],
+            };
+            for part in [header, contents] {
+                self.buf[self.len..self.len + part.len()].copy_from_slice(part);
+                self.len += part.len();
+            }
+            self
+        }
+        fn bytes(&self) -> &[u8] {
+            &self.buf[..self.len]
+        }
+    }
+
+    #[test]
+    fn struct_certificate_functions() {
+        let mut modulus = [0x5A; 257];
+        modulus[0] = 0x00;
+        let mut key = Writer::new();
+        key.put(0x02, &modulus).put(0x02, &[0x01, 0x00, 0x01]);
+        let mut key_bits = Writer::new();
+        key_bits.put(0x30, key.bytes());
+        let mut bits = [0u8; 300];
+        bits[1..1 + key_bits.len].copy_from_slice(key_bits.bytes());
+        let mut key_algorithm = Writer::new();
+        key_algorithm.put(0x06, RSA_ENCRYPTION).put(0x05, &[]);
+        let mut key_info = Writer::new();
+        key_info
+            .put(0x30, key_algorithm.bytes())
+            .put(0x03, &bits[..1 + key_bits.len]);
+
+        let mut hash = Writer::new();
+        hash.put(0x04, &[0xA5; 32]);
+        let mut extension = Writer::new();
+        extension
+            .put(0x06, PAYLOAD_HASH_OID)
+            .put(0x01, &[0xFF])
+            .put(0x04, hash.bytes());
+        let mut extension_list = Writer::new();
+        extension_list.put(0x30, extension.bytes());
+        let mut extensions = Writer::new();
+        extensions.put(0x30, extension_list.bytes());
+
+        let mut algorithm = Writer::new();
+        algorithm
+            .put(0x06, SHA256_WITH_RSA_ENCRYPTION)
+            .put(0x05, &[]);
+        let mut tbs = Writer::new();
+        tbs.put(0xA0, &[0x02, 0x01, 0x02])
+            .put(0x02, &[0x01])
+            .put(0x30, algorithm.bytes())
+            .put(0x30, &[])
+            .put(0x30, &[])
+            .put(0x30, &[])
+            .put(0x30, key_info.bytes())
+            .put(0xA3, extensions.bytes());
+        let mut signature = [0x3C; 257];
+        signature[0] = 0x00;
+        let mut certificate = Writer::new();
+        certificate
+            .put(0x30, tbs.bytes())
+            .put(0x30, algorithm.bytes())
+            .put(0x03, &signature);
+        let mut der = Writer::new();
+        der.put(0x30, certificate.bytes());
+
+        let parsed = Certificate::parse(der.bytes()).unwrap();
+        assert_eq!(parsed.public_key().modulus, &modulus[1..]);
+        assert_eq!(parsed.public_key().exponent, &[0x01, 0x00, 0x01]);
+        assert_eq!(parsed.signature, &signature[1..]);
+        assert_eq!(parsed.tbs[..2], [0x30, 0x82]);
+        assert_eq!(parsed.payload_hash(), Some(&[0xA5; 32]));
+        assert_eq!(parsed.extension(RSA_ENCRYPTION), None);
+
+        // certificate signed with another algorithm
+        let mut other = der;
+        let position = other
+            .bytes()
+            .windows(SHA256_WITH_RSA_ENCRYPTION.len())
+            .rposition(|w| w == SHA256_WITH_RSA_ENCRYPTION)
+            .unwrap();
+        other.buf[position + SHA256_WITH_RSA_ENCRYPTION.len() - 1] = 0x05;
+        assert_eq!(Certificate::parse(other.bytes()), Err(Error::Unsupported));
+        assert_eq!(
+            Certificate::parse(&other.bytes()[..100]),
+            Err(Error::InvalidCertificate)
+        );
+    }
+}
@@ -0,0 +1,118 @@
+use super::Error;
+
+/// BOOLEAN tag.
+pub(super) const BOOLEAN: u8 = 0x01;
+/// INTEGER tag.
+pub(super) const INTEGER: u8 = 0x02;
+/// BIT STRING tag.
+pub(super) const BIT_STRING: u8 = 0x03;
+/// OCTET STRING tag.
+pub(super) const OCTET_STRING: u8 = 0x04;
+/// OBJECT IDENTIFIER tag.
+pub(super) const OBJECT_IDENTIFIER: u8 = 0x06;
+/// SEQUENCE tag.
+pub(super) const SEQUENCE: u8 = 0x30;
+
+/// DER encoded element.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(super) struct Element<'a> {
+    /// Identifier octet.
+    pub tag: u8,
+    /// Contents octets.
+    pub contents: &'a [u8],
+    /// Whole encoding with identifier and length octets.
+    pub raw: &'a [u8],
+}
+
+/// Reader of consecutive DER encoded elements.
+pub(super) struct Reader<'a>(&'a [u8]);
+
+impl<'a> Reader<'a> {
+    /// Create a reader of elements in `data`.
+    #[inline]
+    pub const fn new(data: &'a [u8]) -> Self {
+        Self(data)
+    }
+    /// Check if all elements have been read.
+    #[inline]
+    pub const fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+    /// Get tag of next element without reading it.
+    #[inline]
+    pub fn peek_tag(&self) -> Option<u8> {
+        self.0.first().copied()
+    }
+    /// Read next element.
+    ///
+    /// Supports single octet tags and lengths of up to 3 octets.
+    #[inline]
+    pub fn read(&mut self) -> Result<Element<'a>, Error> {
+        let data = self.0;
+        if data.len() < 2 || data[0] & 0x1F == 0x1F {
+            return Err(Error::InvalidCertificate);
+        }
+        let (len, header) = match data[1] {
+            len @ 0x00..=0x7F => (len as usize, 2),
+            0x81..=0x83 => {
+                let count = (data[1] & 0x7F) as usize;
+                let bytes = data.get(2..2 + count).ok_or(Error::InvalidCertificate)?;
+                let len = bytes.iter().fold(0, |len, &b| len << 8 | b as usize);
+                (len, 2 + count)
+            }
+            _ => return Err(Error::InvalidCertificate),
+        };
+        if data.len() - header < len {
+            return Err(Error::InvalidCertificate);
+        }
+        let (raw, rest) = data.split_at(header + len);
+        self.0 = rest;
+        Ok(Element {
+            tag: data[0],
+            contents: &raw[header..],
+            raw,
+        })
+    }
+    /// Read next element, which must have `tag`, and return its contents.
+    #[inline]
+    pub fn expect(&mut self, tag: u8) -> Result<&'a [u8], Error> {
+        match self.read()? {
+            element if element.tag == tag => Ok(element.contents),
+            _ => Err(Error::InvalidCertificate),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Error, INTEGER, OCTET_STRING, Reader, SEQUENCE};
+
+    #[test]
+    fn struct_reader_functions() {
+        let mut data = [0u8; 140];
+        data[..7].copy_from_slice(&[0x30, 0x05, 0x02, 0x01, 0x2A, 0x05, 0x00]);
+        data[7..10].copy_from_slice(&[0x04, 0x81, 0x80]);
+        let mut reader = Reader::new(&data[..138]);
+        assert_eq!(reader.peek_tag(), Some(SEQUENCE));
+        let sequence = reader.read().unwrap();
+        assert_eq!(sequence.raw, &data[..7]);
+        let mut inner = Reader::new(sequence.contents);
+        assert_eq!(inner.expect(INTEGER), Ok(&[0x2A][..]));
+        assert_eq!(inner.expect(INTEGER), Err(Error::InvalidCertificate));
+        assert_eq!(reader.expect(OCTET_STRING).map(|c| c.len()), Ok(0x80));
+        assert!(reader.is_empty());
+
+        // contents longer than data
+        let mut reader = Reader::new(&data[7..100]);
+        assert_eq!(reader.read(), Err(Error::InvalidCertificate));
+        // high tag numbers and indefinite length are not supported
+        assert_eq!(
+            Reader::new(&[0x1F, 0x01, 0x00]).read(),
+            Err(Error::InvalidCertificate)
+        );
+        assert_eq!(
+            Reader::new(&[0x30, 0x80, 0x00, 0x00]).read(),
+            Err(Error::InvalidCertificate)
+        );
+    }
+}
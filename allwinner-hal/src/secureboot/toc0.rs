@@ -0,0 +1,216 @@
+use super::{Certificate, Error, TOC_MAGIC, Verified, check_payload, checksum, read_u32};
+use crate::ce::{self, Ce};
+use crate::sid::{self, Sid};
+
+/// TOC0 header size in bytes.
+const HEADER_SIZE: usize = 48;
+
+/// TOC0 item information size in bytes.
+const ITEM_SIZE: usize = 32;
+
+/// Name at the start of TOC0 header.
+const HEADER_NAME: &[u8; 8] = b"TOC0.GLH";
+
+/// Marker at the end of TOC0 header.
+const HEADER_END: &[u8; 4] = b"MIE;";
+
+/// Marker at the end of TOC0 item information.
+const ITEM_END: &[u8; 4] = b"IIE;";
+
+/// Byte offset of checksum field in TOC0 header.
+const CHECKSUM_OFFSET: usize = 12;
+
+/// Name of TOC0 certificate item.
+pub const TOC0_CERTIFICATE: u32 = 0x0001_0101;
+
+/// Name of TOC0 firmware item.
+pub const TOC0_FIRMWARE: u32 = 0x0001_0202;
+
+/// Name of TOC0 key item.
+pub const TOC0_KEY: u32 = 0x0001_0303;
+
+/// Item of a TOC0 image.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Toc0Item<'a> {
+    /// Item name, such as [`TOC0_FIRMWARE`].
+    pub name: u32,
+    /// Item contents.
+    pub data: &'a [u8],
+    /// Address the item is to be loaded at.
+    pub load_address: u32,
+}
+
+/// TOC0 image loaded by boot ROM in secure mode.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Toc0<'a> {
+    image: &'a [u8],
+    item_count: usize,
+}
+
+impl<'a> Toc0<'a> {
+    /// Check TOC0 header, item table and checksum of `image`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidHeader`] if header or items are malformed or lie outside the
+    /// image, or [`Error::Checksum`] if checksum doesn't match.
+    #[inline]
+    pub fn parse(image: &'a [u8]) -> Result<Self, Error> {
+        if image.len() < HEADER_SIZE
+            || &image[..8] != HEADER_NAME
+            || read_u32(image, 8) != TOC_MAGIC
+            || &image[44..48] != HEADER_END
+        {
+            return Err(Error::InvalidHeader);
+        }
+        let item_count = read_u32(image, 24) as usize;
+        let length = read_u32(image, 28) as usize;
+        if length < HEADER_SIZE
+            || length > image.len()
+            || !length.is_multiple_of(4)
+            || item_count > (length - HEADER_SIZE) / ITEM_SIZE
+        {
+            return Err(Error::InvalidHeader);
+        }
+        let image = &image[..length];
+        if checksum(image, CHECKSUM_OFFSET) != read_u32(image, CHECKSUM_OFFSET) {
+            return Err(Error::Checksum);
+        }
+        for i in 0..item_count {
+            let info = &image[HEADER_SIZE + i * ITEM_SIZE..][..ITEM_SIZE];
+            let offset = read_u32(info, 4) as usize;
+            let len = read_u32(info, 8) as usize;
+            if &info[28..32] != ITEM_END || offset.checked_add(len).is_none_or(|end| end > length) {
+                return Err(Error::InvalidHeader);
+            }
+        }
+        Ok(Self { image, item_count })
+    }
+    /// Get items of the image.
+    #[inline]
+    pub fn items(&self) -> impl Iterator<Item = Toc0Item<'a>> + 'a {
+        let image = self.image;
+        (0..self.item_count).map(move |i| {
+            let info = &image[HEADER_SIZE + i * ITEM_SIZE..][..ITEM_SIZE];
+            let offset = read_u32(info, 4) as usize;
+            let len = read_u32(info, 8) as usize;
+            Toc0Item {
+                name: read_u32(info, 0),
+                data: &image[offset..offset + len],
+                load_address: read_u32(info, 20),
+            }
+        })
+    }
+    /// Get first item of `name`.
+    #[inline]
+    pub fn item(&self, name: u32) -> Option<Toc0Item<'a>> {
+        self.items().find(|item| item.name == name)
+    }
+    /// Verify firmware item against root of trust public key hash in eFuse.
+    ///
+    /// Public key of certificate item must hash to the eFuse value, certificate must be
+    /// signed by this key, and firmware must hash to the value in certificate.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::UntrustedKey`] if key hash differs or no hash is programmed,
+    /// [`Error::HashMismatch`] if firmware differs from its certificate, or
+    /// [`Error::Crypto`] if signature doesn't match. Images with a key item, whose
+    /// certificate is signed by a key other than root key, are [`Error::Unsupported`].
+    #[inline]
+    pub fn verify<CE: AsRef<ce::RegisterBlock>, SID: AsRef<sid::RegisterBlock>>(
+        &self,
+        ce: &mut Ce<'_, CE>,
+        sid: &Sid<SID>,
+    ) -> Result<Verified<'a>, Error> {
+        if self.item(TOC0_KEY).is_some() {
+            return Err(Error::Unsupported);
+        }
+        let certificate = self.item(TOC0_CERTIFICATE).ok_or(Error::MissingItem)?;
+        let firmware = self.item(TOC0_FIRMWARE).ok_or(Error::MissingItem)?;
+        let certificate = Certificate::parse(certificate.data)?;
+        let key = certificate.public_key();
+        let rotpk = sid.rotpk_hash().map_err(Error::Efuse)?;
+        if rotpk == [0; 32] || key.rotpk_hash(ce)? != rotpk {
+            return Err(Error::UntrustedKey);
+        }
+        certificate.verify(ce, &key)?;
+        check_payload(ce, &certificate, firmware.data)?;
+        Ok(Verified {
+            data: firmware.data,
+            load_address: firmware.load_address,
+            key,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        CHECKSUM_OFFSET, Error, HEADER_SIZE, ITEM_SIZE, TOC0_CERTIFICATE, TOC0_FIRMWARE, Toc0,
+        Toc0Item, checksum,
+    };
+
+    fn put(image: &mut [u8], offset: usize, value: u32) {
+        image[offset..offset + 4].copy_from_slice(&value.to_le_bytes());
+    }
+
+    #[test]
+    fn struct_toc0_functions() {
+        let mut image = [0u8; 192];
+        image[..8].copy_from_slice(b"TOC0.GLH");
+        put(&mut image, 8, 0x8911_9800);
+        put(&mut image, 24, 2);
+        put(&mut image, 28, 160);
+        image[44..48].copy_from_slice(b"MIE;");
+        for (i, (name, offset, len, load)) in [
+            (TOC0_CERTIFICATE, 112, 16, 0),
+            (TOC0_FIRMWARE, 128, 32, 0x0002_0000),
+        ]
+        .into_iter()
+        .enumerate()
+        {
+            let info = HEADER_SIZE + i * ITEM_SIZE;
+            put(&mut image, info, name);
+            put(&mut image, info + 4, offset);
+            put(&mut image, info + 8, len);
+            put(&mut image, info + 20, load);
+            image[info + 28..info + 32].copy_from_slice(b"IIE;");
+        }
+        image[128..160].fill(0xC3);
+        let sum = checksum(&image[..160], CHECKSUM_OFFSET);
+        put(&mut image, CHECKSUM_OFFSET, sum);
+
+        let toc0 = Toc0::parse(&image).unwrap();
+        assert_eq!(toc0.items().count(), 2);
+        assert_eq!(
+            toc0.item(TOC0_FIRMWARE),
+            Some(Toc0Item {
+                name: TOC0_FIRMWARE,
+                data: &[0xC3; 32],
+                load_address: 0x0002_0000,
+            })
+        );
+        assert_eq!(toc0.item(TOC0_CERTIFICATE).map(|i| i.data.len()), Some(16));
+
+        let mut corrupted = image;
+        corrupted[140] ^= 0x01;
+        assert_eq!(Toc0::parse(&corrupted), Err(Error::Checksum));
+        // bytes beyond image length are not covered
+        let mut padded = image;
+        padded[180] = 0xFF;
+        assert!(Toc0::parse(&padded).is_ok());
+        assert_eq!(Toc0::parse(&image[..150]), Err(Error::InvalidHeader));
+        let mut outside = image;
+        put(&mut outside, HEADER_SIZE + ITEM_SIZE + 8, 64);
+        let sum = checksum(&outside[..160], CHECKSUM_OFFSET);
+        put(&mut outside, CHECKSUM_OFFSET, sum);
+        assert_eq!(Toc0::parse(&outside), Err(Error::InvalidHeader));
+        for length in [0, HEADER_SIZE as u32 - 4] {
+            let mut short = image;
+            put(&mut short, 24, 0);
+            put(&mut short, 28, length);
+            assert_eq!(Toc0::parse(&short), Err(Error::InvalidHeader));
+        }
+    }
+}
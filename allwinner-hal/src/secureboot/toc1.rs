@@ -0,0 +1,230 @@
+use super::{
+    Certificate, Error, RsaPublicKey, TOC_MAGIC, Verified, check_payload, checksum, read_u32,
+};
+use crate::ce::{self, Ce};
+
+/// TOC1 header size in bytes.
+const HEADER_SIZE: usize = 64;
+
+/// TOC1 item information size in bytes.
+const ITEM_SIZE: usize = 368;
+
+/// Item name field size in bytes.
+const NAME_SIZE: usize = 64;
+
+/// Marker at the end of TOC1 header.
+const HEADER_END: &[u8; 4] = b"IHE;";
+
+/// Marker at the end of TOC1 item information.
+const ITEM_END: &[u8; 4] = b"IIE;";
+
+/// Byte offset of checksum field in TOC1 header.
+const CHECKSUM_OFFSET: usize = 20;
+
+/// Kind of TOC1 item.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Toc1ItemKind {
+    /// Certificate of keys signing other certificates.
+    KeyCertificate,
+    /// Certificate of the binary item of the same name.
+    Certificate,
+    /// Firmware binary.
+    Binary,
+    /// Item kind unknown to this module.
+    Other(u32),
+}
+
+impl Toc1ItemKind {
+    /// Decode item kind from its raw value.
+    #[inline]
+    pub const fn from_raw(raw: u32) -> Self {
+        match raw {
+            1 => Self::KeyCertificate,
+            2 => Self::Certificate,
+            3 => Self::Binary,
+            raw => Self::Other(raw),
+        }
+    }
+}
+
+/// Item of a TOC1 image.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Toc1Item<'a> {
+    /// Item name without trailing zeros, such as `b"opensbi"`.
+    pub name: &'a [u8],
+    /// Item kind.
+    pub kind: Toc1ItemKind,
+    /// Item contents.
+    pub data: &'a [u8],
+    /// Address the item is to be loaded at and run from.
+    pub run_address: u32,
+    /// Item version, for rollback protection.
+    pub version: u32,
+    /// Whether item contents are encrypted.
+    pub encrypted: bool,
+}
+
+/// TOC1 image packing later boot stages.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Toc1<'a> {
+    image: &'a [u8],
+    item_count: usize,
+}
+
+impl<'a> Toc1<'a> {
+    /// Check TOC1 header, item table and checksum of `image`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidHeader`] if header or items are malformed or lie outside the
+    /// image, or [`Error::Checksum`] if checksum doesn't match.
+    #[inline]
+    pub fn parse(image: &'a [u8]) -> Result<Self, Error> {
+        if image.len() < HEADER_SIZE
+            || read_u32(image, 16) != TOC_MAGIC
+            || &image[60..64] != HEADER_END
+        {
+            return Err(Error::InvalidHeader);
+        }
+        let item_count = read_u32(image, 32) as usize;
+        let length = read_u32(image, 36) as usize;
+        if length < HEADER_SIZE
+            || length > image.len()
+            || !length.is_multiple_of(4)
+            || item_count > (length - HEADER_SIZE) / ITEM_SIZE
+        {
+            return Err(Error::InvalidHeader);
+        }
+        let image = &image[..length];
+        if checksum(image, CHECKSUM_OFFSET) != read_u32(image, CHECKSUM_OFFSET) {
+            return Err(Error::Checksum);
+        }
+        for i in 0..item_count {
+            let info = &image[HEADER_SIZE + i * ITEM_SIZE..][..ITEM_SIZE];
+            let offset = read_u32(info, 64) as usize;
+            let len = read_u32(info, 68) as usize;
+            if &info[364..368] != ITEM_END || offset.checked_add(len).is_none_or(|end| end > length)
+            {
+                return Err(Error::InvalidHeader);
+            }
+        }
+        Ok(Self { image, item_count })
+    }
+    /// Get items of the image.
+    #[inline]
+    pub fn items(&self) -> impl Iterator<Item = Toc1Item<'a>> + 'a {
+        let image = self.image;
+        (0..self.item_count).map(move |i| {
+            let info = &image[HEADER_SIZE + i * ITEM_SIZE..][..ITEM_SIZE];
+            let name = &info[..NAME_SIZE];
+            let name_len = name.iter().position(|&b| b == 0).unwrap_or(NAME_SIZE);
+            let offset = read_u32(info, 64) as usize;
+            let len = read_u32(info, 68) as usize;
+            Toc1Item {
+                name: &name[..name_len],
+                kind: Toc1ItemKind::from_raw(read_u32(info, 76)),
+                data: &image[offset..offset + len],
+                run_address: read_u32(info, 80),
+                version: read_u32(info, 84),
+                encrypted: read_u32(info, 72) != 0,
+            }
+        })
+    }
+    /// Get first item of `name` and `kind`.
+    #[inline]
+    pub fn item(&self, name: &[u8], kind: Toc1ItemKind) -> Option<Toc1Item<'a>> {
+        self.items()
+            .find(|item| item.name == name && item.kind == kind)
+    }
+    /// Verify binary item `name` with its certificate signed by trusted `key`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::MissingItem`] if binary or certificate is absent,
+    /// [`Error::HashMismatch`] if binary differs from its certificate, or [`Error::Crypto`]
+    /// if signature doesn't match. Encrypted binaries are [`Error::Unsupported`].
+    #[inline]
+    pub fn verify<CE: AsRef<ce::RegisterBlock>>(
+        &self,
+        ce: &mut Ce<'_, CE>,
+        key: &RsaPublicKey,
+        name: &[u8],
+    ) -> Result<Verified<'a>, Error> {
+        let binary = self
+            .item(name, Toc1ItemKind::Binary)
+            .ok_or(Error::MissingItem)?;
+        let certificate = self
+            .item(name, Toc1ItemKind::Certificate)
+            .ok_or(Error::MissingItem)?;
+        if binary.encrypted {
+            return Err(Error::Unsupported);
+        }
+        let certificate = Certificate::parse(certificate.data)?;
+        certificate.verify(ce, key)?;
+        check_payload(ce, &certificate, binary.data)?;
+        Ok(Verified {
+            data: binary.data,
+            load_address: binary.run_address,
+            key: certificate.public_key(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{CHECKSUM_OFFSET, Error, HEADER_SIZE, ITEM_SIZE, Toc1, Toc1ItemKind, checksum};
+
+    fn put(image: &mut [u8], offset: usize, value: u32) {
+        image[offset..offset + 4].copy_from_slice(&value.to_le_bytes());
+    }
+
+    #[test]
+    fn struct_toc1_functions() {
+        const LENGTH: usize = HEADER_SIZE + 2 * ITEM_SIZE + 64;
+        let mut image = [0u8; LENGTH];
+        image[..12].copy_from_slice(b"sunxi-secure");
+        put(&mut image, 16, 0x8911_9800);
+        put(&mut image, 32, 2);
+        put(&mut image, 36, LENGTH as u32);
+        image[60..64].copy_from_slice(b"IHE;");
+        let data = HEADER_SIZE + 2 * ITEM_SIZE;
+        for (i, (kind, offset, len, run)) in [(2, data, 32, 0), (3, data + 32, 32, 0x4800_0000)]
+            .into_iter()
+            .enumerate()
+        {
+            let info = HEADER_SIZE + i * ITEM_SIZE;
+            image[info..info + 7].copy_from_slice(b"opensbi");
+            put(&mut image, info + 64, offset as u32);
+            put(&mut image, info + 68, len);
+            put(&mut image, info + 76, kind);
+            put(&mut image, info + 80, run);
+            image[info + 364..info + 368].copy_from_slice(b"IIE;");
+        }
+        image[data + 32..].fill(0x96);
+        let sum = checksum(&image, CHECKSUM_OFFSET);
+        put(&mut image, CHECKSUM_OFFSET, sum);
+
+        let toc1 = Toc1::parse(&image).unwrap();
+        let binary = toc1.item(b"opensbi", Toc1ItemKind::Binary).unwrap();
+        assert_eq!(binary.data, &[0x96; 32]);
+        assert_eq!(binary.run_address, 0x4800_0000);
+        assert!(!binary.encrypted);
+        assert!(toc1.item(b"opensbi", Toc1ItemKind::Certificate).is_some());
+        assert_eq!(toc1.item(b"u-boot", Toc1ItemKind::Binary), None);
+        assert_eq!(Toc1ItemKind::from_raw(1), Toc1ItemKind::KeyCertificate);
+        assert_eq!(Toc1ItemKind::from_raw(9), Toc1ItemKind::Other(9));
+
+        let mut corrupted = image;
+        corrupted[LENGTH - 1] = 0x00;
+        assert_eq!(Toc1::parse(&corrupted), Err(Error::Checksum));
+        let mut bad_magic = image;
+        bad_magic[19] = 0x00;
+        assert_eq!(Toc1::parse(&bad_magic), Err(Error::InvalidHeader));
+        for length in [0, HEADER_SIZE as u32 - 4] {
+            let mut short = image;
+            put(&mut short, 32, 0);
+            put(&mut short, 36, length);
+            assert_eq!(Toc1::parse(&short), Err(Error::InvalidHeader));
+        }
+    }
+}
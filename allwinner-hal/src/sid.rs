@@ -0,0 +1,419 @@
+//! Security ID and eFuse reader.
+//!
+//! SID holds one-time programmable eFuses with the chip ID, factory calibration values and
+//! security configuration. [`Sid`] reads eFuse words through the SID read interface, which
+//! also reads words that are protected from the direct mapped window:
+//!
+//! ```ignore
+//! let sid = Sid::new(p.sid);
+//! let chip_id = sid.chip_id()?;
+//! let calibration = sid.ths_calibration()?;
+//! println!("chip {:02x?}, secure {}", chip_id, sid.is_secure_enabled());
+//! ```
+//!
+//! Fields are located by [`Field`] constants of D1 eFuse map, such as [`CHIP_ID`].
+//!
+//! Burning eFuses is irreversible, so programming requires a [`ProgramToken`] created for this
+//! chip with a confirmation phrase, and every programmed word is read back and verified:
+//!
+//! ```ignore
+//! let mut sid = Sid::new(p.sid);
+//! let chip_id = sid.chip_id()?;
+//! // note(unsafe): board supplies eFuse programming voltage, and values have been checked
+//! let token = unsafe { ProgramToken::new(chip_id, ProgramToken::CONFIRMATION) }.unwrap();
+//! sid.program_rotpk_hash(&token, &rotpk_hash)?;
+//! sid.enable_secure_boot(&token)?;
+//! ```
+
+mod register;
+pub use register::*;
+
+use crate::time::Deadline;
+
+/// Byte offset and length of an eFuse field.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Field {
+    /// Byte offset of the first word.
+    pub offset: u16,
+    /// Length in bytes, a multiple of four.
+    pub len: u16,
+}
+
+/// 128-bit chip ID, unique to each chip.
+pub const CHIP_ID: Field = Field {
+    offset: 0x00,
+    len: 16,
+};
+/// Boot ROM configuration.
+pub const BROM_CONFIG: Field = Field {
+    offset: 0x10,
+    len: 4,
+};
+/// Thermal sensor calibration.
+pub const THS_CALIBRATION: Field = Field {
+    offset: 0x14,
+    len: 4,
+};
+/// Write protection bits of eFuse fields.
+pub const WRITE_PROTECT: Field = Field {
+    offset: 0x40,
+    len: 4,
+};
+/// Read protection bits of eFuse fields.
+pub const READ_PROTECT: Field = Field {
+    offset: 0x44,
+    len: 4,
+};
+/// Life cycle and JTAG security configuration.
+pub const LCJS: Field = Field {
+    offset: 0x48,
+    len: 4,
+};
+/// Secure storage key.
+pub const SSK: Field = Field {
+    offset: 0x50,
+    len: 16,
+};
+/// SHA-256 hash of root of trust public key, checked by secure boot.
+pub const ROTPK_HASH: Field = Field {
+    offset: 0x70,
+    len: 32,
+};
+
+/// Secure enable bit of [`LCJS`] field.
+pub const SECURE_ENABLE: u32 = 1 << 11;
+
+/// Permission to burn eFuses of one chip.
+///
+/// Programming functions of [`Sid`] check that the token was created for the chip they run
+/// on, so that a token can't be reused across chips by mistake.
+#[derive(Debug, PartialEq, Eq, Hash)]
+pub struct ProgramToken {
+    chip_id: [u8; 16],
+}
+
+impl ProgramToken {
+    /// Phrase confirming that eFuse programming is understood to be irreversible.
+    pub const CONFIRMATION: &'static str = "burn efuses permanently";
+
+    /// Create a token for chip `chip_id`, or `None` if `confirmation` is not
+    /// [`ProgramToken::CONFIRMATION`].
+    ///
+    /// # Safety
+    ///
+    /// Burned eFuses can't be cleared; wrong values, such as enabling secure boot without a
+    /// matching signed firmware, permanently brick the chip. Board must supply eFuse
+    /// programming voltage while programming.
+    #[inline]
+    pub unsafe fn new(chip_id: [u8; 16], confirmation: &str) -> Option<Self> {
+        match confirmation == Self::CONFIRMATION {
+            true => Some(Self { chip_id }),
+            false => None,
+        }
+    }
+}
+
+/// Thermal sensor factory calibration.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct ThsCalibration(u32);
+
+impl ThsCalibration {
+    const TEMPERATURE: u32 = 0xFFF;
+    const READING: u32 = 0xFFF << 16;
+
+    /// Create calibration from raw eFuse word.
+    #[inline]
+    pub const fn from_raw(raw: u32) -> Self {
+        Self(raw)
+    }
+    /// Get temperature at calibration in millidegrees Celsius.
+    #[inline]
+    pub const fn reference_temperature(self) -> i32 {
+        (self.0 & Self::TEMPERATURE) as i32 * 100
+    }
+    /// Get sensor reading at calibration temperature.
+    #[inline]
+    pub const fn reference_reading(self) -> u16 {
+        ((self.0 & Self::READING) >> 16) as u16
+    }
+    /// Check if chip has been calibrated.
+    #[inline]
+    pub const fn is_calibrated(self) -> bool {
+        self.0 != 0
+    }
+}
+
+/// SID error.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Error {
+    /// eFuse operation did not finish before crate-wide blocking timeout.
+    Timeout,
+    /// Program token was created for another chip.
+    WrongChip,
+    /// Value needs bits cleared that are already burned.
+    Conflict,
+    /// Word read back after programming differs from programmed value.
+    VerifyFailed,
+    /// Secure boot can't be enabled before root of trust public key hash is programmed.
+    RotpkMissing,
+}
+
+/// Managed SID structure with peripheral.
+pub struct Sid<SID> {
+    sid: SID,
+}
+
+impl<SID: AsRef<RegisterBlock>> Sid<SID> {
+    /// Create an eFuse reader.
+    #[inline]
+    pub fn new(sid: SID) -> Self {
+        Self { sid }
+    }
+    /// Read eFuse word at byte `offset`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `offset` is not word aligned or exceeds eFuse size.
+    #[inline]
+    pub fn read_word(&self, offset: u16) -> Result<u32, Error> {
+        assert!(
+            offset.is_multiple_of(4) && (offset as usize) < EFUSE_WORDS * 4,
+            "eFuse offset out of range"
+        );
+        let regs = self.sid.as_ref();
+        let control = ProgramControl::default().set_index(offset).unlock();
+        unsafe { regs.program_control.write(control.start_read()) };
+        let deadline = Deadline::start();
+        while regs.program_control.read().is_reading() {
+            if deadline.check().is_err() {
+                unsafe { regs.program_control.write(ProgramControl::default()) };
+                return Err(Error::Timeout);
+            }
+            core::hint::spin_loop();
+        }
+        let ans = regs.read_key.read();
+        unsafe { regs.program_control.write(ProgramControl::default()) };
+        Ok(ans)
+    }
+    /// Read eFuse `field` into `buf` in little-endian words.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `buf` is not as long as `field`.
+    #[inline]
+    pub fn read(&self, field: Field, buf: &mut [u8]) -> Result<(), Error> {
+        assert!(
+            buf.len() == field.len as usize,
+            "buffer length differs from field"
+        );
+        for (i, chunk) in buf.chunks_exact_mut(4).enumerate() {
+            let word = self.read_word(field.offset + 4 * i as u16)?;
+            chunk.copy_from_slice(&word.to_le_bytes());
+        }
+        Ok(())
+    }
+    /// Read 128-bit chip ID.
+    #[inline]
+    pub fn chip_id(&self) -> Result<[u8; 16], Error> {
+        let mut ans = [0; 16];
+        self.read(CHIP_ID, &mut ans)?;
+        Ok(ans)
+    }
+    /// Read thermal sensor factory calibration.
+    #[inline]
+    pub fn ths_calibration(&self) -> Result<ThsCalibration, Error> {
+        self.read_word(THS_CALIBRATION.offset)
+            .map(ThsCalibration::from_raw)
+    }
+    /// Read hash of root of trust public key.
+    #[inline]
+    pub fn rotpk_hash(&self) -> Result<[u8; 32], Error> {
+        let mut ans = [0; 32];
+        self.read(ROTPK_HASH, &mut ans)?;
+        Ok(ans)
+    }
+    /// Read write protection bits.
+    #[inline]
+    pub fn write_protect(&self) -> Result<u32, Error> {
+        self.read_word(WRITE_PROTECT.offset)
+    }
+    /// Read read protection bits.
+    #[inline]
+    pub fn read_protect(&self) -> Result<u32, Error> {
+        self.read_word(READ_PROTECT.offset)
+    }
+    /// Burn `value` into eFuse word at byte `offset`, and verify it.
+    ///
+    /// Bits already burned stay burned; programming a word with its current value does
+    /// nothing.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::WrongChip`] if `token` is for another chip, [`Error::Conflict`] if
+    /// `value` lacks bits that are already burned, or [`Error::VerifyFailed`] if the word
+    /// doesn't read back as `value`, e.g. when it is write protected.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `offset` is not word aligned or exceeds eFuse size.
+    #[inline]
+    pub fn program_word(
+        &mut self,
+        token: &ProgramToken,
+        offset: u16,
+        value: u32,
+    ) -> Result<(), Error> {
+        self.check_token(token)?;
+        let old = self.read_word(offset)?;
+        let bits = burned_bits(old, value)?;
+        if bits != 0 {
+            self.burn(offset, bits)?;
+        }
+        match self.read_word(offset)? == value {
+            true => Ok(()),
+            false => Err(Error::VerifyFailed),
+        }
+    }
+    /// Burn `data` into eFuse `field` in little-endian words, and verify it.
+    ///
+    /// # Errors
+    ///
+    /// Returns errors of [`Sid::program_word`]; fails before burning anything if any word
+    /// conflicts.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `data` is not as long as `field`.
+    #[inline]
+    pub fn program(
+        &mut self,
+        token: &ProgramToken,
+        field: Field,
+        data: &[u8],
+    ) -> Result<(), Error> {
+        assert!(
+            data.len() == field.len as usize,
+            "data length differs from field"
+        );
+        self.check_token(token)?;
+        for (i, chunk) in data.chunks_exact(4).enumerate() {
+            let value = u32::from_le_bytes([chunk[0], chunk[1], chunk[2], chunk[3]]);
+            burned_bits(self.read_word(field.offset + 4 * i as u16)?, value)?;
+        }
+        for (i, chunk) in data.chunks_exact(4).enumerate() {
+            let value = u32::from_le_bytes([chunk[0], chunk[1], chunk[2], chunk[3]]);
+            self.program_word(token, field.offset + 4 * i as u16, value)?;
+        }
+        Ok(())
+    }
+    /// Burn hash of root of trust public key, and verify it.
+    ///
+    /// # Errors
+    ///
+    /// Returns errors of [`Sid::program`]; [`Error::Conflict`] means a different hash has
+    /// been programmed.
+    #[inline]
+    pub fn program_rotpk_hash(
+        &mut self,
+        token: &ProgramToken,
+        hash: &[u8; 32],
+    ) -> Result<(), Error> {
+        self.program(token, ROTPK_HASH, hash)
+    }
+    /// Burn secure enable bit, so that chip boots only firmware signed by root of trust key.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::RotpkMissing`] if root of trust public key hash is not programmed, or
+    /// errors of [`Sid::program_word`].
+    #[inline]
+    pub fn enable_secure_boot(&mut self, token: &ProgramToken) -> Result<(), Error> {
+        self.check_token(token)?;
+        if self.rotpk_hash()? == [0; 32] {
+            return Err(Error::RotpkMissing);
+        }
+        let lcjs = self.read_word(LCJS.offset)?;
+        self.program_word(token, LCJS.offset, lcjs | SECURE_ENABLE)
+    }
+    /// Check if chip is in secure mode, booting only signed firmware.
+    #[inline]
+    pub fn is_secure_enabled(&self) -> bool {
+        self.sid.as_ref().secure_status.read().is_secure_enabled()
+    }
+    /// Release the peripheral.
+    #[inline]
+    pub fn free(self) -> SID {
+        self.sid
+    }
+    /// Check that `token` was created for this chip.
+    #[inline]
+    fn check_token(&self, token: &ProgramToken) -> Result<(), Error> {
+        match self.chip_id()? == token.chip_id {
+            true => Ok(()),
+            false => Err(Error::WrongChip),
+        }
+    }
+    /// Burn one bits of `bits` into eFuse word at byte `offset`.
+    #[inline]
+    fn burn(&mut self, offset: u16, bits: u32) -> Result<(), Error> {
+        let regs = self.sid.as_ref();
+        let control = ProgramControl::default().set_index(offset).unlock();
+        unsafe {
+            regs.program_key.write(bits);
+            regs.program_control.write(control.start_program());
+        }
+        let deadline = Deadline::start();
+        while regs.program_control.read().is_programming() {
+            if deadline.check().is_err() {
+                unsafe { regs.program_control.write(ProgramControl::default()) };
+                return Err(Error::Timeout);
+            }
+            core::hint::spin_loop();
+        }
+        unsafe {
+            regs.program_control.write(ProgramControl::default());
+            regs.program_key.write(0);
+        }
+        Ok(())
+    }
+}
+
+/// Bits to burn so that word `old` becomes `new`.
+#[inline]
+const fn burned_bits(old: u32, new: u32) -> Result<u32, Error> {
+    match old & !new {
+        0 => Ok(new & !old),
+        _ => Err(Error::Conflict),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Error, ProgramToken, ThsCalibration, burned_bits};
+
+    #[test]
+    fn struct_ths_calibration_functions() {
+        let val = ThsCalibration::from_raw(0x0800_00FA);
+        assert!(val.is_calibrated());
+        assert_eq!(val.reference_temperature(), 25_000);
+        assert_eq!(val.reference_reading(), 0x800);
+        assert!(!ThsCalibration::from_raw(0).is_calibrated());
+    }
+
+    #[test]
+    fn function_burned_bits() {
+        assert_eq!(burned_bits(0x0000_0000, 0x1234_5678), Ok(0x1234_5678));
+        assert_eq!(burned_bits(0x0000_0800, 0x0000_0801), Ok(0x0000_0001));
+        assert_eq!(burned_bits(0xFFFF_0000, 0xFFFF_0000), Ok(0));
+        assert_eq!(burned_bits(0x0000_0001, 0x0000_0800), Err(Error::Conflict));
+    }
+
+    #[test]
+    fn struct_program_token() {
+        let chip_id = [0x5A; 16];
+        assert_eq!(unsafe { ProgramToken::new(chip_id, "yes") }, None);
+        let token = unsafe { ProgramToken::new(chip_id, ProgramToken::CONFIRMATION) };
+        assert_eq!(token.map(|t| t.chip_id), Some(chip_id));
+    }
+}
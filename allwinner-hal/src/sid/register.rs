@@ -0,0 +1,146 @@
+use volatile_register::{RO, RW};
+
+/// Number of 32-bit eFuse words.
+pub const EFUSE_WORDS: usize = 64;
+
+/// Security ID and eFuse controller registers.
+#[repr(C)]
+pub struct RegisterBlock {
+    _reserved0: [u32; 16],
+    /// 0x40 - eFuse program and read control register.
+    pub program_control: RW<ProgramControl>,
+    _reserved1: [u32; 3],
+    /// 0x50 - eFuse program key value register.
+    pub program_key: RW<u32>,
+    _reserved2: [u32; 3],
+    /// 0x60 - eFuse read key value register.
+    pub read_key: RO<u32>,
+    _reserved3: [u32; 15],
+    /// 0xa0 - Secure mode status register.
+    pub secure_status: RO<SecureStatus>,
+    _reserved4: [u32; 87],
+    /// 0x200 - Direct mapped eFuse words.
+    ///
+    /// Words protected from direct reads read as zero; use the read interface instead.
+    pub efuse: [RO<u32>; EFUSE_WORDS],
+}
+
+/// eFuse program and read control register.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct ProgramControl(u32);
+
+impl ProgramControl {
+    const INDEX: u32 = 0x1FF << 16;
+    const OP_LOCK: u32 = 0xFF << 8;
+    const READ_START: u32 = 1 << 1;
+    const PROGRAM_START: u32 = 1 << 0;
+
+    /// Operation lock value which unlocks eFuse reads and programming.
+    const UNLOCK: u32 = 0xAC << 8;
+
+    /// Get byte offset of eFuse word to read or program.
+    #[inline]
+    pub const fn index(self) -> u16 {
+        ((self.0 & Self::INDEX) >> 16) as u16
+    }
+    /// Set byte offset of eFuse word to read or program.
+    #[inline]
+    pub const fn set_index(self, val: u16) -> Self {
+        Self((self.0 & !Self::INDEX) | ((val as u32 & 0x1FF) << 16))
+    }
+    /// Check if operation lock is unlocked.
+    #[inline]
+    pub const fn is_unlocked(self) -> bool {
+        self.0 & Self::OP_LOCK == Self::UNLOCK
+    }
+    /// Unlock eFuse operations.
+    #[inline]
+    pub const fn unlock(self) -> Self {
+        Self((self.0 & !Self::OP_LOCK) | Self::UNLOCK)
+    }
+    /// Lock eFuse operations.
+    #[inline]
+    pub const fn lock(self) -> Self {
+        Self(self.0 & !Self::OP_LOCK)
+    }
+    /// Check if eFuse word read is in progress.
+    #[inline]
+    pub const fn is_reading(self) -> bool {
+        self.0 & Self::READ_START != 0
+    }
+    /// Start reading eFuse word.
+    #[inline]
+    pub const fn start_read(self) -> Self {
+        Self(self.0 | Self::READ_START)
+    }
+    /// Check if eFuse word programming is in progress.
+    #[inline]
+    pub const fn is_programming(self) -> bool {
+        self.0 & Self::PROGRAM_START != 0
+    }
+    /// Start programming eFuse word.
+    #[inline]
+    pub const fn start_program(self) -> Self {
+        Self(self.0 | Self::PROGRAM_START)
+    }
+}
+
+impl Default for ProgramControl {
+    #[inline]
+    fn default() -> Self {
+        Self(0x0000_0000)
+    }
+}
+
+/// Secure mode status register.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct SecureStatus(u32);
+
+impl SecureStatus {
+    const SECURE: u32 = 1 << 0;
+
+    /// Check if chip is in secure mode, booting only signed firmware.
+    #[inline]
+    pub const fn is_secure_enabled(self) -> bool {
+        self.0 & Self::SECURE != 0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ProgramControl, RegisterBlock, SecureStatus};
+    use core::mem::offset_of;
+
+    #[test]
+    fn offset_sid() {
+        assert_eq!(offset_of!(RegisterBlock, program_control), 0x40);
+        assert_eq!(offset_of!(RegisterBlock, program_key), 0x50);
+        assert_eq!(offset_of!(RegisterBlock, read_key), 0x60);
+        assert_eq!(offset_of!(RegisterBlock, secure_status), 0xa0);
+        assert_eq!(offset_of!(RegisterBlock, efuse), 0x200);
+    }
+
+    #[test]
+    fn struct_program_control_functions() {
+        let val = ProgramControl::default()
+            .set_index(0x14)
+            .unlock()
+            .start_read();
+        assert_eq!(val.0, 0x0014_AC02);
+        assert_eq!(val.index(), 0x14);
+        assert!(val.is_unlocked());
+        assert!(val.is_reading());
+        assert!(!val.is_programming());
+
+        let val = val.lock().start_program();
+        assert_eq!(val.0, 0x0014_0003);
+        assert!(!val.is_unlocked());
+        assert!(val.is_programming());
+        assert_eq!(ProgramControl::default().set_index(0x1FC).index(), 0x1FC);
+
+        assert!(SecureStatus(0x0000_0001).is_secure_enabled());
+        assert!(!SecureStatus(0x0000_0000).is_secure_enabled());
+    }
+}
@@ -0,0 +1,151 @@
+//! Secure Memory Controller.
+//!
+//! Secure memory controller filters DRAM accesses by address region and security state, so
+//! that a secure monitor can keep its own memory from non-secure software. Region 0 is the
+//! background region covering the whole address space; regions 1 and above take priority
+//! over lower numbered regions:
+//!
+//! ```ignore
+//! let mut smc = Smc::new(smc_peripheral);
+//! smc.set_background(Permission::All);
+//! // reserve 2 MiB at DRAM start for secure monitor
+//! smc.set_region(1, 0x4000_0000, 2 * 1024 * 1024, Permission::SecureOnly)?;
+//! smc.set_reaction(Reaction::DecodeErrorInterrupt);
+//! ```
+//!
+//! The controller exists on Allwinner chips with TrustZone DRAM filtering. D1 has none, so
+//! allwinner-rt doesn't define this peripheral; firmware for other chips passes its own
+//! instance implementing `AsRef<RegisterBlock>`.
+
+mod register;
+pub use register::*;
+
+/// Smallest region size in bytes.
+const MIN_REGION_SIZE: u64 = 32 * 1024;
+
+/// Secure memory controller error.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Error {
+    /// Region index is the background region or exceeds region count.
+    InvalidIndex,
+    /// Region size is not a power of two of at least 32 KiB, or base is not aligned to it.
+    InvalidRegion,
+}
+
+/// Access denied by secure memory controller.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct AccessFailure {
+    /// Address of denied access.
+    pub address: u64,
+    /// Master ID of denied access.
+    pub master: u32,
+    /// Whether denied access was a write.
+    pub is_write: bool,
+    /// Whether denied access was non-secure.
+    pub is_non_secure: bool,
+    /// Whether more accesses were denied after this one.
+    pub is_overrun: bool,
+}
+
+/// Secure memory controller driver.
+pub struct Smc<SMC> {
+    smc: SMC,
+}
+
+impl<SMC: AsRef<RegisterBlock>> Smc<SMC> {
+    /// Create secure memory controller driver.
+    #[inline]
+    pub fn new(smc: SMC) -> Self {
+        Self { smc }
+    }
+    /// Get number of regions, including background region 0.
+    #[inline]
+    pub fn region_count(&self) -> usize {
+        let count = self.smc.as_ref().configuration.read().region_count();
+        count.min(REGION_COUNT)
+    }
+    /// Set access permission of background region 0.
+    #[inline]
+    pub fn set_background(&mut self, permission: Permission) {
+        let attributes = &self.smc.as_ref().regions[0].attributes;
+        unsafe { attributes.modify(|val| val.set_permission(permission)) };
+    }
+    /// Set region `index` to cover `size` bytes at `base` with `permission`, and enable it.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidIndex`] if `index` is 0 or not below [`Smc::region_count`], or
+    /// [`Error::InvalidRegion`] if `size` is not a power of two of at least 32 KiB or `base`
+    /// is not a multiple of `size`.
+    #[inline]
+    pub fn set_region(
+        &mut self,
+        index: usize,
+        base: u64,
+        size: u64,
+        permission: Permission,
+    ) -> Result<(), Error> {
+        if index == 0 || index >= self.region_count() {
+            return Err(Error::InvalidIndex);
+        }
+        if !size.is_power_of_two() || size < MIN_REGION_SIZE || !base.is_multiple_of(size) {
+            return Err(Error::InvalidRegion);
+        }
+        let region = &self.smc.as_ref().regions[index];
+        let attributes = RegionAttributes::default()
+            .set_permission(permission)
+            .set_size_log2(size.trailing_zeros() as u8);
+        unsafe {
+            region.attributes.write(attributes);
+            region.setup_low.write(base as u32);
+            region.setup_high.write((base >> 32) as u32);
+            region.attributes.write(attributes.enable());
+        }
+        Ok(())
+    }
+    /// Disable region `index`, so that lower numbered regions apply to its addresses.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidIndex`] if `index` is 0 or not below [`Smc::region_count`].
+    #[inline]
+    pub fn disable_region(&mut self, index: usize) -> Result<(), Error> {
+        if index == 0 || index >= self.region_count() {
+            return Err(Error::InvalidIndex);
+        }
+        let attributes = &self.smc.as_ref().regions[index].attributes;
+        unsafe { attributes.modify(|val| val.disable()) };
+        Ok(())
+    }
+    /// Set reaction to denied accesses.
+    #[inline]
+    pub fn set_reaction(&mut self, reaction: Reaction) {
+        let action = &self.smc.as_ref().action;
+        unsafe { action.modify(|val| val.set_reaction(reaction)) };
+    }
+    /// Take the recorded denied access, if any, and clear the interrupt.
+    #[inline]
+    pub fn take_failure(&mut self) -> Option<AccessFailure> {
+        let smc = self.smc.as_ref();
+        let status = smc.interrupt_status.read();
+        if !status.is_pending() {
+            return None;
+        }
+        let control = smc.fail_control.read();
+        let failure = AccessFailure {
+            address: (smc.fail_address_high.read() as u64) << 32
+                | smc.fail_address_low.read() as u64,
+            master: smc.fail_id.read(),
+            is_write: control.is_write(),
+            is_non_secure: control.is_non_secure(),
+            is_overrun: status.is_overrun(),
+        };
+        unsafe { smc.interrupt_clear.write(1) };
+        Some(failure)
+    }
+    /// Release the peripheral.
+    #[inline]
+    pub fn free(self) -> SMC {
+        self.smc
+    }
+}
@@ -0,0 +1,367 @@
+use volatile_register::{RO, RW, WO};
+
+/// Number of address regions, including background region 0.
+pub const REGION_COUNT: usize = 16;
+
+/// Secure memory controller registers.
+#[repr(C)]
+pub struct RegisterBlock {
+    /// 0x00 - Configuration register.
+    pub configuration: RO<Configuration>,
+    /// 0x04 - Action register.
+    pub action: RW<Action>,
+    /// 0x08 - Lockdown range register.
+    pub lockdown_range: RW<u32>,
+    /// 0x0c - Lockdown select register.
+    pub lockdown_select: RW<u32>,
+    /// 0x10 - Interrupt status register.
+    pub interrupt_status: RO<InterruptStatus>,
+    /// 0x14 - Interrupt clear register.
+    pub interrupt_clear: WO<u32>,
+    _reserved0: [u32; 2],
+    /// 0x20 - Low word of failed access address.
+    pub fail_address_low: RO<u32>,
+    /// 0x24 - High word of failed access address.
+    pub fail_address_high: RO<u32>,
+    /// 0x28 - Failed access control register.
+    pub fail_control: RO<FailControl>,
+    /// 0x2c - Failed access master ID register.
+    pub fail_id: RO<u32>,
+    /// 0x30 - Speculation control register.
+    pub speculation_control: RW<u32>,
+    /// 0x34 - Security inversion enable register.
+    pub security_inversion: RW<u32>,
+    _reserved1: [u32; 14],
+    /// 0x70 - Master bypass register, one bit per DRAM master.
+    ///
+    /// Accesses of a bypassed master are not checked against regions.
+    pub master_bypass: RW<u32>,
+    _reserved2: [u32; 3],
+    /// 0x80 - Master security register, one bit per DRAM master.
+    ///
+    /// Accesses of a master whose bit is set are secure.
+    pub master_secure: RW<u32>,
+    _reserved3: [u32; 31],
+    /// 0x100 - Address region registers.
+    pub regions: [Region; REGION_COUNT],
+}
+
+/// Address region registers.
+#[repr(C)]
+pub struct Region {
+    /// 0x00 - Low word of region base address.
+    pub setup_low: RW<u32>,
+    /// 0x04 - High word of region base address.
+    pub setup_high: RW<u32>,
+    /// 0x08 - Region attributes register.
+    pub attributes: RW<RegionAttributes>,
+    _reserved0: u32,
+}
+
+/// Configuration register.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct Configuration(u32);
+
+impl Configuration {
+    const ADDRESS_WIDTH: u32 = 0x3F << 8;
+    const REGIONS: u32 = 0xF;
+
+    /// Get address width in bits.
+    #[inline]
+    pub const fn address_width(self) -> u8 {
+        ((self.0 & Self::ADDRESS_WIDTH) >> 8) as u8 + 1
+    }
+    /// Get number of regions, including background region 0.
+    #[inline]
+    pub const fn region_count(self) -> usize {
+        (self.0 & Self::REGIONS) as usize + 1
+    }
+}
+
+/// Reaction to an access denied by region permissions.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Reaction {
+    /// Respond okay, reading zero and ignoring writes.
+    Okay,
+    /// Respond with decode error.
+    DecodeError,
+    /// Respond okay and raise interrupt.
+    OkayInterrupt,
+    /// Respond with decode error and raise interrupt.
+    DecodeErrorInterrupt,
+}
+
+/// Action register.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct Action(u32);
+
+impl Action {
+    const REACTION: u32 = 0x3;
+
+    /// Get reaction to denied accesses.
+    #[inline]
+    pub const fn reaction(self) -> Reaction {
+        match self.0 & Self::REACTION {
+            0 => Reaction::Okay,
+            1 => Reaction::DecodeError,
+            2 => Reaction::OkayInterrupt,
+            _ => Reaction::DecodeErrorInterrupt,
+        }
+    }
+    /// Set reaction to denied accesses.
+    #[inline]
+    pub const fn set_reaction(self, val: Reaction) -> Self {
+        let val = match val {
+            Reaction::Okay => 0,
+            Reaction::DecodeError => 1,
+            Reaction::OkayInterrupt => 2,
+            Reaction::DecodeErrorInterrupt => 3,
+        };
+        Self((self.0 & !Self::REACTION) | val)
+    }
+}
+
+impl Default for Action {
+    #[inline]
+    fn default() -> Self {
+        Self(0x0000_0001)
+    }
+}
+
+/// Interrupt status register.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct InterruptStatus(u32);
+
+impl InterruptStatus {
+    const OVERRUN: u32 = 1 << 1;
+    const STATUS: u32 = 1 << 0;
+
+    /// Check if a denied access is recorded in fail registers.
+    #[inline]
+    pub const fn is_pending(self) -> bool {
+        self.0 & Self::STATUS != 0
+    }
+    /// Check if more accesses were denied while one was pending.
+    #[inline]
+    pub const fn is_overrun(self) -> bool {
+        self.0 & Self::OVERRUN != 0
+    }
+}
+
+/// Failed access control register.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct FailControl(u32);
+
+impl FailControl {
+    const WRITE: u32 = 1 << 24;
+    const NON_SECURE: u32 = 1 << 21;
+    const PRIVILEGED: u32 = 1 << 20;
+
+    /// Check if failed access was a write.
+    #[inline]
+    pub const fn is_write(self) -> bool {
+        self.0 & Self::WRITE != 0
+    }
+    /// Check if failed access was non-secure.
+    #[inline]
+    pub const fn is_non_secure(self) -> bool {
+        self.0 & Self::NON_SECURE != 0
+    }
+    /// Check if failed access was privileged.
+    #[inline]
+    pub const fn is_privileged(self) -> bool {
+        self.0 & Self::PRIVILEGED != 0
+    }
+}
+
+/// Access permission of an address region.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Permission {
+    /// No access is allowed.
+    NoAccess,
+    /// Secure reads only.
+    SecureReadOnly,
+    /// Secure reads and writes.
+    SecureOnly,
+    /// Secure reads and writes, and non-secure reads.
+    NonSecureReadOnly,
+    /// All reads and writes.
+    All,
+}
+
+/// Region attributes register.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct RegionAttributes(u32);
+
+impl RegionAttributes {
+    const PERMISSION: u32 = 0xF << 28;
+    const SUBREGION_DISABLE: u32 = 0xFF << 8;
+    const SIZE: u32 = 0x3F << 1;
+    const ENABLE: u32 = 1 << 0;
+
+    /// Get access permission, or `None` if it is not a [`Permission`] variant.
+    #[inline]
+    pub const fn permission(self) -> Option<Permission> {
+        match (self.0 & Self::PERMISSION) >> 28 {
+            0b0000 => Some(Permission::NoAccess),
+            0b1000 => Some(Permission::SecureReadOnly),
+            0b1100 => Some(Permission::SecureOnly),
+            0b1110 => Some(Permission::NonSecureReadOnly),
+            0b1111 => Some(Permission::All),
+            _ => None,
+        }
+    }
+    /// Set access permission.
+    #[inline]
+    pub const fn set_permission(self, val: Permission) -> Self {
+        let val = match val {
+            Permission::NoAccess => 0b0000,
+            Permission::SecureReadOnly => 0b1000,
+            Permission::SecureOnly => 0b1100,
+            Permission::NonSecureReadOnly => 0b1110,
+            Permission::All => 0b1111,
+        };
+        Self((self.0 & !Self::PERMISSION) | (val << 28))
+    }
+    /// Get subregion disable bits, one bit per eighth of the region.
+    #[inline]
+    pub const fn subregion_disable(self) -> u8 {
+        ((self.0 & Self::SUBREGION_DISABLE) >> 8) as u8
+    }
+    /// Set subregion disable bits, one bit per eighth of the region.
+    #[inline]
+    pub const fn set_subregion_disable(self, val: u8) -> Self {
+        Self((self.0 & !Self::SUBREGION_DISABLE) | ((val as u32) << 8))
+    }
+    /// Get region size as power of two in bytes.
+    #[inline]
+    pub const fn size_log2(self) -> u8 {
+        ((self.0 & Self::SIZE) >> 1) as u8 + 1
+    }
+    /// Set region size as power of two in bytes, from 15 (32 KiB) to 64.
+    #[inline]
+    pub const fn set_size_log2(self, val: u8) -> Self {
+        assert!(val >= 15 && val <= 64);
+        Self((self.0 & !Self::SIZE) | ((val as u32 - 1) << 1))
+    }
+    /// Check if region is enabled.
+    #[inline]
+    pub const fn is_enabled(self) -> bool {
+        self.0 & Self::ENABLE != 0
+    }
+    /// Enable region.
+    #[inline]
+    pub const fn enable(self) -> Self {
+        Self(self.0 | Self::ENABLE)
+    }
+    /// Disable region.
+    #[inline]
+    pub const fn disable(self) -> Self {
+        Self(self.0 & !Self::ENABLE)
+    }
+}
+
+impl Default for RegionAttributes {
+    #[inline]
+    fn default() -> Self {
+        Self(0x0000_0000)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        Action, Configuration, FailControl, InterruptStatus, Permission, Reaction, Region,
+        RegionAttributes, RegisterBlock,
+    };
+    use core::mem::{offset_of, size_of};
+
+    #[test]
+    fn offset_smc() {
+        assert_eq!(offset_of!(RegisterBlock, configuration), 0x00);
+        assert_eq!(offset_of!(RegisterBlock, action), 0x04);
+        assert_eq!(offset_of!(RegisterBlock, lockdown_range), 0x08);
+        assert_eq!(offset_of!(RegisterBlock, lockdown_select), 0x0c);
+        assert_eq!(offset_of!(RegisterBlock, interrupt_status), 0x10);
+        assert_eq!(offset_of!(RegisterBlock, interrupt_clear), 0x14);
+        assert_eq!(offset_of!(RegisterBlock, fail_address_low), 0x20);
+        assert_eq!(offset_of!(RegisterBlock, fail_address_high), 0x24);
+        assert_eq!(offset_of!(RegisterBlock, fail_control), 0x28);
+        assert_eq!(offset_of!(RegisterBlock, fail_id), 0x2c);
+        assert_eq!(offset_of!(RegisterBlock, speculation_control), 0x30);
+        assert_eq!(offset_of!(RegisterBlock, security_inversion), 0x34);
+        assert_eq!(offset_of!(RegisterBlock, master_bypass), 0x70);
+        assert_eq!(offset_of!(RegisterBlock, master_secure), 0x80);
+        assert_eq!(offset_of!(RegisterBlock, regions), 0x100);
+        assert_eq!(offset_of!(Region, setup_low), 0x00);
+        assert_eq!(offset_of!(Region, setup_high), 0x04);
+        assert_eq!(offset_of!(Region, attributes), 0x08);
+        assert_eq!(size_of::<Region>(), 0x10);
+    }
+
+    #[test]
+    fn struct_configuration_functions() {
+        let val = Configuration(0x0000_1F0F);
+        assert_eq!(val.address_width(), 32);
+        assert_eq!(val.region_count(), 16);
+
+        assert_eq!(Action::default().reaction(), Reaction::DecodeError);
+        for reaction in [
+            Reaction::Okay,
+            Reaction::DecodeError,
+            Reaction::OkayInterrupt,
+            Reaction::DecodeErrorInterrupt,
+        ] {
+            assert_eq!(
+                Action::default().set_reaction(reaction).reaction(),
+                reaction
+            );
+        }
+        assert_eq!(
+            Action::default().set_reaction(Reaction::OkayInterrupt).0,
+            0x2
+        );
+
+        assert!(InterruptStatus(0x1).is_pending());
+        assert!(InterruptStatus(0x2).is_overrun());
+        assert!(!InterruptStatus(0x0).is_pending());
+
+        let val = FailControl(0x0120_0000);
+        assert!(val.is_write());
+        assert!(val.is_non_secure());
+        assert!(!val.is_privileged());
+    }
+
+    #[test]
+    fn struct_region_attributes_functions() {
+        let val = RegionAttributes::default()
+            .set_permission(Permission::SecureOnly)
+            .set_size_log2(28)
+            .set_subregion_disable(0x81)
+            .enable();
+        assert_eq!(val.0, 0xC000_8137);
+        assert_eq!(val.permission(), Some(Permission::SecureOnly));
+        assert_eq!(val.size_log2(), 28);
+        assert_eq!(val.subregion_disable(), 0x81);
+        assert!(val.is_enabled());
+        assert!(!val.disable().is_enabled());
+
+        for permission in [
+            Permission::NoAccess,
+            Permission::SecureReadOnly,
+            Permission::SecureOnly,
+            Permission::NonSecureReadOnly,
+            Permission::All,
+        ] {
+            let val = RegionAttributes::default().set_permission(permission);
+            assert_eq!(val.permission(), Some(permission));
+        }
+        assert_eq!(RegionAttributes(0x3000_0000).permission(), None);
+        assert_eq!(RegionAttributes::default().set_size_log2(15).0, 0x0000_001C);
+    }
+}
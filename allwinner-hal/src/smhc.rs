@@ -1,7 +1,17 @@
 //! SD/MMC Host Controller peripheral.
 
+mod block;
+pub use block::*;
+mod emmc;
+pub use emmc::*;
+mod idmac;
+pub use idmac::*;
+mod info;
+pub use info::*;
 mod register;
 pub use register::*;
+mod sdio;
+pub use sdio::*;
 mod pad;
 pub use pad::*;
 mod structure;
@@ -27,8 +37,27 @@ pub enum ResponseMode {
     Long,
 }
 
-#[derive(Debug)]
+/// SD card error.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum SdCardError {
+    /// Unknown error.
     Unknown,
+    /// Card responded command with unexpected value.
     UnexpectedResponse(u8, u128),
+    /// Card did not respond command in time.
+    CommandTimeout(u8),
+    /// Command response has CRC or other response errors.
+    ResponseError(u8),
+    /// Data transfer did not finish in time.
+    DataTimeout,
+    /// Controller did not finish a reset or card clock update in time.
+    ControllerTimeout,
+    /// Data transfer has CRC, start bit, end bit or FIFO errors.
+    DataError,
+    /// Requested bus width or timing is not supported by card or controller.
+    Unsupported,
+    /// Data buffer cannot be transferred by one internal DMA descriptor chain.
+    DmaBuffer(IdmacChainError),
+    /// Data buffer length is out of range of the command, or not a multiple of its unit.
+    InvalidLength,
 }
@@ -0,0 +1,200 @@
+pub use embedded_sdmmc::Block;
+
+/// Storage device accessed in 512-byte blocks.
+///
+/// Implemented by SD card and eMMC drivers of this module, which also implement
+/// [`embedded_sdmmc::BlockDevice`] so FAT filesystem crates can mount them directly.
+pub trait BlockDevice {
+    /// Error type of block transfers.
+    type Error: core::fmt::Debug;
+
+    /// Size of one block in bytes.
+    const BLOCK_SIZE: usize = Block::LEN;
+
+    /// Read consecutive blocks starting at `start_block_idx`.
+    fn read_blocks(&self, blocks: &mut [Block], start_block_idx: u32) -> Result<(), Self::Error>;
+    /// Write consecutive blocks starting at `start_block_idx`.
+    fn write_blocks(&self, blocks: &[Block], start_block_idx: u32) -> Result<(), Self::Error>;
+    /// Get total number of blocks on the device.
+    fn block_count(&self) -> u32;
+
+    /// Read a single block.
+    #[inline]
+    fn read_block(&self, block: &mut Block, block_idx: u32) -> Result<(), Self::Error> {
+        self.read_blocks(core::slice::from_mut(block), block_idx)
+    }
+    /// Write a single block.
+    #[inline]
+    fn write_block(&self, block: &Block, block_idx: u32) -> Result<(), Self::Error> {
+        self.write_blocks(core::slice::from_ref(block), block_idx)
+    }
+    /// Get device capacity in bytes.
+    #[inline]
+    fn capacity(&self) -> u64 {
+        self.block_count() as u64 * Self::BLOCK_SIZE as u64
+    }
+}
+
+impl<T: BlockDevice + ?Sized> BlockDevice for &T {
+    type Error = T::Error;
+
+    const BLOCK_SIZE: usize = T::BLOCK_SIZE;
+
+    #[inline]
+    fn read_blocks(&self, blocks: &mut [Block], start_block_idx: u32) -> Result<(), Self::Error> {
+        (**self).read_blocks(blocks, start_block_idx)
+    }
+    #[inline]
+    fn write_blocks(&self, blocks: &[Block], start_block_idx: u32) -> Result<(), Self::Error> {
+        (**self).write_blocks(blocks, start_block_idx)
+    }
+    #[inline]
+    fn block_count(&self) -> u32 {
+        (**self).block_count()
+    }
+}
+
+/// Range of consecutive blocks of a device, exposed as a device of its own.
+///
+/// Used to share one partition or flash region, e.g. over USB mass storage, while keeping
+/// the rest of the device out of reach.
+#[derive(Debug)]
+pub struct BlockRegion<D> {
+    device: D,
+    start: u32,
+    count: u32,
+}
+
+/// Error of [`BlockRegion`] transfers.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum RegionError<E> {
+    /// Transfer goes beyond the end of region.
+    OutOfRange,
+    /// Underlying device failed.
+    Device(E),
+}
+
+impl<D: BlockDevice> BlockRegion<D> {
+    /// Create a region of `count` blocks from block `start` of `device`.
+    ///
+    /// Returns `None` if the region goes beyond the end of device.
+    #[inline]
+    pub fn new(device: D, start: u32, count: u32) -> Option<Self> {
+        if start as u64 + count as u64 > device.block_count() as u64 {
+            return None;
+        }
+        Some(Self {
+            device,
+            start,
+            count,
+        })
+    }
+    /// Release underlying device.
+    #[inline]
+    pub fn free(self) -> D {
+        self.device
+    }
+    #[inline]
+    fn offset(&self, start_block_idx: u32, len: usize) -> Result<u32, RegionError<D::Error>> {
+        if start_block_idx as u64 + len as u64 > self.count as u64 {
+            return Err(RegionError::OutOfRange);
+        }
+        Ok(self.start + start_block_idx)
+    }
+}
+
+impl<D: BlockDevice> BlockDevice for BlockRegion<D> {
+    type Error = RegionError<D::Error>;
+
+    const BLOCK_SIZE: usize = D::BLOCK_SIZE;
+
+    #[inline]
+    fn read_blocks(&self, blocks: &mut [Block], start_block_idx: u32) -> Result<(), Self::Error> {
+        let idx = self.offset(start_block_idx, blocks.len())?;
+        self.device
+            .read_blocks(blocks, idx)
+            .map_err(RegionError::Device)
+    }
+    #[inline]
+    fn write_blocks(&self, blocks: &[Block], start_block_idx: u32) -> Result<(), Self::Error> {
+        let idx = self.offset(start_block_idx, blocks.len())?;
+        self.device
+            .write_blocks(blocks, idx)
+            .map_err(RegionError::Device)
+    }
+    #[inline]
+    fn block_count(&self) -> u32 {
+        self.count
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Block, BlockDevice, BlockRegion, RegionError};
+    use core::cell::RefCell;
+
+    struct RamDisk(RefCell<[Block; 4]>);
+
+    impl BlockDevice for RamDisk {
+        type Error = ();
+
+        fn read_blocks(&self, blocks: &mut [Block], start_block_idx: u32) -> Result<(), ()> {
+            let disk = self.0.borrow();
+            let start = start_block_idx as usize;
+            let src = disk.get(start..start + blocks.len()).ok_or(())?;
+            blocks.clone_from_slice(src);
+            Ok(())
+        }
+        fn write_blocks(&self, blocks: &[Block], start_block_idx: u32) -> Result<(), ()> {
+            let mut disk = self.0.borrow_mut();
+            let start = start_block_idx as usize;
+            let dst = disk.get_mut(start..start + blocks.len()).ok_or(())?;
+            dst.clone_from_slice(blocks);
+            Ok(())
+        }
+        fn block_count(&self) -> u32 {
+            4
+        }
+    }
+
+    #[test]
+    fn trait_block_device_functions() {
+        let disk = RamDisk(RefCell::new(core::array::from_fn(|_| Block::new())));
+        let mut block = Block::new();
+        block.contents[0] = 0x55;
+        block.contents[511] = 0xAA;
+        assert_eq!(disk.write_block(&block, 2), Ok(()));
+        assert_eq!(disk.write_block(&block, 4), Err(()));
+
+        let device = &disk;
+        let mut blocks = [Block::new(), Block::new()];
+        assert_eq!(device.read_blocks(&mut blocks, 1), Ok(()));
+        assert_eq!(blocks[0].contents[0], 0);
+        assert_eq!(blocks[1].contents[0], 0x55);
+        assert_eq!(blocks[1].contents[511], 0xAA);
+        assert_eq!(device.capacity(), 2048);
+        assert_eq!(<&RamDisk as BlockDevice>::BLOCK_SIZE, 512);
+    }
+
+    #[test]
+    fn struct_block_region_functions() {
+        let disk = RamDisk(RefCell::new(core::array::from_fn(|_| Block::new())));
+        assert!(BlockRegion::new(&disk, 2, 3).is_none());
+        let region = BlockRegion::new(&disk, 1, 2).unwrap();
+        assert_eq!(region.block_count(), 2);
+        assert_eq!(region.capacity(), 1024);
+
+        let mut block = Block::new();
+        block.contents[0] = 0x5A;
+        assert_eq!(region.write_block(&block, 1), Ok(()));
+        assert_eq!(region.write_block(&block, 2), Err(RegionError::OutOfRange));
+        let mut blocks = [Block::new(), Block::new()];
+        assert_eq!(
+            region.read_blocks(&mut blocks, 1),
+            Err(RegionError::OutOfRange)
+        );
+        assert_eq!(disk.read_block(&mut blocks[0], 2), Ok(()));
+        assert_eq!(blocks[0].contents[0], 0x5A);
+        assert_eq!(region.free().block_count(), 4);
+    }
+}
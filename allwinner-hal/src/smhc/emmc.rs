@@ -0,0 +1,614 @@
+use super::{
+    Block, BlockDevice, Pads, ResponseMode, SdCardError, TransferMode,
+    register::{BusWidth, RegisterBlock},
+    structure::{DMA_MAX_BLOCKS, Smhc, TUNING_BLOCK_4BIT, TUNING_BLOCK_8BIT},
+};
+use crate::ccu::{self, Clocks};
+use crate::time::Deadline;
+use embedded_sdmmc::BlockIdx;
+use embedded_time::rate::Hertz;
+
+/// eMMC extended CSD register.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ExtCsd(pub [u8; 512]);
+
+impl ExtCsd {
+    const BUS_WIDTH: usize = 183;
+    const HS_TIMING: usize = 185;
+    const DEVICE_TYPE: usize = 196;
+    const SEC_COUNT: usize = 212;
+    const REL_WR_SEC_C: usize = 222;
+    const WR_REL_PARAM: usize = 166;
+    const PARTITION_CONFIG: usize = 179;
+    const BOOT_SIZE_MULT: usize = 226;
+
+    const DEVICE_TYPE_HS_52: u8 = 1 << 1;
+    const DEVICE_TYPE_HS200_1V8: u8 = 1 << 4;
+    const WR_REL_PARAM_EN_REL_WR: u8 = 1 << 2;
+    const PARTITION_ACCESS: u8 = 0x7;
+    const BOOT_PARTITION_ENABLE: u8 = 0x7 << 3;
+    const BOOT_ACK: u8 = 1 << 6;
+
+    /// Get device capacity in 512-byte sectors.
+    #[inline]
+    pub const fn sector_count(&self) -> u32 {
+        u32::from_le_bytes([
+            self.0[Self::SEC_COUNT],
+            self.0[Self::SEC_COUNT + 1],
+            self.0[Self::SEC_COUNT + 2],
+            self.0[Self::SEC_COUNT + 3],
+        ])
+    }
+    /// If device supports high speed timing at 52 MHz.
+    #[inline]
+    pub const fn supports_high_speed(&self) -> bool {
+        self.0[Self::DEVICE_TYPE] & Self::DEVICE_TYPE_HS_52 != 0
+    }
+    /// If device supports HS200 timing at 1.8 V.
+    #[inline]
+    pub const fn supports_hs200(&self) -> bool {
+        self.0[Self::DEVICE_TYPE] & Self::DEVICE_TYPE_HS200_1V8 != 0
+    }
+    /// Get current bus width field value.
+    #[inline]
+    pub const fn bus_width(&self) -> u8 {
+        self.0[Self::BUS_WIDTH]
+    }
+    /// Get current timing interface field value.
+    #[inline]
+    pub const fn hs_timing(&self) -> u8 {
+        self.0[Self::HS_TIMING]
+    }
+    /// Get reliable write sector count.
+    #[inline]
+    pub const fn reliable_write_sector_count(&self) -> u8 {
+        self.0[Self::REL_WR_SEC_C]
+    }
+    /// If enhanced reliable write is supported, allowing any sector count.
+    #[inline]
+    pub const fn supports_enhanced_reliable_write(&self) -> bool {
+        self.0[Self::WR_REL_PARAM] & Self::WR_REL_PARAM_EN_REL_WR != 0
+    }
+    /// Get partition configuration field value.
+    #[inline]
+    pub const fn partition_config(&self) -> u8 {
+        self.0[Self::PARTITION_CONFIG]
+    }
+    /// Get partition currently selected for access, if it's user area or a boot partition.
+    #[inline]
+    pub const fn partition_access(&self) -> Option<Partition> {
+        Partition::from_access(self.0[Self::PARTITION_CONFIG] & Self::PARTITION_ACCESS)
+    }
+    /// Get partition enabled for booting, if any.
+    #[inline]
+    pub const fn boot_partition(&self) -> Option<Partition> {
+        match (self.0[Self::PARTITION_CONFIG] & Self::BOOT_PARTITION_ENABLE) >> 3 {
+            1 => Some(Partition::Boot0),
+            2 => Some(Partition::Boot1),
+            7 => Some(Partition::User),
+            _ => None,
+        }
+    }
+    /// If boot acknowledge is sent by the device during boot operation.
+    #[inline]
+    pub const fn is_boot_ack_enabled(&self) -> bool {
+        self.0[Self::PARTITION_CONFIG] & Self::BOOT_ACK != 0
+    }
+    /// Get size of each boot partition in 512-byte sectors.
+    #[inline]
+    pub const fn boot_sector_count(&self) -> u32 {
+        // boot partition size is BOOT_SIZE_MULT * 128 KiB
+        self.0[Self::BOOT_SIZE_MULT] as u32 * 256
+    }
+}
+
+/// eMMC hardware partition.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Partition {
+    /// User data area.
+    User,
+    /// First boot partition.
+    Boot0,
+    /// Second boot partition.
+    Boot1,
+}
+
+impl Partition {
+    /// Value of partition access field selecting this partition.
+    #[inline]
+    pub const fn access(self) -> u8 {
+        match self {
+            Partition::User => 0,
+            Partition::Boot0 => 1,
+            Partition::Boot1 => 2,
+        }
+    }
+    /// Partition selected by partition access field value.
+    #[inline]
+    pub const fn from_access(access: u8) -> Option<Self> {
+        match access {
+            0 => Some(Partition::User),
+            1 => Some(Partition::Boot0),
+            2 => Some(Partition::Boot1),
+            _ => None,
+        }
+    }
+}
+
+/// eMMC bus timing mode.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Timing {
+    /// Backwards compatible timing, up to 26 MHz.
+    Legacy,
+    /// High speed SDR timing, up to 52 MHz.
+    HighSpeed,
+    /// HS200 timing, up to 200 MHz on 1.8 V I/O.
+    Hs200,
+}
+
+impl Timing {
+    /// Card clock frequency used on this timing.
+    ///
+    /// HS200 is limited to 150 MHz by SMHC on D1-like chips.
+    #[inline]
+    pub const fn frequency(self) -> Hertz {
+        match self {
+            Timing::Legacy => Hertz(26_000_000),
+            Timing::HighSpeed => Hertz(52_000_000),
+            Timing::Hs200 => Hertz(150_000_000),
+        }
+    }
+}
+
+/// Blocking eMMC driver over an SMHC peripheral.
+pub struct Emmc<'a, S, const I: usize, P: Pads<I>> {
+    smhc: &'a mut Smhc<S, I, P>,
+    rca: u32,
+    bus_width: BusWidth,
+    ext_csd: ExtCsd,
+    sector_mode: bool,
+    csd_sector_count: u32,
+}
+
+/// Relative card address assigned to the eMMC device.
+const EMMC_RCA: u32 = 1 << 16;
+
+/// Get capacity in 512-byte sectors from CSD of a byte addressed device, up to 2 GB.
+#[inline]
+const fn csd_sector_count(csd: u128) -> u32 {
+    let c_size = ((csd >> 62) & 0xFFF) as u64;
+    let c_size_mult = ((csd >> 47) & 0x7) as u32;
+    let read_bl_len = ((csd >> 80) & 0xF) as u32;
+    (((c_size + 1) << (c_size_mult + 2 + read_bl_len)) / Block::LEN as u64) as u32
+}
+
+impl<'a, S: AsRef<RegisterBlock>, const I: usize, P: Pads<I>> Emmc<'a, S, I, P> {
+    /// Create an eMMC instance, initializing the device in 1-bit legacy mode.
+    ///
+    /// Devices up to 2 GB answer with byte addressing instead of sector addressing; block
+    /// indices are converted to byte addresses for them.
+    #[inline]
+    pub fn new(smhc: &'a mut Smhc<S, I, P>) -> Result<Self, SdCardError> {
+        /// Sector addressing and 2.7-3.6 V voltage window.
+        const OCR_SECTOR_MODE: u32 = 0x40FF8080;
+        /// Device has finished power up routine if bit is high.
+        const OCR_NBUSY: u32 = 0x80000000;
+        /// Device uses sector addressing if bit is high, byte addressing otherwise.
+        const OCR_ACCESS_MODE: u32 = 0x40000000;
+
+        // CMD0(reset) -> CMD1(wait for power up and set sector mode)
+        smhc.send_card_command(0, 0, TransferMode::Disable, ResponseMode::Disable, false);
+        smhc.wait_for_command(0)?;
+        let deadline = Deadline::start();
+        let sector_mode = loop {
+            smhc.send_card_command(
+                1,
+                OCR_SECTOR_MODE,
+                TransferMode::Disable,
+                ResponseMode::Short,
+                false,
+            );
+            smhc.wait_for_command(1)?;
+            let ocr = smhc.read_response() as u32;
+            if (ocr & OCR_NBUSY) == OCR_NBUSY {
+                break ocr & OCR_ACCESS_MODE != 0;
+            }
+            deadline
+                .check()
+                .map_err(|_| SdCardError::CommandTimeout(1))?;
+        };
+
+        // Send CMD2 to get CID, and CMD3 to assign RCA.
+        smhc.send_card_command(2, 0, TransferMode::Disable, ResponseMode::Long, true);
+        smhc.wait_for_command(2)?;
+        smhc.send_card_command(
+            3,
+            EMMC_RCA,
+            TransferMode::Disable,
+            ResponseMode::Short,
+            true,
+        );
+        smhc.wait_for_command(3)?;
+
+        // Send CMD9 to get CSD, holding capacity of byte addressed devices.
+        smhc.send_card_command(9, EMMC_RCA, TransferMode::Disable, ResponseMode::Long, true);
+        smhc.wait_for_command(9)?;
+        let csd_sector_count = csd_sector_count(smhc.read_response());
+
+        // Send CMD7 to select device.
+        smhc.send_card_command(
+            7,
+            EMMC_RCA,
+            TransferMode::Disable,
+            ResponseMode::Short,
+            true,
+        );
+        smhc.wait_for_command(7)?;
+        smhc.wait_for_card_ready()?;
+
+        let mut emmc = Emmc {
+            smhc,
+            rca: EMMC_RCA,
+            bus_width: BusWidth::OneBit,
+            ext_csd: ExtCsd([0; 512]),
+            sector_mode,
+            csd_sector_count,
+        };
+        emmc.ext_csd = emmc.read_ext_csd()?;
+        Ok(emmc)
+    }
+    /// Get the extended CSD register read on last configuration change.
+    #[inline]
+    pub fn ext_csd(&self) -> &ExtCsd {
+        &self.ext_csd
+    }
+    /// Read extended CSD register from the device.
+    #[inline]
+    pub fn read_ext_csd(&self) -> Result<ExtCsd, SdCardError> {
+        let mut block = Block::new();
+        self.smhc
+            .read_blocks_with(8, 0, core::slice::from_mut(&mut block), false)?;
+        Ok(ExtCsd(block.contents))
+    }
+    /// Write `value` into byte `index` of extended CSD register using CMD6.
+    #[inline]
+    pub fn switch(&self, index: u8, value: u8) -> Result<(), SdCardError> {
+        /// Write byte access mode.
+        const ACCESS_WRITE_BYTE: u32 = 0x3 << 24;
+        /// Device status bit indicating a failed switch.
+        const STATUS_SWITCH_ERROR: u32 = 1 << 7;
+
+        let arg = ACCESS_WRITE_BYTE | (index as u32) << 16 | (value as u32) << 8;
+        self.smhc
+            .send_card_command(6, arg, TransferMode::Disable, ResponseMode::Short, true);
+        self.smhc.wait_for_command(6)?;
+        self.smhc.wait_for_card_ready()?;
+        // Send CMD13 to check switch status.
+        self.smhc.send_card_command(
+            13,
+            self.rca,
+            TransferMode::Disable,
+            ResponseMode::Short,
+            true,
+        );
+        self.smhc.wait_for_command(13)?;
+        let status = self.smhc.read_response();
+        if status as u32 & STATUS_SWITCH_ERROR != 0 {
+            return Err(SdCardError::UnexpectedResponse(6, status));
+        }
+        Ok(())
+    }
+    /// Switch data bus width of device and controller.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SdCardError::Unsupported`] if controller pads lack data lines for `width`.
+    #[inline]
+    pub fn set_bus_width(&mut self, width: BusWidth) -> Result<(), SdCardError> {
+        if !self.smhc.supports_bus_width(width) {
+            return Err(SdCardError::Unsupported);
+        }
+        let value = match width {
+            BusWidth::OneBit => 0,
+            BusWidth::FourBit => 1,
+            BusWidth::EightBit => 2,
+        };
+        self.switch(183, value)?;
+        self.smhc.set_bus_width(width);
+        self.bus_width = width;
+        self.ext_csd = self.read_ext_csd()?;
+        Ok(())
+    }
+    /// Switch bus timing mode, changing card clock accordingly.
+    ///
+    /// Controller delay chains are programmed for the new timing before card clock changes.
+    /// HS200 requires 4-bit or 8-bit bus and 1.8 V I/O; sample delay is tuned afterwards.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SdCardError::Unsupported`] if the device or current bus width doesn't
+    /// support `timing`.
+    #[inline]
+    pub fn set_timing(
+        &mut self,
+        timing: Timing,
+        clocks: &Clocks,
+        ccu: &ccu::RegisterBlock,
+    ) -> Result<(), SdCardError> {
+        let value = match timing {
+            Timing::Legacy => 0,
+            Timing::HighSpeed if self.ext_csd.supports_high_speed() => 1,
+            Timing::Hs200
+                if self.ext_csd.supports_hs200() && self.bus_width != BusWidth::OneBit =>
+            {
+                2
+            }
+            _ => return Err(SdCardError::Unsupported),
+        };
+        self.switch(185, value)?;
+        self.smhc.set_sdr_delay_chains();
+        self.smhc.set_card_clock(timing.frequency(), clocks, ccu)?;
+        if let Timing::Hs200 = timing {
+            self.tune()?;
+        }
+        self.ext_csd = self.read_ext_csd()?;
+        Ok(())
+    }
+    /// Tune sample delay using CMD21.
+    #[inline]
+    fn tune(&self) -> Result<(), SdCardError> {
+        let pattern: &[u8] = match self.bus_width {
+            BusWidth::EightBit => &TUNING_BLOCK_8BIT,
+            _ => &TUNING_BLOCK_4BIT,
+        };
+        self.smhc.tune_sample_delay(21, pattern)
+    }
+    /// Get partition currently selected for access.
+    #[inline]
+    pub fn partition(&self) -> Partition {
+        self.ext_csd.partition_access().unwrap_or(Partition::User)
+    }
+    /// Select `partition` for following reads and writes.
+    #[inline]
+    pub fn select_partition(&mut self, partition: Partition) -> Result<(), SdCardError> {
+        let config = self.ext_csd.partition_config() & !ExtCsd::PARTITION_ACCESS;
+        self.switch(ExtCsd::PARTITION_CONFIG as u8, config | partition.access())?;
+        self.ext_csd = self.read_ext_csd()?;
+        Ok(())
+    }
+    /// Set partition the device boots from, or disable booting if `None`.
+    ///
+    /// If `boot_ack` is true, the device acknowledges boot operation to the host.
+    #[inline]
+    pub fn set_boot_partition(
+        &mut self,
+        partition: Option<Partition>,
+        boot_ack: bool,
+    ) -> Result<(), SdCardError> {
+        let enable = match partition {
+            None => 0,
+            Some(Partition::Boot0) => 1,
+            Some(Partition::Boot1) => 2,
+            Some(Partition::User) => 7,
+        };
+        let config = self.ext_csd.partition_config() & ExtCsd::PARTITION_ACCESS;
+        let config = config | enable << 3 | if boot_ack { ExtCsd::BOOT_ACK } else { 0 };
+        self.switch(ExtCsd::PARTITION_CONFIG as u8, config)?;
+        self.ext_csd = self.read_ext_csd()?;
+        Ok(())
+    }
+    /// Get the size of `partition` in 512-byte blocks.
+    #[inline]
+    pub fn partition_block_count(&self, partition: Partition) -> u32 {
+        match partition {
+            Partition::User if self.sector_mode => self.ext_csd.sector_count(),
+            Partition::User => self.csd_sector_count,
+            Partition::Boot0 | Partition::Boot1 => self.ext_csd.boot_sector_count(),
+        }
+    }
+    /// Get the size of currently selected partition in 512-byte blocks.
+    #[inline]
+    pub fn block_count(&self) -> u32 {
+        self.partition_block_count(self.partition())
+    }
+    /// Read consecutive blocks from the device, starting at `start_block_idx`.
+    #[inline]
+    pub fn read_blocks(
+        &self,
+        blocks: &mut [Block],
+        start_block_idx: u32,
+    ) -> Result<(), SdCardError> {
+        match blocks.len() {
+            0 => Ok(()),
+            1 => self
+                .smhc
+                .read_blocks_with(17, self.address(start_block_idx), blocks, false),
+            _ => {
+                for (i, chunk) in blocks.chunks_mut(DMA_MAX_BLOCKS).enumerate() {
+                    let block_idx = start_block_idx + (i * DMA_MAX_BLOCKS) as u32;
+                    self.smhc
+                        .read_blocks_with(18, self.address(block_idx), chunk, true)?;
+                }
+                Ok(())
+            }
+        }
+    }
+    /// Write consecutive blocks to the device, starting at `start_block_idx`.
+    #[inline]
+    pub fn write_blocks(&self, blocks: &[Block], start_block_idx: u32) -> Result<(), SdCardError> {
+        match blocks.len() {
+            0 => Ok(()),
+            1 => self
+                .smhc
+                .write_blocks_with(24, self.address(start_block_idx), blocks, false),
+            _ => {
+                for (i, chunk) in blocks.chunks(DMA_MAX_BLOCKS).enumerate() {
+                    let block_idx = start_block_idx + (i * DMA_MAX_BLOCKS) as u32;
+                    self.smhc
+                        .write_blocks_with(25, self.address(block_idx), chunk, true)?;
+                }
+                Ok(())
+            }
+        }
+    }
+    /// Write consecutive blocks using reliable write, starting at `start_block_idx`.
+    ///
+    /// Each chunk of blocks is either fully written or left unchanged on power loss.
+    /// Chunk size is limited by reliable write sector count unless enhanced reliable
+    /// write is supported by the device.
+    #[inline]
+    pub fn write_blocks_reliable(
+        &self,
+        blocks: &[Block],
+        start_block_idx: u32,
+    ) -> Result<(), SdCardError> {
+        /// Reliable write request flag of CMD23.
+        const RELIABLE_WRITE: u32 = 1 << 31;
+
+        let chunk_len = if self.ext_csd.supports_enhanced_reliable_write() {
+            DMA_MAX_BLOCKS
+        } else {
+            (self.ext_csd.reliable_write_sector_count() as usize).clamp(1, DMA_MAX_BLOCKS)
+        };
+        for (i, chunk) in blocks.chunks(chunk_len).enumerate() {
+            let block_idx = start_block_idx + (i * chunk_len) as u32;
+            // Send CMD23 to set block count of the following CMD25.
+            self.smhc.send_card_command(
+                23,
+                RELIABLE_WRITE | chunk.len() as u32,
+                TransferMode::Disable,
+                ResponseMode::Short,
+                true,
+            );
+            self.smhc.wait_for_command(23)?;
+            self.smhc
+                .write_blocks_with(25, self.address(block_idx), chunk, false)?;
+        }
+        Ok(())
+    }
+    /// Convert block index into command argument by addressing mode of the device.
+    #[inline]
+    fn address(&self, block_idx: u32) -> u32 {
+        if self.sector_mode {
+            block_idx
+        } else {
+            block_idx * Block::LEN as u32
+        }
+    }
+}
+
+impl<'a, S: AsRef<RegisterBlock>, const I: usize, P: Pads<I>> BlockDevice for Emmc<'a, S, I, P> {
+    type Error = SdCardError;
+
+    #[inline]
+    fn read_blocks(&self, blocks: &mut [Block], start_block_idx: u32) -> Result<(), Self::Error> {
+        Emmc::read_blocks(self, blocks, start_block_idx)
+    }
+    #[inline]
+    fn write_blocks(&self, blocks: &[Block], start_block_idx: u32) -> Result<(), Self::Error> {
+        Emmc::write_blocks(self, blocks, start_block_idx)
+    }
+    #[inline]
+    fn block_count(&self) -> u32 {
+        Emmc::block_count(self)
+    }
+}
+
+impl<'a, S: AsRef<RegisterBlock>, const I: usize, P: Pads<I>> embedded_sdmmc::BlockDevice
+    for Emmc<'a, S, I, P>
+{
+    type Error = SdCardError;
+
+    #[inline]
+    fn read(
+        &self,
+        blocks: &mut [Block],
+        start_block_idx: BlockIdx,
+        _reason: &str,
+    ) -> Result<(), Self::Error> {
+        self.read_blocks(blocks, start_block_idx.0)
+    }
+
+    #[inline]
+    fn write(&self, blocks: &[Block], start_block_idx: BlockIdx) -> Result<(), Self::Error> {
+        self.write_blocks(blocks, start_block_idx.0)
+    }
+
+    #[inline]
+    fn num_blocks(&self) -> Result<embedded_sdmmc::BlockCount, Self::Error> {
+        Ok(embedded_sdmmc::BlockCount(Emmc::block_count(self)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ExtCsd, Partition, Timing, csd_sector_count};
+
+    #[test]
+    fn struct_ext_csd_functions() {
+        let mut raw = [0u8; 512];
+        raw[212..216].copy_from_slice(&0x01D5_A000u32.to_le_bytes());
+        raw[196] = 0x17;
+        raw[183] = 2;
+        raw[185] = 1;
+        raw[222] = 1;
+        raw[166] = 0x04;
+        let ext_csd = ExtCsd(raw);
+        assert_eq!(ext_csd.sector_count(), 0x01D5_A000);
+        assert!(ext_csd.supports_high_speed());
+        assert!(ext_csd.supports_hs200());
+        assert_eq!(ext_csd.bus_width(), 2);
+        assert_eq!(ext_csd.hs_timing(), 1);
+        assert_eq!(ext_csd.reliable_write_sector_count(), 1);
+        assert!(ext_csd.supports_enhanced_reliable_write());
+
+        let ext_csd = ExtCsd([0; 512]);
+        assert!(!ext_csd.supports_high_speed());
+        assert!(!ext_csd.supports_hs200());
+        assert!(!ext_csd.supports_enhanced_reliable_write());
+    }
+
+    #[test]
+    fn struct_ext_csd_partition_functions() {
+        let mut raw = [0u8; 512];
+        raw[179] = 0x4A;
+        raw[226] = 0x20;
+        let ext_csd = ExtCsd(raw);
+        assert_eq!(ext_csd.partition_config(), 0x4A);
+        assert_eq!(ext_csd.partition_access(), Some(Partition::Boot1));
+        assert_eq!(ext_csd.boot_partition(), Some(Partition::Boot0));
+        assert!(ext_csd.is_boot_ack_enabled());
+        assert_eq!(ext_csd.boot_sector_count(), 8192);
+
+        raw[179] = 0x3B;
+        let ext_csd = ExtCsd(raw);
+        assert_eq!(ext_csd.partition_access(), None);
+        assert_eq!(ext_csd.boot_partition(), Some(Partition::User));
+        assert!(!ext_csd.is_boot_ack_enabled());
+    }
+
+    #[test]
+    fn enum_partition_functions() {
+        for partition in [Partition::User, Partition::Boot0, Partition::Boot1] {
+            assert_eq!(Partition::from_access(partition.access()), Some(partition));
+        }
+        assert_eq!(Partition::from_access(3), None);
+    }
+
+    #[test]
+    fn enum_timing_functions() {
+        assert_eq!(Timing::Legacy.frequency().0, 26_000_000);
+        assert_eq!(Timing::HighSpeed.frequency().0, 52_000_000);
+        assert_eq!(Timing::Hs200.frequency().0, 150_000_000);
+    }
+
+    #[test]
+    fn function_csd_sector_count() {
+        // C_SIZE 4095, C_SIZE_MULT 7, READ_BL_LEN 10: 2 GB device
+        let csd = (4095u128 << 62) | (7 << 47) | (10 << 80);
+        assert_eq!(csd_sector_count(csd), 4_194_304);
+        // C_SIZE 1023, C_SIZE_MULT 5, READ_BL_LEN 9: 64 MB device
+        let csd = (1023u128 << 62) | (5 << 47) | (9 << 80);
+        assert_eq!(csd_sector_count(csd), 131_072);
+    }
+}
@@ -0,0 +1,216 @@
+/// Address shift of buffer and descriptor addresses used by IDMAC.
+///
+/// On D1-like chips, IDMAC addresses are stored in 32-bit words instead of bytes.
+const ADDRESS_SHIFT: u32 = 2;
+
+/// Convert a byte address to address representation of IDMAC.
+#[inline]
+pub(crate) const fn idmac_address(address: usize) -> u32 {
+    (address >> ADDRESS_SHIFT) as u32
+}
+
+/// SMHC internal DMA controller (IDMAC) descriptor.
+///
+/// IDMAC reads descriptors from memory, they should stay valid until the transfer finishes.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[repr(C, align(4))]
+pub struct IdmacDescriptor {
+    config: u32,
+    buffer_size: u32,
+    buffer_address: u32,
+    next_descriptor: u32,
+}
+
+impl IdmacDescriptor {
+    const OWN: u32 = 1 << 31;
+    const ERR_FLAG: u32 = 1 << 30;
+    const CHAIN_MOD: u32 = 1 << 4;
+    const FIRST_FLAG: u32 = 1 << 3;
+    const LAST_FLAG: u32 = 1 << 2;
+    const CUR_TXRX_OVER_INT_DIS: u32 = 1 << 1;
+    const BUFFER_SIZE: u32 = 0x1FFF;
+
+    /// Maximum number of bytes transferred by one descriptor.
+    ///
+    /// Buffer size field is 13 bits wide; a zero field stands for this size.
+    pub const MAX_BUFFER_SIZE: usize = 0x2000;
+
+    /// Create an empty descriptor.
+    #[inline]
+    pub const fn new() -> Self {
+        Self {
+            config: 0,
+            buffer_size: 0,
+            buffer_address: 0,
+            next_descriptor: 0,
+        }
+    }
+    /// If descriptor is owned by IDMAC.
+    #[inline]
+    pub const fn is_owned_by_dma(&self) -> bool {
+        self.config & Self::OWN != 0
+    }
+    /// If IDMAC reported an error on this descriptor.
+    #[inline]
+    pub const fn has_error(&self) -> bool {
+        self.config & Self::ERR_FLAG != 0
+    }
+    /// If this is the first descriptor of a transfer.
+    #[inline]
+    pub const fn is_first(&self) -> bool {
+        self.config & Self::FIRST_FLAG != 0
+    }
+    /// If this is the last descriptor of a transfer.
+    #[inline]
+    pub const fn is_last(&self) -> bool {
+        self.config & Self::LAST_FLAG != 0
+    }
+    /// Get buffer size in bytes.
+    #[inline]
+    pub const fn buffer_size(&self) -> usize {
+        match self.buffer_size & Self::BUFFER_SIZE {
+            0 => Self::MAX_BUFFER_SIZE,
+            size => size as usize,
+        }
+    }
+    /// Get buffer address.
+    #[inline]
+    pub const fn buffer_address(&self) -> usize {
+        (self.buffer_address as usize) << ADDRESS_SHIFT
+    }
+    /// Get address of next descriptor.
+    #[inline]
+    pub const fn next_descriptor(&self) -> usize {
+        (self.next_descriptor as usize) << ADDRESS_SHIFT
+    }
+}
+
+impl Default for IdmacDescriptor {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Error on building IDMAC descriptor chain.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum IdmacChainError {
+    /// Buffer address or length is not aligned to 32-bit words.
+    Unaligned,
+    /// Not enough descriptors for the buffer.
+    TooLong,
+}
+
+/// Fill `descriptors` with a chain transferring `len` bytes of buffer at `address`.
+///
+/// On success, returns the number of descriptors used; all of them are handed to IDMAC
+/// by setting their OWN bits. Transfer-complete interrupt is raised on last descriptor only.
+#[inline]
+pub fn build_idmac_chain(
+    descriptors: &mut [IdmacDescriptor],
+    address: usize,
+    len: usize,
+) -> Result<usize, IdmacChainError> {
+    if !address.is_multiple_of(4) || !len.is_multiple_of(4) || len == 0 {
+        return Err(IdmacChainError::Unaligned);
+    }
+    let count = len.div_ceil(IdmacDescriptor::MAX_BUFFER_SIZE);
+    if count > descriptors.len() {
+        return Err(IdmacChainError::TooLong);
+    }
+    let base = descriptors.as_ptr() as usize;
+    let step = core::mem::size_of::<IdmacDescriptor>();
+    for (i, desc) in descriptors[..count].iter_mut().enumerate() {
+        let offset = i * IdmacDescriptor::MAX_BUFFER_SIZE;
+        let size = (len - offset).min(IdmacDescriptor::MAX_BUFFER_SIZE);
+        let mut config = IdmacDescriptor::OWN
+            | IdmacDescriptor::CHAIN_MOD
+            | IdmacDescriptor::CUR_TXRX_OVER_INT_DIS;
+        if i == 0 {
+            config |= IdmacDescriptor::FIRST_FLAG;
+        }
+        if i == count - 1 {
+            config &= !IdmacDescriptor::CUR_TXRX_OVER_INT_DIS;
+            config |= IdmacDescriptor::LAST_FLAG;
+        }
+        let next = if i == count - 1 {
+            0
+        } else {
+            base + (i + 1) * step
+        };
+        *desc = IdmacDescriptor {
+            config,
+            buffer_size: size as u32 & IdmacDescriptor::BUFFER_SIZE,
+            buffer_address: idmac_address(address + offset),
+            next_descriptor: idmac_address(next),
+        };
+    }
+    Ok(count)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{IdmacChainError, IdmacDescriptor, build_idmac_chain};
+    use core::mem::{offset_of, size_of};
+
+    #[test]
+    fn offset_idmac_descriptor() {
+        assert_eq!(offset_of!(IdmacDescriptor, config), 0x00);
+        assert_eq!(offset_of!(IdmacDescriptor, buffer_size), 0x04);
+        assert_eq!(offset_of!(IdmacDescriptor, buffer_address), 0x08);
+        assert_eq!(offset_of!(IdmacDescriptor, next_descriptor), 0x0C);
+        assert_eq!(size_of::<IdmacDescriptor>(), 0x10);
+    }
+
+    #[test]
+    fn struct_idmac_chain_functions() {
+        let mut descriptors = [IdmacDescriptor::new(); 4];
+        let len = IdmacDescriptor::MAX_BUFFER_SIZE * 2 + 512;
+        assert_eq!(build_idmac_chain(&mut descriptors, 0x4000_0000, len), Ok(3));
+        assert_eq!(descriptors[0].config, 0x8000_001A);
+        assert_eq!(descriptors[1].config, 0x8000_0012);
+        assert_eq!(descriptors[2].config, 0x8000_0014);
+        assert!(descriptors[0].is_owned_by_dma() && descriptors[0].is_first());
+        assert!(descriptors[2].is_last());
+        assert_eq!(descriptors[0].buffer_address(), 0x4000_0000);
+        assert_eq!(descriptors[1].buffer_address(), 0x4000_2000);
+        assert_eq!(descriptors[2].buffer_address(), 0x4000_4000);
+        assert_eq!(descriptors[0].buffer_size, 0);
+        assert_eq!(descriptors[0].buffer_size(), 0x2000);
+        assert_eq!(descriptors[2].buffer_size, 512);
+        assert_eq!(descriptors[2].buffer_size(), 512);
+        assert_eq!(
+            descriptors[0].next_descriptor,
+            ((&descriptors[1] as *const IdmacDescriptor as usize) >> 2) as u32
+        );
+        assert_eq!(descriptors[2].next_descriptor(), 0);
+        assert_eq!(descriptors[3], IdmacDescriptor::new());
+
+        assert_eq!(
+            build_idmac_chain(&mut descriptors, 0x4000_0002, 512),
+            Err(IdmacChainError::Unaligned)
+        );
+        assert_eq!(
+            build_idmac_chain(&mut descriptors, 0x4000_0000, 0x2000 * 5),
+            Err(IdmacChainError::TooLong)
+        );
+    }
+
+    #[test]
+    fn function_build_idmac_chain() {
+        // 64 KiB over 8 descriptors; no descriptor exceeds 13-bit buffer size field.
+        let mut descriptors = [IdmacDescriptor::new(); 8];
+        let len = 64 * 1024;
+        assert_eq!(build_idmac_chain(&mut descriptors, 0x4000_0000, len), Ok(8));
+        for (i, desc) in descriptors.iter().enumerate() {
+            assert_eq!(desc.buffer_size & !0x1FFF, 0);
+            assert_eq!(desc.buffer_size(), 8 * 1024);
+            assert_eq!(desc.buffer_address(), 0x4000_0000 + i * 8 * 1024);
+        }
+        assert!(descriptors[7].is_last());
+        assert_eq!(
+            build_idmac_chain(&mut descriptors, 0x4000_0000, len + 512),
+            Err(IdmacChainError::TooLong)
+        );
+    }
+}
@@ -0,0 +1,269 @@
+use embedded_time::rate::Hertz;
+
+/// SD card identification register.
+///
+/// Fields are located by bit positions of the 128-bit register, as in SD specification.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct Cid(pub u128);
+
+impl Cid {
+    /// Get manufacturer ID.
+    #[inline]
+    pub const fn manufacturer_id(self) -> u8 {
+        (self.0 >> 120) as u8
+    }
+    /// Get OEM/application ID in ASCII characters.
+    #[inline]
+    pub const fn oem_id(self) -> [u8; 2] {
+        ((self.0 >> 104) as u16).to_be_bytes()
+    }
+    /// Get product name in ASCII characters.
+    #[inline]
+    pub const fn product_name(self) -> [u8; 5] {
+        let bytes = ((self.0 >> 64) as u64).to_be_bytes();
+        [bytes[3], bytes[4], bytes[5], bytes[6], bytes[7]]
+    }
+    /// Get product revision as major and minor numbers.
+    #[inline]
+    pub const fn product_revision(self) -> (u8, u8) {
+        let prv = (self.0 >> 56) as u8;
+        (prv >> 4, prv & 0xF)
+    }
+    /// Get product serial number.
+    #[inline]
+    pub const fn serial_number(self) -> u32 {
+        (self.0 >> 24) as u32
+    }
+    /// Get manufacturing date as year and month.
+    #[inline]
+    pub const fn manufacturing_date(self) -> (u16, u8) {
+        let mdt = ((self.0 >> 8) & 0xFFF) as u16;
+        (2000 + (mdt >> 4), (mdt & 0xF) as u8)
+    }
+}
+
+/// SD card specific data register, version 1.0 or 2.0.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct Csd(pub u128);
+
+impl Csd {
+    /// Get CSD structure version, 0 for version 1.0 and 1 for version 2.0.
+    #[inline]
+    pub const fn structure(self) -> u8 {
+        (self.0 >> 126) as u8
+    }
+    /// Get supported command classes, bit `n` for class `n`.
+    #[inline]
+    pub const fn command_classes(self) -> u16 {
+        ((self.0 >> 84) & 0xFFF) as u16
+    }
+    /// Get maximum data transfer rate per data line in default speed mode.
+    #[inline]
+    pub const fn max_transfer_rate(self) -> Hertz {
+        /// Time values multiplied by 10.
+        const VALUES: [u32; 16] = [
+            0, 10, 12, 13, 15, 20, 25, 30, 35, 40, 45, 50, 55, 60, 70, 80,
+        ];
+        let tran_speed = (self.0 >> 96) as u8;
+        let unit = match tran_speed & 0x7 {
+            0 => 10_000,
+            1 => 100_000,
+            2 => 1_000_000,
+            3 => 10_000_000,
+            _ => 0,
+        };
+        Hertz(VALUES[((tran_speed >> 3) & 0xF) as usize] * unit)
+    }
+    /// Get maximum read data block length in bytes.
+    #[inline]
+    pub const fn read_block_length(self) -> u32 {
+        1 << ((self.0 >> 80) & 0xF) as u32
+    }
+    /// Get card capacity in bytes.
+    #[inline]
+    pub const fn capacity(self) -> u64 {
+        match self.structure() {
+            0 => {
+                let c_size = ((self.0 >> 62) & 0xFFF) as u64;
+                let c_size_mult = ((self.0 >> 47) & 0x7) as u64;
+                let read_bl_len = ((self.0 >> 80) & 0xF) as u64;
+                (c_size + 1) << (c_size_mult + 2 + read_bl_len)
+            }
+            1 => {
+                let c_size = ((self.0 >> 48) & 0x3F_FFFF) as u64;
+                (c_size + 1) * 512 * 1024
+            }
+            _ => 0,
+        }
+    }
+    /// Get card capacity in 512-byte blocks.
+    #[inline]
+    pub const fn block_count(self) -> u32 {
+        (self.capacity() / 512) as u32
+    }
+}
+
+/// SD card configuration register.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct Scr(pub u64);
+
+impl Scr {
+    const SD_SPEC: u64 = 0xF << 56;
+    const DATA_STAT_AFTER_ERASE: u64 = 1 << 55;
+    const SD_BUS_WIDTH_4: u64 = 1 << 50;
+    const SD_SPEC3: u64 = 1 << 47;
+    const SD_SPEC4: u64 = 1 << 42;
+    const SD_SPECX: u64 = 0xF << 38;
+    const CMD23_SUPPORT: u64 = 1 << 33;
+
+    /// Get major version of physical layer specification supported by the card.
+    #[inline]
+    pub const fn spec_version(self) -> u8 {
+        let sd_spec = ((self.0 & Self::SD_SPEC) >> 56) as u8;
+        let sd_specx = ((self.0 & Self::SD_SPECX) >> 38) as u8;
+        match sd_spec {
+            0 | 1 => 1,
+            2 if self.0 & Self::SD_SPEC3 == 0 => 2,
+            2 if sd_specx != 0 => 4 + sd_specx,
+            2 if self.0 & Self::SD_SPEC4 != 0 => 4,
+            _ => 3,
+        }
+    }
+    /// If card data is all ones after erase.
+    #[inline]
+    pub const fn data_after_erase(self) -> bool {
+        self.0 & Self::DATA_STAT_AFTER_ERASE != 0
+    }
+    /// If card supports 4-bit data bus.
+    #[inline]
+    pub const fn supports_four_bit_bus(self) -> bool {
+        self.0 & Self::SD_BUS_WIDTH_4 != 0
+    }
+    /// If card supports setting block count with CMD23.
+    #[inline]
+    pub const fn supports_set_block_count(self) -> bool {
+        self.0 & Self::CMD23_SUPPORT != 0
+    }
+}
+
+/// SD status register, read by ACMD13.
+///
+/// Fields are located by bit positions of the 512-bit register, sent most significant byte
+/// first.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct SdStatus(pub [u8; 64]);
+
+impl SdStatus {
+    /// Get speed class, the minimum sequential write speed in MB/s: 0, 2, 4, 6 or 10.
+    ///
+    /// Class 0 means the card is not classified.
+    #[inline]
+    pub const fn speed_class(&self) -> u8 {
+        // bits 447:440
+        match self.0[8] {
+            1 => 2,
+            2 => 4,
+            3 => 6,
+            4 => 10,
+            _ => 0,
+        }
+    }
+    /// Get UHS speed grade, the minimum sequential write speed in MB/s of UHS-I bus
+    /// modes: 0, 1 for U1 (10 MB/s) or 3 for U3 (30 MB/s).
+    #[inline]
+    pub const fn uhs_speed_grade(&self) -> u8 {
+        // bits 399:396
+        self.0[14] >> 4
+    }
+    /// Get video speed class, the minimum sequential write speed in MB/s: 0, 6, 10, 30, 60
+    /// or 90.
+    #[inline]
+    pub const fn video_speed_class(&self) -> u8 {
+        // bits 391:384
+        self.0[15]
+    }
+    /// Get application performance class: 0, 1 for A1 or 2 for A2.
+    #[inline]
+    pub const fn app_performance_class(&self) -> u8 {
+        // bits 339:336
+        self.0[21] & 0xF
+    }
+}
+
+/// SD card information read on initialization.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct SdCardInfo {
+    /// Card identification.
+    pub cid: Cid,
+    /// Card specific data.
+    pub csd: Csd,
+    /// Card configuration.
+    pub scr: Scr,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Cid, Csd, Scr, SdStatus};
+
+    #[test]
+    fn struct_cid_functions() {
+        let cid = Cid(0x0353_4453_4430_3847_8012_3456_7801_0301);
+        assert_eq!(cid.manufacturer_id(), 0x03);
+        assert_eq!(&cid.oem_id(), b"SD");
+        assert_eq!(&cid.product_name(), b"SD08G");
+        assert_eq!(cid.product_revision(), (8, 0));
+        assert_eq!(cid.serial_number(), 0x1234_5678);
+        assert_eq!(cid.manufacturing_date(), (2016, 3));
+    }
+
+    #[test]
+    fn struct_csd_functions() {
+        let csd = Csd(0x400E_0032_5B59_0000_3B37_7F80_0A40_4000);
+        assert_eq!(csd.structure(), 1);
+        assert_eq!(csd.command_classes(), 0x5B5);
+        assert_eq!(csd.max_transfer_rate().0, 25_000_000);
+        assert_eq!(csd.read_block_length(), 512);
+        assert_eq!(csd.block_count(), (0x3B37 + 1) * 1024);
+
+        let csd = Csd(0x005E_0032_5F5A_83C8_EDB7_BF9F_9640_0000);
+        assert_eq!(csd.structure(), 0);
+        assert_eq!(csd.read_block_length(), 1024);
+        assert_eq!(csd.capacity(), (0xF23 + 1) << (7 + 2 + 10));
+    }
+
+    #[test]
+    fn struct_scr_functions() {
+        let scr = Scr(0x0235_8003_0000_0000);
+        assert_eq!(scr.spec_version(), 3);
+        assert!(!scr.data_after_erase());
+        assert!(scr.supports_four_bit_bus());
+        assert!(scr.supports_set_block_count());
+
+        let scr = Scr(0x0125_0000_0000_0000);
+        assert_eq!(scr.spec_version(), 1);
+        assert!(!scr.supports_set_block_count());
+    }
+
+    #[test]
+    fn struct_sd_status_functions() {
+        let mut status = [0u8; 64];
+        status[0] = 0x80;
+        status[8] = 0x04;
+        status[14] = 0x39;
+        status[15] = 0x1E;
+        status[21] = 0x02;
+        let status = SdStatus(status);
+        assert_eq!(status.speed_class(), 10);
+        assert_eq!(status.uhs_speed_grade(), 3);
+        assert_eq!(status.video_speed_class(), 30);
+        assert_eq!(status.app_performance_class(), 2);
+
+        let status = SdStatus([0; 64]);
+        assert_eq!(status.speed_class(), 0);
+        assert_eq!(status.uhs_speed_grade(), 0);
+    }
+}
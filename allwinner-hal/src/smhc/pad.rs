@@ -1,10 +1,61 @@
-/// Clock signal pad.
-pub trait Clk {}
+use super::BusWidth;
+use crate::ccu::{self, SmhcClockSource};
 
-/// Command signal pad.
-pub trait Cmd {}
+/// Valid SMHC pads.
+pub trait Pads<const I: usize> {
+    /// Widest data bus width supported by these pads.
+    const BUS_WIDTH: BusWidth;
+    type Clock: ccu::ClockGate + ccu::ClockConfig<Source = SmhcClockSource>;
+}
 
-/// Data input and output pad.
+/// Clock signal pad for SMHC `I`.
+pub trait Clk<const I: usize> {}
+
+/// Command signal pad for SMHC `I`.
+pub trait Cmd<const I: usize> {}
+
+/// Data input and output pad `N` for SMHC `I`.
 ///
-/// This is documented in the User Manual as `D[3:0]`.
-pub trait Data<const I: usize> {}
+/// This is documented in the User Manual as `D[3:0]`, or `D[7:0]` for 8-bit eMMC.
+pub trait Data<const I: usize, const N: usize> {}
+
+impl<const I: usize, CLK, CMD, D0> Pads<I> for (CLK, CMD, D0)
+where
+    CLK: Clk<I>,
+    CMD: Cmd<I>,
+    D0: Data<I, 0>,
+{
+    const BUS_WIDTH: BusWidth = BusWidth::OneBit;
+    type Clock = ccu::SMHC<I>;
+}
+
+impl<const I: usize, CLK, CMD, D0, D1, D2, D3> Pads<I> for (CLK, CMD, D0, D1, D2, D3)
+where
+    CLK: Clk<I>,
+    CMD: Cmd<I>,
+    D0: Data<I, 0>,
+    D1: Data<I, 1>,
+    D2: Data<I, 2>,
+    D3: Data<I, 3>,
+{
+    const BUS_WIDTH: BusWidth = BusWidth::FourBit;
+    type Clock = ccu::SMHC<I>;
+}
+
+impl<const I: usize, CLK, CMD, D0, D1, D2, D3, D4, D5, D6, D7> Pads<I>
+    for (CLK, CMD, D0, D1, D2, D3, D4, D5, D6, D7)
+where
+    CLK: Clk<I>,
+    CMD: Cmd<I>,
+    D0: Data<I, 0>,
+    D1: Data<I, 1>,
+    D2: Data<I, 2>,
+    D3: Data<I, 3>,
+    D4: Data<I, 4>,
+    D5: Data<I, 5>,
+    D6: Data<I, 6>,
+    D7: Data<I, 7>,
+{
+    const BUS_WIDTH: BusWidth = BusWidth::EightBit;
+    type Clock = ccu::SMHC<I>;
+}
@@ -36,13 +36,13 @@ pub struct RegisterBlock {
     pub new_timing_set: RW<NewTimingSet>,
     _reserved1: [u32; 8],
     /// 0x80 - SMC IDMAC Control Register.
-    pub dma_control: RW<u32>,
+    pub dma_control: RW<DmaControl>,
     /// 0x84 - SMC IDMAC Descriptor List Base Address Register.
     pub dma_descriptor_base: RW<u32>,
     /// 0x88 - SMC IDMAC Status Register.
-    pub dma_state: RW<u32>,
+    pub dma_state: RW<DmaState>,
     /// 0x8C - SMC IDMAC Interrupt Enable Register.
-    pub dma_interrupt_enable: RW<u32>,
+    pub dma_interrupt_enable: RW<DmaInterruptEnable>,
     _reserved2: [u32; 44],
     /// 0x140 - Drive Delay Control register.
     pub drive_delay_control: RW<DriveDelayControl>,
@@ -359,6 +359,7 @@ pub enum TransferDirection {
 
 impl Command {
     const CMD_LOAD: u32 = 0x1 << 31;
+    const VOL_SW: u32 = 0x1 << 28;
     const PRG_CLK: u32 = 0x1 << 21;
     const SEND_INIT_SEQ: u32 = 0x1 << 15;
     const STOP_ABT_CMD: u32 = 0x1 << 14;
@@ -376,6 +377,21 @@ impl Command {
     pub const fn set_command_start(self) -> Self {
         Self(self.0 | Self::CMD_LOAD)
     }
+    /// If voltage switch is enabled.
+    #[inline]
+    pub const fn is_voltage_switch_enabled(self) -> bool {
+        (self.0 & Self::VOL_SW) != 0
+    }
+    /// Enable voltage switch, set for CMD11 and clock changes during the switch.
+    #[inline]
+    pub const fn enable_voltage_switch(self) -> Self {
+        Self(self.0 | Self::VOL_SW)
+    }
+    /// Disable voltage switch.
+    #[inline]
+    pub const fn disable_voltage_switch(self) -> Self {
+        Self(self.0 & !Self::VOL_SW)
+    }
     /// If change clock is enabled.
     #[inline]
     pub const fn is_change_clock_enabled(self) -> bool {
@@ -816,6 +832,13 @@ impl InterruptStateRaw {
     }
 }
 
+impl Default for InterruptStateRaw {
+    #[inline]
+    fn default() -> Self {
+        Self(0x0000_0000)
+    }
+}
+
 /// State register.
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 #[repr(transparent)]
@@ -962,6 +985,195 @@ impl NewTimingSet {
     }
 }
 
+/// IDMAC control register.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct DmaControl(u32);
+
+impl DmaControl {
+    const IDMAC_ENB: u32 = 1 << 7;
+    const FIX_BUST_CTRL: u32 = 1 << 1;
+    const IDMAC_RST: u32 = 1 << 0;
+
+    /// Is IDMAC enabled?
+    #[inline]
+    pub const fn is_idmac_enabled(self) -> bool {
+        self.0 & Self::IDMAC_ENB != 0
+    }
+    /// Enable IDMAC.
+    #[inline]
+    pub const fn enable_idmac(self) -> Self {
+        Self(self.0 | Self::IDMAC_ENB)
+    }
+    /// Disable IDMAC.
+    #[inline]
+    pub const fn disable_idmac(self) -> Self {
+        Self(self.0 & !Self::IDMAC_ENB)
+    }
+    /// Is fixed burst enabled?
+    #[inline]
+    pub const fn is_fixed_burst_enabled(self) -> bool {
+        self.0 & Self::FIX_BUST_CTRL != 0
+    }
+    /// Enable fixed burst.
+    #[inline]
+    pub const fn enable_fixed_burst(self) -> Self {
+        Self(self.0 | Self::FIX_BUST_CTRL)
+    }
+    /// Disable fixed burst.
+    #[inline]
+    pub const fn disable_fixed_burst(self) -> Self {
+        Self(self.0 & !Self::FIX_BUST_CTRL)
+    }
+    /// IDMAC Reset.
+    #[inline]
+    pub const fn set_idmac_reset(self) -> Self {
+        Self(self.0 | Self::IDMAC_RST)
+    }
+    /// Is IDMAC Reset signal cleared by hardware?
+    #[inline]
+    pub const fn is_idmac_reset_cleared(self) -> bool {
+        (self.0 & Self::IDMAC_RST) == 0
+    }
+}
+
+impl Default for DmaControl {
+    #[inline]
+    fn default() -> Self {
+        Self(0x0000_0000)
+    }
+}
+
+/// IDMAC interrupt type.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum DmaInterrupt {
+    /// Data transmission of a descriptor finished.
+    TransmitComplete,
+    /// Data reception of a descriptor finished.
+    ReceiveComplete,
+    /// Fatal bus error occurred.
+    FatalBusError,
+    /// Descriptor is not owned by IDMAC.
+    DescriptorUnavailable,
+    /// Card error summary.
+    CardErrorSummary,
+    /// Normal interrupt summary.
+    NormalSummary,
+    /// Abnormal interrupt summary.
+    AbnormalSummary,
+}
+
+/// IDMAC status register.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct DmaState(u32);
+
+impl DmaState {
+    const TX_INT: u32 = 1 << 0;
+    const RX_INT: u32 = 1 << 1;
+    const FATAL_BERR: u32 = 1 << 2;
+    const DES_UNAVL: u32 = 1 << 4;
+    const ERR_FLAG_SUM: u32 = 1 << 5;
+    const NOR_INT_SUM: u32 = 1 << 8;
+    const ABN_INT_SUM: u32 = 1 << 9;
+
+    /// If the interrupt occurs.
+    #[inline]
+    pub const fn has_interrupt(self, interrupt: DmaInterrupt) -> bool {
+        match interrupt {
+            DmaInterrupt::TransmitComplete => self.0 & Self::TX_INT != 0,
+            DmaInterrupt::ReceiveComplete => self.0 & Self::RX_INT != 0,
+            DmaInterrupt::FatalBusError => self.0 & Self::FATAL_BERR != 0,
+            DmaInterrupt::DescriptorUnavailable => self.0 & Self::DES_UNAVL != 0,
+            DmaInterrupt::CardErrorSummary => self.0 & Self::ERR_FLAG_SUM != 0,
+            DmaInterrupt::NormalSummary => self.0 & Self::NOR_INT_SUM != 0,
+            DmaInterrupt::AbnormalSummary => self.0 & Self::ABN_INT_SUM != 0,
+        }
+    }
+    /// Clears the specified interrupt.
+    #[inline]
+    pub const fn clear_interrupt(self, interrupt: DmaInterrupt) -> Self {
+        match interrupt {
+            DmaInterrupt::TransmitComplete => Self(self.0 | Self::TX_INT),
+            DmaInterrupt::ReceiveComplete => Self(self.0 | Self::RX_INT),
+            DmaInterrupt::FatalBusError => Self(self.0 | Self::FATAL_BERR),
+            DmaInterrupt::DescriptorUnavailable => Self(self.0 | Self::DES_UNAVL),
+            DmaInterrupt::CardErrorSummary => Self(self.0 | Self::ERR_FLAG_SUM),
+            DmaInterrupt::NormalSummary => Self(self.0 | Self::NOR_INT_SUM),
+            DmaInterrupt::AbnormalSummary => Self(self.0 | Self::ABN_INT_SUM),
+        }
+    }
+}
+
+impl Default for DmaState {
+    #[inline]
+    fn default() -> Self {
+        Self(0x0000_0000)
+    }
+}
+
+/// IDMAC interrupt enable register.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct DmaInterruptEnable(u32);
+
+impl DmaInterruptEnable {
+    const TX_INT_ENB: u32 = 1 << 0;
+    const RX_INT_ENB: u32 = 1 << 1;
+    const FATAL_BERR_ENB: u32 = 1 << 2;
+    const DES_UNAVL_ENB: u32 = 1 << 4;
+    const ERR_FLAG_SUM_ENB: u32 = 1 << 5;
+    const NOR_INT_SUM_ENB: u32 = 1 << 8;
+    const ABN_INT_SUM_ENB: u32 = 1 << 9;
+
+    /// If the interrupt is enabled.
+    #[inline]
+    pub const fn is_interrupt_enabled(self, interrupt: DmaInterrupt) -> bool {
+        match interrupt {
+            DmaInterrupt::TransmitComplete => self.0 & Self::TX_INT_ENB != 0,
+            DmaInterrupt::ReceiveComplete => self.0 & Self::RX_INT_ENB != 0,
+            DmaInterrupt::FatalBusError => self.0 & Self::FATAL_BERR_ENB != 0,
+            DmaInterrupt::DescriptorUnavailable => self.0 & Self::DES_UNAVL_ENB != 0,
+            DmaInterrupt::CardErrorSummary => self.0 & Self::ERR_FLAG_SUM_ENB != 0,
+            DmaInterrupt::NormalSummary => self.0 & Self::NOR_INT_SUM_ENB != 0,
+            DmaInterrupt::AbnormalSummary => self.0 & Self::ABN_INT_SUM_ENB != 0,
+        }
+    }
+    /// Enable the specified interrupt.
+    #[inline]
+    pub const fn enable_interrupt(self, interrupt: DmaInterrupt) -> Self {
+        match interrupt {
+            DmaInterrupt::TransmitComplete => Self(self.0 | Self::TX_INT_ENB),
+            DmaInterrupt::ReceiveComplete => Self(self.0 | Self::RX_INT_ENB),
+            DmaInterrupt::FatalBusError => Self(self.0 | Self::FATAL_BERR_ENB),
+            DmaInterrupt::DescriptorUnavailable => Self(self.0 | Self::DES_UNAVL_ENB),
+            DmaInterrupt::CardErrorSummary => Self(self.0 | Self::ERR_FLAG_SUM_ENB),
+            DmaInterrupt::NormalSummary => Self(self.0 | Self::NOR_INT_SUM_ENB),
+            DmaInterrupt::AbnormalSummary => Self(self.0 | Self::ABN_INT_SUM_ENB),
+        }
+    }
+    /// Disable the specified interrupt.
+    #[inline]
+    pub const fn disable_interrupt(self, interrupt: DmaInterrupt) -> Self {
+        match interrupt {
+            DmaInterrupt::TransmitComplete => Self(self.0 & !Self::TX_INT_ENB),
+            DmaInterrupt::ReceiveComplete => Self(self.0 & !Self::RX_INT_ENB),
+            DmaInterrupt::FatalBusError => Self(self.0 & !Self::FATAL_BERR_ENB),
+            DmaInterrupt::DescriptorUnavailable => Self(self.0 & !Self::DES_UNAVL_ENB),
+            DmaInterrupt::CardErrorSummary => Self(self.0 & !Self::ERR_FLAG_SUM_ENB),
+            DmaInterrupt::NormalSummary => Self(self.0 & !Self::NOR_INT_SUM_ENB),
+            DmaInterrupt::AbnormalSummary => Self(self.0 & !Self::ABN_INT_SUM_ENB),
+        }
+    }
+}
+
+impl Default for DmaInterruptEnable {
+    #[inline]
+    fn default() -> Self {
+        Self(0x0000_0000)
+    }
+}
+
 /// Drive Delay Control register.
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 #[repr(transparent)]
@@ -1050,9 +1262,10 @@ impl SampleDelayControl {
 mod tests {
     use super::{
         AccessMode, Argument, BlockSize, BurstSize, BusWidth, ByteCount, CardType, ClockControl,
-        Command, DdcTimingPhase, DdrMode, DriveDelayControl, FifoWaterLevel, GlobalControl,
-        Interrupt, InterruptMask, InterruptStateMasked, InterruptStateRaw, NewTimingSet,
-        NtsTimingPhase, RegisterBlock, Status, TimeOut, TransferDirection,
+        Command, DdcTimingPhase, DdrMode, DmaControl, DmaInterrupt, DmaInterruptEnable, DmaState,
+        DriveDelayControl, FifoWaterLevel, GlobalControl, Interrupt, InterruptMask,
+        InterruptStateMasked, InterruptStateRaw, NewTimingSet, NtsTimingPhase, RegisterBlock,
+        Status, TimeOut, TransferDirection,
     };
     use core::mem::offset_of;
     #[test]
@@ -1223,6 +1436,14 @@ mod tests {
         assert!(val.is_command_start_cleared());
 
         val = Command(0x0);
+        val = val.enable_voltage_switch();
+        assert!(val.is_voltage_switch_enabled());
+        assert_eq!(val.0, 0x10000000);
+
+        val = val.disable_voltage_switch();
+        assert!(!val.is_voltage_switch_enabled());
+        assert_eq!(val.0, 0x00000000);
+
         val = val.enable_change_clock();
         assert!(val.is_change_clock_enabled());
         assert_eq!(val.0, 0x00200000);
@@ -1591,4 +1812,53 @@ mod tests {
         assert_eq!(val.command_drive_phase(), DdcTimingPhase::Sdr90Ddr45);
         assert_eq!(val.0, 0x00000000);
     }
+
+    #[test]
+    fn struct_dma_control_functions() {
+        let mut val = DmaControl::default();
+
+        val = val.enable_idmac();
+        assert!(val.is_idmac_enabled());
+        assert_eq!(val.0, 0x00000080);
+
+        val = val.disable_idmac();
+        assert!(!val.is_idmac_enabled());
+        assert_eq!(val.0, 0x00000000);
+
+        val = val.enable_fixed_burst();
+        assert!(val.is_fixed_burst_enabled());
+        assert_eq!(val.0, 0x00000002);
+
+        val = val.disable_fixed_burst();
+        assert!(!val.is_fixed_burst_enabled());
+        assert_eq!(val.0, 0x00000000);
+
+        val = val.set_idmac_reset();
+        assert!(!val.is_idmac_reset_cleared());
+        assert_eq!(val.0, 0x00000001);
+    }
+
+    #[test]
+    fn struct_dma_state_and_interrupt_enable_functions() {
+        for (interrupt, bits) in [
+            (DmaInterrupt::TransmitComplete, 0x00000001),
+            (DmaInterrupt::ReceiveComplete, 0x00000002),
+            (DmaInterrupt::FatalBusError, 0x00000004),
+            (DmaInterrupt::DescriptorUnavailable, 0x00000010),
+            (DmaInterrupt::CardErrorSummary, 0x00000020),
+            (DmaInterrupt::NormalSummary, 0x00000100),
+            (DmaInterrupt::AbnormalSummary, 0x00000200),
+        ] {
+            let val = DmaState::default().clear_interrupt(interrupt);
+            assert!(val.has_interrupt(interrupt));
+            assert_eq!(val.0, bits);
+
+            let mut val = DmaInterruptEnable::default().enable_interrupt(interrupt);
+            assert!(val.is_interrupt_enabled(interrupt));
+            assert_eq!(val.0, bits);
+            val = val.disable_interrupt(interrupt);
+            assert!(!val.is_interrupt_enabled(interrupt));
+            assert_eq!(val.0, 0x00000000);
+        }
+    }
 }
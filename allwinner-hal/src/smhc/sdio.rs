@@ -0,0 +1,427 @@
+use super::{
+    Pads, ResponseMode, SdCardError, TransferMode,
+    register::{BusWidth, Interrupt, RegisterBlock},
+    structure::{Smhc, command},
+};
+use crate::time::Deadline;
+
+/// SDIO function number, 0 for common I/O area (CCCR) and 1 to 7 for card functions.
+pub type Function = u8;
+
+/// Blocking SDIO card driver over an SMHC peripheral.
+pub struct Sdio<'a, S, const I: usize, P: Pads<I>> {
+    smhc: &'a mut Smhc<S, I, P>,
+    functions: u8,
+    // CMD53 block size of each function, 0 until set.
+    block_sizes: [u16; 8],
+}
+
+/// Card common control register addresses.
+mod cccr {
+    /// I/O enable.
+    pub const IO_ENABLE: u32 = 0x02;
+    /// I/O ready.
+    pub const IO_READY: u32 = 0x03;
+    /// Interrupt enable.
+    pub const INT_ENABLE: u32 = 0x04;
+    /// Interrupt pending.
+    pub const INT_PENDING: u32 = 0x05;
+    /// Bus interface control.
+    pub const BUS_INTERFACE: u32 = 0x07;
+}
+
+impl<'a, S: AsRef<RegisterBlock>, const I: usize, P: Pads<I>> Sdio<'a, S, I, P> {
+    /// Create an SDIO instance, initializing the card in 1-bit mode.
+    #[inline]
+    pub fn new(smhc: &'a mut Smhc<S, I, P>) -> Result<Self, SdCardError> {
+        /// Card is ready to operate after initialization.
+        const OCR_READY: u32 = 0x80000000;
+        /// Valid bits for voltage setting, 3.2-3.4 V.
+        const OCR_VOLTAGE_MASK: u32 = 0x00300000;
+
+        // CMD0(reset) -> CMD5(read OCR, then wait for card ready)
+        smhc.send_card_command(0, 0, TransferMode::Disable, ResponseMode::Disable, false);
+        smhc.wait_for_command(0)?;
+        smhc.send_card_command(5, 0, TransferMode::Disable, ResponseMode::Short, false);
+        smhc.wait_for_command(5)?;
+        let ocr = smhc.read_response() as u32;
+        let functions = ((ocr >> 28) & 0x7) as u8;
+        let deadline = Deadline::start();
+        loop {
+            smhc.send_card_command(
+                5,
+                ocr & OCR_VOLTAGE_MASK,
+                TransferMode::Disable,
+                ResponseMode::Short,
+                false,
+            );
+            smhc.wait_for_command(5)?;
+            if smhc.read_response() as u32 & OCR_READY != 0 {
+                break;
+            }
+            deadline
+                .check()
+                .map_err(|_| SdCardError::CommandTimeout(5))?;
+        }
+
+        // Send CMD3 to get RCA.
+        smhc.send_card_command(3, 0, TransferMode::Disable, ResponseMode::Short, true);
+        smhc.wait_for_command(3)?;
+        let rca = smhc.read_response() as u32 & 0xFFFF0000;
+
+        // Send CMD7 to select card.
+        smhc.send_card_command(7, rca, TransferMode::Disable, ResponseMode::Short, true);
+        smhc.wait_for_command(7)?;
+
+        Ok(Sdio {
+            smhc,
+            functions,
+            block_sizes: [0; 8],
+        })
+    }
+    /// Get number of I/O functions on the card.
+    #[inline]
+    pub fn functions(&self) -> u8 {
+        self.functions
+    }
+    /// Read a byte from register `address` of `function` using CMD52.
+    #[inline]
+    pub fn read_byte(&self, function: Function, address: u32) -> Result<u8, SdCardError> {
+        self.io_rw_direct(false, function, address, 0)
+    }
+    /// Write a byte into register `address` of `function` using CMD52.
+    #[inline]
+    pub fn write_byte(&self, function: Function, address: u32, val: u8) -> Result<(), SdCardError> {
+        self.io_rw_direct(true, function, address, val).map(|_| ())
+    }
+    /// Send CMD52, returning data byte of response.
+    #[inline]
+    fn io_rw_direct(
+        &self,
+        write: bool,
+        function: Function,
+        address: u32,
+        val: u8,
+    ) -> Result<u8, SdCardError> {
+        /// Error flags of R5 response.
+        const R5_ERROR: u32 = 0xCB00;
+
+        let arg = (write as u32) << 31
+            | (function as u32 & 0x7) << 28
+            | (address & 0x1FFFF) << 9
+            | val as u32;
+        self.smhc
+            .send_card_command(52, arg, TransferMode::Disable, ResponseMode::Short, true);
+        self.smhc.wait_for_command(52)?;
+        let response = self.smhc.read_response();
+        if response as u32 & R5_ERROR != 0 {
+            return Err(SdCardError::UnexpectedResponse(52, response));
+        }
+        Ok(response as u8)
+    }
+    /// Read bytes from `address` of `function` using CMD53 byte mode.
+    ///
+    /// If `increment` is false, all bytes are read from the same register, e.g. a function
+    /// FIFO.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SdCardError::InvalidLength`] if buffer length is not a multiple of 4 from 4
+    /// to 512 bytes.
+    #[inline]
+    pub fn read_bytes(
+        &self,
+        function: Function,
+        address: u32,
+        buf: &mut [u8],
+        increment: bool,
+    ) -> Result<(), SdCardError> {
+        let arg = io_rw_extended_arg(false, function, address, buf.len(), increment)?;
+        self.smhc.read_data_block(53, arg, buf)
+    }
+    /// Write bytes into `address` of `function` using CMD53 byte mode.
+    ///
+    /// If `increment` is false, all bytes are written into the same register, e.g. a function
+    /// FIFO.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SdCardError::InvalidLength`] if buffer length is not a multiple of 4 from 4
+    /// to 512 bytes.
+    #[inline]
+    pub fn write_bytes(
+        &self,
+        function: Function,
+        address: u32,
+        buf: &[u8],
+        increment: bool,
+    ) -> Result<(), SdCardError> {
+        let arg = io_rw_extended_arg(true, function, address, buf.len(), increment)?;
+        self.smhc.set_block_size(buf.len() as u16);
+        let command = command(53, TransferMode::Write, ResponseMode::Short, true);
+        self.smhc.start_command(command, arg, buf.len() as u32);
+        let result = self
+            .smhc
+            .wait_for_command(53)
+            .and_then(|_| self.smhc.write_data(buf))
+            .and_then(|_| self.smhc.wait_for_data(false));
+        self.smhc.set_block_size(512);
+        result
+    }
+    /// Read blocks from `address` of `function` using CMD53 block mode.
+    ///
+    /// Blocks are of the size set by [`Sdio::set_block_size`]. If `increment` is false, all
+    /// blocks are read from the same register, e.g. a function FIFO.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SdCardError::InvalidLength`] if block size of `function` is not set, or
+    /// buffer length is not a whole number of 1 to 511 blocks.
+    #[inline]
+    pub fn read_blocks(
+        &self,
+        function: Function,
+        address: u32,
+        buf: &mut [u8],
+        increment: bool,
+    ) -> Result<(), SdCardError> {
+        let block_size = self.block_sizes[function as usize & 0x7];
+        let arg =
+            io_rw_extended_block_arg(false, function, address, buf.len(), block_size, increment)?;
+        self.smhc.set_block_size(block_size);
+        let command = command(53, TransferMode::Read, ResponseMode::Short, true);
+        self.smhc.start_command(command, arg, buf.len() as u32);
+        let result = self
+            .smhc
+            .wait_for_command(53)
+            .and_then(|_| self.smhc.read_data(buf))
+            .and_then(|_| self.smhc.wait_for_data(false));
+        self.smhc.set_block_size(512);
+        result
+    }
+    /// Write blocks into `address` of `function` using CMD53 block mode.
+    ///
+    /// Blocks are of the size set by [`Sdio::set_block_size`]. If `increment` is false, all
+    /// blocks are written into the same register, e.g. a function FIFO.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SdCardError::InvalidLength`] if block size of `function` is not set, or
+    /// buffer length is not a whole number of 1 to 511 blocks.
+    #[inline]
+    pub fn write_blocks(
+        &self,
+        function: Function,
+        address: u32,
+        buf: &[u8],
+        increment: bool,
+    ) -> Result<(), SdCardError> {
+        let block_size = self.block_sizes[function as usize & 0x7];
+        let arg =
+            io_rw_extended_block_arg(true, function, address, buf.len(), block_size, increment)?;
+        self.smhc.set_block_size(block_size);
+        let command = command(53, TransferMode::Write, ResponseMode::Short, true);
+        self.smhc.start_command(command, arg, buf.len() as u32);
+        let result = self
+            .smhc
+            .wait_for_command(53)
+            .and_then(|_| self.smhc.write_data(buf))
+            .and_then(|_| self.smhc.wait_for_data(false));
+        self.smhc.set_block_size(512);
+        result
+    }
+    /// Switch card and controller to 4-bit data bus.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SdCardError::Unsupported`] if controller pads lack 4-bit data lines.
+    #[inline]
+    pub fn set_four_bit_bus(&mut self) -> Result<(), SdCardError> {
+        if !self.smhc.supports_bus_width(BusWidth::FourBit) {
+            return Err(SdCardError::Unsupported);
+        }
+        let val = self.read_byte(0, cccr::BUS_INTERFACE)?;
+        self.write_byte(0, cccr::BUS_INTERFACE, (val & !0x3) | 0x2)?;
+        self.smhc.set_bus_width(BusWidth::FourBit);
+        Ok(())
+    }
+    /// Enable I/O `function` and wait until it's ready.
+    ///
+    /// Returns [`SdCardError::CommandTimeout`] of CMD52 if the function isn't ready before
+    /// crate-wide blocking timeout.
+    #[inline]
+    pub fn enable_function(&mut self, function: Function) -> Result<(), SdCardError> {
+        let val = self.read_byte(0, cccr::IO_ENABLE)?;
+        self.write_byte(0, cccr::IO_ENABLE, val | (1 << function))?;
+        let deadline = Deadline::start();
+        while self.read_byte(0, cccr::IO_READY)? & (1 << function) == 0 {
+            deadline
+                .check()
+                .map_err(|_| SdCardError::CommandTimeout(52))?;
+            core::hint::spin_loop();
+        }
+        Ok(())
+    }
+    /// Set block size of I/O `function` used by [`Sdio::read_blocks`] and
+    /// [`Sdio::write_blocks`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SdCardError::InvalidLength`] if `size` is not a multiple of 4 from 4 to 2048
+    /// bytes.
+    #[inline]
+    pub fn set_block_size(&mut self, function: Function, size: u16) -> Result<(), SdCardError> {
+        if size == 0 || size > 2048 || !size.is_multiple_of(4) {
+            return Err(SdCardError::InvalidLength);
+        }
+        let function = function & 0x7;
+        let fbr = (function as u32) * 0x100;
+        self.write_byte(0, fbr + 0x10, size as u8)?;
+        self.write_byte(0, fbr + 0x11, (size >> 8) as u8)?;
+        self.block_sizes[function as usize] = size;
+        Ok(())
+    }
+    /// Enable card interrupt of I/O `function`, routed through SMHC interrupt.
+    #[inline]
+    pub fn enable_card_interrupt(&mut self, function: Function) -> Result<(), SdCardError> {
+        let val = self.read_byte(0, cccr::INT_ENABLE)?;
+        // bit 0 is master interrupt enable
+        self.write_byte(0, cccr::INT_ENABLE, val | (1 << function) | 0x1)?;
+        self.smhc.enable_interrupt(Interrupt::Sdio);
+        Ok(())
+    }
+    /// Disable card interrupt of I/O `function`.
+    #[inline]
+    pub fn disable_card_interrupt(&mut self, function: Function) -> Result<(), SdCardError> {
+        let val = self.read_byte(0, cccr::INT_ENABLE)? & !(1 << function);
+        // keep master interrupt enabled while any function interrupt is on
+        let val = if val & !0x1 == 0 { 0 } else { val };
+        self.write_byte(0, cccr::INT_ENABLE, val)?;
+        if val == 0 {
+            self.smhc.disable_interrupt(Interrupt::Sdio);
+        }
+        Ok(())
+    }
+    /// Check if card interrupt is signaled to SMHC.
+    #[inline]
+    pub fn has_card_interrupt(&self) -> bool {
+        self.smhc.has_interrupt(Interrupt::Sdio)
+    }
+    /// Get pending interrupts of I/O functions, bit `n` for function `n`.
+    #[inline]
+    pub fn pending_functions(&self) -> Result<u8, SdCardError> {
+        self.read_byte(0, cccr::INT_PENDING)
+    }
+    /// Acknowledge card interrupt on SMHC side.
+    ///
+    /// Card interrupt source should be cleared in function registers first.
+    #[inline]
+    pub fn clear_card_interrupt(&self) {
+        self.smhc.clear_interrupt(Interrupt::Sdio);
+    }
+}
+
+/// Build CMD53 argument of byte mode transfer.
+#[inline]
+fn io_rw_extended_arg(
+    write: bool,
+    function: Function,
+    address: u32,
+    len: usize,
+    increment: bool,
+) -> Result<u32, SdCardError> {
+    if len == 0 || len > 512 || !len.is_multiple_of(4) {
+        return Err(SdCardError::InvalidLength);
+    }
+    // byte count of 512 is encoded as 0
+    let count = (len & 0x1FF) as u32;
+    Ok((write as u32) << 31
+        | (function as u32 & 0x7) << 28
+        | (increment as u32) << 26
+        | (address & 0x1FFFF) << 9
+        | count)
+}
+
+/// Build CMD53 argument of block mode transfer of `len` bytes in blocks of `block_size`.
+#[inline]
+fn io_rw_extended_block_arg(
+    write: bool,
+    function: Function,
+    address: u32,
+    len: usize,
+    block_size: u16,
+    increment: bool,
+) -> Result<u32, SdCardError> {
+    // block count of 0 would start an infinite transfer
+    if block_size == 0 || len == 0 || !len.is_multiple_of(block_size as usize) {
+        return Err(SdCardError::InvalidLength);
+    }
+    let count = len / block_size as usize;
+    if count > 0x1FF {
+        return Err(SdCardError::InvalidLength);
+    }
+    Ok((write as u32) << 31
+        | (function as u32 & 0x7) << 28
+        | 1 << 27
+        | (increment as u32) << 26
+        | (address & 0x1FFFF) << 9
+        | count as u32)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{io_rw_extended_arg, io_rw_extended_block_arg};
+    use crate::smhc::SdCardError;
+
+    #[test]
+    fn function_io_rw_extended_arg() {
+        assert_eq!(
+            io_rw_extended_arg(false, 1, 0x10000, 64, true),
+            Ok(0x1600_0040)
+        );
+        assert_eq!(
+            io_rw_extended_arg(true, 2, 0x8, 512, false),
+            Ok(0xA000_1000)
+        );
+        assert_eq!(
+            io_rw_extended_arg(false, 1, 0x0, 6, true),
+            Err(SdCardError::InvalidLength)
+        );
+        assert_eq!(
+            io_rw_extended_arg(false, 1, 0x0, 516, true),
+            Err(SdCardError::InvalidLength)
+        );
+        assert_eq!(
+            io_rw_extended_arg(false, 1, 0x0, 0, true),
+            Err(SdCardError::InvalidLength)
+        );
+    }
+
+    #[test]
+    fn function_io_rw_extended_block_arg() {
+        assert_eq!(
+            io_rw_extended_block_arg(false, 1, 0x10000, 2048, 512, true),
+            Ok(0x1E00_0004)
+        );
+        assert_eq!(
+            io_rw_extended_block_arg(true, 2, 0x8, 64 * 511, 64, false),
+            Ok(0xA800_11FF)
+        );
+        assert_eq!(
+            io_rw_extended_block_arg(false, 1, 0x0, 512, 0, true),
+            Err(SdCardError::InvalidLength)
+        );
+        assert_eq!(
+            io_rw_extended_block_arg(false, 1, 0x0, 0, 64, true),
+            Err(SdCardError::InvalidLength)
+        );
+        assert_eq!(
+            io_rw_extended_block_arg(false, 1, 0x0, 100, 64, true),
+            Err(SdCardError::InvalidLength)
+        );
+        assert_eq!(
+            io_rw_extended_block_arg(false, 1, 0x0, 4 * 512, 4, true),
+            Err(SdCardError::InvalidLength)
+        );
+    }
+}
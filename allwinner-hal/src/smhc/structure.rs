@@ -1,28 +1,37 @@
 use super::{
-    ResponseMode, SdCardError, TransferMode,
+    Block, BlockDevice, Cid, Csd, Pads, ResponseMode, Scr, SdCardError, SdCardInfo, SdStatus,
+    TransferMode,
+    idmac::{IdmacDescriptor, build_idmac_chain, idmac_address},
     register::{
-        AccessMode, BlockSize, BusWidth, CardType, Command, RegisterBlock, TransferDirection,
+        AccessMode, BlockSize, BusWidth, CardType, Command, DdcTimingPhase, DmaControl,
+        DmaInterrupt, DmaInterruptEnable, Interrupt, InterruptStateRaw, NtsTimingPhase,
+        RegisterBlock, TransferDirection,
     },
 };
-use crate::ccu::{self, Clocks, SmhcClockSource};
-use core::arch::asm;
-use embedded_sdmmc::{Block, BlockDevice, BlockIdx};
+use crate::ccu::{self, ClockConfig, ClockGate, Clocks, SmhcClockSource};
+use crate::time::Deadline;
+use core::sync::atomic::{Ordering, fence};
+use embedded_sdmmc::BlockIdx;
+use embedded_time::rate::Hertz;
 
 /// Managed SMHC structure with peripheral and pins.
-pub struct Smhc<SMHC, PADS> {
+pub struct Smhc<SMHC, const I: usize, PADS: Pads<I>> {
     smhc: SMHC,
     pads: PADS,
 }
 
-impl<SMHC: AsRef<RegisterBlock>, PADS> Smhc<SMHC, PADS> {
+impl<SMHC: AsRef<RegisterBlock>, const I: usize, PADS: Pads<I>> Smhc<SMHC, I, PADS> {
     /// Create an SMHC instance.
+    ///
+    /// Returns [`SdCardError::ControllerTimeout`] if controller reset or card clock update
+    /// doesn't finish before crate-wide blocking timeout.
     #[inline]
-    pub fn new<const SMHC_IDX: usize>(
+    pub fn new(
         smhc: SMHC,
         pads: PADS,
         clocks: &Clocks,
         ccu: &ccu::RegisterBlock,
-    ) -> Self {
+    ) -> Result<Self, SdCardError> {
         let divider = 2;
         let (factor_n, factor_m) =
             ccu::calculate_best_peripheral_factors_nm(clocks.psi.0, 20_000_000);
@@ -31,26 +40,23 @@ impl<SMHC: AsRef<RegisterBlock>, PADS> Smhc<SMHC, PADS> {
                 .clock_control
                 .modify(|val| val.disable_card_clock());
         }
-        unsafe {
-            ccu.smhc_bgr.modify(|val| val.assert_reset::<SMHC_IDX>());
-            ccu.smhc_bgr.modify(|val| val.gate_mask::<SMHC_IDX>());
-            ccu.smhc_clk[SMHC_IDX].modify(|val| {
-                val.set_clock_source(SmhcClockSource::PllPeri1x)
-                    .set_factor_n(factor_n)
-                    .set_factor_m(factor_m)
-                    .enable_clock_gating()
-            });
-            ccu.smhc_bgr.modify(|val| val.deassert_reset::<SMHC_IDX>());
-            ccu.smhc_bgr.modify(|val| val.gate_pass::<SMHC_IDX>());
-        }
+        // note(unsafe): async read and write using ccu registers
+        unsafe { PADS::Clock::reconfigure(ccu, SmhcClockSource::PllPeri1x, factor_m, factor_n) };
+        let deadline = Deadline::start();
         unsafe {
             let smhc = smhc.as_ref();
             smhc.global_control.modify(|val| val.set_software_reset());
             while !smhc.global_control.read().is_software_reset_cleared() {
+                deadline
+                    .check()
+                    .map_err(|_| SdCardError::ControllerTimeout)?;
                 core::hint::spin_loop();
             }
             smhc.global_control.modify(|val| val.set_fifo_reset());
             while !smhc.global_control.read().is_fifo_reset_cleared() {
+                deadline
+                    .check()
+                    .map_err(|_| SdCardError::ControllerTimeout)?;
                 core::hint::spin_loop();
             }
             smhc.global_control.modify(|val| val.disable_interrupt());
@@ -63,6 +69,9 @@ impl<SMHC: AsRef<RegisterBlock>, PADS> Smhc<SMHC, PADS> {
                     .set_command_start()
             });
             while !smhc.command.read().is_command_start_cleared() {
+                deadline
+                    .check()
+                    .map_err(|_| SdCardError::ControllerTimeout)?;
                 core::hint::spin_loop();
             }
         }
@@ -84,6 +93,9 @@ impl<SMHC: AsRef<RegisterBlock>, PADS> Smhc<SMHC, PADS> {
                     .set_command_start()
             });
             while !smhc.command.read().is_command_start_cleared() {
+                deadline
+                    .check()
+                    .map_err(|_| SdCardError::ControllerTimeout)?;
                 core::hint::spin_loop();
             }
         }
@@ -95,7 +107,7 @@ impl<SMHC: AsRef<RegisterBlock>, PADS> Smhc<SMHC, PADS> {
                 .write(BlockSize::default().set_block_size(512)); // TODO
         }
 
-        Self { smhc, pads }
+        Ok(Self { smhc, pads })
     }
     /// Get a temporary borrow on the underlying GPIO pads.
     #[inline]
@@ -108,12 +120,217 @@ impl<SMHC: AsRef<RegisterBlock>, PADS> Smhc<SMHC, PADS> {
     /// Close SMHC and release peripheral.
     #[inline]
     pub fn free(self, ccu: &ccu::RegisterBlock) -> (SMHC, PADS) {
+        unsafe { PADS::Clock::free(ccu) };
+        (self.smhc, self.pads)
+    }
+    /// Set card clock frequency.
+    ///
+    /// Module clock runs at twice the card clock, and is divided by 2 inside the controller.
+    ///
+    /// Returns [`SdCardError::ControllerTimeout`] if the controller doesn't take clock
+    /// changes before crate-wide blocking timeout.
+    #[inline]
+    pub fn set_card_clock(
+        &self,
+        freq: Hertz,
+        clocks: &Clocks,
+        ccu: &ccu::RegisterBlock,
+    ) -> Result<(), SdCardError> {
+        let (factor_n, factor_m) =
+            ccu::calculate_best_peripheral_factors_nm(clocks.psi.0, freq.0 * 2);
+        let smhc = self.smhc.as_ref();
         unsafe {
-            const SMHC_IDX: usize = 0; // TODO
-            ccu.smhc_bgr.modify(|val| val.assert_reset::<SMHC_IDX>());
-            ccu.smhc_bgr.modify(|val| val.gate_mask::<SMHC_IDX>());
+            smhc.clock_control.modify(|val| val.disable_card_clock());
+            self.update_card_clock(false)?;
+            ccu.smhc_clk[I].modify(|val| val.set_factor_n(factor_n).set_factor_m(factor_m));
+            smhc.clock_control
+                .modify(|val| val.set_card_clock_divider(1).enable_card_clock());
+            self.update_card_clock(false)
         }
-        (self.smhc, self.pads)
+    }
+    /// Inform the controller of card clock changes.
+    #[inline]
+    fn update_card_clock(&self, voltage_switch: bool) -> Result<(), SdCardError> {
+        let smhc = self.smhc.as_ref();
+        let command = Command::default()
+            .enable_wait_for_complete()
+            .enable_change_clock();
+        let command = if voltage_switch {
+            command.enable_voltage_switch()
+        } else {
+            command
+        };
+        unsafe { smhc.command.write(command.set_command_start()) };
+        let deadline = Deadline::start();
+        while !smhc.command.read().is_command_start_cleared() {
+            deadline
+                .check()
+                .map_err(|_| SdCardError::ControllerTimeout)?;
+            core::hint::spin_loop();
+        }
+        Ok(())
+    }
+    /// Switch card signaling to 1.8 V using CMD11.
+    ///
+    /// Card clock is stopped while `switch_io_voltage` changes I/O supply of the card
+    /// bus, e.g. through a GPIO controlled regulator or a PMIC. The callback should
+    /// return only after the new supply is stable, which takes at least 5 ms.
+    #[inline]
+    pub fn switch_signal_voltage<F: FnOnce()>(
+        &self,
+        switch_io_voltage: F,
+    ) -> Result<(), SdCardError> {
+        let smhc = self.smhc.as_ref();
+        let command = command(11, TransferMode::Disable, ResponseMode::Short, true);
+        self.start_command(command.enable_voltage_switch(), 0, 0);
+        self.wait_for_command(11)?;
+        unsafe { smhc.clock_control.modify(|val| val.disable_card_clock()) };
+        self.update_card_clock(true)?;
+        switch_io_voltage();
+        unsafe { smhc.clock_control.modify(|val| val.enable_card_clock()) };
+        self.update_card_clock(true)?;
+        // the card drives data lines high on 1.8 V once it has switched
+        let deadline = Deadline::start();
+        while !smhc
+            .interrupt_state_raw
+            .read()
+            .has_interrupt(Interrupt::DataStarvationTimeout1V8SwitchDone)
+        {
+            deadline
+                .check()
+                .map_err(|_| SdCardError::CommandTimeout(11))?;
+            core::hint::spin_loop();
+        }
+        self.clear_interrupt(Interrupt::DataStarvationTimeout1V8SwitchDone);
+        if smhc.status.read().card_busy() {
+            return Err(SdCardError::ResponseError(11));
+        }
+        Ok(())
+    }
+    /// Get widest data bus width supported by the pads.
+    #[inline]
+    pub fn max_bus_width(&self) -> BusWidth {
+        PADS::BUS_WIDTH
+    }
+    /// Check if data bus of `width` is supported by the pads.
+    #[inline]
+    pub fn supports_bus_width(&self, width: BusWidth) -> bool {
+        let lanes = |width| match width {
+            BusWidth::OneBit => 1,
+            BusWidth::FourBit => 4,
+            BusWidth::EightBit => 8,
+        };
+        lanes(width) <= lanes(PADS::BUS_WIDTH)
+    }
+    /// Set data bus width of the controller.
+    #[inline]
+    pub fn set_bus_width(&self, width: BusWidth) {
+        unsafe {
+            self.smhc
+                .as_ref()
+                .card_type
+                .modify(|val| val.set_bus_width(width))
+        };
+    }
+    /// Program delay chains for a single data rate bus timing.
+    ///
+    /// New timing mode samples data 90 degrees after the clock edge, and command and data
+    /// lines are driven half a cycle apart from card sampling edge. Sample delay is reset to
+    /// zero, to be tuned afterwards on HS200 and UHS-I timings.
+    #[inline]
+    pub(super) fn set_sdr_delay_chains(&self) {
+        let smhc = self.smhc.as_ref();
+        unsafe {
+            smhc.new_timing_set.modify(|val| {
+                val.enable_new_mode()
+                    .set_sample_timing_phase(NtsTimingPhase::Offset90)
+            });
+            smhc.drive_delay_control.modify(|val| {
+                val.set_command_drive_phase(DdcTimingPhase::Sdr180Ddr90)
+                    .set_data_drive_phase(DdcTimingPhase::Sdr180Ddr90)
+            });
+        }
+        self.set_sample_delay(0);
+    }
+    /// Set software sample delay of data and command lines.
+    #[inline]
+    pub fn set_sample_delay(&self, delay: u8) {
+        unsafe {
+            self.smhc.as_ref().sample_delay_control.modify(|val| {
+                val.set_sample_delay_software(delay)
+                    .enable_sample_delay_software()
+            })
+        };
+    }
+    /// Tune sample delay using tuning command `cmd` returning `pattern`,
+    /// selecting middle of the widest passing window.
+    ///
+    /// A delay passes only if the tuning block is received without error and matches
+    /// `pattern`.
+    #[inline]
+    pub(super) fn tune_sample_delay(&self, cmd: u8, pattern: &[u8]) -> Result<(), SdCardError> {
+        /// Number of software sample delay steps.
+        const DELAY_STEPS: u8 = 64;
+
+        let len = pattern.len();
+        let mut buf = [0u8; TUNING_BLOCK_8BIT.len()];
+        let (mut best_start, mut best_len) = (0u8, 0u8);
+        let (mut start, mut run) = (0u8, 0u8);
+        self.set_block_size(len as u16);
+        for delay in 0..DELAY_STEPS {
+            self.set_sample_delay(delay);
+            let command = command(cmd, TransferMode::Read, ResponseMode::Short, true);
+            self.start_command(command, 0, len as u32);
+            let pass = self.wait_for_command(cmd).is_ok()
+                && self.read_data(&mut buf[..len]).is_ok()
+                && self.wait_for_data(false).is_ok()
+                && buf[..len] == *pattern;
+            if pass {
+                if run == 0 {
+                    start = delay;
+                }
+                run += 1;
+                if run > best_len {
+                    (best_start, best_len) = (start, run);
+                }
+            } else {
+                run = 0;
+            }
+        }
+        self.set_block_size(512);
+        if best_len == 0 {
+            return Err(SdCardError::DataError);
+        }
+        self.set_sample_delay(best_start + best_len / 2);
+        Ok(())
+    }
+    /// Send data command `cmd` reading a short block of `buf.len()` bytes, e.g. a card register.
+    #[inline]
+    pub(super) fn read_data_block(
+        &self,
+        cmd: u8,
+        arg: u32,
+        buf: &mut [u8],
+    ) -> Result<(), SdCardError> {
+        self.set_block_size(buf.len() as u16);
+        let command = command(cmd, TransferMode::Read, ResponseMode::Short, true);
+        self.start_command(command, arg, buf.len() as u32);
+        let result = self
+            .wait_for_command(cmd)
+            .and_then(|_| self.read_data(buf))
+            .and_then(|_| self.wait_for_data(false));
+        self.set_block_size(512);
+        result
+    }
+    /// Set block size of following data transfers.
+    #[inline]
+    pub(super) fn set_block_size(&self, size: u16) {
+        unsafe {
+            self.smhc
+                .as_ref()
+                .block_size
+                .modify(|val| val.set_block_size(size))
+        };
     }
     /// Send a command to the card.
     #[inline]
@@ -125,47 +342,264 @@ impl<SMHC: AsRef<RegisterBlock>, PADS> Smhc<SMHC, PADS> {
         response_mode: ResponseMode,
         crc_check: bool,
     ) {
-        let (data_trans, trans_dir) = match transfer_mode {
-            TransferMode::Disable => (false, TransferDirection::Read),
-            TransferMode::Read => (true, TransferDirection::Read),
-            TransferMode::Write => (true, TransferDirection::Write),
-        };
-        let (resp_recv, resp_size) = match response_mode {
-            ResponseMode::Disable => (false, false),
-            ResponseMode::Short => (true, false),
-            ResponseMode::Long => (true, true),
-        };
+        self.start_command(
+            command(cmd, transfer_mode, response_mode, crc_check),
+            arg,
+            512,
+        );
+    }
+    /// Start `command` with argument `arg`, transferring `byte_count` bytes of data if any.
+    #[inline]
+    pub(super) fn start_command(&self, command: Command, arg: u32, byte_count: u32) {
         let smhc = self.smhc.as_ref();
-        if data_trans {
+        // clear transfer states left by previous commands, keeping card events
+        let mut state = InterruptStateRaw::default();
+        for interrupt in TRANSFER_INTERRUPTS {
+            state = state.clear_interrupt(interrupt);
+        }
+        unsafe { smhc.interrupt_state_raw.write(state) };
+        if command.is_data_transfer_enabled() {
             unsafe {
-                smhc.byte_count.modify(|w| w.set_byte_count(512)); // TODO
-                smhc.global_control
-                    .modify(|w| w.set_access_mode(AccessMode::Ahb));
+                smhc.byte_count.modify(|w| w.set_byte_count(byte_count));
+                // transfer through FIFO unless IDMAC is started
+                if !smhc.global_control.read().is_dma_enabled() {
+                    smhc.global_control
+                        .modify(|w| w.set_access_mode(AccessMode::Ahb));
+                }
             }
         }
         unsafe {
             smhc.argument.modify(|val| val.set_argument(arg));
-            smhc.command.write({
-                let mut val = Command::default()
-                    .set_command_start()
-                    .set_command_index(cmd)
-                    .set_transfer_direction(trans_dir)
-                    .enable_wait_for_complete()
-                    .enable_auto_stop();
-                if data_trans {
-                    val = val.enable_data_transfer();
-                }
-                if crc_check {
-                    val = val.enable_check_response_crc();
-                }
-                if resp_recv {
-                    val = val.enable_response_receive();
-                }
-                if resp_size {
-                    val = val.enable_long_response();
+            smhc.command.write(command);
+        }
+    }
+    /// Wait until command `cmd` completes or fails.
+    ///
+    /// Returns [`SdCardError::CommandTimeout`] if crate-wide blocking timeout passes first.
+    #[inline]
+    pub fn wait_for_command(&self, cmd: u8) -> Result<(), SdCardError> {
+        let smhc = self.smhc.as_ref();
+        let deadline = Deadline::start();
+        loop {
+            let state = smhc.interrupt_state_raw.read();
+            let result = if state.has_interrupt(Interrupt::ResponseTimeoutBootAckReceived) {
+                Err(SdCardError::CommandTimeout(cmd))
+            } else if state.has_interrupt(Interrupt::ResponseCrcError)
+                || state.has_interrupt(Interrupt::ResponseError)
+            {
+                Err(SdCardError::ResponseError(cmd))
+            } else if state.has_interrupt(Interrupt::CommandComplete) {
+                Ok(())
+            } else {
+                deadline
+                    .check()
+                    .map_err(|_| SdCardError::CommandTimeout(cmd))?;
+                core::hint::spin_loop();
+                continue;
+            };
+            unsafe {
+                smhc.interrupt_state_raw.write(
+                    InterruptStateRaw::default()
+                        .clear_interrupt(Interrupt::ResponseTimeoutBootAckReceived)
+                        .clear_interrupt(Interrupt::ResponseCrcError)
+                        .clear_interrupt(Interrupt::ResponseError)
+                        .clear_interrupt(Interrupt::CommandComplete),
+                )
+            };
+            return result;
+        }
+    }
+    /// Wait until data transfer of current command completes or fails.
+    ///
+    /// For multiple block transfers, this also waits for the automatic stop command.
+    /// Returns [`SdCardError::DataTimeout`] if crate-wide blocking timeout passes first.
+    #[inline]
+    pub fn wait_for_data(&self, auto_stop: bool) -> Result<(), SdCardError> {
+        let smhc = self.smhc.as_ref();
+        let deadline = Deadline::start();
+        loop {
+            let state = smhc.interrupt_state_raw.read();
+            let result = if state.has_interrupt(Interrupt::DataTimeoutBootDataStart)
+                || state.has_interrupt(Interrupt::DataStarvationTimeout1V8SwitchDone)
+            {
+                Err(SdCardError::DataTimeout)
+            } else if state.has_interrupt(Interrupt::DataCrcError)
+                || state.has_interrupt(Interrupt::DataStartError)
+                || state.has_interrupt(Interrupt::DataEndBitError)
+                || state.has_interrupt(Interrupt::FifoUnderrunOrOverflow)
+            {
+                Err(SdCardError::DataError)
+            } else if state.has_interrupt(Interrupt::DataTransferComplete)
+                && (!auto_stop || state.has_interrupt(Interrupt::AutoCommandDone))
+            {
+                Ok(())
+            } else {
+                deadline.check().map_err(|_| SdCardError::DataTimeout)?;
+                core::hint::spin_loop();
+                continue;
+            };
+            // clear all transfer states of this command, keeping card events
+            let mut clear = InterruptStateRaw::default();
+            for interrupt in TRANSFER_INTERRUPTS {
+                if state.has_interrupt(interrupt) {
+                    clear = clear.clear_interrupt(interrupt);
                 }
-                val
-            });
+            }
+            unsafe { smhc.interrupt_state_raw.write(clear) };
+            return result;
+        }
+    }
+    /// Start internal DMA of descriptor chain beginning with `descriptor`.
+    ///
+    /// Following data commands transfer through IDMAC instead of FIFO,
+    /// until [`Smhc::stop_dma`] is called.
+    ///
+    /// Returns [`SdCardError::ControllerTimeout`] if DMA or FIFO reset doesn't finish before
+    /// crate-wide blocking timeout; DMA should then be stopped.
+    ///
+    /// # Safety
+    ///
+    /// The descriptor chain, and buffers it refers to, must stay valid until the
+    /// transfer finishes or DMA is stopped.
+    #[inline]
+    pub unsafe fn start_dma(
+        &self,
+        descriptor: *const IdmacDescriptor,
+        direction: TransferDirection,
+    ) -> Result<(), SdCardError> {
+        let smhc = self.smhc.as_ref();
+        let interrupt = match direction {
+            TransferDirection::Read => DmaInterrupt::ReceiveComplete,
+            TransferDirection::Write => DmaInterrupt::TransmitComplete,
+        };
+        // make descriptors and buffers visible to IDMAC before it starts
+        fence(Ordering::SeqCst);
+        unsafe {
+            smhc.global_control
+                .modify(|val| val.set_dma_reset().set_fifo_reset());
+            let deadline = Deadline::start();
+            while !smhc.global_control.read().is_dma_reset_cleared()
+                || !smhc.global_control.read().is_fifo_reset_cleared()
+            {
+                deadline
+                    .check()
+                    .map_err(|_| SdCardError::ControllerTimeout)?;
+                core::hint::spin_loop();
+            }
+            smhc.global_control
+                .modify(|val| val.enable_dma().set_access_mode(AccessMode::Dma));
+            smhc.dma_control
+                .write(DmaControl::default().set_idmac_reset());
+            while !smhc.dma_control.read().is_idmac_reset_cleared() {
+                deadline
+                    .check()
+                    .map_err(|_| SdCardError::ControllerTimeout)?;
+                core::hint::spin_loop();
+            }
+            // clear interrupt states left by previous transfers
+            smhc.dma_state.modify(|val| val);
+            smhc.dma_interrupt_enable.write(
+                DmaInterruptEnable::default()
+                    .enable_interrupt(interrupt)
+                    .enable_interrupt(DmaInterrupt::AbnormalSummary),
+            );
+            smhc.dma_descriptor_base
+                .write(idmac_address(descriptor as usize));
+            smhc.dma_control
+                .write(DmaControl::default().enable_fixed_burst().enable_idmac());
+        }
+        Ok(())
+    }
+    /// Wait until internal DMA transfer completes or fails.
+    ///
+    /// Returns [`SdCardError::DataTimeout`] if crate-wide blocking timeout passes first.
+    #[inline]
+    pub fn wait_for_dma(&self) -> Result<(), SdCardError> {
+        let smhc = self.smhc.as_ref();
+        let deadline = Deadline::start();
+        loop {
+            let state = smhc.dma_state.read();
+            let result = if state.has_interrupt(DmaInterrupt::FatalBusError)
+                || state.has_interrupt(DmaInterrupt::DescriptorUnavailable)
+                || state.has_interrupt(DmaInterrupt::AbnormalSummary)
+            {
+                Err(SdCardError::DataError)
+            } else if state.has_interrupt(DmaInterrupt::ReceiveComplete)
+                || state.has_interrupt(DmaInterrupt::TransmitComplete)
+            {
+                Ok(())
+            } else {
+                deadline.check().map_err(|_| SdCardError::DataTimeout)?;
+                core::hint::spin_loop();
+                continue;
+            };
+            unsafe { smhc.dma_state.write(state) };
+            // make data written by IDMAC visible to following reads
+            fence(Ordering::SeqCst);
+            return result;
+        }
+    }
+    /// Stop internal DMA, following data commands transfer through FIFO.
+    #[inline]
+    pub fn stop_dma(&self) {
+        let smhc = self.smhc.as_ref();
+        unsafe {
+            smhc.dma_control.write(DmaControl::default());
+            smhc.dma_interrupt_enable
+                .write(DmaInterruptEnable::default());
+            smhc.global_control
+                .modify(|val| val.disable_dma().set_access_mode(AccessMode::Ahb));
+        }
+    }
+    /// Wait until the card releases busy signal on data line.
+    ///
+    /// Returns [`SdCardError::DataTimeout`] if crate-wide blocking timeout passes first.
+    #[inline]
+    pub fn wait_for_card_ready(&self) -> Result<(), SdCardError> {
+        let deadline = Deadline::start();
+        while self.smhc.as_ref().status.read().card_busy() {
+            deadline.check().map_err(|_| SdCardError::DataTimeout)?;
+            core::hint::spin_loop();
+        }
+        Ok(())
+    }
+    /// Unmask `interrupt` and enable the controller interrupt line.
+    #[inline]
+    pub fn enable_interrupt(&self, interrupt: Interrupt) {
+        let smhc = self.smhc.as_ref();
+        unsafe {
+            smhc.interrupt_mask
+                .modify(|val| val.unmask_interrupt(interrupt));
+            smhc.global_control.modify(|val| val.enable_interrupt());
+        }
+    }
+    /// Mask `interrupt`.
+    #[inline]
+    pub fn disable_interrupt(&self, interrupt: Interrupt) {
+        unsafe {
+            self.smhc
+                .as_ref()
+                .interrupt_mask
+                .modify(|val| val.mask_interrupt(interrupt))
+        };
+    }
+    /// Check if unmasked `interrupt` is pending.
+    #[inline]
+    pub fn has_interrupt(&self, interrupt: Interrupt) -> bool {
+        self.smhc
+            .as_ref()
+            .interrupt_state_masked
+            .read()
+            .has_interrupt(interrupt)
+    }
+    /// Clear pending state of `interrupt`.
+    #[inline]
+    pub fn clear_interrupt(&self, interrupt: Interrupt) {
+        unsafe {
+            self.smhc
+                .as_ref()
+                .interrupt_state_raw
+                .write(InterruptStateRaw::default().clear_interrupt(interrupt))
         };
     }
     /// Read the response from the card.
@@ -179,11 +613,16 @@ impl<SMHC: AsRef<RegisterBlock>, PADS> Smhc<SMHC, PADS> {
         response
     }
     /// Read data from first-in-first-out buffer.
+    ///
+    /// Returns [`SdCardError::DataTimeout`] if FIFO stays empty until crate-wide blocking
+    /// timeout passes.
     #[inline]
-    pub fn read_data(&self, buf: &mut [u8]) {
+    pub fn read_data(&self, buf: &mut [u8]) -> Result<(), SdCardError> {
         let smhc = self.smhc.as_ref();
         for i in 0..buf.len() / 4 {
+            let deadline = Deadline::start();
             while smhc.status.read().fifo_empty() {
+                deadline.check().map_err(|_| SdCardError::DataTimeout)?;
                 core::hint::spin_loop();
             }
             let data = smhc.fifo.read();
@@ -192,117 +631,557 @@ impl<SMHC: AsRef<RegisterBlock>, PADS> Smhc<SMHC, PADS> {
             buf[i * 4 + 2] = ((data >> 16) & 0xff) as u8;
             buf[i * 4 + 3] = ((data >> 24) & 0xff) as u8;
         }
+        Ok(())
+    }
+    /// Write data into first-in-first-out buffer.
+    ///
+    /// Returns [`SdCardError::DataTimeout`] if FIFO stays full until crate-wide blocking
+    /// timeout passes.
+    #[inline]
+    pub fn write_data(&self, buf: &[u8]) -> Result<(), SdCardError> {
+        let smhc = self.smhc.as_ref();
+        for chunk in buf.chunks_exact(4) {
+            let deadline = Deadline::start();
+            while smhc.status.read().fifo_full() {
+                deadline.check().map_err(|_| SdCardError::DataTimeout)?;
+                core::hint::spin_loop();
+            }
+            let data = u32::from_le_bytes([chunk[0], chunk[1], chunk[2], chunk[3]]);
+            unsafe { smhc.fifo.write(data) };
+        }
+        Ok(())
+    }
+    /// Read `blocks` using data command `cmd` with argument `arg`.
+    ///
+    /// Word-aligned buffers are transferred through internal DMA, others through FIFO.
+    /// Number of blocks should not exceed one multiple block command.
+    #[inline]
+    pub(super) fn read_blocks_with(
+        &self,
+        cmd: u8,
+        arg: u32,
+        blocks: &mut [Block],
+        auto_stop: bool,
+    ) -> Result<(), SdCardError> {
+        let mut command = command(cmd, TransferMode::Read, ResponseMode::Short, true);
+        if auto_stop {
+            command = command.enable_auto_stop();
+        }
+        let byte_count = blocks.len() * Block::LEN;
+        if (blocks.as_ptr() as usize).is_multiple_of(4) {
+            return self.transfer_dma(command, arg, blocks.as_mut_ptr() as usize, byte_count);
+        }
+        self.start_command(command, arg, byte_count as u32);
+        self.wait_for_command(cmd)?;
+        for block in blocks.iter_mut() {
+            self.read_data(&mut block.contents)?;
+        }
+        self.wait_for_data(auto_stop)
+    }
+    /// Write `blocks` using data command `cmd` with argument `arg`.
+    ///
+    /// Word-aligned buffers are transferred through internal DMA, others through FIFO.
+    /// Number of blocks should not exceed one multiple block command.
+    #[inline]
+    pub(super) fn write_blocks_with(
+        &self,
+        cmd: u8,
+        arg: u32,
+        blocks: &[Block],
+        auto_stop: bool,
+    ) -> Result<(), SdCardError> {
+        let mut command = command(cmd, TransferMode::Write, ResponseMode::Short, true);
+        if auto_stop {
+            command = command.enable_auto_stop();
+        }
+        let byte_count = blocks.len() * Block::LEN;
+        if (blocks.as_ptr() as usize).is_multiple_of(4) {
+            self.transfer_dma(command, arg, blocks.as_ptr() as usize, byte_count)?;
+        } else {
+            self.start_command(command, arg, byte_count as u32);
+            self.wait_for_command(cmd)?;
+            for block in blocks {
+                self.write_data(&block.contents)?;
+            }
+            self.wait_for_data(auto_stop)?;
+        }
+        self.wait_for_card_ready()?;
+        Ok(())
+    }
+    /// Run data `command` over word-aligned buffer at `address` through internal DMA.
+    #[inline]
+    fn transfer_dma(
+        &self,
+        command: Command,
+        arg: u32,
+        address: usize,
+        byte_count: usize,
+    ) -> Result<(), SdCardError> {
+        let mut descriptors = [IdmacDescriptor::new(); DMA_DESCRIPTORS];
+        build_idmac_chain(&mut descriptors, address, byte_count).map_err(SdCardError::DmaBuffer)?;
+        // note(unsafe): descriptors and buffer outlive the transfer, DMA is stopped below
+        let result = unsafe { self.start_dma(descriptors.as_ptr(), command.transfer_direction()) }
+            .and_then(|_| {
+                self.start_command(command, arg, byte_count as u32);
+                self.wait_for_command(command.command_index())
+            })
+            .and_then(|_| self.wait_for_data(command.is_auto_stop_enabled()))
+            .and_then(|_| self.wait_for_dma());
+        self.stop_dma();
+        result?;
+        if descriptors.iter().any(|desc| {
+            // note(unsafe): IDMAC writes status back to descriptors, read them as it left them
+            let desc = unsafe { core::ptr::read_volatile(desc) };
+            desc.is_owned_by_dma() || desc.has_error()
+        }) {
+            return Err(SdCardError::DataError);
+        }
+        Ok(())
     }
 }
 
-pub struct SdCard<'a, S, P> {
-    smhc: &'a mut Smhc<S, P>,
-    block_count: u32,
+/// Interrupts raised by command and data transfers.
+const TRANSFER_INTERRUPTS: [Interrupt; 15] = [
+    Interrupt::DataEndBitError,
+    Interrupt::AutoCommandDone,
+    Interrupt::DataStartError,
+    Interrupt::CommandBusyAndIllegalWrite,
+    Interrupt::FifoUnderrunOrOverflow,
+    Interrupt::DataStarvationTimeout1V8SwitchDone,
+    Interrupt::DataTimeoutBootDataStart,
+    Interrupt::ResponseTimeoutBootAckReceived,
+    Interrupt::DataCrcError,
+    Interrupt::ResponseCrcError,
+    Interrupt::DataReceiveRequest,
+    Interrupt::DataTransmitRequest,
+    Interrupt::DataTransferComplete,
+    Interrupt::CommandComplete,
+    Interrupt::ResponseError,
+];
+
+/// Build command register value of card command `cmd`.
+#[inline]
+pub(super) fn command(
+    cmd: u8,
+    transfer_mode: TransferMode,
+    response_mode: ResponseMode,
+    crc_check: bool,
+) -> Command {
+    let (data_trans, trans_dir) = match transfer_mode {
+        TransferMode::Disable => (false, TransferDirection::Read),
+        TransferMode::Read => (true, TransferDirection::Read),
+        TransferMode::Write => (true, TransferDirection::Write),
+    };
+    let (resp_recv, resp_size) = match response_mode {
+        ResponseMode::Disable => (false, false),
+        ResponseMode::Short => (true, false),
+        ResponseMode::Long => (true, true),
+    };
+    let mut val = Command::default()
+        .set_command_start()
+        .set_command_index(cmd)
+        .set_transfer_direction(trans_dir)
+        .enable_wait_for_complete();
+    if data_trans {
+        val = val.enable_data_transfer();
+    }
+    if crc_check {
+        val = val.enable_check_response_crc();
+    }
+    if resp_recv {
+        val = val.enable_response_receive();
+    }
+    if resp_size {
+        val = val.enable_long_response();
+    }
+    val
+}
+
+/// Number of IDMAC descriptors used by one multiple block transfer.
+const DMA_DESCRIPTORS: usize = 8;
+/// Maximum number of blocks transferred by one multiple block command, 64 KiB in total.
+pub(super) const DMA_MAX_BLOCKS: usize =
+    DMA_DESCRIPTORS * IdmacDescriptor::MAX_BUFFER_SIZE / Block::LEN;
+
+/// SD card bus speed mode.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum SdTiming {
+    /// Default speed, or UHS-I SDR12 on 1.8 V signaling, up to 25 MHz.
+    Sdr12,
+    /// High speed, or UHS-I SDR25 on 1.8 V signaling, up to 50 MHz.
+    Sdr25,
+    /// UHS-I SDR50, up to 100 MHz on 1.8 V signaling.
+    Sdr50,
+    /// UHS-I SDR104, up to 208 MHz on 1.8 V signaling.
+    Sdr104,
+}
+
+impl SdTiming {
+    /// Card clock frequency used on this timing.
+    ///
+    /// SDR104 is limited to 150 MHz by SMHC on D1-like chips.
+    #[inline]
+    pub const fn frequency(self) -> Hertz {
+        match self {
+            SdTiming::Sdr12 => Hertz(25_000_000),
+            SdTiming::Sdr25 => Hertz(50_000_000),
+            SdTiming::Sdr50 => Hertz(100_000_000),
+            SdTiming::Sdr104 => Hertz(150_000_000),
+        }
+    }
+    /// Access mode function number of CMD6 function group 1.
+    #[inline]
+    pub const fn function(self) -> u8 {
+        self as u8
+    }
+    /// If this timing requires 1.8 V signaling.
+    #[inline]
+    pub const fn is_uhs(self) -> bool {
+        matches!(self, SdTiming::Sdr50 | SdTiming::Sdr104)
+    }
+}
+
+/// Tuning block pattern returned on 4-bit bus by CMD19 of SD cards and CMD21 of eMMC.
+pub(super) const TUNING_BLOCK_4BIT: [u8; 64] = [
+    0xFF, 0x0F, 0xFF, 0x00, 0xFF, 0xCC, 0xC3, 0xCC, 0xC3, 0x3C, 0xCC, 0xFF, 0xFE, 0xFF, 0xFE, 0xEF,
+    0xFF, 0xDF, 0xFF, 0xDD, 0xFF, 0xFB, 0xFF, 0xFB, 0xBF, 0xFF, 0x7F, 0xFF, 0x77, 0xF7, 0xBD, 0xEF,
+    0xFF, 0xF0, 0xFF, 0xF0, 0x0F, 0xFC, 0xCC, 0x3C, 0xCC, 0x33, 0xCC, 0xCF, 0xFF, 0xEF, 0xFF, 0xEE,
+    0xFF, 0xFD, 0xFF, 0xFD, 0xDF, 0xFF, 0xBF, 0xFF, 0xBB, 0xFF, 0xF7, 0xFF, 0xF7, 0x7F, 0x7B, 0xDE,
+];
+
+/// Tuning block pattern returned on 8-bit bus by CMD21 of eMMC.
+pub(super) const TUNING_BLOCK_8BIT: [u8; 128] = [
+    0xFF, 0xFF, 0x00, 0xFF, 0xFF, 0xFF, 0x00, 0x00, 0xFF, 0xFF, 0xCC, 0xCC, 0xCC, 0x33, 0xCC, 0xCC,
+    0xCC, 0x33, 0x33, 0xCC, 0xCC, 0xCC, 0xFF, 0xFF, 0xFF, 0xEE, 0xFF, 0xFF, 0xFF, 0xEE, 0xEE, 0xFF,
+    0xFF, 0xFF, 0xDD, 0xFF, 0xFF, 0xFF, 0xDD, 0xDD, 0xFF, 0xFF, 0xFF, 0xBB, 0xFF, 0xFF, 0xFF, 0xBB,
+    0xBB, 0xFF, 0xFF, 0xFF, 0x77, 0xFF, 0xFF, 0xFF, 0x77, 0x77, 0xFF, 0x77, 0xBB, 0xDD, 0xEE, 0xFF,
+    0xFF, 0xFF, 0xFF, 0x00, 0xFF, 0xFF, 0xFF, 0x00, 0x00, 0xFF, 0xFF, 0xCC, 0xCC, 0xCC, 0x33, 0xCC,
+    0xCC, 0xCC, 0x33, 0x33, 0xCC, 0xCC, 0xCC, 0xFF, 0xFF, 0xFF, 0xEE, 0xFF, 0xFF, 0xFF, 0xEE, 0xEE,
+    0xFF, 0xFF, 0xFF, 0xDD, 0xFF, 0xFF, 0xFF, 0xDD, 0xDD, 0xFF, 0xFF, 0xFF, 0xBB, 0xFF, 0xFF, 0xFF,
+    0xBB, 0xBB, 0xFF, 0xFF, 0xFF, 0x77, 0xFF, 0xFF, 0xFF, 0x77, 0x77, 0xFF, 0x77, 0xBB, 0xDD, 0xEE,
+];
+
+/// Blocking SD card driver over an SMHC peripheral.
+pub struct SdCard<'a, S, const I: usize, P: Pads<I>> {
+    smhc: &'a mut Smhc<S, I, P>,
+    rca: u32,
+    info: SdCardInfo,
+    high_capacity: bool,
+    signal_1v8: bool,
 }
 
-impl<'a, S: AsRef<RegisterBlock>, P> SdCard<'a, S, P> {
+impl<'a, S: AsRef<RegisterBlock>, const I: usize, P: Pads<I>> SdCard<'a, S, I, P> {
     /// Create an SD card instance.
     #[inline]
-    pub fn new(smhc: &'a mut Smhc<S, P>) -> Result<Self, SdCardError> {
+    pub fn new(smhc: &'a mut Smhc<S, I, P>) -> Result<Self, SdCardError> {
+        Self::init(smhc, None::<fn()>)
+    }
+    /// Create an SD card instance, switching to 1.8 V signaling if the card supports UHS-I.
+    ///
+    /// `switch_io_voltage` is called to change I/O supply of the card bus to 1.8 V,
+    /// see [`Smhc::switch_signal_voltage`].
+    #[inline]
+    pub fn new_uhs<F: FnOnce()>(
+        smhc: &'a mut Smhc<S, I, P>,
+        switch_io_voltage: F,
+    ) -> Result<Self, SdCardError> {
+        Self::init(smhc, Some(switch_io_voltage))
+    }
+    #[inline]
+    fn init<F: FnOnce()>(
+        smhc: &'a mut Smhc<S, I, P>,
+        switch_io_voltage: Option<F>,
+    ) -> Result<Self, SdCardError> {
         /// Host supports high capacity
         const OCR_HCS: u32 = 0x40000000;
         /// Card has finished power up routine if bit is high
         const OCR_NBUSY: u32 = 0x80000000;
         /// Valid bits for voltage setting
         const OCR_VOLTAGE_MASK: u32 = 0x007FFF80;
+        /// Card is high or extended capacity, addressed in blocks
+        const OCR_CCS: u32 = OCR_HCS;
+        /// Host requests, or card accepts switching to 1.8 V signaling
+        const OCR_S18: u32 = 0x01000000;
 
         // CMD0(reset) -> CMD8(check voltage and sdcard version)
         // -> CMD55+ACMD41(init and read OCR)
         smhc.send_card_command(0, 0, TransferMode::Disable, ResponseMode::Disable, false);
-        Self::sleep(100); // TODO: wait for interrupt instead of sleep
+        smhc.wait_for_command(0)?;
         smhc.send_card_command(8, 0x1AA, TransferMode::Disable, ResponseMode::Short, true);
-        Self::sleep(100);
+        smhc.wait_for_command(8)?;
         let data = smhc.read_response();
         if data != 0x1AA {
             return Err(SdCardError::UnexpectedResponse(8, data));
         }
-        loop {
+        let s18r = if switch_io_voltage.is_some() {
+            OCR_S18
+        } else {
+            0
+        };
+        let deadline = Deadline::start();
+        let ocr = loop {
             smhc.send_card_command(55, 0, TransferMode::Disable, ResponseMode::Short, true);
-            Self::sleep(100);
+            smhc.wait_for_command(55)?;
             smhc.send_card_command(
                 41,
-                OCR_VOLTAGE_MASK & 0x00ff8000 | OCR_HCS,
+                OCR_VOLTAGE_MASK & 0x00ff8000 | OCR_HCS | s18r,
                 TransferMode::Disable,
                 ResponseMode::Short,
                 false,
             );
-            Self::sleep(100);
+            smhc.wait_for_command(41)?;
             let ocr = smhc.read_response() as u32;
             if (ocr & OCR_NBUSY) == OCR_NBUSY {
-                break;
+                break ocr;
             }
+            deadline
+                .check()
+                .map_err(|_| SdCardError::CommandTimeout(41))?;
+        };
+
+        // Send CMD11 to switch signal voltage if both sides agree.
+        let mut signal_1v8 = false;
+        if let Some(switch_io_voltage) = switch_io_voltage
+            && ocr & OCR_S18 != 0
+        {
+            smhc.switch_signal_voltage(switch_io_voltage)?;
+            signal_1v8 = true;
         }
 
         // Send CMD2 to get CID.
         smhc.send_card_command(2, 0, TransferMode::Disable, ResponseMode::Long, true);
-        Self::sleep(100);
-        let _cid = smhc.read_response();
+        smhc.wait_for_command(2)?;
+        let cid = Cid(smhc.read_response());
 
         // Send CMD3 to get RCA.
         smhc.send_card_command(3, 0, TransferMode::Disable, ResponseMode::Short, true);
-        Self::sleep(100);
+        smhc.wait_for_command(3)?;
         let rca = smhc.read_response() as u32;
 
         // Send CMD9 to get CSD.
         smhc.send_card_command(9, rca, TransferMode::Disable, ResponseMode::Long, true);
-        Self::sleep(100);
-        let csd_raw = smhc.read_response();
-        let fixed_csd_raw = csd_raw >> 8; // FIXME: 8bit shift for long response, why?
-        let (csd_structure, c_size) = Self::parse_csd_v2(fixed_csd_raw);
-        if csd_structure != 1 {
-            return Err(SdCardError::UnexpectedResponse(9, csd_raw));
+        smhc.wait_for_command(9)?;
+        let csd = Csd(smhc.read_response());
+        if csd.structure() > 1 {
+            return Err(SdCardError::UnexpectedResponse(9, csd.0));
         }
 
         // Send CMD7 to select card.
         smhc.send_card_command(7, rca, TransferMode::Disable, ResponseMode::Short, true);
-        Self::sleep(100);
+        smhc.wait_for_command(7)?;
+        smhc.wait_for_card_ready()?;
+
+        // Read SCR, CMD55 -> ACMD51.
+        let mut scr = [0u8; 8];
+        smhc.send_card_command(55, rca, TransferMode::Disable, ResponseMode::Short, true);
+        smhc.wait_for_command(55)?;
+        smhc.read_data_block(51, 0, &mut scr)?;
+        let scr = Scr(u64::from_be_bytes(scr));
 
         // Set 1 data len, CMD55 -> ACMD6.
         smhc.send_card_command(55, rca, TransferMode::Disable, ResponseMode::Short, true);
-        Self::sleep(100);
+        smhc.wait_for_command(55)?;
         smhc.send_card_command(6, 0, TransferMode::Disable, ResponseMode::Short, true);
-        Self::sleep(100);
+        smhc.wait_for_command(6)?;
 
         Ok(SdCard {
             smhc,
-            block_count: (c_size + 1) * 1024,
+            rca,
+            info: SdCardInfo { cid, csd, scr },
+            high_capacity: ocr & OCR_CCS != 0,
+            signal_1v8,
         })
     }
+    /// Get card information read on initialization.
+    #[inline]
+    pub fn info(&self) -> &SdCardInfo {
+        &self.info
+    }
+    /// Check if the card bus is switched to 1.8 V signaling.
+    #[inline]
+    pub fn is_signal_1v8(&self) -> bool {
+        self.signal_1v8
+    }
+    /// Switch data bus of card and controller to 4-bit using ACMD6.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SdCardError::Unsupported`] if controller pads lack 4-bit data lines.
+    #[inline]
+    pub fn set_four_bit_bus(&mut self) -> Result<(), SdCardError> {
+        if !self.smhc.supports_bus_width(BusWidth::FourBit) {
+            return Err(SdCardError::Unsupported);
+        }
+        self.smhc.send_card_command(
+            55,
+            self.rca,
+            TransferMode::Disable,
+            ResponseMode::Short,
+            true,
+        );
+        self.smhc.wait_for_command(55)?;
+        self.smhc
+            .send_card_command(6, 0x2, TransferMode::Disable, ResponseMode::Short, true);
+        self.smhc.wait_for_command(6)?;
+        self.smhc.set_bus_width(BusWidth::FourBit);
+        Ok(())
+    }
+    /// Switch bus speed mode, changing card clock accordingly.
+    ///
+    /// Data bus is switched to 4-bit first. SDR50 and SDR104 require the card
+    /// to be created by [`SdCard::new_uhs`] with 1.8 V signaling accepted; sample
+    /// delay is tuned using CMD19 afterwards.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SdCardError::Unsupported`] if a UHS-I `timing` is requested without 1.8 V
+    /// signaling, or if controller pads lack 4-bit data lines.
+    #[inline]
+    pub fn set_timing(
+        &mut self,
+        timing: SdTiming,
+        clocks: &Clocks,
+        ccu: &ccu::RegisterBlock,
+    ) -> Result<(), SdCardError> {
+        /// Switch mode, keeping functions of groups 2 to 6.
+        const SWITCH_FUNCTION: u32 = 0x80FF_FFF0;
+
+        if timing.is_uhs() && !self.signal_1v8 {
+            return Err(SdCardError::Unsupported);
+        }
+        self.set_four_bit_bus()?;
+        // Send CMD6 to switch access mode, reading function status.
+        let mut status = [0u8; 64];
+        self.smhc
+            .read_data_block(6, SWITCH_FUNCTION | timing.function() as u32, &mut status)?;
+        // bits 379:376 of status hold the function selected in group 1
+        if status[16] & 0xF != timing.function() {
+            return Err(SdCardError::UnexpectedResponse(6, status[16] as u128));
+        }
+        self.smhc.set_sdr_delay_chains();
+        self.smhc.set_card_clock(timing.frequency(), clocks, ccu)?;
+        if timing.is_uhs() {
+            self.smhc.tune_sample_delay(19, &TUNING_BLOCK_4BIT)?;
+        }
+        Ok(())
+    }
+    /// Get the size of the SD card in 512-byte blocks.
+    #[inline]
+    pub fn block_count(&self) -> u32 {
+        self.info.csd.block_count()
+    }
     /// Get the size of the SD card in kilobytes.
     #[inline]
-    pub fn get_size_kb(&self) -> f64 {
-        (self.block_count as f64) * (512 as f64) / 1024.0
+    pub fn get_size_kb(&self) -> u64 {
+        self.block_count() as u64 * 512 / 1024
+    }
+    /// Read SD status using ACMD13, reporting speed class and performance grades.
+    #[inline]
+    pub fn status(&self) -> Result<SdStatus, SdCardError> {
+        let mut status = [0u8; 64];
+        self.smhc.send_card_command(
+            55,
+            self.rca,
+            TransferMode::Disable,
+            ResponseMode::Short,
+            true,
+        );
+        self.smhc.wait_for_command(55)?;
+        self.smhc.read_data_block(13, 0, &mut status)?;
+        Ok(SdStatus(status))
     }
     /// Read a block from the SD card.
     #[inline]
-    pub fn read_block(&self, block: &mut Block, block_idx: u32) {
-        self.smhc
-            .send_card_command(17, block_idx, TransferMode::Read, ResponseMode::Short, true);
-        self.smhc.read_data(&mut block.contents);
+    pub fn read_block(&self, block: &mut Block, block_idx: u32) -> Result<(), SdCardError> {
+        self.smhc.read_blocks_with(
+            17,
+            self.address(block_idx),
+            core::slice::from_mut(block),
+            false,
+        )
+    }
+    /// Read consecutive blocks from the SD card, starting at `start_block_idx`.
+    ///
+    /// Word-aligned buffers are transferred through internal DMA, others through FIFO.
+    #[inline]
+    pub fn read_blocks(
+        &self,
+        blocks: &mut [Block],
+        start_block_idx: u32,
+    ) -> Result<(), SdCardError> {
+        match blocks.len() {
+            0 => return Ok(()),
+            1 => return self.read_block(&mut blocks[0], start_block_idx),
+            _ => {}
+        }
+        for (i, chunk) in blocks.chunks_mut(DMA_MAX_BLOCKS).enumerate() {
+            let block_idx = start_block_idx + (i * DMA_MAX_BLOCKS) as u32;
+            self.smhc
+                .read_blocks_with(18, self.address(block_idx), chunk, true)?;
+        }
+        Ok(())
+    }
+    /// Write a block to the SD card.
+    #[inline]
+    pub fn write_block(&self, block: &Block, block_idx: u32) -> Result<(), SdCardError> {
+        self.smhc.write_blocks_with(
+            24,
+            self.address(block_idx),
+            core::slice::from_ref(block),
+            false,
+        )
     }
-    /// Parse CSD register version 2.
+    /// Write consecutive blocks to the SD card, starting at `start_block_idx`.
+    ///
+    /// Word-aligned buffers are transferred through internal DMA, others through FIFO.
     #[inline]
-    fn parse_csd_v2(csd: u128) -> (u32, u32) {
-        let csd_structure = (((csd >> (32 * 3)) & 0xC00000) >> 22) as u32;
-        let c_size = (((csd >> 32) & 0x3FFFFF00) >> 8) as u32;
-        (csd_structure, c_size)
+    pub fn write_blocks(&self, blocks: &[Block], start_block_idx: u32) -> Result<(), SdCardError> {
+        match blocks.len() {
+            0 => return Ok(()),
+            1 => return self.write_block(&blocks[0], start_block_idx),
+            _ => {}
+        }
+        for (i, chunk) in blocks.chunks(DMA_MAX_BLOCKS).enumerate() {
+            let block_idx = start_block_idx + (i * DMA_MAX_BLOCKS) as u32;
+            self.smhc
+                .write_blocks_with(25, self.address(block_idx), chunk, true)?;
+        }
+        Ok(())
     }
-    /// Sleep for a number of cycles.
+    /// Command argument addressing block `block_idx`.
+    ///
+    /// Standard capacity cards are addressed in bytes, others in blocks.
     #[inline]
-    fn sleep(n: u32) {
-        for _ in 0..n * 100_000 {
-            unsafe { asm!("nop") }
+    fn address(&self, block_idx: u32) -> u32 {
+        if self.high_capacity {
+            block_idx
+        } else {
+            block_idx * Block::LEN as u32
         }
     }
 }
 
-impl<'a, S: AsRef<RegisterBlock>, P> BlockDevice for SdCard<'a, S, P> {
-    type Error = core::convert::Infallible;
+impl<'a, S: AsRef<RegisterBlock>, const I: usize, P: Pads<I>> BlockDevice for SdCard<'a, S, I, P> {
+    type Error = SdCardError;
+
+    #[inline]
+    fn read_blocks(&self, blocks: &mut [Block], start_block_idx: u32) -> Result<(), Self::Error> {
+        SdCard::read_blocks(self, blocks, start_block_idx)
+    }
+    #[inline]
+    fn write_blocks(&self, blocks: &[Block], start_block_idx: u32) -> Result<(), Self::Error> {
+        SdCard::write_blocks(self, blocks, start_block_idx)
+    }
+    #[inline]
+    fn block_count(&self) -> u32 {
+        SdCard::block_count(self)
+    }
+}
+
+impl<'a, S: AsRef<RegisterBlock>, const I: usize, P: Pads<I>> embedded_sdmmc::BlockDevice
+    for SdCard<'a, S, I, P>
+{
+    type Error = SdCardError;
 
     #[inline]
     fn read(
@@ -311,19 +1190,33 @@ impl<'a, S: AsRef<RegisterBlock>, P> BlockDevice for SdCard<'a, S, P> {
         start_block_idx: BlockIdx,
         _reason: &str,
     ) -> Result<(), Self::Error> {
-        for (i, block) in blocks.iter_mut().enumerate() {
-            self.read_block(block, start_block_idx.0 + i as u32);
-        }
-        Ok(())
+        self.read_blocks(blocks, start_block_idx.0)
     }
 
     #[inline]
-    fn write(&self, _blocks: &[Block], _start_block_idx: BlockIdx) -> Result<(), Self::Error> {
-        todo!();
+    fn write(&self, blocks: &[Block], start_block_idx: BlockIdx) -> Result<(), Self::Error> {
+        self.write_blocks(blocks, start_block_idx.0)
     }
 
     #[inline]
     fn num_blocks(&self) -> Result<embedded_sdmmc::BlockCount, Self::Error> {
-        Ok(embedded_sdmmc::BlockCount(self.block_count))
+        Ok(embedded_sdmmc::BlockCount(SdCard::block_count(self)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::SdTiming;
+
+    #[test]
+    fn enum_sd_timing_functions() {
+        assert_eq!(SdTiming::Sdr12.frequency().0, 25_000_000);
+        assert_eq!(SdTiming::Sdr25.frequency().0, 50_000_000);
+        assert_eq!(SdTiming::Sdr50.frequency().0, 100_000_000);
+        assert_eq!(SdTiming::Sdr104.frequency().0, 150_000_000);
+        assert_eq!(SdTiming::Sdr12.function(), 0);
+        assert_eq!(SdTiming::Sdr104.function(), 3);
+        assert!(!SdTiming::Sdr25.is_uhs());
+        assert!(SdTiming::Sdr50.is_uhs());
     }
 }
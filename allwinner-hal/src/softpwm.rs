@@ -0,0 +1,219 @@
+//! Software PWM on general purpose output pads.
+//!
+//! [`SoftPwm`] drives up to `N` output pads from one periodic timer interrupt. Each PWM
+//! period is divided into `steps` timer ticks, so the timer should run at PWM frequency
+//! times `steps`, as given by [`DutyCycles::tick_rate`].
+//!
+//! Duty cycles live in a [`DutyCycles`] table of atomics, usually placed in a `static`, so
+//! that they can be changed from thread mode while the interrupt handler owns the pads.
+//! New duty cycles take effect at the start of the next PWM period.
+
+use crate::pwm::FrequencyError;
+use core::{
+    convert::Infallible,
+    sync::atomic::{AtomicU16, Ordering},
+};
+use embedded_hal::digital::OutputPin;
+use embedded_time::rate::Hertz;
+
+/// Duty cycles of `N` software PWM channels.
+pub struct DutyCycles<const N: usize> {
+    duty: [AtomicU16; N],
+    steps: u16,
+}
+
+impl<const N: usize> DutyCycles<N> {
+    /// Create duty cycle table of `steps` ticks per period, with all channels at 0%.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `steps` is zero.
+    #[inline]
+    pub const fn new(steps: u16) -> Self {
+        assert!(steps != 0, "software PWM should have at least one step");
+        Self {
+            duty: [const { AtomicU16::new(0) }; N],
+            steps,
+        }
+    }
+    /// Get number of timer ticks in one PWM period.
+    #[inline]
+    pub const fn steps(&self) -> u16 {
+        self.steps
+    }
+    /// Get timer rate for PWM output of `frequency`.
+    ///
+    /// Returns [`FrequencyError::OutOfRange`] if the rate doesn't fit in 32 bits.
+    #[inline]
+    pub const fn tick_rate(&self, frequency: Hertz) -> Result<Hertz, FrequencyError> {
+        match frequency.0.checked_mul(self.steps as u32) {
+            Some(rate) => Ok(Hertz(rate)),
+            None => Err(FrequencyError::OutOfRange),
+        }
+    }
+    /// Set duty cycle of `channel` in ticks, saturating at [`steps`](Self::steps).
+    #[inline]
+    pub fn set(&self, channel: usize, duty: u16) {
+        self.duty[channel].store(duty.min(self.steps), Ordering::Relaxed);
+    }
+    /// Get duty cycle of `channel` in ticks.
+    #[inline]
+    pub fn get(&self, channel: usize) -> u16 {
+        self.duty[channel].load(Ordering::Relaxed)
+    }
+    /// Get a handle of `channel` implementing `SetDutyCycle`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `channel` is not less than `N`.
+    #[inline]
+    pub fn channel(&self, channel: usize) -> SoftPwmChannel<'_, N> {
+        assert!(channel < N, "software PWM channel out of range");
+        SoftPwmChannel {
+            duties: self,
+            channel,
+        }
+    }
+}
+
+/// Duty cycle handle of one software PWM channel.
+pub struct SoftPwmChannel<'a, const N: usize> {
+    duties: &'a DutyCycles<N>,
+    channel: usize,
+}
+
+impl<'a, const N: usize> embedded_hal::pwm::ErrorType for SoftPwmChannel<'a, N> {
+    type Error = Infallible;
+}
+
+impl<'a, const N: usize> embedded_hal::pwm::SetDutyCycle for SoftPwmChannel<'a, N> {
+    #[inline]
+    fn max_duty_cycle(&self) -> u16 {
+        self.duties.steps
+    }
+    #[inline]
+    fn set_duty_cycle(&mut self, duty: u16) -> Result<(), Self::Error> {
+        self.duties.set(self.channel, duty);
+        Ok(())
+    }
+}
+
+/// Software PWM driving `N` output pads, active high.
+pub struct SoftPwm<'a, const N: usize> {
+    pins: [&'a mut dyn OutputPin<Error = Infallible>; N],
+    duties: &'a DutyCycles<N>,
+    active: [u16; N],
+    step: u16,
+}
+
+impl<'a, const N: usize> SoftPwm<'a, N> {
+    /// Create software PWM on `pins` with duty cycles from `duties`, driving all pins low.
+    #[inline]
+    pub fn new(
+        mut pins: [&'a mut dyn OutputPin<Error = Infallible>; N],
+        duties: &'a DutyCycles<N>,
+    ) -> Self {
+        for pin in pins.iter_mut() {
+            let _ = pin.set_low();
+        }
+        Self {
+            pins,
+            duties,
+            active: [0; N],
+            step: 0,
+        }
+    }
+    /// Advance one tick; call this function on each periodic timer interrupt.
+    #[inline]
+    pub fn tick(&mut self) {
+        if self.step == 0 {
+            for (i, pin) in self.pins.iter_mut().enumerate() {
+                self.active[i] = self.duties.get(i);
+                let _ = if self.active[i] == 0 {
+                    pin.set_low()
+                } else {
+                    pin.set_high()
+                };
+            }
+        } else {
+            for (pin, &active) in self.pins.iter_mut().zip(&self.active) {
+                if active == self.step {
+                    let _ = pin.set_low();
+                }
+            }
+        }
+        self.step += 1;
+        if self.step == self.duties.steps {
+            self.step = 0;
+        }
+    }
+    /// Drive all pins low and release them.
+    #[inline]
+    pub fn free(mut self) -> [&'a mut dyn OutputPin<Error = Infallible>; N] {
+        for pin in self.pins.iter_mut() {
+            let _ = pin.set_low();
+        }
+        self.pins
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::FrequencyError;
+    use super::{DutyCycles, SoftPwm};
+    use core::{cell::Cell, convert::Infallible};
+    use embedded_hal::{digital::OutputPin, pwm::SetDutyCycle};
+    use embedded_time::rate::Hertz;
+
+    struct MockPin<'a>(&'a Cell<bool>);
+
+    impl embedded_hal::digital::ErrorType for MockPin<'_> {
+        type Error = Infallible;
+    }
+
+    impl OutputPin for MockPin<'_> {
+        fn set_low(&mut self) -> Result<(), Self::Error> {
+            self.0.set(false);
+            Ok(())
+        }
+        fn set_high(&mut self) -> Result<(), Self::Error> {
+            self.0.set(true);
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn struct_soft_pwm() {
+        let duties = DutyCycles::<3>::new(4);
+        assert_eq!(duties.tick_rate(Hertz(100)), Ok(Hertz(400)));
+        assert_eq!(
+            duties.tick_rate(Hertz(u32::MAX)),
+            Err(FrequencyError::OutOfRange)
+        );
+        duties.set(0, 1);
+        duties.channel(1).set_duty_cycle_fully_on().unwrap();
+        duties.set(2, 0);
+        let levels = [Cell::new(true), Cell::new(true), Cell::new(true)];
+        let (mut a, mut b, mut c) = (
+            MockPin(&levels[0]),
+            MockPin(&levels[1]),
+            MockPin(&levels[2]),
+        );
+        let mut pwm = SoftPwm::new([&mut a, &mut b, &mut c], &duties);
+        assert!(levels.iter().all(|l| !l.get()));
+        let mut output = [[false; 3]; 8];
+        for (i, out) in output.iter_mut().enumerate() {
+            if i == 2 {
+                // takes effect on next period
+                duties.set(0, 3);
+            }
+            pwm.tick();
+            *out = [levels[0].get(), levels[1].get(), levels[2].get()];
+        }
+        assert_eq!(
+            output.map(|l| l[0]),
+            [true, false, false, false, true, true, true, false]
+        );
+        assert!(output.iter().all(|l| l[1] && !l[2]));
+    }
+}
@@ -0,0 +1,135 @@
+//! Critical section implementations.
+//!
+//! With feature `critical-section-single-hart`, a critical section masks machine mode
+//! interrupts of the current hart, which is enough on single-core D1 as long as no other
+//! processor shares the data.
+//!
+//! With feature `critical-section-multi-core`, it additionally takes hardware spinlock
+//! [`LOCK`], so that data can be shared with DSP firmware taking the same lock. Call [`init`]
+//! with the spinlock driver before sharing; until then, only interrupts are masked:
+//!
+//! ```ignore
+//! static SPINLOCK: StaticCell<HwSpinlock<SPINLOCK>> = StaticCell::new();
+//! allwinner_hal::sync::init(SPINLOCK.init(HwSpinlock::new(p.spinlock, &p.ccu)));
+//! critical_section::with(|cs| SHARED.borrow(cs).set(1));
+//! ```
+
+#[cfg(all(
+    feature = "critical-section-single-hart",
+    feature = "critical-section-multi-core"
+))]
+compile_error!(
+    "features `critical-section-single-hart` and `critical-section-multi-core` are mutually exclusive"
+);
+
+#[cfg(feature = "critical-section-multi-core")]
+use crate::hwspinlock::{HwSpinlock, RegisterBlock};
+#[cfg(feature = "critical-section-multi-core")]
+use core::sync::atomic::{AtomicPtr, AtomicU32, Ordering};
+use critical_section::RawRestoreState;
+
+/// Hardware spinlock taken by multi-core critical sections.
+#[cfg(feature = "critical-section-multi-core")]
+pub const LOCK: usize = 31;
+
+struct CriticalSection;
+
+critical_section::set_impl!(CriticalSection);
+
+#[cfg(all(
+    feature = "critical-section-single-hart",
+    not(feature = "critical-section-multi-core")
+))]
+unsafe impl critical_section::Impl for CriticalSection {
+    #[inline]
+    unsafe fn acquire() -> RawRestoreState {
+        disable_interrupts()
+    }
+    #[inline]
+    unsafe fn release(was_enabled: RawRestoreState) {
+        if was_enabled {
+            unsafe { enable_interrupts() };
+        }
+    }
+}
+
+/// Spinlock registers, or null before [`init`].
+#[cfg(feature = "critical-section-multi-core")]
+static SPINLOCK: AtomicPtr<RegisterBlock> = AtomicPtr::new(core::ptr::null_mut());
+
+/// Nesting depth of critical sections on this hart, as hardware spinlocks are not reentrant.
+#[cfg(feature = "critical-section-multi-core")]
+static DEPTH: AtomicU32 = AtomicU32::new(0);
+
+/// Take hardware spinlock [`LOCK`] of `spinlock` in subsequent critical sections.
+///
+/// `spinlock` stays usable for other locks; lock [`LOCK`] should be left to critical sections.
+///
+/// # Panics
+///
+/// Panics if called inside a critical section, which would otherwise release [`LOCK`]
+/// without having taken it.
+#[cfg(feature = "critical-section-multi-core")]
+#[inline]
+pub fn init<S: AsRef<RegisterBlock>>(spinlock: &'static HwSpinlock<S>) {
+    assert_eq!(
+        DEPTH.load(Ordering::Relaxed),
+        0,
+        "spinlock must not be changed inside a critical section"
+    );
+    let registers = spinlock.register_block();
+    SPINLOCK.store(registers as *const _ as *mut _, Ordering::Release);
+}
+
+#[cfg(feature = "critical-section-multi-core")]
+unsafe impl critical_section::Impl for CriticalSection {
+    #[inline]
+    unsafe fn acquire() -> RawRestoreState {
+        let was_enabled = disable_interrupts();
+        if DEPTH.fetch_add(1, Ordering::Relaxed) == 0
+            && let Some(spinlock) = unsafe { SPINLOCK.load(Ordering::Acquire).as_ref() }
+        {
+            while spinlock.lock[LOCK].read() != 0 {
+                core::hint::spin_loop();
+            }
+        }
+        was_enabled
+    }
+    #[inline]
+    unsafe fn release(was_enabled: RawRestoreState) {
+        if DEPTH.fetch_sub(1, Ordering::Relaxed) == 1
+            && let Some(spinlock) = unsafe { SPINLOCK.load(Ordering::Acquire).as_ref() }
+        {
+            unsafe { spinlock.lock[LOCK].write(0) };
+        }
+        if was_enabled {
+            unsafe { enable_interrupts() };
+        }
+    }
+}
+
+/// Clear machine interrupt enable bit, returning whether it was set.
+#[cfg(target_arch = "riscv64")]
+#[inline]
+fn disable_interrupts() -> bool {
+    let mstatus: usize;
+    unsafe { core::arch::asm!("csrrci {}, mstatus, 8", out(reg) mstatus) };
+    mstatus & (1 << 3) != 0
+}
+
+/// Set machine interrupt enable bit.
+#[cfg(target_arch = "riscv64")]
+#[inline]
+unsafe fn enable_interrupts() {
+    unsafe { core::arch::asm!("csrsi mstatus, 8") };
+}
+
+#[cfg(not(target_arch = "riscv64"))]
+#[inline]
+fn disable_interrupts() -> bool {
+    false
+}
+
+#[cfg(not(target_arch = "riscv64"))]
+#[inline]
+unsafe fn enable_interrupts() {}
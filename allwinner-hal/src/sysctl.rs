@@ -5,16 +5,79 @@ use volatile_register::{RO, RW};
 /// System power, LDO and calibration controller registers.
 #[repr(C)]
 pub struct RegisterBlock {
-    /// System LDO Control Register.
-    // TODO: offset = 0x150
+    _reserved0: [u32; 2],
+    /// 0x008 - DSP Boot SRAM Remap Control register.
+    pub dsp_boot_ram_map: RW<DspBootRamMap>,
+    _reserved1: [u32; 81],
+    /// 0x150 - System LDO Control Register.
     pub ldo_control: RW<u32>,
-    /// Resistor Calibration Control register.
-    // TODO: offset = 0x160
+    _reserved2: [u32; 3],
+    /// 0x160 - Resistor Calibration Control register.
     pub zq_resistor_control: RW<u32>,
-    /// 240-Ohm Resistor Manual Control register.
-    // TODO: offset = 0x168
+    _reserved3: u32,
+    /// 0x168 - 240-Ohm Resistor Manual Control register.
     pub zq_resistor_240_control: RW<u32>,
-    /// Resistor Calibration Status register.
-    // TODO: offset = 0x16C
+    /// 0x16c - Resistor Calibration Status register.
     pub zq_resistor_state: RO<u32>,
 }
+
+/// DSP Boot SRAM Remap Control register.
+///
+/// DSP local SRAM is mapped into system address space on reset, so that CPU can load DSP
+/// firmware into it; it must be remapped to the DSP before DSP runs.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct DspBootRamMap(u32);
+
+impl DspBootRamMap {
+    const REMAP_TO_DSP: u32 = 1 << 0;
+
+    /// Check if DSP local SRAM is mapped to the DSP.
+    #[inline]
+    pub const fn is_mapped_to_dsp(self) -> bool {
+        self.0 & Self::REMAP_TO_DSP != 0
+    }
+    /// Map DSP local SRAM to the DSP.
+    #[inline]
+    pub const fn map_to_dsp(self) -> Self {
+        Self(self.0 | Self::REMAP_TO_DSP)
+    }
+    /// Map DSP local SRAM into system address space.
+    #[inline]
+    pub const fn map_to_system(self) -> Self {
+        Self(self.0 & !Self::REMAP_TO_DSP)
+    }
+}
+
+impl Default for DspBootRamMap {
+    #[inline]
+    fn default() -> Self {
+        Self(0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{DspBootRamMap, RegisterBlock};
+    use core::mem::offset_of;
+
+    #[test]
+    fn offset_sysctl() {
+        assert_eq!(offset_of!(RegisterBlock, dsp_boot_ram_map), 0x008);
+        assert_eq!(offset_of!(RegisterBlock, ldo_control), 0x150);
+        assert_eq!(offset_of!(RegisterBlock, zq_resistor_control), 0x160);
+        assert_eq!(offset_of!(RegisterBlock, zq_resistor_240_control), 0x168);
+        assert_eq!(offset_of!(RegisterBlock, zq_resistor_state), 0x16c);
+    }
+
+    #[test]
+    fn struct_dsp_boot_ram_map_functions() {
+        let mut val = DspBootRamMap::default();
+        assert!(!val.is_mapped_to_dsp());
+        val = val.map_to_dsp();
+        assert!(val.is_mapped_to_dsp());
+        assert_eq!(val.0, 0x00000001);
+        val = val.map_to_system();
+        assert_eq!(val.0, 0x00000000);
+    }
+}
@@ -0,0 +1,869 @@
+//! Timing Controller for LCD panels.
+//!
+//! TCON LCD generates data clock, sync and data enable signals of a parallel RGB panel from
+//! [`Timing`], and shifts out pixels of display engine or of a built-in test pattern
+//! selected by [`Source`]. Data clock is divided from video PLL 0, which [`Tcon::new`]
+//! programs to reach the pixel clock of the panel.
+//!
+//! Panels with 8-bit color channels are driven on the 6 most significant data lines of each
+//! channel; [`PixelFormat::Rgb666`] dithers pixels into them by frame rate modulation.
+//!
+//! Outputs stay in high impedance until [`Tcon::enable`], so that panel supply can be
+//! sequenced before its signals, and backlight turned on afterwards.
+//!
+//! LVDS panels are driven by [`Tcon::new_lvds`] on one link, or on two links carrying odd and
+//! even pixels for dual-link panels; link count and lanes in use follow the pads passed.
+//!
+//! [`Tcon::wait_for_vblank`] waits for the vertical blanking period, in which framebuffers can
+//! be swapped without tearing; it requires [`on_interrupt`] to be called from TCON LCD
+//! interrupt handler.
+//!
+//! On D1-like chips RGB666 panels are wired to `PD0`..=`PD21`; LVDS link 0 is wired to
+//! `PD0`..=`PD9`, and link 1 to `PD10`..=`PD19`.
+
+mod register;
+pub use register::*;
+
+use crate::{
+    ccu::{self, ClockConfig, ClockGate, PeriFactorN, TconLcdClockSource},
+    time::{Duration, Instant},
+};
+use atomic_waker::AtomicWaker;
+use core::{future::poll_fn, task::Poll};
+use embedded_time::rate::Hertz;
+
+static VBLANK_WAKER: AtomicWaker = AtomicWaker::new();
+
+/// TCON LCD interrupt entry point.
+///
+/// Call this function from TCON LCD interrupt handler when waiting with
+/// [`Tcon::wait_for_vblank`]. Vertical blank interrupt is disabled and the waiting task is
+/// woken; pending flag is left for that task to handle.
+#[inline]
+pub fn on_interrupt(tcon: &RegisterBlock) {
+    let state = tcon.global_interrupt.read();
+    if state.is_interrupt_enabled(Interrupt::VerticalBlank)
+        && state.has_interrupt(Interrupt::VerticalBlank)
+    {
+        unsafe {
+            tcon.global_interrupt
+                .modify(|val| val.disable_interrupt(Interrupt::VerticalBlank))
+        };
+        VBLANK_WAKER.wake();
+    }
+}
+
+/// Display timing of a panel.
+///
+/// Horizontal values are in pixels, vertical values in lines.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Timing {
+    /// Pixel clock frequency.
+    pub pixel_clock: Hertz,
+    /// Active pixels per line.
+    pub hactive: u16,
+    /// Pixels between end of active area and horizontal sync.
+    pub hfront_porch: u16,
+    /// Width of horizontal sync pulse.
+    pub hsync_len: u16,
+    /// Pixels between horizontal sync and start of active area.
+    pub hback_porch: u16,
+    /// Active lines per frame.
+    pub vactive: u16,
+    /// Lines between end of active area and vertical sync.
+    pub vfront_porch: u16,
+    /// Width of vertical sync pulse.
+    pub vsync_len: u16,
+    /// Lines between vertical sync and start of active area.
+    pub vback_porch: u16,
+}
+
+impl Timing {
+    /// Total pixels per line, including blanking.
+    #[inline]
+    pub const fn htotal(&self) -> u16 {
+        self.hactive + self.hfront_porch + self.hsync_len + self.hback_porch
+    }
+    /// Total lines per frame, including blanking.
+    #[inline]
+    pub const fn vtotal(&self) -> u16 {
+        self.vactive + self.vfront_porch + self.vsync_len + self.vback_porch
+    }
+    /// Check if timing fits TCON timing registers.
+    #[inline]
+    const fn is_supported(&self) -> bool {
+        self.hactive >= 1
+            && self.hactive <= 4096
+            && self.vactive >= 1
+            && self.vactive <= 4096
+            && self.hsync_len >= 1
+            && self.hsync_len <= 1024
+            && self.vsync_len >= 1
+            && self.vsync_len <= 1024
+            && self.hsync_len + self.hback_porch <= 4096
+            && self.vsync_len + self.vback_porch <= 4096
+            && self.htotal() <= 8192
+            && self.vtotal() <= 4095
+    }
+}
+
+/// Color format of panel data lines.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum PixelFormat {
+    /// 6 bits per channel, dithered from 8-bit pixels by frame rate modulation.
+    Rgb666,
+    /// 8 bits per channel, truncated to the 6 bits wired out on D1-like chips for RGB panels.
+    Rgb888,
+}
+
+/// TCON configuration structure.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Config {
+    /// Color format of panel.
+    pub format: PixelFormat,
+    /// Horizontal sync polarity.
+    pub hsync: Polarity,
+    /// Vertical sync polarity.
+    pub vsync: Polarity,
+    /// Data enable polarity.
+    pub data_enable: Polarity,
+    /// Data clock edge on which pixel data changes.
+    pub clock_edge: ClockEdge,
+    /// Pixel data source.
+    pub source: Source,
+}
+
+impl Default for Config {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            format: PixelFormat::Rgb666,
+            hsync: Polarity::ActiveLow,
+            vsync: Polarity::ActiveLow,
+            data_enable: Polarity::ActiveHigh,
+            clock_edge: ClockEdge::Falling,
+            source: Source::DisplayEngine,
+        }
+    }
+}
+
+/// LVDS TCON configuration structure.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct LvdsConfig {
+    /// Color format of panel, on 3 data lanes for RGB666 or 4 data lanes for RGB888.
+    pub format: PixelFormat,
+    /// Bit mapping of RGB888 panels.
+    pub mapping: LvdsMapping,
+    /// Horizontal sync polarity.
+    pub hsync: Polarity,
+    /// Vertical sync polarity.
+    pub vsync: Polarity,
+    /// Data enable polarity.
+    pub data_enable: Polarity,
+    /// Pixel data source.
+    pub source: Source,
+}
+
+impl Default for LvdsConfig {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            format: PixelFormat::Rgb888,
+            mapping: LvdsMapping::Vesa,
+            hsync: Polarity::ActiveLow,
+            vsync: Polarity::ActiveLow,
+            data_enable: Polarity::ActiveHigh,
+            source: Source::DisplayEngine,
+        }
+    }
+}
+
+/// Dividers from video PLL 0 to pixel clock.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct PixelClock {
+    /// Video PLL 0 4X output as a multiple of 24 MHz, in 12..=50.
+    pub pll_multiplier: u8,
+    /// Divider from video PLL 0 4X output to TCON clock, in 1..=16.
+    pub module_divider: u8,
+    /// Divider from TCON clock to data clock, in 6..=127.
+    pub dot_clock_divider: u8,
+}
+
+impl PixelClock {
+    /// Highest TCON clock frequency.
+    const MAX_MODULE_CLOCK: u32 = 600_000_000;
+
+    /// Find dividers closest to pixel clock `frequency`.
+    ///
+    /// Returns `None` if no dividers reach `frequency` within 1%.
+    #[inline]
+    pub const fn from_frequency(frequency: u32) -> Option<Self> {
+        Self::search(frequency, 6, 127)
+    }
+    /// Find dividers closest to LVDS link clock `frequency`.
+    ///
+    /// Data clock divider is fixed to 7, as each LVDS lane serializes 7 bits per clock.
+    /// Returns `None` if no dividers reach `frequency` within 1%.
+    #[inline]
+    pub const fn from_lvds_frequency(frequency: u32) -> Option<Self> {
+        Self::search(frequency, 7, 7)
+    }
+    #[inline]
+    const fn search(frequency: u32, min_divider: u32, max_divider: u32) -> Option<Self> {
+        if frequency == 0 {
+            return None;
+        }
+        let mut best: Option<Self> = None;
+        let mut best_error = u32::MAX;
+        let mut pll_multiplier = 12;
+        while pll_multiplier <= 50 {
+            let mut module_divider = 1;
+            while module_divider <= 16 {
+                let module = 24_000_000 * pll_multiplier / module_divider;
+                let divider = (module + frequency / 2) / frequency;
+                if module <= Self::MAX_MODULE_CLOCK
+                    && divider >= min_divider
+                    && divider <= max_divider
+                {
+                    let error = (module / divider).abs_diff(frequency);
+                    if error < best_error {
+                        best_error = error;
+                        best = Some(Self {
+                            pll_multiplier: pll_multiplier as u8,
+                            module_divider: module_divider as u8,
+                            dot_clock_divider: divider as u8,
+                        });
+                    }
+                }
+                module_divider += 1;
+            }
+            pll_multiplier += 1;
+        }
+        if best_error as u64 * 100 > frequency as u64 {
+            return None;
+        }
+        best
+    }
+    /// Video PLL 0 4X output frequency.
+    #[inline]
+    pub const fn pll_frequency(&self) -> Hertz {
+        Hertz(24_000_000 * self.pll_multiplier as u32)
+    }
+    /// Resulting pixel clock frequency.
+    #[inline]
+    pub const fn frequency(&self) -> Hertz {
+        Hertz(self.pll_frequency().0 / self.module_divider as u32 / self.dot_clock_divider as u32)
+    }
+}
+
+/// Error configuring TCON LCD for a panel timing.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum TimingError {
+    /// Timing exceeds TCON timing registers.
+    Unsupported,
+    /// Pixel clock can't be divided from video PLL 0.
+    PixelClock,
+    /// LVDS pads lack data lanes for pixel format.
+    DataLanes,
+}
+
+/// Managed TCON LCD structure with peripheral and pads.
+pub struct Tcon<TCON, PADS: Pads> {
+    tcon: TCON,
+    pads: PADS,
+    timing: Timing,
+    clock: PixelClock,
+    lvds: bool,
+}
+
+impl<TCON: AsRef<RegisterBlock>, PADS: RgbPads> Tcon<TCON, PADS> {
+    /// Create a TCON instance driving an RGB panel with `timing`, with outputs disabled.
+    ///
+    /// Video PLL 0 is reprogrammed for the pixel clock.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`TimingError::Unsupported`] if `timing` exceeds TCON timing registers, or
+    /// [`TimingError::PixelClock`] if its pixel clock can't be divided from video PLL 0. No
+    /// register is written in either case.
+    #[inline]
+    pub fn new(
+        tcon: TCON,
+        pads: PADS,
+        timing: Timing,
+        config: impl Into<Config>,
+        ccu: &ccu::RegisterBlock,
+    ) -> Result<Self, TimingError> {
+        let config = config.into();
+        if !timing.is_supported() {
+            return Err(TimingError::Unsupported);
+        }
+        let clock =
+            PixelClock::from_frequency(timing.pixel_clock.0).ok_or(TimingError::PixelClock)?;
+        let regs = tcon.as_ref();
+        init(regs, &timing, clock, config.format, config.source, ccu);
+        unsafe {
+            regs.io_polarity.write(
+                IoPolarity::default()
+                    .set_hsync_polarity(config.hsync)
+                    .set_vsync_polarity(config.vsync)
+                    .set_data_enable_polarity(config.data_enable)
+                    .set_clock_edge(config.clock_edge),
+            );
+        }
+        Ok(Self {
+            tcon,
+            pads,
+            timing,
+            clock,
+            lvds: false,
+        })
+    }
+}
+
+impl<TCON: AsRef<RegisterBlock>, PADS: LvdsPads> Tcon<TCON, PADS> {
+    /// Create a TCON instance driving an LVDS panel with `timing`, with outputs disabled.
+    ///
+    /// Dual-link pads split pixels into odd and even ones, so that each link clock runs at
+    /// half the pixel clock. Video PLL 0 is reprogrammed for the link clock.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`TimingError::Unsupported`] if `timing` exceeds TCON timing registers or has
+    /// an odd width on dual link, [`TimingError::DataLanes`] if `config` needs more data lanes
+    /// than `pads` provide, or [`TimingError::PixelClock`] if link clock can't be divided from
+    /// video PLL 0. No register is written in these cases.
+    #[inline]
+    pub fn new_lvds(
+        tcon: TCON,
+        pads: PADS,
+        timing: Timing,
+        config: impl Into<LvdsConfig>,
+        ccu: &ccu::RegisterBlock,
+    ) -> Result<Self, TimingError> {
+        let config = config.into();
+        if !timing.is_supported() || (PADS::DUAL_LINK && !timing.hactive.is_multiple_of(2)) {
+            return Err(TimingError::Unsupported);
+        }
+        let (bit_width, lanes) = match config.format {
+            PixelFormat::Rgb666 => (LvdsBitWidth::Bits18, 3),
+            PixelFormat::Rgb888 => (LvdsBitWidth::Bits24, 4),
+        };
+        if PADS::DATA_LANES < lanes {
+            return Err(TimingError::DataLanes);
+        }
+        let links = if PADS::DUAL_LINK { 2 } else { 1 };
+        let clock = PixelClock::from_lvds_frequency(timing.pixel_clock.0 / links)
+            .ok_or(TimingError::PixelClock)?;
+        let regs = tcon.as_ref();
+        init(regs, &timing, clock, config.format, config.source, ccu);
+        let interface = LvdsInterface::default()
+            .select_tcon_clock()
+            .normal_polarity()
+            .set_mapping(config.mapping)
+            .set_bit_width(bit_width);
+        let interface = if PADS::DUAL_LINK {
+            interface.enable_dual_link()
+        } else {
+            interface
+        };
+        unsafe {
+            regs.io_polarity.write(
+                IoPolarity::default()
+                    .set_hsync_polarity(config.hsync)
+                    .set_vsync_polarity(config.vsync)
+                    .set_data_enable_polarity(config.data_enable),
+            );
+            regs.lvds_interface.write(interface);
+        }
+        let analog = &regs.lvds_analog[..links as usize];
+        for link in analog {
+            unsafe { link.write(LvdsAnalog::default().set_tuning(2, 3, 2).enable()) };
+        }
+        // Bias of LVDS PHY settles before lane drivers are enabled.
+        let settled = Instant::now() + Duration::from_micros(5);
+        while Instant::now() < settled {
+            core::hint::spin_loop();
+        }
+        let data_lanes = (1 << lanes) - 1;
+        for link in analog {
+            unsafe { link.modify(|val| val.enable_clock_lane().set_data_lanes(data_lanes)) };
+        }
+        Ok(Self {
+            tcon,
+            pads,
+            timing,
+            clock,
+            lvds: true,
+        })
+    }
+}
+
+/// Program clocks, timing and pixel processing registers shared by RGB and LVDS panels.
+#[inline]
+fn init(
+    regs: &RegisterBlock,
+    timing: &Timing,
+    clock: PixelClock,
+    format: PixelFormat,
+    source: Source,
+    ccu: &ccu::RegisterBlock,
+) {
+    // note(unsafe): async read and write using ccu registers
+    unsafe {
+        ccu::set_video0_pll_frequency(ccu, clock.pll_frequency());
+        ccu::TCONLCD::reconfigure(
+            ccu,
+            TconLcdClockSource::PllVideo0x4,
+            clock.module_divider - 1,
+            PeriFactorN::N1,
+        );
+    }
+    let start_delay = (timing.vtotal() - timing.vactive).min(30) as u8;
+    unsafe {
+        regs.global_control.write(GlobalControl::default());
+        regs.global_interrupt.write(GlobalInterrupt::default());
+        regs.io_tristate.write(IoTristate::default());
+        regs.lvds_interface.write(LvdsInterface::default());
+        regs.control.write(
+            Control::default()
+                .set_start_delay(start_delay)
+                .set_source(source),
+        );
+        regs.dot_clock
+            .write(DotClock::default().set_divider(clock.dot_clock_divider));
+        regs.size.write(
+            Size::default()
+                .set_width(timing.hactive)
+                .set_height(timing.vactive),
+        );
+        regs.horizontal.write(
+            HorizontalTiming::default()
+                .set_total(timing.htotal())
+                .set_back_porch(timing.hsync_len + timing.hback_porch),
+        );
+        regs.vertical.write(
+            VerticalTiming::default()
+                .set_total(timing.vtotal())
+                .set_back_porch(timing.vsync_len + timing.vback_porch),
+        );
+        regs.sync_width.write(
+            SyncWidth::default()
+                .set_hsync(timing.hsync_len)
+                .set_vsync(timing.vsync_len),
+        );
+        regs.hv_interface
+            .write(HvInterface::default().set_mode(HvMode::Parallel));
+        match format {
+            PixelFormat::Rgb666 => {
+                for seed in &regs.frm_seed {
+                    seed.write(0x1111_1111);
+                }
+                for (table, val) in regs.frm_table.iter().zip(FRM_TABLE) {
+                    table.write(val);
+                }
+                regs.frm_control.write(
+                    FrmControl::default()
+                        .set_five_bit_channels(false, false, false)
+                        .enable(),
+                );
+            }
+            PixelFormat::Rgb888 => regs.frm_control.write(FrmControl::default()),
+        }
+    }
+}
+
+impl<TCON: AsRef<RegisterBlock>, PADS: Pads> Tcon<TCON, PADS> {
+    /// Start timing generator and drive panel signals.
+    #[inline]
+    pub fn enable(&mut self) {
+        let regs = self.tcon.as_ref();
+        unsafe {
+            regs.dot_clock.modify(|val| val.enable());
+            regs.control.modify(|val| val.enable());
+            regs.global_control.modify(|val| val.enable());
+            if self.lvds {
+                regs.lvds_interface.modify(|val| val.enable());
+            } else {
+                regs.io_tristate.modify(|val| val.enable_output());
+            }
+        }
+    }
+    /// Put panel signals in high impedance and stop timing generator.
+    #[inline]
+    pub fn disable(&mut self) {
+        let regs = self.tcon.as_ref();
+        unsafe {
+            regs.io_tristate.modify(|val| val.disable_output());
+            regs.lvds_interface.modify(|val| val.disable());
+            regs.global_control.modify(|val| val.disable());
+            regs.control.modify(|val| val.disable());
+            regs.dot_clock.modify(|val| val.disable());
+        }
+    }
+    /// Check if panel signals are driven.
+    #[inline]
+    pub fn is_enabled(&self) -> bool {
+        self.tcon.as_ref().global_control.read().is_enabled()
+    }
+    /// Select pixel data source, e.g. a test pattern.
+    #[inline]
+    pub fn set_source(&mut self, source: Source) {
+        unsafe {
+            self.tcon
+                .as_ref()
+                .control
+                .modify(|val| val.set_source(source))
+        };
+    }
+    /// Get display timing of panel.
+    #[inline]
+    pub fn timing(&self) -> Timing {
+        self.timing
+    }
+    /// Get actual pixel clock frequency.
+    #[inline]
+    pub fn pixel_clock(&self) -> Hertz {
+        self.clock.frequency()
+    }
+    /// Enable `interrupt`.
+    #[inline]
+    pub fn enable_interrupt(&mut self, interrupt: Interrupt) {
+        unsafe {
+            self.tcon
+                .as_ref()
+                .global_interrupt
+                .modify(|val| val.enable_interrupt(interrupt))
+        };
+    }
+    /// Disable `interrupt`.
+    #[inline]
+    pub fn disable_interrupt(&mut self, interrupt: Interrupt) {
+        unsafe {
+            self.tcon
+                .as_ref()
+                .global_interrupt
+                .modify(|val| val.disable_interrupt(interrupt))
+        };
+    }
+    /// Check if `interrupt` is pending.
+    #[inline]
+    pub fn has_interrupt(&self, interrupt: Interrupt) -> bool {
+        self.tcon
+            .as_ref()
+            .global_interrupt
+            .read()
+            .has_interrupt(interrupt)
+    }
+    /// Clear pending `interrupt`.
+    #[inline]
+    pub fn clear_interrupt(&mut self, interrupt: Interrupt) {
+        unsafe {
+            self.tcon
+                .as_ref()
+                .global_interrupt
+                .modify(|val| val.clear_interrupt(interrupt))
+        };
+    }
+    /// Wait for the start of next vertical blanking period.
+    ///
+    /// Requires [`on_interrupt`] to be called from TCON LCD interrupt handler, and TCON to be
+    /// enabled; otherwise this function never returns.
+    #[inline]
+    pub async fn wait_for_vblank(&mut self) {
+        self.clear_interrupt(Interrupt::VerticalBlank);
+        self.enable_interrupt(Interrupt::VerticalBlank);
+        poll_fn(|cx| {
+            VBLANK_WAKER.register(cx.waker());
+            if self.has_interrupt(Interrupt::VerticalBlank) {
+                Poll::Ready(())
+            } else {
+                Poll::Pending
+            }
+        })
+        .await;
+        self.disable_interrupt(Interrupt::VerticalBlank);
+        self.clear_interrupt(Interrupt::VerticalBlank);
+    }
+    /// Get a temporary borrow on the underlying GPIO pads.
+    #[inline]
+    pub fn pads<F, T>(&mut self, f: F) -> T
+    where
+        F: FnOnce(&mut PADS) -> T,
+    {
+        f(&mut self.pads)
+    }
+    /// Close TCON and release peripheral.
+    #[inline]
+    pub fn free(mut self, ccu: &ccu::RegisterBlock) -> (TCON, PADS) {
+        self.disable();
+        for link in &self.tcon.as_ref().lvds_analog {
+            unsafe { link.modify(|val| val.disable()) };
+        }
+        // note(unsafe): async read and write using ccu registers
+        unsafe { ccu::TCONLCD::free(ccu) };
+        (self.tcon, self.pads)
+    }
+}
+
+/// Frame rate modulation pattern tables.
+const FRM_TABLE: [u32; 4] = [0x0101_0000, 0x1515_1111, 0x5757_5555, 0x7F7F_7777];
+
+/// Valid panel pads of TCON LCD.
+pub trait Pads {}
+
+/// Valid RGB panel pads.
+///
+/// Implemented for `(clk, de, hsync, vsync, red, green, blue)`, with each color a tuple of
+/// its 6 data lines from the least significant bit.
+pub trait RgbPads: Pads {}
+
+/// Valid LVDS panel pads.
+///
+/// Implemented for a single link on link 0, and for a pair of links `(link0, link1)` of a
+/// dual-link panel. A link is `(clkp, clkn, data0, data1, data2)` for RGB666 panels, or
+/// `(clkp, clkn, data0, data1, data2, data3)` for RGB888 panels, with each data lane a
+/// tuple of its positive and negative pads.
+pub trait LvdsPads: Pads {
+    /// Whether pixels are split on two links.
+    const DUAL_LINK: bool;
+    /// Number of data lanes on each link.
+    const DATA_LANES: u8;
+}
+
+/// Valid LVDS link `L` pads.
+pub trait LvdsLink<const L: usize> {
+    /// Number of data lanes.
+    const DATA_LANES: u8;
+}
+
+/// Valid data clock pad.
+pub trait Clk {}
+
+/// Valid data enable pad.
+pub trait DataEnable {}
+
+/// Valid horizontal sync pad.
+pub trait Hsync {}
+
+/// Valid vertical sync pad.
+pub trait Vsync {}
+
+/// Valid pad for bit `N` of 24-bit data bus.
+pub trait Data<const N: usize> {}
+
+/// Valid positive clock pad of LVDS link `L`.
+pub trait LvdsClockP<const L: usize> {}
+
+/// Valid negative clock pad of LVDS link `L`.
+pub trait LvdsClockN<const L: usize> {}
+
+/// Valid positive pad of data lane `N` on LVDS link `L`.
+pub trait LvdsDataP<const L: usize, const N: usize> {}
+
+/// Valid negative pad of data lane `N` on LVDS link `L`.
+pub trait LvdsDataN<const L: usize, const N: usize> {}
+
+impl<CLK, DE, HS, VS, R0, R1, R2, R3, R4, R5, G0, G1, G2, G3, G4, G5, B0, B1, B2, B3, B4, B5> Pads
+    for (
+        CLK,
+        DE,
+        HS,
+        VS,
+        (R0, R1, R2, R3, R4, R5),
+        (G0, G1, G2, G3, G4, G5),
+        (B0, B1, B2, B3, B4, B5),
+    )
+where
+    CLK: Clk,
+    DE: DataEnable,
+    HS: Hsync,
+    VS: Vsync,
+    R0: Data<18>,
+    R1: Data<19>,
+    R2: Data<20>,
+    R3: Data<21>,
+    R4: Data<22>,
+    R5: Data<23>,
+    G0: Data<10>,
+    G1: Data<11>,
+    G2: Data<12>,
+    G3: Data<13>,
+    G4: Data<14>,
+    G5: Data<15>,
+    B0: Data<2>,
+    B1: Data<3>,
+    B2: Data<4>,
+    B3: Data<5>,
+    B4: Data<6>,
+    B5: Data<7>,
+{
+}
+
+impl<CLK, DE, HS, VS, R0, R1, R2, R3, R4, R5, G0, G1, G2, G3, G4, G5, B0, B1, B2, B3, B4, B5>
+    RgbPads
+    for (
+        CLK,
+        DE,
+        HS,
+        VS,
+        (R0, R1, R2, R3, R4, R5),
+        (G0, G1, G2, G3, G4, G5),
+        (B0, B1, B2, B3, B4, B5),
+    )
+where
+    Self: Pads,
+{
+}
+
+impl<const L: usize, CKP, CKN, D0P, D0N, D1P, D1N, D2P, D2N> LvdsLink<L>
+    for (CKP, CKN, (D0P, D0N), (D1P, D1N), (D2P, D2N))
+where
+    CKP: LvdsClockP<L>,
+    CKN: LvdsClockN<L>,
+    D0P: LvdsDataP<L, 0>,
+    D0N: LvdsDataN<L, 0>,
+    D1P: LvdsDataP<L, 1>,
+    D1N: LvdsDataN<L, 1>,
+    D2P: LvdsDataP<L, 2>,
+    D2N: LvdsDataN<L, 2>,
+{
+    const DATA_LANES: u8 = 3;
+}
+
+impl<const L: usize, CKP, CKN, D0P, D0N, D1P, D1N, D2P, D2N, D3P, D3N> LvdsLink<L>
+    for (CKP, CKN, (D0P, D0N), (D1P, D1N), (D2P, D2N), (D3P, D3N))
+where
+    CKP: LvdsClockP<L>,
+    CKN: LvdsClockN<L>,
+    D0P: LvdsDataP<L, 0>,
+    D0N: LvdsDataN<L, 0>,
+    D1P: LvdsDataP<L, 1>,
+    D1N: LvdsDataN<L, 1>,
+    D2P: LvdsDataP<L, 2>,
+    D2N: LvdsDataN<L, 2>,
+    D3P: LvdsDataP<L, 3>,
+    D3N: LvdsDataN<L, 3>,
+{
+    const DATA_LANES: u8 = 4;
+}
+
+impl<CKP, CKN, D0, D1, D2> Pads for (CKP, CKN, D0, D1, D2) where Self: LvdsLink<0> {}
+
+impl<CKP, CKN, D0, D1, D2> LvdsPads for (CKP, CKN, D0, D1, D2)
+where
+    Self: LvdsLink<0>,
+{
+    const DUAL_LINK: bool = false;
+    const DATA_LANES: u8 = <Self as LvdsLink<0>>::DATA_LANES;
+}
+
+impl<CKP, CKN, D0, D1, D2, D3> Pads for (CKP, CKN, D0, D1, D2, D3) where Self: LvdsLink<0> {}
+
+impl<CKP, CKN, D0, D1, D2, D3> LvdsPads for (CKP, CKN, D0, D1, D2, D3)
+where
+    Self: LvdsLink<0>,
+{
+    const DUAL_LINK: bool = false;
+    const DATA_LANES: u8 = <Self as LvdsLink<0>>::DATA_LANES;
+}
+
+impl<A: LvdsLink<0>, B: LvdsLink<1>> Pads for (A, B) {}
+
+impl<A: LvdsLink<0>, B: LvdsLink<1>> LvdsPads for (A, B) {
+    const DUAL_LINK: bool = true;
+    const DATA_LANES: u8 = if A::DATA_LANES < B::DATA_LANES {
+        A::DATA_LANES
+    } else {
+        B::DATA_LANES
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{LvdsClockN, LvdsClockP, LvdsDataN, LvdsDataP, LvdsPads, PixelClock, Timing};
+    use embedded_time::rate::Hertz;
+
+    #[test]
+    fn struct_timing_functions() {
+        let timing = Timing {
+            pixel_clock: Hertz(33_300_000),
+            hactive: 800,
+            hfront_porch: 210,
+            hsync_len: 20,
+            hback_porch: 26,
+            vactive: 480,
+            vfront_porch: 22,
+            vsync_len: 10,
+            vback_porch: 13,
+        };
+        assert_eq!(timing.htotal(), 1056);
+        assert_eq!(timing.vtotal(), 525);
+        assert!(timing.is_supported());
+        assert!(
+            !Timing {
+                hsync_len: 0,
+                ..timing
+            }
+            .is_supported()
+        );
+    }
+
+    #[test]
+    fn function_pixel_clock_from_frequency() {
+        let clock = PixelClock::from_frequency(33_300_000).unwrap();
+        assert_eq!(
+            clock,
+            PixelClock {
+                pll_multiplier: 25,
+                module_divider: 1,
+                dot_clock_divider: 18,
+            }
+        );
+        assert_eq!(clock.frequency().0, 33_333_333);
+        assert!(clock.pll_frequency().0 / clock.module_divider as u32 <= 600_000_000);
+        assert!((6..=127).contains(&clock.dot_clock_divider));
+
+        let clock = PixelClock::from_frequency(74_250_000).unwrap();
+        assert_eq!(clock.pll_frequency().0, 888_000_000);
+        let clock = PixelClock::from_frequency(51_200_000).unwrap();
+        assert!(clock.frequency().0.abs_diff(51_200_000) <= 51_200_000 / 100);
+        let clock = PixelClock::from_frequency(9_000_000).unwrap();
+        assert_eq!(clock.frequency().0, 9_000_000);
+
+        assert_eq!(PixelClock::from_frequency(0), None);
+        assert_eq!(PixelClock::from_frequency(200_000_000), None);
+    }
+
+    #[test]
+    fn function_pixel_clock_from_lvds_frequency() {
+        let clock = PixelClock::from_lvds_frequency(51_200_000).unwrap();
+        assert_eq!(clock.dot_clock_divider, 7);
+        assert!(clock.frequency().0.abs_diff(51_200_000) <= 51_200_000 / 100);
+        assert!(clock.pll_frequency().0 / clock.module_divider as u32 <= 600_000_000);
+
+        let clock = PixelClock::from_lvds_frequency(72_000_000).unwrap();
+        assert_eq!(clock.frequency().0, 72_000_000);
+        assert_eq!(PixelClock::from_lvds_frequency(100_000_000), None);
+    }
+
+    struct Pin;
+    impl<const L: usize> LvdsClockP<L> for Pin {}
+    impl<const L: usize> LvdsClockN<L> for Pin {}
+    impl<const L: usize, const N: usize> LvdsDataP<L, N> for Pin {}
+    impl<const L: usize, const N: usize> LvdsDataN<L, N> for Pin {}
+
+    fn lvds_pads<P: LvdsPads>() -> (bool, u8) {
+        (P::DUAL_LINK, P::DATA_LANES)
+    }
+
+    #[test]
+    fn trait_lvds_pads() {
+        type Link6 = (Pin, Pin, (Pin, Pin), (Pin, Pin), (Pin, Pin));
+        type Link8 = (Pin, Pin, (Pin, Pin), (Pin, Pin), (Pin, Pin), (Pin, Pin));
+        assert_eq!(lvds_pads::<Link6>(), (false, 3));
+        assert_eq!(lvds_pads::<Link8>(), (false, 4));
+        assert_eq!(lvds_pads::<(Link8, Link8)>(), (true, 4));
+        assert_eq!(lvds_pads::<(Link8, Link6)>(), (true, 3));
+    }
+}
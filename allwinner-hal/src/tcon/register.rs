@@ -0,0 +1,1188 @@
+use volatile_register::RW;
+
+/// Timing Controller LCD registers.
+#[repr(C)]
+pub struct RegisterBlock {
+    /// 0x00 - Global control register.
+    pub global_control: RW<GlobalControl>,
+    /// 0x04 - Global interrupt register.
+    pub global_interrupt: RW<GlobalInterrupt>,
+    _reserved0: [u32; 2],
+    /// 0x10 - Frame rate modulation control register.
+    pub frm_control: RW<FrmControl>,
+    /// 0x14..=0x28 - Frame rate modulation seed registers.
+    pub frm_seed: [RW<u32>; 6],
+    /// 0x2c..=0x38 - Frame rate modulation table registers.
+    pub frm_table: [RW<u32>; 4],
+    _reserved1: u32,
+    /// 0x40 - Control register.
+    pub control: RW<Control>,
+    /// 0x44 - Data clock register.
+    pub dot_clock: RW<DotClock>,
+    /// 0x48 - Panel size register.
+    pub size: RW<Size>,
+    /// 0x4c - Horizontal timing register.
+    pub horizontal: RW<HorizontalTiming>,
+    /// 0x50 - Vertical timing register.
+    pub vertical: RW<VerticalTiming>,
+    /// 0x54 - Sync pulse width register.
+    pub sync_width: RW<SyncWidth>,
+    /// 0x58 - HV panel interface register.
+    pub hv_interface: RW<HvInterface>,
+    _reserved2: [u32; 10],
+    /// 0x84 - LVDS interface register.
+    pub lvds_interface: RW<LvdsInterface>,
+    /// 0x88 - IO polarity register.
+    pub io_polarity: RW<IoPolarity>,
+    /// 0x8c - IO tri-state register.
+    pub io_tristate: RW<IoTristate>,
+    _reserved3: [u32; 100],
+    /// 0x220..=0x224 - LVDS link 0 and link 1 analog registers.
+    pub lvds_analog: [RW<LvdsAnalog>; 2],
+}
+
+/// TCON top registers of display subsystem, routing display engine mixers to timing controllers.
+#[repr(C)]
+pub struct TopRegisterBlock {
+    _reserved0: [u32; 7],
+    /// 0x1c - Display engine port select register.
+    pub port_select: RW<PortSelect>,
+    /// 0x20 - Clock gate and HDMI source register.
+    pub gate_source: RW<GateSource>,
+}
+
+/// Global control register.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct GlobalControl(u32);
+
+impl GlobalControl {
+    const TCON_EN: u32 = 1 << 31;
+    const GAMMA_EN: u32 = 1 << 30;
+
+    /// Check if TCON is enabled.
+    #[inline]
+    pub const fn is_enabled(self) -> bool {
+        self.0 & Self::TCON_EN != 0
+    }
+    /// Enable TCON.
+    #[inline]
+    pub const fn enable(self) -> Self {
+        Self(self.0 | Self::TCON_EN)
+    }
+    /// Disable TCON.
+    #[inline]
+    pub const fn disable(self) -> Self {
+        Self(self.0 & !Self::TCON_EN)
+    }
+    /// Check if gamma correction is enabled.
+    #[inline]
+    pub const fn is_gamma_enabled(self) -> bool {
+        self.0 & Self::GAMMA_EN != 0
+    }
+    /// Enable gamma correction.
+    #[inline]
+    pub const fn enable_gamma(self) -> Self {
+        Self(self.0 | Self::GAMMA_EN)
+    }
+    /// Disable gamma correction.
+    #[inline]
+    pub const fn disable_gamma(self) -> Self {
+        Self(self.0 & !Self::GAMMA_EN)
+    }
+}
+
+impl Default for GlobalControl {
+    #[inline]
+    fn default() -> Self {
+        Self(0)
+    }
+}
+
+/// TCON interrupt.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Interrupt {
+    /// Vertical blanking period started.
+    VerticalBlank = 15,
+    /// Configured line is being scanned.
+    Line = 13,
+}
+
+/// Global interrupt register.
+///
+/// Pending flags are cleared by writing zero; writing one to them has no effect.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct GlobalInterrupt(u32);
+
+impl GlobalInterrupt {
+    /// Check if `interrupt` is enabled.
+    #[inline]
+    pub const fn is_interrupt_enabled(self, interrupt: Interrupt) -> bool {
+        self.0 & (1 << (interrupt as u32 + 16)) != 0
+    }
+    /// Enable `interrupt`.
+    #[inline]
+    pub const fn enable_interrupt(self, interrupt: Interrupt) -> Self {
+        Self(self.0 | (1 << (interrupt as u32 + 16)))
+    }
+    /// Disable `interrupt`.
+    #[inline]
+    pub const fn disable_interrupt(self, interrupt: Interrupt) -> Self {
+        Self(self.0 & !(1 << (interrupt as u32 + 16)))
+    }
+    /// Check if `interrupt` is pending.
+    #[inline]
+    pub const fn has_interrupt(self, interrupt: Interrupt) -> bool {
+        self.0 & (1 << interrupt as u32) != 0
+    }
+    /// Clear pending `interrupt`, keeping other pending flags.
+    #[inline]
+    pub const fn clear_interrupt(self, interrupt: Interrupt) -> Self {
+        Self((self.0 | 0xFFFF) & !(1 << interrupt as u32))
+    }
+}
+
+impl Default for GlobalInterrupt {
+    #[inline]
+    fn default() -> Self {
+        Self(0)
+    }
+}
+
+/// Frame rate modulation control register.
+///
+/// Frame rate modulation dithers 8-bit color channels down to 6 or 5 bits on panels with
+/// fewer data lines.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct FrmControl(u32);
+
+impl FrmControl {
+    const FRM_EN: u32 = 1 << 31;
+    const MODE_R: u32 = 1 << 6;
+    const MODE_G: u32 = 1 << 5;
+    const MODE_B: u32 = 1 << 4;
+
+    /// Check if frame rate modulation is enabled.
+    #[inline]
+    pub const fn is_enabled(self) -> bool {
+        self.0 & Self::FRM_EN != 0
+    }
+    /// Enable frame rate modulation.
+    #[inline]
+    pub const fn enable(self) -> Self {
+        Self(self.0 | Self::FRM_EN)
+    }
+    /// Disable frame rate modulation.
+    #[inline]
+    pub const fn disable(self) -> Self {
+        Self(self.0 & !Self::FRM_EN)
+    }
+    /// Get output bits of red, green and blue channels, 6 or 5.
+    #[inline]
+    pub const fn channel_bits(self) -> (u8, u8, u8) {
+        (
+            6 - (self.0 & Self::MODE_R != 0) as u8,
+            6 - (self.0 & Self::MODE_G != 0) as u8,
+            6 - (self.0 & Self::MODE_B != 0) as u8,
+        )
+    }
+    /// Set output bits of red, green and blue channels, 5 if `true` or 6 otherwise.
+    #[inline]
+    pub const fn set_five_bit_channels(self, r: bool, g: bool, b: bool) -> Self {
+        let mut val = self.0 & !(Self::MODE_R | Self::MODE_G | Self::MODE_B);
+        if r {
+            val |= Self::MODE_R;
+        }
+        if g {
+            val |= Self::MODE_G;
+        }
+        if b {
+            val |= Self::MODE_B;
+        }
+        Self(val)
+    }
+}
+
+impl Default for FrmControl {
+    #[inline]
+    fn default() -> Self {
+        Self(0)
+    }
+}
+
+/// Pixel data source of TCON.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Source {
+    /// Display engine output.
+    DisplayEngine = 0,
+    /// Color check test pattern.
+    ColorCheck = 1,
+    /// Grayscale check test pattern.
+    GrayscaleCheck = 2,
+    /// Black by white check test pattern.
+    BlackWhiteCheck = 3,
+    /// All data lines low.
+    AllZero = 4,
+    /// All data lines high.
+    AllOne = 5,
+    /// Gridding check test pattern.
+    Gridding = 7,
+}
+
+/// Control register.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct Control(u32);
+
+impl Control {
+    const LCD_EN: u32 = 1 << 31;
+    const RB_SWAP: u32 = 1 << 23;
+    const START_DELAY: u32 = 0x1F << 4;
+    const SRC_SEL: u32 = 0x7;
+
+    /// Check if timing generator is enabled.
+    #[inline]
+    pub const fn is_enabled(self) -> bool {
+        self.0 & Self::LCD_EN != 0
+    }
+    /// Enable timing generator.
+    #[inline]
+    pub const fn enable(self) -> Self {
+        Self(self.0 | Self::LCD_EN)
+    }
+    /// Disable timing generator.
+    #[inline]
+    pub const fn disable(self) -> Self {
+        Self(self.0 & !Self::LCD_EN)
+    }
+    /// Check if red and blue channels are swapped.
+    #[inline]
+    pub const fn is_red_blue_swapped(self) -> bool {
+        self.0 & Self::RB_SWAP != 0
+    }
+    /// Swap red and blue channels.
+    #[inline]
+    pub const fn swap_red_blue(self) -> Self {
+        Self(self.0 | Self::RB_SWAP)
+    }
+    /// Keep red and blue channels in place.
+    #[inline]
+    pub const fn unswap_red_blue(self) -> Self {
+        Self(self.0 & !Self::RB_SWAP)
+    }
+    /// Get start delay in lines, from vertical sync to first data line.
+    #[inline]
+    pub const fn start_delay(self) -> u8 {
+        ((self.0 & Self::START_DELAY) >> 4) as u8
+    }
+    /// Set start delay in lines, in 0..=31.
+    #[inline]
+    pub const fn set_start_delay(self, val: u8) -> Self {
+        Self((self.0 & !Self::START_DELAY) | ((val as u32 & 0x1F) << 4))
+    }
+    /// Get pixel data source.
+    #[inline]
+    pub const fn source(self) -> Source {
+        match self.0 & Self::SRC_SEL {
+            0 => Source::DisplayEngine,
+            1 => Source::ColorCheck,
+            2 => Source::GrayscaleCheck,
+            3 => Source::BlackWhiteCheck,
+            4 => Source::AllZero,
+            5 => Source::AllOne,
+            _ => Source::Gridding,
+        }
+    }
+    /// Set pixel data source.
+    #[inline]
+    pub const fn set_source(self, val: Source) -> Self {
+        Self((self.0 & !Self::SRC_SEL) | val as u32)
+    }
+}
+
+impl Default for Control {
+    #[inline]
+    fn default() -> Self {
+        Self(0)
+    }
+}
+
+/// Data clock register.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct DotClock(u32);
+
+impl DotClock {
+    const DCLK_EN: u32 = 0xF << 28;
+    const DCLK_DIV: u32 = 0x7F;
+
+    /// Check if data clock outputs are enabled.
+    #[inline]
+    pub const fn is_enabled(self) -> bool {
+        self.0 & Self::DCLK_EN == Self::DCLK_EN
+    }
+    /// Enable data clock outputs.
+    #[inline]
+    pub const fn enable(self) -> Self {
+        Self(self.0 | Self::DCLK_EN)
+    }
+    /// Disable data clock outputs.
+    #[inline]
+    pub const fn disable(self) -> Self {
+        Self(self.0 & !Self::DCLK_EN)
+    }
+    /// Get divider from TCON clock to data clock.
+    #[inline]
+    pub const fn divider(self) -> u8 {
+        (self.0 & Self::DCLK_DIV) as u8
+    }
+    /// Set divider from TCON clock to data clock, in 1..=127.
+    #[inline]
+    pub const fn set_divider(self, val: u8) -> Self {
+        Self((self.0 & !Self::DCLK_DIV) | (val as u32 & 0x7F))
+    }
+}
+
+impl Default for DotClock {
+    #[inline]
+    fn default() -> Self {
+        Self(0)
+    }
+}
+
+/// Panel size register.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct Size(u32);
+
+impl Size {
+    const WIDTH: u32 = 0xFFF << 16;
+    const HEIGHT: u32 = 0xFFF;
+
+    /// Get active width in pixels.
+    #[inline]
+    pub const fn width(self) -> u16 {
+        (((self.0 & Self::WIDTH) >> 16) + 1) as u16
+    }
+    /// Set active width in pixels, in 1..=4096.
+    #[inline]
+    pub const fn set_width(self, val: u16) -> Self {
+        Self((self.0 & !Self::WIDTH) | ((val as u32 - 1) << 16))
+    }
+    /// Get active height in lines.
+    #[inline]
+    pub const fn height(self) -> u16 {
+        ((self.0 & Self::HEIGHT) + 1) as u16
+    }
+    /// Set active height in lines, in 1..=4096.
+    #[inline]
+    pub const fn set_height(self, val: u16) -> Self {
+        Self((self.0 & !Self::HEIGHT) | (val as u32 - 1))
+    }
+}
+
+impl Default for Size {
+    #[inline]
+    fn default() -> Self {
+        Self(0)
+    }
+}
+
+/// Horizontal timing register.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct HorizontalTiming(u32);
+
+impl HorizontalTiming {
+    const TOTAL: u32 = 0x1FFF << 16;
+    const BACK_PORCH: u32 = 0xFFF;
+
+    /// Get total line length in data clock cycles.
+    #[inline]
+    pub const fn total(self) -> u16 {
+        (((self.0 & Self::TOTAL) >> 16) + 1) as u16
+    }
+    /// Set total line length in data clock cycles, in 1..=8192.
+    #[inline]
+    pub const fn set_total(self, val: u16) -> Self {
+        Self((self.0 & !Self::TOTAL) | ((val as u32 - 1) << 16))
+    }
+    /// Get cycles from start of horizontal sync to first active pixel.
+    #[inline]
+    pub const fn back_porch(self) -> u16 {
+        ((self.0 & Self::BACK_PORCH) + 1) as u16
+    }
+    /// Set cycles from start of horizontal sync to first active pixel, in 1..=4096.
+    #[inline]
+    pub const fn set_back_porch(self, val: u16) -> Self {
+        Self((self.0 & !Self::BACK_PORCH) | (val as u32 - 1))
+    }
+}
+
+impl Default for HorizontalTiming {
+    #[inline]
+    fn default() -> Self {
+        Self(0)
+    }
+}
+
+/// Vertical timing register.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct VerticalTiming(u32);
+
+impl VerticalTiming {
+    const TOTAL: u32 = 0x1FFF << 16;
+    const BACK_PORCH: u32 = 0xFFF;
+
+    /// Get total frame length in lines.
+    #[inline]
+    pub const fn total(self) -> u16 {
+        (((self.0 & Self::TOTAL) >> 16) / 2) as u16
+    }
+    /// Set total frame length in lines, in 1..=4095.
+    #[inline]
+    pub const fn set_total(self, val: u16) -> Self {
+        Self((self.0 & !Self::TOTAL) | ((val as u32 * 2) << 16))
+    }
+    /// Get lines from start of vertical sync to first active line.
+    #[inline]
+    pub const fn back_porch(self) -> u16 {
+        ((self.0 & Self::BACK_PORCH) + 1) as u16
+    }
+    /// Set lines from start of vertical sync to first active line, in 1..=4096.
+    #[inline]
+    pub const fn set_back_porch(self, val: u16) -> Self {
+        Self((self.0 & !Self::BACK_PORCH) | (val as u32 - 1))
+    }
+}
+
+impl Default for VerticalTiming {
+    #[inline]
+    fn default() -> Self {
+        Self(0)
+    }
+}
+
+/// Sync pulse width register.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct SyncWidth(u32);
+
+impl SyncWidth {
+    const HSYNC: u32 = 0x3FF << 16;
+    const VSYNC: u32 = 0x3FF;
+
+    /// Get horizontal sync pulse width in data clock cycles.
+    #[inline]
+    pub const fn hsync(self) -> u16 {
+        (((self.0 & Self::HSYNC) >> 16) + 1) as u16
+    }
+    /// Set horizontal sync pulse width in data clock cycles, in 1..=1024.
+    #[inline]
+    pub const fn set_hsync(self, val: u16) -> Self {
+        Self((self.0 & !Self::HSYNC) | ((val as u32 - 1) << 16))
+    }
+    /// Get vertical sync pulse width in lines.
+    #[inline]
+    pub const fn vsync(self) -> u16 {
+        ((self.0 & Self::VSYNC) + 1) as u16
+    }
+    /// Set vertical sync pulse width in lines, in 1..=1024.
+    #[inline]
+    pub const fn set_vsync(self, val: u16) -> Self {
+        Self((self.0 & !Self::VSYNC) | (val as u32 - 1))
+    }
+}
+
+impl Default for SyncWidth {
+    #[inline]
+    fn default() -> Self {
+        Self(0)
+    }
+}
+
+/// Data bus mode of HV panel interface.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum HvMode {
+    /// One pixel per data clock cycle on parallel data lines.
+    Parallel = 0,
+    /// One pixel per 3 data clock cycles on 8 data lines.
+    Serial = 8,
+}
+
+/// HV panel interface register.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct HvInterface(u32);
+
+impl HvInterface {
+    const HV_MODE: u32 = 0xF << 28;
+
+    /// Get data bus mode.
+    #[inline]
+    pub const fn mode(self) -> HvMode {
+        match (self.0 & Self::HV_MODE) >> 28 {
+            8 => HvMode::Serial,
+            _ => HvMode::Parallel,
+        }
+    }
+    /// Set data bus mode.
+    #[inline]
+    pub const fn set_mode(self, val: HvMode) -> Self {
+        Self((self.0 & !Self::HV_MODE) | ((val as u32) << 28))
+    }
+}
+
+impl Default for HvInterface {
+    #[inline]
+    fn default() -> Self {
+        Self(0)
+    }
+}
+
+/// Active level of a control signal.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Polarity {
+    /// Signal is asserted when high.
+    ActiveHigh,
+    /// Signal is asserted when low.
+    ActiveLow,
+}
+
+/// Data clock edge on which pixel data changes.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ClockEdge {
+    /// Data changes on rising edge, and is sampled by panel on falling edge.
+    Rising,
+    /// Data changes on falling edge, and is sampled by panel on rising edge.
+    Falling,
+}
+
+/// IO polarity register.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct IoPolarity(u32);
+
+impl IoPolarity {
+    const DE_NEGATIVE: u32 = 1 << 27;
+    const DCLK_NEGEDGE: u32 = 1 << 26;
+    const HSYNC_POSITIVE: u32 = 1 << 25;
+    const VSYNC_POSITIVE: u32 = 1 << 24;
+
+    /// Get data enable polarity.
+    #[inline]
+    pub const fn data_enable_polarity(self) -> Polarity {
+        if self.0 & Self::DE_NEGATIVE != 0 {
+            Polarity::ActiveLow
+        } else {
+            Polarity::ActiveHigh
+        }
+    }
+    /// Set data enable polarity.
+    #[inline]
+    pub const fn set_data_enable_polarity(self, val: Polarity) -> Self {
+        match val {
+            Polarity::ActiveLow => Self(self.0 | Self::DE_NEGATIVE),
+            Polarity::ActiveHigh => Self(self.0 & !Self::DE_NEGATIVE),
+        }
+    }
+    /// Get data clock edge on which pixel data changes.
+    #[inline]
+    pub const fn clock_edge(self) -> ClockEdge {
+        if self.0 & Self::DCLK_NEGEDGE != 0 {
+            ClockEdge::Falling
+        } else {
+            ClockEdge::Rising
+        }
+    }
+    /// Set data clock edge on which pixel data changes.
+    #[inline]
+    pub const fn set_clock_edge(self, val: ClockEdge) -> Self {
+        match val {
+            ClockEdge::Falling => Self(self.0 | Self::DCLK_NEGEDGE),
+            ClockEdge::Rising => Self(self.0 & !Self::DCLK_NEGEDGE),
+        }
+    }
+    /// Get horizontal sync polarity.
+    #[inline]
+    pub const fn hsync_polarity(self) -> Polarity {
+        if self.0 & Self::HSYNC_POSITIVE != 0 {
+            Polarity::ActiveHigh
+        } else {
+            Polarity::ActiveLow
+        }
+    }
+    /// Set horizontal sync polarity.
+    #[inline]
+    pub const fn set_hsync_polarity(self, val: Polarity) -> Self {
+        match val {
+            Polarity::ActiveHigh => Self(self.0 | Self::HSYNC_POSITIVE),
+            Polarity::ActiveLow => Self(self.0 & !Self::HSYNC_POSITIVE),
+        }
+    }
+    /// Get vertical sync polarity.
+    #[inline]
+    pub const fn vsync_polarity(self) -> Polarity {
+        if self.0 & Self::VSYNC_POSITIVE != 0 {
+            Polarity::ActiveHigh
+        } else {
+            Polarity::ActiveLow
+        }
+    }
+    /// Set vertical sync polarity.
+    #[inline]
+    pub const fn set_vsync_polarity(self, val: Polarity) -> Self {
+        match val {
+            Polarity::ActiveHigh => Self(self.0 | Self::VSYNC_POSITIVE),
+            Polarity::ActiveLow => Self(self.0 & !Self::VSYNC_POSITIVE),
+        }
+    }
+}
+
+impl Default for IoPolarity {
+    #[inline]
+    fn default() -> Self {
+        Self(0)
+    }
+}
+
+/// IO tri-state register.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct IoTristate(u32);
+
+impl IoTristate {
+    const ALL: u32 = 0x0FFF_FFFF;
+
+    /// Check if control and data outputs are driven.
+    #[inline]
+    pub const fn is_output_enabled(self) -> bool {
+        self.0 & Self::ALL == 0
+    }
+    /// Drive control and data outputs.
+    #[inline]
+    pub const fn enable_output(self) -> Self {
+        Self(self.0 & !Self::ALL)
+    }
+    /// Put control and data outputs in high impedance.
+    #[inline]
+    pub const fn disable_output(self) -> Self {
+        Self(self.0 | Self::ALL)
+    }
+}
+
+impl Default for IoTristate {
+    #[inline]
+    fn default() -> Self {
+        Self(0xFFFF_FFFF)
+    }
+}
+
+/// LVDS bit mapping of 24-bit colors.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum LvdsMapping {
+    /// VESA (SPWG) mapping, with the 6 most significant bits of each channel on lanes 0..=2.
+    Vesa,
+    /// JEIDA mapping, with the 6 least significant bits of each channel on lanes 0..=2.
+    Jeida,
+}
+
+/// LVDS bit width of pixels.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum LvdsBitWidth {
+    /// 24-bit pixels on 4 data lanes.
+    Bits24,
+    /// 18-bit pixels on 3 data lanes.
+    Bits18,
+}
+
+/// LVDS interface register.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct LvdsInterface(u32);
+
+impl LvdsInterface {
+    const LVDS_EN: u32 = 1 << 31;
+    const LVDS_LINK: u32 = 1 << 30;
+    const LVDS_MODE: u32 = 1 << 27;
+    const LVDS_BITWIDTH: u32 = 1 << 26;
+    const LVDS_CLK_SEL: u32 = 1 << 20;
+    const LVDS_CLK_POL: u32 = 1 << 4;
+    const LVDS_DATA_POL: u32 = 0xF;
+
+    /// Check if LVDS output is enabled.
+    #[inline]
+    pub const fn is_enabled(self) -> bool {
+        self.0 & Self::LVDS_EN != 0
+    }
+    /// Enable LVDS output.
+    #[inline]
+    pub const fn enable(self) -> Self {
+        Self(self.0 | Self::LVDS_EN)
+    }
+    /// Disable LVDS output.
+    #[inline]
+    pub const fn disable(self) -> Self {
+        Self(self.0 & !Self::LVDS_EN)
+    }
+    /// Check if pixels are split into odd and even ones on two links.
+    #[inline]
+    pub const fn is_dual_link(self) -> bool {
+        self.0 & Self::LVDS_LINK != 0
+    }
+    /// Split pixels into odd and even ones on two links.
+    #[inline]
+    pub const fn enable_dual_link(self) -> Self {
+        Self(self.0 | Self::LVDS_LINK)
+    }
+    /// Send all pixels on link 0.
+    #[inline]
+    pub const fn disable_dual_link(self) -> Self {
+        Self(self.0 & !Self::LVDS_LINK)
+    }
+    /// Get LVDS bit mapping.
+    #[inline]
+    pub const fn mapping(self) -> LvdsMapping {
+        if self.0 & Self::LVDS_MODE != 0 {
+            LvdsMapping::Jeida
+        } else {
+            LvdsMapping::Vesa
+        }
+    }
+    /// Set LVDS bit mapping.
+    #[inline]
+    pub const fn set_mapping(self, val: LvdsMapping) -> Self {
+        match val {
+            LvdsMapping::Vesa => Self(self.0 & !Self::LVDS_MODE),
+            LvdsMapping::Jeida => Self(self.0 | Self::LVDS_MODE),
+        }
+    }
+    /// Get LVDS bit width.
+    #[inline]
+    pub const fn bit_width(self) -> LvdsBitWidth {
+        if self.0 & Self::LVDS_BITWIDTH != 0 {
+            LvdsBitWidth::Bits18
+        } else {
+            LvdsBitWidth::Bits24
+        }
+    }
+    /// Set LVDS bit width.
+    #[inline]
+    pub const fn set_bit_width(self, val: LvdsBitWidth) -> Self {
+        match val {
+            LvdsBitWidth::Bits24 => Self(self.0 & !Self::LVDS_BITWIDTH),
+            LvdsBitWidth::Bits18 => Self(self.0 | Self::LVDS_BITWIDTH),
+        }
+    }
+    /// Check if LVDS is clocked by TCON clock.
+    #[inline]
+    pub const fn is_tcon_clock_selected(self) -> bool {
+        self.0 & Self::LVDS_CLK_SEL != 0
+    }
+    /// Clock LVDS by TCON clock.
+    #[inline]
+    pub const fn select_tcon_clock(self) -> Self {
+        Self(self.0 | Self::LVDS_CLK_SEL)
+    }
+    /// Check if clock and data lanes have normal polarity.
+    #[inline]
+    pub const fn is_polarity_normal(self) -> bool {
+        self.0 & (Self::LVDS_CLK_POL | Self::LVDS_DATA_POL)
+            == Self::LVDS_CLK_POL | Self::LVDS_DATA_POL
+    }
+    /// Use normal polarity on clock and data lanes.
+    #[inline]
+    pub const fn normal_polarity(self) -> Self {
+        Self(self.0 | Self::LVDS_CLK_POL | Self::LVDS_DATA_POL)
+    }
+    /// Invert polarity of clock and data lanes.
+    #[inline]
+    pub const fn invert_polarity(self) -> Self {
+        Self(self.0 & !(Self::LVDS_CLK_POL | Self::LVDS_DATA_POL))
+    }
+}
+
+impl Default for LvdsInterface {
+    #[inline]
+    fn default() -> Self {
+        Self(0x0000_0000)
+    }
+}
+
+/// LVDS analog register of one link.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct LvdsAnalog(u32);
+
+impl LvdsAnalog {
+    const EN_MB: u32 = 1 << 31;
+    const EN_LDO: u32 = 1 << 30;
+    const EN_DRVC: u32 = 1 << 24;
+    const EN_DRVD: u32 = 0xF << 20;
+    const C: u32 = 0x3 << 17;
+    const V: u32 = 0x3 << 8;
+    const PD: u32 = 0x3 << 4;
+
+    /// Check if bias and regulator of LVDS PHY are enabled.
+    #[inline]
+    pub const fn is_enabled(self) -> bool {
+        self.0 & (Self::EN_MB | Self::EN_LDO) == Self::EN_MB | Self::EN_LDO
+    }
+    /// Enable bias and regulator of LVDS PHY.
+    #[inline]
+    pub const fn enable(self) -> Self {
+        Self(self.0 | Self::EN_MB | Self::EN_LDO)
+    }
+    /// Disable bias, regulator and lane drivers of LVDS PHY.
+    #[inline]
+    pub const fn disable(self) -> Self {
+        Self(self.0 & !(Self::EN_MB | Self::EN_LDO | Self::EN_DRVC | Self::EN_DRVD))
+    }
+    /// Check if clock lane driver is enabled.
+    #[inline]
+    pub const fn is_clock_lane_enabled(self) -> bool {
+        self.0 & Self::EN_DRVC != 0
+    }
+    /// Enable clock lane driver.
+    #[inline]
+    pub const fn enable_clock_lane(self) -> Self {
+        Self(self.0 | Self::EN_DRVC)
+    }
+    /// Get bit mask of enabled data lane drivers.
+    #[inline]
+    pub const fn data_lanes(self) -> u8 {
+        ((self.0 & Self::EN_DRVD) >> 20) as u8
+    }
+    /// Enable data lane drivers on set bits of `mask`.
+    #[inline]
+    pub const fn set_data_lanes(self, mask: u8) -> Self {
+        Self((self.0 & !Self::EN_DRVD) | (((mask as u32) << 20) & Self::EN_DRVD))
+    }
+    /// Get analog tuning fields as `(c, v, pd)`.
+    #[inline]
+    pub const fn tuning(self) -> (u8, u8, u8) {
+        (
+            ((self.0 & Self::C) >> 17) as u8,
+            ((self.0 & Self::V) >> 8) as u8,
+            ((self.0 & Self::PD) >> 4) as u8,
+        )
+    }
+    /// Set analog tuning fields, each in 0..=3.
+    #[inline]
+    pub const fn set_tuning(self, c: u8, v: u8, pd: u8) -> Self {
+        Self(
+            (self.0 & !(Self::C | Self::V | Self::PD))
+                | (((c as u32) << 17) & Self::C)
+                | (((v as u32) << 8) & Self::V)
+                | (((pd as u32) << 4) & Self::PD),
+        )
+    }
+}
+
+impl Default for LvdsAnalog {
+    #[inline]
+    fn default() -> Self {
+        Self(0x0000_0000)
+    }
+}
+
+/// Timing controller fed by a display engine mixer.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum DeOutput {
+    /// TCON LCD 0.
+    Lcd0 = 0,
+    /// TCON LCD 1.
+    Lcd1 = 1,
+    /// TCON TV 0.
+    Tv0 = 2,
+    /// TCON TV 1.
+    Tv1 = 3,
+}
+
+/// Display engine port select register.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct PortSelect(u32);
+
+impl PortSelect {
+    const DE_PORT: u32 = 0x3;
+
+    /// Get timing controller fed by display engine mixer `I`.
+    #[inline]
+    pub const fn de_output<const I: usize>(self) -> DeOutput {
+        match (self.0 >> (4 * I)) & Self::DE_PORT {
+            0 => DeOutput::Lcd0,
+            1 => DeOutput::Lcd1,
+            2 => DeOutput::Tv0,
+            3 => DeOutput::Tv1,
+            _ => unreachable!(),
+        }
+    }
+    /// Set timing controller fed by display engine mixer `I`.
+    #[inline]
+    pub const fn set_de_output<const I: usize>(self, val: DeOutput) -> Self {
+        Self((self.0 & !(Self::DE_PORT << (4 * I))) | ((val as u32) << (4 * I)))
+    }
+}
+
+impl Default for PortSelect {
+    #[inline]
+    fn default() -> Self {
+        Self(0x0000_0000)
+    }
+}
+
+/// Timing controller feeding HDMI transmitter.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum HdmiSource {
+    /// HDMI transmitter is not fed.
+    None = 0,
+    /// TCON TV 0.
+    Tv0 = 1,
+    /// TCON TV 1.
+    Tv1 = 2,
+}
+
+/// Clock gate and HDMI source register.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct GateSource(u32);
+
+impl GateSource {
+    const HDMI_SRC: u32 = 0x3 << 28;
+    const TV_GATE: u32 = 1 << 20;
+
+    /// Get timing controller feeding HDMI transmitter.
+    #[inline]
+    pub const fn hdmi_source(self) -> HdmiSource {
+        match (self.0 & Self::HDMI_SRC) >> 28 {
+            1 => HdmiSource::Tv0,
+            2 => HdmiSource::Tv1,
+            _ => HdmiSource::None,
+        }
+    }
+    /// Set timing controller feeding HDMI transmitter.
+    #[inline]
+    pub const fn set_hdmi_source(self, val: HdmiSource) -> Self {
+        Self((self.0 & !Self::HDMI_SRC) | ((val as u32) << 28))
+    }
+    /// Check if clock of TCON TV `I` is enabled.
+    #[inline]
+    pub const fn is_tv_clock_enabled<const I: usize>(self) -> bool {
+        self.0 & (Self::TV_GATE << (4 * I)) != 0
+    }
+    /// Enable clock of TCON TV `I`.
+    #[inline]
+    pub const fn enable_tv_clock<const I: usize>(self) -> Self {
+        Self(self.0 | (Self::TV_GATE << (4 * I)))
+    }
+    /// Disable clock of TCON TV `I`.
+    #[inline]
+    pub const fn disable_tv_clock<const I: usize>(self) -> Self {
+        Self(self.0 & !(Self::TV_GATE << (4 * I)))
+    }
+}
+
+impl Default for GateSource {
+    #[inline]
+    fn default() -> Self {
+        Self(0x0000_0000)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        ClockEdge, Control, DeOutput, DotClock, FrmControl, GateSource, GlobalControl,
+        GlobalInterrupt, HdmiSource, HorizontalTiming, HvInterface, HvMode, Interrupt, IoPolarity,
+        IoTristate, LvdsAnalog, LvdsBitWidth, LvdsInterface, LvdsMapping, Polarity, PortSelect,
+        RegisterBlock, Size, Source, SyncWidth, TopRegisterBlock, VerticalTiming,
+    };
+    use core::mem::offset_of;
+
+    #[test]
+    fn offset_tcon() {
+        assert_eq!(offset_of!(RegisterBlock, global_control), 0x00);
+        assert_eq!(offset_of!(RegisterBlock, global_interrupt), 0x04);
+        assert_eq!(offset_of!(RegisterBlock, frm_control), 0x10);
+        assert_eq!(offset_of!(RegisterBlock, frm_seed), 0x14);
+        assert_eq!(offset_of!(RegisterBlock, frm_table), 0x2c);
+        assert_eq!(offset_of!(RegisterBlock, control), 0x40);
+        assert_eq!(offset_of!(RegisterBlock, dot_clock), 0x44);
+        assert_eq!(offset_of!(RegisterBlock, size), 0x48);
+        assert_eq!(offset_of!(RegisterBlock, horizontal), 0x4c);
+        assert_eq!(offset_of!(RegisterBlock, vertical), 0x50);
+        assert_eq!(offset_of!(RegisterBlock, sync_width), 0x54);
+        assert_eq!(offset_of!(RegisterBlock, hv_interface), 0x58);
+        assert_eq!(offset_of!(RegisterBlock, lvds_interface), 0x84);
+        assert_eq!(offset_of!(RegisterBlock, io_polarity), 0x88);
+        assert_eq!(offset_of!(RegisterBlock, io_tristate), 0x8c);
+        assert_eq!(offset_of!(RegisterBlock, lvds_analog), 0x220);
+        assert_eq!(offset_of!(TopRegisterBlock, port_select), 0x1c);
+        assert_eq!(offset_of!(TopRegisterBlock, gate_source), 0x20);
+    }
+
+    #[test]
+    fn struct_global_functions() {
+        let val = GlobalControl::default().enable().enable_gamma();
+        assert_eq!(val.0, 0xC000_0000);
+        assert!(val.is_enabled() && val.is_gamma_enabled());
+        assert_eq!(val.disable().disable_gamma().0, 0);
+
+        let val = GlobalInterrupt::default().enable_interrupt(Interrupt::VerticalBlank);
+        assert_eq!(val.0, 0x8000_0000);
+        assert!(val.is_interrupt_enabled(Interrupt::VerticalBlank));
+        assert!(!val.is_interrupt_enabled(Interrupt::Line));
+        let val = GlobalInterrupt(0xA000_A000);
+        assert!(val.has_interrupt(Interrupt::VerticalBlank));
+        assert!(val.has_interrupt(Interrupt::Line));
+        assert_eq!(val.clear_interrupt(Interrupt::VerticalBlank).0, 0xA000_7FFF);
+        assert_eq!(
+            val.disable_interrupt(Interrupt::VerticalBlank).0,
+            0x2000_A000
+        );
+    }
+
+    #[test]
+    fn struct_frm_control_functions() {
+        let val = FrmControl::default().enable();
+        assert_eq!(val.0, 0x8000_0000);
+        assert_eq!(val.channel_bits(), (6, 6, 6));
+        let val = val.set_five_bit_channels(true, false, true);
+        assert_eq!(val.0, 0x8000_0050);
+        assert_eq!(val.channel_bits(), (5, 6, 5));
+        assert!(!val.disable().is_enabled());
+    }
+
+    #[test]
+    fn struct_control_functions() {
+        let val = Control::default()
+            .enable()
+            .set_start_delay(22)
+            .set_source(Source::ColorCheck);
+        assert_eq!(val.0, 0x8000_0161);
+        assert!(val.is_enabled());
+        assert_eq!(val.start_delay(), 22);
+        assert_eq!(val.source(), Source::ColorCheck);
+        assert_eq!(val.set_source(Source::Gridding).source(), Source::Gridding);
+        let val = val.swap_red_blue();
+        assert!(val.is_red_blue_swapped());
+        assert_eq!(val.unswap_red_blue().disable().0, 0x0000_0161);
+    }
+
+    #[test]
+    fn struct_timing_functions() {
+        let val = DotClock::default().enable().set_divider(9);
+        assert_eq!(val.0, 0xF000_0009);
+        assert!(val.is_enabled());
+        assert_eq!(val.divider(), 9);
+        assert!(!val.disable().is_enabled());
+
+        let val = Size::default().set_width(800).set_height(480);
+        assert_eq!(val.0, 0x031F_01DF);
+        assert_eq!((val.width(), val.height()), (800, 480));
+
+        let val = HorizontalTiming::default()
+            .set_total(1056)
+            .set_back_porch(46);
+        assert_eq!(val.0, 0x041F_002D);
+        assert_eq!((val.total(), val.back_porch()), (1056, 46));
+
+        let val = VerticalTiming::default().set_total(525).set_back_porch(23);
+        assert_eq!(val.0, 0x041A_0016);
+        assert_eq!((val.total(), val.back_porch()), (525, 23));
+
+        let val = SyncWidth::default().set_hsync(20).set_vsync(10);
+        assert_eq!(val.0, 0x0013_0009);
+        assert_eq!((val.hsync(), val.vsync()), (20, 10));
+
+        let val = HvInterface::default().set_mode(HvMode::Serial);
+        assert_eq!(val.0, 0x8000_0000);
+        assert_eq!(val.mode(), HvMode::Serial);
+    }
+
+    #[test]
+    fn struct_io_functions() {
+        let val = IoPolarity::default();
+        assert_eq!(val.hsync_polarity(), Polarity::ActiveLow);
+        assert_eq!(val.data_enable_polarity(), Polarity::ActiveHigh);
+        let val = val
+            .set_hsync_polarity(Polarity::ActiveHigh)
+            .set_vsync_polarity(Polarity::ActiveHigh)
+            .set_data_enable_polarity(Polarity::ActiveLow)
+            .set_clock_edge(ClockEdge::Falling);
+        assert_eq!(val.0, 0x0F00_0000);
+        assert_eq!(val.vsync_polarity(), Polarity::ActiveHigh);
+        assert_eq!(val.clock_edge(), ClockEdge::Falling);
+
+        let val = IoTristate::default();
+        assert!(!val.is_output_enabled());
+        let val = val.enable_output();
+        assert_eq!(val.0, 0xF000_0000);
+        assert!(val.is_output_enabled());
+        assert_eq!(val.disable_output().0, 0xFFFF_FFFF);
+    }
+
+    #[test]
+    fn struct_port_select_functions() {
+        let val = PortSelect::default();
+        assert_eq!(val.de_output::<0>(), DeOutput::Lcd0);
+        let val = val
+            .set_de_output::<0>(DeOutput::Tv0)
+            .set_de_output::<1>(DeOutput::Lcd1);
+        assert_eq!(val.0, 0x0000_0012);
+        assert_eq!(val.de_output::<0>(), DeOutput::Tv0);
+        assert_eq!(val.de_output::<1>(), DeOutput::Lcd1);
+        assert_eq!(val.set_de_output::<0>(DeOutput::Lcd0).0, 0x0000_0010);
+    }
+
+    #[test]
+    fn struct_gate_source_functions() {
+        let val = GateSource::default();
+        assert_eq!(val.hdmi_source(), HdmiSource::None);
+        assert!(!val.is_tv_clock_enabled::<0>());
+        let val = val.set_hdmi_source(HdmiSource::Tv0).enable_tv_clock::<0>();
+        assert_eq!(val.0, 0x1010_0000);
+        assert_eq!(val.hdmi_source(), HdmiSource::Tv0);
+        assert!(val.is_tv_clock_enabled::<0>());
+        let val = val.enable_tv_clock::<1>().set_hdmi_source(HdmiSource::Tv1);
+        assert_eq!(val.0, 0x2110_0000);
+        assert_eq!(val.disable_tv_clock::<0>().0, 0x2100_0000);
+    }
+
+    #[test]
+    fn struct_lvds_functions() {
+        let val = LvdsInterface::default()
+            .select_tcon_clock()
+            .normal_polarity()
+            .set_mapping(LvdsMapping::Jeida)
+            .set_bit_width(LvdsBitWidth::Bits18)
+            .enable_dual_link()
+            .enable();
+        assert_eq!(val.0, 0xCC10_001F);
+        assert!(val.is_enabled() && val.is_dual_link() && val.is_tcon_clock_selected());
+        assert!(val.is_polarity_normal());
+        assert_eq!(val.mapping(), LvdsMapping::Jeida);
+        assert_eq!(val.bit_width(), LvdsBitWidth::Bits18);
+        let val = val
+            .set_mapping(LvdsMapping::Vesa)
+            .set_bit_width(LvdsBitWidth::Bits24)
+            .disable_dual_link()
+            .invert_polarity()
+            .disable();
+        assert_eq!(val.0, 0x0010_0000);
+
+        let val = LvdsAnalog::default().set_tuning(2, 3, 2).enable();
+        assert_eq!(val.0, 0xC004_0320);
+        assert!(val.is_enabled());
+        assert_eq!(val.tuning(), (2, 3, 2));
+        let val = val.enable_clock_lane().set_data_lanes(0x7);
+        assert_eq!(val.0, 0xC174_0320);
+        assert!(val.is_clock_lane_enabled());
+        assert_eq!(val.data_lanes(), 0x7);
+        assert_eq!(val.disable().0, 0x0004_0320);
+    }
+}
@@ -0,0 +1,125 @@
+//! Thermal governor throttling CPU clock by die temperature.
+//!
+//! Fanless boards may overheat and lock up silently under sustained load. [`Governor`]
+//! compares temperature from [`Ths`] with configured thresholds on each periodic tick,
+//! and steps CPU PLL frequency down one level when a threshold is reached, or back up one
+//! level when temperature falls below it by a hysteresis margin.
+
+use crate::ccu;
+use crate::ths::{self, Ths};
+use embedded_time::rate::Hertz;
+
+/// Throttling level, entered when temperature reaches its threshold.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Step {
+    /// Temperature threshold in millidegrees Celsius.
+    pub threshold: i32,
+    /// CPU PLL frequency at this level.
+    pub frequency: Hertz,
+}
+
+/// Thermal governor stepping CPU PLL frequency across temperature thresholds.
+#[derive(Clone, Debug)]
+pub struct Governor<'a> {
+    max: Hertz,
+    steps: &'a [Step],
+    hysteresis: i32,
+    level: usize,
+}
+
+impl<'a> Governor<'a> {
+    /// Create a governor running at `max` frequency below all thresholds.
+    ///
+    /// `steps` should be sorted by increasing threshold and decreasing frequency.
+    /// Temperature should fall `hysteresis` millidegrees below a threshold before leaving
+    /// its level.
+    #[inline]
+    pub const fn new(max: Hertz, steps: &'a [Step], hysteresis: i32) -> Self {
+        Self {
+            max,
+            steps,
+            hysteresis,
+            level: 0,
+        }
+    }
+    /// Get current throttling level, zero when not throttled.
+    #[inline]
+    pub const fn level(&self) -> usize {
+        self.level
+    }
+    /// Get CPU PLL frequency of current level.
+    #[inline]
+    pub const fn frequency(&self) -> Hertz {
+        match self.level {
+            0 => self.max,
+            level => self.steps[level - 1].frequency,
+        }
+    }
+    /// Move at most one level according to `temperature` in millidegrees Celsius, and
+    /// return new frequency if level has changed.
+    #[inline]
+    pub fn update(&mut self, temperature: i32) -> Option<Hertz> {
+        if self.level < self.steps.len() && temperature >= self.steps[self.level].threshold {
+            self.level += 1;
+        } else if self.level > 0
+            && temperature < self.steps[self.level - 1].threshold - self.hysteresis
+        {
+            self.level -= 1;
+        } else {
+            return None;
+        }
+        Some(self.frequency())
+    }
+    /// Read latest temperature from `ths` and apply frequency of new level, if changed.
+    ///
+    /// Call this function on a periodic timer tick, slower than THS measure period.
+    ///
+    /// # Safety
+    ///
+    /// CPU supply voltage must support `max` frequency and all step frequencies.
+    #[inline]
+    pub unsafe fn tick<THS: AsRef<ths::RegisterBlock>>(
+        &mut self,
+        ths: &Ths<THS>,
+        ccu: &ccu::RegisterBlock,
+    ) -> Option<Hertz> {
+        let frequency = self.update(ths.temperature())?;
+        Some(unsafe { ccu::set_cpu_pll_frequency(ccu, frequency) })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Governor, Step};
+    use embedded_time::rate::Hertz;
+
+    const STEPS: [Step; 2] = [
+        Step {
+            threshold: 75_000,
+            frequency: Hertz(720_000_000),
+        },
+        Step {
+            threshold: 90_000,
+            frequency: Hertz(408_000_000),
+        },
+    ];
+
+    #[test]
+    fn struct_governor() {
+        let mut governor = Governor::new(Hertz(1_008_000_000), &STEPS, 5_000);
+        assert_eq!(governor.update(60_000), None);
+        assert_eq!(governor.frequency().0, 1_008_000_000);
+
+        assert_eq!(governor.update(95_000).map(|f| f.0), Some(720_000_000));
+        assert_eq!(governor.update(95_000).map(|f| f.0), Some(408_000_000));
+        assert_eq!(governor.update(99_000), None);
+        assert_eq!(governor.level(), 2);
+
+        // within hysteresis margin
+        assert_eq!(governor.update(86_000), None);
+        assert_eq!(governor.update(84_000).map(|f| f.0), Some(720_000_000));
+        assert_eq!(governor.update(71_000), None);
+        assert_eq!(governor.update(69_000).map(|f| f.0), Some(1_008_000_000));
+        assert_eq!(governor.level(), 0);
+    }
+}
@@ -0,0 +1,103 @@
+//! Thermal Sensor controller.
+//!
+//! THS measures die temperature periodically, about every 10 milliseconds after enabled.
+//! Readings use nominal conversion factors; per-chip calibration data from eFuse is not
+//! applied, so readings may be off by a few degrees.
+
+mod register;
+pub use register::*;
+
+use crate::ccu::{self, ClockGate};
+use crate::time::Deadline;
+
+/// Temperature at raw reading zero, in millidegrees Celsius.
+const TEMPERATURE_OFFSET: i32 = 188_552;
+/// Temperature decrease per raw reading step, in 1/10000 degrees Celsius.
+const TEMPERATURE_SCALE: i32 = 673;
+
+/// THS error.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Error {
+    /// Measurement did not finish before crate-wide blocking timeout.
+    Timeout,
+}
+
+/// Managed thermal sensor structure with peripheral.
+pub struct Ths<THS> {
+    ths: THS,
+}
+
+impl<THS: AsRef<RegisterBlock>> Ths<THS> {
+    /// Create a thermal sensor instance measuring every 10 milliseconds.
+    #[inline]
+    pub fn new(ths: THS, ccu: &ccu::RegisterBlock) -> Self {
+        // note(unsafe): async read and write using ccu registers
+        unsafe { ccu::THS::reset(ccu) };
+        let regs = ths.as_ref();
+        unsafe {
+            // 500-kHz ADC clock with 20-microsecond acquire time.
+            regs.control
+                .write(Control::default().set_divider(47).set_acquire_time(479));
+            regs.filter.write(
+                Filter::default()
+                    .set_filter_type(FilterType::Samples4)
+                    .enable(),
+            );
+            // 4096 * (58 + 1) cycles of 24-MHz clock, about 10 milliseconds.
+            regs.period.write(Period::default().set_period(58));
+            regs.data_int_status
+                .write(DataStatus::default().clear_pending());
+            regs.enable.write(Enable::default().enable());
+        }
+        Self { ths }
+    }
+    /// Get latest measured temperature in millidegrees Celsius.
+    ///
+    /// Value is meaningless before the first measurement finishes.
+    #[inline]
+    pub fn temperature(&self) -> i32 {
+        raw_to_millicelsius(self.ths.as_ref().data.read() & 0xFFF)
+    }
+    /// Wait for next measurement and return temperature in millidegrees Celsius.
+    #[inline]
+    pub fn read_temperature(&mut self) -> Result<i32, Error> {
+        let regs = self.ths.as_ref();
+        unsafe {
+            regs.data_int_status
+                .write(DataStatus::default().clear_pending())
+        };
+        let deadline = Deadline::start();
+        while !regs.data_int_status.read().is_pending() {
+            deadline.check().map_err(|_| Error::Timeout)?;
+            core::hint::spin_loop();
+        }
+        Ok(self.temperature())
+    }
+    /// Close thermal sensor and release peripheral.
+    #[inline]
+    pub fn free(self, ccu: &ccu::RegisterBlock) -> THS {
+        unsafe {
+            self.ths.as_ref().enable.write(Enable::default());
+            ccu::THS::free(ccu);
+        }
+        self.ths
+    }
+}
+
+/// Convert raw sensor reading to millidegrees Celsius.
+#[inline]
+const fn raw_to_millicelsius(raw: u32) -> i32 {
+    TEMPERATURE_OFFSET - raw as i32 * TEMPERATURE_SCALE / 10
+}
+
+#[cfg(test)]
+mod tests {
+    use super::raw_to_millicelsius;
+
+    #[test]
+    fn function_raw_to_millicelsius() {
+        assert_eq!(raw_to_millicelsius(0), 188_552);
+        assert_eq!(raw_to_millicelsius(2000), 53_952);
+        assert_eq!(raw_to_millicelsius(2800), 112);
+    }
+}
@@ -0,0 +1,262 @@
+use volatile_register::{RO, RW};
+
+/// Thermal sensor registers.
+#[repr(C)]
+pub struct RegisterBlock {
+    /// 0x00 - THS Control Register.
+    pub control: RW<Control>,
+    /// 0x04 - THS Enable Register.
+    pub enable: RW<Enable>,
+    /// 0x08 - THS Period Control Register.
+    pub period: RW<Period>,
+    _reserved0: u32,
+    /// 0x10 - THS Data Interrupt Control Register.
+    pub data_int_control: RW<Enable>,
+    _reserved1: [u32; 3],
+    /// 0x20 - THS Data Interrupt Status Register.
+    pub data_int_status: RW<DataStatus>,
+    _reserved2: [u32; 3],
+    /// 0x30 - THS Median Filter Control Register.
+    pub filter: RW<Filter>,
+    _reserved3: [u32; 27],
+    /// 0xA0 - THS Calibration Data Register.
+    pub calibration_data: RW<u32>,
+    _reserved4: [u32; 7],
+    /// 0xC0 - THS Data Register.
+    pub data: RO<u32>,
+}
+
+/// THS control register.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct Control(u32);
+
+impl Control {
+    const FS_DIV: u32 = 0xFFFF << 16;
+    const TACQ: u32 = 0xFFFF;
+
+    /// Get ADC clock divider; ADC clock is 24 MHz divided by `divider + 1`.
+    #[inline]
+    pub const fn divider(self) -> u16 {
+        ((self.0 & Self::FS_DIV) >> 16) as u16
+    }
+    /// Set ADC clock divider; ADC clock is 24 MHz divided by `divider + 1`.
+    #[inline]
+    pub const fn set_divider(self, val: u16) -> Self {
+        Self((self.0 & !Self::FS_DIV) | ((val as u32) << 16))
+    }
+    /// Get acquire time in 24-MHz clock cycles, minus one.
+    #[inline]
+    pub const fn acquire_time(self) -> u16 {
+        (self.0 & Self::TACQ) as u16
+    }
+    /// Set acquire time in 24-MHz clock cycles, minus one.
+    #[inline]
+    pub const fn set_acquire_time(self, val: u16) -> Self {
+        Self((self.0 & !Self::TACQ) | val as u32)
+    }
+}
+
+impl Default for Control {
+    #[inline]
+    fn default() -> Self {
+        Self(0x01DF_002F)
+    }
+}
+
+/// THS sensor enable register.
+///
+/// Used for sensor enable and sensor data interrupt enable.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct Enable(u32);
+
+impl Enable {
+    const SENSOR0: u32 = 1 << 0;
+
+    /// If sensor is enabled.
+    #[inline]
+    pub const fn is_enabled(self) -> bool {
+        self.0 & Self::SENSOR0 != 0
+    }
+    /// Enable sensor.
+    #[inline]
+    pub const fn enable(self) -> Self {
+        Self(self.0 | Self::SENSOR0)
+    }
+    /// Disable sensor.
+    #[inline]
+    pub const fn disable(self) -> Self {
+        Self(self.0 & !Self::SENSOR0)
+    }
+}
+
+impl Default for Enable {
+    #[inline]
+    fn default() -> Self {
+        Self(0x0000_0000)
+    }
+}
+
+/// THS period control register.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct Period(u32);
+
+impl Period {
+    const THS_PER: u32 = 0xF_FFFF << 12;
+
+    /// Get measure period in units of 4096 24-MHz clock cycles, minus one.
+    #[inline]
+    pub const fn period(self) -> u32 {
+        (self.0 & Self::THS_PER) >> 12
+    }
+    /// Set measure period in units of 4096 24-MHz clock cycles, minus one.
+    #[inline]
+    pub const fn set_period(self, val: u32) -> Self {
+        Self((self.0 & !Self::THS_PER) | ((val & 0xF_FFFF) << 12))
+    }
+}
+
+impl Default for Period {
+    #[inline]
+    fn default() -> Self {
+        Self(0x0003_A000)
+    }
+}
+
+/// THS data interrupt status register.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct DataStatus(u32);
+
+impl DataStatus {
+    const SENSOR0: u32 = 1 << 0;
+
+    /// Check if new sensor data is available.
+    #[inline]
+    pub const fn is_pending(self) -> bool {
+        self.0 & Self::SENSOR0 != 0
+    }
+    /// Clear data available flag on write.
+    #[inline]
+    pub const fn clear_pending(self) -> Self {
+        Self(self.0 | Self::SENSOR0)
+    }
+}
+
+impl Default for DataStatus {
+    #[inline]
+    fn default() -> Self {
+        Self(0x0000_0000)
+    }
+}
+
+/// Number of samples averaged by median filter.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum FilterType {
+    /// 2 samples.
+    Samples2,
+    /// 4 samples.
+    Samples4,
+    /// 8 samples.
+    Samples8,
+    /// 16 samples.
+    Samples16,
+}
+
+/// THS median filter control register.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct Filter(u32);
+
+impl Filter {
+    const FILTER_EN: u32 = 1 << 2;
+    const FILTER_TYPE: u32 = 0x3;
+
+    /// If median filter is enabled.
+    #[inline]
+    pub const fn is_enabled(self) -> bool {
+        self.0 & Self::FILTER_EN != 0
+    }
+    /// Enable median filter.
+    #[inline]
+    pub const fn enable(self) -> Self {
+        Self(self.0 | Self::FILTER_EN)
+    }
+    /// Disable median filter.
+    #[inline]
+    pub const fn disable(self) -> Self {
+        Self(self.0 & !Self::FILTER_EN)
+    }
+    /// Get median filter type.
+    #[inline]
+    pub const fn filter_type(self) -> FilterType {
+        match self.0 & Self::FILTER_TYPE {
+            0 => FilterType::Samples2,
+            1 => FilterType::Samples4,
+            2 => FilterType::Samples8,
+            _ => FilterType::Samples16,
+        }
+    }
+    /// Set median filter type.
+    #[inline]
+    pub const fn set_filter_type(self, val: FilterType) -> Self {
+        Self((self.0 & !Self::FILTER_TYPE) | val as u32)
+    }
+}
+
+impl Default for Filter {
+    #[inline]
+    fn default() -> Self {
+        Self(0x0000_0005)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Control, DataStatus, Enable, Filter, FilterType, Period, RegisterBlock};
+    use core::mem::offset_of;
+
+    #[test]
+    fn offset_ths() {
+        assert_eq!(offset_of!(RegisterBlock, control), 0x00);
+        assert_eq!(offset_of!(RegisterBlock, enable), 0x04);
+        assert_eq!(offset_of!(RegisterBlock, period), 0x08);
+        assert_eq!(offset_of!(RegisterBlock, data_int_control), 0x10);
+        assert_eq!(offset_of!(RegisterBlock, data_int_status), 0x20);
+        assert_eq!(offset_of!(RegisterBlock, filter), 0x30);
+        assert_eq!(offset_of!(RegisterBlock, calibration_data), 0xA0);
+        assert_eq!(offset_of!(RegisterBlock, data), 0xC0);
+    }
+
+    #[test]
+    fn struct_control_period_functions() {
+        let val = Control::default().set_divider(47).set_acquire_time(479);
+        assert_eq!(val.divider(), 47);
+        assert_eq!(val.acquire_time(), 479);
+        assert_eq!(val.0, 0x002F_01DF);
+
+        let val = Period::default();
+        assert_eq!(val.period(), 0x3A);
+        assert_eq!(val.set_period(0x123).0, 0x0012_3000);
+    }
+
+    #[test]
+    fn struct_enable_status_filter_functions() {
+        let val = Enable::default().enable();
+        assert!(val.is_enabled());
+        assert_eq!(val.0, 0x0000_0001);
+        assert_eq!(val.disable().0, 0x0000_0000);
+
+        let val = DataStatus::default().clear_pending();
+        assert!(val.is_pending());
+        assert_eq!(val.0, 0x0000_0001);
+
+        let val = Filter::default();
+        assert!(val.is_enabled());
+        assert_eq!(val.filter_type(), FilterType::Samples4);
+        let val = val.set_filter_type(FilterType::Samples16).disable();
+        assert_eq!(val.0, 0x0000_0003);
+    }
+}
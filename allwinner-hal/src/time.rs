@@ -0,0 +1,375 @@
+//! Monotonic time measurement.
+//!
+//! Time is measured using the 64-bit system counter of RISC-V cores, read from `time` CSR.
+//! On D1-like chips, the counter is clocked by 24-MHz 'HOSC' oscillator and never wraps
+//! in practice.
+//!
+//! On other targets, such as host builds running tests, the counter stands still until
+//! advanced by [`advance_host_counter`].
+
+#[cfg(feature = "embassy-time-driver")]
+pub mod embassy;
+
+use crate::timer::HOSC_FREQUENCY;
+pub use core::time::Duration;
+use core::{
+    future::{Future, poll_fn},
+    ops::{Add, AddAssign, Sub, SubAssign},
+    pin::pin,
+    sync::atomic::{AtomicU64, Ordering},
+    task::{Poll, Waker},
+};
+use embedded_time::rate::Hertz;
+
+/// Frequency of the system counter.
+pub const COUNTER_FREQUENCY: Hertz = HOSC_FREQUENCY;
+
+/// A measurement of the monotonic system counter.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Instant {
+    ticks: u64,
+}
+
+impl Instant {
+    /// Latest instant the system counter can represent.
+    pub const MAX: Instant = Instant { ticks: u64::MAX };
+    /// Get current instant.
+    #[inline]
+    pub fn now() -> Self {
+        Self { ticks: counter() }
+    }
+    /// Create an instant from raw counter ticks.
+    #[inline]
+    pub const fn from_ticks(ticks: u64) -> Self {
+        Self { ticks }
+    }
+    /// Get raw counter ticks of this instant.
+    #[inline]
+    pub const fn ticks(self) -> u64 {
+        self.ticks
+    }
+    /// Get time elapsed from `earlier` to this instant, or zero if `earlier` is later.
+    #[inline]
+    pub const fn elapsed_since(self, earlier: Instant) -> Duration {
+        ticks_to_duration(self.ticks.saturating_sub(earlier.ticks))
+    }
+    /// Get time elapsed since this instant.
+    #[inline]
+    pub fn elapsed(self) -> Duration {
+        Instant::now().elapsed_since(self)
+    }
+    /// Add `duration` to this instant, returning `None` on overflow.
+    #[inline]
+    pub const fn checked_add(self, duration: Duration) -> Option<Instant> {
+        match self.ticks.checked_add(duration_to_ticks(duration)) {
+            Some(ticks) => Some(Instant { ticks }),
+            None => None,
+        }
+    }
+    /// Add `duration` to this instant, saturating at [`Instant::MAX`].
+    #[inline]
+    pub const fn saturating_add(self, duration: Duration) -> Instant {
+        Instant {
+            ticks: self.ticks.saturating_add(duration_to_ticks(duration)),
+        }
+    }
+    /// Subtract `duration` from this instant, returning `None` on underflow.
+    #[inline]
+    pub const fn checked_sub(self, duration: Duration) -> Option<Instant> {
+        match self.ticks.checked_sub(duration_to_ticks(duration)) {
+            Some(ticks) => Some(Instant { ticks }),
+            None => None,
+        }
+    }
+}
+
+impl Add<Duration> for Instant {
+    type Output = Instant;
+
+    /// Add `rhs`, saturating at [`Instant::MAX`], so that long timeouts never expire.
+    #[inline]
+    fn add(self, rhs: Duration) -> Instant {
+        self.saturating_add(rhs)
+    }
+}
+
+impl AddAssign<Duration> for Instant {
+    #[inline]
+    fn add_assign(&mut self, rhs: Duration) {
+        *self = *self + rhs;
+    }
+}
+
+impl Sub<Duration> for Instant {
+    type Output = Instant;
+
+    /// Subtract `rhs`, saturating at counter start.
+    #[inline]
+    fn sub(self, rhs: Duration) -> Instant {
+        Instant {
+            ticks: self.ticks.saturating_sub(duration_to_ticks(rhs)),
+        }
+    }
+}
+
+impl SubAssign<Duration> for Instant {
+    #[inline]
+    fn sub_assign(&mut self, rhs: Duration) {
+        *self = *self - rhs;
+    }
+}
+
+impl Sub<Instant> for Instant {
+    type Output = Duration;
+
+    #[inline]
+    fn sub(self, rhs: Instant) -> Duration {
+        self.elapsed_since(rhs)
+    }
+}
+
+/// Crate-wide timeout of blocking waits in counter ticks, or zero to wait forever.
+static BLOCKING_TIMEOUT: AtomicU64 = AtomicU64::new(0);
+
+/// Set timeout of blocking waits in drivers, or `None` to wait forever.
+///
+/// Drivers check this timeout in spin loops polling hardware, e.g. UART flush and SMHC
+/// command waits, and return a timeout error instead of hanging on stuck hardware.
+/// Blocking waits never time out by default.
+#[inline]
+pub fn set_blocking_timeout(timeout: Option<Duration>) {
+    let ticks = match timeout {
+        Some(timeout) => duration_to_ticks(timeout).max(1),
+        None => 0,
+    };
+    BLOCKING_TIMEOUT.store(ticks, Ordering::Relaxed);
+}
+
+/// Get timeout of blocking waits in drivers, or `None` if they wait forever.
+#[inline]
+pub fn blocking_timeout() -> Option<Duration> {
+    match BLOCKING_TIMEOUT.load(Ordering::Relaxed) {
+        0 => None,
+        ticks => Some(ticks_to_duration(ticks)),
+    }
+}
+
+/// Deadline of a blocking wait.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Deadline {
+    at: Option<Instant>,
+}
+
+impl Deadline {
+    /// Start a deadline using crate-wide blocking timeout.
+    ///
+    /// System counter is not read if blocking waits never time out.
+    #[inline]
+    pub fn start() -> Self {
+        match BLOCKING_TIMEOUT.load(Ordering::Relaxed) {
+            0 => Self::never(),
+            ticks => Self {
+                at: Instant::now()
+                    .ticks()
+                    .checked_add(ticks)
+                    .map(Instant::from_ticks),
+            },
+        }
+    }
+    /// Create a deadline at `instant`.
+    #[inline]
+    pub const fn at(instant: Instant) -> Self {
+        Self { at: Some(instant) }
+    }
+    /// Create a deadline that never expires.
+    #[inline]
+    pub const fn never() -> Self {
+        Self { at: None }
+    }
+    /// Check if deadline has passed.
+    #[inline]
+    pub fn is_expired(&self) -> bool {
+        match self.at {
+            Some(at) => Instant::now() >= at,
+            None => false,
+        }
+    }
+    /// Return an error if deadline has passed.
+    #[inline]
+    pub fn check(&self) -> Result<(), TimeoutError> {
+        if self.is_expired() {
+            Err(TimeoutError)
+        } else {
+            Ok(())
+        }
+    }
+}
+
+/// Error returned when a deadline passes before an operation completes.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct TimeoutError;
+
+/// Run `future` until it completes or `duration` elapses.
+///
+/// With `embassy-time-driver` feature, the task is woken at the deadline by the time driver;
+/// otherwise it's polled continuously until either side is ready.
+#[inline]
+pub async fn with_timeout<F: Future>(
+    duration: Duration,
+    future: F,
+) -> Result<F::Output, TimeoutError> {
+    let deadline = Instant::now() + duration;
+    let mut future = pin!(future);
+    poll_fn(|cx| {
+        if let Poll::Ready(output) = future.as_mut().poll(cx) {
+            return Poll::Ready(Ok(output));
+        }
+        if Instant::now() >= deadline {
+            return Poll::Ready(Err(TimeoutError));
+        }
+        schedule_wake(deadline, cx.waker());
+        Poll::Pending
+    })
+    .await
+}
+
+/// Wake `waker` at or after `deadline`.
+#[inline]
+fn schedule_wake(deadline: Instant, waker: &Waker) {
+    #[cfg(feature = "embassy-time-driver")]
+    embassy::schedule_wake(deadline, waker);
+    #[cfg(not(feature = "embassy-time-driver"))]
+    {
+        let _ = deadline;
+        waker.wake_by_ref();
+    }
+}
+
+/// Convert counter ticks to duration.
+#[inline]
+pub const fn ticks_to_duration(ticks: u64) -> Duration {
+    let freq = COUNTER_FREQUENCY.0 as u64;
+    let nanos = (ticks % freq) * 1_000_000_000 / freq;
+    Duration::new(ticks / freq, nanos as u32)
+}
+
+/// Convert duration to counter ticks, rounding up and saturating at `u64::MAX`.
+#[inline]
+pub const fn duration_to_ticks(duration: Duration) -> u64 {
+    let freq = COUNTER_FREQUENCY.0 as u64;
+    let nanos = duration.subsec_nanos() as u64 * freq;
+    duration
+        .as_secs()
+        .saturating_mul(freq)
+        .saturating_add(nanos.div_ceil(1_000_000_000))
+}
+
+/// Convert `duration` to microseconds, rounding up and saturating at about 71 minutes.
+#[inline]
+pub(crate) const fn duration_to_micros(duration: Duration) -> u32 {
+    let micros = duration.as_nanos().div_ceil(1000);
+    if micros > u32::MAX as u128 {
+        u32::MAX
+    } else {
+        micros as u32
+    }
+}
+
+#[cfg(target_arch = "riscv64")]
+#[inline]
+fn counter() -> u64 {
+    let ticks: u64;
+    unsafe { core::arch::asm!("rdtime {}", out(reg) ticks) };
+    ticks
+}
+
+/// System counter of host builds.
+#[cfg(not(target_arch = "riscv64"))]
+static HOST_COUNTER: AtomicU64 = AtomicU64::new(0);
+
+/// Advance system counter of host builds by `duration`, e.g. to expire deadlines in tests.
+#[cfg(not(target_arch = "riscv64"))]
+#[inline]
+pub fn advance_host_counter(duration: Duration) {
+    HOST_COUNTER.fetch_add(duration_to_ticks(duration), Ordering::Relaxed);
+}
+
+#[cfg(not(target_arch = "riscv64"))]
+#[inline]
+fn counter() -> u64 {
+    HOST_COUNTER.load(Ordering::Relaxed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        Deadline, Duration, Instant, advance_host_counter, blocking_timeout, duration_to_micros,
+        duration_to_ticks, set_blocking_timeout, ticks_to_duration,
+    };
+
+    #[test]
+    fn function_tick_conversions() {
+        assert_eq!(ticks_to_duration(24), Duration::from_micros(1));
+        assert_eq!(ticks_to_duration(36_000_000), Duration::from_millis(1500));
+        assert_eq!(ticks_to_duration(1), Duration::from_nanos(41));
+        assert_eq!(duration_to_ticks(Duration::from_millis(1500)), 36_000_000);
+        assert_eq!(duration_to_ticks(Duration::from_nanos(1)), 1);
+        assert_eq!(duration_to_ticks(Duration::ZERO), 0);
+    }
+
+    #[test]
+    fn function_duration_to_micros() {
+        assert_eq!(duration_to_micros(Duration::from_millis(250)), 250_000);
+        assert_eq!(duration_to_micros(Duration::from_nanos(1)), 1);
+        assert_eq!(duration_to_micros(Duration::from_secs(10_000)), u32::MAX);
+    }
+
+    #[test]
+    fn struct_instant_functions() {
+        let earlier = Instant::from_ticks(1_000);
+        let later = earlier + Duration::from_micros(10);
+        assert_eq!(later.ticks(), 1_240);
+        assert_eq!(later - earlier, Duration::from_micros(10));
+        assert_eq!(later.elapsed_since(earlier), Duration::from_micros(10));
+        assert_eq!(earlier.elapsed_since(later), Duration::ZERO);
+        assert!(later > earlier);
+        assert_eq!(later - Duration::from_micros(10), earlier);
+        assert_eq!(earlier.checked_sub(Duration::from_secs(1)), None);
+        assert_eq!(
+            Instant::from_ticks(u64::MAX).checked_add(Duration::from_nanos(1)),
+            None
+        );
+    }
+
+    #[test]
+    fn function_blocking_timeout() {
+        assert_eq!(blocking_timeout(), None);
+        assert_eq!(Deadline::start(), Deadline::never());
+        assert!(!Deadline::never().is_expired());
+        assert!(Deadline::never().check().is_ok());
+        set_blocking_timeout(Some(Duration::from_millis(10)));
+        assert_eq!(blocking_timeout(), Some(Duration::from_millis(10)));
+        set_blocking_timeout(Some(Duration::ZERO));
+        assert_eq!(blocking_timeout(), Some(Duration::from_nanos(41)));
+        set_blocking_timeout(None);
+        assert_eq!(blocking_timeout(), None);
+    }
+
+    #[test]
+    fn function_advance_host_counter() {
+        let start = Instant::now();
+        let deadline = Deadline::at(start + Duration::from_millis(1));
+        assert!(!deadline.is_expired());
+        advance_host_counter(Duration::from_millis(1));
+        assert!(deadline.check().is_err());
+        assert!(Instant::now().elapsed_since(start) >= Duration::from_millis(1));
+    }
+
+    #[test]
+    fn function_duration_max() {
+        assert_eq!(duration_to_ticks(Duration::MAX), u64::MAX);
+        assert_eq!(Instant::from_ticks(1) + Duration::MAX, Instant::MAX);
+        assert_eq!(Instant::MAX - Duration::MAX, Instant::from_ticks(0));
+        assert!(!Deadline::at(Instant::now() + Duration::MAX).is_expired());
+    }
+}
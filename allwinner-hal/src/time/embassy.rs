@@ -0,0 +1,151 @@
+//! Embassy time driver.
+//!
+//! Time is read from the system counter, and timer 1 of general purpose timers is used
+//! as alarm; timer 0 is left for other uses. Call [`init`] with timer 1 before running
+//! the executor, and [`on_interrupt`] from `TIMER1` interrupt handler. Once initialized,
+//! the driver owns timer 1 and [`timer::on_interrupt`](crate::timer::on_interrupt)
+//! only handles timer 0. A `critical-section` implementation should be provided by
+//! the application.
+
+use super::{COUNTER_FREQUENCY, Instant, counter};
+use crate::timer::{Prescale, RegisterBlock, Timer, TimerMode};
+use core::{
+    cell::RefCell,
+    sync::atomic::{AtomicPtr, Ordering},
+    task::Waker,
+};
+use critical_section::{CriticalSection, Mutex};
+use embassy_time_driver::{Driver, TICK_HZ};
+use embassy_time_queue_utils::Queue;
+
+/// Index of general purpose timer used as alarm.
+const ALARM: usize = 1;
+
+struct TimeDriver {
+    timer: AtomicPtr<RegisterBlock>,
+    queue: Mutex<RefCell<Queue>>,
+}
+
+embassy_time_driver::time_driver_impl!(static DRIVER: TimeDriver = TimeDriver {
+    timer: AtomicPtr::new(core::ptr::null_mut()),
+    queue: Mutex::new(RefCell::new(Queue::new())),
+});
+
+/// Initialize time driver with alarm timer, enabling its interrupt.
+///
+/// The timer is kept by the driver for the rest of the program.
+#[inline]
+pub fn init(mut alarm: Timer<'static, ALARM>) {
+    let timer = alarm.register_block();
+    alarm.stop();
+    alarm.clear_interrupt();
+    alarm.enable_interrupt();
+    DRIVER
+        .timer
+        .store(timer as *const _ as *mut _, Ordering::Release);
+    critical_section::with(|cs| DRIVER.run_queue(cs));
+}
+
+/// Alarm timer interrupt entry point.
+///
+/// Call this function from `TIMER1` interrupt handler. It clears the pending alarm and
+/// wakes all expired tasks.
+#[inline]
+pub fn on_interrupt() {
+    if let Some(timer) = DRIVER.timer() {
+        Timer::<ALARM>::from_register_block(timer).clear_interrupt();
+    }
+    critical_section::with(|cs| DRIVER.run_queue(cs));
+}
+
+impl TimeDriver {
+    #[inline]
+    fn timer(&self) -> Option<&'static RegisterBlock> {
+        // note(unsafe): only a `&'static RegisterBlock` is stored by `init`
+        unsafe { self.timer.load(Ordering::Acquire).as_ref() }
+    }
+    /// Wake expired tasks and arm alarm for the next one.
+    fn run_queue(&self, cs: CriticalSection) {
+        self.arm_next(&mut self.queue.borrow_ref_mut(cs));
+    }
+    /// Arm alarm for next expiration of `queue`, waking tasks already expired.
+    fn arm_next(&self, queue: &mut Queue) {
+        let mut next = queue.next_expiration(self.now());
+        while !self.set_alarm(next) {
+            next = queue.next_expiration(self.now());
+        }
+    }
+    /// Arm alarm at embassy tick `at`, returning false if that moment has already passed.
+    ///
+    /// Alarms beyond range of the 32-bit timer fire early, and are then armed again
+    /// by [`TimeDriver::arm_next`].
+    fn set_alarm(&self, at: u64) -> bool {
+        let Some(timer) = self.timer() else {
+            return true;
+        };
+        let mut alarm = Timer::<ALARM>::from_register_block(timer);
+        alarm.stop();
+        if at == u64::MAX {
+            return true;
+        }
+        let now = counter();
+        let target = tick_to_counter(at);
+        if target <= now {
+            return false;
+        }
+        let ticks = (target - now).min(u32::MAX as u64) as u32;
+        alarm.start(ticks, TimerMode::Single, Prescale::P1);
+        true
+    }
+}
+
+impl Driver for TimeDriver {
+    #[inline]
+    fn now(&self) -> u64 {
+        counter_to_tick(counter())
+    }
+    #[inline]
+    fn schedule_wake(&self, at: u64, waker: &Waker) {
+        critical_section::with(|cs| {
+            let mut queue = self.queue.borrow_ref_mut(cs);
+            if queue.schedule_wake(at, waker) {
+                self.arm_next(&mut queue);
+            }
+        })
+    }
+}
+
+/// Check if timer `I` is used as alarm by an initialized time driver.
+#[inline]
+pub(crate) fn owns<const I: usize>() -> bool {
+    I == ALARM && DRIVER.timer().is_some()
+}
+
+/// Wake `waker` at or after `deadline` using the time driver queue.
+#[inline]
+pub(super) fn schedule_wake(deadline: Instant, waker: &Waker) {
+    let at = counter_to_tick(deadline.ticks()).saturating_add(1);
+    DRIVER.schedule_wake(at, waker);
+}
+
+/// Convert system counter ticks to embassy ticks, rounding down.
+#[inline]
+const fn counter_to_tick(ticks: u64) -> u64 {
+    let tick = ticks as u128 * TICK_HZ as u128 / COUNTER_FREQUENCY.0 as u128;
+    if tick > u64::MAX as u128 {
+        u64::MAX
+    } else {
+        tick as u64
+    }
+}
+
+/// Convert embassy ticks to system counter ticks, rounding up.
+#[inline]
+const fn tick_to_counter(tick: u64) -> u64 {
+    let ticks = (tick as u128 * COUNTER_FREQUENCY.0 as u128).div_ceil(TICK_HZ as u128);
+    if ticks > u64::MAX as u128 {
+        u64::MAX
+    } else {
+        ticks as u64
+    }
+}
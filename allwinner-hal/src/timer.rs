@@ -0,0 +1,331 @@
+//! General purpose timers.
+
+mod register;
+mod watchdog;
+pub use register::*;
+pub use watchdog::*;
+
+use crate::waker::WakerSet;
+use core::sync::atomic::{AtomicPtr, Ordering};
+use embedded_time::rate::Hertz;
+
+/// Frequency of 24-MHz 'HOSC' oscillator clocking the timers.
+pub const HOSC_FREQUENCY: Hertz = Hertz(24_000_000);
+
+/// Periodic timer interrupt handler, called with timer index.
+pub type Handler = fn(timer: usize);
+
+static WAKERS: WakerSet<TIMER_COUNT> = WakerSet::new();
+static HANDLERS: [AtomicPtr<()>; TIMER_COUNT] =
+    [const { AtomicPtr::new(core::ptr::null_mut()) }; TIMER_COUNT];
+
+/// Timer interrupt entry point.
+///
+/// Call this function from timer interrupt handlers. Expired timers with a registered
+/// handler are cleared and their handler is called. Other expired timers get interrupt
+/// disabled and tasks waiting on them woken; pending status is left for those tasks to clear.
+/// Timer 1 is skipped once owned by the embassy time driver.
+#[inline]
+pub fn on_interrupt(timer: &RegisterBlock) {
+    on_timer_interrupt::<0>(timer);
+    on_timer_interrupt::<1>(timer);
+}
+
+#[inline]
+fn on_timer_interrupt<const I: usize>(timer: &RegisterBlock) {
+    #[cfg(feature = "embassy-time-driver")]
+    if crate::time::embassy::owns::<I>() {
+        return;
+    }
+    if !timer.irq_enable.read().is_enabled::<I>() || !timer.irq_status.read().is_pending::<I>() {
+        return;
+    }
+    let handler = HANDLERS[I].load(Ordering::Acquire);
+    if handler.is_null() {
+        unsafe { timer.irq_enable.modify(|val| val.disable::<I>()) };
+        WAKERS.wake(I);
+    } else {
+        unsafe {
+            timer
+                .irq_status
+                .write(IrqStatus::default().clear_pending::<I>())
+        };
+        // note(unsafe): only `Handler` function pointers are stored in `HANDLERS`
+        let handler: Handler = unsafe { core::mem::transmute(handler) };
+        handler(I);
+    }
+}
+
+/// Managed timer structure with peripheral.
+pub struct Timers<TIMER> {
+    timer: TIMER,
+}
+
+impl<TIMER: AsRef<RegisterBlock>> Timers<TIMER> {
+    /// Create a timer instance, stopping all timers and clearing their interrupts.
+    #[inline]
+    pub fn new(timer: TIMER) -> Self {
+        let regs = timer.as_ref();
+        unsafe {
+            for timer in &regs.timers {
+                timer.control.write(Control::default());
+            }
+            regs.irq_enable.write(IrqEnable::default());
+            regs.irq_status.write(
+                IrqStatus::default()
+                    .clear_pending::<0>()
+                    .clear_pending::<1>(),
+            );
+        }
+        Self { timer }
+    }
+    /// Split timer peripheral into separately owned timers and watchdog.
+    #[inline]
+    pub fn split(&mut self) -> (Timer<'_, 0>, Timer<'_, 1>, Watchdog<'_>) {
+        let timer = self.timer.as_ref();
+        (
+            Timer::from_register_block(timer),
+            Timer::from_register_block(timer),
+            Watchdog::from_register_block(timer),
+        )
+    }
+    /// Release timer peripheral.
+    #[inline]
+    pub fn free(self) -> TIMER {
+        self.timer
+    }
+}
+
+/// Timer `I` clocked by 24-MHz 'HOSC' oscillator.
+pub struct Timer<'a, const I: usize> {
+    timer: &'a RegisterBlock,
+}
+
+impl<'a, const I: usize> Timer<'a, I> {
+    /// Create timer `I` from timer register block.
+    #[inline]
+    pub(crate) const fn from_register_block(timer: &'a RegisterBlock) -> Self {
+        Self { timer }
+    }
+    /// Timer register block this timer belongs to.
+    #[cfg(feature = "embassy-time-driver")]
+    #[inline]
+    pub(crate) const fn register_block(&self) -> &'a RegisterBlock {
+        self.timer
+    }
+    /// Start counting down from `ticks` in `mode`, one tick per `prescale` HOSC cycles.
+    #[inline]
+    pub fn start(&mut self, ticks: u32, mode: TimerMode, prescale: Prescale) {
+        let timer = &self.timer.timers[I];
+        unsafe {
+            timer.control.write(Control::default());
+            self.timer
+                .irq_status
+                .write(IrqStatus::default().clear_pending::<I>());
+            timer.interval.write(ticks);
+            let control = Control::default()
+                .set_mode(mode)
+                .set_prescale(prescale)
+                .set_clock_source(ClockSource::Hosc);
+            timer.control.write(control.set_reload());
+            while !timer.control.read().is_reload_finished() {
+                core::hint::spin_loop();
+            }
+            timer.control.write(control.enable());
+        }
+    }
+    /// Stop counting.
+    #[inline]
+    pub fn stop(&mut self) {
+        unsafe { self.timer.timers[I].control.modify(|val| val.disable()) };
+    }
+    /// Check if timer is counting.
+    #[inline]
+    pub fn is_running(&self) -> bool {
+        self.timer.timers[I].control.read().is_enabled()
+    }
+    /// Get number of ticks left until timer reaches zero.
+    #[inline]
+    pub fn current_value(&self) -> u32 {
+        self.timer.timers[I].current_value.read()
+    }
+    /// Enable interrupt on timer reaching zero.
+    #[inline]
+    pub fn enable_interrupt(&mut self) {
+        unsafe { self.timer.irq_enable.modify(|val| val.enable::<I>()) };
+    }
+    /// Disable interrupt on timer reaching zero.
+    #[inline]
+    pub fn disable_interrupt(&mut self) {
+        unsafe { self.timer.irq_enable.modify(|val| val.disable::<I>()) };
+    }
+    /// Check if timer has reached zero since interrupt was last cleared.
+    #[inline]
+    pub fn is_pending(&self) -> bool {
+        self.timer.irq_status.read().is_pending::<I>()
+    }
+    /// Clear pending interrupt of this timer.
+    #[inline]
+    pub fn clear_interrupt(&mut self) {
+        unsafe {
+            self.timer
+                .irq_status
+                .write(IrqStatus::default().clear_pending::<I>())
+        };
+    }
+    /// Convert into a periodic timer expiring at `rate`, and start counting.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `rate` is zero.
+    #[inline]
+    pub fn into_periodic(self, rate: Hertz) -> PeriodicTimer<'a, I> {
+        assert!(rate.0 != 0, "periodic timer rate should not be zero");
+        let mut ans = PeriodicTimer { timer: self, rate };
+        ans.start();
+        ans
+    }
+    /// Block for `ticks` HOSC cycles using single mode counting.
+    #[inline]
+    fn delay_ticks(&mut self, mut ticks: u64) {
+        while ticks > 0 {
+            let chunk = ticks.min(u32::MAX as u64);
+            self.start(chunk as u32, TimerMode::Single, Prescale::P1);
+            while !self.is_pending() {
+                core::hint::spin_loop();
+            }
+            self.clear_interrupt();
+            ticks -= chunk;
+        }
+        self.stop();
+    }
+    /// Wait for `ticks` HOSC cycles using single mode counting and timer interrupt.
+    #[inline]
+    async fn wait_ticks(&mut self, mut ticks: u64) {
+        while ticks > 0 {
+            let chunk = ticks.min(u32::MAX as u64);
+            self.start(chunk as u32, TimerMode::Single, Prescale::P1);
+            self.enable_interrupt();
+            WAKERS.wait_until(I, || self.is_pending()).await;
+            self.clear_interrupt();
+            ticks -= chunk;
+        }
+        self.disable_interrupt();
+        self.stop();
+    }
+}
+
+/// Delay using timer pending status.
+///
+/// Interrupt of this timer should stay disabled while delaying, or the handler may clear
+/// the pending status before it's observed.
+impl<'a, const I: usize> embedded_hal::delay::DelayNs for Timer<'a, I> {
+    #[inline]
+    fn delay_ns(&mut self, ns: u32) {
+        let ticks = (ns as u64 * HOSC_FREQUENCY.0 as u64).div_ceil(1_000_000_000);
+        self.delay_ticks(ticks)
+    }
+    #[inline]
+    fn delay_us(&mut self, us: u32) {
+        self.delay_ticks(us as u64 * (HOSC_FREQUENCY.0 / 1_000_000) as u64)
+    }
+    #[inline]
+    fn delay_ms(&mut self, ms: u32) {
+        self.delay_ticks(ms as u64 * (HOSC_FREQUENCY.0 / 1_000) as u64)
+    }
+}
+
+/// Timer `I` expiring at a fixed rate.
+///
+/// Each expiration either calls a registered [`Handler`], or wakes a task waiting in
+/// [`PeriodicTimer::wait`]. Timer interrupt should be routed to [`on_interrupt`].
+pub struct PeriodicTimer<'a, const I: usize> {
+    timer: Timer<'a, I>,
+    rate: Hertz,
+}
+
+impl<'a, const I: usize> PeriodicTimer<'a, I> {
+    /// Start counting from a full period.
+    #[inline]
+    pub fn start(&mut self) {
+        let ticks = (HOSC_FREQUENCY.0 / self.rate.0).max(1);
+        self.timer.start(ticks, TimerMode::Periodic, Prescale::P1);
+    }
+    /// Stop counting.
+    #[inline]
+    pub fn stop(&mut self) {
+        self.timer.stop();
+    }
+    /// Check if timer is counting.
+    #[inline]
+    pub fn is_running(&self) -> bool {
+        self.timer.is_running()
+    }
+    /// Get expiration rate.
+    #[inline]
+    pub fn rate(&self) -> Hertz {
+        self.rate
+    }
+    /// Change expiration rate, restarting current period if timer is counting.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `rate` is zero.
+    #[inline]
+    pub fn set_rate(&mut self, rate: Hertz) {
+        assert!(rate.0 != 0, "periodic timer rate should not be zero");
+        self.rate = rate;
+        if self.is_running() {
+            self.start();
+        }
+    }
+    /// Register interrupt handler called on each expiration, and enable interrupt.
+    #[inline]
+    pub fn set_handler(&mut self, handler: Handler) {
+        HANDLERS[I].store(handler as *mut (), Ordering::Release);
+        self.timer.enable_interrupt();
+    }
+    /// Disable interrupt and remove registered handler.
+    #[inline]
+    pub fn clear_handler(&mut self) {
+        self.timer.disable_interrupt();
+        HANDLERS[I].store(core::ptr::null_mut(), Ordering::Release);
+    }
+    /// Wait for next expiration of this timer.
+    ///
+    /// Expirations that happen while no task is waiting are merged into one.
+    #[inline]
+    pub async fn wait(&mut self) {
+        self.timer.enable_interrupt();
+        WAKERS.wait_until(I, || self.timer.is_pending()).await;
+        self.timer.clear_interrupt();
+    }
+    /// Stop counting, remove handler and release timer.
+    #[inline]
+    pub fn free(mut self) -> Timer<'a, I> {
+        self.clear_handler();
+        self.stop();
+        self.timer
+    }
+}
+
+/// Delay using timer interrupt.
+///
+/// Timer interrupt should be routed to [`on_interrupt`] to wake waiting tasks.
+impl<'a, const I: usize> embedded_hal_async::delay::DelayNs for Timer<'a, I> {
+    #[inline]
+    async fn delay_ns(&mut self, ns: u32) {
+        let ticks = (ns as u64 * HOSC_FREQUENCY.0 as u64).div_ceil(1_000_000_000);
+        self.wait_ticks(ticks).await
+    }
+    #[inline]
+    async fn delay_us(&mut self, us: u32) {
+        self.wait_ticks(us as u64 * (HOSC_FREQUENCY.0 / 1_000_000) as u64)
+            .await
+    }
+    #[inline]
+    async fn delay_ms(&mut self, ms: u32) {
+        self.wait_ticks(ms as u64 * (HOSC_FREQUENCY.0 / 1_000) as u64)
+            .await
+    }
+}
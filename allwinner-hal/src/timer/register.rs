@@ -0,0 +1,563 @@
+use volatile_register::RW;
+
+/// Number of general purpose timers on current SoC.
+pub const TIMER_COUNT: usize = 2;
+
+/// Timer registers.
+#[repr(C)]
+pub struct RegisterBlock {
+    /// 0x00 - Timer IRQ Enable Register.
+    pub irq_enable: RW<IrqEnable>,
+    /// 0x04 - Timer Status Register.
+    pub irq_status: RW<IrqStatus>,
+    _reserved0: [u32; 2],
+    /// 0x10 ..= 0x2C - Timer 0 and Timer 1 register groups.
+    pub timers: [TimerRegisters; TIMER_COUNT],
+    _reserved1: [u32; 28],
+    /// 0xA0 - Watchdog IRQ Enable Register.
+    pub wdog_irq_enable: RW<u32>,
+    /// 0xA4 - Watchdog Status Register.
+    pub wdog_irq_status: RW<u32>,
+    /// 0xA8 - Watchdog Software Reset Register.
+    pub wdog_soft_reset: RW<u32>,
+    _reserved2: [u32; 1],
+    /// 0xB0 - Watchdog Control Register.
+    pub wdog_control: RW<WatchdogControl>,
+    /// 0xB4 - Watchdog Configuration Register.
+    pub wdog_config: RW<WatchdogConfig>,
+    /// 0xB8 - Watchdog Mode Register.
+    pub wdog_mode: RW<WatchdogMode>,
+    /// 0xBC - Watchdog Output Configuration Register.
+    pub wdog_output_config: RW<u32>,
+}
+
+/// Timer register group.
+#[repr(C)]
+pub struct TimerRegisters {
+    /// 0x00 - Timer Control Register.
+    pub control: RW<Control>,
+    /// 0x04 - Timer Interval Value Register.
+    pub interval: RW<u32>,
+    /// 0x08 - Timer Current Value Register.
+    pub current_value: RW<u32>,
+    _reserved0: [u32; 1],
+}
+
+/// Timer IRQ enable register.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct IrqEnable(u32);
+
+impl IrqEnable {
+    /// If interrupt of timer `I` is enabled.
+    #[inline]
+    pub const fn is_enabled<const I: usize>(self) -> bool {
+        self.0 & (1 << I) != 0
+    }
+    /// Enable interrupt of timer `I`.
+    #[inline]
+    pub const fn enable<const I: usize>(self) -> Self {
+        Self(self.0 | (1 << I))
+    }
+    /// Disable interrupt of timer `I`.
+    #[inline]
+    pub const fn disable<const I: usize>(self) -> Self {
+        Self(self.0 & !(1 << I))
+    }
+}
+
+impl Default for IrqEnable {
+    #[inline]
+    fn default() -> Self {
+        Self(0x0000_0000)
+    }
+}
+
+/// Timer IRQ status register.
+///
+/// Pending bits are set when timer reaches its interval, whether interrupt is enabled or not.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct IrqStatus(u32);
+
+impl IrqStatus {
+    /// If interrupt of timer `I` is pending.
+    #[inline]
+    pub const fn is_pending<const I: usize>(self) -> bool {
+        self.0 & (1 << I) != 0
+    }
+    /// Clear pending interrupt of timer `I` on write.
+    #[inline]
+    pub const fn clear_pending<const I: usize>(self) -> Self {
+        Self(self.0 | (1 << I))
+    }
+}
+
+impl Default for IrqStatus {
+    #[inline]
+    fn default() -> Self {
+        Self(0x0000_0000)
+    }
+}
+
+/// Timer control register.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct Control(u32);
+
+/// Timer counting mode.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum TimerMode {
+    /// Reload interval value and continue counting when timer reaches zero.
+    Periodic,
+    /// Stop counting when timer reaches zero.
+    Single,
+}
+
+/// Timer clock pre-scale.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Prescale {
+    /// Divide clock by 1.
+    P1,
+    /// Divide clock by 2.
+    P2,
+    /// Divide clock by 4.
+    P4,
+    /// Divide clock by 8.
+    P8,
+    /// Divide clock by 16.
+    P16,
+    /// Divide clock by 32.
+    P32,
+    /// Divide clock by 64.
+    P64,
+    /// Divide clock by 128.
+    P128,
+}
+
+/// Timer clock source.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ClockSource {
+    /// 32-KHz 'LOSC' low speed oscillator.
+    Losc,
+    /// 24-MHz 'HOSC' external oscillator.
+    Hosc,
+}
+
+impl Control {
+    const MODE: u32 = 1 << 7;
+    const CLK_PRES: u32 = 0x7 << 4;
+    const CLK_SRC: u32 = 0x3 << 2;
+    const RELOAD: u32 = 1 << 1;
+    const EN: u32 = 1 << 0;
+
+    /// Get timer counting mode.
+    #[inline]
+    pub const fn mode(self) -> TimerMode {
+        match (self.0 & Self::MODE) >> 7 {
+            0 => TimerMode::Periodic,
+            1 => TimerMode::Single,
+            _ => unreachable!(),
+        }
+    }
+    /// Set timer counting mode.
+    #[inline]
+    pub const fn set_mode(self, val: TimerMode) -> Self {
+        Self((self.0 & !Self::MODE) | ((val as u32) << 7))
+    }
+    /// Get timer clock pre-scale.
+    #[inline]
+    pub const fn prescale(self) -> Prescale {
+        match (self.0 & Self::CLK_PRES) >> 4 {
+            0 => Prescale::P1,
+            1 => Prescale::P2,
+            2 => Prescale::P4,
+            3 => Prescale::P8,
+            4 => Prescale::P16,
+            5 => Prescale::P32,
+            6 => Prescale::P64,
+            7 => Prescale::P128,
+            _ => unreachable!(),
+        }
+    }
+    /// Set timer clock pre-scale.
+    #[inline]
+    pub const fn set_prescale(self, val: Prescale) -> Self {
+        Self((self.0 & !Self::CLK_PRES) | ((val as u32) << 4))
+    }
+    /// Get timer clock source.
+    #[inline]
+    pub const fn clock_source(self) -> ClockSource {
+        match (self.0 & Self::CLK_SRC) >> 2 {
+            0 => ClockSource::Losc,
+            1 => ClockSource::Hosc,
+            _ => panic!("impossible clock source"),
+        }
+    }
+    /// Set timer clock source.
+    #[inline]
+    pub const fn set_clock_source(self, val: ClockSource) -> Self {
+        Self((self.0 & !Self::CLK_SRC) | ((val as u32) << 2))
+    }
+    /// Reload interval value into current value.
+    #[inline]
+    pub const fn set_reload(self) -> Self {
+        Self(self.0 | Self::RELOAD)
+    }
+    /// Is reload finished by hardware?
+    #[inline]
+    pub const fn is_reload_finished(self) -> bool {
+        self.0 & Self::RELOAD == 0
+    }
+    /// If timer is enabled.
+    #[inline]
+    pub const fn is_enabled(self) -> bool {
+        self.0 & Self::EN != 0
+    }
+    /// Enable timer.
+    #[inline]
+    pub const fn enable(self) -> Self {
+        Self(self.0 | Self::EN)
+    }
+    /// Disable timer.
+    #[inline]
+    pub const fn disable(self) -> Self {
+        Self(self.0 & !Self::EN)
+    }
+}
+
+impl Default for Control {
+    #[inline]
+    fn default() -> Self {
+        Self(0x0000_0004)
+    }
+}
+
+/// Key to write watchdog configuration, mode and software reset registers.
+const WDOG_KEY: u32 = 0x16AA << 16;
+
+/// Watchdog control register.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct WatchdogControl(u32);
+
+impl WatchdogControl {
+    const KEY_FIELD: u32 = 0xA57 << 1;
+    const RESTART: u32 = 1 << 0;
+
+    /// Restart watchdog counting, with key field filled.
+    #[inline]
+    pub const fn restart() -> Self {
+        Self(Self::KEY_FIELD | Self::RESTART)
+    }
+}
+
+/// Watchdog configuration register.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct WatchdogConfig(u32);
+
+/// Watchdog clock source.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum WatchdogClockSource {
+    /// 24-MHz 'HOSC' oscillator divided by 750.
+    HoscDiv750,
+    /// 32-KHz 'LOSC' low speed oscillator.
+    Losc,
+}
+
+/// Watchdog action on timeout.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum WatchdogAction {
+    /// Reset whole system.
+    Reset,
+    /// Only raise watchdog interrupt.
+    Interrupt,
+}
+
+impl WatchdogConfig {
+    const KEY: u32 = 0xFFFF << 16;
+    const CLK_SRC: u32 = 1 << 8;
+    const CONFIG: u32 = 0x3;
+
+    /// Get watchdog clock source.
+    #[inline]
+    pub const fn clock_source(self) -> WatchdogClockSource {
+        match (self.0 & Self::CLK_SRC) >> 8 {
+            0 => WatchdogClockSource::HoscDiv750,
+            1 => WatchdogClockSource::Losc,
+            _ => unreachable!(),
+        }
+    }
+    /// Set watchdog clock source.
+    #[inline]
+    pub const fn set_clock_source(self, val: WatchdogClockSource) -> Self {
+        Self((self.0 & !Self::CLK_SRC) | ((val as u32) << 8))
+    }
+    /// Get watchdog action on timeout.
+    #[inline]
+    pub const fn action(self) -> Option<WatchdogAction> {
+        match self.0 & Self::CONFIG {
+            1 => Some(WatchdogAction::Reset),
+            2 => Some(WatchdogAction::Interrupt),
+            _ => None,
+        }
+    }
+    /// Set watchdog action on timeout.
+    #[inline]
+    pub const fn set_action(self, val: WatchdogAction) -> Self {
+        let val = match val {
+            WatchdogAction::Reset => 1,
+            WatchdogAction::Interrupt => 2,
+        };
+        Self((self.0 & !Self::CONFIG) | val)
+    }
+    /// Fill key field to make register write effective.
+    #[inline]
+    pub const fn with_key(self) -> Self {
+        Self((self.0 & !Self::KEY) | WDOG_KEY)
+    }
+}
+
+impl Default for WatchdogConfig {
+    #[inline]
+    fn default() -> Self {
+        Self(0x0000_0001)
+    }
+}
+
+/// Watchdog mode register.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct WatchdogMode(u32);
+
+/// Watchdog timeout interval.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum WatchdogTimeout {
+    /// 0.5 seconds.
+    Ms500,
+    /// 1 second.
+    S1,
+    /// 2 seconds.
+    S2,
+    /// 3 seconds.
+    S3,
+    /// 4 seconds.
+    S4,
+    /// 5 seconds.
+    S5,
+    /// 6 seconds.
+    S6,
+    /// 8 seconds.
+    S8,
+    /// 10 seconds.
+    S10,
+    /// 12 seconds.
+    S12,
+    /// 14 seconds.
+    S14,
+    /// 16 seconds.
+    S16,
+}
+
+impl WatchdogTimeout {
+    /// Decode timeout interval field, saturating at 16 seconds.
+    #[inline]
+    pub(crate) const fn from_bits(bits: u32) -> Self {
+        match bits {
+            0x0 => WatchdogTimeout::Ms500,
+            0x1 => WatchdogTimeout::S1,
+            0x2 => WatchdogTimeout::S2,
+            0x3 => WatchdogTimeout::S3,
+            0x4 => WatchdogTimeout::S4,
+            0x5 => WatchdogTimeout::S5,
+            0x6 => WatchdogTimeout::S6,
+            0x7 => WatchdogTimeout::S8,
+            0x8 => WatchdogTimeout::S10,
+            0x9 => WatchdogTimeout::S12,
+            0xA => WatchdogTimeout::S14,
+            _ => WatchdogTimeout::S16,
+        }
+    }
+    /// Get timeout interval in milliseconds.
+    #[inline]
+    pub const fn as_millis(self) -> u32 {
+        match self {
+            WatchdogTimeout::Ms500 => 500,
+            WatchdogTimeout::S1 => 1_000,
+            WatchdogTimeout::S2 => 2_000,
+            WatchdogTimeout::S3 => 3_000,
+            WatchdogTimeout::S4 => 4_000,
+            WatchdogTimeout::S5 => 5_000,
+            WatchdogTimeout::S6 => 6_000,
+            WatchdogTimeout::S8 => 8_000,
+            WatchdogTimeout::S10 => 10_000,
+            WatchdogTimeout::S12 => 12_000,
+            WatchdogTimeout::S14 => 14_000,
+            WatchdogTimeout::S16 => 16_000,
+        }
+    }
+}
+
+impl WatchdogMode {
+    const KEY: u32 = 0xFFFF << 16;
+    const INTV_VALUE: u32 = 0xF << 4;
+    const EN: u32 = 1 << 0;
+
+    /// Get watchdog timeout interval.
+    #[inline]
+    pub const fn timeout(self) -> WatchdogTimeout {
+        WatchdogTimeout::from_bits((self.0 & Self::INTV_VALUE) >> 4)
+    }
+    /// Set watchdog timeout interval.
+    #[inline]
+    pub const fn set_timeout(self, val: WatchdogTimeout) -> Self {
+        Self((self.0 & !Self::INTV_VALUE) | ((val as u32) << 4))
+    }
+    /// If watchdog is enabled.
+    #[inline]
+    pub const fn is_enabled(self) -> bool {
+        self.0 & Self::EN != 0
+    }
+    /// Enable watchdog.
+    #[inline]
+    pub const fn enable(self) -> Self {
+        Self(self.0 | Self::EN)
+    }
+    /// Disable watchdog.
+    #[inline]
+    pub const fn disable(self) -> Self {
+        Self(self.0 & !Self::EN)
+    }
+    /// Fill key field to make register write effective.
+    #[inline]
+    pub const fn with_key(self) -> Self {
+        Self((self.0 & !Self::KEY) | WDOG_KEY)
+    }
+}
+
+impl Default for WatchdogMode {
+    #[inline]
+    fn default() -> Self {
+        Self(0x0000_0000)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        ClockSource, Control, IrqEnable, IrqStatus, Prescale, RegisterBlock, TimerMode,
+        TimerRegisters, WatchdogAction, WatchdogClockSource, WatchdogConfig, WatchdogControl,
+        WatchdogMode, WatchdogTimeout,
+    };
+    use core::mem::{offset_of, size_of};
+
+    #[test]
+    fn offset_timer() {
+        assert_eq!(offset_of!(RegisterBlock, irq_enable), 0x00);
+        assert_eq!(offset_of!(RegisterBlock, irq_status), 0x04);
+        assert_eq!(offset_of!(RegisterBlock, timers), 0x10);
+        assert_eq!(offset_of!(TimerRegisters, control), 0x00);
+        assert_eq!(offset_of!(TimerRegisters, interval), 0x04);
+        assert_eq!(offset_of!(TimerRegisters, current_value), 0x08);
+        assert_eq!(size_of::<TimerRegisters>(), 0x10);
+        assert_eq!(offset_of!(RegisterBlock, wdog_irq_enable), 0xA0);
+        assert_eq!(offset_of!(RegisterBlock, wdog_irq_status), 0xA4);
+        assert_eq!(offset_of!(RegisterBlock, wdog_soft_reset), 0xA8);
+        assert_eq!(offset_of!(RegisterBlock, wdog_control), 0xB0);
+        assert_eq!(offset_of!(RegisterBlock, wdog_config), 0xB4);
+        assert_eq!(offset_of!(RegisterBlock, wdog_mode), 0xB8);
+        assert_eq!(offset_of!(RegisterBlock, wdog_output_config), 0xBC);
+    }
+
+    #[test]
+    fn struct_irq_functions() {
+        let mut val = IrqEnable::default();
+        val = val.enable::<1>();
+        assert!(val.is_enabled::<1>());
+        assert!(!val.is_enabled::<0>());
+        assert_eq!(val.0, 0x00000002);
+        val = val.disable::<1>();
+        assert_eq!(val.0, 0x00000000);
+
+        let val = IrqStatus(0x1);
+        assert!(val.is_pending::<0>());
+        assert!(!val.is_pending::<1>());
+        let val = IrqStatus::default().clear_pending::<1>();
+        assert_eq!(val.0, 0x00000002);
+    }
+
+    #[test]
+    fn struct_control_functions() {
+        let mut val = Control(0x0);
+
+        val = val.set_mode(TimerMode::Single);
+        assert_eq!(val.mode(), TimerMode::Single);
+        assert_eq!(val.0, 0x00000080);
+        val = val.set_mode(TimerMode::Periodic);
+        assert_eq!(val.mode(), TimerMode::Periodic);
+        assert_eq!(val.0, 0x00000000);
+
+        val = val.set_prescale(Prescale::P128);
+        assert_eq!(val.prescale(), Prescale::P128);
+        assert_eq!(val.0, 0x00000070);
+        val = val.set_prescale(Prescale::P4);
+        assert_eq!(val.prescale(), Prescale::P4);
+        assert_eq!(val.0, 0x00000020);
+
+        val = Control(0x0);
+        val = val.set_clock_source(ClockSource::Hosc);
+        assert_eq!(val.clock_source(), ClockSource::Hosc);
+        assert_eq!(val.0, 0x00000004);
+        val = val.set_clock_source(ClockSource::Losc);
+        assert_eq!(val.clock_source(), ClockSource::Losc);
+        assert_eq!(val.0, 0x00000000);
+
+        val = val.set_reload();
+        assert!(!val.is_reload_finished());
+        assert_eq!(val.0, 0x00000002);
+
+        val = Control(0x0).enable();
+        assert!(val.is_enabled());
+        assert_eq!(val.0, 0x00000001);
+        val = val.disable();
+        assert!(!val.is_enabled());
+        assert_eq!(val.0, 0x00000000);
+
+        assert_eq!(Control::default().clock_source(), ClockSource::Hosc);
+    }
+
+    #[test]
+    fn struct_watchdog_functions() {
+        assert_eq!(WatchdogControl::restart().0, 0x0000_14AF);
+
+        let mut val = WatchdogConfig(0x0);
+        val = val.set_action(WatchdogAction::Interrupt);
+        assert_eq!(val.action(), Some(WatchdogAction::Interrupt));
+        assert_eq!(val.0, 0x00000002);
+        val = val.set_action(WatchdogAction::Reset);
+        assert_eq!(val.action(), Some(WatchdogAction::Reset));
+        assert_eq!(val.0, 0x00000001);
+        val = val.set_clock_source(WatchdogClockSource::Losc);
+        assert_eq!(val.clock_source(), WatchdogClockSource::Losc);
+        assert_eq!(val.0, 0x00000101);
+        val = val.with_key();
+        assert_eq!(val.0, 0x16AA_0101);
+        assert_eq!(WatchdogConfig(0x0).action(), None);
+
+        let mut val = WatchdogMode::default();
+        val = val.set_timeout(WatchdogTimeout::S16);
+        assert_eq!(val.timeout(), WatchdogTimeout::S16);
+        assert_eq!(val.0, 0x000000B0);
+        val = val.set_timeout(WatchdogTimeout::S8);
+        assert_eq!(val.timeout(), WatchdogTimeout::S8);
+        assert_eq!(val.0, 0x00000070);
+        val = val.enable().with_key();
+        assert!(val.is_enabled());
+        assert_eq!(val.0, 0x16AA_0071);
+        val = val.disable();
+        assert!(!val.is_enabled());
+        assert_eq!(WatchdogTimeout::Ms500.as_millis(), 500);
+        assert_eq!(WatchdogTimeout::S10.as_millis(), 10_000);
+    }
+}
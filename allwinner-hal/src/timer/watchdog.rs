@@ -0,0 +1,147 @@
+use super::register::{
+    RegisterBlock, WatchdogAction, WatchdogConfig, WatchdogControl, WatchdogMode, WatchdogTimeout,
+};
+use core::sync::atomic::{AtomicPtr, AtomicU32, Ordering};
+
+/// Watchdog pre-timeout handler, called shortly before the watchdog resets the system.
+///
+/// Handlers may flush logs or record a reboot reason, and should finish within the
+/// pre-timeout interval.
+pub type PretimeoutHandler = fn();
+
+/// Watchdog interrupt bit in IRQ enable and status registers.
+const WDOG_IRQ: u32 = 1 << 0;
+/// Marks that no pre-timeout is configured.
+const NO_PRETIMEOUT: u32 = u32::MAX;
+
+static HANDLER: AtomicPtr<()> = AtomicPtr::new(core::ptr::null_mut());
+static PRETIMEOUT: AtomicU32 = AtomicU32::new(NO_PRETIMEOUT);
+static TIMEOUT: AtomicU32 = AtomicU32::new(0);
+
+/// Watchdog timer.
+///
+/// With a pre-timeout handler, the watchdog runs in two stages: it first raises an interrupt
+/// when timeout elapses, then [`on_watchdog_interrupt`] arms a system reset after
+/// pre-timeout interval and calls the handler.
+pub struct Watchdog<'a> {
+    timer: &'a RegisterBlock,
+}
+
+impl<'a> Watchdog<'a> {
+    /// Create watchdog from timer register block.
+    #[inline]
+    pub(crate) const fn from_register_block(timer: &'a RegisterBlock) -> Self {
+        Self { timer }
+    }
+    /// Start watchdog to reset the system if not fed within `timeout`.
+    #[inline]
+    pub fn start(&mut self, timeout: WatchdogTimeout) {
+        self.clear_pretimeout();
+        configure(self.timer, WatchdogAction::Reset, timeout);
+    }
+    /// Start watchdog calling `handler` if not fed within `timeout`, and resetting the
+    /// system `pretimeout` after that.
+    ///
+    /// Watchdog interrupt should be routed to [`on_watchdog_interrupt`].
+    #[inline]
+    pub fn start_with_pretimeout(
+        &mut self,
+        timeout: WatchdogTimeout,
+        pretimeout: WatchdogTimeout,
+        handler: PretimeoutHandler,
+    ) {
+        HANDLER.store(handler as *mut (), Ordering::Release);
+        TIMEOUT.store(timeout as u32, Ordering::Relaxed);
+        PRETIMEOUT.store(pretimeout as u32, Ordering::Release);
+        unsafe {
+            self.timer.wdog_irq_status.write(WDOG_IRQ);
+            self.timer.wdog_irq_enable.write(WDOG_IRQ);
+        }
+        configure(self.timer, WatchdogAction::Interrupt, timeout);
+    }
+    /// Restart watchdog counting.
+    ///
+    /// If pre-timeout stage has begun, watchdog returns to its first stage.
+    #[inline]
+    pub fn feed(&mut self) {
+        if PRETIMEOUT.load(Ordering::Acquire) != NO_PRETIMEOUT
+            && self.timer.wdog_config.read().action() == Some(WatchdogAction::Reset)
+        {
+            let timeout = WatchdogTimeout::from_bits(TIMEOUT.load(Ordering::Relaxed));
+            configure(self.timer, WatchdogAction::Interrupt, timeout);
+            return;
+        }
+        unsafe { self.timer.wdog_control.write(WatchdogControl::restart()) };
+    }
+    /// Stop watchdog and remove pre-timeout handler.
+    #[inline]
+    pub fn stop(&mut self) {
+        unsafe {
+            self.timer
+                .wdog_mode
+                .write(WatchdogMode::default().disable().with_key())
+        };
+        self.clear_pretimeout();
+    }
+    /// Check if watchdog is counting.
+    #[inline]
+    pub fn is_running(&self) -> bool {
+        self.timer.wdog_mode.read().is_enabled()
+    }
+    #[inline]
+    fn clear_pretimeout(&mut self) {
+        unsafe {
+            self.timer.wdog_irq_enable.write(0);
+            self.timer.wdog_irq_status.write(WDOG_IRQ);
+        }
+        PRETIMEOUT.store(NO_PRETIMEOUT, Ordering::Release);
+        HANDLER.store(core::ptr::null_mut(), Ordering::Release);
+    }
+}
+
+/// Watchdog interrupt entry point.
+///
+/// Call this function from watchdog interrupt handler. It arms system reset after the
+/// pre-timeout interval, then calls the registered pre-timeout handler.
+pub fn on_watchdog_interrupt(timer: &RegisterBlock) {
+    if timer.wdog_irq_status.read() & WDOG_IRQ == 0 {
+        return;
+    }
+    unsafe { timer.wdog_irq_status.write(WDOG_IRQ) };
+    let pretimeout = PRETIMEOUT.load(Ordering::Acquire);
+    if pretimeout == NO_PRETIMEOUT {
+        return;
+    }
+    // Arm reset before calling handler, so a stuck handler still resets the system.
+    configure(
+        timer,
+        WatchdogAction::Reset,
+        WatchdogTimeout::from_bits(pretimeout),
+    );
+    let handler = HANDLER.load(Ordering::Acquire);
+    if !handler.is_null() {
+        // note(unsafe): only `PretimeoutHandler` function pointers are stored in `HANDLER`
+        let handler: PretimeoutHandler = unsafe { core::mem::transmute(handler) };
+        handler();
+    }
+}
+
+/// Configure and restart watchdog with `action` on `timeout`.
+#[inline]
+fn configure(timer: &RegisterBlock, action: WatchdogAction, timeout: WatchdogTimeout) {
+    unsafe {
+        timer
+            .wdog_mode
+            .write(WatchdogMode::default().disable().with_key());
+        timer
+            .wdog_config
+            .write(WatchdogConfig::default().set_action(action).with_key());
+        timer.wdog_mode.write(
+            WatchdogMode::default()
+                .set_timeout(timeout)
+                .enable()
+                .with_key(),
+        );
+        timer.wdog_control.write(WatchdogControl::restart());
+    }
+}
@@ -3,8 +3,10 @@
 use core::cell::UnsafeCell;
 
 use crate::ccu::{self, ClockGate, Clocks};
+use crate::power::{Resume, Suspend};
+use crate::time::Deadline;
 use embedded_time::rate::Baud;
-use uart16550::{CharLen, PARITY, Register, Uart16550};
+use uart16550::{CharLen, InterruptTypes, LineControl, PARITY, Register, Uart16550};
 
 /// Universal Asynchronous Receiver-Transmitter registers.
 #[repr(C)]
@@ -86,6 +88,16 @@ impl core::ops::Deref for RegisterBlock {
 pub struct Serial<UART, const I: usize, PADS: Pads<I>> {
     uart: UART,
     pads: PADS,
+    retained: Retained,
+}
+
+/// Serial configuration restored on resume.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct Retained {
+    // divisor latch can't be read back, thus it's kept since creation.
+    divisor: u16,
+    lcr: LineControl,
+    ier: InterruptTypes,
 }
 
 impl<UART: AsRef<RegisterBlock>, const I: usize, PADS: Pads<I>> Serial<UART, I, PADS> {
@@ -135,14 +147,25 @@ impl<UART: AsRef<RegisterBlock>, const I: usize, PADS: Pads<I>> Serial<UART, I,
             Parity::Odd => PARITY::ODD,
             Parity::Even => PARITY::EVEN,
         };
-        let lcr = uart.as_ref().lcr().read();
-        uart.as_ref().lcr().write(
-            lcr.set_char_len(char_len)
-                .set_one_stop_bit(one_stop_bit)
-                .set_parity(parity),
-        );
+        let lcr = uart
+            .as_ref()
+            .lcr()
+            .read()
+            .set_char_len(char_len)
+            .set_one_stop_bit(one_stop_bit)
+            .set_parity(parity);
+        uart.as_ref().lcr().write(lcr);
         // 6. return the instance
-        Serial { uart, pads }
+        let retained = Retained {
+            divisor: uart_clk as u16,
+            lcr,
+            ier: uart.as_ref().ier().read(),
+        };
+        Serial {
+            uart,
+            pads,
+            retained,
+        }
     }
     /// Get a temporary borrow on the underlying GPIO pads.
     #[inline]
@@ -161,6 +184,27 @@ impl<UART: AsRef<RegisterBlock>, const I: usize, PADS: Pads<I>> Serial<UART, I,
     }
 }
 
+impl<UART: AsRef<RegisterBlock>, const I: usize, PADS: Pads<I>> Suspend for Serial<UART, I, PADS> {
+    #[inline]
+    fn suspend(&mut self) {
+        let uart = self.uart.as_ref();
+        // a transmitter stuck on flow control shouldn't prevent standby.
+        let _ = uart_flush_blocking(uart);
+        self.retained.lcr = uart.lcr().read();
+        self.retained.ier = uart.ier().read();
+    }
+}
+
+impl<UART: AsRef<RegisterBlock>, const I: usize, PADS: Pads<I>> Resume for Serial<UART, I, PADS> {
+    #[inline]
+    fn resume(&mut self) {
+        let uart = self.uart.as_ref();
+        uart.lcr().write(self.retained.lcr);
+        uart.write_divisor(self.retained.divisor);
+        uart.ier().write(self.retained.ier);
+    }
+}
+
 impl<UART: AsRef<RegisterBlock>, const I: usize, TX: Transmit<I>, RX: Receive<I>>
     Serial<UART, I, (TX, RX)>
 {
@@ -205,14 +249,31 @@ pub trait Transmit<const I: usize> {}
 /// Valid receive pin for UART peripheral.
 pub trait Receive<const I: usize> {}
 
+/// Serial error.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Error {
+    /// Transmitter did not become ready before crate-wide blocking timeout.
+    ///
+    /// See [`crate::time::set_blocking_timeout`].
+    Timeout,
+}
+
+impl embedded_io::Error for Error {
+    #[inline]
+    fn kind(&self) -> embedded_io::ErrorKind {
+        match self {
+            Error::Timeout => embedded_io::ErrorKind::TimedOut,
+        }
+    }
+}
+
 #[inline]
-fn uart_write_blocking(
-    uart: &RegisterBlock,
-    buffer: &[u8],
-) -> Result<usize, core::convert::Infallible> {
+fn uart_write_blocking(uart: &RegisterBlock, buffer: &[u8]) -> Result<usize, Error> {
     for c in buffer {
+        let deadline = Deadline::start();
         // FIXME: should be transmit_fifo_not_full
         while uart.usr.read().busy() {
+            deadline.check().map_err(|_| Error::Timeout)?;
             core::hint::spin_loop()
         }
         uart.rbr_thr().tx_data(*c);
@@ -221,18 +282,17 @@ fn uart_write_blocking(
 }
 
 #[inline]
-fn uart_flush_blocking(uart: &RegisterBlock) -> Result<(), core::convert::Infallible> {
+fn uart_flush_blocking(uart: &RegisterBlock) -> Result<(), Error> {
+    let deadline = Deadline::start();
     while !uart.usr.read().transmit_fifo_empty() {
+        deadline.check().map_err(|_| Error::Timeout)?;
         core::hint::spin_loop()
     }
     Ok(())
 }
 
 #[inline]
-fn uart_read_blocking(
-    uart: &RegisterBlock,
-    buffer: &mut [u8],
-) -> Result<usize, core::convert::Infallible> {
+fn uart_read_blocking(uart: &RegisterBlock, buffer: &mut [u8]) -> Result<usize, Error> {
     let len = buffer.len();
     for c in buffer {
         while !uart.uart16550.lsr().read().is_data_ready() {
@@ -254,19 +314,19 @@ where
 impl<UART: AsRef<RegisterBlock>, const I: usize, PADS: Pads<I>> embedded_io::ErrorType
     for Serial<UART, I, PADS>
 {
-    type Error = core::convert::Infallible;
+    type Error = Error;
 }
 
 impl<UART: AsRef<RegisterBlock>, const I: usize, PADS: Transmit<I>> embedded_io::ErrorType
     for TransmitHalf<UART, I, PADS>
 {
-    type Error = core::convert::Infallible;
+    type Error = Error;
 }
 
 impl<UART: AsRef<RegisterBlock>, const I: usize, PADS: Receive<I>> embedded_io::ErrorType
     for ReceiveHalf<UART, I, PADS>
 {
-    type Error = core::convert::Infallible;
+    type Error = Error;
 }
 
 impl<UART: AsRef<RegisterBlock>, const I: usize, PADS: Pads<I>> embedded_io::Write
@@ -0,0 +1,220 @@
+//! USB On-The-Go controller.
+//!
+//! USB0 of D1 and T113 is a Mentor Graphics MUSB compatible OTG controller. With `usb-device`
+//! feature, [`UsbBus`] runs it in device mode and implements [`usb_device::bus::UsbBus`], so
+//! that `usb-device` classes such as CDC-ACM, HID and mass storage run on top of it:
+//!
+//! ```ignore
+//! let bus = UsbBusAllocator::new(UsbBus::new(p.usb0, Speed::Full, &p.ccu));
+//! let mut serial = SerialPort::new(&bus);
+//! let mut device = UsbDeviceBuilder::new(&bus, UsbVidPid(0x1209, 0x0001)).build();
+//! loop {
+//!     if device.poll(&mut [&mut serial]) {
+//!         // read and write serial port
+//!     }
+//! }
+//! ```
+//!
+//! For a console without class setup, [`UsbSerial`] wraps a CDC-ACM serial port and its
+//! device into `embedded_io::Read` and `embedded_io::Write`. [`MassStorage`] exposes an SD
+//! card, eMMC or a [`BlockRegion`](crate::smhc::BlockRegion) of flash as a USB drive.
+//!
+//! [`UsbHost`] runs the controller in host mode instead, implementing [`host::Host`] to
+//! enumerate a device attached directly to the port and run control, bulk and interrupt
+//! transfers, e.g. for mass storage drives and HID keyboards. USB1 port has standard EHCI
+//! and OHCI host controllers instead, driven by [`hci::Hci`] with the same API.
+//!
+//! [`RoleSwitch`] follows ID pin of the OTG port over [`IdSense`] and switches its VBUS
+//! supply over [`VbusDrive`], implemented for GPIO pins and PMICs, so that the port changes
+//! between the two drivers at runtime. [`set_vbus_detect`] selects VBUS detection from PHY
+//! or from an external [`VbusSense`], and [`PhyTuning`] adjusts analog PHY parameters.
+//!
+//! Endpoint 0 uses the first 64 bytes of FIFO RAM, and each other endpoint direction takes
+//! its maximum packet size rounded up to a power of two, allocated in order of `alloc_ep`.
+//! Bulk endpoint packets are copied between FIFOs and memory by CPU, or by a DMAC channel
+//! given to [`UsbBus::with_dma`] so that mass storage throughput isn't bound by CPU copies.
+//!
+//! With `embassy-usb-driver` feature, `embassy::Driver` runs device mode for `embassy-usb`
+//! instead, completing endpoint transfers asynchronously on controller interrupts.
+
+#[cfg(feature = "usb-device")]
+mod device;
+#[cfg(feature = "usb-device")]
+mod dma;
+#[cfg(feature = "embassy-usb-driver")]
+pub mod embassy;
+pub mod hci;
+pub mod host;
+#[cfg(feature = "usb-device")]
+mod msc;
+mod otg;
+mod otg_host;
+mod phy;
+mod register;
+#[cfg(feature = "usb-device")]
+mod serial;
+#[cfg(feature = "usb-device")]
+pub use device::*;
+#[cfg(feature = "usb-device")]
+pub use dma::*;
+#[cfg(feature = "usb-device")]
+pub use msc::*;
+pub use otg::*;
+pub use otg_host::*;
+pub use phy::*;
+pub use register::*;
+#[cfg(feature = "usb-device")]
+pub use serial::*;
+
+use crate::ccu::{self, ClockGate};
+use volatile_register::RW;
+
+/// Number of endpoints, including endpoint 0.
+pub const ENDPOINTS: usize = 6;
+
+/// Size of endpoint FIFO RAM in bytes.
+pub const FIFO_RAM_SIZE: u16 = 4096;
+
+#[cfg(any(feature = "usb-device", feature = "embassy-usb-driver"))]
+const EP0_FIFO_SIZE: u16 = 64;
+
+/// USB device speed.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Speed {
+    /// Full speed, 12 Mbit/s.
+    Full,
+    /// High speed, 480 Mbit/s, when host supports it.
+    High,
+}
+
+#[cfg(any(feature = "usb-device", feature = "embassy-usb-driver"))]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+struct Endpoint {
+    max_packet_size: u16,
+    fifo_address: u16,
+    fifo_size: u16,
+    iso: bool,
+    dma: bool,
+}
+
+/// Release USB OTG controller and USB0 PHY from reset, and route the PHY to the controller
+/// with ID pin forced by `force_id`; all interrupts are disabled.
+fn open(regs: &RegisterBlock, ccu: &ccu::RegisterBlock, force_id: fn(Iscr) -> Iscr) {
+    // note(unsafe): async read and write using ccu registers
+    unsafe {
+        ccu::USBOTG::reset(ccu);
+        ccu.usb_clock[0].modify(|val| val.deassert_phy_reset());
+    }
+    unsafe {
+        regs.phy_otg_control.modify(|val| val.route_to_otg());
+        regs.phy_control
+            .modify(|val| val.power_up().set_vbus_valid_external());
+        regs.iscr
+            .modify(|val| force_id(val.keep_change_detect().enable_pullups().force_vbus_valid()));
+        // PIO mode, endpoint FIFOs are connected to DMA request lines only during DMA copies.
+        regs.vendor0.write(Vendor0::default());
+        regs.tx_interrupt_enable.write(0);
+        regs.rx_interrupt_enable.write(0);
+        regs.usb_interrupt_enable.write(UsbInterrupts::default());
+    }
+}
+
+/// Configure packet sizes and FIFOs of endpoints other than endpoint 0, clearing data toggles.
+#[cfg(any(feature = "usb-device", feature = "embassy-usb-driver"))]
+fn configure_endpoints(
+    regs: &RegisterBlock,
+    tx: &[Option<Endpoint>; ENDPOINTS],
+    rx: &[Option<Endpoint>; ENDPOINTS],
+) {
+    for index in 1..ENDPOINTS {
+        unsafe { regs.index.write(index as u8) };
+        if let Some(ep) = tx[index] {
+            let csr = TxCsr::default().clear_data_toggle();
+            let csr = if ep.iso { csr.enable_iso() } else { csr };
+            unsafe {
+                regs.tx_max_packet.write(ep.max_packet_size);
+                regs.tx_fifo_size
+                    .write(FifoSize::default().set_size(ep.fifo_size));
+                regs.tx_fifo_address.write(ep.fifo_address / 8);
+                regs.csr.write(csr.bits());
+            }
+        }
+        if let Some(ep) = rx[index] {
+            let csr = RxCsr::default().clear_data_toggle();
+            let csr = if ep.iso { csr.enable_iso() } else { csr };
+            unsafe {
+                regs.rx_max_packet.write(ep.max_packet_size);
+                regs.rx_fifo_size
+                    .write(FifoSize::default().set_size(ep.fifo_size));
+                regs.rx_fifo_address.write(ep.fifo_address / 8);
+                regs.rx_csr.write(csr.bits());
+            }
+        }
+    }
+}
+
+/// Disable interrupts, power down USB0 PHY and close USB OTG controller.
+fn close(regs: &RegisterBlock, ccu: &ccu::RegisterBlock) {
+    unsafe {
+        regs.tx_interrupt_enable.write(0);
+        regs.rx_interrupt_enable.write(0);
+        regs.usb_interrupt_enable.write(UsbInterrupts::default());
+        regs.phy_control.modify(|val| val.power_down());
+        ccu.usb_clock[0].modify(|val| val.assert_phy_reset());
+        ccu::USBOTG::free(ccu);
+    }
+}
+
+/// FIFO size for `max_packet_size`, a power of two of at least 8 bytes.
+#[cfg(any(feature = "usb-device", feature = "embassy-usb-driver"))]
+#[inline]
+fn fifo_size(max_packet_size: u16) -> Option<u16> {
+    match max_packet_size {
+        0..=8 => Some(8),
+        9..=1024 => Some(max_packet_size.next_power_of_two()),
+        _ => None,
+    }
+}
+
+#[inline]
+fn write_fifo(regs: &RegisterBlock, index: usize, buf: &[u8]) {
+    let fifo = &regs.fifo[index];
+    let mut words = buf.chunks_exact(4);
+    for word in &mut words {
+        unsafe { fifo.write(u32::from_le_bytes([word[0], word[1], word[2], word[3]])) };
+    }
+    // note(unsafe): FIFO register accepts byte access for the trailing bytes.
+    let byte = fifo as *const RW<u32> as *mut u8;
+    for &val in words.remainder() {
+        unsafe { byte.write_volatile(val) };
+    }
+}
+
+#[inline]
+fn read_fifo(regs: &RegisterBlock, index: usize, buf: &mut [u8]) {
+    let fifo = &regs.fifo[index];
+    let mut words = buf.chunks_exact_mut(4);
+    for word in &mut words {
+        word.copy_from_slice(&fifo.read().to_le_bytes());
+    }
+    let byte = fifo as *const RW<u32> as *const u8;
+    for val in words.into_remainder() {
+        *val = unsafe { byte.read_volatile() };
+    }
+}
+
+#[cfg(all(test, any(feature = "usb-device", feature = "embassy-usb-driver")))]
+mod tests {
+    use super::fifo_size;
+
+    #[test]
+    fn function_fifo_size() {
+        assert_eq!(fifo_size(0), Some(8));
+        assert_eq!(fifo_size(8), Some(8));
+        assert_eq!(fifo_size(9), Some(16));
+        assert_eq!(fifo_size(64), Some(64));
+        assert_eq!(fifo_size(188), Some(256));
+        assert_eq!(fifo_size(1023), Some(1024));
+        assert_eq!(fifo_size(1025), None);
+    }
+}
@@ -0,0 +1,644 @@
+use super::{
+    Csr0, ENDPOINTS, EP0_FIFO_SIZE, Endpoint, EndpointDma, FIFO_RAM_SIZE, Interrupt, Iscr, NoDma,
+    Power, RegisterBlock, RxCsr, Speed, TxCsr, UsbInterrupts, close, configure_endpoints,
+    endpoint_drq, fifo_size, open, read_fifo, rx_config, tx_config, write_fifo,
+};
+use crate::ccu;
+use core::{
+    cell::UnsafeCell,
+    sync::atomic::{AtomicBool, AtomicU8, AtomicU16, Ordering},
+};
+use usb_device::{
+    UsbDirection, UsbError,
+    bus::PollResult,
+    endpoint::{EndpointAddress, EndpointType},
+};
+use volatile_register::RW;
+
+// Endpoint 0 control transfer stages.
+const EP0_IDLE: u8 = 0;
+const EP0_IN_DATA: u8 = 1;
+const EP0_OUT_DATA: u8 = 2;
+const EP0_NO_DATA: u8 = 3;
+const EP0_STATUS: u8 = 4;
+
+/// Managed USB OTG controller in device mode.
+///
+/// Packets of bulk endpoints are copied by `DMA` engine, if any, e.g. a DMAC channel given to
+/// [`UsbBus::with_dma`]; other endpoints and unaligned buffers are copied by CPU.
+pub struct UsbBus<USB, DMA = NoDma> {
+    usb: USB,
+    speed: Speed,
+    tx: [Option<Endpoint>; ENDPOINTS],
+    rx: [Option<Endpoint>; ENDPOINTS],
+    fifo_top: u16,
+    ep0_stage: AtomicU8,
+    // Data stage bytes left to transfer, from wLength of setup packet.
+    ep0_remaining: AtomicU16,
+    // Received setup or last data packet not yet acknowledged by serviced rx packet ready,
+    // so that data end or stall is decided by the class.
+    ep0_ack_pending: AtomicBool,
+    // Endpoint 0 packet or status stage in flight, reported as in complete on next interrupt.
+    ep0_in_pending: AtomicBool,
+    dma: UnsafeCell<DMA>,
+    // DMA engine is borrowed by one endpoint copy at a time.
+    dma_busy: AtomicBool,
+}
+
+// note(unsafe): DMA engine is only accessed by the endpoint copy holding `dma_busy`.
+unsafe impl<USB: Sync, DMA: Send> Sync for UsbBus<USB, DMA> {}
+
+impl<USB: AsRef<RegisterBlock>> UsbBus<USB> {
+    /// Create a USB device instance, disconnected from the bus until `usb-device` enables it.
+    ///
+    /// USB0 PHY is routed to OTG controller, with ID and VBUS forced to device mode.
+    #[inline]
+    pub fn new(usb: USB, speed: Speed, ccu: &ccu::RegisterBlock) -> Self {
+        Self::with_dma(usb, speed, NoDma, ccu)
+    }
+    /// Disconnect from the bus, close USB OTG controller and release peripheral.
+    #[inline]
+    pub fn free(self, ccu: &ccu::RegisterBlock) -> USB {
+        self.free_with_dma(ccu).0
+    }
+}
+
+impl<USB: AsRef<RegisterBlock>, DMA: EndpointDma> UsbBus<USB, DMA> {
+    /// Create a USB device instance copying bulk endpoint packets by `dma`.
+    ///
+    /// Packet buffers of bulk endpoints are accessed by DMA, and should reside in DRAM.
+    #[inline]
+    pub fn with_dma(usb: USB, speed: Speed, dma: DMA, ccu: &ccu::RegisterBlock) -> Self {
+        let regs = usb.as_ref();
+        open(regs, ccu, Iscr::force_device);
+        unsafe { regs.power.write(Power::default().soft_disconnect()) };
+        Self {
+            usb,
+            speed,
+            tx: [None; ENDPOINTS],
+            rx: [None; ENDPOINTS],
+            fifo_top: EP0_FIFO_SIZE,
+            ep0_stage: AtomicU8::new(EP0_IDLE),
+            ep0_remaining: AtomicU16::new(0),
+            ep0_ack_pending: AtomicBool::new(false),
+            ep0_in_pending: AtomicBool::new(false),
+            dma: UnsafeCell::new(dma),
+            dma_busy: AtomicBool::new(false),
+        }
+    }
+    /// Check if device has negotiated high speed with host.
+    #[inline]
+    pub fn is_high_speed(&self) -> bool {
+        self.usb.as_ref().power.read().is_high_speed()
+    }
+    /// Check if controller is in suspend mode, entered on bus idle and left on resume or reset.
+    #[inline]
+    pub fn is_suspended(&self) -> bool {
+        self.usb.as_ref().power.read().is_suspended()
+    }
+    /// Disconnect from the bus, close USB OTG controller and release peripheral and DMA
+    /// engine.
+    #[inline]
+    pub fn free_with_dma(self, ccu: &ccu::RegisterBlock) -> (USB, DMA) {
+        let regs = self.usb.as_ref();
+        unsafe { regs.power.modify(|val| val.soft_disconnect()) };
+        close(regs, ccu);
+        (self.usb, self.dma.into_inner())
+    }
+    #[inline]
+    fn select(&self, index: usize) {
+        unsafe { self.usb.as_ref().index.write(index as u8) };
+    }
+    #[inline]
+    fn ep0_max_packet_size(&self) -> u16 {
+        self.tx[0].map_or(EP0_FIFO_SIZE, |ep| ep.max_packet_size)
+    }
+    #[inline]
+    fn reset_ep0(&self) {
+        self.ep0_stage.store(EP0_IDLE, Ordering::Relaxed);
+        self.ep0_remaining.store(0, Ordering::Relaxed);
+        self.ep0_ack_pending.store(false, Ordering::Relaxed);
+        self.ep0_in_pending.store(false, Ordering::Relaxed);
+    }
+    fn write_ep0(&self, buf: &[u8]) -> usb_device::Result<usize> {
+        let regs = self.usb.as_ref();
+        self.select(0);
+        if Csr0::from_bits(regs.csr.read()).is_tx_packet_ready() {
+            return Err(UsbError::WouldBlock);
+        }
+        let max_packet_size = self.ep0_max_packet_size();
+        if buf.len() > max_packet_size as usize {
+            return Err(UsbError::BufferOverflow);
+        }
+        match self.ep0_stage.load(Ordering::Relaxed) {
+            EP0_NO_DATA => {
+                // Zero-length status packet: hardware answers status stage once data end is set.
+                self.ep0_ack_pending.store(false, Ordering::Relaxed);
+                self.ep0_stage.store(EP0_STATUS, Ordering::Relaxed);
+                self.ep0_in_pending.store(true, Ordering::Relaxed);
+                let csr = Csr0::default()
+                    .set_serviced_rx_packet_ready()
+                    .set_data_end();
+                unsafe { regs.csr.write(csr.bits()) };
+                Ok(0)
+            }
+            EP0_IN_DATA => {
+                if self.ep0_ack_pending.swap(false, Ordering::Relaxed) {
+                    let csr = Csr0::default().set_serviced_rx_packet_ready();
+                    unsafe { regs.csr.write(csr.bits()) };
+                }
+                write_fifo(regs, 0, buf);
+                let remaining = self.ep0_remaining.load(Ordering::Relaxed);
+                let remaining = remaining.saturating_sub(buf.len() as u16);
+                self.ep0_remaining.store(remaining, Ordering::Relaxed);
+                let csr = Csr0::default().set_tx_packet_ready();
+                let csr = if buf.len() < max_packet_size as usize || remaining == 0 {
+                    self.ep0_stage.store(EP0_STATUS, Ordering::Relaxed);
+                    csr.set_data_end()
+                } else {
+                    csr
+                };
+                self.ep0_in_pending.store(true, Ordering::Relaxed);
+                unsafe { regs.csr.write(csr.bits()) };
+                Ok(buf.len())
+            }
+            // Zero-length packet after data end has been set; data stage is already complete.
+            _ if buf.is_empty() => Ok(0),
+            _ => Err(UsbError::InvalidState),
+        }
+    }
+    fn read_ep0(&self, buf: &mut [u8]) -> usb_device::Result<usize> {
+        let regs = self.usb.as_ref();
+        self.select(0);
+        let csr = Csr0::from_bits(regs.csr.read());
+        if !csr.is_rx_packet_ready() || self.ep0_ack_pending.load(Ordering::Relaxed) {
+            return Err(UsbError::WouldBlock);
+        }
+        let count = regs.count.read() as usize;
+        if count > buf.len() {
+            return Err(UsbError::BufferOverflow);
+        }
+        read_fifo(regs, 0, &mut buf[..count]);
+        if self.ep0_stage.load(Ordering::Relaxed) == EP0_OUT_DATA {
+            let remaining = self.ep0_remaining.load(Ordering::Relaxed);
+            let remaining = remaining.saturating_sub(count as u16);
+            self.ep0_remaining.store(remaining, Ordering::Relaxed);
+            if remaining == 0 || count < self.ep0_max_packet_size() as usize {
+                // Acknowledge last packet with data end once class accepts the request.
+                self.ep0_stage.store(EP0_NO_DATA, Ordering::Relaxed);
+                self.ep0_ack_pending.store(true, Ordering::Relaxed);
+            } else {
+                let csr = Csr0::default().set_serviced_rx_packet_ready();
+                unsafe { regs.csr.write(csr.bits()) };
+            }
+            return Ok(count);
+        }
+        let (direction, length) = data_stage(&buf[..count]);
+        self.ep0_remaining.store(length, Ordering::Relaxed);
+        self.ep0_in_pending.store(false, Ordering::Relaxed);
+        match (direction, length) {
+            (UsbDirection::Out, 1..) => {
+                self.ep0_stage.store(EP0_OUT_DATA, Ordering::Relaxed);
+                let csr = Csr0::default().set_serviced_rx_packet_ready();
+                unsafe { regs.csr.write(csr.bits()) };
+            }
+            (UsbDirection::In, 1..) => {
+                self.ep0_stage.store(EP0_IN_DATA, Ordering::Relaxed);
+                self.ep0_ack_pending.store(true, Ordering::Relaxed);
+            }
+            _ => {
+                self.ep0_stage.store(EP0_NO_DATA, Ordering::Relaxed);
+                self.ep0_ack_pending.store(true, Ordering::Relaxed);
+            }
+        }
+        Ok(count)
+    }
+    // Returns out, in complete and setup bits of endpoint 0.
+    fn poll_ep0(&self, tx_interrupt: u16) -> (u16, u16, u16) {
+        let regs = self.usb.as_ref();
+        self.select(0);
+        let csr = Csr0::from_bits(regs.csr.read());
+        if csr.is_sent_stall() {
+            // Writing zero clears sent stall; other bits take no effect.
+            unsafe { regs.csr.write(Csr0::default().bits()) };
+            self.reset_ep0();
+        }
+        if csr.is_setup_end() {
+            let csr = Csr0::default().set_serviced_setup_end();
+            unsafe { regs.csr.write(csr.bits()) };
+            self.reset_ep0();
+        }
+        let csr = Csr0::from_bits(regs.csr.read());
+        let (mut ep_out, mut ep_in_complete, mut ep_setup) = (0, 0, 0);
+        if csr.is_rx_packet_ready() && !self.ep0_ack_pending.load(Ordering::Relaxed) {
+            if self.ep0_stage.load(Ordering::Relaxed) == EP0_OUT_DATA {
+                ep_out = 1;
+            } else {
+                self.ep0_stage.store(EP0_IDLE, Ordering::Relaxed);
+                ep_setup = 1;
+            }
+        } else if tx_interrupt & 1 != 0 && self.ep0_in_pending.swap(false, Ordering::Relaxed) {
+            // usb-device drops endpoint 0 in complete reported together with setup or out
+            // packets, thus it is reported alone on its own interrupt.
+            ep_in_complete = 1;
+            if self.ep0_stage.load(Ordering::Relaxed) == EP0_STATUS {
+                self.ep0_stage.store(EP0_IDLE, Ordering::Relaxed);
+            }
+        }
+        (ep_out, ep_in_complete, ep_setup)
+    }
+    /// Copy `buf` into FIFO of selected endpoint `index` by DMA, and trailing bytes by CPU.
+    ///
+    /// Returns `false` without copying if DMA can't take the buffer, leaving it to CPU.
+    fn write_fifo_dma(&self, index: usize, buf: &[u8]) -> usb_device::Result<bool> {
+        let Some(drq) = endpoint_drq(index) else {
+            return Ok(false);
+        };
+        let regs = self.usb.as_ref();
+        let words = buf.len() & !3;
+        let result = self.with_dma_engine(buf.as_ptr() as usize, words, |dma| {
+            let csr = TxCsr::from_bits(regs.csr.read());
+            unsafe {
+                regs.vendor0.modify(|val| val.select_dma_bus());
+                regs.csr.write(csr.enable_dma().bits());
+            }
+            let fifo = &regs.fifo[index] as *const RW<u32> as usize as u32;
+            let result = dma.copy(
+                &tx_config(drq),
+                buf.as_ptr() as usize as u32,
+                fifo,
+                words as u32,
+            );
+            let csr = TxCsr::from_bits(regs.csr.read()).disable_dma();
+            unsafe {
+                regs.csr.write(csr.bits());
+                regs.vendor0.modify(|val| val.select_pio_bus());
+            }
+            if result.is_err() {
+                unsafe { regs.csr.write(csr.flush_fifo().bits()) };
+            }
+            result
+        });
+        match result {
+            Some(Ok(())) => {
+                write_fifo(regs, index, &buf[words..]);
+                Ok(true)
+            }
+            Some(Err(_)) => Err(UsbError::InvalidState),
+            None => Ok(false),
+        }
+    }
+    /// Copy received packet of selected endpoint `index` into `buf` by DMA, and trailing
+    /// bytes by CPU.
+    ///
+    /// Returns `false` without copying if DMA can't take the buffer, leaving it to CPU.
+    fn read_fifo_dma(&self, index: usize, buf: &mut [u8]) -> usb_device::Result<bool> {
+        let Some(drq) = endpoint_drq(index) else {
+            return Ok(false);
+        };
+        let regs = self.usb.as_ref();
+        let words = buf.len() & !3;
+        let result = self.with_dma_engine(buf.as_ptr() as usize, words, |dma| {
+            let csr = RxCsr::from_bits(regs.rx_csr.read());
+            unsafe {
+                regs.vendor0.modify(|val| val.select_dma_bus());
+                regs.rx_csr.write(csr.enable_dma().bits());
+            }
+            let fifo = &regs.fifo[index] as *const RW<u32> as usize as u32;
+            let result = dma.copy(
+                &rx_config(drq),
+                fifo,
+                buf.as_mut_ptr() as usize as u32,
+                words as u32,
+            );
+            let csr = RxCsr::from_bits(regs.rx_csr.read()).disable_dma();
+            unsafe {
+                regs.rx_csr.write(csr.bits());
+                regs.vendor0.modify(|val| val.select_pio_bus());
+            }
+            if result.is_err() {
+                unsafe { regs.rx_csr.write(csr.flush_fifo().bits()) };
+            }
+            result
+        });
+        match result {
+            Some(Ok(())) => {
+                read_fifo(regs, index, &mut buf[words..]);
+                Ok(true)
+            }
+            Some(Err(_)) => Err(UsbError::InvalidState),
+            None => Ok(false),
+        }
+    }
+    /// Run `f` with DMA engine for `words` bytes of buffer at `address`.
+    ///
+    /// Returns `None` if there's no engine, the buffer is not word aligned or has no whole
+    /// word, or the engine is taken by another copy.
+    #[inline]
+    fn with_dma_engine<T>(
+        &self,
+        address: usize,
+        words: usize,
+        f: impl FnOnce(&mut DMA) -> T,
+    ) -> Option<T> {
+        if !DMA::ENABLED || !address.is_multiple_of(4) || words == 0 {
+            return None;
+        }
+        if self.dma_busy.swap(true, Ordering::Acquire) {
+            return None;
+        }
+        // note(unsafe): `dma_busy` grants exclusive access to DMA engine
+        let ans = f(unsafe { &mut *self.dma.get() });
+        self.dma_busy.store(false, Ordering::Release);
+        Some(ans)
+    }
+}
+
+impl<USB: AsRef<RegisterBlock> + Sync, DMA: EndpointDma + Send> usb_device::bus::UsbBus
+    for UsbBus<USB, DMA>
+{
+    fn alloc_ep(
+        &mut self,
+        ep_dir: UsbDirection,
+        ep_addr: Option<EndpointAddress>,
+        ep_type: EndpointType,
+        max_packet_size: u16,
+        _interval: u8,
+    ) -> usb_device::Result<EndpointAddress> {
+        let table = match ep_dir {
+            UsbDirection::Out => &mut self.rx,
+            UsbDirection::In => &mut self.tx,
+        };
+        let index = match ep_addr {
+            Some(addr) => addr.index(),
+            None if ep_type == EndpointType::Control => 0,
+            None => (1..ENDPOINTS)
+                .find(|&i| table[i].is_none())
+                .ok_or(UsbError::EndpointOverflow)?,
+        };
+        if index >= ENDPOINTS || table[index].is_some() {
+            return Err(UsbError::InvalidEndpoint);
+        }
+        if (index == 0) != (ep_type == EndpointType::Control) {
+            return Err(UsbError::InvalidEndpoint);
+        }
+        let endpoint = if index == 0 {
+            if max_packet_size > EP0_FIFO_SIZE {
+                return Err(UsbError::EndpointMemoryOverflow);
+            }
+            Endpoint {
+                max_packet_size,
+                fifo_address: 0,
+                fifo_size: EP0_FIFO_SIZE,
+                iso: false,
+                dma: false,
+            }
+        } else {
+            let fifo_size = fifo_size(max_packet_size).ok_or(UsbError::EndpointMemoryOverflow)?;
+            if FIFO_RAM_SIZE - self.fifo_top < fifo_size {
+                return Err(UsbError::EndpointMemoryOverflow);
+            }
+            let fifo_address = self.fifo_top;
+            self.fifo_top += fifo_size;
+            Endpoint {
+                max_packet_size,
+                fifo_address,
+                fifo_size,
+                iso: matches!(ep_type, EndpointType::Isochronous { .. }),
+                dma: DMA::ENABLED && ep_type == EndpointType::Bulk,
+            }
+        };
+        table[index] = Some(endpoint);
+        Ok(EndpointAddress::from_parts(index, ep_dir))
+    }
+
+    fn enable(&mut self) {
+        configure_endpoints(self.usb.as_ref(), &self.tx, &self.rx);
+        let regs = self.usb.as_ref();
+        let tx = (0..ENDPOINTS)
+            .filter(|&i| self.tx[i].is_some())
+            .fold(1, |mask, i| mask | (1 << i));
+        let rx = (1..ENDPOINTS)
+            .filter(|&i| self.rx[i].is_some())
+            .fold(0, |mask, i| mask | (1 << i));
+        let usb = UsbInterrupts::default()
+            .enable_interrupt(Interrupt::Reset)
+            .enable_interrupt(Interrupt::Suspend)
+            .enable_interrupt(Interrupt::Resume);
+        let power = match self.speed {
+            Speed::Full => Power::default().disable_high_speed(),
+            Speed::High => Power::default().enable_high_speed(),
+        };
+        unsafe {
+            regs.tx_interrupt_enable.write(tx);
+            regs.rx_interrupt_enable.write(rx);
+            regs.usb_interrupt_enable.write(usb);
+            regs.power.write(power.soft_connect());
+        }
+    }
+
+    fn reset(&self) {
+        configure_endpoints(self.usb.as_ref(), &self.tx, &self.rx);
+        self.reset_ep0();
+        unsafe { self.usb.as_ref().function_address.write(0) };
+    }
+
+    fn set_device_address(&self, addr: u8) {
+        unsafe { self.usb.as_ref().function_address.write(addr) };
+    }
+
+    fn write(&self, ep_addr: EndpointAddress, buf: &[u8]) -> usb_device::Result<usize> {
+        let index = ep_addr.index();
+        if index == 0 {
+            return self.write_ep0(buf);
+        }
+        let Some(ep) = self.tx.get(index).copied().flatten() else {
+            return Err(UsbError::InvalidEndpoint);
+        };
+        let regs = self.usb.as_ref();
+        self.select(index);
+        let csr = TxCsr::from_bits(regs.csr.read());
+        if csr.is_tx_packet_ready() {
+            return Err(UsbError::WouldBlock);
+        }
+        if buf.len() > ep.max_packet_size as usize {
+            return Err(UsbError::BufferOverflow);
+        }
+        if !(ep.dma && self.write_fifo_dma(index, buf)?) {
+            write_fifo(regs, index, buf);
+        }
+        let csr = TxCsr::from_bits(regs.csr.read());
+        unsafe {
+            regs.csr
+                .write(csr.clear_underrun().set_tx_packet_ready().bits())
+        };
+        Ok(buf.len())
+    }
+
+    fn read(&self, ep_addr: EndpointAddress, buf: &mut [u8]) -> usb_device::Result<usize> {
+        let index = ep_addr.index();
+        if index == 0 {
+            return self.read_ep0(buf);
+        }
+        let Some(ep) = self.rx.get(index).copied().flatten() else {
+            return Err(UsbError::InvalidEndpoint);
+        };
+        let regs = self.usb.as_ref();
+        self.select(index);
+        let csr = RxCsr::from_bits(regs.rx_csr.read());
+        if !csr.is_rx_packet_ready() {
+            return Err(UsbError::WouldBlock);
+        }
+        let count = regs.count.read() as usize;
+        if count > buf.len() {
+            return Err(UsbError::BufferOverflow);
+        }
+        if !(ep.dma && self.read_fifo_dma(index, &mut buf[..count])?) {
+            read_fifo(regs, index, &mut buf[..count]);
+        }
+        let csr = RxCsr::from_bits(regs.rx_csr.read());
+        unsafe {
+            regs.rx_csr
+                .write(csr.clear_overrun().clear_rx_packet_ready().bits())
+        };
+        Ok(count)
+    }
+
+    fn set_stalled(&self, ep_addr: EndpointAddress, stalled: bool) {
+        let regs = self.usb.as_ref();
+        let index = ep_addr.index();
+        self.select(index);
+        match (index, ep_addr.direction()) {
+            // Endpoint 0 stall ends with current control transfer.
+            (0, _) if stalled => {
+                let csr = Csr0::default().send_stall();
+                let csr = if Csr0::from_bits(regs.csr.read()).is_rx_packet_ready() {
+                    csr.set_serviced_rx_packet_ready()
+                } else {
+                    csr
+                };
+                self.reset_ep0();
+                unsafe { regs.csr.write(csr.bits()) };
+            }
+            (0, _) => {}
+            (_, UsbDirection::In) => {
+                let csr = TxCsr::from_bits(regs.csr.read());
+                let csr = if stalled {
+                    csr.send_stall()
+                } else {
+                    csr.clear_send_stall().clear_data_toggle()
+                };
+                unsafe { regs.csr.write(csr.bits()) };
+            }
+            (_, UsbDirection::Out) => {
+                let csr = RxCsr::from_bits(regs.rx_csr.read());
+                let csr = if stalled {
+                    csr.send_stall()
+                } else {
+                    csr.clear_send_stall().clear_data_toggle()
+                };
+                unsafe { regs.rx_csr.write(csr.bits()) };
+            }
+        }
+    }
+
+    fn is_stalled(&self, ep_addr: EndpointAddress) -> bool {
+        let regs = self.usb.as_ref();
+        let index = ep_addr.index();
+        self.select(index);
+        match (index, ep_addr.direction()) {
+            (0, _) => Csr0::from_bits(regs.csr.read()).is_send_stall(),
+            (_, UsbDirection::In) => TxCsr::from_bits(regs.csr.read()).is_send_stall(),
+            (_, UsbDirection::Out) => RxCsr::from_bits(regs.rx_csr.read()).is_send_stall(),
+        }
+    }
+
+    /// Controller enters suspend mode by itself; nothing to do.
+    fn suspend(&self) {}
+
+    /// Controller leaves suspend mode on bus activity; nothing to do.
+    fn resume(&self) {}
+
+    fn poll(&self) -> PollResult {
+        let regs = self.usb.as_ref();
+        // Interrupt status registers are cleared on read.
+        let usb = regs.usb_interrupt.read();
+        let tx_interrupt = regs.tx_interrupt.read();
+        let _ = regs.rx_interrupt.read();
+        if usb.has_interrupt(Interrupt::Reset) {
+            return PollResult::Reset;
+        }
+        if usb.has_interrupt(Interrupt::Suspend) {
+            return PollResult::Suspend;
+        }
+        if usb.has_interrupt(Interrupt::Resume) {
+            return PollResult::Resume;
+        }
+        let (mut ep_out, mut ep_in_complete, ep_setup) = self.poll_ep0(tx_interrupt);
+        for index in 1..ENDPOINTS {
+            if self.tx[index].is_some() && tx_interrupt & (1 << index) != 0 {
+                self.select(index);
+                let csr = TxCsr::from_bits(regs.csr.read());
+                if csr.is_sent_stall() {
+                    unsafe { regs.csr.write(csr.clear_sent_stall().bits()) };
+                } else {
+                    ep_in_complete |= 1 << index;
+                }
+            }
+            if self.rx[index].is_some() {
+                self.select(index);
+                let csr = RxCsr::from_bits(regs.rx_csr.read());
+                if csr.is_sent_stall() {
+                    unsafe { regs.rx_csr.write(csr.clear_sent_stall().bits()) };
+                }
+                if csr.is_rx_packet_ready() {
+                    ep_out |= 1 << index;
+                }
+            }
+        }
+        if ep_out | ep_in_complete | ep_setup == 0 {
+            return PollResult::None;
+        }
+        PollResult::Data {
+            ep_out,
+            ep_in_complete,
+            ep_setup,
+        }
+    }
+}
+
+/// Direction and length of data stage requested by a setup packet.
+#[inline]
+fn data_stage(setup: &[u8]) -> (UsbDirection, u16) {
+    match setup {
+        [request_type, _, _, _, _, _, l, h, ..] => {
+            let direction = if request_type & 0x80 != 0 {
+                UsbDirection::In
+            } else {
+                UsbDirection::Out
+            };
+            (direction, u16::from_le_bytes([*l, *h]))
+        }
+        _ => (UsbDirection::Out, 0),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::data_stage;
+    use usb_device::UsbDirection;
+
+    #[test]
+    fn function_data_stage() {
+        // GET_DESCRIPTOR, device descriptor, 18 bytes.
+        let get_descriptor = [0x80, 0x06, 0x00, 0x01, 0x00, 0x00, 0x12, 0x00];
+        assert_eq!(data_stage(&get_descriptor), (UsbDirection::In, 18));
+        // SET_ADDRESS, no data stage.
+        let set_address = [0x00, 0x05, 0x07, 0x00, 0x00, 0x00, 0x00, 0x00];
+        assert_eq!(data_stage(&set_address), (UsbDirection::Out, 0));
+        // CDC SET_LINE_CODING, 7 bytes.
+        let set_line_coding = [0x21, 0x20, 0x00, 0x00, 0x00, 0x00, 0x07, 0x00];
+        assert_eq!(data_stage(&set_line_coding), (UsbDirection::Out, 7));
+        assert_eq!(data_stage(&[0x80, 0x06]), (UsbDirection::Out, 0));
+    }
+}
@@ -0,0 +1,136 @@
+use crate::{
+    dmac::{AddressMode, BurstLength, Channel, DataWidth, Descriptor, DmaConfig, Drq, drq},
+    time::{Deadline, TimeoutError},
+};
+use core::sync::atomic::{Ordering, fence};
+
+/// DMA engine moving bulk endpoint packets between memory and FIFOs of
+/// [`UsbBus`](super::UsbBus).
+pub trait EndpointDma {
+    /// If this engine moves packets; otherwise endpoint FIFOs are copied by CPU.
+    const ENABLED: bool = true;
+    /// Copy `len` bytes from `source` to `destination` as configured by `config`, returning
+    /// once the copy finishes.
+    fn copy(
+        &mut self,
+        config: &DmaConfig,
+        source: u32,
+        destination: u32,
+        len: u32,
+    ) -> Result<(), TimeoutError>;
+}
+
+/// No DMA engine, endpoint FIFOs are copied by CPU.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct NoDma;
+
+impl EndpointDma for NoDma {
+    const ENABLED: bool = false;
+    #[inline]
+    fn copy(&mut self, _: &DmaConfig, _: u32, _: u32, _: u32) -> Result<(), TimeoutError> {
+        unreachable!()
+    }
+}
+
+impl<const N: usize> EndpointDma for Channel<'_, N> {
+    #[inline]
+    fn copy(
+        &mut self,
+        config: &DmaConfig,
+        source: u32,
+        destination: u32,
+        len: u32,
+    ) -> Result<(), TimeoutError> {
+        let descriptor = Descriptor::new(config, source, destination, len);
+        // make descriptor and packet data visible to DMAC before it starts
+        fence(Ordering::SeqCst);
+        // note(unsafe): descriptor and buffers outlive the transfer, which is waited below
+        unsafe { self.start(&descriptor) };
+        let deadline = Deadline::start();
+        while self.is_busy() {
+            if let Err(error) = deadline.check() {
+                self.abort()?;
+                return Err(error);
+            }
+        }
+        self.stop();
+        // make data written by DMAC visible to following reads
+        fence(Ordering::SeqCst);
+        Ok(())
+    }
+}
+
+/// DMA request port of endpoint `index`, shared by its transmit and receive directions.
+#[cfg(feature = "d1")]
+#[inline]
+pub(super) const fn endpoint_drq(index: usize) -> Option<Drq> {
+    match index {
+        1 => Some(drq::USB0_EP1),
+        2 => Some(drq::USB0_EP2),
+        3 => Some(drq::USB0_EP3),
+        4 => Some(drq::USB0_EP4),
+        5 => Some(drq::USB0_EP5),
+        _ => None,
+    }
+}
+
+/// DMA request port of endpoint `index`, none on chips without endpoint DMA requests.
+#[cfg(not(feature = "d1"))]
+#[inline]
+pub(super) const fn endpoint_drq(_index: usize) -> Option<Drq> {
+    None
+}
+
+/// Configuration of copying packet words from memory into FIFO of endpoint `drq`.
+#[inline]
+pub(super) fn tx_config(drq: Drq) -> DmaConfig {
+    DmaConfig {
+        source_drq: drq::DRAM,
+        source_address_mode: AddressMode::Linear,
+        source_burst: BurstLength::Single,
+        source_width: DataWidth::Bits32,
+        destination_drq: drq,
+        destination_address_mode: AddressMode::Io,
+        destination_burst: BurstLength::Single,
+        destination_width: DataWidth::Bits32,
+        ..DmaConfig::default()
+    }
+}
+
+/// Configuration of copying packet words from FIFO of endpoint `drq` into memory.
+#[inline]
+pub(super) fn rx_config(drq: Drq) -> DmaConfig {
+    DmaConfig {
+        source_drq: drq,
+        source_address_mode: AddressMode::Io,
+        source_burst: BurstLength::Single,
+        source_width: DataWidth::Bits32,
+        destination_drq: drq::DRAM,
+        destination_address_mode: AddressMode::Linear,
+        destination_burst: BurstLength::Single,
+        destination_width: DataWidth::Bits32,
+        ..DmaConfig::default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{endpoint_drq, rx_config, tx_config};
+    use crate::dmac::{AddressMode, drq};
+
+    #[cfg(feature = "d1")]
+    #[test]
+    fn function_endpoint_drq() {
+        assert_eq!(endpoint_drq(0), None);
+        assert_eq!(endpoint_drq(1), Some(drq::USB0_EP1));
+        assert_eq!(endpoint_drq(5), Some(drq::USB0_EP5));
+        assert_eq!(endpoint_drq(6), None);
+
+        let config = tx_config(drq::USB0_EP2);
+        assert_eq!(config.destination_drq, drq::USB0_EP2);
+        assert_eq!(config.destination_address_mode, AddressMode::Io);
+        let config = rx_config(drq::USB0_EP2);
+        assert_eq!(config.source_drq, drq::USB0_EP2);
+        assert_eq!(config.source_address_mode, AddressMode::Io);
+    }
+}
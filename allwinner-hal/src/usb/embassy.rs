@@ -0,0 +1,676 @@
+//! Async USB device driver for `embassy-usb`.
+//!
+//! [`Driver`] runs USB0 OTG controller in device mode and implements
+//! [`embassy_usb_driver::Driver`], so that `embassy-usb` device stack and its classes share
+//! one executor with other async drivers. Endpoint transfers complete on controller
+//! interrupts; call [`on_interrupt`] from USB0 interrupt handler:
+//!
+//! ```ignore
+//! let driver = Driver::new(&p.usb0, Speed::High, &p.ccu);
+//! let mut builder = embassy_usb::Builder::new(driver, config, ...);
+//! let mut class = CdcAcmClass::new(&mut builder, &mut state, 512);
+//! let mut device = builder.build();
+//! join(device.run(), echo(&mut class)).await;
+//! ```
+//!
+//! Endpoints share FIFO RAM allocation of `UsbBus`, and packets are copied
+//! by CPU.
+
+use super::{
+    Csr0, ENDPOINTS, EP0_FIFO_SIZE, Endpoint, FIFO_RAM_SIZE, Interrupt, Iscr, Power, RegisterBlock,
+    RxCsr, Speed, TxCsr, UsbInterrupts, configure_endpoints, fifo_size, open, read_fifo,
+    write_fifo,
+};
+use crate::{
+    ccu,
+    time::{Duration, with_timeout},
+};
+use atomic_waker::AtomicWaker;
+use core::{
+    future::{pending, poll_fn},
+    sync::atomic::{AtomicBool, AtomicU8, AtomicU16, Ordering},
+    task::Poll,
+};
+use embassy_usb_driver::{
+    self as driver, Direction, EndpointAddress, EndpointAllocError, EndpointError, EndpointInfo,
+    EndpointType, Event, Unsupported,
+};
+
+static BUS_WAKER: AtomicWaker = AtomicWaker::new();
+// Endpoint 0 interrupts are reported as transmit interrupt of endpoint 0.
+static TX_WAKERS: [AtomicWaker; ENDPOINTS] = [const { AtomicWaker::new() }; ENDPOINTS];
+static RX_WAKERS: [AtomicWaker; ENDPOINTS] = [const { AtomicWaker::new() }; ENDPOINTS];
+
+// Bus events latched by interrupt handler, as interrupt status is cleared on read.
+static BUS_EVENTS: AtomicU8 = AtomicU8::new(0);
+const EVENT_RESET: u8 = 1 << 0;
+const EVENT_SUSPEND: u8 = 1 << 1;
+const EVENT_RESUME: u8 = 1 << 2;
+
+// Endpoint 0 interrupt latched for completion of status stage.
+static EP0_INTERRUPT: AtomicBool = AtomicBool::new(false);
+
+// Endpoints enabled by device stack, one bit per endpoint index.
+static TX_ENABLED: AtomicU16 = AtomicU16::new(0);
+static RX_ENABLED: AtomicU16 = AtomicU16::new(0);
+
+/// USB0 interrupt entry point.
+///
+/// Call this function from USB0 interrupt handler when running [`Driver`]. Interrupt status
+/// is cleared on read, thus bus events are latched for [`Bus::poll`](driver::Bus::poll), and
+/// tasks waiting on endpoints are woken to check their own status registers.
+#[inline]
+pub fn on_interrupt(usb: &RegisterBlock) {
+    let status = usb.usb_interrupt.read();
+    let tx = usb.tx_interrupt.read();
+    let rx = usb.rx_interrupt.read();
+    let events = [
+        (Interrupt::Reset, EVENT_RESET),
+        (Interrupt::Suspend, EVENT_SUSPEND),
+        (Interrupt::Resume, EVENT_RESUME),
+    ]
+    .iter()
+    .filter(|(interrupt, _)| status.has_interrupt(*interrupt))
+    .fold(0, |events, (_, event)| events | event);
+    if events != 0 {
+        BUS_EVENTS.fetch_or(events, Ordering::Release);
+        BUS_WAKER.wake();
+    }
+    // Bus reset aborts control transfer in progress.
+    let tx = if events & EVENT_RESET != 0 {
+        tx | 1
+    } else {
+        tx
+    };
+    if tx & 1 != 0 {
+        EP0_INTERRUPT.store(true, Ordering::Release);
+    }
+    for index in 0..ENDPOINTS {
+        if tx & (1 << index) != 0 {
+            TX_WAKERS[index].wake();
+        }
+        if rx & (1 << index) != 0 {
+            RX_WAKERS[index].wake();
+        }
+    }
+}
+
+/// Async USB OTG controller in device mode, before `embassy-usb` starts it.
+pub struct Driver<'d> {
+    regs: &'d RegisterBlock,
+    speed: Speed,
+    tx: [Option<Endpoint>; ENDPOINTS],
+    rx: [Option<Endpoint>; ENDPOINTS],
+    fifo_top: u16,
+}
+
+impl<'d> Driver<'d> {
+    /// Create a USB device driver, disconnected from the bus until `embassy-usb` enables it.
+    ///
+    /// USB0 PHY is routed to OTG controller, with ID and VBUS forced to device mode.
+    #[inline]
+    pub fn new<USB: AsRef<RegisterBlock>>(
+        usb: &'d USB,
+        speed: Speed,
+        ccu: &ccu::RegisterBlock,
+    ) -> Self {
+        let regs = usb.as_ref();
+        open(regs, ccu, Iscr::force_device);
+        unsafe { regs.power.write(Power::default().soft_disconnect()) };
+        BUS_EVENTS.store(0, Ordering::Relaxed);
+        EP0_INTERRUPT.store(false, Ordering::Relaxed);
+        TX_ENABLED.store(0, Ordering::Relaxed);
+        RX_ENABLED.store(0, Ordering::Relaxed);
+        Self {
+            regs,
+            speed,
+            tx: [None; ENDPOINTS],
+            rx: [None; ENDPOINTS],
+            fifo_top: EP0_FIFO_SIZE,
+        }
+    }
+    fn alloc(
+        &mut self,
+        direction: Direction,
+        ep_type: EndpointType,
+        ep_addr: Option<EndpointAddress>,
+        max_packet_size: u16,
+        interval_ms: u8,
+    ) -> Result<EndpointInfo, EndpointAllocError> {
+        let table = match direction {
+            Direction::Out => &mut self.rx,
+            Direction::In => &mut self.tx,
+        };
+        let index = match ep_addr {
+            Some(addr) => addr.index(),
+            None => (1..ENDPOINTS)
+                .find(|&i| table[i].is_none())
+                .ok_or(EndpointAllocError)?,
+        };
+        if index == 0 || index >= ENDPOINTS || table[index].is_some() {
+            return Err(EndpointAllocError);
+        }
+        let fifo_size = fifo_size(max_packet_size).ok_or(EndpointAllocError)?;
+        if FIFO_RAM_SIZE - self.fifo_top < fifo_size {
+            return Err(EndpointAllocError);
+        }
+        table[index] = Some(Endpoint {
+            max_packet_size,
+            fifo_address: self.fifo_top,
+            fifo_size,
+            iso: ep_type == EndpointType::Isochronous,
+            dma: false,
+        });
+        self.fifo_top += fifo_size;
+        Ok(EndpointInfo {
+            addr: EndpointAddress::from_parts(index, direction),
+            ep_type,
+            max_packet_size,
+            interval_ms,
+        })
+    }
+}
+
+impl<'d> driver::Driver<'d> for Driver<'d> {
+    type EndpointOut = EndpointOut<'d>;
+    type EndpointIn = EndpointIn<'d>;
+    type ControlPipe = ControlPipe<'d>;
+    type Bus = Bus<'d>;
+
+    fn alloc_endpoint_out(
+        &mut self,
+        ep_type: EndpointType,
+        ep_addr: Option<EndpointAddress>,
+        max_packet_size: u16,
+        interval_ms: u8,
+    ) -> Result<EndpointOut<'d>, EndpointAllocError> {
+        let info = self.alloc(
+            Direction::Out,
+            ep_type,
+            ep_addr,
+            max_packet_size,
+            interval_ms,
+        )?;
+        Ok(EndpointOut {
+            regs: self.regs,
+            info,
+        })
+    }
+
+    fn alloc_endpoint_in(
+        &mut self,
+        ep_type: EndpointType,
+        ep_addr: Option<EndpointAddress>,
+        max_packet_size: u16,
+        interval_ms: u8,
+    ) -> Result<EndpointIn<'d>, EndpointAllocError> {
+        let info = self.alloc(
+            Direction::In,
+            ep_type,
+            ep_addr,
+            max_packet_size,
+            interval_ms,
+        )?;
+        Ok(EndpointIn {
+            regs: self.regs,
+            info,
+        })
+    }
+
+    fn start(self, control_max_packet_size: u16) -> (Bus<'d>, ControlPipe<'d>) {
+        let bus = Bus {
+            regs: self.regs,
+            speed: self.speed,
+            tx: self.tx,
+            rx: self.rx,
+            powered: false,
+        };
+        let control = ControlPipe {
+            regs: self.regs,
+            max_packet_size: control_max_packet_size.min(EP0_FIFO_SIZE),
+            ack_pending: false,
+        };
+        (bus, control)
+    }
+}
+
+/// Bus of started [`Driver`], reporting bus events and enabling endpoints.
+pub struct Bus<'d> {
+    regs: &'d RegisterBlock,
+    speed: Speed,
+    tx: [Option<Endpoint>; ENDPOINTS],
+    rx: [Option<Endpoint>; ENDPOINTS],
+    powered: bool,
+}
+
+impl driver::Bus for Bus<'_> {
+    async fn enable(&mut self) {
+        let regs = self.regs;
+        configure_endpoints(regs, &self.tx, &self.rx);
+        let tx = (1..ENDPOINTS)
+            .filter(|&i| self.tx[i].is_some())
+            .fold(1, |mask, i| mask | (1 << i));
+        let rx = (1..ENDPOINTS)
+            .filter(|&i| self.rx[i].is_some())
+            .fold(0, |mask, i| mask | (1 << i));
+        let usb = UsbInterrupts::default()
+            .enable_interrupt(Interrupt::Reset)
+            .enable_interrupt(Interrupt::Suspend)
+            .enable_interrupt(Interrupt::Resume);
+        let power = match self.speed {
+            Speed::Full => Power::default().disable_high_speed(),
+            Speed::High => Power::default().enable_high_speed(),
+        };
+        unsafe {
+            regs.tx_interrupt_enable.write(tx);
+            regs.rx_interrupt_enable.write(rx);
+            regs.usb_interrupt_enable.write(usb);
+            regs.power.write(power.soft_connect());
+        }
+    }
+
+    async fn disable(&mut self) {
+        let regs = self.regs;
+        unsafe {
+            regs.power.modify(|val| val.soft_disconnect());
+            regs.tx_interrupt_enable.write(0);
+            regs.rx_interrupt_enable.write(0);
+            regs.usb_interrupt_enable.write(UsbInterrupts::default());
+        }
+    }
+
+    async fn poll(&mut self) -> Event {
+        // VBUS is forced valid, thus power is reported present once on start.
+        if !self.powered {
+            self.powered = true;
+            return Event::PowerDetected;
+        }
+        poll_fn(|cx| {
+            BUS_WAKER.register(cx.waker());
+            let events = BUS_EVENTS.load(Ordering::Acquire);
+            // Report latched events one at a time, reset first.
+            let event = events & events.wrapping_neg();
+            BUS_EVENTS.fetch_and(!event, Ordering::AcqRel);
+            match event {
+                EVENT_RESET => {
+                    TX_ENABLED.store(0, Ordering::Release);
+                    RX_ENABLED.store(0, Ordering::Release);
+                    configure_endpoints(self.regs, &self.tx, &self.rx);
+                    unsafe { self.regs.function_address.write(0) };
+                    for index in 1..ENDPOINTS {
+                        TX_WAKERS[index].wake();
+                        RX_WAKERS[index].wake();
+                    }
+                    Poll::Ready(Event::Reset)
+                }
+                EVENT_SUSPEND => Poll::Ready(Event::Suspend),
+                EVENT_RESUME => Poll::Ready(Event::Resume),
+                _ => Poll::Pending,
+            }
+        })
+        .await
+    }
+
+    fn endpoint_set_enabled(&mut self, ep_addr: EndpointAddress, enabled: bool) {
+        let index = ep_addr.index();
+        let (mask, wakers) = match ep_addr.direction() {
+            Direction::In => (&TX_ENABLED, &TX_WAKERS),
+            Direction::Out => (&RX_ENABLED, &RX_WAKERS),
+        };
+        if index >= ENDPOINTS {
+            return;
+        }
+        if !enabled {
+            mask.fetch_and(!(1 << index), Ordering::AcqRel);
+            wakers[index].wake();
+            return;
+        }
+        // Endpoints start with DATA0 on every configuration.
+        if index != 0 {
+            let regs = self.regs;
+            unsafe { regs.index.write(index as u8) };
+            match ep_addr.direction() {
+                Direction::In => {
+                    let csr = TxCsr::from_bits(regs.csr.read()).clear_data_toggle();
+                    unsafe { regs.csr.write(csr.bits()) };
+                }
+                Direction::Out => {
+                    let csr = RxCsr::from_bits(regs.rx_csr.read()).clear_data_toggle();
+                    unsafe { regs.rx_csr.write(csr.bits()) };
+                }
+            }
+        }
+        mask.fetch_or(1 << index, Ordering::AcqRel);
+        wakers[index].wake();
+    }
+
+    fn endpoint_set_stalled(&mut self, ep_addr: EndpointAddress, stalled: bool) {
+        let regs = self.regs;
+        let index = ep_addr.index();
+        // Endpoint 0 is stalled by rejecting control transfers on control pipe.
+        if index == 0 || index >= ENDPOINTS {
+            return;
+        }
+        unsafe { regs.index.write(index as u8) };
+        match ep_addr.direction() {
+            Direction::In => {
+                let csr = TxCsr::from_bits(regs.csr.read());
+                let csr = if stalled {
+                    csr.send_stall()
+                } else {
+                    csr.clear_send_stall().clear_data_toggle()
+                };
+                unsafe { regs.csr.write(csr.bits()) };
+            }
+            Direction::Out => {
+                let csr = RxCsr::from_bits(regs.rx_csr.read());
+                let csr = if stalled {
+                    csr.send_stall()
+                } else {
+                    csr.clear_send_stall().clear_data_toggle()
+                };
+                unsafe { regs.rx_csr.write(csr.bits()) };
+            }
+        }
+        TX_WAKERS[index].wake();
+        RX_WAKERS[index].wake();
+    }
+
+    fn endpoint_is_stalled(&mut self, ep_addr: EndpointAddress) -> bool {
+        let regs = self.regs;
+        let index = ep_addr.index();
+        unsafe { regs.index.write(index as u8) };
+        match (index, ep_addr.direction()) {
+            (0, _) => Csr0::from_bits(regs.csr.read()).is_send_stall(),
+            (_, Direction::In) => TxCsr::from_bits(regs.csr.read()).is_send_stall(),
+            (_, Direction::Out) => RxCsr::from_bits(regs.rx_csr.read()).is_send_stall(),
+        }
+    }
+
+    async fn remote_wakeup(&mut self) -> Result<(), Unsupported> {
+        let regs = self.regs;
+        unsafe { regs.power.modify(|val| val.start_resume()) };
+        // Resume signaling is driven for 1 to 15 milliseconds.
+        let _ = with_timeout(Duration::from_millis(10), pending::<()>()).await;
+        unsafe { regs.power.modify(|val| val.stop_resume()) };
+        Ok(())
+    }
+}
+
+/// Wait until endpoint `index` is enabled in `enabled` mask.
+#[inline]
+async fn wait_enabled(enabled: &AtomicU16, wakers: &[AtomicWaker; ENDPOINTS], index: usize) {
+    poll_fn(|cx| {
+        wakers[index].register(cx.waker());
+        if enabled.load(Ordering::Acquire) & (1 << index) != 0 {
+            Poll::Ready(())
+        } else {
+            Poll::Pending
+        }
+    })
+    .await
+}
+
+/// OUT endpoint of [`Driver`].
+pub struct EndpointOut<'d> {
+    regs: &'d RegisterBlock,
+    info: EndpointInfo,
+}
+
+impl driver::Endpoint for EndpointOut<'_> {
+    #[inline]
+    fn info(&self) -> &EndpointInfo {
+        &self.info
+    }
+
+    async fn wait_enabled(&mut self) {
+        wait_enabled(&RX_ENABLED, &RX_WAKERS, self.info.addr.index()).await
+    }
+}
+
+impl driver::EndpointOut for EndpointOut<'_> {
+    async fn read(&mut self, buf: &mut [u8]) -> Result<usize, EndpointError> {
+        let regs = self.regs;
+        let index = self.info.addr.index();
+        poll_fn(|cx| {
+            RX_WAKERS[index].register(cx.waker());
+            if RX_ENABLED.load(Ordering::Acquire) & (1 << index) == 0 {
+                return Poll::Ready(Err(EndpointError::Disabled));
+            }
+            unsafe { regs.index.write(index as u8) };
+            let csr = RxCsr::from_bits(regs.rx_csr.read());
+            if csr.is_sent_stall() {
+                unsafe { regs.rx_csr.write(csr.clear_sent_stall().bits()) };
+            }
+            if !csr.is_rx_packet_ready() {
+                return Poll::Pending;
+            }
+            let count = regs.count.read() as usize;
+            let csr = RxCsr::from_bits(regs.rx_csr.read()).clear_overrun();
+            if count > buf.len() {
+                // Packet is dropped, as the device stack can't take it anyway.
+                unsafe { regs.rx_csr.write(csr.flush_fifo().bits()) };
+                return Poll::Ready(Err(EndpointError::BufferOverflow));
+            }
+            read_fifo(regs, index, &mut buf[..count]);
+            unsafe { regs.rx_csr.write(csr.clear_rx_packet_ready().bits()) };
+            Poll::Ready(Ok(count))
+        })
+        .await
+    }
+}
+
+/// IN endpoint of [`Driver`].
+pub struct EndpointIn<'d> {
+    regs: &'d RegisterBlock,
+    info: EndpointInfo,
+}
+
+impl driver::Endpoint for EndpointIn<'_> {
+    #[inline]
+    fn info(&self) -> &EndpointInfo {
+        &self.info
+    }
+
+    async fn wait_enabled(&mut self) {
+        wait_enabled(&TX_ENABLED, &TX_WAKERS, self.info.addr.index()).await
+    }
+}
+
+impl driver::EndpointIn for EndpointIn<'_> {
+    async fn write(&mut self, buf: &[u8]) -> Result<(), EndpointError> {
+        if buf.len() > self.info.max_packet_size as usize {
+            return Err(EndpointError::BufferOverflow);
+        }
+        let regs = self.regs;
+        let index = self.info.addr.index();
+        poll_fn(|cx| {
+            TX_WAKERS[index].register(cx.waker());
+            if TX_ENABLED.load(Ordering::Acquire) & (1 << index) == 0 {
+                return Poll::Ready(Err(EndpointError::Disabled));
+            }
+            unsafe { regs.index.write(index as u8) };
+            let csr = TxCsr::from_bits(regs.csr.read());
+            if csr.is_sent_stall() {
+                unsafe { regs.csr.write(csr.clear_sent_stall().bits()) };
+            }
+            // Previous packet is still waiting in FIFO.
+            if csr.is_tx_packet_ready() {
+                return Poll::Pending;
+            }
+            write_fifo(regs, index, buf);
+            let csr = TxCsr::from_bits(regs.csr.read());
+            unsafe {
+                regs.csr
+                    .write(csr.clear_underrun().set_tx_packet_ready().bits())
+            };
+            Poll::Ready(Ok(()))
+        })
+        .await
+    }
+}
+
+/// Endpoint 0 of started [`Driver`], running control transfers.
+pub struct ControlPipe<'d> {
+    regs: &'d RegisterBlock,
+    max_packet_size: u16,
+    // Received setup or last data packet not yet acknowledged by serviced rx packet ready,
+    // so that data end or stall is decided by the device stack.
+    ack_pending: bool,
+}
+
+impl ControlPipe<'_> {
+    /// Acknowledge the pending received packet, if any.
+    #[inline]
+    fn acknowledge(&mut self) {
+        if core::mem::take(&mut self.ack_pending) {
+            let csr = Csr0::default().set_serviced_rx_packet_ready();
+            unsafe {
+                self.regs.index.write(0);
+                self.regs.csr.write(csr.bits());
+            }
+        }
+    }
+    /// Write endpoint 0 control status, acknowledging the pending received packet if any.
+    #[inline]
+    fn write_csr(&mut self, csr: Csr0) {
+        let csr = if core::mem::take(&mut self.ack_pending) {
+            csr.set_serviced_rx_packet_ready()
+        } else {
+            csr
+        };
+        unsafe {
+            self.regs.index.write(0);
+            self.regs.csr.write(csr.bits());
+        }
+    }
+    /// Wait until endpoint 0 status satisfies `f`, or fail if host aborts control transfer.
+    #[inline]
+    async fn wait(&mut self, f: impl Fn(Csr0) -> bool) -> Result<(), EndpointError> {
+        let regs = self.regs;
+        poll_fn(|cx| {
+            TX_WAKERS[0].register(cx.waker());
+            unsafe { regs.index.write(0) };
+            let csr = Csr0::from_bits(regs.csr.read());
+            if csr.is_setup_end() {
+                let csr = Csr0::default().set_serviced_setup_end();
+                unsafe { regs.csr.write(csr.bits()) };
+                self.ack_pending = false;
+                return Poll::Ready(Err(EndpointError::Disabled));
+            }
+            if f(csr) {
+                Poll::Ready(Ok(()))
+            } else {
+                Poll::Pending
+            }
+        })
+        .await
+    }
+}
+
+impl driver::ControlPipe for ControlPipe<'_> {
+    #[inline]
+    fn max_packet_size(&self) -> usize {
+        self.max_packet_size as usize
+    }
+
+    async fn setup(&mut self) -> [u8; 8] {
+        let regs = self.regs;
+        poll_fn(|cx| {
+            TX_WAKERS[0].register(cx.waker());
+            unsafe { regs.index.write(0) };
+            let csr = Csr0::from_bits(regs.csr.read());
+            if csr.is_sent_stall() {
+                // Writing zero clears sent stall; other bits take no effect.
+                unsafe { regs.csr.write(Csr0::default().bits()) };
+            }
+            if csr.is_setup_end() {
+                let csr = Csr0::default().set_serviced_setup_end();
+                unsafe { regs.csr.write(csr.bits()) };
+                self.ack_pending = false;
+            }
+            if !csr.is_rx_packet_ready() {
+                return Poll::Pending;
+            }
+            if regs.count.read() != 8 {
+                // Not a setup packet, e.g. data left over from an aborted transfer.
+                let csr = Csr0::default().set_serviced_rx_packet_ready();
+                unsafe { regs.csr.write(csr.bits()) };
+                return Poll::Pending;
+            }
+            let mut setup = [0; 8];
+            read_fifo(regs, 0, &mut setup);
+            self.ack_pending = true;
+            Poll::Ready(setup)
+        })
+        .await
+    }
+
+    async fn data_out(
+        &mut self,
+        buf: &mut [u8],
+        first: bool,
+        last: bool,
+    ) -> Result<usize, EndpointError> {
+        if first {
+            // Acknowledge setup packet to start data stage.
+            self.acknowledge();
+        }
+        self.wait(|csr| csr.is_rx_packet_ready()).await?;
+        let regs = self.regs;
+        unsafe { regs.index.write(0) };
+        let count = regs.count.read() as usize;
+        if count > buf.len() {
+            self.ack_pending = true;
+            return Err(EndpointError::BufferOverflow);
+        }
+        read_fifo(regs, 0, &mut buf[..count]);
+        self.ack_pending = true;
+        if !last {
+            self.acknowledge();
+        }
+        // Last packet is acknowledged with data end once device stack accepts the request.
+        Ok(count)
+    }
+
+    async fn data_in(&mut self, data: &[u8], first: bool, last: bool) -> Result<(), EndpointError> {
+        if data.len() > self.max_packet_size as usize {
+            return Err(EndpointError::BufferOverflow);
+        }
+        if first {
+            self.acknowledge();
+        }
+        self.wait(|csr| !csr.is_tx_packet_ready()).await?;
+        write_fifo(self.regs, 0, data);
+        let csr = Csr0::default().set_tx_packet_ready();
+        // Hardware answers status stage once data end is set.
+        let csr = if last { csr.set_data_end() } else { csr };
+        self.write_csr(csr);
+        Ok(())
+    }
+
+    async fn accept(&mut self) {
+        EP0_INTERRUPT.store(false, Ordering::Release);
+        self.write_csr(Csr0::default().set_data_end());
+        // Status stage completes with an endpoint 0 interrupt.
+        poll_fn(|cx| {
+            TX_WAKERS[0].register(cx.waker());
+            if EP0_INTERRUPT.swap(false, Ordering::AcqRel) {
+                Poll::Ready(())
+            } else {
+                Poll::Pending
+            }
+        })
+        .await
+    }
+
+    async fn reject(&mut self) {
+        self.write_csr(Csr0::default().send_stall());
+    }
+
+    async fn accept_set_address(&mut self, addr: u8) {
+        // New address takes effect after status stage of SET_ADDRESS request.
+        self.accept().await;
+        unsafe { self.regs.function_address.write(addr) };
+    }
+}
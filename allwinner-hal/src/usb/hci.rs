@@ -0,0 +1,376 @@
+//! EHCI and OHCI host controllers of USB1.
+//!
+//! USB1 port of D1 and T113 is served by a standard EHCI controller for high-speed devices
+//! and its companion OHCI controller for full and low-speed devices. [`Hci`] hands the port
+//! over between them on reset, and implements the same [`Host`] pipe API as [`UsbHost`] of
+//! USB0, so [`enumerate`] and class drivers run on either port:
+//!
+//! ```ignore
+//! static mut MEMORY: HciMemory = HciMemory::new();
+//! let mut host = Hci::new(p.usb1, unsafe { &mut *addr_of_mut!(MEMORY) }, &p.ccu)?;
+//! while !host.is_connected() {}
+//! let mut config = [0; 256];
+//! let (device, len) = enumerate(&mut host, 1, &mut config)?;
+//! ```
+//!
+//! Transfers are polled without interrupts. Buffers passed to transfers are accessed by DMA,
+//! and should reside in DRAM below 4 GiB.
+//!
+//! [`UsbHost`]: super::UsbHost
+//! [`enumerate`]: super::host::enumerate
+
+mod ehci;
+mod ohci;
+mod register;
+pub use register::*;
+
+use super::host::{DeviceSpeed, Direction, Host, HostError, Pipe, SetupPacket, TransferType, wait};
+use crate::{
+    ccu::{self, ClockGate},
+    time::{Deadline, Duration, Instant},
+};
+
+/// Transfer direction of one transfer descriptor.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+enum StagePid {
+    Setup,
+    Out,
+    In,
+}
+
+/// One transfer descriptor of a transfer, the setup, data or status stage.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+struct Stage {
+    pid: StagePid,
+    toggle: bool,
+    address: usize,
+    len: usize,
+}
+
+impl Stage {
+    const SETUP: Stage = Stage {
+        pid: StagePid::Setup,
+        toggle: false,
+        address: 0,
+        len: 8,
+    };
+
+    #[inline]
+    const fn status(pid: StagePid) -> Self {
+        Self {
+            pid,
+            toggle: true,
+            address: 0,
+            len: 0,
+        }
+    }
+}
+
+/// Memory of USB1 host controller schedules, accessed by DMA.
+///
+/// It should reside in DRAM below 4 GiB, usually as a `static`.
+pub struct HciMemory {
+    setup: Setup,
+    ehci: ehci::Memory,
+    ohci: ohci::Memory,
+}
+
+#[repr(C, align(32))]
+struct Setup([u8; 8]);
+
+impl HciMemory {
+    /// Create empty host controller memory.
+    #[inline]
+    pub const fn new() -> Self {
+        Self {
+            setup: Setup([0; 8]),
+            ehci: ehci::Memory::new(),
+            ohci: ohci::Memory::new(),
+        }
+    }
+}
+
+impl Default for HciMemory {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Managed USB1 host controller.
+pub struct Hci<USB> {
+    usb: USB,
+    memory: &'static mut HciMemory,
+    // Port is released to OHCI for a full or low-speed device.
+    companion: bool,
+}
+
+impl<USB: AsRef<RegisterBlock>> Hci<USB> {
+    /// Create a USB1 host instance, powering up PHY and both controllers.
+    ///
+    /// VBUS of USB1 port is usually always on, or switched by a board GPIO pin.
+    #[inline]
+    pub fn new(
+        usb: USB,
+        memory: &'static mut HciMemory,
+        ccu: &ccu::RegisterBlock,
+    ) -> Result<Self, HostError> {
+        // note(unsafe): async read and write using ccu registers
+        unsafe {
+            ccu::USBEHCI::<1>::reset(ccu);
+            ccu::USBOHCI::<1>::reset(ccu);
+            ccu.usb_clock[1].modify(|val| val.unmask_clock().deassert_phy_reset());
+        }
+        let regs = usb.as_ref();
+        unsafe {
+            regs.interface
+                .modify(|val| val.enable_ahb_burst().enable_ulpi_bypass());
+            regs.phy_control.modify(|val| val.power_up());
+        }
+        ohci::open(&regs.ohci, &mut memory.ohci)?;
+        ehci::open(&regs.ehci, &mut memory.ehci)?;
+        Ok(Self {
+            usb,
+            memory,
+            companion: false,
+        })
+    }
+    /// Close both controllers, power down PHY and release peripheral and memory.
+    #[inline]
+    pub fn free(self, ccu: &ccu::RegisterBlock) -> (USB, &'static mut HciMemory) {
+        let regs = self.usb.as_ref();
+        ehci::close(&regs.ehci);
+        ohci::close(&regs.ohci);
+        unsafe {
+            regs.phy_control.modify(|val| val.power_down());
+            ccu.usb_clock[1].modify(|val| val.mask_clock().assert_phy_reset());
+            ccu::USBEHCI::<1>::free(ccu);
+            ccu::USBOHCI::<1>::free(ccu);
+        }
+        (self.usb, self.memory)
+    }
+    /// Check if attached device is served by companion OHCI controller.
+    #[inline]
+    pub fn is_companion(&self) -> bool {
+        self.companion
+    }
+    /// Claim port back to EHCI, so that it detects speed of the next device.
+    fn claim_port(&mut self) {
+        let port = &self.usb.as_ref().ehci.port_status;
+        if port.read().is_companion_owned() {
+            unsafe { port.modify(|val| val.keep_changes().claim_from_companion()) };
+            // Port routing settles before connect status is valid on EHCI.
+            wait(Duration::from_millis(5));
+        }
+        self.companion = false;
+    }
+    /// Release port to OHCI and reset it there.
+    fn release_port(&mut self) -> Result<DeviceSpeed, HostError> {
+        let regs = self.usb.as_ref();
+        unsafe {
+            regs.ehci
+                .port_status
+                .modify(|val| val.keep_changes().release_to_companion());
+        }
+        self.companion = true;
+        // OHCI root hub reports the device after port routing and connect debounce.
+        wait(Duration::from_millis(20));
+        ohci::reset_port(&regs.ohci)
+    }
+    /// Run `stages` on `pipe`, cancelled at `deadline` with `expired` error.
+    fn run(
+        &mut self,
+        pipe: &mut Pipe,
+        stages: &[Stage],
+        deadline: Deadline,
+        expired: HostError,
+    ) -> Result<usize, HostError> {
+        if !self.is_connected() {
+            return Err(HostError::NotConnected);
+        }
+        let regs = self.usb.as_ref();
+        let memory = &mut *self.memory;
+        let setup = &memory.setup.0;
+        if self.companion {
+            ohci::run(
+                &regs.ohci,
+                &mut memory.ohci,
+                pipe,
+                setup,
+                stages,
+                deadline,
+                expired,
+            )
+        } else {
+            ehci::run(
+                &regs.ehci,
+                &mut memory.ehci,
+                pipe,
+                setup,
+                stages,
+                deadline,
+                expired,
+            )
+        }
+    }
+    /// Run a control transfer with data stage of `data_pid`.
+    fn control(
+        &mut self,
+        pipe: &mut Pipe,
+        setup: &SetupPacket,
+        data_pid: StagePid,
+        address: usize,
+        len: usize,
+    ) -> Result<usize, HostError> {
+        if len > self.max_stage_len(address) {
+            return Err(HostError::Unsupported);
+        }
+        self.memory.setup.0 = setup.to_bytes();
+        let status_pid = match data_pid {
+            StagePid::In => StagePid::Out,
+            _ => StagePid::In,
+        };
+        let data = Stage {
+            pid: data_pid,
+            toggle: true,
+            address,
+            len,
+        };
+        let with_data = [Stage::SETUP, data, Stage::status(status_pid)];
+        let without_data = [Stage::SETUP, Stage::status(status_pid)];
+        let stages: &[Stage] = if len == 0 { &without_data } else { &with_data };
+        self.run(pipe, stages, Deadline::start(), HostError::Timeout)
+    }
+    /// Run a bulk or interrupt transfer of `len` bytes at `address`.
+    fn transfer(
+        &mut self,
+        pipe: &mut Pipe,
+        pid: StagePid,
+        address: usize,
+        len: usize,
+    ) -> Result<usize, HostError> {
+        if !matches!(
+            pipe.transfer_type,
+            TransferType::Bulk | TransferType::Interrupt
+        ) || pipe.max_packet_size == 0
+        {
+            return Err(HostError::Unsupported);
+        }
+        let mut done = 0;
+        loop {
+            let remaining = len - done;
+            let max = self.max_stage_len(address + done);
+            // Descriptors before the last one end on packet boundaries.
+            let chunk = if remaining > max {
+                max - max % pipe.max_packet_size as usize
+            } else {
+                remaining
+            };
+            let stage = Stage {
+                pid,
+                toggle: pipe.data_toggle,
+                address: address + done,
+                len: chunk,
+            };
+            // Interrupt pipes are polled on asynchronous or bulk list for about one interval.
+            let (deadline, expired) = match pipe.transfer_type {
+                TransferType::Interrupt => (
+                    Deadline::at(Instant::now() + pipe.polling_interval()),
+                    HostError::Nak,
+                ),
+                _ => (Deadline::start(), HostError::Timeout),
+            };
+            let count = match self.run(pipe, &[stage], deadline, expired) {
+                Ok(count) => count,
+                Err(HostError::Nak) if done > 0 => return Ok(done),
+                Err(error) => return Err(error),
+            };
+            done += count;
+            if done == len || count < chunk {
+                return Ok(done);
+            }
+        }
+    }
+    #[inline]
+    fn max_stage_len(&self, address: usize) -> usize {
+        if self.companion {
+            ohci::max_stage_len(address)
+        } else {
+            ehci::max_stage_len(address)
+        }
+    }
+}
+
+impl<USB: AsRef<RegisterBlock>> Host for Hci<USB> {
+    #[inline]
+    fn is_connected(&mut self) -> bool {
+        let regs = self.usb.as_ref();
+        let port = regs.ehci.port_status.read();
+        if port.is_companion_owned() {
+            regs.ohci.rh_port_status.read().is_connected()
+        } else {
+            port.is_connected()
+        }
+    }
+    fn reset_port(&mut self) -> Result<DeviceSpeed, HostError> {
+        self.claim_port();
+        let port = &self.usb.as_ref().ehci.port_status;
+        if !port.read().is_connected() {
+            return Err(HostError::NotConnected);
+        }
+        // Low-speed devices idle in K-state, and never take part in high-speed handshake.
+        if port.read().line_state() == LineState::KState {
+            return self.release_port();
+        }
+        unsafe { port.modify(|val| val.clear_changes().start_reset()) };
+        wait(Duration::from_millis(50));
+        unsafe { port.modify(|val| val.keep_changes().stop_reset()) };
+        let deadline = Deadline::start();
+        while port.read().is_resetting() {
+            deadline.check().map_err(|_| HostError::Timeout)?;
+        }
+        // Port is enabled after reset only if the device completed high-speed handshake.
+        if !port.read().is_enabled() {
+            return self.release_port();
+        }
+        // Reset recovery time before the first transfer.
+        wait(Duration::from_millis(10));
+        Ok(DeviceSpeed::High)
+    }
+    fn control_in(
+        &mut self,
+        pipe: &mut Pipe,
+        setup: &SetupPacket,
+        data: &mut [u8],
+    ) -> Result<usize, HostError> {
+        let len = data.len().min(setup.length as usize);
+        self.control(pipe, setup, StagePid::In, data.as_mut_ptr() as usize, len)
+    }
+    fn control_out(
+        &mut self,
+        pipe: &mut Pipe,
+        setup: &SetupPacket,
+        data: &[u8],
+    ) -> Result<(), HostError> {
+        self.control(
+            pipe,
+            setup,
+            StagePid::Out,
+            data.as_ptr() as usize,
+            data.len(),
+        )
+        .map(|_| ())
+    }
+    fn transfer_in(&mut self, pipe: &mut Pipe, data: &mut [u8]) -> Result<usize, HostError> {
+        if pipe.direction != Direction::In {
+            return Err(HostError::Unsupported);
+        }
+        self.transfer(pipe, StagePid::In, data.as_mut_ptr() as usize, data.len())
+    }
+    fn transfer_out(&mut self, pipe: &mut Pipe, data: &[u8]) -> Result<usize, HostError> {
+        if pipe.direction != Direction::Out {
+            return Err(HostError::Unsupported);
+        }
+        self.transfer(pipe, StagePid::Out, data.as_ptr() as usize, data.len())
+    }
+}
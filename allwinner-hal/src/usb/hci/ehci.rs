@@ -0,0 +1,255 @@
+use super::{EhciRegisterBlock, PortStatus, Stage, StagePid, UsbCommand, UsbStatus};
+use crate::{
+    time::Deadline,
+    usb::host::{HostError, Pipe},
+};
+use core::{
+    ptr::{addr_of, addr_of_mut},
+    sync::atomic::{Ordering, fence},
+};
+
+const TERMINATE: u32 = 1 << 0;
+const TYPE_QH: u32 = 1 << 1;
+
+// Queue element transfer descriptor token fields.
+const TOKEN_TOGGLE: u32 = 1 << 31;
+const TOKEN_BYTES: u32 = 0x7FFF << 16;
+const TOKEN_ERROR_COUNTER: u32 = 0x3 << 10;
+const TOKEN_ACTIVE: u32 = 1 << 7;
+const TOKEN_HALTED: u32 = 1 << 6;
+const TOKEN_BUFFER_ERROR: u32 = 1 << 5;
+const TOKEN_BABBLE: u32 = 1 << 4;
+const TOKEN_TRANSACTION_ERROR: u32 = 1 << 3;
+
+// Queue head endpoint characteristics fields.
+const HEAD_OF_LIST: u32 = 1 << 15;
+const TOGGLE_FROM_QTD: u32 = 1 << 14;
+const SPEED_HIGH: u32 = 2 << 12;
+const HIGH_BANDWIDTH_ONE: u32 = 1 << 30;
+
+const PAGE_SIZE: usize = 4096;
+
+/// Maximum bytes one transfer descriptor carries from `address`, using five buffer pages.
+#[inline]
+pub(super) const fn max_stage_len(address: usize) -> usize {
+    5 * PAGE_SIZE - (address % PAGE_SIZE)
+}
+
+/// Queue head, the only entry of the asynchronous schedule.
+#[repr(C, align(32))]
+pub(super) struct QueueHead {
+    link: u32,
+    characteristics: u32,
+    capabilities: u32,
+    current: u32,
+    next: u32,
+    alternate_next: u32,
+    token: u32,
+    buffer: [u32; 5],
+}
+
+/// Queue element transfer descriptor.
+#[repr(C, align(32))]
+pub(super) struct TransferDescriptor {
+    next: u32,
+    alternate_next: u32,
+    token: u32,
+    buffer: [u32; 5],
+}
+
+/// EHCI schedule data structures.
+pub(super) struct Memory {
+    head: QueueHead,
+    descriptors: [TransferDescriptor; 3],
+}
+
+impl Memory {
+    pub(super) const fn new() -> Self {
+        const DESCRIPTOR: TransferDescriptor = TransferDescriptor {
+            next: TERMINATE,
+            alternate_next: TERMINATE,
+            token: 0,
+            buffer: [0; 5],
+        };
+        Self {
+            head: QueueHead {
+                link: TERMINATE,
+                characteristics: 0,
+                capabilities: 0,
+                current: 0,
+                next: TERMINATE,
+                alternate_next: TERMINATE,
+                token: 0,
+                buffer: [0; 5],
+            },
+            descriptors: [DESCRIPTOR; 3],
+        }
+    }
+}
+
+/// Reset EHCI controller, route the port to it and start it with asynchronous schedule off.
+pub(super) fn open(regs: &EhciRegisterBlock, memory: &mut Memory) -> Result<(), HostError> {
+    let deadline = Deadline::start();
+    unsafe { regs.usb_command.modify(|val| val.stop()) };
+    while !regs.usb_status.read().is_halted() {
+        deadline.check().map_err(|_| HostError::Timeout)?;
+    }
+    unsafe { regs.usb_command.write(UsbCommand::default().reset()) };
+    while regs.usb_command.read().is_resetting() {
+        deadline.check().map_err(|_| HostError::Timeout)?;
+    }
+    let head = addr_of_mut!(memory.head);
+    unsafe {
+        regs.usb_interrupt_enable.write(0);
+        regs.usb_status
+            .write(UsbStatus::default().clear_interrupts());
+        regs.async_list_address.write(head as usize as u32);
+        regs.usb_command.write(UsbCommand::default().run());
+        // Route the port to EHCI, companion OHCI takes it only when released.
+        regs.config_flag.write(1);
+        regs.port_status
+            .write(PortStatus::default().claim_from_companion().power_on());
+    }
+    Ok(())
+}
+
+/// Stop EHCI controller.
+pub(super) fn close(regs: &EhciRegisterBlock) {
+    unsafe {
+        regs.config_flag.write(0);
+        regs.usb_command.write(UsbCommand::default().stop());
+    }
+}
+
+/// Run transfer `stages` on high-speed `pipe`, returning bytes transferred after setup stage.
+///
+/// Stages still pending at `deadline` are cancelled with `expired` error.
+pub(super) fn run(
+    regs: &EhciRegisterBlock,
+    memory: &mut Memory,
+    pipe: &mut Pipe,
+    setup: &[u8; 8],
+    stages: &[Stage],
+    deadline: Deadline,
+    expired: HostError,
+) -> Result<usize, HostError> {
+    let head = addr_of_mut!(memory.head);
+    for (index, stage) in stages.iter().enumerate() {
+        let next = match memory.descriptors.get(index + 1) {
+            Some(next) if index + 1 < stages.len() => addr_of!(*next) as usize as u32,
+            _ => TERMINATE,
+        };
+        let (pid, address) = match stage.pid {
+            StagePid::Setup => (2, setup.as_ptr() as usize),
+            StagePid::Out => (0, stage.address),
+            StagePid::In => (1, stage.address),
+        };
+        let mut buffer = [0; 5];
+        for (page, val) in buffer.iter_mut().enumerate() {
+            *val = if page == 0 {
+                address as u32
+            } else {
+                ((address & !(PAGE_SIZE - 1)) + page * PAGE_SIZE) as u32
+            };
+        }
+        let descriptor = &mut memory.descriptors[index];
+        let token = ((stage.toggle as u32) << 31)
+            | ((stage.len as u32) << 16)
+            | TOKEN_ERROR_COUNTER
+            | (pid << 8)
+            | TOKEN_ACTIVE;
+        unsafe {
+            addr_of_mut!(descriptor.next).write_volatile(next);
+            addr_of_mut!(descriptor.alternate_next).write_volatile(TERMINATE);
+            addr_of_mut!(descriptor.buffer).write_volatile(buffer);
+            addr_of_mut!(descriptor.token).write_volatile(token);
+        }
+    }
+    let characteristics = ((pipe.max_packet_size as u32) << 16)
+        | HEAD_OF_LIST
+        | TOGGLE_FROM_QTD
+        | SPEED_HIGH
+        | ((pipe.endpoint as u32 & 0xF) << 8)
+        | (pipe.address as u32 & 0x7F);
+    let first = addr_of!(memory.descriptors[0]) as usize as u32;
+    unsafe {
+        addr_of_mut!((*head).link).write_volatile(head as usize as u32 | TYPE_QH);
+        addr_of_mut!((*head).characteristics).write_volatile(characteristics);
+        addr_of_mut!((*head).capabilities).write_volatile(HIGH_BANDWIDTH_ONE);
+        addr_of_mut!((*head).current).write_volatile(0);
+        addr_of_mut!((*head).next).write_volatile(first);
+        addr_of_mut!((*head).alternate_next).write_volatile(TERMINATE);
+        addr_of_mut!((*head).token).write_volatile(0);
+    }
+    // make schedule visible to EHCI before it starts
+    fence(Ordering::SeqCst);
+    unsafe { regs.usb_command.modify(|val| val.enable_async_schedule()) };
+    let last = &memory.descriptors[stages.len() - 1];
+    loop {
+        let token = unsafe { addr_of!(last.token).read_volatile() };
+        let halted = unsafe { addr_of!((*head).token).read_volatile() } & TOKEN_HALTED != 0;
+        if token & TOKEN_ACTIVE == 0 || halted || deadline.is_expired() {
+            break;
+        }
+        core::hint::spin_loop();
+    }
+    // Stop the schedule; any packet in flight completes before it reads as stopped.
+    unsafe { regs.usb_command.modify(|val| val.disable_async_schedule()) };
+    let stop = Deadline::start();
+    while regs.usb_status.read().is_async_schedule_running() {
+        if stop.is_expired() {
+            break;
+        }
+    }
+    // make data written by EHCI visible to following reads
+    fence(Ordering::SeqCst);
+    let token = unsafe { addr_of!((*head).token).read_volatile() };
+    pipe.data_toggle = token & TOKEN_TOGGLE != 0;
+    let mut transferred = 0;
+    for (stage, descriptor) in stages.iter().zip(&memory.descriptors) {
+        let token = unsafe { addr_of!(descriptor.token).read_volatile() };
+        if token & TOKEN_HALTED != 0 {
+            return Err(if token & TOKEN_BABBLE != 0 {
+                HostError::BufferOverflow
+            } else if token & (TOKEN_TRANSACTION_ERROR | TOKEN_BUFFER_ERROR) != 0 {
+                HostError::Transaction
+            } else {
+                HostError::Stall
+            });
+        }
+        if token & TOKEN_ACTIVE != 0 {
+            return Err(expired);
+        }
+        if stage.pid != StagePid::Setup {
+            let remaining = ((token & TOKEN_BYTES) >> 16) as usize;
+            transferred += stage.len - remaining.min(stage.len);
+        }
+    }
+    Ok(transferred)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{QueueHead, TransferDescriptor, max_stage_len};
+    use core::mem::{align_of, offset_of, size_of};
+
+    #[test]
+    fn struct_queue_head_layout() {
+        assert_eq!(offset_of!(QueueHead, link), 0x00);
+        assert_eq!(offset_of!(QueueHead, current), 0x0C);
+        assert_eq!(offset_of!(QueueHead, next), 0x10);
+        assert_eq!(offset_of!(QueueHead, token), 0x18);
+        assert_eq!(offset_of!(QueueHead, buffer), 0x1C);
+        assert_eq!(align_of::<QueueHead>(), 32);
+        assert_eq!(offset_of!(TransferDescriptor, token), 0x08);
+        assert_eq!(offset_of!(TransferDescriptor, buffer), 0x0C);
+        assert_eq!(size_of::<TransferDescriptor>(), 32);
+    }
+
+    #[test]
+    fn function_max_stage_len() {
+        assert_eq!(max_stage_len(0x4000_0000), 20480);
+        assert_eq!(max_stage_len(0x4000_0010), 20464);
+        assert_eq!(max_stage_len(0x4000_0FFF), 16385);
+    }
+}
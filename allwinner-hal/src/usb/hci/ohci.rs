@@ -0,0 +1,285 @@
+use super::{
+    CommandStatus, FunctionalState, OhciControl, OhciRegisterBlock, RhPortStatus, RhStatus, Stage,
+    StagePid,
+};
+use crate::{
+    time::{Deadline, Duration},
+    usb::host::{DeviceSpeed, HostError, Pipe, TransferType, wait},
+};
+use core::{
+    ptr::{addr_of, addr_of_mut},
+    sync::atomic::{Ordering, fence},
+};
+
+// Endpoint descriptor fields.
+const ED_LOW_SPEED: u32 = 1 << 13;
+const ED_HALTED: u32 = 1 << 0;
+const ED_TOGGLE_CARRY: u32 = 1 << 1;
+const ED_POINTER: u32 = !0xF;
+
+// General transfer descriptor fields.
+const TD_CONDITION: u32 = 0xF << 28;
+const TD_TOGGLE_FROM_TD: u32 = 1 << 25;
+const TD_NO_INTERRUPT: u32 = 0x7 << 21;
+const TD_ROUNDING: u32 = 1 << 18;
+
+// Condition codes of retired transfer descriptors.
+const CC_NO_ERROR: u32 = 0x0;
+const CC_STALL: u32 = 0x4;
+const CC_DATA_OVERRUN: u32 = 0x8;
+const CC_DATA_UNDERRUN: u32 = 0x9;
+const CC_NOT_ACCESSED: u32 = 0xE;
+
+const PAGE_SIZE: usize = 4096;
+// Full-speed frame of 12000 bit times, and maximum packet budget after bit stuffing overhead.
+const FRAME_INTERVAL: u16 = 11999;
+const LARGEST_PACKET: u16 = ((FRAME_INTERVAL as u32 - 210) * 6 / 7) as u16;
+
+/// Maximum bytes one transfer descriptor carries from `address`, crossing one page at most.
+#[inline]
+pub(super) const fn max_stage_len(address: usize) -> usize {
+    2 * PAGE_SIZE - (address % PAGE_SIZE)
+}
+
+/// Host controller communication area.
+#[repr(C, align(256))]
+pub(super) struct Hcca([u32; 64]);
+
+/// Endpoint descriptor, words of control, tail pointer, head pointer and next descriptor.
+#[repr(C, align(16))]
+pub(super) struct EndpointDescriptor([u32; 4]);
+
+/// General transfer descriptor, words of control, current buffer pointer, next descriptor and
+/// buffer end.
+#[repr(C, align(16))]
+pub(super) struct TransferDescriptor([u32; 4]);
+
+/// OHCI schedule data structures.
+pub(super) struct Memory {
+    hcca: Hcca,
+    endpoint: EndpointDescriptor,
+    // Stage descriptors followed by an empty tail descriptor.
+    descriptors: [TransferDescriptor; 4],
+}
+
+impl Memory {
+    pub(super) const fn new() -> Self {
+        Self {
+            hcca: Hcca([0; 64]),
+            endpoint: EndpointDescriptor([0; 4]),
+            descriptors: [const { TransferDescriptor([0; 4]) }; 4],
+        }
+    }
+}
+
+/// Reset OHCI controller and make it operational with power on its port.
+pub(super) fn open(regs: &OhciRegisterBlock, memory: &mut Memory) -> Result<(), HostError> {
+    let deadline = Deadline::start();
+    unsafe { regs.command_status.write(CommandStatus::default().reset()) };
+    while regs.command_status.read().is_resetting() {
+        deadline.check().map_err(|_| HostError::Timeout)?;
+    }
+    // Controller is now suspended, and should turn operational within 2 ms.
+    let hcca = addr_of_mut!(memory.hcca);
+    unsafe {
+        hcca.write_volatile(Hcca([0; 64]));
+        regs.interrupt_disable.write(u32::MAX);
+        regs.hcca.write(hcca as usize as u32);
+        regs.control_head_ed.write(0);
+        regs.bulk_head_ed.write(0);
+        regs.frame_interval.modify(|val| {
+            val.set_interval(FRAME_INTERVAL)
+                .set_largest_packet(LARGEST_PACKET)
+                .toggle()
+        });
+        regs.periodic_start.write(FRAME_INTERVAL as u32 * 9 / 10);
+        regs.control
+            .write(OhciControl::default().set_functional_state(FunctionalState::Operational));
+        regs.rh_descriptor_a
+            .modify(|val| val.disable_power_switching());
+        regs.rh_status.write(RhStatus::default().set_global_power());
+        regs.rh_port_status
+            .write(RhPortStatus::default().set_power());
+    }
+    Ok(())
+}
+
+/// Put OHCI controller into reset state.
+pub(super) fn close(regs: &OhciRegisterBlock) {
+    unsafe {
+        regs.rh_status
+            .write(RhStatus::default().clear_global_power());
+        regs.control.write(OhciControl::default());
+    }
+}
+
+/// Reset port of OHCI controller, returning speed of attached device.
+pub(super) fn reset_port(regs: &OhciRegisterBlock) -> Result<DeviceSpeed, HostError> {
+    if !regs.rh_port_status.read().is_connected() {
+        return Err(HostError::NotConnected);
+    }
+    unsafe {
+        regs.rh_port_status
+            .write(RhPortStatus::default().set_reset());
+    }
+    let deadline = Deadline::start();
+    while !regs.rh_port_status.read().is_reset_changed() {
+        deadline.check().map_err(|_| HostError::Timeout)?;
+    }
+    unsafe {
+        regs.rh_port_status.write(
+            RhPortStatus::default()
+                .clear_reset_change()
+                .clear_connect_change(),
+        );
+    }
+    // Reset recovery time before the first transfer.
+    wait(Duration::from_millis(10));
+    let status = regs.rh_port_status.read();
+    if !status.is_enabled() {
+        return Err(HostError::NotConnected);
+    }
+    Ok(if status.is_low_speed() {
+        DeviceSpeed::Low
+    } else {
+        DeviceSpeed::Full
+    })
+}
+
+/// Run transfer `stages` on full or low-speed `pipe`, returning bytes transferred after setup
+/// stage.
+///
+/// Control pipes run on the control list, and bulk and interrupt pipes on the bulk list.
+/// Stages still pending at `deadline` are cancelled with `expired` error.
+pub(super) fn run(
+    regs: &OhciRegisterBlock,
+    memory: &mut Memory,
+    pipe: &mut Pipe,
+    setup: &[u8; 8],
+    stages: &[Stage],
+    deadline: Deadline,
+    expired: HostError,
+) -> Result<usize, HostError> {
+    let tail = addr_of!(memory.descriptors[stages.len()]) as usize as u32;
+    for (index, stage) in stages.iter().enumerate() {
+        let next = addr_of!(memory.descriptors[index + 1]) as usize as u32;
+        let (pid, address) = match stage.pid {
+            StagePid::Setup => (0, setup.as_ptr() as usize),
+            StagePid::Out => (1, stage.address),
+            StagePid::In => (2, stage.address),
+        };
+        let (current, end) = match stage.len {
+            0 => (0, 0),
+            len => (address as u32, (address + len - 1) as u32),
+        };
+        let control = TD_CONDITION
+            | TD_TOGGLE_FROM_TD
+            | ((stage.toggle as u32) << 24)
+            | TD_NO_INTERRUPT
+            | (pid << 19)
+            | TD_ROUNDING;
+        let descriptor = addr_of_mut!(memory.descriptors[index]);
+        unsafe { descriptor.write_volatile(TransferDescriptor([control, current, next, end])) };
+    }
+    let descriptor = addr_of_mut!(memory.descriptors[stages.len()]);
+    unsafe { descriptor.write_volatile(TransferDescriptor([0; 4])) };
+    let head = addr_of!(memory.descriptors[0]) as usize as u32;
+    let low_speed = match pipe.speed {
+        DeviceSpeed::Low => ED_LOW_SPEED,
+        _ => 0,
+    };
+    let control = ((pipe.max_packet_size as u32) << 16)
+        | low_speed
+        | ((pipe.endpoint as u32 & 0xF) << 7)
+        | (pipe.address as u32 & 0x7F);
+    let endpoint = addr_of_mut!(memory.endpoint);
+    unsafe { endpoint.write_volatile(EndpointDescriptor([control, tail, head, 0])) };
+    let ed = endpoint as usize as u32;
+    let control_list = pipe.transfer_type == TransferType::Control;
+    // make schedule visible to OHCI before it starts
+    fence(Ordering::SeqCst);
+    unsafe {
+        if control_list {
+            regs.control_head_ed.write(ed);
+            regs.control_current_ed.write(0);
+            regs.control.modify(|val| val.enable_control_list());
+            regs.command_status
+                .write(CommandStatus::default().set_control_list_filled());
+        } else {
+            regs.bulk_head_ed.write(ed);
+            regs.bulk_current_ed.write(0);
+            regs.control.modify(|val| val.enable_bulk_list());
+            regs.command_status
+                .write(CommandStatus::default().set_bulk_list_filled());
+        }
+    }
+    loop {
+        let head = unsafe { addr_of!((*endpoint).0[2]).read_volatile() };
+        if head & ED_POINTER == tail || head & ED_HALTED != 0 || deadline.is_expired() {
+            break;
+        }
+        core::hint::spin_loop();
+    }
+    // Remove the endpoint after the next frame starts, when OHCI no longer holds it.
+    unsafe {
+        if control_list {
+            regs.control.modify(|val| val.disable_control_list());
+        } else {
+            regs.control.modify(|val| val.disable_bulk_list());
+        }
+    }
+    wait(Duration::from_millis(2));
+    unsafe {
+        if control_list {
+            regs.control_head_ed.write(0);
+        } else {
+            regs.bulk_head_ed.write(0);
+        }
+    }
+    // make data written by OHCI visible to following reads
+    fence(Ordering::SeqCst);
+    let head = unsafe { addr_of!((*endpoint).0[2]).read_volatile() };
+    pipe.data_toggle = head & ED_TOGGLE_CARRY != 0;
+    let mut transferred = 0;
+    for (index, stage) in stages.iter().enumerate() {
+        let [control, current, _, end] =
+            unsafe { addr_of!(memory.descriptors[index]).read_volatile() }.0;
+        match (control & TD_CONDITION) >> 28 {
+            CC_NO_ERROR | CC_DATA_UNDERRUN => {}
+            CC_STALL => return Err(HostError::Stall),
+            CC_DATA_OVERRUN => return Err(HostError::BufferOverflow),
+            CC_NOT_ACCESSED.. => return Err(expired),
+            _ => return Err(HostError::Transaction),
+        }
+        if stage.pid != StagePid::Setup && stage.len > 0 {
+            // Current buffer pointer is zero when all bytes are transferred.
+            transferred += match current {
+                0 => stage.len,
+                current => stage.len - (end - current + 1) as usize,
+            };
+        }
+    }
+    Ok(transferred)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{EndpointDescriptor, Hcca, LARGEST_PACKET, TransferDescriptor, max_stage_len};
+    use core::mem::{align_of, size_of};
+
+    #[test]
+    fn struct_descriptor_layout() {
+        assert_eq!(size_of::<Hcca>(), 256);
+        assert_eq!(align_of::<Hcca>(), 256);
+        assert_eq!(size_of::<EndpointDescriptor>(), 16);
+        assert_eq!(align_of::<EndpointDescriptor>(), 16);
+        assert_eq!(size_of::<TransferDescriptor>(), 16);
+        assert_eq!(LARGEST_PACKET, 0x2778);
+    }
+
+    #[test]
+    fn function_max_stage_len() {
+        assert_eq!(max_stage_len(0x4000_0000), 8192);
+        assert_eq!(max_stage_len(0x4000_0F00), 4352);
+    }
+}
@@ -0,0 +1,890 @@
+use volatile_register::{RO, RW};
+
+/// USB1 host controller registers, with EHCI and its OHCI companion.
+#[repr(C)]
+pub struct RegisterBlock {
+    /// 0x000..=0x054 - EHCI registers.
+    pub ehci: EhciRegisterBlock,
+    _reserved0: [u32; 234],
+    /// 0x400..=0x454 - OHCI registers.
+    pub ohci: OhciRegisterBlock,
+    _reserved1: [u32; 234],
+    /// 0x800 - HCI Interface Register.
+    pub interface: RW<HciInterface>,
+    _reserved2: [u32; 3],
+    /// 0x810 - HCI PHY Control Register.
+    pub phy_control: RW<HciPhyControl>,
+}
+
+/// Enhanced Host Controller Interface (EHCI) registers.
+#[repr(C)]
+pub struct EhciRegisterBlock {
+    /// 0x00 - Capability Register Length and Interface Version Number Register.
+    pub capability: RO<u32>,
+    /// 0x04 - Structural Parameters Register.
+    pub structural_params: RO<u32>,
+    /// 0x08 - Capability Parameters Register.
+    pub capability_params: RO<u32>,
+    /// 0x0c - Companion Port Route Description Register.
+    pub companion_port_route: RO<u32>,
+    /// 0x10 - USB Command Register.
+    pub usb_command: RW<UsbCommand>,
+    /// 0x14 - USB Status Register.
+    pub usb_status: RW<UsbStatus>,
+    /// 0x18 - USB Interrupt Enable Register.
+    pub usb_interrupt_enable: RW<u32>,
+    /// 0x1c - Frame Index Register.
+    pub frame_index: RW<u32>,
+    /// 0x20 - 4G Segment Selector Register.
+    pub control_segment: RW<u32>,
+    /// 0x24 - Frame List Base Address Register.
+    pub periodic_list_base: RW<u32>,
+    /// 0x28 - Next Asynchronous List Address Register.
+    pub async_list_address: RW<u32>,
+    _reserved0: [u32; 9],
+    /// 0x50 - Configure Flag Register.
+    pub config_flag: RW<u32>,
+    /// 0x54 - Port Status and Control Register.
+    pub port_status: RW<PortStatus>,
+}
+
+/// Open Host Controller Interface (OHCI) registers.
+#[repr(C)]
+pub struct OhciRegisterBlock {
+    /// 0x00 - HC Revision Register.
+    pub revision: RO<u32>,
+    /// 0x04 - HC Control Register.
+    pub control: RW<OhciControl>,
+    /// 0x08 - HC Command Status Register.
+    pub command_status: RW<CommandStatus>,
+    /// 0x0c - HC Interrupt Status Register.
+    pub interrupt_status: RW<u32>,
+    /// 0x10 - HC Interrupt Enable Register.
+    pub interrupt_enable: RW<u32>,
+    /// 0x14 - HC Interrupt Disable Register.
+    pub interrupt_disable: RW<u32>,
+    /// 0x18 - HC Communication Area Base Address Register.
+    pub hcca: RW<u32>,
+    /// 0x1c - HC Period Current Endpoint Descriptor Register.
+    pub period_current_ed: RO<u32>,
+    /// 0x20 - HC Control Head Endpoint Descriptor Register.
+    pub control_head_ed: RW<u32>,
+    /// 0x24 - HC Control Current Endpoint Descriptor Register.
+    pub control_current_ed: RW<u32>,
+    /// 0x28 - HC Bulk Head Endpoint Descriptor Register.
+    pub bulk_head_ed: RW<u32>,
+    /// 0x2c - HC Bulk Current Endpoint Descriptor Register.
+    pub bulk_current_ed: RW<u32>,
+    /// 0x30 - HC Done Head Register.
+    pub done_head: RO<u32>,
+    /// 0x34 - HC Frame Interval Register.
+    pub frame_interval: RW<FrameInterval>,
+    /// 0x38 - HC Frame Remaining Register.
+    pub frame_remaining: RO<u32>,
+    /// 0x3c - HC Frame Number Register.
+    pub frame_number: RO<u32>,
+    /// 0x40 - HC Periodic Start Register.
+    pub periodic_start: RW<u32>,
+    /// 0x44 - HC Low Speed Threshold Register.
+    pub ls_threshold: RW<u32>,
+    /// 0x48 - HC Root Hub Descriptor A Register.
+    pub rh_descriptor_a: RW<RhDescriptorA>,
+    /// 0x4c - HC Root Hub Descriptor B Register.
+    pub rh_descriptor_b: RW<u32>,
+    /// 0x50 - HC Root Hub Status Register.
+    pub rh_status: RW<RhStatus>,
+    /// 0x54 - HC Root Hub Port Status Register.
+    pub rh_port_status: RW<RhPortStatus>,
+}
+
+/// EHCI USB command register.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct UsbCommand(u32);
+
+impl UsbCommand {
+    const ASYNC_ENABLE: u32 = 1 << 5;
+    const HC_RESET: u32 = 1 << 1;
+    const RUN: u32 = 1 << 0;
+
+    /// Check if asynchronous schedule is enabled.
+    #[inline]
+    pub const fn is_async_schedule_enabled(self) -> bool {
+        self.0 & Self::ASYNC_ENABLE != 0
+    }
+    /// Enable asynchronous schedule.
+    #[inline]
+    pub const fn enable_async_schedule(self) -> Self {
+        Self(self.0 | Self::ASYNC_ENABLE)
+    }
+    /// Disable asynchronous schedule.
+    #[inline]
+    pub const fn disable_async_schedule(self) -> Self {
+        Self(self.0 & !Self::ASYNC_ENABLE)
+    }
+    /// Check if host controller reset is in progress.
+    #[inline]
+    pub const fn is_resetting(self) -> bool {
+        self.0 & Self::HC_RESET != 0
+    }
+    /// Reset host controller, cleared by hardware when reset completes.
+    #[inline]
+    pub const fn reset(self) -> Self {
+        Self(self.0 | Self::HC_RESET)
+    }
+    /// Check if host controller is set to run.
+    #[inline]
+    pub const fn is_running(self) -> bool {
+        self.0 & Self::RUN != 0
+    }
+    /// Run host controller.
+    #[inline]
+    pub const fn run(self) -> Self {
+        Self(self.0 | Self::RUN)
+    }
+    /// Stop host controller.
+    #[inline]
+    pub const fn stop(self) -> Self {
+        Self(self.0 & !Self::RUN)
+    }
+}
+
+impl Default for UsbCommand {
+    #[inline]
+    fn default() -> Self {
+        Self(0x0008_0000)
+    }
+}
+
+/// EHCI USB status register.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct UsbStatus(u32);
+
+impl UsbStatus {
+    const ASYNC_STATUS: u32 = 1 << 15;
+    const HALTED: u32 = 1 << 12;
+    const SYSTEM_ERROR: u32 = 1 << 4;
+    const INTERRUPTS: u32 = 0x3F;
+
+    /// Check if asynchronous schedule is running.
+    #[inline]
+    pub const fn is_async_schedule_running(self) -> bool {
+        self.0 & Self::ASYNC_STATUS != 0
+    }
+    /// Check if host controller is halted.
+    #[inline]
+    pub const fn is_halted(self) -> bool {
+        self.0 & Self::HALTED != 0
+    }
+    /// Check if a host system error occurred.
+    #[inline]
+    pub const fn is_system_error(self) -> bool {
+        self.0 & Self::SYSTEM_ERROR != 0
+    }
+    /// Clear all interrupt status bits when written back.
+    #[inline]
+    pub const fn clear_interrupts(self) -> Self {
+        Self(self.0 | Self::INTERRUPTS)
+    }
+}
+
+impl Default for UsbStatus {
+    #[inline]
+    fn default() -> Self {
+        Self(0x0000_1000)
+    }
+}
+
+/// Line state of EHCI port, valid when port is not enabled.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum LineState {
+    /// Single-ended zero.
+    Se0,
+    /// J-state, full or high-speed device attached.
+    JState,
+    /// K-state, low-speed device attached.
+    KState,
+    /// Undefined line state.
+    Undefined,
+}
+
+/// EHCI port status and control register.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct PortStatus(u32);
+
+impl PortStatus {
+    const PORT_OWNER: u32 = 1 << 13;
+    const PORT_POWER: u32 = 1 << 12;
+    const LINE_STATUS: u32 = 0x3 << 10;
+    const PORT_RESET: u32 = 1 << 8;
+    const ENABLE_CHANGE: u32 = 1 << 3;
+    const ENABLED: u32 = 1 << 2;
+    const CONNECT_CHANGE: u32 = 1 << 1;
+    const CONNECTED: u32 = 1 << 0;
+    const CHANGES: u32 = (1 << 5) | Self::ENABLE_CHANGE | Self::CONNECT_CHANGE;
+
+    /// Check if port is owned by companion OHCI controller.
+    #[inline]
+    pub const fn is_companion_owned(self) -> bool {
+        self.0 & Self::PORT_OWNER != 0
+    }
+    /// Release port to companion OHCI controller.
+    #[inline]
+    pub const fn release_to_companion(self) -> Self {
+        Self(self.0 | Self::PORT_OWNER)
+    }
+    /// Claim port back from companion OHCI controller.
+    #[inline]
+    pub const fn claim_from_companion(self) -> Self {
+        Self(self.0 & !Self::PORT_OWNER)
+    }
+    /// Check if port is powered.
+    #[inline]
+    pub const fn is_powered(self) -> bool {
+        self.0 & Self::PORT_POWER != 0
+    }
+    /// Power on port.
+    #[inline]
+    pub const fn power_on(self) -> Self {
+        Self(self.0 | Self::PORT_POWER)
+    }
+    /// Get line state of port.
+    #[inline]
+    pub const fn line_state(self) -> LineState {
+        match (self.0 & Self::LINE_STATUS) >> 10 {
+            0 => LineState::Se0,
+            1 => LineState::KState,
+            2 => LineState::JState,
+            _ => LineState::Undefined,
+        }
+    }
+    /// Check if port reset is in progress.
+    #[inline]
+    pub const fn is_resetting(self) -> bool {
+        self.0 & Self::PORT_RESET != 0
+    }
+    /// Start port reset, disabling port.
+    #[inline]
+    pub const fn start_reset(self) -> Self {
+        Self((self.0 | Self::PORT_RESET) & !Self::ENABLED)
+    }
+    /// Stop port reset.
+    #[inline]
+    pub const fn stop_reset(self) -> Self {
+        Self(self.0 & !Self::PORT_RESET)
+    }
+    /// Check if port is enabled, with a high-speed device after reset.
+    #[inline]
+    pub const fn is_enabled(self) -> bool {
+        self.0 & Self::ENABLED != 0
+    }
+    /// Check if a device is connected.
+    #[inline]
+    pub const fn is_connected(self) -> bool {
+        self.0 & Self::CONNECTED != 0
+    }
+    /// Check if connect status has changed.
+    #[inline]
+    pub const fn is_connect_changed(self) -> bool {
+        self.0 & Self::CONNECT_CHANGE != 0
+    }
+    /// Keep change bits unchanged when written back.
+    #[inline]
+    pub const fn keep_changes(self) -> Self {
+        Self(self.0 & !Self::CHANGES)
+    }
+    /// Clear change bits when written back.
+    #[inline]
+    pub const fn clear_changes(self) -> Self {
+        Self(self.0 | Self::CHANGES)
+    }
+}
+
+impl Default for PortStatus {
+    #[inline]
+    fn default() -> Self {
+        Self(0x0000_2000)
+    }
+}
+
+/// Functional state of OHCI controller.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum FunctionalState {
+    /// USB reset, no traffic on the bus.
+    Reset,
+    /// USB resume signaling.
+    Resume,
+    /// Operational, processing lists and generating start of frames.
+    Operational,
+    /// USB suspend.
+    Suspend,
+}
+
+/// OHCI control register.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct OhciControl(u32);
+
+impl OhciControl {
+    const FUNCTIONAL_STATE: u32 = 0x3 << 6;
+    const BULK_ENABLE: u32 = 1 << 5;
+    const CONTROL_ENABLE: u32 = 1 << 4;
+
+    /// Get functional state.
+    #[inline]
+    pub const fn functional_state(self) -> FunctionalState {
+        match (self.0 & Self::FUNCTIONAL_STATE) >> 6 {
+            0 => FunctionalState::Reset,
+            1 => FunctionalState::Resume,
+            2 => FunctionalState::Operational,
+            _ => FunctionalState::Suspend,
+        }
+    }
+    /// Set functional state.
+    #[inline]
+    pub const fn set_functional_state(self, val: FunctionalState) -> Self {
+        Self((self.0 & !Self::FUNCTIONAL_STATE) | ((val as u32) << 6))
+    }
+    /// Check if bulk list is processed.
+    #[inline]
+    pub const fn is_bulk_list_enabled(self) -> bool {
+        self.0 & Self::BULK_ENABLE != 0
+    }
+    /// Enable processing of bulk list.
+    #[inline]
+    pub const fn enable_bulk_list(self) -> Self {
+        Self(self.0 | Self::BULK_ENABLE)
+    }
+    /// Disable processing of bulk list.
+    #[inline]
+    pub const fn disable_bulk_list(self) -> Self {
+        Self(self.0 & !Self::BULK_ENABLE)
+    }
+    /// Check if control list is processed.
+    #[inline]
+    pub const fn is_control_list_enabled(self) -> bool {
+        self.0 & Self::CONTROL_ENABLE != 0
+    }
+    /// Enable processing of control list.
+    #[inline]
+    pub const fn enable_control_list(self) -> Self {
+        Self(self.0 | Self::CONTROL_ENABLE)
+    }
+    /// Disable processing of control list.
+    #[inline]
+    pub const fn disable_control_list(self) -> Self {
+        Self(self.0 & !Self::CONTROL_ENABLE)
+    }
+}
+
+impl Default for OhciControl {
+    #[inline]
+    fn default() -> Self {
+        Self(0)
+    }
+}
+
+/// OHCI command status register.
+///
+/// Writing zero to a bit has no effect, thus commands are written on a default value.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct CommandStatus(u32);
+
+impl CommandStatus {
+    const BULK_FILLED: u32 = 1 << 2;
+    const CONTROL_FILLED: u32 = 1 << 1;
+    const HC_RESET: u32 = 1 << 0;
+
+    /// Signal that bulk list has transfer descriptors to process.
+    #[inline]
+    pub const fn set_bulk_list_filled(self) -> Self {
+        Self(self.0 | Self::BULK_FILLED)
+    }
+    /// Signal that control list has transfer descriptors to process.
+    #[inline]
+    pub const fn set_control_list_filled(self) -> Self {
+        Self(self.0 | Self::CONTROL_FILLED)
+    }
+    /// Check if host controller reset is in progress.
+    #[inline]
+    pub const fn is_resetting(self) -> bool {
+        self.0 & Self::HC_RESET != 0
+    }
+    /// Reset host controller, cleared by hardware when reset completes.
+    #[inline]
+    pub const fn reset(self) -> Self {
+        Self(self.0 | Self::HC_RESET)
+    }
+}
+
+impl Default for CommandStatus {
+    #[inline]
+    fn default() -> Self {
+        Self(0)
+    }
+}
+
+/// OHCI frame interval register.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct FrameInterval(u32);
+
+impl FrameInterval {
+    const INTERVAL_TOGGLE: u32 = 1 << 31;
+    const LARGEST_PACKET: u32 = 0x7FFF << 16;
+    const INTERVAL: u32 = 0x3FFF;
+
+    /// Toggle frame interval toggle bit, marking a new frame interval.
+    #[inline]
+    pub const fn toggle(self) -> Self {
+        Self(self.0 ^ Self::INTERVAL_TOGGLE)
+    }
+    /// Get largest data packet size in bits of a frame.
+    #[inline]
+    pub const fn largest_packet(self) -> u16 {
+        ((self.0 & Self::LARGEST_PACKET) >> 16) as u16
+    }
+    /// Set largest data packet size in bits of a frame.
+    #[inline]
+    pub const fn set_largest_packet(self, val: u16) -> Self {
+        Self((self.0 & !Self::LARGEST_PACKET) | (((val as u32) << 16) & Self::LARGEST_PACKET))
+    }
+    /// Get frame interval in 12-MHz bit times, minus one.
+    #[inline]
+    pub const fn interval(self) -> u16 {
+        (self.0 & Self::INTERVAL) as u16
+    }
+    /// Set frame interval in 12-MHz bit times, minus one.
+    #[inline]
+    pub const fn set_interval(self, val: u16) -> Self {
+        Self((self.0 & !Self::INTERVAL) | (val as u32 & Self::INTERVAL))
+    }
+}
+
+impl Default for FrameInterval {
+    #[inline]
+    fn default() -> Self {
+        Self(0x0000_2EDF)
+    }
+}
+
+/// OHCI root hub descriptor A register.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct RhDescriptorA(u32);
+
+impl RhDescriptorA {
+    const NO_POWER_SWITCHING: u32 = 1 << 9;
+    const PORT_COUNT: u32 = 0xFF;
+
+    /// Check if ports are always powered.
+    #[inline]
+    pub const fn is_power_switching_disabled(self) -> bool {
+        self.0 & Self::NO_POWER_SWITCHING != 0
+    }
+    /// Keep ports always powered when controller is on.
+    #[inline]
+    pub const fn disable_power_switching(self) -> Self {
+        Self(self.0 | Self::NO_POWER_SWITCHING)
+    }
+    /// Get number of downstream ports.
+    #[inline]
+    pub const fn port_count(self) -> u8 {
+        (self.0 & Self::PORT_COUNT) as u8
+    }
+}
+
+/// OHCI root hub status register.
+///
+/// Writing zero to a bit has no effect, thus commands are written on a default value.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct RhStatus(u32);
+
+impl RhStatus {
+    const SET_GLOBAL_POWER: u32 = 1 << 16;
+    const CLEAR_GLOBAL_POWER: u32 = 1 << 0;
+
+    /// Turn on power of all ports.
+    #[inline]
+    pub const fn set_global_power(self) -> Self {
+        Self(self.0 | Self::SET_GLOBAL_POWER)
+    }
+    /// Turn off power of all ports.
+    #[inline]
+    pub const fn clear_global_power(self) -> Self {
+        Self(self.0 | Self::CLEAR_GLOBAL_POWER)
+    }
+}
+
+impl Default for RhStatus {
+    #[inline]
+    fn default() -> Self {
+        Self(0)
+    }
+}
+
+/// OHCI root hub port status register.
+///
+/// Status bits read differently from command bits written; writing zero to a bit has no
+/// effect, thus commands are written on a default value.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct RhPortStatus(u32);
+
+impl RhPortStatus {
+    const RESET_CHANGE: u32 = 1 << 20;
+    const CONNECT_CHANGE: u32 = 1 << 16;
+    const LOW_SPEED: u32 = 1 << 9;
+    const POWER: u32 = 1 << 8;
+    const RESET: u32 = 1 << 4;
+    const ENABLED: u32 = 1 << 1;
+    const CONNECTED: u32 = 1 << 0;
+
+    /// Check if port reset has completed.
+    #[inline]
+    pub const fn is_reset_changed(self) -> bool {
+        self.0 & Self::RESET_CHANGE != 0
+    }
+    /// Clear port reset completion status.
+    #[inline]
+    pub const fn clear_reset_change(self) -> Self {
+        Self(self.0 | Self::RESET_CHANGE)
+    }
+    /// Check if connect status has changed.
+    #[inline]
+    pub const fn is_connect_changed(self) -> bool {
+        self.0 & Self::CONNECT_CHANGE != 0
+    }
+    /// Clear connect status change.
+    #[inline]
+    pub const fn clear_connect_change(self) -> Self {
+        Self(self.0 | Self::CONNECT_CHANGE)
+    }
+    /// Check if a low-speed device is attached.
+    #[inline]
+    pub const fn is_low_speed(self) -> bool {
+        self.0 & Self::LOW_SPEED != 0
+    }
+    /// Check if port is powered.
+    #[inline]
+    pub const fn is_powered(self) -> bool {
+        self.0 & Self::POWER != 0
+    }
+    /// Turn on port power.
+    #[inline]
+    pub const fn set_power(self) -> Self {
+        Self(self.0 | Self::POWER)
+    }
+    /// Check if port reset is in progress.
+    #[inline]
+    pub const fn is_resetting(self) -> bool {
+        self.0 & Self::RESET != 0
+    }
+    /// Start port reset, ended by hardware.
+    #[inline]
+    pub const fn set_reset(self) -> Self {
+        Self(self.0 | Self::RESET)
+    }
+    /// Check if port is enabled.
+    #[inline]
+    pub const fn is_enabled(self) -> bool {
+        self.0 & Self::ENABLED != 0
+    }
+    /// Check if a device is connected.
+    #[inline]
+    pub const fn is_connected(self) -> bool {
+        self.0 & Self::CONNECTED != 0
+    }
+}
+
+impl Default for RhPortStatus {
+    #[inline]
+    fn default() -> Self {
+        Self(0)
+    }
+}
+
+/// HCI interface register.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct HciInterface(u32);
+
+impl HciInterface {
+    const AHB_BURST: u32 = 0x7 << 8;
+    const ULPI_BYPASS: u32 = 1 << 0;
+
+    /// Check if INCR8, INCR4 and aligned INCRX bursts are enabled on AHB master.
+    #[inline]
+    pub const fn is_ahb_burst_enabled(self) -> bool {
+        self.0 & Self::AHB_BURST == Self::AHB_BURST
+    }
+    /// Enable INCR8, INCR4 and aligned INCRX bursts on AHB master.
+    #[inline]
+    pub const fn enable_ahb_burst(self) -> Self {
+        Self(self.0 | Self::AHB_BURST)
+    }
+    /// Disable INCR8, INCR4 and aligned INCRX bursts on AHB master.
+    #[inline]
+    pub const fn disable_ahb_burst(self) -> Self {
+        Self(self.0 & !Self::AHB_BURST)
+    }
+    /// Check if ULPI interface is bypassed to the internal UTMI PHY.
+    #[inline]
+    pub const fn is_ulpi_bypassed(self) -> bool {
+        self.0 & Self::ULPI_BYPASS != 0
+    }
+    /// Bypass ULPI interface, using the internal UTMI PHY.
+    #[inline]
+    pub const fn enable_ulpi_bypass(self) -> Self {
+        Self(self.0 | Self::ULPI_BYPASS)
+    }
+    /// Use ULPI interface.
+    #[inline]
+    pub const fn disable_ulpi_bypass(self) -> Self {
+        Self(self.0 & !Self::ULPI_BYPASS)
+    }
+}
+
+impl Default for HciInterface {
+    #[inline]
+    fn default() -> Self {
+        Self(0)
+    }
+}
+
+/// HCI PHY control register.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct HciPhyControl(u32);
+
+impl HciPhyControl {
+    const SIDDQ: u32 = 1 << 3;
+
+    /// Check if PHY analog block is powered down.
+    #[inline]
+    pub const fn is_powered_down(self) -> bool {
+        self.0 & Self::SIDDQ != 0
+    }
+    /// Power down PHY analog block.
+    #[inline]
+    pub const fn power_down(self) -> Self {
+        Self(self.0 | Self::SIDDQ)
+    }
+    /// Power up PHY analog block.
+    #[inline]
+    pub const fn power_up(self) -> Self {
+        Self(self.0 & !Self::SIDDQ)
+    }
+}
+
+impl Default for HciPhyControl {
+    #[inline]
+    fn default() -> Self {
+        Self(0x0000_0008)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        CommandStatus, EhciRegisterBlock, FrameInterval, FunctionalState, HciInterface,
+        HciPhyControl, LineState, OhciControl, OhciRegisterBlock, PortStatus, RegisterBlock,
+        RhDescriptorA, RhPortStatus, RhStatus, UsbCommand, UsbStatus,
+    };
+    use core::mem::offset_of;
+
+    #[test]
+    fn offset_usb1() {
+        assert_eq!(offset_of!(RegisterBlock, ehci), 0x000);
+        assert_eq!(offset_of!(RegisterBlock, ohci), 0x400);
+        assert_eq!(offset_of!(RegisterBlock, interface), 0x800);
+        assert_eq!(offset_of!(RegisterBlock, phy_control), 0x810);
+    }
+
+    #[test]
+    fn offset_ehci() {
+        assert_eq!(offset_of!(EhciRegisterBlock, capability), 0x00);
+        assert_eq!(offset_of!(EhciRegisterBlock, companion_port_route), 0x0c);
+        assert_eq!(offset_of!(EhciRegisterBlock, usb_command), 0x10);
+        assert_eq!(offset_of!(EhciRegisterBlock, usb_status), 0x14);
+        assert_eq!(offset_of!(EhciRegisterBlock, usb_interrupt_enable), 0x18);
+        assert_eq!(offset_of!(EhciRegisterBlock, frame_index), 0x1c);
+        assert_eq!(offset_of!(EhciRegisterBlock, periodic_list_base), 0x24);
+        assert_eq!(offset_of!(EhciRegisterBlock, async_list_address), 0x28);
+        assert_eq!(offset_of!(EhciRegisterBlock, config_flag), 0x50);
+        assert_eq!(offset_of!(EhciRegisterBlock, port_status), 0x54);
+    }
+
+    #[test]
+    fn offset_ohci() {
+        assert_eq!(offset_of!(OhciRegisterBlock, revision), 0x00);
+        assert_eq!(offset_of!(OhciRegisterBlock, control), 0x04);
+        assert_eq!(offset_of!(OhciRegisterBlock, command_status), 0x08);
+        assert_eq!(offset_of!(OhciRegisterBlock, interrupt_disable), 0x14);
+        assert_eq!(offset_of!(OhciRegisterBlock, hcca), 0x18);
+        assert_eq!(offset_of!(OhciRegisterBlock, control_head_ed), 0x20);
+        assert_eq!(offset_of!(OhciRegisterBlock, bulk_head_ed), 0x28);
+        assert_eq!(offset_of!(OhciRegisterBlock, done_head), 0x30);
+        assert_eq!(offset_of!(OhciRegisterBlock, frame_interval), 0x34);
+        assert_eq!(offset_of!(OhciRegisterBlock, periodic_start), 0x40);
+        assert_eq!(offset_of!(OhciRegisterBlock, rh_descriptor_a), 0x48);
+        assert_eq!(offset_of!(OhciRegisterBlock, rh_status), 0x50);
+        assert_eq!(offset_of!(OhciRegisterBlock, rh_port_status), 0x54);
+    }
+
+    #[test]
+    fn struct_usb_command_functions() {
+        let mut val = UsbCommand::default();
+        assert_eq!(val.0, 0x0008_0000);
+
+        val = val.run().enable_async_schedule();
+        assert!(val.is_running());
+        assert!(val.is_async_schedule_enabled());
+        assert_eq!(val.0, 0x0008_0021);
+
+        val = val.stop().disable_async_schedule().reset();
+        assert!(!val.is_running());
+        assert!(val.is_resetting());
+        assert_eq!(val.0, 0x0008_0002);
+    }
+
+    #[test]
+    fn struct_usb_status_functions() {
+        let val = UsbStatus::default();
+        assert!(val.is_halted());
+        assert!(!val.is_async_schedule_running());
+        assert_eq!(val.clear_interrupts().0, 0x0000_103F);
+
+        let val = UsbStatus(0x0000_8010);
+        assert!(val.is_async_schedule_running());
+        assert!(val.is_system_error());
+        assert!(!val.is_halted());
+    }
+
+    #[test]
+    fn struct_port_status_functions() {
+        let mut val = PortStatus::default();
+        assert!(val.is_companion_owned());
+
+        val = val.claim_from_companion().power_on();
+        assert!(val.is_powered());
+        assert_eq!(val.0, 0x0000_1000);
+
+        val = PortStatus(0x0000_142F);
+        assert!(val.is_connected());
+        assert!(val.is_connect_changed());
+        assert!(val.is_enabled());
+        assert_eq!(val.line_state(), LineState::KState);
+        assert_eq!(val.keep_changes().0, 0x0000_1405);
+        assert_eq!(val.keep_changes().start_reset().0, 0x0000_1501);
+        assert_eq!(PortStatus(0x0000_1100).stop_reset().0, 0x0000_1000);
+        assert!(PortStatus(0x0000_0100).is_resetting());
+        assert_eq!(PortStatus(0x0000_0800).line_state(), LineState::JState);
+        assert_eq!(PortStatus(0x0000_0000).line_state(), LineState::Se0);
+        assert_eq!(PortStatus(0x0000_0C00).line_state(), LineState::Undefined);
+        assert_eq!(PortStatus(0x0).clear_changes().0, 0x0000_002A);
+        assert_eq!(PortStatus(0x0).release_to_companion().0, 0x0000_2000);
+    }
+
+    #[test]
+    fn struct_ohci_control_functions() {
+        let mut val = OhciControl::default();
+        assert_eq!(val.functional_state(), FunctionalState::Reset);
+
+        val = val
+            .set_functional_state(FunctionalState::Operational)
+            .enable_control_list()
+            .enable_bulk_list();
+        assert_eq!(val.functional_state(), FunctionalState::Operational);
+        assert!(val.is_control_list_enabled());
+        assert!(val.is_bulk_list_enabled());
+        assert_eq!(val.0, 0x0000_00B0);
+
+        val = val
+            .set_functional_state(FunctionalState::Suspend)
+            .disable_control_list()
+            .disable_bulk_list();
+        assert_eq!(val.functional_state(), FunctionalState::Suspend);
+        assert_eq!(val.0, 0x0000_00C0);
+        assert_eq!(
+            val.set_functional_state(FunctionalState::Resume).0,
+            0x0000_0040
+        );
+    }
+
+    #[test]
+    fn struct_command_status_functions() {
+        let val = CommandStatus::default().reset();
+        assert!(val.is_resetting());
+        assert_eq!(val.0, 0x0000_0001);
+        assert_eq!(CommandStatus::default().set_control_list_filled().0, 0x2);
+        assert_eq!(CommandStatus::default().set_bulk_list_filled().0, 0x4);
+    }
+
+    #[test]
+    fn struct_frame_interval_functions() {
+        let mut val = FrameInterval::default();
+        assert_eq!(val.interval(), 0x2EDF);
+
+        val = val.set_largest_packet(0x2778).toggle();
+        assert_eq!(val.largest_packet(), 0x2778);
+        assert_eq!(val.0, 0xA778_2EDF);
+
+        val = val.set_interval(0x1234).toggle();
+        assert_eq!(val.interval(), 0x1234);
+        assert_eq!(val.0, 0x2778_1234);
+    }
+
+    #[test]
+    fn struct_root_hub_functions() {
+        let val = RhDescriptorA(0x0200_1001);
+        assert_eq!(val.port_count(), 1);
+        assert!(!val.is_power_switching_disabled());
+        assert_eq!(val.disable_power_switching().0, 0x0200_1201);
+
+        assert_eq!(RhStatus::default().set_global_power().0, 0x0001_0000);
+        assert_eq!(RhStatus::default().clear_global_power().0, 0x0000_0001);
+
+        let val = RhPortStatus(0x0011_0303);
+        assert!(val.is_connected());
+        assert!(val.is_enabled());
+        assert!(val.is_powered());
+        assert!(val.is_low_speed());
+        assert!(val.is_connect_changed());
+        assert!(val.is_reset_changed());
+        assert!(!val.is_resetting());
+        assert_eq!(RhPortStatus::default().set_reset().0, 0x0000_0010);
+        assert_eq!(RhPortStatus::default().set_power().0, 0x0000_0100);
+        assert_eq!(
+            RhPortStatus::default()
+                .clear_reset_change()
+                .clear_connect_change()
+                .0,
+            0x0011_0000
+        );
+    }
+
+    #[test]
+    fn struct_hci_interface_functions() {
+        let mut val = HciInterface::default();
+
+        val = val.enable_ahb_burst().enable_ulpi_bypass();
+        assert!(val.is_ahb_burst_enabled());
+        assert!(val.is_ulpi_bypassed());
+        assert_eq!(val.0, 0x0000_0701);
+
+        val = val.disable_ahb_burst().disable_ulpi_bypass();
+        assert_eq!(val.0, 0x0000_0000);
+
+        let mut val = HciPhyControl::default();
+        assert!(val.is_powered_down());
+        val = val.power_up();
+        assert!(!val.is_powered_down());
+        assert_eq!(val.0, 0x0000_0000);
+        assert_eq!(val.power_down().0, 0x0000_0008);
+    }
+}
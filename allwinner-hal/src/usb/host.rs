@@ -0,0 +1,691 @@
+//! USB host transfers and device enumeration, shared by USB host controllers.
+//!
+//! Host controllers implement [`Host`], which carries out control, bulk and interrupt
+//! transfers on a [`Pipe`] to one endpoint of the device attached to the root port.
+//! [`enumerate`] resets the port, assigns an address and selects the first configuration
+//! of that device; hubs are not supported.
+//!
+//! ```ignore
+//! let mut host = UsbHost::new(p.usb0, &p.ccu);
+//! while !host.is_connected() {}
+//! let mut config = [0; 256];
+//! let (device, len) = enumerate(&mut host, 1, &mut config)?;
+//! for descriptor in Descriptors::new(&config[..len]) {
+//!     if let Descriptor::Endpoint(endpoint) = descriptor {
+//!         let mut pipe = device.pipe(&endpoint);
+//!         // host.transfer_in(&mut pipe, &mut buf)
+//!     }
+//! }
+//! ```
+
+use crate::time::{Deadline, Duration, Instant};
+
+/// Speed of attached device.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum DeviceSpeed {
+    /// Low speed, 1.5 Mbit/s.
+    Low,
+    /// Full speed, 12 Mbit/s.
+    Full,
+    /// High speed, 480 Mbit/s.
+    High,
+}
+
+/// Endpoint transfer type.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum TransferType {
+    /// Control transfers, endpoint 0.
+    Control = 0,
+    /// Isochronous transfers, not supported by [`Host`] transfers.
+    Isochronous = 1,
+    /// Bulk transfers.
+    Bulk = 2,
+    /// Interrupt transfers.
+    Interrupt = 3,
+}
+
+/// Endpoint transfer direction, as seen from host.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Direction {
+    /// Host to device.
+    Out,
+    /// Device to host.
+    In,
+}
+
+/// USB host transfer error.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum HostError {
+    /// No device is attached to the root port.
+    NotConnected,
+    /// Device answered with stall handshake.
+    Stall,
+    /// Interrupt endpoint had no data or room within its polling interval.
+    Nak,
+    /// Device did not respond, or responded with corrupted packets.
+    Transaction,
+    /// Transfer did not complete before crate-wide blocking timeout.
+    ///
+    /// See [`crate::time::set_blocking_timeout`].
+    Timeout,
+    /// Device sent more data than the buffer holds.
+    BufferOverflow,
+    /// Transfer type or packet size is not supported by the host controller.
+    Unsupported,
+    /// Device returned a malformed descriptor.
+    InvalidDescriptor,
+}
+
+/// Pipe from host to one endpoint of an attached device.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Pipe {
+    /// Device address, 0 before address is assigned.
+    pub address: u8,
+    /// Endpoint number.
+    pub endpoint: u8,
+    /// Transfer direction; ignored by control pipes.
+    pub direction: Direction,
+    /// Transfer type.
+    pub transfer_type: TransferType,
+    /// Maximum packet size of endpoint.
+    pub max_packet_size: u16,
+    /// Polling interval of interrupt endpoint, as in endpoint descriptor.
+    pub interval: u8,
+    /// Speed of device.
+    pub speed: DeviceSpeed,
+    /// Data toggle of next packet, true for DATA1; reset after clearing endpoint halt.
+    pub data_toggle: bool,
+}
+
+impl Pipe {
+    /// Create a control pipe to endpoint 0 of device at `address`.
+    #[inline]
+    pub const fn control(address: u8, speed: DeviceSpeed, max_packet_size: u16) -> Self {
+        Self {
+            address,
+            endpoint: 0,
+            direction: Direction::Out,
+            transfer_type: TransferType::Control,
+            max_packet_size,
+            interval: 0,
+            speed,
+            data_toggle: false,
+        }
+    }
+    /// Get polling interval of interrupt pipe.
+    ///
+    /// Interval is in frames of 1 ms for full and low-speed devices, and 2^(interval-1)
+    /// microframes of 125 µs for high-speed devices.
+    #[inline]
+    pub const fn polling_interval(&self) -> Duration {
+        match self.speed {
+            DeviceSpeed::High => {
+                let exponent = match self.interval {
+                    0 => 0,
+                    1..=16 => self.interval - 1,
+                    _ => 15,
+                };
+                Duration::from_micros(125 << exponent)
+            }
+            _ => Duration::from_millis(self.interval as u64),
+        }
+    }
+}
+
+/// Setup packet of a control transfer.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct SetupPacket {
+    /// Direction, type and recipient of request, `bmRequestType`.
+    pub request_type: u8,
+    /// Request code, `bRequest`.
+    pub request: u8,
+    /// Request value, `wValue`.
+    pub value: u16,
+    /// Request index, `wIndex`.
+    pub index: u16,
+    /// Length of data stage, `wLength`.
+    pub length: u16,
+}
+
+/// Standard descriptor types.
+pub mod descriptor_type {
+    /// Device descriptor.
+    pub const DEVICE: u8 = 1;
+    /// Configuration descriptor.
+    pub const CONFIGURATION: u8 = 2;
+    /// String descriptor.
+    pub const STRING: u8 = 3;
+    /// Interface descriptor.
+    pub const INTERFACE: u8 = 4;
+    /// Endpoint descriptor.
+    pub const ENDPOINT: u8 = 5;
+}
+
+impl SetupPacket {
+    const GET_DESCRIPTOR: u8 = 6;
+    const SET_ADDRESS: u8 = 5;
+    const SET_CONFIGURATION: u8 = 9;
+    const CLEAR_FEATURE: u8 = 1;
+
+    /// Standard `GET_DESCRIPTOR` request from device.
+    #[inline]
+    pub const fn get_descriptor(descriptor_type: u8, index: u8, length: u16) -> Self {
+        Self {
+            request_type: 0x80,
+            request: Self::GET_DESCRIPTOR,
+            value: ((descriptor_type as u16) << 8) | index as u16,
+            index: 0,
+            length,
+        }
+    }
+    /// Standard `SET_ADDRESS` request to device.
+    #[inline]
+    pub const fn set_address(address: u8) -> Self {
+        Self {
+            request_type: 0x00,
+            request: Self::SET_ADDRESS,
+            value: address as u16,
+            index: 0,
+            length: 0,
+        }
+    }
+    /// Standard `SET_CONFIGURATION` request to device.
+    #[inline]
+    pub const fn set_configuration(configuration: u8) -> Self {
+        Self {
+            request_type: 0x00,
+            request: Self::SET_CONFIGURATION,
+            value: configuration as u16,
+            index: 0,
+            length: 0,
+        }
+    }
+    /// Standard `CLEAR_FEATURE(ENDPOINT_HALT)` request to endpoint at `endpoint_address`.
+    #[inline]
+    pub const fn clear_halt(endpoint_address: u8) -> Self {
+        Self {
+            request_type: 0x02,
+            request: Self::CLEAR_FEATURE,
+            value: 0,
+            index: endpoint_address as u16,
+            length: 0,
+        }
+    }
+    /// Check if data stage goes from device to host.
+    #[inline]
+    pub const fn is_in(&self) -> bool {
+        self.request_type & 0x80 != 0
+    }
+    /// Encode into the 8-byte packet sent on the bus.
+    #[inline]
+    pub const fn to_bytes(&self) -> [u8; 8] {
+        let [v0, v1] = self.value.to_le_bytes();
+        let [i0, i1] = self.index.to_le_bytes();
+        let [l0, l1] = self.length.to_le_bytes();
+        [self.request_type, self.request, v0, v1, i0, i1, l0, l1]
+    }
+}
+
+/// USB host controller with one root port.
+pub trait Host {
+    /// Check if a device is attached to the root port.
+    fn is_connected(&mut self) -> bool;
+    /// Reset the root port, returning speed of attached device.
+    fn reset_port(&mut self) -> Result<DeviceSpeed, HostError>;
+    /// Run a control transfer with data stage from device, returning bytes received.
+    fn control_in(
+        &mut self,
+        pipe: &mut Pipe,
+        setup: &SetupPacket,
+        data: &mut [u8],
+    ) -> Result<usize, HostError>;
+    /// Run a control transfer with data stage to device, or without data stage.
+    fn control_out(
+        &mut self,
+        pipe: &mut Pipe,
+        setup: &SetupPacket,
+        data: &[u8],
+    ) -> Result<(), HostError>;
+    /// Receive from bulk or interrupt IN pipe, until a short packet or `data` is full.
+    ///
+    /// Interrupt pipes return [`HostError::Nak`] if device had no data for about one
+    /// polling interval.
+    fn transfer_in(&mut self, pipe: &mut Pipe, data: &mut [u8]) -> Result<usize, HostError>;
+    /// Send `data` to bulk or interrupt OUT pipe, returning bytes sent.
+    ///
+    /// Empty `data` sends one zero-length packet.
+    fn transfer_out(&mut self, pipe: &mut Pipe, data: &[u8]) -> Result<usize, HostError>;
+}
+
+/// Standard device descriptor.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct DeviceDescriptor {
+    /// USB specification release in BCD, `bcdUSB`.
+    pub usb_release: u16,
+    /// Device class.
+    pub class: u8,
+    /// Device sub-class.
+    pub sub_class: u8,
+    /// Device protocol.
+    pub protocol: u8,
+    /// Maximum packet size of endpoint 0.
+    pub max_packet_size0: u8,
+    /// Vendor ID.
+    pub vendor_id: u16,
+    /// Product ID.
+    pub product_id: u16,
+    /// Device release in BCD, `bcdDevice`.
+    pub device_release: u16,
+    /// Index of manufacturer string.
+    pub manufacturer: u8,
+    /// Index of product string.
+    pub product: u8,
+    /// Index of serial number string.
+    pub serial_number: u8,
+    /// Number of configurations.
+    pub num_configurations: u8,
+}
+
+impl DeviceDescriptor {
+    /// Length of device descriptor.
+    pub const LENGTH: usize = 18;
+
+    /// Parse a device descriptor.
+    #[inline]
+    pub fn parse(bytes: &[u8]) -> Option<Self> {
+        match bytes {
+            [
+                18,
+                descriptor_type::DEVICE,
+                u0,
+                u1,
+                class,
+                sub_class,
+                protocol,
+                mps,
+                v0,
+                v1,
+                p0,
+                p1,
+                d0,
+                d1,
+                manufacturer,
+                product,
+                serial_number,
+                num_configurations,
+                ..,
+            ] => Some(Self {
+                usb_release: u16::from_le_bytes([*u0, *u1]),
+                class: *class,
+                sub_class: *sub_class,
+                protocol: *protocol,
+                max_packet_size0: *mps,
+                vendor_id: u16::from_le_bytes([*v0, *v1]),
+                product_id: u16::from_le_bytes([*p0, *p1]),
+                device_release: u16::from_le_bytes([*d0, *d1]),
+                manufacturer: *manufacturer,
+                product: *product,
+                serial_number: *serial_number,
+                num_configurations: *num_configurations,
+            }),
+            _ => None,
+        }
+    }
+}
+
+/// Standard interface descriptor.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct InterfaceDescriptor {
+    /// Interface number.
+    pub number: u8,
+    /// Alternate setting.
+    pub alternate_setting: u8,
+    /// Number of endpoints besides endpoint 0.
+    pub num_endpoints: u8,
+    /// Interface class.
+    pub class: u8,
+    /// Interface sub-class.
+    pub sub_class: u8,
+    /// Interface protocol.
+    pub protocol: u8,
+}
+
+/// Standard endpoint descriptor.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct EndpointDescriptor {
+    /// Endpoint address, with direction in bit 7.
+    pub address: u8,
+    /// Endpoint attributes, with transfer type in bits 1:0.
+    pub attributes: u8,
+    /// Maximum packet size.
+    pub max_packet_size: u16,
+    /// Polling interval.
+    pub interval: u8,
+}
+
+impl EndpointDescriptor {
+    /// Get endpoint number.
+    #[inline]
+    pub const fn number(&self) -> u8 {
+        self.address & 0x0F
+    }
+    /// Get transfer direction.
+    #[inline]
+    pub const fn direction(&self) -> Direction {
+        if self.address & 0x80 != 0 {
+            Direction::In
+        } else {
+            Direction::Out
+        }
+    }
+    /// Get transfer type.
+    #[inline]
+    pub const fn transfer_type(&self) -> TransferType {
+        match self.attributes & 0x3 {
+            0 => TransferType::Control,
+            1 => TransferType::Isochronous,
+            2 => TransferType::Bulk,
+            _ => TransferType::Interrupt,
+        }
+    }
+}
+
+/// Descriptor in a configuration descriptor set.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Descriptor<'a> {
+    /// Configuration descriptor.
+    Configuration {
+        /// Configuration value for `SET_CONFIGURATION`.
+        value: u8,
+        /// Number of interfaces.
+        num_interfaces: u8,
+    },
+    /// Interface descriptor.
+    Interface(InterfaceDescriptor),
+    /// Endpoint descriptor.
+    Endpoint(EndpointDescriptor),
+    /// Class-specific or other descriptor, with its type and whole bytes.
+    Other(u8, &'a [u8]),
+}
+
+/// Iterator over descriptors of a configuration descriptor set.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Descriptors<'a> {
+    bytes: &'a [u8],
+}
+
+impl<'a> Descriptors<'a> {
+    /// Iterate descriptors in `bytes`, stopping at the first truncated descriptor.
+    #[inline]
+    pub const fn new(bytes: &'a [u8]) -> Self {
+        Self { bytes }
+    }
+}
+
+impl<'a> Iterator for Descriptors<'a> {
+    type Item = Descriptor<'a>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let len = *self.bytes.first()? as usize;
+        if len < 2 || len > self.bytes.len() {
+            return None;
+        }
+        let (bytes, rest) = self.bytes.split_at(len);
+        self.bytes = rest;
+        Some(match *bytes {
+            [
+                9,
+                descriptor_type::CONFIGURATION,
+                _,
+                _,
+                num_interfaces,
+                value,
+                ..,
+            ] => Descriptor::Configuration {
+                value,
+                num_interfaces,
+            },
+            [
+                9,
+                descriptor_type::INTERFACE,
+                number,
+                alternate_setting,
+                num_endpoints,
+                class,
+                sub_class,
+                protocol,
+                ..,
+            ] => Descriptor::Interface(InterfaceDescriptor {
+                number,
+                alternate_setting,
+                num_endpoints,
+                class,
+                sub_class,
+                protocol,
+            }),
+            [
+                _,
+                descriptor_type::ENDPOINT,
+                address,
+                attributes,
+                m0,
+                m1,
+                interval,
+                ..,
+            ] => Descriptor::Endpoint(EndpointDescriptor {
+                address,
+                attributes,
+                max_packet_size: u16::from_le_bytes([m0, m1]) & 0x7FF,
+                interval,
+            }),
+            _ => Descriptor::Other(bytes[1], bytes),
+        })
+    }
+}
+
+/// Device enumerated by [`enumerate`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Device {
+    /// Assigned device address.
+    pub address: u8,
+    /// Device speed.
+    pub speed: DeviceSpeed,
+    /// Device descriptor.
+    pub descriptor: DeviceDescriptor,
+    /// Selected configuration value.
+    pub configuration: u8,
+}
+
+impl Device {
+    /// Create a control pipe to endpoint 0.
+    #[inline]
+    pub const fn control_pipe(&self) -> Pipe {
+        Pipe::control(
+            self.address,
+            self.speed,
+            self.descriptor.max_packet_size0 as u16,
+        )
+    }
+    /// Create a pipe to endpoint described by `endpoint`.
+    #[inline]
+    pub const fn pipe(&self, endpoint: &EndpointDescriptor) -> Pipe {
+        Pipe {
+            address: self.address,
+            endpoint: endpoint.number(),
+            direction: endpoint.direction(),
+            transfer_type: endpoint.transfer_type(),
+            max_packet_size: endpoint.max_packet_size,
+            interval: endpoint.interval,
+            speed: self.speed,
+            data_toggle: false,
+        }
+    }
+}
+
+/// Reset the root port and enumerate attached device at `address`, from 1 to 127.
+///
+/// Configuration descriptor set of the first configuration is read into `config`, which
+/// should hold at least 9 bytes; it is truncated if longer than `config`. Returns the device
+/// with the first configuration selected, and number of bytes read into `config`.
+pub fn enumerate<H: Host>(
+    host: &mut H,
+    address: u8,
+    config: &mut [u8],
+) -> Result<(Device, usize), HostError> {
+    let speed = host.reset_port()?;
+    // Low-speed devices have 8-byte endpoint 0; others take the first 8 bytes regardless.
+    let mut pipe = Pipe::control(0, speed, 8);
+    let mut bytes = [0; DeviceDescriptor::LENGTH];
+    let setup = SetupPacket::get_descriptor(descriptor_type::DEVICE, 0, 8);
+    if host.control_in(&mut pipe, &setup, &mut bytes[..8])? < 8 {
+        return Err(HostError::InvalidDescriptor);
+    }
+    pipe.max_packet_size = bytes[7] as u16;
+    host.control_out(&mut pipe, &SetupPacket::set_address(address), &[])?;
+    // Device may take up to 2 ms to switch to the new address.
+    wait(Duration::from_millis(2));
+    pipe.address = address;
+    let setup = SetupPacket::get_descriptor(descriptor_type::DEVICE, 0, bytes.len() as u16);
+    let len = host.control_in(&mut pipe, &setup, &mut bytes)?;
+    let descriptor = DeviceDescriptor::parse(&bytes[..len]).ok_or(HostError::InvalidDescriptor)?;
+    let setup = SetupPacket::get_descriptor(descriptor_type::CONFIGURATION, 0, 9);
+    if config.len() < 9 || host.control_in(&mut pipe, &setup, &mut config[..9])? < 9 {
+        return Err(HostError::InvalidDescriptor);
+    }
+    let total = u16::from_le_bytes([config[2], config[3]]) as usize;
+    let configuration = config[5];
+    let len = total.min(config.len());
+    let setup = SetupPacket::get_descriptor(descriptor_type::CONFIGURATION, 0, len as u16);
+    let len = host.control_in(&mut pipe, &setup, &mut config[..len])?;
+    host.control_out(
+        &mut pipe,
+        &SetupPacket::set_configuration(configuration),
+        &[],
+    )?;
+    let device = Device {
+        address,
+        speed,
+        descriptor,
+        configuration,
+    };
+    Ok((device, len))
+}
+
+/// Busy-wait for `duration`, for bus timings of host controllers.
+#[inline]
+pub(crate) fn wait(duration: Duration) {
+    let deadline = Deadline::at(Instant::now() + duration);
+    while !deadline.is_expired() {
+        core::hint::spin_loop()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        Descriptor, Descriptors, DeviceDescriptor, DeviceSpeed, Direction, Duration,
+        EndpointDescriptor, InterfaceDescriptor, Pipe, SetupPacket, TransferType, descriptor_type,
+    };
+
+    #[test]
+    fn struct_setup_packet_functions() {
+        let setup = SetupPacket::get_descriptor(descriptor_type::CONFIGURATION, 0, 0x109);
+        assert!(setup.is_in());
+        assert_eq!(
+            setup.to_bytes(),
+            [0x80, 0x06, 0x00, 0x02, 0x00, 0x00, 0x09, 0x01]
+        );
+        let setup = SetupPacket::set_address(0x12);
+        assert!(!setup.is_in());
+        assert_eq!(
+            setup.to_bytes(),
+            [0x00, 0x05, 0x12, 0x00, 0x00, 0x00, 0x00, 0x00]
+        );
+        assert_eq!(
+            SetupPacket::set_configuration(1).to_bytes(),
+            [0x00, 0x09, 0x01, 0x00, 0x00, 0x00, 0x00, 0x00]
+        );
+        assert_eq!(
+            SetupPacket::clear_halt(0x81).to_bytes(),
+            [0x02, 0x01, 0x00, 0x00, 0x81, 0x00, 0x00, 0x00]
+        );
+    }
+
+    #[test]
+    fn struct_pipe_functions() {
+        let mut pipe = Pipe::control(1, DeviceSpeed::Full, 64);
+        pipe.interval = 10;
+        assert_eq!(pipe.polling_interval(), Duration::from_millis(10));
+        pipe.speed = DeviceSpeed::High;
+        pipe.interval = 4;
+        assert_eq!(pipe.polling_interval(), Duration::from_millis(1));
+        pipe.interval = 1;
+        assert_eq!(pipe.polling_interval(), Duration::from_micros(125));
+        pipe.interval = 0;
+        assert_eq!(pipe.polling_interval(), Duration::from_micros(125));
+    }
+
+    #[test]
+    fn struct_device_descriptor_functions() {
+        let bytes = [
+            0x12, 0x01, 0x00, 0x02, 0x00, 0x00, 0x00, 0x40, 0x09, 0x12, 0x01, 0x00, 0x00, 0x01,
+            0x01, 0x02, 0x03, 0x01,
+        ];
+        let val = DeviceDescriptor::parse(&bytes).unwrap();
+        assert_eq!(val.usb_release, 0x0200);
+        assert_eq!(val.max_packet_size0, 64);
+        assert_eq!(val.vendor_id, 0x1209);
+        assert_eq!(val.product_id, 0x0001);
+        assert_eq!(val.device_release, 0x0100);
+        assert_eq!(
+            (val.manufacturer, val.product, val.serial_number),
+            (1, 2, 3)
+        );
+        assert_eq!(val.num_configurations, 1);
+        assert_eq!(DeviceDescriptor::parse(&bytes[..8]), None);
+    }
+
+    #[test]
+    fn struct_descriptors_functions() {
+        // HID boot keyboard: configuration, interface, HID class descriptor, endpoint.
+        let bytes = [
+            0x09, 0x02, 0x22, 0x00, 0x01, 0x01, 0x00, 0xA0, 0x32, 0x09, 0x04, 0x00, 0x00, 0x01,
+            0x03, 0x01, 0x01, 0x00, 0x09, 0x21, 0x11, 0x01, 0x00, 0x01, 0x22, 0x3F, 0x00, 0x07,
+            0x05, 0x81, 0x03, 0x08, 0x00, 0x0A,
+        ];
+        let mut iter = Descriptors::new(&bytes);
+        assert_eq!(
+            iter.next(),
+            Some(Descriptor::Configuration {
+                value: 1,
+                num_interfaces: 1
+            })
+        );
+        assert_eq!(
+            iter.next(),
+            Some(Descriptor::Interface(InterfaceDescriptor {
+                number: 0,
+                alternate_setting: 0,
+                num_endpoints: 1,
+                class: 3,
+                sub_class: 1,
+                protocol: 1,
+            }))
+        );
+        assert_eq!(iter.next(), Some(Descriptor::Other(0x21, &bytes[18..27])));
+        let endpoint = EndpointDescriptor {
+            address: 0x81,
+            attributes: 0x03,
+            max_packet_size: 8,
+            interval: 10,
+        };
+        assert_eq!(iter.next(), Some(Descriptor::Endpoint(endpoint)));
+        assert_eq!(iter.next(), None);
+        assert_eq!(endpoint.number(), 1);
+        assert_eq!(endpoint.direction(), Direction::In);
+        assert_eq!(endpoint.transfer_type(), TransferType::Interrupt);
+        assert_eq!(Descriptors::new(&bytes[..30]).count(), 3);
+        assert_eq!(Descriptors::new(&[0x00, 0x02]).next(), None);
+    }
+}
@@ -0,0 +1,617 @@
+use crate::smhc::{Block, BlockDevice};
+use usb_device::{
+    bus::{self, InterfaceNumber, UsbBusAllocator},
+    class::{ControlIn, ControlOut, UsbClass},
+    control::{Recipient, RequestType},
+    descriptor::DescriptorWriter,
+    endpoint::{EndpointIn, EndpointOut},
+};
+
+const USB_CLASS_MSC: u8 = 0x08;
+const MSC_SUBCLASS_SCSI: u8 = 0x06;
+const MSC_PROTOCOL_BULK_ONLY: u8 = 0x50;
+
+const REQ_GET_MAX_LUN: u8 = 0xFE;
+const REQ_BULK_ONLY_RESET: u8 = 0xFF;
+
+const CBW_SIGNATURE: u32 = 0x4342_5355;
+const CSW_SIGNATURE: u32 = 0x5342_5355;
+const CBW_LEN: usize = 31;
+
+// Command status of command status wrapper.
+const STATUS_PASSED: u8 = 0x00;
+const STATUS_FAILED: u8 = 0x01;
+
+// SCSI operation codes.
+const TEST_UNIT_READY: u8 = 0x00;
+const REQUEST_SENSE: u8 = 0x03;
+const INQUIRY: u8 = 0x12;
+const MODE_SENSE_6: u8 = 0x1A;
+const START_STOP_UNIT: u8 = 0x1B;
+const PREVENT_ALLOW_MEDIUM_REMOVAL: u8 = 0x1E;
+const READ_FORMAT_CAPACITIES: u8 = 0x23;
+const READ_CAPACITY_10: u8 = 0x25;
+const READ_10: u8 = 0x28;
+const WRITE_10: u8 = 0x2A;
+const VERIFY_10: u8 = 0x2F;
+const SYNCHRONIZE_CACHE_10: u8 = 0x35;
+const MODE_SENSE_10: u8 = 0x5A;
+
+/// Inquiry strings and access mode of [`MassStorage`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct StorageConfig<'a> {
+    /// Vendor identification, up to 8 ASCII characters.
+    pub vendor: &'a str,
+    /// Product identification, up to 16 ASCII characters.
+    pub product: &'a str,
+    /// Product revision, up to 4 ASCII characters.
+    pub revision: &'a str,
+    /// Reject writes from host.
+    pub read_only: bool,
+}
+
+impl Default for StorageConfig<'_> {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            vendor: "Generic",
+            product: "Mass Storage",
+            revision: "1.00",
+            read_only: false,
+        }
+    }
+}
+
+/// SCSI sense key and additional sense code of the last failed command.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+struct Sense {
+    key: u8,
+    code: u8,
+}
+
+impl Sense {
+    const NONE: Sense = Sense::new(0x00, 0x00);
+    const READ_ERROR: Sense = Sense::new(0x03, 0x11);
+    const WRITE_ERROR: Sense = Sense::new(0x03, 0x0C);
+    const INVALID_COMMAND: Sense = Sense::new(0x05, 0x20);
+    const OUT_OF_RANGE: Sense = Sense::new(0x05, 0x21);
+    const INVALID_FIELD: Sense = Sense::new(0x05, 0x24);
+    const WRITE_PROTECTED: Sense = Sense::new(0x07, 0x27);
+
+    #[inline]
+    const fn new(key: u8, code: u8) -> Self {
+        Self { key, code }
+    }
+    /// Fixed format sense data.
+    #[inline]
+    const fn to_bytes(self) -> [u8; 18] {
+        let mut ans = [0; 18];
+        ans[0] = 0x70;
+        ans[2] = self.key;
+        ans[7] = 10;
+        ans[12] = self.code;
+        ans
+    }
+}
+
+/// Command block wrapper sent by host on bulk out endpoint.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+struct CommandBlock {
+    tag: u32,
+    data_len: u32,
+    data_in: bool,
+    lun: u8,
+    command: [u8; 16],
+}
+
+impl CommandBlock {
+    /// Parse a command block wrapper, or `None` if it is not valid.
+    #[inline]
+    fn parse(bytes: &[u8]) -> Option<Self> {
+        if bytes.len() != CBW_LEN || read_u32_le(bytes, 0) != CBW_SIGNATURE {
+            return None;
+        }
+        let len = bytes[14] as usize;
+        if !(1..=16).contains(&len) {
+            return None;
+        }
+        let mut command = [0; 16];
+        command[..len].copy_from_slice(&bytes[15..15 + len]);
+        Some(Self {
+            tag: read_u32_le(bytes, 4),
+            data_len: read_u32_le(bytes, 8),
+            data_in: bytes[12] & 0x80 != 0,
+            lun: bytes[13] & 0xF,
+            command,
+        })
+    }
+}
+
+/// Command status wrapper sent to host on bulk in endpoint.
+#[inline]
+fn command_status(tag: u32, residue: u32, status: u8) -> [u8; 13] {
+    let mut ans = [0; 13];
+    ans[0..4].copy_from_slice(&CSW_SIGNATURE.to_le_bytes());
+    ans[4..8].copy_from_slice(&tag.to_le_bytes());
+    ans[8..12].copy_from_slice(&residue.to_le_bytes());
+    ans[12] = status;
+    ans
+}
+
+/// Standard inquiry data of a removable direct access device.
+#[inline]
+fn inquiry_data(config: &StorageConfig) -> [u8; 36] {
+    let mut ans = [b' '; 36];
+    // direct access block device, removable, SPC-2, response data format 2
+    ans[..8].copy_from_slice(&[0x00, 0x80, 0x04, 0x02, 31, 0x00, 0x00, 0x00]);
+    for (range, text) in [
+        (8..16, config.vendor),
+        (16..32, config.product),
+        (32..36, config.revision),
+    ] {
+        for (dst, src) in ans[range].iter_mut().zip(text.bytes()) {
+            *dst = src;
+        }
+    }
+    ans
+}
+
+#[inline]
+fn read_u32_le(bytes: &[u8], offset: usize) -> u32 {
+    u32::from_le_bytes([
+        bytes[offset],
+        bytes[offset + 1],
+        bytes[offset + 2],
+        bytes[offset + 3],
+    ])
+}
+
+#[inline]
+fn read_u32_be(bytes: &[u8], offset: usize) -> u32 {
+    u32::from_be_bytes([
+        bytes[offset],
+        bytes[offset + 1],
+        bytes[offset + 2],
+        bytes[offset + 3],
+    ])
+}
+
+#[inline]
+fn read_u16_be(bytes: &[u8], offset: usize) -> u16 {
+    u16::from_be_bytes([bytes[offset], bytes[offset + 1]])
+}
+
+/// Bulk-only transport stage.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+enum Stage {
+    /// Waiting for a command block.
+    Command,
+    /// Sending `len` response bytes from buffer, `offset` bytes sent.
+    Respond { len: usize, offset: usize },
+    /// Sending `count` blocks from `lba`, `offset` bytes of buffered block sent.
+    Read { lba: u32, count: u32, offset: usize },
+    /// Receiving `count` blocks to `lba`, `offset` bytes of buffered block received.
+    Write { lba: u32, count: u32, offset: usize },
+    /// Sending command status.
+    Status,
+}
+
+/// USB mass storage class exposing a block device as a SCSI disk over bulk-only transport.
+///
+/// Backing storage is any [`BlockDevice`], such as an SD card or eMMC driver of module
+/// [`smhc`](crate::smhc), or a flash region wrapped in
+/// [`BlockRegion`](crate::smhc::BlockRegion). Commands are served from
+/// [`UsbClass::poll`], called by `UsbDevice::poll`:
+///
+/// ```ignore
+/// let bus = UsbBusAllocator::new(UsbBus::new(p.usb0, Speed::High, &p.ccu));
+/// let mut storage = MassStorage::new(&bus, 512, card, StorageConfig::default());
+/// let mut device = UsbDeviceBuilder::new(&bus, UsbVidPid(0x1209, 0x0001)).build();
+/// loop {
+///     device.poll(&mut [&mut storage]);
+/// }
+/// ```
+pub struct MassStorage<'a, B: bus::UsbBus, D> {
+    interface: InterfaceNumber,
+    read_ep: EndpointOut<'a, B>,
+    write_ep: EndpointIn<'a, B>,
+    device: D,
+    config: StorageConfig<'a>,
+    stage: Stage,
+    tag: u32,
+    residue: u32,
+    status: u8,
+    sense: Sense,
+    buffer: Block,
+}
+
+impl<'a, B: bus::UsbBus, D: BlockDevice> MassStorage<'a, B, D> {
+    /// Allocate interface and bulk endpoints of mass storage class serving `device`.
+    ///
+    /// `max_packet_size` is 64 at full speed, or 512 at high speed.
+    #[inline]
+    pub fn new(
+        alloc: &'a UsbBusAllocator<B>,
+        max_packet_size: u16,
+        device: D,
+        config: StorageConfig<'a>,
+    ) -> Self {
+        Self {
+            interface: alloc.interface(),
+            read_ep: alloc.bulk(max_packet_size),
+            write_ep: alloc.bulk(max_packet_size),
+            device,
+            config,
+            stage: Stage::Command,
+            tag: 0,
+            residue: 0,
+            status: STATUS_PASSED,
+            sense: Sense::NONE,
+            buffer: Block::new(),
+        }
+    }
+    /// Get backing block device.
+    #[inline]
+    pub fn device(&self) -> &D {
+        &self.device
+    }
+    /// Check if a command is in progress.
+    #[inline]
+    pub fn is_busy(&self) -> bool {
+        self.stage != Stage::Command
+    }
+    /// Release backing block device.
+    #[inline]
+    pub fn free(self) -> D {
+        self.device
+    }
+    /// Run transport stages until an endpoint would block.
+    fn process(&mut self) -> usb_device::Result<()> {
+        let max_packet_size = self.write_ep.max_packet_size() as usize;
+        loop {
+            match self.stage {
+                Stage::Command => {
+                    let count = self.read_ep.read(&mut self.buffer.contents)?;
+                    match CommandBlock::parse(&self.buffer.contents[..count]) {
+                        Some(cbw) => self.execute(&cbw),
+                        None => {
+                            // Invalid command block, host recovers by bulk-only reset.
+                            self.read_ep.stall();
+                            self.write_ep.stall();
+                            return Ok(());
+                        }
+                    }
+                }
+                Stage::Respond { len, offset } if offset < len => {
+                    let end = len.min(offset + max_packet_size);
+                    let count = self.write_ep.write(&self.buffer.contents[offset..end])?;
+                    self.stage = Stage::Respond {
+                        len,
+                        offset: offset + count,
+                    };
+                }
+                Stage::Respond { len, .. } => {
+                    // Host waits for more data unless a short packet ended the transfer.
+                    if self.residue > 0 && len % max_packet_size == 0 {
+                        self.write_ep.stall();
+                    }
+                    self.stage = Stage::Status;
+                }
+                Stage::Read { count: 0, .. } => {
+                    if self.residue > 0 {
+                        self.write_ep.stall();
+                    }
+                    self.stage = Stage::Status;
+                }
+                Stage::Read { lba, count, offset } => {
+                    if offset == Block::LEN {
+                        if self.device.read_block(&mut self.buffer, lba).is_err() {
+                            self.residue += count * Block::LEN as u32;
+                            self.fail(Sense::READ_ERROR, true);
+                            continue;
+                        }
+                        self.stage = Stage::Read {
+                            lba,
+                            count,
+                            offset: 0,
+                        };
+                        continue;
+                    }
+                    let end = Block::LEN.min(offset + max_packet_size);
+                    let sent = self.write_ep.write(&self.buffer.contents[offset..end])?;
+                    self.stage = match offset + sent {
+                        Block::LEN => Stage::Read {
+                            lba: lba + 1,
+                            count: count - 1,
+                            offset: Block::LEN,
+                        },
+                        offset => Stage::Read { lba, count, offset },
+                    };
+                }
+                Stage::Write { count: 0, .. } => {
+                    if self.residue > 0 {
+                        self.read_ep.stall();
+                    }
+                    self.stage = Stage::Status;
+                }
+                Stage::Write { lba, count, offset } => {
+                    let received = self.read_ep.read(&mut self.buffer.contents[offset..])?;
+                    let offset = offset + received;
+                    if offset < Block::LEN && received < max_packet_size {
+                        // Host ended data stage early with a short packet.
+                        self.residue += count * Block::LEN as u32 - offset as u32;
+                        self.fail(Sense::INVALID_FIELD, false);
+                        continue;
+                    }
+                    if offset < Block::LEN {
+                        self.stage = Stage::Write { lba, count, offset };
+                        continue;
+                    }
+                    if self.device.write_block(&self.buffer, lba).is_err() {
+                        self.residue += (count - 1) * Block::LEN as u32;
+                        self.fail(Sense::WRITE_ERROR, false);
+                        continue;
+                    }
+                    self.stage = Stage::Write {
+                        lba: lba + 1,
+                        count: count - 1,
+                        offset: 0,
+                    };
+                }
+                Stage::Status => {
+                    self.write_ep
+                        .write(&command_status(self.tag, self.residue, self.status))?;
+                    self.stage = Stage::Command;
+                }
+            }
+        }
+    }
+    /// Start data stage of command block `cbw`.
+    fn execute(&mut self, cbw: &CommandBlock) {
+        self.tag = cbw.tag;
+        self.residue = cbw.data_len;
+        self.status = STATUS_PASSED;
+        if cbw.lun != 0 {
+            return self.fail(Sense::INVALID_FIELD, cbw.data_in);
+        }
+        let command = &cbw.command;
+        let block_count = self.device.block_count();
+        let read_only = if self.config.read_only { 0x80 } else { 0x00 };
+        match command[0] {
+            TEST_UNIT_READY
+            | START_STOP_UNIT
+            | PREVENT_ALLOW_MEDIUM_REMOVAL
+            | VERIFY_10
+            | SYNCHRONIZE_CACHE_10 => self.complete(cbw.data_in),
+            REQUEST_SENSE => {
+                let data = self.sense.to_bytes();
+                self.sense = Sense::NONE;
+                self.respond(&data, command[4] as usize, cbw);
+            }
+            INQUIRY => {
+                let data = inquiry_data(&self.config);
+                self.respond(&data, read_u16_be(command, 3) as usize, cbw);
+            }
+            MODE_SENSE_6 => {
+                let data = [3, 0x00, read_only, 0];
+                self.respond(&data, command[4] as usize, cbw);
+            }
+            MODE_SENSE_10 => {
+                let data = [0, 6, 0x00, read_only, 0, 0, 0, 0];
+                self.respond(&data, read_u16_be(command, 7) as usize, cbw);
+            }
+            READ_FORMAT_CAPACITIES => {
+                let mut data = [0, 0, 0, 8, 0, 0, 0, 0, 0x02, 0, 0, 0];
+                data[4..8].copy_from_slice(&block_count.to_be_bytes());
+                data[9..12].copy_from_slice(&(Block::LEN as u32).to_be_bytes()[1..]);
+                self.respond(&data, read_u16_be(command, 7) as usize, cbw);
+            }
+            READ_CAPACITY_10 => {
+                let mut data = [0; 8];
+                data[..4].copy_from_slice(&block_count.saturating_sub(1).to_be_bytes());
+                data[4..].copy_from_slice(&(Block::LEN as u32).to_be_bytes());
+                self.respond(&data, data.len(), cbw);
+            }
+            READ_10 | WRITE_10 => {
+                let write = command[0] == WRITE_10;
+                let lba = read_u32_be(command, 2);
+                let count = read_u16_be(command, 7) as u32;
+                let len = count as u64 * Block::LEN as u64;
+                if lba as u64 + count as u64 > block_count as u64 {
+                    return self.fail(Sense::OUT_OF_RANGE, cbw.data_in);
+                }
+                if count > 0 && (cbw.data_in == write || len > cbw.data_len as u64) {
+                    return self.fail(Sense::INVALID_FIELD, cbw.data_in);
+                }
+                if write && self.config.read_only {
+                    return self.fail(Sense::WRITE_PROTECTED, cbw.data_in);
+                }
+                self.residue = cbw.data_len - len as u32;
+                self.stage = if write {
+                    Stage::Write {
+                        lba,
+                        count,
+                        offset: 0,
+                    }
+                } else {
+                    Stage::Read {
+                        lba,
+                        count,
+                        offset: Block::LEN,
+                    }
+                };
+                if count == 0 {
+                    self.complete(cbw.data_in);
+                }
+            }
+            _ => self.fail(Sense::INVALID_COMMAND, cbw.data_in),
+        }
+    }
+    /// Send `data` to host, up to allocation length `allocated` and host data length.
+    #[inline]
+    fn respond(&mut self, data: &[u8], allocated: usize, cbw: &CommandBlock) {
+        if !cbw.data_in {
+            return self.fail(Sense::INVALID_FIELD, false);
+        }
+        let len = data.len().min(allocated).min(cbw.data_len as usize);
+        self.buffer.contents[..len].copy_from_slice(&data[..len]);
+        self.residue = cbw.data_len - len as u32;
+        self.stage = Stage::Respond { len, offset: 0 };
+    }
+    /// Complete a command without data, stalling data stage expected by host.
+    #[inline]
+    fn complete(&mut self, data_in: bool) {
+        if self.residue > 0 {
+            self.stall(data_in);
+        }
+        self.stage = Stage::Status;
+    }
+    /// Fail current command with `sense`, stalling the rest of data stage.
+    #[inline]
+    fn fail(&mut self, sense: Sense, data_in: bool) {
+        self.sense = sense;
+        self.status = STATUS_FAILED;
+        self.complete(data_in);
+    }
+    #[inline]
+    fn stall(&self, data_in: bool) {
+        if data_in {
+            self.write_ep.stall();
+        } else {
+            self.read_ep.stall();
+        }
+    }
+}
+
+impl<B: bus::UsbBus, D: BlockDevice> UsbClass<B> for MassStorage<'_, B, D> {
+    fn get_configuration_descriptors(
+        &self,
+        writer: &mut DescriptorWriter,
+    ) -> usb_device::Result<()> {
+        writer.interface(
+            self.interface,
+            USB_CLASS_MSC,
+            MSC_SUBCLASS_SCSI,
+            MSC_PROTOCOL_BULK_ONLY,
+        )?;
+        writer.endpoint(&self.write_ep)?;
+        writer.endpoint(&self.read_ep)?;
+        Ok(())
+    }
+
+    fn reset(&mut self) {
+        self.stage = Stage::Command;
+        self.sense = Sense::NONE;
+    }
+
+    fn poll(&mut self) {
+        // Endpoints would block until host moves on; other errors restart from command stage.
+        if let Err(usb_device::UsbError::WouldBlock) | Ok(()) = self.process() {
+            return;
+        }
+        self.stage = Stage::Command;
+    }
+
+    fn control_in(&mut self, xfer: ControlIn<B>) {
+        let req = *xfer.request();
+        if req.request_type != RequestType::Class
+            || req.recipient != Recipient::Interface
+            || req.index != u8::from(self.interface) as u16
+        {
+            return;
+        }
+        match req.request {
+            // Single logical unit.
+            REQ_GET_MAX_LUN => {
+                xfer.accept_with(&[0]).ok();
+            }
+            _ => {
+                xfer.reject().ok();
+            }
+        }
+    }
+
+    fn control_out(&mut self, xfer: ControlOut<B>) {
+        let req = *xfer.request();
+        if req.request_type != RequestType::Class
+            || req.recipient != Recipient::Interface
+            || req.index != u8::from(self.interface) as u16
+        {
+            return;
+        }
+        match req.request {
+            REQ_BULK_ONLY_RESET => {
+                self.stage = Stage::Command;
+                xfer.accept().ok();
+            }
+            _ => {
+                xfer.reject().ok();
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{CommandBlock, Sense, StorageConfig, command_status, inquiry_data};
+
+    #[test]
+    fn struct_command_block_functions() {
+        // READ(10) of 8 blocks from block 0x1234, tag 0xAABBCCDD, 4096 bytes to host.
+        let mut bytes = [0; 31];
+        bytes[..15].copy_from_slice(&[
+            0x55, 0x53, 0x42, 0x43, 0xDD, 0xCC, 0xBB, 0xAA, 0x00, 0x10, 0x00, 0x00, 0x80, 0x00,
+            0x0A,
+        ]);
+        bytes[15..25].copy_from_slice(&[0x28, 0, 0x00, 0x00, 0x12, 0x34, 0, 0x00, 0x08, 0]);
+        let cbw = CommandBlock::parse(&bytes).unwrap();
+        assert_eq!(cbw.tag, 0xAABBCCDD);
+        assert_eq!(cbw.data_len, 4096);
+        assert!(cbw.data_in);
+        assert_eq!(cbw.lun, 0);
+        assert_eq!(cbw.command[0], 0x28);
+        assert_eq!(cbw.command[10], 0);
+
+        assert_eq!(CommandBlock::parse(&bytes[..30]), None);
+        bytes[14] = 17;
+        assert_eq!(CommandBlock::parse(&bytes), None);
+        bytes[14] = 10;
+        bytes[0] = 0x54;
+        assert_eq!(CommandBlock::parse(&bytes), None);
+    }
+
+    #[test]
+    fn function_command_status() {
+        assert_eq!(
+            command_status(0xAABBCCDD, 0x200, 1),
+            [
+                0x55, 0x53, 0x42, 0x53, 0xDD, 0xCC, 0xBB, 0xAA, 0x00, 0x02, 0x00, 0x00, 0x01
+            ]
+        );
+    }
+
+    #[test]
+    fn function_inquiry_data() {
+        let data = inquiry_data(&StorageConfig::default());
+        assert_eq!(&data[..5], &[0x00, 0x80, 0x04, 0x02, 31]);
+        assert_eq!(&data[8..16], b"Generic ");
+        assert_eq!(&data[16..32], b"Mass Storage    ");
+        assert_eq!(&data[32..36], b"1.00");
+
+        let config = StorageConfig {
+            vendor: "VeryLongVendor",
+            ..StorageConfig::default()
+        };
+        assert_eq!(&inquiry_data(&config)[8..16], b"VeryLong");
+    }
+
+    #[test]
+    fn struct_sense_functions() {
+        let data = Sense::OUT_OF_RANGE.to_bytes();
+        assert_eq!(data[0], 0x70);
+        assert_eq!(data[2], 0x05);
+        assert_eq!(data[7], 10);
+        assert_eq!(data[12], 0x21);
+        assert_eq!(Sense::NONE.to_bytes()[2], 0);
+    }
+}
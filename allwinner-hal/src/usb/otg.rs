@@ -0,0 +1,240 @@
+use embedded_hal::digital::{InputPin, OutputPin};
+
+/// Role of USB0 OTG port.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Role {
+    /// Host, supplying VBUS to the attached device.
+    Host,
+    /// Peripheral, powered by or sensing VBUS of the attached host.
+    Device,
+}
+
+/// VBUS supply switch of a USB port.
+pub trait VbusDrive {
+    /// Error of switching VBUS.
+    type Error;
+    /// Switch VBUS supply on or off.
+    fn set_vbus(&mut self, enabled: bool) -> Result<(), Self::Error>;
+}
+
+/// VBUS presence sensing of a USB port.
+pub trait VbusSense {
+    /// Error of sensing VBUS.
+    type Error;
+    /// Check if VBUS is present.
+    fn is_vbus_present(&mut self) -> Result<bool, Self::Error>;
+}
+
+/// ID pin sensing of an OTG port.
+pub trait IdSense {
+    /// Error of sensing ID pin.
+    type Error;
+    /// Check if ID pin is grounded, i.e. an OTG host cable is plugged in.
+    fn is_id_grounded(&mut self) -> Result<bool, Self::Error>;
+}
+
+/// VBUS supply switch on a GPIO output pin.
+pub struct GpioVbusDrive<P> {
+    pin: P,
+    active_low: bool,
+}
+
+impl<P: OutputPin> GpioVbusDrive<P> {
+    /// Create a VBUS switch enabled on high level of `pin`.
+    #[inline]
+    pub fn new(pin: P) -> Self {
+        Self {
+            pin,
+            active_low: false,
+        }
+    }
+    /// Create a VBUS switch enabled on low level of `pin`.
+    #[inline]
+    pub fn new_active_low(pin: P) -> Self {
+        Self {
+            pin,
+            active_low: true,
+        }
+    }
+    /// Release output pin.
+    #[inline]
+    pub fn free(self) -> P {
+        self.pin
+    }
+}
+
+impl<P: OutputPin> VbusDrive for GpioVbusDrive<P> {
+    type Error = P::Error;
+    #[inline]
+    fn set_vbus(&mut self, enabled: bool) -> Result<(), Self::Error> {
+        if enabled != self.active_low {
+            self.pin.set_high()
+        } else {
+            self.pin.set_low()
+        }
+    }
+}
+
+/// VBUS sensing on a GPIO input pin, high when VBUS is present.
+pub struct GpioVbusSense<P> {
+    pin: P,
+}
+
+impl<P: InputPin> GpioVbusSense<P> {
+    /// Create VBUS sensing on `pin`.
+    #[inline]
+    pub fn new(pin: P) -> Self {
+        Self { pin }
+    }
+    /// Release input pin.
+    #[inline]
+    pub fn free(self) -> P {
+        self.pin
+    }
+}
+
+impl<P: InputPin> VbusSense for GpioVbusSense<P> {
+    type Error = P::Error;
+    #[inline]
+    fn is_vbus_present(&mut self) -> Result<bool, Self::Error> {
+        self.pin.is_high()
+    }
+}
+
+/// ID pin sensing on a GPIO input pin, which should be pulled up.
+pub struct GpioIdSense<P> {
+    pin: P,
+}
+
+impl<P: InputPin> GpioIdSense<P> {
+    /// Create ID sensing on `pin`.
+    #[inline]
+    pub fn new(pin: P) -> Self {
+        Self { pin }
+    }
+    /// Release input pin.
+    #[inline]
+    pub fn free(self) -> P {
+        self.pin
+    }
+}
+
+impl<P: InputPin> IdSense for GpioIdSense<P> {
+    type Error = P::Error;
+    #[inline]
+    fn is_id_grounded(&mut self) -> Result<bool, Self::Error> {
+        self.pin.is_low()
+    }
+}
+
+/// Error of OTG role switching.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum OtgError<I, V> {
+    /// Error sensing ID pin.
+    Id(I),
+    /// Error switching VBUS.
+    Vbus(V),
+}
+
+/// Runtime role switching of USB0 OTG port from its ID pin.
+///
+/// [`poll`](Self::poll) reports a new role when the ID pin changes, switching VBUS on for
+/// host role and off for device role. Caller then frees the driver of the old role and
+/// creates [`UsbHost`](super::UsbHost) or [`UsbBus`](super::UsbBus) for the new one:
+///
+/// ```ignore
+/// let mut otg = RoleSwitch::new(GpioIdSense::new(id), GpioVbusDrive::new(vbus));
+/// loop {
+///     match otg.poll()? {
+///         Some(Role::Host) => { /* free UsbBus, create UsbHost */ }
+///         Some(Role::Device) => { /* free UsbHost, create UsbBus */ }
+///         None => {}
+///     }
+/// }
+/// ```
+pub struct RoleSwitch<ID, VBUS> {
+    id: ID,
+    vbus: VBUS,
+    role: Option<Role>,
+}
+
+impl<ID: IdSense, VBUS: VbusDrive> RoleSwitch<ID, VBUS> {
+    /// Create role switching with no role selected yet.
+    #[inline]
+    pub fn new(id: ID, vbus: VBUS) -> Self {
+        Self {
+            id,
+            vbus,
+            role: None,
+        }
+    }
+    /// Get current role, or `None` before the first poll.
+    #[inline]
+    pub fn role(&self) -> Option<Role> {
+        self.role
+    }
+    /// Sense ID pin and switch VBUS for its role, returning the role if it changed.
+    #[inline]
+    pub fn poll(&mut self) -> Result<Option<Role>, OtgError<ID::Error, VBUS::Error>> {
+        let role = match self.id.is_id_grounded().map_err(OtgError::Id)? {
+            true => Role::Host,
+            false => Role::Device,
+        };
+        if self.role == Some(role) {
+            return Ok(None);
+        }
+        self.vbus
+            .set_vbus(role == Role::Host)
+            .map_err(OtgError::Vbus)?;
+        self.role = Some(role);
+        Ok(Some(role))
+    }
+    /// Switch VBUS off and release ID sensing and VBUS switch.
+    #[inline]
+    pub fn free(mut self) -> Result<(ID, VBUS), OtgError<ID::Error, VBUS::Error>> {
+        self.vbus.set_vbus(false).map_err(OtgError::Vbus)?;
+        Ok((self.id, self.vbus))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{IdSense, Role, RoleSwitch, VbusDrive};
+    use core::convert::Infallible;
+
+    struct MockId(bool);
+
+    impl IdSense for MockId {
+        type Error = Infallible;
+        fn is_id_grounded(&mut self) -> Result<bool, Infallible> {
+            Ok(self.0)
+        }
+    }
+
+    struct MockVbus(Option<bool>);
+
+    impl VbusDrive for MockVbus {
+        type Error = Infallible;
+        fn set_vbus(&mut self, enabled: bool) -> Result<(), Infallible> {
+            self.0 = Some(enabled);
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn struct_role_switch_functions() {
+        let mut otg = RoleSwitch::new(MockId(false), MockVbus(None));
+        assert_eq!(otg.role(), None);
+        assert_eq!(otg.poll(), Ok(Some(Role::Device)));
+        assert_eq!(otg.vbus.0, Some(false));
+        assert_eq!(otg.poll(), Ok(None));
+
+        otg.id.0 = true;
+        assert_eq!(otg.poll(), Ok(Some(Role::Host)));
+        assert_eq!(otg.role(), Some(Role::Host));
+        assert_eq!(otg.vbus.0, Some(true));
+
+        let (_, vbus) = otg.free().unwrap();
+        assert_eq!(vbus.0, Some(false));
+    }
+}
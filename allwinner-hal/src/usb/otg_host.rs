@@ -0,0 +1,393 @@
+use super::{
+    DeviceControl, FifoSize, HostCsr0, HostRxCsr, HostTxCsr, Iscr, Power, RegisterBlock,
+    TargetType, close,
+    host::{DeviceSpeed, Direction, Host, HostError, Pipe, SetupPacket, TransferType, wait},
+    open, read_fifo, write_fifo,
+};
+use crate::{
+    ccu,
+    time::{Deadline, Duration},
+};
+
+// Hardware endpoint used for bulk and interrupt pipes, with one FIFO for each direction.
+const PIPE_EP: usize = 1;
+const PIPE_FIFO_SIZE: u16 = 512;
+const PIPE_TX_FIFO_ADDRESS: u16 = 64;
+const PIPE_RX_FIFO_ADDRESS: u16 = PIPE_TX_FIFO_ADDRESS + PIPE_FIFO_SIZE;
+
+/// Managed USB OTG controller in host mode.
+///
+/// Control transfers run on endpoint 0, and bulk and interrupt pipes share hardware endpoint 1
+/// one transfer at a time, so pipes of at most 512-byte packets are supported.
+pub struct UsbHost<USB> {
+    usb: USB,
+}
+
+impl<USB: AsRef<RegisterBlock>> UsbHost<USB> {
+    /// Create a USB host instance and start a session on the root port.
+    ///
+    /// USB0 PHY is routed to OTG controller, with ID forced to host mode and VBUS forced
+    /// valid; the board should turn on its VBUS supply, usually by a GPIO pin, before devices
+    /// would attach.
+    #[inline]
+    pub fn new(usb: USB, ccu: &ccu::RegisterBlock) -> Self {
+        let regs = usb.as_ref();
+        open(regs, ccu, Iscr::force_host);
+        unsafe {
+            regs.power.write(Power::default().enable_high_speed());
+            regs.index.write(PIPE_EP as u8);
+            regs.tx_fifo_size
+                .write(FifoSize::default().set_size(PIPE_FIFO_SIZE));
+            regs.tx_fifo_address.write(PIPE_TX_FIFO_ADDRESS / 8);
+            regs.rx_fifo_size
+                .write(FifoSize::default().set_size(PIPE_FIFO_SIZE));
+            regs.rx_fifo_address.write(PIPE_RX_FIFO_ADDRESS / 8);
+            regs.index.write(0);
+            regs.device_control
+                .write(DeviceControl::default().start_session());
+        }
+        Self { usb }
+    }
+    /// End session, close USB OTG controller and release peripheral.
+    #[inline]
+    pub fn free(self, ccu: &ccu::RegisterBlock) -> USB {
+        let regs = self.usb.as_ref();
+        unsafe { regs.device_control.modify(|val| val.end_session()) };
+        close(regs, ccu);
+        self.usb
+    }
+    #[inline]
+    fn select(&self, index: usize) {
+        unsafe { self.usb.as_ref().index.write(index as u8) };
+    }
+    /// Send setup stage on endpoint 0 to device of `pipe`.
+    fn setup(&mut self, pipe: &Pipe, setup: &SetupPacket) -> Result<(), HostError> {
+        if !self.is_connected() {
+            return Err(HostError::NotConnected);
+        }
+        if pipe.max_packet_size == 0 || pipe.max_packet_size > 64 {
+            return Err(HostError::Unsupported);
+        }
+        let regs = self.usb.as_ref();
+        self.select(0);
+        unsafe {
+            regs.function_address.write(pipe.address);
+            regs.tx_hub_address.write(0);
+            regs.tx_hub_port.write(0);
+            regs.tx_type
+                .write(TargetType::default().set_speed(pipe.speed));
+            // Disable NAK limit; a device keeping NAK is caught by blocking timeout.
+            regs.tx_interval.write(0);
+        }
+        write_fifo(regs, 0, &setup.to_bytes());
+        let csr = HostCsr0::default().set_setup_packet().set_tx_packet_ready();
+        unsafe { regs.csr.write(csr.bits()) };
+        wait_ep0(regs, |csr| !csr.is_tx_packet_ready())
+    }
+    /// Configure hardware pipe endpoint for `pipe`.
+    fn open_pipe(&self, pipe: &Pipe) -> Result<(), HostError> {
+        if !matches!(
+            pipe.transfer_type,
+            TransferType::Bulk | TransferType::Interrupt
+        ) || pipe.max_packet_size == 0
+            || pipe.max_packet_size > PIPE_FIFO_SIZE
+        {
+            return Err(HostError::Unsupported);
+        }
+        let regs = self.usb.as_ref();
+        // Interrupt pipes are polled as bulk endpoints with NAK limit, so that an idle
+        // endpoint returns NAK timeout instead of being polled forever.
+        let target = TargetType::default()
+            .set_speed(pipe.speed)
+            .set_protocol(TransferType::Bulk)
+            .set_endpoint(pipe.endpoint);
+        let interval = match pipe.transfer_type {
+            TransferType::Interrupt => nak_limit(pipe.speed, pipe.interval),
+            _ => 0,
+        };
+        self.select(PIPE_EP);
+        unsafe {
+            match pipe.direction {
+                Direction::Out => {
+                    regs.function_address.write(pipe.address);
+                    regs.tx_hub_address.write(0);
+                    regs.tx_hub_port.write(0);
+                    regs.tx_type.write(target);
+                    regs.tx_interval.write(interval);
+                    regs.tx_max_packet.write(pipe.max_packet_size);
+                    let csr = HostTxCsr::default()
+                        .set_tx_mode()
+                        .flush_fifo()
+                        .set_data_toggle(pipe.data_toggle);
+                    regs.csr.write(csr.bits());
+                }
+                Direction::In => {
+                    regs.rx_function_address.write(pipe.address);
+                    regs.rx_hub_address.write(0);
+                    regs.rx_hub_port.write(0);
+                    regs.rx_type.write(target);
+                    regs.rx_interval.write(interval);
+                    regs.rx_max_packet.write(pipe.max_packet_size);
+                    let csr = HostRxCsr::default().set_data_toggle(pipe.data_toggle);
+                    regs.rx_csr.write(csr.bits());
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<USB: AsRef<RegisterBlock>> Host for UsbHost<USB> {
+    #[inline]
+    fn is_connected(&mut self) -> bool {
+        let devctl = self.usb.as_ref().device_control.read();
+        devctl.is_full_speed_device() || devctl.is_low_speed_device()
+    }
+    fn reset_port(&mut self) -> Result<DeviceSpeed, HostError> {
+        if !self.is_connected() {
+            return Err(HostError::NotConnected);
+        }
+        let regs = self.usb.as_ref();
+        unsafe { regs.power.modify(|val| val.start_reset()) };
+        wait(Duration::from_millis(50));
+        unsafe { regs.power.modify(|val| val.stop_reset()) };
+        // Reset recovery time before the first transfer.
+        wait(Duration::from_millis(10));
+        if !self.is_connected() {
+            return Err(HostError::NotConnected);
+        }
+        let regs = self.usb.as_ref();
+        Ok(if regs.power.read().is_high_speed() {
+            DeviceSpeed::High
+        } else if regs.device_control.read().is_low_speed_device() {
+            DeviceSpeed::Low
+        } else {
+            DeviceSpeed::Full
+        })
+    }
+    fn control_in(
+        &mut self,
+        pipe: &mut Pipe,
+        setup: &SetupPacket,
+        data: &mut [u8],
+    ) -> Result<usize, HostError> {
+        self.setup(pipe, setup)?;
+        let regs = self.usb.as_ref();
+        let length = data.len().min(setup.length as usize);
+        let mut received = 0;
+        while received < length {
+            let csr = HostCsr0::default().set_request_packet();
+            unsafe { regs.csr.write(csr.bits()) };
+            wait_ep0(regs, HostCsr0::is_rx_packet_ready)?;
+            let count = regs.count.read() as usize;
+            if received + count > data.len() {
+                unsafe { regs.csr.write(HostCsr0::default().flush_fifo().bits()) };
+                return Err(HostError::BufferOverflow);
+            }
+            read_fifo(regs, 0, &mut data[received..received + count]);
+            unsafe { regs.csr.write(HostCsr0::default().bits()) };
+            received += count;
+            if count < pipe.max_packet_size as usize {
+                break;
+            }
+        }
+        let csr = HostCsr0::default()
+            .set_status_packet()
+            .set_tx_packet_ready();
+        unsafe { regs.csr.write(csr.bits()) };
+        wait_ep0(regs, |csr| !csr.is_tx_packet_ready())?;
+        unsafe { regs.csr.write(HostCsr0::default().bits()) };
+        Ok(received)
+    }
+    fn control_out(
+        &mut self,
+        pipe: &mut Pipe,
+        setup: &SetupPacket,
+        data: &[u8],
+    ) -> Result<(), HostError> {
+        self.setup(pipe, setup)?;
+        let regs = self.usb.as_ref();
+        for packet in data.chunks(pipe.max_packet_size as usize) {
+            write_fifo(regs, 0, packet);
+            let csr = HostCsr0::default().set_tx_packet_ready();
+            unsafe { regs.csr.write(csr.bits()) };
+            wait_ep0(regs, |csr| !csr.is_tx_packet_ready())?;
+        }
+        let csr = HostCsr0::default().set_status_packet().set_request_packet();
+        unsafe { regs.csr.write(csr.bits()) };
+        wait_ep0(regs, HostCsr0::is_rx_packet_ready)?;
+        unsafe { regs.csr.write(HostCsr0::default().bits()) };
+        Ok(())
+    }
+    fn transfer_in(&mut self, pipe: &mut Pipe, data: &mut [u8]) -> Result<usize, HostError> {
+        if pipe.direction != Direction::In {
+            return Err(HostError::Unsupported);
+        }
+        if !self.is_connected() {
+            return Err(HostError::NotConnected);
+        }
+        self.open_pipe(pipe)?;
+        let regs = self.usb.as_ref();
+        let mut received = 0;
+        let result = loop {
+            let csr = HostRxCsr::default().set_request_packet();
+            unsafe { regs.rx_csr.write(csr.bits()) };
+            if let Err(error) = wait_rx(regs) {
+                // Packets already received of an interrupt transfer are returned as they are.
+                break match error {
+                    HostError::Nak if received > 0 => Ok(received),
+                    error => Err(error),
+                };
+            }
+            let count = regs.count.read() as usize;
+            if received + count > data.len() {
+                unsafe { regs.rx_csr.write(HostRxCsr::default().flush_fifo().bits()) };
+                break Err(HostError::BufferOverflow);
+            }
+            read_fifo(regs, PIPE_EP, &mut data[received..received + count]);
+            unsafe { regs.rx_csr.write(HostRxCsr::default().bits()) };
+            received += count;
+            if count < pipe.max_packet_size as usize || received == data.len() {
+                break Ok(received);
+            }
+        };
+        pipe.data_toggle = HostRxCsr::from_bits(regs.rx_csr.read()).data_toggle();
+        result
+    }
+    fn transfer_out(&mut self, pipe: &mut Pipe, data: &[u8]) -> Result<usize, HostError> {
+        if pipe.direction != Direction::Out {
+            return Err(HostError::Unsupported);
+        }
+        if !self.is_connected() {
+            return Err(HostError::NotConnected);
+        }
+        self.open_pipe(pipe)?;
+        let regs = self.usb.as_ref();
+        let mut sent = 0;
+        let result = loop {
+            let packet = &data[sent..data.len().min(sent + pipe.max_packet_size as usize)];
+            write_fifo(regs, PIPE_EP, packet);
+            let csr = HostTxCsr::default().set_tx_mode().set_tx_packet_ready();
+            unsafe { regs.csr.write(csr.bits()) };
+            if let Err(error) = wait_tx(regs) {
+                break Err(error);
+            }
+            sent += packet.len();
+            if sent == data.len() {
+                break Ok(sent);
+            }
+        };
+        pipe.data_toggle = HostTxCsr::from_bits(regs.csr.read()).data_toggle();
+        result
+    }
+}
+
+/// Poll `status` until it returns a result, or crate-wide blocking timeout passes.
+#[inline]
+fn poll(mut status: impl FnMut() -> Option<Result<(), HostError>>) -> Result<(), HostError> {
+    let deadline = Deadline::start();
+    loop {
+        if let Some(result) = status() {
+            return result;
+        }
+        deadline.check().map_err(|_| HostError::Timeout)?;
+    }
+}
+
+/// Wait on endpoint 0 until `done`, flushing FIFO on errors.
+fn wait_ep0(regs: &RegisterBlock, done: fn(HostCsr0) -> bool) -> Result<(), HostError> {
+    let result = poll(|| {
+        let csr = HostCsr0::from_bits(regs.csr.read());
+        if csr.is_rx_stall() {
+            Some(Err(HostError::Stall))
+        } else if csr.is_error() {
+            Some(Err(HostError::Transaction))
+        } else if csr.is_nak_timeout() {
+            Some(Err(HostError::Nak))
+        } else {
+            done(csr).then_some(Ok(()))
+        }
+    });
+    if result.is_err() {
+        unsafe { regs.csr.write(HostCsr0::default().flush_fifo().bits()) };
+    }
+    result
+}
+
+/// Wait on pipe endpoint until a packet is received, clearing request on errors.
+fn wait_rx(regs: &RegisterBlock) -> Result<(), HostError> {
+    let result = poll(|| {
+        let csr = HostRxCsr::from_bits(regs.rx_csr.read());
+        if csr.is_rx_stall() {
+            Some(Err(HostError::Stall))
+        } else if csr.is_error() {
+            Some(Err(HostError::Transaction))
+        } else if csr.is_nak_timeout() {
+            Some(Err(HostError::Nak))
+        } else {
+            csr.is_rx_packet_ready().then_some(Ok(()))
+        }
+    });
+    if result.is_err() {
+        unsafe { regs.rx_csr.write(HostRxCsr::default().bits()) };
+    }
+    result
+}
+
+/// Wait on pipe endpoint until packet is sent, flushing FIFO on errors.
+fn wait_tx(regs: &RegisterBlock) -> Result<(), HostError> {
+    let result = poll(|| {
+        let csr = HostTxCsr::from_bits(regs.csr.read());
+        if csr.is_rx_stall() {
+            Some(Err(HostError::Stall))
+        } else if csr.is_error() {
+            Some(Err(HostError::Transaction))
+        } else if csr.is_nak_timeout() {
+            Some(Err(HostError::Nak))
+        } else {
+            (!csr.is_tx_packet_ready() && !csr.is_fifo_not_empty()).then_some(Ok(()))
+        }
+    });
+    if result.is_err() {
+        let csr = HostTxCsr::default().set_tx_mode().flush_fifo();
+        unsafe { regs.csr.write(csr.bits()) };
+    }
+    result
+}
+
+/// NAK limit value covering one polling `interval` of an interrupt endpoint.
+///
+/// NAK limit is 2^(m-1) frames, or microframes in high speed, for value m from 2 to 16.
+/// High-speed endpoint intervals use the same exponent encoding, while full and low speed
+/// intervals are in frames.
+#[inline]
+fn nak_limit(speed: DeviceSpeed, interval: u8) -> u8 {
+    let m = match speed {
+        DeviceSpeed::High => interval,
+        _ => {
+            (interval.max(1) as u16)
+                .next_power_of_two()
+                .trailing_zeros() as u8
+                + 1
+        }
+    };
+    m.clamp(2, 16)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::nak_limit;
+    use crate::usb::host::DeviceSpeed;
+
+    #[test]
+    fn function_nak_limit() {
+        assert_eq!(nak_limit(DeviceSpeed::Full, 0), 2);
+        assert_eq!(nak_limit(DeviceSpeed::Full, 1), 2);
+        assert_eq!(nak_limit(DeviceSpeed::Full, 2), 2);
+        assert_eq!(nak_limit(DeviceSpeed::Low, 10), 5);
+        assert_eq!(nak_limit(DeviceSpeed::Full, 255), 9);
+        assert_eq!(nak_limit(DeviceSpeed::High, 4), 4);
+        assert_eq!(nak_limit(DeviceSpeed::High, 1), 2);
+        assert_eq!(nak_limit(DeviceSpeed::High, 16), 16);
+    }
+}
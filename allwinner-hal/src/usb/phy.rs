@@ -0,0 +1,136 @@
+use super::RegisterBlock;
+
+// Analog tuning registers of USB PHYs, written bit by bit through USB0 PHY control register.
+const TX_AMPLITUDE: u8 = 0x20;
+const TX_SLEW_RATE: u8 = 0x22;
+const VBUS_VALID_THRESHOLD: u8 = 0x25;
+const DISCONNECT_THRESHOLD: u8 = 0x2A;
+const SQUELCH_DETECT: u8 = 0x3C;
+
+/// Write the lowest `len` bits of `value` to analog tuning register `address` of PHY `index`.
+///
+/// Tuning registers of all PHYs are reached through PHY control register of USB0, one bit
+/// per address from the least significant bit. PHY 0 is the OTG port, PHY 1 is USB1.
+#[inline]
+pub fn write_phy(regs: &RegisterBlock, index: usize, address: u8, value: u32, len: u8) {
+    for bit in 0..len {
+        let data = value & (1 << bit) != 0;
+        unsafe {
+            regs.phy_control.modify(|val| {
+                val.set_tune_address(address + bit)
+                    .set_tune_data(data)
+                    .clear_tune_clock(index)
+            });
+            regs.phy_control.modify(|val| val.set_tune_clock(index));
+            regs.phy_control.modify(|val| val.clear_tune_clock(index));
+        }
+    }
+}
+
+/// Analog tuning of one USB PHY.
+///
+/// Fields left as `None` keep their reset values. Board or chip specific values are usually
+/// calibrated at factory and read from SID eFuse by the caller.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct PhyTuning {
+    /// High-speed transmitter amplitude, 5 bits.
+    pub tx_amplitude: Option<u8>,
+    /// High-speed transmitter slew rate, 3 bits.
+    pub tx_slew_rate: Option<u8>,
+    /// VBUS valid comparator threshold, 2 bits.
+    pub vbus_valid_threshold: Option<u8>,
+    /// High-speed disconnect detection threshold, 2 bits.
+    pub disconnect_threshold: Option<u8>,
+    /// Enable squelch detector, which should be off while OTG port idles in device mode
+    /// without VBUS.
+    pub squelch_detect: Option<bool>,
+}
+
+impl PhyTuning {
+    /// Write tuning values to PHY `index`.
+    #[inline]
+    pub fn apply(&self, regs: &RegisterBlock, index: usize) {
+        if let Some(val) = self.tx_amplitude {
+            write_phy(regs, index, TX_AMPLITUDE, val as u32, 5);
+        }
+        if let Some(val) = self.tx_slew_rate {
+            write_phy(regs, index, TX_SLEW_RATE, val as u32, 3);
+        }
+        if let Some(val) = self.vbus_valid_threshold {
+            write_phy(regs, index, VBUS_VALID_THRESHOLD, val as u32, 2);
+        }
+        if let Some(val) = self.disconnect_threshold {
+            write_phy(regs, index, DISCONNECT_THRESHOLD, val as u32, 2);
+        }
+        if let Some(enabled) = self.squelch_detect {
+            let val = if enabled { 0 } else { 2 };
+            write_phy(regs, index, SQUELCH_DETECT, val, 2);
+        }
+    }
+}
+
+/// VBUS detection of USB0 OTG port.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum VbusDetect {
+    /// Detected by VBUS comparator of PHY.
+    Internal,
+    /// Reported by software from an external sense, e.g. a PMIC or a GPIO pin.
+    External(bool),
+}
+
+/// Select VBUS detection of USB0 OTG port.
+///
+/// With [`VbusDetect::External`], call it again whenever the external sense changes.
+#[inline]
+pub fn set_vbus_detect(regs: &RegisterBlock, detect: VbusDetect) {
+    unsafe {
+        match detect {
+            VbusDetect::Internal => {
+                regs.phy_control
+                    .modify(|val| val.clear_vbus_valid_external());
+                regs.iscr
+                    .modify(|val| val.keep_change_detect().release_vbus_force());
+            }
+            VbusDetect::External(valid) => {
+                regs.phy_control.modify(|val| val.set_vbus_valid_external());
+                regs.iscr.modify(|val| {
+                    let val = val.keep_change_detect();
+                    if valid {
+                        val.force_vbus_valid()
+                    } else {
+                        val.force_vbus_invalid()
+                    }
+                });
+            }
+        }
+    }
+}
+
+/// ID detection of USB0 OTG port.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum IdDetect {
+    /// Role follows ID pin of the port.
+    Pin,
+    /// ID forced low, selecting host mode.
+    Host,
+    /// ID forced high, selecting peripheral mode.
+    Device,
+}
+
+/// Select ID detection of USB0 OTG port.
+///
+/// [`UsbBus`](super::UsbBus) and [`UsbHost`](super::UsbHost) force ID for their own role when
+/// created; this selects it while neither of them holds the port.
+#[inline]
+pub fn set_id_detect(regs: &RegisterBlock, detect: IdDetect) {
+    unsafe {
+        regs.iscr.modify(|val| {
+            let val = val.keep_change_detect().enable_pullups();
+            match detect {
+                IdDetect::Pin => val.release_id_force(),
+                IdDetect::Host => val.force_host(),
+                IdDetect::Device => val.force_device(),
+            }
+        });
+    }
+}
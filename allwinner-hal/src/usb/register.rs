@@ -0,0 +1,1691 @@
+use super::host::{DeviceSpeed, TransferType};
+use volatile_register::{RO, RW};
+
+/// USB On-The-Go controller registers.
+///
+/// Registers from 0x80 to 0x98 belong to the endpoint selected by [`RegisterBlock::index`].
+#[repr(C)]
+pub struct RegisterBlock {
+    /// 0x00..=0x14 - Endpoint 0 to 5 FIFO registers.
+    pub fifo: [RW<u32>; 6],
+    _reserved0: [u32; 10],
+    /// 0x40 - Power Management Register.
+    pub power: RW<Power>,
+    /// 0x41 - Device Control Register.
+    pub device_control: RW<DeviceControl>,
+    /// 0x42 - Endpoint Index Register.
+    pub index: RW<u8>,
+    /// 0x43 - Vendor Register 0.
+    pub vendor0: RW<Vendor0>,
+    /// 0x44 - Transmit Interrupt Status Register.
+    pub tx_interrupt: RO<u16>,
+    /// 0x46 - Receive Interrupt Status Register.
+    pub rx_interrupt: RO<u16>,
+    /// 0x48 - Transmit Interrupt Enable Register.
+    pub tx_interrupt_enable: RW<u16>,
+    /// 0x4a - Receive Interrupt Enable Register.
+    pub rx_interrupt_enable: RW<u16>,
+    /// 0x4c - USB Interrupt Status Register.
+    pub usb_interrupt: RO<UsbInterrupts>,
+    _reserved1: [u8; 3],
+    /// 0x50 - USB Interrupt Enable Register.
+    pub usb_interrupt_enable: RW<UsbInterrupts>,
+    _reserved2: [u8; 3],
+    /// 0x54 - Frame Number Register.
+    pub frame: RO<u16>,
+    _reserved3: [u16; 21],
+    /// 0x80 - Transmit Maximum Packet Size Register.
+    pub tx_max_packet: RW<u16>,
+    /// 0x82 - Endpoint 0 Control Status Register, or Transmit Control Status Register.
+    ///
+    /// Read and write as [`Csr0`] when endpoint 0 is selected, otherwise as [`TxCsr`]; in host
+    /// mode, as [`HostCsr0`] or [`HostTxCsr`].
+    pub csr: RW<u16>,
+    /// 0x84 - Receive Maximum Packet Size Register.
+    pub rx_max_packet: RW<u16>,
+    /// 0x86 - Receive Control Status Register.
+    ///
+    /// Read and write as [`RxCsr`] in device mode, or as [`HostRxCsr`] in host mode.
+    pub rx_csr: RW<u16>,
+    /// 0x88 - Endpoint 0 Count Register, or Receive Count Register.
+    pub count: RO<u16>,
+    _reserved4: u16,
+    /// 0x8c - Transmit Type Register, host mode only.
+    pub tx_type: RW<TargetType>,
+    /// 0x8d - Transmit Interval Register, or NAK limit, host mode only.
+    pub tx_interval: RW<u8>,
+    /// 0x8e - Receive Type Register, host mode only.
+    pub rx_type: RW<TargetType>,
+    /// 0x8f - Receive Interval Register, or NAK limit, host mode only.
+    pub rx_interval: RW<u8>,
+    /// 0x90 - Transmit FIFO Size Register.
+    pub tx_fifo_size: RW<FifoSize>,
+    _reserved5: u8,
+    /// 0x92 - Transmit FIFO Start Address Register, in units of 8 bytes.
+    pub tx_fifo_address: RW<u16>,
+    /// 0x94 - Receive FIFO Size Register.
+    pub rx_fifo_size: RW<FifoSize>,
+    _reserved6: u8,
+    /// 0x96 - Receive FIFO Start Address Register, in units of 8 bytes.
+    pub rx_fifo_address: RW<u16>,
+    /// 0x98 - Function Address Register.
+    ///
+    /// Address of this device in device mode, or transmit target address of selected
+    /// endpoint in host mode.
+    pub function_address: RW<u8>,
+    _reserved7: u8,
+    /// 0x9a - Transmit Hub Address Register, host mode only.
+    pub tx_hub_address: RW<u8>,
+    /// 0x9b - Transmit Hub Port Register, host mode only.
+    pub tx_hub_port: RW<u8>,
+    /// 0x9c - Receive Function Address Register, host mode only.
+    pub rx_function_address: RW<u8>,
+    _reserved8: u8,
+    /// 0x9e - Receive Hub Address Register, host mode only.
+    pub rx_hub_address: RW<u8>,
+    /// 0x9f - Receive Hub Port Register, host mode only.
+    pub rx_hub_port: RW<u8>,
+    _reserved9: [u32; 216],
+    /// 0x400 - Interface Status and Control Register.
+    pub iscr: RW<Iscr>,
+    _reserved10: [u32; 3],
+    /// 0x410 - PHY Control Register.
+    pub phy_control: RW<PhyControl>,
+    _reserved11: [u32; 3],
+    /// 0x420 - PHY OTG Control Register.
+    pub phy_otg_control: RW<PhyOtgControl>,
+}
+
+/// Power management register.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct Power(u8);
+
+impl Power {
+    const SOFT_CONN: u8 = 1 << 6;
+    const HS_ENAB: u8 = 1 << 5;
+    const HS_MODE: u8 = 1 << 4;
+    const RESET: u8 = 1 << 3;
+    const RESUME: u8 = 1 << 2;
+    const SUSPEND_MODE: u8 = 1 << 1;
+
+    /// Check if device is connected to the bus by pulling up D+.
+    #[inline]
+    pub const fn is_soft_connected(self) -> bool {
+        self.0 & Self::SOFT_CONN != 0
+    }
+    /// Connect device to the bus.
+    #[inline]
+    pub const fn soft_connect(self) -> Self {
+        Self(self.0 | Self::SOFT_CONN)
+    }
+    /// Disconnect device from the bus.
+    #[inline]
+    pub const fn soft_disconnect(self) -> Self {
+        Self(self.0 & !Self::SOFT_CONN)
+    }
+    /// Check if high-speed negotiation is enabled.
+    #[inline]
+    pub const fn is_high_speed_enabled(self) -> bool {
+        self.0 & Self::HS_ENAB != 0
+    }
+    /// Enable high-speed negotiation during reset.
+    #[inline]
+    pub const fn enable_high_speed(self) -> Self {
+        Self(self.0 | Self::HS_ENAB)
+    }
+    /// Disable high-speed negotiation, staying at full speed.
+    #[inline]
+    pub const fn disable_high_speed(self) -> Self {
+        Self(self.0 & !Self::HS_ENAB)
+    }
+    /// Check if device has negotiated high speed.
+    #[inline]
+    pub const fn is_high_speed(self) -> bool {
+        self.0 & Self::HS_MODE != 0
+    }
+    /// Check if reset signaling is present on the bus.
+    #[inline]
+    pub const fn is_reset(self) -> bool {
+        self.0 & Self::RESET != 0
+    }
+    /// Start driving reset signaling on the bus, host mode only.
+    #[inline]
+    pub const fn start_reset(self) -> Self {
+        Self(self.0 | Self::RESET)
+    }
+    /// Stop driving reset signaling on the bus, host mode only.
+    #[inline]
+    pub const fn stop_reset(self) -> Self {
+        Self(self.0 & !Self::RESET)
+    }
+    /// Start resume signaling.
+    #[inline]
+    pub const fn start_resume(self) -> Self {
+        Self(self.0 | Self::RESUME)
+    }
+    /// Stop resume signaling.
+    #[inline]
+    pub const fn stop_resume(self) -> Self {
+        Self(self.0 & !Self::RESUME)
+    }
+    /// Check if device is in suspend mode.
+    #[inline]
+    pub const fn is_suspended(self) -> bool {
+        self.0 & Self::SUSPEND_MODE != 0
+    }
+}
+
+impl Default for Power {
+    #[inline]
+    fn default() -> Self {
+        Self(0x20)
+    }
+}
+
+/// Device control register.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct DeviceControl(u8);
+
+impl DeviceControl {
+    const B_DEVICE: u8 = 1 << 7;
+    const FS_DEV: u8 = 1 << 6;
+    const LS_DEV: u8 = 1 << 5;
+    const HOST_MODE: u8 = 1 << 2;
+    const SESSION: u8 = 1 << 0;
+
+    /// Check if controller is the B device of OTG session.
+    #[inline]
+    pub const fn is_b_device(self) -> bool {
+        self.0 & Self::B_DEVICE != 0
+    }
+    /// Check if a full-speed or high-speed device is attached, host mode only.
+    #[inline]
+    pub const fn is_full_speed_device(self) -> bool {
+        self.0 & Self::FS_DEV != 0
+    }
+    /// Check if a low-speed device is attached, host mode only.
+    #[inline]
+    pub const fn is_low_speed_device(self) -> bool {
+        self.0 & Self::LS_DEV != 0
+    }
+    /// Check if controller acts as host.
+    #[inline]
+    pub const fn is_host_mode(self) -> bool {
+        self.0 & Self::HOST_MODE != 0
+    }
+    /// Check if a session is in progress.
+    #[inline]
+    pub const fn is_session(self) -> bool {
+        self.0 & Self::SESSION != 0
+    }
+    /// Start a session; as A device, controller becomes host.
+    #[inline]
+    pub const fn start_session(self) -> Self {
+        Self(self.0 | Self::SESSION)
+    }
+    /// End current session.
+    #[inline]
+    pub const fn end_session(self) -> Self {
+        Self(self.0 & !Self::SESSION)
+    }
+}
+
+impl Default for DeviceControl {
+    #[inline]
+    fn default() -> Self {
+        Self(0x80)
+    }
+}
+
+/// USB bus interrupt.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Interrupt {
+    /// Suspend signaling detected.
+    Suspend,
+    /// Resume signaling detected while suspended.
+    Resume,
+    /// Reset signaling detected.
+    Reset,
+    /// Start of frame.
+    StartOfFrame,
+    /// Device connected, host mode only.
+    Connect,
+    /// Device disconnected, or session ended.
+    Disconnect,
+    /// Session request detected, host mode only.
+    SessionRequest,
+    /// VBUS dropped below valid threshold, host mode only.
+    VbusError,
+}
+
+/// USB interrupt status and enable register.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct UsbInterrupts(u8);
+
+impl UsbInterrupts {
+    /// Check if `interrupt` is pending or enabled.
+    #[inline]
+    pub const fn has_interrupt(self, interrupt: Interrupt) -> bool {
+        self.0 & (1 << interrupt as u8) != 0
+    }
+    /// Enable `interrupt`.
+    #[inline]
+    pub const fn enable_interrupt(self, interrupt: Interrupt) -> Self {
+        Self(self.0 | (1 << interrupt as u8))
+    }
+    /// Disable `interrupt`.
+    #[inline]
+    pub const fn disable_interrupt(self, interrupt: Interrupt) -> Self {
+        Self(self.0 & !(1 << interrupt as u8))
+    }
+}
+
+impl Default for UsbInterrupts {
+    #[inline]
+    fn default() -> Self {
+        Self(0)
+    }
+}
+
+/// Endpoint 0 control status register, in peripheral mode.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct Csr0(u16);
+
+impl Csr0 {
+    const FLUSH_FIFO: u16 = 1 << 8;
+    const SERVICED_SETUP_END: u16 = 1 << 7;
+    const SERVICED_RX_PKT_RDY: u16 = 1 << 6;
+    const SEND_STALL: u16 = 1 << 5;
+    const SETUP_END: u16 = 1 << 4;
+    const DATA_END: u16 = 1 << 3;
+    const SENT_STALL: u16 = 1 << 2;
+    const TX_PKT_RDY: u16 = 1 << 1;
+    const RX_PKT_RDY: u16 = 1 << 0;
+
+    /// Create register value from raw bits.
+    #[inline]
+    pub const fn from_bits(bits: u16) -> Self {
+        Self(bits)
+    }
+    /// Get raw bits of register value.
+    #[inline]
+    pub const fn bits(self) -> u16 {
+        self.0
+    }
+    /// Flush endpoint 0 FIFO.
+    #[inline]
+    pub const fn flush_fifo(self) -> Self {
+        Self(self.0 | Self::FLUSH_FIFO)
+    }
+    /// Acknowledge an aborted control transfer, clearing setup end status.
+    #[inline]
+    pub const fn set_serviced_setup_end(self) -> Self {
+        Self(self.0 | Self::SERVICED_SETUP_END)
+    }
+    /// Acknowledge the received packet, clearing rx packet ready status.
+    #[inline]
+    pub const fn set_serviced_rx_packet_ready(self) -> Self {
+        Self(self.0 | Self::SERVICED_RX_PKT_RDY)
+    }
+    /// Check if stall handshake is being sent.
+    #[inline]
+    pub const fn is_send_stall(self) -> bool {
+        self.0 & Self::SEND_STALL != 0
+    }
+    /// Terminate current control transfer with a stall handshake.
+    #[inline]
+    pub const fn send_stall(self) -> Self {
+        Self(self.0 | Self::SEND_STALL)
+    }
+    /// Check if control transfer ended before data end was set.
+    #[inline]
+    pub const fn is_setup_end(self) -> bool {
+        self.0 & Self::SETUP_END != 0
+    }
+    /// Mark the last data packet of control transfer.
+    #[inline]
+    pub const fn set_data_end(self) -> Self {
+        Self(self.0 | Self::DATA_END)
+    }
+    /// Check if stall handshake has been sent.
+    #[inline]
+    pub const fn is_sent_stall(self) -> bool {
+        self.0 & Self::SENT_STALL != 0
+    }
+    /// Clear sent stall status.
+    #[inline]
+    pub const fn clear_sent_stall(self) -> Self {
+        Self(self.0 & !Self::SENT_STALL)
+    }
+    /// Check if transmit packet is still waiting in FIFO.
+    #[inline]
+    pub const fn is_tx_packet_ready(self) -> bool {
+        self.0 & Self::TX_PKT_RDY != 0
+    }
+    /// Mark transmit packet in FIFO as ready to send.
+    #[inline]
+    pub const fn set_tx_packet_ready(self) -> Self {
+        Self(self.0 | Self::TX_PKT_RDY)
+    }
+    /// Check if a packet has been received into FIFO.
+    #[inline]
+    pub const fn is_rx_packet_ready(self) -> bool {
+        self.0 & Self::RX_PKT_RDY != 0
+    }
+}
+
+impl Default for Csr0 {
+    #[inline]
+    fn default() -> Self {
+        Self(0)
+    }
+}
+
+/// Vendor register 0.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct Vendor0(u8);
+
+impl Vendor0 {
+    const BUS_SEL: u8 = 1 << 0;
+
+    /// Check if endpoint FIFOs are connected to DMA request lines.
+    #[inline]
+    pub const fn is_dma_bus(self) -> bool {
+        self.0 & Self::BUS_SEL != 0
+    }
+    /// Connect endpoint FIFOs to DMA request lines.
+    #[inline]
+    pub const fn select_dma_bus(self) -> Self {
+        Self(self.0 | Self::BUS_SEL)
+    }
+    /// Access endpoint FIFOs by CPU only.
+    #[inline]
+    pub const fn select_pio_bus(self) -> Self {
+        Self(self.0 & !Self::BUS_SEL)
+    }
+}
+
+impl Default for Vendor0 {
+    #[inline]
+    fn default() -> Self {
+        Self(0)
+    }
+}
+
+/// Transmit control status register, in peripheral mode.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct TxCsr(u16);
+
+impl TxCsr {
+    const AUTO_SET: u16 = 1 << 15;
+    const ISO: u16 = 1 << 14;
+    const MODE: u16 = 1 << 13;
+    const DMA_REQ_EN: u16 = 1 << 12;
+    const INCOMP_TX: u16 = 1 << 7;
+    const CLR_DATA_TOG: u16 = 1 << 6;
+    const SENT_STALL: u16 = 1 << 5;
+    const SEND_STALL: u16 = 1 << 4;
+    const FLUSH_FIFO: u16 = 1 << 3;
+    const UNDER_RUN: u16 = 1 << 2;
+    const FIFO_NOT_EMPTY: u16 = 1 << 1;
+    const TX_PKT_RDY: u16 = 1 << 0;
+
+    /// Create register value from raw bits.
+    #[inline]
+    pub const fn from_bits(bits: u16) -> Self {
+        Self(bits)
+    }
+    /// Get raw bits of register value.
+    #[inline]
+    pub const fn bits(self) -> u16 {
+        self.0
+    }
+    /// Check if packets are sent automatically once FIFO holds maximum packet size.
+    #[inline]
+    pub const fn is_auto_set_enabled(self) -> bool {
+        self.0 & Self::AUTO_SET != 0
+    }
+    /// Send packets automatically once FIFO holds maximum packet size.
+    #[inline]
+    pub const fn enable_auto_set(self) -> Self {
+        Self(self.0 | Self::AUTO_SET)
+    }
+    /// Set packet ready by software only.
+    #[inline]
+    pub const fn disable_auto_set(self) -> Self {
+        Self(self.0 & !Self::AUTO_SET)
+    }
+    /// Check if endpoint requests DMA while FIFO has room for a packet.
+    #[inline]
+    pub const fn is_dma_enabled(self) -> bool {
+        self.0 & Self::DMA_REQ_EN != 0
+    }
+    /// Request DMA while FIFO has room for a packet.
+    #[inline]
+    pub const fn enable_dma(self) -> Self {
+        Self(self.0 | Self::DMA_REQ_EN)
+    }
+    /// Stop requesting DMA.
+    #[inline]
+    pub const fn disable_dma(self) -> Self {
+        Self(self.0 & !Self::DMA_REQ_EN)
+    }
+    /// Check if endpoint uses isochronous transfers.
+    #[inline]
+    pub const fn is_iso(self) -> bool {
+        self.0 & Self::ISO != 0
+    }
+    /// Use isochronous transfers on this endpoint.
+    #[inline]
+    pub const fn enable_iso(self) -> Self {
+        Self(self.0 | Self::ISO)
+    }
+    /// Use bulk or interrupt transfers on this endpoint.
+    #[inline]
+    pub const fn disable_iso(self) -> Self {
+        Self(self.0 & !Self::ISO)
+    }
+    /// Check if shared FIFO of this endpoint is used for transmit.
+    #[inline]
+    pub const fn is_tx_mode(self) -> bool {
+        self.0 & Self::MODE != 0
+    }
+    /// Use shared FIFO of this endpoint for transmit.
+    #[inline]
+    pub const fn set_tx_mode(self) -> Self {
+        Self(self.0 | Self::MODE)
+    }
+    /// Use shared FIFO of this endpoint for receive.
+    #[inline]
+    pub const fn set_rx_mode(self) -> Self {
+        Self(self.0 & !Self::MODE)
+    }
+    /// Check if an isochronous packet was split and not completely sent.
+    #[inline]
+    pub const fn is_incomplete(self) -> bool {
+        self.0 & Self::INCOMP_TX != 0
+    }
+    /// Reset data toggle to DATA0.
+    #[inline]
+    pub const fn clear_data_toggle(self) -> Self {
+        Self(self.0 | Self::CLR_DATA_TOG)
+    }
+    /// Check if stall handshake has been sent.
+    #[inline]
+    pub const fn is_sent_stall(self) -> bool {
+        self.0 & Self::SENT_STALL != 0
+    }
+    /// Clear sent stall status.
+    #[inline]
+    pub const fn clear_sent_stall(self) -> Self {
+        Self(self.0 & !Self::SENT_STALL)
+    }
+    /// Check if endpoint answers IN tokens with stall handshake.
+    #[inline]
+    pub const fn is_send_stall(self) -> bool {
+        self.0 & Self::SEND_STALL != 0
+    }
+    /// Answer IN tokens with stall handshake.
+    #[inline]
+    pub const fn send_stall(self) -> Self {
+        Self(self.0 | Self::SEND_STALL)
+    }
+    /// Stop answering IN tokens with stall handshake.
+    #[inline]
+    pub const fn clear_send_stall(self) -> Self {
+        Self(self.0 & !Self::SEND_STALL)
+    }
+    /// Flush the oldest packet in FIFO.
+    #[inline]
+    pub const fn flush_fifo(self) -> Self {
+        Self(self.0 | Self::FLUSH_FIFO)
+    }
+    /// Check if an IN token arrived while no packet was ready.
+    #[inline]
+    pub const fn is_underrun(self) -> bool {
+        self.0 & Self::UNDER_RUN != 0
+    }
+    /// Clear underrun status.
+    #[inline]
+    pub const fn clear_underrun(self) -> Self {
+        Self(self.0 & !Self::UNDER_RUN)
+    }
+    /// Check if FIFO holds at least one packet.
+    #[inline]
+    pub const fn is_fifo_not_empty(self) -> bool {
+        self.0 & Self::FIFO_NOT_EMPTY != 0
+    }
+    /// Check if transmit packet is still waiting in FIFO.
+    #[inline]
+    pub const fn is_tx_packet_ready(self) -> bool {
+        self.0 & Self::TX_PKT_RDY != 0
+    }
+    /// Mark transmit packet in FIFO as ready to send.
+    #[inline]
+    pub const fn set_tx_packet_ready(self) -> Self {
+        Self(self.0 | Self::TX_PKT_RDY)
+    }
+}
+
+impl Default for TxCsr {
+    #[inline]
+    fn default() -> Self {
+        Self(0)
+    }
+}
+
+/// Receive control status register, in peripheral mode.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct RxCsr(u16);
+
+impl RxCsr {
+    const AUTO_CLEAR: u16 = 1 << 15;
+    const ISO: u16 = 1 << 14;
+    const DMA_REQ_EN: u16 = 1 << 13;
+    const CLR_DATA_TOG: u16 = 1 << 7;
+    const SENT_STALL: u16 = 1 << 6;
+    const SEND_STALL: u16 = 1 << 5;
+    const FLUSH_FIFO: u16 = 1 << 4;
+    const DATA_ERROR: u16 = 1 << 3;
+    const OVER_RUN: u16 = 1 << 2;
+    const FIFO_FULL: u16 = 1 << 1;
+    const RX_PKT_RDY: u16 = 1 << 0;
+
+    /// Create register value from raw bits.
+    #[inline]
+    pub const fn from_bits(bits: u16) -> Self {
+        Self(bits)
+    }
+    /// Get raw bits of register value.
+    #[inline]
+    pub const fn bits(self) -> u16 {
+        self.0
+    }
+
+    /// Check if packet ready is cleared automatically once maximum packet size is unloaded.
+    #[inline]
+    pub const fn is_auto_clear_enabled(self) -> bool {
+        self.0 & Self::AUTO_CLEAR != 0
+    }
+    /// Clear packet ready automatically once maximum packet size is unloaded.
+    #[inline]
+    pub const fn enable_auto_clear(self) -> Self {
+        Self(self.0 | Self::AUTO_CLEAR)
+    }
+    /// Clear packet ready by software only.
+    #[inline]
+    pub const fn disable_auto_clear(self) -> Self {
+        Self(self.0 & !Self::AUTO_CLEAR)
+    }
+    /// Check if endpoint requests DMA while a received packet is ready.
+    #[inline]
+    pub const fn is_dma_enabled(self) -> bool {
+        self.0 & Self::DMA_REQ_EN != 0
+    }
+    /// Request DMA while a received packet is ready.
+    #[inline]
+    pub const fn enable_dma(self) -> Self {
+        Self(self.0 | Self::DMA_REQ_EN)
+    }
+    /// Stop requesting DMA.
+    #[inline]
+    pub const fn disable_dma(self) -> Self {
+        Self(self.0 & !Self::DMA_REQ_EN)
+    }
+    /// Check if endpoint uses isochronous transfers.
+    #[inline]
+    pub const fn is_iso(self) -> bool {
+        self.0 & Self::ISO != 0
+    }
+    /// Use isochronous transfers on this endpoint.
+    #[inline]
+    pub const fn enable_iso(self) -> Self {
+        Self(self.0 | Self::ISO)
+    }
+    /// Use bulk or interrupt transfers on this endpoint.
+    #[inline]
+    pub const fn disable_iso(self) -> Self {
+        Self(self.0 & !Self::ISO)
+    }
+    /// Reset data toggle to DATA0.
+    #[inline]
+    pub const fn clear_data_toggle(self) -> Self {
+        Self(self.0 | Self::CLR_DATA_TOG)
+    }
+    /// Check if stall handshake has been sent.
+    #[inline]
+    pub const fn is_sent_stall(self) -> bool {
+        self.0 & Self::SENT_STALL != 0
+    }
+    /// Clear sent stall status.
+    #[inline]
+    pub const fn clear_sent_stall(self) -> Self {
+        Self(self.0 & !Self::SENT_STALL)
+    }
+    /// Check if endpoint answers OUT tokens with stall handshake.
+    #[inline]
+    pub const fn is_send_stall(self) -> bool {
+        self.0 & Self::SEND_STALL != 0
+    }
+    /// Answer OUT tokens with stall handshake.
+    #[inline]
+    pub const fn send_stall(self) -> Self {
+        Self(self.0 | Self::SEND_STALL)
+    }
+    /// Stop answering OUT tokens with stall handshake.
+    #[inline]
+    pub const fn clear_send_stall(self) -> Self {
+        Self(self.0 & !Self::SEND_STALL)
+    }
+    /// Flush the next packet to be read from FIFO.
+    #[inline]
+    pub const fn flush_fifo(self) -> Self {
+        Self(self.0 | Self::FLUSH_FIFO)
+    }
+    /// Check if received isochronous packet has CRC or bit-stuff error.
+    #[inline]
+    pub const fn is_data_error(self) -> bool {
+        self.0 & Self::DATA_ERROR != 0
+    }
+    /// Check if an isochronous packet was dropped because FIFO was full.
+    #[inline]
+    pub const fn is_overrun(self) -> bool {
+        self.0 & Self::OVER_RUN != 0
+    }
+    /// Clear overrun status.
+    #[inline]
+    pub const fn clear_overrun(self) -> Self {
+        Self(self.0 & !Self::OVER_RUN)
+    }
+    /// Check if FIFO cannot accept another packet.
+    #[inline]
+    pub const fn is_fifo_full(self) -> bool {
+        self.0 & Self::FIFO_FULL != 0
+    }
+    /// Check if a packet has been received into FIFO.
+    #[inline]
+    pub const fn is_rx_packet_ready(self) -> bool {
+        self.0 & Self::RX_PKT_RDY != 0
+    }
+    /// Release the packet read from FIFO.
+    #[inline]
+    pub const fn clear_rx_packet_ready(self) -> Self {
+        Self(self.0 & !Self::RX_PKT_RDY)
+    }
+}
+
+impl Default for RxCsr {
+    #[inline]
+    fn default() -> Self {
+        Self(0)
+    }
+}
+
+/// Endpoint 0 control status register, in host mode.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct HostCsr0(u16);
+
+impl HostCsr0 {
+    const FLUSH_FIFO: u16 = 1 << 8;
+    const NAK_TIMEOUT: u16 = 1 << 7;
+    const STATUS_PKT: u16 = 1 << 6;
+    const REQ_PKT: u16 = 1 << 5;
+    const ERROR: u16 = 1 << 4;
+    const SETUP_PKT: u16 = 1 << 3;
+    const RX_STALL: u16 = 1 << 2;
+    const TX_PKT_RDY: u16 = 1 << 1;
+    const RX_PKT_RDY: u16 = 1 << 0;
+
+    /// Create register value from raw bits.
+    #[inline]
+    pub const fn from_bits(bits: u16) -> Self {
+        Self(bits)
+    }
+    /// Get raw bits of register value.
+    #[inline]
+    pub const fn bits(self) -> u16 {
+        self.0
+    }
+    /// Flush endpoint 0 FIFO.
+    #[inline]
+    pub const fn flush_fifo(self) -> Self {
+        Self(self.0 | Self::FLUSH_FIFO)
+    }
+    /// Check if device kept answering NAK beyond NAK limit.
+    #[inline]
+    pub const fn is_nak_timeout(self) -> bool {
+        self.0 & Self::NAK_TIMEOUT != 0
+    }
+    /// Mark the transaction as status stage, with tx or request packet set together.
+    #[inline]
+    pub const fn set_status_packet(self) -> Self {
+        Self(self.0 | Self::STATUS_PKT)
+    }
+    /// Check if an IN transaction is requested.
+    #[inline]
+    pub const fn is_request_packet(self) -> bool {
+        self.0 & Self::REQ_PKT != 0
+    }
+    /// Request an IN transaction.
+    #[inline]
+    pub const fn set_request_packet(self) -> Self {
+        Self(self.0 | Self::REQ_PKT)
+    }
+    /// Check if device did not respond after three attempts.
+    #[inline]
+    pub const fn is_error(self) -> bool {
+        self.0 & Self::ERROR != 0
+    }
+    /// Send packet in FIFO as a setup packet, with tx packet ready set together.
+    #[inline]
+    pub const fn set_setup_packet(self) -> Self {
+        Self(self.0 | Self::SETUP_PKT)
+    }
+    /// Check if device answered with stall handshake.
+    #[inline]
+    pub const fn is_rx_stall(self) -> bool {
+        self.0 & Self::RX_STALL != 0
+    }
+    /// Check if transmit packet is still waiting in FIFO.
+    #[inline]
+    pub const fn is_tx_packet_ready(self) -> bool {
+        self.0 & Self::TX_PKT_RDY != 0
+    }
+    /// Mark transmit packet in FIFO as ready to send.
+    #[inline]
+    pub const fn set_tx_packet_ready(self) -> Self {
+        Self(self.0 | Self::TX_PKT_RDY)
+    }
+    /// Check if a packet has been received into FIFO.
+    #[inline]
+    pub const fn is_rx_packet_ready(self) -> bool {
+        self.0 & Self::RX_PKT_RDY != 0
+    }
+}
+
+impl Default for HostCsr0 {
+    #[inline]
+    fn default() -> Self {
+        Self(0)
+    }
+}
+
+/// Transmit control status register, in host mode.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct HostTxCsr(u16);
+
+impl HostTxCsr {
+    const MODE: u16 = 1 << 13;
+    const DATA_TOGGLE_WE: u16 = 1 << 9;
+    const DATA_TOGGLE: u16 = 1 << 8;
+    const NAK_TIMEOUT: u16 = 1 << 7;
+    const CLR_DATA_TOG: u16 = 1 << 6;
+    const RX_STALL: u16 = 1 << 5;
+    const FLUSH_FIFO: u16 = 1 << 3;
+    const ERROR: u16 = 1 << 2;
+    const FIFO_NOT_EMPTY: u16 = 1 << 1;
+    const TX_PKT_RDY: u16 = 1 << 0;
+
+    /// Create register value from raw bits.
+    #[inline]
+    pub const fn from_bits(bits: u16) -> Self {
+        Self(bits)
+    }
+    /// Get raw bits of register value.
+    #[inline]
+    pub const fn bits(self) -> u16 {
+        self.0
+    }
+    /// Use shared FIFO of this endpoint for transmit.
+    #[inline]
+    pub const fn set_tx_mode(self) -> Self {
+        Self(self.0 | Self::MODE)
+    }
+    /// Get current data toggle, true for DATA1.
+    #[inline]
+    pub const fn data_toggle(self) -> bool {
+        self.0 & Self::DATA_TOGGLE != 0
+    }
+    /// Load data toggle of next packet, true for DATA1.
+    #[inline]
+    pub const fn set_data_toggle(self, val: bool) -> Self {
+        let val = if val { Self::DATA_TOGGLE } else { 0 };
+        Self((self.0 & !Self::DATA_TOGGLE) | Self::DATA_TOGGLE_WE | val)
+    }
+    /// Check if device kept answering NAK beyond NAK limit.
+    #[inline]
+    pub const fn is_nak_timeout(self) -> bool {
+        self.0 & Self::NAK_TIMEOUT != 0
+    }
+    /// Reset data toggle to DATA0.
+    #[inline]
+    pub const fn clear_data_toggle(self) -> Self {
+        Self(self.0 | Self::CLR_DATA_TOG)
+    }
+    /// Check if device answered with stall handshake.
+    #[inline]
+    pub const fn is_rx_stall(self) -> bool {
+        self.0 & Self::RX_STALL != 0
+    }
+    /// Flush the oldest packet in FIFO.
+    #[inline]
+    pub const fn flush_fifo(self) -> Self {
+        Self(self.0 | Self::FLUSH_FIFO)
+    }
+    /// Check if device did not respond after three attempts.
+    #[inline]
+    pub const fn is_error(self) -> bool {
+        self.0 & Self::ERROR != 0
+    }
+    /// Check if FIFO holds at least one packet.
+    #[inline]
+    pub const fn is_fifo_not_empty(self) -> bool {
+        self.0 & Self::FIFO_NOT_EMPTY != 0
+    }
+    /// Check if transmit packet is still waiting in FIFO.
+    #[inline]
+    pub const fn is_tx_packet_ready(self) -> bool {
+        self.0 & Self::TX_PKT_RDY != 0
+    }
+    /// Mark transmit packet in FIFO as ready to send.
+    #[inline]
+    pub const fn set_tx_packet_ready(self) -> Self {
+        Self(self.0 | Self::TX_PKT_RDY)
+    }
+}
+
+impl Default for HostTxCsr {
+    #[inline]
+    fn default() -> Self {
+        Self(0)
+    }
+}
+
+/// Receive control status register, in host mode.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct HostRxCsr(u16);
+
+impl HostRxCsr {
+    const DATA_TOGGLE_WE: u16 = 1 << 10;
+    const DATA_TOGGLE: u16 = 1 << 9;
+    const CLR_DATA_TOG: u16 = 1 << 7;
+    const RX_STALL: u16 = 1 << 6;
+    const REQ_PKT: u16 = 1 << 5;
+    const FLUSH_FIFO: u16 = 1 << 4;
+    const NAK_TIMEOUT: u16 = 1 << 3;
+    const ERROR: u16 = 1 << 2;
+    const FIFO_FULL: u16 = 1 << 1;
+    const RX_PKT_RDY: u16 = 1 << 0;
+
+    /// Create register value from raw bits.
+    #[inline]
+    pub const fn from_bits(bits: u16) -> Self {
+        Self(bits)
+    }
+    /// Get raw bits of register value.
+    #[inline]
+    pub const fn bits(self) -> u16 {
+        self.0
+    }
+    /// Get current data toggle, true for DATA1.
+    #[inline]
+    pub const fn data_toggle(self) -> bool {
+        self.0 & Self::DATA_TOGGLE != 0
+    }
+    /// Load data toggle of next packet, true for DATA1.
+    #[inline]
+    pub const fn set_data_toggle(self, val: bool) -> Self {
+        let val = if val { Self::DATA_TOGGLE } else { 0 };
+        Self((self.0 & !Self::DATA_TOGGLE) | Self::DATA_TOGGLE_WE | val)
+    }
+    /// Reset data toggle to DATA0.
+    #[inline]
+    pub const fn clear_data_toggle(self) -> Self {
+        Self(self.0 | Self::CLR_DATA_TOG)
+    }
+    /// Check if device answered with stall handshake.
+    #[inline]
+    pub const fn is_rx_stall(self) -> bool {
+        self.0 & Self::RX_STALL != 0
+    }
+    /// Check if an IN transaction is requested.
+    #[inline]
+    pub const fn is_request_packet(self) -> bool {
+        self.0 & Self::REQ_PKT != 0
+    }
+    /// Request an IN transaction.
+    #[inline]
+    pub const fn set_request_packet(self) -> Self {
+        Self(self.0 | Self::REQ_PKT)
+    }
+    /// Flush the next packet to be read from FIFO.
+    #[inline]
+    pub const fn flush_fifo(self) -> Self {
+        Self(self.0 | Self::FLUSH_FIFO)
+    }
+    /// Check if device kept answering NAK beyond NAK limit.
+    #[inline]
+    pub const fn is_nak_timeout(self) -> bool {
+        self.0 & Self::NAK_TIMEOUT != 0
+    }
+    /// Check if device did not respond after three attempts.
+    #[inline]
+    pub const fn is_error(self) -> bool {
+        self.0 & Self::ERROR != 0
+    }
+    /// Check if FIFO cannot accept another packet.
+    #[inline]
+    pub const fn is_fifo_full(self) -> bool {
+        self.0 & Self::FIFO_FULL != 0
+    }
+    /// Check if a packet has been received into FIFO.
+    #[inline]
+    pub const fn is_rx_packet_ready(self) -> bool {
+        self.0 & Self::RX_PKT_RDY != 0
+    }
+}
+
+impl Default for HostRxCsr {
+    #[inline]
+    fn default() -> Self {
+        Self(0)
+    }
+}
+
+/// Transmit and receive type register, in host mode.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct TargetType(u8);
+
+impl TargetType {
+    const SPEED: u8 = 0x3 << 6;
+    const PROTOCOL: u8 = 0x3 << 4;
+    const TARGET_EP: u8 = 0xF;
+
+    /// Get speed of target device.
+    #[inline]
+    pub const fn speed(self) -> DeviceSpeed {
+        match (self.0 & Self::SPEED) >> 6 {
+            1 => DeviceSpeed::High,
+            3 => DeviceSpeed::Low,
+            _ => DeviceSpeed::Full,
+        }
+    }
+    /// Set speed of target device.
+    #[inline]
+    pub const fn set_speed(self, val: DeviceSpeed) -> Self {
+        let val = match val {
+            DeviceSpeed::High => 1,
+            DeviceSpeed::Full => 2,
+            DeviceSpeed::Low => 3,
+        };
+        Self((self.0 & !Self::SPEED) | (val << 6))
+    }
+    /// Get transfer type of target endpoint.
+    #[inline]
+    pub const fn protocol(self) -> TransferType {
+        match (self.0 & Self::PROTOCOL) >> 4 {
+            0 => TransferType::Control,
+            1 => TransferType::Isochronous,
+            2 => TransferType::Bulk,
+            _ => TransferType::Interrupt,
+        }
+    }
+    /// Set transfer type of target endpoint.
+    #[inline]
+    pub const fn set_protocol(self, val: TransferType) -> Self {
+        Self((self.0 & !Self::PROTOCOL) | ((val as u8) << 4))
+    }
+    /// Get target endpoint number.
+    #[inline]
+    pub const fn endpoint(self) -> u8 {
+        self.0 & Self::TARGET_EP
+    }
+    /// Set target endpoint number.
+    #[inline]
+    pub const fn set_endpoint(self, val: u8) -> Self {
+        Self((self.0 & !Self::TARGET_EP) | (val & Self::TARGET_EP))
+    }
+}
+
+impl Default for TargetType {
+    #[inline]
+    fn default() -> Self {
+        Self(0)
+    }
+}
+
+/// Endpoint FIFO size register.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct FifoSize(u8);
+
+impl FifoSize {
+    const DOUBLE_BUFFER: u8 = 1 << 4;
+    const SIZE: u8 = 0xF;
+
+    /// Get FIFO size in bytes, of one buffer.
+    #[inline]
+    pub const fn size(self) -> u16 {
+        8 << (self.0 & Self::SIZE)
+    }
+    /// Set FIFO size in bytes, a power of two from 8 to 4096.
+    #[inline]
+    pub const fn set_size(self, bytes: u16) -> Self {
+        assert!(bytes.is_power_of_two() && bytes >= 8 && bytes <= 4096);
+        Self((self.0 & !Self::SIZE) | (bytes.trailing_zeros() as u8 - 3))
+    }
+    /// Check if FIFO holds two packets.
+    #[inline]
+    pub const fn is_double_buffered(self) -> bool {
+        self.0 & Self::DOUBLE_BUFFER != 0
+    }
+    /// Let FIFO hold two packets, using twice the size.
+    #[inline]
+    pub const fn enable_double_buffer(self) -> Self {
+        Self(self.0 | Self::DOUBLE_BUFFER)
+    }
+    /// Let FIFO hold one packet.
+    #[inline]
+    pub const fn disable_double_buffer(self) -> Self {
+        Self(self.0 & !Self::DOUBLE_BUFFER)
+    }
+}
+
+impl Default for FifoSize {
+    #[inline]
+    fn default() -> Self {
+        Self(0)
+    }
+}
+
+/// Interface status and control register.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct Iscr(u32);
+
+impl Iscr {
+    const ID_PULLUP_EN: u32 = 1 << 17;
+    const DPDM_PULLUP_EN: u32 = 1 << 16;
+    const FORCE_ID: u32 = 0x3 << 14;
+    const FORCE_ID_LOW: u32 = 0x2 << 14;
+    const FORCE_VBUS_VALID: u32 = 0x3 << 12;
+    const FORCE_VBUS_LOW: u32 = 0x2 << 12;
+    const CHANGE_DETECT: u32 = 0x7 << 4;
+
+    /// Enable pull-up resistors on ID pin and on DP and DM lines.
+    #[inline]
+    pub const fn enable_pullups(self) -> Self {
+        Self(self.0 | Self::ID_PULLUP_EN | Self::DPDM_PULLUP_EN)
+    }
+    /// Disable pull-up resistors on ID pin and on DP and DM lines.
+    #[inline]
+    pub const fn disable_pullups(self) -> Self {
+        Self(self.0 & !(Self::ID_PULLUP_EN | Self::DPDM_PULLUP_EN))
+    }
+    /// Check if ID pin is forced high, selecting peripheral mode.
+    #[inline]
+    pub const fn is_device_forced(self) -> bool {
+        self.0 & Self::FORCE_ID == Self::FORCE_ID
+    }
+    /// Force ID pin high, selecting peripheral mode regardless of cable.
+    #[inline]
+    pub const fn force_device(self) -> Self {
+        Self(self.0 | Self::FORCE_ID)
+    }
+    /// Check if ID pin is forced low, selecting host mode.
+    #[inline]
+    pub const fn is_host_forced(self) -> bool {
+        self.0 & Self::FORCE_ID == Self::FORCE_ID_LOW
+    }
+    /// Force ID pin low, selecting host mode regardless of cable.
+    #[inline]
+    pub const fn force_host(self) -> Self {
+        Self((self.0 & !Self::FORCE_ID) | Self::FORCE_ID_LOW)
+    }
+    /// Check if VBUS is forced valid.
+    #[inline]
+    pub const fn is_vbus_valid_forced(self) -> bool {
+        self.0 & Self::FORCE_VBUS_VALID == Self::FORCE_VBUS_VALID
+    }
+    /// Force VBUS valid, for boards without VBUS detection.
+    #[inline]
+    pub const fn force_vbus_valid(self) -> Self {
+        Self(self.0 | Self::FORCE_VBUS_VALID)
+    }
+    /// Check if VBUS is forced invalid.
+    #[inline]
+    pub const fn is_vbus_invalid_forced(self) -> bool {
+        self.0 & Self::FORCE_VBUS_VALID == Self::FORCE_VBUS_LOW
+    }
+    /// Force VBUS invalid, e.g. after external VBUS sense reports the cable is removed.
+    #[inline]
+    pub const fn force_vbus_invalid(self) -> Self {
+        Self((self.0 & !Self::FORCE_VBUS_VALID) | Self::FORCE_VBUS_LOW)
+    }
+    /// Release ID and VBUS forcing.
+    #[inline]
+    pub const fn release_force(self) -> Self {
+        Self(self.0 & !(Self::FORCE_ID | Self::FORCE_VBUS_VALID))
+    }
+    /// Release ID forcing, so that ID pin selects host or peripheral mode.
+    #[inline]
+    pub const fn release_id_force(self) -> Self {
+        Self(self.0 & !Self::FORCE_ID)
+    }
+    /// Release VBUS forcing, so that PHY comparator detects VBUS.
+    #[inline]
+    pub const fn release_vbus_force(self) -> Self {
+        Self(self.0 & !Self::FORCE_VBUS_VALID)
+    }
+    /// Keep change detect status bits, which are cleared by writing one, from being cleared.
+    #[inline]
+    pub const fn keep_change_detect(self) -> Self {
+        Self(self.0 & !Self::CHANGE_DETECT)
+    }
+}
+
+impl Default for Iscr {
+    #[inline]
+    fn default() -> Self {
+        Self(0)
+    }
+}
+
+/// USB PHY control register.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct PhyControl(u32);
+
+impl PhyControl {
+    const TUNE_ADDRESS: u32 = 0xFF << 8;
+    const TUNE_DATA: u32 = 1 << 7;
+    const VBUSVLDEXT: u32 = 1 << 5;
+    const SIDDQ: u32 = 1 << 3;
+
+    /// Get address of PHY tuning register.
+    #[inline]
+    pub const fn tune_address(self) -> u8 {
+        ((self.0 & Self::TUNE_ADDRESS) >> 8) as u8
+    }
+    /// Set address of PHY tuning register.
+    #[inline]
+    pub const fn set_tune_address(self, address: u8) -> Self {
+        Self((self.0 & !Self::TUNE_ADDRESS) | ((address as u32) << 8))
+    }
+    /// Get data bit written to PHY tuning register.
+    #[inline]
+    pub const fn tune_data(self) -> bool {
+        self.0 & Self::TUNE_DATA != 0
+    }
+    /// Set data bit written to PHY tuning register.
+    #[inline]
+    pub const fn set_tune_data(self, bit: bool) -> Self {
+        Self((self.0 & !Self::TUNE_DATA) | ((bit as u32) << 7))
+    }
+    /// Check if write clock of PHY `index` is high.
+    #[inline]
+    pub const fn is_tune_clock_high(self, index: usize) -> bool {
+        self.0 & (1 << (2 * index)) != 0
+    }
+    /// Raise write clock of PHY `index`, latching data bit into tuning register.
+    #[inline]
+    pub const fn set_tune_clock(self, index: usize) -> Self {
+        Self(self.0 | (1 << (2 * index)))
+    }
+    /// Lower write clock of PHY `index`.
+    #[inline]
+    pub const fn clear_tune_clock(self, index: usize) -> Self {
+        Self(self.0 & !(1 << (2 * index)))
+    }
+
+    /// Check if external VBUS valid input is asserted.
+    #[inline]
+    pub const fn is_vbus_valid_external(self) -> bool {
+        self.0 & Self::VBUSVLDEXT != 0
+    }
+    /// Assert external VBUS valid input.
+    #[inline]
+    pub const fn set_vbus_valid_external(self) -> Self {
+        Self(self.0 | Self::VBUSVLDEXT)
+    }
+    /// Deassert external VBUS valid input.
+    #[inline]
+    pub const fn clear_vbus_valid_external(self) -> Self {
+        Self(self.0 & !Self::VBUSVLDEXT)
+    }
+    /// Check if PHY analog blocks are powered down.
+    #[inline]
+    pub const fn is_powered_down(self) -> bool {
+        self.0 & Self::SIDDQ != 0
+    }
+    /// Power down PHY analog blocks.
+    #[inline]
+    pub const fn power_down(self) -> Self {
+        Self(self.0 | Self::SIDDQ)
+    }
+    /// Power up PHY analog blocks.
+    #[inline]
+    pub const fn power_up(self) -> Self {
+        Self(self.0 & !Self::SIDDQ)
+    }
+}
+
+impl Default for PhyControl {
+    #[inline]
+    fn default() -> Self {
+        Self(0x8)
+    }
+}
+
+/// USB PHY OTG control register.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct PhyOtgControl(u32);
+
+impl PhyOtgControl {
+    const ROUTE_MUSB: u32 = 1 << 0;
+
+    /// Check if USB0 PHY is routed to OTG controller.
+    #[inline]
+    pub const fn is_routed_to_otg(self) -> bool {
+        self.0 & Self::ROUTE_MUSB != 0
+    }
+    /// Route USB0 PHY to OTG controller.
+    #[inline]
+    pub const fn route_to_otg(self) -> Self {
+        Self(self.0 | Self::ROUTE_MUSB)
+    }
+    /// Route USB0 PHY to EHCI0 and OHCI0 host controllers.
+    #[inline]
+    pub const fn route_to_host(self) -> Self {
+        Self(self.0 & !Self::ROUTE_MUSB)
+    }
+}
+
+impl Default for PhyOtgControl {
+    #[inline]
+    fn default() -> Self {
+        Self(0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        Csr0, DeviceControl, FifoSize, HostCsr0, HostRxCsr, HostTxCsr, Interrupt, Iscr, PhyControl,
+        PhyOtgControl, Power, RegisterBlock, RxCsr, TargetType, TxCsr, UsbInterrupts, Vendor0,
+    };
+    use crate::usb::host::{DeviceSpeed, TransferType};
+    use core::mem::offset_of;
+
+    #[test]
+    fn offset_usb() {
+        assert_eq!(offset_of!(RegisterBlock, fifo), 0x00);
+        assert_eq!(offset_of!(RegisterBlock, power), 0x40);
+        assert_eq!(offset_of!(RegisterBlock, device_control), 0x41);
+        assert_eq!(offset_of!(RegisterBlock, index), 0x42);
+        assert_eq!(offset_of!(RegisterBlock, vendor0), 0x43);
+        assert_eq!(offset_of!(RegisterBlock, tx_interrupt), 0x44);
+        assert_eq!(offset_of!(RegisterBlock, rx_interrupt), 0x46);
+        assert_eq!(offset_of!(RegisterBlock, tx_interrupt_enable), 0x48);
+        assert_eq!(offset_of!(RegisterBlock, rx_interrupt_enable), 0x4a);
+        assert_eq!(offset_of!(RegisterBlock, usb_interrupt), 0x4c);
+        assert_eq!(offset_of!(RegisterBlock, usb_interrupt_enable), 0x50);
+        assert_eq!(offset_of!(RegisterBlock, frame), 0x54);
+        assert_eq!(offset_of!(RegisterBlock, tx_max_packet), 0x80);
+        assert_eq!(offset_of!(RegisterBlock, csr), 0x82);
+        assert_eq!(offset_of!(RegisterBlock, rx_max_packet), 0x84);
+        assert_eq!(offset_of!(RegisterBlock, rx_csr), 0x86);
+        assert_eq!(offset_of!(RegisterBlock, count), 0x88);
+        assert_eq!(offset_of!(RegisterBlock, tx_type), 0x8c);
+        assert_eq!(offset_of!(RegisterBlock, tx_interval), 0x8d);
+        assert_eq!(offset_of!(RegisterBlock, rx_type), 0x8e);
+        assert_eq!(offset_of!(RegisterBlock, rx_interval), 0x8f);
+        assert_eq!(offset_of!(RegisterBlock, tx_fifo_size), 0x90);
+        assert_eq!(offset_of!(RegisterBlock, tx_fifo_address), 0x92);
+        assert_eq!(offset_of!(RegisterBlock, rx_fifo_size), 0x94);
+        assert_eq!(offset_of!(RegisterBlock, rx_fifo_address), 0x96);
+        assert_eq!(offset_of!(RegisterBlock, function_address), 0x98);
+        assert_eq!(offset_of!(RegisterBlock, tx_hub_address), 0x9a);
+        assert_eq!(offset_of!(RegisterBlock, tx_hub_port), 0x9b);
+        assert_eq!(offset_of!(RegisterBlock, rx_function_address), 0x9c);
+        assert_eq!(offset_of!(RegisterBlock, rx_hub_address), 0x9e);
+        assert_eq!(offset_of!(RegisterBlock, rx_hub_port), 0x9f);
+        assert_eq!(offset_of!(RegisterBlock, iscr), 0x400);
+        assert_eq!(offset_of!(RegisterBlock, phy_control), 0x410);
+        assert_eq!(offset_of!(RegisterBlock, phy_otg_control), 0x420);
+    }
+
+    #[test]
+    fn struct_power_functions() {
+        let mut val = Power::default();
+        assert!(val.is_high_speed_enabled());
+
+        val = val.soft_connect().disable_high_speed();
+        assert!(val.is_soft_connected());
+        assert_eq!(val.0, 0x40);
+
+        val = val.start_resume();
+        assert_eq!(val.0, 0x44);
+        val = val.stop_resume().soft_disconnect();
+        assert_eq!(val.0, 0x00);
+
+        assert!(Power(0x18).is_reset());
+        assert_eq!(Power(0x20).start_reset().0, 0x28);
+        assert_eq!(Power(0x28).stop_reset().0, 0x20);
+        assert!(Power(0x18).is_high_speed());
+        assert!(Power(0x02).is_suspended());
+    }
+
+    #[test]
+    fn struct_device_control_functions() {
+        let mut val = DeviceControl::default();
+        assert!(val.is_b_device());
+
+        val = val.start_session();
+        assert!(val.is_session());
+        assert_eq!(val.0, 0x81);
+        val = val.end_session();
+        assert_eq!(val.0, 0x80);
+
+        let val = DeviceControl(0x5D);
+        assert!(val.is_full_speed_device());
+        assert!(!val.is_low_speed_device());
+        assert!(val.is_host_mode());
+        assert!(DeviceControl(0x25).is_low_speed_device());
+    }
+
+    #[test]
+    fn struct_usb_interrupts_functions() {
+        let mut val = UsbInterrupts::default();
+        val = val
+            .enable_interrupt(Interrupt::Reset)
+            .enable_interrupt(Interrupt::Suspend)
+            .enable_interrupt(Interrupt::VbusError);
+        assert_eq!(val.0, 0x85);
+        assert!(val.has_interrupt(Interrupt::Reset));
+        assert!(!val.has_interrupt(Interrupt::Resume));
+
+        val = val.disable_interrupt(Interrupt::VbusError);
+        assert_eq!(val.0, 0x05);
+    }
+
+    #[test]
+    fn struct_csr0_functions() {
+        let val = Csr0::default()
+            .set_serviced_rx_packet_ready()
+            .set_data_end();
+        assert_eq!(val.bits(), 0x48);
+        assert_eq!(Csr0::default().send_stall().bits(), 0x20);
+        assert_eq!(Csr0::default().set_serviced_setup_end().bits(), 0x80);
+        assert_eq!(Csr0::default().set_tx_packet_ready().bits(), 0x02);
+        assert_eq!(Csr0::default().flush_fifo().bits(), 0x100);
+
+        let val = Csr0::from_bits(0x15);
+        assert!(val.is_rx_packet_ready());
+        assert!(val.is_sent_stall());
+        assert!(val.is_setup_end());
+        assert!(!val.is_tx_packet_ready());
+        assert_eq!(val.clear_sent_stall().bits(), 0x11);
+    }
+
+    #[test]
+    fn struct_tx_csr_functions() {
+        let mut val = TxCsr::default();
+        val = val.set_tx_mode().enable_iso().enable_auto_set();
+        assert!(val.is_tx_mode() && val.is_iso() && val.is_auto_set_enabled());
+        assert_eq!(val.bits(), 0xE000);
+
+        val = val.set_rx_mode().disable_iso().disable_auto_set();
+        assert_eq!(val.bits(), 0x0000);
+
+        val = TxCsr::default().enable_dma();
+        assert!(val.is_dma_enabled());
+        assert_eq!(val.bits(), 0x1000);
+        assert_eq!(val.disable_dma().bits(), 0x0000);
+
+        assert_eq!(TxCsr::default().clear_data_toggle().bits(), 0x40);
+        assert_eq!(TxCsr::default().flush_fifo().bits(), 0x08);
+        assert_eq!(TxCsr::default().set_tx_packet_ready().bits(), 0x01);
+
+        val = TxCsr::default().send_stall();
+        assert!(val.is_send_stall());
+        assert_eq!(val.bits(), 0x10);
+        assert_eq!(val.clear_send_stall().bits(), 0x00);
+
+        let val = TxCsr::from_bits(0x00A7);
+        assert!(val.is_incomplete());
+        assert!(val.is_sent_stall());
+        assert!(val.is_underrun());
+        assert!(val.is_fifo_not_empty());
+        assert!(val.is_tx_packet_ready());
+        assert_eq!(val.clear_sent_stall().clear_underrun().bits(), 0x0083);
+    }
+
+    #[test]
+    fn struct_rx_csr_functions() {
+        let mut val = RxCsr::default();
+        val = val.enable_iso().enable_auto_clear();
+        assert!(val.is_iso() && val.is_auto_clear_enabled());
+        assert_eq!(val.0, 0xC000);
+
+        val = val.disable_iso().disable_auto_clear();
+        assert_eq!(val.0, 0x0000);
+
+        val = RxCsr::default().enable_dma();
+        assert!(val.is_dma_enabled());
+        assert_eq!(val.0, 0x2000);
+        assert_eq!(val.disable_dma().0, 0x0000);
+
+        assert_eq!(RxCsr::default().clear_data_toggle().0, 0x80);
+        assert_eq!(RxCsr::default().flush_fifo().0, 0x10);
+
+        val = RxCsr::default().send_stall();
+        assert!(val.is_send_stall());
+        assert_eq!(val.0, 0x20);
+        assert_eq!(val.clear_send_stall().0, 0x00);
+
+        let val = RxCsr(0x004F);
+        assert!(val.is_sent_stall());
+        assert!(val.is_data_error());
+        assert!(val.is_overrun());
+        assert!(val.is_fifo_full());
+        assert!(val.is_rx_packet_ready());
+        assert_eq!(
+            val.clear_sent_stall()
+                .clear_overrun()
+                .clear_rx_packet_ready()
+                .0,
+            0x000A
+        );
+    }
+
+    #[test]
+    fn struct_host_csr0_functions() {
+        let val = HostCsr0::default().set_setup_packet().set_tx_packet_ready();
+        assert_eq!(val.bits(), 0x0A);
+        let val = HostCsr0::default().set_status_packet().set_request_packet();
+        assert!(val.is_request_packet());
+        assert_eq!(val.bits(), 0x60);
+        assert_eq!(HostCsr0::default().flush_fifo().bits(), 0x100);
+
+        let val = HostCsr0::from_bits(0x97);
+        assert!(val.is_nak_timeout());
+        assert!(val.is_error());
+        assert!(val.is_rx_stall());
+        assert!(val.is_tx_packet_ready());
+        assert!(val.is_rx_packet_ready());
+    }
+
+    #[test]
+    fn struct_host_tx_csr_functions() {
+        let mut val = HostTxCsr::default().set_tx_mode().set_data_toggle(true);
+        assert_eq!(val.bits(), 0x2300);
+        assert!(val.data_toggle());
+        val = val.set_data_toggle(false);
+        assert_eq!(val.bits(), 0x2200);
+        assert_eq!(HostTxCsr::default().clear_data_toggle().bits(), 0x40);
+        assert_eq!(HostTxCsr::default().flush_fifo().bits(), 0x08);
+        assert_eq!(HostTxCsr::default().set_tx_packet_ready().bits(), 0x01);
+
+        let val = HostTxCsr::from_bits(0x00A7);
+        assert!(val.is_nak_timeout());
+        assert!(val.is_rx_stall());
+        assert!(val.is_error());
+        assert!(val.is_fifo_not_empty());
+        assert!(val.is_tx_packet_ready());
+    }
+
+    #[test]
+    fn struct_host_rx_csr_functions() {
+        let mut val = HostRxCsr::default().set_data_toggle(true);
+        assert_eq!(val.bits(), 0x0600);
+        assert!(val.data_toggle());
+        val = val.set_data_toggle(false).set_request_packet();
+        assert!(val.is_request_packet());
+        assert_eq!(val.bits(), 0x0420);
+        assert_eq!(HostRxCsr::default().clear_data_toggle().bits(), 0x80);
+        assert_eq!(HostRxCsr::default().flush_fifo().bits(), 0x10);
+
+        let val = HostRxCsr::from_bits(0x004F);
+        assert!(val.is_rx_stall());
+        assert!(val.is_nak_timeout());
+        assert!(val.is_error());
+        assert!(val.is_fifo_full());
+        assert!(val.is_rx_packet_ready());
+    }
+
+    #[test]
+    fn struct_target_type_functions() {
+        let mut val = TargetType::default();
+        val = val
+            .set_speed(DeviceSpeed::Full)
+            .set_protocol(TransferType::Bulk)
+            .set_endpoint(2);
+        assert_eq!(val.0, 0xA2);
+        assert_eq!(val.speed(), DeviceSpeed::Full);
+        assert_eq!(val.protocol(), TransferType::Bulk);
+        assert_eq!(val.endpoint(), 2);
+
+        val = val
+            .set_speed(DeviceSpeed::High)
+            .set_protocol(TransferType::Interrupt)
+            .set_endpoint(0x11);
+        assert_eq!(val.0, 0x71);
+
+        val = val
+            .set_speed(DeviceSpeed::Low)
+            .set_protocol(TransferType::Control);
+        assert_eq!(val.0, 0xC1);
+        assert_eq!(val.speed(), DeviceSpeed::Low);
+        assert_eq!(val.protocol(), TransferType::Control);
+        assert_eq!(
+            val.set_protocol(TransferType::Isochronous).protocol(),
+            TransferType::Isochronous
+        );
+    }
+
+    #[test]
+    fn struct_fifo_size_functions() {
+        let mut val = FifoSize::default();
+        assert_eq!(val.size(), 8);
+
+        val = val.set_size(512).enable_double_buffer();
+        assert!(val.is_double_buffered());
+        assert_eq!(val.size(), 512);
+        assert_eq!(val.0, 0x16);
+
+        val = val.set_size(64).disable_double_buffer();
+        assert_eq!(val.0, 0x03);
+        assert_eq!(FifoSize::default().set_size(4096).0, 0x09);
+    }
+
+    #[test]
+    fn struct_vendor0_functions() {
+        let mut val = Vendor0::default();
+        val = val.select_dma_bus();
+        assert!(val.is_dma_bus());
+        assert_eq!(val.0, 0x01);
+
+        val = val.select_pio_bus();
+        assert!(!val.is_dma_bus());
+        assert_eq!(val.0, 0x00);
+    }
+
+    #[test]
+    fn struct_iscr_functions() {
+        let mut val = Iscr::default();
+        val = val.enable_pullups().force_device().force_vbus_valid();
+        assert!(val.is_device_forced());
+        assert!(val.is_vbus_valid_forced());
+        assert_eq!(val.0, 0x0003F000);
+        assert!(!val.is_host_forced());
+
+        val = val.force_host();
+        assert!(val.is_host_forced());
+        assert!(!val.is_device_forced());
+        assert_eq!(val.0, 0x0003B000);
+
+        val = val.force_vbus_invalid();
+        assert!(val.is_vbus_invalid_forced());
+        assert!(!val.is_vbus_valid_forced());
+        assert_eq!(val.0, 0x0003A000);
+
+        val = val.release_id_force();
+        assert_eq!(val.0, 0x00032000);
+        val = val.release_vbus_force();
+        assert_eq!(val.0, 0x00030000);
+
+        val = val.release_force().disable_pullups();
+        assert_eq!(val.0, 0x00000000);
+
+        assert_eq!(Iscr(0x4000_0070).keep_change_detect().0, 0x4000_0000);
+    }
+
+    #[test]
+    fn struct_phy_control_functions() {
+        let mut val = PhyControl::default();
+        assert!(val.is_powered_down());
+
+        val = val.power_up().set_vbus_valid_external();
+        assert!(val.is_vbus_valid_external());
+        assert_eq!(val.0, 0x20);
+
+        val = val.power_down();
+        assert_eq!(val.0, 0x28);
+
+        val = val.clear_vbus_valid_external().set_tune_address(0x3C);
+        assert_eq!(val.tune_address(), 0x3C);
+        assert_eq!(val.0, 0x3C08);
+
+        val = val.set_tune_data(true).set_tune_clock(1);
+        assert!(val.tune_data());
+        assert!(val.is_tune_clock_high(1));
+        assert!(!val.is_tune_clock_high(0));
+        assert_eq!(val.0, 0x3C8C);
+
+        val = val.clear_tune_clock(1).set_tune_data(false);
+        assert_eq!(val.0, 0x3C08);
+    }
+
+    #[test]
+    fn struct_phy_otg_control_functions() {
+        let mut val = PhyOtgControl::default();
+        val = val.route_to_otg();
+        assert!(val.is_routed_to_otg());
+        assert_eq!(val.0, 0x1);
+        val = val.route_to_host();
+        assert_eq!(val.0, 0x0);
+    }
+}
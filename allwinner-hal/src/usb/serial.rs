@@ -0,0 +1,504 @@
+use super::{RegisterBlock, UsbBus};
+use crate::time::Deadline;
+use usb_device::{
+    UsbError,
+    bus::{self, InterfaceNumber, UsbBusAllocator},
+    class::{ControlIn, ControlOut, UsbClass},
+    control::{Recipient, RequestType},
+    descriptor::DescriptorWriter,
+    device::{StringDescriptors, UsbDevice, UsbDeviceBuilder, UsbDeviceState, UsbVidPid},
+    endpoint::{EndpointIn, EndpointOut},
+};
+
+const USB_CLASS_CDC: u8 = 0x02;
+const USB_CLASS_CDC_DATA: u8 = 0x0A;
+const CDC_SUBCLASS_ACM: u8 = 0x02;
+const CDC_PROTOCOL_NONE: u8 = 0x00;
+
+const CS_INTERFACE: u8 = 0x24;
+const CDC_TYPE_HEADER: u8 = 0x00;
+const CDC_TYPE_CALL_MANAGEMENT: u8 = 0x01;
+const CDC_TYPE_ACM: u8 = 0x02;
+const CDC_TYPE_UNION: u8 = 0x06;
+
+const REQ_SEND_ENCAPSULATED_COMMAND: u8 = 0x00;
+const REQ_SET_LINE_CODING: u8 = 0x20;
+const REQ_GET_LINE_CODING: u8 = 0x21;
+const REQ_SET_CONTROL_LINE_STATE: u8 = 0x22;
+const REQ_SEND_BREAK: u8 = 0x23;
+
+/// Maximum packet size of [`UsbSerial`] data endpoints, valid at full speed.
+pub const SERIAL_PACKET_SIZE: u16 = 64;
+
+/// Number of stop bits requested by host.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum StopBits {
+    /// 1 stop bit.
+    One,
+    /// 1.5 stop bits.
+    OnePointFive,
+    /// 2 stop bits.
+    Two,
+}
+
+/// Parity requested by host.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Parity {
+    /// No parity bit.
+    None,
+    /// Odd parity.
+    Odd,
+    /// Even parity.
+    Even,
+    /// Parity bit always one.
+    Mark,
+    /// Parity bit always zero.
+    Space,
+}
+
+/// Serial line settings set by host, as in CDC `SET_LINE_CODING` request.
+///
+/// These have no effect on USB transfers, but let firmware bridge to a real UART.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct LineCoding {
+    /// Baud rate in bits per second.
+    pub data_rate: u32,
+    /// Number of stop bits.
+    pub stop_bits: StopBits,
+    /// Parity.
+    pub parity: Parity,
+    /// Number of data bits, 5, 6, 7, 8 or 16.
+    pub data_bits: u8,
+}
+
+impl LineCoding {
+    /// Parse line coding structure of CDC requests.
+    #[inline]
+    pub const fn from_bytes(bytes: [u8; 7]) -> Self {
+        let [r0, r1, r2, r3, stop_bits, parity, data_bits] = bytes;
+        Self {
+            data_rate: u32::from_le_bytes([r0, r1, r2, r3]),
+            stop_bits: match stop_bits {
+                1 => StopBits::OnePointFive,
+                2 => StopBits::Two,
+                _ => StopBits::One,
+            },
+            parity: match parity {
+                1 => Parity::Odd,
+                2 => Parity::Even,
+                3 => Parity::Mark,
+                4 => Parity::Space,
+                _ => Parity::None,
+            },
+            data_bits,
+        }
+    }
+    /// Encode into line coding structure of CDC requests.
+    #[inline]
+    pub const fn to_bytes(self) -> [u8; 7] {
+        let [r0, r1, r2, r3] = self.data_rate.to_le_bytes();
+        let stop_bits = match self.stop_bits {
+            StopBits::One => 0,
+            StopBits::OnePointFive => 1,
+            StopBits::Two => 2,
+        };
+        let parity = match self.parity {
+            Parity::None => 0,
+            Parity::Odd => 1,
+            Parity::Even => 2,
+            Parity::Mark => 3,
+            Parity::Space => 4,
+        };
+        [r0, r1, r2, r3, stop_bits, parity, self.data_bits]
+    }
+}
+
+impl Default for LineCoding {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            data_rate: 115200,
+            stop_bits: StopBits::One,
+            parity: Parity::None,
+            data_bits: 8,
+        }
+    }
+}
+
+/// CDC Abstract Control Model class with one pair of bulk data endpoints.
+pub struct CdcAcm<'a, B: bus::UsbBus> {
+    comm_if: InterfaceNumber,
+    data_if: InterfaceNumber,
+    comm_ep: EndpointIn<'a, B>,
+    read_ep: EndpointOut<'a, B>,
+    write_ep: EndpointIn<'a, B>,
+    line_coding: LineCoding,
+    dtr: bool,
+    rts: bool,
+}
+
+impl<'a, B: bus::UsbBus> CdcAcm<'a, B> {
+    /// Allocate interfaces and endpoints of CDC-ACM class.
+    #[inline]
+    pub fn new(alloc: &'a UsbBusAllocator<B>, max_packet_size: u16) -> Self {
+        Self {
+            comm_if: alloc.interface(),
+            data_if: alloc.interface(),
+            comm_ep: alloc.interrupt(8, 255),
+            write_ep: alloc.bulk(max_packet_size),
+            read_ep: alloc.bulk(max_packet_size),
+            line_coding: LineCoding::default(),
+            dtr: false,
+            rts: false,
+        }
+    }
+    /// Get line settings last set by host.
+    #[inline]
+    pub fn line_coding(&self) -> LineCoding {
+        self.line_coding
+    }
+    /// Check if host has opened the port, asserting Data Terminal Ready.
+    #[inline]
+    pub fn dtr(&self) -> bool {
+        self.dtr
+    }
+    /// Check if host asserts Request To Send.
+    #[inline]
+    pub fn rts(&self) -> bool {
+        self.rts
+    }
+    /// Get maximum packet size of data endpoints.
+    #[inline]
+    pub fn max_packet_size(&self) -> u16 {
+        self.write_ep.max_packet_size()
+    }
+    /// Write one packet to host.
+    #[inline]
+    pub fn write_packet(&mut self, data: &[u8]) -> usb_device::Result<usize> {
+        self.write_ep.write(data)
+    }
+    /// Read one packet from host.
+    ///
+    /// `data` should hold at least maximum packet size.
+    #[inline]
+    pub fn read_packet(&mut self, data: &mut [u8]) -> usb_device::Result<usize> {
+        self.read_ep.read(data)
+    }
+}
+
+impl<B: bus::UsbBus> UsbClass<B> for CdcAcm<'_, B> {
+    fn get_configuration_descriptors(
+        &self,
+        writer: &mut DescriptorWriter,
+    ) -> usb_device::Result<()> {
+        writer.iad(
+            self.comm_if,
+            2,
+            USB_CLASS_CDC,
+            CDC_SUBCLASS_ACM,
+            CDC_PROTOCOL_NONE,
+            None,
+        )?;
+        writer.interface(
+            self.comm_if,
+            USB_CLASS_CDC,
+            CDC_SUBCLASS_ACM,
+            CDC_PROTOCOL_NONE,
+        )?;
+        // CDC 1.10, call management by host, line coding and control line state supported.
+        writer.write(CS_INTERFACE, &[CDC_TYPE_HEADER, 0x10, 0x01])?;
+        writer.write(
+            CS_INTERFACE,
+            &[CDC_TYPE_CALL_MANAGEMENT, 0x00, self.data_if.into()],
+        )?;
+        writer.write(CS_INTERFACE, &[CDC_TYPE_ACM, 0x02])?;
+        writer.write(
+            CS_INTERFACE,
+            &[CDC_TYPE_UNION, self.comm_if.into(), self.data_if.into()],
+        )?;
+        writer.endpoint(&self.comm_ep)?;
+        writer.interface(self.data_if, USB_CLASS_CDC_DATA, 0x00, 0x00)?;
+        writer.endpoint(&self.write_ep)?;
+        writer.endpoint(&self.read_ep)?;
+        Ok(())
+    }
+
+    fn reset(&mut self) {
+        self.line_coding = LineCoding::default();
+        self.dtr = false;
+        self.rts = false;
+    }
+
+    fn control_in(&mut self, xfer: ControlIn<B>) {
+        let req = *xfer.request();
+        if req.request_type != RequestType::Class
+            || req.recipient != Recipient::Interface
+            || req.index != u8::from(self.comm_if) as u16
+        {
+            return;
+        }
+        match req.request {
+            REQ_GET_LINE_CODING if req.length >= 7 => {
+                xfer.accept_with(&self.line_coding.to_bytes()).ok();
+            }
+            _ => {
+                xfer.reject().ok();
+            }
+        }
+    }
+
+    fn control_out(&mut self, xfer: ControlOut<B>) {
+        let req = *xfer.request();
+        if req.request_type != RequestType::Class
+            || req.recipient != Recipient::Interface
+            || req.index != u8::from(self.comm_if) as u16
+        {
+            return;
+        }
+        match req.request {
+            REQ_SEND_ENCAPSULATED_COMMAND | REQ_SEND_BREAK => {
+                xfer.accept().ok();
+            }
+            REQ_SET_LINE_CODING if xfer.data().len() >= 7 => {
+                let mut bytes = [0; 7];
+                bytes.copy_from_slice(&xfer.data()[..7]);
+                self.line_coding = LineCoding::from_bytes(bytes);
+                xfer.accept().ok();
+            }
+            REQ_SET_CONTROL_LINE_STATE => {
+                self.dtr = req.value & 0x1 != 0;
+                self.rts = req.value & 0x2 != 0;
+                xfer.accept().ok();
+            }
+            _ => {
+                xfer.reject().ok();
+            }
+        }
+    }
+}
+
+/// Device descriptor settings of [`UsbSerial`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct SerialConfig<'a> {
+    /// USB vendor ID.
+    pub vendor_id: u16,
+    /// USB product ID.
+    pub product_id: u16,
+    /// Manufacturer string.
+    pub manufacturer: &'a str,
+    /// Product string.
+    pub product: &'a str,
+    /// Serial number string.
+    pub serial_number: &'a str,
+}
+
+impl Default for SerialConfig<'_> {
+    /// Test vendor and product ID of pid.codes, for development only.
+    #[inline]
+    fn default() -> Self {
+        Self {
+            vendor_id: 0x1209,
+            product_id: 0x0001,
+            manufacturer: "Allwinner",
+            product: "USB Serial",
+            serial_number: "0",
+        }
+    }
+}
+
+/// USB serial error.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SerialError {
+    /// Host did not take written data before crate-wide blocking timeout.
+    ///
+    /// See [`crate::time::set_blocking_timeout`].
+    Timeout,
+    /// Error from USB device stack.
+    Usb(UsbError),
+}
+
+impl embedded_io::Error for SerialError {
+    #[inline]
+    fn kind(&self) -> embedded_io::ErrorKind {
+        match self {
+            SerialError::Timeout => embedded_io::ErrorKind::TimedOut,
+            SerialError::Usb(_) => embedded_io::ErrorKind::Other,
+        }
+    }
+}
+
+/// USB CDC-ACM serial port over USB OTG, a ready-made USB device with one class.
+///
+/// Reads and writes poll the USB device while waiting; call [`UsbSerial::poll`] from USB
+/// interrupt handler or main loop as well, so that host requests are answered in time.
+pub struct UsbSerial<'a, USB: AsRef<RegisterBlock> + Sync> {
+    device: UsbDevice<'a, UsbBus<USB>>,
+    class: CdcAcm<'a, UsbBus<USB>>,
+    rx: [u8; SERIAL_PACKET_SIZE as usize],
+    rx_start: usize,
+    rx_end: usize,
+    // Last packet written was full, so host waits for a short packet to end the transfer.
+    zlp_pending: bool,
+}
+
+impl<'a, USB: AsRef<RegisterBlock> + Sync> UsbSerial<'a, USB> {
+    /// Create a USB serial device on `alloc`, which should hold no other classes.
+    #[inline]
+    pub fn new(alloc: &'a UsbBusAllocator<UsbBus<USB>>, config: SerialConfig<'a>) -> Self {
+        let class = CdcAcm::new(alloc, SERIAL_PACKET_SIZE);
+        let strings = StringDescriptors::default()
+            .manufacturer(config.manufacturer)
+            .product(config.product)
+            .serial_number(config.serial_number);
+        // note(unwrap): one language and packet size of 64 are always accepted.
+        let device = UsbDeviceBuilder::new(alloc, UsbVidPid(config.vendor_id, config.product_id))
+            .strings(&[strings])
+            .unwrap()
+            .max_packet_size_0(64)
+            .unwrap()
+            .device_class(USB_CLASS_CDC)
+            .build();
+        Self {
+            device,
+            class,
+            rx: [0; SERIAL_PACKET_SIZE as usize],
+            rx_start: 0,
+            rx_end: 0,
+            zlp_pending: false,
+        }
+    }
+    /// Handle USB events and host requests; returns true if data may be available.
+    #[inline]
+    pub fn poll(&mut self) -> bool {
+        self.device.poll(&mut [&mut self.class])
+    }
+    /// Check if host has configured the device and opened the port.
+    #[inline]
+    pub fn is_connected(&self) -> bool {
+        self.device.state() == UsbDeviceState::Configured && self.class.dtr()
+    }
+    /// Get line settings last set by host.
+    #[inline]
+    pub fn line_coding(&self) -> LineCoding {
+        self.class.line_coding()
+    }
+    /// Get the underlying CDC-ACM class.
+    #[inline]
+    pub fn class(&mut self) -> &mut CdcAcm<'a, UsbBus<USB>> {
+        &mut self.class
+    }
+}
+
+impl<USB: AsRef<RegisterBlock> + Sync> embedded_io::ErrorType for UsbSerial<'_, USB> {
+    type Error = SerialError;
+}
+
+impl<USB: AsRef<RegisterBlock> + Sync> embedded_io::Read for UsbSerial<'_, USB> {
+    /// Block until at least one byte is received from host.
+    #[inline]
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize, Self::Error> {
+        if buf.is_empty() {
+            return Ok(0);
+        }
+        while self.rx_start == self.rx_end {
+            self.poll();
+            match self.class.read_packet(&mut self.rx) {
+                Ok(count) => (self.rx_start, self.rx_end) = (0, count),
+                Err(UsbError::WouldBlock) => core::hint::spin_loop(),
+                Err(e) => return Err(SerialError::Usb(e)),
+            }
+        }
+        let count = buf.len().min(self.rx_end - self.rx_start);
+        buf[..count].copy_from_slice(&self.rx[self.rx_start..self.rx_start + count]);
+        self.rx_start += count;
+        Ok(count)
+    }
+}
+
+impl<USB: AsRef<RegisterBlock> + Sync> embedded_io::ReadReady for UsbSerial<'_, USB> {
+    #[inline]
+    fn read_ready(&mut self) -> Result<bool, Self::Error> {
+        if self.rx_start == self.rx_end {
+            self.poll();
+            match self.class.read_packet(&mut self.rx) {
+                Ok(count) => (self.rx_start, self.rx_end) = (0, count),
+                Err(UsbError::WouldBlock) => {}
+                Err(e) => return Err(SerialError::Usb(e)),
+            }
+        }
+        Ok(self.rx_start != self.rx_end)
+    }
+}
+
+impl<USB: AsRef<RegisterBlock> + Sync> embedded_io::Write for UsbSerial<'_, USB> {
+    /// Write at most one packet, waiting for device to be configured and host to take
+    /// previous packet.
+    #[inline]
+    fn write(&mut self, buf: &[u8]) -> Result<usize, Self::Error> {
+        if buf.is_empty() {
+            return Ok(0);
+        }
+        let len = buf.len().min(self.class.max_packet_size() as usize);
+        let deadline = Deadline::start();
+        loop {
+            self.poll();
+            if self.device.state() == UsbDeviceState::Configured {
+                match self.class.write_packet(&buf[..len]) {
+                    Ok(count) => {
+                        self.zlp_pending = count == self.class.max_packet_size() as usize;
+                        return Ok(count);
+                    }
+                    Err(UsbError::WouldBlock) => {}
+                    Err(e) => return Err(SerialError::Usb(e)),
+                }
+            }
+            deadline.check().map_err(|_| SerialError::Timeout)?;
+            core::hint::spin_loop()
+        }
+    }
+
+    /// End current transfer with a zero-length packet if the last packet was full.
+    #[inline]
+    fn flush(&mut self) -> Result<(), Self::Error> {
+        let deadline = Deadline::start();
+        while self.zlp_pending {
+            self.poll();
+            match self.class.write_packet(&[]) {
+                Ok(_) => self.zlp_pending = false,
+                Err(UsbError::WouldBlock) => {
+                    deadline.check().map_err(|_| SerialError::Timeout)?;
+                    core::hint::spin_loop()
+                }
+                Err(e) => return Err(SerialError::Usb(e)),
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{LineCoding, Parity, StopBits};
+
+    #[test]
+    fn struct_line_coding_functions() {
+        let val = LineCoding::from_bytes([0x00, 0xC2, 0x01, 0x00, 0x00, 0x00, 0x08]);
+        assert_eq!(val, LineCoding::default());
+        assert_eq!(val.to_bytes(), [0x00, 0xC2, 0x01, 0x00, 0x00, 0x00, 0x08]);
+
+        let val = LineCoding {
+            data_rate: 1_500_000,
+            stop_bits: StopBits::Two,
+            parity: Parity::Even,
+            data_bits: 7,
+        };
+        assert_eq!(val.to_bytes(), [0x60, 0xE3, 0x16, 0x00, 0x02, 0x02, 0x07]);
+        assert_eq!(LineCoding::from_bytes(val.to_bytes()), val);
+
+        let val = LineCoding::from_bytes([0x80, 0x25, 0x00, 0x00, 0x01, 0x04, 0x05]);
+        assert_eq!(val.data_rate, 9600);
+        assert_eq!(val.stop_bits, StopBits::OnePointFive);
+        assert_eq!(val.parity, Parity::Space);
+        assert_eq!(val.data_bits, 5);
+    }
+}
@@ -1,6 +1,6 @@
 //! SoC configuration on D1-like chips.
 
-use crate::{smhc, spi, uart};
+use crate::{cir, interrupt, ledc, pwm, smhc, spi, tcon, uart};
 use core::num::NonZeroU32;
 
 impl_gpio_pins! {
@@ -150,6 +150,15 @@ impl_pins_trait! {
     ('G', 18, 2): uart::Receive<2>;
 }
 
+// CAN pins
+#[cfg(feature = "embedded-can")]
+impl_pins_trait! {
+    ('B', 2, 8): crate::can::Transmit<0>;
+    ('B', 3, 8): crate::can::Receive<0>;
+    ('B', 4, 8): crate::can::Transmit<1>;
+    ('B', 5, 8): crate::can::Receive<1>;
+}
+
 // SPI PINS
 impl_pins_trait! {
     ('B', 9, 5): spi::Miso<1>;
@@ -165,24 +174,213 @@ impl_pins_trait! {
 
 // SMHC pins
 impl_pins_trait! {
-    ('F', 0, 2): smhc::Data<1>;
-    ('F', 1, 2): smhc::Data<0>;
-    ('F', 2, 2): smhc::Clk;
-    ('F', 3, 2): smhc::Cmd;
-    ('F', 4, 2): smhc::Data<3>;
-    ('F', 5, 2): smhc::Data<2>;
-    ('G', 0, 2): smhc::Clk;
-    ('G', 1, 2): smhc::Cmd;
-    ('G', 2, 2): smhc::Data<0>;
-    ('G', 3, 2): smhc::Data<1>;
-    ('G', 4, 2): smhc::Data<2>;
-    ('G', 5, 2): smhc::Data<3>;
-    ('C', 2, 3): smhc::Clk;
-    ('C', 3, 3): smhc::Cmd;
-    ('C', 4, 3): smhc::Data<2>;
-    ('C', 5, 3): smhc::Data<1>;
-    ('C', 6, 3): smhc::Data<0>;
-    ('C', 7, 3): smhc::Data<3>;
+    ('F', 0, 2): smhc::Data<0, 1>;
+    ('F', 1, 2): smhc::Data<0, 0>;
+    ('F', 2, 2): smhc::Clk<0>;
+    ('F', 3, 2): smhc::Cmd<0>;
+    ('F', 4, 2): smhc::Data<0, 3>;
+    ('F', 5, 2): smhc::Data<0, 2>;
+    ('G', 0, 2): smhc::Clk<1>;
+    ('G', 1, 2): smhc::Cmd<1>;
+    ('G', 2, 2): smhc::Data<1, 0>;
+    ('G', 3, 2): smhc::Data<1, 1>;
+    ('G', 4, 2): smhc::Data<1, 2>;
+    ('G', 5, 2): smhc::Data<1, 3>;
+    ('C', 2, 3): smhc::Clk<2>;
+    ('C', 3, 3): smhc::Cmd<2>;
+    ('C', 4, 3): smhc::Data<2, 2>;
+    ('C', 5, 3): smhc::Data<2, 1>;
+    ('C', 6, 3): smhc::Data<2, 0>;
+    ('C', 7, 3): smhc::Data<2, 3>;
+}
+
+// PWM pins
+impl_pins_trait! {
+    ('B', 0, 2): pwm::Output<3>;
+    ('B', 1, 2): pwm::Output<4>;
+    ('B', 5, 5): pwm::Output<0>;
+    ('B', 6, 5): pwm::Output<1>;
+    ('B', 8, 3): pwm::Output<5>;
+    ('B', 9, 3): pwm::Output<6>;
+    ('B', 10, 3): pwm::Output<7>;
+    ('B', 11, 3): pwm::Output<2>;
+    ('B', 12, 3): pwm::Output<0>;
+    ('D', 16, 5): pwm::Output<0>;
+    ('D', 17, 5): pwm::Output<1>;
+    ('D', 18, 5): pwm::Output<2>;
+    ('D', 19, 5): pwm::Output<3>;
+    ('D', 20, 5): pwm::Output<4>;
+    ('D', 21, 5): pwm::Output<5>;
+    ('D', 22, 5): pwm::Output<7>;
+}
+
+// LEDC pins
+impl_pins_trait! {
+    ('C', 0, 4): ledc::Output;
+}
+
+// CIR pins
+impl_pins_trait! {
+    ('B', 7, 5): cir::Receive;
+}
+
+// RGB LCD pins
+impl_pins_trait! {
+    ('D', 0, 2): tcon::Data<2>;
+    ('D', 1, 2): tcon::Data<3>;
+    ('D', 2, 2): tcon::Data<4>;
+    ('D', 3, 2): tcon::Data<5>;
+    ('D', 4, 2): tcon::Data<6>;
+    ('D', 5, 2): tcon::Data<7>;
+    ('D', 6, 2): tcon::Data<10>;
+    ('D', 7, 2): tcon::Data<11>;
+    ('D', 8, 2): tcon::Data<12>;
+    ('D', 9, 2): tcon::Data<13>;
+    ('D', 10, 2): tcon::Data<14>;
+    ('D', 11, 2): tcon::Data<15>;
+    ('D', 12, 2): tcon::Data<18>;
+    ('D', 13, 2): tcon::Data<19>;
+    ('D', 14, 2): tcon::Data<20>;
+    ('D', 15, 2): tcon::Data<21>;
+    ('D', 16, 2): tcon::Data<22>;
+    ('D', 17, 2): tcon::Data<23>;
+    ('D', 18, 2): tcon::Clk;
+    ('D', 19, 2): tcon::DataEnable;
+    ('D', 20, 2): tcon::Hsync;
+    ('D', 21, 2): tcon::Vsync;
+}
+
+// LVDS pins
+impl_pins_trait! {
+    ('D', 0, 3): tcon::LvdsDataP<0, 0>;
+    ('D', 1, 3): tcon::LvdsDataN<0, 0>;
+    ('D', 2, 3): tcon::LvdsDataP<0, 1>;
+    ('D', 3, 3): tcon::LvdsDataN<0, 1>;
+    ('D', 4, 3): tcon::LvdsDataP<0, 2>;
+    ('D', 5, 3): tcon::LvdsDataN<0, 2>;
+    ('D', 6, 3): tcon::LvdsClockP<0>;
+    ('D', 7, 3): tcon::LvdsClockN<0>;
+    ('D', 8, 3): tcon::LvdsDataP<0, 3>;
+    ('D', 9, 3): tcon::LvdsDataN<0, 3>;
+    ('D', 10, 3): tcon::LvdsDataP<1, 0>;
+    ('D', 11, 3): tcon::LvdsDataN<1, 0>;
+    ('D', 12, 3): tcon::LvdsDataP<1, 1>;
+    ('D', 13, 3): tcon::LvdsDataN<1, 1>;
+    ('D', 14, 3): tcon::LvdsDataP<1, 2>;
+    ('D', 15, 3): tcon::LvdsDataN<1, 2>;
+    ('D', 16, 3): tcon::LvdsClockP<1>;
+    ('D', 17, 3): tcon::LvdsClockN<1>;
+    ('D', 18, 3): tcon::LvdsDataP<1, 3>;
+    ('D', 19, 3): tcon::LvdsDataN<1, 3>;
+}
+
+/// Dedicated analog input pad of GPADC channel 0.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct GPADC0;
+
+#[cfg(feature = "embedded-hal-02")]
+impl<GPADC> embedded_hal_02::adc::Channel<crate::gpadc::Gpadc<GPADC>> for GPADC0 {
+    type ID = u8;
+    #[inline]
+    fn channel() -> u8 {
+        0
+    }
+}
+
+/// DMA request ports of D1-like chips.
+///
+/// `_TX` ports are transfer destinations and `_RX` ports are transfer sources.
+pub mod drq {
+    use crate::dmac::Drq;
+
+    /// One Wire Audio receive.
+    pub const OWA_RX: Drq = Drq::new(2);
+    /// One Wire Audio transmit.
+    pub const OWA_TX: Drq = Drq::new(2);
+    /// I2S/PCM 0 receive.
+    pub const I2S0_RX: Drq = Drq::new(3);
+    /// I2S/PCM 0 transmit.
+    pub const I2S0_TX: Drq = Drq::new(3);
+    /// I2S/PCM 1 receive.
+    pub const I2S1_RX: Drq = Drq::new(4);
+    /// I2S/PCM 1 transmit.
+    pub const I2S1_TX: Drq = Drq::new(4);
+    /// I2S/PCM 2 receive.
+    pub const I2S2_RX: Drq = Drq::new(5);
+    /// I2S/PCM 2 transmit.
+    pub const I2S2_TX: Drq = Drq::new(5);
+    /// Audio codec ADC.
+    pub const AUDIO_CODEC_RX: Drq = Drq::new(7);
+    /// Audio codec DAC.
+    pub const AUDIO_CODEC_TX: Drq = Drq::new(7);
+    /// Digital microphone receive.
+    pub const DMIC_RX: Drq = Drq::new(8);
+    /// General purpose ADC data.
+    pub const GPADC_RX: Drq = Drq::new(12);
+    /// Touch panel ADC data.
+    pub const TPADC_RX: Drq = Drq::new(13);
+    /// Infrared transmitter.
+    pub const IR_TX: Drq = Drq::new(13);
+    /// UART 0 receive.
+    pub const UART0_RX: Drq = Drq::new(14);
+    /// UART 0 transmit.
+    pub const UART0_TX: Drq = Drq::new(14);
+    /// UART 1 receive.
+    pub const UART1_RX: Drq = Drq::new(15);
+    /// UART 1 transmit.
+    pub const UART1_TX: Drq = Drq::new(15);
+    /// UART 2 receive.
+    pub const UART2_RX: Drq = Drq::new(16);
+    /// UART 2 transmit.
+    pub const UART2_TX: Drq = Drq::new(16);
+    /// UART 3 receive.
+    pub const UART3_RX: Drq = Drq::new(17);
+    /// UART 3 transmit.
+    pub const UART3_TX: Drq = Drq::new(17);
+    /// UART 4 receive.
+    pub const UART4_RX: Drq = Drq::new(18);
+    /// UART 4 transmit.
+    pub const UART4_TX: Drq = Drq::new(18);
+    /// UART 5 receive.
+    pub const UART5_RX: Drq = Drq::new(19);
+    /// UART 5 transmit.
+    pub const UART5_TX: Drq = Drq::new(19);
+    /// SPI 0 receive.
+    pub const SPI0_RX: Drq = Drq::new(22);
+    /// SPI 0 transmit.
+    pub const SPI0_TX: Drq = Drq::new(22);
+    /// SPI 1 receive.
+    pub const SPI1_RX: Drq = Drq::new(23);
+    /// SPI 1 transmit.
+    pub const SPI1_TX: Drq = Drq::new(23);
+    /// USB 0 device endpoint 1.
+    pub const USB0_EP1: Drq = Drq::new(30);
+    /// USB 0 device endpoint 2.
+    pub const USB0_EP2: Drq = Drq::new(31);
+    /// USB 0 device endpoint 3.
+    pub const USB0_EP3: Drq = Drq::new(32);
+    /// USB 0 device endpoint 4.
+    pub const USB0_EP4: Drq = Drq::new(33);
+    /// USB 0 device endpoint 5.
+    pub const USB0_EP5: Drq = Drq::new(34);
+    /// LED controller transmit.
+    pub const LEDC_TX: Drq = Drq::new(42);
+    /// TWI 0 receive.
+    pub const TWI0_RX: Drq = Drq::new(43);
+    /// TWI 0 transmit.
+    pub const TWI0_TX: Drq = Drq::new(43);
+    /// TWI 1 receive.
+    pub const TWI1_RX: Drq = Drq::new(44);
+    /// TWI 1 transmit.
+    pub const TWI1_TX: Drq = Drq::new(44);
+    /// TWI 2 receive.
+    pub const TWI2_RX: Drq = Drq::new(45);
+    /// TWI 2 transmit.
+    pub const TWI2_TX: Drq = Drq::new(45);
+    /// TWI 3 receive.
+    pub const TWI3_RX: Drq = Drq::new(46);
+    /// TWI 3 transmit.
+    pub const TWI3_TX: Drq = Drq::new(46);
 }
 
 /// Allwinner D1 interrupts.
@@ -201,10 +399,106 @@ pub enum Interrupt {
     UART4 = 22,
     /// Universal Asynchronous Receiver-Transmitter 5.
     UART5 = 23,
+    /// Two-Wire Interface 0.
+    TWI0 = 25,
+    /// Two-Wire Interface 1.
+    TWI1 = 26,
+    /// Two-Wire Interface 2.
+    TWI2 = 27,
+    /// Two-Wire Interface 3.
+    TWI3 = 28,
     /// Serial Peripheral Interface 0.
     SPI0 = 31,
     /// Serial Peripheral Interface 1.
     SPI1 = 32,
+    /// Pulse Width Modulation.
+    PWM = 34,
+    /// Infrared transmitter.
+    IRTX = 35,
+    /// LED Controller.
+    LEDC = 36,
+    /// Controller Area Network 0.
+    CAN0 = 37,
+    /// Controller Area Network 1.
+    CAN1 = 38,
+    /// One Wire Audio (S/PDIF).
+    OWA = 39,
+    /// Digital microphone interface.
+    DMIC = 40,
+    /// Audio codec.
+    CODEC = 41,
+    /// I2S/PCM 0.
+    I2S0 = 42,
+    /// I2S/PCM 1.
+    I2S1 = 43,
+    /// I2S/PCM 2.
+    I2S2 = 44,
+    /// USB On-The-Go controller 0, in device mode.
+    USB0 = 45,
+    /// USB 0 Enhanced Host Controller.
+    EHCI0 = 46,
+    /// USB 0 Open Host Controller.
+    OHCI0 = 47,
+    /// USB 1 Enhanced Host Controller.
+    EHCI1 = 49,
+    /// USB 1 Open Host Controller.
+    OHCI1 = 50,
+    /// SD/MMC Host Controller 0.
+    SMHC0 = 56,
+    /// SD/MMC Host Controller 1.
+    SMHC1 = 57,
+    /// SD/MMC Host Controller 2.
+    SMHC2 = 58,
+    /// Ethernet Media Access Controller.
+    EMAC = 62,
+    /// Direct Memory Access Controller, non-secure.
+    DMAC = 66,
+    /// Crypto Engine, non-secure.
+    CE = 68,
+    /// Hardware spinlock.
+    SPINLOCK = 70,
+    /// High speed timer 0.
+    HSTIMER0 = 71,
+    /// High speed timer 1.
+    HSTIMER1 = 72,
+    /// General Purpose Analog-to-Digital Converter.
+    GPADC = 73,
+    /// Thermal Sensor.
+    THS = 74,
+    /// Timer 0.
+    TIMER0 = 75,
+    /// Timer 1.
+    TIMER1 = 76,
+    /// Low Rate Analog-to-Digital Converter.
+    LRADC = 77,
+    /// Touch panel Analog-to-Digital Converter.
+    TPADC = 78,
+    /// Watchdog.
+    WATCHDOG = 79,
+    /// GPIO port B external interrupts, non-secure.
+    GPIOB = 85,
+    /// GPIO port C external interrupts, non-secure.
+    GPIOC = 87,
+    /// GPIO port D external interrupts, non-secure.
+    GPIOD = 89,
+    /// GPIO port E external interrupts, non-secure.
+    GPIOE = 91,
+    /// GPIO port F external interrupts, non-secure.
+    GPIOF = 93,
+    /// GPIO port G external interrupts, non-secure.
+    GPIOG = 95,
+    /// Display Engine.
+    DE = 103,
+    /// 2D graphics accelerator.
+    G2D = 105,
+    /// LCD timing controller 0.
+    LCD0 = 106,
+    /// High-Definition Multimedia Interface.
+    HDMI = 109,
+    /// Real-time clock alarm.
+    RTC = 160,
+    /// Consumer infrared receiver.
+    IRRX = 167,
 }
 
 impl plic::InterruptSource for Interrupt {
@@ -214,6 +508,71 @@ impl plic::InterruptSource for Interrupt {
     }
 }
 
+impl interrupt::Source for Interrupt {
+    #[inline]
+    fn from_id(id: NonZeroU32) -> Option<Self> {
+        Some(match id.get() {
+            18 => Interrupt::UART0,
+            19 => Interrupt::UART1,
+            20 => Interrupt::UART2,
+            21 => Interrupt::UART3,
+            22 => Interrupt::UART4,
+            23 => Interrupt::UART5,
+            25 => Interrupt::TWI0,
+            26 => Interrupt::TWI1,
+            27 => Interrupt::TWI2,
+            28 => Interrupt::TWI3,
+            31 => Interrupt::SPI0,
+            32 => Interrupt::SPI1,
+            34 => Interrupt::PWM,
+            35 => Interrupt::IRTX,
+            36 => Interrupt::LEDC,
+            37 => Interrupt::CAN0,
+            38 => Interrupt::CAN1,
+            39 => Interrupt::OWA,
+            40 => Interrupt::DMIC,
+            41 => Interrupt::CODEC,
+            42 => Interrupt::I2S0,
+            43 => Interrupt::I2S1,
+            44 => Interrupt::I2S2,
+            45 => Interrupt::USB0,
+            46 => Interrupt::EHCI0,
+            47 => Interrupt::OHCI0,
+            49 => Interrupt::EHCI1,
+            50 => Interrupt::OHCI1,
+            56 => Interrupt::SMHC0,
+            57 => Interrupt::SMHC1,
+            58 => Interrupt::SMHC2,
+            62 => Interrupt::EMAC,
+            66 => Interrupt::DMAC,
+            68 => Interrupt::CE,
+            70 => Interrupt::SPINLOCK,
+            71 => Interrupt::HSTIMER0,
+            72 => Interrupt::HSTIMER1,
+            73 => Interrupt::GPADC,
+            74 => Interrupt::THS,
+            75 => Interrupt::TIMER0,
+            76 => Interrupt::TIMER1,
+            77 => Interrupt::LRADC,
+            78 => Interrupt::TPADC,
+            79 => Interrupt::WATCHDOG,
+            85 => Interrupt::GPIOB,
+            87 => Interrupt::GPIOC,
+            89 => Interrupt::GPIOD,
+            91 => Interrupt::GPIOE,
+            93 => Interrupt::GPIOF,
+            95 => Interrupt::GPIOG,
+            103 => Interrupt::DE,
+            105 => Interrupt::G2D,
+            106 => Interrupt::LCD0,
+            109 => Interrupt::HDMI,
+            160 => Interrupt::RTC,
+            167 => Interrupt::IRRX,
+            _ => return None,
+        })
+    }
+}
+
 /// Machine mode hart context for T-Head C906 core.
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
 pub struct Machine;
@@ -233,3 +592,19 @@ impl plic::HartContext for Supevisor {
         1
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::Interrupt;
+    use crate::interrupt::Source;
+    use core::num::NonZeroU32;
+    use plic::InterruptSource;
+
+    #[test]
+    fn struct_interrupt_functions() {
+        for interrupt in [Interrupt::UART0, Interrupt::GPIOB, Interrupt::IRRX] {
+            assert_eq!(Interrupt::from_id(interrupt.id()), Some(interrupt));
+        }
+        assert_eq!(Interrupt::from_id(NonZeroU32::new(24).unwrap()), None);
+    }
+}
@@ -0,0 +1,69 @@
+//! Waker registration shared by async drivers.
+//!
+//! A peripheral keeps a [`WakerSet`] with one waker per interrupt source, such as a timer,
+//! a CAN half or a DMA channel. Async methods enable the interrupt of their source and wait
+//! with [`WakerSet::wait_until`]. The peripheral's public `on_interrupt` function, which
+//! applications must call from its interrupt handler, disables interrupts of the sources
+//! that raised them and wakes their tasks, leaving pending status for those tasks to handle.
+
+use atomic_waker::AtomicWaker;
+use core::{future::poll_fn, task::Poll};
+
+/// Wakers of tasks waiting on `N` interrupt sources of a peripheral.
+pub(crate) struct WakerSet<const N: usize> {
+    wakers: [AtomicWaker; N],
+}
+
+impl<const N: usize> WakerSet<N> {
+    /// Create a waker set with no task waiting.
+    #[inline]
+    pub const fn new() -> Self {
+        Self {
+            wakers: [const { AtomicWaker::new() }; N],
+        }
+    }
+    /// Wake task waiting on interrupt `source`, if any.
+    #[inline]
+    pub fn wake(&self, source: usize) {
+        self.wakers[source].wake();
+    }
+    /// Wait until `ready` returns true, checking again whenever `source` is woken.
+    ///
+    /// Waker is registered before each check, so that wakeups between the check and
+    /// returning pending are not lost.
+    #[inline]
+    pub async fn wait_until(&self, source: usize, mut ready: impl FnMut() -> bool) {
+        poll_fn(|cx| {
+            self.wakers[source].register(cx.waker());
+            if ready() {
+                Poll::Ready(())
+            } else {
+                Poll::Pending
+            }
+        })
+        .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::WakerSet;
+    use core::{
+        cell::Cell,
+        future::Future,
+        pin::pin,
+        task::{Context, Poll, Waker},
+    };
+
+    #[test]
+    fn struct_waker_set_functions() {
+        static WAKERS: WakerSet<2> = WakerSet::new();
+        let ready = Cell::new(false);
+        let mut cx = Context::from_waker(Waker::noop());
+        let mut future = pin!(WAKERS.wait_until(1, || ready.get()));
+        assert_eq!(future.as_mut().poll(&mut cx), Poll::Pending);
+        ready.set(true);
+        WAKERS.wake(1);
+        assert_eq!(future.as_mut().poll(&mut cx), Poll::Ready(()));
+    }
+}
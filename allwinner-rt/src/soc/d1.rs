@@ -1,6 +1,7 @@
 //! D1-H, D1s, F133, F133A/B chip platforms.
 
 use allwinner_hal::{ccu::Clocks, gpio::Disabled, wafer::d1::Pads};
+use core::sync::atomic::{AtomicBool, Ordering};
 use embedded_time::rate::Extensions;
 
 /// ROM runtime peripheral ownership and configurations.
@@ -15,6 +16,10 @@ pub struct Peripherals<'a> {
     pub com: COM,
     /// Memory controller physical layer (PHY) of DDR SDRAM.
     pub phy: PHY,
+    /// Direct Memory Access Controller.
+    pub dmac: DMAC,
+    /// Security ID and eFuse controller.
+    pub sid: SID,
     /// SD/MMC Host Controller peripheral 0.
     pub smhc0: SMHC0,
     /// SD/MMC Host Controller peripheral 1.
@@ -23,6 +28,68 @@ pub struct Peripherals<'a> {
     pub smhc2: SMHC2,
     /// Serial Peripheral Interface peripheral 0.
     pub spi0: SPI0,
+    /// Controller Area Network peripheral 0.
+    pub can0: CAN0,
+    /// Controller Area Network peripheral 1.
+    pub can1: CAN1,
+    /// General purpose timers.
+    pub timer: TIMER,
+    /// High speed timers.
+    pub hstimer: HSTIMER,
+    /// Real-time clock.
+    pub rtc: RTC,
+    /// Pulse Width Modulation.
+    pub pwm: PWM,
+    /// LED Controller.
+    pub ledc: LEDC,
+    /// General Purpose Analog-to-Digital Converter.
+    pub gpadc: GPADC,
+    /// Thermal Sensor.
+    pub ths: THS,
+    /// Low Rate Analog-to-Digital Converter.
+    pub lradc: LRADC,
+    /// Internal audio codec.
+    pub audio_codec: AUDIO_CODEC,
+    /// Digital microphone interface.
+    pub dmic: DMIC,
+    /// One Wire Audio, S/PDIF transmitter.
+    pub owa: OWA,
+    /// Crypto engine.
+    pub ce: CE,
+    /// USB On-The-Go controller.
+    pub usb0: USB0,
+    /// USB1 EHCI and OHCI host controllers.
+    pub usb1: USB1,
+    /// Ethernet Media Access Controller.
+    pub emac: EMAC,
+    /// Display engine top.
+    pub de: DE,
+    /// Display engine mixer 0.
+    pub de_mixer0: DE_MIXER0,
+    /// 2D graphics accelerator.
+    pub g2d: G2D,
+    /// Display subsystem top, routing display engine to timing controllers.
+    pub dpss_top: DPSS_TOP,
+    /// Timing controller of LCD panels.
+    pub tcon_lcd0: TCON_LCD0,
+    /// HDMI transmitter controller.
+    pub hdmi: HDMI,
+    /// HDMI transmitter PHY.
+    pub hdmi_phy: HDMI_PHY,
+    /// Clock control unit of CPUS power domain.
+    pub r_ccu: R_CCU,
+    /// Consumer infrared receiver.
+    pub cir: CIR,
+    /// System configuration, including DSP local SRAM mapping.
+    pub sysctl: SYSCTL,
+    /// HiFi4 Digital Signal Processor configuration.
+    pub dsp: DSP,
+    /// Message box of HiFi4 DSP.
+    pub msgbox0: MSGBOX0,
+    /// Message box of RISC-V CPU.
+    pub msgbox1: MSGBOX1,
+    /// Hardware spinlock shared with HiFi4 DSP.
+    pub spinlock: SPINLOCK,
     /// Platform-local Interrupt Controller.
     pub plic: PLIC,
 }
@@ -38,6 +105,10 @@ soc! {
     pub struct COM => 0x03102000, allwinner_hal::com::RegisterBlock;
     /// Memory controller physical layer (PHY) of DDR SDRAM.
     pub struct PHY => 0x03103000, allwinner_hal::phy::RegisterBlock;
+    /// Direct Memory Access Controller.
+    pub struct DMAC => 0x03002000, allwinner_hal::dmac::RegisterBlock;
+    /// Security ID and eFuse controller.
+    pub struct SID => 0x03006000, allwinner_hal::sid::RegisterBlock;
     /// SD/MMC Host Controller peripheral 0.
     pub struct SMHC0 => 0x04020000, allwinner_hal::smhc::RegisterBlock;
     /// SD/MMC Host Controller peripheral 1.
@@ -46,118 +117,243 @@ soc! {
     pub struct SMHC2 => 0x04022000, allwinner_hal::smhc::RegisterBlock;
     /// Serial Peripheral Interface peripheral 0.
     pub struct SPI0 => 0x04025000, allwinner_hal::spi::RegisterBlock;
+    /// Controller Area Network peripheral 0.
+    pub struct CAN0 => 0x02504000, allwinner_hal::can::RegisterBlock;
+    /// Controller Area Network peripheral 1.
+    pub struct CAN1 => 0x02504400, allwinner_hal::can::RegisterBlock;
+    /// General purpose timers.
+    pub struct TIMER => 0x02050000, allwinner_hal::timer::RegisterBlock;
+    /// High speed timers.
+    pub struct HSTIMER => 0x03008000, allwinner_hal::hstimer::RegisterBlock;
+    /// Real-time clock.
+    pub struct RTC => 0x07090000, allwinner_hal::rtc::RegisterBlock;
+    /// Pulse Width Modulation.
+    pub struct PWM => 0x02000C00, allwinner_hal::pwm::RegisterBlock;
+    /// LED Controller.
+    pub struct LEDC => 0x02008000, allwinner_hal::ledc::RegisterBlock;
+    /// General Purpose Analog-to-Digital Converter.
+    pub struct GPADC => 0x02009000, allwinner_hal::gpadc::RegisterBlock;
+    /// Thermal Sensor.
+    pub struct THS => 0x02009400, allwinner_hal::ths::RegisterBlock;
+    /// Low Rate Analog-to-Digital Converter.
+    pub struct LRADC => 0x02009800, allwinner_hal::lradc::RegisterBlock;
+    /// Internal audio codec.
+    pub struct AUDIO_CODEC => 0x02030000, allwinner_hal::audio_codec::RegisterBlock;
+    /// Digital microphone interface.
+    pub struct DMIC => 0x02031000, allwinner_hal::dmic::RegisterBlock;
+    /// One Wire Audio, S/PDIF transmitter.
+    pub struct OWA => 0x02036000, allwinner_hal::owa::RegisterBlock;
+    /// Crypto engine.
+    pub struct CE => 0x03040000, allwinner_hal::ce::RegisterBlock;
+    /// USB On-The-Go controller.
+    pub struct USB0 => 0x04100000, allwinner_hal::usb::RegisterBlock;
+    /// USB1 EHCI and OHCI host controllers.
+    pub struct USB1 => 0x04200000, allwinner_hal::usb::hci::RegisterBlock;
+    /// Ethernet Media Access Controller.
+    pub struct EMAC => 0x04500000, allwinner_hal::emac::RegisterBlock;
+    /// Display engine top.
+    pub struct DE => 0x05000000, allwinner_hal::de::RegisterBlock;
+    /// Display engine mixer 0.
+    pub struct DE_MIXER0 => 0x05100000, allwinner_hal::de::MixerRegisterBlock;
+    /// 2D graphics accelerator.
+    pub struct G2D => 0x05410000, allwinner_hal::g2d::RegisterBlock;
+    /// Display subsystem top, routing display engine to timing controllers.
+    pub struct DPSS_TOP => 0x05460000, allwinner_hal::tcon::TopRegisterBlock;
+    /// Timing controller of LCD panels.
+    pub struct TCON_LCD0 => 0x05461000, allwinner_hal::tcon::RegisterBlock;
+    /// HDMI transmitter controller.
+    pub struct HDMI => 0x05500000, allwinner_hal::hdmi::RegisterBlock;
+    /// HDMI transmitter PHY.
+    pub struct HDMI_PHY => 0x05510000, allwinner_hal::hdmi::PhyRegisterBlock;
+    /// Clock control unit of CPUS power domain.
+    pub struct R_CCU => 0x07010000, allwinner_hal::r_ccu::RegisterBlock;
+    /// Consumer infrared receiver.
+    pub struct CIR => 0x07040000, allwinner_hal::cir::RegisterBlock;
+    /// System configuration, including DSP local SRAM mapping.
+    pub struct SYSCTL => 0x03000000, allwinner_hal::sysctl::RegisterBlock;
+    /// HiFi4 Digital Signal Processor configuration.
+    pub struct DSP => 0x01700000, allwinner_hal::dsp::RegisterBlock;
+    /// Message box of HiFi4 DSP.
+    pub struct MSGBOX0 => 0x01701000, allwinner_hal::msgbox::RegisterBlock;
+    /// Message box of RISC-V CPU.
+    pub struct MSGBOX1 => 0x0601F000, allwinner_hal::msgbox::RegisterBlock;
+    /// Hardware spinlock shared with HiFi4 DSP.
+    pub struct SPINLOCK => 0x03005000, allwinner_hal::hwspinlock::RegisterBlock;
     /// Platform-local Interrupt Controller.
     pub struct PLIC => 0x10000000, plic::Plic;
 }
 
+/// Set once peripherals are handed out.
+static TAKEN: AtomicBool = AtomicBool::new(false);
+
+impl Peripherals<'static> {
+    /// Take ownership of all peripherals, or `None` if they were already taken.
+    ///
+    /// ROM runtime takes peripherals before calling the `#[entry]` function, which receives
+    /// them as its parameter; this function then returns `None`.
+    #[inline]
+    pub fn take() -> Option<Self> {
+        if TAKEN.swap(true, Ordering::AcqRel) {
+            None
+        } else {
+            Some(unsafe { Self::steal() })
+        }
+    }
+    /// Create ownership tokens of all peripherals, whether or not they were taken.
+    ///
+    /// # Safety
+    ///
+    /// Tokens alias those handed out before. Caller must ensure that no peripheral
+    /// is driven through two tokens at the same time.
+    #[inline]
+    pub unsafe fn steal() -> Self {
+        static _GPIO: GPIO = GPIO { _private: () };
+        Peripherals {
+            gpio: Pads {
+                pb0: unsafe { Disabled::__new(&_GPIO) },
+                pb1: unsafe { Disabled::__new(&_GPIO) },
+                pb2: unsafe { Disabled::__new(&_GPIO) },
+                pb3: unsafe { Disabled::__new(&_GPIO) },
+                pb4: unsafe { Disabled::__new(&_GPIO) },
+                pb5: unsafe { Disabled::__new(&_GPIO) },
+                pb6: unsafe { Disabled::__new(&_GPIO) },
+                pb7: unsafe { Disabled::__new(&_GPIO) },
+                pb8: unsafe { Disabled::__new(&_GPIO) },
+                pb9: unsafe { Disabled::__new(&_GPIO) },
+                pb10: unsafe { Disabled::__new(&_GPIO) },
+                pb11: unsafe { Disabled::__new(&_GPIO) },
+                pb12: unsafe { Disabled::__new(&_GPIO) },
+                pc0: unsafe { Disabled::__new(&_GPIO) },
+                pc1: unsafe { Disabled::__new(&_GPIO) },
+                pc2: unsafe { Disabled::__new(&_GPIO) },
+                pc3: unsafe { Disabled::__new(&_GPIO) },
+                pc4: unsafe { Disabled::__new(&_GPIO) },
+                pc5: unsafe { Disabled::__new(&_GPIO) },
+                pc6: unsafe { Disabled::__new(&_GPIO) },
+                pc7: unsafe { Disabled::__new(&_GPIO) },
+                pd0: unsafe { Disabled::__new(&_GPIO) },
+                pd1: unsafe { Disabled::__new(&_GPIO) },
+                pd2: unsafe { Disabled::__new(&_GPIO) },
+                pd3: unsafe { Disabled::__new(&_GPIO) },
+                pd4: unsafe { Disabled::__new(&_GPIO) },
+                pd5: unsafe { Disabled::__new(&_GPIO) },
+                pd6: unsafe { Disabled::__new(&_GPIO) },
+                pd7: unsafe { Disabled::__new(&_GPIO) },
+                pd8: unsafe { Disabled::__new(&_GPIO) },
+                pd9: unsafe { Disabled::__new(&_GPIO) },
+                pd10: unsafe { Disabled::__new(&_GPIO) },
+                pd11: unsafe { Disabled::__new(&_GPIO) },
+                pd12: unsafe { Disabled::__new(&_GPIO) },
+                pd13: unsafe { Disabled::__new(&_GPIO) },
+                pd14: unsafe { Disabled::__new(&_GPIO) },
+                pd15: unsafe { Disabled::__new(&_GPIO) },
+                pd16: unsafe { Disabled::__new(&_GPIO) },
+                pd17: unsafe { Disabled::__new(&_GPIO) },
+                pd18: unsafe { Disabled::__new(&_GPIO) },
+                pd19: unsafe { Disabled::__new(&_GPIO) },
+                pd20: unsafe { Disabled::__new(&_GPIO) },
+                pd21: unsafe { Disabled::__new(&_GPIO) },
+                pd22: unsafe { Disabled::__new(&_GPIO) },
+                pe0: unsafe { Disabled::__new(&_GPIO) },
+                pe1: unsafe { Disabled::__new(&_GPIO) },
+                pe2: unsafe { Disabled::__new(&_GPIO) },
+                pe3: unsafe { Disabled::__new(&_GPIO) },
+                pe4: unsafe { Disabled::__new(&_GPIO) },
+                pe5: unsafe { Disabled::__new(&_GPIO) },
+                pe6: unsafe { Disabled::__new(&_GPIO) },
+                pe7: unsafe { Disabled::__new(&_GPIO) },
+                pe8: unsafe { Disabled::__new(&_GPIO) },
+                pe9: unsafe { Disabled::__new(&_GPIO) },
+                pe10: unsafe { Disabled::__new(&_GPIO) },
+                pe11: unsafe { Disabled::__new(&_GPIO) },
+                pe12: unsafe { Disabled::__new(&_GPIO) },
+                pe13: unsafe { Disabled::__new(&_GPIO) },
+                pe14: unsafe { Disabled::__new(&_GPIO) },
+                pe15: unsafe { Disabled::__new(&_GPIO) },
+                pe16: unsafe { Disabled::__new(&_GPIO) },
+                pe17: unsafe { Disabled::__new(&_GPIO) },
+                pf0: unsafe { Disabled::__new(&_GPIO) },
+                pf1: unsafe { Disabled::__new(&_GPIO) },
+                pf2: unsafe { Disabled::__new(&_GPIO) },
+                pf3: unsafe { Disabled::__new(&_GPIO) },
+                pf4: unsafe { Disabled::__new(&_GPIO) },
+                pf5: unsafe { Disabled::__new(&_GPIO) },
+                pf6: unsafe { Disabled::__new(&_GPIO) },
+                pg0: unsafe { Disabled::__new(&_GPIO) },
+                pg1: unsafe { Disabled::__new(&_GPIO) },
+                pg2: unsafe { Disabled::__new(&_GPIO) },
+                pg3: unsafe { Disabled::__new(&_GPIO) },
+                pg4: unsafe { Disabled::__new(&_GPIO) },
+                pg5: unsafe { Disabled::__new(&_GPIO) },
+                pg6: unsafe { Disabled::__new(&_GPIO) },
+                pg7: unsafe { Disabled::__new(&_GPIO) },
+                pg8: unsafe { Disabled::__new(&_GPIO) },
+                pg9: unsafe { Disabled::__new(&_GPIO) },
+                pg10: unsafe { Disabled::__new(&_GPIO) },
+                pg11: unsafe { Disabled::__new(&_GPIO) },
+                pg12: unsafe { Disabled::__new(&_GPIO) },
+                pg13: unsafe { Disabled::__new(&_GPIO) },
+                pg14: unsafe { Disabled::__new(&_GPIO) },
+                pg15: unsafe { Disabled::__new(&_GPIO) },
+                pg16: unsafe { Disabled::__new(&_GPIO) },
+                pg17: unsafe { Disabled::__new(&_GPIO) },
+                pg18: unsafe { Disabled::__new(&_GPIO) },
+            },
+            ccu: CCU { _private: () },
+            uart0: UART0 { _private: () },
+            com: COM { _private: () },
+            phy: PHY { _private: () },
+            dmac: DMAC { _private: () },
+            sid: SID { _private: () },
+            smhc0: SMHC0 { _private: () },
+            smhc1: SMHC1 { _private: () },
+            smhc2: SMHC2 { _private: () },
+            spi0: SPI0 { _private: () },
+            can0: CAN0 { _private: () },
+            can1: CAN1 { _private: () },
+            timer: TIMER { _private: () },
+            hstimer: HSTIMER { _private: () },
+            rtc: RTC { _private: () },
+            pwm: PWM { _private: () },
+            ledc: LEDC { _private: () },
+            gpadc: GPADC { _private: () },
+            ths: THS { _private: () },
+            lradc: LRADC { _private: () },
+            audio_codec: AUDIO_CODEC { _private: () },
+            dmic: DMIC { _private: () },
+            owa: OWA { _private: () },
+            ce: CE { _private: () },
+            usb0: USB0 { _private: () },
+            usb1: USB1 { _private: () },
+            emac: EMAC { _private: () },
+            de: DE { _private: () },
+            de_mixer0: DE_MIXER0 { _private: () },
+            g2d: G2D { _private: () },
+            dpss_top: DPSS_TOP { _private: () },
+            tcon_lcd0: TCON_LCD0 { _private: () },
+            hdmi: HDMI { _private: () },
+            hdmi_phy: HDMI_PHY { _private: () },
+            r_ccu: R_CCU { _private: () },
+            cir: CIR { _private: () },
+            sysctl: SYSCTL { _private: () },
+            dsp: DSP { _private: () },
+            msgbox0: MSGBOX0 { _private: () },
+            msgbox1: MSGBOX1 { _private: () },
+            spinlock: SPINLOCK { _private: () },
+            plic: PLIC { _private: () },
+        }
+    }
+}
+
 #[doc(hidden)]
 #[inline]
 pub fn __rom_init_params() -> (Peripherals<'static>, Clocks) {
-    static _GPIO: GPIO = GPIO { _private: () };
-    let peripherals = Peripherals {
-        gpio: Pads {
-            pb0: unsafe { Disabled::__new(&_GPIO) },
-            pb1: unsafe { Disabled::__new(&_GPIO) },
-            pb2: unsafe { Disabled::__new(&_GPIO) },
-            pb3: unsafe { Disabled::__new(&_GPIO) },
-            pb4: unsafe { Disabled::__new(&_GPIO) },
-            pb5: unsafe { Disabled::__new(&_GPIO) },
-            pb6: unsafe { Disabled::__new(&_GPIO) },
-            pb7: unsafe { Disabled::__new(&_GPIO) },
-            pb8: unsafe { Disabled::__new(&_GPIO) },
-            pb9: unsafe { Disabled::__new(&_GPIO) },
-            pb10: unsafe { Disabled::__new(&_GPIO) },
-            pb11: unsafe { Disabled::__new(&_GPIO) },
-            pb12: unsafe { Disabled::__new(&_GPIO) },
-            pc0: unsafe { Disabled::__new(&_GPIO) },
-            pc1: unsafe { Disabled::__new(&_GPIO) },
-            pc2: unsafe { Disabled::__new(&_GPIO) },
-            pc3: unsafe { Disabled::__new(&_GPIO) },
-            pc4: unsafe { Disabled::__new(&_GPIO) },
-            pc5: unsafe { Disabled::__new(&_GPIO) },
-            pc6: unsafe { Disabled::__new(&_GPIO) },
-            pc7: unsafe { Disabled::__new(&_GPIO) },
-            pd0: unsafe { Disabled::__new(&_GPIO) },
-            pd1: unsafe { Disabled::__new(&_GPIO) },
-            pd2: unsafe { Disabled::__new(&_GPIO) },
-            pd3: unsafe { Disabled::__new(&_GPIO) },
-            pd4: unsafe { Disabled::__new(&_GPIO) },
-            pd5: unsafe { Disabled::__new(&_GPIO) },
-            pd6: unsafe { Disabled::__new(&_GPIO) },
-            pd7: unsafe { Disabled::__new(&_GPIO) },
-            pd8: unsafe { Disabled::__new(&_GPIO) },
-            pd9: unsafe { Disabled::__new(&_GPIO) },
-            pd10: unsafe { Disabled::__new(&_GPIO) },
-            pd11: unsafe { Disabled::__new(&_GPIO) },
-            pd12: unsafe { Disabled::__new(&_GPIO) },
-            pd13: unsafe { Disabled::__new(&_GPIO) },
-            pd14: unsafe { Disabled::__new(&_GPIO) },
-            pd15: unsafe { Disabled::__new(&_GPIO) },
-            pd16: unsafe { Disabled::__new(&_GPIO) },
-            pd17: unsafe { Disabled::__new(&_GPIO) },
-            pd18: unsafe { Disabled::__new(&_GPIO) },
-            pd19: unsafe { Disabled::__new(&_GPIO) },
-            pd20: unsafe { Disabled::__new(&_GPIO) },
-            pd21: unsafe { Disabled::__new(&_GPIO) },
-            pd22: unsafe { Disabled::__new(&_GPIO) },
-            pe0: unsafe { Disabled::__new(&_GPIO) },
-            pe1: unsafe { Disabled::__new(&_GPIO) },
-            pe2: unsafe { Disabled::__new(&_GPIO) },
-            pe3: unsafe { Disabled::__new(&_GPIO) },
-            pe4: unsafe { Disabled::__new(&_GPIO) },
-            pe5: unsafe { Disabled::__new(&_GPIO) },
-            pe6: unsafe { Disabled::__new(&_GPIO) },
-            pe7: unsafe { Disabled::__new(&_GPIO) },
-            pe8: unsafe { Disabled::__new(&_GPIO) },
-            pe9: unsafe { Disabled::__new(&_GPIO) },
-            pe10: unsafe { Disabled::__new(&_GPIO) },
-            pe11: unsafe { Disabled::__new(&_GPIO) },
-            pe12: unsafe { Disabled::__new(&_GPIO) },
-            pe13: unsafe { Disabled::__new(&_GPIO) },
-            pe14: unsafe { Disabled::__new(&_GPIO) },
-            pe15: unsafe { Disabled::__new(&_GPIO) },
-            pe16: unsafe { Disabled::__new(&_GPIO) },
-            pe17: unsafe { Disabled::__new(&_GPIO) },
-            pf0: unsafe { Disabled::__new(&_GPIO) },
-            pf1: unsafe { Disabled::__new(&_GPIO) },
-            pf2: unsafe { Disabled::__new(&_GPIO) },
-            pf3: unsafe { Disabled::__new(&_GPIO) },
-            pf4: unsafe { Disabled::__new(&_GPIO) },
-            pf5: unsafe { Disabled::__new(&_GPIO) },
-            pf6: unsafe { Disabled::__new(&_GPIO) },
-            pg0: unsafe { Disabled::__new(&_GPIO) },
-            pg1: unsafe { Disabled::__new(&_GPIO) },
-            pg2: unsafe { Disabled::__new(&_GPIO) },
-            pg3: unsafe { Disabled::__new(&_GPIO) },
-            pg4: unsafe { Disabled::__new(&_GPIO) },
-            pg5: unsafe { Disabled::__new(&_GPIO) },
-            pg6: unsafe { Disabled::__new(&_GPIO) },
-            pg7: unsafe { Disabled::__new(&_GPIO) },
-            pg8: unsafe { Disabled::__new(&_GPIO) },
-            pg9: unsafe { Disabled::__new(&_GPIO) },
-            pg10: unsafe { Disabled::__new(&_GPIO) },
-            pg11: unsafe { Disabled::__new(&_GPIO) },
-            pg12: unsafe { Disabled::__new(&_GPIO) },
-            pg13: unsafe { Disabled::__new(&_GPIO) },
-            pg14: unsafe { Disabled::__new(&_GPIO) },
-            pg15: unsafe { Disabled::__new(&_GPIO) },
-            pg16: unsafe { Disabled::__new(&_GPIO) },
-            pg17: unsafe { Disabled::__new(&_GPIO) },
-            pg18: unsafe { Disabled::__new(&_GPIO) },
-        },
-        ccu: CCU { _private: () },
-        uart0: UART0 { _private: () },
-        com: COM { _private: () },
-        phy: PHY { _private: () },
-        smhc0: SMHC0 { _private: () },
-        smhc1: SMHC1 { _private: () },
-        smhc2: SMHC2 { _private: () },
-        spi0: SPI0 { _private: () },
-        plic: PLIC { _private: () },
-    };
-    let clocks = Clocks {
-        psi: 600_000_000.Hz(),
-        apb1: 24_000_000.Hz(),
-    };
+    let peripherals = Peripherals::take().expect("peripherals are taken once by ROM runtime");
+    let clocks = Clocks::new(
+        allwinner_hal::ccu::cpu_frequency(&peripherals.ccu),
+        600_000_000.Hz(),
+        24_000_000.Hz(),
+        24_000_000.Hz(),
+    );
     (peripherals, clocks)
 }
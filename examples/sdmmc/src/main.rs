@@ -35,11 +35,17 @@ fn main(p: Peripherals, c: Clocks) {
         let sdc0_cmd = p.gpio.pf3.into_function::<2>();
         let sdc0_d3 = p.gpio.pf4.into_function::<2>();
         let sdc0_d2 = p.gpio.pf5.into_function::<2>();
-        (sdc0_d1, sdc0_d0, sdc0_clk, sdc0_cmd, sdc0_d3, sdc0_d2)
+        (sdc0_clk, sdc0_cmd, sdc0_d0, sdc0_d1, sdc0_d2, sdc0_d3)
     };
 
     writeln!(serial, "initialize smhc...").ok();
-    let mut smhc = Smhc::new::<0>(p.smhc0, sdmmc_pins, &c, &p.ccu);
+    let mut smhc = match Smhc::new(p.smhc0, sdmmc_pins, &c, &p.ccu) {
+        Ok(smhc) => smhc,
+        Err(e) => {
+            writeln!(serial, "Failed to initialize smhc: {:?}", e).ok();
+            loop {}
+        }
+    };
 
     writeln!(serial, "initializing SD card...").ok();
     let sdcard = match SdCard::new(&mut smhc) {
@@ -52,7 +58,7 @@ fn main(p: Peripherals, c: Clocks) {
     writeln!(
         serial,
         "SD card initialized, size: {:.2}GB",
-        sdcard.get_size_kb() / 1024.0 / 1024.0
+        sdcard.get_size_kb() as f64 / 1024.0 / 1024.0
     )
     .ok();
 